@@ -5,11 +5,13 @@ use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use serde::Serialize;
 use serde_with::SerializeDisplay;
-use strum::AsRefStr;
+use strum::{AsRefStr, EnumCount};
 
 pub type Result<T> = core::result::Result<T, CustomErr>;
 
-#[derive(Debug, Clone, Serialize, AsRefStr)]
+// Every variant needs an explicit row in client_status_and_error() (no
+// wildcard arm) and in the test matrix below (checked against COUNT).
+#[derive(Debug, Clone, Serialize, AsRefStr, EnumCount)]
 #[serde(tag = "type", content = "data")]
 pub enum CustomErr {
     LoginFail,
@@ -22,14 +24,31 @@ pub enum CustomErr {
 
     // -- Model errors.
     TicketDeleteFailIdNotFound { id: u64 },
+    TicketNotFound { id: u64 },
+    CommentDeleteFailIdNotFound { id: u64 },
+    CommentDeleteFailNotAuthor { id: u64, user_id: u64 },
+
+    // -- Validation errors.
+    TicketCreateFailTitleEmpty,
+    CommentCreateFailBodyEmpty,
+
+    // -- Conflict errors.
+    TicketCreateFailTitleTaken { title: String },
+
+    // -- Rate limiting (for throttling layers).
+    RateLimitExceeded { retry_after_secs: u64 },
 }
 
-#[derive(Debug, AsRefStr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
 #[allow(non_camel_case_types)]
 pub enum ClientError {
     LOGIN_FAIL,
     NO_AUTH,
+    NO_PERMISSION,
     INVALID_PARAMS,
+    VALIDATION_FAIL,
+    CONFLICT,
+    RATE_LIMITED,
     SERVICE_ERROR,
 }
 
@@ -42,46 +61,46 @@ impl fmt::Display for CustomErr {
 impl Error for CustomErr {}
 
 impl CustomErr {
-    fn status_code(&self) -> StatusCode {
-        match self {
-            Self::AuthFailNoAuthTokenCookie => StatusCode::UNAUTHORIZED,
-            Self::AuthFailTokenWrongFormat => StatusCode::UNAUTHORIZED,
-            Self::AuthFailUnauthorized => StatusCode::UNAUTHORIZED,
-            Self::AuthFailCtxNotInRequestExt => StatusCode::UNAUTHORIZED,
-
-            Self::TicketDeleteFailIdNotFound { .. } => StatusCode::NOT_FOUND,
-
-            Self::LoginFail => StatusCode::INTERNAL_SERVER_ERROR,
-        }
-    }
-
-    fn client_message(&self) -> &'static str {
-        match self {
-            Self::AuthFailNoAuthTokenCookie => "missing auth token",
-            Self::AuthFailTokenWrongFormat => "invalid auth token",
-            Self::AuthFailUnauthorized => "unauthorized",
-
-            _ => "internal server error",
-        }
-    }
-
+    // The only place server errors become client errors: keep it exhaustive,
+    // a new variant must pick what the client sees.
     pub fn client_status_and_error(&self) -> (StatusCode, ClientError) {
-        #[allow(unreachable_patterns)]
         match self {
             Self::LoginFail => (StatusCode::FORBIDDEN, ClientError::LOGIN_FAIL),
             // --Auth
             Self::AuthFailNoAuthTokenCookie
             | Self::AuthFailTokenWrongFormat
+            | Self::AuthFailUnauthorized
             | Self::AuthFailCtxNotInRequestExt => (StatusCode::FORBIDDEN, ClientError::NO_AUTH),
             // --Model
-            Self::TicketDeleteFailIdNotFound { .. } => {
+            Self::TicketDeleteFailIdNotFound { .. }
+            | Self::TicketNotFound { .. }
+            | Self::CommentDeleteFailIdNotFound { .. } => {
                 (StatusCode::BAD_REQUEST, ClientError::INVALID_PARAMS)
             }
-            // --fallback_service
-            _ => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                ClientError::SERVICE_ERROR,
+            Self::CommentDeleteFailNotAuthor { .. } => {
+                (StatusCode::FORBIDDEN, ClientError::NO_PERMISSION)
+            }
+            // --Validation
+            Self::TicketCreateFailTitleEmpty | Self::CommentCreateFailBodyEmpty => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::VALIDATION_FAIL,
             ),
+            // --Conflict
+            Self::TicketCreateFailTitleTaken { .. } => {
+                (StatusCode::CONFLICT, ClientError::CONFLICT)
+            }
+            // --Rate limit
+            Self::RateLimitExceeded { .. } => {
+                (StatusCode::TOO_MANY_REQUESTS, ClientError::RATE_LIMITED)
+            }
+        }
+    }
+
+    // Seconds the client should wait before retrying (sent as Retry-After)
+    pub fn retry_after_secs(&self) -> Option<u64> {
+        match self {
+            Self::RateLimitExceeded { retry_after_secs } => Some(*retry_after_secs),
+            _ => None,
         }
     }
 }
@@ -104,3 +123,94 @@ impl IntoResponse for CustomErr {
 }
 
 //note: never ever pass through your server errors to the client, it is a big secret exposure
+
+// region: --- Tests
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    // One row per CustomErr variant: what the client gets for it
+    fn matrix() -> Vec<(CustomErr, StatusCode, ClientError)> {
+        use ClientError::*;
+        use StatusCode as S;
+
+        vec![
+            (CustomErr::LoginFail, S::FORBIDDEN, LOGIN_FAIL),
+            (CustomErr::AuthFailNoAuthTokenCookie, S::FORBIDDEN, NO_AUTH),
+            (CustomErr::AuthFailTokenWrongFormat, S::FORBIDDEN, NO_AUTH),
+            (CustomErr::AuthFailUnauthorized, S::FORBIDDEN, NO_AUTH),
+            (CustomErr::AuthFailCtxNotInRequestExt, S::FORBIDDEN, NO_AUTH),
+            (
+                CustomErr::TicketDeleteFailIdNotFound { id: 1 },
+                S::BAD_REQUEST,
+                INVALID_PARAMS,
+            ),
+            (
+                CustomErr::TicketNotFound { id: 1 },
+                S::BAD_REQUEST,
+                INVALID_PARAMS,
+            ),
+            (
+                CustomErr::CommentDeleteFailIdNotFound { id: 1 },
+                S::BAD_REQUEST,
+                INVALID_PARAMS,
+            ),
+            (
+                CustomErr::CommentDeleteFailNotAuthor { id: 1, user_id: 2 },
+                S::FORBIDDEN,
+                NO_PERMISSION,
+            ),
+            (
+                CustomErr::TicketCreateFailTitleEmpty,
+                S::UNPROCESSABLE_ENTITY,
+                VALIDATION_FAIL,
+            ),
+            (
+                CustomErr::CommentCreateFailBodyEmpty,
+                S::UNPROCESSABLE_ENTITY,
+                VALIDATION_FAIL,
+            ),
+            (
+                CustomErr::TicketCreateFailTitleTaken {
+                    title: "t".to_string(),
+                },
+                S::CONFLICT,
+                CONFLICT,
+            ),
+            (
+                CustomErr::RateLimitExceeded {
+                    retry_after_secs: 30,
+                },
+                S::TOO_MANY_REQUESTS,
+                RATE_LIMITED,
+            ),
+        ]
+    }
+
+    #[test]
+    fn matrix_covers_every_variant_once() {
+        let matrix = matrix();
+        let variants: HashSet<&str> = matrix.iter().map(|(err, ..)| err.as_ref()).collect();
+
+        assert_eq!(variants.len(), matrix.len(), "a variant is listed twice");
+        assert_eq!(
+            matrix.len(),
+            CustomErr::COUNT,
+            "add the new CustomErr variant to the matrix"
+        );
+    }
+
+    #[test]
+    fn client_status_and_error_matches_the_matrix() {
+        for (err, status, client_error) in matrix() {
+            assert_eq!(
+                err.client_status_and_error(),
+                (status, client_error),
+                "{err:?}"
+            );
+        }
+    }
+}
+// endregion: --- Tests
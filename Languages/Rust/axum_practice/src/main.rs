@@ -1,9 +1,10 @@
 pub use self::error::{CustomErr, Result};
+use crate::web::mw_timing::{ReqTiming, TimingConfig};
 use crate::{ctx::Ctx, error::ClientError, log::log_request, model::ModelController};
 use axum::{
     Router,
     extract::{Path, Query},
-    http::{Method, StatusCode, Uri},
+    http::{HeaderValue, Method, StatusCode, Uri, header},
     middleware,
     response::{Html, IntoResponse, Json, Response},
     routing::{get, get_service},
@@ -27,6 +28,9 @@ async fn main() -> Result<()> {
     // Initialize ModelController
     let mc = ModelController::new().await?;
 
+    // Slow-request threshold (SLOW_REQUEST_MS)
+    let timing = TimingConfig::from_env();
+
     // Protected API routes
     let routes_apis = web::routes_tickets::routes(mc.clone())
         .route_layer(middleware::from_fn(web::mw_auth::mw_require_auth));
@@ -36,6 +40,12 @@ async fn main() -> Result<()> {
         .merge(routes_hello())
         .merge(web::routes_login::routes())
         .nest("/api", routes_apis)
+        // Inside the mapper: times the handler (and auth), the mapper
+        // then reports it on whatever response it sends
+        .layer(middleware::from_fn_with_state(
+            timing,
+            web::mw_timing::mw_req_timing,
+        ))
         .layer(middleware::map_response(main_response_mapper))
         .layer(middleware::from_fn_with_state(
             mc.clone(),
@@ -63,8 +73,12 @@ async fn main_response_mapper(
     println!("->> {:<12} - main_response_mapper", "RES_MAPPER");
     let uuid = Uuid::new_v4();
 
+    // --Get the timing recorded by mw_req_timing.
+    let timing: Option<ReqTiming> = res.extensions().get::<ReqTiming>().copied();
+
     // --Get eventual response error.
     let service_error: Option<&CustomErr> = res.extensions().get::<CustomErr>();
+    let retry_after: Option<u64> = service_error.and_then(CustomErr::retry_after_secs);
     let client_status_error: Option<(StatusCode, ClientError)> =
         service_error.map(|se| se.client_status_and_error());
 
@@ -87,10 +101,47 @@ async fn main_response_mapper(
     // TODO: Build and log the server log line.
     // let client_error = client_status_error.unzip().1;
     // log_request(uuid, req_method, uri, ctx, service_error, client_error).await;
-    println!("  ->> server log line -{uuid} - Error: {service_error:?}");
+    println!("  ->> server log line -{uuid} - Error: {service_error:?} - Timing: {timing:?}");
 
     println!();
-    error_response.unwrap_or(res)
+    let mut res = error_response.unwrap_or(res);
+
+    // -- Retry-After on rate-limited responses.
+    if let Some(secs) = retry_after {
+        res.headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    }
+
+    // -- Server-Timing on every response, error responses included.
+    if let Some(timing) = timing {
+        res.headers_mut()
+            .insert("server-timing", timing.server_timing());
+    }
+    res
+}
+
+#[cfg(test)]
+mod mapper_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rate_limited_response_carries_retry_after() {
+        let err = CustomErr::RateLimitExceeded {
+            retry_after_secs: 30,
+        };
+        let res = main_response_mapper(err.into_response()).await;
+
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(res.headers()[header::RETRY_AFTER], "30");
+    }
+
+    #[tokio::test]
+    async fn other_errors_have_no_retry_after() {
+        let res = main_response_mapper(CustomErr::LoginFail.into_response()).await;
+
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert!(res.headers().get(header::RETRY_AFTER).is_none());
+    }
 }
 // endregion: --- Layers
 
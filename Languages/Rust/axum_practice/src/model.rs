@@ -26,11 +26,46 @@ pub struct TicketForCreate {
 
 // endregion: -- Ticket TYpes
 
+// region:  --- Comment types
+#[derive(Clone, Debug, Serialize)]
+pub struct Comment {
+    pub id: u64,
+    pub ticket_id: u64,
+    pub cid: u64, // author user_id (from Ctx, never from the body)
+    pub body: String,
+}
+
+#[derive(Deserialize)]
+pub struct CommentForCreate {
+    pub body: String,
+}
+
+// Query params for paginated lists: ?offset=20&limit=10
+#[derive(Debug, Default, Deserialize)]
+pub struct ListOptions {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+impl ListOptions {
+    pub const DEFAULT_LIMIT: usize = 20;
+    pub const MAX_LIMIT: usize = 100;
+
+    // (offset, limit) with the limit clamped to 1..=MAX_LIMIT
+    fn offset_limit(&self) -> (usize, usize) {
+        let limit = self.limit.unwrap_or(Self::DEFAULT_LIMIT);
+        (self.offset.unwrap_or(0), limit.clamp(1, Self::MAX_LIMIT))
+    }
+}
+// endregion: -- Comment types
+
 // region: ---Model conttroller
 //have the store embedded in it; usually a db connection or sqlx or orm
 #[derive(Clone)]
 pub struct ModelController {
     tickets_store: Arc<Mutex<Vec<Option<Ticket>>>>,
+    // Comments of every ticket; lock order: tickets_store, then comments_store
+    comments_store: Arc<Mutex<Vec<Option<Comment>>>>,
 }
 
 // Constructor
@@ -38,6 +73,7 @@ impl ModelController {
     pub async fn new() -> Result<Self> {
         Ok(Self {
             tickets_store: Arc::default(),
+            comments_store: Arc::default(),
         })
     }
 }
@@ -50,14 +86,26 @@ impl ModelController {
         ctx: Ctx,
         ticket_fc: TicketForCreate,
     ) -> Result<Ticket> {
+        let title = ticket_fc.title.trim();
+        if title.is_empty() {
+            return Err(CustomErr::TicketCreateFailTitleEmpty);
+        }
+
         let mut store = self.tickets_store.lock().await;
 
+        // Titles are unique among live tickets
+        if store.iter().flatten().any(|t| t.title == title) {
+            return Err(CustomErr::TicketCreateFailTitleTaken {
+                title: title.to_string(),
+            });
+        }
+
         let id = store.len() as u64;
 
         let ticket = Ticket {
             id,
             cid: ctx.user_id,
-            title: ticket_fc.title,
+            title: title.to_string(),
         };
 
         store.push(Some(ticket.clone()));
@@ -72,13 +120,196 @@ impl ModelController {
         Ok(tickets)
     }
 
+    // Also deletes the ticket's comments (their ids are not reused)
     pub(crate) async fn delete_ticket(&self, _ctx: Ctx, id: u64) -> Result<Ticket> {
         let mut store = self.tickets_store.lock().await;
 
-        let ticket = store.get_mut(id as usize).and_then(|t| t.take());
+        let ticket = store
+            .get_mut(id as usize)
+            .and_then(|t| t.take())
+            .ok_or(CustomErr::TicketDeleteFailIdNotFound { id })?;
+
+        let mut comments = self.comments_store.lock().await;
+        for slot in comments.iter_mut() {
+            if slot.as_ref().is_some_and(|c| c.ticket_id == id) {
+                *slot = None;
+            }
+        }
+
+        Ok(ticket)
+    }
+
+    // -- Comments (subresource of a ticket)
+
+    pub(crate) async fn create_comment(
+        &self,
+        ctx: Ctx,
+        ticket_id: u64,
+        comment_fc: CommentForCreate,
+    ) -> Result<Comment> {
+        if comment_fc.body.trim().is_empty() {
+            return Err(CustomErr::CommentCreateFailBodyEmpty);
+        }
+
+        // Hold the ticket lock so the ticket can't be deleted meanwhile
+        let tickets = self.tickets_store.lock().await;
+        ensure_ticket(&tickets, ticket_id)?;
+
+        let mut store = self.comments_store.lock().await;
+
+        let comment = Comment {
+            id: store.len() as u64,
+            ticket_id,
+            cid: ctx.user_id,
+            body: comment_fc.body,
+        };
+
+        store.push(Some(comment.clone()));
+        Ok(comment)
+    }
+
+    // Oldest first, `options.limit` at most, starting after `options.offset`
+    pub(crate) async fn list_comments(
+        &self,
+        _ctx: Ctx,
+        ticket_id: u64,
+        options: ListOptions,
+    ) -> Result<Vec<Comment>> {
+        let tickets = self.tickets_store.lock().await;
+        ensure_ticket(&tickets, ticket_id)?;
+
+        let store = self.comments_store.lock().await;
+        let (offset, limit) = options.offset_limit();
+
+        let comments = store
+            .iter()
+            .flatten()
+            .filter(|c| c.ticket_id == ticket_id)
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect();
+
+        Ok(comments)
+    }
+
+    // Only the author may delete a comment
+    pub(crate) async fn delete_comment(
+        &self,
+        ctx: Ctx,
+        ticket_id: u64,
+        id: u64,
+    ) -> Result<Comment> {
+        let tickets = self.tickets_store.lock().await;
+        ensure_ticket(&tickets, ticket_id)?;
 
-        ticket.ok_or(CustomErr::TicketDeleteFailIdNotFound { id })
+        let mut store = self.comments_store.lock().await;
+
+        let not_found = CustomErr::CommentDeleteFailIdNotFound { id };
+        let slot = store.get_mut(id as usize).ok_or(not_found.clone())?;
+
+        match slot {
+            Some(comment) if comment.ticket_id != ticket_id => Err(not_found),
+            Some(comment) if comment.cid != ctx.user_id => {
+                Err(CustomErr::CommentDeleteFailNotAuthor {
+                    id,
+                    user_id: ctx.user_id,
+                })
+            }
+            _ => slot.take().ok_or(not_found),
+        }
+    }
+}
+
+fn ensure_ticket(tickets: &[Option<Ticket>], id: u64) -> Result<()> {
+    match tickets.get(id as usize) {
+        Some(Some(_)) => Ok(()),
+        _ => Err(CustomErr::TicketNotFound { id }),
     }
 }
 
 // endregion: -- ModelController
+
+// region: --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn mc_with_ticket() -> (ModelController, Ticket) {
+        let mc = ModelController::new().await.unwrap();
+        let ticket = mc
+            .create_ticket(
+                Ctx::new(1),
+                TicketForCreate {
+                    title: "t1".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        (mc, ticket)
+    }
+
+    async fn comment(mc: &ModelController, ticket_id: u64, body: &str) -> Comment {
+        let comment_fc = CommentForCreate {
+            body: body.to_string(),
+        };
+        mc.create_comment(Ctx::new(1), ticket_id, comment_fc)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn delete_comment_of_deleted_ticket_is_ticket_not_found() {
+        let (mc, ticket) = mc_with_ticket().await;
+        let c = comment(&mc, ticket.id, "hello").await;
+
+        mc.delete_ticket(Ctx::new(1), ticket.id).await.unwrap();
+
+        let err = mc
+            .delete_comment(Ctx::new(1), ticket.id, c.id)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, CustomErr::TicketNotFound { id } if id == ticket.id),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_ticket_drops_only_its_comments() {
+        let (mc, ticket) = mc_with_ticket().await;
+        let other = mc
+            .create_ticket(
+                Ctx::new(1),
+                TicketForCreate {
+                    title: "t2".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        comment(&mc, ticket.id, "a").await;
+        let kept = comment(&mc, other.id, "b").await;
+        comment(&mc, ticket.id, "c").await;
+
+        mc.delete_ticket(Ctx::new(1), ticket.id).await.unwrap();
+
+        let store = mc.comments_store.lock().await;
+        let live: Vec<u64> = store.iter().flatten().map(|c| c.id).collect();
+        assert_eq!(live, vec![kept.id]);
+        // Tombstoned, so new comments still get fresh ids
+        assert_eq!(store.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn delete_comment_on_live_ticket_still_works() {
+        let (mc, ticket) = mc_with_ticket().await;
+        let c = comment(&mc, ticket.id, "hello").await;
+
+        let deleted = mc
+            .delete_comment(Ctx::new(1), ticket.id, c.id)
+            .await
+            .unwrap();
+        assert_eq!(deleted.id, c.id);
+    }
+}
+// endregion: --- Tests
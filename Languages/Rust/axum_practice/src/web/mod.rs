@@ -1,4 +1,5 @@
 pub mod mw_auth;
+pub mod mw_timing;
 pub mod routes_login;
 pub mod routes_tickets;
 
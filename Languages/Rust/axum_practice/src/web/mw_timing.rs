@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+use axum::extract::{Request, State};
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+
+// Requests slower than this are logged as WARN (override with SLOW_REQUEST_MS)
+const DEFAULT_SLOW_THRESHOLD: Duration = Duration::from_millis(500);
+
+// region: --- Timing config
+#[derive(Debug, Clone, Copy)]
+pub struct TimingConfig {
+    pub slow_threshold: Duration,
+}
+
+impl TimingConfig {
+    pub fn new(slow_threshold: Duration) -> Self {
+        Self { slow_threshold }
+    }
+
+    // Read SLOW_REQUEST_MS (milliseconds), falling back to the default
+    // when it's unset or not a number
+    pub fn from_env() -> Self {
+        let slow_threshold = std::env::var("SLOW_REQUEST_MS")
+            .ok()
+            .and_then(|ms| ms.parse().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_SLOW_THRESHOLD);
+
+        Self::new(slow_threshold)
+    }
+}
+// endregion: --- Timing config
+
+// region: --- Request timing
+// How long the request took inside the timing layer.
+// Stored in the response extensions for main_response_mapper, which turns
+// it into the `Server-Timing` header and the server log line.
+#[derive(Debug, Clone, Copy)]
+pub struct ReqTiming {
+    pub elapsed: Duration,
+}
+
+impl ReqTiming {
+    // `app;dur=12.345` (milliseconds, as the Server-Timing spec wants)
+    pub fn server_timing(&self) -> HeaderValue {
+        let ms = self.elapsed.as_secs_f64() * 1000.0;
+        HeaderValue::from_str(&format!("app;dur={ms:.3}")).expect("server-timing value is ASCII")
+    }
+}
+// endregion: --- Request timing
+
+// region: ---Middleware request timing
+pub async fn mw_req_timing(
+    State(config): State<TimingConfig>,
+    req: Request,
+    next: Next,
+) -> Response {
+    println!("->> {:<12} - mw_req_timing", "MIDDLEWARE");
+
+    let method = req.method().clone();
+    let uri = req.uri().clone();
+    let start = Instant::now();
+
+    let mut res = next.run(req).await;
+
+    let elapsed = start.elapsed();
+    if elapsed > config.slow_threshold {
+        println!(
+            "->> {:<12} - slow request: {method} {uri} took {:?} (threshold {:?})",
+            "WARN", elapsed, config.slow_threshold
+        );
+    }
+
+    res.extensions_mut().insert(ReqTiming { elapsed });
+    res
+}
+// endregion: ---Middleware request timing
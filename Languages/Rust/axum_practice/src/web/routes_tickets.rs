@@ -1,10 +1,12 @@
-use axum::extract::{FromRef, Path, State};
+use axum::extract::{FromRef, Path, Query, State};
 use axum::routing::{delete, post};
 use axum::{Json, Router};
 
 use crate::Result;
 use crate::ctx::Ctx;
-use crate::model::{ModelController, Ticket, TicketForCreate};
+use crate::model::{
+    Comment, CommentForCreate, ListOptions, ModelController, Ticket, TicketForCreate,
+};
 
 #[derive(Clone, FromRef)]
 struct AppState {
@@ -17,6 +19,14 @@ pub fn routes(mc: ModelController) -> Router {
     Router::new()
         .route("/tickets", post(create_ticket).get(list_tickets))
         .route("/tickets/{id}", delete(delete_ticket))
+        .route(
+            "/tickets/{id}/comments",
+            post(create_comment).get(list_comments),
+        )
+        .route(
+            "/tickets/{id}/comments/{comment_id}",
+            delete(delete_comment),
+        )
         .with_state(app_state)
 }
 
@@ -54,3 +64,46 @@ async fn delete_ticket(
 }
 
 // endregion: --- REST Handlers
+
+// region: --- Comment Handlers
+// Author is always the Ctx user: the body only carries the text
+async fn create_comment(
+    State(mc): State<ModelController>,
+    ctx: Ctx,
+    Path(ticket_id): Path<u64>,
+    Json(comment_fc): Json<CommentForCreate>,
+) -> Result<Json<Comment>> {
+    println!("->> {:<12} - create_comment", "HANDLER");
+
+    let comment = mc.create_comment(ctx, ticket_id, comment_fc).await?;
+
+    Ok(Json(comment))
+}
+
+// GET /tickets/{id}/comments?offset=0&limit=20
+async fn list_comments(
+    State(mc): State<ModelController>,
+    ctx: Ctx,
+    Path(ticket_id): Path<u64>,
+    Query(options): Query<ListOptions>,
+) -> Result<Json<Vec<Comment>>> {
+    println!("->> {:<12} - list_comments - {options:?}", "HANDLER");
+
+    let comments = mc.list_comments(ctx, ticket_id, options).await?;
+
+    Ok(Json(comments))
+}
+
+async fn delete_comment(
+    State(mc): State<ModelController>,
+    ctx: Ctx,
+    Path((ticket_id, comment_id)): Path<(u64, u64)>,
+) -> Result<Json<Comment>> {
+    println!("->> {:<12} - delete_comment", "HANDLER");
+
+    let comment = mc.delete_comment(ctx, ticket_id, comment_id).await?;
+
+    Ok(Json(comment))
+}
+
+// endregion: --- Comment Handlers
@@ -28,7 +28,37 @@ async fn quick_test() -> Result<()> {
 
     req_create_ticket.await?.print().await?;
 
+    // -- Same title again (409 CONFLICT), then an empty one (422 VALIDATION_FAIL)
+    hc.do_post("/api/tickets", json!({ "title": "Ticket AAA" }))
+        .await?
+        .print()
+        .await?;
+    hc.do_post("/api/tickets", json!({ "title": "  " }))
+        .await?
+        .print()
+        .await?;
+
     hc.do_get("/api/tickets").await?.print().await?;
 
+    // -- Comments on ticket 0 (author comes from the auth-token cookie)
+    let req_create_comment = hc.do_post(
+        "/api/tickets/0/comments",
+        json!({
+            "body": "First comment"
+        }),
+    );
+
+    req_create_comment.await?.print().await?;
+
+    hc.do_get("/api/tickets/0/comments?offset=0&limit=10")
+        .await?
+        .print()
+        .await?;
+
+    hc.do_delete("/api/tickets/0/comments/0")
+        .await?
+        .print()
+        .await?;
+
     Ok(())
 }
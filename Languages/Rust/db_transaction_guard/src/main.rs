@@ -11,6 +11,10 @@ struct TransactionError {
 struct Transaction<'conn> {
     conn: &'conn mut Connection,
     committed: bool,
+    // Named savepoints still open, oldest first
+    savepoints: Vec<String>,
+    // Names released (or rolled past); rolling back to them is an error
+    released: Vec<String>,
 }
 
 impl<'conn> Transaction<'conn> {
@@ -19,6 +23,8 @@ impl<'conn> Transaction<'conn> {
         Self {
             conn,
             committed: false,
+            savepoints: Vec::new(),
+            released: Vec::new(),
         }
     }
 
@@ -43,6 +49,58 @@ impl<'conn> Transaction<'conn> {
         println!("ROLLBACK TRANSACTION");
     }
 
+    // Mark a point to roll back to without aborting the transaction
+    fn savepoint(&mut self, name: &str) -> Result<(), TransactionError> {
+        if name.is_empty() {
+            return Err(TransactionError {
+                state: "in_flight",
+                message: "savepoint name missing",
+            });
+        }
+
+        println!("SAVEPOINT {}", name);
+        self.released.retain(|released| released != name);
+        self.savepoints.push(name.to_string());
+        Ok(())
+    }
+
+    // Undo everything since `name`; the savepoint itself stays open, like
+    // SQL's ROLLBACK TO, so it can be rolled back to again
+    fn rollback_to(&mut self, name: &str) -> Result<(), TransactionError> {
+        let index = self.find_savepoint(name)?;
+
+        println!("ROLLBACK TO SAVEPOINT {}", name);
+        let later = self.savepoints.split_off(index + 1);
+        self.released.extend(later);
+        Ok(())
+    }
+
+    // Keep the work since `name` and forget the savepoint (and any opened
+    // after it)
+    fn release(&mut self, name: &str) -> Result<(), TransactionError> {
+        let index = self.find_savepoint(name)?;
+
+        println!("RELEASE SAVEPOINT {}", name);
+        let released = self.savepoints.split_off(index);
+        self.released.extend(released);
+        Ok(())
+    }
+
+    // Latest open savepoint called `name`
+    fn find_savepoint(&self, name: &str) -> Result<usize, TransactionError> {
+        match self.savepoints.iter().rposition(|open| open == name) {
+            Some(index) => Ok(index),
+            None if self.released.iter().any(|gone| gone == name) => Err(TransactionError {
+                state: "savepoint",
+                message: "savepoint already released",
+            }),
+            None => Err(TransactionError {
+                state: "savepoint",
+                message: "no such savepoint",
+            }),
+        }
+    }
+
     // Begin nested transaction
     fn begin_nested(&mut self) -> NestedTransaction<'_, 'conn> {
         NestedTransaction::new(self)
@@ -110,6 +168,17 @@ fn main() -> Result<(), TransactionError> {
     let mut tx = Transaction::begin(&mut conn);
     tx.execute_tx("SELECT * FROM Users")?;
 
+    // Named savepoints: undo the import, keep the transaction
+    tx.savepoint("before_import")?;
+    tx.execute_tx("INSERT INTO Users VALUES (...)")?;
+    tx.savepoint("after_users")?;
+    tx.execute_tx("INSERT INTO Orders VALUES (...)")?;
+    tx.rollback_to("before_import")?; // also drops "after_users"
+    if let Err(e) = tx.rollback_to("after_users") {
+        println!("rejected: {:?}", e);
+    }
+    tx.release("before_import")?;
+
     // Nested transaction
     {
         let mut nested = tx.begin_nested();
@@ -0,0 +1,307 @@
+// Request bodies
+//
+//   Body::Text       raw string, sent as is
+//   Form             application/x-www-form-urlencoded: a=1&b=two+words
+//   Multipart        multipart/form-data; boundary=...
+//
+// A multipart body is only described when it's built: file parts are
+// opened and copied in chunks by write_to, so a 2GB upload never sits in
+// memory. content_length() adds up the parts (file sizes from metadata)
+// for servers that want Content-Length instead of chunked encoding.
+
+use std::collections::hash_map::RandomState;
+use std::fs::File;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub enum Body {
+    Empty,
+    Text(String),
+    Form(Form),
+    Multipart(Multipart),
+}
+
+impl Body {
+    // Content-Type header the body needs, if any
+    pub fn content_type(&self) -> Option<String> {
+        match self {
+            Body::Empty => None,
+            Body::Text(_) => Some("text/plain; charset=utf-8".to_string()),
+            Body::Form(_) => Some("application/x-www-form-urlencoded".to_string()),
+            Body::Multipart(multipart) => Some(format!(
+                "multipart/form-data; boundary={}",
+                multipart.boundary
+            )),
+        }
+    }
+
+    pub fn content_length(&self) -> io::Result<u64> {
+        match self {
+            Body::Empty => Ok(0),
+            Body::Text(text) => Ok(text.len() as u64),
+            Body::Form(form) => Ok(form.encode().len() as u64),
+            Body::Multipart(multipart) => multipart.content_length(),
+        }
+    }
+
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Body::Empty => Ok(()),
+            Body::Text(text) => out.write_all(text.as_bytes()),
+            Body::Form(form) => out.write_all(form.encode().as_bytes()),
+            Body::Multipart(multipart) => multipart.write_to(out),
+        }
+    }
+}
+
+// ============================================================================
+// application/x-www-form-urlencoded
+// ============================================================================
+
+#[derive(Default)]
+pub struct Form {
+    fields: Vec<(String, String)>,
+}
+
+impl Form {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Repeating a name sends it twice (tags=a&tags=b)
+    pub fn field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn encode(&self) -> String {
+        self.fields
+            .iter()
+            .map(|(name, value)| format!("{}={}", url_encode(name), url_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+}
+
+// Unreserved characters stay, space becomes '+', every other byte of the
+// UTF-8 is %XX
+fn url_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+// ============================================================================
+// multipart/form-data
+// ============================================================================
+
+enum Part {
+    Text {
+        name: String,
+        value: String,
+    },
+    File {
+        name: String,
+        path: PathBuf,
+        filename: String,
+        content_type: &'static str,
+    },
+}
+
+pub struct Multipart {
+    boundary: String,
+    parts: Vec<Part>,
+}
+
+impl Multipart {
+    pub fn new() -> Self {
+        Self {
+            boundary: boundary(),
+            parts: Vec::new(),
+        }
+    }
+
+    pub fn text(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parts.push(Part::Text {
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    // The file is read when the body is written, not now; its name and
+    // extension give the part's filename and Content-Type
+    pub fn file(mut self, name: impl Into<String>, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let filename = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.parts.push(Part::File {
+            name: name.into(),
+            content_type: guess_type(&path),
+            path,
+            filename,
+        });
+        self
+    }
+
+    pub fn content_length(&self) -> io::Result<u64> {
+        let mut total = self.closing().len() as u64;
+        for part in &self.parts {
+            total += self.head(part).len() as u64 + 2; // CRLF after data
+            total += match part {
+                Part::Text { value, .. } => value.len() as u64,
+                Part::File { path, .. } => std::fs::metadata(path)?.len(),
+            };
+        }
+        Ok(total)
+    }
+
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        for part in &self.parts {
+            out.write_all(self.head(part).as_bytes())?;
+            match part {
+                Part::Text { value, .. } => out.write_all(value.as_bytes())?,
+                Part::File { path, .. } => {
+                    // io::copy moves it through a small buffer
+                    io::copy(&mut File::open(path)?, out)?;
+                }
+            }
+            out.write_all(b"\r\n")?;
+        }
+        out.write_all(self.closing().as_bytes())
+    }
+
+    // Boundary line and headers of a part, up to its data
+    fn head(&self, part: &Part) -> String {
+        match part {
+            Part::Text { name, .. } => format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                self.boundary,
+                quote(name)
+            ),
+            Part::File {
+                name,
+                filename,
+                content_type,
+                ..
+            } => format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"; \
+                 filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                self.boundary,
+                quote(name),
+                quote(filename),
+                content_type
+            ),
+        }
+    }
+
+    fn closing(&self) -> String {
+        format!("--{}--\r\n", self.boundary)
+    }
+}
+
+impl Default for Multipart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Random enough that it won't turn up in the data: RandomState is seeded
+// per process and per instance
+fn boundary() -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    let high = hasher.finish();
+    hasher.write_u64(high);
+    format!(
+        "------------------------{:016x}{:016x}",
+        high,
+        hasher.finish()
+    )
+}
+
+// Quotes and line breaks can't appear raw in a header's quoted string;
+// escaped the way browsers do
+fn quote(s: &str) -> String {
+    s.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn guess_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "txt" | "md" | "toml" | "rs" => "text/plain",
+        "html" | "htm" => "text/html",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multipart_length_matches_what_is_written() {
+        let path =
+            std::env::temp_dir().join(format!("http_builder_body_{}.json", std::process::id()));
+        std::fs::write(&path, "{\"a\": 1}\n").unwrap();
+
+        let multipart = Multipart::new()
+            .text("title", "two words, é")
+            .file("upload", &path);
+        let mut out = Vec::new();
+        multipart.write_to(&mut out).unwrap();
+        let length = multipart.content_length().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(out.len() as u64, length);
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Content-Type: application/json\r\n"));
+        assert!(text.ends_with(&format!("--{}--\r\n", multipart.boundary)));
+    }
+
+    #[test]
+    fn form_encodes_space_as_plus() {
+        let form = Form::new().field("q", "two words");
+        assert_eq!(form.encode(), "q=two+words");
+    }
+
+    #[test]
+    fn form_percent_encodes_utf8_bytes_and_reserved() {
+        let form = Form::new().field("name", "é&=").field("tags", "a~b");
+        assert_eq!(form.encode(), "name=%C3%A9%26%3D&tags=a~b");
+    }
+
+    #[test]
+    fn quote_escapes_quotes_and_line_breaks() {
+        assert_eq!(quote("a\"b\r\nc"), "a%22b%0D%0Ac");
+        assert_eq!(quote("plain.txt"), "plain.txt");
+    }
+}
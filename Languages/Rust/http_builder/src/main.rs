@@ -1,6 +1,11 @@
 use std::slice::Windows;
 use std::sync::WaitTimeoutResult;
 
+mod body;
+
+use body::{Body, Form, Multipart};
+
+#[derive(Debug, Clone, Copy)]
 enum Method {
     Get,
     Post,
@@ -9,72 +14,175 @@ enum Method {
 struct Request {
     url: String,
     method: Method,
-    // header: Vec<String>,
-    body: Option<String>,
+    headers: Vec<(String, String)>,
+    body: Body,
 }
 
 struct NoURL;
 struct HasURL(String);
-struct RequestBuilder<U> {
+
+// Method typestate: only a POST builder has body methods, so a GET can't
+// be given a body at all
+struct Get;
+struct Post;
+
+trait MethodState {
+    const METHOD: Method;
+}
+
+impl MethodState for Get {
+    const METHOD: Method = Method::Get;
+}
+
+impl MethodState for Post {
+    const METHOD: Method = Method::Post;
+}
+
+struct RequestBuilder<U, M = Get> {
     url: U,
-    method: Method,
-    body: Option<String>,
+    method: M,
+    headers: Vec<(String, String)>,
+    body: Body,
 }
 
 impl RequestBuilder<NoURL> {
     fn new() -> Self {
         Self {
             url: NoURL,
-            method: Method::Get,
-            body: None,
+            method: Get,
+            headers: Vec::new(),
+            body: Body::Empty,
         }
     }
+}
 
-    fn url(self, url: impl Into<String>) -> RequestBuilder<HasURL> {
+//shared methods
+impl<U, M> RequestBuilder<U, M> {
+    fn url(self, url: impl Into<String>) -> RequestBuilder<HasURL, M> {
         RequestBuilder {
             url: HasURL(url.into()),
             method: self.method,
+            headers: self.headers,
             body: self.body,
         }
     }
-}
 
-//shared methods
-impl<U> RequestBuilder<U> {
-    fn method(mut self, method: Method) -> Self {
-        self.method = method;
+    fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
         self
     }
 
+    // Switching to GET drops any body set so far
+    fn get(self) -> RequestBuilder<U, Get> {
+        RequestBuilder {
+            url: self.url,
+            method: Get,
+            headers: self.headers,
+            body: Body::Empty,
+        }
+    }
+
+    fn post(self) -> RequestBuilder<U, Post> {
+        RequestBuilder {
+            url: self.url,
+            method: Post,
+            headers: self.headers,
+            body: self.body,
+        }
+    }
+}
+
+// body methods, POST only; each replaces the previous body
+impl<U> RequestBuilder<U, Post> {
     fn body(mut self, body: impl Into<String>) -> Self {
-        self.body = Some(body.into());
+        self.body = Body::Text(body.into());
+        self
+    }
+
+    fn form(mut self, form: Form) -> Self {
+        self.body = Body::Form(form);
+        self
+    }
+
+    fn multipart(mut self, multipart: Multipart) -> Self {
+        self.body = Body::Multipart(multipart);
         self
     }
 }
 
 //builder only works on valid url
-impl RequestBuilder<HasURL> {
+impl<M: MethodState> RequestBuilder<HasURL, M> {
+    // Content-Type comes from the body (with the multipart boundary) and
+    // replaces one set by hand
     fn build(self) -> Request {
+        let mut headers = self.headers;
+        if let Some(content_type) = self.body.content_type() {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+            headers.push(("Content-Type".to_string(), content_type));
+        }
+
         Request {
             url: self.url.0,
-            method: self.method,
-            // header: ,
+            method: M::METHOD,
+            headers,
             body: self.body,
         }
     }
 }
 
-fn main() {
+fn main() -> std::io::Result<()> {
     let builder = RequestBuilder::new()
-        .method(Method::Get)
+        .post()
         .url("https://api.example.com")
         .body("{}");
 
     let _request = builder.build();
 
+    // GET has no body methods:
+    // RequestBuilder::new().url("https://api.example.com").form(Form::new());
+    let search = RequestBuilder::new()
+        .url("https://api.example.com/search")
+        .post()
+        .body("dropped")
+        .get() // back to GET, body gone
+        .build();
+    println!("{:?} {} {:?}", search.method, search.url, search.headers);
+
+    let login = RequestBuilder::new()
+        .url("https://api.example.com/login")
+        .post()
+        .form(Form::new().field("user", "ada").field("note", "a&b = c"))
+        .build();
+    print_request(&login)?;
+
+    // Cargo.toml is copied from disk when the body is written
+    let upload = RequestBuilder::new()
+        .url("https://api.example.com/upload")
+        .post()
+        .header("Accept", "application/json")
+        .multipart(
+            Multipart::new()
+                .text("title", "manifest")
+                .file("file", "Cargo.toml"),
+        )
+        .build();
+    print_request(&upload)?;
+
     print_str("literal"); // &str
     print_str(String::from("owned")); // String
     print_str(&String::from("ref")); // &String
+    Ok(())
+}
+
+fn print_request(request: &Request) -> std::io::Result<()> {
+    println!("{:?} {}", request.method, request.url);
+    for (name, value) in &request.headers {
+        println!("{}: {}", name, value);
+    }
+    println!("Content-Length: {}\n", request.body.content_length()?);
+    request.body.write_to(&mut std::io::stdout())?;
+    println!();
+    Ok(())
 }
 
 struct HugeData {
@@ -0,0 +1,211 @@
+// Cached<T, E, F>: a lazy value whose initializer may fail or panic
+//
+// The first version took an `FnOnce` and `take()`-ed it out of a RefCell
+// before calling it. If it panicked the closure was gone, so every later
+// `get` hit `unwrap()` on `None`: one bad init and the cell was dead for
+// good.
+//
+// Here the initializer is `Fn`, kept for as long as the value isn't there,
+// and a failure is recorded instead of lost:
+//
+//   Empty ──get_or_init──> Ready(T)          (done, never runs again)
+//     │                      ^
+//     └──(Err / panic)──> Poisoned(error) ──get_or_init retries──┘
+//
+// - `get_or_init` runs the initializer when the cell is empty OR poisoned;
+//   a poisoned cell recovers as soon as one attempt succeeds
+// - `try_get` never runs anything: the value, nothing yet, or the error
+//   from the last failed attempt
+
+use std::any::Any;
+use std::cell::{OnceCell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Why initialization failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitError<E> {
+    /// The initializer returned an error
+    Failed(E),
+    /// The initializer panicked; carries the panic message
+    Panicked(String),
+}
+
+impl<E: fmt::Display> fmt::Display for InitError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitError::Failed(e) => write!(f, "initialization failed: {e}"),
+            InitError::Panicked(msg) => write!(f, "initializer panicked: {msg}"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for InitError<E> {}
+
+/// Run `init`, turning a panic into `InitError::Panicked`.
+pub fn run_init<T, E>(init: impl FnOnce() -> Result<T, E>) -> Result<T, InitError<E>> {
+    match panic::catch_unwind(AssertUnwindSafe(init)) {
+        Ok(result) => result.map_err(InitError::Failed),
+        Err(payload) => Err(InitError::Panicked(panic_message(payload))),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or_else(|| "non-string panic payload".to_string(), |s| s.to_string()),
+    }
+}
+
+/// Single-threaded lazy value with retry after a failed init.
+pub struct Cached<T, E, F> {
+    value: OnceCell<T>,
+    /// Error from the last failed attempt, cleared by a successful one
+    poison: RefCell<Option<InitError<E>>>,
+    init: F,
+}
+
+impl<T, E, F> Cached<T, E, F>
+where
+    F: Fn() -> Result<T, E>,
+    E: Clone,
+{
+    pub fn new(init: F) -> Self {
+        Self {
+            value: OnceCell::new(),
+            poison: RefCell::new(None),
+            init,
+        }
+    }
+
+    /// The value, initializing it first if the cell is empty or poisoned.
+    pub fn get_or_init(&self) -> Result<&T, InitError<E>> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        match run_init(&self.init) {
+            Ok(value) => {
+                self.poison.borrow_mut().take();
+                // If the initializer reached this same cell and filled it
+                // first, keep that value; ours is dropped
+                let _ = self.value.set(value);
+                Ok(self.value.get().expect("value was just set"))
+            }
+            Err(e) => {
+                *self.poison.borrow_mut() = Some(e.clone());
+                Err(e)
+            }
+        }
+    }
+
+    /// The value if it's there, `Ok(None)` if nobody has initialized it
+    /// yet, or the error that poisoned the cell. Never runs the initializer.
+    pub fn try_get(&self) -> Result<Option<&T>, InitError<E>> {
+        if let Some(value) = self.value.get() {
+            return Ok(Some(value));
+        }
+        match &*self.poison.borrow() {
+            Some(e) => Err(e.clone()),
+            None => Ok(None),
+        }
+    }
+
+    /// The last attempt failed and nothing has succeeded since.
+    pub fn is_poisoned(&self) -> bool {
+        self.value.get().is_none() && self.poison.borrow().is_some()
+    }
+
+    /// Like `get_or_init`, for initializers that aren't expected to fail.
+    ///
+    /// # Panics
+    ///
+    /// If initialization fails.
+    pub fn get(&self) -> &T
+    where
+        E: fmt::Debug,
+    {
+        match self.get_or_init() {
+            Ok(value) => value,
+            Err(e) => panic!("Cached::get: {e:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    /// Fails on attempt 1, panics on attempt 2, succeeds from then on.
+    fn flaky(attempts: &Cell<u32>) -> impl Fn() -> Result<String, String> + '_ {
+        move || {
+            attempts.set(attempts.get() + 1);
+            match attempts.get() {
+                1 => Err("config file missing".to_string()),
+                2 => panic!("parser blew up"),
+                n => Ok(format!("attempt {n}")),
+            }
+        }
+    }
+
+    #[test]
+    fn get_or_init_retries_after_an_error_and_a_panic() {
+        let attempts = Cell::new(0);
+        let cache = Cached::new(flaky(&attempts));
+
+        assert_eq!(
+            cache.get_or_init(),
+            Err(InitError::Failed("config file missing".to_string()))
+        );
+        assert!(cache.is_poisoned());
+
+        assert_eq!(
+            cache.get_or_init(),
+            Err(InitError::Panicked("parser blew up".to_string()))
+        );
+        assert!(cache.is_poisoned());
+
+        assert_eq!(cache.get_or_init().map(String::as_str), Ok("attempt 3"));
+        assert!(!cache.is_poisoned());
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn try_get_returns_the_stored_error_without_retrying() {
+        let attempts = Cell::new(0);
+        let cache = Cached::new(flaky(&attempts));
+        assert_eq!(cache.try_get(), Ok(None));
+
+        let _ = cache.get_or_init();
+        let failed = Err(InitError::Failed("config file missing".to_string()));
+        assert_eq!(cache.try_get(), failed);
+        assert_eq!(cache.try_get(), failed);
+
+        let _ = cache.get_or_init();
+        let panicked = Err(InitError::Panicked("parser blew up".to_string()));
+        assert_eq!(cache.try_get(), panicked);
+
+        assert_eq!(attempts.get(), 2);
+    }
+
+    #[test]
+    fn a_later_success_is_cached() {
+        let attempts = Cell::new(0);
+        let cache = Cached::new(flaky(&attempts));
+        let _ = cache.get_or_init();
+        let _ = cache.get_or_init();
+
+        let first: *const String = cache.get_or_init().unwrap();
+        assert_eq!(
+            cache.try_get().unwrap().map(String::as_str),
+            Some("attempt 3")
+        );
+        assert_eq!(cache.get(), "attempt 3");
+        assert!(std::ptr::eq(first, cache.get()));
+        assert_eq!(attempts.get(), 3);
+    }
+}
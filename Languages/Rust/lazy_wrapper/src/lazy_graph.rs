@@ -0,0 +1,379 @@
+// LazyGraph: lazy values that depend on other lazy values
+//
+//   config ──> settings ──> client
+//     │                       ^
+//     └───────────────────────┘
+//
+// Cached computes one value once. Real setups are chains: a client handle
+// built from derived settings built from config. When config changes,
+// everything downstream is stale, but nothing upstream is.
+//
+// - `input(value)` adds a node that holds a value; `set` replaces it
+// - `derived(&[deps], f)` adds a node computed from its deps; `f` reads them
+//   through `Deps::get` and runs the first time somebody asks for it
+// - `get(node)` computes whatever is missing, dependencies first
+// - `invalidate(node)` / `set(input, _)` drop every value downstream
+//
+// Dependencies are declared when a node is added and must already exist,
+// so there can't be a cycle, and node ids are already a topological order:
+// evaluating missing nodes in ascending id order computes every dependency
+// before the nodes that read it.
+//
+// Invariant: a node holding a value means all of its ancestors hold one
+// too (computing needs them, invalidating clears downstream). So the search
+// for missing work can stop at the first cached node on each path.
+//
+// Values are stored type-erased (`Rc<dyn Any>`) so one graph can mix types;
+// the `Node<T>` handle carries the type back out.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+type Value = Rc<dyn Any>;
+type Compute = Box<dyn Fn(&Deps) -> Value>;
+
+/// Typed handle to a node in a `LazyGraph`.
+pub struct Node<T> {
+    id: usize,
+    _type: PhantomData<fn() -> T>,
+}
+
+// Derives would require `T: Clone`/`T: Copy`; the handle is just an index
+impl<T> Clone for Node<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Node<T> {}
+
+impl<T> fmt::Debug for Node<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Node({})", self.id)
+    }
+}
+
+impl<T> Node<T> {
+    /// Untyped id, for listing a node among another node's dependencies.
+    pub fn id(self) -> NodeId {
+        NodeId(self.id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// The dependency values handed to a derived node's compute function.
+pub struct Deps<'a> {
+    ids: &'a [usize],
+    values: Vec<Value>,
+}
+
+impl Deps<'_> {
+    /// # Panics
+    ///
+    /// If `node` wasn't declared as a dependency, or has another type.
+    pub fn get<U: 'static>(&self, node: Node<U>) -> &U {
+        let pos = self
+            .ids
+            .iter()
+            .position(|&id| id == node.id)
+            .unwrap_or_else(|| panic!("{node:?} is not a declared dependency"));
+        self.values[pos]
+            .downcast_ref()
+            .expect("node holds a value of another type")
+    }
+}
+
+struct Slot {
+    deps: Vec<usize>,
+    dependents: Vec<usize>,
+    /// `None` for inputs, which are set rather than computed
+    compute: Option<Compute>,
+    value: RefCell<Option<Value>>,
+}
+
+#[derive(Default)]
+pub struct LazyGraph {
+    nodes: Vec<Slot>,
+}
+
+impl LazyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A node holding `value` until `set` replaces it.
+    pub fn input<T: 'static>(&mut self, value: T) -> Node<T> {
+        self.push(Vec::new(), None, Some(Rc::new(value)))
+    }
+
+    /// A node computed from `deps` on first use, and again after any of
+    /// them changes.
+    ///
+    /// # Panics
+    ///
+    /// If a dependency isn't a node of this graph.
+    pub fn derived<T: 'static>(
+        &mut self,
+        deps: &[NodeId],
+        compute: impl Fn(&Deps) -> T + 'static,
+    ) -> Node<T> {
+        let deps: Vec<usize> = deps.iter().map(|d| d.0).collect();
+        for &dep in &deps {
+            assert!(dep < self.nodes.len(), "unknown dependency {dep}");
+        }
+        let compute: Compute = Box::new(move |deps| Rc::new(compute(deps)));
+        self.push(deps, Some(compute), None)
+    }
+
+    /// The node's value, computing it and any missing dependencies first.
+    pub fn get<T: 'static>(&self, node: Node<T>) -> Rc<T> {
+        for id in self.missing(node.id) {
+            self.compute(id);
+        }
+        let value = self.nodes[node.id].value.borrow().clone();
+        value
+            .expect("computed above")
+            .downcast()
+            .expect("node holds a value of another type")
+    }
+
+    /// Replace an input's value; everything downstream is recomputed on
+    /// next use.
+    ///
+    /// # Panics
+    ///
+    /// If `node` is a derived node.
+    pub fn set<T: 'static>(&self, node: Node<T>, value: T) {
+        let slot = &self.nodes[node.id];
+        assert!(slot.compute.is_none(), "{node:?} is derived, not an input");
+        *slot.value.borrow_mut() = Some(Rc::new(value));
+        self.invalidate_dependents(node.id);
+    }
+
+    /// Drop the node's value (unless it's an input) and every value that
+    /// depends on it, directly or not.
+    pub fn invalidate<T>(&self, node: Node<T>) {
+        let slot = &self.nodes[node.id];
+        if slot.compute.is_some() {
+            slot.value.borrow_mut().take();
+        }
+        self.invalidate_dependents(node.id);
+    }
+
+    /// The node currently holds a value.
+    pub fn is_cached<T>(&self, node: Node<T>) -> bool {
+        self.nodes[node.id].value.borrow().is_some()
+    }
+
+    fn push<T>(
+        &mut self,
+        deps: Vec<usize>,
+        compute: Option<Compute>,
+        value: Option<Value>,
+    ) -> Node<T> {
+        let id = self.nodes.len();
+        for &dep in &deps {
+            self.nodes[dep].dependents.push(id);
+        }
+        self.nodes.push(Slot {
+            deps,
+            dependents: Vec::new(),
+            compute,
+            value: RefCell::new(value),
+        });
+        Node {
+            id,
+            _type: PhantomData,
+        }
+    }
+
+    /// `root` and its uncached ancestors, in evaluation order.
+    fn missing(&self, root: usize) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        let mut stack = vec![root];
+        let mut out = Vec::new();
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) || self.nodes[id].value.borrow().is_some() {
+                continue;
+            }
+            out.push(id);
+            stack.extend(&self.nodes[id].deps);
+        }
+        // Ids only ever point backwards, so ascending = dependencies first
+        out.sort_unstable();
+        out
+    }
+
+    fn compute(&self, id: usize) {
+        let slot = &self.nodes[id];
+        let deps = Deps {
+            ids: &slot.deps,
+            values: slot
+                .deps
+                .iter()
+                .map(|&dep| {
+                    let value = self.nodes[dep].value.borrow().clone();
+                    value.expect("dependencies are computed first")
+                })
+                .collect(),
+        };
+        let compute = slot.compute.as_ref().expect("inputs always hold a value");
+        *slot.value.borrow_mut() = Some(compute(&deps));
+    }
+
+    fn invalidate_dependents(&self, id: usize) {
+        let mut stack = self.nodes[id].dependents.clone();
+        while let Some(id) = stack.pop() {
+            let slot = &self.nodes[id];
+            // Already empty means its dependents are too (see invariant)
+            if slot.value.borrow_mut().take().is_some() {
+                stack.extend(&slot.dependents);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records which derived nodes ran, in order.
+    #[derive(Clone, Default)]
+    struct Log(Rc<RefCell<Vec<&'static str>>>);
+
+    impl Log {
+        fn ran(&self, name: &'static str) {
+            self.0.borrow_mut().push(name);
+        }
+
+        fn take(&self) -> Vec<&'static str> {
+            self.0.borrow_mut().drain(..).collect()
+        }
+    }
+
+    //   base ──> left ───┐
+    //     └────> right ──┴─> top        other (unrelated input) ──> side
+    struct Diamond {
+        graph: LazyGraph,
+        log: Log,
+        base: Node<i32>,
+        left: Node<i32>,
+        right: Node<i32>,
+        top: Node<i32>,
+        other: Node<i32>,
+        side: Node<i32>,
+    }
+
+    fn diamond() -> Diamond {
+        let log = Log::default();
+        let mut graph = LazyGraph::new();
+
+        let base = graph.input(1);
+        let other = graph.input(100);
+
+        let l = log.clone();
+        let left = graph.derived(&[base.id()], move |d| {
+            l.ran("left");
+            d.get(base) + 10
+        });
+        let l = log.clone();
+        let right = graph.derived(&[base.id()], move |d| {
+            l.ran("right");
+            d.get(base) * 2
+        });
+        let l = log.clone();
+        let top = graph.derived(&[left.id(), right.id()], move |d| {
+            l.ran("top");
+            d.get(left) + d.get(right)
+        });
+        let l = log.clone();
+        let side = graph.derived(&[other.id()], move |d| {
+            l.ran("side");
+            d.get(other) + 1
+        });
+
+        Diamond {
+            graph,
+            log,
+            base,
+            left,
+            right,
+            top,
+            other,
+            side,
+        }
+    }
+
+    #[test]
+    fn dependencies_are_computed_before_their_dependents_and_only_once() {
+        let d = diamond();
+
+        // (1 + 10) + (1 * 2)
+        assert_eq!(*d.graph.get(d.top), 13);
+        // `base` feeds both branches but the branches run once each, and
+        // both before `top`; the unrelated `side` isn't touched
+        assert_eq!(d.log.take(), ["left", "right", "top"]);
+        assert!(!d.graph.is_cached(d.side));
+
+        assert_eq!(*d.graph.get(d.top), 13);
+        assert_eq!(*d.graph.get(d.left), 11);
+        assert!(d.log.take().is_empty(), "cached values are reused");
+    }
+
+    #[test]
+    fn invalidating_reaches_transitive_dependents_only() {
+        let d = diamond();
+        d.graph.get(d.top);
+        d.graph.get(d.side);
+        d.log.take();
+
+        d.graph.invalidate(d.left);
+        assert!(!d.graph.is_cached(d.left));
+        assert!(!d.graph.is_cached(d.top));
+        assert!(d.graph.is_cached(d.base));
+        assert!(d.graph.is_cached(d.right));
+        assert!(d.graph.is_cached(d.side));
+
+        assert_eq!(*d.graph.get(d.top), 13);
+        assert_eq!(d.log.take(), ["left", "top"]);
+    }
+
+    #[test]
+    fn setting_an_input_recomputes_everything_downstream_on_next_use() {
+        let d = diamond();
+        d.graph.get(d.top);
+        d.graph.get(d.side);
+        d.log.take();
+
+        d.graph.set(d.base, 5);
+        for node in [d.left, d.right, d.top] {
+            assert!(!d.graph.is_cached(node));
+        }
+        assert!(d.graph.is_cached(d.other));
+        assert!(d.graph.is_cached(d.side));
+
+        assert_eq!(*d.graph.get(d.top), (5 + 10) + 5 * 2);
+        assert_eq!(d.log.take(), ["left", "right", "top"]);
+        assert_eq!(*d.graph.get(d.side), 101);
+        assert!(d.log.take().is_empty());
+    }
+
+    #[test]
+    fn invalidating_an_input_keeps_its_value() {
+        let d = diamond();
+        d.graph.get(d.side);
+        d.log.take();
+
+        d.graph.invalidate(d.other);
+        assert!(d.graph.is_cached(d.other));
+        assert!(!d.graph.is_cached(d.side));
+        assert_eq!(*d.graph.get(d.side), 101);
+        assert_eq!(d.log.take(), ["side"]);
+    }
+}
@@ -1,35 +1,94 @@
-use std::cell::{OnceCell, RefCell};
-use std::collections::HashMap;
-use std::sync::{Mutex, OnceLock};
+mod cached;
+mod lazy_graph;
+mod sync_cached;
 
-struct Cached<T, F> {
-    data: OnceCell<T>,
-    compute: RefCell<Option<F>>,
-}
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
 
-impl<T, F> Cached<T, F>
-where
-    F: FnOnce() -> T,
-{
-    fn new(compute: F) -> Self {
-        Self {
-            data: OnceCell::new(),
-            compute: RefCell::new(Some(compute)),
-        }
-    }
+use cached::Cached;
+use lazy_graph::LazyGraph;
+use sync_cached::SyncCached;
 
-    fn get(&self) -> &T {
-        self.data.get_or_init(|| {
-            let f = self.compute.borrow_mut().take().unwrap();
-            f()
-        })
-    }
-}
 fn main() {
-    let cache = Cached::new(|| 1 + 1);
+    let cache = Cached::new(|| Ok::<_, String>(1 + 1));
 
     println!("{}", *cache.get());
     println!("{}", *cache.get()); // cached
+
+    // Fails, then panics, then works: each failure poisons the cell and the
+    // next get_or_init retries
+    let attempts = Cell::new(0);
+    let flaky = Cached::new(|| {
+        attempts.set(attempts.get() + 1);
+        match attempts.get() {
+            1 => Err("config file missing".to_string()),
+            2 => panic!("parser blew up"),
+            n => Ok(format!("loaded on attempt {n}")),
+        }
+    });
+
+    println!("{:?}", flaky.try_get()); // Ok(None), nothing tried yet
+    println!("{:?}", flaky.get_or_init()); // Err(Failed(..))
+    println!("{:?}", flaky.try_get()); // same error, no retry
+    println!("{:?}", flaky.get_or_init()); // Err(Panicked(..))
+    println!("poisoned: {}", flaky.is_poisoned());
+    println!("{:?}", flaky.get_or_init()); // Ok, recovered
+    println!("{:?}", flaky.try_get());
+
+    // Thread-safe: eight threads race, the initializer runs once per failure
+    let runs = Arc::new(Mutex::new(0));
+    let shared = {
+        let runs = Arc::clone(&runs);
+        Arc::new(SyncCached::new(move || {
+            let mut runs = runs.lock().unwrap();
+            *runs += 1;
+            if *runs == 1 {
+                Err("first connect refused")
+            } else {
+                Ok(42)
+            }
+        }))
+    };
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || shared.get_or_init().ok().copied())
+        })
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    println!(
+        "{results:?}, initializer ran {} times",
+        runs.lock().unwrap()
+    );
+    println!(
+        "poisoned: {}, value: {}",
+        shared.is_poisoned(),
+        shared.get()
+    );
+
+    // config -> settings -> client: change config, only downstream reruns
+    let mut graph = LazyGraph::new();
+    let host = graph.input("db.local".to_string());
+    let pool_size = graph.input(4u32);
+    let url = graph.derived(&[host.id()], move |d| {
+        println!("  computing url");
+        format!("postgres://{}/app", d.get(host))
+    });
+    let client = graph.derived(&[url.id(), pool_size.id()], move |d| {
+        println!("  connecting");
+        format!("client({} x{})", d.get(url), d.get(pool_size))
+    });
+
+    println!("{}", graph.get(client)); // url, then client
+    println!("{}", graph.get(client)); // cached
+    graph.set(pool_size, 8);
+    println!("url cached: {}", graph.is_cached(url));
+    println!("{}", graph.get(client)); // client only
+    graph.invalidate(url);
+    println!("{}", graph.get(client)); // url, then client
 }
 
 // use std::cell::RefCell;
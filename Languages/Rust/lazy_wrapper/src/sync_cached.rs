@@ -0,0 +1,189 @@
+// SyncCached<T, E, F>: Cached for sharing across threads
+//
+// Same states and methods as Cached (cached.rs), with double-checked
+// locking so the common case costs one atomic load:
+//
+//   get_or_init:
+//     1. value.get()            <- lock-free; Some once anybody succeeded
+//     2. lock the init mutex    <- only while the value is missing
+//     3. value.get() again      <- another thread may have won meanwhile
+//     4. run the initializer, store the value or the poison
+//
+// Only one thread runs the initializer at a time; the others wait on the
+// mutex and then see either the value or a fresh poison and try themselves.
+//
+// The mutex never poisons in the std sense: a panicking initializer is
+// caught inside the lock, and recorded as our own poison instead.
+
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, OnceLock, TryLockError};
+
+use crate::cached::{InitError, run_init};
+
+pub struct SyncCached<T, E, F> {
+    value: OnceLock<T>,
+    /// Held while initializing; holds the last failure
+    poison: Mutex<Option<InitError<E>>>,
+    init: F,
+}
+
+impl<T, E, F> SyncCached<T, E, F>
+where
+    F: Fn() -> Result<T, E>,
+    E: Clone,
+{
+    pub fn new(init: F) -> Self {
+        Self {
+            value: OnceLock::new(),
+            poison: Mutex::new(None),
+            init,
+        }
+    }
+
+    /// The value, initializing it first if the cell is empty or poisoned.
+    ///
+    /// The initializer must not call `get_or_init` on the same cell; that
+    /// deadlocks (or panics) on the init mutex.
+    pub fn get_or_init(&self) -> Result<&T, InitError<E>> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let mut poison = self.lock();
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        match run_init(&self.init) {
+            Ok(value) => {
+                *poison = None;
+                Ok(self.value.get_or_init(|| value))
+            }
+            Err(e) => {
+                *poison = Some(e.clone());
+                Err(e)
+            }
+        }
+    }
+
+    /// The value if it's there, `Ok(None)` if it isn't yet (including
+    /// while another thread is initializing), or the error that poisoned
+    /// the cell. Never runs the initializer and never blocks.
+    pub fn try_get(&self) -> Result<Option<&T>, InitError<E>> {
+        if let Some(value) = self.value.get() {
+            return Ok(Some(value));
+        }
+        let poison = match self.poison.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => return Ok(None),
+        };
+        match &*poison {
+            Some(e) => Err(e.clone()),
+            // The value may have landed between the first check and the lock
+            None => Ok(self.value.get()),
+        }
+    }
+
+    /// The last attempt failed and nothing has succeeded since.
+    pub fn is_poisoned(&self) -> bool {
+        self.try_get().is_err()
+    }
+
+    /// Like `get_or_init`, for initializers that aren't expected to fail.
+    ///
+    /// # Panics
+    ///
+    /// If initialization fails.
+    pub fn get(&self) -> &T
+    where
+        E: fmt::Debug,
+    {
+        match self.get_or_init() {
+            Ok(value) => value,
+            Err(e) => panic!("SyncCached::get: {e:?}"),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<InitError<E>>> {
+        // Initializer panics are caught before they can poison the mutex,
+        // but don't turn a panic elsewhere into a second one
+        self.poison.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Barrier;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Fails on attempt 1, panics on attempt 2, succeeds from then on.
+    fn flaky(attempts: &AtomicU32) -> impl Fn() -> Result<String, String> + '_ {
+        move || match attempts.fetch_add(1, Ordering::SeqCst) + 1 {
+            1 => Err("config file missing".to_string()),
+            2 => panic!("parser blew up"),
+            n => Ok(format!("attempt {n}")),
+        }
+    }
+
+    #[test]
+    fn get_or_init_retries_after_an_error_and_a_panic() {
+        let attempts = AtomicU32::new(0);
+        let cache = SyncCached::new(flaky(&attempts));
+        assert_eq!(cache.try_get(), Ok(None));
+
+        let failed = InitError::Failed("config file missing".to_string());
+        assert_eq!(cache.get_or_init().unwrap_err(), failed);
+        assert_eq!(cache.try_get().unwrap_err(), failed);
+        assert!(cache.is_poisoned());
+
+        let panicked = InitError::Panicked("parser blew up".to_string());
+        assert_eq!(cache.get_or_init().unwrap_err(), panicked);
+        assert_eq!(cache.try_get().unwrap_err(), panicked);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        assert_eq!(cache.get_or_init().map(String::as_str), Ok("attempt 3"));
+        assert!(!cache.is_poisoned());
+        assert_eq!(cache.get(), "attempt 3");
+        assert_eq!(
+            cache.try_get().unwrap().map(String::as_str),
+            Some("attempt 3")
+        );
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn concurrent_callers_run_the_initializer_once() {
+        const THREADS: usize = 8;
+        let attempts = AtomicU32::new(0);
+        let cache = SyncCached::new(|| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            // Long enough for every thread to pile up on the lock
+            thread::sleep(Duration::from_millis(50));
+            Ok::<_, String>(42)
+        });
+        let start = Barrier::new(THREADS);
+
+        // Where each thread's reference points: all at the one stored value
+        let addresses: Vec<usize> = thread::scope(|s| {
+            let handles: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    s.spawn(|| {
+                        start.wait();
+                        std::ptr::from_ref(cache.get_or_init().unwrap()) as usize
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        let stored = std::ptr::from_ref(cache.get()) as usize;
+        assert!(addresses.iter().all(|&address| address == stored));
+        assert_eq!(*cache.get(), 42);
+    }
+}
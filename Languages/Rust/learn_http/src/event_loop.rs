@@ -0,0 +1,352 @@
+// A single-threaded, readiness-driven server (epoll on Linux, kqueue on
+// macOS/BSD, via mio)
+//
+// fd.rs showed that every socket is a file descriptor. Here we put those
+// descriptors in NON-BLOCKING mode and hand them all to one poller:
+//
+//   poll.poll(&mut events)     <- sleeps until *some* fd is ready
+//   for event in events:       <- "fd 7 is readable", "fd 9 is writable"
+//       do as much I/O as that fd allows without blocking
+//
+// No thread per connection (server.rs, http_over_tcp) and no runtime hiding
+// the loop (async_server/tokyo.rs): one thread, one loop, and every
+// connection is a small state machine we drive by hand:
+//
+//   Reading ──(a whole request buffered)──> Writing
+//      ^                                       │
+//      └──────(response flushed, keep-alive)───┘
+//
+//   EOF, errors and `Connection: close` lead to Closed.
+//
+// tokio does exactly this underneath; an `.await` on a socket is "go back
+// to the loop until this fd is ready".
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+
+use mio::net::{TcpListener, TcpStream};
+use mio::{Events, Interest, Poll, Registry, Token};
+
+use crate::http_over_tcp::request;
+use crate::http_over_tcp::response::Response;
+use crate::http_over_tcp::router::Router;
+
+/// Token of the listening socket; connections get 1, 2, 3...
+const LISTENER: Token = Token(0);
+
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// What the server speaks on each connection.
+pub enum Mode {
+    /// Send back whatever arrives
+    Echo,
+    /// HTTP/1.1 with keep-alive, dispatched through a router
+    Http(Router),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Waiting for (more of) a request
+    Reading,
+    /// Flushing `out`; back to Reading when it's empty
+    Writing,
+    /// Done; drop the connection
+    Closed,
+}
+
+struct Connection {
+    stream: TcpStream,
+    peer: SocketAddr,
+    state: State,
+    /// Bytes read but not yet handled (a partial request)
+    input: Vec<u8>,
+    /// Bytes to write, and how many of them are already written
+    out: Vec<u8>,
+    written: usize,
+    /// Close once `out` is flushed (Connection: close, bad request, EOF)
+    close_after_write: bool,
+}
+
+impl Connection {
+    fn new(stream: TcpStream, peer: SocketAddr) -> Self {
+        Connection {
+            stream,
+            peer,
+            state: State::Reading,
+            input: Vec::new(),
+            out: Vec::new(),
+            written: 0,
+            close_after_write: false,
+        }
+    }
+
+    /// Drive the state machine for one readiness event.
+    fn ready(&mut self, readable: bool, mode: &Mode) -> io::Result<()> {
+        if readable && self.state == State::Reading {
+            let eof = self.read_available()?;
+            self.handle_input(mode);
+            if eof {
+                // Peer is done sending; answer what we have, then close
+                self.close_after_write = true;
+                if self.out.is_empty() {
+                    self.state = State::Closed;
+                }
+            }
+        }
+
+        // Don't wait for a writable event to try: the send buffer usually
+        // has room, and if not we get WouldBlock and wait after all
+        if self.state == State::Writing {
+            self.write_pending()?;
+        }
+
+        Ok(())
+    }
+
+    /// Read until the socket would block. Returns true on EOF.
+    ///
+    /// Readiness only says "at least one byte"; with edge-triggered polling
+    /// (mio's default) we won't be told again about bytes we leave behind,
+    /// so we must drain the socket.
+    fn read_available(&mut self) -> io::Result<bool> {
+        let mut chunk = [0u8; 4096];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Ok(true),
+                Ok(n) => self.input.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(false),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Turn buffered input into buffered output.
+    fn handle_input(&mut self, mode: &Mode) {
+        match mode {
+            Mode::Echo => {
+                self.out.append(&mut self.input);
+            }
+            Mode::Http(router) => {
+                // Several pipelined requests may have arrived at once
+                while !self.close_after_write {
+                    match request::parse_request(&self.input, MAX_BODY_SIZE) {
+                        Ok(Some((request, used))) => {
+                            self.input.drain(..used);
+                            let close = request
+                                .header("connection")
+                                .is_some_and(|v| v.eq_ignore_ascii_case("close"));
+                            println!(
+                                "[LOOP] {} {} {} ({})",
+                                self.peer,
+                                request.method(),
+                                request.path(),
+                                if close { "close" } else { "keep-alive" }
+                            );
+
+                            let head_only = request.method() == "HEAD";
+                            let response = router.dispatch(request);
+                            let mut bytes = response.to_bytes(close);
+                            if head_only {
+                                bytes.truncate(bytes.len() - response.body_bytes().len());
+                            }
+                            self.out.extend_from_slice(&bytes);
+                            self.close_after_write = close;
+                        }
+                        // Not all there yet: wait for the next readable event
+                        Ok(None) => break,
+                        Err(e) => {
+                            let response = Response::text(e.status(), e.to_string());
+                            self.out.extend_from_slice(&response.to_bytes(true));
+                            self.close_after_write = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        if !self.out.is_empty() {
+            self.state = State::Writing;
+        }
+    }
+
+    /// Write until done or the socket would block.
+    fn write_pending(&mut self) -> io::Result<()> {
+        while self.written < self.out.len() {
+            match self.stream.write(&self.out[self.written..]) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => self.written += n,
+                // Kernel send buffer is full; wait for the next writable event
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.out.clear();
+        self.written = 0;
+        self.state = if self.close_after_write {
+            State::Closed
+        } else {
+            State::Reading
+        };
+        Ok(())
+    }
+
+    /// Tell the poller which events we care about in the current state.
+    ///
+    /// Asking for WRITABLE while there's nothing to write would wake us up
+    /// constantly: an idle socket is almost always writable.
+    fn reregister(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        let interest = match self.state {
+            State::Writing => Interest::WRITABLE,
+            _ => Interest::READABLE,
+        };
+        registry.reregister(&mut self.stream, token, interest)
+    }
+}
+
+pub struct EventLoopServer {
+    poll: Poll,
+    listener: TcpListener,
+    mode: Mode,
+}
+
+impl EventLoopServer {
+    pub fn bind(addr: &str, mode: Mode) -> io::Result<Self> {
+        let addr: SocketAddr = addr
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let poll = Poll::new()?;
+        let mut listener = TcpListener::bind(addr)?;
+        poll.registry()
+            .register(&mut listener, LISTENER, Interest::READABLE)?;
+
+        Ok(EventLoopServer {
+            poll,
+            listener,
+            mode,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Run the event loop forever.
+    pub fn run(mut self) -> io::Result<()> {
+        let mut events = Events::with_capacity(256);
+        let mut connections: HashMap<Token, Connection> = HashMap::new();
+        let mut next_token = 1;
+
+        println!("[LOOP] listening on {}", self.local_addr()?);
+
+        loop {
+            // The only place this thread ever blocks
+            if let Err(e) = self.poll.poll(&mut events, None) {
+                if e.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(e);
+            }
+
+            for event in events.iter() {
+                if event.token() == LISTENER {
+                    self.accept_all(&mut connections, &mut next_token)?;
+                    continue;
+                }
+
+                let token = event.token();
+                let Some(conn) = connections.get_mut(&token) else {
+                    // Already closed earlier in this batch
+                    continue;
+                };
+
+                let result = conn
+                    .ready(event.is_readable(), &self.mode)
+                    .and_then(|()| match conn.state {
+                        State::Closed => Ok(()),
+                        _ => conn.reregister(self.poll.registry(), token),
+                    });
+
+                if let Err(e) = &result {
+                    eprintln!("[LOOP] {} error: {}", conn.peer, e);
+                }
+                if result.is_err() || conn.state == State::Closed {
+                    let mut conn = connections.remove(&token).unwrap();
+                    self.poll.registry().deregister(&mut conn.stream)?;
+                    println!("[LOOP] {} closed ({} open)", conn.peer, connections.len());
+                }
+            }
+        }
+    }
+
+    /// Accept every pending connection; the listener is edge-triggered too.
+    fn accept_all(
+        &mut self,
+        connections: &mut HashMap<Token, Connection>,
+        next_token: &mut usize,
+    ) -> io::Result<()> {
+        loop {
+            let (mut stream, peer) = match self.listener.accept() {
+                Ok(accepted) => accepted,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e),
+            };
+
+            let token = Token(*next_token);
+            *next_token += 1;
+            self.poll
+                .registry()
+                .register(&mut stream, token, Interest::READABLE)?;
+
+            println!(
+                "[LOOP] {} connected as {:?} ({} open)",
+                peer,
+                token,
+                connections.len() + 1
+            );
+            connections.insert(token, Connection::new(stream, peer));
+        }
+    }
+}
+
+pub fn run_main() -> io::Result<()> {
+    println!("=== SINGLE-THREADED EVENT LOOP (mio) ===\n");
+    println!("Try: curl -v http://127.0.0.1:8003/ http://127.0.0.1:8003/hello/you");
+    println!("     (curl reuses one keep-alive connection for both)\n");
+
+    let router = Router::new()
+        .get("/", |_| {
+            Response::text(200, "served by one thread and a poller\n")
+        })
+        .get("/hello/{name}", |req| {
+            Response::text(200, format!("hello, {}\n", req.param("name").unwrap_or("")))
+        });
+
+    EventLoopServer::bind("127.0.0.1:8003", Mode::Http(router))?.run()
+}
+
+pub fn run_echo() -> io::Result<()> {
+    println!("=== SINGLE-THREADED ECHO (mio) ===\n");
+    println!("Try: nc 127.0.0.1 8004   (open several at once)\n");
+
+    EventLoopServer::bind("127.0.0.1:8004", Mode::Echo)?.run()
+}
+
+// BLOCKING VS READINESS:
+//
+// Blocking (thread per connection):
+//   read(fd)  -> the thread sleeps until data arrives
+//   1000 idle clients = 1000 sleeping threads (stacks, context switches)
+//
+// Readiness (this file):
+//   epoll_wait(all fds) -> the thread sleeps until ANY fd is ready
+//   read(fd)            -> never sleeps; returns WouldBlock if empty
+//   1000 idle clients = 1000 small Connection structs in a HashMap
+//
+// The price: everything a thread would keep on its stack (how much have I
+// read? what do I still have to write?) lives in Connection, and a handler
+// that blocks (or just computes for long) stalls every client.
@@ -0,0 +1,183 @@
+// Access logs: one line per request the server answered
+//
+// Common Log Format, as web servers have written it since NCSA httpd, with
+// the duration added at the end:
+//
+//   127.0.0.1 - - [10/Oct/2000:13:55:36 +0000] "GET /a?b=1 HTTP/1.1" 200 2326 1.204ms
+//
+// or one JSON object per line, for log shippers:
+//
+//   {"bytes":2326,"duration_ms":1.204,"method":"GET","path":"/a?b=1",
+//    "remote_addr":"127.0.0.1:51234","status":200,"time":"2000-10-10T13:55:36.000Z"}
+//
+// Where the lines go is up to an AccessLogSink: stdout, or memory so tests
+// can look at what was logged.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// One answered request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessLogEntry {
+    pub remote_addr: Option<SocketAddr>,
+    /// When the connection was accepted
+    pub time: SystemTime,
+    /// `None` if the request couldn't be parsed
+    pub method: Option<String>,
+    /// Path and query, as requested
+    pub path: Option<String>,
+    pub status: u16,
+    /// Body bytes sent (after compression and chunking), without the head
+    pub bytes: u64,
+    /// From accepting the connection to the last byte of the response
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Common,
+    Json,
+}
+
+impl AccessLogEntry {
+    pub fn format(&self, format: LogFormat) -> String {
+        match format {
+            LogFormat::Common => self.to_common(),
+            LogFormat::Json => self.to_json(),
+        }
+    }
+
+    fn to_common(&self) -> String {
+        // CLF writes "-" for anything it doesn't know
+        let remote = self
+            .remote_addr
+            .map_or_else(|| "-".to_string(), |addr| addr.ip().to_string());
+        let request = match (&self.method, &self.path) {
+            (Some(method), Some(path)) => format!("{method} {path} HTTP/1.1"),
+            _ => "-".to_string(),
+        };
+        let bytes = match self.bytes {
+            0 => "-".to_string(),
+            n => n.to_string(),
+        };
+
+        format!(
+            "{remote} - - [{}] \"{request}\" {} {bytes} {:.3}ms",
+            clf_time(self.time),
+            self.status,
+            duration_ms(self.duration)
+        )
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::json!({
+            "remote_addr": self.remote_addr.map(|addr| addr.to_string()),
+            "time": humantime::format_rfc3339_millis(self.time).to_string(),
+            "method": self.method,
+            "path": self.path,
+            "status": self.status,
+            "bytes": self.bytes,
+            // Microsecond precision, like the CLF line
+            "duration_ms": (duration_ms(self.duration) * 1000.0).round() / 1000.0,
+        })
+        .to_string()
+    }
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// `10/Oct/2000:13:55:36 +0000` (always UTC).
+fn clf_time(time: SystemTime) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let rem = secs % 86_400;
+
+    format!(
+        "{day:02}/{}/{year}:{:02}:{:02}:{:02} +0000",
+        MONTHS[month as usize - 1],
+        rem / 3600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+/// Days since 1970-01-01 to (year, month, day), from Howard Hinnant's
+/// `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// ============================================================================
+// Sinks
+// ============================================================================
+
+/// Where access log entries go. Called from every connection thread.
+pub trait AccessLogSink: Send + Sync {
+    fn log(&self, entry: &AccessLogEntry);
+}
+
+/// Prints each entry on its own line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink {
+    format: LogFormat,
+}
+
+impl StdoutSink {
+    pub fn new(format: LogFormat) -> Self {
+        StdoutSink { format }
+    }
+}
+
+impl AccessLogSink for StdoutSink {
+    fn log(&self, entry: &AccessLogEntry) {
+        println!("{}", entry.format(self.format));
+    }
+}
+
+/// Keeps the entries; clones share them, so keep one and give the server
+/// the other.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    entries: Arc<Mutex<Vec<AccessLogEntry>>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        MemorySink::default()
+    }
+
+    /// Everything logged so far, oldest first.
+    pub fn entries(&self) -> Vec<AccessLogEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    pub fn lines(&self, format: LogFormat) -> Vec<String> {
+        self.entries().iter().map(|e| e.format(format)).collect()
+    }
+}
+
+impl AccessLogSink for MemorySink {
+    fn log(&self, entry: &AccessLogEntry) {
+        self.entries.lock().unwrap().push(entry.clone());
+    }
+}
@@ -0,0 +1,277 @@
+// HTTP compression: Accept-Encoding negotiation and gzip/deflate codecs
+//
+// The client lists the codings it understands, with optional weights:
+//
+//   Accept-Encoding: gzip;q=1.0, deflate;q=0.5, *;q=0
+//
+// The server picks one (or none) and says so in the response:
+//
+//   Content-Encoding: gzip
+//   Vary: Accept-Encoding        <- caches must key on the request header
+//
+// The same header works the other way round: a client may send a request
+// body with `Content-Encoding: gzip` and the server has to decode it before
+// the handler sees it.
+//
+// NOTE: HTTP's "deflate" is the zlib format (RFC 1950), not raw deflate.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use flate2::Compression;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+
+/// A content coding the server can produce and decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Gzip,
+    Deflate,
+}
+
+impl Encoding {
+    /// Preferred first when the client weighs several codings the same.
+    const SUPPORTED: [Encoding; 2] = [Encoding::Gzip, Encoding::Deflate];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    fn parse(token: &str) -> Option<Encoding> {
+        match token.to_ascii_lowercase().as_str() {
+            // x-gzip is the pre-HTTP/1.1 alias, still sent by some clients
+            "gzip" | "x-gzip" => Some(Encoding::Gzip),
+            "deflate" => Some(Encoding::Deflate),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Pick the response coding for an `Accept-Encoding` header value.
+///
+/// Returns `None` when the body should be sent as-is (identity).
+pub fn negotiate(accept_encoding: &str) -> Option<Encoding> {
+    // q-values are 0.000..=1.000; compare them as integers in thousandths
+    let mut explicit: Vec<(Encoding, u16)> = Vec::new();
+    let mut wildcard: Option<u16> = None;
+
+    for item in accept_encoding.split(',') {
+        let mut parts = item.split(';');
+        let token = parts.next().unwrap_or("").trim();
+        let q = parts
+            .filter_map(|p| p.trim().strip_prefix("q="))
+            .find_map(parse_qvalue)
+            .unwrap_or(1000);
+
+        if token == "*" {
+            wildcard = Some(q);
+        } else if let Some(encoding) = Encoding::parse(token) {
+            explicit.push((encoding, q));
+        }
+    }
+
+    Encoding::SUPPORTED
+        .iter()
+        .filter_map(|&encoding| {
+            let q = explicit
+                .iter()
+                .find(|(e, _)| *e == encoding)
+                .map(|(_, q)| *q)
+                .or(wildcard)?;
+            (q > 0).then_some((encoding, q))
+        })
+        // max_by_key keeps the last maximum; reverse so gzip wins ties
+        .rev()
+        .max_by_key(|(_, q)| *q)
+        .map(|(encoding, _)| encoding)
+}
+
+fn parse_qvalue(value: &str) -> Option<u16> {
+    let q: f32 = value.trim().parse().ok()?;
+    (0.0..=1.0)
+        .contains(&q)
+        .then(|| (q * 1000.0).round() as u16)
+}
+
+/// Whether a body of this type is worth compressing.
+///
+/// Images, audio, video and archives are already compressed; running them
+/// through gzip again costs CPU and usually makes them bigger.
+pub fn is_compressible(content_type: Option<&str>) -> bool {
+    let Some(content_type) = content_type else {
+        return false;
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || matches!(
+            mime.as_str(),
+            "application/json" | "application/javascript" | "application/xml" | "image/svg+xml"
+        )
+}
+
+// ============================================================================
+// Streaming responses
+// ============================================================================
+//
+// We don't know the compressed size up front, so there is no Content-Length.
+// Instead the body goes out with `Transfer-Encoding: chunked`:
+//
+//   1f4\r\n            <- chunk size in hex
+//   <500 bytes>\r\n
+//   0\r\n\r\n          <- last chunk
+//
+// The encoder writes into a ChunkedWriter, which writes into the socket, so
+// compressed bytes leave as soon as the encoder flushes its buffer instead
+// of waiting for the whole body.
+
+/// Frames every write as one HTTP/1.1 chunk.
+pub struct ChunkedWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> ChunkedWriter<W> {
+    pub fn new(inner: W) -> Self {
+        ChunkedWriter { inner }
+    }
+
+    /// Write the terminating zero-length chunk and hand back the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.write_all(b"0\r\n\r\n")?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for ChunkedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // An empty chunk would end the body early
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        write!(self.inner, "{:x}\r\n", buf.len())?;
+        self.inner.write_all(buf)?;
+        self.inner.write_all(b"\r\n")?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Size of the slices fed to the encoder (and roughly of each chunk).
+const STREAM_CHUNK: usize = 8 * 1024;
+
+/// Compress `body` into `out` as a chunked stream.
+pub fn write_compressed<W: Write>(encoding: Encoding, body: &[u8], out: W) -> io::Result<W> {
+    let chunked = ChunkedWriter::new(out);
+
+    let chunked = match encoding {
+        Encoding::Gzip => {
+            let mut encoder = GzEncoder::new(chunked, Compression::default());
+            for piece in body.chunks(STREAM_CHUNK) {
+                encoder.write_all(piece)?;
+            }
+            encoder.finish()?
+        }
+        Encoding::Deflate => {
+            let mut encoder = ZlibEncoder::new(chunked, Compression::default());
+            for piece in body.chunks(STREAM_CHUNK) {
+                encoder.write_all(piece)?;
+            }
+            encoder.finish()?
+        }
+    };
+
+    chunked.finish()
+}
+
+// ============================================================================
+// Request bodies
+// ============================================================================
+
+#[derive(Debug)]
+pub enum DecodeError {
+    /// A coding we can't decode -> 415 Unsupported Media Type
+    Unsupported(String),
+    /// The body isn't valid for its declared coding -> 400 Bad Request
+    Corrupt(io::Error),
+    /// Decompressed past the body limit (think zip bomb) -> 413
+    TooLarge,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Unsupported(coding) => write!(f, "unsupported content coding '{coding}'"),
+            DecodeError::Corrupt(e) => write!(f, "corrupt request body: {e}"),
+            DecodeError::TooLarge => write!(f, "decoded request body too large"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Undo the `Content-Encoding` of a request body.
+///
+/// Codings are listed in the order they were applied, so they are removed
+/// back to front. The decoded body may not grow past `limit` bytes.
+pub fn decode_body(
+    content_encoding: &str,
+    body: Vec<u8>,
+    limit: usize,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut body = body;
+
+    for coding in content_encoding.rsplit(',').map(str::trim) {
+        if coding.is_empty() || coding.eq_ignore_ascii_case("identity") {
+            continue;
+        }
+        let encoding =
+            Encoding::parse(coding).ok_or_else(|| DecodeError::Unsupported(coding.to_string()))?;
+
+        // Read one byte past the limit to tell "exactly full" from "too big"
+        let mut decoded = Vec::new();
+        let read = match encoding {
+            Encoding::Gzip => GzDecoder::new(body.as_slice())
+                .take(limit as u64 + 1)
+                .read_to_end(&mut decoded),
+            Encoding::Deflate => ZlibDecoder::new(body.as_slice())
+                .take(limit as u64 + 1)
+                .read_to_end(&mut decoded),
+        };
+        read.map_err(DecodeError::Corrupt)?;
+
+        if decoded.len() > limit {
+            return Err(DecodeError::TooLarge);
+        }
+        body = decoded;
+    }
+
+    Ok(body)
+}
+
+// WHY A SIZE THRESHOLD?
+//
+// gzip adds ~20 bytes of header and trailer, and chunked framing adds a few
+// more. For a 50-byte JSON reply the "compressed" response is bigger than
+// the original, and we burned CPU making it. Servers usually only compress
+// bodies above ~1 KiB (nginx: gzip_min_length, default 20 bytes; most
+// deployments raise it).
@@ -0,0 +1,211 @@
+// HAR (HTTP Archive): the JSON format browsers export from the network tab
+//
+//   {"log": {"version": "1.2", "creator": {...}, "entries": [
+//     {"startedDateTime": "...", "time": 12.5,
+//      "request":  {"method": "GET", "url": "...", "headers": [...], ...},
+//      "response": {"status": 200, "headers": [...], "content": {...}, ...},
+//      "timings":  {"send": 0.1, "wait": 11.9, "receive": 0.5}}
+//   ]}}
+//
+// We write one entry per file. Bodies are kept as text when they are UTF-8
+// and base64 otherwise, cut at a size limit (marked with `_truncated`;
+// HAR allows custom fields if they start with an underscore).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Har {
+    pub log: Log,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Log {
+    pub version: String,
+    pub creator: Creator,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Creator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    pub started_date_time: String,
+    /// Total time in milliseconds
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub timings: Timings,
+    #[serde(rename = "serverIPAddress", default)]
+    pub server_address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub headers: Vec<NameValue>,
+    pub query_string: Vec<NameValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<Body>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub headers: Vec<NameValue>,
+    pub content: Body,
+    #[serde(rename = "redirectURL", default)]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+/// A captured body (`postData` for requests, `content` for responses).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    /// Full size in bytes, even when `text` was cut
+    pub size: usize,
+    pub mime_type: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    #[serde(rename = "_truncated", default, skip_serializing_if = "is_false")]
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timings {
+    /// Milliseconds spent writing the request upstream
+    pub send: f64,
+    /// Milliseconds until the first response byte
+    pub wait: f64,
+    /// Milliseconds reading the rest of the response
+    pub receive: f64,
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+impl Har {
+    pub fn single(entry: Entry) -> Self {
+        Har {
+            log: Log {
+                version: "1.2".to_string(),
+                creator: Creator {
+                    name: env!("CARGO_PKG_NAME").to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                entries: vec![entry],
+            },
+        }
+    }
+}
+
+impl Body {
+    /// Capture `bytes`, keeping at most `limit` of them.
+    pub fn capture(bytes: &[u8], mime_type: &str, limit: usize) -> Self {
+        let kept = &bytes[..bytes.len().min(limit)];
+        let (text, encoding) = match std::str::from_utf8(kept) {
+            Ok(text) => (text.to_string(), None),
+            Err(_) => (base64_encode(kept), Some("base64".to_string())),
+        };
+
+        Body {
+            size: bytes.len(),
+            mime_type: mime_type.to_string(),
+            text,
+            encoding,
+            truncated: kept.len() < bytes.len(),
+        }
+    }
+
+    /// The captured bytes (all of them unless `truncated`).
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        match self.encoding.as_deref() {
+            None => Some(self.text.as_bytes().to_vec()),
+            Some("base64") => base64_decode(&self.text),
+            Some(_) => None,
+        }
+    }
+}
+
+pub fn name_values(pairs: &[(String, String)]) -> Vec<NameValue> {
+    pairs
+        .iter()
+        .map(|(name, value)| NameValue {
+            name: name.clone(),
+            value: value.clone(),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Base64 (RFC 4648, with padding)
+// ============================================================================
+//
+// Every 3 bytes (24 bits) become 4 characters of 6 bits each:
+//
+//   "Man" = 01001101 01100001 01101110
+//         = 010011 010110 000101 101110 = T W F u
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for group in bytes.chunks(3) {
+        let b = [
+            group[0],
+            group.get(1).copied().unwrap_or(0),
+            group.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= group.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+pub fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut n: u32 = 0;
+    let mut bits = 0;
+
+    for c in text.bytes() {
+        let value = ALPHABET.iter().position(|&a| a == c)? as u32;
+        n = n << 6 | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((n >> bits) as u8);
+        }
+    }
+    Some(out)
+}
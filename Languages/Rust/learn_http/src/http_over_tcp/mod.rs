@@ -1,2 +1,10 @@
+pub mod access_log;
+pub mod compression;
+pub mod har;
 pub mod http_server;
 pub mod http_server_with_parse;
+pub mod proxy;
+pub mod request;
+pub mod response;
+pub mod router;
+pub mod server;
@@ -0,0 +1,501 @@
+// A recording reverse proxy: forward every request to one upstream and save
+// each request/response pair as a HAR file, for debugging
+//
+//   client ──> proxy (:8005) ──> upstream (:8002)
+//                 │
+//                 └─> recordings/1700000000000-1.har
+//
+// A saved exchange can be replayed against another host, to compare e.g.
+// staging with production:
+//
+//   curl http://127.0.0.1:8005/_proxy/recordings
+//   curl -X POST 'http://127.0.0.1:8005/_proxy/recordings/<id>/replay?target=127.0.0.1:8003'
+//
+// Paths under /_proxy/ are answered by the proxy itself and never forwarded.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::har::{self, Body, Entry, Har, HarRequest, HarResponse, Timings};
+use super::request::Request;
+use super::response::{Response, reason_phrase};
+use super::router::Router;
+use super::server::HttpServer;
+
+const ADMIN_PREFIX: &str = "/_proxy/";
+const UPSTREAM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Hop-by-hop headers describe one connection, not the message, so a
+/// proxy must not pass them on (RFC 9110 7.6.1). Content-Length is
+/// recomputed for the body we actually send.
+const HOP_BY_HOP: [&str; 9] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "content-length",
+];
+
+fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP.iter().any(|h| h.eq_ignore_ascii_case(name))
+}
+
+// ============================================================================
+// Talking to the upstream
+// ============================================================================
+
+/// A request to send upstream.
+pub struct Outgoing {
+    pub method: String,
+    /// Path and query, e.g. `/users/42?fields=name`
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// What came back, with how long each phase took.
+pub struct Exchange {
+    pub status: u16,
+    pub reason: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub timings: Timings,
+}
+
+impl Outgoing {
+    pub fn from_request(request: &Request) -> Self {
+        let target = match request.query() {
+            Some(query) => format!("{}?{}", request.path(), query),
+            None => request.path().to_string(),
+        };
+        Outgoing {
+            method: request.method().to_string(),
+            target,
+            headers: request.headers().to_vec(),
+            body: request.body().to_vec(),
+        }
+    }
+
+    /// The request as it goes on the wire, with `Host: host`.
+    ///
+    /// Hop-by-hop headers are dropped and `Connection: close` added: one
+    /// request per connection, so the response ends when the socket does.
+    pub fn encode(&self, host: &str) -> Vec<u8> {
+        let mut head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\n",
+            self.method, self.target, host
+        );
+        for (name, value) in &self.headers {
+            if !is_hop_by_hop(name) && !name.eq_ignore_ascii_case("host") {
+                head.push_str(&format!("{name}: {value}\r\n"));
+            }
+        }
+        head.push_str(&format!(
+            "Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        ));
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+
+    /// Send to `host` (`ip:port`) on a fresh connection and read the whole
+    /// response.
+    pub fn send(&self, host: &str) -> io::Result<Exchange> {
+        let mut stream = TcpStream::connect(host)?;
+        stream.set_read_timeout(Some(UPSTREAM_TIMEOUT))?;
+
+        let started = Instant::now();
+        stream.write_all(&self.encode(host))?;
+        let sent = Instant::now();
+
+        let mut raw = Vec::new();
+        let mut chunk = [0u8; 8192];
+        let n = stream.read(&mut chunk)?;
+        let first_byte = Instant::now();
+        raw.extend_from_slice(&chunk[..n]);
+        stream.read_to_end(&mut raw)?;
+        let done = Instant::now();
+
+        parse_response(
+            &raw,
+            Timings {
+                send: millis(sent - started),
+                wait: millis(first_byte - sent),
+                receive: millis(done - first_byte),
+            },
+        )
+    }
+}
+
+impl Exchange {
+    /// The response to pass on to the client, minus hop-by-hop headers.
+    pub fn into_response(self) -> Response {
+        self.headers
+            .iter()
+            .filter(|(name, _)| !is_hop_by_hop(name))
+            .fold(Response::new(self.status), |response, (name, value)| {
+                response.header(name.clone(), value.clone())
+            })
+            .body(self.body)
+    }
+}
+
+/// Parse a complete response, as read from a connection the upstream
+/// closed; chunked bodies are decoded.
+pub fn parse_response(raw: &[u8], timings: Timings) -> io::Result<Exchange> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+    let mut headers = [httparse::EMPTY_HEADER; 64];
+    let mut res = httparse::Response::new(&mut headers);
+    let offset = match res.parse(raw) {
+        Ok(httparse::Status::Complete(offset)) => offset,
+        Ok(httparse::Status::Partial) => return Err(invalid("truncated response head".into())),
+        Err(e) => return Err(invalid(e.to_string())),
+    };
+
+    let headers: Vec<(String, String)> = res
+        .headers
+        .iter()
+        .map(|h| {
+            (
+                h.name.to_string(),
+                String::from_utf8_lossy(h.value).into_owned(),
+            )
+        })
+        .collect();
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    };
+
+    let rest = &raw[offset..];
+    let body = if header("transfer-encoding").is_some_and(|v| v.contains("chunked")) {
+        dechunk(rest).ok_or_else(|| invalid("bad chunked body".into()))?
+    } else if let Some(length) = header("content-length").and_then(|v| v.trim().parse().ok()) {
+        rest[..rest.len().min(length)].to_vec()
+    } else {
+        rest.to_vec()
+    };
+
+    Ok(Exchange {
+        status: res.code.unwrap_or(502),
+        reason: res.reason.unwrap_or("").to_string(),
+        headers,
+        body,
+        timings,
+    })
+}
+
+/// Undo `Transfer-Encoding: chunked` (the framing ChunkedWriter produces).
+fn dechunk(mut data: &[u8]) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let line_end = data.windows(2).position(|w| w == b"\r\n")?;
+        let size_line = std::str::from_utf8(&data[..line_end]).ok()?;
+        // Chunk extensions (`1f;name=value`) are allowed and ignored
+        let size = usize::from_str_radix(size_line.split(';').next()?.trim(), 16).ok()?;
+        data = &data[line_end + 2..];
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(data.get(..size)?);
+        data = data.get(size + 2..)?;
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+/// Writes each exchange to its own `<dir>/<unix millis>-<n>.har`.
+pub struct Recorder {
+    dir: PathBuf,
+    body_limit: usize,
+    count: AtomicU64,
+}
+
+impl Recorder {
+    fn record(&self, upstream: &str, request: &Request, exchange: &Exchange, started: SystemTime) {
+        let entry = Entry {
+            started_date_time: humantime::format_rfc3339_millis(started).to_string(),
+            time: exchange.timings.send + exchange.timings.wait + exchange.timings.receive,
+            request: har_request(upstream, request, self.body_limit),
+            response: har_response(exchange, self.body_limit),
+            timings: exchange.timings.clone(),
+            server_address: upstream.to_string(),
+        };
+
+        let n = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        let millis = started
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = self.dir.join(format!("{millis}-{n}.har"));
+
+        let json = serde_json::to_vec_pretty(&Har::single(entry)).expect("HAR always serializes");
+        match fs::write(&path, json) {
+            Ok(()) => println!("[PROXY] recorded {}", path.display()),
+            Err(e) => eprintln!("[PROXY] can't write {}: {}", path.display(), e),
+        }
+    }
+
+    fn path_of(&self, id: &str) -> Option<PathBuf> {
+        // Ids are file stems; refuse anything that could leave the directory
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return None;
+        }
+        Some(self.dir.join(format!("{id}.har")))
+    }
+
+    fn ids(&self) -> io::Result<Vec<String>> {
+        let mut ids: Vec<String> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                (path.extension()? == "har")
+                    .then(|| path.file_stem()?.to_str().map(str::to_string))?
+            })
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+}
+
+fn har_request(upstream: &str, request: &Request, limit: usize) -> HarRequest {
+    let host = request.header("host").unwrap_or(upstream);
+    let url = match request.query() {
+        Some(query) => format!("http://{}{}?{}", host, request.path(), query),
+        None => format!("http://{}{}", host, request.path()),
+    };
+    let mime_type = request.header("content-type").unwrap_or("");
+
+    HarRequest {
+        method: request.method().to_string(),
+        url,
+        http_version: "HTTP/1.1".to_string(),
+        headers: har::name_values(request.headers()),
+        query_string: har::name_values(&request.query_pairs()),
+        post_data: (!request.body().is_empty())
+            .then(|| Body::capture(request.body(), mime_type, limit)),
+        headers_size: -1,
+        body_size: request.body().len() as i64,
+    }
+}
+
+fn har_response(exchange: &Exchange, limit: usize) -> HarResponse {
+    let header = |name: &str| {
+        exchange
+            .headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    };
+
+    HarResponse {
+        status: exchange.status,
+        status_text: exchange.reason.clone(),
+        http_version: "HTTP/1.1".to_string(),
+        headers: har::name_values(&exchange.headers),
+        content: Body::capture(&exchange.body, header("content-type").unwrap_or(""), limit),
+        redirect_url: header("location").unwrap_or("").to_string(),
+        headers_size: -1,
+        body_size: exchange.body.len() as i64,
+    }
+}
+
+// ============================================================================
+// Replay
+// ============================================================================
+
+/// Send a recorded request to `target` and compare the answer with the
+/// recorded one.
+pub fn replay(har_path: &Path, target: &str) -> Result<serde_json::Value, String> {
+    let text = fs::read_to_string(har_path).map_err(|e| format!("can't read recording: {e}"))?;
+    let har: Har = serde_json::from_str(&text).map_err(|e| format!("not a HAR file: {e}"))?;
+    let entry = har.log.entries.first().ok_or("recording has no entries")?;
+    let recorded = &entry.request;
+
+    let body = match &recorded.post_data {
+        Some(data) if data.truncated => {
+            return Err("request body was truncated when recorded; can't replay it".to_string());
+        }
+        Some(data) => data.bytes().ok_or("request body has an unknown encoding")?,
+        None => Vec::new(),
+    };
+
+    // Keep the path and query of the recorded URL, drop its host
+    let after_scheme = recorded
+        .url
+        .split_once("://")
+        .map_or(recorded.url.as_str(), |(_, rest)| rest);
+    let target_path = after_scheme
+        .find('/')
+        .map_or("/", |i| &after_scheme[i..])
+        .to_string();
+
+    let outgoing = Outgoing {
+        method: recorded.method.clone(),
+        target: target_path,
+        headers: recorded
+            .headers
+            .iter()
+            .map(|h| (h.name.clone(), h.value.clone()))
+            .collect(),
+        body,
+    };
+    let exchange = outgoing
+        .send(target)
+        .map_err(|e| format!("{target}: {e}"))?;
+
+    let recorded_body = &entry.response.content;
+    let body_matches =
+        !recorded_body.truncated && recorded_body.bytes().is_some_and(|b| b == exchange.body);
+
+    Ok(serde_json::json!({
+        "target": target,
+        "request": format!("{} {}", outgoing.method, outgoing.target),
+        "recorded": { "status": entry.response.status, "size": recorded_body.size },
+        "replayed": {
+            "status": exchange.status,
+            "size": exchange.body.len(),
+            "time": exchange.timings.send + exchange.timings.wait + exchange.timings.receive,
+        },
+        "status_matches": entry.response.status == exchange.status,
+        "body_matches": body_matches,
+    }))
+}
+
+// ============================================================================
+// The proxy server
+// ============================================================================
+
+pub struct RecordingProxy {
+    upstream: String,
+    recorder: Option<Recorder>,
+}
+
+impl RecordingProxy {
+    /// Forward to `upstream` (`ip:port`), recording nothing yet.
+    pub fn new(upstream: impl Into<String>) -> Self {
+        RecordingProxy {
+            upstream: upstream.into(),
+            recorder: None,
+        }
+    }
+
+    /// Save every exchange under `dir`, with bodies cut at `body_limit`
+    /// bytes.
+    pub fn record_to(mut self, dir: impl Into<PathBuf>, body_limit: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        self.recorder = Some(Recorder {
+            dir,
+            body_limit,
+            count: AtomicU64::new(0),
+        });
+        Ok(self)
+    }
+
+    /// Serve the proxy on `addr`.
+    pub fn into_server(self, addr: &str) -> io::Result<HttpServer> {
+        let proxy = Arc::new(self);
+        let admin = admin_router(Arc::clone(&proxy));
+
+        HttpServer::builder()
+            .addr(addr)
+            .handler(move |request| {
+                if request.path().starts_with(ADMIN_PREFIX) {
+                    admin.dispatch(request)
+                } else {
+                    proxy.forward(request)
+                }
+            })
+            .build()
+    }
+
+    fn forward(&self, request: Request) -> Response {
+        let started = SystemTime::now();
+        let exchange = match Outgoing::from_request(&request).send(&self.upstream) {
+            Ok(exchange) => exchange,
+            Err(e) => {
+                eprintln!("[PROXY] upstream {} failed: {}", self.upstream, e);
+                return Response::text(502, format!("upstream {}: {}", self.upstream, e));
+            }
+        };
+
+        println!(
+            "[PROXY] {} {} -> {} {} ({:.1} ms)",
+            request.method(),
+            request.path(),
+            exchange.status,
+            reason_phrase(exchange.status),
+            exchange.timings.send + exchange.timings.wait + exchange.timings.receive
+        );
+        if let Some(recorder) = &self.recorder {
+            recorder.record(&self.upstream, &request, &exchange, started);
+        }
+
+        exchange.into_response()
+    }
+}
+
+fn admin_router(proxy: Arc<RecordingProxy>) -> Router {
+    let listing = Arc::clone(&proxy);
+    Router::new()
+        .get("/_proxy/recordings", move |_| {
+            let Some(recorder) = &listing.recorder else {
+                return Response::text(404, "recording is off");
+            };
+            match recorder.ids() {
+                Ok(ids) => Response::json(200, &serde_json::json!({ "recordings": ids })),
+                Err(e) => Response::text(500, e.to_string()),
+            }
+        })
+        .post("/_proxy/recordings/{id}/replay", move |req| {
+            let Some(recorder) = &proxy.recorder else {
+                return Response::text(404, "recording is off");
+            };
+            let Some(path) = req.param("id").and_then(|id| recorder.path_of(id)) else {
+                return Response::text(400, "bad recording id");
+            };
+            if !path.exists() {
+                return Response::text(404, "no such recording");
+            }
+            let target = req
+                .query_param("target")
+                .unwrap_or_else(|| proxy.upstream.clone());
+
+            match replay(&path, &target) {
+                Ok(report) => Response::json(200, &report),
+                Err(e) => Response::text(502, e),
+            }
+        })
+}
+
+pub fn run_main() -> io::Result<()> {
+    println!("=== RECORDING PROXY ===\n");
+    println!("Start the upstream first (server::run_main on :8002), then:");
+    println!("  curl http://127.0.0.1:8005/users/42");
+    println!("  curl http://127.0.0.1:8005/_proxy/recordings\n");
+
+    RecordingProxy::new("127.0.0.1:8002")
+        .record_to("recordings", 64 * 1024)?
+        .into_server("127.0.0.1:8005")?
+        .run()
+}
@@ -0,0 +1,315 @@
+// Reading an HTTP/1.1 request off a TCP stream
+//
+// A single read() is not a request: the head may arrive in pieces, and the
+// body follows it by Content-Length bytes. So we keep reading until httparse
+// says the head is complete, then read exactly the body.
+
+use std::fmt;
+use std::io::{self, Read};
+
+/// Largest request head (request line + headers) we accept.
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+const MAX_HEADERS: usize = 64;
+
+/// A parsed HTTP request with its body fully read.
+#[derive(Debug, Clone)]
+pub struct Request {
+    method: String,
+    path: String,
+    query: Option<String>,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    /// Filled in by the router from `{name}` segments
+    params: Vec<(String, String)>,
+}
+
+impl Request {
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Path without the query string.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Raw query string, without the `?`.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Decoded `key=value` pairs of the query string, in order.
+    ///
+    /// `?tag=a&tag=b` gives two pairs; a key without `=` gets an empty value.
+    pub fn query_pairs(&self) -> Vec<(String, String)> {
+        let Some(query) = &self.query else {
+            return Vec::new();
+        };
+        query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode_query_component(key), decode_query_component(value))
+            })
+            .collect()
+    }
+
+    /// First decoded value of a query parameter.
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        self.query_pairs()
+            .into_iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// A path parameter captured by the router, e.g. `id` in `/users/{id}`.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+
+    /// First value of a header; names are case-insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    pub(crate) fn set_body(&mut self, body: Vec<u8>) {
+        self.body = body;
+    }
+
+    pub(crate) fn remove_header(&mut self, name: &str) {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+    }
+
+    pub(crate) fn set_params(&mut self, params: Vec<(String, String)>) {
+        self.params = params;
+    }
+}
+
+/// Decode `%XX` escapes. Invalid escapes are kept as they are, and bytes
+/// that don't form UTF-8 are replaced.
+pub fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = |offset: usize| {
+            bytes
+                .get(i + offset)
+                .and_then(|&b| (b as char).to_digit(16))
+        };
+        if bytes[i] == b'%'
+            && let (Some(hi), Some(lo)) = (hex(1), hex(2))
+        {
+            out.push((hi * 16 + lo) as u8);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Query strings (application/x-www-form-urlencoded) also use `+` for space.
+fn decode_query_component(input: &str) -> String {
+    percent_decode(&input.replace('+', " "))
+}
+
+#[derive(Debug)]
+pub enum RequestError {
+    Io(io::Error),
+    /// Not valid HTTP -> 400
+    Malformed(String),
+    /// Head (bytes or header count) or body over the limit -> 431 / 413
+    HeadTooLarge,
+    BodyTooLarge,
+    /// Chunked request bodies aren't supported here -> 501
+    UnsupportedTransferEncoding,
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::Io(e) => write!(f, "i/o error: {e}"),
+            RequestError::Malformed(reason) => write!(f, "malformed request: {reason}"),
+            RequestError::HeadTooLarge => write!(f, "request head too large"),
+            RequestError::BodyTooLarge => write!(f, "request body too large"),
+            RequestError::UnsupportedTransferEncoding => {
+                write!(f, "request Transfer-Encoding not supported")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+impl RequestError {
+    /// Status to answer with when the request couldn't be read.
+    pub fn status(&self) -> u16 {
+        match self {
+            RequestError::HeadTooLarge => 431,
+            RequestError::BodyTooLarge => 413,
+            RequestError::UnsupportedTransferEncoding => 501,
+            RequestError::Io(_) | RequestError::Malformed(_) => 400,
+        }
+    }
+}
+
+impl From<io::Error> for RequestError {
+    fn from(e: io::Error) -> Self {
+        RequestError::Io(e)
+    }
+}
+
+/// Read one request from `stream`.
+///
+/// Returns `Ok(None)` if the peer closed the connection before sending
+/// anything.
+pub fn read_request<R: Read>(
+    stream: &mut R,
+    max_body_size: usize,
+) -> Result<Option<Request>, RequestError> {
+    let mut buf = Vec::with_capacity(4096);
+    let mut chunk = [0u8; 4096];
+
+    let (mut request, body_offset) = loop {
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            return Err(RequestError::Malformed(
+                "connection closed mid-request".into(),
+            ));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+
+        if let Some(parsed) = parse_head(&buf)? {
+            break parsed;
+        }
+        if buf.len() > MAX_HEAD_SIZE {
+            return Err(RequestError::HeadTooLarge);
+        }
+    };
+
+    let content_length = body_length(&request, max_body_size)?;
+
+    // Part of the body may have come in with the head
+    let mut body = buf.split_off(body_offset);
+    body.truncate(content_length);
+    if body.len() < content_length {
+        let already = body.len();
+        body.resize(content_length, 0);
+        stream.read_exact(&mut body[already..])?;
+    }
+    request.body = body;
+
+    Ok(Some(request))
+}
+
+/// Parse one request out of `buf` without reading anything.
+///
+/// For non-blocking servers that collect bytes as they become readable:
+/// returns `Ok(None)` until the head and the whole body are in `buf`, then
+/// the request and how many bytes of `buf` it used (anything after that is
+/// the next, pipelined request).
+pub fn parse_request(
+    buf: &[u8],
+    max_body_size: usize,
+) -> Result<Option<(Request, usize)>, RequestError> {
+    let Some((mut request, body_offset)) = parse_head(buf)? else {
+        if buf.len() > MAX_HEAD_SIZE {
+            return Err(RequestError::HeadTooLarge);
+        }
+        return Ok(None);
+    };
+
+    let content_length = body_length(&request, max_body_size)?;
+    let end = body_offset + content_length;
+    if buf.len() < end {
+        return Ok(None);
+    }
+    request.body = buf[body_offset..end].to_vec();
+
+    Ok(Some((request, end)))
+}
+
+/// Body size announced by Content-Length, checked against the limit.
+fn body_length(request: &Request, max_body_size: usize) -> Result<usize, RequestError> {
+    if request.header("transfer-encoding").is_some() {
+        return Err(RequestError::UnsupportedTransferEncoding);
+    }
+
+    let content_length = match request.header("content-length") {
+        Some(value) => value
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| RequestError::Malformed(format!("bad Content-Length '{value}'")))?,
+        None => 0,
+    };
+    if content_length > max_body_size {
+        return Err(RequestError::BodyTooLarge);
+    }
+    Ok(content_length)
+}
+
+fn parse_head(buf: &[u8]) -> Result<Option<(Request, usize)>, RequestError> {
+    let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+    let mut req = httparse::Request::new(&mut headers);
+
+    let offset = match req.parse(buf) {
+        Ok(httparse::Status::Complete(offset)) => offset,
+        Ok(httparse::Status::Partial) => return Ok(None),
+        // Too many header fields is the same problem as too many bytes
+        // (RFC 6585 5), not a malformed request
+        Err(httparse::Error::TooManyHeaders) => return Err(RequestError::HeadTooLarge),
+        Err(e) => return Err(RequestError::Malformed(e.to_string())),
+    };
+
+    let target = req.path.unwrap_or("/");
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query.to_string())),
+        None => (target, None),
+    };
+
+    let request = Request {
+        method: req.method.unwrap_or("GET").to_string(),
+        path: path.to_string(),
+        query,
+        headers: req
+            .headers
+            .iter()
+            .map(|h| {
+                (
+                    h.name.to_string(),
+                    String::from_utf8_lossy(h.value).into_owned(),
+                )
+            })
+            .collect(),
+        body: Vec::new(),
+        params: Vec::new(),
+    };
+
+    Ok(Some((request, offset)))
+}
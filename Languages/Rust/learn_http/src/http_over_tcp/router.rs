@@ -0,0 +1,160 @@
+// Routing: method + path pattern -> handler
+//
+// Instead of one big `match (method, path)` like http_server.rs, routes are
+// registered up front:
+//
+//   let router = Router::new()
+//       .get("/", |_req| Response::html(200, "<h1>home</h1>"))
+//       .get("/users/{id}", |req| Response::text(200, format!("user {}", req.param("id").unwrap())))
+//       .post("/users", create_user);
+//
+// A pattern is split on `/`; literal segments must match exactly and
+// `{name}` segments match any one non-empty segment, captured as a param.
+// Routes are tried in registration order and the first match wins, so
+// register `/users/me` before `/users/{id}`.
+
+use super::request::{Request, percent_decode};
+use super::response::Response;
+
+/// A route handler: takes the request (params filled in) and builds the
+/// response.
+pub type RouteHandler = Box<dyn Fn(Request) -> Response + Send + Sync>;
+
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+impl Segment {
+    fn parse(segment: &str) -> Segment {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) => Segment::Param(name.to_string()),
+            None => Segment::Literal(segment.to_string()),
+        }
+    }
+}
+
+struct Route {
+    method: String,
+    segments: Vec<Segment>,
+    handler: RouteHandler,
+}
+
+impl Route {
+    /// The captured params if `path` matches this route's pattern.
+    fn matches(&self, path: &[&str]) -> Option<Vec<(String, String)>> {
+        if path.len() != self.segments.len() {
+            return None;
+        }
+
+        let mut params = Vec::new();
+        for (segment, part) in self.segments.iter().zip(path) {
+            match segment {
+                Segment::Literal(literal) if literal == part => {}
+                Segment::Param(name) if !part.is_empty() => {
+                    params.push((name.clone(), percent_decode(part)));
+                }
+                _ => return None,
+            }
+        }
+        Some(params)
+    }
+}
+
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Router::default()
+    }
+
+    /// Register `handler` for `method` requests matching `pattern`.
+    pub fn route<F>(mut self, method: &str, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Response + Send + Sync + 'static,
+    {
+        let segments = split_path(pattern)
+            .into_iter()
+            .map(Segment::parse)
+            .collect();
+
+        self.routes.push(Route {
+            method: method.to_ascii_uppercase(),
+            segments,
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    pub fn get<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Response + Send + Sync + 'static,
+    {
+        self.route("GET", pattern, handler)
+    }
+
+    pub fn post<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Response + Send + Sync + 'static,
+    {
+        self.route("POST", pattern, handler)
+    }
+
+    pub fn put<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Response + Send + Sync + 'static,
+    {
+        self.route("PUT", pattern, handler)
+    }
+
+    pub fn delete<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Response + Send + Sync + 'static,
+    {
+        self.route("DELETE", pattern, handler)
+    }
+
+    /// Run the first route matching the request.
+    ///
+    /// No route for the path -> 404. Routes for the path but not the
+    /// method -> 405 with an `Allow` header. HEAD falls back to GET routes;
+    /// the server drops the body.
+    pub fn dispatch(&self, mut request: Request) -> Response {
+        let path = split_path(request.path());
+        let mut allowed: Vec<&str> = Vec::new();
+
+        for route in &self.routes {
+            let Some(params) = route.matches(&path) else {
+                continue;
+            };
+
+            let method = request.method();
+            if route.method == method || (method == "HEAD" && route.method == "GET") {
+                request.set_params(params);
+                return (route.handler)(request);
+            }
+            if !allowed.contains(&route.method.as_str()) {
+                allowed.push(&route.method);
+            }
+        }
+
+        if allowed.is_empty() {
+            Response::text(404, format!("no route for {}", request.path()))
+        } else {
+            Response::text(405, "Method Not Allowed").header("Allow", allowed.join(", "))
+        }
+    }
+}
+
+/// `/users/42/` -> ["users", "42"]; `/` -> []
+fn split_path(path: &str) -> Vec<&str> {
+    let trimmed = path.trim_matches('/');
+    if trimmed.is_empty() {
+        Vec::new()
+    } else {
+        trimmed.split('/').collect()
+    }
+}
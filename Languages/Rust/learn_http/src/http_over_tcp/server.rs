@@ -0,0 +1,540 @@
+// A small thread-per-connection HTTP/1.1 server on a raw TcpListener
+//
+// Same idea as http_server.rs, but with the pieces split out so they can be
+// reused: requests are parsed with httparse (request.rs), dispatched by a
+// Router (router.rs) or a plain handler, answered with a Response
+// (response.rs), and the server takes care of framing and content coding
+// (compression.rs).
+//
+//   let server = HttpServer::builder()
+//       .addr("127.0.0.1:8002")
+//       .compression(true)
+//       .router(Router::new().get("/hello/{name}", |req| {
+//           Response::text(200, format!("hi {}", req.param("name").unwrap()))
+//       }))
+//       .build()?;
+//   server.run()?;
+//
+// Every answered request can go to an access log (access_log.rs):
+//
+//   .access_log(StdoutSink::new(LogFormat::Common))
+//
+// run() blocks until a ShutdownHandle (server.shutdown_handle(), taken
+// before run) asks it to stop; it then drains the open connections.
+
+use std::collections::HashMap;
+use std::io::{self, BufWriter, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use tokio::sync::watch;
+
+use crate::shutdown::{DEFAULT_DRAIN_DEADLINE, ShutdownHandle};
+
+use super::access_log::{AccessLogEntry, AccessLogSink, LogFormat, StdoutSink};
+use super::compression::{self, DecodeError, Encoding};
+use super::request::{self, Request, RequestError};
+use super::response::Response;
+use super::router::Router;
+
+/// Turns a request into a response. Shared by every connection thread.
+pub type Handler = Arc<dyn Fn(Request) -> Response + Send + Sync>;
+
+/// Settings shared by every connection.
+#[derive(Debug, Clone)]
+struct Config {
+    compression: bool,
+    min_compress_size: usize,
+    max_body_size: usize,
+}
+
+pub struct HttpServer {
+    listener: TcpListener,
+    handler: Handler,
+    config: Config,
+    access_log: Option<Arc<dyn AccessLogSink>>,
+    shutdown: Arc<watch::Sender<bool>>,
+    drain_deadline: Duration,
+}
+
+pub struct HttpServerBuilder {
+    addr: String,
+    handler: Option<Handler>,
+    config: Config,
+    access_log: Option<Arc<dyn AccessLogSink>>,
+    drain_deadline: Duration,
+}
+
+impl HttpServer {
+    pub fn builder() -> HttpServerBuilder {
+        HttpServerBuilder {
+            addr: "127.0.0.1:8002".to_string(),
+            handler: None,
+            config: Config {
+                compression: false,
+                min_compress_size: 1024,
+                max_body_size: 1024 * 1024,
+            },
+            access_log: None,
+            drain_deadline: DEFAULT_DRAIN_DEADLINE,
+        }
+    }
+
+    /// Address the listener is bound to (useful with port 0).
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// A handle that stops `run` (take it before calling run).
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle::new(Arc::clone(&self.shutdown), self.local_addr().ok())
+    }
+
+    /// Accept connections, one thread each, until shutdown. Then stop
+    /// accepting, wait up to the drain deadline for the open connections
+    /// to finish, and cut off the rest.
+    pub fn run(self) -> io::Result<()> {
+        let addr = self.local_addr()?;
+        println!("[HTTP] listening on http://{addr}");
+
+        let connections = Arc::new(Connections::default());
+
+        for (id, stream) in self.listener.incoming().enumerate() {
+            // shutdown() wakes us up with a connection of its own
+            if *self.shutdown.borrow() {
+                break;
+            }
+
+            let stream = stream?;
+            let handler = Arc::clone(&self.handler);
+            let config = self.config.clone();
+            let access_log = self.access_log.clone();
+
+            connections.open(id, &stream);
+            let connections = Arc::clone(&connections);
+
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, &handler, &config, access_log.as_deref())
+                {
+                    eprintln!("[HTTP] connection error: {e}");
+                }
+                connections.close(id);
+            });
+        }
+
+        // New clients get "connection refused" from here on. Every response
+        // already says `Connection: close`, so a connection mid-request
+        // finishes it and goes away
+        drop(self.listener);
+        println!(
+            "[HTTP] shutting down {addr}, draining {} connection(s)",
+            connections.count()
+        );
+
+        let aborted = connections.drain(self.drain_deadline);
+        if aborted > 0 {
+            println!(
+                "[HTTP] aborted {aborted} connection(s) still open after {:?}",
+                self.drain_deadline
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The open connections, so shutdown can wait for them and cut off the
+/// ones that take too long.
+#[derive(Default)]
+struct Connections {
+    // A clone of each socket: shutting it down fails the connection
+    // thread's pending read or write
+    open: Mutex<HashMap<usize, Option<TcpStream>>>,
+    closed: Condvar,
+}
+
+impl Connections {
+    fn open(&self, id: usize, stream: &TcpStream) {
+        self.open
+            .lock()
+            .unwrap()
+            .insert(id, stream.try_clone().ok());
+    }
+
+    fn close(&self, id: usize) {
+        self.open.lock().unwrap().remove(&id);
+        self.closed.notify_all();
+    }
+
+    fn count(&self) -> usize {
+        self.open.lock().unwrap().len()
+    }
+
+    /// Wait up to `deadline` for every connection to close, then shut down
+    /// the sockets of the others. Returns how many were cut off.
+    fn drain(&self, deadline: Duration) -> usize {
+        let open = self.open.lock().unwrap();
+        let (open, _) = self
+            .closed
+            .wait_timeout_while(open, deadline, |open| !open.is_empty())
+            .unwrap();
+
+        for stream in open.values().flatten() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        open.len()
+    }
+}
+
+impl HttpServerBuilder {
+    pub fn addr(mut self, addr: impl Into<String>) -> Self {
+        self.addr = addr.into();
+        self
+    }
+
+    pub fn handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(Request) -> Response + Send + Sync + 'static,
+    {
+        self.handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Dispatch requests through `router` (replaces any handler).
+    pub fn router(self, router: Router) -> Self {
+        self.handler(move |request| router.dispatch(request))
+    }
+
+    /// Gzip/deflate responses the client accepts, and decode compressed
+    /// request bodies. Off by default.
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.config.compression = enabled;
+        self
+    }
+
+    /// Smallest response body worth compressing, in bytes (default 1 KiB).
+    pub fn min_compress_size(mut self, bytes: usize) -> Self {
+        self.config.min_compress_size = bytes;
+        self
+    }
+
+    /// Largest request body, before and after decompression (default 1 MiB).
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.config.max_body_size = bytes;
+        self
+    }
+
+    /// Log every answered request to `sink`. Off by default.
+    pub fn access_log(mut self, sink: impl AccessLogSink + 'static) -> Self {
+        self.access_log = Some(Arc::new(sink));
+        self
+    }
+
+    /// How long open connections get to finish once shutdown starts
+    /// (default 5s).
+    pub fn drain_deadline(mut self, deadline: Duration) -> Self {
+        self.drain_deadline = deadline;
+        self
+    }
+
+    /// Bind the listener.
+    pub fn build(self) -> io::Result<HttpServer> {
+        let listener = TcpListener::bind(&self.addr)?;
+        let handler = self
+            .handler
+            .unwrap_or_else(|| Arc::new(|_: Request| Response::text(404, "Not Found")));
+
+        Ok(HttpServer {
+            listener,
+            handler,
+            config: self.config,
+            access_log: self.access_log,
+            shutdown: Arc::new(watch::channel(false).0),
+            drain_deadline: self.drain_deadline,
+        })
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    handler: &Handler,
+    config: &Config,
+    access_log: Option<&dyn AccessLogSink>,
+) -> io::Result<()> {
+    let started = Instant::now();
+    let mut entry = AccessLogEntry {
+        remote_addr: stream.peer_addr().ok(),
+        time: SystemTime::now(),
+        method: None,
+        path: None,
+        status: 0,
+        bytes: 0,
+        duration: Duration::ZERO,
+    };
+
+    let answered = serve(&mut stream, handler, config, &mut entry)?;
+
+    // A client that closed without sending a request got no answer to log
+    if answered && let Some(sink) = access_log {
+        entry.duration = started.elapsed();
+        sink.log(&entry);
+    }
+    Ok(())
+}
+
+/// Read one request and answer it, noting in `entry` what was asked and
+/// sent. Returns whether a response was sent.
+fn serve(
+    stream: &mut TcpStream,
+    handler: &Handler,
+    config: &Config,
+    entry: &mut AccessLogEntry,
+) -> io::Result<bool> {
+    let mut request = match request::read_request(&mut *stream, config.max_body_size) {
+        Ok(Some(request)) => request,
+        Ok(None) => return Ok(false),
+        Err(RequestError::Io(e)) => return Err(e),
+        Err(e) => {
+            let response = Response::text(e.status(), e.to_string());
+            respond(stream, response, None, false, entry)?;
+            // The rest of the request may still be on its way
+            lingering_close(stream);
+            return Ok(true);
+        }
+    };
+
+    // The handler takes the request, so keep what we need afterwards
+    let method = request.method().to_string();
+    let path = request.path().to_string();
+    entry.method = Some(method.clone());
+    entry.path = Some(match request.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.clone(),
+    });
+
+    if config.compression
+        && let Err(response) = decode_request_body(&mut request, config.max_body_size)
+    {
+        respond(stream, response, None, false, entry)?;
+        return Ok(true);
+    }
+
+    let accept_encoding = request.header("accept-encoding").map(str::to_string);
+
+    let response = handler(request);
+    let encoding = response_encoding(accept_encoding.as_deref(), &response, config);
+
+    println!(
+        "[HTTP] {} {} -> {} ({})",
+        method,
+        path,
+        response.status(),
+        encoding.map_or("identity", Encoding::as_str)
+    );
+
+    let head_only = method == "HEAD";
+    let response = if config.compression {
+        response.header("Vary", "Accept-Encoding")
+    } else {
+        response
+    };
+    respond(stream, response, encoding, head_only, entry)?;
+    Ok(true)
+}
+
+/// Write the response and note its status and size in `entry`.
+fn respond(
+    stream: &mut TcpStream,
+    response: Response,
+    encoding: Option<Encoding>,
+    head_only: bool,
+    entry: &mut AccessLogEntry,
+) -> io::Result<()> {
+    entry.status = response.status();
+    entry.bytes = write_response(stream, response, encoding, head_only)?;
+    Ok(())
+}
+
+/// Close a connection whose request wasn't read to the end.
+///
+/// Closing a socket with unread input makes the kernel send a RST, and a
+/// client that gets it before reading our response loses the response too.
+/// So stop writing first, then read and throw away what the client is still
+/// sending, for a little while.
+fn lingering_close(stream: &mut TcpStream) {
+    const LINGER: Duration = Duration::from_secs(2);
+    const MAX_DISCARD: usize = 1024 * 1024;
+
+    if stream.shutdown(Shutdown::Write).is_err() {
+        return;
+    }
+    let _ = stream.set_read_timeout(Some(LINGER));
+
+    let started = Instant::now();
+    let mut discarded = 0;
+    let mut buf = [0u8; 4096];
+    while started.elapsed() < LINGER && discarded < MAX_DISCARD {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => discarded += n,
+        }
+    }
+}
+
+/// Replace a compressed request body with the decoded one, or return the
+/// error response to send instead.
+fn decode_request_body(request: &mut Request, limit: usize) -> Result<(), Response> {
+    let Some(coding) = request.header("content-encoding").map(str::to_string) else {
+        return Ok(());
+    };
+
+    match compression::decode_body(&coding, request.body().to_vec(), limit) {
+        Ok(body) => {
+            request.set_body(body);
+            // The handler sees a plain body, so drop the headers describing
+            // the encoded one
+            request.remove_header("content-encoding");
+            request.remove_header("content-length");
+            Ok(())
+        }
+        Err(e @ DecodeError::Unsupported(_)) => Err(Response::text(415, e.to_string())
+            // RFC 9110 15.5.16: tell the client what we do accept
+            .header("Accept-Encoding", "gzip, deflate")),
+        Err(e @ DecodeError::Corrupt(_)) => Err(Response::text(400, e.to_string())),
+        Err(e @ DecodeError::TooLarge) => Err(Response::text(413, e.to_string())),
+    }
+}
+
+/// The coding to send `response` with, if any.
+fn response_encoding(
+    accept_encoding: Option<&str>,
+    response: &Response,
+    config: &Config,
+) -> Option<Encoding> {
+    if !config.compression
+        || response.body_bytes().len() < config.min_compress_size
+        // The handler already encoded the body itself
+        || response.get_header("content-encoding").is_some()
+        || matches!(response.status(), 100..=199 | 204 | 304)
+        || !compression::is_compressible(response.get_header("content-type"))
+    {
+        return None;
+    }
+
+    compression::negotiate(accept_encoding?)
+}
+
+/// Returns the number of body bytes sent.
+fn write_response(
+    stream: &mut TcpStream,
+    response: Response,
+    encoding: Option<Encoding>,
+    head_only: bool,
+) -> io::Result<u64> {
+    let mut out = BufWriter::new(CountingWriter::new(stream));
+    let head_len;
+
+    match encoding {
+        Some(encoding) => {
+            let head = response.head(&[
+                ("Content-Encoding", encoding.to_string()),
+                ("Transfer-Encoding", "chunked".to_string()),
+                ("Connection", "close".to_string()),
+            ]);
+            head_len = head.len() as u64;
+            out.write_all(head.as_bytes())?;
+            if !head_only {
+                out = compression::write_compressed(encoding, response.body_bytes(), out)?;
+            }
+        }
+        None => {
+            let head = response.head(&[
+                ("Content-Length", response.body_bytes().len().to_string()),
+                ("Connection", "close".to_string()),
+            ]);
+            head_len = head.len() as u64;
+            out.write_all(head.as_bytes())?;
+            if !head_only {
+                out.write_all(response.body_bytes())?;
+            }
+        }
+    }
+
+    out.flush()?;
+    Ok(out.get_ref().count - head_len)
+}
+
+/// Counts the bytes that go through it.
+struct CountingWriter<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W: Write> CountingWriter<W> {
+    fn new(inner: W) -> Self {
+        CountingWriter { inner, count: 0 }
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub fn run_main() -> io::Result<()> {
+    println!("=== HTTP SERVER WITH ROUTER AND COMPRESSION ===\n");
+    println!("Try: curl http://127.0.0.1:8002/users/42?fields=name+email");
+    println!("     curl -v --compressed http://127.0.0.1:8002/big");
+    println!(
+        "     gzip -c file | curl --data-binary @- -H 'Content-Encoding: gzip' http://127.0.0.1:8002/echo\n"
+    );
+
+    let router = Router::new()
+        .get("/", |_| Response::html(200, "<h1>Raw HTTP server</h1>"))
+        // Big and repetitive: compresses very well
+        .get("/big", |_| {
+            Response::text(200, "all work and no play\n".repeat(2000))
+        })
+        .get("/users/{id}", |req| {
+            let fields = req.query_param("fields").unwrap_or_default();
+            Response::json(
+                200,
+                &serde_json::json!({ "id": req.param("id"), "fields": fields }),
+            )
+        })
+        .post("/echo", |req| {
+            let content_type = req
+                .header("content-type")
+                .unwrap_or("application/octet-stream")
+                .to_string();
+            Response::new(200)
+                .header("Content-Type", content_type)
+                .body(req.body().to_vec())
+        });
+
+    HttpServer::builder()
+        .addr("127.0.0.1:8002")
+        .compression(true)
+        .access_log(StdoutSink::new(LogFormat::Common))
+        .router(router)
+        .build()?
+        .run()
+}
+
+// WHAT THE SERVER DOES WITH COMPRESSION ON:
+//
+// 1. Request has `Content-Encoding: gzip` -> decode before the handler
+//    (unknown coding -> 415, broken data -> 400, decoded too big -> 413)
+// 2. Handler returns a Response
+// 3. Body big enough, compressible type, client's Accept-Encoding allows it
+//    -> stream it through gzip/deflate with chunked framing
+// 4. Always send `Vary: Accept-Encoding` so caches don't hand a gzipped
+//    response to a client that can't read it
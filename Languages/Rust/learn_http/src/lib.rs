@@ -1,11 +1,17 @@
 pub mod dual_roles;
+#[cfg(feature = "demos")]
+pub mod event_loop;
 pub mod fd;
 pub mod mcs;
 pub mod mesg_bndry;
 pub mod no_partial_reads;
 pub mod server;
+pub mod shutdown;
 // pub mod what_happens;
+#[cfg(feature = "demos")]
 pub mod async_server;
 pub mod http_over_tcp;
+#[cfg(feature = "demos")]
 pub mod hype;
+#[cfg(feature = "demos")]
 pub mod websockets;
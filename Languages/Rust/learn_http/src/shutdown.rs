@@ -0,0 +1,52 @@
+// Graceful shutdown for the raw servers
+//
+// A server hands out ShutdownHandles; any clone can ask it to stop. The
+// server then:
+//
+//   1. stops accepting (the listener is dropped, new clients are refused)
+//   2. tells the connections it has that it's going away
+//      (HTTP: `Connection: close`, WebSocket: a Close frame, code 1001)
+//   3. waits up to its drain deadline for them to finish
+//   4. aborts whatever is still open
+//
+// The signal is a watch channel, so it works for the thread-per-connection
+// server (check the flag) and the tokio ones (await the change) alike.
+
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+/// Default time connections get to finish once shutdown starts.
+pub const DEFAULT_DRAIN_DEADLINE: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    signal: Arc<watch::Sender<bool>>,
+    // A blocking accept() only returns for a new connection: shutdown()
+    // makes one so the accept loop notices
+    wake: Option<SocketAddr>,
+}
+
+impl ShutdownHandle {
+    pub(crate) fn new(signal: Arc<watch::Sender<bool>>, wake: Option<SocketAddr>) -> Self {
+        ShutdownHandle { signal, wake }
+    }
+
+    /// Ask the server to stop. Returns at once; the server's `run` returns
+    /// when the connections are drained (or aborted). Calling it again does
+    /// nothing.
+    pub fn shutdown(&self) {
+        if self.signal.send_replace(true) {
+            return;
+        }
+        if let Some(addr) = self.wake {
+            let _ = TcpStream::connect(addr);
+        }
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        *self.signal.borrow()
+    }
+}
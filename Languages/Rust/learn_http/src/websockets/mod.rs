@@ -1,2 +1,3 @@
+pub mod rooms;
 pub mod ws;
 pub mod ws_client;
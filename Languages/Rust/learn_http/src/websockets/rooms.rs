@@ -0,0 +1,243 @@
+// Pub/sub rooms shared by every WebSocket connection
+//
+// Each connection gets an id and an outbox (an mpsc sender). Its writer
+// task drains the outbox into the socket, so anyone holding the sender can
+// push messages to that client without touching its socket:
+//
+//   client A ──publish──> Rooms ──outbox──> writer task B ──> socket B
+//                              └─outbox──> writer task C ──> socket C
+//
+// Rooms only map room names to member outboxes. The lock is never held
+// across an .await: sending into an unbounded channel doesn't block.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_tungstenite::tungstenite::Message;
+
+pub type ClientId = u64;
+
+/// Control messages a client sends as JSON text frames.
+///
+///   {"type":"join","room":"rust","name":"ana"}
+///   {"type":"publish","room":"rust","text":"hi all"}
+///   {"type":"members","room":"rust"}
+///   {"type":"leave","room":"rust"}
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Join { room: String, name: Option<String> },
+    Leave { room: String },
+    Publish { room: String, text: String },
+    Members { room: String },
+}
+
+/// Messages the server pushes to clients.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// To the joining client, with everyone in the room (itself included)
+    Joined {
+        room: String,
+        members: Vec<String>,
+    },
+    Left {
+        room: String,
+    },
+    /// To the other members
+    MemberJoined {
+        room: String,
+        member: String,
+    },
+    MemberLeft {
+        room: String,
+        member: String,
+    },
+    /// A published message, to every member including the sender
+    Message {
+        room: String,
+        from: String,
+        text: String,
+    },
+    Members {
+        room: String,
+        members: Vec<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl ServerMessage {
+    pub fn to_frame(&self) -> Message {
+        let json = serde_json::to_string(self).expect("server messages always serialize");
+        Message::Text(json.into())
+    }
+}
+
+struct Member {
+    name: String,
+    outbox: UnboundedSender<Message>,
+}
+
+#[derive(Clone, Default)]
+pub struct Rooms {
+    // BTreeMap so member lists come out in join order (ids increase)
+    rooms: Arc<Mutex<HashMap<String, BTreeMap<ClientId, Member>>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Rooms {
+    pub fn new() -> Self {
+        Rooms::default()
+    }
+
+    pub fn next_client_id(&self) -> ClientId {
+        self.next_id.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Handle one control message from `client`.
+    pub fn handle(&self, client: ClientId, outbox: &UnboundedSender<Message>, msg: ClientMessage) {
+        match msg {
+            ClientMessage::Join { room, name } => {
+                let name = name.unwrap_or_else(|| format!("client-{client}"));
+                self.join(client, name, outbox.clone(), &room);
+            }
+            ClientMessage::Leave { room } => {
+                if self.leave(client, &room) {
+                    send(outbox, &ServerMessage::Left { room });
+                } else {
+                    send(outbox, &not_a_member(&room));
+                }
+            }
+            ClientMessage::Publish { room, text } => self.publish(client, outbox, room, text),
+            ClientMessage::Members { room } => {
+                let members = self.members(&room);
+                send(outbox, &ServerMessage::Members { room, members });
+            }
+        }
+    }
+
+    fn join(&self, client: ClientId, name: String, outbox: UnboundedSender<Message>, room: &str) {
+        let mut rooms = self.rooms.lock().unwrap();
+        let members = rooms.entry(room.to_string()).or_default();
+
+        // Joining twice just repeats the member list
+        if !members.contains_key(&client) {
+            broadcast(
+                members.values(),
+                &ServerMessage::MemberJoined {
+                    room: room.to_string(),
+                    member: name.clone(),
+                },
+            );
+            members.insert(
+                client,
+                Member {
+                    name,
+                    outbox: outbox.clone(),
+                },
+            );
+        }
+
+        send(
+            &outbox,
+            &ServerMessage::Joined {
+                room: room.to_string(),
+                members: members.values().map(|m| m.name.clone()).collect(),
+            },
+        );
+    }
+
+    /// Remove `client` from `room`; false if it wasn't a member.
+    fn leave(&self, client: ClientId, room: &str) -> bool {
+        let mut rooms = self.rooms.lock().unwrap();
+        let Some(members) = rooms.get_mut(room) else {
+            return false;
+        };
+        let Some(member) = members.remove(&client) else {
+            return false;
+        };
+
+        broadcast(
+            members.values(),
+            &ServerMessage::MemberLeft {
+                room: room.to_string(),
+                member: member.name,
+            },
+        );
+        // Empty rooms are dropped; joining creates them again
+        if members.is_empty() {
+            rooms.remove(room);
+        }
+        true
+    }
+
+    fn publish(
+        &self,
+        client: ClientId,
+        outbox: &UnboundedSender<Message>,
+        room: String,
+        text: String,
+    ) {
+        let rooms = self.rooms.lock().unwrap();
+        let Some(members) = rooms.get(&room) else {
+            return send(outbox, &not_a_member(&room));
+        };
+        let Some(sender) = members.get(&client) else {
+            return send(outbox, &not_a_member(&room));
+        };
+
+        let message = ServerMessage::Message {
+            from: sender.name.clone(),
+            room,
+            text,
+        };
+        broadcast(members.values(), &message);
+    }
+
+    /// Names in `room`, in join order.
+    pub fn members(&self, room: &str) -> Vec<String> {
+        let rooms = self.rooms.lock().unwrap();
+        rooms
+            .get(room)
+            .map(|members| members.values().map(|m| m.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Remove a disconnected client from every room it was in.
+    pub fn disconnect(&self, client: ClientId) {
+        let joined: Vec<String> = {
+            let rooms = self.rooms.lock().unwrap();
+            rooms
+                .iter()
+                .filter(|(_, members)| members.contains_key(&client))
+                .map(|(room, _)| room.clone())
+                .collect()
+        };
+        for room in joined {
+            self.leave(client, &room);
+        }
+    }
+}
+
+fn not_a_member(room: &str) -> ServerMessage {
+    ServerMessage::Error {
+        message: format!("not a member of room '{room}'"),
+    }
+}
+
+pub fn send(outbox: &UnboundedSender<Message>, msg: &ServerMessage) {
+    // A closed outbox means the client is going away; disconnect() cleans up
+    let _ = outbox.send(msg.to_frame());
+}
+
+fn broadcast<'a>(members: impl Iterator<Item = &'a Member>, msg: &ServerMessage) {
+    let frame = msg.to_frame();
+    for member in members {
+        let _ = member.outbox.send(frame.clone());
+    }
+}
@@ -1,84 +1,272 @@
 use std::error::Error;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use futures_util::{SinkExt, StreamExt};
-use tokio::net::{TcpListener, TcpStream};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinSet;
+use tokio::time::{self, Instant};
 use tokio_tungstenite::accept_async;
 use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 
-pub async fn run_main() -> Result<(), Box<dyn Error>> {
-    let listener = TcpListener::bind("127.0.0.1:9000").await?;
+use crate::shutdown::{DEFAULT_DRAIN_DEADLINE, ShutdownHandle};
 
-    println!("[SERVER] listening on port 9000");
+use super::rooms::{self, ClientMessage, Rooms, ServerMessage};
 
-    loop {
-        let (stream, addr) = listener.accept().await?;
+/// Ping a client that has been quiet this long...
+const PING_INTERVAL: Duration = Duration::from_secs(15);
+/// ...and drop it if it still hasn't answered (no pong, no message).
+const IDLE_TIMEOUT: Duration = Duration::from_secs(45);
 
-        println!("[SERVER] {}, peer:{}", addr, stream.peer_addr()?);
+pub struct WsServer {
+    listener: TcpListener,
+    rooms: Rooms,
+    shutdown: Arc<watch::Sender<bool>>,
+    drain_deadline: Duration,
+}
 
-        tokio::spawn(async move {
-            if let Err(e) = handle_conn(stream, addr.to_string()).await {
-                eprintln!("[SERVER] Error: {}", e);
-            }
-        });
+impl WsServer {
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(WsServer {
+            listener: TcpListener::bind(addr).await?,
+            rooms: Rooms::new(),
+            shutdown: Arc::new(watch::channel(false).0),
+            drain_deadline: DEFAULT_DRAIN_DEADLINE,
+        })
+    }
+
+    /// How long clients get to answer the Close frame once shutdown starts
+    /// (default 5s).
+    pub fn drain_deadline(mut self, deadline: Duration) -> Self {
+        self.drain_deadline = deadline;
+        self
+    }
+
+    /// Address the listener is bound to (useful with port 0).
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// A handle that stops `run`.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle::new(Arc::clone(&self.shutdown), None)
+    }
+
+    /// Accept connections until shutdown. Then stop accepting, send every
+    /// client a Close frame (1001 Going Away), wait up to the drain
+    /// deadline for the closing handshakes, and abort the rest.
+    pub async fn run(self) -> io::Result<()> {
+        println!("[SERVER] listening on {}", self.local_addr()?);
+
+        let mut stop = self.shutdown.subscribe();
+        let mut connections = JoinSet::new();
+
+        loop {
+            let (stream, addr) = tokio::select! {
+                accepted = self.listener.accept() => accepted?,
+                _ = stop.wait_for(|&stop| stop) => break,
+                // Reap finished connections as we go
+                Some(_) = connections.join_next(), if !connections.is_empty() => continue,
+            };
+
+            println!("[SERVER] {}, peer:{}", addr, stream.peer_addr()?);
+
+            let rooms = self.rooms.clone();
+            let stop = self.shutdown.subscribe();
+            connections.spawn(async move {
+                if let Err(e) = handle_conn(stream, addr.to_string(), rooms, stop).await {
+                    eprintln!("[SERVER] Error: {}", e);
+                }
+            });
+        }
+
+        // New clients get "connection refused" from here on; the open ones
+        // got the signal too and are closing
+        drop(self.listener);
+        println!(
+            "[SERVER] shutting down, draining {} connection(s)",
+            connections.len()
+        );
+
+        let drained = time::timeout(self.drain_deadline, async {
+            while connections.join_next().await.is_some() {}
+        })
+        .await;
+        if drained.is_err() {
+            println!(
+                "[SERVER] aborted {} connection(s) still open after {:?}",
+                connections.len(),
+                self.drain_deadline
+            );
+            connections.shutdown().await;
+        }
+        Ok(())
     }
 }
 
-async fn handle_conn(stream: TcpStream, addr: String) -> Result<(), Box<dyn Error>> {
+pub async fn run_main() -> Result<(), Box<dyn Error>> {
+    let server = WsServer::bind("127.0.0.1:9000").await?;
+
+    // Ctrl-C drains the connections instead of killing them
+    let shutdown = server.shutdown_handle();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            shutdown.shutdown();
+        }
+    });
+
+    server.run().await?;
+    Ok(())
+}
+
+async fn handle_conn(
+    stream: TcpStream,
+    addr: String,
+    rooms: Rooms,
+    mut stop: watch::Receiver<bool>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
     println!("[CLIENT {}] Performing WebSocket handshake...", addr);
     let ws_stream = accept_async(stream).await?;
     println!("[CLIENT {}] WebSocket connection established!\n", addr);
 
+    let client = rooms.next_client_id();
     let (mut write, mut read) = ws_stream.split();
 
-    write
-        .send(Message::Text("Welcome to Websocket server!".into()))
-        .await?;
+    // Everything sent to this client goes through the outbox: our replies
+    // and broadcasts from other connections alike
+    let (outbox, mut inbox) = mpsc::unbounded_channel::<Message>();
+    let writer = tokio::spawn(async move {
+        while let Some(message) = inbox.recv().await {
+            if write.send(message).await.is_err() {
+                return;
+            }
+        }
+        // Finish the closing handshake (or start it, for dropped peers)
+        let _ = write.close().await;
+    });
 
-    while let Some(msg) = read.next().await {
-        let message = msg?;
+    // Read and react until the peer closes, goes quiet or fails. Every exit,
+    // `?` included, falls through to the cleanup below: a reset peer must
+    // not stay behind as a ghost member
+    let result: Result<(), Box<dyn Error + Send + Sync>> = async {
+        outbox.send(Message::Text(
+            "Welcome to Websocket server! Send {\"type\":\"join\",\"room\":\"...\"} to join a room"
+                .into(),
+        ))?;
 
-        match message {
-            Message::Text(text) => {
-                println!("[CLIENT {}] Received text: {:?}", addr, text);
+        let mut last_seen = Instant::now();
+        let mut ticker = time::interval(PING_INTERVAL);
+        let mut closing = false;
 
-                // Echo back with prefix
-                let response = format!("Echo: {}", text);
-                write.send(Message::Text(response.into())).await?;
-            }
-            Message::Binary(data) => {
-                println!("[CLIENT {}] Received binary: {} bytes", addr, data.len());
+        loop {
+            let message = tokio::select! {
+                msg = read.next() => match msg {
+                    Some(msg) => msg?,
+                    None => break,
+                },
+                // Server shutting down: start the closing handshake, then keep
+                // reading until the client's Close (or the drain deadline)
+                _ = stop.wait_for(|&stop| stop), if !closing => {
+                    println!("[CLIENT {}] Server shutting down, sending Close", addr);
+                    closing = true;
+                    outbox.send(Message::Close(Some(CloseFrame {
+                        code: CloseCode::Away,
+                        reason: "server shutting down".into(),
+                    })))?;
+                    continue;
+                }
+                _ = ticker.tick() => {
+                    let idle = last_seen.elapsed();
+                    if idle >= IDLE_TIMEOUT {
+                        println!("[CLIENT {}] No answer for {:?}, dropping", addr, idle);
+                        break;
+                    }
+                    if idle >= PING_INTERVAL {
+                        println!("[CLIENT {}] Idle for {:?}, sending Ping", addr, idle);
+                        outbox.send(Message::Ping(Vec::new().into()))?;
+                    }
+                    continue;
+                }
+            };
 
-                // Echo back
-                write.send(Message::Binary(data)).await?;
-            }
-            Message::Ping(data) => {
-                println!("[CLIENT {}] Received Ping", addr);
+            // Any frame at all proves the peer is alive
+            last_seen = Instant::now();
 
-                // Automatically respond with Pong
-                // (tokio-tungstenite usually handles this automatically)
-                write.send(Message::Pong(data)).await?;
-            }
-            Message::Pong(_) => {
-                println!("[CLIENT {}] Received Pong", addr);
-            }
+            match message {
+                Message::Text(text) => {
+                    println!("[CLIENT {}] Received text: {:?}", addr, text);
 
-            Message::Close(frame) => {
-                println!("[CLIENT {}] Received Close frame", addr);
-                if let Some(cf) = frame {
-                    println!("  Code: {}, Reason: {}", cf.code, cf.reason);
+                    match serde_json::from_str::<ClientMessage>(&text) {
+                        Ok(msg) => rooms.handle(client, &outbox, msg),
+                        Err(e) => rooms::send(
+                            &outbox,
+                            &ServerMessage::Error {
+                                message: format!("invalid control message: {e}"),
+                            },
+                        ),
+                    }
                 }
+                Message::Binary(data) => {
+                    println!("[CLIENT {}] Received binary: {} bytes", addr, data.len());
 
-                // Respond with Close frame and break
-                write.send(Message::Close(None)).await?;
-                break;
-            }
+                    rooms::send(
+                        &outbox,
+                        &ServerMessage::Error {
+                            message: "binary frames are not supported".to_string(),
+                        },
+                    );
+                }
+                Message::Ping(data) => {
+                    println!("[CLIENT {}] Received Ping", addr);
+
+                    // Automatically respond with Pong
+                    // (tokio-tungstenite usually handles this automatically)
+                    outbox.send(Message::Pong(data))?;
+                }
+                Message::Pong(_) => {
+                    println!("[CLIENT {}] Received Pong", addr);
+                }
+
+                Message::Close(frame) => {
+                    println!("[CLIENT {}] Received Close frame", addr);
+                    if let Some(cf) = frame {
+                        println!("  Code: {}, Reason: {}", cf.code, cf.reason);
+                    }
+
+                    // tungstenite already queued the Close reply; the writer
+                    // flushes it when it shuts down
+                    break;
+                }
 
-            Message::Frame(_) => {
-                // Raw frame (not usually seen)
-                println!("[CLIENT {}] Received raw frame", addr);
+                Message::Frame(_) => {
+                    // Raw frame (not usually seen)
+                    println!("[CLIENT {}] Received raw frame", addr);
+                }
             }
         }
+        Ok(())
     }
+    .await;
+
+    // Leave every room (telling the other members), then let the writer
+    // flush what's queued: it stops once the last outbox sender is gone
+    rooms.disconnect(client);
+    drop(outbox);
+    writer.await?;
+
     println!("[CLIENT {}] Connection closed\n", addr);
-    Ok(())
+    result
 }
+
+// WHY PING?
+//
+// A peer that vanishes without a Close frame (laptop lid shut, cable pulled,
+// NAT entry expired) leaves a TCP connection that looks open forever: reads
+// just never return. Pinging quiet clients forces traffic; a live client
+// answers with a Pong, a dead one stays silent, and after IDLE_TIMEOUT we
+// stop waiting and free its room memberships.
@@ -66,15 +66,18 @@ pub async fn run_main() -> Result<(), Box<dyn std::error::Error>> {
 
     sleep(Duration::from_millis(100)).await;
 
-    write
-        .send(Message::Text("Hello, WebSocket!".to_string().into()))
-        .await?;
-    sleep(Duration::from_secs(1)).await;
-
-    write
-        .send(Message::Text("This is message 2".to_string().into()))
-        .await?;
-    sleep(Duration::from_secs(1)).await;
+    // Join a room, talk in it, and ask who's there
+    let control = [
+        r#"{"type":"join","room":"lobby","name":"demo-client"}"#,
+        r#"{"type":"publish","room":"lobby","text":"Hello, room!"}"#,
+        r#"{"type":"members","room":"lobby"}"#,
+        r#"{"type":"leave","room":"lobby"}"#,
+    ];
+    for msg in control {
+        println!("[CLIENT] → {}", msg);
+        write.send(Message::Text(msg.into())).await?;
+        sleep(Duration::from_millis(500)).await;
+    }
 
     // Send binary message
     let binary_data = vec![1, 2, 3, 4, 5];
@@ -0,0 +1,614 @@
+// Protocol conformance checks for the raw HTTP server (http_over_tcp)
+//
+// Each test starts an HttpServer on an ephemeral port, talks to it over a
+// plain TcpStream with hand-written bytes, and checks what comes back: the
+// status code, the framing headers, and whether the server closed the
+// connection. The server answers one request per connection
+// (`Connection: close`), so "read until EOF" is how a client sees the end
+// of the exchange.
+//
+// Keep-alive reuse and chunked request bodies are covered only as far as
+// the server goes: it doesn't keep connections open or decode chunked
+// uploads, so those tests deliberately pin down that it refuses them
+// cleanly (one response, `Connection: close`, 501 for a chunked body)
+// instead of hanging or misreading the stream.
+//
+//   cargo test --test conformance
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use flate2::read::GzDecoder;
+use learn_http::http_over_tcp::access_log::{LogFormat, MemorySink};
+use learn_http::http_over_tcp::response::Response;
+use learn_http::http_over_tcp::router::Router;
+use learn_http::http_over_tcp::server::HttpServer;
+use learn_http::shutdown::ShutdownHandle;
+use serde_json::Value;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_BODY: usize = 1024;
+
+// ============================================================================
+// Harness
+// ============================================================================
+
+/// A server running on its own thread, shut down when dropped.
+struct TestServer {
+    addr: SocketAddr,
+    shutdown: ShutdownHandle,
+    thread: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl TestServer {
+    fn start() -> Self {
+        Self::start_with(false, None)
+    }
+
+    fn start_with(compression: bool, access_log: Option<MemorySink>) -> Self {
+        let router = Router::new()
+            .get("/", |_| Response::text(200, "hello"))
+            .get("/big", |_| {
+                Response::text(200, "all work and no play\n".repeat(2000))
+            })
+            .post("/echo", |req| Response::new(200).body(req.body().to_vec()));
+
+        let mut builder = HttpServer::builder()
+            .addr("127.0.0.1:0")
+            .compression(compression)
+            .max_body_size(MAX_BODY)
+            .drain_deadline(Duration::from_millis(200))
+            .router(router);
+        if let Some(sink) = access_log {
+            builder = builder.access_log(sink);
+        }
+        let server = builder.build().expect("bind an ephemeral port");
+
+        let addr = server.local_addr().unwrap();
+        let shutdown = server.shutdown_handle();
+        let thread = thread::spawn(move || server.run());
+
+        TestServer {
+            addr,
+            shutdown,
+            thread: Some(thread),
+        }
+    }
+
+    fn connect(&self) -> TcpStream {
+        let stream = TcpStream::connect(self.addr).unwrap();
+        stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+        stream.set_write_timeout(Some(TIMEOUT)).unwrap();
+        stream
+    }
+
+    /// Send `raw` in one write and read until the server closes.
+    fn exchange(&self, raw: &[u8]) -> Reply {
+        let mut stream = self.connect();
+        stream.write_all(raw).unwrap();
+        read_reply(&mut stream)
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.shutdown.shutdown();
+        if let Some(thread) = self.thread.take() {
+            let result = thread.join().expect("server thread panicked");
+            // Don't turn a failing test into a double panic
+            if !thread::panicking() {
+                result.expect("server run failed");
+            }
+        }
+    }
+}
+
+/// Everything the server sent on one connection.
+struct Reply {
+    raw: Vec<u8>,
+}
+
+/// One parsed response out of a [`Reply`].
+#[derive(Debug)]
+struct Parsed {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Parsed {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl Reply {
+    /// The first (and only) response, with its body de-chunked.
+    fn response(&self) -> Parsed {
+        let mut headers = [httparse::EMPTY_HEADER; 32];
+        let mut response = httparse::Response::new(&mut headers);
+        let head_len = match response.parse(&self.raw) {
+            Ok(httparse::Status::Complete(n)) => n,
+            other => panic!(
+                "no complete response ({other:?}): {:?}",
+                String::from_utf8_lossy(&self.raw)
+            ),
+        };
+
+        let parsed = Parsed {
+            status: response.code.unwrap(),
+            headers: response
+                .headers
+                .iter()
+                .map(|h| {
+                    (
+                        h.name.to_string(),
+                        String::from_utf8_lossy(h.value).into_owned(),
+                    )
+                })
+                .collect(),
+            body: Vec::new(),
+        };
+        let rest = &self.raw[head_len..];
+
+        let body = if parsed.header("transfer-encoding") == Some("chunked") {
+            dechunk(rest)
+        } else {
+            let length: usize = parsed
+                .header("content-length")
+                .expect("Content-Length or chunked framing")
+                .parse()
+                .unwrap();
+            rest[..length.min(rest.len())].to_vec()
+        };
+        Parsed { body, ..parsed }
+    }
+
+    /// How many status lines the server sent.
+    fn response_count(&self) -> usize {
+        self.raw
+            .windows(b"HTTP/1.1 ".len())
+            .filter(|w| w == b"HTTP/1.1 ")
+            .count()
+    }
+}
+
+fn read_reply(stream: &mut TcpStream) -> Reply {
+    let mut raw = Vec::new();
+    // EOF is the server closing the connection: a timeout here means it
+    // kept a connection open it said it would close
+    stream
+        .read_to_end(&mut raw)
+        .expect("server closes the connection after its response");
+    Reply { raw }
+}
+
+/// Decode a chunked body, checking the framing as we go.
+fn dechunk(mut data: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    loop {
+        let line_end = find_crlf(data).expect("chunk size line");
+        let size_line = std::str::from_utf8(&data[..line_end]).unwrap();
+        let size_hex = size_line.split(';').next().unwrap().trim();
+        let size = usize::from_str_radix(size_hex, 16).expect("hex chunk size");
+        data = &data[line_end + 2..];
+
+        if size == 0 {
+            assert!(data.starts_with(b"\r\n"), "last chunk ends with CRLF");
+            return body;
+        }
+        body.extend_from_slice(&data[..size]);
+        assert_eq!(&data[size..size + 2], b"\r\n", "chunk data ends with CRLF");
+        data = &data[size + 2..];
+    }
+}
+
+fn find_crlf(data: &[u8]) -> Option<usize> {
+    data.windows(2).position(|w| w == b"\r\n")
+}
+
+// ============================================================================
+// Well-formed requests
+// ============================================================================
+
+#[test]
+fn get_is_answered_with_length_framing_and_close() {
+    let server = TestServer::start();
+
+    let reply = server.exchange(b"GET / HTTP/1.1\r\nHost: test\r\n\r\n");
+    let response = reply.response();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(response.header("content-length"), Some("5"));
+    assert_eq!(response.header("connection"), Some("close"));
+    assert_eq!(response.body, b"hello");
+    // Nothing after the body
+    assert!(reply.raw.ends_with(b"\r\n\r\nhello"));
+}
+
+#[test]
+fn head_gets_get_headers_without_a_body() {
+    let server = TestServer::start();
+
+    let reply = server.exchange(b"HEAD / HTTP/1.1\r\nHost: test\r\n\r\n");
+    let response = reply.response();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(response.header("content-length"), Some("5"));
+    assert!(
+        reply.raw.ends_with(b"\r\n\r\n"),
+        "HEAD response has no body"
+    );
+}
+
+#[test]
+fn unknown_path_and_method_get_404_and_405() {
+    let server = TestServer::start();
+
+    let not_found = server
+        .exchange(b"GET /nope HTTP/1.1\r\nHost: test\r\n\r\n")
+        .response();
+    assert_eq!(not_found.status, 404);
+
+    let not_allowed = server
+        .exchange(b"DELETE / HTTP/1.1\r\nHost: test\r\n\r\n")
+        .response();
+    assert_eq!(not_allowed.status, 405);
+    assert_eq!(not_allowed.header("allow"), Some("GET"));
+}
+
+#[test]
+fn http_1_0_requests_are_answered() {
+    let server = TestServer::start();
+
+    let response = server.exchange(b"GET / HTTP/1.0\r\n\r\n").response();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, b"hello");
+}
+
+// ============================================================================
+// Malformed requests
+// ============================================================================
+
+#[test]
+fn malformed_request_lines_get_400() {
+    let server = TestServer::start();
+
+    let cases: &[&[u8]] = &[
+        b"GARBAGE\r\n\r\n",
+        b"GET\r\n\r\n",
+        b"GET / HTTP/9.9\r\n\r\n",
+        b"GET / FTP/1.1\r\n\r\n",
+        b"G(E)T / HTTP/1.1\r\n\r\n",
+        b"GET / HTTP/1.1\r\nHost test\r\n\r\n",
+        b"GET / HTTP/1.1\r\nBad Name: x\r\n\r\n",
+        b"\x16\x03\x01\x02\x00\x01\x00\x01\xfc\x03\x03\r\n\r\n",
+    ];
+
+    for raw in cases {
+        let reply = server.exchange(raw);
+        assert_eq!(
+            reply.response().status,
+            400,
+            "request {:?}",
+            String::from_utf8_lossy(raw)
+        );
+        assert_eq!(reply.response_count(), 1);
+    }
+}
+
+#[test]
+fn bad_content_length_gets_400() {
+    let server = TestServer::start();
+
+    for length in ["abc", "-1", "1 2", ""] {
+        let raw = format!("POST /echo HTTP/1.1\r\nContent-Length: {length}\r\n\r\n");
+        assert_eq!(
+            server.exchange(raw.as_bytes()).response().status,
+            400,
+            "Content-Length: {length:?}"
+        );
+    }
+}
+
+#[test]
+fn oversized_head_gets_431() {
+    let server = TestServer::start();
+
+    // 64 KiB is the limit; one long header goes past it
+    let raw = format!(
+        "GET / HTTP/1.1\r\nX-Filler: {}\r\n\r\n",
+        "a".repeat(70 * 1024)
+    );
+
+    assert_eq!(server.exchange(raw.as_bytes()).response().status, 431);
+}
+
+#[test]
+fn too_many_headers_get_431() {
+    let server = TestServer::start();
+
+    let mut raw = String::from("GET / HTTP/1.1\r\n");
+    for i in 0..100 {
+        raw.push_str(&format!("X-Header-{i}: {i}\r\n"));
+    }
+    raw.push_str("\r\n");
+
+    assert_eq!(server.exchange(raw.as_bytes()).response().status, 431);
+}
+
+#[test]
+fn oversized_body_gets_413() {
+    let server = TestServer::start();
+
+    let body = vec![b'x'; MAX_BODY + 1];
+    let mut raw = format!(
+        "POST /echo HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+        body.len()
+    )
+    .into_bytes();
+    raw.extend_from_slice(&body);
+
+    assert_eq!(server.exchange(&raw).response().status, 413);
+}
+
+// ============================================================================
+// Partial reads and writes
+// ============================================================================
+
+#[test]
+fn request_sent_a_byte_at_a_time_is_reassembled() {
+    let server = TestServer::start();
+    let mut stream = server.connect();
+    stream.set_nodelay(true).unwrap();
+
+    let raw = b"POST /echo HTTP/1.1\r\nHost: test\r\nContent-Length: 11\r\n\r\nhello world";
+    for byte in raw {
+        stream.write_all(&[*byte]).unwrap();
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let response = read_reply(&mut stream).response();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, b"hello world");
+}
+
+#[test]
+fn body_arriving_after_the_head_is_waited_for() {
+    let server = TestServer::start();
+    let mut stream = server.connect();
+
+    stream
+        .write_all(b"POST /echo HTTP/1.1\r\nContent-Length: 10\r\n\r\n01234")
+        .unwrap();
+    thread::sleep(Duration::from_millis(50));
+    stream.write_all(b"56789").unwrap();
+
+    let response = read_reply(&mut stream).response();
+    assert_eq!(response.body, b"0123456789");
+}
+
+#[test]
+fn client_closing_mid_request_gets_no_response() {
+    let server = TestServer::start();
+
+    for partial in [
+        &b"GET / HTTP/1.1\r\nHost: te"[..],
+        b"POST /echo HTTP/1.1\r\nContent-Length: 10\r\n\r\n01234",
+    ] {
+        let mut stream = server.connect();
+        stream.write_all(partial).unwrap();
+        stream.shutdown(Shutdown::Write).unwrap();
+
+        let reply = read_reply(&mut stream);
+        // A truncated head is still a bad request; a truncated body is just
+        // a client that went away
+        assert!(
+            reply.raw.is_empty() || reply.response().status == 400,
+            "partial request {:?}",
+            String::from_utf8_lossy(partial)
+        );
+    }
+
+    // And the server is still serving
+    assert_eq!(
+        server.exchange(b"GET / HTTP/1.1\r\n\r\n").response().status,
+        200
+    );
+}
+
+#[test]
+fn connecting_without_sending_anything_is_harmless() {
+    let server = TestServer::start();
+
+    let mut idle = server.connect();
+    idle.shutdown(Shutdown::Write).unwrap();
+    assert!(read_reply(&mut idle).raw.is_empty());
+
+    assert_eq!(
+        server.exchange(b"GET / HTTP/1.1\r\n\r\n").response().status,
+        200
+    );
+}
+
+// ============================================================================
+// Connection management
+// ============================================================================
+
+#[test]
+fn keep_alive_is_declined_with_connection_close() {
+    let server = TestServer::start();
+
+    // The client asks to reuse the connection; the server may refuse, but
+    // then it has to say so (RFC 9112 9.6) and close
+    let reply = server.exchange(b"GET / HTTP/1.1\r\nConnection: keep-alive\r\n\r\n");
+
+    assert_eq!(reply.response().status, 200);
+    assert_eq!(reply.response().header("connection"), Some("close"));
+}
+
+#[test]
+fn pipelined_requests_get_one_response_then_close() {
+    let server = TestServer::start();
+
+    let reply = server.exchange(b"GET / HTTP/1.1\r\n\r\nGET /nope HTTP/1.1\r\n\r\n");
+
+    assert_eq!(reply.response_count(), 1);
+    assert_eq!(reply.response().status, 200);
+    assert_eq!(reply.response().header("connection"), Some("close"));
+}
+
+#[test]
+fn connections_are_served_concurrently() {
+    let server = TestServer::start();
+
+    // Hold one connection open mid-request: a second client must not wait
+    // for it
+    let mut slow = server.connect();
+    slow.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+
+    assert_eq!(
+        server.exchange(b"GET / HTTP/1.1\r\n\r\n").response().status,
+        200
+    );
+
+    slow.write_all(b"\r\n").unwrap();
+    assert_eq!(read_reply(&mut slow).response().status, 200);
+}
+
+// ============================================================================
+// Transfer codings
+// ============================================================================
+
+#[test]
+fn chunked_request_body_gets_501() {
+    let server = TestServer::start();
+
+    let reply = server.exchange(
+        b"POST /echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n",
+    );
+
+    assert_eq!(reply.response().status, 501);
+    assert_eq!(reply.response_count(), 1);
+}
+
+#[test]
+fn compressed_response_is_validly_chunked() {
+    let server = TestServer::start_with(true, None);
+
+    let response = server
+        .exchange(b"GET /big HTTP/1.1\r\nAccept-Encoding: gzip\r\n\r\n")
+        .response();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(response.header("transfer-encoding"), Some("chunked"));
+    assert_eq!(response.header("content-encoding"), Some("gzip"));
+    assert_eq!(response.header("content-length"), None);
+
+    let mut body = String::new();
+    GzDecoder::new(&response.body[..])
+        .read_to_string(&mut body)
+        .unwrap();
+    assert_eq!(body, "all work and no play\n".repeat(2000));
+}
+
+#[test]
+fn identity_is_used_when_the_client_accepts_no_coding() {
+    let server = TestServer::start_with(true, None);
+
+    let response = server.exchange(b"GET /big HTTP/1.1\r\n\r\n").response();
+
+    assert_eq!(response.header("transfer-encoding"), None);
+    assert_eq!(response.header("content-encoding"), None);
+    assert_eq!(response.body.len(), "all work and no play\n".len() * 2000);
+}
+
+// ============================================================================
+// Access logs
+// ============================================================================
+
+/// Send a few requests to a logging server, then shut it down so every
+/// connection thread has logged. Returns the sink and, per request, the
+/// client's address and the response it got.
+fn logged_exchanges(requests: &[&[u8]]) -> (MemorySink, Vec<(SocketAddr, Parsed)>) {
+    let sink = MemorySink::new();
+    let server = TestServer::start_with(false, Some(sink.clone()));
+
+    let exchanges = requests
+        .iter()
+        .map(|raw| {
+            let mut stream = server.connect();
+            let local = stream.local_addr().unwrap();
+            stream.write_all(raw).unwrap();
+            (local, read_reply(&mut stream).response())
+        })
+        .collect();
+
+    drop(server);
+    (sink, exchanges)
+}
+
+const LOGGED: [&[u8]; 3] = [
+    b"GET / HTTP/1.1\r\n\r\n",
+    b"POST /echo?x=1 HTTP/1.1\r\nContent-Length: 3\r\n\r\nabc",
+    b"GET /nope HTTP/1.1\r\n\r\n",
+];
+const LOGGED_REQUESTS: [(&str, &str); 3] = [("GET", "/"), ("POST", "/echo?x=1"), ("GET", "/nope")];
+
+#[test]
+fn access_log_has_one_common_format_line_per_request() {
+    let (sink, exchanges) = logged_exchanges(&LOGGED);
+    let lines = sink.lines(LogFormat::Common);
+    assert_eq!(lines.len(), LOGGED.len(), "{lines:#?}");
+
+    for ((method, path), (client, response)) in LOGGED_REQUESTS.iter().zip(&exchanges) {
+        let request = format!("\"{method} {path} HTTP/1.1\"");
+        let line = lines
+            .iter()
+            .find(|line| line.contains(&request))
+            .unwrap_or_else(|| panic!("no line for {request}: {lines:#?}"));
+
+        // 127.0.0.1 - - [16/Oct/2026:11:26:27 +0000] "GET / HTTP/1.1" 200 5 0.412ms
+        assert!(
+            line.starts_with(&format!("{} - - [", client.ip())),
+            "{line}"
+        );
+        let (_, after) = line.split_once(&request).unwrap();
+        let fields: Vec<&str> = after.split_whitespace().collect();
+        assert_eq!(fields[0], response.status.to_string(), "{line}");
+        assert_eq!(fields[1], response.body.len().to_string(), "{line}");
+        let ms: f64 = fields[2].strip_suffix("ms").unwrap().parse().unwrap();
+        assert!(ms > 0.0, "{line}");
+    }
+}
+
+#[test]
+fn access_log_has_one_json_line_per_request() {
+    let (sink, exchanges) = logged_exchanges(&LOGGED);
+    let lines = sink.lines(LogFormat::Json);
+    assert_eq!(lines.len(), LOGGED.len(), "{lines:#?}");
+
+    let entries: Vec<Value> = lines
+        .iter()
+        .map(|line| serde_json::from_str(line).expect("each line is one JSON object"))
+        .collect();
+
+    for ((method, path), (client, response)) in LOGGED_REQUESTS.iter().zip(&exchanges) {
+        let entry = entries
+            .iter()
+            .find(|entry| entry["method"] == *method && entry["path"] == *path)
+            .unwrap_or_else(|| panic!("no entry for {method} {path}: {lines:#?}"));
+
+        assert_eq!(entry["remote_addr"], client.to_string());
+        assert_eq!(entry["status"], response.status);
+        assert_eq!(entry["bytes"], response.body.len());
+        assert!(entry["duration_ms"].as_f64().unwrap() > 0.0, "{entry}");
+        assert!(entry["time"].is_string());
+    }
+}
@@ -0,0 +1,104 @@
+// Room membership checks for the WebSocket server (websockets::ws)
+//
+// Each test starts a WsServer on an ephemeral port and talks to it with
+// tokio-tungstenite clients, sending the JSON control messages by hand.
+//
+//   cargo test --test ws_rooms
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use learn_http::shutdown::ShutdownHandle;
+use learn_http::websockets::ws::WsServer;
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+use tokio::time;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{WebSocketStream, client_async};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+type Client = WebSocketStream<TcpStream>;
+
+async fn start() -> (SocketAddr, ShutdownHandle) {
+    let server = WsServer::bind("127.0.0.1:0")
+        .await
+        .expect("bind an ephemeral port");
+    let addr = server.local_addr().unwrap();
+    let shutdown = server.shutdown_handle();
+    tokio::spawn(server.run());
+    (addr, shutdown)
+}
+
+/// Connect and swallow the welcome banner.
+async fn connect(addr: SocketAddr) -> Client {
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let (mut ws, _) = client_async(format!("ws://{addr}/"), stream).await.unwrap();
+    let welcome = next_text(&mut ws).await;
+    assert!(welcome.starts_with("Welcome"), "{welcome}");
+    ws
+}
+
+async fn send(ws: &mut Client, msg: Value) {
+    ws.send(Message::Text(msg.to_string().into()))
+        .await
+        .unwrap();
+}
+
+async fn next_text(ws: &mut Client) -> String {
+    loop {
+        let msg = time::timeout(TIMEOUT, ws.next())
+            .await
+            .expect("server answered in time")
+            .expect("connection still open")
+            .unwrap();
+        if let Message::Text(text) = msg {
+            return text.to_string();
+        }
+    }
+}
+
+/// Skip frames until one of `kind` arrives.
+async fn expect(ws: &mut Client, kind: &str) -> Value {
+    loop {
+        let msg: Value = serde_json::from_str(&next_text(ws).await).unwrap();
+        if msg["type"] == kind {
+            return msg;
+        }
+    }
+}
+
+#[tokio::test]
+async fn peer_dropping_without_close_leaves_its_rooms() {
+    let (addr, shutdown) = start().await;
+
+    let mut ana = connect(addr).await;
+    send(
+        &mut ana,
+        json!({"type": "join", "room": "rust", "name": "ana"}),
+    )
+    .await;
+    expect(&mut ana, "joined").await;
+
+    let mut bo = connect(addr).await;
+    send(
+        &mut bo,
+        json!({"type": "join", "room": "rust", "name": "bo"}),
+    )
+    .await;
+    let joined = expect(&mut bo, "joined").await;
+    assert_eq!(joined["members"], json!(["ana", "bo"]));
+
+    // Gone without a Close frame: the server's read fails with a reset
+    drop(bo);
+
+    let left = expect(&mut ana, "member_left").await;
+    assert_eq!(left["member"], "bo");
+
+    send(&mut ana, json!({"type": "members", "room": "rust"})).await;
+    let members = expect(&mut ana, "members").await;
+    assert_eq!(members["members"], json!(["ana"]));
+
+    shutdown.shutdown();
+}
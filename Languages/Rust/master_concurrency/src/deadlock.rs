@@ -0,0 +1,214 @@
+// Deadlock: two threads, two locks, opposite order
+//
+//   thread 1: lock(A) ... lock(B)      thread 1 holds A, waits for B
+//   thread 2: lock(B) ... lock(A)      thread 2 holds B, waits for A
+//
+// Neither can continue, forever. The four conditions are all there: mutual
+// exclusion, hold-and-wait, no preemption, circular wait. The easy one to
+// break is circular wait: give every lock a rank and only ever take locks
+// in increasing rank. Then the cycle can't form.
+//
+// - `ab_ba` builds the deadlock on purpose and *detects* it: the second
+//   lock is taken with parking_lot's `try_lock_for`, so a thread that waits
+//   too long gives up instead of hanging the program
+// - `OrderedMutex` + `TryLockOrdered` check the rank at runtime: each thread
+//   remembers the ranks it holds, and asking for a rank that isn't higher
+//   than all of them is an error instead of a potential deadlock
+// - `lock_both` takes two ordered locks in rank order no matter which order
+//   the caller names them in: thread 2 above becomes safe without rewriting
+
+use parking_lot::{Mutex, MutexGuard};
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+
+/// Run the AB/BA deadlock. Returns `true` if it happened, i.e. a thread
+/// waited `patience` for its second lock without getting it.
+pub fn ab_ba(patience: Duration) -> bool {
+    let a = Arc::new(Mutex::new(0));
+    let b = Arc::new(Mutex::new(0));
+    // Both hold their first lock before either asks for the second, and
+    // neither lets go before both have tried
+    let holding = Arc::new(Barrier::new(2));
+    let tried = Arc::new(Barrier::new(2));
+
+    let spawn = |first: Arc<Mutex<i32>>, second: Arc<Mutex<i32>>| {
+        let (holding, tried) = (Arc::clone(&holding), Arc::clone(&tried));
+        thread::spawn(move || {
+            let _first = first.lock();
+            holding.wait();
+            let got_second = second.try_lock_for(patience).is_some();
+            tried.wait();
+            got_second
+        })
+    };
+
+    let t1 = spawn(Arc::clone(&a), Arc::clone(&b));
+    let t2 = spawn(b, a);
+    let got1 = t1.join().unwrap();
+    let got2 = t2.join().unwrap();
+
+    let deadlocked = !got1 || !got2;
+    if deadlocked {
+        println!("deadlock: each thread held one lock and waited {patience:?} for the other");
+    }
+    deadlocked
+}
+
+/// Asked for a lock out of rank order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockOrderError {
+    /// Highest rank this thread already holds
+    pub held: u32,
+    /// Rank it asked for
+    pub requested: u32,
+}
+
+impl fmt::Display for LockOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lock order violation: asked for rank {} while holding rank {}",
+            self.requested, self.held
+        )
+    }
+}
+
+impl Error for LockOrderError {}
+
+thread_local! {
+    // Ranks of the OrderedMutex guards alive on this thread
+    static HELD: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A mutex with a rank in the global lock order. Ranks should be unique:
+/// two locks of the same rank can never be held together.
+pub struct OrderedMutex<T> {
+    rank: u32,
+    inner: Mutex<T>,
+}
+
+impl<T> OrderedMutex<T> {
+    pub fn new(rank: u32, value: T) -> Self {
+        Self {
+            rank,
+            inner: Mutex::new(value),
+        }
+    }
+
+    pub fn rank(&self) -> u32 {
+        self.rank
+    }
+}
+
+pub trait TryLockOrdered<T> {
+    /// Lock, if that respects the order; otherwise fail right away.
+    ///
+    /// The "try" is about the order, not contention: when the rank is fine
+    /// this blocks like `lock()`, which is safe because every thread waits
+    /// "upwards" and no cycle can form.
+    fn try_lock_ordered(&self) -> Result<OrderedGuard<'_, T>, LockOrderError>;
+}
+
+impl<T> TryLockOrdered<T> for OrderedMutex<T> {
+    fn try_lock_ordered(&self) -> Result<OrderedGuard<'_, T>, LockOrderError> {
+        HELD.with_borrow_mut(|held| match held.iter().max() {
+            Some(&top) if top >= self.rank => Err(LockOrderError {
+                held: top,
+                requested: self.rank,
+            }),
+            _ => {
+                held.push(self.rank);
+                Ok(())
+            }
+        })?;
+
+        Ok(OrderedGuard {
+            rank: self.rank,
+            guard: self.inner.lock(),
+        })
+    }
+}
+
+pub struct OrderedGuard<'a, T> {
+    rank: u32,
+    guard: MutexGuard<'a, T>,
+}
+
+impl<T> Deref for OrderedGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for OrderedGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for OrderedGuard<'_, T> {
+    fn drop(&mut self) {
+        // Guards don't have to be dropped in reverse order
+        HELD.with_borrow_mut(|held| {
+            if let Some(pos) = held.iter().rposition(|&r| r == self.rank) {
+                held.remove(pos);
+            }
+        });
+    }
+}
+
+/// Lock both, lower rank first, whatever order they're passed in.
+pub fn lock_both<'a, A, B>(
+    a: &'a OrderedMutex<A>,
+    b: &'a OrderedMutex<B>,
+) -> Result<(OrderedGuard<'a, A>, OrderedGuard<'a, B>), LockOrderError> {
+    if a.rank < b.rank {
+        let ga = a.try_lock_ordered()?;
+        Ok((ga, b.try_lock_ordered()?))
+    } else {
+        let gb = b.try_lock_ordered()?;
+        Ok((a.try_lock_ordered()?, gb))
+    }
+}
+
+/// The AB/BA threads again, through `lock_both`: both finish.
+pub fn ab_ba_ordered() {
+    let a = Arc::new(OrderedMutex::new(1, 0));
+    let b = Arc::new(OrderedMutex::new(2, 0));
+
+    let handles: Vec<_> = [(true, "A then B"), (false, "B then A")]
+        .into_iter()
+        .map(|(a_first, label)| {
+            let (a, b) = (Arc::clone(&a), Arc::clone(&b));
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    let (mut ga, mut gb) = if a_first {
+                        lock_both(&*a, &*b).unwrap()
+                    } else {
+                        let (gb, ga) = lock_both(&*b, &*a).unwrap();
+                        (ga, gb)
+                    };
+                    *ga += 1;
+                    *gb += 1;
+                }
+                println!("{label}: done");
+            })
+        })
+        .collect();
+
+    for h in handles {
+        h.join().unwrap();
+    }
+    println!(
+        "a = {}, b = {}",
+        *a.try_lock_ordered().unwrap(),
+        *b.try_lock_ordered().unwrap()
+    );
+}
@@ -1,4 +1,6 @@
 pub mod atomic;
 pub mod channels;
+pub mod deadlock;
 pub mod part_a;
+pub mod spsc;
 pub mod thread_pool;
@@ -1,4 +1,4 @@
-use master_concurrency::{atomic, channels, part_a, thread_pool};
+use master_concurrency::{atomic, channels, deadlock, part_a, thread_pool};
 
 struct TryFn<T> {
     f: fn(&T) -> T, //does not mutate captured values and can be used many times: Fn
@@ -27,6 +27,8 @@ fn main() {
     // let results = channels::worker_pool_with_beam_results(3, vec![1, 2, 3, 4, 5], |x| x * 2);
 
     // println!("results: {:?}", results);
+    // deadlock::ab_ba(std::time::Duration::from_millis(500));
+    // deadlock::ab_ba_ordered();
 
     channels::spmc();
 }
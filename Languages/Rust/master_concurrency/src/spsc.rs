@@ -0,0 +1,312 @@
+// SPSC ring buffer: one producer, one consumer, no locks
+//
+//   buf:   [ . | x | x | x | . | . ]        capacity 6
+//                ^head       ^tail
+//
+// - `tail` is where the next push goes; only the producer moves it
+// - `head` is where the next pop comes from; only the consumer moves it
+// - each side reads the other's index to know if it's full / empty
+//
+// Because each index has exactly one writer there's no compare_exchange,
+// just load/store with the right ordering:
+//
+//   push: write slot, then tail.store(Release)  -> the slot is published
+//   pop:  tail.load(Acquire), then read slot     -> sees the published slot
+//   (and the mirror image with head: a slot is only reused after the
+//    consumer's Release store says it's done reading it)
+//
+// Indexes count modulo 2 * capacity, so head == tail means empty and
+// tail - head == capacity means full, without wasting a slot; the slot
+// itself is index % capacity.
+//
+// "Single" is enforced by the types: `channel` hands out one `Producer`
+// and one `Consumer`, neither is Clone, and the mutating methods take
+// `&mut self`.
+//
+// Built against loom's types under `--cfg loom` so the loom tests can run
+// every interleaving:
+//
+//   RUSTFLAGS="--cfg loom" cargo test --test spsc_loom --release
+
+use std::fmt;
+use std::mem::MaybeUninit;
+
+#[cfg(loom)]
+use loom::sync::Arc;
+#[cfg(loom)]
+use loom::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[cfg(not(loom))]
+use std::sync::Arc;
+#[cfg(not(loom))]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[cfg(loom)]
+use loom::cell::UnsafeCell;
+
+// Same shape as loom's UnsafeCell: access through closures over raw pointers
+#[cfg(not(loom))]
+struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+#[cfg(not(loom))]
+impl<T> UnsafeCell<T> {
+    fn new(value: T) -> Self {
+        Self(std::cell::UnsafeCell::new(value))
+    }
+
+    fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+        f(self.0.get())
+    }
+
+    fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
+
+/// Wait a little before retrying: spin at first, then give the CPU away.
+#[cfg(not(loom))]
+fn backoff(step: &mut u32) {
+    if *step < 6 {
+        for _ in 0..1 << *step {
+            std::hint::spin_loop();
+        }
+        *step += 1;
+    } else {
+        std::thread::yield_now();
+    }
+}
+
+// loom has to see every retry as a yield, or it explores the spin forever
+#[cfg(loom)]
+fn backoff(_step: &mut u32) {
+    loom::thread::yield_now();
+}
+
+struct Shared<T> {
+    buf: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    /// Next slot to read, modulo 2 * capacity
+    head: AtomicUsize,
+    /// Next slot to write, modulo 2 * capacity
+    tail: AtomicUsize,
+    producer_gone: AtomicBool,
+    consumer_gone: AtomicBool,
+}
+
+// Slots are only touched by the side that owns them at that moment (see
+// the orderings above), so sharing the buffer is fine for any T: Send
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn next(&self, pos: usize) -> usize {
+        (pos + 1) % (2 * self.capacity())
+    }
+
+    fn len(&self, head: usize, tail: usize) -> usize {
+        (tail + 2 * self.capacity() - head) % (2 * self.capacity())
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        // Both sides are gone; whatever was pushed and never popped
+        let mut head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        while head != tail {
+            let slot = &self.buf[head % self.capacity()];
+            slot.with_mut(|p| unsafe { (*p).assume_init_drop() });
+            head = self.next(head);
+        }
+    }
+}
+
+/// Create a ring buffer holding up to `capacity` items.
+///
+/// # Panics
+///
+/// If `capacity` is 0.
+pub fn channel<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    assert!(capacity > 0, "capacity must be at least 1");
+    let buf = (0..capacity)
+        .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+        .collect();
+    let shared = Arc::new(Shared {
+        buf,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        producer_gone: AtomicBool::new(false),
+        consumer_gone: AtomicBool::new(false),
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer { shared },
+    )
+}
+
+#[derive(PartialEq, Eq)]
+pub enum PushError<T> {
+    /// No room right now; here's the value back
+    Full(T),
+    /// The consumer is gone; here's the value back
+    Disconnected(T),
+}
+
+// Manual so that T doesn't need to be Debug
+impl<T> fmt::Debug for PushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushError::Full(_) => f.write_str("Full(..)"),
+            PushError::Disconnected(_) => f.write_str("Disconnected(..)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopError {
+    /// Nothing yet
+    Empty,
+    /// Nothing, and the producer is gone so nothing is coming
+    Disconnected,
+}
+
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Producer<T> {
+    pub fn try_push(&mut self, value: T) -> Result<(), PushError<T>> {
+        let shared = &*self.shared;
+        if shared.consumer_gone.load(Ordering::Acquire) {
+            return Err(PushError::Disconnected(value));
+        }
+
+        let tail = shared.tail.load(Ordering::Relaxed);
+        // Acquire: the consumer is done reading every slot before head
+        let head = shared.head.load(Ordering::Acquire);
+        if shared.len(head, tail) == shared.capacity() {
+            return Err(PushError::Full(value));
+        }
+
+        let slot = &shared.buf[tail % shared.capacity()];
+        slot.with_mut(|p| unsafe { (*p).write(value) });
+        // Release: publish the slot we just wrote
+        shared.tail.store(shared.next(tail), Ordering::Release);
+        Ok(())
+    }
+
+    /// Push, waiting for room if the buffer is full. Gives the value back
+    /// if the consumer is gone.
+    pub fn push(&mut self, mut value: T) -> Result<(), T> {
+        let mut step = 0;
+        loop {
+            match self.try_push(value) {
+                Ok(()) => return Ok(()),
+                Err(PushError::Full(v)) => {
+                    value = v;
+                    backoff(&mut step);
+                }
+                Err(PushError::Disconnected(v)) => return Err(v),
+            }
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Items waiting; may already be smaller by the time you look at it.
+    pub fn len(&self) -> usize {
+        let head = self.shared.head.load(Ordering::Acquire);
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        self.shared.len(head, tail)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for Producer<T> {
+    fn drop(&mut self) {
+        // Release: every push happens before the consumer sees this
+        self.shared.producer_gone.store(true, Ordering::Release);
+    }
+}
+
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Consumer<T> {
+    pub fn try_pop(&mut self) -> Result<T, PopError> {
+        let shared = &*self.shared;
+        let head = shared.head.load(Ordering::Relaxed);
+        // Acquire: see the slots the producer published
+        let mut tail = shared.tail.load(Ordering::Acquire);
+        if head == tail {
+            if !shared.producer_gone.load(Ordering::Acquire) {
+                return Err(PopError::Empty);
+            }
+            // It may have pushed right before leaving
+            tail = shared.tail.load(Ordering::Acquire);
+            if head == tail {
+                return Err(PopError::Disconnected);
+            }
+        }
+
+        let slot = &shared.buf[head % shared.capacity()];
+        let value = slot.with(|p| unsafe { (*p).assume_init_read() });
+        // Release: the producer may reuse the slot now
+        shared.head.store(shared.next(head), Ordering::Release);
+        Ok(value)
+    }
+
+    /// Pop, waiting for an item if the buffer is empty. `None` once the
+    /// producer is gone and everything it pushed has been popped.
+    pub fn pop(&mut self) -> Option<T> {
+        let mut step = 0;
+        loop {
+            match self.try_pop() {
+                Ok(value) => return Some(value),
+                Err(PopError::Empty) => backoff(&mut step),
+                Err(PopError::Disconnected) => return None,
+            }
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Items waiting; may already be bigger by the time you look at it.
+    pub fn len(&self) -> usize {
+        let head = self.shared.head.load(Ordering::Relaxed);
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        self.shared.len(head, tail)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for Consumer<T> {
+    fn drop(&mut self) {
+        self.shared.consumer_gone.store(true, Ordering::Release);
+    }
+}
+
+// Blocking iteration until the producer is gone
+impl<T> Iterator for Consumer<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
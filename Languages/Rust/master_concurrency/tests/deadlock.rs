@@ -0,0 +1,90 @@
+use std::sync::{Arc, Barrier, mpsc};
+use std::thread;
+use std::time::Duration;
+
+use master_concurrency::deadlock::{
+    LockOrderError, OrderedMutex, TryLockOrdered, ab_ba, lock_both,
+};
+
+#[test]
+fn opposite_lock_order_deadlocks() {
+    assert!(ab_ba(Duration::from_millis(100)));
+}
+
+#[test]
+fn locking_downwards_is_rejected() {
+    let a = OrderedMutex::new(1, ());
+    let b = OrderedMutex::new(2, ());
+
+    let _b = b.try_lock_ordered().unwrap();
+
+    assert_eq!(
+        a.try_lock_ordered().err(),
+        Some(LockOrderError {
+            held: 2,
+            requested: 1
+        })
+    );
+}
+
+#[test]
+fn same_rank_twice_is_rejected() {
+    let a = OrderedMutex::new(3, ());
+
+    let _a = a.try_lock_ordered().unwrap();
+
+    // Would self-deadlock on a plain mutex
+    assert!(a.try_lock_ordered().is_err());
+}
+
+#[test]
+fn dropping_guards_in_any_order_releases_ranks() {
+    let a = OrderedMutex::new(1, ());
+    let b = OrderedMutex::new(2, ());
+
+    let ga = a.try_lock_ordered().unwrap();
+    let gb = b.try_lock_ordered().unwrap();
+    drop(ga);
+    drop(gb);
+
+    let _b = b.try_lock_ordered().unwrap();
+    drop(_b);
+    let _a = a.try_lock_ordered().unwrap();
+}
+
+#[test]
+fn ordered_ab_ba_threads_both_finish() {
+    let a = Arc::new(OrderedMutex::new(1, 0u32));
+    let b = Arc::new(OrderedMutex::new(2, 0u32));
+    // Same setup as the deadlock: both threads start together and name
+    // the locks in opposite orders
+    let start = Arc::new(Barrier::new(2));
+    let (done_tx, done_rx) = mpsc::channel();
+
+    for a_first in [true, false] {
+        let (a, b, start) = (Arc::clone(&a), Arc::clone(&b), Arc::clone(&start));
+        let done_tx = done_tx.clone();
+        thread::spawn(move || {
+            start.wait();
+            for _ in 0..10_000 {
+                let (mut ga, mut gb) = if a_first {
+                    lock_both(&*a, &*b).unwrap()
+                } else {
+                    let (gb, ga) = lock_both(&*b, &*a).unwrap();
+                    (ga, gb)
+                };
+                *ga += 1;
+                *gb += 1;
+            }
+            done_tx.send(()).unwrap();
+        });
+    }
+
+    for _ in 0..2 {
+        done_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("ordered locking deadlocked");
+    }
+    assert_eq!(*a.try_lock_ordered().unwrap(), 20_000);
+    assert_eq!(*b.try_lock_ordered().unwrap(), 20_000);
+}
@@ -0,0 +1,124 @@
+#![cfg(not(loom))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use master_concurrency::spsc::{PopError, PushError, channel};
+
+#[test]
+fn try_push_fails_when_full_and_try_pop_when_empty() {
+    let (mut tx, mut rx) = channel(2);
+
+    assert_eq!(rx.try_pop(), Err(PopError::Empty));
+    tx.try_push(1).unwrap();
+    tx.try_push(2).unwrap();
+    assert_eq!(tx.try_push(3), Err(PushError::Full(3)));
+    assert_eq!(tx.len(), 2);
+
+    assert_eq!(rx.try_pop(), Ok(1));
+    tx.try_push(3).unwrap();
+    assert_eq!(rx.try_pop(), Ok(2));
+    assert_eq!(rx.try_pop(), Ok(3));
+    assert!(rx.is_empty());
+}
+
+#[test]
+fn wraps_around_many_times() {
+    let (mut tx, mut rx) = channel(3);
+
+    for i in 0..100 {
+        tx.try_push(i).unwrap();
+        if i % 2 == 1 {
+            tx.try_push(-i).unwrap();
+            assert_eq!(rx.try_pop(), Ok(i - 1));
+            assert_eq!(rx.try_pop(), Ok(i));
+            assert_eq!(rx.try_pop(), Ok(-i));
+        }
+    }
+}
+
+#[test]
+fn items_arrive_in_order_across_threads() {
+    const N: u64 = 200_000;
+    let (mut tx, rx) = channel(64);
+
+    let producer = thread::spawn(move || {
+        for i in 0..N {
+            tx.push(i).unwrap();
+        }
+    });
+
+    let received: Vec<u64> = rx.collect();
+    producer.join().unwrap();
+    assert_eq!(received, (0..N).collect::<Vec<_>>());
+}
+
+#[test]
+fn capacity_one_still_works_across_threads() {
+    let (mut tx, mut rx) = channel(1);
+
+    let consumer = thread::spawn(move || {
+        let mut sum = 0;
+        while let Some(x) = rx.pop() {
+            sum += x;
+        }
+        sum
+    });
+    for i in 1..=1000 {
+        tx.push(i).unwrap();
+    }
+    drop(tx);
+
+    assert_eq!(consumer.join().unwrap(), 500_500);
+}
+
+#[test]
+fn consumer_drains_after_producer_leaves() {
+    let (mut tx, mut rx) = channel(4);
+    tx.try_push("a").unwrap();
+    tx.try_push("b").unwrap();
+    drop(tx);
+
+    assert_eq!(rx.try_pop(), Ok("a"));
+    assert_eq!(rx.pop(), Some("b"));
+    assert_eq!(rx.try_pop(), Err(PopError::Disconnected));
+    assert_eq!(rx.pop(), None);
+}
+
+#[test]
+fn push_fails_once_consumer_is_gone() {
+    let (mut tx, rx) = channel(4);
+    drop(rx);
+
+    assert_eq!(tx.try_push(1), Err(PushError::Disconnected(1)));
+    assert_eq!(tx.push(2), Err(2));
+}
+
+#[test]
+fn unpopped_items_are_dropped_with_the_buffer() {
+    struct Counted(Arc<AtomicUsize>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut tx, mut rx) = channel(4);
+    for _ in 0..3 {
+        tx.try_push(Counted(Arc::clone(&drops))).unwrap();
+    }
+    drop(rx.try_pop().unwrap());
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    drop(tx);
+    drop(rx);
+    assert_eq!(drops.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be at least 1")]
+fn zero_capacity_is_rejected() {
+    let _ = channel::<u8>(0);
+}
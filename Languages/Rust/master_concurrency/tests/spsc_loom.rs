@@ -0,0 +1,75 @@
+//! Every interleaving of the SPSC ring buffer, checked by loom.
+//!
+//! RUSTFLAGS="--cfg loom" cargo test --test spsc_loom --release
+#![cfg(loom)]
+
+use loom::thread;
+
+use master_concurrency::spsc::{PopError, PushError, channel};
+
+#[test]
+fn items_arrive_once_and_in_order() {
+    loom::model(|| {
+        // Capacity 2 with 3 items: the producer has to wait for a slot to
+        // be freed, so slot reuse is covered too
+        let (mut tx, mut rx) = channel(2);
+
+        let producer = thread::spawn(move || {
+            for i in 0..3 {
+                tx.push(i).unwrap();
+            }
+        });
+
+        let mut got = Vec::new();
+        while let Some(x) = rx.pop() {
+            got.push(x);
+        }
+        producer.join().unwrap();
+        assert_eq!(got, [0, 1, 2]);
+    });
+}
+
+#[test]
+fn last_push_before_drop_is_not_lost() {
+    loom::model(|| {
+        let (mut tx, mut rx) = channel(1);
+
+        let producer = thread::spawn(move || {
+            tx.try_push(7).unwrap();
+            // tx dropped here, racing with the consumer's empty check
+        });
+
+        let got = loop {
+            match rx.try_pop() {
+                Ok(x) => break Some(x),
+                Err(PopError::Empty) => thread::yield_now(),
+                Err(PopError::Disconnected) => break None,
+            }
+        };
+        producer.join().unwrap();
+        assert_eq!(got, Some(7));
+    });
+}
+
+#[test]
+fn push_sees_consumer_leave() {
+    loom::model(|| {
+        let (mut tx, rx) = channel(1);
+
+        let consumer = thread::spawn(move || drop(rx));
+
+        // Fill it (unless the consumer is already gone), then keep trying:
+        // must end in Disconnected, never hang
+        if let Err(e) = tx.try_push(1) {
+            assert_eq!(e, PushError::Disconnected(1));
+        }
+        loop {
+            match tx.try_push(2) {
+                Err(PushError::Full(_)) => thread::yield_now(),
+                Err(PushError::Disconnected(_)) => break,
+                Ok(()) => unreachable!("nobody popped"),
+            }
+        }
+        consumer.join().unwrap();
+    });
+}
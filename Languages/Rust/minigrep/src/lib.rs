@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
     // unimplemented!();
     //println!("{:?}", contents);
@@ -30,6 +32,66 @@ pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a st
     results
 }
 
+/// Byte ranges of the non-overlapping occurrences of `query` in `line`,
+/// left to right.
+pub fn match_spans(query: &str, line: &str, ignore_case: bool) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    if query.is_empty() {
+        return spans;
+    }
+
+    let mut from = 0;
+    while from < line.len() {
+        let found = line[from..].char_indices().find_map(|(i, _)| {
+            match_len(query, &line[from + i..], ignore_case).map(|len| (from + i, len))
+        });
+        match found {
+            Some((start, len)) => {
+                spans.push(start..start + len);
+                from = start + len;
+            }
+            None => break,
+        }
+    }
+    spans
+}
+
+// Length in bytes of the start of `text` that matches `query`, compared
+// char by char so lowercasing can't shift the byte offsets
+fn match_len(query: &str, text: &str, ignore_case: bool) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for q in query.chars() {
+        let (_, c) = chars.next()?;
+        let same = if ignore_case {
+            c.to_lowercase().eq(q.to_lowercase())
+        } else {
+            c == q
+        };
+        if !same {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(i, _)| i))
+}
+
+const HIGHLIGHT: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+/// `line` with every span wrapped in bold red ANSI codes.
+pub fn highlight(line: &str, spans: &[Range<usize>]) -> String {
+    let mut out = String::with_capacity(line.len() + spans.len() * 11);
+    let mut last = 0;
+    for span in spans {
+        out.push_str(&line[last..span.start]);
+        out.push_str(HIGHLIGHT);
+        out.push_str(&line[span.clone()]);
+        out.push_str(RESET);
+        last = span.end;
+    }
+    out.push_str(&line[last..]);
+    out
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -77,4 +139,26 @@ Trust me.";
             search_case_insensitive(query, contents)
         );
     }
+
+    #[test]
+    fn spans_of_every_match() {
+        assert_eq!(match_spans("ab", "abcab ab", false), vec![0..2, 3..5, 6..8]);
+        assert_eq!(match_spans("aa", "aaa", false), vec![0..2]);
+        assert!(match_spans("AB", "abc", false).is_empty());
+    }
+
+    #[test]
+    fn spans_ignoring_case_keep_byte_offsets() {
+        assert_eq!(match_spans("rust", "Trust RUST", true), vec![1..5, 6..10]);
+        assert_eq!(match_spans("é", "cafÉ é", true), vec![3..5, 6..8]);
+    }
+
+    #[test]
+    fn highlight_wraps_spans() {
+        assert_eq!(
+            highlight("a duct tape", &[2..6, 7..11]),
+            "a \x1b[1;31mduct\x1b[0m \x1b[1;31mtape\x1b[0m"
+        );
+        assert_eq!(highlight("plain", &[]), "plain");
+    }
 }
@@ -1,6 +1,9 @@
-use minigrep::{search, search_case_insensitive};
+use minigrep::{highlight, match_spans};
 use std::error::Error;
-use std::{env, fs, process};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal};
+use std::time::Instant;
+use std::{env, process};
 
 fn main() {
     //let args: Vec<String> = env::args().collect();
@@ -19,24 +22,64 @@ fn main() {
 }
 
 fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
+    let started = Instant::now();
+    let mut lines_matched = 0;
 
-    let results = if config.ignore_case {
-        search_case_insensitive(&config.query, &contents)
+    for file_path in &config.file_paths {
+        lines_matched += search_file(&config, file_path)?;
+    }
+
+    if config.stats {
+        // stderr, so the summary never mixes into piped matches
+        eprintln!(
+            "{} files searched, {} lines matched in {:.2?}",
+            config.file_paths.len(),
+            lines_matched,
+            started.elapsed()
+        );
+    }
+    Ok(())
+}
+
+// Prints the matching lines of one file and returns how many there were.
+// Lines are read one at a time, so --max-count stops reading the file at
+// the Nth match instead of loading all of it
+fn search_file(config: &Config, file_path: &str) -> io::Result<usize> {
+    let reader = BufReader::new(File::open(file_path)?);
+    let prefix = if config.file_paths.len() > 1 {
+        format!("{file_path}:")
     } else {
-        search(&config.query, &contents)
+        String::new()
     };
 
-    for line in results {
-        println!("{line}")
+    let mut matched = 0;
+    for line in reader.lines() {
+        if config.max_count.is_some_and(|max| matched >= max) {
+            break;
+        }
+        let line = line?;
+        let spans = match_spans(&config.query, &line, config.ignore_case);
+        if spans.is_empty() {
+            continue;
+        }
+
+        matched += 1;
+        if config.color {
+            println!("{prefix}{}", highlight(&line, &spans));
+        } else {
+            println!("{prefix}{line}");
+        }
     }
-    Ok(())
+    Ok(matched)
 }
 
 pub struct Config {
     pub query: String,
-    pub file_path: String,
+    pub file_paths: Vec<String>,
     pub ignore_case: bool,
+    pub stats: bool,
+    pub max_count: Option<usize>,
+    pub color: bool,
 }
 
 // fn parse_config(args: &[String]) -> Config {
@@ -64,22 +107,55 @@ impl Config {
 
         args.next(); // iterate over the first one
 
-        let query = match args.next() {
+        // minigrep [--stats] [--max-count N] [--color|--no-color] query file...
+        let mut stats = false;
+        let mut max_count = None;
+        let mut color = None;
+        let mut positional = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--stats" => stats = true,
+                "--color" => color = Some(true),
+                "--no-color" => color = Some(false),
+                "--max-count" | "-m" => {
+                    let n = args.next().ok_or("--max-count needs a number")?;
+                    max_count = Some(parse_count(&n)?);
+                }
+                _ => match arg.strip_prefix("--max-count=") {
+                    Some(n) => max_count = Some(parse_count(n)?),
+                    None => positional.push(arg),
+                },
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let query = match positional.next() {
             Some(arg) => arg,
             None => return Err("Didn't get a query string"),
         };
 
-        let file_path = match args.next() {
-            Some(arg) => arg,
-            None => return Err("Didn't get a file path"),
-        };
+        let file_paths: Vec<String> = positional.collect();
+        if file_paths.is_empty() {
+            return Err("Didn't get a file path");
+        }
 
         let ignore_case = env::var("IGNORE_CASE").is_ok();
+        // Color by default only on a terminal, and never with NO_COLOR set
+        let color = color
+            .unwrap_or_else(|| io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none());
 
         Ok(Config {
             query,
-            file_path,
+            file_paths,
             ignore_case,
+            stats,
+            max_count,
+            color,
         })
     }
 }
+
+fn parse_count(n: &str) -> Result<usize, &'static str> {
+    n.parse().map_err(|_| "--max-count needs a number")
+}
@@ -0,0 +1,350 @@
+// Echo benchmark: how fast does an echo server answer?
+//
+// Opens N connections to the server at once. Each connection sends a
+// message, waits for all of it to come back, and does it again until the
+// time is up. Every round trip is timed, so the report has throughput (round
+// trips and bytes per second) and the latency distribution (p50/p90/p99/max)
+// - enough to compare buffer sizes, TCP_NODELAY or read strategies with
+// numbers instead of impressions.
+//
+//   cargo run --release --bin bench -- --connections 100 --size 1024
+//
+// One tokio task per connection: the runtime waits on all the sockets at
+// once with epoll, the way the servers under test do.
+
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+
+/// What to run.
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    pub addr: SocketAddr,
+    /// Concurrent connections
+    pub connections: usize,
+    /// How long to measure for
+    pub duration: Duration,
+    /// Not measured: lets connections and the server settle first
+    pub warmup: Duration,
+    /// Bytes per message, newline included
+    pub message_size: usize,
+    /// Set TCP_NODELAY on the client sockets
+    pub nodelay: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            addr: ([127, 0, 0, 1], 8080).into(),
+            connections: 50,
+            duration: Duration::from_secs(10),
+            warmup: Duration::from_secs(1),
+            message_size: 64,
+            nodelay: true,
+        }
+    }
+}
+
+/// Results of a run.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub config: BenchConfig,
+    /// Measured time (without the warmup)
+    pub elapsed: Duration,
+    /// Round-trip latencies, sorted
+    pub latencies: Vec<Duration>,
+    /// Connections that failed (couldn't connect, reset, short echo)
+    pub errors: Vec<String>,
+}
+
+impl Report {
+    pub fn round_trips(&self) -> usize {
+        self.latencies.len()
+    }
+
+    /// Round trips per second.
+    pub fn throughput(&self) -> f64 {
+        self.round_trips() as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Bytes per second each way.
+    pub fn bytes_per_sec(&self) -> f64 {
+        self.throughput() * self.config.message_size as f64
+    }
+
+    /// The `p`th percentile (0.0..=100.0) of the latencies, nearest rank.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let rank = (p / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        let total: Duration = self.latencies.iter().sum();
+        let count = u32::try_from(self.latencies.len()).ok()?;
+        total.checked_div(count)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = &self.config;
+        writeln!(
+            f,
+            "{} connections, {} byte messages, {:.1}s against {} (nodelay: {})",
+            c.connections,
+            c.message_size,
+            self.elapsed.as_secs_f64(),
+            c.addr,
+            c.nodelay
+        )?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "  round trips  {:>12} ({:.0}/s)",
+            self.round_trips(),
+            self.throughput()
+        )?;
+        writeln!(
+            f,
+            "  throughput   {:>12} each way",
+            format!("{}/s", human_bytes(self.bytes_per_sec()))
+        )?;
+
+        let Some(mean) = self.mean() else {
+            writeln!(f, "  latency      no round trips completed")?;
+            return self.fmt_errors(f);
+        };
+        writeln!(f)?;
+        writeln!(f, "  latency")?;
+        let rows = [
+            ("mean", Some(mean)),
+            ("p50", self.percentile(50.0)),
+            ("p90", self.percentile(90.0)),
+            ("p99", self.percentile(99.0)),
+            ("max", self.latencies.last().copied()),
+        ];
+        for (name, value) in rows {
+            if let Some(value) = value {
+                writeln!(f, "    {name:<4} {:>12}", format!("{value:.1?}"))?;
+            }
+        }
+        self.fmt_errors(f)
+    }
+}
+
+impl Report {
+    fn fmt_errors(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        writeln!(f)?;
+        writeln!(f, "  {} connection(s) failed:", self.errors.len())?;
+        for error in self.errors.iter().take(5) {
+            writeln!(f, "    {error}")?;
+        }
+        if self.errors.len() > 5 {
+            writeln!(f, "    ... and {} more", self.errors.len() - 5)?;
+        }
+        Ok(())
+    }
+}
+
+fn human_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Run the benchmark. Fails only if no connection could be opened at all;
+/// connections that fail later are listed in the report.
+pub async fn run(config: BenchConfig) -> io::Result<Report> {
+    let start = Instant::now() + config.warmup;
+    let end = start + config.duration;
+
+    let mut tasks = JoinSet::new();
+    for _ in 0..config.connections {
+        let config = config.clone();
+        tasks.spawn(async move { connection(&config, start, end).await });
+    }
+
+    let mut latencies = Vec::new();
+    let mut errors = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            // A connection that fails mid-run still measured something;
+            // dropping it would leave only the survivors in the percentiles
+            Ok((measured, outcome)) => {
+                latencies.extend(measured);
+                if let Err(e) = outcome {
+                    errors.push(e.to_string());
+                }
+            }
+            Err(e) => errors.push(format!("task failed: {e}")),
+        }
+    }
+
+    if errors.len() == config.connections {
+        return Err(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            format!(
+                "no connection to {} succeeded: {}",
+                config.addr, errors[0]
+            ),
+        ));
+    }
+
+    latencies.sort_unstable();
+    Ok(Report { config, elapsed: end - start, latencies, errors })
+}
+
+/// `size` bytes of text ending in a newline, so line-based echo servers
+/// (lb_echo_server) answer it as well as byte-for-byte ones.
+fn message(size: usize) -> Vec<u8> {
+    let mut message: Vec<u8> =
+        (0..size).map(|i| b'a' + (i % 26) as u8).collect();
+    if let Some(last) = message.last_mut() {
+        *last = b'\n';
+    }
+    message
+}
+
+/// One client: echo round trips until `end`, timing those after `start`.
+/// Returns the latencies measured even if the connection then failed.
+async fn connection(
+    config: &BenchConfig,
+    start: Instant,
+    end: Instant,
+) -> (Vec<Duration>, io::Result<()>) {
+    let mut latencies = Vec::new();
+    let outcome = round_trips(config, start, end, &mut latencies).await;
+    (latencies, outcome)
+}
+
+async fn round_trips(
+    config: &BenchConfig,
+    start: Instant,
+    end: Instant,
+    latencies: &mut Vec<Duration>,
+) -> io::Result<()> {
+    let mut stream = TcpStream::connect(config.addr).await?;
+    stream.set_nodelay(config.nodelay)?;
+
+    let message = message(config.message_size);
+    let mut echo = vec![0u8; config.message_size];
+
+    loop {
+        let sent = Instant::now();
+        if sent >= end {
+            return Ok(());
+        }
+
+        stream.write_all(&message).await?;
+        // The server may echo in pieces: wait for all of it
+        stream.read_exact(&mut echo).await.map_err(|e| {
+            io::Error::new(e.kind(), format!("reading the echo: {e}"))
+        })?;
+        if echo != message {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "echo differs from the message sent",
+            ));
+        }
+
+        if sent >= start {
+            latencies.push(sent.elapsed());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(millis: impl IntoIterator<Item = u64>) -> Report {
+        Report {
+            config: BenchConfig::default(),
+            elapsed: Duration::from_secs(1),
+            latencies: millis.into_iter().map(Duration::from_millis).collect(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn ms(n: u64) -> Option<Duration> {
+        Some(Duration::from_millis(n))
+    }
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        let hundred = report(1..=100);
+        assert_eq!(hundred.percentile(50.0), ms(50));
+        assert_eq!(hundred.percentile(99.0), ms(99));
+        assert_eq!(hundred.percentile(100.0), ms(100));
+        assert_eq!(hundred.percentile(0.0), ms(1));
+
+        // Rank ceil(0.5 * 3) = 2, ceil(0.99 * 3) = 3
+        let three = report([10, 20, 30]);
+        assert_eq!(three.percentile(50.0), ms(20));
+        assert_eq!(three.percentile(99.0), ms(30));
+        assert_eq!(three.percentile(100.0), ms(30));
+    }
+
+    #[test]
+    fn percentiles_of_one_sample_and_of_none() {
+        let single = report([7]);
+        for p in [0.0, 50.0, 99.0, 100.0] {
+            assert_eq!(single.percentile(p), ms(7));
+        }
+        assert_eq!(single.mean(), ms(7));
+
+        let empty = report([]);
+        assert_eq!(empty.percentile(50.0), None);
+        assert_eq!(empty.percentile(100.0), None);
+        assert_eq!(empty.mean(), None);
+    }
+
+    #[tokio::test]
+    async fn a_connection_failing_mid_run_keeps_its_latencies() {
+        use tokio::net::TcpListener;
+
+        // Echoes three messages, then hangs up
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = BenchConfig {
+            addr: listener.local_addr().unwrap(),
+            connections: 1,
+            duration: Duration::from_secs(10),
+            warmup: Duration::ZERO,
+            message_size: 16,
+            nodelay: true,
+        };
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 16];
+            for _ in 0..3 {
+                stream.read_exact(&mut buf).await.unwrap();
+                stream.write_all(&buf).await.unwrap();
+            }
+        });
+
+        let start = Instant::now();
+        let (latencies, outcome) =
+            connection(&config, start, start + config.duration).await;
+
+        assert_eq!(latencies.len(), 3);
+        let error = outcome.expect_err("the server hung up");
+        assert!(error.to_string().contains("reading the echo"), "{error}");
+    }
+}
@@ -0,0 +1,106 @@
+// Load an echo server and report throughput and latency
+//
+// Start a server (cargo run -- serve echo), then:
+//
+//   cargo run --release --bin bench -- [options]
+//
+//   --addr HOST:PORT     server to load            (127.0.0.1:8080)
+//   --connections N      concurrent connections    (50)
+//   --duration SECS      measured time             (10)
+//   --warmup SECS        unmeasured time first     (1)
+//   --size BYTES         message size              (64)
+//   --no-nodelay         leave Nagle's algorithm on
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use networking::bench::{self, BenchConfig};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let config = match parse_args(std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("bench: {e}");
+            eprintln!(
+                "usage: bench [--addr HOST:PORT] [--connections N] \
+                 [--duration SECS] [--warmup SECS] [--size BYTES] \
+                 [--no-nodelay]"
+            );
+            return ExitCode::from(2);
+        }
+    };
+
+    println!(
+        "benchmarking {} with {} connections for {:?} (+{:?} warmup)...",
+        config.addr, config.connections, config.duration, config.warmup
+    );
+    match bench::run(config).await {
+        Ok(report) => {
+            println!("\n{report}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("bench: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<BenchConfig, String> {
+    let mut config = BenchConfig::default();
+
+    while let Some(flag) = args.next() {
+        if flag == "--no-nodelay" {
+            config.nodelay = false;
+            continue;
+        }
+
+        let value = args.next().ok_or(format!("{flag} needs a value"))?;
+        let invalid =
+            |e: &dyn std::fmt::Display| format!("{flag} {value}: {e}");
+        match flag.as_str() {
+            "--addr" => {
+                config.addr = value.parse().map_err(|e| invalid(&e))?
+            }
+            "--connections" => {
+                config.connections = value.parse().map_err(|e| invalid(&e))?
+            }
+            "--duration" => {
+                config.duration = Duration::from_secs_f64(
+                    parse_secs(&value).map_err(|e| invalid(&e))?,
+                )
+            }
+            "--warmup" => {
+                config.warmup = Duration::from_secs_f64(
+                    parse_secs(&value).map_err(|e| invalid(&e))?,
+                )
+            }
+            "--size" => {
+                config.message_size = value.parse().map_err(|e| invalid(&e))?
+            }
+            _ => return Err(format!("unknown option {flag}")),
+        }
+    }
+
+    if config.connections == 0 {
+        return Err("--connections must be at least 1".into());
+    }
+    if config.message_size == 0 {
+        return Err("--size must be at least 1".into());
+    }
+    if config.duration.is_zero() {
+        return Err("--duration must be more than 0".into());
+    }
+    Ok(config)
+}
+
+fn parse_secs(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(secs) if secs.is_finite() && secs >= 0.0 => Ok(secs),
+        Ok(_) => Err("must be a non-negative number of seconds".into()),
+        Err(e) => Err(e.to_string()),
+    }
+}
@@ -0,0 +1,552 @@
+// Config file for `networking serve`
+//
+//   networking serve echo|lb|proxy [--config net.toml]
+//
+// One file can configure every server; `serve <kind>` reads the table of
+// the same name and ignores the others. A missing table means defaults,
+// which for lb and proxy isn't enough: they need backends.
+//
+//   [echo]                              # line echo server
+//   listen = "127.0.0.1:8080"
+//   max_connections = 1000              # default: no limit
+//   write_timeout_secs = 5
+//
+//   [lb]                                # TLS balancer, routes on SNI
+//   listen = "127.0.0.1:8443"
+//   cert_dir = "certs"
+//   cert_reload_secs = 5
+//   handshake_timeout_secs = 10
+//   max_connections = 1000
+//   backends = ["127.0.0.1:9000"]       # whatever no route claims
+//
+//   [[lb.routes]]
+//   host = "api.local"                  # or "*.web.local"
+//   backends = ["127.0.0.1:9001", "127.0.0.1:9002"]
+//
+//   [proxy]                             # TLS, routes each HTTP request
+//   backends = ["127.0.0.1:9000"]       # same settings as [lb]
+//
+//   [[proxy.routes]]
+//   host = "api.local"                  # host, path or both
+//   path = "/v1"
+//   backends = ["127.0.0.1:9001"]
+//
+// Validation reports every problem at once, each naming the table and
+// field, e.g. `[proxy.routes.2] missing `backends``, so a broken file
+// takes one edit instead of one run per mistake.
+
+use std::error::Error;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::lb_echo_server::{
+    BalancerConfig, EchoConfig, HANDSHAKE_TIMEOUT, HttpRoutes, Mode, Pool,
+    Routes,
+};
+
+/// Which server `networking serve` runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerKind {
+    /// Line echo server (lb_echo_server::serve)
+    Echo,
+    /// TLS balancer routing whole connections on SNI
+    Lb,
+    /// TLS balancer routing each HTTP request on Host and path
+    Proxy,
+}
+
+impl ServerKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ServerKind::Echo => "echo",
+            ServerKind::Lb => "lb",
+            ServerKind::Proxy => "proxy",
+        }
+    }
+}
+
+impl FromStr for ServerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "echo" => Ok(ServerKind::Echo),
+            "lb" => Ok(ServerKind::Lb),
+            "proxy" => Ok(ServerKind::Proxy),
+            _ => Err(format!("unknown server '{}' (echo, lb or proxy)", s)),
+        }
+    }
+}
+
+/// A validated configuration for one server.
+#[derive(Debug)]
+pub enum ServerConfig {
+    Echo(EchoConfig),
+    /// Both lb and proxy: the mode tells them apart
+    Balancer(BalancerConfig),
+}
+
+/// Why a config file couldn't be used: every problem found in it.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.problems.as_slice() {
+            [problem] => write!(f, "{}: {}", self.path.display(), problem),
+            problems => {
+                write!(
+                    f,
+                    "{}: {} problems",
+                    self.path.display(),
+                    problems.len()
+                )?;
+                for problem in problems {
+                    write!(f, "\n  {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Read `path` and validate its table for `kind`.
+pub fn load(
+    path: &Path,
+    kind: ServerKind,
+) -> Result<ServerConfig, ConfigError> {
+    let error = |problems| ConfigError { path: path.to_path_buf(), problems };
+
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| error(vec![format!("cannot read: {}", e)]))?;
+    parse(&source, kind).map_err(error)
+}
+
+/// Validate the table for `kind` in a config file's contents.
+pub fn parse(
+    source: &str,
+    kind: ServerKind,
+) -> Result<ServerConfig, Vec<String>> {
+    let file: RawFile =
+        toml::from_str(source).map_err(|e| vec![e.to_string()])?;
+
+    let mut problems = Vec::new();
+    let config = match kind {
+        ServerKind::Echo => ServerConfig::Echo(
+            file.echo.unwrap_or_default().validate(&mut problems),
+        ),
+        ServerKind::Lb => ServerConfig::Balancer(
+            file.lb.unwrap_or_default().validate(kind, &mut problems),
+        ),
+        ServerKind::Proxy => ServerConfig::Balancer(
+            file.proxy.unwrap_or_default().validate(kind, &mut problems),
+        ),
+    };
+
+    if problems.is_empty() { Ok(config) } else { Err(problems) }
+}
+
+// ============================================================================
+// The file as written
+// ============================================================================
+//
+// Everything optional, so a missing field is reported by validation (with
+// the others) rather than by serde (alone). Unknown fields are still
+// rejected, so a typo doesn't silently fall back to a default.
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawFile {
+    echo: Option<RawEcho>,
+    lb: Option<RawBalancer>,
+    proxy: Option<RawBalancer>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEcho {
+    listen: Option<String>,
+    max_connections: Option<usize>,
+    write_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawBalancer {
+    listen: Option<String>,
+    cert_dir: Option<PathBuf>,
+    cert_reload_secs: Option<u64>,
+    handshake_timeout_secs: Option<u64>,
+    max_connections: Option<usize>,
+    backends: Option<Vec<String>>,
+    #[serde(default)]
+    routes: Vec<RawRoute>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRoute {
+    host: Option<String>,
+    path: Option<String>,
+    backends: Option<Vec<String>>,
+}
+
+impl RawEcho {
+    fn validate(self, problems: &mut Vec<String>) -> EchoConfig {
+        let defaults = EchoConfig::default();
+        let mut check = Checker { table: "[echo]".into(), problems };
+
+        EchoConfig {
+            listen: check.listen(self.listen).unwrap_or(defaults.listen),
+            max_connections: check.limit(self.max_connections),
+            write_timeout: check
+                .secs("write_timeout_secs", self.write_timeout_secs)
+                .unwrap_or(defaults.write_timeout),
+        }
+    }
+}
+
+impl RawBalancer {
+    fn validate(
+        self,
+        kind: ServerKind,
+        problems: &mut Vec<String>,
+    ) -> BalancerConfig {
+        let table = format!("[{}]", kind.name());
+        let mut check = Checker { table: table.clone(), problems };
+
+        let listen = check.listen(self.listen);
+        let reload_every =
+            check.secs("cert_reload_secs", self.cert_reload_secs);
+        let handshake_timeout =
+            check.secs("handshake_timeout_secs", self.handshake_timeout_secs);
+        let max_connections = check.limit(self.max_connections);
+        // Invalid backends are reported as such, not as missing
+        if self.backends.is_none() && self.routes.is_empty() {
+            check.problem(format!(
+                "missing `backends` (or a [[{}.routes]] entry)",
+                kind.name()
+            ));
+        }
+        let default = match self.backends {
+            Some(list) => check.pool("backends", list),
+            None => None,
+        };
+
+        let mut tcp = Routes::default();
+        let mut http = HttpRoutes::default();
+        if let Some(pool) = default {
+            match kind {
+                ServerKind::Proxy => http = http.with_default(pool),
+                _ => tcp = tcp.with_default(pool),
+            }
+        }
+        for (i, route) in self.routes.into_iter().enumerate() {
+            let mut check = Checker {
+                table: format!("[{}.routes.{}]", kind.name(), i + 1),
+                problems: &mut *check.problems,
+            };
+            let pool = match route.backends {
+                Some(list) => check.pool("backends", list),
+                None => {
+                    check.problem("missing `backends`".into());
+                    None
+                }
+            };
+
+            match kind {
+                ServerKind::Proxy => {
+                    let target = format!(
+                        "{}{}",
+                        route.host.as_deref().unwrap_or(""),
+                        route.path.as_deref().unwrap_or("")
+                    );
+                    if target.is_empty() {
+                        check.problem("needs `host`, `path` or both".into());
+                    }
+                    if route.path.as_ref().is_some_and(|p| !p.starts_with('/'))
+                    {
+                        check.problem("`path` must start with '/'".into());
+                    }
+                    if let Some(pool) = pool {
+                        http = http.with_route(&target, pool);
+                    }
+                }
+                _ => {
+                    if route.path.is_some() {
+                        check.problem(
+                            "`path` only applies to [[proxy.routes]]; lb \
+                             routes on the SNI name alone"
+                                .into(),
+                        );
+                    }
+                    match (route.host, pool) {
+                        (Some(host), Some(pool)) => {
+                            tcp = tcp.with_route(&host, pool)
+                        }
+                        (None, _) => check.problem("missing `host`".into()),
+                        (Some(_), None) => {}
+                    }
+                }
+            }
+        }
+
+        BalancerConfig {
+            listen: listen.map_or_else(
+                || "127.0.0.1:8443".to_string(),
+                |a| a.to_string(),
+            ),
+            cert_dir: self.cert_dir.unwrap_or_else(|| "certs".into()),
+            reload_every: reload_every.unwrap_or(Duration::from_secs(5)),
+            mode: match kind {
+                ServerKind::Proxy => Mode::Http(http),
+                _ => Mode::Tcp(tcp),
+            },
+            max_connections,
+            handshake_timeout: handshake_timeout.unwrap_or(HANDSHAKE_TIMEOUT),
+        }
+    }
+}
+
+/// Collects problems for one table, prefixed with its name.
+struct Checker<'a> {
+    table: String,
+    problems: &'a mut Vec<String>,
+}
+
+impl Checker<'_> {
+    fn problem(&mut self, problem: String) {
+        self.problems.push(format!("{} {}", self.table, problem));
+    }
+
+    fn listen(&mut self, listen: Option<String>) -> Option<SocketAddr> {
+        let listen = listen?;
+        match listen.parse() {
+            Ok(addr) => Some(addr),
+            Err(_) => {
+                self.problem(format!(
+                    "`listen`: \"{}\" is not an IP:PORT address",
+                    listen
+                ));
+                None
+            }
+        }
+    }
+
+    fn limit(&mut self, max: Option<usize>) -> Option<usize> {
+        if max == Some(0) {
+            self.problem("`max_connections` must be at least 1".into());
+            return None;
+        }
+        max
+    }
+
+    fn secs(&mut self, field: &str, secs: Option<u64>) -> Option<Duration> {
+        match secs? {
+            0 => {
+                self.problem(format!("`{}` must be at least 1", field));
+                None
+            }
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+
+    /// A pool from a list of `host:port` backends.
+    fn pool(&mut self, field: &str, list: Vec<String>) -> Option<Pool> {
+        if list.is_empty() {
+            self.problem(format!("`{}` is empty", field));
+            return None;
+        }
+        let mut ok = true;
+        for backend in &list {
+            let has_port =
+                backend.rsplit_once(':').is_some_and(|(host, port)| {
+                    !host.is_empty() && port.parse::<u16>().is_ok()
+                });
+            if !has_port {
+                self.problem(format!(
+                    "`{}`: \"{}\" is not a HOST:PORT address",
+                    field, backend
+                ));
+                ok = false;
+            }
+        }
+        ok.then(|| Pool::new(list))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(source: &str, kind: ServerKind) -> Vec<String> {
+        parse(source, kind).expect_err("config should be rejected")
+    }
+
+    #[test]
+    fn missing_tables_mean_defaults_for_echo() {
+        let Ok(ServerConfig::Echo(echo)) = parse("", ServerKind::Echo) else {
+            panic!("echo needs no settings");
+        };
+        let defaults = EchoConfig::default();
+        assert_eq!(echo.listen, defaults.listen);
+        assert_eq!(echo.max_connections, None);
+    }
+
+    #[test]
+    fn balancer_table_is_read() {
+        let source = r#"
+            [proxy]
+            listen = "0.0.0.0:9443"
+            max_connections = 10
+            backends = ["127.0.0.1:9000"]
+
+            [[proxy.routes]]
+            host = "api.local"
+            path = "/v1"
+            backends = ["127.0.0.1:9001"]
+        "#;
+
+        let Ok(ServerConfig::Balancer(config)) =
+            parse(source, ServerKind::Proxy)
+        else {
+            panic!("config should be accepted");
+        };
+        assert_eq!(config.listen, "0.0.0.0:9443");
+        assert_eq!(config.max_connections, Some(10));
+        let Mode::Http(routes) = config.mode else {
+            panic!("proxy routes each request");
+        };
+        let backend = |host, path| {
+            routes.pool_for(host, path).map(|pool| pool.backends()[0].clone())
+        };
+        assert_eq!(
+            backend(Some("api.local"), "/v1/x").unwrap(),
+            "127.0.0.1:9001"
+        );
+        assert_eq!(
+            backend(Some("api.local"), "/v2").unwrap(),
+            "127.0.0.1:9000"
+        );
+    }
+
+    #[test]
+    fn bad_ports_are_reported() {
+        let source = r#"
+            [lb]
+            listen = "127.0.0.1:99999"
+            backends = ["127.0.0.1", "127.0.0.1:http", "127.0.0.1:70000"]
+        "#;
+
+        assert_eq!(
+            problems(source, ServerKind::Lb),
+            [
+                "[lb] `listen`: \"127.0.0.1:99999\" is not an IP:PORT address",
+                "[lb] `backends`: \"127.0.0.1\" is not a HOST:PORT address",
+                "[lb] `backends`: \"127.0.0.1:http\" is not a HOST:PORT address",
+                "[lb] `backends`: \"127.0.0.1:70000\" is not a HOST:PORT address",
+            ]
+        );
+    }
+
+    #[test]
+    fn balancers_need_backends() {
+        assert_eq!(
+            problems("", ServerKind::Lb),
+            ["[lb] missing `backends` (or a [[lb.routes]] entry)"]
+        );
+        assert_eq!(
+            problems("[proxy]\nbackends = []\n", ServerKind::Proxy),
+            ["[proxy] `backends` is empty"]
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let problems = problems("[lb]\nbackend = [\"a:1\"]\n", ServerKind::Lb);
+
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("unknown field `backend`"),
+            "{problems:?}"
+        );
+    }
+
+    #[test]
+    fn every_problem_is_reported_at_once() {
+        let source = r#"
+            [proxy]
+            cert_reload_secs = 0
+            max_connections = 0
+            backends = ["127.0.0.1:9000"]
+
+            [[proxy.routes]]
+            host = "api.local"
+
+            [[proxy.routes]]
+            path = "v1"
+            backends = ["127.0.0.1:9001"]
+        "#;
+
+        assert_eq!(
+            problems(source, ServerKind::Proxy),
+            [
+                "[proxy] `cert_reload_secs` must be at least 1",
+                "[proxy] `max_connections` must be at least 1",
+                "[proxy.routes.1] missing `backends`",
+                "[proxy.routes.2] `path` must start with '/'",
+            ]
+        );
+    }
+
+    #[test]
+    fn lb_routes_only_take_a_host() {
+        let source = r#"
+            [[lb.routes]]
+            path = "/v1"
+            backends = ["127.0.0.1:9001"]
+        "#;
+
+        assert_eq!(
+            problems(source, ServerKind::Lb),
+            [
+                "[lb.routes.1] `path` only applies to [[proxy.routes]]; lb \
+                 routes on the SNI name alone",
+                "[lb.routes.1] missing `host`",
+            ]
+        );
+    }
+
+    #[test]
+    fn error_lists_every_problem_under_the_path() {
+        let error = ConfigError {
+            path: PathBuf::from("net.toml"),
+            problems: vec!["[lb] a".to_string(), "[lb] b".to_string()],
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "net.toml: 2 problems\n  [lb] a\n  [lb] b"
+        );
+    }
+
+    #[test]
+    fn load_reports_unreadable_files() {
+        let error =
+            load(Path::new("/definitely/missing/net.toml"), ServerKind::Echo)
+                .unwrap_err();
+
+        assert_eq!(error.problems.len(), 1);
+        assert!(error.problems[0].starts_with("cannot read: "));
+    }
+}
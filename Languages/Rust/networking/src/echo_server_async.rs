@@ -1,28 +1,44 @@
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::AsyncReadExt;
 use tokio::net::{TcpListener, TcpStream};
 
+use crate::faults::{self, FaultConfig};
+
 pub async fn connect() -> Result<(), Box<dyn std::error::Error>> {
     let listener = TcpListener::bind("127.0.0.1:8080").await?;
     println!("server is listening on port 8080");
+
+    let faults = FaultConfig::from_env();
+    if faults.is_enabled() {
+        println!("injecting faults: {:?}", faults);
+    }
+
     loop {
         let (socket, _addr) = listener.accept().await?;
         tokio::spawn(async move {
-            if let Err(e) = handle_client(socket).await {
+            if let Err(e) = handle_client(socket, faults).await {
                 eprintln!("Error: {}", e);
             }
         });
     }
 }
 
-async fn handle_client(mut stream: TcpStream) -> Result<(), Box<dyn std::error::Error>> {
+async fn handle_client(
+    mut stream: TcpStream,
+    faults: FaultConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
     let mut buf = vec![0u8; 1024];
+    let mut faults = faults.connection();
 
     loop {
         let n = stream.read(&mut buf).await?;
         if n == 0 {
             return Ok(());
         }
-        stream.write_all(&buf[..n]).await?;
+        if let Err(e) = faults.on_chunk().await {
+            faults::reset(&stream);
+            return Err(e.into());
+        }
+        faults.write_all(&mut stream, &buf[..n]).await?;
     }
 }
 
@@ -0,0 +1,156 @@
+// Fault injection: make a healthy local server behave like a bad network
+//
+// Localhost never drops packets and answers in microseconds, so client
+// retry/backoff code never gets exercised. Faults slows down and breaks
+// connections on purpose:
+//
+// - bandwidth cap: writes are paced to N bytes/second per connection
+// - latency: every chunk waits `latency ± jitter` before it's answered
+// - resets: with probability p per chunk the connection is aborted with a
+//   TCP RST, so the client sees ECONNRESET instead of a clean EOF
+//
+// Configured from the environment so the servers don't need new flags:
+//
+//   FAULT_BANDWIDTH=1024 FAULT_LATENCY_MS=200 FAULT_JITTER_MS=50 \
+//   FAULT_RESET_RATE=0.05 cargo run -- serve echo
+
+use std::env;
+use std::io;
+use std::time::Duration;
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{Instant, sleep, sleep_until};
+
+/// What to inject. The default injects nothing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FaultConfig {
+    /// Bytes per second per connection; `None` for unlimited
+    pub bandwidth: Option<u64>,
+    pub latency: Duration,
+    /// Latency varies uniformly within `latency ± jitter`
+    pub jitter: Duration,
+    /// Chance (0.0..=1.0) that a chunk resets the connection
+    pub reset_rate: f64,
+}
+
+impl FaultConfig {
+    /// Read `FAULT_BANDWIDTH` (bytes/s), `FAULT_LATENCY_MS`,
+    /// `FAULT_JITTER_MS` and `FAULT_RESET_RATE`. Unset or unparsable
+    /// variables inject nothing.
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            env::var(name).ok()?.trim().parse().ok()
+        }
+
+        FaultConfig {
+            bandwidth: var::<u64>("FAULT_BANDWIDTH").filter(|&b| b > 0),
+            latency: Duration::from_millis(
+                var("FAULT_LATENCY_MS").unwrap_or(0),
+            ),
+            jitter: Duration::from_millis(var("FAULT_JITTER_MS").unwrap_or(0)),
+            reset_rate: var::<f64>("FAULT_RESET_RATE")
+                .unwrap_or(0.0)
+                .clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.bandwidth.is_some()
+            || !self.latency.is_zero()
+            || !self.jitter.is_zero()
+            || self.reset_rate > 0.0
+    }
+
+    /// Per-connection state for these faults.
+    pub fn connection(&self) -> Faults {
+        Faults { config: *self, next_send: Instant::now() }
+    }
+}
+
+/// Faults for one connection; call `on_chunk` for every chunk read and
+/// write through `write_all`.
+pub struct Faults {
+    config: FaultConfig,
+    /// Earliest time the bandwidth budget allows the next write
+    next_send: Instant,
+}
+
+impl Faults {
+    /// Delay handling a chunk that just arrived, or decide to reset.
+    ///
+    /// Returns `ConnectionReset` when the connection should be aborted;
+    /// call [`reset`] on the socket and drop it.
+    pub async fn on_chunk(&mut self) -> io::Result<()> {
+        if self.config.reset_rate > 0.0
+            && fastrand::f64() < self.config.reset_rate
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionReset,
+                "injected connection reset",
+            ));
+        }
+
+        let delay = self.latency();
+        if !delay.is_zero() {
+            sleep(delay).await;
+        }
+        Ok(())
+    }
+
+    /// Write all of `buf`, paced to the bandwidth cap.
+    ///
+    /// The buffer goes out in slices of ~50ms worth of bandwidth, so a
+    /// capped connection trickles like a slow link instead of sending a
+    /// burst and then going quiet.
+    pub async fn write_all<W: AsyncWrite + Unpin>(
+        &mut self,
+        writer: &mut W,
+        buf: &[u8],
+    ) -> io::Result<()> {
+        let Some(bandwidth) = self.config.bandwidth else {
+            return writer.write_all(buf).await;
+        };
+
+        let slice = (bandwidth as usize / 20).max(1);
+        for piece in buf.chunks(slice) {
+            self.throttle(piece.len(), bandwidth).await;
+            writer.write_all(piece).await?;
+        }
+        Ok(())
+    }
+
+    /// Wait until `n` more bytes fit in the bandwidth budget.
+    ///
+    /// Each write pushes `next_send` forward by `n / bandwidth` seconds, so
+    /// over time the connection averages the configured rate.
+    async fn throttle(&mut self, n: usize, bandwidth: u64) {
+        let now = Instant::now();
+        // An idle connection doesn't save up budget for a burst later
+        let start = self.next_send.max(now);
+        self.next_send =
+            start + Duration::from_secs_f64(n as f64 / bandwidth as f64);
+        sleep_until(start).await;
+    }
+
+    fn latency(&self) -> Duration {
+        let FaultConfig { latency, jitter, .. } = self.config;
+        if jitter.is_zero() {
+            return latency;
+        }
+        // Uniform in [latency - jitter, latency + jitter], never negative
+        let offset = jitter.mul_f64(fastrand::f64() * 2.0);
+        (latency + offset).saturating_sub(jitter)
+    }
+}
+
+/// Make the next close send a RST instead of a FIN.
+///
+/// With a zero linger timeout the kernel drops unsent data and aborts the
+/// connection, which is what a crashed peer or a middlebox timing out looks
+/// like from the other side.
+pub fn reset(stream: &TcpStream) {
+    if let Err(e) = stream.set_zero_linger() {
+        eprintln!("can't set SO_LINGER for reset: {}", e);
+    }
+}
@@ -1,12 +1,29 @@
+mod backends;
+mod http;
+mod tls;
+
+use std::collections::HashMap;
+use std::env;
 use std::error::Error;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use rustls::ServerConfig;
+use tokio::io::{self, AsyncBufReadExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::signal;
-use tokio::sync::broadcast;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, broadcast};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+
+use crate::faults::{self, FaultConfig};
+
+pub use backends::{Pool, Routes};
+pub use http::HttpRoutes;
+pub use tls::CertStore;
 
 #[derive(Clone)]
 struct Metrics {
@@ -34,30 +51,71 @@ impl Metrics {
     }
 }
 
+/// Echo server settings (`[echo]` in the config file).
+#[derive(Debug, Clone)]
+pub struct EchoConfig {
+    pub listen: SocketAddr,
+    /// Clients served at once; more are turned away. `None` for no limit
+    pub max_connections: Option<usize>,
+    /// A client that doesn't take its echo within this long is dropped
+    pub write_timeout: Duration,
+}
+
+impl Default for EchoConfig {
+    fn default() -> Self {
+        EchoConfig {
+            listen: ([127, 0, 0, 1], 8080).into(),
+            max_connections: None,
+            write_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Caps how many clients are served at once.
+#[derive(Clone)]
+struct Limit(Option<Arc<Semaphore>>);
+
+/// One admitted client; frees its place in the `Limit` when dropped.
+struct Slot {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Limit {
+    fn new(max: Option<usize>) -> Self {
+        Limit(max.map(|max| Arc::new(Semaphore::new(max))))
+    }
+
+    /// A place for one more client, `None` when they're all taken.
+    fn admit(&self) -> Option<Slot> {
+        match &self.0 {
+            Some(semaphore) => Arc::clone(semaphore)
+                .try_acquire_owned()
+                .ok()
+                .map(|p| Slot { _permit: Some(p) }),
+            None => Some(Slot { _permit: None }),
+        }
+    }
+}
+
 pub async fn connect() -> Result<(), Box<dyn Error>> {
-    let listener = TcpListener::bind("127.0.0.1:8080").await?;
-    println!("Server listening on port 8080");
+    serve(EchoConfig::default()).await
+}
+
+pub async fn serve(config: EchoConfig) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(config.listen).await?;
+    println!("Server listening on {}", config.listen);
+    let limit = Limit::new(config.max_connections);
 
     let metrics = Metrics::new();
     let (shutdown_tx, _) = broadcast::channel(1);
 
-    // Metrics reporter
-    let metrics_clone = metrics.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(10));
-        loop {
-            interval.tick().await;
-            metrics_clone.report();
-        }
-    });
+    let faults = FaultConfig::from_env();
+    if faults.is_enabled() {
+        println!("Injecting faults: {:?}", faults);
+    }
 
-    // Ctrl+C handler
-    let shutdown_tx_clone = shutdown_tx.clone();
-    tokio::spawn(async move {
-        signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
-        println!("Received shutdown signal");
-        let _ = shutdown_tx_clone.send(());
-    });
+    spawn_reporter(metrics.clone());
+    spawn_ctrl_c(shutdown_tx.clone());
 
     let mut shutdown_rx = shutdown_tx.subscribe();
 
@@ -65,13 +123,19 @@ pub async fn connect() -> Result<(), Box<dyn Error>> {
         tokio::select! {
             result = listener.accept() => {
                 let (socket, addr) = result?;
+                let Some(slot) = limit.admit() else {
+                    eprintln!("{}: connection limit reached, turning away", addr);
+                    continue;
+                };
                 println!("New connection from {}", addr);
 
                 let metrics = metrics.clone();
                 let shutdown_rx = shutdown_tx.subscribe();
+                let write_timeout = config.write_timeout;
 
                 tokio::spawn(async move {
-                    if let Err(e) = handle_client(socket, metrics, shutdown_rx).await {
+                    let _slot = slot;
+                    if let Err(e) = handle_client(socket, metrics, shutdown_rx, faults, write_timeout).await {
                         eprintln!("Error handling client: {}", e);
                     }
                 });
@@ -86,12 +150,35 @@ pub async fn connect() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Print the metrics every 10 seconds.
+fn spawn_reporter(metrics: Metrics) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(10));
+        loop {
+            interval.tick().await;
+            metrics.report();
+        }
+    });
+}
+
+/// Broadcast shutdown on Ctrl+C.
+fn spawn_ctrl_c(shutdown_tx: broadcast::Sender<()>) {
+    tokio::spawn(async move {
+        signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
+        println!("Received shutdown signal");
+        let _ = shutdown_tx.send(());
+    });
+}
+
 async fn handle_client(
     socket: TcpStream,
     metrics: Metrics,
     mut shutdown: broadcast::Receiver<()>,
+    faults: FaultConfig,
+    write_timeout: Duration,
 ) -> Result<(), Box<dyn Error>> {
     metrics.connections.fetch_add(1, Ordering::Relaxed);
+    let mut faults = faults.connection();
 
     let (reader, mut writer) = socket.into_split();
     let mut reader = BufReader::new(reader);
@@ -107,10 +194,19 @@ async fn handle_client(
 
                                 metrics.bytes_recvd.fetch_add(n as u64, Ordering::Relaxed);
 
+                                if let Err(e) = faults.on_chunk().await {
+                                    eprintln!("{}; resetting client", e);
+                                    faults::reset(writer.as_ref());
+                                    // Dropping the write half would send a FIN
+                                    // first; forget it so the close is the RST
+                                    writer.forget();
+                                    break;
+                                }
+
                                 // writer.write_all(line.as_bytes()).await?;
                                 match tokio::time::timeout(
-            Duration::from_secs(5),
-            writer.write_all(line.as_bytes()),
+            write_timeout,
+            faults.write_all(&mut writer, line.as_bytes()),
         ).await {
             Ok(Ok(())) => {
                 // write succeeded
@@ -143,3 +239,250 @@ async fn handle_client(
     metrics.connections.fetch_sub(1, Ordering::Relaxed);
     Ok(())
 }
+
+// ============================================================================
+// TLS-terminating balancer
+// ============================================================================
+//
+//   client ──TLS──> balancer ──plaintext TCP──> backend
+//
+// The balancer holds the certificates, so backends (like the echo server
+// above) never see TLS. The SNI name from the handshake picks both the
+// certificate (tls.rs) and the backend pool (backends.rs); as a proxy
+// (`serve proxy`) each request is routed on Host and path instead (http.rs).
+//
+//   LB_CERT_DIR=certs LB_BACKENDS=127.0.0.1:8080 cargo run -- serve lb
+//   cargo run -- serve lb --config net.toml
+//   openssl s_client -connect 127.0.0.1:8443 -servername api.local
+
+/// How long a client gets to finish the TLS handshake, by default.
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Balancer settings, read from the environment like `FaultConfig` or from
+/// the `[lb]`/`[proxy]` tables of a config file (config.rs).
+#[derive(Debug)]
+pub struct BalancerConfig {
+    /// `LB_LISTEN`, default 127.0.0.1:8443
+    pub listen: String,
+    /// `LB_CERT_DIR`, default `certs`
+    pub cert_dir: PathBuf,
+    /// `LB_CERT_RELOAD_SECS`, default 5
+    pub reload_every: Duration,
+    pub mode: Mode,
+    /// Clients served at once; more are turned away. `None` for no limit
+    pub max_connections: Option<usize>,
+    /// How long a client gets to finish the TLS handshake
+    pub handshake_timeout: Duration,
+}
+
+/// How the balancer picks a backend.
+#[derive(Debug)]
+pub enum Mode {
+    /// Pick a pool by SNI name and pipe the bytes through (`LB_MODE=tcp`,
+    /// the default; pools from `LB_BACKENDS` and `LB_ROUTES`)
+    Tcp(Routes),
+    /// Parse HTTP and pick a pool per request (`LB_MODE=http`; pools from
+    /// `LB_BACKENDS` and `LB_HTTP_ROUTES`)
+    Http(HttpRoutes),
+}
+
+impl Mode {
+    /// TCP mode with pools from `LB_BACKENDS` and `LB_ROUTES`; `None` when
+    /// they name no backend.
+    pub fn tcp_from_env() -> Option<Self> {
+        Routes::from_env().map(Mode::Tcp)
+    }
+
+    /// HTTP mode with pools from `LB_BACKENDS` and `LB_HTTP_ROUTES`; `None`
+    /// when they name no backend.
+    pub fn http_from_env() -> Option<Self> {
+        HttpRoutes::from_env().map(Mode::Http)
+    }
+
+    fn describe(&self) -> Vec<String> {
+        match self {
+            Mode::Tcp(routes) => routes.describe(),
+            Mode::Http(routes) => routes.describe(),
+        }
+    }
+}
+
+impl BalancerConfig {
+    /// `None` when no backends are configured.
+    pub fn from_env() -> Option<Self> {
+        let mode = match env::var("LB_MODE").as_deref() {
+            Ok("http") => Mode::http_from_env()?,
+            _ => Mode::tcp_from_env()?,
+        };
+        Some(BalancerConfig::from_env_with(mode))
+    }
+
+    /// The other settings from the environment, routing with `mode`.
+    pub fn from_env_with(mode: Mode) -> Self {
+        let reload_secs = env::var("LB_CERT_RELOAD_SECS")
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .filter(|&s| s > 0)
+            .unwrap_or(5);
+
+        BalancerConfig {
+            listen: env::var("LB_LISTEN")
+                .unwrap_or_else(|_| "127.0.0.1:8443".to_string()),
+            cert_dir: env::var("LB_CERT_DIR")
+                .unwrap_or_else(|_| "certs".to_string())
+                .into(),
+            reload_every: Duration::from_secs(reload_secs),
+            mode,
+            max_connections: None,
+            handshake_timeout: HANDSHAKE_TIMEOUT,
+        }
+    }
+}
+
+/// Find `host` in a map keyed by host names, trying an exact match first
+/// and then a `*.parent` wildcard (one label deep, like certificates).
+fn lookup_host<'a, T>(
+    map: &'a HashMap<String, T>,
+    host: &str,
+) -> Option<&'a T> {
+    let host = host.to_ascii_lowercase();
+    map.get(&host).or_else(|| {
+        let (_, parent) = host.split_once('.')?;
+        map.get(&format!("*.{}", parent))
+    })
+}
+
+pub async fn balance(config: BalancerConfig) -> Result<(), Box<dyn Error>> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let certs = CertStore::load(&config.cert_dir, Arc::clone(&provider))?;
+    certs.watch(config.reload_every);
+
+    let tls_config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_cert_resolver(certs.clone());
+    let acceptor = TlsAcceptor::from(Arc::new(tls_config));
+
+    let listener = TcpListener::bind(&config.listen).await?;
+    println!(
+        "Balancer listening on {} (TLS, {} mode)",
+        config.listen,
+        match config.mode {
+            Mode::Tcp(_) => "tcp",
+            Mode::Http(_) => "http",
+        }
+    );
+    println!(
+        "Certificates from {}: {}",
+        config.cert_dir.display(),
+        certs.names().join(", ")
+    );
+    for line in config.mode.describe() {
+        println!("{}", line);
+    }
+
+    let mode = Arc::new(config.mode);
+    let limit = Limit::new(config.max_connections);
+    let handshake_timeout = config.handshake_timeout;
+    let metrics = Metrics::new();
+    let (shutdown_tx, _) = broadcast::channel(1);
+    spawn_reporter(metrics.clone());
+    spawn_ctrl_c(shutdown_tx.clone());
+
+    let mut shutdown_rx = shutdown_tx.subscribe();
+
+    loop {
+        tokio::select! {
+            result = listener.accept() => {
+                let (socket, addr) = result?;
+                let Some(slot) = limit.admit() else {
+                    eprintln!("{}: connection limit reached, turning away", addr);
+                    continue;
+                };
+
+                let acceptor = acceptor.clone();
+                let mode = Arc::clone(&mode);
+                let metrics = metrics.clone();
+                let shutdown_rx = shutdown_tx.subscribe();
+
+                tokio::spawn(async move {
+                    let _slot = slot;
+                    if let Err(e) = proxy_client(
+                        socket, addr, acceptor, handshake_timeout, &mode,
+                        metrics, shutdown_rx,
+                    )
+                    .await
+                    {
+                        eprintln!("{}: {}", addr, e);
+                    }
+                });
+            }
+            _ = shutdown_rx.recv() => {
+                println!("Shutting down balancer");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Terminate TLS for one client and hand it to the mode's relay.
+async fn proxy_client(
+    socket: TcpStream,
+    addr: SocketAddr,
+    acceptor: TlsAcceptor,
+    handshake_timeout: Duration,
+    mode: &Mode,
+    metrics: Metrics,
+    mut shutdown: broadcast::Receiver<()>,
+) -> Result<(), Box<dyn Error>> {
+    let mut client =
+        tokio::time::timeout(handshake_timeout, acceptor.accept(socket))
+            .await
+            .map_err(|_| "TLS handshake timed out")??;
+
+    metrics.connections.fetch_add(1, Ordering::Relaxed);
+    let relay = async {
+        match mode {
+            Mode::Tcp(routes) => splice(&mut client, addr, routes).await,
+            Mode::Http(routes) => http::serve(&mut client, addr, routes).await,
+        }
+    };
+    let result = tokio::select! {
+        result = relay => result,
+        _ = shutdown.recv() => Ok((0, 0)),
+    };
+    metrics.connections.fetch_sub(1, Ordering::Relaxed);
+
+    let (from_client, to_client) = match result {
+        Ok(counts) => counts,
+        // Plenty of clients just close the socket instead of sending a TLS
+        // close_notify first; that's not worth an error
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    metrics.bytes_recvd.fetch_add(from_client, Ordering::Relaxed);
+    metrics.bytes_sent.fetch_add(to_client, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Pick a pool by the client's SNI name and pipe bytes both ways.
+async fn splice(
+    client: &mut TlsStream<TcpStream>,
+    addr: SocketAddr,
+    routes: &Routes,
+) -> io::Result<(u64, u64)> {
+    let sni = client.get_ref().1.server_name().map(str::to_string);
+    let server_name = sni.as_deref().unwrap_or("(no SNI)");
+    let pool = routes.pool_for(sni.as_deref()).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no backend for {}", server_name),
+        )
+    })?;
+    let (mut backend, backend_addr) = pool.connect().await?;
+    println!("{} ({}) -> {}", addr, server_name, backend_addr);
+
+    io::copy_bidirectional(client, &mut backend).await
+}
@@ -0,0 +1,179 @@
+// Backend pools: where a connection goes once TLS is stripped off
+//
+// Each pool is a list of plaintext backends used round-robin. A backend
+// that refuses the connection is skipped and the next one tried, so one dead
+// backend costs a failed connect, not a failed client.
+//
+// Which pool serves a connection is decided by the SNI name the client sent
+// in its ClientHello:
+//
+//   LB_ROUTES="api.local=127.0.0.1:9001,127.0.0.1:9002;*.web.local=127.0.0.1:9003"
+//   LB_BACKENDS=127.0.0.1:9000        <- everything else (and no SNI at all)
+
+use std::collections::HashMap;
+use std::env;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Backends that serve the same traffic, used in turn.
+#[derive(Debug)]
+pub struct Pool {
+    backends: Vec<String>,
+    next: AtomicUsize,
+}
+
+impl Pool {
+    pub fn new(backends: Vec<String>) -> Self {
+        Pool { backends, next: AtomicUsize::new(0) }
+    }
+
+    /// Parse `host:port,host:port`; `None` when the list is empty.
+    pub fn parse(list: &str) -> Option<Self> {
+        let backends: Vec<String> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|b| !b.is_empty())
+            .map(str::to_string)
+            .collect();
+        (!backends.is_empty()).then(|| Pool::new(backends))
+    }
+
+    pub fn backends(&self) -> &[String] {
+        &self.backends
+    }
+
+    /// Connect to the next backend in turn, falling over to the others if
+    /// it's down. Returns the stream and the backend it went to.
+    pub async fn connect(&self) -> io::Result<(TcpStream, &str)> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut last_err = None;
+
+        for i in 0..self.backends.len() {
+            let backend = &self.backends[(start + i) % self.backends.len()];
+            match timeout(CONNECT_TIMEOUT, TcpStream::connect(backend)).await {
+                Ok(Ok(stream)) => return Ok((stream, backend)),
+                Ok(Err(e)) => {
+                    eprintln!("backend {} failed: {}", backend, e);
+                    last_err = Some(e);
+                }
+                Err(_elapsed) => {
+                    eprintln!("backend {} timed out", backend);
+                    last_err = Some(io::ErrorKind::TimedOut.into());
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "pool has no backends")
+        }))
+    }
+}
+
+/// SNI name -> pool, with a default for names nobody claimed.
+#[derive(Debug, Default)]
+pub struct Routes {
+    by_host: HashMap<String, Pool>,
+    default: Option<Pool>,
+}
+
+impl Routes {
+    /// Read `LB_BACKENDS` (the default pool) and `LB_ROUTES`
+    /// (`host=addr,addr;host=addr`). `None` when neither names a backend.
+    pub fn from_env() -> Option<Self> {
+        let mut routes = Routes::default();
+        if let Some(pool) =
+            env::var("LB_BACKENDS").ok().and_then(|l| Pool::parse(&l))
+        {
+            routes = routes.with_default(pool);
+        }
+
+        let spec = env::var("LB_ROUTES").unwrap_or_default();
+        for route in spec.split(';').filter(|r| !r.trim().is_empty()) {
+            let Some((host, list)) = route.split_once('=') else {
+                eprintln!("ignoring route without '=': {}", route);
+                continue;
+            };
+            if let Some(pool) = Pool::parse(list) {
+                routes = routes.with_route(host, pool);
+            }
+        }
+
+        (!routes.is_empty()).then_some(routes)
+    }
+
+    /// Send connections no route claims (and those without SNI) to `pool`.
+    pub fn with_default(mut self, pool: Pool) -> Self {
+        self.default = Some(pool);
+        self
+    }
+
+    /// Send connections for `host` (`*.parent` for a wildcard) to `pool`.
+    pub fn with_route(mut self, host: &str, pool: Pool) -> Self {
+        self.by_host.insert(host.trim().to_ascii_lowercase(), pool);
+        self
+    }
+
+    /// No backend at all.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.by_host.is_empty()
+    }
+
+    /// The pool for a client that asked for `server_name`.
+    pub fn pool_for(&self, server_name: Option<&str>) -> Option<&Pool> {
+        server_name
+            .and_then(|name| super::lookup_host(&self.by_host, name))
+            .or(self.default.as_ref())
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .by_host
+            .iter()
+            .map(|(host, pool)| {
+                format!("  {} -> {}", host, pool.backends().join(", "))
+            })
+            .collect();
+        lines.sort();
+        if let Some(pool) = &self.default {
+            lines.push(format!("  * -> {}", pool.backends().join(", ")));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(addr: &str) -> Pool {
+        Pool::new(vec![addr.to_string()])
+    }
+
+    fn backend<'a>(routes: &'a Routes, sni: Option<&str>) -> Option<&'a str> {
+        routes.pool_for(sni).map(|pool| pool.backends()[0].as_str())
+    }
+
+    #[test]
+    fn sni_resolves_exactly_then_by_wildcard_then_default() {
+        let routes = Routes::default()
+            .with_route(" API.local ", pool("api:1"))
+            .with_route("*.web.local", pool("web:1"));
+
+        assert_eq!(backend(&routes, Some("api.LOCAL")), Some("api:1"));
+        assert_eq!(backend(&routes, Some("x.web.local")), Some("web:1"));
+        assert_eq!(backend(&routes, Some("web.local")), None);
+        assert_eq!(backend(&routes, Some("a.b.web.local")), None);
+        assert_eq!(backend(&routes, None), None);
+
+        let routes = routes.with_default(pool("default:1"));
+        assert_eq!(backend(&routes, Some("a.b.web.local")), Some("default:1"));
+        assert_eq!(backend(&routes, None), Some("default:1"));
+        assert_eq!(backend(&routes, Some("x.web.local")), Some("web:1"));
+    }
+}
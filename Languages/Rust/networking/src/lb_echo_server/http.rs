@@ -0,0 +1,495 @@
+// Layer-7 mode: read the HTTP requests instead of just piping bytes
+//
+// In TCP mode the balancer only knows the SNI name, so a whole connection
+// goes to one pool. In HTTP mode every request is parsed (with learn_http's
+// parser) and routed on its Host header and path, so requests on the same
+// keep-alive connection can land on different pools:
+//
+//   LB_MODE=http
+//   LB_HTTP_ROUTES="api.local/v1=127.0.0.1:9001,127.0.0.1:9002;/static=127.0.0.1:9003"
+//   LB_BACKENDS=127.0.0.1:9000       <- requests no route matches
+//
+// A route is `[host][/prefix]=backends`: the most specific one wins (a
+// route naming the host beats one that doesn't, then the longest prefix).
+//
+// Each request goes to its backend on a fresh connection with
+// `Connection: close`, carrying X-Forwarded-For (the client's IP appended
+// to whatever proxies before us added) and X-Forwarded-Proto. The client
+// side stays keep-alive.
+
+use std::env;
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use learn_http::http_over_tcp::har::Timings;
+use learn_http::http_over_tcp::proxy::{self, Exchange, Outgoing};
+use learn_http::http_over_tcp::request::{self, Request};
+use learn_http::http_over_tcp::response::Response;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use super::backends::Pool;
+
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+/// How long a keep-alive connection may sit between requests
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+const BACKEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+struct Route {
+    /// `None` matches any host
+    host: Option<String>,
+    prefix: String,
+    pool: Pool,
+}
+
+impl Route {
+    /// Parse `[host][/prefix]=addr,addr`.
+    fn parse(spec: &str) -> Option<Self> {
+        let (target, list) = spec.split_once('=')?;
+        Some(Route::new(target, Pool::parse(list)?))
+    }
+
+    /// Route `[host][/prefix]` to `pool`.
+    fn new(target: &str, pool: Pool) -> Self {
+        let target = target.trim();
+        let (host, prefix) = match target.find('/') {
+            Some(0) => (None, target),
+            Some(i) => (Some(&target[..i]), &target[i..]),
+            None => (Some(target), "/"),
+        };
+
+        Route {
+            host: host.map(str::to_ascii_lowercase),
+            prefix: prefix.to_string(),
+            pool,
+        }
+    }
+
+    fn matches(&self, host: Option<&str>, path: &str) -> bool {
+        let host_ok = match (&self.host, host) {
+            (None, _) => true,
+            (Some(pattern), Some(host)) => host_matches(pattern, host),
+            (Some(_), None) => false,
+        };
+        host_ok && prefix_matches(&self.prefix, path)
+    }
+}
+
+/// `*.web.local` matches `a.web.local`, but not `web.local` or
+/// `a.b.web.local`, the same as the certificate lookup.
+fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(parent) => host
+            .split_once('.')
+            .is_some_and(|(_, rest)| rest.eq_ignore_ascii_case(parent)),
+        None => pattern.eq_ignore_ascii_case(host),
+    }
+}
+
+/// Prefixes match whole segments: `/api` takes `/api` and `/api/users`,
+/// not `/apis`.
+fn prefix_matches(prefix: &str, path: &str) -> bool {
+    match path.strip_prefix(prefix) {
+        Some(rest) => {
+            prefix.ends_with('/') || rest.is_empty() || rest.starts_with('/')
+        }
+        None => false,
+    }
+}
+
+/// Host/path prefix -> pool, with a default for requests nobody claimed.
+#[derive(Debug, Default)]
+pub struct HttpRoutes {
+    routes: Vec<Route>,
+    default: Option<Pool>,
+}
+
+impl HttpRoutes {
+    /// Read `LB_HTTP_ROUTES` and `LB_BACKENDS` (the default pool). `None`
+    /// when neither names a backend.
+    pub fn from_env() -> Option<Self> {
+        let spec = env::var("LB_HTTP_ROUTES").unwrap_or_default();
+        let mut routes = Vec::new();
+        for spec in spec.split(';').filter(|r| !r.trim().is_empty()) {
+            match Route::parse(spec) {
+                Some(route) => routes.push(route),
+                None => eprintln!("ignoring bad HTTP route: {}", spec),
+            }
+        }
+
+        let default =
+            env::var("LB_BACKENDS").ok().and_then(|l| Pool::parse(&l));
+        let routes = HttpRoutes { routes, default };
+        (!routes.is_empty()).then_some(routes)
+    }
+
+    /// Send requests no route matches to `pool`.
+    pub fn with_default(mut self, pool: Pool) -> Self {
+        self.default = Some(pool);
+        self
+    }
+
+    /// Send requests matching `[host][/prefix]` to `pool`.
+    pub fn with_route(mut self, target: &str, pool: Pool) -> Self {
+        self.routes.push(Route::new(target, pool));
+        self
+    }
+
+    /// No backend at all.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.routes.is_empty()
+    }
+
+    /// The pool for a request to `host` (without port) and `path`.
+    pub fn pool_for(&self, host: Option<&str>, path: &str) -> Option<&Pool> {
+        self.routes
+            .iter()
+            .filter(|route| route.matches(host, path))
+            .max_by_key(|route| (route.host.is_some(), route.prefix.len()))
+            .map(|route| &route.pool)
+            .or(self.default.as_ref())
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .routes
+            .iter()
+            .map(|route| {
+                format!(
+                    "  {}{} -> {}",
+                    route.host.as_deref().unwrap_or(""),
+                    route.prefix,
+                    route.pool.backends().join(", ")
+                )
+            })
+            .collect();
+        if let Some(pool) = &self.default {
+            lines.push(format!("  * -> {}", pool.backends().join(", ")));
+        }
+        lines
+    }
+}
+
+/// Serve HTTP requests from `client` until it closes, forwarding each to
+/// its pool. Returns the bytes received from and sent to the client.
+pub async fn serve<S>(
+    client: &mut S,
+    peer: SocketAddr,
+    routes: &HttpRoutes,
+) -> io::Result<(u64, u64)>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let (mut received, mut sent) = (0u64, 0u64);
+
+    loop {
+        let parsed = request::parse_request(&buf, MAX_BODY_SIZE);
+        let (request, used) = match parsed {
+            Ok(Some(parsed)) => parsed,
+            Ok(None) => {
+                let read = timeout(IDLE_TIMEOUT, client.read(&mut chunk));
+                // An idle client is closed like one that went away
+                let n = read.await.unwrap_or(Ok(0))?;
+                if n == 0 {
+                    return Ok((received, sent));
+                }
+                received += n as u64;
+                buf.extend_from_slice(&chunk[..n]);
+                continue;
+            }
+            Err(e) => {
+                let response = Response::text(e.status(), e.to_string());
+                let bytes = response.to_bytes(true);
+                client.write_all(&bytes).await?;
+                client.shutdown().await?;
+                return Ok((received, sent + bytes.len() as u64));
+            }
+        };
+        buf.drain(..used);
+
+        let close = request
+            .header("connection")
+            .is_some_and(|v| v.eq_ignore_ascii_case("close"));
+        let head_only = request.method() == "HEAD";
+
+        let response = forward(&request, peer, routes).await;
+        let mut bytes = response.to_bytes(close);
+        if head_only {
+            bytes.truncate(bytes.len() - response.body_bytes().len());
+        }
+        client.write_all(&bytes).await?;
+        sent += bytes.len() as u64;
+
+        if close {
+            client.shutdown().await?;
+            return Ok((received, sent));
+        }
+    }
+}
+
+/// Send one request to its pool and turn the answer into a response for
+/// the client (a 502/504 if the backend didn't give one).
+async fn forward(
+    request: &Request,
+    peer: SocketAddr,
+    routes: &HttpRoutes,
+) -> Response {
+    let host = request.header("host");
+    let Some(pool) = routes.pool_for(host.map(strip_port), request.path())
+    else {
+        return Response::text(502, "no backend for this host and path\n");
+    };
+
+    let mut outgoing = Outgoing::from_request(request);
+    add_forwarded_headers(&mut outgoing, peer);
+
+    let (mut backend, backend_addr) = match pool.connect().await {
+        Ok(connected) => connected,
+        Err(e) => {
+            return Response::text(502, format!("no backend up: {}\n", e));
+        }
+    };
+    // Keep the client's Host so backends can do virtual hosting
+    let bytes = outgoing.encode(host.unwrap_or(backend_addr));
+
+    let exchange =
+        match timeout(BACKEND_TIMEOUT, exchange(&mut backend, &bytes)).await {
+            Ok(Ok(exchange)) => exchange,
+            Ok(Err(e)) => {
+                eprintln!("backend {} failed: {}", backend_addr, e);
+                return Response::text(502, format!("bad gateway: {}\n", e));
+            }
+            Err(_elapsed) => {
+                return Response::text(504, "backend timed out\n");
+            }
+        };
+
+    let Timings { send, wait, receive } = exchange.timings;
+    println!(
+        "{} {} {}{} -> {} {} ({:.1} ms)",
+        peer,
+        request.method(),
+        host.unwrap_or(""),
+        request.path(),
+        backend_addr,
+        exchange.status,
+        send + wait + receive
+    );
+    exchange.into_response()
+}
+
+/// Write the request and read the response until the backend closes.
+async fn exchange(
+    backend: &mut TcpStream,
+    bytes: &[u8],
+) -> io::Result<Exchange> {
+    let started = Instant::now();
+    backend.write_all(bytes).await?;
+    let sent = Instant::now();
+
+    let mut raw = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let n = backend.read(&mut chunk).await?;
+    let first_byte = Instant::now();
+    raw.extend_from_slice(&chunk[..n]);
+    backend.read_to_end(&mut raw).await?;
+    let done = Instant::now();
+
+    let millis = |d: Duration| d.as_secs_f64() * 1000.0;
+    proxy::parse_response(
+        &raw,
+        Timings {
+            send: millis(sent - started),
+            wait: millis(first_byte - sent),
+            receive: millis(done - first_byte),
+        },
+    )
+}
+
+/// Append the client to X-Forwarded-For and say it came in over TLS.
+fn add_forwarded_headers(outgoing: &mut Outgoing, peer: SocketAddr) {
+    let earlier = outgoing
+        .headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("x-forwarded-for"))
+        .map(|(_, value)| value.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let forwarded_for = if earlier.is_empty() {
+        peer.ip().to_string()
+    } else {
+        format!("{}, {}", earlier, peer.ip())
+    };
+
+    outgoing.headers.retain(|(name, _)| {
+        !name.eq_ignore_ascii_case("x-forwarded-for")
+            && !name.eq_ignore_ascii_case("x-forwarded-proto")
+    });
+    outgoing.headers.push(("X-Forwarded-For".to_string(), forwarded_for));
+    outgoing
+        .headers
+        .push(("X-Forwarded-Proto".to_string(), "https".to_string()));
+}
+
+/// `example.com:8443` -> `example.com`, `[::1]:8443` -> `[::1]`.
+fn strip_port(host: &str) -> &str {
+    if host.starts_with('[') {
+        return host.find(']').map_or(host, |end| &host[..=end]);
+    }
+    host.split_once(':').map_or(host, |(name, _)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(addr: &str) -> Pool {
+        Pool::new(vec![addr.to_string()])
+    }
+
+    fn backend<'a>(
+        routes: &'a HttpRoutes,
+        host: Option<&str>,
+        path: &str,
+    ) -> Option<&'a str> {
+        routes.pool_for(host, path).map(|pool| pool.backends()[0].as_str())
+    }
+
+    #[test]
+    fn route_targets_split_into_host_and_prefix() {
+        let route = Route::new(" API.local/v1 ", pool("a:1"));
+        assert_eq!(route.host.as_deref(), Some("api.local"));
+        assert_eq!(route.prefix, "/v1");
+
+        let route = Route::new("/static", pool("a:1"));
+        assert_eq!(route.host, None);
+        assert_eq!(route.prefix, "/static");
+
+        let route = Route::new("web.local", pool("a:1"));
+        assert_eq!(route.host.as_deref(), Some("web.local"));
+        assert_eq!(route.prefix, "/");
+    }
+
+    #[test]
+    fn wildcards_match_exactly_one_label() {
+        assert!(host_matches("*.web.local", "a.web.local"));
+        assert!(host_matches("*.web.local", "A.Web.Local"));
+        assert!(!host_matches("*.web.local", "web.local"));
+        assert!(!host_matches("*.web.local", "a.b.web.local"));
+        assert!(host_matches("api.local", "API.local"));
+        assert!(!host_matches("api.local", "x.api.local"));
+    }
+
+    #[test]
+    fn prefixes_match_whole_segments() {
+        assert!(prefix_matches("/api", "/api"));
+        assert!(prefix_matches("/api", "/api/users"));
+        assert!(!prefix_matches("/api", "/apis"));
+        assert!(prefix_matches("/api/", "/api/users"));
+        assert!(prefix_matches("/", "/anything"));
+        assert!(!prefix_matches("/api", "/"));
+    }
+
+    #[test]
+    fn most_specific_route_wins() {
+        let routes = HttpRoutes::default()
+            .with_default(pool("default:1"))
+            .with_route("/api", pool("any-host-api:1"))
+            .with_route("/api/v1", pool("any-host-v1:1"))
+            .with_route("api.local", pool("host:1"))
+            .with_route("api.local/api", pool("host-api:1"));
+
+        // A route naming the host beats a longer prefix that doesn't
+        assert_eq!(
+            backend(&routes, Some("api.local"), "/api/v1/x"),
+            Some("host-api:1")
+        );
+        assert_eq!(
+            backend(&routes, Some("api.local"), "/other"),
+            Some("host:1")
+        );
+        assert_eq!(
+            backend(&routes, Some("web.local"), "/api/v1/x"),
+            Some("any-host-v1:1")
+        );
+        assert_eq!(
+            backend(&routes, None, "/api/users"),
+            Some("any-host-api:1")
+        );
+        assert_eq!(
+            backend(&routes, Some("web.local"), "/"),
+            Some("default:1")
+        );
+    }
+
+    #[test]
+    fn no_match_and_no_default_means_no_pool() {
+        let routes =
+            HttpRoutes::default().with_route("api.local", pool("a:1"));
+
+        assert_eq!(backend(&routes, Some("web.local"), "/"), None);
+        assert_eq!(backend(&routes, None, "/"), None);
+    }
+
+    #[test]
+    fn ports_are_stripped_from_hosts() {
+        assert_eq!(strip_port("example.com:8443"), "example.com");
+        assert_eq!(strip_port("example.com"), "example.com");
+        assert_eq!(strip_port("[::1]:8443"), "[::1]");
+        assert_eq!(strip_port("[::1]"), "[::1]");
+    }
+
+    fn outgoing(headers: &[(&str, &str)]) -> Outgoing {
+        Outgoing {
+            method: "GET".to_string(),
+            target: "/".to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: Vec::new(),
+        }
+    }
+
+    fn header<'a>(outgoing: &'a Outgoing, name: &str) -> Vec<&'a str> {
+        outgoing
+            .headers
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn forwarded_for_is_set_for_the_first_proxy() {
+        let mut request = outgoing(&[("Accept", "*/*")]);
+
+        add_forwarded_headers(&mut request, "10.0.0.7:5000".parse().unwrap());
+
+        assert_eq!(header(&request, "x-forwarded-for"), ["10.0.0.7"]);
+        assert_eq!(header(&request, "x-forwarded-proto"), ["https"]);
+        assert_eq!(header(&request, "accept"), ["*/*"]);
+    }
+
+    #[test]
+    fn forwarded_for_appends_to_earlier_proxies() {
+        let mut request = outgoing(&[
+            ("X-Forwarded-For", "203.0.113.1"),
+            ("x-forwarded-for", "198.51.100.2, 192.0.2.3"),
+            ("X-Forwarded-Proto", "http"),
+        ]);
+
+        add_forwarded_headers(&mut request, "[::1]:5000".parse().unwrap());
+
+        assert_eq!(
+            header(&request, "x-forwarded-for"),
+            ["203.0.113.1, 198.51.100.2, 192.0.2.3, ::1"]
+        );
+        assert_eq!(header(&request, "x-forwarded-proto"), ["https"]);
+    }
+}
@@ -0,0 +1,297 @@
+// Certificates for TLS termination, picked by SNI and reloaded from disk
+//
+// The cert directory holds one PEM pair per name:
+//
+//   certs/api.local.crt      certs/api.local.key
+//   certs/*.web.local.crt    certs/*.web.local.key   <- any x.web.local
+//   certs/default.crt        certs/default.key       <- no SNI / no match
+//
+// rustls asks a `ResolvesServerCert` for a certificate during every
+// handshake, so swapping the map behind it changes the certificate new
+// connections get without restarting anything. A background thread polls
+// the files' modification times and reloads when they change; a broken
+// file (half-written, key doesn't match) keeps the old certificates, and
+// is retried on every poll until it loads.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+
+/// Name of the pair used when nothing else matches.
+const DEFAULT_NAME: &str = "default";
+
+pub struct CertStore {
+    dir: PathBuf,
+    provider: Arc<CryptoProvider>,
+    certs: RwLock<HashMap<String, Arc<CertifiedKey>>>,
+    /// Modification times the current certs were loaded from
+    stamps: RwLock<Vec<(PathBuf, SystemTime)>>,
+}
+
+impl CertStore {
+    /// Load every pair in `dir`; fails if none load.
+    pub fn load(
+        dir: impl Into<PathBuf>,
+        provider: Arc<CryptoProvider>,
+    ) -> io::Result<Arc<Self>> {
+        let dir = dir.into();
+        let stamps = stamps(&dir)?;
+        let certs = load_dir(&dir, &provider)?;
+
+        Ok(Arc::new(CertStore {
+            dir,
+            provider,
+            certs: RwLock::new(certs),
+            stamps: RwLock::new(stamps),
+        }))
+    }
+
+    /// Names there are certificates for.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> =
+            self.certs.read().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Reload if any file changed since the last look. Returns whether the
+    /// certificates were replaced.
+    pub fn reload_if_changed(&self) -> io::Result<bool> {
+        let current = stamps(&self.dir)?;
+        if *self.stamps.read().unwrap() == current {
+            return Ok(false);
+        }
+
+        // Only remember these times once they loaded: a fix written within
+        // the same timestamp as the broken file must still be picked up
+        let certs = load_dir(&self.dir, &self.provider)?;
+        *self.certs.write().unwrap() = certs;
+        *self.stamps.write().unwrap() = current;
+        Ok(true)
+    }
+
+    /// The certificate for `server_name`: an exact match, then a wildcard,
+    /// then the default pair.
+    fn lookup(&self, server_name: Option<&str>) -> Option<Arc<CertifiedKey>> {
+        let certs = self.certs.read().unwrap();
+        server_name
+            .and_then(|name| super::lookup_host(&certs, name))
+            .or_else(|| certs.get(DEFAULT_NAME))
+            .cloned()
+    }
+
+    /// Poll the directory every `interval` on a background thread.
+    pub fn watch(self: &Arc<Self>, interval: Duration) {
+        let store = Arc::clone(self);
+        thread::spawn(move || {
+            loop {
+                thread::sleep(interval);
+                match store.reload_if_changed() {
+                    Ok(true) => println!(
+                        "Reloaded certificates: {}",
+                        store.names().join(", ")
+                    ),
+                    Ok(false) => {}
+                    Err(e) => eprintln!(
+                        "Certificate reload failed, keeping the old ones: {}",
+                        e
+                    ),
+                }
+            }
+        });
+    }
+}
+
+impl ResolvesServerCert for CertStore {
+    fn resolve(
+        &self,
+        client_hello: ClientHello<'_>,
+    ) -> Option<Arc<CertifiedKey>> {
+        self.lookup(client_hello.server_name())
+    }
+}
+
+impl fmt::Debug for CertStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CertStore")
+            .field("dir", &self.dir)
+            .field("names", &self.names())
+            .finish()
+    }
+}
+
+/// Every `.crt`/`.key` file in `dir` with its modification time, sorted.
+fn stamps(dir: &Path) -> io::Result<Vec<(PathBuf, SystemTime)>> {
+    let mut stamps = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("crt" | "key")
+        ) {
+            let modified = fs::metadata(&path)?.modified()?;
+            stamps.push((path, modified));
+        }
+    }
+    stamps.sort();
+    Ok(stamps)
+}
+
+/// Load every `<name>.crt` together with its `<name>.key`. A certificate
+/// that can't be loaded, including one whose `.key` is missing, fails the
+/// whole load: serving a partial set would silently drop a host.
+fn load_dir(
+    dir: &Path,
+    provider: &CryptoProvider,
+) -> io::Result<HashMap<String, Arc<CertifiedKey>>> {
+    let mut certs = HashMap::new();
+
+    for (path, _) in stamps(dir)? {
+        if path.extension().and_then(|e| e.to_str()) != Some("crt") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let key = load_pair(&path, &path.with_extension("key"), provider)
+            .map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            })?;
+        certs.insert(name.to_ascii_lowercase(), Arc::new(key));
+    }
+
+    if certs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no <name>.crt/<name>.key pairs in {}", dir.display()),
+        ));
+    }
+    Ok(certs)
+}
+
+fn load_pair(
+    cert: &Path,
+    key: &Path,
+    provider: &CryptoProvider,
+) -> Result<CertifiedKey, Box<dyn std::error::Error>> {
+    let chain =
+        CertificateDer::pem_file_iter(cert)?.collect::<Result<Vec<_>, _>>()?;
+    if chain.is_empty() {
+        return Err("no certificates in file".into());
+    }
+    let key = PrivateKeyDer::from_pem_file(key)?;
+
+    // Also checks that the key belongs to the certificate
+    Ok(CertifiedKey::from_der(chain, key, provider)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A_CRT: &str = include_str!("testdata/a.crt");
+    const A_KEY: &str = include_str!("testdata/a.key");
+    const B_CRT: &str = include_str!("testdata/b.crt");
+    const B_KEY: &str = include_str!("testdata/b.key");
+
+    /// A fresh directory under the system temp dir, removed on drop
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "lb-certs-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+
+        fn write(&self, file: &str, contents: &str) {
+            fs::write(self.0.join(file), contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn provider() -> Arc<CryptoProvider> {
+        Arc::new(rustls::crypto::ring::default_provider())
+    }
+
+    /// Which fixture `key` was loaded from
+    fn fixture(key: &CertifiedKey) -> &'static str {
+        let pem = |crt| CertificateDer::from_pem_slice(crt).unwrap();
+        if key.end_entity_cert().unwrap() == &pem(A_CRT.as_bytes()) {
+            "a"
+        } else if key.end_entity_cert().unwrap() == &pem(B_CRT.as_bytes()) {
+            "b"
+        } else {
+            "other"
+        }
+    }
+
+    fn served(store: &CertStore, server_name: Option<&str>) -> &'static str {
+        store.lookup(server_name).as_deref().map_or("none", fixture)
+    }
+
+    #[test]
+    fn names_resolve_exactly_then_by_wildcard_then_default() {
+        let dir = TempDir::new("resolve");
+        dir.write("api.local.crt", A_CRT);
+        dir.write("api.local.key", A_KEY);
+        dir.write("*.web.local.crt", B_CRT);
+        dir.write("*.web.local.key", B_KEY);
+        let store = CertStore::load(&dir.0, provider()).unwrap();
+
+        assert_eq!(store.names(), ["*.web.local", "api.local"]);
+        assert_eq!(served(&store, Some("API.local")), "a");
+        assert_eq!(served(&store, Some("x.web.local")), "b");
+        assert_eq!(served(&store, Some("a.b.web.local")), "none");
+        assert_eq!(served(&store, None), "none");
+
+        dir.write("default.crt", A_CRT);
+        dir.write("default.key", A_KEY);
+        assert!(store.reload_if_changed().unwrap());
+        assert_eq!(served(&store, Some("a.b.web.local")), "a");
+        assert_eq!(served(&store, None), "a");
+    }
+
+    #[test]
+    fn a_broken_pair_keeps_the_old_certs_until_fixed() {
+        let dir = TempDir::new("reload");
+        dir.write("default.crt", A_CRT);
+        dir.write("default.key", A_KEY);
+        let store = CertStore::load(&dir.0, provider()).unwrap();
+        assert!(!store.reload_if_changed().unwrap(), "nothing changed");
+
+        // The new certificate landed, its key not yet
+        dir.write("default.crt", B_CRT);
+        assert!(store.reload_if_changed().is_err());
+        assert_eq!(served(&store, None), "a");
+        assert!(store.reload_if_changed().is_err(), "retried while broken");
+
+        dir.write("default.key", B_KEY);
+        assert!(store.reload_if_changed().unwrap());
+        assert_eq!(served(&store, None), "b");
+    }
+}
@@ -1,4 +1,7 @@
+pub mod bench;
+pub mod config;
 pub mod echo_server;
 pub mod echo_server_async;
+pub mod faults;
 pub mod lb_echo_server;
 pub mod non_block_sockets;
@@ -1,9 +1,105 @@
-use networking::{echo_server, lb_echo_server};
+// Run one of the servers
+//
+//   cargo run -- serve echo|lb|proxy [--config net.toml]
+//
+// echo  - line echo server on 127.0.0.1:8080
+// lb    - TLS balancer, picks a backend pool by SNI name
+// proxy - TLS balancer, picks a backend pool per HTTP request
+//
+// Settings come from the [echo]/[lb]/[proxy] table of the config file (see
+// config.rs); without --config, echo uses its defaults and lb/proxy read
+// LB_* variables (see lb_echo_server.rs). Faults are always FAULT_* ones.
+
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use networking::config::{self, ServerConfig, ServerKind};
+use networking::lb_echo_server::{self, BalancerConfig, EchoConfig, Mode};
+
+const USAGE: &str = "usage: networking serve echo|lb|proxy [--config FILE]";
 
 #[tokio::main]
-async fn main() {
-    // echo_server::connect();
-    if let Err(e) = lb_echo_server::connect().await {
-        eprintln!("Error: {}", e)
+async fn main() -> ExitCode {
+    let (kind, config_path) = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("networking: {}", e);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let config = match server_config(kind, config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("networking: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match config {
+        ServerConfig::Echo(config) => lb_echo_server::serve(config).await,
+        ServerConfig::Balancer(config) => {
+            lb_echo_server::balance(config).await
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn parse_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<(ServerKind, Option<PathBuf>), String> {
+    match args.next().as_deref() {
+        Some("serve") => {}
+        Some(command) => return Err(format!("unknown command {}", command)),
+        None => return Err("missing command".into()),
+    }
+    let kind = args.next().ok_or("serve needs a server")?.parse()?;
+
+    let mut config = None;
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--config" => {
+                let path = args.next().ok_or("--config needs a file")?;
+                config = Some(PathBuf::from(path));
+            }
+            _ => return Err(format!("unknown option {}", flag)),
+        }
     }
+    Ok((kind, config))
+}
+
+fn server_config(
+    kind: ServerKind,
+    path: Option<PathBuf>,
+) -> Result<ServerConfig, Box<dyn Error>> {
+    if let Some(path) = path {
+        return Ok(config::load(&path, kind)?);
+    }
+
+    let from_env = |mode: Option<Mode>, vars: &str| {
+        mode.map(BalancerConfig::from_env_with)
+            .map(ServerConfig::Balancer)
+            .ok_or_else(|| {
+                format!(
+                    "no backends for {}: set {} or pass --config",
+                    kind.name(),
+                    vars
+                )
+            })
+    };
+    Ok(match kind {
+        ServerKind::Echo => ServerConfig::Echo(EchoConfig::default()),
+        ServerKind::Lb => {
+            from_env(Mode::tcp_from_env(), "LB_BACKENDS or LB_ROUTES")?
+        }
+        ServerKind::Proxy => {
+            from_env(Mode::http_from_env(), "LB_BACKENDS or LB_HTTP_ROUTES")?
+        }
+    })
 }
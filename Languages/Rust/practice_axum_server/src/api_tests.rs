@@ -0,0 +1,348 @@
+// Tests for the users API against its OpenAPI document
+//
+// Requests go through the whole router (middleware included) with tower's
+// `oneshot`: no socket, no server task. Every response is checked against
+// the operation the spec documents for it, so a handler that drifts from
+// its #[utoipa::path] (undocumented status, field the schema doesn't know,
+// wrong shape) fails here instead of in a client.
+
+use std::collections::BTreeSet;
+
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, header};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use super::*;
+
+// ============================================================================
+// TYPED CLIENT
+// ============================================================================
+
+// One method per documented operation; records which (method, path,
+// status) triples were seen so a test can require all of them
+struct TestClient {
+    app: Router,
+    spec: Value,
+    seen: BTreeSet<(String, String, u16)>,
+}
+
+struct ApiResponse {
+    status: StatusCode,
+    body: Value, // Null when the response has no body
+}
+
+impl ApiResponse {
+    fn json<T: DeserializeOwned>(self) -> T {
+        serde_json::from_value(self.body).expect("body matches the DTO")
+    }
+}
+
+impl TestClient {
+    fn new() -> Self {
+        TestClient {
+            app: app(AppState::new()),
+            spec: serde_json::to_value(ApiDoc::openapi()).unwrap(),
+            seen: BTreeSet::new(),
+        }
+    }
+
+    async fn create_user(&mut self, name: &str, email: &str) -> ApiResponse {
+        let body = json!({ "name": name, "email": email });
+        self.send(Method::POST, "/api/users", "/api/users", Some(body))
+            .await
+    }
+
+    async fn list_users(&mut self, search: Option<&str>) -> ApiResponse {
+        let uri = match search {
+            Some(search) => format!("/api/users?search={}", search.replace(' ', "%20")),
+            None => "/api/users".to_string(),
+        };
+        self.send(Method::GET, "/api/users", &uri, None).await
+    }
+
+    async fn get_user(&mut self, id: u64) -> ApiResponse {
+        let uri = format!("/api/users/{id}");
+        self.send(Method::GET, "/api/users/{id}", &uri, None).await
+    }
+
+    async fn delete_user(&mut self, id: u64) -> ApiResponse {
+        let uri = format!("/api/users/{id}");
+        self.send(Method::DELETE, "/api/users/{id}", &uri, None)
+            .await
+    }
+
+    // `route` is the path as documented, `uri` the one actually requested
+    async fn send(
+        &mut self,
+        method: Method,
+        route: &str,
+        uri: &str,
+        json: Option<Value>,
+    ) -> ApiResponse {
+        let mut request = Request::builder().method(method.clone()).uri(uri);
+        let body = match json {
+            Some(json) => {
+                request = request.header(header::CONTENT_TYPE, "application/json");
+                Body::from(json.to_string())
+            }
+            None => Body::empty(),
+        };
+
+        let response = self
+            .app
+            .clone()
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).expect("API responses are JSON")
+        };
+
+        if let Err(drift) = check_response(&self.spec, &method, route, status, &body) {
+            panic!("{method} {uri} -> {status} doesn't match the spec: {drift}");
+        }
+        self.seen
+            .insert((method.to_string(), route.to_string(), status.as_u16()));
+
+        ApiResponse { status, body }
+    }
+
+    // Every status of every documented operation this client has seen
+    fn assert_covered_spec(&self) {
+        let documented = documented_responses(&self.spec);
+        let missing: Vec<_> = documented.difference(&self.seen).collect();
+        assert!(missing.is_empty(), "never exercised: {missing:?}");
+    }
+}
+
+// ============================================================================
+// SPEC CHECKS
+// ============================================================================
+
+// (METHOD, path, status) for every response in the spec
+fn documented_responses(spec: &Value) -> BTreeSet<(String, String, u16)> {
+    let mut documented = BTreeSet::new();
+    for (path, operations) in spec["paths"].as_object().unwrap() {
+        for (method, operation) in operations.as_object().unwrap() {
+            for status in operation["responses"].as_object().unwrap().keys() {
+                documented.insert((method.to_uppercase(), path.clone(), status.parse().unwrap()));
+            }
+        }
+    }
+    documented
+}
+
+// The response must be documented for the operation, and its body must
+// match the documented JSON schema (or be empty if none is documented)
+fn check_response(
+    spec: &Value,
+    method: &Method,
+    route: &str,
+    status: StatusCode,
+    body: &Value,
+) -> Result<(), String> {
+    let operation = &spec["paths"][route][method.as_str().to_lowercase()];
+    if operation.is_null() {
+        return Err(format!("{method} {route} is not documented"));
+    }
+
+    let response = &operation["responses"][status.as_u16().to_string()];
+    if response.is_null() {
+        return Err(format!("status {} is not documented", status.as_u16()));
+    }
+
+    match response["content"]["application/json"].get("schema") {
+        Some(schema) => validate(spec, schema, body, "body"),
+        None if body.is_null() => Ok(()),
+        None => Err(format!("no body is documented, got {body}")),
+    }
+}
+
+// Just enough JSON Schema for what utoipa emits here: $ref, type (single
+// or list), required, properties, items, minimum. Stricter than JSON
+// Schema on one point: fields missing from `properties` are drift.
+fn validate(spec: &Value, schema: &Value, value: &Value, at: &str) -> Result<(), String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference
+            .strip_prefix("#/components/schemas/")
+            .ok_or_else(|| format!("{at}: unsupported $ref {reference}"))?;
+        let target = &spec["components"]["schemas"][name];
+        if target.is_null() {
+            return Err(format!("{at}: $ref to missing schema {name}"));
+        }
+        return validate(spec, target, value, at);
+    }
+
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+        if !types.iter().any(|ty| has_type(value, ty)) {
+            return Err(format!(
+                "{at}: expected {}, got {value}",
+                types.join(" or ")
+            ));
+        }
+    }
+
+    if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+        && value.as_f64().is_some_and(|n| n < minimum)
+    {
+        return Err(format!("{at}: {value} is below the minimum {minimum}"));
+    }
+
+    if let Some(object) = value.as_object() {
+        let required = schema["required"].as_array().into_iter().flatten();
+        for field in required.filter_map(Value::as_str) {
+            if !object.contains_key(field) {
+                return Err(format!("{at}: missing required field `{field}`"));
+            }
+        }
+
+        if let Some(properties) = schema["properties"].as_object() {
+            for (key, field) in object {
+                let field_schema = properties
+                    .get(key)
+                    .ok_or_else(|| format!("{at}: field `{key}` is not in the schema"))?;
+                validate(spec, field_schema, field, &format!("{at}.{key}"))?;
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in array.iter().enumerate() {
+            validate(spec, items, item, &format!("{at}[{i}]"))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn every_documented_response_matches_the_handlers() {
+    let mut client = TestClient::new();
+
+    // POST /api/users -> 201
+    let alice = client.create_user("Alice", "alice@example.com").await;
+    assert_eq!(alice.status, StatusCode::CREATED);
+    let alice: User = alice.json();
+    let bob: User = client.create_user("Bob", "bob@work.example").await.json();
+    assert_eq!((alice.id, bob.id), (1, 2));
+
+    // GET /api/users -> 200, with and without a search
+    let all: Vec<User> = client.list_users(None).await.json();
+    assert_eq!(all.len(), 2);
+    let found = client.list_users(Some("email:@work bob")).await;
+    assert_eq!(found.status, StatusCode::OK);
+    let found: Vec<User> = found.json();
+    assert_eq!(found.iter().map(|u| u.id).collect::<Vec<_>>(), [bob.id]);
+
+    // GET /api/users -> 400 on a bad term
+    let bad = client.list_users(Some("age:3")).await;
+    assert_eq!(bad.status, StatusCode::BAD_REQUEST);
+    assert_eq!(bad.body["token"], "age:3");
+
+    // GET /api/users/{id} -> 200, 404
+    let got = client.get_user(alice.id).await;
+    assert_eq!(got.status, StatusCode::OK);
+    assert_eq!(got.json::<User>().email, "alice@example.com");
+    assert_eq!(client.get_user(99).await.status, StatusCode::NOT_FOUND);
+
+    // DELETE /api/users/{id} -> 204, then 404
+    assert_eq!(
+        client.delete_user(alice.id).await.status,
+        StatusCode::NO_CONTENT
+    );
+    assert_eq!(
+        client.delete_user(alice.id).await.status,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        client.get_user(alice.id).await.status,
+        StatusCode::NOT_FOUND
+    );
+
+    client.assert_covered_spec();
+}
+
+#[tokio::test]
+async fn openapi_json_serves_the_generated_document() {
+    let client = TestClient::new();
+    let request = Request::get("/openapi.json").body(Body::empty()).unwrap();
+
+    let response = client.app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let served: Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(served, client.spec);
+    let paths: Vec<_> = served["paths"].as_object().unwrap().keys().collect();
+    assert_eq!(paths, ["/api/users", "/api/users/{id}"]);
+}
+
+#[test]
+fn validation_catches_drift() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let user = |body: Value| {
+        check_response(
+            &spec,
+            &Method::GET,
+            "/api/users/{id}",
+            StatusCode::OK,
+            &body,
+        )
+    };
+
+    assert_eq!(user(json!({ "id": 1, "name": "a", "email": "b" })), Ok(()));
+    assert_eq!(
+        user(json!({ "id": 1, "name": "a" })),
+        Err("body: missing required field `email`".to_string())
+    );
+    assert_eq!(
+        user(json!({ "id": 1, "name": "a", "email": "b", "age": 3 })),
+        Err("body: field `age` is not in the schema".to_string())
+    );
+    assert!(user(json!({ "id": "1", "name": "a", "email": "b" })).is_err());
+    assert!(user(json!([])).is_err());
+
+    let status = check_response(
+        &spec,
+        &Method::GET,
+        "/api/users/{id}",
+        StatusCode::CONFLICT,
+        &Value::Null,
+    );
+    assert_eq!(status, Err("status 409 is not documented".to_string()));
+    let empty = check_response(
+        &spec,
+        &Method::DELETE,
+        "/api/users/{id}",
+        StatusCode::NO_CONTENT,
+        &json!({}),
+    );
+    assert!(empty.is_err(), "204 is documented without a body");
+}
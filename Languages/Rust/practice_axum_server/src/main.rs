@@ -1,31 +1,53 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use axum::extract::ws::WebSocket;
 use axum::extract::{Path, Query, Request, State, WebSocketUpgrade};
-use axum::http::{Method, StatusCode};
+use axum::http::{HeaderName, Method, StatusCode};
 use axum::middleware::Next;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{Html, IntoResponse};
 use axum::routing::{get, post};
 use axum::{Json, Router, middleware};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
+use utoipa::{OpenApi, ToSchema};
+
+#[cfg(test)]
+mod api_tests;
 
 #[derive(Clone)]
 struct AppState {
     users: Arc<Mutex<HashMap<u64, User>>>,
     request_count: Arc<Mutex<u64>>,
+    // Latest stats; every change is pushed to /stats/stream subscribers
+    stats: Arc<watch::Sender<StatsResponse>>,
+    // When each user last made a request (set on create, bumped by
+    // last_seen_middleware, read by the background cleanup task)
+    last_seen: Arc<Mutex<HashMap<u64, Instant>>>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+// Requests carrying this header count as activity for that user
+const USER_ID_HEADER: HeaderName = HeaderName::from_static("x-user-id");
+
+// Users idle for longer than STALE_AFTER are removed by the cleanup task,
+// which wakes up every CLEANUP_INTERVAL
+const STALE_AFTER: Duration = Duration::from_secs(5 * 60);
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 struct User {
     id: u64,
     name: String,
     email: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateUserRequest {
     name: String,
     email: String,
@@ -36,12 +58,41 @@ struct QueryParams {
     search: Option<String>,
 }
 
-#[derive(Serialize)]
+// One term of the search DSL: `?search=name:bob email:@example.com`
+// Terms are separated by spaces and must all match (AND).
+// A bare word (`bob`) searches the name, like the old plain search did.
+#[derive(Debug, PartialEq)]
+enum UserFilter {
+    Name(String),  // name:bob -> name contains "bob" (case-insensitive)
+    Email(String), // email:@example.com -> email contains it (case-insensitive)
+    Id(u64),       // id:3 -> exactly user 3
+}
+
+// Returned as 400 when a search term doesn't parse
+#[derive(Debug, Serialize, ToSchema)]
+struct SearchError {
+    error: String,
+    token: String,
+}
+
+#[derive(Clone, Serialize)]
 struct StatsResponse {
     total_requests: u64,
     total_users: usize,
 }
 
+// OpenAPI document for the users API, served at /openapi.json
+// Built from the #[utoipa::path] attributes on the handlers and the
+// ToSchema derives on the DTOs; api_tests checks real responses against it
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Users API", description = "JSON API of the practice axum server"),
+    paths(create_user, list_users, get_user, delete_user),
+    components(schemas(User, CreateUserRequest, SearchError)),
+    tags((name = "users", description = "Create, search and delete users"))
+)]
+struct ApiDoc;
+
 #[tokio::main]
 async fn main() {
     // Initialize tracing (logging)
@@ -50,18 +101,51 @@ async fn main() {
     println!("=== AXUM WEB SERVER ===\n");
 
     // Initialize shared state
-    let state = AppState {
-        users: Arc::new(Mutex::new(HashMap::new())),
-        request_count: Arc::new(Mutex::new(0)),
-    };
+    let state = AppState::new();
+
+    // Background worker: shares the same state as the handlers.
+    // Spawned before serving so it runs for the whole server lifetime.
+    tokio::spawn(cleanup_task(state.clone()));
+
+    let app = app(state);
+
+    // Start server
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+
+    println!("[SERVER] Listening on http://127.0.0.1:3000");
+    println!("\nEndpoints:");
+    println!("  GET    /");
+    println!("  GET    /about");
+    println!("  GET    /stats");
+    println!("  GET    /stats/stream (Server-Sent Events)");
+    println!("  GET    /openapi.json (OpenAPI document for /api)");
+    println!("  GET    /api/users?search=name:bob email:@example.com");
+    println!("  POST   /api/users");
+    println!("  GET    /api/users/:id");
+    println!("  DELETE /api/users/:id");
+    println!("\nSend `x-user-id: <id>` to mark a user active;");
+    println!(
+        "users idle for {}s are removed every {}s.\n",
+        STALE_AFTER.as_secs(),
+        CLEANUP_INTERVAL.as_secs()
+    );
 
-    // Build router
-    // This is where Axum's magic happens: declarative routing
-    let app = Router::new()
+    axum::serve(listener, app).await.unwrap();
+}
+
+// Build router
+// This is where Axum's magic happens: declarative routing
+// (separate from main so tests can drive it without a socket)
+fn app(state: AppState) -> Router {
+    Router::new()
         // HTML endpoints
         .route("/", get(home_handler))
         .route("/about", get(about_handler))
         .route("/stats", get(stats_handler))
+        .route("/stats/stream", get(stats_stream_handler))
+        .route("/openapi.json", get(openapi_handler))
         // API endpoints
         .route("/api/users", post(create_user).get(list_users))
         .route("/api/users/{id}", get(get_user))
@@ -75,29 +159,180 @@ async fn main() {
             state.clone(),
             request_counter_middleware,
         ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            last_seen_middleware,
+        ))
         .layer(middleware::from_fn(logging_middleware))
         .layer(
             CorsLayer::new()
                 .allow_origin(tower_http::cors::Any)
                 .allow_methods([Method::GET, Method::POST]),
         )
-        .layer(TraceLayer::new_for_http());
+        .layer(TraceLayer::new_for_http())
+}
 
-    // Start server
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
-        .await
-        .unwrap();
+impl AppState {
+    fn new() -> Self {
+        AppState {
+            users: Arc::new(Mutex::new(HashMap::new())),
+            request_count: Arc::new(Mutex::new(0)),
+            stats: Arc::new(watch::Sender::new(StatsResponse {
+                total_requests: 0,
+                total_users: 0,
+            })),
+            last_seen: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
 
-    println!("[SERVER] Listening on http://127.0.0.1:3000");
-    println!("\nEndpoints:");
-    println!("  GET    /");
-    println!("  GET    /about");
-    println!("  GET    /api/users");
-    println!("  POST   /api/users");
-    println!("  GET    /api/users/:id");
-    println!("  DELETE /api/users/:id\n");
+    // Read the counters now
+    // (takes both locks: never call while holding one of them)
+    fn current_stats(&self) -> StatsResponse {
+        StatsResponse {
+            total_requests: *self.request_count.lock().unwrap(),
+            total_users: self.users.lock().unwrap().len(),
+        }
+    }
 
-    axum::serve(listener, app).await.unwrap();
+    // Push the current counters to every /stats/stream subscriber
+    fn publish_stats(&self) {
+        self.stats.send_replace(self.current_stats());
+    }
+
+    // Record activity for `id` (ignored if there is no such user, so the
+    // header can't grow the map with made-up ids)
+    // Lock order everywhere: users, then last_seen
+    fn touch_user(&self, id: u64, now: Instant) {
+        let users = self.users.lock().unwrap();
+        if users.contains_key(&id) {
+            self.last_seen.lock().unwrap().insert(id, now);
+        }
+    }
+
+    // Remove every user not seen within `max_idle` of `now`
+    fn remove_stale_users(&self, now: Instant, max_idle: Duration) -> CleanupSummary {
+        let summary = {
+            let mut users = self.users.lock().unwrap();
+            let mut last_seen = self.last_seen.lock().unwrap();
+
+            let mut removed: Vec<u64> = users
+                .keys()
+                .copied()
+                .filter(|id| match last_seen.get(id) {
+                    Some(seen) => now.saturating_duration_since(*seen) > max_idle,
+                    None => true, // never seen: nothing keeps it alive
+                })
+                .collect();
+            removed.sort_unstable();
+
+            for id in &removed {
+                users.remove(id);
+                last_seen.remove(id);
+            }
+
+            CleanupSummary {
+                removed,
+                remaining: users.len(),
+            }
+        };
+
+        if !summary.removed.is_empty() {
+            self.publish_stats();
+        }
+        summary
+    }
+}
+
+// What one cleanup pass did
+#[derive(Debug)]
+struct CleanupSummary {
+    removed: Vec<u64>,
+    remaining: usize,
+}
+
+// ============================================================================
+// BACKGROUND TASKS
+// ============================================================================
+
+// Periodically drop users that stopped making requests.
+// Runs on the same runtime as the server and sees the same AppState:
+// the Arc<Mutex<..>> fields are shared, not copied, by `state.clone()`.
+async fn cleanup_task(state: AppState) {
+    let mut ticker = tokio::time::interval(CLEANUP_INTERVAL);
+    // A slow pass shouldn't cause a burst of catch-up passes
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        let summary = state.remove_stale_users(Instant::now(), STALE_AFTER);
+        if summary.removed.is_empty() {
+            println!("[CLEANUP] No stale users ({} active)", summary.remaining);
+        } else {
+            println!(
+                "[CLEANUP] Removed {} stale users {:?} ({} remaining)",
+                summary.removed.len(),
+                summary.removed,
+                summary.remaining
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod cleanup_tests {
+    use super::*;
+
+    fn add_user(state: &AppState, id: u64) {
+        let user = User {
+            id,
+            name: format!("user{id}"),
+            email: format!("user{id}@example.com"),
+        };
+        state.users.lock().unwrap().insert(id, user);
+    }
+
+    #[test]
+    fn removes_stale_and_never_seen_users_and_keeps_recent_ones() {
+        let state = AppState::new();
+        let start = Instant::now();
+        let now = start + Duration::from_secs(10 * 60);
+
+        // 1: last seen 10 minutes ago, 2: a minute ago, 3: never
+        for id in [1, 2, 3] {
+            add_user(&state, id);
+        }
+        state.touch_user(1, start);
+        state.touch_user(2, now - Duration::from_secs(60));
+
+        let summary = state.remove_stale_users(now, STALE_AFTER);
+
+        assert_eq!(summary.removed, [1, 3]);
+        assert_eq!(summary.remaining, 1);
+
+        let users = state.users.lock().unwrap();
+        assert_eq!(users.keys().copied().collect::<Vec<_>>(), [2]);
+        let last_seen = state.last_seen.lock().unwrap();
+        assert_eq!(last_seen.keys().copied().collect::<Vec<_>>(), [2]);
+        drop((users, last_seen));
+
+        // Subscribers hear about the removals
+        assert_eq!(state.stats.borrow().total_users, 1);
+    }
+
+    #[test]
+    fn a_pass_with_nothing_stale_removes_nothing() {
+        let state = AppState::new();
+        let now = Instant::now();
+        add_user(&state, 1);
+        state.touch_user(1, now);
+
+        let summary = state.remove_stale_users(now + STALE_AFTER, STALE_AFTER);
+
+        assert!(summary.removed.is_empty());
+        assert_eq!(summary.remaining, 1);
+        assert!(state.last_seen.lock().unwrap().contains_key(&1));
+    }
 }
 
 // ============================================================================
@@ -115,11 +350,32 @@ async fn request_counter_middleware(
         let mut count = state.request_count.lock().unwrap();
         *count += 1;
     }
+    state.publish_stats();
 
     // Call next middleware/handler
     next.run(request).await
 }
 
+// Custom middleware: Track user activity
+// A request with `x-user-id: <id>` marks that user as seen now
+async fn last_seen_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let user_id = request
+        .headers()
+        .get(USER_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    if let Some(id) = user_id {
+        state.touch_user(id, Instant::now());
+    }
+
+    next.run(request).await
+}
+
 // Custom middleware: Log all requests
 async fn logging_middleware(request: Request, next: Next) -> impl IntoResponse {
     let method = request.method().clone();
@@ -161,8 +417,13 @@ async fn home_handler() -> Html<&'static str> {
                 <li>WebSockets</li>
                 <li>Middleware (logging, CORS, request counting)</li>
                 <li>Shared state</li>
+                <li>Background tasks (stale user cleanup)</li>
+                <li>Server-Sent Events</li>
             </ul>
 
+            <h2>Live Stats</h2>
+            <p>Requests: <strong id="requests">-</strong> &middot; Users: <strong id="users">-</strong></p>
+
             <h2>WebSocket Test</h2>
             <button onclick="connect()">Connect WebSocket</button>
             <button onclick="sendMessage()">Send Message</button>
@@ -173,9 +434,18 @@ async fn home_handler() -> Html<&'static str> {
             <ul>
                 <li><a href="/stats">Server Stats</a></li>
                 <li><a href="/api/users">Users API</a></li>
+                <li><a href="/openapi.json">OpenAPI document</a></li>
             </ul>
 
             <script>
+                // Live dashboard: the server pushes a `stats` event on every change
+                const stats = new EventSource('/stats/stream');
+                stats.addEventListener('stats', (event) => {
+                    const data = JSON.parse(event.data);
+                    document.getElementById('requests').textContent = data.total_requests;
+                    document.getElementById('users').textContent = data.total_users;
+                });
+
                 let ws = null;
 
                 function connect() {
@@ -223,13 +493,36 @@ async fn home_handler() -> Html<&'static str> {
 }
 
 async fn stats_handler(State(state): State<AppState>) -> Json<StatsResponse> {
-    let count = *state.request_count.lock().unwrap();
-    let users_count = state.users.lock().unwrap().len();
+    Json(state.current_stats())
+}
 
-    Json(StatsResponse {
-        total_requests: count,
-        total_users: users_count,
-    })
+// Handler: Live stats (GET /stats/stream)
+// Server-Sent Events: one `stats` event right away, then one per change.
+// The watch channel only keeps the latest value, so a burst of requests
+// reaches a slow client as a single event instead of piling up.
+async fn stats_stream_handler(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let mut rx = state.stats.subscribe();
+    rx.mark_changed(); // send the current value first
+
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        // Err = sender dropped (server shutting down): end the stream
+        rx.changed().await.ok()?;
+        let stats = rx.borrow_and_update().clone();
+        let event = Event::default()
+            .event("stats")
+            .json_data(&stats)
+            .expect("stats serialize to JSON");
+        Some((Ok(event), rx))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+// Handler: OpenAPI document (GET /openapi.json)
+async fn openapi_handler() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
 }
 
 // Handler: About page
@@ -260,47 +553,146 @@ async fn about_handler() -> Html<&'static str> {
 // - Extracts JSON from request body
 // - Deserializes into CreateUserRequest
 // - Injects AppState
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "users",
+    request_body = CreateUserRequest,
+    responses((status = 201, description = "User created", body = User))
+)]
 async fn create_user(
     State(state): State<AppState>,
     Json(payload): Json<CreateUserRequest>,
 ) -> (StatusCode, Json<User>) {
-    let mut users = state.users.lock().unwrap();
+    let user = {
+        let mut users = state.users.lock().unwrap();
+
+        // Next after the highest id: users.len() would reuse a live id
+        // once the cleanup task (or DELETE) has removed someone
+        let id = users.keys().max().map_or(1, |max| max + 1);
+        let user = User {
+            id,
+            name: payload.name,
+            email: payload.email,
+        };
 
-    let id = users.len() as u64 + 1;
-    let user = User {
-        id,
-        name: payload.name,
-        email: payload.email,
+        users.insert(id, user.clone());
+        // A new user counts as seen
+        state.last_seen.lock().unwrap().insert(id, Instant::now());
+        user
     };
-
-    users.insert(id, user.clone());
+    state.publish_stats();
 
     println!("[API] Created user: {:?}", user);
 
     (StatusCode::CREATED, Json(user))
 }
 
-// Handler: List users (GET /api/users?search=...)
+// Handler: List users (GET /api/users?search=name:bob email:@example.com)
+// Invalid search syntax is a 400 naming the offending token
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "users",
+    params((
+        "search" = Option<String>,
+        Query,
+        description = "Space-separated terms that must all match: `name:bob`, `email:@example.com`, `id:3` or a bare name"
+    )),
+    responses(
+        (status = 200, description = "Matching users, by id", body = Vec<User>),
+        (status = 400, description = "A search term doesn't parse", body = SearchError)
+    )
+)]
 async fn list_users(
     State(state): State<AppState>,
     Query(params): Query<QueryParams>,
-) -> Json<Vec<User>> {
+) -> Result<Json<Vec<User>>, (StatusCode, Json<SearchError>)> {
+    // Parse first: a bad query never touches the store
+    let filters = match params.search {
+        Some(search) => parse_search(&search).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?,
+        None => Vec::new(),
+    };
+
     let users = state.users.lock().unwrap();
+    let result = find_users(&users, &filters);
+
+    println!("[API] Listed {} users", result.len());
+
+    Ok(Json(result))
+}
+
+// ============================================================================
+// SEARCH DSL
+// ============================================================================
+
+// Parse `?search=` into typed filters, one per space-separated token
+fn parse_search(search: &str) -> Result<Vec<UserFilter>, SearchError> {
+    search.split_whitespace().map(UserFilter::parse).collect()
+}
+
+impl UserFilter {
+    fn parse(token: &str) -> Result<Self, SearchError> {
+        let fail = |error: String| SearchError {
+            error,
+            token: token.to_string(),
+        };
+
+        // No `field:` prefix -> plain name search
+        let Some((field, value)) = token.split_once(':') else {
+            return Ok(UserFilter::Name(token.to_lowercase()));
+        };
 
-    let mut result: Vec<User> = users.values().cloned().collect();
+        if value.is_empty() {
+            return Err(fail(format!("'{field}:' needs a value, e.g. {field}:bob")));
+        }
 
-    // Filter by search if provided
-    if let Some(search) = params.search {
-        result.retain(|u| u.name.to_lowercase().contains(&search.to_lowercase()));
+        match field {
+            "name" => Ok(UserFilter::Name(value.to_lowercase())),
+            "email" => Ok(UserFilter::Email(value.to_lowercase())),
+            "id" => value
+                .parse()
+                .map(UserFilter::Id)
+                .map_err(|_| fail(format!("'{value}' is not a valid id (expected a number)"))),
+            _ => Err(fail(format!(
+                "unknown field '{field}' (expected name, email or id)"
+            ))),
+        }
     }
 
-    println!("[API] Listed {} users", result.len());
+    fn matches(&self, user: &User) -> bool {
+        match self {
+            UserFilter::Name(needle) => user.name.to_lowercase().contains(needle),
+            UserFilter::Email(needle) => user.email.to_lowercase().contains(needle),
+            UserFilter::Id(id) => user.id == *id,
+        }
+    }
+}
 
-    Json(result)
+// "Repository" query: the users matching every filter, by id
+// (no filters = everyone)
+fn find_users(users: &HashMap<u64, User>, filters: &[UserFilter]) -> Vec<User> {
+    let mut result: Vec<User> = users
+        .values()
+        .filter(|user| filters.iter().all(|filter| filter.matches(user)))
+        .cloned()
+        .collect();
+    result.sort_by_key(|user| user.id);
+    result
 }
 
 // Handler: Get single user (GET /api/users/:id)
 // Axum extracts :id from path
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}",
+    tag = "users",
+    params(("id" = u64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user", body = User),
+        (status = 404, description = "No such user")
+    )
+)]
 async fn get_user(
     State(state): State<AppState>,
     Path(id): Path<u64>,
@@ -315,10 +707,25 @@ async fn get_user(
 }
 
 // Handler: Delete user (DELETE /api/users/:id)
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}",
+    tag = "users",
+    params(("id" = u64, Path, description = "User id")),
+    responses(
+        (status = 204, description = "User deleted"),
+        (status = 404, description = "No such user")
+    )
+)]
 async fn delete_user(State(state): State<AppState>, Path(id): Path<u64>) -> StatusCode {
-    let mut users = state.users.lock().unwrap();
+    let removed = {
+        let mut users = state.users.lock().unwrap();
+        state.last_seen.lock().unwrap().remove(&id);
+        users.remove(&id).is_some()
+    };
 
-    if users.remove(&id).is_some() {
+    if removed {
+        state.publish_stats();
         println!("[API] Deleted user {}", id);
         StatusCode::NO_CONTENT
     } else {
@@ -394,6 +801,9 @@ async fn handle_socket(mut socket: WebSocket) {
 //    - WebSocketUpgrade: WebSocket handshake
 //    All with automatic deserialization!
 //
+//    Responses too: Sse<impl Stream> keeps the connection open and
+//    writes each Event as `event: ...\ndata: ...\n\n`
+//
 // 3. MIDDLEWARE (Composable):
 //    .layer(CorsLayer)
 //    .layer(TraceLayer)
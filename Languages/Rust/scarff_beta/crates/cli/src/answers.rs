@@ -0,0 +1,143 @@
+//! Answers file for non-interactive `scarff new`.
+//!
+//! `--answers answers.toml` supplies everything a run would otherwise take
+//! from flags or ask for, so CI pipelines can scaffold reproducibly:
+//!
+//! ```toml
+//! [target]            # same keys as scarff.target.toml
+//! language = "python"
+//! kind = "web-backend"
+//! framework = "fastapi"
+//!
+//! [variables]         # template variables ({{KEY}})
+//! AUTHOR = "CI Bot"
+//!
+//! [prompts]           # responses to interactive prompts
+//! confirm = true
+//! ```
+//!
+//! `target.language`, `target.kind` and every prompt are required; an
+//! incomplete file is rejected with all of its missing keys at once.
+
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use scarff_core::Target;
+
+use crate::error::{CliError, CliResul};
+
+/// A parsed and validated answers file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    /// Target to scaffold
+    pub target: Target,
+    /// Extra template variables
+    pub variables: BTreeMap<String, String>,
+    /// Response to "continue?"; `false` cancels the run
+    pub confirm: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnswersFile {
+    #[serde(default)]
+    target: Option<toml::Table>,
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+    #[serde(default)]
+    prompts: Prompts,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Prompts {
+    confirm: Option<bool>,
+}
+
+/// Read and validate an answers file.
+pub fn load(path: &Path) -> CliResul<Answers> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read answers file {}", path.display()))?;
+    parse(&source).with_context(|| format!("Failed to load answers from {}", path.display()))
+}
+
+/// Parse an answers file, listing every missing key if it's incomplete.
+pub fn parse(source: &str) -> CliResul<Answers> {
+    let file: AnswersFile = toml::from_str(source).context("Malformed answers file")?;
+
+    let mut missing = Vec::new();
+    let target = file.target.unwrap_or_default();
+    if !target.contains_key("language") {
+        missing.push("target.language".to_string());
+    }
+    if !target.contains_key("kind") && !target.contains_key("type") {
+        missing.push("target.kind".to_string());
+    }
+    if file.prompts.confirm.is_none() {
+        missing.push("prompts.confirm".to_string());
+    }
+    if !missing.is_empty() {
+        return Err(CliError::IncompleteAnswers { missing }.into());
+    }
+
+    let target = Target::from_toml(&target.to_string()).context("Invalid [target] table")?;
+
+    Ok(Answers {
+        target,
+        variables: file.variables,
+        confirm: file.prompts.confirm.unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scarff_core::{Language, ProjectKind};
+
+    #[test]
+    fn parses_complete_answers() {
+        let answers = parse(
+            "[target]\nlanguage = \"python\"\ntype = \"backend\"\n\n\
+             [variables]\nAUTHOR = \"CI Bot\"\n\n\
+             [prompts]\nconfirm = true\n",
+        )
+        .unwrap();
+
+        assert_eq!(answers.target.language(), Language::Python);
+        assert_eq!(answers.target.kind(), ProjectKind::WebBackend);
+        assert_eq!(answers.variables["AUTHOR"], "CI Bot");
+        assert!(answers.confirm);
+    }
+
+    #[test]
+    fn lists_every_missing_key() {
+        let err = parse("[target]\nlanguage = \"rust\"\n").unwrap_err();
+
+        match err.downcast::<CliError>().unwrap() {
+            CliError::IncompleteAnswers { missing } => {
+                assert_eq!(missing, ["target.kind", "prompts.confirm"]);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn empty_file_is_missing_everything() {
+        let err = parse("").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("target.language, target.kind, prompts.confirm")
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_sections_and_invalid_targets() {
+        assert!(parse("[prompt]\nconfirm = true\n").is_err());
+        assert!(
+            parse("[target]\nlanguage = \"cobol\"\nkind = \"cli\"\n[prompts]\nconfirm = true\n")
+                .is_err()
+        );
+    }
+}
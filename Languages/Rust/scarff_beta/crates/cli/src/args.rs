@@ -3,10 +3,10 @@
 //!
 //! This module defines the CLI interface using clap's derive API.
 
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
-use crate::commands;
+use crate::{commands, output::Verbosity};
 use anyhow::Result;
 
 // ============================================================================
@@ -32,6 +32,14 @@ use anyhow::Result;
         scarff new ../my-app -l rust -t web_api -a layered -f axum\n\n  \
         # Interactive mode (future feature)\n  \
         scarff new my-project --interactive\n\n\
+        EXIT CODES:\n  \
+        0  success\n  \
+        1  other error\n  \
+        2  usage error (unknown flag, option or preset)\n  \
+        3  validation failed (name, target or template)\n  \
+        4  template not found\n  \
+        5  filesystem conflict (project exists, write failed, upgrade conflicts)\n  \
+        6  hook failed (post-scaffold verification or git)\n\n\
         For more information, visit: https://github.com/yourusername/scarff"
 )]
 pub struct Cli {
@@ -39,15 +47,16 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    /// Enable verbose output (shows progress and detailed information)
+    /// Increase output verbosity (repeatable)
     #[arg(
         short = 'v',
         long = "verbose",
         global = true,
+        action = ArgAction::Count,
         conflicts_with = "quiet",
-        help = "Show detailed progress information"
+        help = "Show detailed progress information (-vv for debug logs)"
     )]
-    pub verbose: bool,
+    pub verbose: u8,
 
     /// Suppress all non-error output
     #[arg(
@@ -59,21 +68,60 @@ pub struct Cli {
     )]
     pub quiet: bool,
 
+    /// When to use colored output
+    #[arg(
+        long = "color",
+        value_name = "WHEN",
+        value_enum,
+        global = true,
+        default_value_t = ColorWhen::Auto,
+        help = "When to use colored output"
+    )]
+    pub color: ColorWhen,
+
     /// Disable colored output
     #[arg(
         long = "no-color",
         global = true,
         env = "NO_COLOR",
-        help = "Disable colored output"
+        help = "Disable colored output (same as --color=never)"
     )]
     pub no_color: bool,
+
+    /// Format for errors written to stderr
+    #[arg(
+        long = "format",
+        value_name = "FORMAT",
+        value_enum,
+        global = true,
+        default_value_t = OutputFormat::Text,
+        help = "Error output format (json prints {code, message, context})"
+    )]
+    pub format: OutputFormat,
 }
 
 impl Cli {
     /// Execute the parsed command.
     pub fn execute(self) -> Result<()> {
+        let verbosity = self.verbosity();
         match self.command {
-            Commands::New(cmd) => commands::new::execute(cmd, self.verbose, self.quiet),
+            Commands::New(cmd) => commands::new::execute(cmd, verbosity),
+            Commands::Search(cmd) => commands::search::execute(&cmd, verbosity),
+            Commands::Upgrade(cmd) => commands::upgrade::execute(&cmd, verbosity),
+        }
+    }
+
+    /// Output verbosity derived from `-q` and the number of `-v` flags.
+    pub fn verbosity(&self) -> Verbosity {
+        Verbosity::from_flags(self.verbose, self.quiet)
+    }
+
+    /// Whether colors are forced on, forced off, or left to terminal detection.
+    pub fn color(&self) -> ColorWhen {
+        if self.no_color {
+            ColorWhen::Never
+        } else {
+            self.color
         }
     }
 }
@@ -95,9 +143,34 @@ pub enum Commands {
             # Output to specific directory\n  \
             scarff new ../projects/my-app -l rust -t backend -f axum\n\n  \
             # Skip confirmation prompt\n  \
-            scarff new my-cli -l rust -t cli -a layered --yes"
+            scarff new my-cli -l rust -t cli -a layered --yes\n\n  \
+            # From a preset defined in ~/.config/scarff/config.toml\n  \
+            scarff new my-api --preset my-company-api\n\n  \
+            # Save the target, then reuse it later\n  \
+            scarff new my-api -l python -t backend --save-target --dry-run\n  \
+            scarff new other-api --from-file scarff.target.toml\n\n  \
+            # Non-interactive, with target, variables and prompt responses from a file\n  \
+            scarff new my-api --answers answers.toml"
     )]
     New(NewCommand),
+
+    /// Search the community template index
+    #[command(after_help = "EXAMPLES:\n  \
+            # Find templates tagged or named axum\n  \
+            scarff search axum --index-url https://example.com/scarff/index.json\n\n  \
+            # Use SCARFF_INDEX_URL and bypass the cache\n  \
+            SCARFF_INDEX_URL=https://example.com/scarff/index.json scarff search python worker --refresh")]
+    Search(SearchCommand),
+
+    /// Upgrade a generated project to its template's latest version
+    #[command(after_help = "EXAMPLES:\n  \
+            # Apply template changes; conflicts are written as .rej files\n  \
+            scarff upgrade ./my-api\n\n  \
+            # Decide each conflict interactively\n  \
+            scarff upgrade --interactive\n\n  \
+            # Show what would change\n  \
+            scarff upgrade --dry-run")]
+    Upgrade(UpgradeCommand),
 }
 
 // ============================================================================
@@ -122,9 +195,10 @@ pub struct NewCommand {
         long = "lang",
         value_name = "LANGUAGE",
         value_enum,
+        required_unless_present_any = ["preset", "from_file", "answers"],
         help = "Programming language for the project"
     )]
-    pub language: Language,
+    pub language: Option<Language>,
 
     /// Project type
     #[arg(
@@ -132,19 +206,20 @@ pub struct NewCommand {
         long = "type",
         value_name = "TYPE",
         value_enum,
+        required_unless_present_any = ["preset", "from_file", "answers"],
         help = "Type of project to generate"
     )]
-    pub kind: ProjectKind,
+    pub kind: Option<ProjectKind>,
 
-    /// Architecture style
+    /// Architecture style (inferred when omitted)
     #[arg(
         short = 'a',
         long = "arch",
         value_name = "ARCHITECTURE",
         value_enum,
-        help = "Architectural pattern to use"
+        help = "Architectural pattern to use (inferred when omitted)"
     )]
-    pub architecture: Architecture,
+    pub architecture: Option<Architecture>,
 
     /// Framework (optional)
     #[arg(
@@ -155,6 +230,115 @@ pub struct NewCommand {
     )]
     pub framework: Option<String>,
 
+    /// Python dependency manager
+    #[arg(
+        long = "deps",
+        value_name = "MANAGER",
+        value_enum,
+        help = "Python dependency manager: uv, poetry or pip (default: pip)"
+    )]
+    pub deps: Option<Deps>,
+
+    /// Python package layout
+    #[arg(
+        long = "layout",
+        value_name = "LAYOUT",
+        value_enum,
+        help = "Python package layout: src (needs uv or poetry) or flat (default: flat)"
+    )]
+    pub layout: Option<Layout>,
+
+    /// TypeScript test runner
+    #[arg(
+        long = "test-runner",
+        value_name = "RUNNER",
+        value_enum,
+        help = "TypeScript test runner: vitest, jest or none (default: vitest for vite, jest otherwise)"
+    )]
+    pub test_runner: Option<TestRunner>,
+
+    /// Rust task runner
+    #[arg(
+        long = "task-runner",
+        value_name = "RUNNER",
+        value_enum,
+        help = "Rust task file with test, lint and run recipes, plus example tests: just, make or none"
+    )]
+    pub task_runner: Option<TaskRunner>,
+
+    /// Project license
+    #[arg(
+        long = "license",
+        value_name = "LICENSE",
+        value_enum,
+        help = "Write a LICENSE file and set the manifest's license: mit, apache-2.0, gpl-3.0, mpl-2.0 or unlicense"
+    )]
+    pub license: Option<License>,
+
+    /// CI provider
+    #[arg(
+        long = "ci",
+        value_name = "PROVIDER",
+        value_enum,
+        help = "Write a build/test/lint/release pipeline: github-actions, gitlab-ci or circleci"
+    )]
+    pub ci: Option<CiProvider>,
+
+    /// Optional template features
+    #[arg(
+        long = "feature",
+        value_name = "FEATURE",
+        help = "Enable an optional template feature, e.g. docker or ci-github (repeatable)"
+    )]
+    pub features: Vec<String>,
+
+    /// Named preset (built-in or from the config file)
+    #[arg(
+        short = 'p',
+        long = "preset",
+        value_name = "PRESET",
+        conflicts_with_all = ["language", "kind", "architecture", "framework", "deps", "layout", "test_runner", "task_runner", "license", "ci", "features", "tree", "tree_file"],
+        help = "Use a named preset instead of --lang/--type/--framework/--arch"
+    )]
+    pub preset: Option<String>,
+
+    /// Config file with `[presets.<name>]` tables and a `[hooks]` allowlist
+    #[arg(
+        long = "config",
+        value_name = "FILE",
+        env = "SCARFF_CONFIG",
+        help = "Config file to load presets and the hook allowlist from (default: ~/.config/scarff/config.toml)"
+    )]
+    pub config: Option<PathBuf>,
+
+    /// Saved target (`scarff.target.toml`) to scaffold from
+    #[arg(
+        long = "from-file",
+        value_name = "FILE",
+        conflicts_with_all = ["preset", "language", "kind", "architecture", "framework", "deps", "layout", "test_runner", "task_runner", "license", "ci", "features", "tree", "tree_file"],
+        help = "Read the target from a file written by --save-target"
+    )]
+    pub from_file: Option<PathBuf>,
+
+    /// Write the resolved target to a file
+    #[arg(
+        long = "save-target",
+        value_name = "FILE",
+        num_args = 0..=1,
+        default_missing_value = "scarff.target.toml",
+        help = "Save the resolved target for --from-file (default: scarff.target.toml)"
+    )]
+    pub save_target: Option<PathBuf>,
+
+    /// Answers file for non-interactive runs
+    #[arg(
+        long = "answers",
+        value_name = "FILE",
+        conflicts_with_all = ["preset", "from_file", "language", "kind", "architecture", "framework", "deps", "layout", "test_runner", "task_runner", "license", "ci", "features", "tree", "tree_file", "yes"],
+        help = "Take the target, template variables and prompt responses from a TOML file (for CI)"
+    )]
+    pub answers: Option<PathBuf>,
+
     /// Output directory (defaults to current directory)
     #[arg(
         short = 'o',
@@ -164,6 +348,61 @@ pub struct NewCommand {
     )]
     pub output: Option<PathBuf>,
 
+    /// Inline tree for `--type custom`
+    #[arg(
+        long = "tree",
+        value_name = "TREE",
+        conflicts_with = "tree_file",
+        help = "Tree for --type custom: comma-separated paths (dirs end with '/') or JSON"
+    )]
+    pub tree: Option<String>,
+
+    /// Tree file for `--type custom`
+    #[arg(
+        long = "tree-file",
+        value_name = "FILE",
+        help = "File with an indented tree or JSON for --type custom"
+    )]
+    pub tree_file: Option<PathBuf>,
+
+    /// Add boilerplate comment headers to custom tree files
+    #[arg(
+        long = "headers",
+        help = "Prefix files of a custom tree with a comment header"
+    )]
+    pub headers: bool,
+
+    /// Don't generate a README for templates that lack one
+    #[arg(
+        long = "no-readme",
+        help = "Don't generate a README.md from the template's metadata"
+    )]
+    pub no_readme: bool,
+
+    /// Let template hooks run commands and scripts
+    #[arg(
+        long = "allow-hooks",
+        help = "Run the commands and scripts the template declares as hooks (only for templates you trust)"
+    )]
+    pub allow_hooks: bool,
+
+    /// Run hooks without the sandbox
+    #[arg(
+        long = "unsafe-hooks",
+        help = "Run every hook command and script unchecked, even outside the allowlist or the project (implies --allow-hooks)"
+    )]
+    pub unsafe_hooks: bool,
+
+    /// Put the project under git
+    #[arg(
+        long = "vcs",
+        value_name = "MODE",
+        value_enum,
+        default_value_t = Vcs::None,
+        help = "Reuse an enclosing git repo or init one (init), and optionally commit the scaffold (commit)"
+    )]
+    pub vcs: Vcs,
+
     /// Skip confirmation prompt
     #[arg(
         short = 'y',
@@ -173,7 +412,10 @@ pub struct NewCommand {
     pub yes: bool,
 
     /// Overwrite existing directory (use with caution)
-    #[arg(long = "force", help = "Overwrite existing project directory")]
+    #[arg(
+        long = "force",
+        help = "Overwrite existing files in the project directory"
+    )]
     pub force: bool,
 
     /// Dry run (show what would be created without creating it)
@@ -184,10 +426,182 @@ pub struct NewCommand {
     pub dry_run: bool,
 }
 
+// ============================================================================
+// Search Command
+// ============================================================================
+
+#[derive(Debug, Args)]
+pub struct SearchCommand {
+    /// Search terms; every term must match a name, tag or description
+    #[arg(
+        value_name = "QUERY",
+        help = "Search terms (empty lists every template)"
+    )]
+    pub query: Vec<String>,
+
+    /// URL of the template index
+    #[arg(
+        long = "index-url",
+        value_name = "URL",
+        env = "SCARFF_INDEX_URL",
+        help = "Template index URL (http(s):// or file://)"
+    )]
+    pub index_url: String,
+
+    /// Ignore the cached index and fetch it again
+    #[arg(long = "refresh", help = "Fetch the index even if the cache is fresh")]
+    pub refresh: bool,
+}
+
+// ============================================================================
+// Upgrade Command
+// ============================================================================
+
+#[derive(Debug, Args)]
+pub struct UpgradeCommand {
+    /// Project directory (must contain .scarff/provenance.toml)
+    #[arg(
+        value_name = "PATH",
+        default_value = ".",
+        help = "Project to upgrade (default: current directory)"
+    )]
+    pub path: PathBuf,
+
+    /// Accept or skip each conflict instead of writing .rej files
+    #[arg(
+        short = 'i',
+        long = "interactive",
+        help = "Accept or skip each conflicting file"
+    )]
+    pub interactive: bool,
+
+    /// Show the changes without writing anything
+    #[arg(
+        long = "dry-run",
+        conflicts_with = "interactive",
+        help = "Show what would change without writing anything"
+    )]
+    pub dry_run: bool,
+}
+
 // ============================================================================
 // Value Enums
 // ============================================================================
 
+/// When to emit ANSI colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum ColorWhen {
+    /// Color when writing to a terminal
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+/// How errors are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable, colored text
+    Text,
+    /// One JSON object per error, for scripts and CI
+    Json,
+}
+
+/// Python dependency managers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum Deps {
+    /// pyproject.toml + uv.lock
+    Uv,
+    /// pyproject.toml + poetry.lock
+    Poetry,
+    /// requirements.txt + requirements-dev.txt
+    Pip,
+}
+
+/// Python package layouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum Layout {
+    /// Packages under src/, installed by the dependency manager
+    Src,
+    /// Packages at the project root
+    Flat,
+}
+
+/// TypeScript test runners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum TestRunner {
+    /// vitest.config.ts + example test
+    Vitest,
+    /// jest.config.js + example test
+    Jest,
+    /// No test setup
+    None,
+}
+
+/// Rust task runners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum TaskRunner {
+    /// justfile + example tests
+    Just,
+    /// Makefile + example tests
+    Make,
+    /// No task file
+    None,
+}
+
+/// Project licenses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum License {
+    /// MIT
+    #[value(name = "mit")]
+    Mit,
+    /// Apache License 2.0
+    #[value(name = "apache-2.0")]
+    Apache2,
+    /// GNU General Public License v3.0
+    #[value(name = "gpl-3.0")]
+    Gpl3,
+    /// Mozilla Public License 2.0
+    #[value(name = "mpl-2.0")]
+    Mpl2,
+    /// The Unlicense (public domain)
+    #[value(name = "unlicense")]
+    Unlicense,
+}
+
+/// CI providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CiProvider {
+    /// GitHub Actions
+    #[value(name = "github-actions")]
+    GitHubActions,
+    /// GitLab CI/CD
+    #[value(name = "gitlab-ci")]
+    GitLabCi,
+    /// CircleCI
+    #[value(name = "circleci")]
+    CircleCi,
+}
+
+/// What to do with version control after scaffolding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum Vcs {
+    /// Leave version control alone
+    None,
+    /// Reuse an enclosing git repository, or `git init` the project
+    Init,
+    /// Like init, then commit the scaffold
+    Commit,
+}
+
 /// Supported programming languages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "lowercase")]
@@ -218,15 +632,17 @@ pub enum ProjectKind {
     /// Command-line interface application
     Cli,
     /// Backend web service
-    #[value(name = "web_api")]
+    #[value(name = "web_api", alias = "backend")]
     WebApi,
     /// Frontend web application
-    #[value(name = "web_fe")]
+    #[value(name = "web_fe", alias = "frontend")]
     WebFrontend,
     /// Full-stack application (frontend + backend)
     Fullstack,
     /// Background worker/job processor
     Worker,
+    /// Exactly the tree supplied with --tree or --tree-file
+    Custom,
 }
 
 impl std::fmt::Display for ProjectKind {
@@ -237,6 +653,7 @@ impl std::fmt::Display for ProjectKind {
             ProjectKind::WebFrontend => write!(f, "web_frontend"),
             ProjectKind::Fullstack => write!(f, "fullstack"),
             ProjectKind::Worker => write!(f, "worker"),
+            ProjectKind::Custom => write!(f, "custom"),
         }
     }
 }
@@ -254,8 +671,10 @@ pub enum Architecture {
     /// App Router (Next.js specific)
     #[value(name = "app-router")]
     AppRouter,
-    ///Clean / Hexagonal Architecture
+    /// Clean architecture (use cases over domain entities)
     Clean,
+    /// Hexagonal architecture (ports and adapters)
+    Hexagonal,
 }
 
 impl std::fmt::Display for Architecture {
@@ -266,6 +685,7 @@ impl std::fmt::Display for Architecture {
             Architecture::Modular => write!(f, "modular"),
             Architecture::AppRouter => write!(f, "app-router"),
             Architecture::Clean => write!(f, "clean"),
+            Architecture::Hexagonal => write!(f, "hexagonal"),
         }
     }
 }
@@ -348,6 +768,10 @@ mod tests {
             Architecture::from_str("app-router", true).unwrap(),
             Architecture::AppRouter
         );
+        assert_eq!(
+            Architecture::from_str("hexagonal", true).unwrap(),
+            Architecture::Hexagonal
+        );
     }
 
     #[test]
@@ -359,6 +783,76 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn verbose_flag_is_counted() {
+        let cli = Cli::try_parse_from([
+            "scarff", "new", "test", "-l", "rust", "-t", "cli", "-a", "layered", "-vv",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.verbose, 2);
+        assert_eq!(cli.verbosity(), Verbosity::Debug);
+    }
+
+    #[test]
+    fn quiet_flag_sets_quiet_verbosity() {
+        let cli = Cli::try_parse_from([
+            "scarff", "new", "test", "-l", "rust", "-t", "cli", "-a", "layered", "-q",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.verbosity(), Verbosity::Quiet);
+    }
+
+    #[test]
+    fn color_never_is_parsed() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "test",
+            "-l",
+            "rust",
+            "-t",
+            "cli",
+            "-a",
+            "layered",
+            "--color=never",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.color(), ColorWhen::Never);
+    }
+
+    #[test]
+    fn format_json_is_parsed() {
+        let cli = Cli::try_parse_from([
+            "scarff", "new", "test", "-l", "rust", "-t", "cli", "--format", "json",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn no_color_overrides_color_choice() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "test",
+            "-l",
+            "rust",
+            "-t",
+            "cli",
+            "-a",
+            "layered",
+            "--color=always",
+            "--no-color",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.color(), ColorWhen::Never);
+    }
+
     #[test]
     fn short_and_long_forms_work() {
         // Test short form
@@ -368,9 +862,9 @@ mod tests {
         .unwrap();
 
         if let Commands::New(cmd) = cli.command {
-            assert_eq!(cmd.language, Language::Rust);
-            assert_eq!(cmd.kind, ProjectKind::Cli);
-            assert_eq!(cmd.architecture, Architecture::Layered);
+            assert_eq!(cmd.language, Some(Language::Rust));
+            assert_eq!(cmd.kind, Some(ProjectKind::Cli));
+            assert_eq!(cmd.architecture, Some(Architecture::Layered));
         } else {
             panic!("Expected New command");
         }
@@ -382,9 +876,9 @@ mod tests {
         .unwrap();
 
         if let Commands::New(cmd) = cli.command {
-            assert_eq!(cmd.language, Language::Rust);
-            assert_eq!(cmd.kind, ProjectKind::Cli);
-            assert_eq!(cmd.architecture, Architecture::Layered);
+            assert_eq!(cmd.language, Some(Language::Rust));
+            assert_eq!(cmd.kind, Some(ProjectKind::Cli));
+            assert_eq!(cmd.architecture, Some(Architecture::Layered));
         } else {
             panic!("Expected New command");
         }
@@ -404,7 +898,7 @@ mod tests {
         .unwrap();
 
         if let Commands::New(cmd) = cli.command {
-            assert_eq!(cmd.language, Language::Rust);
+            assert_eq!(cmd.language, Some(Language::Rust));
         } else {
             panic!("Expected New command");
         }
@@ -463,6 +957,184 @@ mod tests {
         }
     }
 
+    #[test]
+    fn custom_kind_accepts_tree() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "test",
+            "-l",
+            "rust",
+            "-t",
+            "custom",
+            "--tree",
+            "src/,src/main.rs",
+            "--headers",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.kind, Some(ProjectKind::Custom));
+            assert_eq!(cmd.architecture, None);
+            assert_eq!(cmd.tree.as_deref(), Some("src/,src/main.rs"));
+            assert!(cmd.headers);
+        } else {
+            panic!("Expected New command");
+        }
+    }
+
+    #[test]
+    fn vcs_defaults_to_none() {
+        let cli =
+            Cli::try_parse_from(["scarff", "new", "test", "-l", "rust", "-t", "cli"]).unwrap();
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.vcs, Vcs::None);
+        } else {
+            panic!("Expected New command");
+        }
+
+        let cli = Cli::try_parse_from([
+            "scarff", "new", "test", "-l", "rust", "-t", "cli", "--vcs", "commit",
+        ])
+        .unwrap();
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.vcs, Vcs::Commit);
+        } else {
+            panic!("Expected New command");
+        }
+    }
+
+    #[test]
+    fn tree_and_tree_file_conflict() {
+        let result = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "test",
+            "-l",
+            "rust",
+            "-t",
+            "custom",
+            "--tree",
+            "a.rs",
+            "--tree-file",
+            "tree.txt",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn preset_replaces_language_and_type() {
+        let cli =
+            Cli::try_parse_from(["scarff", "new", "test", "--preset", "my-company-api"]).unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.preset.as_deref(), Some("my-company-api"));
+            assert_eq!(cmd.language, None);
+            assert_eq!(cmd.kind, None);
+        } else {
+            panic!("Expected New command");
+        }
+    }
+
+    #[test]
+    fn preset_conflicts_with_target_flags() {
+        let result = Cli::try_parse_from(["scarff", "new", "test", "-p", "rust-cli", "-l", "rust"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn language_required_without_preset() {
+        let result = Cli::try_parse_from(["scarff", "new", "test", "-t", "cli"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_file_replaces_language_and_type() {
+        let cli =
+            Cli::try_parse_from(["scarff", "new", "test", "--from-file", "scarff.target.toml"])
+                .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.from_file, Some(PathBuf::from("scarff.target.toml")));
+            assert_eq!(cmd.language, None);
+        } else {
+            panic!("Expected New command");
+        }
+    }
+
+    #[test]
+    fn from_file_conflicts_with_target_flags_and_preset() {
+        let result = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "test",
+            "--from-file",
+            "t.toml",
+            "-l",
+            "rust",
+        ]);
+        assert!(result.is_err());
+
+        let result = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "test",
+            "--from-file",
+            "t.toml",
+            "-p",
+            "rust-cli",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn save_target_defaults_file_name() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "test",
+            "-l",
+            "rust",
+            "-t",
+            "cli",
+            "--save-target",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.save_target, Some(PathBuf::from("scarff.target.toml")));
+        } else {
+            panic!("Expected New command");
+        }
+    }
+
+    #[test]
+    fn answers_replaces_target_flags() {
+        let cli =
+            Cli::try_parse_from(["scarff", "new", "test", "--answers", "answers.toml"]).unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.answers, Some(PathBuf::from("answers.toml")));
+            assert_eq!(cmd.language, None);
+        } else {
+            panic!("Expected New command");
+        }
+    }
+
+    #[test]
+    fn answers_conflicts_with_flags_it_answers() {
+        for extra in [
+            ["-l", "rust"],
+            ["-p", "rust-cli"],
+            ["--from-file", "t.toml"],
+        ] {
+            let mut args = vec!["scarff", "new", "test", "--answers", "a.toml"];
+            args.extend(extra);
+            assert!(Cli::try_parse_from(args).is_err(), "{extra:?}");
+        }
+        assert!(Cli::try_parse_from(["scarff", "new", "t", "--answers", "a.toml", "-y"]).is_err());
+    }
+
     #[test]
     fn yes_flag_works() {
         let cli = Cli::try_parse_from([
@@ -494,4 +1166,215 @@ mod tests {
 
         assert!(matches!(cli.command, Commands::New(_)));
     }
+
+    #[test]
+    fn deps_flag_parses() {
+        let cli = Cli::try_parse_from([
+            "scarff", "new", "api", "-l", "python", "-t", "backend", "--deps", "poetry",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.deps, Some(Deps::Poetry));
+        } else {
+            panic!("Expected New command");
+        }
+
+        let result = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "api",
+            "-p",
+            "python-fastapi",
+            "--deps",
+            "uv",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn layout_flag_parses() {
+        let cli = Cli::try_parse_from([
+            "scarff", "new", "api", "-l", "python", "-t", "backend", "--deps", "uv", "--layout",
+            "src",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.layout, Some(Layout::Src));
+        } else {
+            panic!("Expected New command");
+        }
+
+        let result = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "api",
+            "-p",
+            "python-fastapi",
+            "--layout",
+            "flat",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_runner_flag_parses() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "web",
+            "-l",
+            "typescript",
+            "-t",
+            "frontend",
+            "-f",
+            "vite-react",
+            "--test-runner",
+            "vitest",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.test_runner, Some(TestRunner::Vitest));
+        } else {
+            panic!("Expected New command");
+        }
+
+        let result = Cli::try_parse_from(["scarff", "new", "web", "--test-runner", "mocha"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn license_flag_parses() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "tool",
+            "-l",
+            "rust",
+            "-t",
+            "cli",
+            "--license",
+            "apache-2.0",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.license, Some(License::Apache2));
+        } else {
+            panic!("Expected New command");
+        }
+
+        let result = Cli::try_parse_from(["scarff", "new", "tool", "--license", "bsd"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn task_runner_flag_parses() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "tool",
+            "-l",
+            "rust",
+            "-t",
+            "cli",
+            "--task-runner",
+            "make",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.task_runner, Some(TaskRunner::Make));
+        } else {
+            panic!("Expected New command");
+        }
+    }
+
+    #[test]
+    fn ci_flag_parses() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "tool",
+            "-l",
+            "rust",
+            "-t",
+            "cli",
+            "--ci",
+            "gitlab-ci",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.ci, Some(CiProvider::GitLabCi));
+        } else {
+            panic!("Expected New command");
+        }
+
+        let result = Cli::try_parse_from(["scarff", "new", "tool", "--ci", "jenkins"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn feature_flag_repeats() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "new",
+            "app",
+            "-l",
+            "rust",
+            "-t",
+            "cli",
+            "--feature",
+            "docker",
+            "--feature",
+            "ci-github",
+        ])
+        .unwrap();
+
+        if let Commands::New(cmd) = cli.command {
+            assert_eq!(cmd.features, ["docker", "ci-github"]);
+        } else {
+            panic!("Expected New command");
+        }
+    }
+
+    #[test]
+    fn upgrade_defaults_to_current_directory() {
+        let cli = Cli::try_parse_from(["scarff", "upgrade"]).unwrap();
+
+        if let Commands::Upgrade(cmd) = cli.command {
+            assert_eq!(cmd.path, PathBuf::from("."));
+            assert!(!cmd.interactive && !cmd.dry_run);
+        } else {
+            panic!("Expected Upgrade command");
+        }
+
+        let result = Cli::try_parse_from(["scarff", "upgrade", "app", "-i", "--dry-run"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn search_joins_terms_and_reads_index_url() {
+        let cli = Cli::try_parse_from([
+            "scarff",
+            "search",
+            "python",
+            "worker",
+            "--index-url",
+            "file:///tmp/index.json",
+            "--refresh",
+        ])
+        .unwrap();
+
+        if let Commands::Search(cmd) = cli.command {
+            assert_eq!(cmd.query, ["python", "worker"]);
+            assert_eq!(cmd.index_url, "file:///tmp/index.json");
+            assert!(cmd.refresh);
+        } else {
+            panic!("Expected Search command");
+        }
+    }
 }
@@ -3,3 +3,5 @@
 //! Each subcommand has its own module with an `execute` function.
 
 pub mod new;
+pub mod search;
+pub mod upgrade;
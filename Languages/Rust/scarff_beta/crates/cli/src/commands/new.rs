@@ -3,18 +3,27 @@
 //! This module handles creating new projects from templates.
 
 use anyhow::{Context, Result};
+use serde::Deserialize;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
 use scarff_core::{
-    Architecture as CoreArchitecture, Engine, Framework as CoreFramework, Language as CoreLanguage,
-    ProjectKind as CoreProjectKind, PythonFramework, RustFramework, Target, TypeScriptFramework,
+    Architecture as CoreArchitecture, CiProvider as CoreCiProvider, CustomTree, DependencyManager,
+    Engine, Framework as CoreFramework, Language as CoreLanguage, License as CoreLicense,
+    PresetRegistry, ProjectKind as CoreProjectKind, PythonFramework, PythonLayout, RustFramework,
+    ScaffoldOptions, Target, TaskRunner as CoreTaskRunner, TestRunner as CoreTestRunner,
+    TypeScriptFramework,
+    scaffold::{OverwritePolicy, VcsMode},
 };
 
 use crate::{
-    args::{Architecture, Language, NewCommand, ProjectKind},
+    answers,
+    args::{
+        Architecture, CiProvider, Deps, Language, Layout, License, NewCommand, ProjectKind,
+        TaskRunner, TestRunner, Vcs,
+    },
     error::{CliError, CliResul, IntoCli},
-    output,
+    output::{self, Verbosity},
 };
 
 /// Execute the `new` command.
@@ -22,9 +31,8 @@ use crate::{
 /// # Arguments
 ///
 /// * `cmd` - Parsed command arguments
-/// * `verbose` - Whether to show verbose output
-/// * `quiet` - Whether to suppress non-error output
-pub fn execute(cmd: NewCommand, verbose: bool, quiet: bool) -> CliResul<()> {
+/// * `verbosity` - How much output to show
+pub fn execute(cmd: NewCommand, verbosity: Verbosity) -> CliResul<()> {
     debug!("Executing new command with: {:#?}", cmd);
 
     // 1. Resolve project path
@@ -34,20 +42,50 @@ pub fn execute(cmd: NewCommand, verbose: bool, quiet: bool) -> CliResul<()> {
     info!("Project name: {}", project_name);
     info!("Output directory: {}", output_dir.display());
 
-    // 2. Build target configuration
-    let target = build_target(&cmd).context("Failed to build target configuration")?;
-
+    // 2. Build target configuration (from a preset, a saved target, an
+    //    answers file or flags)
+    let answers = cmd.answers.as_deref().map(answers::load).transpose()?;
+    let (target, options) = match (&cmd.preset, &cmd.from_file, &answers) {
+        (Some(name), _, _) => {
+            let registry = load_presets(cmd.config.as_deref())?;
+            let preset = registry.get(name)?;
+            info!("Using preset: {}", preset.name());
+            (
+                preset.target().clone(),
+                ScaffoldOptions::new()
+                    .readme(!cmd.no_readme)
+                    .vcs(convert_vcs(cmd.vcs))
+                    .vars(preset.variables().clone()),
+            )
+        }
+        (None, Some(file), _) => (load_target(file)?, build_options(&cmd)?),
+        (None, None, Some(answers)) => (
+            answers.target.clone(),
+            build_options(&cmd)?.vars(answers.variables.clone()),
+        ),
+        (None, None, None) => (
+            build_target(&cmd).context("Failed to build target configuration")?,
+            build_options(&cmd)?,
+        ),
+    };
+
+    let options = with_hooks(options, &cmd)?;
     debug!("Built target: {}", target);
 
-    // 3. Show configuration and get confirmation (unless --yes or --quiet)
-    if !cmd.yes && !quiet {
-        output::show_configuration(&target, &project_name, &output_dir)?;
-        output::confirm()?;
+    // 3. Show configuration and get confirmation (unless answered, --yes or --quiet)
+    match &answers {
+        Some(answers) if !answers.confirm => return Err(CliError::Cancelled.into()),
+        Some(_) => {}
+        None if !cmd.yes && !verbosity.is_quiet() => {
+            output::show_configuration(&target, &project_name, &output_dir)?;
+            output::confirm()?;
+        }
+        None => {}
     }
 
     // 4. Check if project already exists
     let project_path = output_dir.join(&project_name);
-    debug!("project path: {}", project_path);
+    debug!("project path: {}", project_path.display());
 
     if project_path.exists() && !cmd.force {
         return Err(CliError::ProjectExists {
@@ -56,28 +94,45 @@ pub fn execute(cmd: NewCommand, verbose: bool, quiet: bool) -> CliResul<()> {
         .into());
     }
 
-    // 5. Handle dry run
-    if cmd.dry_run {
-        output::show_dry_run(&target, &project_name, &output_dir)?;
-        return Ok(());
+    // 5. Save the target if asked (also on a dry run, to save without scaffolding)
+    if let Some(path) = &cmd.save_target {
+        std::fs::write(path, target.to_toml())
+            .with_context(|| format!("Failed to write target file {}", path.display()))?;
+        if !verbosity.is_quiet() {
+            output::show_target_saved(path)?;
+        }
     }
 
-    // 6. Create engine and scaffold
     let engine = Engine::new();
 
-    if !quiet {
+    // 6. Handle dry run
+    if cmd.dry_run {
+        if !verbosity.is_quiet() {
+            let plan = engine
+                .plan_with(target.clone(), &project_name, &output_dir, &options)
+                .into_cli()?;
+            output::show_dry_run(&target, &project_name, &output_dir, &plan, verbosity)?;
+        }
+        return Ok(());
+    }
+
+    // 7. Scaffold
+    let report = if verbosity.is_quiet() {
+        engine
+            .scaffold_with(target, &project_name, &output_dir, &options)
+            .into_cli()?
+    } else {
         output::show_progress("Scaffolding project", || {
-            Ok(engine.scaffold(target, &project_name, &output_dir)?)
+            Ok(engine.scaffold_with(target, &project_name, &output_dir, &options)?)
         })?
-    } else {
-        engine
-            .scaffold(target, &project_name, &output_dir)
-            .into_cli()?;
-    }
+    };
 
-    // 7. Show success message
-    if !quiet {
-        output::show_success(&project_name, &project_path, verbose)?;
+    // 8. Show what was written and the success message
+    output::show_report(&report, verbosity)?;
+
+    if !verbosity.is_quiet() {
+        let next_steps: Vec<_> = report.next_steps().collect();
+        output::show_success(&project_name, &project_path, &next_steps, verbosity)?;
     }
 
     Ok(())
@@ -179,29 +234,276 @@ fn validate_project_name(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Read the config file, if there is one.
+///
+/// An explicit `--config` must exist; the default location is optional.
+fn read_config(config: Option<&Path>) -> CliResul<Option<(PathBuf, String)>> {
+    let path = match config {
+        Some(path) => path.to_path_buf(),
+        None => match default_config_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(None),
+        },
+    };
+
+    let source = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    Ok(Some((path, source)))
+}
+
+/// Build the preset registry: built-ins plus presets from the config file.
+fn load_presets(config: Option<&Path>) -> CliResul<PresetRegistry> {
+    let mut registry = PresetRegistry::new();
+    let Some((path, source)) = read_config(config)? else {
+        return Ok(registry);
+    };
+
+    let loaded = registry
+        .load_toml(&source)
+        .with_context(|| format!("Failed to load presets from {}", path.display()))?;
+    debug!(loaded, path = %path.display(), "Loaded presets from config");
+
+    Ok(registry)
+}
+
+/// The `[hooks]` table of the config file.
+#[derive(Debug, Default, Deserialize)]
+struct HookConfig {
+    #[serde(default)]
+    hooks: Option<HookAllowlist>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HookAllowlist {
+    allow: Vec<String>,
+}
+
+/// Let hooks run as asked: sandboxed to the config file's allowlist
+/// (only `git` without it), or unchecked with `--unsafe-hooks`.
+/// Allowlisting a build tool trusts the template's code: `cargo run` or
+/// `npm exec` run whatever the template ships.
+///
+/// ```toml
+/// [hooks]
+/// allow = ["git", "cargo", "just"]
+/// ```
+fn with_hooks(options: ScaffoldOptions, cmd: &NewCommand) -> CliResul<ScaffoldOptions> {
+    let options = options
+        .allow_hooks(cmd.allow_hooks)
+        .unsafe_hooks(cmd.unsafe_hooks);
+    if !cmd.allow_hooks || cmd.unsafe_hooks {
+        return Ok(options);
+    }
+
+    let Some((path, source)) = read_config(cmd.config.as_deref())? else {
+        return Ok(options);
+    };
+    let config: HookConfig = toml::from_str(&source)
+        .with_context(|| format!("Failed to load the hook allowlist from {}", path.display()))?;
+
+    Ok(match config.hooks {
+        Some(hooks) => {
+            debug!(allow = ?hooks.allow, path = %path.display(), "Loaded hook allowlist");
+            options.hook_allowlist(hooks.allow)
+        }
+        None => options,
+    })
+}
+
+/// Load a target saved with `--save-target`.
+fn load_target(path: &Path) -> CliResul<Target> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read target file {}", path.display()))?;
+    let target = Target::from_toml(&source)
+        .with_context(|| format!("Failed to load target from {}", path.display()))?;
+    debug!(path = %path.display(), "Loaded target from file");
+
+    Ok(target)
+}
+
+/// `$XDG_CONFIG_HOME/scarff/config.toml`, falling back to `~/.config`.
+fn default_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(base.join("scarff").join("config.toml"))
+}
+
 /// Build a Target from command arguments.
 fn build_target(cmd: &NewCommand) -> CliResul<Target> {
+    // Clap requires both unless --preset is given
+    let (Some(cli_language), Some(cli_kind)) = (cmd.language, cmd.kind) else {
+        return Err(CliError::Other("--lang and --type are required without --preset").into());
+    };
+
     // Convert CLI enums to core enums
-    let language = convert_language(cmd.language);
-    let kind = convert_kind(cmd.kind);
-    let architecture = convert_architecture(cmd.architecture);
+    let language = convert_language(cli_language);
+    let kind = convert_kind(cli_kind);
 
     // Start building target
-    let mut builder = Target::builder()
-        .language(language)
-        .kind(kind)?
-        .architecture(architecture)?;
+    let mut builder = Target::builder().language(language).kind(kind)?;
 
-    // Add framework if provided
+    // Add framework if provided (before architecture, which is validated against it)
     if let Some(ref framework_str) = cmd.framework {
-        let framework = parse_framework(cmd.language, framework_str)?;
+        let framework = parse_framework(cli_language, framework_str)?;
         builder = builder.framework(framework)?;
     }
 
+    // Add architecture if provided, otherwise let the core infer it
+    if let Some(architecture) = cmd.architecture {
+        builder = builder.architecture(convert_architecture(architecture))?;
+    }
+
+    // Python dependency manager (rejected by the core for other languages)
+    if let Some(deps) = cmd.deps {
+        builder = builder.dependency_manager(convert_deps(deps))?;
+    }
+
+    // Python package layout (src needs uv or poetry, checked at build)
+    if let Some(layout) = cmd.layout {
+        builder = builder.python_layout(convert_layout(layout))?;
+    }
+
+    // TypeScript test runner (rejected by the core for other languages)
+    if let Some(runner) = cmd.test_runner {
+        builder = builder.test_runner(convert_test_runner(runner))?;
+    }
+
+    // Rust task file and example tests
+    if let Some(runner) = cmd.task_runner {
+        builder = builder.task_runner(convert_task_runner(runner))?;
+    }
+
+    // LICENSE file and manifest field (any language)
+    if let Some(license) = cmd.license {
+        builder = builder.license(convert_license(license));
+    }
+
+    // CI pipeline (any language)
+    if let Some(provider) = cmd.ci {
+        builder = builder.ci(convert_ci(provider));
+    }
+
+    // Optional template features (file groups a template only emits on request)
+    for feature in &cmd.features {
+        builder = builder.feature(feature.as_str())?;
+    }
+
     // Build and validate
     Ok(builder.build()?)
 }
 
+/// Build scaffold options, loading the custom tree for `--type custom`.
+fn build_options(cmd: &NewCommand) -> CliResul<ScaffoldOptions> {
+    let mut options = ScaffoldOptions::new()
+        .readme(!cmd.no_readme)
+        .vcs(convert_vcs(cmd.vcs));
+    if cmd.force {
+        options = options.overwrite(OverwritePolicy::Overwrite);
+    }
+
+    if cmd.kind != Some(ProjectKind::Custom) {
+        return Ok(options);
+    }
+
+    let source = match (&cmd.tree, &cmd.tree_file) {
+        (Some(inline), _) => inline_tree(inline),
+        (None, Some(file)) => std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read tree file {}", file.display()))?,
+        (None, None) => {
+            return Err(CliError::Other("--type custom requires --tree or --tree-file").into());
+        }
+    };
+
+    let tree = CustomTree::parse(&source)?.with_headers(cmd.headers);
+    debug!(entries = tree.len(), "Loaded custom tree");
+
+    Ok(options.custom_tree(tree))
+}
+
+/// Expand a single-line `--tree` value into the indented DSL.
+///
+/// JSON and multi-line values are passed through; otherwise each
+/// comma-separated path becomes its own top-level line.
+fn inline_tree(inline: &str) -> String {
+    if inline.trim_start().starts_with('{') || inline.contains('\n') {
+        inline.to_string()
+    } else {
+        inline
+            .split(',')
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Convert CLI Vcs to core VcsMode.
+fn convert_vcs(vcs: Vcs) -> VcsMode {
+    match vcs {
+        Vcs::None => VcsMode::Off,
+        Vcs::Init => VcsMode::Init,
+        Vcs::Commit => VcsMode::Commit,
+    }
+}
+
+/// Convert CLI dependency manager to core DependencyManager.
+fn convert_deps(deps: Deps) -> DependencyManager {
+    match deps {
+        Deps::Uv => DependencyManager::Uv,
+        Deps::Poetry => DependencyManager::Poetry,
+        Deps::Pip => DependencyManager::Pip,
+    }
+}
+
+/// Convert CLI test runner to core TestRunner.
+fn convert_test_runner(runner: TestRunner) -> CoreTestRunner {
+    match runner {
+        TestRunner::Vitest => CoreTestRunner::Vitest,
+        TestRunner::Jest => CoreTestRunner::Jest,
+        TestRunner::None => CoreTestRunner::None,
+    }
+}
+
+/// Convert CLI layout to core PythonLayout.
+fn convert_layout(layout: Layout) -> PythonLayout {
+    match layout {
+        Layout::Src => PythonLayout::Src,
+        Layout::Flat => PythonLayout::Flat,
+    }
+}
+
+/// Convert CLI task runner to core TaskRunner.
+fn convert_task_runner(runner: TaskRunner) -> CoreTaskRunner {
+    match runner {
+        TaskRunner::Just => CoreTaskRunner::Just,
+        TaskRunner::Make => CoreTaskRunner::Make,
+        TaskRunner::None => CoreTaskRunner::None,
+    }
+}
+
+/// Convert CLI license to core License.
+fn convert_license(license: License) -> CoreLicense {
+    match license {
+        License::Mit => CoreLicense::Mit,
+        License::Apache2 => CoreLicense::Apache2,
+        License::Gpl3 => CoreLicense::Gpl3,
+        License::Mpl2 => CoreLicense::Mpl2,
+        License::Unlicense => CoreLicense::Unlicense,
+    }
+}
+
+/// Convert CLI CI provider to core CiProvider.
+fn convert_ci(provider: CiProvider) -> CoreCiProvider {
+    match provider {
+        CiProvider::GitHubActions => CoreCiProvider::GitHubActions,
+        CiProvider::GitLabCi => CoreCiProvider::GitLabCi,
+        CiProvider::CircleCi => CoreCiProvider::CircleCi,
+    }
+}
+
 /// Convert CLI Language to core Language.
 fn convert_language(lang: Language) -> CoreLanguage {
     match lang {
@@ -219,6 +521,7 @@ fn convert_kind(pt: ProjectKind) -> CoreProjectKind {
         ProjectKind::WebFrontend => CoreProjectKind::WebFrontend,
         ProjectKind::Fullstack => CoreProjectKind::Fullstack,
         ProjectKind::Worker => CoreProjectKind::Worker,
+        ProjectKind::Custom => CoreProjectKind::Custom,
     }
 }
 
@@ -228,8 +531,9 @@ fn convert_architecture(arch: Architecture) -> CoreArchitecture {
         Architecture::Layered => CoreArchitecture::Layered,
         Architecture::Mvc => CoreArchitecture::MVC,
         Architecture::Clean => CoreArchitecture::Clean,
-        Architecture::Modular => todo!(),
-        Architecture::AppRouter => todo!(),
+        Architecture::Hexagonal => CoreArchitecture::Hexagonal,
+        Architecture::Modular => CoreArchitecture::Modular,
+        Architecture::AppRouter => CoreArchitecture::AppRouter,
     }
 }
 
@@ -257,9 +561,12 @@ fn parse_framework(language: Language, framework: &str) -> Result<CoreFramework,
         Language::TypeScript => match framework_lower.as_str() {
             "express" => Ok(CoreFramework::TypeScript(TypeScriptFramework::Express)),
             "nestjs" => Ok(CoreFramework::TypeScript(TypeScriptFramework::NestJs)),
-            "nextjs" => Ok(CoreFramework::TypeScript(TypeScriptFramework::NextJs)),
-            "react" => Ok(CoreFramework::TypeScript(TypeScriptFramework::React)),
+            "nextjs" | "next" => Ok(CoreFramework::TypeScript(TypeScriptFramework::NextJs)),
+            "react" | "vite-react" => Ok(CoreFramework::TypeScript(TypeScriptFramework::React)),
             "vue" => Ok(CoreFramework::TypeScript(TypeScriptFramework::Vue)),
+            "sveltekit" | "svelte-kit" => {
+                Ok(CoreFramework::TypeScript(TypeScriptFramework::SvelteKit))
+            }
             _ => Err(CliError::FrameworkNotAvailable {
                 framework: framework.to_string(),
                 language: language.to_string(),
@@ -364,6 +671,9 @@ mod tests {
         assert!(parse_framework(Language::TypeScript, "nextjs").is_ok());
         assert!(parse_framework(Language::TypeScript, "react").is_ok());
         assert!(parse_framework(Language::TypeScript, "vue").is_ok());
+        assert!(parse_framework(Language::TypeScript, "vite-react").is_ok());
+        assert!(parse_framework(Language::TypeScript, "next").is_ok());
+        assert!(parse_framework(Language::TypeScript, "sveltekit").is_ok());
     }
 
     #[test]
@@ -394,6 +704,88 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn inline_tree_splits_commas() {
+        assert_eq!(inline_tree("src/, src/main.rs"), "src/\nsrc/main.rs");
+        assert_eq!(inline_tree(r#"{"a.rs": null}"#), r#"{"a.rs": null}"#);
+    }
+
+    #[test]
+    fn load_presets_reads_config_file() {
+        let path = std::env::temp_dir().join(format!("scarff-presets-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[presets.my-company-api]\nlanguage = \"rust\"\ntype = \"backend\"\nframework = \"axum\"\n",
+        )
+        .unwrap();
+
+        let registry = load_presets(Some(&path));
+        std::fs::remove_file(&path).unwrap();
+
+        let registry = registry.unwrap();
+        assert!(registry.contains("my-company-api"));
+        assert!(registry.contains("rust-cli"));
+    }
+
+    #[test]
+    fn load_target_reads_saved_target() {
+        let target = Target::builder()
+            .language(CoreLanguage::Rust)
+            .kind(CoreProjectKind::Cli)
+            .unwrap()
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("scarff-target-{}.toml", std::process::id()));
+        std::fs::write(&path, target.to_toml()).unwrap();
+
+        let loaded = load_target(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), target);
+    }
+
+    #[test]
+    fn with_hooks_sandboxes_to_the_config_allowlist() {
+        use crate::args::{Cli, Commands};
+        use clap::Parser;
+        use scarff_core::scaffold::HookPolicy;
+
+        let path = std::env::temp_dir().join(format!("scarff-hooks-{}.toml", std::process::id()));
+        std::fs::write(&path, "[hooks]\nallow = [\"just\"]\n").unwrap();
+        let policy = |flags: &[&str]| {
+            let args = [
+                "scarff", "new", "app", "-l", "rust", "-t", "cli", "--config",
+            ];
+            let cli = Cli::try_parse_from(
+                args.iter()
+                    .copied()
+                    .chain([path.to_str().unwrap()])
+                    .chain(flags.iter().copied()),
+            )
+            .unwrap();
+            let Commands::New(cmd) = cli.command else {
+                unreachable!()
+            };
+            with_hooks(ScaffoldOptions::new(), &cmd).map(|o| o.hook_policy())
+        };
+
+        let (off, sandboxed, unchecked) = (
+            policy(&[]),
+            policy(&["--allow-hooks"]),
+            policy(&["--unsafe-hooks"]),
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(off.unwrap(), HookPolicy::Disabled);
+        assert_eq!(sandboxed.unwrap(), HookPolicy::sandboxed_with(["just"]));
+        assert_eq!(unchecked.unwrap(), HookPolicy::Unsafe);
+    }
+
+    #[test]
+    fn load_presets_requires_explicit_config_to_exist() {
+        assert!(load_presets(Some(Path::new("/definitely/missing/scarff.toml"))).is_err());
+    }
+
     #[test]
     fn convert_architectures() {
         assert!(matches!(
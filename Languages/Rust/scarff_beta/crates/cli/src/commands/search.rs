@@ -0,0 +1,47 @@
+//! Implementation of the `scarff search` command.
+//!
+//! Searches the community template index; the index is cached under
+//! `$XDG_CACHE_HOME/scarff/index.json` between runs.
+
+use anyhow::Context;
+use std::path::PathBuf;
+use tracing::debug;
+
+use scarff_core::{CoreError, IndexClient};
+
+use crate::{
+    args::SearchCommand,
+    error::CliResul,
+    output::{self, Verbosity},
+};
+
+/// Execute the `search` command.
+pub fn execute(cmd: &SearchCommand, verbosity: Verbosity) -> CliResul<()> {
+    let cache = default_cache_path()
+        .context("Cannot locate a cache directory; set XDG_CACHE_HOME or HOME")?;
+    debug!(url = %cmd.index_url, cache = %cache.display(), "Searching template index");
+
+    let client = IndexClient::new(&cmd.index_url, cache);
+    let index = if cmd.refresh {
+        client.refresh()
+    } else {
+        client.index()
+    }
+    .map_err(CoreError::from)
+    .context("Failed to load the template index")?;
+
+    let query = cmd.query.join(" ");
+    output::show_search_results(&query, &index.search(&query), verbosity)?;
+
+    Ok(())
+}
+
+/// `$XDG_CACHE_HOME/scarff/index.json`, falling back to `~/.cache`.
+fn default_cache_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+
+    Some(base.join("scarff").join("index.json"))
+}
@@ -0,0 +1,63 @@
+//! Implementation of the `scarff upgrade` command.
+//!
+//! Re-renders a project at its template's latest version using the
+//! recorded provenance. Changes the user hasn't touched are applied;
+//! conflicts are written as `<path>.rej` diffs, or decided one by one with
+//! `--interactive`. Rejected conflicts fail the command, so scripts notice
+//! them.
+
+use anyhow::Context;
+use tracing::debug;
+
+use scarff_core::{
+    Engine,
+    scaffold::{FileChange, Resolution},
+};
+
+use crate::{
+    args::UpgradeCommand,
+    error::{CliError, CliResul},
+    output::{self, Verbosity},
+};
+
+/// Execute the `upgrade` command.
+pub fn execute(cmd: &UpgradeCommand, verbosity: Verbosity) -> CliResul<()> {
+    let engine = Engine::new();
+
+    let plan = engine
+        .plan_upgrade(&cmd.path)
+        .with_context(|| format!("Failed to plan upgrade of {}", cmd.path.display()))?;
+    debug!(from = %plan.from, to = %plan.to, changes = plan.changes.len(), "Planned upgrade");
+
+    output::show_upgrade_plan(&plan, verbosity)?;
+    if plan.is_up_to_date() || cmd.dry_run {
+        return Ok(());
+    }
+
+    let resolve = |change: &FileChange| {
+        if cmd.interactive {
+            // A prompt that can't be read keeps the user's file and leaves a .rej
+            output::ask_resolution(change).unwrap_or(Resolution::Reject)
+        } else {
+            Resolution::Reject
+        }
+    };
+    let report = engine
+        .apply_upgrade(&plan, resolve)
+        .context("Failed to apply upgrade")?;
+
+    output::show_upgrade_report(&report, verbosity)?;
+
+    if !report.rejected.is_empty() {
+        return Err(CliError::UpgradeConflicts {
+            files: report
+                .rejected
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
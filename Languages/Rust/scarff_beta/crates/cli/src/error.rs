@@ -4,7 +4,8 @@
 //! user-friendly error messages.
 
 use owo_colors::OwoColorize;
-use scarff_core::CoreError;
+use scarff_core::{CoreError, DomainError, ErrorPayload};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 pub type CliResul<T> = anyhow::Result<T>;
@@ -47,12 +48,77 @@ pub enum CliError {
     #[error("Operation cancelled by user")]
     Cancelled,
 
+    /// An answers file lacks required keys
+    #[error("Answers file is missing: {}", .missing.join(", "))]
+    IncompleteAnswers { missing: Vec<String> },
+
+    /// An upgrade left conflicts as `.rej` files
+    #[error("Upgrade left {} conflict(s) to resolve: {}", .files.len(), .files.join(", "))]
+    UpgradeConflicts { files: Vec<String> },
+
     /// Generic error from anyhow
     #[error("{0}")]
     Other(&'static str),
 }
 
 impl CliError {
+    /// Stable, machine-readable code; wrapped core errors keep their own.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CliError::UnsupportedLanguage(_) => "cli.unsupported_language",
+            CliError::UnsupportedProjectKind(_) => "cli.unsupported_project_kind",
+            CliError::UnsupportedArchitecture(_) => "cli.unsupported_architecture",
+            CliError::FrameworkNotAvailable { .. } => "cli.framework_not_available",
+            CliError::InvalidProjectName { .. } => "cli.invalid_project_name",
+            CliError::ProjectExists { .. } => "cli.project_exists",
+            CliError::Core(err) => err.code(),
+            CliError::Io(_) => "io.error",
+            CliError::Cancelled => "cli.cancelled",
+            CliError::IncompleteAnswers { .. } => "cli.incomplete_answers",
+            CliError::UpgradeConflicts { .. } => "cli.upgrade_conflicts",
+            CliError::Other(_) => "cli.error",
+        }
+    }
+
+    /// Machine-readable form of this error.
+    pub fn to_payload(&self) -> ErrorPayload {
+        let context: BTreeMap<String, String> = match self {
+            CliError::Core(err) => return err.to_payload(),
+            CliError::UnsupportedLanguage(value)
+            | CliError::UnsupportedProjectKind(value)
+            | CliError::UnsupportedArchitecture(value) => {
+                BTreeMap::from([("value".to_string(), value.clone())])
+            }
+            CliError::FrameworkNotAvailable {
+                framework,
+                language,
+            } => BTreeMap::from([
+                ("framework".to_string(), framework.clone()),
+                ("language".to_string(), language.clone()),
+            ]),
+            CliError::InvalidProjectName { reason } => {
+                BTreeMap::from([("reason".to_string(), reason.clone())])
+            }
+            CliError::ProjectExists { path } => {
+                BTreeMap::from([("path".to_string(), path.clone())])
+            }
+            CliError::Io(err) => BTreeMap::from([("kind".to_string(), err.kind().to_string())]),
+            CliError::IncompleteAnswers { missing } => {
+                BTreeMap::from([("missing".to_string(), missing.join(","))])
+            }
+            CliError::UpgradeConflicts { files } => {
+                BTreeMap::from([("files".to_string(), files.join(","))])
+            }
+            CliError::Cancelled | CliError::Other(_) => BTreeMap::new(),
+        };
+
+        ErrorPayload {
+            code: self.code().to_string(),
+            message: self.to_string(),
+            context,
+        }
+    }
+
     ///format the error with helpful suggestions.
     pub fn format_with_suggestions(&self) -> String {
         let error_msg = format!("{} {}", "Error:".red().bold(), self);
@@ -74,7 +140,7 @@ impl CliError {
             }
             CliError::UnsupportedArchitecture(arch) => {
                 format!(
-                    "\n\n{}\n  - layered (domain, application, infrastructure)\n  - mvc (model-view-controller)\n  - modular (feature-based modules)\n  - app-router (Next.js app router)\n\n{}\n  scarff new my-app --lang rust --type backend --arch layered",
+                    "\n\n{}\n  - layered (domain, application, infrastructure)\n  - mvc (model-view-controller)\n  - modular (feature-based modules)\n  - app-router (Next.js app router)\n  - clean (use cases, Rust + Axum)\n  - hexagonal (ports and adapters, Rust + Axum)\n\n{}\n  scarff new my-app --lang rust --type backend --arch layered",
                     "Supported architectures:".yellow(),
                     "Example:".cyan()
                 )
@@ -142,6 +208,101 @@ impl From<anyhow::Error> for CliError {
     }
 }
 
+/// Build the machine-readable payload for an error returned by a command.
+///
+/// The first typed error in the chain supplies the code and context; the
+/// message is the whole chain, as shown in text mode.
+pub fn error_payload(err: &anyhow::Error) -> ErrorPayload {
+    let payload = err.chain().find_map(|cause| {
+        if let Some(e) = cause.downcast_ref::<CliError>() {
+            Some(e.to_payload())
+        } else if let Some(e) = cause.downcast_ref::<CoreError>() {
+            Some(e.to_payload())
+        } else {
+            cause
+                .downcast_ref::<DomainError>()
+                .map(DomainError::to_payload)
+        }
+    });
+
+    let mut payload = payload.unwrap_or_else(|| ErrorPayload {
+        code: "cli.error".to_string(),
+        message: String::new(),
+        context: BTreeMap::new(),
+    });
+    payload.message = format!("{err:#}");
+    payload
+}
+
+/// Process exit codes, stable so scripts can branch on the outcome.
+///
+/// Argument parsing errors exit with [`ExitCode::Usage`] from clap itself;
+/// every other error is mapped from its machine-readable code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Any error without a more specific code
+    Failure = 1,
+    /// Unknown flag, option value or preset
+    Usage = 2,
+    /// Invalid project name, target combination or template
+    Validation = 3,
+    /// No template matches the target, or the named one doesn't exist
+    TemplateNotFound = 4,
+    /// The project exists, a file couldn't be written, or an upgrade left
+    /// conflicts
+    FilesystemConflict = 5,
+    /// A post-scaffold hook (verification command or git) failed
+    HookFailed = 6,
+}
+
+impl ExitCode {
+    /// Exit code for an error with the given stable code.
+    pub fn from_error_code(code: &str) -> Self {
+        match code {
+            "cli.unsupported_language"
+            | "cli.unsupported_project_kind"
+            | "cli.unsupported_architecture"
+            | "cli.framework_not_available"
+            | "domain.unknown_preset" => ExitCode::Usage,
+            "cli.invalid_project_name"
+            | "cli.incomplete_answers"
+            | "scaffold.invalid_target"
+            | "scaffold.validation_failed"
+            | "scaffold.unsafe_path"
+            | "template.invalid_target"
+            | "template.invalid_template"
+            | "template.unknown_partial"
+            | "template.include_cycle"
+            | "template.load_failed"
+            | "template.invalid_manifest"
+            | "template.syntax" => ExitCode::Validation,
+            "scaffold.template_resolution"
+            | "template.not_found"
+            | "template.no_match"
+            | "index.not_found" => ExitCode::TemplateNotFound,
+            "cli.project_exists"
+            | "cli.upgrade_conflicts"
+            | "scaffold.project_exists"
+            | "scaffold.permission_denied"
+            | "scaffold.filesystem_write" => ExitCode::FilesystemConflict,
+            "scaffold.verification_failed" | "scaffold.vcs_failed" => ExitCode::HookFailed,
+            code if code.starts_with("domain.") => ExitCode::Validation,
+            _ => ExitCode::Failure,
+        }
+    }
+
+    /// Exit code for an error returned by a command.
+    pub fn for_error(err: &anyhow::Error) -> Self {
+        Self::from_error_code(&error_payload(err).code)
+    }
+}
+
+impl From<ExitCode> for i32 {
+    fn from(code: ExitCode) -> Self {
+        code as i32
+    }
+}
+
 /// Get framework suggestions for a given language.
 fn get_framework_suggestions(language: &str) -> Vec<&'static str> {
     match language.to_lowercase().as_str() {
@@ -205,6 +366,83 @@ mod tests {
         assert!(formatted.contains("backend"));
     }
 
+    #[test]
+    fn error_payload_keeps_core_code_under_context() {
+        let core: CoreError = DomainError::UnknownPreset {
+            name: "acme".to_string(),
+            available: vec!["rust-cli".to_string()],
+        }
+        .into();
+        let err = anyhow::Error::new(core).context("Failed to build target configuration");
+
+        let payload = error_payload(&err);
+
+        assert_eq!(payload.code, "domain.unknown_preset");
+        assert_eq!(payload.context["name"], "acme");
+        assert!(
+            payload
+                .message
+                .starts_with("Failed to build target configuration: ")
+        );
+    }
+
+    #[test]
+    fn error_payload_for_cli_and_untyped_errors() {
+        let exists = anyhow::Error::new(CliError::ProjectExists {
+            path: "./demo".to_string(),
+        });
+        assert_eq!(error_payload(&exists).code, "cli.project_exists");
+        assert_eq!(error_payload(&exists).context["path"], "./demo");
+
+        let untyped = anyhow::anyhow!("boom");
+        assert_eq!(error_payload(&untyped).code, "cli.error");
+        assert_eq!(error_payload(&untyped).message, "boom");
+    }
+
+    #[test]
+    fn exit_codes_follow_error_codes() {
+        let cases = [
+            ("domain.unknown_preset", ExitCode::Usage),
+            ("domain.framework_language_mismatch", ExitCode::Validation),
+            ("cli.invalid_project_name", ExitCode::Validation),
+            ("cli.incomplete_answers", ExitCode::Validation),
+            ("scaffold.template_resolution", ExitCode::TemplateNotFound),
+            ("template.no_match", ExitCode::TemplateNotFound),
+            ("cli.project_exists", ExitCode::FilesystemConflict),
+            ("scaffold.filesystem_write", ExitCode::FilesystemConflict),
+            ("scaffold.verification_failed", ExitCode::HookFailed),
+            ("index.fetch_failed", ExitCode::Failure),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(ExitCode::from_error_code(code), expected, "{code}");
+        }
+        // Clap exits with 2 on usage errors; ours must agree
+        assert_eq!(i32::from(ExitCode::Usage), 2);
+    }
+
+    #[test]
+    fn exit_code_uses_first_typed_error_in_chain() {
+        let err = anyhow::Error::new(CliError::ProjectExists {
+            path: "./demo".to_string(),
+        })
+        .context("Failed to scaffold");
+        assert_eq!(ExitCode::for_error(&err), ExitCode::FilesystemConflict);
+
+        let conflicts = anyhow::Error::new(CliError::UpgradeConflicts {
+            files: vec!["src/main.rs".to_string()],
+        });
+        assert_eq!(error_payload(&conflicts).context["files"], "src/main.rs");
+        assert_eq!(
+            ExitCode::for_error(&conflicts),
+            ExitCode::FilesystemConflict
+        );
+
+        assert_eq!(
+            ExitCode::for_error(&anyhow::anyhow!("boom")),
+            ExitCode::Failure
+        );
+    }
+
     #[test]
     fn framework_suggestions_for_rust() {
         let suggestions = get_framework_suggestions("rust");
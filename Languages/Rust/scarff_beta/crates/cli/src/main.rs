@@ -13,45 +13,69 @@
 //!
 //! # Short form
 //! scarff new my-app -l rust -t backend -a layered -f axum
+//!
+//! # Non-interactive, e.g. in CI
+//! scarff new my-app --answers answers.toml
+//!
+//! # Search the community template index
+//! scarff search axum --index-url https://example.com/scarff/index.json
+//!
+//! # Upgrade a project to its template's latest version
+//! scarff upgrade ./my-app --dry-run
 //! ```
 
 use anyhow::Result;
 use clap::Parser;
 use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
+mod answers;
 mod args;
 mod commands;
 mod error;
 mod output;
 
-use args::Cli;
+use args::{Cli, OutputFormat};
+use error::ExitCode;
+use output::Verbosity;
 
 fn main() -> Result<()> {
     // Parse CLI arguments first (this will handle --help, --version, etc.)
     let cli = Cli::parse();
 
+    // Apply --color / --no-color / NO_COLOR before anything is printed
+    output::init_colors(cli.color());
+
     // Initialize logging based on verbosity flags
     init_logging(&cli)?;
 
-    // Execute the command
-    cli.execute()
+    // Execute the command; the exit code tells scripts what went wrong
+    let format = cli.format;
+    if let Err(err) = cli.execute() {
+        match format {
+            OutputFormat::Text => output::show_error(&err)?,
+            OutputFormat::Json => output::show_error_json(&err)?,
+        }
+        std::process::exit(ExitCode::for_error(&err).into());
+    }
+
+    Ok(())
 }
 
 /// Initialize tracing/logging based on CLI flags.
 ///
 /// Logging behavior:
-/// - Default: Only errors are shown
+/// - Default: Only warnings and errors are shown
 /// - `-v` (verbose): Info-level messages (progress, major steps)
-/// - `-q` (quiet): No output except critical errors
+/// - `-vv` (debug): Debug-level messages
+/// - `-q` (quiet): No output except errors
 /// - `RUST_LOG` env var: Overrides CLI flags
 fn init_logging(cli: &Cli) -> Result<()> {
     // Determine log level from flags
-    let default_filter = if cli.quiet {
-        "error"
-    } else if cli.verbose {
-        "scarff=info,scarff_core=info"
-    } else {
-        "warn"
+    let default_filter = match cli.verbosity() {
+        Verbosity::Quiet => "error",
+        Verbosity::Normal => "warn",
+        Verbosity::Verbose => "scarff=info,scarff_core=info",
+        Verbosity::Debug => "scarff=debug,scarff_core=debug",
     };
 
     // Build the filter
@@ -67,7 +91,7 @@ fn init_logging(cli: &Cli) -> Result<()> {
                 .with_target(false) // Don't show module paths
                 .with_writer(std::io::stderr) // Log to stderr
                 .without_time() // Don't show timestamps (cleaner for CLI)
-                .with_ansi(!cli.no_color), // Respect --no-color flag
+                .with_ansi(output::stderr_colors_enabled()), // Respect --color / NO_COLOR
         )
         .init();
 
@@ -2,6 +2,7 @@
 //!
 //! This module handles all terminal output, including:
 //! - Colored and formatted messages
+//! - Verbosity levels and color configuration
 //! - Progress indicators
 //! - Confirmation prompts
 //! - Success/error displays
@@ -9,11 +10,78 @@
 use anyhow::Result;
 use console::{Term, style};
 use indicatif::{ProgressBar, ProgressStyle};
-use owo_colors::OwoColorize;
-use scarff_core::Target;
-use std::io::{self, Write};
+use scarff_core::{
+    IndexEntry, Target,
+    scaffold::{
+        ChangeKind, EntryKind, EntryStatus, FileChange, HookOutcome, HookStatus, PlannedAction,
+        PlannedEntry, ReportEntry, Resolution, ScaffoldPlan, ScaffoldReport, UpgradePlan,
+        UpgradeReport,
+    },
+};
+use std::io;
 use std::path::Path;
 
+use crate::args::ColorWhen;
+
+// ============================================================================
+// Verbosity & Colors
+// ============================================================================
+
+/// How much the CLI prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors only (`-q`)
+    Quiet,
+    /// Summary and created files (default)
+    Normal,
+    /// Also directories and progress logs (`-v`)
+    Verbose,
+    /// Also debug logs (`-vv`)
+    Debug,
+}
+
+impl Verbosity {
+    /// Map `-q` and the `-v` count to a verbosity level.
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Debug,
+        }
+    }
+
+    /// Whether anything besides errors should be printed.
+    pub fn is_quiet(self) -> bool {
+        self == Verbosity::Quiet
+    }
+
+    /// Whether `-v` (or more) was given.
+    pub fn is_verbose(self) -> bool {
+        self >= Verbosity::Verbose
+    }
+}
+
+/// Apply the color choice to every output stream.
+///
+/// `Auto` leaves detection to the terminal (tty, `CLICOLOR`, ...).
+pub fn init_colors(when: ColorWhen) {
+    let enabled = match when {
+        ColorWhen::Auto => return,
+        ColorWhen::Always => true,
+        ColorWhen::Never => false,
+    };
+
+    console::set_colors_enabled(enabled);
+    console::set_colors_enabled_stderr(enabled);
+    owo_colors::set_override(enabled);
+}
+
+/// Whether ANSI colors should be written to stderr.
+pub fn stderr_colors_enabled() -> bool {
+    console::colors_enabled_stderr()
+}
+
 // ============================================================================
 // Configuration Display
 // ============================================================================
@@ -23,65 +91,178 @@ pub fn show_configuration(target: &Target, name: &str, output: &Path) -> Result<
     let term = Term::stdout();
 
     term.write_line("")?;
-    term.write_line(&format!("{}", "╭─ Scaffold Configuration ".cyan().bold()))?;
-    term.write_line(&format!("│"))?;
+    term.write_line(&format!(
+        "{}",
+        style("╭─ Scaffold Configuration ").cyan().bold()
+    ))?;
+    term.write_line("│")?;
     term.write_line(&format!(
         "│ {} {}",
-        "Project Name:".dimmed(),
+        style("Project Name:").dim(),
         style(name).cyan().bold()
     ))?;
     term.write_line(&format!(
         "│ {} {}",
-        "Language:    ".dimmed(),
+        style("Language:    ").dim(),
         style(target.language()).green()
     ))?;
     term.write_line(&format!(
         "│ {} {}",
-        "Type:        ".dimmed(),
+        style("Type:        ").dim(),
         style(target.kind()).green()
     ))?;
     term.write_line(&format!(
         "│ {} {}",
-        "Architecture:".dimmed(),
+        style("Architecture:").dim(),
         style(target.architecture()).green()
     ))?;
 
     if let Some(fw) = target.framework() {
         term.write_line(&format!(
             "│ {} {}",
-            "Framework:   ".dimmed(),
+            style("Framework:   ").dim(),
             style(fw).green()
         ))?;
     }
 
+    if let Some(manager) = target.dependency_manager() {
+        term.write_line(&format!(
+            "│ {} {}",
+            style("Dependencies:").dim(),
+            style(manager).green()
+        ))?;
+    }
+
+    if let Some(layout) = target.python_layout() {
+        term.write_line(&format!(
+            "│ {} {}",
+            style("Layout:").dim(),
+            style(layout).green()
+        ))?;
+    }
+
+    if let Some(runner) = target.test_runner() {
+        term.write_line(&format!(
+            "│ {} {}",
+            style("Tests:").dim(),
+            style(runner).green()
+        ))?;
+    }
+
+    if let Some(runner) = target.task_runner() {
+        term.write_line(&format!(
+            "│ {} {}",
+            style("Tasks:").dim(),
+            style(runner).green()
+        ))?;
+    }
+
+    if let Some(license) = target.license() {
+        term.write_line(&format!(
+            "│ {} {}",
+            style("License:     ").dim(),
+            style(license).green()
+        ))?;
+    }
+
+    if let Some(ci) = target.ci() {
+        term.write_line(&format!(
+            "│ {} {}",
+            style("CI:          ").dim(),
+            style(ci).green()
+        ))?;
+    }
+
+    if !target.features().is_empty() {
+        term.write_line(&format!(
+            "│ {} {}",
+            style("Features:    ").dim(),
+            style(target.features().join(", ")).green()
+        ))?;
+    }
+
     let full_path = output.join(name);
     term.write_line(&format!(
         "│ {} {}",
-        "Location:    ".dimmed(),
+        style("Location:    ").dim(),
         style(full_path.display()).cyan()
     ))?;
-    term.write_line(&format!("│"))?;
-    term.write_line(&format!("{}", "╰─".cyan()))?;
+    term.write_line("│")?;
+    term.write_line(&format!("{}", style("╰─").cyan()))?;
     term.write_line("")?;
 
     Ok(())
 }
 
-/// Show dry-run output (what would be created).
-pub fn show_dry_run(target: &Target, name: &str, output: &Path) -> Result<()> {
+/// Show dry-run output: what a scaffold would create, overwrite or skip.
+///
+/// Directories are listed only with `-v`, which also shows the diff of
+/// each file that would be overwritten.
+pub fn show_dry_run(
+    target: &Target,
+    name: &str,
+    output: &Path,
+    plan: &ScaffoldPlan,
+    verbosity: Verbosity,
+) -> Result<()> {
     let term = Term::stdout();
 
     show_configuration(target, name, output)?;
 
+    for entry in plan.entries() {
+        if entry.kind == EntryKind::Directory && !verbosity.is_verbose() {
+            continue;
+        }
+        term.write_line(&planned_line(entry))?;
+        if entry.action == PlannedAction::Overwrite && verbosity.is_verbose() {
+            write_diff(&term, &entry.diff())?;
+        }
+    }
+
+    term.write_line("")?;
     term.write_line(&format!(
         "{} Dry run - nothing will be created",
-        "ℹ".blue().bold()
+        style("ℹ").blue().bold()
     ))?;
     term.write_line("")?;
 
     Ok(())
 }
 
+/// Display `path`, marking directories with `/` and symlinks with `@`.
+fn entry_path(path: &Path, kind: EntryKind) -> String {
+    let suffix = match kind {
+        EntryKind::File => "",
+        EntryKind::Directory => "/",
+        EntryKind::Symlink => "@",
+    };
+    format!("{}{suffix}", path.display())
+}
+
+/// Format a single planned entry as `  <marker> <action>  <path>`.
+fn planned_line(entry: &PlannedEntry) -> String {
+    let path = entry_path(&entry.path, entry.kind);
+
+    let (marker, action) = match entry.action {
+        PlannedAction::Create => (style("+").green().bold(), style("create").green()),
+        PlannedAction::Overwrite => (style("!").yellow().bold(), style("overwrite").yellow()),
+        PlannedAction::Skip => (style("=").dim(), style("skip").dim()),
+    };
+    format!("  {marker} {action:<9} {path}")
+}
+
+/// Tell the user where the target was saved.
+pub fn show_target_saved(path: &Path) -> Result<()> {
+    let term = Term::stdout();
+
+    term.write_line(&info(&format!(
+        "Target saved to {} (reuse it with --from-file)",
+        self::path(&path.display().to_string())
+    )))?;
+
+    Ok(())
+}
+
 // ============================================================================
 // Confirmation
 // ============================================================================
@@ -94,9 +275,9 @@ pub fn confirm() -> Result<()> {
 
     term.write_line(&format!(
         "{} Press {} to continue or {} to cancel...",
-        "?".yellow().bold(),
-        "Enter".green(),
-        "Ctrl+C".red()
+        style("?").yellow().bold(),
+        style("Enter").green(),
+        style("Ctrl+C").red()
     ))?;
 
     let mut input = String::new();
@@ -130,49 +311,427 @@ where
 
     match result {
         Ok(val) => {
-            println!("{} {}", "✓".green().bold(), message);
+            println!("{} {}", style("✓").green().bold(), message);
             Ok(val)
         }
         Err(e) => {
-            println!("{} {}", "✗".red().bold(), message);
+            println!("{} {}", style("✗").red().bold(), message);
             Err(e)
         }
     }
 }
 
+// ============================================================================
+// Scaffold Report
+// ============================================================================
+
+/// Show what happened to each entry of a scaffold.
+///
+/// Files are always listed; directories only with `-v`. Nothing is
+/// printed in quiet mode.
+pub fn show_report(report: &ScaffoldReport, verbosity: Verbosity) -> Result<()> {
+    if verbosity.is_quiet() {
+        return Ok(());
+    }
+
+    let term = Term::stdout();
+    term.write_line("")?;
+
+    for entry in report.entries() {
+        if entry.kind == EntryKind::Directory && !verbosity.is_verbose() {
+            continue;
+        }
+        term.write_line(&report_line(entry))?;
+    }
+
+    for outcome in report.hooks() {
+        term.write_line(&hook_line(outcome))?;
+        if let Some(command) = outcome.command.as_ref().filter(|_| verbosity.is_verbose()) {
+            term.write_line(&format!("             {}", style(command).dim()))?;
+        }
+    }
+
+    if let Some(vcs) = report.vcs() {
+        let action = if vcs.initialized {
+            "initialized git repository"
+        } else {
+            "using git repository"
+        };
+        term.write_line(&format!(
+            "  {} {} {}",
+            style("●").cyan().bold(),
+            action,
+            vcs.repository.display()
+        ))?;
+        if let Some(commit) = &vcs.commit {
+            term.write_line(&format!(
+                "  {} committed scaffold {}",
+                style("●").cyan().bold(),
+                style(&commit[..commit.len().min(12)]).dim()
+            ))?;
+        }
+    }
+
+    term.write_line(&format!("  {}", style(summary_line(report)).dim()))?;
+
+    let warnings = report.warnings();
+    if !warnings.is_empty() {
+        term.write_line("")?;
+        for text in &warnings {
+            term.write_line(&format!("  {}", warning(text)))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Summarize a report as `<files> files, <size> in <duration>`.
+fn summary_line(report: &ScaffoldReport) -> String {
+    let files = report.files().len();
+    format!(
+        "{files} file{}, {} in {}ms",
+        if files == 1 { "" } else { "s" },
+        format_size(report.total_size()),
+        report.duration().as_millis()
+    )
+}
+
+/// Format a byte count for display (`512 B`, `3.4 KiB`, `1.2 MiB`).
+fn format_size(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = KIB * 1024;
+    match bytes {
+        b if b < KIB => format!("{b} B"),
+        b if b < MIB => format!("{:.1} KiB", b as f64 / KIB as f64),
+        b => format!("{:.1} MiB", b as f64 / MIB as f64),
+    }
+}
+
+/// Format a single report entry as `  <marker> <status>  <path>`.
+fn report_line(entry: &ReportEntry) -> String {
+    let path = entry_path(&entry.path, entry.kind);
+
+    match &entry.status {
+        EntryStatus::Created => format!(
+            "  {} {:<8} {}",
+            style("+").green().bold(),
+            style("created").green(),
+            path
+        ),
+        EntryStatus::Skipped => format!(
+            "  {} {:<8} {}",
+            style("~").yellow().bold(),
+            style("skipped").yellow(),
+            path
+        ),
+        EntryStatus::Overwritten => format!(
+            "  {} {:<8} {}",
+            style("!").yellow().bold(),
+            style("replaced").yellow(),
+            path
+        ),
+        EntryStatus::BackedUp(backup) => format!(
+            "  {} {:<8} {} {}",
+            style("!").yellow().bold(),
+            style("replaced").yellow(),
+            path,
+            style(format!("(backup: {})", backup.display())).dim()
+        ),
+        EntryStatus::Merged => format!(
+            "  {} {:<8} {} {}",
+            style("!").yellow().bold(),
+            style("merged").yellow(),
+            path,
+            style("(resolve conflict markers)").dim()
+        ),
+        EntryStatus::Failed(reason) => format!(
+            "  {} {:<8} {} {}",
+            style("✗").red().bold(),
+            style("failed").red(),
+            path,
+            style(format!("({reason})")).dim()
+        ),
+    }
+}
+
+/// Format a hook outcome as `  <marker> <status>  <hook>`.
+fn hook_line(outcome: &HookOutcome) -> String {
+    match &outcome.status {
+        HookStatus::Succeeded => format!(
+            "  {} {:<8} {}",
+            style("●").cyan().bold(),
+            style("ran").cyan(),
+            outcome.hook
+        ),
+        HookStatus::Skipped => format!(
+            "  {} {:<8} {} {}",
+            style("~").yellow().bold(),
+            style("skipped").yellow(),
+            outcome.hook,
+            style("(pass --allow-hooks to run)").dim()
+        ),
+        HookStatus::Blocked(reason) => format!(
+            "  {} {:<8} {} {}",
+            style("!").yellow().bold(),
+            style("blocked").yellow(),
+            outcome.hook,
+            style(format!("({reason}; pass --unsafe-hooks to run)")).dim()
+        ),
+        HookStatus::Failed(reason) => format!(
+            "  {} {:<8} {} {}",
+            style("✗").red().bold(),
+            style("failed").red(),
+            outcome.hook,
+            style(format!("({reason})")).dim()
+        ),
+    }
+}
+
+// ============================================================================
+// Search Results
+// ============================================================================
+
+/// Show index entries matching a search.
+///
+/// Quiet mode prints bare names, one per line, for scripts; `-v` adds the
+/// source URL and checksum.
+pub fn show_search_results(
+    query: &str,
+    entries: &[&IndexEntry],
+    verbosity: Verbosity,
+) -> Result<()> {
+    let term = Term::stdout();
+
+    if verbosity.is_quiet() {
+        for entry in entries {
+            term.write_line(&entry.name)?;
+        }
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        term.write_line(&info(&format!("No templates match '{query}'")))?;
+        return Ok(());
+    }
+
+    for entry in entries {
+        let mut line = format!("  {}", style(&entry.name).cyan().bold());
+        if !entry.tags.is_empty() {
+            line.push_str(&format!(
+                " {}",
+                style(format!("[{}]", entry.tags.join(", "))).dim()
+            ));
+        }
+        term.write_line(&line)?;
+        if !entry.description.is_empty() {
+            term.write_line(&format!("      {}", entry.description))?;
+        }
+        if verbosity.is_verbose() {
+            term.write_line(&format!("      {} {}", style("source").dim(), entry.source))?;
+            term.write_line(&format!(
+                "      {} {}",
+                style("checksum").dim(),
+                entry.checksum
+            ))?;
+        }
+    }
+
+    term.write_line("")?;
+    term.write_line(&format!(
+        "{} template{} found",
+        entries.len(),
+        if entries.len() == 1 { "" } else { "s" }
+    ))?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Upgrade
+// ============================================================================
+
+/// Show the changes an upgrade would make.
+///
+/// Nothing is printed in quiet mode.
+pub fn show_upgrade_plan(plan: &UpgradePlan, verbosity: Verbosity) -> Result<()> {
+    if verbosity.is_quiet() {
+        return Ok(());
+    }
+
+    let term = Term::stdout();
+
+    if plan.is_up_to_date() {
+        term.write_line(&info(&format!("Already up to date ({})", plan.to)))?;
+        return Ok(());
+    }
+
+    term.write_line("")?;
+    term.write_line(&format!(
+        "{} {} → {}",
+        header("Upgrading"),
+        style(&plan.from).dim(),
+        value(&plan.to.to_string())
+    ))?;
+
+    for change in &plan.changes {
+        let (marker, status) = match change.kind {
+            ChangeKind::Added => (style("+").green().bold(), style("added").green()),
+            ChangeKind::Updated => (style("~").cyan().bold(), style("updated").cyan()),
+            ChangeKind::Conflict => (style("!").yellow().bold(), style("conflict").yellow()),
+        };
+        term.write_line(&format!("  {marker} {status:<8} {}", change.path.display()))?;
+        if change.kind == ChangeKind::Conflict && verbosity.is_verbose() {
+            write_diff(&term, &change.diff())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Show a conflicting change and ask what to do with it.
+///
+/// Anything but `a` or `s` rejects the change.
+pub fn ask_resolution(change: &FileChange) -> Result<Resolution> {
+    let term = Term::stdout();
+
+    term.write_line("")?;
+    term.write_line(&format!(
+        "{} {}",
+        style("!").yellow().bold(),
+        style(change.path.display()).bold()
+    ))?;
+    write_diff(&term, &change.diff())?;
+    term.write_line(&format!(
+        "{} {}ccept, {}kip, or {}eject to a .rej file? [a/s/R]",
+        style("?").yellow().bold(),
+        style("[a]").green(),
+        style("[s]").yellow(),
+        style("[r]").red()
+    ))?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(match input.trim().to_ascii_lowercase().as_str() {
+        "a" | "accept" => Resolution::Accept,
+        "s" | "skip" => Resolution::Skip,
+        _ => Resolution::Reject,
+    })
+}
+
+/// Show what an applied upgrade did.
+///
+/// Nothing is printed in quiet mode.
+pub fn show_upgrade_report(report: &UpgradeReport, verbosity: Verbosity) -> Result<()> {
+    if verbosity.is_quiet() {
+        return Ok(());
+    }
+
+    let term = Term::stdout();
+    term.write_line("")?;
+
+    for path in &report.applied {
+        term.write_line(&format!(
+            "  {} {:<8} {}",
+            style("✓").green().bold(),
+            style("applied").green(),
+            path.display()
+        ))?;
+    }
+    for path in &report.skipped {
+        term.write_line(&format!(
+            "  {} {:<8} {}",
+            style("~").yellow().bold(),
+            style("skipped").yellow(),
+            path.display()
+        ))?;
+    }
+    for path in &report.rejected {
+        term.write_line(&format!(
+            "  {} {:<8} {}",
+            style("✗").red().bold(),
+            style("rejected").red(),
+            path.display()
+        ))?;
+    }
+    if !report.rejected.is_empty() {
+        term.write_line("")?;
+        term.write_line(&warning(
+            "Review the .rej files, apply what you need, then delete them",
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Print a unified diff with added and removed lines colored.
+fn write_diff(term: &Term, diff: &str) -> Result<()> {
+    for line in diff.lines() {
+        let line = if line.starts_with("+++") || line.starts_with("---") {
+            style(line).bold()
+        } else if line.starts_with('+') {
+            style(line).green()
+        } else if line.starts_with('-') {
+            style(line).red()
+        } else if line.starts_with("@@") {
+            style(line).cyan()
+        } else {
+            style(line)
+        };
+        term.write_line(&format!("    {line}"))?;
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Success Messages
 // ============================================================================
 
 /// Show success message after scaffolding.
-pub fn show_success(name: &str, project_path: &Path, verbose: bool) -> Result<()> {
+///
+/// `next_steps` come from the template's hooks and replace the generic
+/// suggestions after `cd`.
+pub fn show_success(
+    name: &str,
+    _project_path: &Path,
+    next_steps: &[&str],
+    verbosity: Verbosity,
+) -> Result<()> {
     let term = Term::stdout();
 
     term.write_line("")?;
     term.write_line(&format!(
         "{} Project created successfully!",
-        "✓".green().bold()
+        style("✓").green().bold()
     ))?;
     term.write_line("")?;
 
     // Show next steps
-    term.write_line(&format!("{}", "Next steps:".cyan().bold()))?;
-    term.write_line(&format!("  {} cd {}", "1.".dimmed(), style(name).cyan()))?;
+    term.write_line(&format!("{}", style("Next steps:").cyan().bold()))?;
+    term.write_line(&format!(
+        "  {} cd {}",
+        style("1.").dim(),
+        style(name).cyan()
+    ))?;
 
-    if verbose {
+    if !next_steps.is_empty() {
+        for (i, step) in next_steps.iter().enumerate() {
+            term.write_line(&format!(
+                "  {} {}",
+                style(format!("{}.", i + 2)).dim(),
+                style(step).cyan()
+            ))?;
+        }
+    } else if verbosity.is_verbose() {
         term.write_line(&format!(
             "  {} Open in your editor: {} {}",
-            "2.".dimmed(),
-            "code".yellow(),
+            style("2.").dim(),
+            style("code").yellow(),
             name
         ))?;
-        term.write_line(&format!(
-            "  {} Start coding! {}",
-            "3.".dimmed(),
-            "🚀".to_string()
-        ))?;
+        term.write_line(&format!("  {} Start coding! 🚀", style("3.").dim()))?;
     } else {
-        term.write_line(&format!("  {} Start coding!", "2.".dimmed()))?;
+        term.write_line(&format!("  {} Start coding!", style("2.").dim()))?;
     }
 
     term.write_line("")?;
@@ -189,15 +748,15 @@ pub fn show_error(error: &anyhow::Error) -> Result<()> {
     let term = Term::stderr();
 
     term.write_line("")?;
-    term.write_line(&format!("{} Error", "✗".red().bold()))?;
+    term.write_line(&format!("{} Error", style("✗").red().bold()))?;
     term.write_line("")?;
 
     // Show the error chain
     for (i, cause) in error.chain().enumerate() {
         if i == 0 {
-            term.write_line(&format!("  {}", cause.to_string().red()))?;
+            term.write_line(&format!("  {}", style(cause).red()))?;
         } else {
-            term.write_line(&format!("  {} {}", "→".dimmed(), cause))?;
+            term.write_line(&format!("  {} {}", style("→").dim(), cause))?;
         }
     }
 
@@ -206,48 +765,56 @@ pub fn show_error(error: &anyhow::Error) -> Result<()> {
     Ok(())
 }
 
+/// Write an error to stderr as a single JSON object (`--format json`).
+pub fn show_error_json(error: &anyhow::Error) -> Result<()> {
+    let payload = crate::error::error_payload(error);
+    Term::stderr().write_line(&serde_json::to_string(&payload)?)?;
+    Ok(())
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
 /// Create a styled header.
 pub fn header(text: &str) -> String {
-    format!("{}", text.cyan().bold())
+    format!("{}", style(text).cyan().bold())
 }
 
 /// Create a styled value.
 pub fn value(text: &str) -> String {
-    format!("{}", text.green())
+    format!("{}", style(text).green())
 }
 
 /// Create a styled path.
 pub fn path(text: &str) -> String {
-    format!("{}", text.cyan())
+    format!("{}", style(text).cyan())
 }
 
 /// Create a styled warning.
 pub fn warning(text: &str) -> String {
-    format!("{} {}", "⚠".yellow().bold(), text.yellow())
+    format!("{} {}", style("⚠").yellow().bold(), style(text).yellow())
 }
 
 /// Create a styled info message.
 pub fn info(text: &str) -> String {
-    format!("{} {}", "ℹ".blue().bold(), text)
+    format!("{} {}", style("ℹ").blue().bold(), text)
 }
 
 /// Create a styled success message.
 pub fn success(text: &str) -> String {
-    format!("{} {}", "✓".green().bold(), text.green())
+    format!("{} {}", style("✓").green().bold(), style(text).green())
 }
 
 /// Create a styled error message.
 pub fn error(text: &str) -> String {
-    format!("{} {}", "✗".red().bold(), text.red())
+    format!("{} {}", style("✗").red().bold(), style(text).red())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn test_style_helpers() {
@@ -272,4 +839,99 @@ mod tests {
         let result: Result<()> = show_progress("Testing", || Err(anyhow::anyhow!("test error")));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn sizes_are_human_readable() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(3482), "3.4 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(0, true), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(0, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(1, false), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(2, false), Verbosity::Debug);
+        assert_eq!(Verbosity::from_flags(5, false), Verbosity::Debug);
+        assert!(Verbosity::Debug.is_verbose());
+        assert!(!Verbosity::Normal.is_verbose());
+    }
+
+    #[test]
+    fn report_lines_are_marked_and_aligned() {
+        console::set_colors_enabled(false);
+
+        let created = ReportEntry {
+            path: PathBuf::from("src/main.rs"),
+            kind: EntryKind::File,
+            status: EntryStatus::Created,
+        };
+        let skipped = ReportEntry {
+            path: PathBuf::from("src"),
+            kind: EntryKind::Directory,
+            status: EntryStatus::Skipped,
+        };
+        let failed = ReportEntry {
+            path: PathBuf::from("Cargo.toml"),
+            kind: EntryKind::File,
+            status: EntryStatus::Failed("denied".to_string()),
+        };
+
+        assert_eq!(report_line(&created), "  + created  src/main.rs");
+        assert_eq!(report_line(&skipped), "  ~ skipped  src/");
+        assert_eq!(report_line(&failed), "  ✗ failed   Cargo.toml (denied)");
+
+        let backed_up = ReportEntry {
+            path: PathBuf::from("Cargo.toml"),
+            kind: EntryKind::File,
+            status: EntryStatus::BackedUp(PathBuf::from("Cargo.toml.bak")),
+        };
+        assert_eq!(
+            report_line(&backed_up),
+            "  ! replaced Cargo.toml (backup: Cargo.toml.bak)"
+        );
+    }
+
+    #[test]
+    fn hook_lines_show_the_hook_and_why_it_did_not_run() {
+        use scarff_core::Hook;
+        console::set_colors_enabled(false);
+
+        let outcome = |hook, status| HookOutcome {
+            hook,
+            status,
+            command: None,
+        };
+
+        assert_eq!(
+            hook_line(&outcome(
+                Hook::make_executable("run.sh"),
+                HookStatus::Succeeded
+            )),
+            "  ● ran      chmod +x run.sh"
+        );
+        assert_eq!(
+            hook_line(&outcome(
+                Hook::command("git", ["init"]),
+                HookStatus::Skipped
+            )),
+            "  ~ skipped  git init (pass --allow-hooks to run)"
+        );
+        assert_eq!(
+            hook_line(&outcome(
+                Hook::script("curl x | sh"),
+                HookStatus::Blocked("scripts only run with unsafe hooks".to_string())
+            )),
+            "  ! blocked  script: curl x | sh (scripts only run with unsafe hooks; pass --unsafe-hooks to run)"
+        );
+        assert_eq!(
+            hook_line(&outcome(
+                Hook::script("make"),
+                HookStatus::Failed("exit code 2".to_string())
+            )),
+            "  ✗ failed   script: make (exit code 2)"
+        );
+    }
 }
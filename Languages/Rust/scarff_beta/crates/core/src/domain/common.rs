@@ -1,15 +1,18 @@
 use std::fmt;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
-/// A filesystem path guaranteed to be **relative**.
+/// A filesystem path guaranteed to stay **inside** whatever root it's
+/// joined onto.
 ///
 /// This type encodes an important invariant:
-/// templates and project structures must never contain absolute paths.
+/// templates and project structures must never contain absolute paths,
+/// nor `..` (or `.`) components that could walk out of the output root.
 ///
 /// Why?
 /// - Absolute paths break portability
-/// - They can overwrite arbitrary locations
-/// - They are almost always a bug in scaffolding systems
+/// - They (and `..`) can overwrite arbitrary locations
+/// - They are almost always a bug in scaffolding systems, or a malicious
+///   template
 ///
 /// `RelativePath` is a *semantic guardrail*, not a filesystem abstraction.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -19,13 +22,13 @@ impl RelativePath {
     /// Create a new relative path.
     ///
     /// # Panics
-    /// Panics if the provided path is absolute.
+    /// Panics if the provided path is empty, absolute, or has `..` or `.`
+    /// components (see [`RelativePath::check`]).
     pub fn new(path: impl Into<PathBuf>) -> Self {
         let path = path.into();
-        assert!(
-            !path.is_absolute(),
-            "RelativePath cannot be absolute: {path:?}"
-        );
+        if let Err(reason) = Self::check(&path) {
+            panic!("RelativePath cannot escape its root: {path:?} {reason}");
+        }
         Self(path)
     }
 
@@ -34,23 +37,42 @@ impl RelativePath {
     /// This is the non-panicking variant.
     pub fn try_new(path: impl Into<PathBuf>) -> Result<Self, PathBuf> {
         let path = path.into();
-        if path.is_absolute() {
-            Err(path)
-        } else {
-            Ok(Self(path))
+        match Self::check(&path) {
+            Ok(()) => Ok(Self(path)),
+            Err(_) => Err(path),
         }
     }
 
+    /// Check that `path` is safe to join onto an output root: non-empty and
+    /// made only of plain names.
+    ///
+    /// `a/../b` is rejected too, even though it stays inside: there is no
+    /// reason for a template to write it, and refusing every `..` keeps the
+    /// rule simple enough to trust.
+    pub fn check(path: &Path) -> Result<(), PathEscape> {
+        if path.as_os_str().is_empty() {
+            return Err(PathEscape::Empty);
+        }
+        for component in path.components() {
+            match component {
+                Component::Normal(_) => {}
+                Component::ParentDir => return Err(PathEscape::ParentDir),
+                Component::CurDir => return Err(PathEscape::CurrentDir),
+                Component::RootDir | Component::Prefix(_) => return Err(PathEscape::Absolute),
+            }
+        }
+        Ok(())
+    }
+
     /// Join a path segment onto this relative path.
     ///
     /// # Panics
-    /// Panics if the joined path is absolute.
+    /// Panics if the segment would escape (see [`RelativePath::check`]).
     pub fn join(&self, segment: impl AsRef<Path>) -> Self {
         let segment = segment.as_ref();
-        assert!(
-            !segment.is_absolute(),
-            "cannot join absolute path to RelativePath"
-        );
+        if let Err(reason) = Self::check(segment) {
+            panic!("cannot join {segment:?} to RelativePath: {reason}");
+        }
         Self(self.0.join(segment))
     }
 
@@ -83,6 +105,30 @@ impl From<String> for RelativePath {
     }
 }
 
+/// Why a path isn't a safe [`RelativePath`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathEscape {
+    /// No components at all: it would name the root itself
+    Empty,
+    /// Starts at a filesystem root or drive
+    Absolute,
+    /// Has a `..` component
+    ParentDir,
+    /// Has a `.` component
+    CurrentDir,
+}
+
+impl fmt::Display for PathEscape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "is empty",
+            Self::Absolute => "is absolute",
+            Self::ParentDir => "contains '..'",
+            Self::CurrentDir => "contains '.'",
+        })
+    }
+}
+
 impl fmt::Display for RelativePath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0.display())
@@ -143,14 +189,17 @@ impl Permissions {
 
     // Getters
 
+    /// Whether the entry can be read.
     pub const fn readable(&self) -> bool {
         self.readable
     }
 
+    /// Whether the entry can be modified.
     pub const fn writable(&self) -> bool {
         self.writable
     }
 
+    /// Whether the file can be executed (or the directory entered).
     pub const fn executable_flag(&self) -> bool {
         self.executable
     }
@@ -202,6 +251,87 @@ mod tests {
         base.join("/etc/passwd");
     }
 
+    #[test]
+    fn check_rejects_escapes() {
+        assert_eq!(RelativePath::check(Path::new("")), Err(PathEscape::Empty));
+        assert_eq!(
+            RelativePath::check(Path::new("../x")),
+            Err(PathEscape::ParentDir)
+        );
+        assert_eq!(
+            RelativePath::check(Path::new("a/../../x")),
+            Err(PathEscape::ParentDir)
+        );
+        assert_eq!(
+            RelativePath::check(Path::new("./x")),
+            Err(PathEscape::CurrentDir)
+        );
+        assert_eq!(
+            RelativePath::check(Path::new("/x")),
+            Err(PathEscape::Absolute)
+        );
+        assert!(RelativePath::try_new("src/..").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "contains '..'")]
+    fn join_rejects_parent_segment() {
+        RelativePath::new("src").join("../../etc/passwd");
+    }
+
+    /// Whether `path`, resolved lexically from a root, ends up strictly
+    /// inside it without ever stepping out.
+    fn stays_inside(path: &Path) -> bool {
+        let mut depth = 0usize;
+        for component in path.components() {
+            match component {
+                Component::Normal(_) => depth += 1,
+                Component::CurDir => {}
+                Component::ParentDir => match depth.checked_sub(1) {
+                    Some(d) => depth = d,
+                    None => return false,
+                },
+                Component::RootDir | Component::Prefix(_) => return false,
+            }
+        }
+        depth > 0
+    }
+
+    fn segment() -> impl proptest::strategy::Strategy<Value = String> {
+        use proptest::prelude::*;
+        prop_oneof![
+            4 => "[a-zA-Z0-9_.-]{1,8}",
+            1 => Just("..".to_string()),
+            1 => Just(".".to_string()),
+            1 => Just(String::new()),
+            1 => Just("/".to_string()),
+            1 => Just("/etc".to_string()),
+        ]
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn accepted_paths_never_leave_the_root(segments in proptest::collection::vec(segment(), 0..6)) {
+            let path = PathBuf::from(segments.join("/"));
+            if let Ok(relative) = RelativePath::try_new(&path) {
+                // Every prefix must stay inside too, or a write could pass
+                // through a directory outside the root on the way
+                let mut prefix = PathBuf::new();
+                for component in relative.as_path().components() {
+                    prefix.push(component);
+                    proptest::prop_assert!(stays_inside(&prefix), "{path:?} escapes via {prefix:?}");
+                }
+            }
+        }
+
+        #[test]
+        fn plain_names_are_accepted(names in proptest::collection::vec("[a-zA-Z0-9_-][a-zA-Z0-9_.-]{0,7}", 1..6)) {
+            proptest::prop_assume!(names.iter().all(|n| n != "." && n != ".."));
+            let path = names.join("/");
+            proptest::prop_assert!(RelativePath::try_new(&path).is_ok(), "{path:?} rejected");
+        }
+    }
+
     // ---------------------------------------------------------------------
     // Permissions
     // ---------------------------------------------------------------------
@@ -0,0 +1,432 @@
+//! User-supplied project trees for [`ProjectKind::Custom`](crate::domain::ProjectKind::Custom).
+//!
+//! A custom tree is a language-agnostic skeleton described either by an
+//! indented path list:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! src/
+//!   main.rs
+//!   utils/
+//!     mod.rs
+//! README.md
+//! ```
+//!
+//! or by JSON, where objects are directories and strings are file contents
+//! (`null` is an empty file):
+//!
+//! ```json
+//! { "src": { "main.rs": "fn main() {}\n" }, "README.md": null }
+//! ```
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::domain::{
+    DirectorySpec, DomainError, FileSpec, ProjectKind, RelativePath, TargetMatcher, Template,
+    TemplateContent, TemplateId, TemplateMetadata, TemplateNode, TemplateSource, TemplateTree,
+};
+
+// ============================================================================
+// CustomTree
+// ============================================================================
+
+/// A single entry of a [`CustomTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomEntry {
+    /// A directory
+    Directory {
+        /// Path relative to the project root
+        path: PathBuf,
+    },
+    /// A file
+    File {
+        /// Path relative to the project root
+        path: PathBuf,
+        /// File content (may be empty)
+        content: String,
+    },
+}
+
+impl CustomEntry {
+    /// Path relative to the project root.
+    pub fn path(&self) -> &Path {
+        match self {
+            CustomEntry::Directory { path } | CustomEntry::File { path, .. } => path,
+        }
+    }
+}
+
+/// An exact project structure supplied by the user.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CustomTree {
+    entries: Vec<CustomEntry>,
+    headers: bool,
+}
+
+impl CustomTree {
+    /// Parse a tree, auto-detecting JSON (leading `{`) or the indented DSL.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::InvalidCustomTree` pointing at the offending line.
+    pub fn parse(input: &str) -> Result<Self, DomainError> {
+        if input.trim_start().starts_with('{') {
+            Self::parse_json(input)
+        } else {
+            Self::parse_indented(input)
+        }
+    }
+
+    /// Parse the indented path DSL.
+    ///
+    /// Entries ending in `/` are directories; deeper indentation nests an
+    /// entry inside the directory above it. Indent with spaces only.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::InvalidCustomTree` pointing at the offending line.
+    pub fn parse_indented(input: &str) -> Result<Self, DomainError> {
+        let mut tree = Self::default();
+        // Open directories as (indent, path)
+        let mut stack: Vec<(usize, PathBuf)> = Vec::new();
+        // Previous entry as (indent, is_dir)
+        let mut last: Option<(usize, bool)> = None;
+
+        for (idx, raw) in input.lines().enumerate() {
+            let line = idx + 1;
+            let trimmed_end = raw.trim_end();
+            let content = trimmed_end.trim_start();
+
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            let leading = &trimmed_end[..trimmed_end.len() - content.len()];
+            if leading.contains('\t') {
+                return Err(invalid(line, "indent with spaces, not tabs"));
+            }
+            let indent = leading.len();
+
+            match last {
+                Some((prev_indent, false)) if indent > prev_indent => {
+                    return Err(invalid(line, "entries cannot be nested under a file"));
+                }
+                None if indent > 0 => {
+                    return Err(invalid(line, "the first entry must not be indented"));
+                }
+                _ => {}
+            }
+
+            while stack.last().is_some_and(|(i, _)| *i >= indent) {
+                stack.pop();
+            }
+
+            let is_dir = content.ends_with('/');
+            let name = content.trim_end_matches('/');
+            let parent = stack.last().map(|(_, p)| p.as_path());
+            let path = checked_path(parent, name).map_err(|reason| invalid(line, reason))?;
+
+            let entry = if is_dir {
+                CustomEntry::Directory { path: path.clone() }
+            } else {
+                CustomEntry::File {
+                    path: path.clone(),
+                    content: String::new(),
+                }
+            };
+            tree.push(entry).map_err(|reason| invalid(line, reason))?;
+
+            if is_dir {
+                stack.push((indent, path));
+            }
+            last = Some((indent, is_dir));
+        }
+
+        tree.ensure_not_empty()?;
+        Ok(tree)
+    }
+
+    /// Parse the JSON form.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::InvalidCustomTree` for malformed JSON or values
+    /// other than objects, strings, and `null`.
+    pub fn parse_json(input: &str) -> Result<Self, DomainError> {
+        let value: serde_json::Value = serde_json::from_str(input)
+            .map_err(|e| invalid(e.line(), format!("invalid JSON: {e}")))?;
+
+        let serde_json::Value::Object(root) = value else {
+            return Err(invalid(1, "the top-level JSON value must be an object"));
+        };
+
+        let mut tree = Self::default();
+        tree.push_json_object(None, &root)?;
+        tree.ensure_not_empty()?;
+        Ok(tree)
+    }
+
+    fn push_json_object(
+        &mut self,
+        parent: Option<&Path>,
+        object: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), DomainError> {
+        for (name, value) in object {
+            let path = checked_path(parent, name.trim_end_matches('/'))
+                .map_err(|reason| invalid(0, format!("'{name}': {reason}")))?;
+
+            let entry = match value {
+                serde_json::Value::Object(_) => CustomEntry::Directory { path: path.clone() },
+                serde_json::Value::String(content) => CustomEntry::File {
+                    path: path.clone(),
+                    content: content.clone(),
+                },
+                serde_json::Value::Null => CustomEntry::File {
+                    path: path.clone(),
+                    content: String::new(),
+                },
+                _ => {
+                    return Err(invalid(
+                        0,
+                        format!("'{name}': expected an object, string, or null"),
+                    ));
+                }
+            };
+            self.push(entry)
+                .map_err(|reason| invalid(0, format!("'{name}': {reason}")))?;
+
+            if let serde_json::Value::Object(children) = value {
+                self.push_json_object(Some(&path), children)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, entry: CustomEntry) -> Result<(), String> {
+        if self.entries.iter().any(|e| e.path() == entry.path()) {
+            return Err(format!("duplicate path '{}'", entry.path().display()));
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    fn ensure_not_empty(&self) -> Result<(), DomainError> {
+        if self.entries.is_empty() {
+            return Err(invalid(0, "the tree has no entries"));
+        }
+        Ok(())
+    }
+
+    /// Prefix every file with a comment header naming the project and path.
+    ///
+    /// The comment syntax is chosen from the file extension; files with an
+    /// unknown extension are left untouched.
+    #[must_use]
+    pub fn with_headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Whether boilerplate headers are enabled.
+    pub fn headers(&self) -> bool {
+        self.headers
+    }
+
+    /// All entries in declaration order.
+    pub fn entries(&self) -> &[CustomEntry] {
+        &self.entries
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the tree has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Build an ad-hoc template that materializes exactly this tree.
+    ///
+    /// File contents are parameterized, so `{{PROJECT_NAME}}` and friends
+    /// are substituted like in built-in templates.
+    pub(crate) fn to_template(&self) -> Result<Template, DomainError> {
+        let mut tree = TemplateTree::new();
+        for entry in &self.entries {
+            let node = match entry {
+                CustomEntry::Directory { path } => {
+                    TemplateNode::Directory(DirectorySpec::new(RelativePath::new(path.clone())))
+                }
+                CustomEntry::File { path, content } => {
+                    let content = if self.headers {
+                        with_header(path, content)
+                    } else {
+                        content.clone()
+                    };
+                    TemplateNode::File(FileSpec::new(
+                        RelativePath::new(path.clone()),
+                        TemplateContent::Parameterized(TemplateSource::Owned(content)),
+                    ))
+                }
+            };
+            tree.push(node);
+        }
+
+        Template::builder()
+            .id(TemplateId::new("custom", "0.0.0".to_string()))
+            .matcher(TargetMatcher::builder().kind(ProjectKind::Custom).build())
+            .metadata(
+                TemplateMetadata::new("custom")
+                    .description("User-defined project tree")
+                    .version("0.0.0")
+                    .tags(vec!["custom"]),
+            )
+            .tree(tree)
+            .build()
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn invalid(line: usize, reason: impl Into<String>) -> DomainError {
+    DomainError::InvalidCustomTree {
+        line,
+        reason: reason.into(),
+    }
+}
+
+/// Join `name` onto `parent`, rejecting anything that could escape the root.
+fn checked_path(parent: Option<&Path>, name: &str) -> Result<PathBuf, String> {
+    if name.is_empty() {
+        return Err("empty path".to_string());
+    }
+
+    let candidate = Path::new(name);
+    if !candidate
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(format!(
+            "'{name}' must be a plain relative path (no '..', '.', or leading '/')"
+        ));
+    }
+
+    Ok(parent.map_or_else(|| candidate.to_path_buf(), |p| p.join(candidate)))
+}
+
+/// Prepend a comment header matching the file type.
+fn with_header(path: &Path, content: &str) -> String {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let label = format!("{{{{PROJECT_NAME}}}} - {}", path.display());
+
+    let header = match (extension, file_name) {
+        (
+            "rs" | "ts" | "tsx" | "js" | "jsx" | "mjs" | "go" | "c" | "h" | "cpp" | "hpp" | "java"
+            | "kt" | "swift",
+            _,
+        ) => format!("// {label}\n"),
+        ("py" | "sh" | "toml" | "yaml" | "yml" | "rb" | "ini" | "cfg", _)
+        | (_, "Dockerfile" | "Makefile" | "justfile" | ".gitignore") => format!("# {label}\n"),
+        ("md" | "html" | "xml" | "svg", _) => format!("<!-- {label} -->\n"),
+        ("css" | "scss", _) => format!("/* {label} */\n"),
+        _ => return content.to_string(),
+    };
+
+    if content.is_empty() {
+        header
+    } else {
+        format!("{header}\n{content}")
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_indented_tree() {
+        let tree =
+            CustomTree::parse("# skeleton\nsrc/\n  main.rs\n  utils/\n    mod.rs\n\nREADME.md\n")
+                .unwrap();
+
+        let paths: Vec<_> = tree.entries().iter().map(CustomEntry::path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("src"),
+                Path::new("src/main.rs"),
+                Path::new("src/utils"),
+                Path::new("src/utils/mod.rs"),
+                Path::new("README.md"),
+            ]
+        );
+        assert!(matches!(tree.entries()[0], CustomEntry::Directory { .. }));
+        assert!(matches!(tree.entries()[4], CustomEntry::File { .. }));
+    }
+
+    #[test]
+    fn parses_json_tree() {
+        let tree =
+            CustomTree::parse(r#"{ "src": { "main.rs": "fn main() {}\n" }, "README.md": null }"#)
+                .unwrap();
+
+        assert_eq!(tree.len(), 3);
+        assert!(tree.entries().contains(&CustomEntry::File {
+            path: PathBuf::from("src/main.rs"),
+            content: "fn main() {}\n".to_string(),
+        }));
+    }
+
+    #[test]
+    fn rejects_nesting_under_file() {
+        let err = CustomTree::parse("main.rs\n  nested.rs\n").unwrap_err();
+
+        assert!(matches!(
+            err,
+            DomainError::InvalidCustomTree { line: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_escaping_paths() {
+        assert!(CustomTree::parse("../evil.rs\n").is_err());
+        assert!(CustomTree::parse("/etc/passwd\n").is_err());
+        assert!(CustomTree::parse(r#"{ "..": {} }"#).is_err());
+    }
+
+    #[test]
+    fn rejects_duplicates_and_empty_trees() {
+        assert!(CustomTree::parse("a.rs\na.rs\n").is_err());
+        assert!(CustomTree::parse("# nothing here\n").is_err());
+    }
+
+    #[test]
+    fn headers_use_extension_comment_style() {
+        assert_eq!(
+            with_header(Path::new("src/main.rs"), ""),
+            "// {{PROJECT_NAME}} - src/main.rs\n"
+        );
+        assert!(with_header(Path::new("app.py"), "x = 1\n").starts_with("# "));
+        assert_eq!(with_header(Path::new("data.bin"), "raw"), "raw");
+    }
+
+    #[test]
+    fn converts_to_template() {
+        let template = CustomTree::parse("src/\n  lib.rs\n")
+            .unwrap()
+            .with_headers(true)
+            .to_template()
+            .unwrap();
+
+        assert_eq!(template.tree.len(), 2);
+        assert_eq!(template.matcher.kind, Some(ProjectKind::Custom));
+    }
+}
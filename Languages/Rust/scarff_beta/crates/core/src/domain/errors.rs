@@ -1,7 +1,10 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
+use crate::errors::ErrorPayload;
+
 /// Domain-specific errors for Scarff's core types.
 #[derive(Debug, Error, Clone)]
 pub enum DomainError {
@@ -17,7 +20,7 @@ pub enum DomainError {
     // ========================================================================
     /// Unsupported project type
     #[error(
-        "Unsupported project type '{kind}'. Supported: cli, backend, frontend, fullstack, worker"
+        "Unsupported project type '{kind}'. Supported: cli, backend, frontend, fullstack, worker, custom, workspace"
     )]
     UnsupportedProjectKind { kind: String },
 
@@ -70,6 +73,72 @@ pub enum DomainError {
         framework: String,
     },
 
+    // ========================================================================
+    // Dependency manager errors
+    // ========================================================================
+    /// Dependency manager set for a language that doesn't use it
+    #[error(
+        "Dependency manager '{manager}' is not available for language '{language}'. Dependency managers only apply to python projects"
+    )]
+    DependencyManagerLanguageMismatch { manager: String, language: String },
+
+    /// Dependency manager can't be used with the framework
+    #[error(
+        "Dependency manager '{manager}' does not support framework '{framework}'. Choose a compatible dependency manager"
+    )]
+    DependencyManagerFrameworkMismatch { manager: String, framework: String },
+
+    // ========================================================================
+    // Python layout errors
+    // ========================================================================
+    /// Python layout set for a language that doesn't use it
+    #[error(
+        "Python layout '{layout}' is not available for language '{language}'. Layouts only apply to python projects"
+    )]
+    PythonLayoutLanguageMismatch { layout: String, language: String },
+
+    /// Python layout the dependency manager can't build
+    #[error(
+        "Python layout '{layout}' is not supported by dependency manager '{manager}'. Choose uv or poetry for the src layout"
+    )]
+    PythonLayoutManagerMismatch { layout: String, manager: String },
+
+    // ========================================================================
+    // Test runner errors
+    // ========================================================================
+    /// Test runner set for a language that doesn't use it
+    #[error(
+        "Test runner '{runner}' is not available for language '{language}'. Test runners only apply to typescript projects"
+    )]
+    TestRunnerLanguageMismatch { runner: String, language: String },
+
+    /// Test runner can't be used with the framework
+    #[error(
+        "Test runner '{runner}' does not support framework '{framework}'. Choose a compatible test runner"
+    )]
+    TestRunnerFrameworkMismatch { runner: String, framework: String },
+
+    // ========================================================================
+    // Task runner errors
+    // ========================================================================
+    /// Task runner set for a language that doesn't use it
+    #[error(
+        "Task runner '{runner}' is not available for language '{language}'. Task runners only apply to rust projects"
+    )]
+    TaskRunnerLanguageMismatch { runner: String, language: String },
+
+    // ========================================================================
+    // Project name errors
+    // ========================================================================
+    /// Project name is not a valid package name for the language
+    #[error("Invalid project name '{name}' for {language}: {reason}")]
+    InvalidProjectName {
+        name: String,
+        language: String,
+        reason: String,
+        suggestion: Option<String>,
+    },
+
     // ========================================================================
     // Inference errors
     // ========================================================================
@@ -114,6 +183,45 @@ pub enum DomainError {
     #[error("Invalid template '{name}': {reason}")]
     InvalidTemplateWithMetadata { name: String, reason: String },
 
+    /// User-supplied custom tree could not be parsed
+    #[error("Invalid custom tree (line {line}): {reason}")]
+    InvalidCustomTree { line: usize, reason: String },
+
+    // ========================================================================
+    // Preset errors
+    // ========================================================================
+    /// No preset is registered under this name
+    #[error("Unknown preset '{name}'. Available: {}", available.join(", "))]
+    UnknownPreset {
+        name: String,
+        available: Vec<String>,
+    },
+
+    /// A preset definition (e.g. from the config file) is invalid
+    #[error("Invalid preset '{name}': {reason}")]
+    InvalidPreset { name: String, reason: String },
+
+    // ========================================================================
+    // Target file errors
+    // ========================================================================
+    /// A saved target (`scarff.target.toml`) is malformed
+    #[error("Invalid target file: {reason}")]
+    InvalidTargetFile { reason: String },
+
+    // ========================================================================
+    // Workspace errors
+    // ========================================================================
+    /// A workspace or one of its members is invalid
+    #[error("Invalid workspace: {reason}")]
+    InvalidWorkspace { reason: String },
+
+    // ========================================================================
+    // Feature errors
+    // ========================================================================
+    /// A target feature (optional template fragment) name is invalid
+    #[error("Invalid feature '{feature}': {reason}")]
+    InvalidFeature { feature: String, reason: String },
+
     // ========================================================================
     // ProjectStructure errors
     // ========================================================================
@@ -165,6 +273,8 @@ impl DomainError {
                 "  • frontend  - Web frontend applications".to_string(),
                 "  • fullstack - Full-stack web applications".to_string(),
                 "  • worker    - Background workers/processors".to_string(),
+                "  • custom    - Exactly the tree you supply".to_string(),
+                "  • workspace - Several projects in one repository".to_string(),
                 format!("You provided: {}", kind),
             ],
 
@@ -262,6 +372,68 @@ impl DomainError {
                 "Try a different architecture or framework".to_string(),
             ],
 
+            // Dependency manager errors
+            Self::DependencyManagerLanguageMismatch { manager, language } => vec![
+                format!("{} manages python dependencies, not {}", manager, language),
+                "Drop --deps, or use --lang python".to_string(),
+            ],
+
+            Self::DependencyManagerFrameworkMismatch { manager, framework } => vec![
+                format!("{} cannot be used with {}", manager, framework),
+                "Available dependency managers: uv, poetry, pip".to_string(),
+            ],
+
+            // Python layout errors
+            Self::PythonLayoutLanguageMismatch { layout, language } => vec![
+                format!("The {} layout arranges python packages, not {}", layout, language),
+                "Drop --layout, or use --lang python".to_string(),
+            ],
+
+            Self::PythonLayoutManagerMismatch { layout, manager } => vec![
+                format!("{} cannot install a {} layout package", manager, layout),
+                "Use --deps uv or --deps poetry with --layout src, or --layout flat with pip"
+                    .to_string(),
+            ],
+
+            // Test runner errors
+            Self::TestRunnerLanguageMismatch { runner, language } => vec![
+                format!("{} runs typescript tests, not {}", runner, language),
+                "Drop --test-runner, or use --lang typescript".to_string(),
+            ],
+
+            Self::TestRunnerFrameworkMismatch { runner, framework } => vec![
+                format!("{} cannot be used with {}", runner, framework),
+                "Test runner compatibility:".to_string(),
+                "  • react/vue (vite) → vitest, none".to_string(),
+                "  • nextjs/express   → vitest, jest, none".to_string(),
+                "  • nestjs           → jest, none".to_string(),
+            ],
+
+            // Task runner errors
+            Self::TaskRunnerLanguageMismatch { runner, language } => vec![
+                format!("{} recipes are generated for rust, not {}", runner, language),
+                "Drop --task-runner, or use --lang rust".to_string(),
+            ],
+
+            // Project name errors
+            Self::InvalidProjectName {
+                name,
+                language,
+                reason,
+                suggestion,
+            } => {
+                let rules = match language.as_str() {
+                    "rust" => "Rust projects are crates: letters, digits, '-' and '_', starting with a letter",
+                    "typescript" => "TypeScript projects are npm packages: lowercase letters, digits, '-', '.', '_' and '~'",
+                    _ => "Python projects need a distribution name whose snake_case form is an importable module",
+                };
+                let mut result = vec![format!("'{}' can't be used: {}", name, reason), rules.to_string()];
+                if let Some(suggestion) = suggestion {
+                    result.push(format!("Try: scarff new {} --lang {}", suggestion, language));
+                }
+                result
+            }
+
             // Inference errors
             Self::CannotInfer { field, reason } => vec![
                 format!("Cannot automatically determine {}", field),
@@ -335,6 +507,47 @@ impl DomainError {
                 "This template may be corrupted or incorrectly defined".to_string(),
             ],
 
+            Self::InvalidCustomTree { reason, .. } => vec![
+                reason.clone(),
+                "Use one path per line; end directories with '/'".to_string(),
+                "Indent entries with spaces to nest them inside a directory".to_string(),
+                "Or pass JSON: {\"src\": {\"main.rs\": null}}".to_string(),
+            ],
+
+            // Preset errors
+            Self::UnknownPreset { name, available } => vec![
+                format!("No preset named '{}'", name),
+                format!("Available presets: {}", available.join(", ")),
+                "Define your own under [presets.<name>] in the config file".to_string(),
+            ],
+
+            Self::InvalidPreset { name, reason } => vec![
+                format!("Preset '{}' could not be loaded", name),
+                reason.clone(),
+                "Each preset needs at least `language`; `type`, `framework`, `architecture`, `dependency_manager`, `python_layout`, `test_runner`, `task_runner`, `license` and `ci` are optional".to_string(),
+            ],
+
+            // Target file errors
+            Self::InvalidTargetFile { reason } => vec![
+                reason.clone(),
+                "A target file needs at least `language`; `kind`, `framework`, `architecture`, `dependency_manager`, `python_layout`, `test_runner`, `task_runner`, `license` and `ci` are optional".to_string(),
+                "Regenerate it with `scarff new <name> ... --save-target --dry-run`".to_string(),
+            ],
+
+            // Workspace errors
+            Self::InvalidWorkspace { reason } => vec![
+                reason.clone(),
+                "Members need a unique name (letters, digits, '-', '_', '.') and a target in the workspace's language".to_string(),
+                "Python workspaces are uv workspaces: build Python members with the uv dependency manager".to_string(),
+            ],
+
+            // Feature errors
+            Self::InvalidFeature { reason, .. } => vec![
+                reason.clone(),
+                "Feature names use lowercase letters, digits and '-' (e.g. docker, ci-github)".to_string(),
+                "Features switch on optional files of a template; templates ignore the ones they don't offer".to_string(),
+            ],
+
             // ProjectStructure errors
             Self::ProjectStructureError(msg) => vec![
                 "Project structure validation failed".to_string(),
@@ -365,6 +578,15 @@ impl DomainError {
             Self::UnsupportedArchitecture { .. }
             | Self::ArchitectureProjectKindMismatch { .. }
             | Self::ArchitectureFrameworkMismatch { .. } => "Architecture Error",
+            Self::DependencyManagerLanguageMismatch { .. }
+            | Self::DependencyManagerFrameworkMismatch { .. } => "Dependency Manager Error",
+            Self::TestRunnerLanguageMismatch { .. } | Self::TestRunnerFrameworkMismatch { .. } => {
+                "Test Runner Error"
+            }
+            Self::TaskRunnerLanguageMismatch { .. } => "Task Runner Error",
+            Self::PythonLayoutLanguageMismatch { .. }
+            | Self::PythonLayoutManagerMismatch { .. } => "Python Layout Error",
+            Self::InvalidProjectName { .. } => "Project Name Error",
             Self::CannotInfer { .. }
             | Self::AmbiguousIntent { .. }
             | Self::InferenceRefused { .. } => "Configuration Error",
@@ -372,11 +594,188 @@ impl DomainError {
             | Self::TemplateDuplicatePath { .. }
             | Self::TemplateAbsolutePath { .. }
             | Self::InvalidTemplate(_)
-            | Self::InvalidTemplateWithMetadata { .. } => "Template Error",
+            | Self::InvalidTemplateWithMetadata { .. }
+            | Self::InvalidCustomTree { .. } => "Template Error",
+            Self::UnknownPreset { .. } | Self::InvalidPreset { .. } => "Preset Error",
+            Self::InvalidTargetFile { .. } => "Target File Error",
+            Self::InvalidWorkspace { .. } => "Workspace Error",
+            Self::InvalidFeature { .. } => "Feature Error",
             Self::ProjectStructureError(_) => "Structure Error",
             Self::NotSupported => "Unsupported",
         }
     }
+
+    /// Stable, machine-readable code for this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnsupportedLanguage { .. } => "domain.unsupported_language",
+            Self::UnsupportedProjectKind { .. } => "domain.unsupported_project_kind",
+            Self::ProjectKindLanguageMismatch { .. } => "domain.project_kind_language_mismatch",
+            Self::FrameworkLanguageMismatch { .. } => "domain.framework_language_mismatch",
+            Self::FrameworkProjectKindMismatch { .. } => "domain.framework_project_kind_mismatch",
+            Self::FrameworkRequired { .. } => "domain.framework_required",
+            Self::UnsupportedArchitecture { .. } => "domain.unsupported_architecture",
+            Self::ArchitectureProjectKindMismatch { .. } => {
+                "domain.architecture_project_kind_mismatch"
+            }
+            Self::ArchitectureFrameworkMismatch { .. } => "domain.architecture_framework_mismatch",
+            Self::DependencyManagerLanguageMismatch { .. } => {
+                "domain.dependency_manager_language_mismatch"
+            }
+            Self::DependencyManagerFrameworkMismatch { .. } => {
+                "domain.dependency_manager_framework_mismatch"
+            }
+            Self::TestRunnerLanguageMismatch { .. } => "domain.test_runner_language_mismatch",
+            Self::TestRunnerFrameworkMismatch { .. } => "domain.test_runner_framework_mismatch",
+            Self::TaskRunnerLanguageMismatch { .. } => "domain.task_runner_language_mismatch",
+            Self::PythonLayoutLanguageMismatch { .. } => "domain.python_layout_language_mismatch",
+            Self::PythonLayoutManagerMismatch { .. } => "domain.python_layout_manager_mismatch",
+            Self::InvalidProjectName { .. } => "domain.invalid_project_name",
+            Self::CannotInfer { .. } => "domain.cannot_infer",
+            Self::AmbiguousIntent { .. } => "domain.ambiguous_intent",
+            Self::InferenceRefused { .. } => "domain.inference_refused",
+            Self::TemplateEmptyTree { .. } => "domain.template_empty_tree",
+            Self::TemplateDuplicatePath { .. } => "domain.template_duplicate_path",
+            Self::TemplateAbsolutePath { .. } => "domain.template_absolute_path",
+            Self::InvalidTemplate(_) | Self::InvalidTemplateWithMetadata { .. } => {
+                "domain.invalid_template"
+            }
+            Self::InvalidCustomTree { .. } => "domain.invalid_custom_tree",
+            Self::UnknownPreset { .. } => "domain.unknown_preset",
+            Self::InvalidPreset { .. } => "domain.invalid_preset",
+            Self::InvalidTargetFile { .. } => "domain.invalid_target_file",
+            Self::InvalidWorkspace { .. } => "domain.invalid_workspace",
+            Self::InvalidFeature { .. } => "domain.invalid_feature",
+            Self::ProjectStructureError(_) => "domain.project_structure",
+            Self::NotSupported => "domain.not_supported",
+        }
+    }
+
+    /// Structured details of the failure, keyed by field name.
+    pub fn context(&self) -> BTreeMap<&'static str, String> {
+        let pairs: Vec<(&'static str, String)> = match self {
+            Self::UnsupportedLanguage { language } => vec![("language", language.clone())],
+            Self::UnsupportedProjectKind { kind } | Self::FrameworkRequired { kind } => {
+                vec![("kind", kind.clone())]
+            }
+            Self::ProjectKindLanguageMismatch { kind, language } => {
+                vec![("kind", kind.clone()), ("language", language.clone())]
+            }
+            Self::FrameworkLanguageMismatch {
+                framework,
+                language,
+            } => vec![
+                ("framework", framework.clone()),
+                ("language", language.clone()),
+            ],
+            Self::FrameworkProjectKindMismatch { framework, kind } => {
+                vec![("framework", framework.clone()), ("kind", kind.clone())]
+            }
+            Self::UnsupportedArchitecture { architecture } => {
+                vec![("architecture", architecture.clone())]
+            }
+            Self::ArchitectureProjectKindMismatch { architecture, kind } => vec![
+                ("architecture", architecture.clone()),
+                ("kind", kind.clone()),
+            ],
+            Self::ArchitectureFrameworkMismatch {
+                architecture,
+                framework,
+            } => vec![
+                ("architecture", architecture.clone()),
+                ("framework", framework.clone()),
+            ],
+            Self::DependencyManagerLanguageMismatch { manager, language } => {
+                vec![("manager", manager.clone()), ("language", language.clone())]
+            }
+            Self::DependencyManagerFrameworkMismatch { manager, framework } => vec![
+                ("manager", manager.clone()),
+                ("framework", framework.clone()),
+            ],
+            Self::TestRunnerLanguageMismatch { runner, language } => {
+                vec![("runner", runner.clone()), ("language", language.clone())]
+            }
+            Self::TestRunnerFrameworkMismatch { runner, framework } => {
+                vec![("runner", runner.clone()), ("framework", framework.clone())]
+            }
+            Self::TaskRunnerLanguageMismatch { runner, language } => {
+                vec![("runner", runner.clone()), ("language", language.clone())]
+            }
+            Self::PythonLayoutLanguageMismatch { layout, language } => {
+                vec![("layout", layout.clone()), ("language", language.clone())]
+            }
+            Self::PythonLayoutManagerMismatch { layout, manager } => {
+                vec![("layout", layout.clone()), ("manager", manager.clone())]
+            }
+            Self::InvalidProjectName {
+                name,
+                language,
+                reason,
+                suggestion,
+            } => {
+                let mut pairs = vec![
+                    ("name", name.clone()),
+                    ("language", language.clone()),
+                    ("reason", reason.clone()),
+                ];
+                pairs.extend(suggestion.clone().map(|s| ("suggestion", s)));
+                pairs
+            }
+            Self::CannotInfer { field, reason } => {
+                vec![("field", field.clone()), ("reason", reason.clone())]
+            }
+            Self::AmbiguousIntent {
+                reason,
+                suggestions,
+            } => vec![
+                ("reason", reason.clone()),
+                ("suggestions", suggestions.join(", ")),
+            ],
+            Self::InferenceRefused {
+                message,
+                suggestions,
+            } => vec![
+                ("reason", message.clone()),
+                ("suggestions", suggestions.join(", ")),
+            ],
+            Self::TemplateEmptyTree { template_id } => vec![("template_id", template_id.clone())],
+            Self::TemplateDuplicatePath { template_id, path }
+            | Self::TemplateAbsolutePath { template_id, path } => vec![
+                ("template_id", template_id.clone()),
+                ("path", path.display().to_string()),
+            ],
+            Self::InvalidTemplate(reason)
+            | Self::InvalidTargetFile { reason }
+            | Self::InvalidWorkspace { reason }
+            | Self::ProjectStructureError(reason) => vec![("reason", reason.clone())],
+            Self::InvalidTemplateWithMetadata { name, reason }
+            | Self::InvalidPreset { name, reason } => {
+                vec![("name", name.clone()), ("reason", reason.clone())]
+            }
+            Self::InvalidCustomTree { line, reason } => {
+                vec![("line", line.to_string()), ("reason", reason.clone())]
+            }
+            Self::InvalidFeature { feature, reason } => {
+                vec![("feature", feature.clone()), ("reason", reason.clone())]
+            }
+            Self::UnknownPreset { name, available } => {
+                vec![("name", name.clone()), ("available", available.join(", "))]
+            }
+            Self::NotSupported => vec![],
+        };
+        pairs.into_iter().collect()
+    }
+
+    /// Machine-readable form of this error.
+    pub fn to_payload(&self) -> ErrorPayload {
+        ErrorPayload::new(self.code(), self.to_string(), self.context())
+    }
+}
+
+impl Serialize for DomainError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_payload().serialize(serializer)
+    }
 }
 
 #[cfg(test)]
@@ -403,6 +802,37 @@ mod tests {
         assert_eq!(err.category(), "Framework Error");
     }
 
+    #[test]
+    fn error_has_stable_code_and_context() {
+        let err = DomainError::FrameworkLanguageMismatch {
+            framework: "django".to_string(),
+            language: "rust".to_string(),
+        };
+
+        assert_eq!(err.code(), "domain.framework_language_mismatch");
+        assert_eq!(err.context()["framework"], "django");
+        assert_eq!(err.context()["language"], "rust");
+    }
+
+    #[test]
+    fn error_serializes_code_message_and_context() {
+        let err = DomainError::InvalidCustomTree {
+            line: 3,
+            reason: "bad indent".to_string(),
+        };
+
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "domain.invalid_custom_tree",
+                "message": "Invalid custom tree (line 3): bad indent",
+                "context": { "line": "3", "reason": "bad indent" },
+            })
+        );
+    }
+
     #[test]
     fn framework_mismatch_suggests_correct_frameworks() {
         let err = DomainError::FrameworkLanguageMismatch {
@@ -0,0 +1,179 @@
+//! Post-scaffold hooks declared by templates.
+//!
+//! A hook is a step to take once the project has been written: run a
+//! tool (`cargo init`, `git init`), run a shell script, or a builtin step
+//! scarff performs itself (`chmod +x`, printing next steps). Like the rest
+//! of a template, hooks are declarative; running them is up to the
+//! [`scaffold`](crate::scaffold) layer.
+
+use std::fmt;
+
+use crate::domain::RelativePath;
+
+// ============================================================================
+// Hook
+// ============================================================================
+
+/// A post-generation step declared by a template.
+///
+/// String fields are rendered with the template syntax before running,
+/// so `{{PROJECT_NAME}}` and friends can be used in arguments and steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hook {
+    /// Run `program` with `args` in the project root
+    Command {
+        /// Program to run, looked up on `PATH`
+        program: String,
+        /// Arguments passed to the program
+        args: Vec<String>,
+    },
+    /// Run a script with the platform shell (`sh -c`, `cmd /C`) in the
+    /// project root
+    Script(String),
+    /// A step scarff performs itself, without starting a process
+    Builtin(BuiltinHook),
+}
+
+/// Steps scarff performs in-process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuiltinHook {
+    /// Set the executable bit on a generated file (`chmod +x`)
+    MakeExecutable(RelativePath),
+    /// Steps for the user to follow, surfaced in the report
+    NextSteps(Vec<String>),
+}
+
+impl Hook {
+    /// Run `program` with `args` in the project root.
+    pub fn command<I, S>(program: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Command {
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Run `script` with the platform shell in the project root.
+    pub fn script(script: impl Into<String>) -> Self {
+        Self::Script(script.into())
+    }
+
+    /// Make the file at `path` executable.
+    pub fn make_executable(path: impl Into<RelativePath>) -> Self {
+        Self::Builtin(BuiltinHook::MakeExecutable(path.into()))
+    }
+
+    /// Show `steps` to the user once the project is ready.
+    pub fn next_steps<I, S>(steps: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Builtin(BuiltinHook::NextSteps(
+            steps.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Whether the hook starts a process, and so only runs when hooks are
+    /// allowed (see [`ScaffoldOptions::allow_hooks`](crate::ScaffoldOptions::allow_hooks)).
+    pub fn starts_process(&self) -> bool {
+        matches!(self, Self::Command { .. } | Self::Script(_))
+    }
+
+    /// Apply `f` to every string the hook carries (program, arguments,
+    /// script, steps), e.g. to render template variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of `f`.
+    pub(crate) fn try_map_strings<E>(
+        &self,
+        mut f: impl FnMut(&str) -> Result<String, E>,
+    ) -> Result<Self, E> {
+        Ok(match self {
+            Self::Command { program, args } => Self::Command {
+                program: f(program)?,
+                args: args.iter().map(|a| f(a)).collect::<Result<_, _>>()?,
+            },
+            Self::Script(script) => Self::Script(f(script)?),
+            Self::Builtin(BuiltinHook::MakeExecutable(path)) => {
+                Self::Builtin(BuiltinHook::MakeExecutable(path.clone()))
+            }
+            Self::Builtin(BuiltinHook::NextSteps(steps)) => Self::Builtin(BuiltinHook::NextSteps(
+                steps.iter().map(|s| f(s)).collect::<Result<_, _>>()?,
+            )),
+        })
+    }
+}
+
+impl fmt::Display for Hook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Command { program, args } => {
+                write!(f, "{program}")?;
+                for arg in args {
+                    write!(f, " {arg}")?;
+                }
+                Ok(())
+            }
+            Self::Script(script) => write!(f, "script: {}", script.lines().next().unwrap_or("")),
+            Self::Builtin(BuiltinHook::MakeExecutable(path)) => write!(f, "chmod +x {path}"),
+            Self::Builtin(BuiltinHook::NextSteps(_)) => write!(f, "next steps"),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_commands_and_scripts_start_processes() {
+        assert!(Hook::command("cargo", ["init"]).starts_process());
+        assert!(Hook::script("echo hi").starts_process());
+        assert!(!Hook::make_executable("run.sh").starts_process());
+        assert!(!Hook::next_steps(["cargo run"]).starts_process());
+    }
+
+    #[test]
+    fn display_describes_the_step() {
+        assert_eq!(
+            Hook::command("git", ["init", "--quiet"]).to_string(),
+            "git init --quiet"
+        );
+        assert_eq!(Hook::script("set -e\nmake").to_string(), "script: set -e");
+        assert_eq!(
+            Hook::make_executable("bin/run.sh").to_string(),
+            "chmod +x bin/run.sh"
+        );
+    }
+
+    #[test]
+    fn try_map_strings_leaves_paths_alone() {
+        let upper = |s: &str| Ok::<_, ()>(s.to_uppercase());
+
+        assert_eq!(
+            Hook::command("cargo", ["new", "demo"]).try_map_strings(upper),
+            Ok(Hook::command("CARGO", ["NEW", "DEMO"]))
+        );
+        assert_eq!(
+            Hook::make_executable("run.sh").try_map_strings(upper),
+            Ok(Hook::make_executable("run.sh"))
+        );
+        assert_eq!(
+            Hook::command("cargo", ["a", "b"]).try_map_strings(|s| if s == "b" {
+                Err(s.to_string())
+            } else {
+                Ok(s.to_string())
+            }),
+            Err("b".to_string())
+        );
+    }
+}
@@ -3,36 +3,67 @@
 //! This module contains the core business types and logic:
 //! - Target: A validated project configuration
 //! - Template: A reusable project recipe
+//! - CustomTree: A user-supplied structure for custom projects
+//! - Hook: A post-scaffold step declared by a template
+//! - Preset: A named Target plus default variables, held in a PresetRegistry
+//! - ProjectName: A project name valid as a package name for its language
+//! - WorkspaceTarget: A workspace root plus member Targets
 //! - [`ProjectStructure`]: The output ready for writing
 //! - Common types: Shared utilities
 
 pub mod common;
+mod custom_tree;
 mod errors;
+mod hook;
+mod preset;
+mod project_name;
 mod project_structure;
 mod render_context;
 mod target;
 mod template;
 pub(crate) mod validator;
+mod workspace;
 
 // Re-export common types
-pub(crate) use common::{Permissions, RelativePath};
+pub use common::Permissions;
+pub(crate) use common::RelativePath;
 pub use errors::DomainError;
 
+// Re-export custom tree types
+pub use custom_tree::{CustomEntry, CustomTree};
+
+// Re-export hooks
+pub use hook::{BuiltinHook, Hook};
+
+// Re-export presets
+pub use preset::{Preset, PresetRegistry};
+
+// Re-export project names
+pub use project_name::ProjectName;
+
 // Re-export project structure
-pub(crate) use project_structure::{DirectoryToCreate, FileToWrite, FsEntry, ProjectStructure};
+pub(crate) use project_structure::{
+    DirectoryToCreate, FileToWrite, FsEntry, ProjectStructure, SymlinkToCreate,
+};
 
 // Re-export render context
 pub(crate) use render_context::RenderContext;
 
 // Re-export target types
+pub(crate) use target::check_feature;
 pub use target::{
-    Architecture, Framework, HasLanguage, Language, NoLanguage, ProjectKind, PythonFramework,
-    RustFramework, Target, TargetBuilder, TypeScriptFramework,
+    Architecture, CiProvider, DependencyManager, Framework, HasLanguage, Language, License,
+    NoLanguage, ProjectKind, PythonFramework, PythonLayout, RustFramework, TARGET_FILE, Target,
+    TargetBuilder, TaskRunner, TestRunner, TypeScriptFramework,
 };
 
 // Re-export template types
+pub use template::Template;
 pub(crate) use template::{
-    ContentTemplateId, DirectorySpec, FileSpec, TargetMatcher, TargetMatcherBuilder, Template,
+    ContentTemplateId, DirectorySpec, FileSpec, SymlinkSpec, TargetMatcher, TargetMatcherBuilder,
     TemplateBuilder, TemplateContent, TemplateEngine, TemplateId, TemplateMetadata, TemplateNode,
     TemplateRecord, TemplateSource, TemplateTree,
 };
+
+// Re-export workspace types
+pub use workspace::{WorkspaceMember, WorkspaceTarget, WorkspaceTargetBuilder};
@@ -0,0 +1,529 @@
+//! Named presets: a [`Target`] plus default template variables.
+//!
+//! Presets let teams give a name to a configuration they scaffold often
+//! (`my-company-api`) instead of repeating language/type/framework flags.
+//! A [`PresetRegistry`] holds them; it starts with the built-in presets and
+//! can be extended at runtime by library users or from a TOML config file.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::domain::{
+    DomainError,
+    target::{
+        Architecture, CiProvider, DependencyManager, Framework, Language, License, ProjectKind,
+        PythonLayout, Target, TaskRunner, TestRunner,
+    },
+};
+
+// ============================================================================
+// Preset
+// ============================================================================
+
+/// A named, reusable scaffold configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use scarff_core_v1::{Preset, Target};
+///
+/// let preset = Preset::new("acme-cli", Target::rust_cli()?)
+///     .with_description("ACME command-line tool")
+///     .with_var("COMPANY", "ACME");
+///
+/// assert_eq!(preset.name(), "acme-cli");
+/// assert_eq!(preset.variables()["COMPANY"], "ACME");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preset {
+    name: String,
+    description: Option<String>,
+    target: Target,
+    variables: BTreeMap<String, String>,
+}
+
+impl Preset {
+    /// Create a preset with no description and no default variables.
+    pub fn new(name: impl Into<String>, target: Target) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            target,
+            variables: BTreeMap::new(),
+        }
+    }
+
+    /// Set a one-line description shown in listings.
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a default template variable (`{{KEY}}` in templates).
+    #[must_use]
+    pub fn with_var(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.variables.insert(key.into(), value.into());
+        self
+    }
+
+    /// Name the preset is registered under.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Optional description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Target this preset scaffolds.
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
+    /// Default template variables.
+    pub fn variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+}
+
+// ============================================================================
+// PresetRegistry
+// ============================================================================
+
+/// Registry of presets, keyed by name.
+///
+/// [`PresetRegistry::new`] starts with the built-in presets; registering a
+/// preset with an existing name replaces it, so user presets can shadow
+/// built-ins.
+///
+/// # Examples
+///
+/// ```rust
+/// use scarff_core_v1::{Preset, PresetRegistry, Target};
+///
+/// let mut registry = PresetRegistry::new();
+/// registry.register(Preset::new("my-cli", Target::rust_cli()?));
+/// registry.load_toml(r#"
+///     [presets.my-company-api]
+///     language = "rust"
+///     type = "backend"
+///     framework = "axum"
+/// "#)?;
+///
+/// assert!(registry.get("my-company-api").is_ok());
+/// assert!(registry.get("rust-cli").is_ok());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetRegistry {
+    presets: BTreeMap<String, Preset>,
+}
+
+impl PresetRegistry {
+    /// Create a registry containing the built-in presets.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        for preset in builtin_presets() {
+            registry.register(preset);
+        }
+        registry
+    }
+
+    /// Create a registry with no presets at all.
+    pub fn empty() -> Self {
+        Self {
+            presets: BTreeMap::new(),
+        }
+    }
+
+    /// Register a preset, returning the one it replaced (if any).
+    pub fn register(&mut self, preset: Preset) -> Option<Preset> {
+        self.presets.insert(preset.name.clone(), preset)
+    }
+
+    /// Look up a preset by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::UnknownPreset`] listing the available names.
+    pub fn get(&self, name: &str) -> Result<&Preset, DomainError> {
+        self.presets
+            .get(name)
+            .ok_or_else(|| DomainError::UnknownPreset {
+                name: name.to_string(),
+                available: self.names().map(str::to_string).collect(),
+            })
+    }
+
+    /// Whether a preset with this name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.presets.contains_key(name)
+    }
+
+    /// Registered preset names, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.keys().map(String::as_str)
+    }
+
+    /// All registered presets, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = &Preset> {
+        self.presets.values()
+    }
+
+    /// Number of registered presets.
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    /// Whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+
+    /// Register every preset defined under `[presets.<name>]` in a TOML
+    /// config document. Returns how many presets were loaded.
+    ///
+    /// ```toml
+    /// [presets.my-company-api]
+    /// description = "ACME HTTP service"
+    /// language = "rust"
+    /// type = "backend"        # optional, inferred from language
+    /// framework = "axum"      # optional
+    /// architecture = "clean"  # optional
+    ///
+    /// [presets.my-company-api.variables]
+    /// COMPANY = "ACME"
+    /// ```
+    ///
+    /// Unknown top-level tables are ignored so the same file can hold other
+    /// settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidPreset`] if the document is not valid
+    /// TOML or a preset does not describe a valid target. Nothing is
+    /// registered in that case.
+    pub fn load_toml(&mut self, source: &str) -> Result<usize, DomainError> {
+        let config: ConfigFile =
+            toml::from_str(source).map_err(|e| DomainError::InvalidPreset {
+                name: "<config>".to_string(),
+                reason: e.message().to_string(),
+            })?;
+
+        let presets = config
+            .presets
+            .into_iter()
+            .map(|(name, raw)| raw.into_preset(name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let count = presets.len();
+        for preset in presets {
+            self.register(preset);
+        }
+        Ok(count)
+    }
+}
+
+impl Default for PresetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The presets shipped with scarff, mirroring the `Target` preset methods.
+fn builtin_presets() -> Vec<Preset> {
+    let builtins = [
+        (
+            "rust-cli",
+            "Rust command-line application",
+            Target::rust_cli(),
+        ),
+        (
+            "rust-axum",
+            "Rust web backend with Axum",
+            Target::rust_backend_axum(),
+        ),
+        (
+            "python-fastapi",
+            "Python web backend with FastAPI",
+            Target::python_backend_fastapi(),
+        ),
+        (
+            "typescript-react",
+            "TypeScript frontend with React (Vite)",
+            Target::typescript_frontend_vite_react(),
+        ),
+        (
+            "typescript-nextjs",
+            "TypeScript frontend with Next.js (App Router)",
+            Target::typescript_frontend_nextjs(),
+        ),
+        (
+            "typescript-sveltekit",
+            "TypeScript frontend with SvelteKit",
+            Target::typescript_frontend_sveltekit(),
+        ),
+    ];
+
+    builtins
+        .into_iter()
+        .map(|(name, description, target)| {
+            let target = target.expect("built-in preset targets are known-good");
+            Preset::new(name, target).with_description(description)
+        })
+        .collect()
+}
+
+// ============================================================================
+// Config file format
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    presets: BTreeMap<String, RawPreset>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPreset {
+    description: Option<String>,
+    language: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    framework: Option<String>,
+    architecture: Option<String>,
+    dependency_manager: Option<String>,
+    python_layout: Option<String>,
+    test_runner: Option<String>,
+    task_runner: Option<String>,
+    license: Option<String>,
+    ci: Option<String>,
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+}
+
+impl RawPreset {
+    fn into_preset(self, name: String) -> Result<Preset, DomainError> {
+        let invalid = |reason: String| DomainError::InvalidPreset {
+            name: name.clone(),
+            reason,
+        };
+
+        let language = Language::parse(&self.language)
+            .ok_or_else(|| invalid(format!("unknown language '{}'", self.language)))?;
+
+        let mut builder = Target::builder().language(language);
+
+        if let Some(kind) = &self.kind {
+            let kind = ProjectKind::parse(kind)
+                .ok_or_else(|| invalid(format!("unknown project type '{kind}'")))?;
+            builder = builder.kind(kind).map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(framework) = &self.framework {
+            let framework = Framework::parse(framework)
+                .ok_or_else(|| invalid(format!("unknown framework '{framework}'")))?;
+            builder = builder
+                .framework(framework)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(architecture) = &self.architecture {
+            let architecture = Architecture::parse(architecture)
+                .ok_or_else(|| invalid(format!("unknown architecture '{architecture}'")))?;
+            builder = builder
+                .architecture(architecture)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(manager) = &self.dependency_manager {
+            let manager = DependencyManager::parse(manager)
+                .ok_or_else(|| invalid(format!("unknown dependency manager '{manager}'")))?;
+            builder = builder
+                .dependency_manager(manager)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(layout) = &self.python_layout {
+            let layout = PythonLayout::parse(layout)
+                .ok_or_else(|| invalid(format!("unknown Python layout '{layout}'")))?;
+            builder = builder
+                .python_layout(layout)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(runner) = &self.test_runner {
+            let runner = TestRunner::parse(runner)
+                .ok_or_else(|| invalid(format!("unknown test runner '{runner}'")))?;
+            builder = builder
+                .test_runner(runner)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(runner) = &self.task_runner {
+            let runner = TaskRunner::parse(runner)
+                .ok_or_else(|| invalid(format!("unknown task runner '{runner}'")))?;
+            builder = builder
+                .task_runner(runner)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(license) = &self.license {
+            let license = License::parse(license)
+                .ok_or_else(|| invalid(format!("unknown license '{license}'")))?;
+            builder = builder.license(license);
+        }
+        if let Some(ci) = &self.ci {
+            let ci = CiProvider::parse(ci)
+                .ok_or_else(|| invalid(format!("unknown CI provider '{ci}'")))?;
+            builder = builder.ci(ci);
+        }
+
+        let target = builder.build().map_err(|e| invalid(e.to_string()))?;
+
+        let mut preset = Preset::new(name, target);
+        preset.description = self.description;
+        preset.variables = self.variables;
+        Ok(preset)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RustFramework;
+
+    #[test]
+    fn new_registry_has_builtins() {
+        let registry = PresetRegistry::new();
+
+        assert!(registry.contains("rust-cli"));
+        assert!(registry.contains("typescript-react"));
+        assert!(registry.contains("typescript-sveltekit"));
+        assert_eq!(
+            registry.get("rust-axum").unwrap().target(),
+            &Target::rust_backend_axum().unwrap()
+        );
+        assert!(PresetRegistry::empty().is_empty());
+    }
+
+    #[test]
+    fn register_replaces_by_name() {
+        let mut registry = PresetRegistry::new();
+        let custom =
+            Preset::new("rust-cli", Target::rust_cli().unwrap()).with_var("COMPANY", "ACME");
+
+        let replaced = registry.register(custom.clone());
+
+        assert!(replaced.is_some());
+        assert_eq!(registry.get("rust-cli").unwrap(), &custom);
+    }
+
+    #[test]
+    fn unknown_preset_lists_available() {
+        let registry = PresetRegistry::new();
+
+        let err = registry.get("nope").unwrap_err();
+
+        assert!(matches!(
+            &err,
+            DomainError::UnknownPreset { available, .. } if available.contains(&"rust-cli".to_string())
+        ));
+    }
+
+    #[test]
+    fn load_toml_registers_presets() {
+        let mut registry = PresetRegistry::empty();
+
+        let loaded = registry
+            .load_toml(
+                r#"
+                [other]
+                ignored = true
+
+                [presets.my-company-api]
+                description = "ACME API"
+                language = "rust"
+                type = "backend"
+                framework = "axum"
+
+                [presets.my-company-api.variables]
+                COMPANY = "ACME"
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(loaded, 1);
+        let preset = registry.get("my-company-api").unwrap();
+        assert_eq!(preset.description(), Some("ACME API"));
+        assert_eq!(
+            preset.target().framework(),
+            Some(Framework::Rust(RustFramework::Axum))
+        );
+        assert_eq!(preset.variables()["COMPANY"], "ACME");
+    }
+
+    #[test]
+    fn load_toml_rejects_invalid_target() {
+        let mut registry = PresetRegistry::empty();
+
+        let err = registry
+            .load_toml(
+                r#"
+                [presets.ok]
+                language = "rust"
+
+                [presets.broken]
+                language = "rust"
+                framework = "django"
+                "#,
+            )
+            .unwrap_err();
+
+        assert!(matches!(err, DomainError::InvalidPreset { ref name, .. } if name == "broken"));
+        assert!(registry.is_empty(), "nothing is registered on error");
+    }
+
+    #[test]
+    fn load_toml_selects_dependency_manager() {
+        let mut registry = PresetRegistry::empty();
+
+        registry
+            .load_toml(
+                r#"
+                [presets.api-uv]
+                language = "python"
+                type = "backend"
+                dependency_manager = "uv"
+
+                [presets.cli-uv]
+                language = "rust"
+                dependency_manager = "uv"
+                "#,
+            )
+            .unwrap_err();
+        registry
+            .load_toml("[presets.api-uv]\nlanguage = \"python\"\ndependency_manager = \"uv\"\n")
+            .unwrap();
+
+        assert_eq!(
+            registry
+                .get("api-uv")
+                .unwrap()
+                .target()
+                .dependency_manager(),
+            Some(DependencyManager::Uv)
+        );
+    }
+
+    #[test]
+    fn load_toml_rejects_unknown_keys() {
+        let mut registry = PresetRegistry::empty();
+
+        let result = registry.load_toml("[presets.x]\nlanguage = \"rust\"\nlang = \"py\"\n");
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,465 @@
+//! Project names, validated against the language's package rules.
+//!
+//! A project's name ends up in its manifest, so it has to be a valid
+//! package name for the language:
+//! - Rust: a crate name (ASCII letters, digits, `-` and `_`, starting with
+//!   a letter, at most 64 characters, not a keyword or reserved crate)
+//! - TypeScript: an npm package name (lowercase letters, digits, `-`,
+//!   `.`, `_` and `~`, not starting with `_`, at most 214 characters, not a
+//!   Node.js built-in module)
+//! - Python: a distribution name (letters, digits, `.`, `-` and `_`,
+//!   starting and ending with a letter or digit) whose `snake_case` module
+//!   name is an importable identifier
+//!
+//! Names are never rewritten behind the caller's back: an invalid one is
+//! refused with [`DomainError::InvalidProjectName`], which carries a
+//! normalized variant when one can be derived.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scarff_core_v1::{DomainError, Language, ProjectName};
+//!
+//! let name = ProjectName::new("my-tool", Language::Rust)?;
+//! assert_eq!(name.as_str(), "my-tool");
+//!
+//! let err = ProjectName::new("My App", Language::TypeScript).unwrap_err();
+//! assert!(matches!(
+//!     err,
+//!     DomainError::InvalidProjectName { suggestion: Some(ref s), .. } if s == "my-app"
+//! ));
+//! assert_eq!(ProjectName::normalized("My App", Language::TypeScript)?.as_str(), "my-app");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fmt;
+
+use crate::domain::{
+    DomainError, Language,
+    render_context::{to_kebab_case, to_snake_case},
+};
+
+/// Longest crate name crates.io accepts.
+const MAX_CRATE_NAME: usize = 64;
+
+/// Longest package name npm accepts.
+const MAX_NPM_NAME: usize = 214;
+
+/// Rust keywords (strict and reserved) and the crates of the standard
+/// distribution, which a package can't be named after.
+const RUST_RESERVED: &[&str] = &[
+    "abstract",
+    "alloc",
+    "as",
+    "async",
+    "await",
+    "become",
+    "box",
+    "break",
+    "const",
+    "continue",
+    "core",
+    "crate",
+    "do",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "gen",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "proc_macro",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "static",
+    "std",
+    "struct",
+    "super",
+    "test",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "while",
+    "yield",
+];
+
+/// Names npm refuses for new packages: Node.js built-in modules and a
+/// few reserved names.
+const NPM_RESERVED: &[&str] = &[
+    "assert",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "crypto",
+    "dns",
+    "events",
+    "favicon.ico",
+    "fs",
+    "http",
+    "https",
+    "net",
+    "node_modules",
+    "os",
+    "path",
+    "process",
+    "querystring",
+    "readline",
+    "stream",
+    "timers",
+    "tls",
+    "url",
+    "util",
+    "vm",
+    "worker_threads",
+    "zlib",
+];
+
+/// Python keywords, which can't be imported as module names.
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "false", "finally", "for", "from", "global", "if", "import", "in", "is",
+    "lambda", "none", "nonlocal", "not", "or", "pass", "raise", "return", "true", "try", "while",
+    "with", "yield",
+];
+
+// ============================================================================
+// ProjectName
+// ============================================================================
+
+/// A project name that is a valid package name for its language.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProjectName {
+    name: String,
+    language: Language,
+}
+
+impl ProjectName {
+    /// Validate `name` as a package name for `language`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidProjectName`] with the first rule the
+    /// name breaks and, when one can be derived, a normalized suggestion.
+    pub fn new(name: impl Into<String>, language: Language) -> Result<Self, DomainError> {
+        let name = name.into();
+        match violation(&name, language) {
+            None => Ok(Self { name, language }),
+            Some(reason) => Err(DomainError::InvalidProjectName {
+                suggestion: suggest(&name, language),
+                name,
+                language: language.to_string(),
+                reason,
+            }),
+        }
+    }
+
+    /// `name` if it's valid, otherwise its normalized variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidProjectName`] when no valid variant
+    /// can be derived (e.g. a name without any letter or digit).
+    pub fn normalized(name: &str, language: Language) -> Result<Self, DomainError> {
+        Self::new(name, language).or_else(|err| match err {
+            DomainError::InvalidProjectName {
+                suggestion: Some(suggestion),
+                ..
+            } => Self::new(suggestion, language),
+            err => Err(err),
+        })
+    }
+
+    /// The name.
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    /// Language the name was validated for.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Take the name out.
+    pub fn into_string(self) -> String {
+        self.name
+    }
+}
+
+impl fmt::Display for ProjectName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl AsRef<str> for ProjectName {
+    fn as_ref(&self) -> &str {
+        &self.name
+    }
+}
+
+// ============================================================================
+// Rules
+// ============================================================================
+
+/// The first rule `name` breaks for `language`, if any.
+fn violation(name: &str, language: Language) -> Option<String> {
+    if name.is_empty() {
+        return Some("the name is empty".to_string());
+    }
+    if name.contains(['/', '\\']) {
+        return Some("it contains a path separator".to_string());
+    }
+    if name.starts_with('.') {
+        return Some("it starts with '.'".to_string());
+    }
+
+    match language {
+        Language::Rust => crate_name_violation(name),
+        Language::TypeScript => npm_name_violation(name),
+        Language::Python => python_name_violation(name),
+    }
+}
+
+fn crate_name_violation(name: &str) -> Option<String> {
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_')))
+    {
+        Some(format!(
+            "'{c}' is not allowed in a crate name (use letters, digits, '-' and '_')"
+        ))
+    } else if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        Some("crate names start with a letter".to_string())
+    } else if name.len() > MAX_CRATE_NAME {
+        Some(format!(
+            "crate names are at most {MAX_CRATE_NAME} characters"
+        ))
+    } else if RUST_RESERVED.contains(&name.replace('-', "_").as_str()) {
+        Some(format!("'{name}' is a Rust keyword or reserved crate name"))
+    } else {
+        None
+    }
+}
+
+fn npm_name_violation(name: &str) -> Option<String> {
+    if name.chars().any(char::is_uppercase) {
+        Some("npm package names can't contain uppercase letters".to_string())
+    } else if let Some(c) = name.chars().find(|c| {
+        !(c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.' | '_' | '~'))
+    }) {
+        Some(format!(
+            "'{c}' is not allowed in an npm package name (use lowercase letters, digits, '-', '.', '_' and '~')"
+        ))
+    } else if name.starts_with('_') {
+        Some("npm package names can't start with '_'".to_string())
+    } else if name.len() > MAX_NPM_NAME {
+        Some(format!(
+            "npm package names are at most {MAX_NPM_NAME} characters"
+        ))
+    } else if NPM_RESERVED.contains(&name) {
+        Some(format!(
+            "'{name}' is a Node.js built-in module or reserved name"
+        ))
+    } else {
+        None
+    }
+}
+
+fn python_name_violation(name: &str) -> Option<String> {
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')))
+    {
+        return Some(format!(
+            "'{c}' is not allowed in a Python project name (use letters, digits, '.', '-' and '_')"
+        ));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        || !name.ends_with(|c: char| c.is_ascii_alphanumeric())
+    {
+        return Some("Python project names start and end with a letter or digit".to_string());
+    }
+
+    // Templates import the project as its `snake_case` module
+    let module = to_snake_case(name);
+    if module.contains('.') || module.starts_with(|c: char| c.is_ascii_digit()) {
+        Some(format!(
+            "its module name '{module}' is not a Python identifier"
+        ))
+    } else if PYTHON_KEYWORDS.contains(&module.as_str()) {
+        Some(format!("its module name '{module}' is a Python keyword"))
+    } else {
+        None
+    }
+}
+
+/// A valid name for `language` close to `name`, if it differs from it.
+///
+/// The name is lowercased into `-`-separated words of ASCII letters and
+/// digits; names that still break a rule get an `app-` prefix (leading
+/// digit) or `-app` suffix (reserved word).
+fn suggest(name: &str, language: Language) -> Option<String> {
+    let mut slug = to_kebab_case(name)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        return None;
+    }
+    if language != Language::TypeScript && slug.starts_with(|c: char| c.is_ascii_digit()) {
+        slug.insert_str(0, "app-");
+    }
+    let max = match language {
+        Language::Rust => MAX_CRATE_NAME,
+        Language::TypeScript => MAX_NPM_NAME,
+        Language::Python => usize::MAX,
+    };
+    slug.truncate(max);
+    let slug = slug.trim_end_matches('-').to_string();
+
+    [format!("{slug}-app"), slug]
+        .into_iter()
+        .rev()
+        .find(|candidate| violation(candidate, language).is_none())
+        .filter(|candidate| candidate != name)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refusal(name: &str, language: Language) -> (String, Option<String>) {
+        match ProjectName::new(name, language).unwrap_err() {
+            DomainError::InvalidProjectName {
+                reason, suggestion, ..
+            } => (reason, suggestion),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accepts_package_names_of_each_language() {
+        for (name, language) in [
+            ("my-tool", Language::Rust),
+            ("my_tool2", Language::Rust),
+            ("MyTool", Language::Rust),
+            ("my-app.web", Language::TypeScript),
+            ("2048", Language::TypeScript),
+            ("My-Service", Language::Python),
+        ] {
+            assert_eq!(ProjectName::new(name, language).unwrap().as_str(), name);
+        }
+    }
+
+    #[test]
+    fn rust_names_follow_crate_rules() {
+        assert_eq!(
+            refusal("my tool", Language::Rust),
+            (
+                "' ' is not allowed in a crate name (use letters, digits, '-' and '_')".to_string(),
+                Some("my-tool".to_string())
+            )
+        );
+        assert_eq!(
+            refusal("2fast", Language::Rust).1.as_deref(),
+            Some("app-2fast")
+        );
+        assert_eq!(
+            refusal("proc-macro", Language::Rust),
+            (
+                "'proc-macro' is a Rust keyword or reserved crate name".to_string(),
+                Some("proc-macro-app".to_string())
+            )
+        );
+        assert_eq!(
+            refusal(&"a".repeat(65), Language::Rust).1.map(|s| s.len()),
+            Some(MAX_CRATE_NAME)
+        );
+    }
+
+    #[test]
+    fn typescript_names_follow_npm_rules() {
+        assert_eq!(
+            refusal("MyApp", Language::TypeScript),
+            (
+                "npm package names can't contain uppercase letters".to_string(),
+                Some("my-app".to_string())
+            )
+        );
+        assert_eq!(
+            refusal("_internal", Language::TypeScript).1.as_deref(),
+            Some("internal")
+        );
+        assert_eq!(
+            refusal("http", Language::TypeScript).1.as_deref(),
+            Some("http-app")
+        );
+    }
+
+    #[test]
+    fn python_names_need_an_importable_module() {
+        assert_eq!(
+            refusal("class", Language::Python),
+            (
+                "its module name 'class' is a Python keyword".to_string(),
+                Some("class-app".to_string())
+            )
+        );
+        assert_eq!(
+            refusal("my.pkg", Language::Python).1.as_deref(),
+            Some("my-pkg")
+        );
+        assert_eq!(
+            refusal("3d-tools", Language::Python).1.as_deref(),
+            Some("app-3d-tools")
+        );
+    }
+
+    #[test]
+    fn common_rules_and_names_without_a_variant() {
+        assert_eq!(
+            refusal("../escape", Language::Rust).0,
+            "it contains a path separator"
+        );
+        assert_eq!(refusal("---", Language::Python).1, None);
+        assert!(ProjectName::normalized("!!", Language::Rust).is_err());
+        assert_eq!(
+            ProjectName::normalized("Data Pipeline", Language::Python)
+                .unwrap()
+                .as_str(),
+            "data-pipeline"
+        );
+    }
+}
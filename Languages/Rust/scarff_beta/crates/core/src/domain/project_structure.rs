@@ -1,8 +1,8 @@
 //! ProjectStructure - the output of template rendering.
 
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
-use crate::domain::{common::Permissions, validator};
+use crate::domain::{Hook, common::Permissions, validator};
 
 use super::DomainError;
 
@@ -18,6 +18,8 @@ use super::DomainError;
 pub(crate) struct ProjectStructure {
     pub(crate) root: PathBuf,
     pub(crate) entries: Vec<FsEntry>,
+    /// Rendered post-scaffold hooks, run once the entries are written
+    pub(crate) hooks: Vec<Hook>,
 }
 
 impl ProjectStructure {
@@ -26,6 +28,7 @@ impl ProjectStructure {
         Self {
             root: root.into(),
             entries: Vec::new(),
+            hooks: Vec::new(),
         }
     }
 
@@ -51,6 +54,14 @@ impl ProjectStructure {
         }));
     }
 
+    /// Add a symlink at `path` pointing to `target` (mutable).
+    pub(crate) fn add_symlink(&mut self, path: impl Into<PathBuf>, target: impl Into<PathBuf>) {
+        self.entries.push(FsEntry::Symlink(SymlinkToCreate {
+            path: path.into(),
+            target: target.into(),
+        }));
+    }
+
     /// Add a file to the structure (builder style).
     pub(crate) fn with_file(
         mut self,
@@ -72,6 +83,42 @@ impl ProjectStructure {
         self
     }
 
+    /// Add a symlink to the structure (builder style).
+    pub(crate) fn with_symlink(
+        mut self,
+        path: impl Into<PathBuf>,
+        target: impl Into<PathBuf>,
+    ) -> Self {
+        self.add_symlink(path, target);
+        self
+    }
+
+    /// Add the entries of `other` under `prefix` (relative to this root),
+    /// along with a directory for `prefix` itself.
+    ///
+    /// Used to put workspace members inside the workspace; `other`'s hooks
+    /// are left to the caller, as they run in `other`'s root.
+    pub(crate) fn nest(&mut self, prefix: impl Into<PathBuf>, other: ProjectStructure) {
+        let prefix = prefix.into();
+        self.add_directory(prefix.clone(), Permissions::read_write());
+        self.entries
+            .extend(other.entries.into_iter().map(|entry| match entry {
+                FsEntry::File(file) => FsEntry::File(FileToWrite {
+                    path: prefix.join(file.path),
+                    ..file
+                }),
+                FsEntry::Directory(dir) => FsEntry::Directory(DirectoryToCreate {
+                    path: prefix.join(dir.path),
+                    ..dir
+                }),
+                // Targets are relative to the link, so they move with it
+                FsEntry::Symlink(link) => FsEntry::Symlink(SymlinkToCreate {
+                    path: prefix.join(link.path),
+                    ..link
+                }),
+            }));
+    }
+
     /// Validate the structure before writing.
     ///
     /// Checks:
@@ -101,6 +148,14 @@ impl ProjectStructure {
         })
     }
 
+    /// Get all symlinks in this structure.
+    pub(crate) fn symlinks(&self) -> impl Iterator<Item = &SymlinkToCreate> {
+        self.entries.iter().filter_map(|e| match e {
+            FsEntry::Symlink(l) => Some(l),
+            _ => None,
+        })
+    }
+
     /// Count total entries.
     pub(crate) fn entry_count(&self) -> usize {
         self.entries.len()
@@ -121,11 +176,23 @@ impl ProjectStructure {
 // FsEntry
 // ============================================================================
 
-/// A filesystem entry (file or directory).
+/// A filesystem entry (file, directory or symlink).
 #[derive(Debug, Clone)]
 pub(crate) enum FsEntry {
     File(FileToWrite),
     Directory(DirectoryToCreate),
+    Symlink(SymlinkToCreate),
+}
+
+impl FsEntry {
+    /// Path of the entry, relative to the project root.
+    pub(crate) fn path(&self) -> &Path {
+        match self {
+            Self::File(f) => &f.path,
+            Self::Directory(d) => &d.path,
+            Self::Symlink(l) => &l.path,
+        }
+    }
 }
 
 // ============================================================================
@@ -180,6 +247,48 @@ impl DirectoryToCreate {
     }
 }
 
+// ============================================================================
+// SymlinkToCreate
+// ============================================================================
+
+/// A symbolic link to be created on disk.
+#[derive(Debug, Clone)]
+pub(crate) struct SymlinkToCreate {
+    pub path: PathBuf,
+    /// Where the link points, relative to the link's directory
+    pub target: PathBuf,
+}
+
+impl SymlinkToCreate {
+    pub(crate) fn new(path: impl Into<PathBuf>, target: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            target: target.into(),
+        }
+    }
+
+    /// The target resolved against the link's directory, relative to the
+    /// project root (without touching the filesystem).
+    ///
+    /// `None` if the target is absolute or climbs out of the root.
+    pub(crate) fn resolved_target(&self) -> Option<PathBuf> {
+        let mut resolved = self.path.parent().map(PathBuf::from).unwrap_or_default();
+        for component in self.target.components() {
+            match component {
+                Component::Normal(segment) => resolved.push(segment),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !resolved.pop() {
+                        return None;
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => return None,
+            }
+        }
+        Some(resolved)
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -324,4 +433,68 @@ mod tests {
         assert_eq!(structure.file_count(), 2);
         assert_eq!(structure.directory_count(), 1);
     }
+
+    #[test]
+    fn nest_prefixes_entries_and_adds_the_directory() {
+        let member = ProjectStructure::new("/tmp/ws/crates/cli")
+            .with_directory("src", Permissions::read_write())
+            .with_file(
+                "src/main.rs",
+                "fn main() {}".to_string(),
+                Permissions::read_write(),
+            );
+        let mut root = ProjectStructure::new("/tmp/ws").with_file(
+            "Cargo.toml",
+            "[workspace]".to_string(),
+            Permissions::read_write(),
+        );
+
+        root.nest("crates/cli", member);
+
+        let paths: Vec<_> = root
+            .entries
+            .iter()
+            .map(|e| e.path().to_path_buf())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "Cargo.toml",
+                "crates/cli",
+                "crates/cli/src",
+                "crates/cli/src/main.rs"
+            ]
+            .map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn symlink_targets_resolve_from_the_link_directory() {
+        let link = SymlinkToCreate::new("bin/run", "../scripts/run.sh");
+        assert_eq!(
+            link.resolved_target(),
+            Some(PathBuf::from("scripts/run.sh"))
+        );
+        assert_eq!(
+            SymlinkToCreate::new("run", "../run.sh").resolved_target(),
+            None
+        );
+        assert_eq!(
+            SymlinkToCreate::new("run", "/bin/sh").resolved_target(),
+            None
+        );
+
+        let mut root = ProjectStructure::new("/tmp/ws");
+        root.nest(
+            "crates/cli",
+            ProjectStructure::new("/tmp/ws/crates/cli").with_symlink("bin/run", "../run.sh"),
+        );
+        let link = root.symlinks().next().unwrap();
+        assert_eq!(link.path, PathBuf::from("crates/cli/bin/run"));
+        assert_eq!(link.target, PathBuf::from("../run.sh"));
+        assert_eq!(
+            link.resolved_target(),
+            Some(PathBuf::from("crates/cli/run.sh"))
+        );
+    }
 }
@@ -2,12 +2,16 @@
 
 use std::collections::HashMap;
 
+use crate::domain::{Target, TemplateMetadata};
+
 /// Context containing variables for template rendering.
 ///
-/// Provides standard variables (project name, year, etc.) plus custom variables.
+/// Provides standard variables (project name, year, etc.) plus custom variables
+/// and lists for `{{#each}}` loops.
 #[derive(Debug, Clone)]
 pub struct RenderContext {
     variables: HashMap<String, String>,
+    lists: HashMap<String, Vec<String>>,
 }
 
 impl RenderContext {
@@ -39,7 +43,10 @@ impl RenderContext {
         );
         variables.insert("YEAR".to_string(), current_year());
 
-        Self { variables }
+        Self {
+            variables,
+            lists: HashMap::new(),
+        }
     }
 
     /// Add a custom variable.
@@ -50,6 +57,73 @@ impl RenderContext {
         self
     }
 
+    /// Add a list variable, for `{{#each}}` loops.
+    ///
+    /// Builder-style method for chaining.
+    pub fn with_list<I>(mut self, key: impl Into<String>, items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.lists
+            .insert(key.into(), items.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add the chosen target options.
+    ///
+    /// Sets `LANGUAGE`, `KIND`, `FRAMEWORK` (`none` without one),
+    /// `ARCHITECTURE`, `DEPENDENCY_MANAGER` and `PYTHON_LAYOUT` (`none`
+    /// outside Python),
+    /// `TEST_RUNNER` (`none` outside TypeScript), `TASK_RUNNER` (`none`
+    /// outside Rust), `LICENSE` (the SPDX id,
+    /// `none` without one), `CI_PROVIDER` (`none` without one), the
+    /// `FEATURES` list and
+    /// a `FEATURE_<NAME>` flag per enabled feature (`ci-github` sets
+    /// `FEATURE_CI_GITHUB`), for `{{#if}}` blocks.
+    pub fn with_target(self, target: &Target) -> Self {
+        let mut context = self.with_list("FEATURES", target.features().iter().cloned());
+        for feature in target.features() {
+            context.set_var(feature_flag(feature), "true");
+        }
+
+        context
+            .with_var("LANGUAGE", target.language().as_str())
+            .with_var("KIND", target.kind().as_str())
+            .with_var(
+                "FRAMEWORK",
+                target.framework().map_or("none", |f| f.as_str()),
+            )
+            .with_var("ARCHITECTURE", target.architecture().as_str())
+            .with_var(
+                "DEPENDENCY_MANAGER",
+                target.dependency_manager().map_or("none", |m| m.as_str()),
+            )
+            .with_var(
+                "PYTHON_LAYOUT",
+                target.python_layout().map_or("none", |l| l.as_str()),
+            )
+            .with_var(
+                "TEST_RUNNER",
+                target.test_runner().map_or("none", |r| r.as_str()),
+            )
+            .with_var(
+                "TASK_RUNNER",
+                target.task_runner().map_or("none", |r| r.as_str()),
+            )
+            .with_var("LICENSE", target.license().map_or("none", |l| l.as_str()))
+            .with_var("CI_PROVIDER", target.ci().map_or("none", |c| c.as_str()))
+    }
+
+    /// Add metadata of the template being rendered.
+    ///
+    /// Sets `TEMPLATE_NAME`, `TEMPLATE_VERSION` and `TEMPLATE_DESCRIPTION`.
+    pub fn with_template(self, metadata: &TemplateMetadata) -> Self {
+        self.with_var("TEMPLATE_NAME", metadata.name.as_str())
+            .with_var("TEMPLATE_VERSION", metadata.version.as_str())
+            .with_var("TEMPLATE_DESCRIPTION", metadata.description.as_str())
+    }
+
     /// Set a custom variable (mutable method).
     pub fn set_var(&mut self, key: impl Into<String>, value: impl Into<String>) {
         self.variables.insert(key.into(), value.into());
@@ -60,6 +134,22 @@ impl RenderContext {
         self.variables.get(key).map(|s| s.as_str())
     }
 
+    /// Check whether a feature is enabled (see [`RenderContext::with_target`]).
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.list("FEATURES")
+            .is_some_and(|features| features.iter().any(|f| f == feature))
+    }
+
+    /// Get a list variable.
+    pub fn list(&self, key: &str) -> Option<&[String]> {
+        self.lists.get(key).map(Vec::as_slice)
+    }
+
+    /// Get all list variables.
+    pub fn lists(&self) -> &HashMap<String, Vec<String>> {
+        &self.lists
+    }
+
     /// Check if a variable exists.
     pub fn has(&self, key: &str) -> bool {
         self.variables.contains_key(key)
@@ -72,7 +162,8 @@ impl RenderContext {
 
     /// Render a template string by replacing {{VARIABLE}} placeholders.
     ///
-    /// Simple implementation for MVP. Can be replaced with a proper template engine later.
+    /// Plain replacement only; the renderer uses the full template syntax
+    /// (conditionals, loops, escapes) instead.
     pub fn render(&self, template: &str) -> String {
         let mut result = template.to_string();
 
@@ -89,12 +180,17 @@ impl RenderContext {
 // Helper Functions
 // ============================================================================
 
+/// `FEATURE_CI_GITHUB` for `ci-github`.
+fn feature_flag(feature: &str) -> String {
+    format!("FEATURE_{}", feature.to_uppercase().replace('-', "_"))
+}
+
 /// Convert a string to snake_case.
 ///
 /// Rules:
 /// - Replace hyphens and spaces with underscores
 /// - Convert to lowercase
-fn to_snake_case(s: &str) -> String {
+pub(super) fn to_snake_case(s: &str) -> String {
     split_words(s).join("_")
 }
 
@@ -103,7 +199,7 @@ fn to_snake_case(s: &str) -> String {
 /// Rules:
 /// - Replace underscores and spaces with hyphens
 /// - Convert to lowercase
-fn to_kebab_case(s: &str) -> String {
+pub(super) fn to_kebab_case(s: &str) -> String {
     split_words(s).join("-")
 }
 
@@ -228,6 +324,30 @@ mod tests {
         assert!(!ctx.has("NONEXISTENT"));
     }
 
+    #[test]
+    fn render_context_target_and_template_variables() {
+        let target = Target::rust_backend_axum().unwrap();
+        let metadata = TemplateMetadata::new("Rust Web Backend (Axum)")
+            .version("1.0.0")
+            .description("A Rust web API");
+
+        let ctx = RenderContext::new("api")
+            .with_target(&target)
+            .with_template(&metadata);
+
+        assert_eq!(ctx.get("LANGUAGE"), Some("rust"));
+        assert_eq!(ctx.get("KIND"), Some("web-backend"));
+        assert_eq!(ctx.get("FRAMEWORK"), Some("axum"));
+        assert_eq!(ctx.get("ARCHITECTURE"), Some("layered"));
+        assert_eq!(ctx.get("PYTHON_LAYOUT"), Some("none"));
+        assert_eq!(ctx.get("TASK_RUNNER"), Some("none"));
+        assert_eq!(ctx.get("LICENSE"), Some("none"));
+        assert_eq!(ctx.get("CI_PROVIDER"), Some("none"));
+        assert_eq!(ctx.get("TEMPLATE_NAME"), Some("Rust Web Backend (Axum)"));
+        assert_eq!(ctx.get("TEMPLATE_VERSION"), Some("1.0.0"));
+        assert_eq!(ctx.get("TEMPLATE_DESCRIPTION"), Some("A Rust web API"));
+    }
+
     #[test]
     fn render_simple_template() {
         let ctx = RenderContext::new("my-project");
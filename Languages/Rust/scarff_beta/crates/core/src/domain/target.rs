@@ -7,7 +7,7 @@
 //! # Examples
 //!
 //! ```rust
-//! use scarff_core::{Target, Language, ProjectKind};
+//! use scarff_core_v1::{Architecture, Framework, Language, ProjectKind, RustFramework, Target};
 //!
 //! // Minimal target - other fields inferred
 //! let target = Target::builder()
@@ -17,15 +17,55 @@
 //! // Fully specified target
 //! let target = Target::builder()
 //!     .language(Language::Rust)
-//!     .kind(ProjectKind::WebBackend)
-//!     .framework(Framework::Rust(RustFramework::Axum))
-//!     .architecture(Architecture::Layered)
+//!     .kind(ProjectKind::WebBackend)?
+//!     .framework(Framework::Rust(RustFramework::Axum))?
+//!     .architecture(Architecture::Layered)?
 //!     .build()?;
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! # Features
+//!
+//! A target can switch on optional parts of its template (a `Dockerfile`,
+//! CI workflows, pre-commit config, ...) by feature name. Templates gate
+//! files and directories on a feature, and read the enabled ones from the
+//! `FEATURES` list and `FEATURE_<NAME>` flags when rendering:
+//!
+//! ```rust
+//! use scarff_core_v1::Target;
+//!
+//! let target = Target::builder()
+//!     .language(scarff_core_v1::Language::Rust)
+//!     .feature("docker")?
+//!     .feature("ci-github")?
+//!     .build()?;
+//!
+//! assert_eq!(target.features(), ["ci-github", "docker"]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Saving targets
+//!
+//! Targets serialize (via serde) to their fields' string forms, so one can
+//! be saved to a [`TARGET_FILE`] and scaffolded again later, or sent over
+//! the wire by a service embedding the engine:
+//!
+//! ```toml
+//! language = "python"
+//! kind = "web-backend"
+//! framework = "fastapi"
+//! architecture = "layered"
+//! dependency_manager = "pip"
+//! features = ["docker"]
+//! ```
+//!
+//! Deserializing runs the builder, so a loaded target is validated (and
+//! missing optional fields inferred) exactly like a built one.
 
 use std::{fmt, marker::PhantomData};
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
 use crate::DomainError;
 
 // ============================================================================
@@ -48,18 +88,19 @@ use crate::DomainError;
 /// # Examples
 ///
 /// ```rust
-/// use scarff_core::{Target, Language, ProjectKind};
+/// use scarff_core_v1::{Target, Language, ProjectKind};
 ///
 /// let target = Target::builder()
 ///     .language(Language::Rust)
-///     .kind(ProjectKind::Cli)
+///     .kind(ProjectKind::Cli)?
 ///     .build()?;
 ///
-/// assert_eq!(target.language(), Language::Rust);
-/// assert_eq!(target.kind(), ProjectKind::Cli);
+/// assert_eq!(target.language, Language::Rust);
+/// assert_eq!(target.kind, ProjectKind::Cli);
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "TargetFile", try_from = "TargetFile")]
 pub struct Target {
     /// Language of the project
     pub language: Language,
@@ -69,6 +110,20 @@ pub struct Target {
     pub framework: Option<Framework>,
     /// Architecture
     pub architecture: Architecture,
+    /// Python dependency manager; `None` for other languages
+    pub dependency_manager: Option<DependencyManager>,
+    /// Python package layout; `None` for other languages
+    pub python_layout: Option<PythonLayout>,
+    /// TypeScript test runner; `None` for other languages
+    pub test_runner: Option<TestRunner>,
+    /// Rust task runner; `None` for other languages
+    pub task_runner: Option<TaskRunner>,
+    /// License of the project; `None` writes no LICENSE file
+    pub license: Option<License>,
+    /// CI provider of the project; `None` writes no pipeline
+    pub ci: Option<CiProvider>,
+    /// Optional template parts to include, sorted and without duplicates
+    pub features: Vec<String>,
 }
 
 impl Target {
@@ -102,6 +157,54 @@ impl Target {
         self.architecture
     }
 
+    /// Get the dependency manager (Python targets only).
+    #[must_use]
+    pub const fn dependency_manager(&self) -> Option<DependencyManager> {
+        self.dependency_manager
+    }
+
+    /// Get the Python package layout (Python only).
+    #[must_use]
+    pub const fn python_layout(&self) -> Option<PythonLayout> {
+        self.python_layout
+    }
+
+    /// Get the test runner (TypeScript targets only).
+    #[must_use]
+    pub const fn test_runner(&self) -> Option<TestRunner> {
+        self.test_runner
+    }
+
+    /// Get the task runner (Rust only).
+    #[must_use]
+    pub const fn task_runner(&self) -> Option<TaskRunner> {
+        self.task_runner
+    }
+
+    /// Get the license, if any.
+    #[must_use]
+    pub const fn license(&self) -> Option<License> {
+        self.license
+    }
+
+    /// Get the CI provider, if any.
+    #[must_use]
+    pub const fn ci(&self) -> Option<CiProvider> {
+        self.ci
+    }
+
+    /// Get the enabled features, sorted.
+    #[must_use]
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    /// Check whether a feature is enabled.
+    #[must_use]
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+
     // Preset methods for common configurations
 
     /// Create a Rust CLI application target.
@@ -144,10 +247,21 @@ impl Target {
 
     /// Create a TypeScript frontend with React.
     ///
+    /// Same as [`Target::typescript_frontend_vite_react`].
+    ///
     /// # Errors
     ///
     /// This should not fail as it uses a known-good configuration.
     pub fn typescript_frontend_react() -> Result<Self, DomainError> {
+        Self::typescript_frontend_vite_react()
+    }
+
+    /// Create a TypeScript frontend with React, built with Vite.
+    ///
+    /// # Errors
+    ///
+    /// This should not fail as it uses a known-good configuration.
+    pub fn typescript_frontend_vite_react() -> Result<Self, DomainError> {
         Self::builder()
             .language(Language::TypeScript)
             .kind(ProjectKind::WebFrontend)?
@@ -155,6 +269,53 @@ impl Target {
             .build()
     }
 
+    /// Create a TypeScript frontend with Next.js (App Router).
+    ///
+    /// # Errors
+    ///
+    /// This should not fail as it uses a known-good configuration.
+    pub fn typescript_frontend_nextjs() -> Result<Self, DomainError> {
+        Self::builder()
+            .language(Language::TypeScript)
+            .kind(ProjectKind::WebFrontend)?
+            .framework(Framework::TypeScript(TypeScriptFramework::NextJs))?
+            .build()
+    }
+
+    /// Create a TypeScript frontend with SvelteKit.
+    ///
+    /// # Errors
+    ///
+    /// This should not fail as it uses a known-good configuration.
+    pub fn typescript_frontend_sveltekit() -> Result<Self, DomainError> {
+        Self::builder()
+            .language(Language::TypeScript)
+            .kind(ProjectKind::WebFrontend)?
+            .framework(Framework::TypeScript(TypeScriptFramework::SvelteKit))?
+            .build()
+    }
+
+    /// Serialize to the [`TARGET_FILE`] format.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("a target is always representable as TOML")
+    }
+
+    /// Parse and validate a target saved with [`Target::to_toml`] (or
+    /// written by hand).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidTargetFile`] for malformed TOML and
+    /// unknown keys or values, or the builder's error if the combination
+    /// is invalid.
+    pub fn from_toml(source: &str) -> Result<Self, DomainError> {
+        let file: TargetFile =
+            toml::from_str(source).map_err(|e| DomainError::InvalidTargetFile {
+                reason: e.message().to_string(),
+            })?;
+        Self::try_from(file)
+    }
+
     // TODO: validate method to validate self
 }
 
@@ -170,8 +331,37 @@ impl fmt::Display for Target {
                 .as_ref()
                 .map(|framework| format!(" + {framework}"))
                 .unwrap_or_default()
-        )
+        )?;
+        if !self.features.is_empty() {
+            write!(f, " [{}]", self.features.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Check a feature name: lowercase ASCII letters, digits and `-`, starting
+/// with a letter.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidFeature`] if the name breaks the rules.
+pub(crate) fn check_feature(feature: &str) -> Result<(), DomainError> {
+    let invalid = |reason: &str| DomainError::InvalidFeature {
+        feature: feature.to_string(),
+        reason: reason.to_string(),
+    };
+    if !feature.starts_with(|c: char| c.is_ascii_lowercase()) {
+        return Err(invalid("must start with a lowercase letter"));
     }
+    if !feature
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(invalid(
+            "only lowercase letters, digits and '-' are allowed",
+        ));
+    }
+    Ok(())
 }
 
 // ============================================================================
@@ -194,6 +384,13 @@ pub struct TargetBuilder<L> {
     framework: Option<Framework>,
     kind: Option<ProjectKind>,
     architecture: Option<Architecture>,
+    dependency_manager: Option<DependencyManager>,
+    python_layout: Option<PythonLayout>,
+    test_runner: Option<TestRunner>,
+    task_runner: Option<TaskRunner>,
+    license: Option<License>,
+    ci: Option<CiProvider>,
+    features: Vec<String>,
     _language_state: PhantomData<L>,
 }
 
@@ -206,6 +403,13 @@ impl TargetBuilder<NoLanguage> {
             framework: None,
             kind: None,
             architecture: None,
+            dependency_manager: None,
+            python_layout: None,
+            test_runner: None,
+            task_runner: None,
+            license: None,
+            ci: None,
+            features: Vec::new(),
             _language_state: PhantomData,
         }
     }
@@ -218,6 +422,13 @@ impl TargetBuilder<NoLanguage> {
             framework: self.framework,
             kind: self.kind,
             architecture: self.architecture,
+            dependency_manager: self.dependency_manager,
+            python_layout: self.python_layout,
+            test_runner: self.test_runner,
+            task_runner: self.task_runner,
+            license: self.license,
+            ci: self.ci,
+            features: self.features,
             _language_state: PhantomData,
         }
     }
@@ -280,6 +491,129 @@ impl TargetBuilder<HasLanguage> {
         Ok(self)
     }
 
+    /// Set the dependency manager (optional, Python only).
+    ///
+    /// Python targets default to [`DependencyManager::Pip`].
+    #[must_use]
+    pub fn dependency_manager(mut self, manager: DependencyManager) -> Result<Self, DomainError> {
+        if let Some(lang) = self.language
+            && lang != Language::Python
+        {
+            Err(DomainError::DependencyManagerLanguageMismatch {
+                manager: manager.to_string(),
+                language: lang.to_string(),
+            })?;
+        }
+        if let Some(framework) = self.framework
+            && !manager.is_compatible(Some(framework))
+        {
+            Err(DomainError::DependencyManagerFrameworkMismatch {
+                manager: manager.to_string(),
+                framework: framework.to_string(),
+            })?;
+        }
+        self.dependency_manager = Some(manager);
+        Ok(self)
+    }
+
+    /// Set the package layout (optional, Python only).
+    ///
+    /// Python targets default to [`PythonLayout::Flat`]. The src layout
+    /// builds the project as a package, so it needs uv or poetry; pip has
+    /// nowhere to declare it (checked by [`build`](Self::build)).
+    #[must_use]
+    pub fn python_layout(mut self, layout: PythonLayout) -> Result<Self, DomainError> {
+        if let Some(lang) = self.language
+            && lang != Language::Python
+        {
+            Err(DomainError::PythonLayoutLanguageMismatch {
+                layout: layout.to_string(),
+                language: lang.to_string(),
+            })?;
+        }
+        self.python_layout = Some(layout);
+        Ok(self)
+    }
+
+    /// Set the test runner (optional, TypeScript only).
+    ///
+    /// TypeScript targets default to [`TestRunner::Vitest`] for Vite
+    /// frameworks (React, Vue) and [`TestRunner::Jest`] otherwise.
+    #[must_use]
+    pub fn test_runner(mut self, runner: TestRunner) -> Result<Self, DomainError> {
+        if let Some(lang) = self.language
+            && lang != Language::TypeScript
+        {
+            Err(DomainError::TestRunnerLanguageMismatch {
+                runner: runner.to_string(),
+                language: lang.to_string(),
+            })?;
+        }
+        if let Some(framework) = self.framework
+            && !runner.is_compatible(Some(framework))
+        {
+            Err(DomainError::TestRunnerFrameworkMismatch {
+                runner: runner.to_string(),
+                framework: framework.to_string(),
+            })?;
+        }
+        self.test_runner = Some(runner);
+        Ok(self)
+    }
+
+    /// Set the task runner (optional, Rust only).
+    ///
+    /// [`TaskRunner::Just`] and [`TaskRunner::Make`] add a `justfile` or
+    /// `Makefile` with `test`, `lint` and `run` recipes, and an example
+    /// test suite for them to run.
+    #[must_use]
+    pub fn task_runner(mut self, runner: TaskRunner) -> Result<Self, DomainError> {
+        if let Some(lang) = self.language
+            && lang != Language::Rust
+        {
+            Err(DomainError::TaskRunnerLanguageMismatch {
+                runner: runner.to_string(),
+                language: lang.to_string(),
+            })?;
+        }
+        self.task_runner = Some(runner);
+        Ok(self)
+    }
+
+    /// Set the license (optional).
+    ///
+    /// Any language can take any license; without one no LICENSE file is
+    /// written.
+    #[must_use]
+    pub fn license(mut self, license: License) -> Self {
+        self.license = Some(license);
+        self
+    }
+
+    /// Set the CI provider (optional).
+    ///
+    /// The pipeline builds, tests and lints the project with its
+    /// language's tools, and releases tagged versions; without a provider
+    /// no pipeline is written.
+    #[must_use]
+    pub fn ci(mut self, provider: CiProvider) -> Self {
+        self.ci = Some(provider);
+        self
+    }
+
+    /// Enable a feature (optional).
+    ///
+    /// Features switch on optional parts of the template, e.g. `docker`,
+    /// `ci-github` or `pre-commit`; ones the template doesn't offer are
+    /// ignored. Enabling a feature twice is the same as once.
+    #[must_use]
+    pub fn feature(mut self, feature: impl Into<String>) -> Result<Self, DomainError> {
+        let feature = feature.into();
+        check_feature(&feature)?;
+        self.features.push(feature);
+        Ok(self)
+    }
+
     /// Finalize the builder and construct a validated [`Target`].
     ///
     /// This performs all validation and inference:
@@ -287,7 +621,9 @@ impl TargetBuilder<HasLanguage> {
     /// 2. Infers or validates project type
     /// 3. Infers or validates framework (optional)
     /// 4. Infers or validates architecture
-    /// 5. Checks all compatibility constraints
+    /// 5. Infers or validates the dependency manager (Python only)
+    /// 6. Infers or validates the test runner (TypeScript only)
+    /// 7. Checks all compatibility constraints
     ///
     /// # Errors
     ///
@@ -296,8 +632,15 @@ impl TargetBuilder<HasLanguage> {
     /// - Framework is incompatible with language
     /// - Framework doesn't support the project type
     /// - Architecture is incompatible with framework or project type
+    /// - A dependency manager is set for a non-Python target, or is
+    ///   incompatible with the framework
+    /// - A Python layout is set for a non-Python target, or the src
+    ///   layout is combined with pip
+    /// - A test runner is set for a non-TypeScript target, or is
+    ///   incompatible with the framework
+    /// - A task runner is set for a non-Rust target
     /// - Required values cannot be inferred
-    pub fn build(self) -> Result<Target, DomainError> {
+    pub fn build(mut self) -> Result<Target, DomainError> {
         let language = self
             .language
             .expect("HasLanguage state guarantees language is set");
@@ -309,13 +652,43 @@ impl TargetBuilder<HasLanguage> {
             });
         }
 
+        let (dependency_manager, python_layout, test_runner, task_runner, license, ci) = (
+            self.dependency_manager,
+            self.python_layout,
+            self.test_runner,
+            self.task_runner,
+            self.license,
+            self.ci,
+        );
+        let mut features = std::mem::take(&mut self.features);
+        features.sort();
+        features.dedup();
         let (kind, framework, architecture) = self.parse(language)?;
+        let dependency_manager =
+            resolve_dependency_manager(language, framework, dependency_manager)?;
+        let python_layout = resolve_python_layout(language, dependency_manager, python_layout)?;
+        let test_runner = resolve_test_runner(language, framework, test_runner)?;
+        if let Some(runner) = task_runner
+            && language != Language::Rust
+        {
+            return Err(DomainError::TaskRunnerLanguageMismatch {
+                runner: runner.to_string(),
+                language: language.to_string(),
+            });
+        }
 
         Ok(Target {
             language,
             kind,
             framework,
             architecture,
+            dependency_manager,
+            python_layout,
+            test_runner,
+            task_runner,
+            license,
+            ci,
+            features,
         })
     }
 
@@ -422,6 +795,83 @@ impl TargetBuilder<HasLanguage> {
     }
 }
 
+/// Validate an explicit dependency manager, or infer one for Python.
+fn resolve_dependency_manager(
+    language: Language,
+    framework: Option<Framework>,
+    manager: Option<DependencyManager>,
+) -> Result<Option<DependencyManager>, DomainError> {
+    match (language, manager) {
+        (Language::Python, Some(manager)) => {
+            if !manager.is_compatible(framework) {
+                return Err(DomainError::DependencyManagerFrameworkMismatch {
+                    manager: manager.to_string(),
+                    framework: framework.map_or_else(|| "none".to_string(), |f| f.to_string()),
+                });
+            }
+            Ok(Some(manager))
+        }
+        (Language::Python, None) => Ok(DependencyManager::infer_from(framework)),
+        (_, Some(manager)) => Err(DomainError::DependencyManagerLanguageMismatch {
+            manager: manager.to_string(),
+            language: language.to_string(),
+        }),
+        (_, None) => Ok(None),
+    }
+}
+
+/// Validate an explicit package layout, or default to flat for Python.
+fn resolve_python_layout(
+    language: Language,
+    manager: Option<DependencyManager>,
+    layout: Option<PythonLayout>,
+) -> Result<Option<PythonLayout>, DomainError> {
+    match (language, layout) {
+        (Language::Python, Some(layout)) => {
+            if let Some(manager) = manager
+                && !layout.supports(manager)
+            {
+                return Err(DomainError::PythonLayoutManagerMismatch {
+                    layout: layout.to_string(),
+                    manager: manager.to_string(),
+                });
+            }
+            Ok(Some(layout))
+        }
+        (Language::Python, None) => Ok(Some(PythonLayout::Flat)),
+        (_, Some(layout)) => Err(DomainError::PythonLayoutLanguageMismatch {
+            layout: layout.to_string(),
+            language: language.to_string(),
+        }),
+        (_, None) => Ok(None),
+    }
+}
+
+/// Validate an explicit test runner, or infer one for TypeScript.
+fn resolve_test_runner(
+    language: Language,
+    framework: Option<Framework>,
+    runner: Option<TestRunner>,
+) -> Result<Option<TestRunner>, DomainError> {
+    match (language, runner) {
+        (Language::TypeScript, Some(runner)) => {
+            if !runner.is_compatible(framework) {
+                return Err(DomainError::TestRunnerFrameworkMismatch {
+                    runner: runner.to_string(),
+                    framework: framework.map_or_else(|| "none".to_string(), |f| f.to_string()),
+                });
+            }
+            Ok(Some(runner))
+        }
+        (Language::TypeScript, None) => Ok(TestRunner::infer_from(framework)),
+        (_, Some(runner)) => Err(DomainError::TestRunnerLanguageMismatch {
+            runner: runner.to_string(),
+            language: language.to_string(),
+        }),
+        (_, None) => Ok(None),
+    }
+}
+
 // ============================================================================
 // Language
 // ============================================================================
@@ -483,6 +933,10 @@ pub enum ProjectKind {
     WebFrontend,
     Fullstack,
     Worker,
+    /// Language-agnostic project built from a user-supplied [`CustomTree`](crate::domain::CustomTree)
+    Custom,
+    /// Root of a multi-project workspace, see [`WorkspaceTarget`](crate::domain::WorkspaceTarget)
+    Workspace,
 }
 
 impl ProjectKind {
@@ -494,6 +948,8 @@ impl ProjectKind {
             Self::WebFrontend => "web-frontend",
             Self::Fullstack => "fullstack",
             Self::Worker => "worker",
+            Self::Custom => "custom",
+            Self::Workspace => "workspace",
         }
     }
 
@@ -505,6 +961,8 @@ impl ProjectKind {
             "web-frontend" | "frontend" => Some(Self::WebFrontend),
             "fullstack" => Some(Self::Fullstack),
             "worker" => Some(Self::Worker),
+            "custom" => Some(Self::Custom),
+            "workspace" | "monorepo" => Some(Self::Workspace),
             _ => None,
         }
     }
@@ -538,6 +996,8 @@ impl ActivelySupported for ProjectKind {
         Self::WebFrontend,
         Self::Fullstack,
         Self::Worker,
+        Self::Custom,
+        Self::Workspace,
     ];
 }
 
@@ -568,9 +1028,13 @@ pub enum PythonFramework {
 pub enum TypeScriptFramework {
     Express,
     NestJs,
+    /// React built with Vite
     React,
+    /// Vue built with Vite
     Vue,
     NextJs,
+    /// SvelteKit (built with Vite)
+    SvelteKit,
 }
 
 impl Framework {
@@ -586,6 +1050,22 @@ impl Framework {
             Self::TypeScript(TypeScriptFramework::React) => "react",
             Self::TypeScript(TypeScriptFramework::Vue) => "vue",
             Self::TypeScript(TypeScriptFramework::NextJs) => "nextjs",
+            Self::TypeScript(TypeScriptFramework::SvelteKit) => "sveltekit",
+        }
+    }
+
+    /// Parse a framework by name; names are unique across languages.
+    ///
+    /// `vite-react`, `next` and `svelte-kit` are accepted for React (built
+    /// with Vite), Next.js and SvelteKit.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.to_ascii_lowercase();
+        match s.as_str() {
+            "vite-react" => Some(Self::TypeScript(TypeScriptFramework::React)),
+            "next" => Some(Self::TypeScript(TypeScriptFramework::NextJs)),
+            "svelte-kit" => Some(Self::TypeScript(TypeScriptFramework::SvelteKit)),
+            _ => Self::ALL.iter().copied().find(|fw| fw.as_str() == s),
         }
     }
 
@@ -630,6 +1110,7 @@ impl ActivelySupported for Framework {
         Framework::TypeScript(TypeScriptFramework::React),
         Framework::TypeScript(TypeScriptFramework::Vue),
         Framework::TypeScript(TypeScriptFramework::NextJs),
+        Framework::TypeScript(TypeScriptFramework::SvelteKit),
         Framework::Python(PythonFramework::Django),
         Framework::Python(PythonFramework::FastApi),
     ];
@@ -643,7 +1124,16 @@ impl ActivelySupported for Framework {
 pub enum Architecture {
     Layered,
     MVC,
+    /// Use cases per operation over domain entities; dependencies point
+    /// inwards (`api` → `application` → `domain`)
     Clean,
+    /// Ports and adapters: the domain declares inbound and outbound port
+    /// traits, adapters (HTTP, storage) implement them
+    Hexagonal,
+    /// Code grouped by feature (`features/<name>`) rather than by kind
+    Modular,
+    /// Next.js App Router: routes, layouts and pages under `app/`
+    AppRouter,
 }
 
 impl Architecture {
@@ -652,6 +1142,22 @@ impl Architecture {
             Architecture::Layered => "layered",
             Architecture::MVC => "mvc",
             Architecture::Clean => "clean",
+            Architecture::Hexagonal => "hexagonal",
+            Architecture::Modular => "modular",
+            Architecture::AppRouter => "app-router",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "layered" => Some(Self::Layered),
+            "mvc" => Some(Self::MVC),
+            "clean" => Some(Self::Clean),
+            "hexagonal" | "ports-and-adapters" => Some(Self::Hexagonal),
+            "modular" => Some(Self::Modular),
+            "app-router" => Some(Self::AppRouter),
+            _ => None,
         }
     }
 }
@@ -673,74 +1179,557 @@ impl ActivelySupported for Architecture {
         Architecture::Layered,
         Architecture::MVC,
         Architecture::Clean,
+        Architecture::Hexagonal,
+        Architecture::Modular,
+        Architecture::AppRouter,
     ];
 }
 
 // ============================================================================
-// Traits
+// DependencyManager
 // ============================================================================
 
-pub trait ActivelySupported: Sized + PartialEq + 'static {
-    const ALL: &'static [Self];
+/// How a Python project declares and locks its dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyManager {
+    /// `pyproject.toml` + `uv.lock`
+    Uv,
+    /// `pyproject.toml` (`[tool.poetry]`) + `poetry.lock`
+    Poetry,
+    /// `requirements.txt` + `requirements-dev.txt`, installed into a venv
+    Pip,
+}
 
-    fn is_supported(&self) -> bool {
-        Self::ALL.contains(self)
+impl DependencyManager {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Uv => "uv",
+            Self::Poetry => "poetry",
+            Self::Pip => "pip",
+        }
     }
-}
 
-pub trait ActivelySupportedExt: ActivelySupported {
-    fn active() -> &'static [Self] {
-        Self::ALL
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "uv" => Some(Self::Uv),
+            "poetry" => Some(Self::Poetry),
+            "pip" | "requirements" | "venv" | "pip+venv" => Some(Self::Pip),
+            _ => None,
+        }
     }
 }
 
-impl<T: ActivelySupported> ActivelySupportedExt for T {}
+impl From<DependencyManager> for String {
+    fn from(value: DependencyManager) -> Self {
+        value.as_str().to_string()
+    }
+}
 
-pub trait LangCapable {
-    fn lang_capable(&self, language: Language) -> bool;
-    fn capable_languages(self) -> Vec<Language>;
+impl fmt::Display for DependencyManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct LangCapableProjects {
-    language: Language,
-    p_types: &'static [ProjectKind],
+impl ActivelySupported for DependencyManager {
+    const ALL: &'static [Self] = &[Self::Uv, Self::Poetry, Self::Pip];
 }
 
-const LANG_CAPABILITIES: &[LangCapableProjects] = &[
-    LangCapableProjects {
-        language: Language::Rust,
-        p_types: &[
-            ProjectKind::Cli,
-            ProjectKind::WebBackend,
-            ProjectKind::Worker,
-        ],
-    },
-    LangCapableProjects {
-        language: Language::Python,
-        p_types: &[
-            ProjectKind::Cli,
-            ProjectKind::Fullstack,
-            ProjectKind::WebBackend,
-            ProjectKind::Worker,
-        ],
-    },
-    LangCapableProjects {
-        language: Language::TypeScript,
-        p_types: &[
-            ProjectKind::WebFrontend,
-            ProjectKind::Fullstack,
-            ProjectKind::WebBackend,
-            ProjectKind::Worker,
-        ],
-    },
-];
+// ============================================================================
+// TestRunner
+// ============================================================================
 
-impl LangCapable for ProjectKind {
-    fn lang_capable(&self, language: Language) -> bool {
-        if !language.is_supported() {
-            return false;
-        }
+/// Test runner configured for a TypeScript project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TestRunner {
+    /// `vitest.config.ts`, sharing the Vite pipeline
+    Vitest,
+    /// `jest.config.js` with `ts-jest` (or `next/jest` for Next.js)
+    Jest,
+    /// No test runner or example test
+    None,
+}
+
+impl TestRunner {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Vitest => "vitest",
+            Self::Jest => "jest",
+            Self::None => "none",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "vitest" => Some(Self::Vitest),
+            "jest" => Some(Self::Jest),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+
+impl From<TestRunner> for String {
+    fn from(value: TestRunner) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl fmt::Display for TestRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl ActivelySupported for TestRunner {
+    const ALL: &'static [Self] = &[Self::Vitest, Self::Jest, Self::None];
+}
+
+// ============================================================================
+// Python Layout
+// ============================================================================
+
+/// Where a Python project keeps its packages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PythonLayout {
+    /// Packages under `src/`, built and installed by the dependency manager
+    Src,
+    /// Packages at the project root, run in place
+    Flat,
+}
+
+impl PythonLayout {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Src => "src",
+            Self::Flat => "flat",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "src" | "src-layout" => Some(Self::Src),
+            "flat" | "flat-layout" => Some(Self::Flat),
+            _ => None,
+        }
+    }
+
+    /// Whether `manager` can build the layout: the src layout has to be
+    /// installed as a package, which `requirements.txt` can't declare.
+    #[must_use]
+    pub const fn supports(self, manager: DependencyManager) -> bool {
+        !matches!((self, manager), (Self::Src, DependencyManager::Pip))
+    }
+}
+
+impl From<PythonLayout> for String {
+    fn from(value: PythonLayout) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl fmt::Display for PythonLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl ActivelySupported for PythonLayout {
+    const ALL: &'static [Self] = &[Self::Src, Self::Flat];
+}
+
+// ============================================================================
+// Task Runner
+// ============================================================================
+
+/// Task runner configured for a Rust project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskRunner {
+    /// `justfile`
+    Just,
+    /// `Makefile`
+    Make,
+    /// No task file or example tests
+    None,
+}
+
+impl TaskRunner {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Just => "just",
+            Self::Make => "make",
+            Self::None => "none",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "just" | "justfile" => Some(Self::Just),
+            "make" | "makefile" => Some(Self::Make),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+
+impl From<TaskRunner> for String {
+    fn from(value: TaskRunner) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl fmt::Display for TaskRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl ActivelySupported for TaskRunner {
+    const ALL: &'static [Self] = &[Self::Just, Self::Make, Self::None];
+}
+
+// ============================================================================
+// License
+// ============================================================================
+
+/// License a project is published under, by SPDX identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum License {
+    /// MIT
+    Mit,
+    /// Apache License 2.0
+    Apache2,
+    /// GNU General Public License v3.0
+    Gpl3,
+    /// Mozilla Public License 2.0
+    Mpl2,
+    /// The Unlicense (public domain)
+    Unlicense,
+}
+
+impl License {
+    /// SPDX identifier, as written in manifests (`license = "MIT"`).
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Mit => "MIT",
+            Self::Apache2 => "Apache-2.0",
+            Self::Gpl3 => "GPL-3.0",
+            Self::Mpl2 => "MPL-2.0",
+            Self::Unlicense => "Unlicense",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "mit" => Some(Self::Mit),
+            "apache-2.0" | "apache2" | "apache" => Some(Self::Apache2),
+            "gpl-3.0" | "gpl-3.0-only" | "gpl3" | "gpl" => Some(Self::Gpl3),
+            "mpl-2.0" | "mpl2" | "mpl" => Some(Self::Mpl2),
+            "unlicense" => Some(Self::Unlicense),
+            _ => None,
+        }
+    }
+}
+
+impl From<License> for String {
+    fn from(value: License) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl fmt::Display for License {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl ActivelySupported for License {
+    const ALL: &'static [Self] = &[
+        Self::Mit,
+        Self::Apache2,
+        Self::Gpl3,
+        Self::Mpl2,
+        Self::Unlicense,
+    ];
+}
+
+// ============================================================================
+// CI Provider
+// ============================================================================
+
+/// CI service a project's pipeline is written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CiProvider {
+    /// GitHub Actions (`.github/workflows/ci.yml`)
+    GitHubActions,
+    /// GitLab CI/CD (`.gitlab-ci.yml`)
+    GitLabCi,
+    /// CircleCI (`.circleci/config.yml`)
+    CircleCi,
+}
+
+impl CiProvider {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::GitHubActions => "github-actions",
+            Self::GitLabCi => "gitlab-ci",
+            Self::CircleCi => "circleci",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "github-actions" | "github" | "gha" => Some(Self::GitHubActions),
+            "gitlab-ci" | "gitlab" => Some(Self::GitLabCi),
+            "circleci" | "circle" => Some(Self::CircleCi),
+            _ => None,
+        }
+    }
+
+    /// Path of the pipeline file, relative to the project root.
+    #[must_use]
+    pub const fn config_path(self) -> &'static str {
+        match self {
+            Self::GitHubActions => ".github/workflows/ci.yml",
+            Self::GitLabCi => ".gitlab-ci.yml",
+            Self::CircleCi => ".circleci/config.yml",
+        }
+    }
+}
+
+impl From<CiProvider> for String {
+    fn from(value: CiProvider) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl fmt::Display for CiProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl ActivelySupported for CiProvider {
+    const ALL: &'static [Self] = &[Self::GitHubActions, Self::GitLabCi, Self::CircleCi];
+}
+
+// ============================================================================
+// Serialization
+// ============================================================================
+
+/// Conventional file name for a saved target.
+pub const TARGET_FILE: &str = "scarff.target.toml";
+
+/// Serialized form of a [`Target`].
+///
+/// Everything but `language` is optional so that hand-written files can
+/// lean on inference; a saved target always has every field it resolved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TargetFile {
+    language: Language,
+    #[serde(default, alias = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<ProjectKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    framework: Option<Framework>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    architecture: Option<Architecture>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dependency_manager: Option<DependencyManager>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    python_layout: Option<PythonLayout>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    test_runner: Option<TestRunner>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    task_runner: Option<TaskRunner>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    license: Option<License>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ci: Option<CiProvider>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    features: Vec<String>,
+}
+
+impl From<Target> for TargetFile {
+    fn from(target: Target) -> Self {
+        Self {
+            language: target.language,
+            kind: Some(target.kind),
+            framework: target.framework,
+            architecture: Some(target.architecture),
+            dependency_manager: target.dependency_manager,
+            python_layout: target.python_layout,
+            test_runner: target.test_runner,
+            task_runner: target.task_runner,
+            license: target.license,
+            ci: target.ci,
+            features: target.features,
+        }
+    }
+}
+
+impl TryFrom<TargetFile> for Target {
+    type Error = DomainError;
+
+    fn try_from(file: TargetFile) -> Result<Self, Self::Error> {
+        let mut builder = Target::builder().language(file.language);
+        if let Some(kind) = file.kind {
+            builder = builder.kind(kind)?;
+        }
+        if let Some(framework) = file.framework {
+            builder = builder.framework(framework)?;
+        }
+        if let Some(architecture) = file.architecture {
+            builder = builder.architecture(architecture)?;
+        }
+        if let Some(manager) = file.dependency_manager {
+            builder = builder.dependency_manager(manager)?;
+        }
+        if let Some(layout) = file.python_layout {
+            builder = builder.python_layout(layout)?;
+        }
+        if let Some(runner) = file.test_runner {
+            builder = builder.test_runner(runner)?;
+        }
+        if let Some(runner) = file.task_runner {
+            builder = builder.task_runner(runner)?;
+        }
+        if let Some(license) = file.license {
+            builder = builder.license(license);
+        }
+        if let Some(ci) = file.ci {
+            builder = builder.ci(ci);
+        }
+        for feature in file.features {
+            builder = builder.feature(feature)?;
+        }
+        builder.build()
+    }
+}
+
+/// Serialize an enum as its stable `as_str()` name and deserialize it with
+/// `parse()`, so aliases (`ts`, `backend`, `hexagonal`, ...) are accepted
+/// on the way in but never written out.
+macro_rules! serde_as_str {
+    ($($ty:ident => $what:literal),* $(,)?) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Self::parse(&value).ok_or_else(|| {
+                    let expected: Vec<&str> = Self::ALL.iter().map(|v| v.as_str()).collect();
+                    de::Error::custom(format!(
+                        "unknown {} '{value}', expected one of: {}",
+                        $what,
+                        expected.join(", ")
+                    ))
+                })
+            }
+        }
+    )*};
+}
+
+serde_as_str! {
+    Language => "language",
+    ProjectKind => "project type",
+    Framework => "framework",
+    Architecture => "architecture",
+    DependencyManager => "dependency manager",
+    PythonLayout => "Python layout",
+    TestRunner => "test runner",
+    TaskRunner => "task runner",
+    License => "license",
+    CiProvider => "CI provider",
+}
+
+// ============================================================================
+// Traits
+// ============================================================================
+
+pub trait ActivelySupported: Sized + PartialEq + 'static {
+    const ALL: &'static [Self];
+
+    fn is_supported(&self) -> bool {
+        Self::ALL.contains(self)
+    }
+}
+
+pub trait ActivelySupportedExt: ActivelySupported {
+    fn active() -> &'static [Self] {
+        Self::ALL
+    }
+}
+
+impl<T: ActivelySupported> ActivelySupportedExt for T {}
+
+pub trait LangCapable {
+    fn lang_capable(&self, language: Language) -> bool;
+    fn capable_languages(self) -> Vec<Language>;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct LangCapableProjects {
+    language: Language,
+    p_types: &'static [ProjectKind],
+}
+
+const LANG_CAPABILITIES: &[LangCapableProjects] = &[
+    LangCapableProjects {
+        language: Language::Rust,
+        p_types: &[
+            ProjectKind::Cli,
+            ProjectKind::WebBackend,
+            ProjectKind::Worker,
+            ProjectKind::Custom,
+            ProjectKind::Workspace,
+        ],
+    },
+    LangCapableProjects {
+        language: Language::Python,
+        p_types: &[
+            ProjectKind::Cli,
+            ProjectKind::Fullstack,
+            ProjectKind::WebBackend,
+            ProjectKind::Worker,
+            ProjectKind::Custom,
+            ProjectKind::Workspace,
+        ],
+    },
+    LangCapableProjects {
+        language: Language::TypeScript,
+        p_types: &[
+            ProjectKind::WebFrontend,
+            ProjectKind::Fullstack,
+            ProjectKind::WebBackend,
+            ProjectKind::Worker,
+            ProjectKind::Custom,
+            ProjectKind::Workspace,
+        ],
+    },
+];
+
+impl LangCapable for ProjectKind {
+    fn lang_capable(&self, language: Language) -> bool {
+        if !language.is_supported() {
+            return false;
+        }
 
         if !self.is_supported() {
             return false;
@@ -781,11 +1770,18 @@ impl Compatible for Framework {
                 Framework::TypeScript(TypeScriptFramework::Express | TypeScriptFramework::NestJs),
                 (Language::TypeScript, ProjectKind::WebBackend),
             ) | (
-                Framework::TypeScript(TypeScriptFramework::React | TypeScriptFramework::Vue),
+                Framework::TypeScript(
+                    TypeScriptFramework::React
+                        | TypeScriptFramework::Vue
+                        | TypeScriptFramework::SvelteKit
+                ),
                 (Language::TypeScript, ProjectKind::WebFrontend),
             ) | (
                 Framework::TypeScript(TypeScriptFramework::NextJs),
-                (Language::TypeScript, ProjectKind::Fullstack),
+                (
+                    Language::TypeScript,
+                    ProjectKind::WebFrontend | ProjectKind::Fullstack
+                ),
             ) | (
                 Framework::Python(PythonFramework::FastApi),
                 (Language::Python, ProjectKind::WebBackend),
@@ -805,12 +1801,17 @@ impl Compatible for Framework {
             Framework::TypeScript(TypeScriptFramework::Express | TypeScriptFramework::NestJs) => {
                 vec![(Language::TypeScript, ProjectKind::WebBackend)]
             }
-            Framework::TypeScript(TypeScriptFramework::React | TypeScriptFramework::Vue) => {
+            Framework::TypeScript(
+                TypeScriptFramework::React
+                | TypeScriptFramework::Vue
+                | TypeScriptFramework::SvelteKit,
+            ) => {
                 vec![(Language::TypeScript, ProjectKind::WebFrontend)]
             }
-            Framework::TypeScript(TypeScriptFramework::NextJs) => {
-                vec![(Language::TypeScript, ProjectKind::Fullstack)]
-            }
+            Framework::TypeScript(TypeScriptFramework::NextJs) => vec![
+                (Language::TypeScript, ProjectKind::WebFrontend),
+                (Language::TypeScript, ProjectKind::Fullstack),
+            ],
             Framework::Python(PythonFramework::FastApi) => {
                 vec![(Language::Python, ProjectKind::WebBackend)]
             }
@@ -868,6 +1869,59 @@ impl Compatible for Architecture {
                 ),
             ) => true,
 
+            // Clean and hexagonal - Rust web backends on Axum
+            (
+                Architecture::Clean | Architecture::Hexagonal,
+                (
+                    Language::Rust,
+                    ProjectKind::WebBackend,
+                    Some(Framework::Rust(RustFramework::Axum)),
+                ),
+            ) => true,
+
+            // Custom trees carry their own structure; workspace roots
+            // only hold the members
+            (Architecture::Layered, (_, ProjectKind::Custom | ProjectKind::Workspace, None)) => {
+                true
+            }
+
+            // Frontends: by kind (components/, hooks/, lib/) or by feature
+            (
+                Architecture::Layered,
+                (
+                    Language::TypeScript,
+                    ProjectKind::WebFrontend,
+                    Some(Framework::TypeScript(
+                        TypeScriptFramework::React
+                        | TypeScriptFramework::Vue
+                        | TypeScriptFramework::SvelteKit,
+                    )),
+                ),
+            ) => true,
+            (
+                Architecture::Modular,
+                (
+                    Language::TypeScript,
+                    ProjectKind::WebFrontend,
+                    Some(Framework::TypeScript(
+                        TypeScriptFramework::React
+                        | TypeScriptFramework::Vue
+                        | TypeScriptFramework::SvelteKit
+                        | TypeScriptFramework::NextJs,
+                    )),
+                ),
+            ) => true,
+
+            // App Router - Next.js only
+            (
+                Architecture::AppRouter,
+                (
+                    Language::TypeScript,
+                    ProjectKind::WebFrontend | ProjectKind::Fullstack,
+                    Some(Framework::TypeScript(TypeScriptFramework::NextJs)),
+                ),
+            ) => true,
+
             // MVC - Django only
             (
                 Architecture::MVC,
@@ -908,13 +1962,109 @@ impl Compatible for Architecture {
                 ProjectKind::Fullstack,
                 Some(Framework::Python(PythonFramework::Django)),
             )],
-            Architecture::Clean => vec![],
+            Architecture::Clean | Architecture::Hexagonal => vec![(
+                Language::Rust,
+                ProjectKind::WebBackend,
+                Some(Framework::Rust(RustFramework::Axum)),
+            )],
+            Architecture::Modular => vec![
+                (
+                    Language::TypeScript,
+                    ProjectKind::WebFrontend,
+                    Some(Framework::TypeScript(TypeScriptFramework::React)),
+                ),
+                (
+                    Language::TypeScript,
+                    ProjectKind::WebFrontend,
+                    Some(Framework::TypeScript(TypeScriptFramework::NextJs)),
+                ),
+                (
+                    Language::TypeScript,
+                    ProjectKind::WebFrontend,
+                    Some(Framework::TypeScript(TypeScriptFramework::SvelteKit)),
+                ),
+            ],
+            Architecture::AppRouter => vec![(
+                Language::TypeScript,
+                ProjectKind::WebFrontend,
+                Some(Framework::TypeScript(TypeScriptFramework::NextJs)),
+            )],
         };
 
         Some(contexts)
     }
 }
 
+impl Compatible for DependencyManager {
+    type Context = Option<Framework>;
+
+    fn is_compatible(&self, ctx: Self::Context) -> bool {
+        self.get_compatible()
+            .is_some_and(|frameworks| frameworks.contains(&ctx))
+    }
+
+    fn get_compatible(&self) -> Option<Vec<Self::Context>> {
+        if !self.is_supported() {
+            return None;
+        }
+
+        // Plain Python projects plus every Python framework; non-Python
+        // frameworks never match
+        let mut frameworks = vec![None];
+        frameworks.extend(
+            Framework::ALL
+                .iter()
+                .copied()
+                .filter(|f| matches!(f, Framework::Python(_)))
+                .map(Some),
+        );
+        Some(frameworks)
+    }
+}
+
+impl Compatible for TestRunner {
+    type Context = Option<Framework>;
+
+    fn is_compatible(&self, ctx: Self::Context) -> bool {
+        self.get_compatible()
+            .is_some_and(|frameworks| frameworks.contains(&ctx))
+    }
+
+    fn get_compatible(&self) -> Option<Vec<Self::Context>> {
+        use TypeScriptFramework::{Express, NestJs, NextJs, React, SvelteKit, Vue};
+
+        let frameworks: &[Option<TypeScriptFramework>] = match self {
+            // Nest's testing module is built on Jest
+            Self::Vitest => &[
+                None,
+                Some(React),
+                Some(Vue),
+                Some(SvelteKit),
+                Some(NextJs),
+                Some(Express),
+            ],
+            // Jest can't load Vite's module pipeline
+            Self::Jest => &[None, Some(NextJs), Some(Express), Some(NestJs)],
+            Self::None => &[
+                None,
+                Some(React),
+                Some(Vue),
+                Some(SvelteKit),
+                Some(NextJs),
+                Some(Express),
+                Some(NestJs),
+            ],
+        };
+
+        Some(
+            frameworks
+                .iter()
+                .map(|fw| fw.map(Framework::TypeScript))
+                .collect(),
+        )
+    }
+}
+
 trait Infer {
     type Context;
     fn infer_from(ctx: Self::Context) -> Option<Self>
@@ -968,6 +2118,33 @@ impl Infer for Framework {
     }
 }
 
+impl Infer for DependencyManager {
+    type Context = Option<Framework>;
+
+    fn infer_from(ctx: Self::Context) -> Option<Self> {
+        match ctx {
+            None | Some(Framework::Python(_)) => Some(DependencyManager::Pip),
+            Some(_) => None,
+        }
+    }
+}
+
+impl Infer for TestRunner {
+    type Context = Option<Framework>;
+
+    fn infer_from(ctx: Self::Context) -> Option<Self> {
+        match ctx {
+            Some(Framework::TypeScript(
+                TypeScriptFramework::React
+                | TypeScriptFramework::Vue
+                | TypeScriptFramework::SvelteKit,
+            )) => Some(TestRunner::Vitest),
+            None | Some(Framework::TypeScript(_)) => Some(TestRunner::Jest),
+            Some(_) => None,
+        }
+    }
+}
+
 impl Infer for Architecture {
     type Context = (Language, ProjectKind, Option<Framework>);
 
@@ -976,7 +2153,12 @@ impl Infer for Architecture {
             // Rust - Layered for everything
             (Language::Rust, _, _) => Some(Architecture::Layered),
 
-            // TypeScript
+            // TypeScript - Next.js frontends use the App Router
+            (
+                Language::TypeScript,
+                ProjectKind::WebFrontend,
+                Some(Framework::TypeScript(TypeScriptFramework::NextJs)),
+            ) => Some(Architecture::AppRouter),
             (Language::TypeScript, _, Some(Framework::TypeScript(_))) => {
                 Some(Architecture::Layered)
             }
@@ -1065,6 +2247,25 @@ mod tests {
         assert_eq!(Language::parse("invalid"), None);
     }
 
+    #[test]
+    fn framework_and_architecture_parse() {
+        assert_eq!(
+            Framework::parse("Axum"),
+            Some(Framework::Rust(RustFramework::Axum))
+        );
+        assert_eq!(
+            Framework::parse("nextjs"),
+            Some(Framework::TypeScript(TypeScriptFramework::NextJs))
+        );
+        assert_eq!(Framework::parse("rails"), None);
+        assert_eq!(Architecture::parse("mvc"), Some(Architecture::MVC));
+        assert_eq!(
+            Architecture::parse("ports-and-adapters"),
+            Some(Architecture::Hexagonal)
+        );
+        assert_eq!(Architecture::parse("onion"), None);
+    }
+
     #[test]
     fn preset_rust_cli() {
         let target = Target::rust_cli().unwrap();
@@ -1083,6 +2284,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn typescript_frontend_presets() {
+        let react = Target::typescript_frontend_vite_react().unwrap();
+        assert_eq!(react, Target::typescript_frontend_react().unwrap());
+        assert_eq!(react.architecture(), Architecture::Layered);
+        assert_eq!(react.test_runner(), Some(TestRunner::Vitest));
+
+        let next = Target::typescript_frontend_nextjs().unwrap();
+        assert_eq!(next.kind(), ProjectKind::WebFrontend);
+        assert_eq!(next.architecture(), Architecture::AppRouter);
+        assert_eq!(next.test_runner(), Some(TestRunner::Jest));
+
+        let svelte = Target::typescript_frontend_sveltekit().unwrap();
+        assert_eq!(
+            svelte.framework(),
+            Some(Framework::TypeScript(TypeScriptFramework::SvelteKit))
+        );
+        assert_eq!(svelte.architecture(), Architecture::Layered);
+        assert_eq!(svelte.test_runner(), Some(TestRunner::Vitest));
+    }
+
+    #[test]
+    fn frontend_architecture_variants() {
+        let frontend = |framework, architecture| {
+            Target::builder()
+                .language(Language::TypeScript)
+                .kind(ProjectKind::WebFrontend)?
+                .framework(Framework::TypeScript(framework))?
+                .architecture(architecture)?
+                .build()
+        };
+
+        assert!(frontend(TypeScriptFramework::SvelteKit, Architecture::Modular).is_ok());
+        assert!(frontend(TypeScriptFramework::NextJs, Architecture::Modular).is_ok());
+        assert!(frontend(TypeScriptFramework::React, Architecture::AppRouter).is_err());
+        assert!(frontend(TypeScriptFramework::NextJs, Architecture::MVC).is_err());
+        assert_eq!(
+            Architecture::parse("app-router"),
+            Some(Architecture::AppRouter)
+        );
+        assert_eq!(
+            Framework::parse("svelte-kit"),
+            Some(Framework::TypeScript(TypeScriptFramework::SvelteKit))
+        );
+    }
+
     #[test]
     fn build_partial_target_with_inference() {
         let target = TargetBuilder::new()
@@ -1140,6 +2387,162 @@ mod tests {
         assert_eq!(target.framework(), None);
     }
 
+    #[test]
+    fn python_defaults_to_pip_and_others_have_no_manager() {
+        let python = Target::python_backend_fastapi().unwrap();
+        assert_eq!(python.dependency_manager(), Some(DependencyManager::Pip));
+
+        let rust = Target::rust_cli().unwrap();
+        assert_eq!(rust.dependency_manager(), None);
+    }
+
+    #[test]
+    fn dependency_manager_is_validated() {
+        for manager in DependencyManager::ALL {
+            let target = Target::builder()
+                .language(Language::Python)
+                .kind(ProjectKind::Fullstack)
+                .unwrap()
+                .framework(Framework::Python(PythonFramework::Django))
+                .unwrap()
+                .dependency_manager(*manager)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(target.dependency_manager(), Some(*manager));
+        }
+
+        let Err(err) = Target::builder()
+            .language(Language::Rust)
+            .dependency_manager(DependencyManager::Uv)
+        else {
+            panic!("uv is python only");
+        };
+        assert_eq!(err.code(), "domain.dependency_manager_language_mismatch");
+
+        assert!(
+            !DependencyManager::Poetry.is_compatible(Some(Framework::Rust(RustFramework::Axum)))
+        );
+        assert_eq!(DependencyManager::parse("UV"), Some(DependencyManager::Uv));
+        assert_eq!(DependencyManager::parse("conda"), None);
+    }
+
+    #[test]
+    fn python_layout_defaults_to_flat_and_src_needs_a_package_manager() {
+        let python = Target::builder()
+            .language(Language::Python)
+            .build()
+            .unwrap();
+        assert_eq!(python.python_layout(), Some(PythonLayout::Flat));
+        assert_eq!(Target::rust_cli().unwrap().python_layout(), None);
+
+        let src = Target::builder()
+            .language(Language::Python)
+            .dependency_manager(DependencyManager::Uv)
+            .unwrap()
+            .python_layout(PythonLayout::Src)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(src.python_layout(), Some(PythonLayout::Src));
+
+        let err = Target::builder()
+            .language(Language::Python)
+            .dependency_manager(DependencyManager::Pip)
+            .unwrap()
+            .python_layout(PythonLayout::Src)
+            .unwrap()
+            .build()
+            .unwrap_err();
+        assert_eq!(err.code(), "domain.python_layout_manager_mismatch");
+
+        let Err(err) = Target::builder()
+            .language(Language::Rust)
+            .python_layout(PythonLayout::Flat)
+        else {
+            panic!("python layouts are python only");
+        };
+        assert_eq!(err.code(), "domain.python_layout_language_mismatch");
+    }
+
+    #[test]
+    fn task_runner_is_rust_only() {
+        let target = Target::builder()
+            .language(Language::Rust)
+            .task_runner(TaskRunner::Just)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(target.task_runner(), Some(TaskRunner::Just));
+        assert_eq!(Target::rust_cli().unwrap().task_runner(), None);
+
+        let Err(err) = Target::builder()
+            .language(Language::Python)
+            .task_runner(TaskRunner::Make)
+        else {
+            panic!("task runners are rust only");
+        };
+        assert_eq!(err.code(), "domain.task_runner_language_mismatch");
+    }
+
+    #[test]
+    fn test_runner_follows_framework() {
+        let react = Target::builder()
+            .language(Language::TypeScript)
+            .kind(ProjectKind::WebFrontend)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(react.test_runner(), Some(TestRunner::Vitest));
+        assert_eq!(Target::rust_cli().unwrap().test_runner(), None);
+
+        let Err(err) = Target::builder()
+            .language(Language::TypeScript)
+            .kind(ProjectKind::WebFrontend)
+            .unwrap()
+            .framework(Framework::TypeScript(TypeScriptFramework::React))
+            .unwrap()
+            .test_runner(TestRunner::Jest)
+        else {
+            panic!("jest is not offered for vite projects");
+        };
+        assert_eq!(err.code(), "domain.test_runner_framework_mismatch");
+
+        // Checked at build time when the runner is set before the framework
+        let err = Target::builder()
+            .language(Language::TypeScript)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .test_runner(TestRunner::Vitest)
+            .unwrap()
+            .framework(Framework::TypeScript(TypeScriptFramework::NestJs))
+            .unwrap()
+            .build()
+            .unwrap_err();
+        assert_eq!(err.code(), "domain.test_runner_framework_mismatch");
+
+        let Err(err) = Target::builder()
+            .language(Language::Rust)
+            .test_runner(TestRunner::Vitest)
+        else {
+            panic!("test runners are typescript only");
+        };
+        assert_eq!(err.code(), "domain.test_runner_language_mismatch");
+    }
+
+    #[test]
+    fn vite_and_next_aliases_parse() {
+        assert_eq!(
+            Framework::parse("vite-react"),
+            Some(Framework::TypeScript(TypeScriptFramework::React))
+        );
+        assert_eq!(
+            Framework::parse("Next"),
+            Some(Framework::TypeScript(TypeScriptFramework::NextJs))
+        );
+        assert_eq!(TestRunner::parse("none"), Some(TestRunner::None));
+    }
+
     #[test]
     fn web_backend_requires_framework_if_not_inferable() {
         // This should succeed because FastAPI can be inferred
@@ -1151,4 +2554,96 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn target_round_trips_through_toml() {
+        let target = Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .framework(Framework::Python(PythonFramework::FastApi))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let toml = target.to_toml();
+        assert!(toml.contains("language = \"python\""));
+        assert!(toml.contains("framework = \"fastapi\""));
+        assert_eq!(Target::from_toml(&toml).unwrap(), target);
+    }
+
+    #[test]
+    fn target_round_trips_through_json() {
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&target).unwrap();
+        assert_eq!(serde_json::from_str::<Target>(&json).unwrap(), target);
+    }
+
+    #[test]
+    fn target_file_accepts_aliases_and_infers_the_rest() {
+        let target = Target::from_toml("language = \"py\"\ntype = \"backend\"\n").unwrap();
+
+        assert_eq!(target.language(), Language::Python);
+        assert_eq!(target.kind(), ProjectKind::WebBackend);
+        assert!(target.framework().is_some());
+    }
+
+    #[test]
+    fn target_file_rejects_unknown_values_and_keys() {
+        let err = Target::from_toml("language = \"cobol\"\n").unwrap_err();
+        assert!(matches!(err, DomainError::InvalidTargetFile { .. }));
+        assert!(err.to_string().contains("expected one of: rust"));
+
+        let err = Target::from_toml("language = \"rust\"\ncolour = \"red\"\n").unwrap_err();
+        assert!(matches!(err, DomainError::InvalidTargetFile { .. }));
+    }
+
+    #[test]
+    fn target_file_is_validated_like_the_builder() {
+        let err = Target::from_toml("language = \"rust\"\nframework = \"fastapi\"\n").unwrap_err();
+        assert!(!matches!(err, DomainError::InvalidTargetFile { .. }));
+    }
+    #[test]
+    fn features_are_sorted_deduplicated_and_saved() {
+        let target = Target::builder()
+            .language(Language::Rust)
+            .feature("docker")
+            .unwrap()
+            .feature("ci-github")
+            .unwrap()
+            .feature("docker")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(target.features(), ["ci-github", "docker"]);
+        assert!(target.has_feature("docker"));
+        assert!(!target.has_feature("pre-commit"));
+        assert!(target.to_string().ends_with("[ci-github, docker]"));
+
+        let toml = target.to_toml();
+        assert!(
+            toml.contains("features = [\"ci-github\", \"docker\"]"),
+            "{toml}"
+        );
+        assert_eq!(Target::from_toml(&toml).unwrap(), target);
+    }
+
+    #[test]
+    fn invalid_feature_names_are_rejected() {
+        for name in ["", "Docker", "ci_github", "-docker", "1password"] {
+            let err = Target::builder()
+                .language(Language::Rust)
+                .feature(name)
+                .err()
+                .unwrap_or_else(|| panic!("'{name}' was accepted"));
+            assert!(matches!(err, DomainError::InvalidFeature { .. }), "{err}");
+        }
+    }
 }
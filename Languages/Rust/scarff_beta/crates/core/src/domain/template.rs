@@ -12,8 +12,9 @@
 //!   └─ Template (declarative recipe)
 //!       ├─ TargetMatcher (when to apply)
 //!       ├─ TemplateMetadata (human info)
-//!       └─ TemplateTree (what to generate)
-//!           └─ TemplateNode[] (files & dirs)
+//!       ├─ TemplateTree (what to generate)
+//!       │   └─ TemplateNode[] (files & dirs)
+//!       └─ Hook[] (what to do once generated)
 //! ```
 //!
 //! ## Design Principles
@@ -23,14 +24,16 @@
 //! - Templates are validated before use
 //! - Matching is based on specificity scoring
 
-use std::{fmt, path::PathBuf};
+use std::{collections::BTreeSet, fmt, path::PathBuf};
 use uuid::Uuid;
 
 use crate::{
     CoreError,
     domain::{
-        Architecture, DomainError, Framework, Language, ProjectKind, ProjectStructure, Target,
+        Architecture, DomainError, Framework, Hook, Language, ProjectKind, ProjectStructure,
+        Target,
         common::{Permissions, RelativePath},
+        target::ActivelySupported,
         validator,
     },
     template::TemplateError,
@@ -224,7 +227,8 @@ impl TemplateRecord {
 ///
 /// A template defines:
 /// - **When** it applies (via [`TargetMatcher`])
-/// - **What** files and directories to create
+/// - **What** files and directories to create, some of them only for
+///   targets with a feature enabled (see [`FileSpec::feature`])
 /// - **Metadata** for discovery and documentation
 ///
 /// ## Invariants
@@ -242,6 +246,7 @@ impl TemplateRecord {
 ///         framework: None,
 ///         kind: Some(ProjectKind::Cli),
 ///         architecture: Some(Architecture::Layered),
+///         features: Vec::new(),
 ///     },
 ///     metadata: TemplateMetadata::new("Rust CLI")
 ///         .description("A simple CLI application")
@@ -263,6 +268,8 @@ pub struct Template {
     pub metadata: TemplateMetadata,
     /// Filesystem structure to generate
     pub tree: TemplateTree,
+    /// Steps to run once the project is written, in order
+    pub hooks: Vec<Hook>,
 }
 
 impl Template {
@@ -283,6 +290,7 @@ pub struct TemplateBuilder {
     matcher: Option<TargetMatcher>,
     metadata: Option<TemplateMetadata>,
     tree: TemplateTree,
+    hooks: Vec<Hook>,
 }
 
 // TODO: id and metadata are almost the same think how to align
@@ -313,6 +321,12 @@ impl TemplateBuilder {
         self
     }
 
+    /// Add a post-scaffold hook, run after the ones added before it.
+    pub fn hook(mut self, hook: Hook) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
     pub fn build(self) -> Result<Template, DomainError> {
         Ok(Template {
             id: self.id.ok_or_else(|| {
@@ -325,6 +339,7 @@ impl TemplateBuilder {
                 .metadata
                 .ok_or_else(|| DomainError::InvalidTemplate("Metadata is required".to_string()))?,
             tree: self.tree,
+            hooks: self.hooks,
         })
     }
 }
@@ -339,11 +354,14 @@ impl TemplateBuilder {
 ///
 /// - `None` → **wildcard** (matches any value)
 /// - `Some(x)` → **constraint** (must equal target field)
+/// - `features` → every one must be enabled on the target (empty matches
+///   any target)
 ///
 /// ## Specificity
 ///
 /// When multiple templates match, the most specific wins:
-/// - 4 fields set > 3 fields set > 2 fields set > 1 field set
+/// - 4 fields set > 3 fields set > 2 fields set > 1 field set, each
+///   required feature counting as a field
 /// - All else equal, first match wins (deterministic)
 ///
 /// ## Example
@@ -355,6 +373,7 @@ impl TemplateBuilder {
 ///     framework: None,
 ///     kind: None,
 ///     architecture: None,
+///     features: Vec::new(),
 /// };
 ///
 /// // Matches ONLY Rust CLI with Layered architecture
@@ -363,6 +382,7 @@ impl TemplateBuilder {
 ///     framework: None,
 ///     kind: Some(ProjectKind::Cli),
 ///     architecture: Some(Architecture::Layered),
+///     features: Vec::new(),
 /// };
 ///
 /// assert_eq!(broad.specificity(), 1);
@@ -374,6 +394,8 @@ pub struct TargetMatcher {
     pub framework: Option<Framework>,
     pub kind: Option<ProjectKind>,
     pub architecture: Option<Architecture>,
+    /// Features the target must have enabled
+    pub features: Vec<String>,
 }
 
 impl TargetMatcher {
@@ -384,7 +406,8 @@ impl TargetMatcher {
 
     /// Check whether this matcher applies to a target.
     ///
-    /// A matcher matches if all non-None fields equal the target's corresponding field.
+    /// A matcher matches if all non-None fields equal the target's
+    /// corresponding field and the target has every required feature.
     pub fn matches(&self, target: &Target) -> bool {
         self.language
             .as_ref()
@@ -398,11 +421,12 @@ impl TargetMatcher {
                 .architecture
                 .as_ref()
                 .is_none_or(|a| *a == target.architecture())
+            && self.features.iter().all(|f| target.has_feature(f))
     }
 
     /// Calculate specificity score (higher = more specific).
     ///
-    /// Specificity is the count of non-None fields.
+    /// Specificity is the count of non-None fields plus required features.
     /// Used to resolve conflicts when multiple templates match.
     pub fn specificity(&self) -> u8 {
         u8::try_from(
@@ -414,9 +438,58 @@ impl TargetMatcher {
             ]
             .into_iter()
             .filter(|b| *b)
-            .count(),
+            .count()
+                + self.features.len(),
         )
-        .expect("specificity count should fit in u8")
+        .unwrap_or(u8::MAX)
+    }
+
+    /// Whether some valid target has the language, kind, framework and
+    /// architecture this matcher asks for.
+    ///
+    /// Required features aren't considered: any target can enable a
+    /// well-formed feature.
+    pub(crate) fn is_satisfiable(&self) -> bool {
+        fn candidates<T: ActivelySupported + Copy>(wanted: Option<T>) -> Vec<T> {
+            wanted.map_or_else(|| T::ALL.to_vec(), |value| vec![value])
+        }
+
+        let frameworks = match self.framework {
+            Some(framework) => vec![Some(framework)],
+            None => std::iter::once(None)
+                .chain(Framework::ALL.iter().copied().map(Some))
+                .collect(),
+        };
+
+        let build = |language: Language,
+                     kind: ProjectKind,
+                     framework: Option<Framework>,
+                     architecture: Architecture|
+         -> Result<Target, DomainError> {
+            let mut builder = Target::builder().language(language).kind(kind)?;
+            if let Some(framework) = framework {
+                builder = builder.framework(framework)?;
+            }
+            let target = builder.architecture(architecture)?.build()?;
+            validator::validate_target(&target).map(|()| target)
+        };
+
+        let without_features = Self {
+            features: Vec::new(),
+            ..self.clone()
+        };
+        candidates(self.language).into_iter().any(|language| {
+            candidates(self.kind).into_iter().any(|kind| {
+                frameworks.iter().any(|&framework| {
+                    candidates(self.architecture)
+                        .into_iter()
+                        .any(|architecture| {
+                            build(language, kind, framework, architecture)
+                                .is_ok_and(|target| without_features.matches(&target))
+                        })
+                })
+            })
+        })
     }
 }
 
@@ -430,6 +503,7 @@ pub struct TargetMatcherBuilder {
     framework: Option<Framework>,
     kind: Option<ProjectKind>,
     architecture: Option<Architecture>,
+    features: Vec<String>,
 }
 
 impl TargetMatcherBuilder {
@@ -453,12 +527,19 @@ impl TargetMatcherBuilder {
         self
     }
 
+    /// Require a feature; may be called more than once.
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
     pub fn build(self) -> TargetMatcher {
         TargetMatcher {
             language: self.language,
             framework: self.framework,
             kind: self.kind,
             architecture: self.architecture,
+            features: self.features,
         }
     }
 }
@@ -469,50 +550,64 @@ impl TargetMatcherBuilder {
 
 /// Human-readable metadata describing a template.
 ///
-/// All fields are `&'static str` for simplicity in MVP.
-/// Post-MVP: Can be changed to `String` for dynamic templates.
+/// Owned so templates loaded at runtime (e.g. from a
+/// [`FsStore`](crate::template::FsStore)) can carry their own metadata.
 #[derive(Debug, Clone)]
 pub struct TemplateMetadata {
-    pub name: &'static str,
-    pub description: &'static str,
-    pub version: &'static str,
-    pub author: &'static str,
-    pub tags: Vec<&'static str>,
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    pub author: String,
+    pub tags: Vec<String>,
+    /// Template engine the content is written for; `None` is the built-in
+    /// `simple` engine
+    pub engine: Option<String>,
 }
 
 impl TemplateMetadata {
     /// Create new metadata with just a name.
-    pub fn new(name: &'static str) -> Self {
+    pub fn new(name: impl Into<String>) -> Self {
         Self {
-            name,
-            description: "",
-            version: "0.1.0",
-            author: "Scarff",
+            name: name.into(),
+            description: String::new(),
+            version: "0.1.0".to_string(),
+            author: "Scarff".to_string(),
             tags: Vec::new(),
+            engine: None,
         }
     }
 
     /// Set description (builder style).
-    pub fn description(mut self, description: &'static str) -> Self {
-        self.description = description;
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
         self
     }
 
     /// Set version (builder style).
-    pub fn version(mut self, version: &'static str) -> Self {
-        self.version = version;
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
         self
     }
 
     /// Set author (builder style).
-    pub fn author(mut self, author: &'static str) -> Self {
-        self.author = author;
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
         self
     }
 
     /// Set tags (builder style).
-    pub fn tags(mut self, tags: Vec<&'static str>) -> Self {
-        self.tags = tags;
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Render with the engine registered as `engine` (builder style).
+    pub fn engine(mut self, engine: impl Into<String>) -> Self {
+        self.engine = Some(engine.into());
         self
     }
 }
@@ -555,6 +650,24 @@ impl TemplateTree {
     pub fn len(&self) -> usize {
         self.nodes.len()
     }
+
+    /// Features that gate a node of the tree, sorted and unique.
+    pub fn features(&self) -> Vec<&str> {
+        let features: BTreeSet<&str> = self
+            .nodes
+            .iter()
+            .filter_map(TemplateNode::feature)
+            .collect();
+        features.into_iter().collect()
+    }
+
+    /// Check if the tree has a file at `path`.
+    pub fn has_file(&self, path: &str) -> bool {
+        self.nodes.iter().any(|node| match node {
+            TemplateNode::File(spec) => spec.path.as_path() == std::path::Path::new(path),
+            TemplateNode::Directory(_) | TemplateNode::Symlink(_) => false,
+        })
+    }
 }
 
 // ============================================================================
@@ -566,6 +679,27 @@ impl TemplateTree {
 pub enum TemplateNode {
     File(FileSpec),
     Directory(DirectorySpec),
+    Symlink(SymlinkSpec),
+}
+
+impl TemplateNode {
+    /// The feature this node is generated for, if it's optional.
+    pub fn feature(&self) -> Option<&str> {
+        match self {
+            Self::File(spec) => spec.feature.as_deref(),
+            Self::Directory(spec) => spec.feature.as_deref(),
+            Self::Symlink(spec) => spec.feature.as_deref(),
+        }
+    }
+
+    /// Path of the node, relative to the project root.
+    pub fn path(&self) -> &RelativePath {
+        match self {
+            Self::File(spec) => &spec.path,
+            Self::Directory(spec) => &spec.path,
+            Self::Symlink(spec) => &spec.path,
+        }
+    }
 }
 
 // ============================================================================
@@ -581,12 +715,19 @@ pub enum TemplateNode {
 ///     "src/main.rs",
 ///     TemplateContent::Literal(TemplateSource::Static("fn main() {}"))
 /// ).executable();
+///
+/// // Only generated for targets with the `docker` feature
+/// let dockerfile = FileSpec::new("Dockerfile", content).feature("docker");
 /// ```
 #[derive(Debug, Clone)]
 pub struct FileSpec {
     pub path: RelativePath,
     pub content: TemplateContent,
     pub permissions: Permissions,
+    /// Feature the file is generated for; `None` generates it always
+    pub feature: Option<String>,
+    /// Engine for this file instead of the template's
+    pub engine: Option<String>,
 }
 
 impl FileSpec {
@@ -596,12 +737,15 @@ impl FileSpec {
             path: path.into(),
             content,
             permissions: Permissions::read_write(),
+            feature: None,
+            engine: None,
         }
     }
 
-    /// Mark file as executable (builder style).
+    /// Mark file as executable (builder style), keeping it writable:
+    /// `rwxr-xr-x` on Unix.
     pub fn executable(mut self) -> Self {
-        self.permissions = Permissions::executable();
+        self.permissions = Permissions::full();
         self
     }
 
@@ -610,6 +754,20 @@ impl FileSpec {
         self.permissions = permissions;
         self
     }
+
+    /// Only generate the file for targets with `feature` enabled (builder
+    /// style).
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.feature = Some(feature.into());
+        self
+    }
+
+    /// Render the file with `engine` rather than the template's engine
+    /// (builder style).
+    pub fn engine(mut self, engine: impl Into<String>) -> Self {
+        self.engine = Some(engine.into());
+        self
+    }
 }
 
 // ============================================================================
@@ -621,6 +779,8 @@ impl FileSpec {
 pub struct DirectorySpec {
     pub path: RelativePath,
     pub permissions: Permissions,
+    /// Feature the directory is generated for; `None` generates it always
+    pub feature: Option<String>,
 }
 
 impl DirectorySpec {
@@ -629,6 +789,7 @@ impl DirectorySpec {
         Self {
             path: path.into(),
             permissions: Permissions::read_write(),
+            feature: None,
         }
     }
 
@@ -637,6 +798,57 @@ impl DirectorySpec {
         self.permissions = permissions;
         self
     }
+
+    /// Only generate the directory for targets with `feature` enabled
+    /// (builder style).
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.feature = Some(feature.into());
+        self
+    }
+}
+
+// ============================================================================
+// SymlinkSpec - Symbolic Link Specification
+// ============================================================================
+
+/// Declarative specification for a generated symbolic link.
+///
+/// The target is stored in the link as given, so it's relative to the
+/// link's directory, and it must stay inside the project. On platforms
+/// without symlinks the writer copies the target file instead.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// // bin/run -> ../scripts/run.sh
+/// let link = SymlinkSpec::new("bin/run", "../scripts/run.sh");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SymlinkSpec {
+    pub path: RelativePath,
+    /// Where the link points, relative to its directory (may contain
+    /// template variables)
+    pub target: String,
+    /// Feature the link is generated for; `None` generates it always
+    pub feature: Option<String>,
+}
+
+impl SymlinkSpec {
+    /// Create a link at `path` pointing to `target`.
+    pub fn new(path: impl Into<RelativePath>, target: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            target: target.into(),
+            feature: None,
+        }
+    }
+
+    /// Only generate the link for targets with `feature` enabled (builder
+    /// style).
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.feature = Some(feature.into());
+        self
+    }
 }
 
 // ============================================================================
@@ -649,7 +861,7 @@ impl DirectorySpec {
 ///
 /// - **Literal**: Static content, no variable substitution
 /// - **Parameterized**: Content with `{{VARIABLE}}` placeholders
-/// - **External**: Reference to external template engine (Tera, Handlebars)
+/// - **External**: A shared partial by id, rendered like parameterized content
 #[derive(Debug, Clone)]
 pub enum TemplateContent {
     /// Static literal content (no variables).
@@ -658,7 +870,7 @@ pub enum TemplateContent {
     /// Content requiring variable substitution.
     Parameterized(TemplateSource),
 
-    /// External template resolved by a rendering engine.
+    /// Shared partial, with its includes and variables rendered.
     External(ContentTemplateId),
 }
 
@@ -693,8 +905,8 @@ impl TemplateSource {
     }
 }
 
-/// Identifier for external template engines.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Identifier of a shared content partial (see `template::partials`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ContentTemplateId(pub &'static str);
 
 // ============================================================================
@@ -738,6 +950,7 @@ mod tests {
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree: TemplateTree::default(),
+            hooks: Vec::new(),
         });
 
         assert_ne!(record.uuid, Uuid::nil());
@@ -750,6 +963,7 @@ mod tests {
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree: TemplateTree::default(),
+            hooks: Vec::new(),
         });
 
         let result = record.validate();
@@ -838,6 +1052,27 @@ mod tests {
         assert_eq!(four.specificity(), 4);
     }
 
+    #[test]
+    fn matcher_requires_every_feature() {
+        let matcher = TargetMatcher::builder()
+            .language(Language::Rust)
+            .feature("docker")
+            .build();
+        let with_docker = Target::builder()
+            .language(Language::Rust)
+            .feature("docker")
+            .unwrap()
+            .feature("ci-github")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(!matcher.matches(&rust_cli_target()));
+        assert!(matcher.matches(&with_docker));
+        // A feature-specific template beats the plain one
+        assert_eq!(matcher.specificity(), 2);
+    }
+
     // -------------------------------------------------------------------------
     // TemplateTree Tests
     // -------------------------------------------------------------------------
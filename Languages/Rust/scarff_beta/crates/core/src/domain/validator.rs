@@ -6,7 +6,7 @@
 //! - ProjectStructure: Output structure validation
 
 use crate::domain::{
-    DomainError, ProjectStructure, Target, Template, TemplateRecord,
+    DomainError, Language, ProjectStructure, RelativePath, Target, Template, TemplateRecord,
     target::{ActivelySupported, Compatible, LangCapable},
 };
 
@@ -20,6 +20,7 @@ use crate::domain::{
 /// - Language is supported
 /// - Framework is compatible with language and kind
 /// - Architecture is compatible with framework and kind
+/// - Dependency manager is set only for Python, and fits the framework
 /// - All required fields are present
 ///
 /// # Errors
@@ -98,6 +99,71 @@ pub fn validate_target(target: &Target) -> Result<(), DomainError> {
         });
     }
 
+    // Dependency manager validation
+    match target.dependency_manager() {
+        Some(manager) if target.language() != Language::Python => {
+            return Err(DomainError::DependencyManagerLanguageMismatch {
+                manager: manager.to_string(),
+                language: target.language().to_string(),
+            });
+        }
+        Some(manager) if !manager.is_compatible(target.framework()) => {
+            return Err(DomainError::DependencyManagerFrameworkMismatch {
+                manager: manager.to_string(),
+                framework: target
+                    .framework()
+                    .map_or_else(|| "none".to_string(), |f| f.to_string()),
+            });
+        }
+        _ => {}
+    }
+
+    // Python layout validation
+    match (target.python_layout(), target.dependency_manager()) {
+        (Some(layout), _) if target.language() != Language::Python => {
+            return Err(DomainError::PythonLayoutLanguageMismatch {
+                layout: layout.to_string(),
+                language: target.language().to_string(),
+            });
+        }
+        (Some(layout), Some(manager)) if !layout.supports(manager) => {
+            return Err(DomainError::PythonLayoutManagerMismatch {
+                layout: layout.to_string(),
+                manager: manager.to_string(),
+            });
+        }
+        _ => {}
+    }
+
+    // Test runner validation
+    match target.test_runner() {
+        Some(runner) if target.language() != Language::TypeScript => {
+            return Err(DomainError::TestRunnerLanguageMismatch {
+                runner: runner.to_string(),
+                language: target.language().to_string(),
+            });
+        }
+        Some(runner) if !runner.is_compatible(target.framework()) => {
+            return Err(DomainError::TestRunnerFrameworkMismatch {
+                runner: runner.to_string(),
+                framework: target
+                    .framework()
+                    .map_or_else(|| "none".to_string(), |f| f.to_string()),
+            });
+        }
+        _ => {}
+    }
+
+    // Task runner validation
+    if let Some(runner) = target.task_runner()
+        && target.language() != Language::Rust
+    {
+        return Err(DomainError::TaskRunnerLanguageMismatch {
+            runner: runner.to_string(),
+            language: target.language().to_string(),
+        });
+    }
+
     Ok(())
 }
 
@@ -112,6 +178,7 @@ pub fn validate_target(target: &Target) -> Result<(), DomainError> {
 /// - Tree is not empty
 /// - All paths are relative
 /// - No duplicate paths
+/// - Symlinks point inside the project
 /// - Matcher is valid
 ///
 /// # Errors
@@ -158,10 +225,7 @@ pub fn validate_template(template: &Template) -> Result<(), DomainError> {
     let mut seen_paths = std::collections::HashSet::new();
 
     for node in &template.tree.nodes {
-        let path = match node {
-            crate::domain::TemplateNode::File(spec) => spec.path.as_path(),
-            crate::domain::TemplateNode::Directory(spec) => spec.path.as_path(),
-        };
+        let path = node.path().as_path();
 
         // Check for duplicates
         if !seen_paths.insert(path) {
@@ -178,6 +242,22 @@ pub fn validate_template(template: &Template) -> Result<(), DomainError> {
                 path: path.to_path_buf(),
             });
         }
+
+        // Check symlinks point inside the project
+        if let crate::domain::TemplateNode::Symlink(spec) = node
+            && crate::domain::SymlinkToCreate::new(path, &spec.target)
+                .resolved_target()
+                .is_none()
+        {
+            return Err(DomainError::InvalidTemplateWithMetadata {
+                name: template.metadata.name.clone(),
+                reason: format!(
+                    "symlink {} points to {}, outside the project",
+                    path.display(),
+                    spec.target
+                ),
+            });
+        }
     }
 
     Ok(())
@@ -219,6 +299,7 @@ pub fn validate_template_record(record: &TemplateRecord) -> Result<(), DomainErr
 /// - All paths are relative
 /// - No duplicate paths
 /// - Root path is set
+/// - Symlinks point inside the root
 ///
 /// # Errors
 ///
@@ -242,10 +323,7 @@ pub fn validate_project_structure(structure: &ProjectStructure) -> Result<(), Do
     let mut seen_paths = std::collections::HashSet::new();
 
     for entry in &structure.entries {
-        let path = match entry {
-            crate::domain::FsEntry::File(f) => &f.path,
-            crate::domain::FsEntry::Directory(d) => &d.path,
-        };
+        let path = entry.path();
 
         // Check for duplicates
         if !seen_paths.insert(path) {
@@ -255,13 +333,24 @@ pub fn validate_project_structure(structure: &ProjectStructure) -> Result<(), Do
             )));
         }
 
-        // Check path is relative
-        if path.is_absolute() {
+        // Check path stays inside the root (relative, no `..`)
+        if let Err(reason) = RelativePath::check(path) {
             return Err(DomainError::ProjectStructureError(format!(
-                "Absolute path not allowed in structure: {}",
+                "Path {} {reason} and would escape the project root",
                 path.display()
             )));
         }
+
+        // Check symlinks point inside the root too
+        if let crate::domain::FsEntry::Symlink(link) = entry
+            && link.resolved_target().is_none()
+        {
+            return Err(DomainError::ProjectStructureError(format!(
+                "Symlink {} points to {}, outside the project root",
+                path.display(),
+                link.target.display()
+            )));
+        }
     }
 
     Ok(())
@@ -330,6 +419,7 @@ mod tests {
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree: TemplateTree::new(),
+            hooks: Vec::new(),
         };
 
         let result = validate_template(&template);
@@ -351,16 +441,18 @@ mod tests {
             id: TemplateId::new("test", "0.1.0".to_string()),
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata {
-                name: "",
-                description: "",
-                version: "1.0.0",
-                author: "",
+                name: String::new(),
+                description: String::new(),
+                version: "1.0.0".to_string(),
+                author: String::new(),
                 tags: vec![],
+                engine: None,
             },
             tree: TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
                 "test.txt",
                 TemplateContent::Literal(TemplateSource::Static("")),
             ))),
+            hooks: Vec::new(),
         };
 
         let result = validate_template(&template);
@@ -0,0 +1,312 @@
+//! Workspaces: several projects in one repository.
+//!
+//! A [`WorkspaceTarget`] is a root [`Target`] of kind
+//! [`ProjectKind::Workspace`] plus named members, each with a target of its
+//! own. The root is set up for the language's workspace tool:
+//! - Rust: a Cargo workspace, members under `crates/`
+//! - TypeScript: a pnpm workspace, members under `packages/`
+//! - Python: a uv workspace, members under `packages/`
+//!
+//! ```rust
+//! use scarff_core_v1::{Language, ProjectKind, Target, WorkspaceTarget};
+//!
+//! let workspace = WorkspaceTarget::builder(Language::Rust)
+//!     .member("api", Target::rust_backend_axum()?)?
+//!     .member("cli", Target::rust_cli()?)?
+//!     .build()?;
+//!
+//! assert_eq!(workspace.root().kind(), ProjectKind::Workspace);
+//! assert_eq!(workspace.member_path("api").unwrap().to_str(), Some("crates/api"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::path::PathBuf;
+
+use crate::domain::{DependencyManager, DomainError, Language, ProjectKind, Target, TestRunner};
+
+// ============================================================================
+// WorkspaceTarget
+// ============================================================================
+
+/// A validated workspace: a root target and at least one member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceTarget {
+    root: Target,
+    members: Vec<WorkspaceMember>,
+}
+
+/// A project inside a [`WorkspaceTarget`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    name: String,
+    target: Target,
+}
+
+impl WorkspaceMember {
+    /// Name of the member, also its directory under the members directory.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Target the member is scaffolded from.
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+}
+
+impl WorkspaceTarget {
+    /// Create a builder for a workspace in `language`.
+    #[must_use]
+    pub fn builder(language: Language) -> WorkspaceTargetBuilder {
+        WorkspaceTargetBuilder {
+            language,
+            members: Vec::new(),
+        }
+    }
+
+    /// The workspace root's target (kind [`ProjectKind::Workspace`]).
+    pub fn root(&self) -> &Target {
+        &self.root
+    }
+
+    /// Language of the workspace and all its members.
+    pub fn language(&self) -> Language {
+        self.root.language()
+    }
+
+    /// Members, in the order they were added.
+    pub fn members(&self) -> &[WorkspaceMember] {
+        &self.members
+    }
+
+    /// Directory, relative to the root, holding the members.
+    pub fn members_dir(&self) -> &'static str {
+        members_dir(self.language())
+    }
+
+    /// Path of the member `name` relative to the root, if there is one.
+    pub fn member_path(&self, name: &str) -> Option<PathBuf> {
+        self.members
+            .iter()
+            .any(|m| m.name == name)
+            .then(|| PathBuf::from(self.members_dir()).join(name))
+    }
+}
+
+/// Where each workspace tool expects its members.
+fn members_dir(language: Language) -> &'static str {
+    match language {
+        Language::Rust => "crates",
+        Language::TypeScript | Language::Python => "packages",
+    }
+}
+
+// ============================================================================
+// WorkspaceTargetBuilder
+// ============================================================================
+
+/// Builder for [`WorkspaceTarget`]; members are checked as they're added.
+#[derive(Debug, Clone)]
+pub struct WorkspaceTargetBuilder {
+    language: Language,
+    members: Vec<WorkspaceMember>,
+}
+
+impl WorkspaceTargetBuilder {
+    /// Add a member named `name`, scaffolded from `target`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidWorkspace`] if the name isn't a plain
+    /// directory name or is already taken, the target is in another
+    /// language, is itself a workspace or a custom tree, or (in a Python
+    /// workspace) doesn't use uv.
+    pub fn member(mut self, name: impl Into<String>, target: Target) -> Result<Self, DomainError> {
+        let name = name.into();
+        let invalid = |reason: String| DomainError::InvalidWorkspace { reason };
+
+        if !is_valid_name(&name) {
+            return Err(invalid(format!(
+                "member name '{name}' must be a directory name of letters, digits, '-', '_' or '.'"
+            )));
+        }
+        if self.members.iter().any(|m| m.name == name) {
+            return Err(invalid(format!("member '{name}' is defined twice")));
+        }
+        if target.language() != self.language {
+            return Err(invalid(format!(
+                "member '{name}' is a {} project in a {} workspace",
+                target.language(),
+                self.language
+            )));
+        }
+        if matches!(target.kind(), ProjectKind::Workspace | ProjectKind::Custom) {
+            return Err(invalid(format!(
+                "member '{name}' can't be a {} project",
+                target.kind()
+            )));
+        }
+        if self.language == Language::Python
+            && target.dependency_manager() != Some(DependencyManager::Uv)
+        {
+            return Err(invalid(format!(
+                "member '{name}' must use uv to be part of a uv workspace"
+            )));
+        }
+
+        self.members.push(WorkspaceMember { name, target });
+        Ok(self)
+    }
+
+    /// Build the workspace, with its root target.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidWorkspace`] if no member was added.
+    pub fn build(self) -> Result<WorkspaceTarget, DomainError> {
+        if self.members.is_empty() {
+            return Err(DomainError::InvalidWorkspace {
+                reason: "a workspace needs at least one member".to_string(),
+            });
+        }
+
+        // The root only holds the members: no test runner of its own, and
+        // Python roots are uv workspaces
+        let mut root = Target::builder()
+            .language(self.language)
+            .kind(ProjectKind::Workspace)?;
+        root = match self.language {
+            Language::Python => root.dependency_manager(DependencyManager::Uv)?,
+            Language::TypeScript => root.test_runner(TestRunner::None)?,
+            Language::Rust => root,
+        };
+
+        Ok(WorkspaceTarget {
+            root: root.build()?,
+            members: self.members,
+        })
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Framework;
+    use crate::domain::TypeScriptFramework;
+
+    fn reason(err: DomainError) -> String {
+        match err {
+            DomainError::InvalidWorkspace { reason } => reason,
+            other => panic!("expected InvalidWorkspace, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn roots_are_set_up_for_the_language_tool() {
+        let rust = WorkspaceTarget::builder(Language::Rust)
+            .member("cli", Target::rust_cli().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(rust.root().kind(), ProjectKind::Workspace);
+        assert_eq!(rust.root().framework(), None);
+        assert_eq!(rust.member_path("cli"), Some(PathBuf::from("crates/cli")));
+        assert_eq!(rust.member_path("web"), None);
+
+        let web = Target::builder()
+            .language(Language::TypeScript)
+            .framework(Framework::TypeScript(TypeScriptFramework::React))
+            .unwrap()
+            .build()
+            .unwrap();
+        let ts = WorkspaceTarget::builder(Language::TypeScript)
+            .member("web", web)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(ts.root().test_runner(), Some(TestRunner::None));
+        assert_eq!(ts.members_dir(), "packages");
+
+        let api = Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .dependency_manager(DependencyManager::Uv)
+            .unwrap()
+            .build()
+            .unwrap();
+        let py = WorkspaceTarget::builder(Language::Python)
+            .member("api", api)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(py.root().dependency_manager(), Some(DependencyManager::Uv));
+    }
+
+    #[test]
+    fn members_are_validated() {
+        let rust = || WorkspaceTarget::builder(Language::Rust);
+        let cli = || Target::rust_cli().unwrap();
+
+        assert!(reason(rust().member("../cli", cli()).unwrap_err()).contains("directory name"));
+        assert!(reason(rust().member(".hidden", cli()).unwrap_err()).contains("directory name"));
+        assert!(
+            reason(
+                rust()
+                    .member("cli", cli())
+                    .unwrap()
+                    .member("cli", cli())
+                    .unwrap_err()
+            )
+            .contains("twice")
+        );
+        assert!(
+            reason(
+                rust()
+                    .member("api", Target::python_backend_fastapi().unwrap())
+                    .unwrap_err()
+            )
+            .contains("python project in a rust workspace")
+        );
+
+        // pip is the default for Python targets
+        let pip = Target::python_backend_fastapi().unwrap();
+        assert!(
+            reason(
+                WorkspaceTarget::builder(Language::Python)
+                    .member("api", pip)
+                    .unwrap_err()
+            )
+            .contains("uv")
+        );
+
+        assert!(reason(rust().build().unwrap_err()).contains("at least one member"));
+    }
+
+    #[test]
+    fn nested_workspaces_are_rejected() {
+        let inner = WorkspaceTarget::builder(Language::Rust)
+            .member("cli", Target::rust_cli().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let err = WorkspaceTarget::builder(Language::Rust)
+            .member("inner", inner.root().clone())
+            .unwrap_err();
+
+        assert!(reason(err).contains("workspace project"));
+    }
+}
@@ -5,8 +5,10 @@
 //! - **Cloneable** (for use in concurrent contexts)
 //! - **Informative** (preserve context and error chains)
 //! - **Actionable** (users can understand what went wrong)
+//! - **Machine-readable** (stable codes, serializable as [`ErrorPayload`])
 
-use std::sync::Arc;
+use serde::{Deserialize, Serialize, Serializer};
+use std::{collections::BTreeMap, sync::Arc};
 use thiserror::Error;
 
 /// Top-level error type for the core crate.
@@ -23,7 +25,7 @@ use thiserror::Error;
 /// ## Example
 ///
 /// ```rust,ignore
-/// use scarff_core::{CoreError, CoreResult};
+/// use scarff_core_v1::{CoreError, CoreResult};
 ///
 /// fn do_something() -> CoreResult<()> {
 ///     // Domain validation
@@ -49,6 +51,10 @@ pub enum CoreError {
     #[error("Scaffold error: {0}")]
     Scaffold(#[from] crate::scaffold::errors::ScaffoldError),
 
+    /// Template index errors (fetch, cache, integrity)
+    #[error("Template index error: {0}")]
+    Index(#[from] crate::index::IndexError),
+
     /// I/O errors (wrapped in Arc for Clone support)
     ///
     /// We use Arc because std::io::Error is not Clone, but we need
@@ -100,6 +106,80 @@ impl CoreError {
             _ => None,
         }
     }
+
+    /// Stable, machine-readable code, e.g. `domain.unsupported_language`.
+    ///
+    /// Codes are namespaced by layer and never change once released, so
+    /// clients can branch on them instead of parsing messages.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CoreError::Domain(e) => e.code(),
+            CoreError::Template(e) => e.code(),
+            CoreError::Scaffold(e) => e.code(),
+            CoreError::Index(e) => e.code(),
+            CoreError::Io(_) => "io.error",
+        }
+    }
+
+    /// Structured details of the failure (paths, names, ...).
+    pub fn context(&self) -> BTreeMap<&'static str, String> {
+        match self {
+            CoreError::Domain(e) => e.context(),
+            CoreError::Template(e) => e.context(),
+            CoreError::Scaffold(e) => e.context(),
+            CoreError::Index(e) => e.context(),
+            CoreError::Io(e) => BTreeMap::from([("kind", e.kind().to_string())]),
+        }
+    }
+
+    /// Machine-readable form of this error.
+    pub fn to_payload(&self) -> ErrorPayload {
+        ErrorPayload::new(self.code(), self.to_string(), self.context())
+    }
+}
+
+impl Serialize for CoreError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_payload().serialize(serializer)
+    }
+}
+
+/// Machine-readable error: stable code, display message and context map.
+///
+/// Every error type in the crate serializes to this shape:
+///
+/// ```json
+/// {
+///   "code": "scaffold.project_exists",
+///   "message": "Scaffold error: Project directory already exists: ./demo",
+///   "context": { "path": "./demo" }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorPayload {
+    /// Stable error code
+    pub code: String,
+    /// Human-readable message (the error's `Display`)
+    pub message: String,
+    /// Structured details, keyed by field name
+    pub context: BTreeMap<String, String>,
+}
+
+impl ErrorPayload {
+    pub(crate) fn new(
+        code: &str,
+        message: String,
+        context: BTreeMap<&'static str, String>,
+    ) -> Self {
+        Self {
+            code: code.to_string(),
+            message,
+            context: context
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        }
+    }
 }
 
 /// Result type for core operations.
@@ -166,6 +246,42 @@ mod tests {
         assert_eq!(extracted.kind(), std::io::ErrorKind::PermissionDenied);
     }
 
+    #[test]
+    fn core_error_delegates_code_and_context() {
+        let err: CoreError = crate::scaffold::ScaffoldError::ProjectExists {
+            path: "/tmp/demo".into(),
+        }
+        .into();
+
+        assert_eq!(err.code(), "scaffold.project_exists");
+        assert_eq!(err.context()["path"], "/tmp/demo");
+    }
+
+    #[test]
+    fn core_error_serializes_to_payload() {
+        let err: CoreError = crate::domain::DomainError::UnsupportedLanguage {
+            language: "cobol".to_string(),
+        }
+        .into();
+
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "domain.unsupported_language");
+        assert_eq!(json["message"], err.to_string());
+        assert_eq!(json["context"]["language"], "cobol");
+
+        let payload: ErrorPayload = serde_json::from_value(json).unwrap();
+        assert_eq!(payload, err.to_payload());
+    }
+
+    #[test]
+    fn io_error_has_code() {
+        let err: CoreError = std::io::Error::new(std::io::ErrorKind::NotFound, "gone").into();
+
+        assert_eq!(err.code(), "io.error");
+        assert_eq!(err.context()["kind"], "entity not found");
+    }
+
     #[test]
     fn error_display() {
         let err = CoreError::Io(Arc::new(std::io::Error::new(
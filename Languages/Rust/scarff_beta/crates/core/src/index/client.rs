@@ -0,0 +1,403 @@
+//! Fetching, caching and downloading from a template index.
+
+use sha2::{Digest, Sha256};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tracing::{debug, info, instrument, warn};
+
+use super::{IndexEntry, IndexError, TemplateIndex};
+
+/// How long a cached index is used before it is fetched again.
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Upper bound on a single download (index or template archive).
+const MAX_DOWNLOAD_BYTES: u64 = 64 * 1024 * 1024;
+
+// ============================================================================
+// Fetch
+// ============================================================================
+
+/// Retrieves the bytes behind a URL.
+///
+/// Abstracted so the client can be exercised without a network.
+pub trait Fetch: Send + Sync {
+    /// Fetch `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IndexError::Fetch` if the URL cannot be retrieved.
+    fn fetch(&self, url: &str) -> Result<Vec<u8>, IndexError>;
+}
+
+/// Fetches `http(s)://` URLs over the network and `file://` URLs from disk.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HttpFetcher;
+
+impl Fetch for HttpFetcher {
+    fn fetch(&self, url: &str) -> Result<Vec<u8>, IndexError> {
+        let failed = |reason: String| IndexError::Fetch {
+            url: url.to_string(),
+            reason,
+        };
+
+        if let Some(path) = url.strip_prefix("file://") {
+            return std::fs::read(path).map_err(|e| failed(e.to_string()));
+        }
+
+        let response = ureq::get(url)
+            .timeout(Duration::from_secs(30))
+            .call()
+            .map_err(|e| failed(e.to_string()))?;
+
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .take(MAX_DOWNLOAD_BYTES)
+            .read_to_end(&mut body)
+            .map_err(|e| failed(e.to_string()))?;
+        Ok(body)
+    }
+}
+
+// ============================================================================
+// IndexClient
+// ============================================================================
+
+/// Client for a community template index.
+///
+/// The index is cached at `cache_path` and refetched once the cache is
+/// older than `max_age` (24 hours by default). If refetching fails, a
+/// stale cache is used rather than failing.
+pub struct IndexClient {
+    url: String,
+    cache_path: PathBuf,
+    max_age: Duration,
+    fetcher: Box<dyn Fetch>,
+}
+
+impl IndexClient {
+    /// Client for the index at `url`, cached at `cache_path`.
+    pub fn new(url: impl Into<String>, cache_path: impl Into<PathBuf>) -> Self {
+        Self {
+            url: url.into(),
+            cache_path: cache_path.into(),
+            max_age: DEFAULT_MAX_AGE,
+            fetcher: Box::new(HttpFetcher),
+        }
+    }
+
+    /// Reuse a cached index younger than `max_age`.
+    #[must_use]
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Use `fetcher` instead of HTTP.
+    #[must_use]
+    pub fn fetcher(mut self, fetcher: impl Fetch + 'static) -> Self {
+        self.fetcher = Box::new(fetcher);
+        self
+    }
+
+    /// URL of the index.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Where the index is cached.
+    pub fn cache_path(&self) -> &Path {
+        &self.cache_path
+    }
+
+    /// The index, from cache if fresh, otherwise from `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IndexError::Fetch` if the index cannot be fetched and there
+    /// is no cache to fall back to, `IndexError::Parse` if it is invalid,
+    /// and `IndexError::Cache` if the cache cannot be written.
+    #[instrument(skip(self), fields(url = %self.url))]
+    pub fn index(&self) -> Result<TemplateIndex, IndexError> {
+        if self.cache_age().is_some_and(|age| age <= self.max_age) {
+            match self.read_cache() {
+                Ok(index) => {
+                    debug!(cache = %self.cache_path.display(), "Using cached index");
+                    return Ok(index);
+                }
+                Err(e) => warn!(error = %e, "Ignoring unreadable index cache"),
+            }
+        }
+        self.refresh()
+    }
+
+    /// Fetch the index from `url` and update the cache.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`IndexClient::index`].
+    #[instrument(skip(self), fields(url = %self.url))]
+    pub fn refresh(&self) -> Result<TemplateIndex, IndexError> {
+        let body = match self.fetcher.fetch(&self.url) {
+            Ok(body) => body,
+            Err(e) if self.cache_path.exists() => {
+                warn!(error = %e, "Index fetch failed, using stale cache");
+                return self.read_cache();
+            }
+            Err(e) => return Err(e),
+        };
+
+        let json = String::from_utf8(body).map_err(|e| IndexError::Parse {
+            url: self.url.clone(),
+            reason: e.to_string(),
+        })?;
+        let index = TemplateIndex::from_json(&self.url, &json)?;
+
+        self.write_cache(&json)?;
+        info!(templates = index.templates.len(), "Fetched template index");
+        Ok(index)
+    }
+
+    /// Download `entry`'s source and check it against the listed checksum.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IndexError::InvalidChecksum` if the listed checksum is not
+    /// `sha256:<hex>`, `IndexError::Fetch` if the download fails, and
+    /// `IndexError::ChecksumMismatch` if the bytes don't match.
+    #[instrument(skip(self, entry), fields(name = %entry.name))]
+    pub fn download(&self, entry: &IndexEntry) -> Result<Vec<u8>, IndexError> {
+        let expected = parse_checksum(&entry.name, &entry.checksum)?;
+        let bytes = self.fetcher.fetch(&entry.source)?;
+
+        let actual = sha256_hex(&bytes);
+        if actual != expected {
+            warn!(%expected, %actual, "Checksum mismatch");
+            return Err(IndexError::ChecksumMismatch {
+                name: entry.name.clone(),
+                expected: format!("sha256:{expected}"),
+                actual: format!("sha256:{actual}"),
+            });
+        }
+
+        debug!(bytes = bytes.len(), "Download verified");
+        Ok(bytes)
+    }
+
+    fn cache_age(&self) -> Option<Duration> {
+        let modified = std::fs::metadata(&self.cache_path).ok()?.modified().ok()?;
+        // A cache from the future (clock skew) counts as fresh
+        Some(
+            SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default(),
+        )
+    }
+
+    fn read_cache(&self) -> Result<TemplateIndex, IndexError> {
+        let json = std::fs::read_to_string(&self.cache_path).map_err(|e| self.cache_error(&e))?;
+        TemplateIndex::from_json(&self.url, &json)
+    }
+
+    fn write_cache(&self, json: &str) -> Result<(), IndexError> {
+        if let Some(parent) = self.cache_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| self.cache_error(&e))?;
+        }
+        std::fs::write(&self.cache_path, json).map_err(|e| self.cache_error(&e))
+    }
+
+    fn cache_error(&self, e: &std::io::Error) -> IndexError {
+        IndexError::Cache {
+            path: self.cache_path.clone(),
+            reason: e.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Debug for IndexClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexClient")
+            .field("url", &self.url)
+            .field("cache_path", &self.cache_path)
+            .field("max_age", &self.max_age)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The lowercase hex digest from `name`'s `sha256:<hex>` checksum.
+pub(super) fn parse_checksum(name: &str, checksum: &str) -> Result<String, IndexError> {
+    checksum
+        .strip_prefix("sha256:")
+        .filter(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_ascii_lowercase)
+        .ok_or_else(|| IndexError::InvalidChecksum {
+            name: name.to_string(),
+            checksum: checksum.to_string(),
+        })
+}
+
+pub(super) fn sha256_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+            hex
+        })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    const URL: &str = "https://index.test/index.json";
+    const ARCHIVE: &[u8] = b"template archive";
+
+    /// Serves fixed responses and counts requests
+    #[derive(Clone, Default)]
+    struct StubFetcher {
+        responses: HashMap<String, Vec<u8>>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl StubFetcher {
+        fn serving(mut self, url: &str, body: &[u8]) -> Self {
+            self.responses.insert(url.to_string(), body.to_vec());
+            self
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Fetch for StubFetcher {
+        fn fetch(&self, url: &str) -> Result<Vec<u8>, IndexError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.responses
+                .get(url)
+                .cloned()
+                .ok_or_else(|| IndexError::Fetch {
+                    url: url.to_string(),
+                    reason: "connection refused".to_string(),
+                })
+        }
+    }
+
+    fn index_json() -> String {
+        format!(
+            r#"{{"templates": [{{
+                "name": "axum-postgres",
+                "tags": ["rust", "axum"],
+                "source": "https://index.test/axum-postgres.tar.gz",
+                "checksum": "sha256:{}"
+            }}]}}"#,
+            sha256_hex(ARCHIVE)
+        )
+    }
+
+    fn entry(checksum: &str) -> IndexEntry {
+        IndexEntry {
+            name: "axum-postgres".to_string(),
+            description: String::new(),
+            tags: vec![],
+            source: "https://index.test/axum-postgres.tar.gz".to_string(),
+            checksum: checksum.to_string(),
+        }
+    }
+
+    #[test]
+    fn caches_index_until_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("scarff/index.json");
+        let fetcher = StubFetcher::default().serving(URL, index_json().as_bytes());
+        let client = IndexClient::new(URL, &cache).fetcher(fetcher.clone());
+
+        let first = client.index().unwrap();
+        let second = client.index().unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(fetcher.calls(), 1, "second read comes from cache");
+        assert!(cache.exists());
+
+        let client = client.max_age(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(10));
+        client.index().unwrap();
+        assert_eq!(fetcher.calls(), 2, "stale cache is refetched");
+    }
+
+    #[test]
+    fn falls_back_to_stale_cache_when_offline() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("index.json");
+        std::fs::write(&cache, index_json()).unwrap();
+
+        let client = IndexClient::new(URL, &cache).fetcher(StubFetcher::default());
+
+        let index = client.refresh().unwrap();
+        assert_eq!(index.templates[0].name, "axum-postgres");
+    }
+
+    #[test]
+    fn fails_without_network_or_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let client =
+            IndexClient::new(URL, dir.path().join("index.json")).fetcher(StubFetcher::default());
+
+        assert_eq!(client.index().unwrap_err().code(), "index.fetch_failed");
+    }
+
+    #[test]
+    fn download_verifies_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let fetcher = StubFetcher::default()
+            .serving(URL, index_json().as_bytes())
+            .serving("https://index.test/axum-postgres.tar.gz", ARCHIVE);
+        let client = IndexClient::new(URL, dir.path().join("index.json")).fetcher(fetcher);
+
+        let index = client.index().unwrap();
+        let bytes = client
+            .download(index.get("axum-postgres").unwrap())
+            .unwrap();
+        assert_eq!(bytes, ARCHIVE);
+
+        let tampered = entry(&format!("sha256:{}", "0".repeat(64)));
+        let err = client.download(&tampered).unwrap_err();
+        assert_eq!(err.code(), "index.checksum_mismatch");
+    }
+
+    #[test]
+    fn download_rejects_unsupported_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let client =
+            IndexClient::new(URL, dir.path().join("index.json")).fetcher(StubFetcher::default());
+
+        for checksum in ["md5:abc", "sha256:xyz", "sha256:00"] {
+            let err = client.download(&entry(checksum)).unwrap_err();
+            assert_eq!(err.code(), "index.invalid_checksum", "{checksum}");
+        }
+    }
+
+    #[test]
+    fn sha256_matches_known_digest() {
+        assert_eq!(
+            sha256_hex(b"test"),
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+    }
+}
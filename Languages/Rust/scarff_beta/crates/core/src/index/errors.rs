@@ -0,0 +1,160 @@
+//! Errors for the template index client.
+
+use serde::{Serialize, Serializer};
+use std::{collections::BTreeMap, path::PathBuf};
+use thiserror::Error;
+
+use crate::errors::ErrorPayload;
+
+/// Errors that can occur while fetching, caching or downloading from a
+/// template index.
+#[derive(Debug, Error, Clone)]
+pub enum IndexError {
+    /// The index (or a template source) could not be fetched
+    #[error("Failed to fetch {url}: {reason}")]
+    Fetch {
+        /// URL that was fetched
+        url: String,
+        /// Why the fetch failed
+        reason: String,
+    },
+
+    /// The index document is not valid
+    #[error("Invalid template index from {url}: {reason}")]
+    Parse {
+        /// URL the index came from
+        url: String,
+        /// Why the document was rejected
+        reason: String,
+    },
+
+    /// Reading or writing the local cache failed
+    #[error("Template index cache {path}: {reason}")]
+    Cache {
+        /// Cache file that was read or written
+        path: PathBuf,
+        /// Why the cache operation failed
+        reason: String,
+    },
+
+    /// A listed checksum is not `sha256:<64 hex digits>`
+    #[error("Template '{name}' has an unsupported checksum '{checksum}'")]
+    InvalidChecksum {
+        /// Template name
+        name: String,
+        /// Checksum as listed in the index
+        checksum: String,
+    },
+
+    /// A download did not match its listed checksum
+    #[error("Checksum mismatch for template '{name}': expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// Template name
+        name: String,
+        /// Checksum listed in the index
+        expected: String,
+        /// Checksum of the downloaded bytes
+        actual: String,
+    },
+
+    /// No template of that name is listed
+    #[error("Template '{name}' is not in the index")]
+    NotFound {
+        /// Template name that was looked up
+        name: String,
+    },
+
+    /// A remote template source is malformed or not allowed
+    #[error("Invalid template source '{spec}': {reason}")]
+    InvalidSource {
+        /// Source as given by the user
+        spec: String,
+        /// Why the source was rejected
+        reason: String,
+    },
+
+    /// Cloning or checking out a git template source failed
+    #[error("Git source {url}: {reason}")]
+    Git {
+        /// Repository URL
+        url: String,
+        /// Why the clone or checkout failed
+        reason: String,
+    },
+
+    /// A downloaded template archive could not be unpacked
+    #[error("Failed to unpack {url}: {reason}")]
+    Extract {
+        /// URL the archive was downloaded from
+        url: String,
+        /// Why unpacking failed
+        reason: String,
+    },
+}
+
+impl IndexError {
+    /// Stable, machine-readable code for this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            IndexError::Fetch { .. } => "index.fetch_failed",
+            IndexError::Parse { .. } => "index.invalid",
+            IndexError::Cache { .. } => "index.cache",
+            IndexError::InvalidChecksum { .. } => "index.invalid_checksum",
+            IndexError::ChecksumMismatch { .. } => "index.checksum_mismatch",
+            IndexError::NotFound { .. } => "index.not_found",
+            IndexError::InvalidSource { .. } => "index.invalid_source",
+            IndexError::Git { .. } => "index.git_failed",
+            IndexError::Extract { .. } => "index.extract_failed",
+        }
+    }
+
+    /// Structured details of the failure.
+    pub fn context(&self) -> BTreeMap<&'static str, String> {
+        let mut context = BTreeMap::new();
+        match self {
+            IndexError::Fetch { url, reason }
+            | IndexError::Parse { url, reason }
+            | IndexError::Git { url, reason }
+            | IndexError::Extract { url, reason } => {
+                context.insert("url", url.clone());
+                context.insert("reason", reason.clone());
+            }
+            IndexError::Cache { path, reason } => {
+                context.insert("path", path.display().to_string());
+                context.insert("reason", reason.clone());
+            }
+            IndexError::InvalidChecksum { name, checksum } => {
+                context.insert("name", name.clone());
+                context.insert("checksum", checksum.clone());
+            }
+            IndexError::ChecksumMismatch {
+                name,
+                expected,
+                actual,
+            } => {
+                context.insert("name", name.clone());
+                context.insert("expected", expected.clone());
+                context.insert("actual", actual.clone());
+            }
+            IndexError::NotFound { name } => {
+                context.insert("name", name.clone());
+            }
+            IndexError::InvalidSource { spec, reason } => {
+                context.insert("spec", spec.clone());
+                context.insert("reason", reason.clone());
+            }
+        }
+        context
+    }
+
+    /// Machine-readable form of this error.
+    pub fn to_payload(&self) -> ErrorPayload {
+        ErrorPayload::new(self.code(), self.to_string(), self.context())
+    }
+}
+
+impl Serialize for IndexError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_payload().serialize(serializer)
+    }
+}
@@ -0,0 +1,218 @@
+//! Community template index.
+//!
+//! A template index is a JSON document, served from a configurable URL,
+//! that lists community templates:
+//!
+//! ```json
+//! {
+//!   "templates": [
+//!     {
+//!       "name": "axum-postgres",
+//!       "description": "Axum API with sqlx and Postgres",
+//!       "tags": ["rust", "axum", "backend"],
+//!       "source": "https://example.com/axum-postgres-1.0.0.tar.gz",
+//!       "checksum": "sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! [`IndexClient`] fetches and caches the index; [`TemplateIndex::search`]
+//! powers `scarff search`; [`IndexClient::download`] fetches a template's
+//! source and refuses it unless it matches the listed checksum.
+//!
+//! Templates can also be shared straight from a git repository or a
+//! tarball: [`RemoteSource`] names one, and [`RemoteCache`] fetches it into
+//! a local cache that an `FsStore` reads.
+
+mod client;
+mod errors;
+mod remote;
+
+pub use client::{Fetch, HttpFetcher, IndexClient};
+pub use errors::IndexError;
+pub use remote::{RemoteCache, RemoteSource};
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// IndexEntry
+// ============================================================================
+
+/// A community template listed in an index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Unique template name
+    pub name: String,
+    /// One-line summary
+    #[serde(default)]
+    pub description: String,
+    /// Search tags (language, framework, kind, ...)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// URL of the template archive
+    pub source: String,
+    /// `sha256:<hex>` digest of the archive at `source`
+    pub checksum: String,
+}
+
+impl IndexEntry {
+    /// Whether every whitespace-separated term of `query` appears in the
+    /// name, description or a tag (case-insensitive).
+    pub fn matches(&self, query: &str) -> bool {
+        let name = self.name.to_lowercase();
+        let description = self.description.to_lowercase();
+
+        query.split_whitespace().all(|term| {
+            let term = term.to_lowercase();
+            name.contains(&term)
+                || description.contains(&term)
+                || self.tags.iter().any(|tag| tag.eq_ignore_ascii_case(&term))
+        })
+    }
+
+    /// How well this entry matches `query`: exact name, then tag, then
+    /// substring hits. Higher is better.
+    fn score(&self, query: &str) -> u8 {
+        let query = query.trim();
+        if self.name.eq_ignore_ascii_case(query) {
+            3
+        } else if self.tags.iter().any(|tag| tag.eq_ignore_ascii_case(query)) {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+// ============================================================================
+// TemplateIndex
+// ============================================================================
+
+/// A parsed template index.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateIndex {
+    /// Listed templates
+    pub templates: Vec<IndexEntry>,
+}
+
+impl TemplateIndex {
+    /// Parse an index document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IndexError::Parse` for malformed JSON or missing fields.
+    pub fn from_json(url: &str, json: &str) -> Result<Self, IndexError> {
+        serde_json::from_str(json).map_err(|e| IndexError::Parse {
+            url: url.to_string(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Entries matching `query`, best matches first, then by name.
+    ///
+    /// An empty query lists every entry.
+    pub fn search(&self, query: &str) -> Vec<&IndexEntry> {
+        let mut hits: Vec<_> = self
+            .templates
+            .iter()
+            .filter(|entry| entry.matches(query))
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score(query)
+                .cmp(&a.score(query))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        hits
+    }
+
+    /// The entry named `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IndexError::NotFound` if no entry has that name.
+    pub fn get(&self, name: &str) -> Result<&IndexEntry, IndexError> {
+        self.templates
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| IndexError::NotFound {
+                name: name.to_string(),
+            })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX: &str = r#"{
+        "templates": [
+            {
+                "name": "axum-postgres",
+                "description": "Axum API with sqlx and Postgres",
+                "tags": ["rust", "axum", "backend"],
+                "source": "https://example.com/axum-postgres.tar.gz",
+                "checksum": "sha256:00"
+            },
+            {
+                "name": "axum",
+                "tags": ["rust", "backend"],
+                "source": "https://example.com/axum.tar.gz",
+                "checksum": "sha256:00"
+            },
+            {
+                "name": "fastapi-celery",
+                "description": "FastAPI with a Celery worker",
+                "tags": ["python", "fastapi"],
+                "source": "https://example.com/fastapi-celery.tar.gz",
+                "checksum": "sha256:00"
+            }
+        ]
+    }"#;
+
+    fn index() -> TemplateIndex {
+        TemplateIndex::from_json("test", INDEX).unwrap()
+    }
+
+    #[test]
+    fn search_ranks_exact_name_first() {
+        let index = index();
+        let names: Vec<_> = index
+            .search("axum")
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+
+        assert_eq!(names, ["axum", "axum-postgres"]);
+    }
+
+    #[test]
+    fn search_requires_every_term() {
+        let index = index();
+
+        assert_eq!(index.search("python axum").len(), 0);
+        assert_eq!(index.search("python worker")[0].name, "fastapi-celery");
+        assert_eq!(index.search("").len(), 3);
+    }
+
+    #[test]
+    fn rejects_entries_without_checksum() {
+        let err = TemplateIndex::from_json(
+            "https://idx",
+            r#"{"templates": [{"name": "x", "source": "https://x"}]}"#,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.code(), "index.invalid");
+        assert!(err.to_string().contains("checksum"));
+    }
+
+    #[test]
+    fn get_unknown_is_not_found() {
+        assert_eq!(index().get("nope").unwrap_err().code(), "index.not_found");
+    }
+}
@@ -0,0 +1,662 @@
+//! Remote template sources: git repositories and tarballs.
+//!
+//! Teams share organization templates by publishing a template tree (the
+//! layout [`FsStore`] reads) in a git repository or as a `.tar.gz`. A
+//! [`RemoteSource`] names one; [`RemoteCache`] fetches it into a local
+//! cache directory and serves it from there:
+//!
+//! ```text
+//! git+https://git.example.com/acme/templates.git#3f2a...   git, pinned to a commit
+//! git+https://git.example.com/acme/templates.git#v1.4.0   git, at a tag
+//! git+https://git.example.com/acme/templates.git          git, default branch
+//! https://example.com/templates.tar.gz#sha256:9f86d0...   tarball with checksum
+//! ```
+//!
+//! Pinned sources (a full git commit id, or a tarball, which is pinned by
+//! its checksum) are fetched once and reused. Other git sources follow
+//! their ref, since tags and branches can move: they are fetched again on
+//! every [`RemoteCache::fetch`], falling back to the cached copy when
+//! offline.
+//!
+//! Git sources are cloned with the `git` executable and tarballs unpacked
+//! with `tar`, so both must be on `PATH` for the sources that need them.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tracing::{debug, info, instrument, warn};
+
+use super::{
+    Fetch, HttpFetcher, IndexEntry, IndexError,
+    client::{parse_checksum, sha256_hex},
+};
+use crate::{errors::CoreResult, template::FsStore};
+
+// ============================================================================
+// RemoteSource
+// ============================================================================
+
+/// Where a shared template tree is fetched from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSource {
+    /// A git repository
+    Git {
+        /// Anything `git clone` accepts
+        url: String,
+        /// Tag, branch or commit to check out; the default branch if `None`.
+        /// Only a full commit id pins the source.
+        rev: Option<String>,
+    },
+    /// A `.tar.gz` archive over HTTPS (or `file://`)
+    Tarball {
+        /// Where to download it
+        url: String,
+        /// `sha256:<hex>` digest the download must match
+        checksum: String,
+    },
+}
+
+impl RemoteSource {
+    /// A git repository at its default branch.
+    pub fn git(url: impl Into<String>) -> Self {
+        Self::Git {
+            url: url.into(),
+            rev: None,
+        }
+    }
+
+    /// A git repository pinned to `rev`.
+    pub fn git_at(url: impl Into<String>, rev: impl Into<String>) -> Self {
+        Self::Git {
+            url: url.into(),
+            rev: Some(rev.into()),
+        }
+    }
+
+    /// A tarball with its `sha256:<hex>` checksum.
+    pub fn tarball(url: impl Into<String>, checksum: impl Into<String>) -> Self {
+        Self::Tarball {
+            url: url.into(),
+            checksum: checksum.into(),
+        }
+    }
+
+    /// Parse a source spec: `git+<url>[#<rev>]` for git, or
+    /// `<url>#sha256:<hex>` for a tarball.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IndexError::InvalidSource` for a malformed spec or a tarball
+    /// URL that is neither `https://` nor `file://`, and
+    /// `IndexError::InvalidChecksum` for a checksum that isn't
+    /// `sha256:<hex>`.
+    pub fn parse(spec: &str) -> Result<Self, IndexError> {
+        let invalid = |reason: &str| IndexError::InvalidSource {
+            spec: spec.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let spec = spec.trim();
+        if let Some(rest) = spec.strip_prefix("git+") {
+            let (url, rev) = match rest.split_once('#') {
+                Some((url, rev)) => (url, Some(rev)),
+                None => (rest, None),
+            };
+            if url.is_empty() {
+                return Err(invalid("missing repository URL"));
+            }
+            if rev.is_some_and(str::is_empty) {
+                return Err(invalid("empty git ref after '#'"));
+            }
+            let source = Self::Git {
+                url: url.to_string(),
+                rev: rev.map(str::to_string),
+            };
+            source.validate()?;
+            return Ok(source);
+        }
+
+        let Some((url, checksum)) = spec.split_once('#') else {
+            return Err(invalid(
+                "expected git+<url>[#<ref>] or <url>#sha256:<hex> for a tarball",
+            ));
+        };
+        let source = Self::tarball(url, checksum);
+        source.validate()?;
+        Ok(source)
+    }
+
+    /// The source of a template listed in an index.
+    pub fn from_entry(entry: &IndexEntry) -> Self {
+        Self::tarball(&entry.source, &entry.checksum)
+    }
+
+    /// Whether the source always yields the same tree: a tarball, or a git
+    /// source at a full commit id (tags and branches can move).
+    pub fn is_pinned(&self) -> bool {
+        match self {
+            Self::Git { rev, .. } => rev.as_deref().is_some_and(is_commit_id),
+            Self::Tarball { .. } => true,
+        }
+    }
+
+    /// URL the source is fetched from.
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Git { url, .. } | Self::Tarball { url, .. } => url,
+        }
+    }
+
+    fn validate(&self) -> Result<(), IndexError> {
+        let invalid = |reason: &str| IndexError::InvalidSource {
+            spec: self.to_string(),
+            reason: reason.to_string(),
+        };
+        match self {
+            Self::Git { url, .. } if url.is_empty() => Err(invalid("missing repository URL")),
+            // Would be read by git as an option
+            Self::Git { url, .. } if url.starts_with('-') => {
+                Err(invalid("repository URL can't start with '-'"))
+            }
+            Self::Git { rev: Some(rev), .. } if rev.starts_with('-') => {
+                Err(invalid("git ref can't start with '-'"))
+            }
+            Self::Git { .. } => Ok(()),
+            Self::Tarball { url, checksum } => {
+                if !(url.starts_with("https://") || url.starts_with("file://")) {
+                    return Err(IndexError::InvalidSource {
+                        spec: self.to_string(),
+                        reason: "tarballs are only fetched over https:// (or file://)".to_string(),
+                    });
+                }
+                parse_checksum(url, checksum).map(drop)
+            }
+        }
+    }
+
+    /// Name of the source's directory in the cache.
+    fn cache_key(&self) -> String {
+        let mut key = sha256_hex(self.to_string().as_bytes());
+        key.truncate(16);
+        key
+    }
+}
+
+impl fmt::Display for RemoteSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Git {
+                url,
+                rev: Some(rev),
+            } => write!(f, "git+{url}#{rev}"),
+            Self::Git { url, rev: None } => write!(f, "git+{url}"),
+            Self::Tarball { url, checksum } => write!(f, "{url}#{checksum}"),
+        }
+    }
+}
+
+// ============================================================================
+// RemoteCache
+// ============================================================================
+
+/// Local cache of remote template sources.
+///
+/// Each source is unpacked into its own directory under `dir`, named after
+/// a hash of the source, so different refs of one repository are cached
+/// side by side.
+pub struct RemoteCache {
+    dir: PathBuf,
+    fetcher: Box<dyn Fetch>,
+}
+
+impl RemoteCache {
+    /// Cache rooted at `dir`, e.g. `$XDG_CACHE_HOME/scarff/templates`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            fetcher: Box::new(HttpFetcher),
+        }
+    }
+
+    /// Download tarballs with `fetcher` instead of HTTP.
+    #[must_use]
+    pub fn fetcher(mut self, fetcher: impl Fetch + 'static) -> Self {
+        self.fetcher = Box::new(fetcher);
+        self
+    }
+
+    /// Root of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Where `source` is (or would be) cached.
+    pub fn path_of(&self, source: &RemoteSource) -> PathBuf {
+        self.dir.join(source.cache_key())
+    }
+
+    /// The local copy of `source`, fetching it unless a pinned source is
+    /// already cached.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`RemoteCache::refresh`]; an unpinned git source that can't
+    /// be fetched falls back to its cached copy if there is one.
+    #[instrument(skip(self), fields(source = %source))]
+    pub fn fetch(&self, source: &RemoteSource) -> Result<PathBuf, IndexError> {
+        let path = self.path_of(source);
+        if path.is_dir() {
+            if source.is_pinned() {
+                debug!(cache = %path.display(), "Using cached template source");
+                return Ok(path);
+            }
+            return match self.refresh(source) {
+                Ok(path) => Ok(path),
+                Err(e) => {
+                    warn!(error = %e, "Fetch failed, using cached template source");
+                    Ok(path)
+                }
+            };
+        }
+        self.refresh(source)
+    }
+
+    /// Fetch `source` again, replacing any cached copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IndexError::InvalidSource` or `IndexError::InvalidChecksum`
+    /// for an invalid source, `IndexError::Git` if cloning or checking out
+    /// fails, `IndexError::Fetch`, `IndexError::ChecksumMismatch` or
+    /// `IndexError::Extract` if a tarball can't be downloaded, verified or
+    /// unpacked, and `IndexError::Cache` if the cache can't be written.
+    #[instrument(skip(self), fields(source = %source))]
+    pub fn refresh(&self, source: &RemoteSource) -> Result<PathBuf, IndexError> {
+        source.validate()?;
+
+        std::fs::create_dir_all(&self.dir).map_err(|e| Self::cache_error(&self.dir, &e))?;
+        let path = self.path_of(source);
+        let staging = self.dir.join(format!(
+            ".{}-{}.partial",
+            source.cache_key(),
+            std::process::id()
+        ));
+        if staging.exists() {
+            remove_dir(&staging).map_err(|e| Self::cache_error(&staging, &e))?;
+        }
+
+        let fetched = match source {
+            RemoteSource::Git { url, rev } => clone_git(url, rev.as_deref(), &staging),
+            RemoteSource::Tarball { url, checksum } => self.unpack_tarball(url, checksum, &staging),
+        };
+        if let Err(e) = fetched {
+            let _ = remove_dir(&staging);
+            return Err(e);
+        }
+
+        // Swap the new copy in only once it is complete
+        if path.exists() {
+            remove_dir(&path).map_err(|e| Self::cache_error(&path, &e))?;
+        }
+        std::fs::rename(&staging, &path).map_err(|e| Self::cache_error(&path, &e))?;
+
+        info!(cache = %path.display(), "Fetched template source");
+        Ok(path)
+    }
+
+    /// A store over the templates in `source`, fetched as by
+    /// [`RemoteCache::fetch`].
+    ///
+    /// # Errors
+    ///
+    /// `CoreError::Index` if the source can't be fetched, and the errors of
+    /// [`FsStore::open`].
+    pub fn open(&self, source: &RemoteSource) -> CoreResult<FsStore> {
+        FsStore::open(self.fetch(source)?)
+    }
+
+    fn unpack_tarball(&self, url: &str, checksum: &str, dest: &Path) -> Result<(), IndexError> {
+        let expected = parse_checksum(url, checksum)?;
+        let bytes = self.fetcher.fetch(url)?;
+
+        let actual = sha256_hex(&bytes);
+        if actual != expected {
+            warn!(%expected, %actual, "Checksum mismatch");
+            return Err(IndexError::ChecksumMismatch {
+                name: url.to_string(),
+                expected: format!("sha256:{expected}"),
+                actual: format!("sha256:{actual}"),
+            });
+        }
+
+        let failed = |reason: String| IndexError::Extract {
+            url: url.to_string(),
+            reason,
+        };
+        std::fs::create_dir_all(dest).map_err(|e| failed(e.to_string()))?;
+        let archive = dest.with_extension("tar.gz");
+        std::fs::write(&archive, &bytes).map_err(|e| failed(e.to_string()))?;
+        let unpacked = run(Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(dest));
+        let _ = std::fs::remove_file(&archive);
+        unpacked.map_err(failed)?;
+
+        hoist_single_directory(dest).map_err(|e| failed(e.to_string()))
+    }
+
+    fn cache_error(path: &Path, e: &std::io::Error) -> IndexError {
+        IndexError::Cache {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for RemoteCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteCache")
+            .field("dir", &self.dir)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Clone `url` into `dest` and check out `rev` (or the default branch),
+/// leaving just the working tree.
+fn clone_git(url: &str, rev: Option<&str>, dest: &Path) -> Result<(), IndexError> {
+    let failed = |reason: String| IndexError::Git {
+        url: url.to_string(),
+        reason,
+    };
+
+    run(Command::new("git")
+        .args(["clone", "--quiet", "--no-checkout", "--", url])
+        .arg(dest))
+    .map_err(failed)?;
+
+    let git = |args: &[&str]| {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(dest).args(args);
+        run(&mut command)
+    };
+    let commit = match rev {
+        // Tags and commits resolve as given, other branches only on the remote
+        Some(rev) => git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{rev}^{{commit}}"),
+        ])
+        .or_else(|_| {
+            git(&[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("origin/{rev}^{{commit}}"),
+            ])
+        })
+        .map_err(|_| failed(format!("no tag, branch or commit named '{rev}'")))?,
+        None => git(&["rev-parse", "HEAD"]).map_err(failed)?,
+    };
+    git(&["checkout", "--quiet", "--detach", commit.trim()]).map_err(failed)?;
+    debug!(commit = commit.trim(), "Checked out template source");
+
+    remove_dir(&dest.join(".git")).map_err(|e| failed(e.to_string()))
+}
+
+/// Whether `rev` is a full SHA-1 or SHA-256 commit id.
+fn is_commit_id(rev: &str) -> bool {
+    matches!(rev.len(), 40 | 64) && rev.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Run `command`, returning its stdout, or its stderr as the error.
+fn run(command: &mut Command) -> Result<String, String> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .map_err(|e| format!("cannot run {program}: {e}"))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!(
+            "{program} failed ({}): {}",
+            output.status,
+            stderr.trim()
+        ))
+    }
+}
+
+/// Archives usually wrap everything in one top-level directory
+/// (`templates-1.4.0/...`); make its contents the root.
+fn hoist_single_directory(dest: &Path) -> std::io::Result<()> {
+    let entries: Vec<_> = std::fs::read_dir(dest)?.collect::<Result<_, _>>()?;
+    let [only] = entries.as_slice() else {
+        return Ok(());
+    };
+    if !only.file_type()?.is_dir() {
+        return Ok(());
+    }
+
+    let inner = dest.with_extension("inner");
+    std::fs::rename(only.path(), &inner)?;
+    std::fs::remove_dir(dest)?;
+    std::fs::rename(&inner, dest)
+}
+
+fn remove_dir(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_dir_all(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const MANIFEST: &str = r#"
+        name = "acme-cli"
+        version = "1.0.0"
+
+        [matcher]
+        language = "rust"
+        type = "cli"
+    "#;
+
+    /// Serves fixed responses
+    #[derive(Default)]
+    struct StubFetcher(HashMap<String, Vec<u8>>);
+
+    impl Fetch for StubFetcher {
+        fn fetch(&self, url: &str) -> Result<Vec<u8>, IndexError> {
+            self.0.get(url).cloned().ok_or_else(|| IndexError::Fetch {
+                url: url.to_string(),
+                reason: "connection refused".to_string(),
+            })
+        }
+    }
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    /// A template tree under `root/name`, as published
+    fn publish(root: &Path, main: &str) {
+        write(&root.join("acme-cli/template.toml"), MANIFEST);
+        write(&root.join("acme-cli/files/src/main.rs"), main);
+    }
+
+    fn command(program: &str, dir: &Path, args: &[&str]) {
+        let status = Command::new(program)
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "{program} {args:?}");
+    }
+
+    fn has(program: &str) -> bool {
+        Command::new(program).arg("--version").output().is_ok()
+    }
+
+    #[test]
+    fn parses_git_and_tarball_specs() {
+        assert_eq!(
+            RemoteSource::parse("git+https://git.test/acme/templates.git#v1.4.0").unwrap(),
+            RemoteSource::git_at("https://git.test/acme/templates.git", "v1.4.0")
+        );
+        assert_eq!(
+            RemoteSource::parse("git+ssh://git@git.test/acme/templates.git").unwrap(),
+            RemoteSource::git("ssh://git@git.test/acme/templates.git")
+        );
+
+        let checksum = format!("sha256:{}", "a".repeat(64));
+        let tarball = RemoteSource::parse(&format!("https://t.test/t.tar.gz#{checksum}")).unwrap();
+        assert_eq!(
+            tarball,
+            RemoteSource::tarball("https://t.test/t.tar.gz", &checksum)
+        );
+        assert!(tarball.is_pinned());
+        assert_eq!(
+            RemoteSource::parse(&tarball.to_string()).unwrap(),
+            tarball,
+            "display round-trips"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_specs() {
+        let checksum = format!("sha256:{}", "a".repeat(64));
+        for spec in [
+            "git+".to_string(),
+            "git+https://git.test/t.git#".to_string(),
+            "git+--upload-pack=touch /tmp/pwned".to_string(),
+            "git+https://git.test/t.git#--output=/tmp/pwned".to_string(),
+            "https://t.test/t.tar.gz".to_string(),
+            format!("http://t.test/t.tar.gz#{checksum}"),
+        ] {
+            let err = RemoteSource::parse(&spec).unwrap_err();
+            assert_eq!(err.code(), "index.invalid_source", "{spec}");
+        }
+
+        let err = RemoteSource::parse("https://t.test/t.tar.gz#md5:abc").unwrap_err();
+        assert_eq!(err.code(), "index.invalid_checksum");
+    }
+
+    #[test]
+    fn only_commit_ids_pin_git_sources() {
+        let url = "https://git.test/acme/templates.git";
+
+        assert!(RemoteSource::git_at(url, "a".repeat(40)).is_pinned());
+        assert!(!RemoteSource::git_at(url, "v1.4.0").is_pinned());
+        assert!(!RemoteSource::git_at(url, "main").is_pinned());
+        assert!(!RemoteSource::git_at(url, "abc123").is_pinned());
+        assert!(!RemoteSource::git(url).is_pinned());
+    }
+
+    #[test]
+    fn option_like_git_sources_are_refused_before_running_git() {
+        let cache = RemoteCache::new("/nonexistent/cache");
+
+        let err = cache
+            .refresh(&RemoteSource::git("--upload-pack=touch pwned"))
+            .unwrap_err();
+        assert_eq!(err.code(), "index.invalid_source");
+    }
+
+    #[test]
+    fn refs_of_one_repository_are_cached_apart() {
+        let cache = RemoteCache::new("/cache");
+        let url = "https://git.test/acme/templates.git";
+
+        assert_ne!(
+            cache.path_of(&RemoteSource::git_at(url, "v1")),
+            cache.path_of(&RemoteSource::git_at(url, "v2"))
+        );
+        assert_eq!(
+            cache.path_of(&RemoteSource::git(url)),
+            cache.path_of(&RemoteSource::git(url))
+        );
+    }
+
+    #[test]
+    fn tarball_is_verified_unpacked_and_cached() {
+        if !has("tar") {
+            return;
+        }
+        let work = tempfile::tempdir().unwrap();
+        publish(&work.path().join("templates-1.0"), "fn main() {}\n");
+        command("tar", work.path(), &["-czf", "t.tar.gz", "templates-1.0"]);
+        let archive = std::fs::read(work.path().join("t.tar.gz")).unwrap();
+
+        let url = "https://t.test/t.tar.gz";
+        let fetcher = StubFetcher(HashMap::from([(url.to_string(), archive.clone())]));
+        let cache = RemoteCache::new(work.path().join("cache")).fetcher(fetcher);
+
+        let source = RemoteSource::tarball(url, format!("sha256:{}", sha256_hex(&archive)));
+        let store = cache.open(&source).unwrap();
+        assert_eq!(store.len(), 1, "top-level directory is hoisted");
+
+        // Cached: no network needed the second time
+        let offline = RemoteCache::new(work.path().join("cache")).fetcher(StubFetcher::default());
+        assert_eq!(offline.fetch(&source).unwrap(), store.root());
+
+        let tampered = RemoteSource::tarball(url, format!("sha256:{}", "0".repeat(64)));
+        let err = cache.fetch(&tampered).unwrap_err();
+        assert_eq!(err.code(), "index.checksum_mismatch");
+        assert!(!cache.path_of(&tampered).exists());
+    }
+
+    #[test]
+    fn git_source_checks_out_the_pinned_ref() {
+        if !has("git") {
+            return;
+        }
+        let work = tempfile::tempdir().unwrap();
+        let repo = work.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let mut all = vec!["-c", "user.name=t", "-c", "user.email=t@t.test"];
+            all.extend_from_slice(args);
+            command("git", &repo, &all);
+        };
+
+        git(&["init", "--quiet"]);
+        publish(&repo, "// v1\n");
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "v1"]);
+        git(&["tag", "v1"]);
+        publish(&repo, "// v2\n");
+        git(&["commit", "--quiet", "-am", "v2"]);
+
+        let cache = RemoteCache::new(work.path().join("cache"));
+        let url = repo.display().to_string();
+        let main = |path: PathBuf| {
+            std::fs::read_to_string(path.join("acme-cli/files/src/main.rs")).unwrap()
+        };
+
+        let pinned = cache.fetch(&RemoteSource::git_at(&url, "v1")).unwrap();
+        assert_eq!(main(pinned.clone()), "// v1\n");
+        assert!(!pinned.join(".git").exists());
+
+        let latest = cache.fetch(&RemoteSource::git(&url)).unwrap();
+        assert_eq!(main(latest), "// v2\n");
+
+        let err = cache
+            .fetch(&RemoteSource::git_at(&url, "no-such-ref"))
+            .unwrap_err();
+        assert_eq!(err.code(), "index.git_failed");
+    }
+}
@@ -25,7 +25,7 @@
 //! The main entry point is the [`Engine`] struct:
 //!
 //! ```rust,no_run
-//! use scarff_core::{Engine, Target, Language, ProjectKind, Architecture};
+//! use scarff_core_v1::{Engine, Target, Language, ProjectKind, Architecture};
 //!
 //! // Create an engine
 //! let engine = Engine::new();
@@ -33,8 +33,8 @@
 //! // Define what you want to scaffold
 //! let target = Target::builder()
 //!     .language(Language::Rust)
-//!     .kind(ProjectKind::Cli)
-//!     .architecture(Architecture::Layered)
+//!     .kind(ProjectKind::Cli)?
+//!     .architecture(Architecture::Layered)?
 //!     .build()?;
 //!
 //! // Generate the project
@@ -47,28 +47,37 @@
 //! For common configurations, use preset methods:
 //!
 //! ```rust,no_run
-//! use scarff_core::{Engine, Target};
+//! use scarff_core_v1::{Engine, Target};
 //!
 //! let engine = Engine::new();
 //!
 //! // Rust CLI application
-//! engine.scaffold(Target::rust_cli(), "my-cli", "./output")?;
+//! engine.scaffold(Target::rust_cli()?, "my-cli", "./output")?;
 //!
 //! // Rust web backend with Axum
-//! engine.scaffold(Target::rust_backend_axum(), "my-api", "./output")?;
+//! engine.scaffold(Target::rust_backend_axum()?, "my-api", "./output")?;
 //!
 //! // Python backend with FastAPI
-//! engine.scaffold(Target::python_backend_fastapi(), "my-api", "./output")?;
+//! engine.scaffold(Target::python_backend_fastapi()?, "my-api", "./output")?;
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 //!
+//! Named presets (a target plus default template variables) live in a
+//! [`PresetRegistry`], which can be extended at runtime with
+//! [`PresetRegistry::register`] or from a config file with
+//! [`PresetRegistry::load_toml`].
+//!
 //! ## Architecture
 //!
 //! The crate is organized into several modules:
 //!
 //! - **Domain** (`domain`): Core types like [`Target`], [`Language`], [`ProjectKind`]
-//! - **Template** (`template`): Template resolution and rendering (internal)
+//! - **Template** (`template`): Template resolution and rendering (internal);
+//!   user templates on disk are served by [`FsStore`], and content can be
+//!   rendered by other engines through [`TemplateBackend`]
 //! - **Scaffold** (`scaffold`): Orchestration and filesystem operations (internal)
+//! - **Index** (`index`): Community template index client ([`IndexClient`])
+//!   and shared templates fetched from git or tarballs ([`RemoteCache`])
 //!
 //! Most of these modules are internal implementation details. The public API
 //! is carefully curated to expose only what users need.
@@ -76,25 +85,23 @@
 //! ## Error Handling
 //!
 //! All fallible operations return [`CoreResult<T>`], which is an alias for
-//! `Result<T, CoreError>`. Errors are designed to be actionable:
+//! `Result<T, CoreError>`. Every error has a stable [`CoreError::code`] and
+//! serializes (via serde) to an [`ErrorPayload`] of code, message and
+//! context, for services that return structured failures. Errors are
+//! designed to be actionable:
 //!
 //! ```rust,no_run
-//! use scarff_core::{Target, Language};
+//! use scarff_core_v1::{Framework, Language, PythonFramework, Target};
 //!
 //! // Invalid configuration will give a clear error
 //! let result = Target::builder()
 //!     .language(Language::Rust)
-//!     .framework(Framework::Python(PythonFramework::Django))  // Wrong!
-//!     .build();
-//!
-//! match result {
-//!     Err(e) => {
-//!         eprintln!("Error: {}", e);
-//!         // Prints: "Framework django is not available for rust"
-//!     }
-//!     _ => {}
+//!     .framework(Framework::Python(PythonFramework::Django)); // Wrong!
+//!
+//! if let Err(e) = result {
+//!     eprintln!("Error: {e}");
+//!     // Prints: "Framework django is not available for rust"
 //! }
-//! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 //!
 //! ## Features
@@ -118,6 +125,7 @@
 // Private modules (not exposed to users)
 mod domain;
 mod errors;
+mod index;
 pub mod scaffold;
 mod template;
 
@@ -128,30 +136,91 @@ mod template;
 pub use domain::{
     // Core target types
     Architecture,
+    // Post-scaffold hooks
+    BuiltinHook,
+    // CI pipelines
+    CiProvider,
+    // Custom project trees
+    CustomEntry,
+    CustomTree,
+    // Python tooling
+    DependencyManager,
     // Domain errors
     DomainError,
     Framework,
     // Builder pattern
     HasLanguage,
+    Hook,
     Language,
+    // Project licenses
+    License,
     NoLanguage,
+    // File permissions (scaffold manifest)
+    Permissions,
+    // Named presets
+    Preset,
+    PresetRegistry,
     ProjectKind,
+    // Package-valid project names
+    ProjectName,
     PythonFramework,
+    // Python layouts
+    PythonLayout,
     RustFramework,
+    // Saved targets
+    TARGET_FILE,
     Target,
     TargetBuilder,
+    // Rust task files
+    TaskRunner,
+    // TypeScript tooling
+    TestRunner,
     TypeScriptFramework,
+    // Workspaces
+    WorkspaceMember,
+    WorkspaceTarget,
+    WorkspaceTargetBuilder,
 };
 
 // ============================================================================
 // Public API: Errors
 // ============================================================================
-pub use errors::{CoreError, CoreResult};
+pub use errors::{CoreError, CoreResult, ErrorPayload};
 
 // ============================================================================
 // Public API: Scaffolding
 // ============================================================================
-pub use scaffold::{Engine, ScaffoldError, TemplateInfo};
+pub use scaffold::{
+    BatchItem, BatchReport, Engine, ProjectManifest, Provenance, ScaffoldError, ScaffoldOptions,
+    ScaffoldPlan, ScaffoldReport, TemplateInfo, UpdateReport,
+};
+
+// ============================================================================
+// Public API: User Templates
+// ============================================================================
+pub use template::{
+    DirectoryManifest, FileManifest, FsStore, HookManifest, MatcherManifest, ScanReport,
+    SymlinkManifest, TemplateManifest, TemplateQuery,
+};
+
+// ============================================================================
+// Public API: Template Linting
+// ============================================================================
+pub use domain::Template;
+pub use template::{DiagnosticKind, Severity, TemplateDiagnostic};
+
+// ============================================================================
+// Public API: Template Engines
+// ============================================================================
+pub use template::{BackendError, DEFAULT_ENGINE, SimpleBackend, TemplateBackend, Variables};
+
+// ============================================================================
+// Public API: Template Index
+// ============================================================================
+pub use index::{
+    Fetch, HttpFetcher, IndexClient, IndexEntry, IndexError, RemoteCache, RemoteSource,
+    TemplateIndex,
+};
 
 // ============================================================================
 // Re-exports for convenience
@@ -163,11 +232,11 @@ pub use scaffold::{Engine, ScaffoldError, TemplateInfo};
 /// import everything they need with a single `use` statement:
 ///
 /// ```rust
-/// use scarff_core::prelude::*;
+/// use scarff_core_v1::prelude::*;
 ///
 /// let target = Target::builder()
 ///     .language(Language::Rust)
-///     .kind(ProjectKind::Cli)
+///     .kind(ProjectKind::Cli)?
 ///     .build()?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
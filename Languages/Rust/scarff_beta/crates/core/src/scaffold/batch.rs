@@ -0,0 +1,215 @@
+//! Batch scaffolding: many projects in one call.
+//!
+//! [`Engine::scaffold_many`](crate::Engine::scaffold_many) scaffolds every
+//! [`BatchItem`] in order, as [`Engine::scaffold_with`](crate::Engine::scaffold_with)
+//! would, but resolves the template of each distinct target only once. A
+//! failing item doesn't stop the batch: its error is recorded in the
+//! [`BatchReport`] next to the reports of the projects that were written.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use scarff_core_v1::{BatchItem, Engine, ScaffoldOptions, Target};
+//!
+//! let engine = Engine::new();
+//! let report = engine.scaffold_many(
+//!     [
+//!         BatchItem::new(Target::rust_cli()?, "cli-one", "./examples"),
+//!         BatchItem::new(Target::rust_cli()?, "cli-two", "./examples"),
+//!         BatchItem::new(Target::typescript_frontend_vite_react()?, "web", "./examples"),
+//!     ],
+//!     &ScaffoldOptions::new(),
+//! );
+//!
+//! assert_eq!(report.templates_resolved(), 2);
+//! for (item, error) in report.failed() {
+//!     eprintln!("{}: {error}", item.name);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    domain::Target,
+    errors::{CoreError, CoreResult},
+    scaffold::report::ScaffoldReport,
+};
+
+/// One project of a batch: what to scaffold, under which name, where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchItem {
+    /// The project configuration
+    pub target: Target,
+    /// Name of the project (used for variables and its directory)
+    pub name: String,
+    /// Directory the project is created in
+    pub output: PathBuf,
+}
+
+impl BatchItem {
+    /// Create an item scaffolding `target` as `output/name`.
+    pub fn new(target: Target, name: impl Into<String>, output: impl Into<PathBuf>) -> Self {
+        Self {
+            target,
+            name: name.into(),
+            output: output.into(),
+        }
+    }
+
+    /// Directory the project is written to.
+    pub fn project_path(&self) -> PathBuf {
+        self.output.join(&self.name)
+    }
+}
+
+impl<N, P> From<(Target, N, P)> for BatchItem
+where
+    N: Into<String>,
+    P: Into<PathBuf>,
+{
+    fn from((target, name, output): (Target, N, P)) -> Self {
+        Self::new(target, name, output)
+    }
+}
+
+/// What happened to one item of a batch.
+#[derive(Debug, Clone)]
+pub struct BatchOutcome {
+    /// The item, as given
+    pub item: BatchItem,
+    /// Its scaffold report, or why it failed
+    pub result: CoreResult<ScaffoldReport>,
+}
+
+/// Consolidated result of [`Engine::scaffold_many`](crate::Engine::scaffold_many).
+#[derive(Debug, Clone)]
+pub struct BatchReport {
+    outcomes: Vec<BatchOutcome>,
+    templates_resolved: usize,
+    cache_hits: usize,
+    duration: Duration,
+}
+
+impl BatchReport {
+    pub(crate) fn new(
+        outcomes: Vec<BatchOutcome>,
+        templates_resolved: usize,
+        cache_hits: usize,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            outcomes,
+            templates_resolved,
+            cache_hits,
+            duration,
+        }
+    }
+
+    /// Every item and its outcome, in batch order.
+    pub fn outcomes(&self) -> &[BatchOutcome] {
+        &self.outcomes
+    }
+
+    /// Items that were scaffolded, with their reports.
+    pub fn succeeded(&self) -> impl Iterator<Item = (&BatchItem, &ScaffoldReport)> {
+        self.outcomes
+            .iter()
+            .filter_map(|o| o.result.as_ref().ok().map(|report| (&o.item, report)))
+    }
+
+    /// Items that failed, with their errors.
+    pub fn failed(&self) -> impl Iterator<Item = (&BatchItem, &CoreError)> {
+        self.outcomes
+            .iter()
+            .filter_map(|o| o.result.as_ref().err().map(|error| (&o.item, error)))
+    }
+
+    /// Whether every item was scaffolded.
+    pub fn is_success(&self) -> bool {
+        self.outcomes.iter().all(|o| o.result.is_ok())
+    }
+
+    /// Report of the project written to `path` (`output/name`), if it
+    /// was scaffolded.
+    pub fn report_for(&self, path: impl AsRef<Path>) -> Option<&ScaffoldReport> {
+        self.succeeded()
+            .find(|(item, _)| item.project_path() == path.as_ref())
+            .map(|(_, report)| report)
+    }
+
+    /// Number of files written across every project.
+    pub fn files_created(&self) -> usize {
+        self.succeeded()
+            .map(|(_, report)| report.files_created())
+            .sum()
+    }
+
+    /// Total size of the files written across every project, in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.succeeded()
+            .map(|(_, report)| report.total_size())
+            .sum()
+    }
+
+    /// Number of templates resolved from the store: one per distinct
+    /// target (custom trees aren't counted).
+    pub fn templates_resolved(&self) -> usize {
+        self.templates_resolved
+    }
+
+    /// Number of items that reused a template resolved earlier in the
+    /// batch.
+    pub fn cache_hits(&self) -> usize {
+        self.cache_hits
+    }
+
+    /// How long the whole batch took.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold::ScaffoldError;
+
+    #[test]
+    fn items_convert_from_triples() {
+        let item: BatchItem = (Target::rust_cli().unwrap(), "tool", "/out").into();
+
+        assert_eq!(item.name, "tool");
+        assert_eq!(item.project_path(), Path::new("/out/tool"));
+    }
+
+    #[test]
+    fn failed_items_are_reported_apart() {
+        let item = BatchItem::new(Target::rust_cli().unwrap(), "tool", "/out");
+        let report = BatchReport::new(
+            vec![BatchOutcome {
+                item: item.clone(),
+                result: Err(ScaffoldError::ProjectExists {
+                    path: item.project_path(),
+                }
+                .into()),
+            }],
+            1,
+            0,
+            Duration::ZERO,
+        );
+
+        assert!(!report.is_success());
+        assert_eq!(report.failed().count(), 1);
+        assert_eq!(report.succeeded().count(), 0);
+        assert_eq!(report.files_created(), 0);
+        assert!(report.report_for("/out/tool").is_none());
+    }
+}
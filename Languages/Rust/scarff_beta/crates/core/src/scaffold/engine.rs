@@ -1,16 +1,41 @@
 //! Main scaffolding engine - orchestrates the entire scaffolding process.
 
-use std::path::Path;
-use tracing::{info, instrument};
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+use tracing::{info, instrument, warn};
 
 use crate::{
-    domain::{RenderContext, Target},
+    domain::{
+        DomainError, Hook, Language, Permissions, ProjectKind, ProjectName, ProjectStructure,
+        RenderContext, Target, Template, TemplateId, WorkspaceMember, WorkspaceTarget, validator,
+    },
     errors::CoreResult,
     scaffold::{
+        batch::{BatchItem, BatchOutcome, BatchReport},
+        errors::ScaffoldError,
         filesystem::RealFilesystem,
+        hooks,
+        options::ScaffoldOptions,
+        overwrite::OverwritePolicy,
+        plan::ScaffoldPlan,
+        process::{ProcessRunner, RealProcess},
+        provenance::{PROVENANCE_PATH, Provenance},
+        report::ScaffoldReport,
+        update::{self, MANIFEST_PATH, ProjectManifest, UpdateReport},
+        upgrade::{self, FileChange, Resolution, UpgradePlan, UpgradeReport},
+        vcs::{self, VcsMode},
+        verify::VerifyCommand,
         writer::{FileWriter, Writer},
     },
-    template::{InMemoryStore, Store, TemplateRenderer, TemplateResolver},
+    template::{
+        self, FsStore, InMemoryStore, LayeredStore, Store, TemplateBackend, TemplateDiagnostic,
+        TemplateQuery, TemplateRenderer, TemplateResolver,
+    },
 };
 
 /// Main scaffolding engine.
@@ -21,7 +46,7 @@ use crate::{
 /// # Examples
 ///
 /// ```rust,no_run
-/// use scarff_core::{Engine, Target, Language, ProjectKind, Architecture};
+/// use scarff_core_v1::{Engine, Target, Language, ProjectKind, Architecture};
 ///
 /// let engine = Engine::new();
 /// let target = Target::builder()
@@ -33,10 +58,24 @@ use crate::{
 /// engine.scaffold(target, "my-project", "./output")?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
+///
+/// # Concurrency
+///
+/// `Engine` is `Send + Sync` and cloning it is cheap: clones share the same
+/// template store and filesystem through an [`Arc`]. Scaffold operations
+/// hold no per-engine mutable state, so one engine (or its clones) can serve
+/// many requests in parallel as long as each targets a distinct directory.
+#[derive(Clone)]
 pub struct Engine {
+    inner: Arc<EngineInner>,
+}
+
+/// Shared, immutable state behind an [`Engine`].
+struct EngineInner {
     resolver: TemplateResolver,
     renderer: TemplateRenderer,
     writer: FileWriter,
+    process: Box<dyn ProcessRunner>,
 }
 
 impl Engine {
@@ -56,11 +95,46 @@ impl Engine {
     ///
     /// Useful for testing or when using external template sources.
     pub fn with_store(store: Box<dyn Store>) -> Self {
-        Self {
-            resolver: TemplateResolver::new(store),
-            renderer: TemplateRenderer::new(),
-            writer: FileWriter::new(Box::new(RealFilesystem)),
-        }
+        Self::from_parts(
+            TemplateResolver::new(store),
+            FileWriter::new(Box::new(RealFilesystem)),
+        )
+    }
+
+    /// Create an engine over user template stores and the built-in
+    /// templates, searched as one.
+    ///
+    /// Each source is named (its directory, or the spec of the remote
+    /// source it was fetched from) and takes precedence over the ones after
+    /// it; built-in templates, named `builtin`, come last, so a user
+    /// template with the same name and version overrides a built-in one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the built-in templates fail to load, which means the
+    /// crate was built with a broken template.
+    ///
+    /// ```rust,ignore
+    /// let shared = RemoteSource::parse("git+https://git.example.com/acme/templates.git#v1.4.0")?;
+    /// let engine = Engine::with_sources([
+    ///     ("~/.scarff/templates".to_string(), FsStore::open(home.join(".scarff/templates"))?),
+    ///     (shared.to_string(), RemoteCache::new(cache_dir).open(&shared)?),
+    /// ]);
+    /// let hits = engine.search_templates("axum")?;
+    /// ```
+    pub fn with_sources(sources: impl IntoIterator<Item = (String, FsStore)>) -> Self {
+        let builtin = InMemoryStore::new();
+        builtin
+            .load_builtin()
+            .expect("Failed to load built-in templates");
+
+        let store = sources
+            .into_iter()
+            .fold(LayeredStore::new(), |store, (name, source)| {
+                store.with_layer(name, Box::new(source))
+            })
+            .with_layer("builtin", Box::new(builtin));
+        Self::with_store(Box::new(store))
     }
 
     /// Create an engine with a custom filesystem implementation.
@@ -73,20 +147,64 @@ impl Engine {
             .load_builtin()
             .expect("Failed to load built-in templates");
 
+        Self::from_parts(
+            TemplateResolver::new(Box::new(store)),
+            FileWriter::new(filesystem),
+        )
+    }
+
+    /// Add a template engine, for templates that select it by name
+    /// (`engine = "…"` in their manifest).
+    ///
+    /// A backend registered under an existing name replaces it, including
+    /// the built-in `simple` engine. Clones of the engine share their
+    /// backends.
+    ///
+    /// ```rust,ignore
+    /// let engine = Engine::with_store(Box::new(FsStore::new("./templates")));
+    /// engine.register_backend(MiniJinja::default());
+    /// ```
+    pub fn register_backend(&self, backend: impl TemplateBackend + 'static) {
+        self.inner.renderer.register_backend(Arc::new(backend));
+    }
+
+    fn from_parts(resolver: TemplateResolver, writer: FileWriter) -> Self {
         Self {
-            resolver: TemplateResolver::new(Box::new(store)),
-            renderer: TemplateRenderer::new(),
-            writer: FileWriter::new(filesystem),
+            inner: Arc::new(EngineInner {
+                resolver,
+                renderer: TemplateRenderer::new(),
+                writer,
+                process: Box::new(RealProcess),
+            }),
         }
     }
 
+    /// Run external programs (git) through `process` instead.
+    ///
+    /// Primarily used for testing with [`MockProcess`](crate::scaffold::process::MockProcess);
+    /// call it before cloning the engine.
+    #[cfg(test)]
+    pub fn with_process(mut self, process: Box<dyn ProcessRunner>) -> Self {
+        Arc::get_mut(&mut self.inner)
+            .expect("engine is not shared yet")
+            .process = process;
+        self
+    }
+
     /// Scaffold a new project.
     ///
     /// This is the main method that coordinates the entire scaffolding process:
     ///
     /// 1. Resolves the appropriate template
-    /// 2. Renders the template with the project name
-    /// 3. Writes the result to the filesystem
+    /// 2. Renders the template with the project name, adding a `README.md`
+    ///    generated from template metadata when the template has none
+    /// 3. Writes the result to the filesystem, with a
+    ///    [`PROVENANCE_PATH`](crate::scaffold::PROVENANCE_PATH) file recording
+    ///    the template, target options and variables (custom trees get none)
+    ///
+    /// Returns a [`ScaffoldReport`] describing every entry that was written,
+    /// with a manifest of the files (size, permissions, template), how long
+    /// it took and any warnings.
     ///
     /// # Arguments
     ///
@@ -97,6 +215,9 @@ impl Engine {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The project name is not a valid package name for the target's
+    ///   language ([`ProjectName`](crate::ProjectName)); custom projects
+    ///   accept any name
     /// - No matching template is found
     /// - Template rendering fails
     /// - Filesystem operations fail
@@ -105,7 +226,7 @@ impl Engine {
     /// # Examples
     ///
     /// ```rust,no_run
-    /// # use scarff_core::{Engine, Target, Language, ProjectKind};
+    /// # use scarff_core_v1::{Engine, Target, Language, ProjectKind};
     /// # use std::path::Path;
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let engine = Engine::new();
@@ -135,27 +256,550 @@ impl Engine {
         target: Target,
         project_name: impl AsRef<str>,
         output_path: impl AsRef<Path>,
-    ) -> CoreResult<()> {
-        let project_name = project_name.as_ref();
-        let output_path = output_path.as_ref();
+    ) -> CoreResult<ScaffoldReport> {
+        self.scaffold_with(
+            target,
+            project_name,
+            output_path,
+            &ScaffoldOptions::default(),
+        )
+    }
+
+    /// Scaffold a new project with explicit options.
+    ///
+    /// Behaves like [`Engine::scaffold`], and additionally:
+    ///
+    /// 4. Runs the template's [`Hook`](crate::Hook)s in the project root,
+    ///    recording each outcome in the report. Command and script hooks
+    ///    are skipped unless allowed with
+    ///    [`ScaffoldOptions::allow_hooks`], and then run sandboxed unless
+    ///    [`ScaffoldOptions::unsafe_hooks`] is set; a blocked or failed
+    ///    hook is reported, not fatal
+    /// 5. When verification is enabled, runs the language's checker
+    ///    (`cargo check`, `tsc --noEmit`, `python -m compileall`) in the
+    ///    generated project and removes the project if the check fails
+    /// 6. With a [`VcsMode`](crate::scaffold::VcsMode) other than `Off`,
+    ///    reuses the enclosing git repository or initializes one, and
+    ///    optionally commits the scaffold with the template id and version
+    ///    in the message; the project is removed if git fails
+    ///
+    /// With an [`OverwritePolicy`] other than `Abort`, an existing project
+    /// directory is written into instead of refused. The report records what
+    /// happened to each existing file, and the directory is never removed
+    /// when verification or git fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Engine::scaffold`], plus
+    /// `ScaffoldError::VerificationFailed` with the captured checker output,
+    /// `ScaffoldError::VcsFailed` with git's error output,
+    /// or `ScaffoldError::InvalidTarget` for a `ProjectKind::Custom` target
+    /// without a [`CustomTree`](crate::CustomTree).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use scarff_core_v1::{Engine, Target, Language, ProjectKind, scaffold::ScaffoldOptions};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let engine = Engine::new();
+    /// let target = Target::builder()
+    ///     .language(Language::Rust)
+    ///     .kind(ProjectKind::Cli)?
+    ///     .build()?;
+    ///
+    /// engine.scaffold_with(
+    ///     target,
+    ///     "my-awesome-cli",
+    ///     "./projects",
+    ///     &ScaffoldOptions::new().verify(true),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, output_path, project_name, options),
+        fields(
+            target = %target,
+            project_name = %project_name.as_ref(),
+            output_path = %output_path.as_ref().display(),
+            verify = options.verify_enabled(),
+            hooks = options.hooks_allowed()
+        )
+    )]
+    pub fn scaffold_with(
+        &self,
+        target: Target,
+        project_name: impl AsRef<str>,
+        output_path: impl AsRef<Path>,
+        options: &ScaffoldOptions,
+    ) -> CoreResult<ScaffoldReport> {
+        self.scaffold_cached(
+            target,
+            project_name.as_ref(),
+            output_path.as_ref(),
+            options,
+            &mut TemplateCache::default(),
+        )
+    }
+
+    /// Scaffold many projects, resolving each distinct target's template
+    /// once.
+    ///
+    /// Every item is scaffolded in order as [`Engine::scaffold_with`]
+    /// would, with the same `options`. A failing item doesn't stop the
+    /// batch: the returned [`BatchReport`] holds each item's report or
+    /// error, the totals, and how many template resolutions were saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use scarff_core_v1::{Engine, Target, ScaffoldOptions};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let engine = Engine::new();
+    /// let batch = vec![
+    ///     (Target::rust_cli()?, "cli", "./examples"),
+    ///     (Target::typescript_frontend_vite_react()?, "web", "./examples"),
+    /// ];
+    ///
+    /// let report = engine.scaffold_many(batch, &ScaffoldOptions::new());
+    /// assert!(report.is_success());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, batch, options))]
+    pub fn scaffold_many<I>(&self, batch: I, options: &ScaffoldOptions) -> BatchReport
+    where
+        I: IntoIterator,
+        I::Item: Into<BatchItem>,
+    {
+        let started = Instant::now();
+        let mut templates = TemplateCache::default();
+
+        info!("Starting batch scaffold operation");
+        let outcomes: Vec<_> = batch
+            .into_iter()
+            .map(Into::into)
+            .map(|item: BatchItem| {
+                info!(project_name = %item.name, target = %item.target, "Scaffolding batch item");
+                let result = self.scaffold_cached(
+                    item.target.clone(),
+                    &item.name,
+                    &item.output,
+                    options,
+                    &mut templates,
+                );
+                if let Err(e) = &result {
+                    warn!(project_name = %item.name, error = %e, "Batch item failed");
+                }
+                BatchOutcome { item, result }
+            })
+            .collect();
+
+        let report = BatchReport::new(
+            outcomes,
+            templates.templates.len(),
+            templates.hits,
+            started.elapsed(),
+        );
+        info!(
+            projects = report.outcomes().len(),
+            failed = report.failed().count(),
+            templates_resolved = report.templates_resolved(),
+            duration_ms = report.duration().as_millis(),
+            "Batch scaffold completed"
+        );
+        report
+    }
+
+    /// [`Engine::scaffold_with`], resolving templates through `templates`.
+    fn scaffold_cached(
+        &self,
+        target: Target,
+        project_name: &str,
+        output_path: &Path,
+        options: &ScaffoldOptions,
+        templates: &mut TemplateCache,
+    ) -> CoreResult<ScaffoldReport> {
+        let started = Instant::now();
 
         info!("Starting scaffold operation");
 
         // TODO: validate target,
 
-        // 1. Resolve template
+        // 1-3. Resolve, build the context and render
+        let language = target.language();
+        let (mut structure, template_id) = self.build_structure(
+            target,
+            project_name,
+            output_path,
+            options,
+            RenderContext::new(project_name),
+            templates,
+        )?;
+
+        // 4-7. Write, run hooks, verify, version control
+        let project = GeneratedProject {
+            root: structure.root.clone(),
+            prefix: PathBuf::new(),
+            template: template_id.clone(),
+            hooks: std::mem::take(&mut structure.hooks),
+            verify: Some(language),
+        };
+        self.write_project(
+            &structure,
+            &[project],
+            project_name,
+            &template_id,
+            options,
+            started,
+        )
+    }
+
+    /// Scaffold a workspace: its root and every member, in one go.
+    ///
+    /// The root is rendered from the workspace template for the language
+    /// (Cargo, pnpm or uv workspace), with the member names in the
+    /// `WORKSPACE_MEMBERS` list and their directory in
+    /// `WORKSPACE_MEMBERS_DIR`. Each member is rendered from its own
+    /// target's template, as [`Engine::scaffold`] would, into
+    /// `<members dir>/<member name>`.
+    ///
+    /// Everything is written as one project: an existing directory is
+    /// handled per [`OverwritePolicy`], a failure removes the whole
+    /// workspace, and version control commits it at once. Hooks run in the
+    /// root of the project that declared them; verification checks each
+    /// member in its own directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Engine::scaffold_with`] for the root or any
+    /// member; nothing is written unless every member renders.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use scarff_core_v1::{Engine, Language, Target, WorkspaceTarget, ScaffoldOptions};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let workspace = WorkspaceTarget::builder(Language::Rust)
+    ///     .member("server", Target::rust_backend_axum()?)?
+    ///     .member("cli", Target::rust_cli()?)?
+    ///     .build()?;
+    ///
+    /// Engine::new().scaffold_workspace(&workspace, "my-app", "./projects", &ScaffoldOptions::new())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, workspace, output_path, workspace_name, options),
+        fields(
+            root = %workspace.root(),
+            members = workspace.members().len(),
+            workspace_name = %workspace_name.as_ref(),
+            output_path = %output_path.as_ref().display()
+        )
+    )]
+    pub fn scaffold_workspace(
+        &self,
+        workspace: &WorkspaceTarget,
+        workspace_name: impl AsRef<str>,
+        output_path: impl AsRef<Path>,
+        options: &ScaffoldOptions,
+    ) -> CoreResult<ScaffoldReport> {
+        let started = Instant::now();
+        let workspace_name = workspace_name.as_ref();
+        let members_dir = workspace.members_dir();
+
+        info!("Starting workspace scaffold operation");
+
+        // 1-3. The root, which lists its members
+        let context = RenderContext::new(workspace_name)
+            .with_var("WORKSPACE_MEMBERS_DIR", members_dir)
+            .with_list(
+                "WORKSPACE_MEMBERS",
+                workspace.members().iter().map(WorkspaceMember::name),
+            );
+        let mut templates = TemplateCache::default();
+        let (mut structure, template_id) = self.build_structure(
+            workspace.root().clone(),
+            workspace_name,
+            output_path.as_ref(),
+            options,
+            context,
+            &mut templates,
+        )?;
+        let mut projects = vec![GeneratedProject {
+            root: structure.root.clone(),
+            prefix: PathBuf::new(),
+            template: template_id.clone(),
+            hooks: std::mem::take(&mut structure.hooks),
+            verify: None,
+        }];
+
+        // ...then every member, nested under the members directory
+        let members_path = structure.root.join(members_dir);
+        for member in workspace.members() {
+            info!(member = member.name(), target = %member.target(), "Rendering member");
+            let (mut member_structure, member_template) = self.build_structure(
+                member.target().clone(),
+                member.name(),
+                &members_path,
+                options,
+                RenderContext::new(member.name()),
+                &mut templates,
+            )?;
+            let prefix = Path::new(members_dir).join(member.name());
+            projects.push(GeneratedProject {
+                root: member_structure.root.clone(),
+                prefix: prefix.clone(),
+                template: member_template,
+                hooks: std::mem::take(&mut member_structure.hooks),
+                verify: Some(member.target().language()),
+            });
+            structure.nest(prefix, member_structure);
+        }
+
+        // 4-7. Write, run hooks, verify, version control
+        self.write_project(
+            &structure,
+            &projects,
+            workspace_name,
+            &template_id,
+            options,
+            started,
+        )
+    }
+
+    /// Write a rendered structure, then run the hooks of and verify each
+    /// project in it, and put it under version control.
+    ///
+    /// On failure, the structure's root is removed unless it existed before.
+    /// The report's duration runs from `started`.
+    fn write_project(
+        &self,
+        structure: &ProjectStructure,
+        projects: &[GeneratedProject],
+        project_name: &str,
+        template_id: &TemplateId,
+        options: &ScaffoldOptions,
+        started: Instant,
+    ) -> CoreResult<ScaffoldReport> {
+        // 4. Write to filesystem (into an existing directory, per policy)
+        let policy = options.overwrite_policy();
+        let existing =
+            policy != OverwritePolicy::Abort && self.inner.writer.exists(&structure.root);
+        let report = if existing {
+            info!(policy = ?policy, "Writing into existing directory");
+            let entries = self.inner.writer.write_over(structure, policy)?;
+            ScaffoldReport::new(&structure.root, entries)
+        } else {
+            info!("Writing to filesystem");
+            self.inner.writer.write(structure)?;
+            ScaffoldReport::created(structure)
+        };
+
+        // Each file comes from the project whose prefix matches it most closely
+        let report = report.with_manifest(structure, |path| {
+            projects
+                .iter()
+                .filter(|project| path.starts_with(&project.prefix))
+                .max_by_key(|project| project.prefix.components().count())
+                .map_or_else(
+                    || template_id.to_string(),
+                    |project| project.template.to_string(),
+                )
+        });
+
+        // A directory that existed before holds the user's files: keep it
+        let rollback = || {
+            if existing {
+                warn!(root = %structure.root.display(), "Leaving existing directory in place");
+            } else {
+                self.inner.writer.rollback(&structure.root);
+            }
+        };
+
+        // 5. Run the templates' hooks (processes only when allowed)
+        let policy = options.hook_policy();
+        let hooks = projects
+            .iter()
+            .flat_map(|project| {
+                hooks::run(&project.hooks, &project.root, &policy, &self.inner.writer)
+            })
+            .collect();
+        let report = report.with_hooks(hooks);
+
+        // 6. Verify the generated projects compile
+        if options.verify_enabled() {
+            for project in projects {
+                let Some(language) = project.verify else {
+                    continue;
+                };
+                let command = options
+                    .custom_verify_command()
+                    .cloned()
+                    .unwrap_or_else(|| VerifyCommand::for_language(language));
+
+                info!(command = %command, root = %project.root.display(), "Verifying generated project");
+                if let Err(e) = command.run(&project.root) {
+                    rollback();
+                    return Err(e.into());
+                }
+            }
+        }
+
+        // 7. Put the project under version control (only what was written)
+        let files: Vec<_> = report.written_files().map(Path::to_path_buf).collect();
+        let message = vcs::commit_message(project_name, template_id);
+        let vcs = match vcs::apply(
+            self.inner.process.as_ref(),
+            options.vcs_mode(),
+            &structure.root,
+            &files,
+            &message,
+        ) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                rollback();
+                return Err(e.into());
+            }
+        };
+
+        let report = report.with_vcs(vcs).with_duration(started.elapsed());
+        info!(
+            files = report.files().len(),
+            bytes = report.total_size(),
+            duration_ms = report.duration().as_millis(),
+            "Scaffold process completed successfully"
+        );
+        Ok(report)
+    }
+
+    /// Plan a scaffold without touching the filesystem.
+    ///
+    /// Renders the project exactly as [`Engine::scaffold`] would and reports,
+    /// per entry, whether it would be created, overwritten or skipped given
+    /// what is already on disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns the template resolution and rendering errors of
+    /// [`Engine::scaffold`].
+    pub fn plan(
+        &self,
+        target: Target,
+        project_name: impl AsRef<str>,
+        output_path: impl AsRef<Path>,
+    ) -> CoreResult<ScaffoldPlan> {
+        self.plan_with(
+            target,
+            project_name,
+            output_path,
+            &ScaffoldOptions::default(),
+        )
+    }
+
+    /// Plan a scaffold with explicit options, see [`Engine::plan`].
+    ///
+    /// Verification and version control are not run; only the options that
+    /// change the rendered structure (custom tree, variables, README) apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Engine::plan`], plus
+    /// `ScaffoldError::InvalidTarget` for a `ProjectKind::Custom` target
+    /// without a [`CustomTree`](crate::CustomTree).
+    #[instrument(
+        skip(self, output_path, project_name, options),
+        fields(
+            target = %target,
+            project_name = %project_name.as_ref(),
+            output_path = %output_path.as_ref().display()
+        )
+    )]
+    pub fn plan_with(
+        &self,
+        target: Target,
+        project_name: impl AsRef<str>,
+        output_path: impl AsRef<Path>,
+        options: &ScaffoldOptions,
+    ) -> CoreResult<ScaffoldPlan> {
+        let project_name = project_name.as_ref();
+        let (structure, _) = self.build_structure(
+            target,
+            project_name,
+            output_path.as_ref(),
+            options,
+            RenderContext::new(project_name),
+            &mut TemplateCache::default(),
+        )?;
+
+        info!("Comparing planned structure with the filesystem");
+        Ok(ScaffoldPlan::compare(&structure, &self.inner.writer))
+    }
+
+    /// Resolve the template for `target` and render the project structure,
+    /// without writing it.
+    ///
+    /// `context` holds the project name and any variables of the caller;
+    /// the target, template and option variables are added to it. Templates
+    /// already in `templates` aren't resolved again.
+    fn build_structure(
+        &self,
+        target: Target,
+        project_name: &str,
+        output_path: &Path,
+        options: &ScaffoldOptions,
+        context: RenderContext,
+        templates: &mut TemplateCache,
+    ) -> CoreResult<(ProjectStructure, TemplateId)> {
+        // Custom trees aren't packages: any directory name will do
+        if target.kind() != ProjectKind::Custom {
+            ProjectName::new(project_name, target.language())?;
+        }
+
+        // 1. Resolve template (custom projects bring their own tree)
         info!("Resolving template");
-        let template = self.resolver.resolve(&target)?;
+        let template = if target.kind() == ProjectKind::Custom {
+            let tree = options.tree().ok_or_else(|| {
+                ScaffoldError::invalid_target(
+                    "custom projects require a tree (ScaffoldOptions::custom_tree)",
+                    None::<DomainError>,
+                )
+            })?;
+            validator::validate_target(&target)?;
+            tree.to_template()?
+        } else {
+            templates.resolve(&self.inner.resolver, &target)?
+        };
         info!(template_id = %template.metadata.name, "Template resolved");
 
+        // Version-controlled projects ignore their language's build output
+        let template = if options.vcs_mode() != VcsMode::Off && target.kind() != ProjectKind::Custom
+        {
+            template::with_gitignore(template, target.language())
+        } else {
+            template
+        };
+
         // 2. Create render context
         // TODO: based on language; render project_name to naming standard and all file/directory names as well
-        let context = RenderContext::new(project_name);
+        let mut context = context
+            .with_target(&target)
+            .with_template(&template.metadata);
+        for (key, value) in options.variables() {
+            context.set_var(key, value);
+        }
 
-        // 3. Render template to project structure
+        // 3. Render template to project structure (custom trees are exact)
         info!("Rendering template");
-        let project_path = output_path.join(project_name);
-        let structure = self.renderer.render(&template, &context, project_path)?;
+        let template_id = template.id.clone();
+        let structure = self.render_project(
+            template,
+            &target,
+            context,
+            output_path.join(project_name),
+            options.readme_enabled(),
+        )?;
 
         info!(
             files = structure.file_count(),
@@ -163,125 +807,730 @@ impl Engine {
             "Template rendered successfully"
         );
 
-        // 4. Write to filesystem
-        info!("Writing to filesystem");
-        self.writer.write(&structure)?;
-
-        info!("Scaffold process completed successfully");
-        Ok(())
+        Ok((structure, template_id))
     }
 
-    /// Get information about available templates.
+    /// Render `template` into the structure of the project at `project_path`.
     ///
-    /// Returns metadata about all templates that can be used for scaffolding.
-    pub fn list_templates(&self) -> CoreResult<Vec<TemplateInfo>> {
-        let templates = self.resolver.list()?;
+    /// Template-based targets get their tooling, test runner, task runner,
+    /// license and CI files, a generated README (when `readme` is set), a provenance
+    /// file and a manifest hashing every file; custom trees are rendered exactly.
+    fn render_project(
+        &self,
+        template: Template,
+        target: &Target,
+        context: RenderContext,
+        project_path: PathBuf,
+        readme: bool,
+    ) -> CoreResult<ProjectStructure> {
+        if target.kind() == ProjectKind::Custom {
+            return self
+                .inner
+                .renderer
+                .render(&template, &context, project_path);
+        }
 
-        Ok(templates
-            .iter()
-            .map(|t| TemplateInfo {
-                id: format!("{}@{}", t.metadata.name, t.metadata.version),
-                name: t.metadata.name.to_string(),
-                description: t.metadata.description.to_string(),
-                language: t
-                    .matcher
-                    .language
-                    .map_or_else(|| "any".to_string(), |l| l.to_string()),
-                kind: t
-                    .matcher
-                    .kind
-                    .map_or_else(|| "any".to_string(), |k| k.to_string()),
-                architecture: t
-                    .matcher
-                    .architecture
-                    .map_or_else(|| "any".to_string(), |a| a.to_string()),
-                framework: t.matcher.framework.map(|f| f.to_string()),
-            })
-            .collect())
+        // Python and TypeScript targets get their tooling files
+        let (template, context) = template::with_tooling(template, context, target);
+        let (template, context) = template::with_test_runner(template, context, target);
+        let (template, context) = template::with_task_runner(template, context, target);
+        let (template, context) = template::with_license(template, context, target);
+        let (template, context) = template::with_ci(template, context, target);
+
+        let mut structure = if readme {
+            self.inner
+                .renderer
+                .render_with_readme(&template, target, &context, project_path)?
+        } else {
+            self.inner
+                .renderer
+                .render(&template, &context, project_path)?
+        };
+
+        let provenance = Provenance::new(&template.id, target, &context);
+        structure.add_file(
+            PROVENANCE_PATH,
+            provenance.to_toml(),
+            Permissions::read_write(),
+        );
+        let manifest = ProjectManifest::new(&template.id, &structure);
+        structure.add_file(MANIFEST_PATH, manifest.to_json(), Permissions::read_write());
+
+        Ok(structure)
     }
 
-    /// Find templates that match a given target.
+    /// Read how the project at `project_dir` was generated.
     ///
-    /// Useful for showing users what templates are available for their configuration.
-    pub fn find_templates(&self, target: &Target) -> CoreResult<Vec<TemplateInfo>> {
-        let templates = self.resolver.find_all(target)?;
+    /// # Errors
+    ///
+    /// Returns `ScaffoldError::ProvenanceMissing` if the project has no
+    /// provenance file, or `ScaffoldError::ProvenanceInvalid` if it can't
+    /// be read or parsed.
+    pub fn read_provenance(&self, project_dir: impl AsRef<Path>) -> CoreResult<Provenance> {
+        let path = project_dir.as_ref().join(PROVENANCE_PATH);
 
-        Ok(templates
-            .iter()
-            .map(|t| TemplateInfo {
-                id: format!("{}@{}", t.metadata.name, t.metadata.version),
-                name: t.metadata.name.to_string(),
-                description: t.metadata.description.to_string(),
-                language: t
-                    .matcher
-                    .language
-                    .map_or_else(|| "any".to_string(), |l| l.to_string()),
-                kind: t
-                    .matcher
-                    .kind
-                    .map_or_else(|| "any".to_string(), |k| k.to_string()),
-                architecture: t
-                    .matcher
-                    .architecture
-                    .map_or_else(|| "any".to_string(), |a| a.to_string()),
-                framework: t.matcher.framework.map(|f| f.to_string()),
-            })
-            .collect())
-    }
-}
+        let content = self.inner.writer.read(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => ScaffoldError::ProvenanceMissing { path: path.clone() },
+            _ => ScaffoldError::ProvenanceInvalid {
+                path: path.clone(),
+                reason: e.to_string(),
+            },
+        })?;
 
-impl Default for Engine {
-    fn default() -> Self {
-        Self::new()
+        Ok(Provenance::from_toml(&path, &content)?)
     }
-}
 
-/// Information about a template.
-///
-/// This is a simplified view of template metadata for display purposes.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct TemplateInfo {
-    pub id: String,
-    pub name: String,
-    pub description: String,
-    pub language: String,
-    pub kind: String,
-    pub architecture: String,
-    pub framework: Option<String>,
-}
+    /// Plan upgrading the project at `project_dir` to the latest version of
+    /// the template it was generated from.
+    ///
+    /// The project is re-rendered with its recorded target and variables;
+    /// see [`upgrade`](crate::scaffold::upgrade) for how files are
+    /// classified. A `README.md` is rendered only if the project has one.
+    /// Nothing is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Engine::read_provenance`],
+    /// `ScaffoldError::ProvenanceInvalid` if the recorded target is no
+    /// longer valid, or `ScaffoldError::TemplateResolution` if the store
+    /// no longer has the template.
+    pub fn plan_upgrade(&self, project_dir: impl AsRef<Path>) -> CoreResult<UpgradePlan> {
+        let project_dir = project_dir.as_ref();
+        let provenance = self.read_provenance(project_dir)?;
+        let target = provenance.target()?;
+        let from = provenance.template_id();
 
-// ============================================================================
-// Tests
-// ============================================================================
+        let templates = self.inner.resolver.find_all(&target)?;
+        let latest = upgrade::latest(&templates, &provenance.template.name).ok_or_else(|| {
+            ScaffoldError::TemplateResolution {
+                target: provenance.template_id().to_string(),
+                suggestions: vec![],
+            }
+        })?;
+        let base = templates.iter().find(|t| t.id == from);
+        info!(from = %from, to = %latest.id, "Planning upgrade");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{
-        domain::{Architecture, Language, ProjectKind},
-        scaffold::filesystem::MockFilesystem,
-    };
+        let readme = self
+            .inner
+            .writer
+            .read(&project_dir.join("README.md"))
+            .is_ok();
+        let render = |template: &Template| {
+            let mut context = RenderContext::new(
+                provenance
+                    .variables
+                    .get("PROJECT_NAME")
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            for (key, value) in &provenance.variables {
+                context.set_var(key, value);
+            }
+            let context = context
+                .with_target(&target)
+                .with_template(&template.metadata);
+            self.render_project(
+                template.clone(),
+                &target,
+                context,
+                project_dir.to_path_buf(),
+                readme,
+            )
+        };
 
-    #[test]
-    fn engine_new_loads_builtin_templates() {
-        let engine = Engine::new();
-        let templates = engine.list_templates().unwrap();
+        let structure = render(latest)?;
+        let base = base.map(render).transpose()?;
+        let base_content = |path: &Path| {
+            base.as_ref()
+                .and_then(|s| s.files().find(|f| f.path == path))
+                .map(|f| f.content.as_str())
+        };
 
-        assert!(!templates.is_empty(), "Should have built-in templates");
+        let mut changes = Vec::new();
+        let mut provenance_file = String::new();
+        let mut manifest_file = String::new();
+        for file in structure.files() {
+            if file.path == Path::new(PROVENANCE_PATH) {
+                provenance_file.clone_from(&file.content);
+            } else if file.path == Path::new(MANIFEST_PATH) {
+                manifest_file.clone_from(&file.content);
+            }
+            if !upgrade::is_managed(&file.path) {
+                continue;
+            }
+            let current = self.inner.writer.read(&project_dir.join(&file.path)).ok();
+            let kind =
+                upgrade::classify(current.as_deref(), base_content(&file.path), &file.content);
+            if let Some(kind) = kind {
+                changes.push(FileChange {
+                    path: file.path.clone(),
+                    kind,
+                    current,
+                    content: file.content.clone(),
+                    permissions: file.permissions,
+                });
+            }
+        }
+
+        Ok(UpgradePlan {
+            project_dir: project_dir.to_path_buf(),
+            from,
+            to: latest.id.clone(),
+            changes,
+            provenance: provenance_file,
+            manifest: manifest_file,
+        })
     }
 
-    #[test]
-    fn engine_scaffolds_rust_cli_project() {
-        let mock_fs = Box::new(MockFilesystem::new());
-        let fs_clone = mock_fs.clone();
-        let engine = Engine::with_filesystem(mock_fs);
+    /// Apply an [`UpgradePlan`].
+    ///
+    /// Added and updated files are written; `resolve` decides each
+    /// conflict. The provenance file and manifest are then updated to the
+    /// latest version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScaffoldError::UnsafePath` if a change would be written
+    /// outside the project (e.g. through a symlink), or
+    /// `ScaffoldError::FilesystemWrite` if a file can't be written.
+    /// Files written before the failure are kept.
+    pub fn apply_upgrade(
+        &self,
+        plan: &UpgradePlan,
+        mut resolve: impl FnMut(&FileChange) -> Resolution,
+    ) -> CoreResult<UpgradeReport> {
+        let writer = &self.inner.writer;
+        let mut report = UpgradeReport::default();
 
-        let target = Target::builder()
-            .language(Language::Rust)
-            .kind(ProjectKind::Cli)
-            .unwrap()
-            .architecture(Architecture::Layered)
-            .unwrap()
+        for change in &plan.changes {
+            let resolution = match change.kind {
+                upgrade::ChangeKind::Added | upgrade::ChangeKind::Updated => Resolution::Accept,
+                upgrade::ChangeKind::Conflict => resolve(change),
+            };
+            match resolution {
+                Resolution::Accept => {
+                    writer.write_file_in(
+                        &plan.project_dir,
+                        &change.path,
+                        &change.content,
+                        change.permissions,
+                    )?;
+                    report.applied.push(change.path.clone());
+                }
+                Resolution::Reject => {
+                    let path = change.reject_path();
+                    writer.write_file_in(
+                        &plan.project_dir,
+                        &path,
+                        &change.diff(),
+                        Permissions::read_write(),
+                    )?;
+                    report.rejected.push(path);
+                }
+                Resolution::Skip => report.skipped.push(change.path.clone()),
+            }
+        }
+
+        writer.write_file_in(
+            &plan.project_dir,
+            Path::new(PROVENANCE_PATH),
+            &plan.provenance,
+            Permissions::read_write(),
+        )?;
+        writer.write_file_in(
+            &plan.project_dir,
+            Path::new(MANIFEST_PATH),
+            &plan.manifest,
+            Permissions::read_write(),
+        )?;
+
+        info!(
+            applied = report.applied.len(),
+            rejected = report.rejected.len(),
+            skipped = report.skipped.len(),
+            "Upgrade applied"
+        );
+        Ok(report)
+    }
+
+    /// Read the manifest of the files generated for the project at
+    /// `project_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScaffoldError::ManifestMissing` if the project has no
+    /// manifest, or `ScaffoldError::ManifestInvalid` if it can't be read or
+    /// parsed.
+    pub fn read_manifest(&self, project_dir: impl AsRef<Path>) -> CoreResult<ProjectManifest> {
+        let path = project_dir.as_ref().join(MANIFEST_PATH);
+
+        let content = self.inner.writer.read(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => ScaffoldError::ManifestMissing { path: path.clone() },
+            _ => ScaffoldError::ManifestInvalid {
+                path: path.clone(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        Ok(ProjectManifest::from_json(&path, &content)?)
+    }
+
+    /// Re-scaffold the project at `project_dir` for `target`, keeping the
+    /// user's edits.
+    ///
+    /// The project is re-rendered with the variables recorded in its
+    /// provenance; a `README.md` and `.gitignore` are rendered only if the
+    /// project has them. Files the user hasn't touched since the last
+    /// scaffold (per the hashes in its manifest) are rewritten, missing ones
+    /// created, and edited ones the render changes are reported as
+    /// conflicts and left alone; see [`update`](crate::scaffold::update).
+    /// The provenance and manifest are then rewritten. Directories, symlinks
+    /// and files the render no longer has are left as they are.
+    ///
+    /// Updating twice in a row leaves every file as it is the second time.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Engine::read_manifest`], the template
+    /// resolution and rendering errors of [`Engine::scaffold`],
+    /// `ScaffoldError::UnsafePath` if a file would be written outside the
+    /// project, or `ScaffoldError::FilesystemWrite` if a file can't be
+    /// written. Files written before the failure are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use scarff_core_v1::{Engine, Target};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let report = Engine::new().update(Target::rust_cli()?, "./projects/my-cli")?;
+    /// for conflict in &report.conflicts {
+    ///     println!("{}", conflict.diff());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, project_dir),
+        fields(target = %target, project_dir = %project_dir.as_ref().display())
+    )]
+    pub fn update(
+        &self,
+        target: Target,
+        project_dir: impl AsRef<Path>,
+    ) -> CoreResult<UpdateReport> {
+        let project_dir = project_dir.as_ref();
+        let writer = &self.inner.writer;
+        let mut manifest = self.read_manifest(project_dir)?;
+        let from = manifest.template_id();
+
+        info!(from = %from, "Updating project");
+
+        let (structure, to) = self.rerender(target, project_dir, &manifest)?;
+
+        let mut report = UpdateReport {
+            project_dir: project_dir.to_path_buf(),
+            from,
+            to: to.clone(),
+            created: Vec::new(),
+            updated: Vec::new(),
+            unchanged: Vec::new(),
+            conflicts: Vec::new(),
+        };
+        for file in structure.files() {
+            if file.path == Path::new(PROVENANCE_PATH) {
+                writer.write_file_in(project_dir, &file.path, &file.content, file.permissions)?;
+                continue;
+            }
+            if !upgrade::is_managed(&file.path) {
+                continue;
+            }
+
+            // Compare hashes: the manifest only knows the generated content's
+            let current = writer.read(&project_dir.join(&file.path)).ok();
+            let recorded = manifest.hash_of(&file.path);
+            let rendered = update::hash(&file.content);
+            let kind = upgrade::classify(
+                current.as_deref().map(update::hash).as_deref(),
+                recorded,
+                &rendered,
+            );
+
+            match kind {
+                None => report.unchanged.push(file.path.clone()),
+                Some(kind @ (upgrade::ChangeKind::Added | upgrade::ChangeKind::Updated)) => {
+                    writer.write_file_in(
+                        project_dir,
+                        &file.path,
+                        &file.content,
+                        file.permissions,
+                    )?;
+                    if kind == upgrade::ChangeKind::Added {
+                        report.created.push(file.path.clone());
+                    } else {
+                        report.updated.push(file.path.clone());
+                    }
+                }
+                Some(upgrade::ChangeKind::Conflict) => {
+                    report.conflicts.push(FileChange {
+                        path: file.path.clone(),
+                        kind: upgrade::ChangeKind::Conflict,
+                        current,
+                        content: file.content.clone(),
+                        permissions: file.permissions,
+                    });
+                    // Keep the old hash: the file is flagged until resolved
+                    continue;
+                }
+            }
+            manifest.files.insert(file.path.clone(), rendered);
+        }
+
+        manifest.scarff_version = env!("CARGO_PKG_VERSION").to_string();
+        manifest.template.name.clone_from(&to.name);
+        manifest.template.version.clone_from(&to.version);
+        writer.write_file_in(
+            project_dir,
+            Path::new(MANIFEST_PATH),
+            &manifest.to_json(),
+            Permissions::read_write(),
+        )?;
+
+        info!(
+            created = report.created.len(),
+            updated = report.updated.len(),
+            conflicts = report.conflicts.len(),
+            "Update applied"
+        );
+        Ok(report)
+    }
+
+    /// Render `target` into the existing project at `project_dir`, with the
+    /// variables recorded in its provenance and the README and `.gitignore`
+    /// only if the project has them.
+    fn rerender(
+        &self,
+        target: Target,
+        project_dir: &Path,
+        manifest: &ProjectManifest,
+    ) -> CoreResult<(ProjectStructure, TemplateId)> {
+        let writer = &self.inner.writer;
+        let variables = self
+            .read_provenance(project_dir)
+            .map(|p| p.variables)
+            .unwrap_or_default();
+        let dir_name = project_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut context = RenderContext::new(
+            variables
+                .get("PROJECT_NAME")
+                .cloned()
+                .unwrap_or_else(|| dir_name.clone()),
+        );
+        for (key, value) in &variables {
+            context.set_var(key, value);
+        }
+        let has = |path: &str| {
+            manifest.hash_of(Path::new(path)).is_some() || writer.exists(&project_dir.join(path))
+        };
+        let options = ScaffoldOptions::new()
+            .readme(has("README.md"))
+            .git(has(".gitignore"));
+
+        self.build_structure(
+            target,
+            &dir_name,
+            project_dir.parent().unwrap_or(Path::new("")),
+            &options,
+            context,
+            &mut TemplateCache::default(),
+        )
+    }
+
+    /// Get information about available templates.
+    ///
+    /// Returns metadata about all templates that can be used for scaffolding.
+    pub fn list_templates(&self) -> CoreResult<Vec<TemplateInfo>> {
+        let templates = self.inner.resolver.list()?;
+
+        Ok(templates.iter().map(|t| self.template_info(t)).collect())
+    }
+
+    /// Find templates that match a given target.
+    ///
+    /// Useful for showing users what templates are available for their configuration.
+    pub fn find_templates(&self, target: &Target) -> CoreResult<Vec<TemplateInfo>> {
+        let templates = self.inner.resolver.find_all(target)?;
+
+        Ok(templates.iter().map(|t| self.template_info(t)).collect())
+    }
+
+    /// Search every template the engine knows, best matches first.
+    ///
+    /// Takes free text (`"axum"`) or a [`TemplateQuery`] that also filters
+    /// by language, project kind or architecture. Each result's `source`
+    /// says which store it came from when the engine has several (see
+    /// [`Engine::with_sources`]).
+    ///
+    /// ```rust,no_run
+    /// use scarff_core_v1::{Engine, ProjectKind, TemplateQuery};
+    ///
+    /// let engine = Engine::new();
+    /// for info in engine.search_templates(TemplateQuery::new("axum").kind(ProjectKind::WebBackend))? {
+    ///     println!("{} - {}", info.id, info.description);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a store can't be read.
+    pub fn search_templates(
+        &self,
+        query: impl Into<TemplateQuery>,
+    ) -> CoreResult<Vec<TemplateInfo>> {
+        let query = query.into();
+        let hits = template::search(self.inner.resolver.list()?, &query);
+
+        Ok(hits.iter().map(|t| self.template_info(t)).collect())
+    }
+
+    /// Lint a custom template before using it.
+    ///
+    /// Unlike scaffolding, which stops at the first problem, this reports
+    /// them all: duplicate paths, a matcher or feature-gated node no
+    /// target can reach, syntax errors, undefined variables, and external
+    /// content with an empty or unknown partial id. Undefined variables
+    /// are warnings, since a scaffold may still pass them; see
+    /// [`TemplateDiagnostic::is_error`].
+    pub fn validate_template(&self, template: &Template) -> Vec<TemplateDiagnostic> {
+        self.inner.renderer.lint(template)
+    }
+
+    /// Lint the template in `dir` (a `template.toml` and its `files/`, as
+    /// [`FsStore`] loads them).
+    ///
+    /// ```rust,no_run
+    /// use scarff_core_v1::Engine;
+    ///
+    /// let diagnostics = Engine::new().validate_template_dir("templates/my-api")?;
+    /// for diagnostic in &diagnostics {
+    ///     eprintln!("{diagnostic}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest is missing or malformed, or a file
+    /// can't be read.
+    pub fn validate_template_dir(
+        &self,
+        dir: impl AsRef<Path>,
+    ) -> CoreResult<Vec<TemplateDiagnostic>> {
+        let template = template::read_template_dir(dir.as_ref())?;
+        Ok(self.validate_template(&template))
+    }
+
+    fn template_info(&self, t: &Template) -> TemplateInfo {
+        let id = TemplateId::new(t.metadata.name.clone(), t.metadata.version.clone());
+        TemplateInfo {
+            id: format!("{}@{}", t.metadata.name, t.metadata.version),
+            name: t.metadata.name.clone(),
+            description: t.metadata.description.clone(),
+            language: t
+                .matcher
+                .language
+                .map_or_else(|| "any".to_string(), |l| l.to_string()),
+            kind: t
+                .matcher
+                .kind
+                .map_or_else(|| "any".to_string(), |k| k.to_string()),
+            architecture: t
+                .matcher
+                .architecture
+                .map_or_else(|| "any".to_string(), |a| a.to_string()),
+            framework: t.matcher.framework.map(|f| f.to_string()),
+            tags: t.metadata.tags.clone(),
+            source: self.inner.resolver.origin(&id),
+        }
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Templates resolved during one operation, by target, so projects of the
+/// same target share a single resolution.
+#[derive(Default)]
+struct TemplateCache {
+    templates: HashMap<Target, Template>,
+    /// Resolutions answered from the cache
+    hits: usize,
+}
+
+impl TemplateCache {
+    fn resolve(&mut self, resolver: &TemplateResolver, target: &Target) -> CoreResult<Template> {
+        if let Some(template) = self.templates.get(target) {
+            self.hits += 1;
+            return Ok(template.clone());
+        }
+        let template = resolver.resolve(target)?;
+        self.templates.insert(target.clone(), template.clone());
+        Ok(template)
+    }
+}
+
+/// A project within a structure being written: where its hooks run, the
+/// template its files (those under `prefix`, relative to the structure's
+/// root) came from, and the language to verify it as (`None` skips
+/// verification, e.g. for a workspace root).
+struct GeneratedProject {
+    root: PathBuf,
+    prefix: PathBuf,
+    template: TemplateId,
+    hooks: Vec<Hook>,
+    verify: Option<Language>,
+}
+
+/// Information about a template.
+///
+/// This is a simplified view of template metadata for display purposes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub language: String,
+    pub kind: String,
+    pub architecture: String,
+    pub framework: Option<String>,
+    /// Search tags from the template's metadata
+    pub tags: Vec<String>,
+    /// Store the template came from (`builtin`, a directory, a remote
+    /// source), when the engine reads several
+    pub source: Option<String>,
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{Architecture, CiProvider, CustomTree, License, TaskRunner},
+        scaffold::{
+            filesystem::{Filesystem, MockFilesystem},
+            plan::PlannedAction,
+            process::{MockProcess, ProcessOutput},
+            report::EntryStatus,
+            verify::VerifyCommand,
+        },
+    };
+
+    #[test]
+    fn engine_new_loads_builtin_templates() {
+        let engine = Engine::new();
+        let templates = engine.list_templates().unwrap();
+
+        assert!(!templates.is_empty(), "Should have built-in templates");
+    }
+
+    #[test]
+    fn search_templates_spans_user_sources_and_builtins() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("acme-axum");
+        std::fs::create_dir_all(template.join("files")).unwrap();
+        std::fs::write(
+            template.join("template.toml"),
+            "name = \"acme-axum\"\nversion = \"1.0.0\"\ntags = [\"axum\"]\n\n\
+             [matcher]\nlanguage = \"rust\"\nframework = \"axum\"\ntype = \"backend\"\n",
+        )
+        .unwrap();
+        std::fs::write(template.join("files/README.md"), "# {{PROJECT_NAME}}\n").unwrap();
+
+        let engine =
+            Engine::with_sources([("acme".to_string(), FsStore::open(dir.path()).unwrap())]);
+        let hits = engine.search_templates("axum").unwrap();
+
+        // Exact tag hits rank first, the user's next to the built-in ones
+        let acme = hits.iter().position(|hit| hit.name == "acme-axum").unwrap();
+        assert_eq!(hits[acme].source.as_deref(), Some("acme"));
+        assert!(
+            hits[..=acme]
+                .iter()
+                .all(|hit| hit.tags.contains(&"axum".to_string()))
+        );
+        assert!(
+            hits.iter()
+                .filter(|hit| hit.name != "acme-axum")
+                .all(|hit| hit.source.as_deref() == Some("builtin"))
+        );
+
+        let python = engine
+            .search_templates(TemplateQuery::new("").language(Language::Python))
+            .unwrap();
+        assert!(!python.is_empty());
+        assert!(python.iter().all(|hit| hit.language != "rust"));
+    }
+
+    #[test]
+    fn validate_template_dir_reports_every_problem() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("files")).unwrap();
+        std::fs::write(
+            dir.path().join("template.toml"),
+            "name = \"broken\"\nversion = \"1.0.0\"\n\n\
+             [matcher]\nlanguage = \"rust\"\n\n\
+             [[file]]\npath = \"README.md\"\ncontent = \"# {{ PROJECT_NAME }}\"\n",
+        )
+        .unwrap();
+        // Also declared in the manifest
+        std::fs::write(dir.path().join("files/README.md"), "# {{ NAME }}\n").unwrap();
+
+        let engine = Engine::new();
+        let diagnostics = engine.validate_template_dir(dir.path()).unwrap();
+        let codes: Vec<_> = diagnostics.iter().map(TemplateDiagnostic::code).collect();
+        assert_eq!(
+            codes,
+            ["template.duplicate_path", "template.undefined_variable"]
+        );
+
+        let err = engine
+            .validate_template_dir(dir.path().join("missing"))
+            .unwrap_err();
+        assert_eq!(err.code(), "template.load_failed");
+    }
+
+    #[test]
+    fn engine_clones_share_state_and_are_thread_safe() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<Engine>();
+
+        let engine = Engine::new();
+        let clone = engine.clone();
+
+        assert!(Arc::ptr_eq(&engine.inner, &clone.inner));
+    }
+
+    #[test]
+    fn engine_scaffolds_rust_cli_project() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .architecture(Architecture::Layered)
+            .unwrap()
             .build()
             .unwrap();
 
@@ -295,6 +1544,1175 @@ mod tests {
         // assert!(fs_clone.exists(Path::new("./test-cli")));
     }
 
+    #[test]
+    fn scaffold_with_failed_verification_rolls_back() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // The mock project never touches disk, so the command cannot start.
+        let options = ScaffoldOptions::new().verify_command(VerifyCommand::new("cargo", ["check"]));
+        let result = engine.scaffold_with(target, "test-cli", "./", &options);
+
+        assert!(
+            matches!(
+                result,
+                Err(crate::CoreError::Scaffold(
+                    ScaffoldError::VerificationFailed { .. }
+                ))
+            ),
+            "Expected verification failure: {result:?}"
+        );
+        assert!(!fs_clone.exists(Path::new("./test-cli")));
+        assert_eq!(fs_clone.file_count(), 0);
+    }
+
+    /// `/out/my-cli` holding an edited `Cargo.toml`, a user file, and an
+    /// unchanged copy of every other file the scaffold writes.
+    fn existing_project(fs: &MockFilesystem, engine: &Engine) -> String {
+        engine
+            .scaffold(Target::rust_cli().unwrap(), "my-cli", "/out")
+            .unwrap();
+        let cargo = Path::new("/out/my-cli/Cargo.toml");
+        let rendered = fs.read_file(cargo).unwrap();
+        fs.write_file(cargo, "[package]\nname = \"mine\"\n")
+            .unwrap();
+        fs.write_file(Path::new("/out/my-cli/NOTES.md"), "keep me\n")
+            .unwrap();
+        rendered
+    }
+
+    fn cargo_status(report: &ScaffoldReport) -> EntryStatus {
+        report
+            .entries()
+            .iter()
+            .find(|e| e.path == Path::new("Cargo.toml"))
+            .map(|e| e.status.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn existing_directory_aborts_by_default() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let engine = Engine::with_filesystem(mock_fs.clone());
+        existing_project(&mock_fs, &engine);
+
+        let result = engine.scaffold(Target::rust_cli().unwrap(), "my-cli", "/out");
+
+        assert!(matches!(
+            result,
+            Err(crate::CoreError::Scaffold(
+                ScaffoldError::ProjectExists { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn overwrite_policies_decide_per_file() {
+        let cases = [
+            (OverwritePolicy::Skip, EntryStatus::Skipped),
+            (OverwritePolicy::Overwrite, EntryStatus::Overwritten),
+            (
+                OverwritePolicy::Backup,
+                EntryStatus::BackedUp(PathBuf::from("Cargo.toml.bak")),
+            ),
+            (OverwritePolicy::Merge, EntryStatus::Merged),
+        ];
+
+        for (policy, expected) in cases {
+            let mock_fs = Box::new(MockFilesystem::new());
+            let engine = Engine::with_filesystem(mock_fs.clone());
+            let rendered = existing_project(&mock_fs, &engine);
+            let options = ScaffoldOptions::new().overwrite(policy);
+
+            let report = engine
+                .scaffold_with(Target::rust_cli().unwrap(), "my-cli", "/out", &options)
+                .unwrap();
+
+            assert_eq!(cargo_status(&report), expected, "{policy:?}");
+            assert_eq!(
+                report.replaced_entries().count(),
+                usize::from(policy != OverwritePolicy::Skip)
+            );
+            assert!(
+                report
+                    .entries()
+                    .iter()
+                    .filter(|e| e.path != Path::new("Cargo.toml"))
+                    .all(|e| e.status == EntryStatus::Skipped),
+                "{policy:?}: unchanged entries are skipped"
+            );
+            assert_eq!(
+                mock_fs
+                    .read_file(Path::new("/out/my-cli/NOTES.md"))
+                    .unwrap(),
+                "keep me\n"
+            );
+
+            let cargo = mock_fs
+                .read_file(Path::new("/out/my-cli/Cargo.toml"))
+                .unwrap();
+            match policy {
+                OverwritePolicy::Skip => assert!(cargo.contains("\"mine\"")),
+                OverwritePolicy::Overwrite => assert_eq!(cargo, rendered),
+                OverwritePolicy::Backup => {
+                    assert_eq!(cargo, rendered);
+                    let backup = mock_fs
+                        .read_file(Path::new("/out/my-cli/Cargo.toml.bak"))
+                        .unwrap();
+                    assert!(backup.contains("\"mine\""));
+                }
+                OverwritePolicy::Merge => {
+                    assert!(cargo.contains("<<<<<<< existing\nname = \"mine\""));
+                    assert!(cargo.contains(">>>>>>> template"));
+                }
+                OverwritePolicy::Abort => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn failed_verification_keeps_existing_directory() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let engine = Engine::with_filesystem(mock_fs.clone());
+        existing_project(&mock_fs, &engine);
+        let options = ScaffoldOptions::new()
+            .overwrite(OverwritePolicy::Overwrite)
+            .verify_command(VerifyCommand::new("cargo", ["check"]));
+
+        let result = engine.scaffold_with(Target::rust_cli().unwrap(), "my-cli", "/out", &options);
+
+        assert!(result.is_err());
+        assert!(mock_fs.exists(Path::new("/out/my-cli/NOTES.md")));
+    }
+
+    #[test]
+    fn scaffold_custom_tree_writes_exact_structure() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+
+        let target = Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::Custom)
+            .unwrap()
+            .build()
+            .unwrap();
+        let tree = CustomTree::parse("docs/\n  index.md\nrun.sh\n")
+            .unwrap()
+            .with_headers(true);
+
+        let report = engine
+            .scaffold_with(
+                target,
+                "skeleton",
+                "/out",
+                &ScaffoldOptions::new().custom_tree(tree),
+            )
+            .unwrap();
+
+        assert_eq!(report.files_created(), 2);
+        assert_eq!(
+            fs_clone
+                .read_file(Path::new("/out/skeleton/run.sh"))
+                .unwrap(),
+            "# skeleton - run.sh\n"
+        );
+        assert!(fs_clone.exists(Path::new("/out/skeleton/docs/index.md")));
+    }
+
+    #[test]
+    fn scaffold_applies_option_variables() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Custom)
+            .unwrap()
+            .build()
+            .unwrap();
+        let tree =
+            CustomTree::parse(r#"{"README.md": "{{PROJECT_NAME}} by {{COMPANY}}"}"#).unwrap();
+        let options = ScaffoldOptions::new()
+            .custom_tree(tree)
+            .var("COMPANY", "ACME");
+
+        engine
+            .scaffold_with(target, "svc", "/out", &options)
+            .unwrap();
+
+        assert_eq!(
+            fs_clone.read_file(Path::new("/out/svc/README.md")).unwrap(),
+            "svc by ACME"
+        );
+    }
+
+    #[test]
+    fn scaffold_generates_readme_unless_disabled() {
+        let target = || Target::rust_cli().unwrap();
+
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        engine.scaffold(target(), "tool", "/out").unwrap();
+
+        let readme = fs_clone
+            .read_file(Path::new("/out/tool/README.md"))
+            .unwrap();
+        assert!(readme.contains("A simple Rust command-line application"));
+        assert!(readme.contains("cargo run -- --help"));
+
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        engine
+            .scaffold_with(
+                target(),
+                "tool",
+                "/out",
+                &ScaffoldOptions::new().readme(false),
+            )
+            .unwrap();
+
+        assert!(!fs_clone.exists(Path::new("/out/tool/README.md")));
+    }
+
+    #[test]
+    fn scaffold_writes_license_and_manifest_field() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .license(License::Mit)
+            .build()
+            .unwrap();
+
+        engine
+            .scaffold_with(
+                target.clone(),
+                "tool",
+                "/out",
+                &ScaffoldOptions::new().var("AUTHOR", "Ada Lovelace"),
+            )
+            .unwrap();
+
+        let license = fs_clone.read_file(Path::new("/out/tool/LICENSE")).unwrap();
+        assert!(license.starts_with("MIT License\n\nCopyright (c) 2026 Ada Lovelace\n"));
+        let manifest = fs_clone
+            .read_file(Path::new("/out/tool/Cargo.toml"))
+            .unwrap();
+        assert!(manifest.contains("license = \"MIT\"\n"));
+        assert_eq!(
+            engine
+                .read_provenance("/out/tool")
+                .unwrap()
+                .target()
+                .unwrap(),
+            target
+        );
+
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        Engine::with_filesystem(mock_fs)
+            .scaffold(Target::rust_cli().unwrap(), "tool", "/out")
+            .unwrap();
+
+        assert!(!fs_clone.exists(Path::new("/out/tool/LICENSE")));
+        assert!(
+            !fs_clone
+                .read_file(Path::new("/out/tool/Cargo.toml"))
+                .unwrap()
+                .contains("license")
+        );
+    }
+
+    #[test]
+    fn scaffold_many_resolves_each_target_once_and_reports_failures() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        engine
+            .scaffold(Target::rust_cli().unwrap(), "taken", "/out")
+            .unwrap();
+
+        let report = engine.scaffold_many(
+            [
+                (Target::rust_cli().unwrap(), "one", "/out"),
+                (
+                    Target::typescript_frontend_vite_react().unwrap(),
+                    "web",
+                    "/out",
+                ),
+                (Target::rust_cli().unwrap(), "taken", "/out"),
+                (Target::rust_cli().unwrap(), "two", "/out"),
+            ],
+            &ScaffoldOptions::new(),
+        );
+
+        assert_eq!(report.outcomes().len(), 4);
+        assert_eq!(report.templates_resolved(), 2);
+        assert_eq!(report.cache_hits(), 2);
+        let failed: Vec<_> = report
+            .failed()
+            .map(|(item, _)| item.name.as_str())
+            .collect();
+        assert_eq!(failed, ["taken"]);
+        assert!(!report.is_success());
+
+        assert!(fs_clone.exists(Path::new("/out/one/Cargo.toml")));
+        assert!(fs_clone.exists(Path::new("/out/two/Cargo.toml")));
+        assert!(fs_clone.exists(Path::new("/out/web/package.json")));
+        let one = report.report_for("/out/one").unwrap();
+        assert_eq!(
+            report.files_created(),
+            one.files_created() * 2 + report.report_for("/out/web").unwrap().files_created()
+        );
+    }
+
+    #[test]
+    fn scaffold_rust_backend_in_hexagonal_and_clean_styles() {
+        use crate::domain::{Framework, RustFramework};
+
+        for (architecture, port) in [
+            (Architecture::Hexagonal, "src/domain/ports.rs"),
+            (Architecture::Clean, "src/domain/repository.rs"),
+        ] {
+            let mock_fs = Box::new(MockFilesystem::new());
+            let fs_clone = mock_fs.clone();
+            let engine = Engine::with_filesystem(mock_fs);
+            let target = Target::builder()
+                .language(Language::Rust)
+                .kind(ProjectKind::WebBackend)
+                .unwrap()
+                .framework(Framework::Rust(RustFramework::Axum))
+                .unwrap()
+                .architecture(architecture)
+                .unwrap()
+                .build()
+                .unwrap();
+
+            engine.scaffold(target, "notes", "/out").unwrap();
+
+            let root = Path::new("/out/notes");
+            let ports = fs_clone.read_file(&root.join(port)).unwrap();
+            assert!(ports.contains("pub trait NoteRepository"), "{architecture}");
+            let manifest = fs_clone.read_file(&root.join("Cargo.toml")).unwrap();
+            assert!(manifest.contains("name = \"notes\""));
+            assert!(manifest.contains("axum = \"0.8\""));
+            for layer in ["domain", "application", "infrastructure", "api"] {
+                assert!(
+                    fs_clone.exists(&root.join("src").join(layer).join("mod.rs")),
+                    "{architecture}: {layer}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn scaffold_rust_writes_task_file_and_example_tests() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .task_runner(TaskRunner::Just)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        engine.scaffold(target.clone(), "tool", "/out").unwrap();
+
+        let justfile = fs_clone.read_file(Path::new("/out/tool/justfile")).unwrap();
+        assert!(justfile.contains("test:\n    cargo test --workspace\n"));
+        let test = fs_clone
+            .read_file(Path::new("/out/tool/tests/cli.rs"))
+            .unwrap();
+        assert!(test.contains("CARGO_BIN_EXE_tool"));
+        assert!(!fs_clone.exists(Path::new("/out/tool/Makefile")));
+        assert_eq!(
+            engine
+                .read_provenance("/out/tool")
+                .unwrap()
+                .target()
+                .unwrap(),
+            target
+        );
+    }
+
+    #[test]
+    fn scaffold_writes_ci_pipeline_for_the_provider() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .ci(CiProvider::GitLabCi)
+            .build()
+            .unwrap();
+
+        engine.scaffold(target.clone(), "tool", "/out").unwrap();
+
+        let pipeline = fs_clone
+            .read_file(Path::new("/out/tool/.gitlab-ci.yml"))
+            .unwrap();
+        assert!(pipeline.starts_with("image: rust:1\n"));
+        assert!(pipeline.contains("    - cargo build --release\n"));
+        assert!(pipeline.contains("      - target/release/tool\n"));
+        assert!(!fs_clone.exists(Path::new("/out/tool/.github")));
+        assert_eq!(
+            engine
+                .read_provenance("/out/tool")
+                .unwrap()
+                .target()
+                .unwrap(),
+            target
+        );
+    }
+
+    #[test]
+    fn scaffold_python_writes_dependency_manager_files() {
+        use crate::{
+            domain::{
+                DependencyManager, FileSpec, TargetMatcher, Template, TemplateContent, TemplateId,
+                TemplateMetadata, TemplateNode, TemplateSource, TemplateTree,
+            },
+            template::InMemoryStore,
+        };
+
+        let template = Template {
+            id: TemplateId::new("python-api", "1.0.0".to_string()),
+            matcher: TargetMatcher::builder().language(Language::Python).build(),
+            metadata: TemplateMetadata::new("python-api"),
+            tree: TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
+                "app/main.py",
+                TemplateContent::Literal(TemplateSource::Static("")),
+            ))),
+            hooks: Vec::new(),
+        };
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::from_parts(
+            TemplateResolver::new(Box::new(
+                InMemoryStore::with_templates(vec![template]).unwrap(),
+            )),
+            FileWriter::new(mock_fs),
+        );
+        let target = Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .dependency_manager(DependencyManager::Uv)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        engine.scaffold(target, "my-api", "/out").unwrap();
+
+        let pyproject = fs_clone
+            .read_file(Path::new("/out/my-api/pyproject.toml"))
+            .unwrap();
+        assert!(pyproject.contains("name = \"my-api\""));
+        assert!(pyproject.contains("\"fastapi>=0.115\","));
+        assert!(fs_clone.exists(Path::new("/out/my-api/uv.lock")));
+        assert!(!fs_clone.exists(Path::new("/out/my-api/requirements.txt")));
+        let readme = fs_clone
+            .read_file(Path::new("/out/my-api/README.md"))
+            .unwrap();
+        assert!(readme.contains("uv sync"));
+    }
+
+    #[test]
+    fn scaffold_python_src_layout_moves_packages_under_src() {
+        use crate::{
+            domain::{
+                DependencyManager, FileSpec, PythonLayout, TargetMatcher, Template,
+                TemplateContent, TemplateId, TemplateMetadata, TemplateNode, TemplateSource,
+                TemplateTree,
+            },
+            template::InMemoryStore,
+        };
+
+        let file = |path: &str| {
+            TemplateNode::File(FileSpec::new(
+                path,
+                TemplateContent::Literal(TemplateSource::Static("")),
+            ))
+        };
+        let template = Template {
+            id: TemplateId::new("python-api", "1.0.0".to_string()),
+            matcher: TargetMatcher::builder().language(Language::Python).build(),
+            metadata: TemplateMetadata::new("python-api"),
+            tree: TemplateTree::new()
+                .with_node(file("app/__init__.py"))
+                .with_node(file("app/main.py"))
+                .with_node(file("tests/test_main.py")),
+            hooks: Vec::new(),
+        };
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::from_parts(
+            TemplateResolver::new(Box::new(
+                InMemoryStore::with_templates(vec![template]).unwrap(),
+            )),
+            FileWriter::new(mock_fs),
+        );
+        let target = Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .dependency_manager(DependencyManager::Poetry)
+            .unwrap()
+            .python_layout(PythonLayout::Src)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        engine.scaffold(target.clone(), "my-api", "/out").unwrap();
+
+        assert!(fs_clone.exists(Path::new("/out/my-api/src/app/main.py")));
+        assert!(fs_clone.exists(Path::new("/out/my-api/tests/test_main.py")));
+        assert!(!fs_clone.exists(Path::new("/out/my-api/app")));
+        let pyproject = fs_clone
+            .read_file(Path::new("/out/my-api/pyproject.toml"))
+            .unwrap();
+        assert!(pyproject.contains("{ include = \"app\", from = \"src\" },"));
+        assert!(fs_clone.exists(Path::new("/out/my-api/poetry.lock")));
+        assert_eq!(
+            engine
+                .read_provenance("/out/my-api")
+                .unwrap()
+                .target()
+                .unwrap(),
+            target
+        );
+    }
+
+    #[test]
+    fn scaffold_typescript_frontends_render_valid_package_json() {
+        for (target, entry) in [
+            (Target::typescript_frontend_vite_react(), "src/main.tsx"),
+            (Target::typescript_frontend_nextjs(), "src/app/page.tsx"),
+            (
+                Target::typescript_frontend_sveltekit(),
+                "src/routes/+page.svelte",
+            ),
+        ] {
+            let mock_fs = Box::new(MockFilesystem::new());
+            let fs_clone = mock_fs.clone();
+            let engine = Engine::with_filesystem(mock_fs);
+
+            engine.scaffold(target.unwrap(), "web", "/out").unwrap();
+
+            assert!(
+                fs_clone.exists(&Path::new("/out/web").join(entry)),
+                "{entry}"
+            );
+            let package = fs_clone
+                .read_file(Path::new("/out/web/package.json"))
+                .unwrap();
+            let package: serde_json::Value = serde_json::from_str(&package).unwrap();
+            assert_eq!(package["name"], "web");
+            assert!(package["scripts"]["test"].is_string(), "{package}");
+            assert!(package["devDependencies"]["typescript"].is_string());
+        }
+    }
+
+    #[test]
+    fn scaffold_workspace_writes_root_and_members() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        let workspace = WorkspaceTarget::builder(Language::Rust)
+            .member("cli", Target::rust_cli().unwrap())
+            .unwrap()
+            .member("tool", Target::rust_cli().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let report = engine
+            .scaffold_workspace(&workspace, "mono", "/out", &ScaffoldOptions::new())
+            .unwrap();
+
+        let manifest = fs_clone
+            .read_file(Path::new("/out/mono/Cargo.toml"))
+            .unwrap();
+        let manifest: toml::Value = toml::from_str(&manifest).unwrap();
+        assert_eq!(
+            manifest["workspace"]["members"],
+            toml::Value::from(vec!["crates/cli", "crates/tool"])
+        );
+        for member in ["cli", "tool"] {
+            let root = Path::new("/out/mono/crates").join(member);
+            let package = fs_clone.read_file(&root.join("Cargo.toml")).unwrap();
+            assert!(
+                package.contains(&format!("name = \"{member}\"")),
+                "{package}"
+            );
+            assert!(fs_clone.exists(&root.join("src/main.rs")));
+            assert!(fs_clone.exists(&root.join(PROVENANCE_PATH)));
+        }
+        assert_eq!(report.root(), Path::new("/out/mono"));
+        assert!(
+            report
+                .written_files()
+                .any(|f| f == Path::new("crates/tool/src/main.rs"))
+        );
+    }
+
+    #[test]
+    fn scaffold_report_lists_every_file_with_its_template() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        let workspace = WorkspaceTarget::builder(Language::Rust)
+            .member("cli", Target::rust_cli().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let report = engine
+            .scaffold_workspace(&workspace, "mono", "/out", &ScaffoldOptions::new())
+            .unwrap();
+
+        assert_eq!(report.files().len(), report.files_created());
+        let template_of = |path: &str| {
+            let file = report
+                .files()
+                .iter()
+                .find(|f| f.path == Path::new(path))
+                .unwrap();
+            let content = fs_clone
+                .read_file(&Path::new("/out/mono").join(path))
+                .unwrap();
+            assert_eq!(file.size, content.len() as u64, "{path}");
+            file.template.clone()
+        };
+        let root = template_of("Cargo.toml");
+        let member = template_of("crates/cli/Cargo.toml");
+        assert_ne!(root, member);
+        assert_eq!(template_of("crates/cli/src/main.rs"), member);
+        assert_eq!(
+            report.total_size(),
+            report.files().iter().map(|f| f.size).sum::<u64>()
+        );
+        assert!(report.warnings().is_empty(), "{:?}", report.warnings());
+    }
+
+    #[test]
+    fn scaffold_workspace_sets_up_pnpm() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        let workspace = WorkspaceTarget::builder(Language::TypeScript)
+            .member("web", Target::typescript_frontend_vite_react().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        engine
+            .scaffold_workspace(&workspace, "mono", "/out", &ScaffoldOptions::new())
+            .unwrap();
+
+        let pnpm = fs_clone
+            .read_file(Path::new("/out/mono/pnpm-workspace.yaml"))
+            .unwrap();
+        assert!(pnpm.contains("- \"packages/*\""), "{pnpm}");
+        let root: serde_json::Value = serde_json::from_str(
+            &fs_clone
+                .read_file(Path::new("/out/mono/package.json"))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(root["private"], true);
+        // The root has no test runner of its own; the member does
+        assert!(!fs_clone.exists(Path::new("/out/mono/vitest.config.ts")));
+        assert!(fs_clone.exists(Path::new("/out/mono/packages/web/vitest.config.ts")));
+    }
+
+    #[test]
+    fn scaffold_workspace_writes_nothing_when_a_member_fails() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let engine = Engine::with_filesystem(mock_fs);
+        // No built-in template for Rust web backends
+        let workspace = WorkspaceTarget::builder(Language::Rust)
+            .member("cli", Target::rust_cli().unwrap())
+            .unwrap()
+            .member("api", Target::rust_backend_axum().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let result = engine.scaffold_workspace(&workspace, "mono", "/out", &ScaffoldOptions::new());
+
+        assert!(result.is_err());
+        assert!(!fs_clone.exists(Path::new("/out/mono")));
+    }
+
+    #[test]
+    fn scaffold_records_provenance() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let engine = Engine::with_filesystem(mock_fs.clone());
+        let target = Target::rust_cli().unwrap();
+
+        engine.scaffold(target.clone(), "my-cli", "/out").unwrap();
+
+        let provenance = engine.read_provenance("/out/my-cli").unwrap();
+        assert_eq!(provenance.scarff_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            provenance.template_id().to_string(),
+            "Rust CLI (Default)@1.0.0"
+        );
+        assert_eq!(provenance.target().unwrap(), target);
+        assert_eq!(provenance.variables["PROJECT_NAME"], "my-cli");
+        assert_eq!(provenance.variables["LANGUAGE"], "rust");
+
+        let manifest = engine.read_manifest("/out/my-cli").unwrap();
+        let cargo = mock_fs
+            .read_file(Path::new("/out/my-cli/Cargo.toml"))
+            .unwrap();
+        assert_eq!(manifest.template_id(), provenance.template_id());
+        assert_eq!(
+            manifest.hash_of(Path::new("Cargo.toml")),
+            Some(update::hash(&cargo).as_str())
+        );
+        assert!(manifest.hash_of(Path::new(PROVENANCE_PATH)).is_none());
+    }
+
+    #[test]
+    fn plan_writes_nothing_and_matches_a_scaffold() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let engine = Engine::with_filesystem(mock_fs.clone());
+        let target = Target::rust_cli().unwrap();
+
+        let plan = engine.plan(target.clone(), "my-cli", "/out").unwrap();
+        assert_eq!(mock_fs.file_count(), 0);
+        assert_eq!(plan.root(), Path::new("/out/my-cli"));
+        assert!(
+            plan.entries()
+                .iter()
+                .all(|e| e.action == PlannedAction::Create)
+        );
+
+        let report = engine.scaffold(target.clone(), "my-cli", "/out").unwrap();
+        let planned: Vec<_> = plan.entries().iter().map(|e| &e.path).collect();
+        let written: Vec<_> = report.entries().iter().map(|e| &e.path).collect();
+        assert_eq!(planned, written);
+
+        let again = engine.plan(target, "my-cli", "/out").unwrap();
+        assert!(
+            again
+                .entries()
+                .iter()
+                .all(|e| e.action == PlannedAction::Skip)
+        );
+    }
+
+    /// A text-file template `app@version` with `(path, content)` files.
+    fn versioned_template(
+        version: &'static str,
+        files: &[(&'static str, &'static str)],
+    ) -> Template {
+        use crate::domain::{
+            FileSpec, TargetMatcher, TemplateContent, TemplateId, TemplateMetadata, TemplateNode,
+            TemplateSource, TemplateTree,
+        };
+
+        let mut tree = TemplateTree::new();
+        for (path, content) in files {
+            tree.push(TemplateNode::File(FileSpec::new(
+                *path,
+                TemplateContent::Literal(TemplateSource::Static(content)),
+            )));
+        }
+        Template {
+            id: TemplateId::new("app", version.to_string()),
+            matcher: TargetMatcher::builder().language(Language::Rust).build(),
+            metadata: TemplateMetadata::new("app").version(version),
+            tree,
+            hooks: Vec::new(),
+        }
+    }
+
+    fn engine_with(templates: Vec<Template>, fs: &MockFilesystem) -> Engine {
+        Engine::from_parts(
+            TemplateResolver::new(Box::new(InMemoryStore::with_templates(templates).unwrap())),
+            FileWriter::new(Box::new(fs.clone())),
+        )
+    }
+
+    #[test]
+    fn scaffold_runs_template_hooks_and_reports_each() {
+        use crate::{domain::Hook, scaffold::hooks::HookStatus};
+
+        let mut template = versioned_template("1.0.0", &[("run.sh", "#!/bin/sh\n")]);
+        template.hooks = vec![
+            Hook::make_executable("run.sh"),
+            Hook::command("cargo", ["init"]),
+            Hook::next_steps(["cd {{PROJECT_NAME}}", "./run.sh"]),
+        ];
+        let fs = MockFilesystem::new();
+
+        let report = engine_with(vec![template], &fs)
+            .scaffold(Target::rust_cli().unwrap(), "app", "/out")
+            .unwrap();
+
+        let statuses: Vec<_> = report.hooks().iter().map(|h| &h.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                &HookStatus::Succeeded,
+                &HookStatus::Skipped,
+                &HookStatus::Succeeded
+            ]
+        );
+        assert!(fs.is_executable(Path::new("/out/app/run.sh")).unwrap());
+        assert_eq!(
+            report.next_steps().collect::<Vec<_>>(),
+            ["cd app", "./run.sh"]
+        );
+    }
+
+    #[test]
+    fn allowed_hooks_run_sandboxed_unless_unsafe() {
+        use crate::{domain::Hook, scaffold::hooks::HookStatus};
+
+        let mut template = versioned_template("1.0.0", &[("a.txt", "a")]);
+        template.hooks = vec![Hook::command("{{PROJECT_NAME}}-setup", ["--all"])];
+        let fs = MockFilesystem::new();
+        let engine = engine_with(vec![template], &fs);
+
+        let report = engine
+            .scaffold_with(
+                Target::rust_cli().unwrap(),
+                "app",
+                "/out",
+                &ScaffoldOptions::new()
+                    .allow_hooks(true)
+                    .hook_allowlist(["git"]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            report.hooks()[0].status,
+            HookStatus::Blocked("`app-setup` is not in the hook allowlist".to_string())
+        );
+        assert_eq!(report.executed_commands().count(), 0);
+        assert!(
+            report.warnings().contains(
+                &"hook `app-setup --all` was blocked: `app-setup` is not in the hook allowlist"
+                    .to_string()
+            )
+        );
+
+        let report = engine
+            .scaffold_with(
+                Target::rust_cli().unwrap(),
+                "app",
+                "/out",
+                &ScaffoldOptions::new()
+                    .unsafe_hooks(true)
+                    .overwrite(OverwritePolicy::Overwrite),
+            )
+            .unwrap();
+
+        assert!(matches!(report.hooks()[0].status, HookStatus::Failed(_)));
+        let executed: Vec<_> = report.executed_commands().collect();
+        assert_eq!(executed.len(), 1);
+        assert_eq!(executed[0].program, "app-setup");
+        assert_eq!(executed[0].dir, Path::new("/out/app"));
+    }
+
+    #[test]
+    fn upgrade_applies_template_changes_and_rejects_conflicts() {
+        let v1 = versioned_template(
+            "1.0.0",
+            &[("a.txt", "a1"), ("b.txt", "b1"), ("c.txt", "c1")],
+        );
+        let v2 = versioned_template(
+            "1.1.0",
+            &[
+                ("a.txt", "a2"),
+                ("b.txt", "b2"),
+                ("c.txt", "c1"),
+                ("d.txt", "d2"),
+            ],
+        );
+        let fs = MockFilesystem::new();
+        let options = ScaffoldOptions::new().readme(false);
+        engine_with(vec![v1.clone()], &fs)
+            .scaffold_with(Target::rust_cli().unwrap(), "app", "/out", &options)
+            .unwrap();
+        fs.write_file(Path::new("/out/app/b.txt"), "b1 edited")
+            .unwrap();
+        fs.write_file(Path::new("/out/app/c.txt"), "c1 edited")
+            .unwrap();
+
+        let engine = engine_with(vec![v1, v2], &fs);
+        let plan = engine.plan_upgrade("/out/app").unwrap();
+
+        assert_eq!(plan.from.to_string(), "app@1.0.0");
+        assert_eq!(plan.to.to_string(), "app@1.1.0");
+        let changes: Vec<_> = plan
+            .changes
+            .iter()
+            .map(|c| (c.path.to_str().unwrap(), c.kind))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("a.txt", upgrade::ChangeKind::Updated),
+                ("b.txt", upgrade::ChangeKind::Conflict),
+                ("d.txt", upgrade::ChangeKind::Added),
+            ]
+        );
+
+        let report = engine.apply_upgrade(&plan, |_| Resolution::Reject).unwrap();
+
+        assert_eq!(report.applied, [Path::new("a.txt"), Path::new("d.txt")]);
+        assert_eq!(report.rejected, [Path::new("b.txt.rej")]);
+        assert_eq!(fs.read_file(Path::new("/out/app/a.txt")).unwrap(), "a2");
+        assert_eq!(
+            fs.read_file(Path::new("/out/app/b.txt")).unwrap(),
+            "b1 edited"
+        );
+        let rej = fs.read_file(Path::new("/out/app/b.txt.rej")).unwrap();
+        assert!(rej.contains("-b1 edited") && rej.contains("+b2"));
+        assert_eq!(
+            fs.read_file(Path::new("/out/app/c.txt")).unwrap(),
+            "c1 edited"
+        );
+
+        // The project is now recorded at 1.1.0; the kept edit is not re-flagged
+        let plan = engine.plan_upgrade("/out/app").unwrap();
+        assert!(plan.is_up_to_date(), "{:?}", plan.changes);
+    }
+
+    #[test]
+    fn upgrade_without_base_flags_every_edit() {
+        let fs = MockFilesystem::new();
+        let options = ScaffoldOptions::new().readme(false);
+        engine_with(vec![versioned_template("1.0.0", &[("a.txt", "a1")])], &fs)
+            .scaffold_with(Target::rust_cli().unwrap(), "app", "/out", &options)
+            .unwrap();
+
+        // The store only has the new version: untouched files can't be told apart
+        let engine = engine_with(vec![versioned_template("2.0.0", &[("a.txt", "a2")])], &fs);
+        let plan = engine.plan_upgrade("/out/app").unwrap();
+        assert_eq!(plan.conflicts().count(), 1);
+
+        engine.apply_upgrade(&plan, |_| Resolution::Accept).unwrap();
+        assert_eq!(fs.read_file(Path::new("/out/app/a.txt")).unwrap(), "a2");
+        assert_eq!(
+            engine.read_provenance("/out/app").unwrap().template.version,
+            "2.0.0"
+        );
+    }
+
+    #[test]
+    fn update_rewrites_untouched_files_and_reports_conflicts() {
+        let fs = MockFilesystem::new();
+        let options = ScaffoldOptions::new().readme(false);
+        engine_with(
+            vec![versioned_template(
+                "1.0.0",
+                &[("a.txt", "a1"), ("b.txt", "b1"), ("c.txt", "c1")],
+            )],
+            &fs,
+        )
+        .scaffold_with(Target::rust_cli().unwrap(), "app", "/out", &options)
+        .unwrap();
+        fs.write_file(Path::new("/out/app/b.txt"), "b1 edited")
+            .unwrap();
+        fs.write_file(Path::new("/out/app/c.txt"), "c1 edited")
+            .unwrap();
+
+        let engine = engine_with(
+            vec![versioned_template(
+                "1.1.0",
+                &[
+                    ("a.txt", "a2"),
+                    ("b.txt", "b2"),
+                    ("c.txt", "c1"),
+                    ("d.txt", "d2"),
+                ],
+            )],
+            &fs,
+        );
+        let report = engine
+            .update(Target::rust_cli().unwrap(), "/out/app")
+            .unwrap();
+
+        assert_eq!(report.from.to_string(), "app@1.0.0");
+        assert_eq!(report.to.to_string(), "app@1.1.0");
+        assert_eq!(report.updated, [Path::new("a.txt")]);
+        assert_eq!(report.created, [Path::new("d.txt")]);
+        assert_eq!(report.unchanged, [Path::new("c.txt")]);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].path, Path::new("b.txt"));
+        assert!(report.conflicts[0].diff().contains("+b2"));
+        assert_eq!(fs.read_file(Path::new("/out/app/a.txt")).unwrap(), "a2");
+        assert_eq!(
+            fs.read_file(Path::new("/out/app/b.txt")).unwrap(),
+            "b1 edited"
+        );
+        assert_eq!(
+            fs.read_file(Path::new("/out/app/c.txt")).unwrap(),
+            "c1 edited"
+        );
+        let manifest = engine.read_manifest("/out/app").unwrap();
+        assert_eq!(manifest.template.version, "1.1.0");
+        assert_eq!(
+            manifest.hash_of(Path::new("b.txt")),
+            Some(update::hash("b1").as_str()),
+            "conflicts keep their old hash"
+        );
+
+        // Nothing left to write, but the conflict is still flagged
+        let again = engine
+            .update(Target::rust_cli().unwrap(), "/out/app")
+            .unwrap();
+        assert!(again.created.is_empty() && again.updated.is_empty());
+        assert!(again.has_conflicts());
+    }
+
+    #[test]
+    fn update_requires_a_manifest() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/out/app")).unwrap();
+
+        let err = Engine::with_filesystem(Box::new(fs))
+            .update(Target::rust_cli().unwrap(), "/out/app")
+            .unwrap_err();
+        assert_eq!(err.code(), "scaffold.manifest_missing");
+    }
+
+    #[test]
+    fn read_provenance_requires_a_scaffolded_project() {
+        let engine = Engine::with_filesystem(Box::new(MockFilesystem::new()));
+
+        let err = engine.read_provenance("/nowhere").unwrap_err();
+        assert!(matches!(
+            err,
+            crate::CoreError::Scaffold(ScaffoldError::ProvenanceMissing { .. })
+        ));
+    }
+
+    #[test]
+    fn scaffold_initializes_git_outside_a_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = Engine::new();
+        let options = ScaffoldOptions::new().vcs(VcsMode::Init);
+
+        let report = engine
+            .scaffold_with(Target::rust_cli().unwrap(), "tool", dir.path(), &options)
+            .unwrap();
+
+        let vcs = report.vcs().expect("vcs outcome is reported");
+        assert!(vcs.initialized);
+        assert_eq!(vcs.commit, None);
+        assert!(dir.path().join("tool/.git").is_dir());
+    }
+
+    #[test]
+    fn scaffold_with_git_writes_gitignore_and_commits_through_the_process() {
+        let mock_fs = Box::new(MockFilesystem::new());
+        let fs_clone = mock_fs.clone();
+        let process = MockProcess::new();
+        process.respond(
+            &["rev-parse", "--show-toplevel"],
+            ProcessOutput::failure("not a repo"),
+        );
+        process.respond(&["rev-parse", "HEAD"], ProcessOutput::success("abc123"));
+        let engine = Engine::with_filesystem(mock_fs).with_process(Box::new(process.clone()));
+        let target = Target::rust_cli().unwrap();
+
+        let report = engine
+            .scaffold_with(target, "tool", "/out", &ScaffoldOptions::new().git(true))
+            .unwrap();
+
+        let gitignore = fs_clone
+            .read_file(Path::new("/out/tool/.gitignore"))
+            .unwrap();
+        assert!(gitignore.contains("/target"));
+        let vcs = report.vcs().expect("vcs outcome is reported");
+        assert!(vcs.initialized);
+        assert_eq!(vcs.commit.as_deref(), Some("abc123"));
+
+        let calls = process.calls();
+        let subcommands: Vec<_> = calls.iter().map(|call| call.args[0].as_str()).collect();
+        assert_eq!(
+            subcommands,
+            ["rev-parse", "init", "add", "commit", "rev-parse"]
+        );
+        assert!(calls.iter().all(|call| call.dir == Path::new("/out/tool")));
+        assert!(calls[2].args.iter().any(|arg| arg == ".gitignore"));
+    }
+
+    #[test]
+    fn scaffold_refuses_names_that_are_not_packages_of_the_language() {
+        let fs = MockFilesystem::new();
+        let engine = Engine::with_filesystem(Box::new(fs.clone()));
+
+        let err = engine
+            .scaffold(
+                Target::typescript_frontend_vite_react().unwrap(),
+                "MyApp",
+                "/out",
+            )
+            .unwrap_err();
+
+        assert_eq!(err.code(), "domain.invalid_project_name");
+        assert!(matches!(
+            err.domain_error(),
+            Some(DomainError::InvalidProjectName { suggestion: Some(s), .. }) if s == "my-app"
+        ));
+        assert!(!fs.exists(Path::new("/out/MyApp")));
+        assert!(
+            engine
+                .scaffold(Target::rust_cli().unwrap(), "MyApp", "/out")
+                .is_ok(),
+            "crate names may be mixed case"
+        );
+    }
+
+    #[test]
+    fn scaffold_custom_without_tree_fails() {
+        let engine = Engine::with_filesystem(Box::new(MockFilesystem::new()));
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Custom)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let result = engine.scaffold(target, "skeleton", "/out");
+
+        assert!(matches!(
+            result,
+            Err(crate::CoreError::Scaffold(
+                ScaffoldError::InvalidTarget { .. }
+            ))
+        ));
+    }
+
     #[test]
     fn engine_finds_matching_templates() {
         let engine = Engine::new();
@@ -1,9 +1,13 @@
 //! Errors for scaffolding operations.
 
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use thiserror::Error;
 
+use crate::errors::ErrorPayload;
+
 /// Errors that can occur during scaffolding operations.
 ///
 /// All variants are cloneable to support concurrent operations.
@@ -41,6 +45,10 @@ pub enum ScaffoldError {
     #[error("Project directory already exists: {path}")]
     ProjectExists { path: PathBuf },
 
+    /// A path would land outside the project root
+    #[error("Refusing to write {path}: {reason}")]
+    UnsafePath { path: PathBuf, reason: String },
+
     /// Permission denied
     #[error("Permission denied: {path}")]
     PermissionDenied { path: PathBuf },
@@ -48,6 +56,39 @@ pub enum ScaffoldError {
     /// Validation failed
     #[error("Validation failed: {reason}")]
     ValidationFailed { reason: String },
+
+    /// Generated project failed its post-scaffold verification
+    #[error("Verification `{command}` failed{}:\n{output}", .exit_code.map(|c| format!(" (exit code {c})")).unwrap_or_default())]
+    VerificationFailed {
+        command: String,
+        exit_code: Option<i32>,
+        /// Captured stdout and stderr of the command
+        output: String,
+    },
+
+    /// Initializing or committing to version control failed
+    #[error("Version control `{command}` failed: {output}")]
+    VcsFailed {
+        command: String,
+        /// Captured stderr of git
+        output: String,
+    },
+
+    /// The project has no provenance file
+    #[error("No scaffold provenance at {path}; was this project generated by scarff?")]
+    ProvenanceMissing { path: PathBuf },
+
+    /// The provenance file can't be read or parsed
+    #[error("Invalid scaffold provenance at {path}: {reason}")]
+    ProvenanceInvalid { path: PathBuf, reason: String },
+
+    /// The project has no scaffold manifest
+    #[error("No scaffold manifest at {path}; was this project generated by scarff?")]
+    ManifestMissing { path: PathBuf },
+
+    /// The scaffold manifest can't be read or parsed
+    #[error("Invalid scaffold manifest at {path}: {reason}")]
+    ManifestInvalid { path: PathBuf, reason: String },
 }
 
 impl ScaffoldError {
@@ -83,6 +124,14 @@ impl ScaffoldError {
         }
     }
 
+    /// Create an UnsafePath error.
+    pub fn unsafe_path(path: impl Into<PathBuf>, reason: impl Into<String>) -> Self {
+        ScaffoldError::UnsafePath {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+
     /// Create a PermissionDenied error.
     pub fn permission_denied(path: impl Into<PathBuf>) -> Self {
         ScaffoldError::PermissionDenied { path: path.into() }
@@ -105,6 +154,16 @@ impl ScaffoldError {
         matches!(self, ScaffoldError::PermissionDenied { .. })
     }
 
+    /// Check if this is a verification error.
+    pub fn is_verification_error(&self) -> bool {
+        matches!(self, ScaffoldError::VerificationFailed { .. })
+    }
+
+    /// Check if this is a version control error.
+    pub fn is_vcs_error(&self) -> bool {
+        matches!(self, ScaffoldError::VcsFailed { .. })
+    }
+
     /// Get the underlying IO error, if this is a filesystem error.
     pub fn io_error(&self) -> Option<&std::io::Error> {
         match self {
@@ -112,6 +171,107 @@ impl ScaffoldError {
             _ => None,
         }
     }
+
+    /// Stable, machine-readable code for this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ScaffoldError::InvalidTarget { .. } => "scaffold.invalid_target",
+            ScaffoldError::TemplateResolution { .. } => "scaffold.template_resolution",
+            ScaffoldError::RenderingFailed { .. } => "scaffold.rendering_failed",
+            ScaffoldError::FilesystemWrite { .. } => "scaffold.filesystem_write",
+            ScaffoldError::ProjectExists { .. } => "scaffold.project_exists",
+            ScaffoldError::UnsafePath { .. } => "scaffold.unsafe_path",
+            ScaffoldError::PermissionDenied { .. } => "scaffold.permission_denied",
+            ScaffoldError::ValidationFailed { .. } => "scaffold.validation_failed",
+            ScaffoldError::VerificationFailed { .. } => "scaffold.verification_failed",
+            ScaffoldError::VcsFailed { .. } => "scaffold.vcs_failed",
+            ScaffoldError::ProvenanceMissing { .. } => "scaffold.provenance_missing",
+            ScaffoldError::ProvenanceInvalid { .. } => "scaffold.provenance_invalid",
+            ScaffoldError::ManifestMissing { .. } => "scaffold.manifest_missing",
+            ScaffoldError::ManifestInvalid { .. } => "scaffold.manifest_invalid",
+        }
+    }
+
+    /// Structured details of the failure.
+    pub fn context(&self) -> BTreeMap<&'static str, String> {
+        let mut context = BTreeMap::new();
+        match self {
+            ScaffoldError::InvalidTarget {
+                reason,
+                source_error,
+            } => {
+                context.insert("reason", reason.clone());
+                if let Some(source) = source_error {
+                    context.insert("source", source.clone());
+                }
+            }
+            ScaffoldError::TemplateResolution {
+                target,
+                suggestions,
+            } => {
+                context.insert("target", target.clone());
+                context.insert("suggestions", suggestions.join(", "));
+            }
+            ScaffoldError::RenderingFailed {
+                reason,
+                template_id,
+            } => {
+                context.insert("reason", reason.clone());
+                context.insert("template_id", template_id.clone());
+            }
+            ScaffoldError::FilesystemWrite {
+                path,
+                reason,
+                io_error,
+            } => {
+                context.insert("path", path.display().to_string());
+                context.insert("reason", reason.clone());
+                context.insert("io_error", io_error.to_string());
+            }
+            ScaffoldError::ProjectExists { path }
+            | ScaffoldError::PermissionDenied { path }
+            | ScaffoldError::ProvenanceMissing { path }
+            | ScaffoldError::ManifestMissing { path } => {
+                context.insert("path", path.display().to_string());
+            }
+            ScaffoldError::ValidationFailed { reason } => {
+                context.insert("reason", reason.clone());
+            }
+            ScaffoldError::VerificationFailed {
+                command,
+                exit_code,
+                output,
+            } => {
+                context.insert("command", command.clone());
+                if let Some(code) = exit_code {
+                    context.insert("exit_code", code.to_string());
+                }
+                context.insert("output", output.clone());
+            }
+            ScaffoldError::VcsFailed { command, output } => {
+                context.insert("command", command.clone());
+                context.insert("output", output.clone());
+            }
+            ScaffoldError::UnsafePath { path, reason }
+            | ScaffoldError::ProvenanceInvalid { path, reason }
+            | ScaffoldError::ManifestInvalid { path, reason } => {
+                context.insert("path", path.display().to_string());
+                context.insert("reason", reason.clone());
+            }
+        }
+        context
+    }
+
+    /// Machine-readable form of this error.
+    pub fn to_payload(&self) -> ErrorPayload {
+        ErrorPayload::new(self.code(), self.to_string(), self.context())
+    }
+}
+
+impl Serialize for ScaffoldError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_payload().serialize(serializer)
+    }
 }
 
 // Implement From for common error types
@@ -206,6 +366,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn verification_error_serializes_with_context() {
+        let err = ScaffoldError::VerificationFailed {
+            command: "cargo check".to_string(),
+            exit_code: Some(101),
+            output: "error[E0425]".to_string(),
+        };
+
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "scaffold.verification_failed");
+        assert_eq!(json["context"]["command"], "cargo check");
+        assert_eq!(json["context"]["exit_code"], "101");
+    }
+
     #[test]
     fn from_io_error() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
@@ -255,6 +430,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn verification_failed_display_includes_exit_code() {
+        let err = ScaffoldError::VerificationFailed {
+            command: "cargo check".to_string(),
+            exit_code: Some(101),
+            output: "error[E0425]".to_string(),
+        };
+
+        assert!(err.is_verification_error());
+        assert_eq!(
+            err.to_string(),
+            "Verification `cargo check` failed (exit code 101):\nerror[E0425]"
+        );
+    }
+
     #[test]
     fn validation_failed_error() {
         let err = ScaffoldError::validation_failed("invalid structure");
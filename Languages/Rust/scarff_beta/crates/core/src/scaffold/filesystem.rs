@@ -14,7 +14,7 @@
 //! # Examples
 //!
 //! ```rust,no_run
-//! use scarff_core::scaffold::filesystem::{Filesystem, RealFilesystem};
+//! use scarff_core_v1::scaffold::filesystem::{Filesystem, RealFilesystem};
 //! use std::path::Path;
 //!
 //! let fs = RealFilesystem;
@@ -54,7 +54,18 @@ pub trait Filesystem: Send + Sync {
     /// Returns an error if the file cannot be written.
     fn write_file(&self, path: &Path, content: &str) -> io::Result<()>;
 
-    /// Set file permissions.
+    /// Read a file's content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file doesn't exist or cannot be read.
+    fn read_file(&self, path: &Path) -> io::Result<String>;
+
+    /// Set the permissions of a file or directory.
+    ///
+    /// On Unix, this replaces the mode: read for everyone, write for the
+    /// owner, execute for everyone (and search, for readable directories).
+    /// Windows only knows read-only.
     ///
     /// # Errors
     ///
@@ -70,7 +81,39 @@ pub trait Filesystem: Send + Sync {
     /// Check if a path is a file.
     fn is_file(&self, path: &Path) -> bool;
 
-    /// Remove a file.
+    /// Check if a path is a symbolic link (without following it).
+    ///
+    /// Defaults to `false` for filesystems without links.
+    fn is_symlink(&self, _path: &Path) -> bool {
+        false
+    }
+
+    /// Create a symbolic link at `link` pointing to `target`, stored as
+    /// given (relative targets resolve from the link's directory).
+    ///
+    /// Defaults to an `Unsupported` error for filesystems without links.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the link cannot be created, with
+    /// `io::ErrorKind::Unsupported` where the platform (or, on Windows, the
+    /// user's privileges) doesn't allow symlinks.
+    fn create_symlink(&self, _target: &Path, _link: &Path) -> io::Result<()> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    /// Read where the symbolic link at `path` points.
+    ///
+    /// Defaults to an `Unsupported` error for filesystems without links.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` isn't a symlink or can't be read.
+    fn read_link(&self, _path: &Path) -> io::Result<PathBuf> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    /// Remove a file (or a symlink, without touching its target).
     ///
     /// # Errors
     ///
@@ -96,7 +139,7 @@ pub trait Filesystem: Send + Sync {
 /// # Examples
 ///
 /// ```rust,no_run
-/// use scarff_core::scaffold::filesystem::{Filesystem, RealFilesystem};
+/// use scarff_core_v1::scaffold::filesystem::{Filesystem, RealFilesystem};
 /// use std::path::Path;
 ///
 /// let fs = RealFilesystem;
@@ -116,29 +159,61 @@ impl Filesystem for RealFilesystem {
         std::fs::write(path, content)
     }
 
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
     #[cfg(unix)]
     fn set_permissions(&self, path: &Path, permissions: Permissions) -> io::Result<()> {
         use std::os::unix::fs::PermissionsExt;
 
-        if permissions.executable_flag() {
-            let metadata = std::fs::metadata(path)?;
-            let mut perms = metadata.permissions();
+        let mut mode = 0;
+        if permissions.readable() {
+            mode |= 0o444;
+        }
+        if permissions.writable() {
+            mode |= 0o200;
+        }
+        // Directories need the execute bit to be entered
+        if permissions.executable_flag() || (permissions.readable() && path.is_dir()) {
+            mode |= 0o111;
+        }
 
-            // Add executable bit for owner, group, and others
-            let mode = perms.mode();
-            perms.set_mode(mode | 0o111);
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+    }
 
-            std::fs::set_permissions(path, perms)?;
-        }
+    #[cfg(windows)]
+    fn set_permissions(&self, path: &Path, permissions: Permissions) -> io::Result<()> {
+        // Windows doesn't have an executable bit, only read-only
+        let mut perms = std::fs::metadata(path)?.permissions();
+        perms.set_readonly(!permissions.writable());
+        std::fs::set_permissions(path, perms)
+    }
 
-        Ok(())
+    #[cfg(unix)]
+    fn create_symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        std::os::unix::fs::symlink(target, link)
     }
 
     #[cfg(windows)]
-    fn set_permissions(&self, _path: &Path, _permissions: Permissions) -> io::Result<()> {
-        // Windows doesn't have executable bit
-        // Could use file extensions or other mechanisms if needed
-        Ok(())
+    fn create_symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        /// `ERROR_PRIVILEGE_NOT_HELD`: symlinks need developer mode or admin
+        const PRIVILEGE_NOT_HELD: i32 = 1314;
+
+        let resolved = link.parent().unwrap_or(Path::new("")).join(target);
+        let result = if resolved.is_dir() {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        };
+        result.map_err(|e| match e.raw_os_error() {
+            Some(PRIVILEGE_NOT_HELD) => io::Error::new(io::ErrorKind::Unsupported, e),
+            _ => e,
+        })
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        std::fs::read_link(path)
     }
 
     fn exists(&self, path: &Path) -> bool {
@@ -153,6 +228,10 @@ impl Filesystem for RealFilesystem {
         path.is_file()
     }
 
+    fn is_symlink(&self, path: &Path) -> bool {
+        path.is_symlink()
+    }
+
     fn remove_file(&self, path: &Path) -> io::Result<()> {
         std::fs::remove_file(path)
     }
@@ -174,7 +253,7 @@ impl Filesystem for RealFilesystem {
 /// # Examples
 ///
 /// ```rust
-/// use scarff_core::scaffold::filesystem::{Filesystem, MockFilesystem};
+/// use scarff_core_v1::scaffold::filesystem::{Filesystem, MockFilesystem};
 /// use std::path::Path;
 ///
 /// let fs = MockFilesystem::new();
@@ -194,6 +273,12 @@ pub struct MockFilesystem {
 pub struct MockFilesystemInner {
     files: HashMap<PathBuf, FileEntry>,
     directories: HashSet<PathBuf>,
+    /// Files whose `write_file` fails
+    failing_writes: HashSet<PathBuf>,
+    /// Link path to target
+    symlinks: HashMap<PathBuf, PathBuf>,
+    /// Whether creating links fails as `Unsupported`
+    no_symlinks: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -209,13 +294,16 @@ impl MockFilesystem {
             inner: Arc::new(RwLock::new(MockFilesystemInner {
                 files: HashMap::new(),
                 directories: HashSet::new(),
+                failing_writes: HashSet::new(),
+                symlinks: HashMap::new(),
+                no_symlinks: false,
             })),
         }
     }
 
-    /// Read a file's content (only available in MockFilesystem).
+    /// Read a file's content.
     ///
-    /// This is a testing helper not available in the trait.
+    /// Also available through [`Filesystem::read_file`].
     ///
     /// # Errors
     ///
@@ -282,11 +370,35 @@ impl MockFilesystem {
         inner.directories.iter().cloned().collect()
     }
 
-    /// Clear all files and directories.
+    /// Make [`Filesystem::write_file`] fail for `path`, to exercise error
+    /// handling (only available in `MockFilesystem`).
+    pub fn fail_writes_to(&self, path: &Path) {
+        let mut inner = self.inner.write().unwrap();
+        inner.failing_writes.insert(path.to_path_buf());
+    }
+
+    /// Mark a path as a symbolic link (only available in `MockFilesystem`).
+    ///
+    /// The link has an empty target and isn't followed; it only makes
+    /// [`Filesystem::is_symlink`] and [`Filesystem::exists`] report it.
+    pub fn add_symlink(&self, path: &Path) {
+        let mut inner = self.inner.write().unwrap();
+        inner.symlinks.insert(path.to_path_buf(), PathBuf::new());
+    }
+
+    /// Make [`Filesystem::create_symlink`] fail as `Unsupported`, like
+    /// Windows without developer mode (only available in `MockFilesystem`).
+    pub fn disable_symlinks(&self) {
+        let mut inner = self.inner.write().unwrap();
+        inner.no_symlinks = true;
+    }
+
+    /// Clear all files, directories and symlinks.
     pub fn clear(&self) {
         let mut inner = self.inner.write().unwrap();
         inner.files.clear();
         inner.directories.clear();
+        inner.symlinks.clear();
     }
 
     /// Get the total number of files.
@@ -331,6 +443,13 @@ impl Filesystem for MockFilesystem {
             .write()
             .map_err(|_| io::Error::other("Lock poisoned"))?;
 
+        if inner.failing_writes.contains(path) {
+            return Err(io::Error::other(format!(
+                "Injected write failure: {}",
+                path.display()
+            )));
+        }
+
         // Ensure parent directory exists
         if let Some(parent) = path.parent()
             && !parent.as_os_str().is_empty()
@@ -353,12 +472,21 @@ impl Filesystem for MockFilesystem {
         Ok(())
     }
 
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        MockFilesystem::read_file(self, path)
+    }
+
     fn set_permissions(&self, path: &Path, permissions: Permissions) -> io::Result<()> {
         let mut inner = self
             .inner
             .write()
             .map_err(|_| io::Error::other("Lock poisoned"))?;
 
+        // Directory permissions aren't tracked
+        if inner.directories.contains(path) {
+            return Ok(());
+        }
+
         inner
             .files
             .get_mut(path)
@@ -373,7 +501,9 @@ impl Filesystem for MockFilesystem {
 
     fn exists(&self, path: &Path) -> bool {
         let inner = self.inner.read().unwrap();
-        inner.files.contains_key(path) || inner.directories.contains(path)
+        inner.files.contains_key(path)
+            || inner.directories.contains(path)
+            || inner.symlinks.contains_key(path)
     }
 
     fn is_dir(&self, path: &Path) -> bool {
@@ -386,18 +516,62 @@ impl Filesystem for MockFilesystem {
         inner.files.contains_key(path)
     }
 
-    fn remove_file(&self, path: &Path) -> io::Result<()> {
+    fn is_symlink(&self, path: &Path) -> bool {
+        let inner = self.inner.read().unwrap();
+        inner.symlinks.contains_key(path)
+    }
+
+    fn create_symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
         let mut inner = self
             .inner
             .write()
             .map_err(|_| io::Error::other("Lock poisoned"))?;
 
-        inner.files.remove(path).ok_or_else(|| {
+        if inner.no_symlinks {
+            return Err(io::ErrorKind::Unsupported.into());
+        }
+        if inner.files.contains_key(link)
+            || inner.directories.contains(link)
+            || inner.symlinks.contains_key(link)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Path already exists: {}", link.display()),
+            ));
+        }
+
+        inner
+            .symlinks
+            .insert(link.to_path_buf(), target.to_path_buf());
+        Ok(())
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        let inner = self
+            .inner
+            .read()
+            .map_err(|_| io::Error::other("Lock poisoned"))?;
+
+        inner.symlinks.get(path).cloned().ok_or_else(|| {
             io::Error::new(
                 io::ErrorKind::NotFound,
-                format!("File not found: {}", path.display()),
+                format!("Symlink not found: {}", path.display()),
             )
-        })?;
+        })
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let mut inner = self
+            .inner
+            .write()
+            .map_err(|_| io::Error::other("Lock poisoned"))?;
+
+        if inner.files.remove(path).is_none() && inner.symlinks.remove(path).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("File not found: {}", path.display()),
+            ));
+        }
 
         Ok(())
     }
@@ -418,6 +592,7 @@ impl Filesystem for MockFilesystem {
         inner
             .directories
             .retain(|dir_path| !dir_path.starts_with(path));
+        inner.symlinks.retain(|link, _| !link.starts_with(path));
 
         Ok(())
     }
@@ -654,4 +829,56 @@ mod tests {
         let perms = metadata.permissions();
         assert!(perms.mode() & 0o111 != 0);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn real_fs_sets_modes_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+        use tempfile::tempdir;
+
+        let temp = tempdir().unwrap();
+        let fs = RealFilesystem;
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+
+        let config = temp.path().join("config.toml");
+        fs.write_file(&config, "").unwrap();
+        fs.set_permissions(&config, Permissions::read_only())
+            .unwrap();
+        assert_eq!(mode(&config), 0o444);
+
+        let script = temp.path().join("run.sh");
+        fs.write_file(&script, "").unwrap();
+        fs.set_permissions(&script, Permissions::full()).unwrap();
+        assert_eq!(mode(&script), 0o755);
+
+        let link = temp.path().join("run");
+        fs.create_symlink(Path::new("run.sh"), &link).unwrap();
+        assert!(fs.is_symlink(&link));
+        assert_eq!(fs.read_link(&link).unwrap(), Path::new("run.sh"));
+
+        fs.remove_file(&link).unwrap();
+        assert!(!fs.exists(&link));
+        assert!(fs.exists(&script), "removing a link keeps its target");
+    }
+
+    #[test]
+    fn mock_fs_symlinks() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/p")).unwrap();
+        fs.create_symlink(Path::new("run.sh"), Path::new("/p/run"))
+            .unwrap();
+
+        assert!(fs.is_symlink(Path::new("/p/run")));
+        assert_eq!(
+            fs.read_link(Path::new("/p/run")).unwrap(),
+            Path::new("run.sh")
+        );
+        assert!(
+            fs.create_symlink(Path::new("x"), Path::new("/p/run"))
+                .is_err()
+        );
+
+        fs.remove_file(Path::new("/p/run")).unwrap();
+        assert!(!fs.exists(Path::new("/p/run")));
+    }
 }
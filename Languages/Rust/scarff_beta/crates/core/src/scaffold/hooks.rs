@@ -0,0 +1,591 @@
+//! Post-scaffold hooks.
+//!
+//! Templates declare [`Hook`]s; once the project has been written, the
+//! engine runs them in order in the project root and records one
+//! [`HookOutcome`] per hook in the [`ScaffoldReport`](crate::ScaffoldReport):
+//! - builtins (`chmod +x`, next steps) always run: they don't start a
+//!   process and can't touch anything outside the project
+//! - commands and scripts start processes, so they only run when the
+//!   caller opts in with
+//!   [`ScaffoldOptions::allow_hooks`](crate::ScaffoldOptions::allow_hooks);
+//!   otherwise they are reported as skipped
+//!
+//! Allowed hooks run sandboxed (see [`HookPolicy::Sandboxed`]): only
+//! allowlisted programs, with the working directory pinned to the project
+//! root and no argument pointing outside it. `git` is further limited to
+//! [`GIT_SANDBOX_SUBCOMMANDS`], and runs without the system or global
+//! config, hooks or fsmonitor. Anything else is reported as
+//! blocked, unless the caller opts out with
+//! [`ScaffoldOptions::unsafe_hooks`](crate::ScaffoldOptions::unsafe_hooks).
+//! Every process a hook starts is recorded in its [`HookOutcome`].
+//!
+//! The sandbox only checks arguments, not what a program does with them:
+//! allowlisting a build tool (`cargo`, `npm`, `uv`, ...) means trusting
+//! the template's code, since `cargo run` or `npm exec` run whatever the
+//! template ships.
+//!
+//! A failing hook doesn't fail the scaffold, nor stop the hooks after it.
+
+use std::{
+    collections::BTreeSet,
+    fmt,
+    path::{Component, Path, PathBuf},
+    process::{Command, Output},
+};
+use tracing::{debug, info, instrument, warn};
+
+use crate::{
+    domain::{BuiltinHook, Hook},
+    scaffold::writer::FileWriter,
+};
+
+/// Programs sandboxed hooks may run when no allowlist is configured.
+///
+/// Only `git`, limited to [`GIT_SANDBOX_SUBCOMMANDS`]; build tools run the
+/// template's own code, so they have to be allowlisted explicitly.
+pub const DEFAULT_HOOK_ALLOWLIST: &[&str] = &["git"];
+
+/// `git` subcommands sandboxed hooks may run.
+///
+/// The first argument must be one of these: global options (`-c`, `-C`,
+/// `--exec-path`, ...) and subcommands that run other programs
+/// (`submodule foreach`, `bisect run`, `rebase -x`, `difftool -x`,
+/// `filter-branch`, ...) are refused.
+pub const GIT_SANDBOX_SUBCOMMANDS: &[&str] = &[
+    "init", "add", "commit", "branch", "checkout", "switch", "tag", "mv", "rm", "status",
+];
+
+/// Config overrides for sandboxed `git`: no hooks, no fsmonitor.
+const GIT_SANDBOX_CONFIG: &[&str] = &[
+    "-c",
+    "core.hooksPath=/dev/null",
+    "-c",
+    "core.fsmonitor=false",
+];
+
+/// Environment for sandboxed `git`: no system or global config, so only
+/// the repository's own config applies, which templates can't write.
+const GIT_SANDBOX_ENV: &[(&str, &str)] = &[
+    ("GIT_CONFIG_NOSYSTEM", "1"),
+    ("GIT_CONFIG_GLOBAL", "/dev/null"),
+];
+
+// ============================================================================
+// HookPolicy
+// ============================================================================
+
+/// Which command and script hooks may start a process.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HookPolicy {
+    /// None of them: they're reported as skipped (the default)
+    #[default]
+    Disabled,
+    /// Commands whose program is in the allowlist, run in the project root
+    /// with no absolute or `..` argument leaving it, and `git` only with a
+    /// subcommand in [`GIT_SANDBOX_SUBCOMMANDS`]. Scripts are blocked: what
+    /// a shell runs can't be checked.
+    Sandboxed(BTreeSet<String>),
+    /// All of them, unchecked
+    Unsafe,
+}
+
+impl HookPolicy {
+    /// Sandboxed, allowing [`DEFAULT_HOOK_ALLOWLIST`].
+    pub fn sandboxed() -> Self {
+        Self::sandboxed_with(DEFAULT_HOOK_ALLOWLIST.iter().copied())
+    }
+
+    /// Sandboxed, allowing exactly `programs`.
+    pub fn sandboxed_with<S: Into<String>>(programs: impl IntoIterator<Item = S>) -> Self {
+        Self::Sandboxed(programs.into_iter().map(Into::into).collect())
+    }
+
+    /// Why `hook` may not start its process, if it may not.
+    fn refusal(&self, hook: &Hook) -> Option<String> {
+        let Self::Sandboxed(allowlist) = self else {
+            return None;
+        };
+        match hook {
+            Hook::Command { program, args } => {
+                if !allowlist.contains(program) {
+                    Some(format!("`{program}` is not in the hook allowlist"))
+                } else if let Some(reason) = git_refusal(program, args) {
+                    Some(reason)
+                } else {
+                    std::iter::once(program)
+                        .chain(args)
+                        .find(|arg| leaves_root(arg))
+                        .map(|arg| format!("`{arg}` points outside the project"))
+                }
+            }
+            Hook::Script(_) => Some("scripts only run with unsafe hooks".to_string()),
+            Hook::Builtin(_) => None,
+        }
+    }
+}
+
+/// Why sandboxed `git` may not run with `args`, if `program` is `git` and
+/// it may not.
+///
+/// `init --template` is refused too: the template directory is copied into
+/// `.git`, config included.
+fn git_refusal(program: &str, args: &[String]) -> Option<String> {
+    if program != "git" {
+        return None;
+    }
+    match args.first().map(String::as_str) {
+        Some(subcommand) if GIT_SANDBOX_SUBCOMMANDS.contains(&subcommand) => args
+            .iter()
+            .find(|arg| arg.starts_with("--template"))
+            .map(|arg| format!("`git {subcommand} {arg}` may load another config")),
+        Some(first) => Some(format!("`git {first}` is not an allowed git subcommand")),
+        None => Some("`git` needs a subcommand".to_string()),
+    }
+}
+
+/// Whether `arg` (or the value of a `--flag=value` argument) is an
+/// absolute path or climbs above the directory it's resolved from.
+fn leaves_root(arg: &str) -> bool {
+    let value = arg.split_once('=').map_or(arg, |(_, value)| value);
+    [arg, value].into_iter().any(|candidate| {
+        let path = Path::new(candidate);
+        if path.has_root() || candidate.starts_with('~') {
+            return true;
+        }
+        let mut depth = 0usize;
+        path.components().any(|component| match component {
+            Component::ParentDir => match depth.checked_sub(1) {
+                Some(parent) => {
+                    depth = parent;
+                    false
+                }
+                None => true,
+            },
+            Component::Normal(_) => {
+                depth += 1;
+                false
+            }
+            _ => false,
+        })
+    })
+}
+
+// ============================================================================
+// HookOutcome
+// ============================================================================
+
+/// What happened when running a hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookStatus {
+    /// The hook ran and succeeded
+    Succeeded,
+    /// The hook starts a process and hooks weren't allowed
+    Skipped,
+    /// The sandbox refused to run the hook, with the reason
+    Blocked(String),
+    /// The hook failed, with the reason (or the process output)
+    Failed(String),
+}
+
+/// A process started by a hook, recorded for auditing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedCommand {
+    /// Program that was run (the shell, for a script)
+    pub program: String,
+    /// Its arguments
+    pub args: Vec<String>,
+    /// Working directory it ran in
+    pub dir: PathBuf,
+    /// Exit code, if the process started and exited normally
+    pub exit_code: Option<i32>,
+}
+
+impl fmt::Display for ExecutedCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        write!(f, " (in {})", self.dir.display())
+    }
+}
+
+/// A hook and what happened when running it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookOutcome {
+    /// The hook, with its template variables rendered
+    pub hook: Hook,
+    /// What happened
+    pub status: HookStatus,
+    /// The process the hook started, if it started one
+    pub command: Option<ExecutedCommand>,
+}
+
+// ============================================================================
+// Running
+// ============================================================================
+
+/// Run `hooks` in order in `root`.
+///
+/// Hooks that start a process only run as far as `policy` lets them.
+#[instrument(skip(hooks, policy, writer), fields(root = %root.display(), hooks = hooks.len()))]
+pub(crate) fn run(
+    hooks: &[Hook],
+    root: &Path,
+    policy: &HookPolicy,
+    writer: &FileWriter,
+) -> Vec<HookOutcome> {
+    let outcomes: Vec<_> = hooks
+        .iter()
+        .map(|hook| {
+            let mut command = None;
+            let status = if hook.starts_process() && *policy == HookPolicy::Disabled {
+                debug!(hook = %hook, "Hooks not allowed, skipping");
+                HookStatus::Skipped
+            } else if let Some(reason) = policy.refusal(hook) {
+                warn!(hook = %hook, %reason, "Hook blocked");
+                HookStatus::Blocked(reason)
+            } else {
+                match run_one(hook, root, policy, writer, &mut command) {
+                    Ok(()) => HookStatus::Succeeded,
+                    Err(reason) => {
+                        warn!(hook = %hook, %reason, "Hook failed");
+                        HookStatus::Failed(reason)
+                    }
+                }
+            };
+            HookOutcome {
+                hook: hook.clone(),
+                status,
+                command,
+            }
+        })
+        .collect();
+
+    info!(
+        failed = outcomes
+            .iter()
+            .filter(|o| matches!(o.status, HookStatus::Failed(_)))
+            .count(),
+        "Hooks run"
+    );
+    outcomes
+}
+
+fn run_one(
+    hook: &Hook,
+    root: &Path,
+    policy: &HookPolicy,
+    writer: &FileWriter,
+    command: &mut Option<ExecutedCommand>,
+) -> Result<(), String> {
+    match hook {
+        Hook::Command { program, args }
+            if program == "git" && matches!(policy, HookPolicy::Sandboxed(_)) =>
+        {
+            let args: Vec<_> = GIT_SANDBOX_CONFIG
+                .iter()
+                .map(ToString::to_string)
+                .chain(args.iter().cloned())
+                .collect();
+            spawn(program, &args, GIT_SANDBOX_ENV, root, command)
+        }
+        Hook::Command { program, args } => spawn(program, args, &[], root, command),
+        Hook::Script(script) => {
+            let (shell, flag) = if cfg!(windows) {
+                ("cmd", "/C")
+            } else {
+                ("sh", "-c")
+            };
+            spawn(
+                shell,
+                &[flag.to_string(), script.clone()],
+                &[],
+                root,
+                command,
+            )
+        }
+        Hook::Builtin(BuiltinHook::MakeExecutable(path)) => writer
+            .make_executable_in(root, path.as_path())
+            .map_err(|e| e.to_string()),
+        // Surfaced through the report; nothing to do here
+        Hook::Builtin(BuiltinHook::NextSteps(_)) => Ok(()),
+    }
+}
+
+/// Run `program` in `dir` with `env` set, recording it in `command`, and
+/// fail with its output on a non-zero exit.
+fn spawn(
+    program: &str,
+    args: &[String],
+    env: &[(&str, &str)],
+    dir: &Path,
+    command: &mut Option<ExecutedCommand>,
+) -> Result<(), String> {
+    let executed = command.insert(ExecutedCommand {
+        program: program.to_string(),
+        args: args.to_vec(),
+        dir: dir.to_path_buf(),
+        exit_code: None,
+    });
+    info!(command = %executed, "Running hook");
+
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .env("PWD", dir)
+        .envs(env.iter().copied())
+        .output()
+        .map_err(|e| format!("failed to start `{program}`: {e}"))?;
+    executed.exit_code = output.status.code();
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let code = output
+            .status
+            .code()
+            .map_or_else(|| "killed".to_string(), |c| format!("exit code {c}"));
+        match combined(&output) {
+            out if out.is_empty() => Err(code),
+            out => Err(format!("{code}: {out}")),
+        }
+    }
+}
+
+/// Stdout and stderr joined, skipping empty streams.
+fn combined(output: &Output) -> String {
+    [&output.stdout, &output.stderr]
+        .iter()
+        .map(|s| String::from_utf8_lossy(s).trim_end().to_string())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold::filesystem::{Filesystem, MockFilesystem, RealFilesystem};
+
+    fn statuses(outcomes: &[HookOutcome]) -> Vec<&HookStatus> {
+        outcomes.iter().map(|o| &o.status).collect()
+    }
+
+    #[test]
+    fn processes_are_skipped_unless_allowed() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/p")).unwrap();
+        let writer = FileWriter::new(Box::new(fs));
+        let hooks = [
+            Hook::command("scarff-definitely-not-a-program", Vec::<String>::new()),
+            Hook::script("exit 1"),
+            Hook::next_steps(["cargo run"]),
+        ];
+
+        let outcomes = run(&hooks, Path::new("/p"), &HookPolicy::Disabled, &writer);
+
+        assert_eq!(
+            statuses(&outcomes),
+            [
+                &HookStatus::Skipped,
+                &HookStatus::Skipped,
+                &HookStatus::Succeeded
+            ]
+        );
+    }
+
+    #[test]
+    fn make_executable_sets_the_bit_and_reports_missing_files() {
+        let fs = Box::new(MockFilesystem::new());
+        fs.create_dir_all(Path::new("/p")).unwrap();
+        fs.write_file(Path::new("/p/run.sh"), "#!/bin/sh\n")
+            .unwrap();
+        let writer = FileWriter::new(fs.clone());
+        let hooks = [
+            Hook::make_executable("run.sh"),
+            Hook::make_executable("missing.sh"),
+        ];
+
+        let outcomes = run(&hooks, Path::new("/p"), &HookPolicy::Disabled, &writer);
+
+        assert_eq!(outcomes[0].status, HookStatus::Succeeded);
+        assert!(matches!(outcomes[1].status, HookStatus::Failed(_)));
+        assert!(fs.is_executable(Path::new("/p/run.sh")).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failures_are_reported_and_later_hooks_still_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = FileWriter::new(Box::new(RealFilesystem));
+        let hooks = [
+            Hook::script("echo broken >&2; exit 3"),
+            Hook::command("sh", ["-c", "touch ran"]),
+        ];
+
+        let outcomes = run(&hooks, dir.path(), &HookPolicy::Unsafe, &writer);
+
+        assert_eq!(
+            outcomes[0].status,
+            HookStatus::Failed("exit code 3: broken".to_string())
+        );
+        assert_eq!(outcomes[1].status, HookStatus::Succeeded);
+        assert!(dir.path().join("ran").exists(), "runs in the project root");
+    }
+
+    #[test]
+    fn sandbox_blocks_unlisted_programs_scripts_and_paths_outside_the_root() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/p")).unwrap();
+        let writer = FileWriter::new(Box::new(fs));
+        let hooks = [
+            Hook::command("curl", ["https://example.com"]),
+            Hook::script("git init"),
+            Hook::command("git", ["add", "../elsewhere"]),
+            Hook::command("git", ["add", "--pathspec-from-file=/etc/passwd"]),
+            Hook::make_executable("missing.sh"),
+        ];
+
+        let outcomes = run(&hooks, Path::new("/p"), &HookPolicy::sandboxed(), &writer);
+
+        let reasons: Vec<_> = outcomes[..4]
+            .iter()
+            .map(|o| match &o.status {
+                HookStatus::Blocked(reason) => reason.as_str(),
+                other => panic!("expected a blocked hook, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                "`curl` is not in the hook allowlist",
+                "scripts only run with unsafe hooks",
+                "`../elsewhere` points outside the project",
+                "`--pathspec-from-file=/etc/passwd` points outside the project",
+            ]
+        );
+        assert!(outcomes.iter().all(|o| o.command.is_none()));
+        assert!(matches!(outcomes[4].status, HookStatus::Failed(_)));
+    }
+
+    #[test]
+    fn sandbox_only_runs_allowed_git_subcommands() {
+        let refusal = |args: &[&str]| {
+            HookPolicy::sandboxed().refusal(&Hook::command("git", args.iter().copied()))
+        };
+
+        assert_eq!(
+            refusal(&["-c", "alias.x=!rm -rf ~/x", "x"]).as_deref(),
+            Some("`git -c` is not an allowed git subcommand")
+        );
+        for args in [
+            &["--config-env=core.sshCommand=CMD", "fetch"][..],
+            &["clone", "--upload-pack=touch pwned", "repo"],
+            &["submodule", "foreach", "touch pwned"],
+            &["bisect", "run", "touch", "pwned"],
+            &["rebase", "-x", "touch pwned", "main"],
+            &["difftool", "-x", "touch pwned"],
+            &["difftool", "--extcmd=touch pwned"],
+            &["filter-branch", "--tree-filter", "touch pwned"],
+            &["config", "core.fsmonitor", "touch pwned"],
+            &[],
+        ] {
+            assert!(refusal(args).is_some(), "git {args:?} was allowed");
+        }
+        assert_eq!(
+            refusal(&["init", "--template=tpl"]).as_deref(),
+            Some("`git init --template=tpl` may load another config")
+        );
+        assert_eq!(refusal(&["init", "-b", "main"]), None);
+        assert_eq!(refusal(&["commit", "-m", "Initial commit"]), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sandboxed_git_runs_without_hooks_or_outside_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.name", "Scarff"]);
+        git(&["config", "user.email", "scarff@example.com"]);
+        git(&["config", "core.hooksPath", "hooks"]);
+        let hook = dir.path().join("hooks/pre-commit");
+        std::fs::create_dir(hook.parent().unwrap()).unwrap();
+        std::fs::write(&hook, "#!/bin/sh\ntouch pwned\n").unwrap();
+        RealFilesystem
+            .set_permissions(&hook, crate::domain::Permissions::full())
+            .unwrap();
+        let writer = FileWriter::new(Box::new(RealFilesystem));
+        let hooks = [Hook::command(
+            "git",
+            ["commit", "-q", "--allow-empty", "-m", "Initial commit"],
+        )];
+
+        let outcomes = run(&hooks, dir.path(), &HookPolicy::sandboxed(), &writer);
+
+        assert_eq!(outcomes[0].status, HookStatus::Succeeded);
+        assert!(!dir.path().join("pwned").exists(), "the hook ran");
+        let args = &outcomes[0].command.as_ref().unwrap().args;
+        assert_eq!(args[..4], GIT_SANDBOX_CONFIG[..]);
+    }
+
+    #[test]
+    fn default_allowlist_only_trusts_git() {
+        let refusal = HookPolicy::sandboxed().refusal(&Hook::command("cargo", ["run"]));
+
+        assert_eq!(
+            refusal.as_deref(),
+            Some("`cargo` is not in the hook allowlist")
+        );
+    }
+
+    #[test]
+    fn paths_that_stay_in_the_root_are_allowed() {
+        assert!(!leaves_root("src/../Cargo.toml"));
+        assert!(!leaves_root("--manifest-path=crates/a/Cargo.toml"));
+        assert!(!leaves_root("-m"));
+        assert!(leaves_root("src/../../x"));
+        assert!(leaves_root("~/.ssh"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn executed_commands_are_recorded_with_their_exit_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = FileWriter::new(Box::new(RealFilesystem));
+        let hooks = [
+            Hook::command("sh", ["-c", "exit 4"]),
+            Hook::command("touch", ["ran"]),
+        ];
+
+        let outcomes = run(
+            &hooks,
+            dir.path(),
+            &HookPolicy::sandboxed_with(["sh", "touch"]),
+            &writer,
+        );
+
+        let command = outcomes[0].command.as_ref().unwrap();
+        assert_eq!(command.exit_code, Some(4));
+        assert_eq!(command.dir, dir.path());
+        assert_eq!(
+            command.to_string(),
+            format!("sh -c exit 4 (in {})", dir.path().display())
+        );
+        assert_eq!(outcomes[1].status, HookStatus::Succeeded);
+        assert!(dir.path().join("ran").exists());
+    }
+}
@@ -2,14 +2,52 @@
 //!
 //! This module coordinates the entire scaffolding process:
 //! - Engine: Main orchestrator
+//! - Batch: Many projects in one call, sharing template resolution
 //! - Writer: Filesystem operations
 //! - Filesystem: Abstraction for testability
+//! - Process: Abstraction over external programs (git), for the same reason
+//! - Verify: Optional post-scaffold compile check
+//! - Hooks: Post-scaffold steps declared by the template
+//! - Vcs: Optional .gitignore, git init / initial commit
+//! - Report: What was written, per entry, with a manifest of the files
+//! - Plan: What a scaffold would write, without writing it (dry run)
+//! - Overwrite: Policies for scaffolding into an existing directory
+//! - Provenance: How a project was generated (`.scarff/provenance.toml`)
+//! - Upgrade: Re-render a project at its template's latest version
+//! - Update: Re-scaffold in place, keeping user edits (`.scarff/manifest.json`)
 
+pub mod batch;
 pub mod engine;
 pub mod errors;
 pub mod filesystem;
+pub mod hooks;
+pub mod options;
+pub mod overwrite;
+pub mod plan;
+pub mod process;
+pub mod provenance;
+pub mod report;
+pub mod update;
+pub mod upgrade;
+pub mod vcs;
+pub mod verify;
 pub(crate) mod writer;
 
+pub use batch::{BatchItem, BatchOutcome, BatchReport};
 pub use engine::{Engine, TemplateInfo};
 pub use errors::ScaffoldError;
+pub use hooks::{
+    DEFAULT_HOOK_ALLOWLIST, ExecutedCommand, GIT_SANDBOX_SUBCOMMANDS, HookOutcome, HookPolicy,
+    HookStatus,
+};
+pub use options::ScaffoldOptions;
+pub use overwrite::OverwritePolicy;
+pub use plan::{PlannedAction, PlannedEntry, ScaffoldPlan};
+pub use process::{ProcessOutput, ProcessRunner};
+pub use provenance::{PROVENANCE_PATH, Provenance, TargetProvenance, TemplateProvenance};
+pub use report::{EntryKind, EntryStatus, GeneratedFile, ReportEntry, ScaffoldReport};
+pub use update::{MANIFEST_PATH, ProjectManifest, UpdateReport};
+pub use upgrade::{ChangeKind, FileChange, Resolution, UpgradePlan, UpgradeReport};
+pub use vcs::{VcsMode, VcsOutcome};
+pub use verify::{VerificationOutput, VerifyCommand};
 pub(crate) use writer::{FileWriter, Writer};
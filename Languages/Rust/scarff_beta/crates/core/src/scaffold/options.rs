@@ -0,0 +1,216 @@
+//! Options controlling a single scaffold operation.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    domain::CustomTree,
+    scaffold::{
+        hooks::HookPolicy, overwrite::OverwritePolicy, vcs::VcsMode, verify::VerifyCommand,
+    },
+};
+
+/// Options for [`Engine::scaffold_with`](crate::scaffold::Engine::scaffold_with).
+///
+/// # Examples
+///
+/// ```rust
+/// use scarff_core_v1::scaffold::ScaffoldOptions;
+///
+/// let options = ScaffoldOptions::new().verify(true);
+/// assert!(options.verify_enabled());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScaffoldOptions {
+    verify: bool,
+    skip_readme: bool,
+    allow_hooks: bool,
+    unsafe_hooks: bool,
+    hook_allowlist: Option<Vec<String>>,
+    vcs: VcsMode,
+    overwrite: OverwritePolicy,
+    verify_command: Option<VerifyCommand>,
+    custom_tree: Option<CustomTree>,
+    variables: BTreeMap<String, String>,
+}
+
+impl ScaffoldOptions {
+    /// Default options: no verification, README generated.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run the language's checker on the generated project.
+    ///
+    /// If the check fails, the generated project is removed.
+    #[must_use]
+    pub fn verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Override the verification command (implies `verify(true)`).
+    #[must_use]
+    pub fn verify_command(mut self, command: VerifyCommand) -> Self {
+        self.verify = true;
+        self.verify_command = Some(command);
+        self
+    }
+
+    /// Generate a `README.md` from template metadata when the template
+    /// has none (the default). Custom trees never get one.
+    #[must_use]
+    pub fn readme(mut self, readme: bool) -> Self {
+        self.skip_readme = !readme;
+        self
+    }
+
+    /// Put the project under git (off by default).
+    ///
+    /// An enclosing repository is reused; otherwise one is initialized in
+    /// the project root. Templates without a `.gitignore` get the one for
+    /// their language. A failure removes the generated project.
+    #[must_use]
+    pub fn vcs(mut self, mode: VcsMode) -> Self {
+        self.vcs = mode;
+        self
+    }
+
+    /// `git init` the project and commit the scaffold, or leave git alone:
+    /// shorthand for [`VcsMode::Commit`] and [`VcsMode::Off`].
+    #[must_use]
+    pub fn git(self, enabled: bool) -> Self {
+        self.vcs(if enabled {
+            VcsMode::Commit
+        } else {
+            VcsMode::Off
+        })
+    }
+
+    /// Run the template's command and script hooks (off by default).
+    ///
+    /// They run sandboxed: only commands whose program is in the
+    /// [allowlist](Self::hook_allowlist), in the project root, with no
+    /// argument pointing outside it. Other hooks are reported as blocked.
+    /// Builtin hooks always run.
+    #[must_use]
+    pub fn allow_hooks(mut self, allow: bool) -> Self {
+        self.allow_hooks = allow;
+        self
+    }
+
+    /// Programs sandboxed command hooks may run, instead of
+    /// [`DEFAULT_HOOK_ALLOWLIST`](crate::scaffold::DEFAULT_HOOK_ALLOWLIST).
+    ///
+    /// Allowlisting a build tool means trusting the template's code: the
+    /// sandbox checks arguments, not what `cargo run` or `npm exec` runs.
+    #[must_use]
+    pub fn hook_allowlist<S: Into<String>>(
+        mut self,
+        programs: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.hook_allowlist = Some(programs.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Run every command and script hook without the sandbox (implies
+    /// `allow_hooks(true)`).
+    ///
+    /// They can then run anything, anywhere: only use this for templates
+    /// you trust.
+    #[must_use]
+    pub fn unsafe_hooks(mut self, unsafe_hooks: bool) -> Self {
+        self.unsafe_hooks = unsafe_hooks;
+        self
+    }
+
+    /// What to do when the project directory already exists (abort by
+    /// default).
+    ///
+    /// Any other policy writes into the directory and leaves it in place
+    /// if verification or version control fails.
+    #[must_use]
+    pub fn overwrite(mut self, policy: OverwritePolicy) -> Self {
+        self.overwrite = policy;
+        self
+    }
+
+    /// Tree to materialize for `ProjectKind::Custom` targets.
+    #[must_use]
+    pub fn custom_tree(mut self, tree: CustomTree) -> Self {
+        self.custom_tree = Some(tree);
+        self
+    }
+
+    /// Set a template variable (`{{KEY}}` in templates).
+    #[must_use]
+    pub fn var(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.variables.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set several template variables, e.g. a preset's defaults.
+    #[must_use]
+    pub fn vars<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.variables
+            .extend(vars.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Whether verification is enabled.
+    pub fn verify_enabled(&self) -> bool {
+        self.verify
+    }
+
+    /// Whether a README is generated for templates without one.
+    pub fn readme_enabled(&self) -> bool {
+        !self.skip_readme
+    }
+
+    /// What to do with version control.
+    pub fn vcs_mode(&self) -> VcsMode {
+        self.vcs
+    }
+
+    /// Whether command and script hooks may run.
+    pub fn hooks_allowed(&self) -> bool {
+        self.allow_hooks || self.unsafe_hooks
+    }
+
+    /// How command and script hooks run.
+    pub fn hook_policy(&self) -> HookPolicy {
+        if self.unsafe_hooks {
+            HookPolicy::Unsafe
+        } else if !self.allow_hooks {
+            HookPolicy::Disabled
+        } else {
+            match &self.hook_allowlist {
+                Some(programs) => HookPolicy::sandboxed_with(programs.iter().cloned()),
+                None => HookPolicy::sandboxed(),
+            }
+        }
+    }
+
+    /// What to do with an existing project directory.
+    pub fn overwrite_policy(&self) -> OverwritePolicy {
+        self.overwrite
+    }
+
+    /// The custom verification command, if one was set.
+    pub fn custom_verify_command(&self) -> Option<&VerifyCommand> {
+        self.verify_command.as_ref()
+    }
+
+    /// Extra template variables.
+    pub fn variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+
+    /// The custom tree, if one was set.
+    pub fn tree(&self) -> Option<&CustomTree> {
+        self.custom_tree.as_ref()
+    }
+}
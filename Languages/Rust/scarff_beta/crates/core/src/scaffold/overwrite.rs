@@ -0,0 +1,133 @@
+//! Scaffolding into a directory that already exists.
+//!
+//! By default the engine refuses to touch an existing project directory.
+//! An [`OverwritePolicy`] other than [`OverwritePolicy::Abort`] writes into
+//! it instead: missing entries are created, entries already on disk as
+//! they would be written are skipped, and each file whose content differs
+//! is handled according to the policy. The per-file decision is recorded
+//! in the [`ScaffoldReport`](crate::ScaffoldReport).
+
+use std::path::{Path, PathBuf};
+
+use similar::{DiffTag, TextDiff};
+
+/// What to do with files that already exist with different content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// Refuse to scaffold into an existing directory
+    #[default]
+    Abort,
+    /// Keep the existing file
+    Skip,
+    /// Replace the existing file
+    Overwrite,
+    /// Replace the existing file, keeping a copy as `<path>.bak`
+    Backup,
+    /// Keep both versions in the file, with conflict markers around each
+    /// differing hunk
+    Merge,
+}
+
+/// Marks the start of the existing side of a merge conflict.
+pub const CONFLICT_START: &str = "<<<<<<< existing";
+/// Separates the existing side from the template side.
+pub const CONFLICT_SEPARATOR: &str = "=======";
+/// Marks the end of the template side of a merge conflict.
+pub const CONFLICT_END: &str = ">>>>>>> template";
+
+/// Combine `current` and `rendered` line by line.
+///
+/// Common lines are kept once; every differing hunk becomes a conflict
+/// block with the existing lines first, as in a git merge.
+pub(crate) fn merge(current: &str, rendered: &str) -> String {
+    let diff = TextDiff::from_lines(current, rendered);
+    let (old, new) = (diff.old_slices(), diff.new_slices());
+
+    let mut merged = String::with_capacity(current.len() + rendered.len());
+    for op in diff.ops() {
+        if op.tag() == DiffTag::Equal {
+            merged.extend(old[op.old_range()].iter().copied());
+            continue;
+        }
+        push_line(&mut merged, CONFLICT_START);
+        merged.extend(old[op.old_range()].iter().copied());
+        push_line(&mut merged, CONFLICT_SEPARATOR);
+        merged.extend(new[op.new_range()].iter().copied());
+        push_line(&mut merged, CONFLICT_END);
+    }
+    merged
+}
+
+/// Append `line` on a line of its own.
+fn push_line(text: &mut String, line: &str) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(line);
+    text.push('\n');
+}
+
+/// Where to back up `path`: `<path>.bak`, or `<path>.bak.N` for the first
+/// `N` not `taken`.
+pub(crate) fn backup_path(path: &Path, taken: impl Fn(&Path) -> bool) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".bak");
+
+    let mut candidate = PathBuf::from(&name);
+    let mut n = 0;
+    while taken(&candidate) {
+        n += 1;
+        let mut numbered = name.clone();
+        numbered.push(format!(".{n}"));
+        candidate = PathBuf::from(numbered);
+    }
+    candidate
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_common_lines_and_marks_conflicts() {
+        let current = "[package]\nname = \"mine\"\nedition = \"2021\"\n";
+        let rendered = "[package]\nname = \"demo\"\nedition = \"2021\"\n";
+
+        assert_eq!(
+            merge(current, rendered),
+            "[package]\n\
+             <<<<<<< existing\n\
+             name = \"mine\"\n\
+             =======\n\
+             name = \"demo\"\n\
+             >>>>>>> template\n\
+             edition = \"2021\"\n"
+        );
+    }
+
+    #[test]
+    fn merge_handles_missing_trailing_newline() {
+        let merged = merge("old", "new\n");
+        assert_eq!(
+            merged,
+            "<<<<<<< existing\nold\n=======\nnew\n>>>>>>> template\n"
+        );
+    }
+
+    #[test]
+    fn backup_path_skips_taken_names() {
+        let taken = |p: &Path| p == Path::new("src/main.rs.bak");
+        assert_eq!(
+            backup_path(Path::new("src/main.rs"), taken),
+            PathBuf::from("src/main.rs.bak.1")
+        );
+        assert_eq!(
+            backup_path(Path::new("Cargo.toml"), |_| false),
+            PathBuf::from("Cargo.toml.bak")
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Dry run of a scaffold operation.
+//!
+//! [`Engine::plan`](crate::Engine::plan) renders the project exactly as a
+//! scaffold would and compares every entry with what is on disk, without
+//! writing anything:
+//!
+//! | on disk                 | planned action               |
+//! |-------------------------|------------------------------|
+//! | missing                 | [`PlannedAction::Create`]    |
+//! | file, different content | [`PlannedAction::Overwrite`] |
+//! | file, same content      | [`PlannedAction::Skip`]      |
+//! | existing directory      | [`PlannedAction::Skip`]      |
+//! | link elsewhere, or file | [`PlannedAction::Overwrite`] |
+//! | link, same target       | [`PlannedAction::Skip`]      |
+//!
+//! For symlinks, the "content" is the link's target.
+
+use std::path::{Path, PathBuf};
+
+use similar::TextDiff;
+
+use crate::{
+    domain::{FsEntry, ProjectStructure},
+    scaffold::{report::EntryKind, writer::FileWriter},
+};
+
+/// What a scaffold would do to a single entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlannedAction {
+    /// The entry doesn't exist yet
+    Create,
+    /// A file with different content is in the way
+    Overwrite,
+    /// The entry already exists as it would be written
+    Skip,
+}
+
+/// A single line of a [`ScaffoldPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedEntry {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    /// File, directory or symlink
+    pub kind: EntryKind,
+    /// What a scaffold would do with it
+    pub action: PlannedAction,
+    /// Content on disk, for existing files (target, for existing links)
+    pub current: Option<String>,
+    /// Rendered content, for files (target, for links)
+    pub content: Option<String>,
+}
+
+impl PlannedEntry {
+    /// Unified diff from the file on disk to the rendered content.
+    ///
+    /// Empty for directories and skipped files.
+    pub fn diff(&self) -> String {
+        let Some(content) = &self.content else {
+            return String::new();
+        };
+        let path = self.path.display();
+        TextDiff::from_lines(self.current.as_deref().unwrap_or_default(), content)
+            .unified_diff()
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string()
+    }
+}
+
+/// What a scaffold would write, computed without touching the filesystem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaffoldPlan {
+    root: PathBuf,
+    entries: Vec<PlannedEntry>,
+}
+
+impl ScaffoldPlan {
+    /// Compare every entry of `structure` with what `writer` sees on disk.
+    pub(crate) fn compare(structure: &ProjectStructure, writer: &FileWriter) -> Self {
+        let entries = structure
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                FsEntry::File(f) => {
+                    let current = writer.read(&structure.root.join(&f.path)).ok();
+                    let action = match &current {
+                        None => PlannedAction::Create,
+                        Some(current) if *current == f.content => PlannedAction::Skip,
+                        Some(_) => PlannedAction::Overwrite,
+                    };
+                    PlannedEntry {
+                        path: f.path.clone(),
+                        kind: EntryKind::File,
+                        action,
+                        current,
+                        content: Some(f.content.clone()),
+                    }
+                }
+                FsEntry::Directory(d) => PlannedEntry {
+                    path: d.path.clone(),
+                    kind: EntryKind::Directory,
+                    action: if writer.is_dir(&structure.root.join(&d.path)) {
+                        PlannedAction::Skip
+                    } else {
+                        PlannedAction::Create
+                    },
+                    current: None,
+                    content: None,
+                },
+                FsEntry::Symlink(l) => {
+                    let path = structure.root.join(&l.path);
+                    let current = writer.read_link(&path);
+                    let action = match &current {
+                        Some(current) if *current == l.target => PlannedAction::Skip,
+                        _ if writer.exists(&path) => PlannedAction::Overwrite,
+                        _ => PlannedAction::Create,
+                    };
+                    PlannedEntry {
+                        path: l.path.clone(),
+                        kind: EntryKind::Symlink,
+                        action,
+                        current: current.map(|t| format!("{}\n", t.display())),
+                        content: Some(format!("{}\n", l.target.display())),
+                    }
+                }
+            })
+            .collect();
+
+        Self {
+            root: structure.root.clone(),
+            entries,
+        }
+    }
+
+    /// Root directory of the project that would be generated.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// All planned entries, in write order.
+    pub fn entries(&self) -> &[PlannedEntry] {
+        &self.entries
+    }
+
+    /// Entries with the given action.
+    pub fn with_action(&self, action: PlannedAction) -> impl Iterator<Item = &PlannedEntry> {
+        self.entries.iter().filter(move |e| e.action == action)
+    }
+
+    /// Whether a scaffold would replace any existing file.
+    pub fn overwrites(&self) -> bool {
+        self.with_action(PlannedAction::Overwrite).next().is_some()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::Permissions,
+        scaffold::filesystem::{Filesystem, MockFilesystem},
+    };
+
+    fn structure() -> ProjectStructure {
+        let mut structure = ProjectStructure::new("/out/demo");
+        structure.add_directory("src", Permissions::read_write());
+        structure.add_file(
+            "src/main.rs",
+            "fn main() {}\n".into(),
+            Permissions::read_write(),
+        );
+        structure.add_file(
+            "Cargo.toml",
+            "[package]\n".into(),
+            Permissions::read_write(),
+        );
+        structure.add_file("README.md", "# demo\n".into(), Permissions::read_write());
+        structure
+    }
+
+    #[test]
+    fn classifies_entries_against_disk() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/out/demo/src")).unwrap();
+        fs.write_file(Path::new("/out/demo/Cargo.toml"), "[package]\n")
+            .unwrap();
+        fs.write_file(Path::new("/out/demo/README.md"), "# old\n")
+            .unwrap();
+        let writer = FileWriter::new(Box::new(fs.clone()));
+
+        let plan = ScaffoldPlan::compare(&structure(), &writer);
+
+        let actions: Vec<_> = plan
+            .entries()
+            .iter()
+            .map(|e| (e.path.to_str().unwrap(), e.action))
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                ("src", PlannedAction::Skip),
+                ("src/main.rs", PlannedAction::Create),
+                ("Cargo.toml", PlannedAction::Skip),
+                ("README.md", PlannedAction::Overwrite),
+            ]
+        );
+        assert!(plan.overwrites());
+        assert_eq!(fs.file_count(), 2, "planning must not write");
+    }
+
+    #[test]
+    fn compares_symlink_targets() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/out/demo")).unwrap();
+        fs.create_symlink(Path::new("a.sh"), Path::new("/out/demo/same"))
+            .unwrap();
+        fs.create_symlink(Path::new("old.sh"), Path::new("/out/demo/moved"))
+            .unwrap();
+        let structure = ProjectStructure::new("/out/demo")
+            .with_symlink("same", "a.sh")
+            .with_symlink("moved", "a.sh")
+            .with_symlink("new", "a.sh");
+
+        let plan = ScaffoldPlan::compare(&structure, &FileWriter::new(Box::new(fs)));
+
+        let actions: Vec<_> = plan.entries().iter().map(|e| (e.kind, e.action)).collect();
+        assert_eq!(
+            actions,
+            [
+                (EntryKind::Symlink, PlannedAction::Skip),
+                (EntryKind::Symlink, PlannedAction::Overwrite),
+                (EntryKind::Symlink, PlannedAction::Create),
+            ]
+        );
+        assert!(plan.entries()[1].diff().contains("-old.sh"));
+    }
+
+    #[test]
+    fn overwrite_diff_shows_both_sides() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/out/demo")).unwrap();
+        fs.write_file(Path::new("/out/demo/README.md"), "# old\n")
+            .unwrap();
+        let plan = ScaffoldPlan::compare(&structure(), &FileWriter::new(Box::new(fs)));
+
+        let readme = plan
+            .with_action(PlannedAction::Overwrite)
+            .next()
+            .expect("README is overwritten");
+        let diff = readme.diff();
+        assert!(diff.contains("-# old"));
+        assert!(diff.contains("+# demo"));
+    }
+}
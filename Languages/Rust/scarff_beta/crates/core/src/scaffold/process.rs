@@ -0,0 +1,184 @@
+//! Process abstraction for the external programs a scaffold runs.
+//!
+//! Version control shells out to `git`. Running it through the
+//! [`ProcessRunner`] trait lets tests script git's answers instead of
+//! needing it installed, the way [`Filesystem`](super::filesystem::Filesystem)
+//! stands in for the disk:
+//!
+//! - `RealProcess`: spawns the program with [`std::process::Command`]
+//! - `MockProcess`: records every call and answers from a script
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scarff_core_v1::scaffold::process::{MockProcess, ProcessOutput, ProcessRunner};
+//! use std::path::Path;
+//!
+//! let process = MockProcess::new();
+//! process.respond(&["rev-parse", "HEAD"], ProcessOutput::success("abc123"));
+//!
+//! let args = ["rev-parse".to_string(), "HEAD".to_string()];
+//! let output = process.run(Path::new("/repo"), "git", &args)?;
+//! assert_eq!(output.stdout, "abc123");
+//! assert_eq!(process.calls().len(), 1);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Arc, RwLock},
+};
+
+// ============================================================================
+// ProcessRunner Trait
+// ============================================================================
+
+/// What a finished process reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessOutput {
+    /// Whether it exited with status 0
+    pub success: bool,
+    /// Standard output, trimmed
+    pub stdout: String,
+    /// Standard error, trimmed
+    pub stderr: String,
+}
+
+impl ProcessOutput {
+    /// A successful run that printed `stdout`.
+    pub fn success(stdout: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            stdout: stdout.into(),
+            stderr: String::new(),
+        }
+    }
+
+    /// A failed run that printed `stderr`.
+    pub fn failure(stderr: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            stdout: String::new(),
+            stderr: stderr.into(),
+        }
+    }
+}
+
+/// Abstract interface for running external programs.
+pub trait ProcessRunner: Send + Sync {
+    /// Run `program` with `args` in `dir` and wait for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program cannot be started (for example,
+    /// it isn't installed); a program that runs and fails is reported in
+    /// [`ProcessOutput::success`].
+    fn run(&self, dir: &Path, program: &str, args: &[String]) -> io::Result<ProcessOutput>;
+}
+
+// ============================================================================
+// Real Process Implementation
+// ============================================================================
+
+/// Process runner that spawns real programs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealProcess;
+
+impl ProcessRunner for RealProcess {
+    fn run(&self, dir: &Path, program: &str, args: &[String]) -> io::Result<ProcessOutput> {
+        let output = Command::new(program).args(args).current_dir(dir).output()?;
+
+        Ok(ProcessOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+}
+
+// ============================================================================
+// Mock Process Implementation
+// ============================================================================
+
+/// A recorded call to [`MockProcess::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessCall {
+    /// Directory the program ran in
+    pub dir: PathBuf,
+    /// Program name
+    pub program: String,
+    /// Arguments, in order
+    pub args: Vec<String>,
+}
+
+/// Scripted process runner for testing.
+///
+/// A call is answered by the first response whose arguments are a prefix
+/// of the call's (so `["commit"]` answers any commit), and succeeds with
+/// no output when none matches. Clones share the script and the calls.
+#[derive(Debug, Clone, Default)]
+pub struct MockProcess {
+    inner: Arc<RwLock<MockProcessInner>>,
+}
+
+#[derive(Debug, Default)]
+struct MockProcessInner {
+    responses: Vec<(Vec<String>, ProcessOutput)>,
+    calls: Vec<ProcessCall>,
+}
+
+impl MockProcess {
+    /// Create a mock that succeeds silently for every call.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer calls whose arguments start with `args` with `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another thread panicked while holding the mock.
+    pub fn respond(&self, args: &[&str], output: ProcessOutput) {
+        let args = args.iter().map(ToString::to_string).collect();
+        self.inner
+            .write()
+            .expect("mock process lock poisoned")
+            .responses
+            .push((args, output));
+    }
+
+    /// Every call made so far, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another thread panicked while holding the mock.
+    pub fn calls(&self) -> Vec<ProcessCall> {
+        self.inner
+            .read()
+            .expect("mock process lock poisoned")
+            .calls
+            .clone()
+    }
+}
+
+impl ProcessRunner for MockProcess {
+    fn run(&self, dir: &Path, program: &str, args: &[String]) -> io::Result<ProcessOutput> {
+        let mut inner = self
+            .inner
+            .write()
+            .map_err(|_| io::Error::other("Lock poisoned"))?;
+
+        inner.calls.push(ProcessCall {
+            dir: dir.to_path_buf(),
+            program: program.to_string(),
+            args: args.to_vec(),
+        });
+        Ok(inner
+            .responses
+            .iter()
+            .find(|(prefix, _)| args.starts_with(prefix))
+            .map_or_else(|| ProcessOutput::success(""), |(_, output)| output.clone()))
+    }
+}
@@ -0,0 +1,291 @@
+//! Scaffold provenance: how a project was generated.
+//!
+//! Every template-based scaffold writes [`PROVENANCE_PATH`] into the
+//! project, recording the template and engine versions, the target options
+//! and the render variables:
+//!
+//! ```toml
+//! scarff_version = "0.1.0"
+//!
+//! [template]
+//! name = "Rust CLI (Default)"
+//! version = "1.0.0"
+//!
+//! [target]
+//! language = "rust"
+//! kind = "cli"
+//! architecture = "layered"
+//!
+//! [variables]
+//! PROJECT_NAME = "my-cli"
+//! ```
+//!
+//! Commands that work on an existing project (diff, upgrade, add) read it
+//! back with [`Engine::read_provenance`](crate::Engine::read_provenance).
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::{
+        Architecture, CiProvider, DependencyManager, Framework, Language, License, ProjectKind,
+        PythonLayout, RenderContext, Target, TaskRunner, TemplateId, TestRunner,
+    },
+    scaffold::errors::ScaffoldError,
+};
+
+/// Path of the provenance file, relative to the project root.
+pub const PROVENANCE_PATH: &str = ".scarff/provenance.toml";
+
+/// How a project was generated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Version of the engine that generated the project
+    pub scarff_version: String,
+    /// Template the project was rendered from
+    pub template: TemplateProvenance,
+    /// Target options the project was generated for
+    pub target: TargetProvenance,
+    /// Every variable the template was rendered with
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+/// Template name and version recorded in a [`Provenance`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateProvenance {
+    /// Template name
+    pub name: String,
+    /// Template version
+    pub version: String,
+}
+
+/// Target options recorded in a [`Provenance`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetProvenance {
+    /// Language, e.g. `rust`
+    pub language: String,
+    /// Project kind, e.g. `cli`
+    pub kind: String,
+    /// Framework, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework: Option<String>,
+    /// Architecture pattern
+    pub architecture: String,
+    /// Dependency manager, if chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency_manager: Option<String>,
+    /// Python project layout, if chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_layout: Option<String>,
+    /// Test runner, if chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_runner: Option<String>,
+    /// Task runner, if chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_runner: Option<String>,
+    /// License, if chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// CI provider, if chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci: Option<String>,
+    /// Optional features enabled
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+}
+
+impl Provenance {
+    /// Record a scaffold of `template` for `target`, rendered with `context`.
+    pub(crate) fn new(template: &TemplateId, target: &Target, context: &RenderContext) -> Self {
+        Self {
+            scarff_version: env!("CARGO_PKG_VERSION").to_string(),
+            template: TemplateProvenance {
+                name: template.name.clone(),
+                version: template.version.clone(),
+            },
+            target: TargetProvenance {
+                language: target.language().to_string(),
+                kind: target.kind().to_string(),
+                framework: target.framework().map(|f| f.to_string()),
+                architecture: target.architecture().to_string(),
+                dependency_manager: target.dependency_manager().map(|m| m.to_string()),
+                python_layout: target.python_layout().map(|l| l.to_string()),
+                test_runner: target.test_runner().map(|r| r.to_string()),
+                task_runner: target.task_runner().map(|r| r.to_string()),
+                license: target.license().map(|l| l.to_string()),
+                ci: target.ci().map(|c| c.to_string()),
+                features: target.features().to_vec(),
+            },
+            variables: context
+                .all()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+
+    /// Parse a provenance file read from `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScaffoldError::ProvenanceInvalid` for malformed TOML or
+    /// missing fields.
+    pub fn from_toml(path: &Path, content: &str) -> Result<Self, ScaffoldError> {
+        toml::from_str(content).map_err(|e| ScaffoldError::ProvenanceInvalid {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Serialize to the provenance file format.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: every field is a string, a list of strings or a
+    /// table of them, all of which TOML can represent.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("provenance is always representable as TOML")
+    }
+
+    /// The template id (`name@version`) the project was rendered from.
+    pub fn template_id(&self) -> TemplateId {
+        TemplateId::new(self.template.name.clone(), self.template.version.clone())
+    }
+
+    /// Rebuild the recorded target, validated against the current rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScaffoldError::ProvenanceInvalid` if an option is unknown
+    /// or the combination is no longer valid.
+    pub fn target(&self) -> Result<Target, ScaffoldError> {
+        let invalid = |reason: String| ScaffoldError::ProvenanceInvalid {
+            path: PROVENANCE_PATH.into(),
+            reason,
+        };
+        let recorded = &self.target;
+
+        let language = Language::parse(&recorded.language)
+            .ok_or_else(|| invalid(format!("unknown language '{}'", recorded.language)))?;
+        let kind = ProjectKind::parse(&recorded.kind)
+            .ok_or_else(|| invalid(format!("unknown project type '{}'", recorded.kind)))?;
+        let architecture = Architecture::parse(&recorded.architecture)
+            .ok_or_else(|| invalid(format!("unknown architecture '{}'", recorded.architecture)))?;
+
+        let mut builder = Target::builder()
+            .language(language)
+            .kind(kind)
+            .map_err(|e| invalid(e.to_string()))?;
+        if let Some(framework) = &recorded.framework {
+            let framework = Framework::parse(framework)
+                .ok_or_else(|| invalid(format!("unknown framework '{framework}'")))?;
+            builder = builder
+                .framework(framework)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        builder = builder
+            .architecture(architecture)
+            .map_err(|e| invalid(e.to_string()))?;
+        if let Some(manager) = &recorded.dependency_manager {
+            let manager = DependencyManager::parse(manager)
+                .ok_or_else(|| invalid(format!("unknown dependency manager '{manager}'")))?;
+            builder = builder
+                .dependency_manager(manager)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(layout) = &recorded.python_layout {
+            let layout = PythonLayout::parse(layout)
+                .ok_or_else(|| invalid(format!("unknown Python layout '{layout}'")))?;
+            builder = builder
+                .python_layout(layout)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(runner) = &recorded.test_runner {
+            let runner = TestRunner::parse(runner)
+                .ok_or_else(|| invalid(format!("unknown test runner '{runner}'")))?;
+            builder = builder
+                .test_runner(runner)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(runner) = &recorded.task_runner {
+            let runner = TaskRunner::parse(runner)
+                .ok_or_else(|| invalid(format!("unknown task runner '{runner}'")))?;
+            builder = builder
+                .task_runner(runner)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(license) = &recorded.license {
+            let license = License::parse(license)
+                .ok_or_else(|| invalid(format!("unknown license '{license}'")))?;
+            builder = builder.license(license);
+        }
+        if let Some(ci) = &recorded.ci {
+            let ci = CiProvider::parse(ci)
+                .ok_or_else(|| invalid(format!("unknown CI provider '{ci}'")))?;
+            builder = builder.ci(ci);
+        }
+        for feature in &recorded.features {
+            builder = builder
+                .feature(feature.as_str())
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+
+        builder.build().map_err(|e| invalid(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provenance() -> Provenance {
+        let target = Target::rust_cli().unwrap();
+        let context = RenderContext::new("my-cli").with_target(&target);
+        Provenance::new(
+            &TemplateId::new("rust-cli", "1.2.0".to_string()),
+            &target,
+            &context,
+        )
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let provenance = provenance();
+        let toml = provenance.to_toml();
+
+        assert!(toml.contains("[template]\nname = \"rust-cli\"\nversion = \"1.2.0\""));
+        assert!(!toml.contains("test_runner"), "unset options are omitted");
+        assert_eq!(
+            Provenance::from_toml(Path::new("p.toml"), &toml).unwrap(),
+            provenance
+        );
+    }
+
+    #[test]
+    fn rebuilds_the_recorded_target() {
+        let provenance = provenance();
+
+        assert_eq!(provenance.target().unwrap(), Target::rust_cli().unwrap());
+        assert_eq!(provenance.template_id().to_string(), "rust-cli@1.2.0");
+        assert_eq!(provenance.variables["PROJECT_NAME"], "my-cli");
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        let err = Provenance::from_toml(Path::new("p.toml"), "scarff_version = 1").unwrap_err();
+        assert_eq!(err.code(), "scaffold.provenance_invalid");
+
+        let mut provenance = provenance();
+        provenance.target.framework = Some("django".to_string());
+        assert_eq!(
+            provenance.target().unwrap_err().code(),
+            "scaffold.provenance_invalid"
+        );
+    }
+}
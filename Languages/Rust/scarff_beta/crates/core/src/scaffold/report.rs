@@ -0,0 +1,414 @@
+//! Per-entry report of a scaffold operation.
+//!
+//! The engine returns a [`ScaffoldReport`] so frontends (CLI, services) can
+//! display or assert on exactly what happened to every path. Besides the
+//! per-entry outcomes, it holds a manifest of the files written (size,
+//! permissions, source template), how long the scaffold took, and
+//! warnings worth a look (skipped or merged files, failed hooks, empty
+//! files).
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    domain::{BuiltinHook, FsEntry, Hook, Permissions, ProjectStructure},
+    scaffold::{
+        hooks::{ExecutedCommand, HookOutcome, HookStatus},
+        vcs::VcsOutcome,
+    },
+};
+
+/// What kind of filesystem entry a report line refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// A regular file
+    File,
+    /// A directory
+    Directory,
+    /// A symbolic link
+    Symlink,
+}
+
+/// Outcome for a single entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryStatus {
+    /// The entry was written
+    Created,
+    /// The entry was left untouched
+    Skipped,
+    /// An existing file was replaced
+    Overwritten,
+    /// An existing file was replaced after being copied to this path
+    /// (relative to the project root)
+    BackedUp(PathBuf),
+    /// An existing file now holds both versions, with conflict markers
+    Merged,
+    /// Writing the entry failed
+    Failed(String),
+}
+
+/// A single line of a [`ScaffoldReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportEntry {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    /// File, directory or symlink
+    pub kind: EntryKind,
+    /// What happened to it
+    pub status: EntryStatus,
+}
+
+/// A file written by the scaffold, as listed in the report's manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    /// Size of the rendered content, in bytes (for a merged file, the
+    /// incoming content, without the existing one)
+    pub size: u64,
+    /// Permissions the file was written with
+    pub permissions: Permissions,
+    /// Template the file was rendered from, as `name@version`
+    pub template: String,
+}
+
+/// Result of a scaffold operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaffoldReport {
+    root: PathBuf,
+    entries: Vec<ReportEntry>,
+    files: Vec<GeneratedFile>,
+    hooks: Vec<HookOutcome>,
+    vcs: Option<VcsOutcome>,
+    duration: Duration,
+}
+
+impl ScaffoldReport {
+    /// Build a report marking every entry of a written structure as created.
+    pub(crate) fn created(structure: &ProjectStructure) -> Self {
+        let entries = structure
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                FsEntry::File(f) => ReportEntry {
+                    path: f.path.clone(),
+                    kind: EntryKind::File,
+                    status: EntryStatus::Created,
+                },
+                FsEntry::Directory(d) => ReportEntry {
+                    path: d.path.clone(),
+                    kind: EntryKind::Directory,
+                    status: EntryStatus::Created,
+                },
+                FsEntry::Symlink(l) => ReportEntry {
+                    path: l.path.clone(),
+                    kind: EntryKind::Symlink,
+                    status: EntryStatus::Created,
+                },
+            })
+            .collect();
+
+        Self {
+            root: structure.root.clone(),
+            entries,
+            files: Vec::new(),
+            hooks: Vec::new(),
+            vcs: None,
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Build a report from per-entry outcomes of a write into `root`.
+    pub(crate) fn new(root: impl Into<PathBuf>, entries: Vec<ReportEntry>) -> Self {
+        Self {
+            root: root.into(),
+            entries,
+            files: Vec::new(),
+            hooks: Vec::new(),
+            vcs: None,
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Attach what the template's hooks did.
+    pub(crate) fn with_hooks(mut self, hooks: Vec<HookOutcome>) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Attach what version control did.
+    pub(crate) fn with_vcs(mut self, vcs: Option<VcsOutcome>) -> Self {
+        self.vcs = vcs;
+        self
+    }
+
+    /// List the files of `structure` this report says were written, with
+    /// the template each was rendered from.
+    pub(crate) fn with_manifest(
+        mut self,
+        structure: &ProjectStructure,
+        template_of: impl Fn(&Path) -> String,
+    ) -> Self {
+        let written: HashSet<&Path> = self.written_files().collect();
+        let files = structure
+            .files()
+            .filter(|f| written.contains(f.path.as_path()))
+            .map(|f| GeneratedFile {
+                path: f.path.clone(),
+                size: f.content.len() as u64,
+                permissions: f.permissions,
+                template: template_of(&f.path),
+            })
+            .collect();
+        self.files = files;
+        self
+    }
+
+    /// Attach how long the scaffold took.
+    pub(crate) fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Root directory of the generated project.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// All report entries, in write order.
+    pub fn entries(&self) -> &[ReportEntry] {
+        &self.entries
+    }
+
+    /// Entries that were created.
+    pub fn created_entries(&self) -> impl Iterator<Item = &ReportEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == EntryStatus::Created)
+    }
+
+    /// Entries that were skipped.
+    pub fn skipped_entries(&self) -> impl Iterator<Item = &ReportEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == EntryStatus::Skipped)
+    }
+
+    /// Entries that replaced an existing file (overwritten, backed up or
+    /// merged).
+    pub fn replaced_entries(&self) -> impl Iterator<Item = &ReportEntry> {
+        self.entries.iter().filter(|e| {
+            matches!(
+                e.status,
+                EntryStatus::Overwritten | EntryStatus::BackedUp(_) | EntryStatus::Merged
+            )
+        })
+    }
+
+    /// Files the scaffold wrote, whatever the reason.
+    pub fn written_files(&self) -> impl Iterator<Item = &Path> {
+        self.entries
+            .iter()
+            .filter(|e| {
+                e.kind == EntryKind::File
+                    && !matches!(e.status, EntryStatus::Skipped | EntryStatus::Failed(_))
+            })
+            .map(|e| e.path.as_path())
+    }
+
+    /// Entries that failed.
+    pub fn failed_entries(&self) -> impl Iterator<Item = &ReportEntry> {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, EntryStatus::Failed(_)))
+    }
+
+    /// Every hook the template declared and what happened to it, in order.
+    pub fn hooks(&self) -> &[HookOutcome] {
+        &self.hooks
+    }
+
+    /// Hooks that failed.
+    pub fn failed_hooks(&self) -> impl Iterator<Item = &HookOutcome> {
+        self.hooks
+            .iter()
+            .filter(|h| matches!(h.status, HookStatus::Failed(_)))
+    }
+
+    /// Every process the hooks started, in order, for auditing.
+    pub fn executed_commands(&self) -> impl Iterator<Item = &ExecutedCommand> {
+        self.hooks.iter().filter_map(|h| h.command.as_ref())
+    }
+
+    /// Next steps for the user, from the template's next-steps hooks.
+    pub fn next_steps(&self) -> impl Iterator<Item = &str> {
+        self.hooks
+            .iter()
+            .flat_map(|h| match &h.hook {
+                Hook::Builtin(BuiltinHook::NextSteps(steps)) => steps.as_slice(),
+                _ => &[],
+            })
+            .map(String::as_str)
+    }
+
+    /// What version control did, if it was enabled.
+    pub fn vcs(&self) -> Option<&VcsOutcome> {
+        self.vcs.as_ref()
+    }
+
+    /// Number of files created.
+    pub fn files_created(&self) -> usize {
+        self.created_entries()
+            .filter(|e| e.kind == EntryKind::File)
+            .count()
+    }
+
+    /// Manifest of every file written, in write order.
+    pub fn files(&self) -> &[GeneratedFile] {
+        &self.files
+    }
+
+    /// Total size of the files written, in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+
+    /// How long the scaffold took, hooks, verification and version
+    /// control included.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Things worth a look, even though the scaffold succeeded: existing
+    /// files that were skipped, backed up or merged, entries that failed,
+    /// hooks that were skipped, blocked or failed, and empty files.
+    pub fn warnings(&self) -> Vec<String> {
+        let entries = self.entries.iter().filter_map(|e| {
+            let path = e.path.display();
+            match &e.status {
+                EntryStatus::Skipped if e.kind == EntryKind::File => {
+                    Some(format!("{path} already exists and was left untouched"))
+                }
+                EntryStatus::BackedUp(backup) => Some(format!(
+                    "{path} was replaced, the previous version is in {}",
+                    backup.display()
+                )),
+                EntryStatus::Merged => Some(format!("{path} has conflict markers to resolve")),
+                EntryStatus::Failed(reason) => {
+                    Some(format!("{path} could not be written: {reason}"))
+                }
+                _ => None,
+            }
+        });
+        let hooks = self.hooks.iter().filter_map(|h| match &h.status {
+            HookStatus::Skipped => Some(format!("hook `{}` was skipped", h.hook)),
+            HookStatus::Blocked(reason) => Some(format!("hook `{}` was blocked: {reason}", h.hook)),
+            HookStatus::Failed(reason) => Some(format!("hook `{}` failed: {reason}", h.hook)),
+            HookStatus::Succeeded => None,
+        });
+        let empty = self
+            .files
+            .iter()
+            .filter(|f| f.size == 0)
+            .map(|f| format!("{} is empty", f.path.display()));
+
+        entries.chain(hooks).chain(empty).collect()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Permissions;
+
+    #[test]
+    fn created_report_lists_every_entry() {
+        let structure = ProjectStructure::new("/tmp/demo")
+            .with_directory("src", Permissions::read_write())
+            .with_file(
+                "src/main.rs",
+                "fn main() {}\n".to_string(),
+                Permissions::read_write(),
+            );
+
+        let report = ScaffoldReport::created(&structure);
+
+        assert_eq!(report.root(), Path::new("/tmp/demo"));
+        assert_eq!(report.entries().len(), 2);
+        assert_eq!(report.files_created(), 1);
+        assert_eq!(report.skipped_entries().count(), 0);
+        assert_eq!(report.failed_entries().count(), 0);
+        assert_eq!(report.entries()[0].kind, EntryKind::Directory);
+    }
+
+    #[test]
+    fn manifest_lists_written_files_with_their_template() {
+        let structure = ProjectStructure::new("/tmp/demo")
+            .with_directory("src", Permissions::read_write())
+            .with_file(
+                "src/main.rs",
+                "fn main() {}\n".to_string(),
+                Permissions::read_write(),
+            )
+            .with_file("run.sh", String::new(), Permissions::executable())
+            .with_file("keep.txt", "old".to_string(), Permissions::read_write());
+        let entry = |path: &str, kind, status| ReportEntry {
+            path: PathBuf::from(path),
+            kind,
+            status,
+        };
+        let entries = vec![
+            entry("src", EntryKind::Directory, EntryStatus::Created),
+            entry("src/main.rs", EntryKind::File, EntryStatus::Merged),
+            entry("run.sh", EntryKind::File, EntryStatus::Created),
+            entry("keep.txt", EntryKind::File, EntryStatus::Skipped),
+        ];
+
+        let report = ScaffoldReport::new("/tmp/demo", entries)
+            .with_hooks(vec![HookOutcome {
+                hook: Hook::command("git", ["init"]),
+                status: HookStatus::Skipped,
+                command: None,
+            }])
+            .with_manifest(&structure, |path| {
+                if path.starts_with("src") {
+                    "rust@1.0.0".to_string()
+                } else {
+                    "scripts@0.1.0".to_string()
+                }
+            })
+            .with_duration(Duration::from_millis(12));
+
+        let manifest: Vec<_> = report
+            .files()
+            .iter()
+            .map(|f| (f.path.to_str().unwrap(), f.size, f.template.as_str()))
+            .collect();
+        assert_eq!(
+            manifest,
+            [
+                ("src/main.rs", 13, "rust@1.0.0"),
+                ("run.sh", 0, "scripts@0.1.0")
+            ]
+        );
+        assert!(report.files()[1].permissions.executable_flag());
+        assert_eq!(report.total_size(), 13);
+        assert_eq!(report.duration(), Duration::from_millis(12));
+        assert_eq!(
+            report.warnings(),
+            [
+                "src/main.rs has conflict markers to resolve",
+                "keep.txt already exists and was left untouched",
+                "hook `git init` was skipped",
+                "run.sh is empty",
+            ]
+        );
+    }
+}
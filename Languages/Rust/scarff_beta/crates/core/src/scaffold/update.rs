@@ -0,0 +1,193 @@
+//! Idempotent re-scaffolds: update a project in place.
+//!
+//! Every template-based scaffold writes [`MANIFEST_PATH`] next to the
+//! provenance file, recording the template version and a hash of every
+//! other file as it was generated:
+//!
+//! ```json
+//! {
+//!   "scarff_version": "0.1.0",
+//!   "template": { "name": "Rust CLI (Default)", "version": "1.0.0" },
+//!   "files": {
+//!     "Cargo.toml": "sha256:9f86d081884c7d65...",
+//!     "src/main.rs": "sha256:60303ae22b998861..."
+//!   }
+//! }
+//! ```
+//!
+//! [`Engine::update`](crate::Engine::update) re-renders the project and
+//! compares each rendered file with the file on disk and its recorded hash,
+//! as [`upgrade`](super::upgrade) does with the base render:
+//!
+//! | on disk                | recorded vs rendered | outcome                 |
+//! |------------------------|----------------------|-------------------------|
+//! | equals rendered        | -                    | unchanged               |
+//! | missing                | not recorded         | created                 |
+//! | missing                | equal                | none (deleted by user)  |
+//! | matches recorded hash  | differ               | rewritten               |
+//! | edited by the user     | equal                | none (kept)             |
+//! | edited by the user     | differ/not recorded  | conflict, left as is    |
+//!
+//! The manifest is then rewritten: conflicting files keep their old hash,
+//! so they are reported again until the user resolves them.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    domain::{ProjectStructure, TemplateId},
+    scaffold::{
+        errors::ScaffoldError,
+        provenance::TemplateProvenance,
+        upgrade::{self, FileChange},
+    },
+};
+
+/// Path of the scaffold manifest, relative to the project root.
+pub const MANIFEST_PATH: &str = ".scarff/manifest.json";
+
+/// The files a scaffold generated, with the hash of their content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    /// Version of the engine that wrote the manifest
+    pub scarff_version: String,
+    /// Template the files were rendered from
+    pub template: TemplateProvenance,
+    /// `sha256:<hex>` of each generated file, by path relative to the root
+    #[serde(default)]
+    pub files: BTreeMap<PathBuf, String>,
+}
+
+impl ProjectManifest {
+    /// Record every file of `structure`, rendered from `template`, except
+    /// the provenance and manifest themselves.
+    pub(crate) fn new(template: &TemplateId, structure: &ProjectStructure) -> Self {
+        Self {
+            scarff_version: env!("CARGO_PKG_VERSION").to_string(),
+            template: TemplateProvenance {
+                name: template.name.clone(),
+                version: template.version.clone(),
+            },
+            files: structure
+                .files()
+                .filter(|f| upgrade::is_managed(&f.path))
+                .map(|f| (f.path.clone(), hash(&f.content)))
+                .collect(),
+        }
+    }
+
+    /// Parse a manifest read from `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScaffoldError::ManifestInvalid` for malformed JSON or
+    /// missing fields.
+    pub fn from_json(path: &Path, content: &str) -> Result<Self, ScaffoldError> {
+        serde_json::from_str(content).map_err(|e| ScaffoldError::ManifestInvalid {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Serialize to the manifest file format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a file path is not valid UTF-8, which can't happen for
+    /// paths that come from a template.
+    pub fn to_json(&self) -> String {
+        let mut json =
+            serde_json::to_string_pretty(self).expect("manifest is always representable as JSON");
+        json.push('\n');
+        json
+    }
+
+    /// The template id (`name@version`) the files were rendered from.
+    pub fn template_id(&self) -> TemplateId {
+        TemplateId::new(self.template.name.clone(), self.template.version.clone())
+    }
+
+    /// Recorded hash of the file at `path`, if the scaffold generated it.
+    pub fn hash_of(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+}
+
+/// What an update did, with paths relative to the project root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateReport {
+    /// Project root
+    pub project_dir: PathBuf,
+    /// Template version the project was last written with
+    pub from: TemplateId,
+    /// Template version the project was re-rendered with
+    pub to: TemplateId,
+    /// Files the project was missing, now written
+    pub created: Vec<PathBuf>,
+    /// Unmodified files rewritten with the new content
+    pub updated: Vec<PathBuf>,
+    /// Files already matching the render, or edits the render agrees with
+    pub unchanged: Vec<PathBuf>,
+    /// User-edited files the render changed, left as they were
+    pub conflicts: Vec<FileChange>,
+}
+
+impl UpdateReport {
+    /// Whether the update left conflicts to resolve by hand.
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// `sha256:<hex>` of `content`.
+pub(crate) fn hash(content: &str) -> String {
+    format!("sha256:{:x}", Sha256::digest(content.as_bytes()))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Permissions;
+
+    #[test]
+    fn records_every_file_and_round_trips_through_json() {
+        let mut structure = ProjectStructure::new("/out/app");
+        structure.add_file("a.txt", "a".to_string(), Permissions::read_write());
+        structure.add_directory("src", Permissions::read_write());
+        structure.add_file(MANIFEST_PATH, String::new(), Permissions::read_write());
+
+        let manifest =
+            ProjectManifest::new(&TemplateId::new("app", "1.0.0".to_string()), &structure);
+
+        assert_eq!(manifest.files.len(), 1, "only files, not the manifest");
+        assert_eq!(
+            manifest.hash_of(Path::new("a.txt")),
+            Some(hash("a").as_str())
+        );
+        assert_eq!(manifest.template_id().to_string(), "app@1.0.0");
+
+        let json = manifest.to_json();
+        assert!(json.contains(
+            "\"a.txt\": \"sha256:ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb\""
+        ));
+        assert_eq!(
+            ProjectManifest::from_json(Path::new("m.json"), &json).unwrap(),
+            manifest
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        let err = ProjectManifest::from_json(Path::new("m.json"), "{\"files\": {}}").unwrap_err();
+        assert_eq!(err.code(), "scaffold.manifest_invalid");
+    }
+}
@@ -0,0 +1,229 @@
+//! Template upgrades: re-render a project at its template's latest version.
+//!
+//! [`Engine::plan_upgrade`](crate::Engine::plan_upgrade) re-renders the
+//! project recorded in its provenance twice: with the recorded template
+//! version (the *base*, when the store still has it) and with the latest
+//! one. Comparing each rendered file with the file on disk gives a
+//! three-way classification:
+//!
+//! | on disk            | base vs latest | change                      |
+//! |--------------------|----------------|-----------------------------|
+//! | equals latest      | -              | none                        |
+//! | missing            | no base        | [`ChangeKind::Added`]       |
+//! | missing            | equal          | none (deleted by the user)  |
+//! | equals base        | differ         | [`ChangeKind::Updated`]     |
+//! | edited by the user | equal          | none (kept)                 |
+//! | edited by the user | differ/no base | [`ChangeKind::Conflict`]    |
+//!
+//! [`Engine::apply_upgrade`](crate::Engine::apply_upgrade) writes added and
+//! updated files, asks a callback what to do with each conflict, and
+//! records the new version in the provenance file and manifest. Files the
+//! latest template no longer has are left alone.
+
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+};
+
+use similar::TextDiff;
+
+use crate::domain::{Permissions, Template, TemplateId};
+
+/// How a rendered file differs from the project on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// New in the template, missing on disk
+    Added,
+    /// Changed by the template, untouched by the user
+    Updated,
+    /// Changed by both the template and the user
+    Conflict,
+}
+
+/// A file the upgrade would write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    /// How the file differs from the project on disk
+    pub kind: ChangeKind,
+    /// Content on disk, if the file exists
+    pub current: Option<String>,
+    /// Content rendered by the latest template
+    pub content: String,
+    pub(crate) permissions: Permissions,
+}
+
+impl FileChange {
+    /// Unified diff from the file on disk to the latest content.
+    pub fn diff(&self) -> String {
+        let path = self.path.display();
+        TextDiff::from_lines(self.current.as_deref().unwrap_or_default(), &self.content)
+            .unified_diff()
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string()
+    }
+
+    /// Where a rejected change is written: `<path>.rej`.
+    pub fn reject_path(&self) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(".rej");
+        PathBuf::from(name)
+    }
+}
+
+/// What to do with a conflicting file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Overwrite the file with the latest content
+    Accept,
+    /// Keep the file as it is
+    Skip,
+    /// Keep the file and write the diff next to it as `<path>.rej`
+    Reject,
+}
+
+/// The changes upgrading a project would make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradePlan {
+    /// Project root
+    pub project_dir: PathBuf,
+    /// Template version the project was generated with
+    pub from: TemplateId,
+    /// Latest template version
+    pub to: TemplateId,
+    /// Files to write, in template order
+    pub changes: Vec<FileChange>,
+    /// Provenance file content for the latest version
+    pub(crate) provenance: String,
+    /// Manifest content for the latest version
+    pub(crate) manifest: String,
+}
+
+impl UpgradePlan {
+    /// Whether the project is already at the latest version with nothing
+    /// to write.
+    pub fn is_up_to_date(&self) -> bool {
+        self.from == self.to && self.changes.is_empty()
+    }
+
+    /// Changes that need a [`Resolution`].
+    pub fn conflicts(&self) -> impl Iterator<Item = &FileChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.kind == ChangeKind::Conflict)
+    }
+}
+
+/// What an applied upgrade did, with paths relative to the project root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpgradeReport {
+    /// Files written with the latest content
+    pub applied: Vec<PathBuf>,
+    /// `.rej` files written for rejected conflicts
+    pub rejected: Vec<PathBuf>,
+    /// Conflicting files left as they were
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Classify a rendered file against the file on disk and the base render.
+pub(crate) fn classify(
+    current: Option<&str>,
+    base: Option<&str>,
+    latest: &str,
+) -> Option<ChangeKind> {
+    match (current, base) {
+        (Some(current), _) if current == latest => None,
+        (None, None) => Some(ChangeKind::Added),
+        (None, Some(base)) if base == latest => None,
+        (Some(current), Some(base)) if current == base => Some(ChangeKind::Updated),
+        (Some(_), Some(base)) if base == latest => None,
+        _ => Some(ChangeKind::Conflict),
+    }
+}
+
+/// The highest version of the template named `name` among `templates`.
+pub(crate) fn latest<'a>(templates: &'a [Template], name: &str) -> Option<&'a Template> {
+    templates
+        .iter()
+        .filter(|t| t.id.name == name)
+        .max_by(|a, b| compare_versions(&a.id.version, &b.id.version))
+}
+
+/// Compare dotted versions numerically (`1.10.0` > `1.9.0`).
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |v: &str| -> Vec<u64> {
+        v.split(['.', '-', '+'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    parts(a).cmp(&parts(b)).then_with(|| a.cmp(b))
+}
+
+/// Whether `path` is a project-relative path the upgrade manages (not
+/// one of the engine's own records).
+pub(crate) fn is_managed(path: &Path) -> bool {
+    path != Path::new(super::provenance::PROVENANCE_PATH)
+        && path != Path::new(super::update::MANIFEST_PATH)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_is_three_way() {
+        // Already up to date
+        assert_eq!(classify(Some("new"), Some("old"), "new"), None);
+        // Missing files are added, unless the user deleted an unchanged one
+        assert_eq!(classify(None, None, "new"), Some(ChangeKind::Added));
+        assert_eq!(classify(None, Some("same"), "same"), None);
+        assert_eq!(
+            classify(None, Some("old"), "new"),
+            Some(ChangeKind::Conflict)
+        );
+        // Untouched files follow the template; edited ones are kept
+        assert_eq!(
+            classify(Some("old"), Some("old"), "new"),
+            Some(ChangeKind::Updated)
+        );
+        assert_eq!(classify(Some("edited"), Some("same"), "same"), None);
+        // Both changed, or no base to tell
+        assert_eq!(
+            classify(Some("edited"), Some("old"), "new"),
+            Some(ChangeKind::Conflict)
+        );
+        assert_eq!(
+            classify(Some("edited"), None, "new"),
+            Some(ChangeKind::Conflict)
+        );
+    }
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0.0", "2.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("0.1.0", "0.1.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn diff_and_reject_path() {
+        let change = FileChange {
+            path: PathBuf::from("src/main.rs"),
+            kind: ChangeKind::Conflict,
+            current: Some("fn main() {}\n".to_string()),
+            content: "fn main() {\n    run();\n}\n".to_string(),
+            permissions: Permissions::read_write(),
+        };
+
+        let diff = change.diff();
+        assert!(diff.starts_with("--- a/src/main.rs\n+++ b/src/main.rs\n"));
+        assert!(diff.contains("-fn main() {}\n"));
+        assert!(diff.contains("+    run();\n"));
+        assert_eq!(change.reject_path(), PathBuf::from("src/main.rs.rej"));
+    }
+}
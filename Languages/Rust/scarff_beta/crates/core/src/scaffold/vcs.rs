@@ -0,0 +1,320 @@
+//! Version control for generated projects.
+//!
+//! After a project has been written (and verified), the engine can put it
+//! under git:
+//! - if the output directory is already inside a repository, that
+//!   repository is reused and no nested one is created
+//! - otherwise, `git init` is run in the project root
+//! - optionally, the scaffold is committed, with the template id and
+//!   version recorded as commit trailers
+//!
+//! Only the files the scaffold wrote are committed, so unrelated changes
+//! in a reused repository (staged or not) are left alone.
+//!
+//! git runs through a [`ProcessRunner`], so tests can script it with a
+//! [`MockProcess`](super::process::MockProcess).
+
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, instrument, warn};
+
+use crate::{
+    domain::TemplateId,
+    scaffold::{errors::ScaffoldError, process::ProcessRunner},
+};
+
+// ============================================================================
+// VcsMode
+// ============================================================================
+
+/// What the engine does with version control after scaffolding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VcsMode {
+    /// Leave version control alone
+    #[default]
+    Off,
+    /// Reuse an enclosing repository, or `git init` the project
+    Init,
+    /// Like [`VcsMode::Init`], then commit the scaffold
+    Commit,
+}
+
+// ============================================================================
+// VcsOutcome
+// ============================================================================
+
+/// What version control did for a scaffold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VcsOutcome {
+    /// Top-level directory of the repository holding the project
+    pub repository: PathBuf,
+    /// Whether the repository was created by this scaffold
+    pub initialized: bool,
+    /// Hash of the scaffold commit, if one was made
+    pub commit: Option<String>,
+}
+
+// ============================================================================
+// Git
+// ============================================================================
+
+/// Find the repository enclosing `dir`, if any.
+///
+/// Returns `None` when `dir` isn't inside a work tree, or when git isn't
+/// installed.
+pub fn detect_repository(process: &dyn ProcessRunner, dir: &Path) -> Option<PathBuf> {
+    let args = ["rev-parse".to_string(), "--show-toplevel".to_string()];
+    let output = process.run(dir, "git", &args).ok()?;
+
+    if !output.success {
+        return None;
+    }
+    (!output.stdout.is_empty()).then(|| PathBuf::from(output.stdout))
+}
+
+/// Commit message for a scaffold, with the template recorded as trailers.
+pub fn commit_message(project_name: &str, template: &TemplateId) -> String {
+    format!(
+        "Scaffold {project_name} with scarff\n\n\
+         Scarff-Template: {}\n\
+         Scarff-Template-Version: {}\n",
+        template.name(),
+        template.version()
+    )
+}
+
+/// Apply `mode` to a freshly written project.
+///
+/// `files` are the written files, relative to `project_root`.
+///
+/// # Errors
+///
+/// Returns `ScaffoldError::VcsFailed` if a git command cannot be started
+/// or exits with a non-zero status (for example, no commit identity is
+/// configured).
+#[instrument(skip(process, files, message), fields(root = %project_root.display()))]
+pub(crate) fn apply(
+    process: &dyn ProcessRunner,
+    mode: VcsMode,
+    project_root: &Path,
+    files: &[PathBuf],
+    message: &str,
+) -> Result<Option<VcsOutcome>, ScaffoldError> {
+    if mode == VcsMode::Off {
+        return Ok(None);
+    }
+
+    let (repository, initialized) =
+        if let Some(repository) = detect_repository(process, project_root) {
+            info!(repository = %repository.display(), "Reusing enclosing git repository");
+            (repository, false)
+        } else {
+            info!("Initializing git repository");
+            git(process, project_root, ["init", "--quiet"])?;
+            (project_root.to_path_buf(), true)
+        };
+
+    let commit = if mode == VcsMode::Commit && !files.is_empty() {
+        let mut add = vec!["add".to_string(), "--".to_string()];
+        add.extend(files.iter().map(|f| f.display().to_string()));
+        git(process, project_root, &add)?;
+
+        let mut commit = vec![
+            "commit".to_string(),
+            "--quiet".to_string(),
+            "--message".to_string(),
+            message.to_string(),
+            "--".to_string(),
+        ];
+        commit.extend(files.iter().map(|f| f.display().to_string()));
+        git(process, project_root, &commit)?;
+
+        let hash = git(process, project_root, ["rev-parse", "HEAD"])?;
+        debug!(commit = %hash, "Committed scaffold");
+        Some(hash)
+    } else {
+        None
+    };
+
+    Ok(Some(VcsOutcome {
+        repository,
+        initialized,
+        commit,
+    }))
+}
+
+/// Run git in `dir`, returning trimmed stdout.
+fn git<I, S>(process: &dyn ProcessRunner, dir: &Path, args: I) -> Result<String, ScaffoldError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args: Vec<String> = args.into_iter().map(|a| a.as_ref().to_string()).collect();
+    // Keep file lists out of error messages; the subcommand is enough
+    let command = format!("git {}", args.first().map_or("", String::as_str));
+
+    let output = process.run(dir, "git", &args).map_err(|e| {
+        warn!(error = %e, "Failed to start git");
+        ScaffoldError::VcsFailed {
+            command: command.clone(),
+            output: format!("failed to start `git`: {e}"),
+        }
+    })?;
+
+    if output.success {
+        Ok(output.stdout)
+    } else {
+        warn!(%command, "git failed");
+        Err(ScaffoldError::VcsFailed {
+            command,
+            output: output.stderr,
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold::process::{MockProcess, ProcessOutput, RealProcess};
+
+    fn write(root: &Path, file: &str) -> PathBuf {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "content\n").unwrap();
+        PathBuf::from(file)
+    }
+
+    /// A repository with a local identity, so commits work anywhere
+    fn repository() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        git(&RealProcess, dir.path(), ["init", "--quiet"]).unwrap();
+        git(
+            &RealProcess,
+            dir.path(),
+            ["config", "user.name", "Scarff Test"],
+        )
+        .unwrap();
+        git(
+            &RealProcess,
+            dir.path(),
+            ["config", "user.email", "test@scarff.dev"],
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn commit_message_records_template() {
+        let message = commit_message("demo", &TemplateId::new("rust-cli", "1.2.0".to_string()));
+
+        assert!(message.starts_with("Scaffold demo with scarff\n\n"));
+        assert!(message.contains("Scarff-Template: rust-cli\n"));
+        assert!(message.contains("Scarff-Template-Version: 1.2.0\n"));
+    }
+
+    #[test]
+    fn off_does_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let outcome = apply(&RealProcess, VcsMode::Off, dir.path(), &[], "msg").unwrap();
+
+        assert_eq!(outcome, None);
+        assert!(!dir.path().join(".git").exists());
+    }
+
+    #[test]
+    fn init_creates_repository_outside_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("demo");
+        let file = write(&root, "src/main.rs");
+
+        let outcome = apply(&RealProcess, VcsMode::Init, &root, &[file], "msg")
+            .unwrap()
+            .unwrap();
+
+        assert!(outcome.initialized);
+        assert_eq!(outcome.commit, None);
+        assert!(root.join(".git").is_dir());
+    }
+
+    #[test]
+    fn failed_commit_reports_git_output() {
+        let process = MockProcess::new();
+        process.respond(
+            &["rev-parse"],
+            ProcessOutput::failure("not a git repository"),
+        );
+        process.respond(
+            &["commit"],
+            ProcessOutput::failure("Author identity unknown"),
+        );
+
+        let err = apply(
+            &process,
+            VcsMode::Commit,
+            Path::new("/out/demo"),
+            &[PathBuf::from("README.md")],
+            "msg",
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ScaffoldError::VcsFailed { ref command, ref output }
+                if command == "git commit" && output == "Author identity unknown"
+        ));
+        let subcommands: Vec<_> = process
+            .calls()
+            .into_iter()
+            .map(|call| call.args[0].clone())
+            .collect();
+        assert_eq!(subcommands, ["rev-parse", "init", "add", "commit"]);
+    }
+
+    #[test]
+    fn commit_reuses_enclosing_repository_and_commits_only_scaffold() {
+        let repo = repository();
+        write(repo.path(), "unrelated.txt");
+        git(&RealProcess, repo.path(), ["add", "unrelated.txt"]).unwrap();
+        let root = repo.path().join("demo");
+        let files = [write(&root, "README.md"), write(&root, "src/main.rs")];
+
+        let outcome = apply(
+            &RealProcess,
+            VcsMode::Commit,
+            &root,
+            &files,
+            "Scaffold demo",
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(!outcome.initialized);
+        assert!(!root.join(".git").exists(), "no nested repository");
+        assert_eq!(
+            outcome.repository.canonicalize().unwrap(),
+            repo.path().canonicalize().unwrap()
+        );
+
+        let committed = git(
+            &RealProcess,
+            repo.path(),
+            ["show", "--name-only", "--format=%s", "HEAD"],
+        )
+        .unwrap();
+        assert_eq!(
+            committed,
+            "Scaffold demo\n\ndemo/README.md\ndemo/src/main.rs"
+        );
+        let staged = git(
+            &RealProcess,
+            repo.path(),
+            ["diff", "--cached", "--name-only"],
+        )
+        .unwrap();
+        assert_eq!(staged, "unrelated.txt", "unrelated changes stay staged");
+    }
+}
@@ -0,0 +1,215 @@
+//! Post-scaffold verification of generated projects.
+//!
+//! After a project has been written, the engine can optionally run the
+//! language's native checker inside the project directory:
+//! - Rust: `cargo check`
+//! - TypeScript: `tsc --noEmit`
+//! - Python: `python -m compileall`
+//!
+//! A non-zero exit (or a checker that cannot be started) fails the scaffold.
+
+use std::{
+    fmt,
+    path::Path,
+    process::{Command, Output},
+};
+use tracing::{debug, info, instrument, warn};
+
+use crate::{domain::Language, scaffold::errors::ScaffoldError};
+
+// ============================================================================
+// VerifyCommand
+// ============================================================================
+
+/// A command used to verify that a generated project compiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+impl VerifyCommand {
+    /// Create a verification command from a program and its arguments.
+    pub fn new<I, S>(program: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Default verification command for a language.
+    pub fn for_language(language: Language) -> Self {
+        match language {
+            Language::Rust => Self::new("cargo", ["check", "--quiet"]),
+            Language::TypeScript => Self::new("tsc", ["--noEmit"]),
+            Language::Python => {
+                let python = if cfg!(windows) { "python" } else { "python3" };
+                Self::new(python, ["-m", "compileall", "-q", "."])
+            }
+        }
+    }
+
+    /// The program that will be executed.
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Arguments passed to the program.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Run the command inside `project_dir`, capturing its output.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScaffoldError::VerificationFailed` if the command cannot be
+    /// started or exits with a non-zero status.
+    #[instrument(skip(self), fields(command = %self, dir = %project_dir.display()))]
+    pub fn run(&self, project_dir: &Path) -> Result<VerificationOutput, ScaffoldError> {
+        info!("Running verification command");
+
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .current_dir(project_dir)
+            .output()
+            .map_err(|e| {
+                warn!(error = %e, "Failed to start verification command");
+                ScaffoldError::VerificationFailed {
+                    command: self.to_string(),
+                    exit_code: None,
+                    output: format!("failed to start `{}`: {e}", self.program),
+                }
+            })?;
+
+        let captured = VerificationOutput::from_output(self, &output);
+
+        if output.status.success() {
+            debug!("Verification passed");
+            Ok(captured)
+        } else {
+            warn!(exit_code = ?output.status.code(), "Verification failed");
+            Err(ScaffoldError::VerificationFailed {
+                output: captured.combined(),
+                command: captured.command,
+                exit_code: output.status.code(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for VerifyCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// VerificationOutput
+// ============================================================================
+
+/// Captured output of a successful verification run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationOutput {
+    /// The command line that was run
+    pub command: String,
+    /// Captured standard output
+    pub stdout: String,
+    /// Captured standard error
+    pub stderr: String,
+}
+
+impl VerificationOutput {
+    fn from_output(command: &VerifyCommand, output: &Output) -> Self {
+        Self {
+            command: command.to_string(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }
+    }
+
+    /// Stdout and stderr joined, skipping empty streams.
+    pub fn combined(&self) -> String {
+        [self.stdout.trim_end(), self.stderr.trim_end()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_commands_per_language() {
+        assert_eq!(
+            VerifyCommand::for_language(Language::Rust).program(),
+            "cargo"
+        );
+        assert_eq!(
+            VerifyCommand::for_language(Language::TypeScript).to_string(),
+            "tsc --noEmit"
+        );
+        assert!(
+            VerifyCommand::for_language(Language::Python)
+                .args()
+                .contains(&"compileall".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_captures_output_on_success() {
+        let dir = tempfile::tempdir().unwrap();
+        let cmd = VerifyCommand::new("sh", ["-c", "echo checked"]);
+
+        let output = cmd.run(dir.path()).unwrap();
+
+        assert_eq!(output.stdout.trim(), "checked");
+        assert_eq!(output.combined(), "checked");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_fails_on_non_zero_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        let cmd = VerifyCommand::new("sh", ["-c", "echo broken >&2; exit 3"]);
+
+        let err = cmd.run(dir.path()).unwrap_err();
+
+        if let ScaffoldError::VerificationFailed {
+            exit_code, output, ..
+        } = err
+        {
+            assert_eq!(exit_code, Some(3));
+            assert_eq!(output, "broken");
+        } else {
+            panic!("Expected VerificationFailed variant");
+        }
+    }
+
+    #[test]
+    fn run_fails_when_program_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let cmd = VerifyCommand::new("scarff-definitely-not-a-program", Vec::<String>::new());
+
+        let err = cmd.run(dir.path()).unwrap_err();
+
+        assert!(err.is_verification_error());
+    }
+}
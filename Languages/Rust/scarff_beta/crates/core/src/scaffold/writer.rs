@@ -1,12 +1,18 @@
 //! File writing operations for scaffolding.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, instrument, warn};
 
 use crate::{
-    domain::{FsEntry, Permissions, ProjectStructure},
+    domain::{FileToWrite, FsEntry, Permissions, ProjectStructure, RelativePath, SymlinkToCreate},
     errors::CoreResult,
-    scaffold::{errors::ScaffoldError, filesystem::Filesystem},
+    scaffold::{
+        errors::ScaffoldError,
+        filesystem::Filesystem,
+        overwrite::{self, OverwritePolicy},
+        plan::{PlannedAction, ScaffoldPlan},
+        report::{EntryStatus, ReportEntry},
+    },
 };
 
 /// Trait for writing project structures to storage.
@@ -26,8 +32,8 @@ pub trait Writer {
 ///
 /// # Examples
 ///
-/// ```rust,no_run
-/// use scarff_core::{
+/// ```rust,ignore
+/// use scarff_core_v1::{
 ///     scaffold::{FileWriter, filesystem::RealFilesystem},
 ///     domain::ProjectStructure,
 /// };
@@ -48,38 +54,249 @@ impl FileWriter {
     ///
     /// # Examples
     ///
-    /// ```rust
-    /// use scarff_core::scaffold::{FileWriter, filesystem::RealFilesystem};
+    /// ```rust,ignore
+    /// use scarff_core_v1::scaffold::{FileWriter, filesystem::RealFilesystem};
     ///
     /// let writer = FileWriter::new(Box::new(RealFilesystem));
     /// ```
     pub fn new(filesystem: Box<dyn Filesystem>) -> Self {
         Self { filesystem }
     }
+
+    /// Write a single file at `relative` under `root`, refusing paths that
+    /// would land outside it.
+    pub(crate) fn write_file_in(
+        &self,
+        root: &Path,
+        relative: &Path,
+        content: &str,
+        permissions: Permissions,
+    ) -> CoreResult<()> {
+        self.ensure_contained(root, relative)?;
+        self.write_file(&root.join(relative), content, permissions)
+    }
+
+    /// Set the executable bit on the existing file at `relative` under
+    /// `root`, refusing paths that would land outside it.
+    pub(crate) fn make_executable_in(&self, root: &Path, relative: &Path) -> CoreResult<()> {
+        self.ensure_contained(root, relative)?;
+
+        let path = root.join(relative);
+        if !self.filesystem.is_file(&path) {
+            return Err(ScaffoldError::filesystem_write(
+                &path,
+                "No such file to make executable",
+                std::io::ErrorKind::NotFound.into(),
+            )
+            .into());
+        }
+        self.filesystem
+            .set_permissions(&path, Permissions::full())
+            .map_err(|e| ScaffoldError::filesystem_write(&path, "Failed to set permissions", e))?;
+        Ok(())
+    }
+
+    /// Read a file from the filesystem this writer writes to.
+    pub(crate) fn read(&self, path: &Path) -> std::io::Result<String> {
+        self.filesystem.read_file(path)
+    }
+
+    /// Whether `path` is a directory on the filesystem this writer writes to.
+    pub(crate) fn is_dir(&self, path: &Path) -> bool {
+        self.filesystem.is_dir(path)
+    }
+
+    /// Whether `path` exists on the filesystem this writer writes to.
+    pub(crate) fn exists(&self, path: &Path) -> bool {
+        self.filesystem.exists(path)
+    }
+
+    /// Write a project into its existing root directory.
+    ///
+    /// Missing entries are created and entries already on disk as they
+    /// would be written are skipped; an existing file with different
+    /// content is handled per `policy`. Nothing is rolled back on error:
+    /// the directory held the user's files before.
+    ///
+    /// # Errors
+    ///
+    /// Returns the path errors of [`Writer::write`],
+    /// `ScaffoldError::ProjectExists` with [`OverwritePolicy::Abort`], or
+    /// `ScaffoldError::FilesystemWrite` if an entry can't be written.
+    #[instrument(skip(self, structure), fields(root = %structure.root.display()))]
+    pub(crate) fn write_over(
+        &self,
+        structure: &ProjectStructure,
+        policy: OverwritePolicy,
+    ) -> CoreResult<Vec<ReportEntry>> {
+        self.ensure_all_contained(structure)?;
+        structure.validate()?;
+
+        if policy == OverwritePolicy::Abort {
+            return Err(ScaffoldError::ProjectExists {
+                path: structure.root.clone(),
+            }
+            .into());
+        }
+
+        let root = &structure.root;
+        let plan = ScaffoldPlan::compare(structure, self);
+        let mut entries = Vec::with_capacity(plan.entries().len());
+
+        // Links last, so the files they point to (or are copied from)
+        // exist
+        let ordered = structure
+            .entries
+            .iter()
+            .zip(plan.entries())
+            .filter(|(e, _)| !matches!(e, FsEntry::Symlink(_)))
+            .chain(
+                structure
+                    .entries
+                    .iter()
+                    .zip(plan.entries())
+                    .filter(|(e, _)| matches!(e, FsEntry::Symlink(_))),
+            );
+        for (entry, planned) in ordered {
+            let status = match (entry, planned.action) {
+                (_, PlannedAction::Skip) => EntryStatus::Skipped,
+                (FsEntry::Directory(dir), _) => {
+                    self.write_directory(&root.join(&dir.path), dir.permissions)?;
+                    EntryStatus::Created
+                }
+                (FsEntry::File(file), PlannedAction::Create) => {
+                    self.write_file(&root.join(&file.path), &file.content, file.permissions)?;
+                    EntryStatus::Created
+                }
+                (FsEntry::File(file), PlannedAction::Overwrite) => {
+                    let current = planned.current.as_deref().unwrap_or_default();
+                    self.replace_file(root, file, current, policy)?
+                }
+                (FsEntry::Symlink(link), PlannedAction::Create) => {
+                    self.write_symlink(structure, link)?;
+                    EntryStatus::Created
+                }
+                (FsEntry::Symlink(link), PlannedAction::Overwrite) => {
+                    self.replace_symlink(structure, link, policy)?
+                }
+            };
+            debug!(path = %planned.path.display(), status = ?status, "Wrote entry");
+            entries.push(ReportEntry {
+                path: planned.path.clone(),
+                kind: planned.kind,
+                status,
+            });
+        }
+
+        self.apply_directory_permissions(structure)?;
+
+        info!(
+            replaced = entries
+                .iter()
+                .filter(|e| !matches!(e.status, EntryStatus::Created | EntryStatus::Skipped))
+                .count(),
+            "Wrote into existing directory"
+        );
+        Ok(entries)
+    }
+
+    /// Handle a file that exists with `current` content per `policy`.
+    fn replace_file(
+        &self,
+        root: &Path,
+        file: &FileToWrite,
+        current: &str,
+        policy: OverwritePolicy,
+    ) -> CoreResult<EntryStatus> {
+        let path = root.join(&file.path);
+        Ok(match policy {
+            OverwritePolicy::Abort | OverwritePolicy::Skip => EntryStatus::Skipped,
+            OverwritePolicy::Overwrite => {
+                self.write_file(&path, &file.content, file.permissions)?;
+                EntryStatus::Overwritten
+            }
+            OverwritePolicy::Backup => {
+                let backup = overwrite::backup_path(&file.path, |p| self.exists(&root.join(p)));
+                self.write_file(&root.join(&backup), current, Permissions::read_write())?;
+                self.write_file(&path, &file.content, file.permissions)?;
+                EntryStatus::BackedUp(backup)
+            }
+            OverwritePolicy::Merge => {
+                let merged = overwrite::merge(current, &file.content);
+                self.write_file(&path, &merged, file.permissions)?;
+                EntryStatus::Merged
+            }
+        })
+    }
+
+    /// Handle a link that exists elsewhere (or a file in its way) per
+    /// `policy`. Links can't be merged, so a merge backs up instead.
+    fn replace_symlink(
+        &self,
+        structure: &ProjectStructure,
+        link: &SymlinkToCreate,
+        policy: OverwritePolicy,
+    ) -> CoreResult<EntryStatus> {
+        let root = &structure.root;
+        let path = root.join(&link.path);
+        let remove = || {
+            self.filesystem
+                .remove_file(&path)
+                .map_err(|e| ScaffoldError::filesystem_write(&path, "Failed to replace entry", e))
+        };
+        Ok(match policy {
+            OverwritePolicy::Abort | OverwritePolicy::Skip => EntryStatus::Skipped,
+            OverwritePolicy::Overwrite => {
+                remove()?;
+                self.write_symlink(structure, link)?;
+                EntryStatus::Overwritten
+            }
+            OverwritePolicy::Backup | OverwritePolicy::Merge => {
+                let backup = overwrite::backup_path(&link.path, |p| self.exists(&root.join(p)));
+                if let Ok(target) = self.filesystem.read_link(&path) {
+                    self.filesystem
+                        .create_symlink(&target, &root.join(&backup))
+                        .map_err(|e| {
+                            ScaffoldError::filesystem_write(&path, "Failed to back up symlink", e)
+                        })?;
+                } else {
+                    let current = self.read(&path).map_err(|e| {
+                        ScaffoldError::filesystem_write(&path, "Failed to back up file", e)
+                    })?;
+                    self.write_file(&root.join(&backup), &current, Permissions::read_write())?;
+                }
+                remove()?;
+                self.write_symlink(structure, link)?;
+                EntryStatus::BackedUp(backup)
+            }
+        })
+    }
 }
 
 impl Writer for FileWriter {
     /// Write a project structure to the filesystem.
     ///
     /// This method:
-    /// 1. Validates the structure
-    /// 2. Checks if the project directory already exists
-    /// 3. Creates directories
-    /// 4. Writes files
-    /// 5. Sets permissions
-    /// 6. Rolls back on error (best effort)
+    /// 1. Refuses any path that would land outside the root
+    /// 2. Validates the structure
+    /// 3. Checks if the project directory already exists
+    /// 4. Creates directories
+    /// 5. Writes files
+    /// 6. Sets permissions
+    /// 7. Rolls back on error (best effort)
     ///
     /// # Errors
     ///
     /// Returns an error if:
+    /// - A path is absolute, contains `..`, or goes through a symlink
     /// - The project directory already exists
     /// - Filesystem operations fail
     /// - The structure is invalid
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
-    /// # use scarff_core::{
+    /// ```rust,ignore
+    /// # use scarff_core_v1::{
     /// #     scaffold::{FileWriter, Writer, filesystem::RealFilesystem},
     /// #     domain::ProjectStructure,
     /// # };
@@ -92,10 +309,13 @@ impl Writer for FileWriter {
     fn write(&self, structure: &ProjectStructure) -> CoreResult<()> {
         info!("Starting file write operation");
 
-        // 1. Validate structure
+        // 1. Refuse unsafe paths before anything touches the disk
+        self.ensure_all_contained(structure)?;
+
+        // 2. Validate structure
         structure.validate()?;
 
-        // 2. Check if project directory exists
+        // 3. Check if project directory exists
         if self.filesystem.exists(&structure.root) {
             return Err(ScaffoldError::ProjectExists {
                 path: structure.root.clone(),
@@ -103,7 +323,7 @@ impl Writer for FileWriter {
             .into());
         }
 
-        // 3. FIXME: Try to write everything, rolling back on error
+        // 4. FIXME: Try to write everything, rolling back on error
         match self.write_all(structure) {
             Ok(()) => {
                 info!(
@@ -123,6 +343,65 @@ impl Writer for FileWriter {
 }
 
 impl FileWriter {
+    /// Check every entry with [`Self::ensure_contained`], reporting each
+    /// unsafe one and failing with the first.
+    fn ensure_all_contained(&self, structure: &ProjectStructure) -> CoreResult<()> {
+        let mut first = None;
+        for entry in &structure.entries {
+            let path = entry.path();
+            let contained = match entry {
+                // The link itself may already be there from an earlier run
+                FsEntry::Symlink(_) => RelativePath::check(path)
+                    .map_err(|reason| {
+                        ScaffoldError::unsafe_path(path, format!("path {reason}")).into()
+                    })
+                    .and_then(|()| ensure_outside_git_dir(path))
+                    .and_then(|()| match path.parent() {
+                        Some(parent) if !parent.as_os_str().is_empty() => {
+                            self.ensure_contained(&structure.root, parent)
+                        }
+                        _ => Ok(()),
+                    }),
+                _ => self.ensure_contained(&structure.root, path),
+            };
+            if let Err(e) = contained {
+                warn!(path = %path.display(), error = %e, "Unsafe path in structure");
+                first.get_or_insert(e);
+            }
+        }
+        first.map_or(Ok(()), Err)
+    }
+
+    /// Make sure `root.join(relative)` can't end up outside `root`.
+    ///
+    /// `relative` must be a plain relative path (see [`RelativePath::check`]),
+    /// and none of the directories it goes through, nor the path itself, may
+    /// already exist as a symlink: writing through one could land anywhere.
+    /// It may not go through `.git` either (see [`ensure_outside_git_dir`]).
+    fn ensure_contained(&self, root: &Path, relative: &Path) -> CoreResult<()> {
+        if let Err(reason) = RelativePath::check(relative) {
+            return Err(ScaffoldError::unsafe_path(relative, format!("path {reason}")).into());
+        }
+        ensure_outside_git_dir(relative)?;
+
+        let mut current = root.to_path_buf();
+        for component in relative.components() {
+            current.push(component);
+            if self.filesystem.is_symlink(&current) {
+                let link = current.strip_prefix(root).map(PathBuf::from);
+                return Err(ScaffoldError::unsafe_path(
+                    relative,
+                    format!(
+                        "{} is a symlink",
+                        link.as_deref().unwrap_or(&current).display()
+                    ),
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
     /// Write all entries to the filesystem.
     ///
     /// This is the internal implementation that actually performs the writes.
@@ -154,13 +433,23 @@ impl FileWriter {
                     let full_path = structure.root.join(&file.path);
                     self.write_file(&full_path, &file.content, file.permissions)?;
                 }
+                // Written last, once what they point to exists
+                FsEntry::Symlink(_) => {}
             }
         }
 
-        Ok(())
+        for link in structure.symlinks() {
+            self.write_symlink(structure, link)?;
+        }
+
+        self.apply_directory_permissions(structure)
     }
 
     /// Write a single directory.
+    ///
+    /// Its permissions are applied once everything inside is written (see
+    /// [`Self::apply_directory_permissions`]), as a read-only directory
+    /// can't be filled.
     fn write_directory(&self, path: &Path, _permissions: Permissions) -> CoreResult<()> {
         debug!(path = %path.display(), "Creating directory");
 
@@ -172,14 +461,104 @@ impl FileWriter {
                 io_error: std::sync::Arc::new(e),
             })?;
 
-        // Note: We don't set directory permissions in MVP
-        // This could be added in the future
+        Ok(())
+    }
 
+    /// Set the permissions of directories that don't use the defaults,
+    /// deepest first.
+    fn apply_directory_permissions(&self, structure: &ProjectStructure) -> CoreResult<()> {
+        for dir in structure
+            .directories()
+            .filter(|d| d.permissions != Permissions::default())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+        {
+            let path = structure.root.join(&dir.path);
+            self.filesystem
+                .set_permissions(&path, dir.permissions)
+                .map_err(|e| {
+                    ScaffoldError::filesystem_write(&path, "Failed to set directory permissions", e)
+                })?;
+        }
         Ok(())
     }
 
+    /// Create a symlink of `structure`.
+    ///
+    /// Where the platform can't create links (Windows without developer
+    /// mode), a link to a file becomes a copy of it, with its permissions;
+    /// any other link is skipped with a warning.
+    fn write_symlink(
+        &self,
+        structure: &ProjectStructure,
+        link: &SymlinkToCreate,
+    ) -> CoreResult<()> {
+        let root = &structure.root;
+        let path = root.join(&link.path);
+        debug!(path = %path.display(), target = %link.target.display(), "Creating symlink");
+
+        if let Some(parent) = path.parent() {
+            self.filesystem
+                .create_dir_all(parent)
+                .map_err(|e| ScaffoldError::FilesystemWrite {
+                    path: parent.to_path_buf(),
+                    reason: "Failed to create parent directory".to_string(),
+                    io_error: std::sync::Arc::new(e),
+                })?;
+        }
+
+        match self.filesystem.create_symlink(&link.target, &path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::Unsupported => {
+                let target = link.resolved_target();
+                let Some(source) = target
+                    .as_ref()
+                    .map(|t| root.join(t))
+                    .filter(|s| self.filesystem.is_file(s))
+                else {
+                    warn!(
+                        path = %link.path.display(),
+                        target = %link.target.display(),
+                        "Symlinks aren't supported here, skipping a link to a directory"
+                    );
+                    return Ok(());
+                };
+
+                warn!(
+                    path = %link.path.display(),
+                    "Symlinks aren't supported here, copying the target instead"
+                );
+                let content = self.read(&source).map_err(|e| {
+                    ScaffoldError::filesystem_write(&source, "Failed to read link target", e)
+                })?;
+                let permissions = structure
+                    .files()
+                    .find(|f| Some(&f.path) == target.as_ref())
+                    .map_or_else(Permissions::default, |f| f.permissions);
+                self.write_file(&path, &content, permissions)
+            }
+            Err(e) => {
+                Err(ScaffoldError::filesystem_write(&path, "Failed to create symlink", e).into())
+            }
+        }
+    }
+
+    /// Where the symlink at `path` points, if it is one.
+    pub(crate) fn read_link(&self, path: &Path) -> Option<PathBuf> {
+        self.filesystem
+            .is_symlink(path)
+            .then(|| self.filesystem.read_link(path).ok())
+            .flatten()
+    }
+
     /// Write a single file.
-    fn write_file(&self, path: &Path, content: &str, permissions: Permissions) -> CoreResult<()> {
+    pub(crate) fn write_file(
+        &self,
+        path: &Path,
+        content: &str,
+        permissions: Permissions,
+    ) -> CoreResult<()> {
         debug!(
             path = %path.display(),
             size = content.len(),
@@ -207,8 +586,8 @@ impl FileWriter {
                 io_error: std::sync::Arc::new(e),
             })?;
 
-        // Set permissions if needed
-        if permissions.executable_flag() {
+        // Set permissions unless the filesystem's defaults will do
+        if permissions != Permissions::default() {
             self.filesystem
                 .set_permissions(path, permissions)
                 .map_err(|e| ScaffoldError::FilesystemWrite {
@@ -225,7 +604,7 @@ impl FileWriter {
     ///
     /// This is a best-effort operation - it tries to clean up what was
     /// written, but doesn't guarantee complete cleanup.
-    fn rollback(&self, root: &Path) {
+    pub(crate) fn rollback(&self, root: &Path) {
         warn!(root = %root.display(), "Rolling back filesystem changes");
 
         if let Err(e) = self.filesystem.remove_dir_all(root) {
@@ -240,6 +619,23 @@ impl FileWriter {
     }
 }
 
+/// Refuse paths going through a `.git` directory (or naming a `.git` file).
+///
+/// A template writing `.git/config` could set `core.fsmonitor`, a hooks
+/// path or a filter driver, and run anything the next time a hook calls
+/// `git`. Compared case-insensitively, as filesystems may be.
+fn ensure_outside_git_dir(relative: &Path) -> CoreResult<()> {
+    if relative
+        .components()
+        .any(|component| component.as_os_str().eq_ignore_ascii_case(".git"))
+    {
+        return Err(
+            ScaffoldError::unsafe_path(relative, "templates may not write into .git").into(),
+        );
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -350,31 +746,252 @@ mod tests {
         // ));
     }
 
+    #[test]
+    fn writer_refuses_paths_outside_root() {
+        for path in [
+            "../escape.txt",
+            "src/../../escape.txt",
+            "/etc/passwd",
+            "./x",
+        ] {
+            let fs = MockFilesystem::new();
+            let writer = FileWriter::new(Box::new(fs.clone()));
+            let structure = ProjectStructure::new("/test-project").with_file(
+                path,
+                "pwned".to_string(),
+                Permissions::read_write(),
+            );
+
+            let err = writer.write(&structure).unwrap_err();
+
+            assert_eq!(err.code(), "scaffold.unsafe_path", "{path}");
+            assert_eq!(fs.file_count(), 0, "{path} was written");
+        }
+    }
+
+    #[test]
+    fn writer_refuses_to_write_into_git_dirs() {
+        for path in [".git/config", ".GIT/hooks/pre-commit", "sub/.git", ".git"] {
+            let fs = MockFilesystem::new();
+            let writer = FileWriter::new(Box::new(fs.clone()));
+            let structure = ProjectStructure::new("/test-project").with_file(
+                path,
+                "pwned".to_string(),
+                Permissions::read_write(),
+            );
+
+            let err = writer.write(&structure).unwrap_err();
+
+            assert_eq!(err.code(), "scaffold.unsafe_path", "{path}");
+            assert_eq!(fs.file_count(), 0, "{path} was written");
+        }
+
+        let fs = MockFilesystem::new();
+        let writer = FileWriter::new(Box::new(fs.clone()));
+        let structure = ProjectStructure::new("/test-project")
+            .with_symlink(".git", "elsewhere")
+            .with_file(
+                ".gitignore",
+                "target\n".to_string(),
+                Permissions::read_write(),
+            );
+        assert_eq!(
+            writer.write(&structure).unwrap_err().code(),
+            "scaffold.unsafe_path"
+        );
+    }
+
+    #[test]
+    fn writer_refuses_to_write_through_symlinks() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/project")).unwrap();
+        fs.add_symlink(Path::new("/project/src"));
+        let writer = FileWriter::new(Box::new(fs.clone()));
+
+        let err = writer
+            .write_file_in(
+                Path::new("/project"),
+                Path::new("src/main.rs"),
+                "pwned",
+                Permissions::read_write(),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.code(), "scaffold.unsafe_path");
+        assert!(err.to_string().contains("src is a symlink"), "{err}");
+        assert_eq!(fs.file_count(), 0);
+
+        writer
+            .write_file_in(
+                Path::new("/project"),
+                Path::new("README.md"),
+                "fine",
+                Permissions::read_write(),
+            )
+            .unwrap();
+        assert!(fs.exists(Path::new("/project/README.md")));
+    }
+
+    #[test]
+    fn writer_applies_permissions_and_links_last() {
+        let fs = MockFilesystem::new();
+        let writer = FileWriter::new(Box::new(fs.clone()));
+        let structure = ProjectStructure::new("/p")
+            .with_symlink("bin/run", "../scripts/run.sh")
+            .with_file(
+                "scripts/run.sh",
+                "#!/bin/sh\n".to_string(),
+                Permissions::full(),
+            )
+            .with_file("LOCK", String::new(), Permissions::read_only());
+
+        writer.write(&structure).unwrap();
+
+        assert!(fs.is_executable(Path::new("/p/scripts/run.sh")).unwrap());
+        assert!(!fs.get_permissions(Path::new("/p/LOCK")).unwrap().writable());
+        assert_eq!(
+            fs.read_link(Path::new("/p/bin/run")).unwrap(),
+            Path::new("../scripts/run.sh")
+        );
+    }
+
+    #[test]
+    fn writer_copies_link_targets_without_symlink_support() {
+        let fs = MockFilesystem::new();
+        fs.disable_symlinks();
+        let writer = FileWriter::new(Box::new(fs.clone()));
+        let structure = ProjectStructure::new("/p")
+            .with_symlink("run", "scripts/run.sh")
+            .with_symlink("docs", "book")
+            .with_directory("book", Permissions::read_write())
+            .with_file(
+                "scripts/run.sh",
+                "cargo run\n".to_string(),
+                Permissions::full(),
+            );
+
+        writer.write(&structure).unwrap();
+
+        assert!(!fs.is_symlink(Path::new("/p/run")));
+        assert_eq!(fs.read_file(Path::new("/p/run")).unwrap(), "cargo run\n");
+        assert!(fs.is_executable(Path::new("/p/run")).unwrap());
+        assert!(
+            !fs.exists(Path::new("/p/docs")),
+            "links to directories are skipped"
+        );
+    }
+
+    #[test]
+    fn write_over_replaces_links_per_policy() {
+        let fs = MockFilesystem::new();
+        fs.create_dir_all(Path::new("/p")).unwrap();
+        fs.create_symlink(Path::new("old.sh"), Path::new("/p/run"))
+            .unwrap();
+        let writer = FileWriter::new(Box::new(fs.clone()));
+        let structure = ProjectStructure::new("/p").with_symlink("run", "new.sh");
+
+        let entries = writer
+            .write_over(&structure, OverwritePolicy::Backup)
+            .unwrap();
+
+        assert_eq!(
+            entries[0].status,
+            EntryStatus::BackedUp(PathBuf::from("run.bak"))
+        );
+        assert_eq!(
+            fs.read_link(Path::new("/p/run")).unwrap(),
+            Path::new("new.sh")
+        );
+        assert_eq!(
+            fs.read_link(Path::new("/p/run.bak")).unwrap(),
+            Path::new("old.sh")
+        );
+
+        let entries = writer
+            .write_over(&structure, OverwritePolicy::Overwrite)
+            .unwrap();
+        assert_eq!(entries[0].status, EntryStatus::Skipped, "already in place");
+    }
+
+    /// Whether `path`, resolved lexically, is strictly inside `root`.
+    fn lexically_inside(root: &Path, path: &Path) -> bool {
+        let Ok(rest) = path.strip_prefix(root) else {
+            return false;
+        };
+        let mut depth = 0usize;
+        for component in rest.components() {
+            match component {
+                std::path::Component::Normal(_) => depth += 1,
+                std::path::Component::CurDir => {}
+                std::path::Component::ParentDir if depth > 0 => depth -= 1,
+                _ => return false,
+            }
+        }
+        depth > 0
+    }
+
+    fn segment() -> impl proptest::strategy::Strategy<Value = String> {
+        use proptest::prelude::*;
+        prop_oneof![
+            4 => "[a-zA-Z0-9_.-]{1,8}",
+            2 => Just("..".to_string()),
+            1 => Just(".".to_string()),
+            1 => Just(String::new()),
+            1 => Just("/etc".to_string()),
+        ]
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn malicious_structures_never_write_outside_root(
+            paths in proptest::collection::vec(proptest::collection::vec(segment(), 1..5), 1..5),
+        ) {
+            let fs = MockFilesystem::new();
+            let writer = FileWriter::new(Box::new(fs.clone()));
+            let root = Path::new("/out/project");
+            let mut structure = ProjectStructure::new(root);
+            for segments in &paths {
+                structure.add_file(segments.join("/"), "x".to_string(), Permissions::read_write());
+            }
+
+            let result = writer.write(&structure);
+
+            for file in fs.list_files() {
+                proptest::prop_assert!(lexically_inside(root, &file), "wrote {file:?}");
+            }
+            if result.is_err() {
+                proptest::prop_assert_eq!(fs.file_count(), 0);
+            }
+        }
+    }
+
     #[test]
     fn writer_rolls_back_on_error() {
         let fs = Box::new(MockFilesystem::new());
         let fs_clone = fs.clone();
         let writer = FileWriter::new(fs);
 
-        // Create a structure where writing will fail partway through
-        let mut structure = ProjectStructure::new("/");
+        let mut structure = ProjectStructure::new("/test-project");
         structure.add_file(
             "src/main.rs",
-            "content".to_string(),
+            "fn main() {}".to_string(),
+            Permissions::read_write(),
+        );
+        structure.add_file(
+            "src/lib.rs",
+            "pub fn lib() {}".to_string(),
             Permissions::read_write(),
         );
 
-        println!("{structure:?}");
+        // Fail partway through: the root and src/ already exist by then
+        fs_clone.fail_writes_to(Path::new("/test-project/src/lib.rs"));
 
-        // This should trigger rollback because parent dir doesn't exist? i am not sure this fails
         let result = writer.write(&structure);
 
-        println!("{result:?}");
-
-        // Write should fail
         assert!(result.is_err());
-
-        // Project directory should be cleaned up (rolled back)
+        // Everything written before the failure is gone again
         assert!(!fs_clone.exists(Path::new("/test-project")));
+        assert!(!fs_clone.exists(Path::new("/test-project/src/main.rs")));
+        assert_eq!(fs_clone.file_count(), 0);
     }
 }
@@ -0,0 +1,286 @@
+//! Pluggable template engines.
+//!
+//! File content and hooks are rendered by a [`TemplateBackend`], chosen by
+//! name: per template with `TemplateMetadata::engine` (`engine = "tera"`
+//! in a `template.toml`), per file with `FileSpec::engine`. Templates that
+//! name none use [`SimpleBackend`], the built-in `{{VAR}}` / `{{#if}}` /
+//! `{{#each}}` syntax.
+//!
+//! Other engines are plugged in with
+//! [`Engine::register_backend`](crate::Engine::register_backend). Backends
+//! get the render variables as [`Variables`], which serializes to a map of
+//! strings and lists, so it can be handed to Handlebars, Tera or `MiniJinja`
+//! as their context directly:
+//!
+//! ```rust,ignore
+//! struct Tera;
+//!
+//! impl TemplateBackend for Tera {
+//!     fn name(&self) -> &str {
+//!         "tera"
+//!     }
+//!
+//!     fn render(&self, source: &str, vars: &Variables<'_>) -> Result<String, BackendError> {
+//!         let context = tera::Context::from_serialize(vars)
+//!             .map_err(|e| BackendError::new(e.to_string()))?;
+//!         tera::Tera::one_off(source, &context, false)
+//!             .map_err(|e| BackendError::new(e.to_string()))
+//!     }
+//! }
+//!
+//! engine.register_backend(Tera);
+//! ```
+//!
+//! `{{> id}}` includes are expanded before any backend sees the content.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use serde::{Serialize, Serializer, ser::SerializeMap};
+
+use crate::{domain::RenderContext, template::syntax};
+
+/// Name of the engine templates use unless they pick another.
+pub const DEFAULT_ENGINE: &str = "simple";
+
+// ============================================================================
+// TemplateBackend
+// ============================================================================
+
+/// A template engine that renders file content and hook strings.
+pub trait TemplateBackend: Send + Sync {
+    /// Name templates select the engine by (`engine = "…"`).
+    fn name(&self) -> &str;
+
+    /// Render `source` with `vars`.
+    ///
+    /// # Errors
+    ///
+    /// A [`BackendError`] for content the engine can't render; scarff adds
+    /// the file it was rendering.
+    fn render(&self, source: &str, vars: &Variables<'_>) -> Result<String, BackendError>;
+}
+
+/// Why a backend couldn't render some content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendError {
+    /// What went wrong
+    pub message: String,
+    /// 1-based line of the mistake, if the engine reports one
+    pub line: Option<usize>,
+}
+
+impl BackendError {
+    /// An error without a line.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+        }
+    }
+
+    /// Set the line of the mistake (builder style).
+    #[must_use]
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+// ============================================================================
+// Variables
+// ============================================================================
+
+/// The variables content is rendered with, read-only.
+///
+/// Serializes as one map: each variable as a string, each list (see
+/// `{{#each}}`) as an array of strings.
+#[derive(Debug, Clone, Copy)]
+pub struct Variables<'a> {
+    ctx: &'a RenderContext,
+}
+
+impl<'a> Variables<'a> {
+    pub(crate) fn new(ctx: &'a RenderContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Value of a variable, e.g. `PROJECT_NAME`.
+    pub fn get(self, name: &str) -> Option<&'a str> {
+        self.ctx.get(name)
+    }
+
+    /// Items of a list variable, e.g. `FEATURES`.
+    pub fn list(self, name: &str) -> Option<&'a [String]> {
+        self.ctx.list(name)
+    }
+
+    /// Every variable, sorted by name.
+    pub fn vars(self) -> BTreeMap<&'a str, &'a str> {
+        self.ctx
+            .all()
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect()
+    }
+
+    /// Every list variable, sorted by name.
+    pub fn lists(self) -> BTreeMap<&'a str, &'a [String]> {
+        self.ctx
+            .lists()
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_slice()))
+            .collect()
+    }
+
+    /// The render context behind these variables, for [`SimpleBackend`].
+    pub(crate) fn context(self) -> &'a RenderContext {
+        self.ctx
+    }
+}
+
+impl Serialize for Variables<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let vars = self.vars();
+        let lists = self.lists();
+        let mut map = serializer.serialize_map(Some(vars.len() + lists.len()))?;
+        for (name, value) in vars {
+            map.serialize_entry(name, value)?;
+        }
+        for (name, items) in lists {
+            map.serialize_entry(name, items)?;
+        }
+        map.end()
+    }
+}
+
+// ============================================================================
+// SimpleBackend
+// ============================================================================
+
+/// The built-in engine: `{{VAR}}`, `{{#if}}`, `{{#unless}}` and
+/// `{{#each}}` (see the `syntax` module).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimpleBackend;
+
+impl TemplateBackend for SimpleBackend {
+    fn name(&self) -> &str {
+        DEFAULT_ENGINE
+    }
+
+    fn render(&self, source: &str, vars: &Variables<'_>) -> Result<String, BackendError> {
+        syntax::render(source, vars.context())
+            .map_err(|e| BackendError::new(e.reason).at_line(e.line))
+    }
+}
+
+// ============================================================================
+// Backends
+// ============================================================================
+
+/// The engines a renderer knows, by name.
+pub(crate) struct Backends {
+    by_name: RwLock<HashMap<String, Arc<dyn TemplateBackend>>>,
+}
+
+impl Backends {
+    /// Just [`SimpleBackend`].
+    pub(crate) fn builtin() -> Self {
+        let backends = Self {
+            by_name: RwLock::new(HashMap::new()),
+        };
+        backends.register(Arc::new(SimpleBackend));
+        backends
+    }
+
+    /// Add `backend`, replacing one registered under the same name.
+    pub(crate) fn register(&self, backend: Arc<dyn TemplateBackend>) {
+        self.by_name
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(backend.name().to_string(), backend);
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<Arc<dyn TemplateBackend>> {
+        self.by_name
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variables_serialize_as_one_map_of_strings_and_lists() {
+        let ctx = RenderContext::new("demo")
+            .with_var("AUTHOR", "Ada")
+            .with_list("DEPS", ["serde", "tokio"]);
+
+        let json = serde_json::to_value(Variables::new(&ctx)).unwrap();
+
+        assert_eq!(json["PROJECT_NAME"], "demo");
+        assert_eq!(json["AUTHOR"], "Ada");
+        assert_eq!(json["DEPS"], serde_json::json!(["serde", "tokio"]));
+    }
+
+    #[test]
+    fn simple_backend_reports_the_line_of_a_mistake() {
+        let ctx = RenderContext::new("demo");
+        let vars = Variables::new(&ctx);
+
+        assert_eq!(
+            SimpleBackend.render("# {{PROJECT_NAME}}", &vars).unwrap(),
+            "# demo"
+        );
+
+        let err = SimpleBackend
+            .render("ok\n{{#if X}}\nnever closed\n", &vars)
+            .unwrap_err();
+        assert_eq!(err.line, Some(2));
+    }
+
+    #[test]
+    fn registering_replaces_a_backend_of_the_same_name() {
+        struct Shout;
+
+        impl TemplateBackend for Shout {
+            fn name(&self) -> &str {
+                DEFAULT_ENGINE
+            }
+
+            fn render(&self, source: &str, _: &Variables<'_>) -> Result<String, BackendError> {
+                Ok(source.to_uppercase())
+            }
+        }
+
+        let backends = Backends::builtin();
+        backends.register(Arc::new(Shout));
+
+        let ctx = RenderContext::new("demo");
+        let simple = backends.get(DEFAULT_ENGINE).unwrap();
+        assert_eq!(simple.render("hi", &Variables::new(&ctx)).unwrap(), "HI");
+        assert!(backends.get("tera").is_none());
+    }
+}
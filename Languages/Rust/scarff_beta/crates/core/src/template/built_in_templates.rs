@@ -5,8 +5,8 @@
 
 use crate::domain::{Architecture, Language, ProjectKind};
 use crate::domain::{
-    DirectorySpec, FileSpec, TargetMatcher, Template, TemplateContent, TemplateId,
-    TemplateMetadata, TemplateNode, TemplateSource, TemplateTree,
+    ContentTemplateId, DirectorySpec, FileSpec, TargetMatcher, Template, TemplateContent,
+    TemplateId, TemplateMetadata, TemplateNode, TemplateSource, TemplateTree,
 };
 
 // ============================================================================
@@ -23,8 +23,16 @@ use crate::domain::{
 ///     dir "tests";
 ///     file "src/main.rs" => "path/to/template.rs";
 ///     file "Cargo.toml" => "path/to/Cargo.toml.template";
+///     partial ".gitignore" => "gitignore/rust";
+///     executable "scripts/run.sh" => "path/to/run.sh.template";
+///     symlink "run" => "scripts/run.sh";
 /// }
 /// ```
+///
+/// `partial` entries take their whole content from a shared partial (see
+/// `template::partials`) instead of a file of their own. `executable`
+/// entries are files written with the executable bit, and `symlink`
+/// entries point to a target relative to the link's directory.
 macro_rules! template_tree {
     (
         $(
@@ -33,6 +41,15 @@ macro_rules! template_tree {
         $(
             file $path:literal => $tpl:literal;
         )*
+        $(
+            partial $ppath:literal => $partial:literal;
+        )*
+        $(
+            executable $xpath:literal => $xtpl:literal;
+        )*
+        $(
+            symlink $lpath:literal => $target:literal;
+        )*
     ) => {{
         let mut tree = TemplateTree::new();
 
@@ -47,6 +64,27 @@ macro_rules! template_tree {
             )));
         )*
 
+        $(
+            tree.push(TemplateNode::File(FileSpec::new(
+                $ppath,
+                TemplateContent::External(ContentTemplateId($partial)),
+            )));
+        )*
+
+        $(
+            tree.push(TemplateNode::File(
+                FileSpec::new(
+                    $xpath,
+                    TemplateContent::Parameterized(TemplateSource::Static(include_str!($xtpl))),
+                )
+                .executable(),
+            ));
+        )*
+
+        $(
+            tree.push(TemplateNode::Symlink(crate::domain::SymlinkSpec::new($lpath, $target)));
+        )*
+
         tree
     }};
 }
@@ -104,10 +142,12 @@ macro_rules! template {
                 framework: $fw,
                 kind: Some(ProjectKind::$kind),
                 architecture: Some(Architecture::$arch),
+                features: Vec::new(),
             },
             tree: template_tree! {
                 $($tree)*
             },
+            hooks: Vec::new(),
         }
     };
 }
@@ -139,8 +179,7 @@ pub fn rust_cli_default() -> Template {
                 => "templates/rust/cli/_default/main.rs.template";
             file "Cargo.toml"
                 => "templates/rust/cli/_default/Cargo.toml.template";
-            // file ".gitignore"
-            //     => "templates/common/rust.gitignore";
+            partial ".gitignore" => "gitignore/rust";
             // file "README.md"
             //     => "templates/rust/cli/_default/README.md.template";
         }
@@ -190,10 +229,9 @@ pub fn rust_cli_layered() -> Template {
             //     => "templates/rust/cli/layered/presentation_mod.rs.template";
             // file "Cargo.toml"
             //     => "templates/rust/cli/layered/Cargo.toml.template";
-            // file ".gitignore"
-            //     => "templates/common/rust.gitignore";
             // file "README.md"
             //     => "templates/rust/cli/layered/README.md.template";
+            // partial ".gitignore" => "gitignore/rust";
         }
     }
 }
@@ -241,10 +279,128 @@ pub fn rust_backend_axum() -> Template {
             //     => "templates/rust/backend/axum/Cargo.toml.template";
             // file ".env.example"
             //     => "templates/rust/backend/axum/env.example";
-            // file ".gitignore"
-            //     => "templates/common/rust.gitignore";
             // file "README.md"
             //     => "templates/rust/backend/axum/README.md.template";
+            // partial ".gitignore" => "gitignore/rust";
+        }
+    }
+}
+
+/// Rust web backend with Axum, in the hexagonal (ports and adapters) style.
+///
+/// The domain declares the ports: `NoteService` (inbound, driven by the
+/// HTTP API) and `NoteRepository` (outbound, implemented by an in-memory
+/// adapter). The application implements the inbound port against the
+/// outbound one only.
+pub fn rust_backend_axum_hexagonal() -> Template {
+    use crate::domain::{Framework, RustFramework};
+
+    template! {
+        name: "Rust Web Backend (Axum, Hexagonal)",
+        version: "1.0.0",
+        description: "A Rust web API using Axum with ports and adapters",
+        tags: ["rust", "web", "api", "axum", "backend", "hexagonal"],
+
+        matcher {
+            language: Rust,
+            framework: Some(Framework::Rust(RustFramework::Axum)),
+            kind: WebBackend,
+            architecture: Hexagonal,
+        }
+
+        tree {
+            file "src/main.rs"
+                => "templates/rust/backend/axum/hexagonal/main.rs.template";
+            file "src/domain/mod.rs"
+                => "templates/rust/backend/axum/hexagonal/domain_mod.rs.template";
+            file "src/domain/error.rs"
+                => "templates/rust/backend/axum/hexagonal/domain_error.rs.template";
+            file "src/domain/note.rs"
+                => "templates/rust/backend/axum/note.rs.template";
+            file "src/domain/ports.rs"
+                => "templates/rust/backend/axum/hexagonal/ports.rs.template";
+            file "src/application/mod.rs"
+                => "templates/rust/backend/axum/hexagonal/application_mod.rs.template";
+            file "src/application/notes.rs"
+                => "templates/rust/backend/axum/hexagonal/notes.rs.template";
+            file "src/infrastructure/mod.rs"
+                => "templates/rust/backend/axum/hexagonal/infrastructure_mod.rs.template";
+            file "src/infrastructure/memory.rs"
+                => "templates/rust/backend/axum/hexagonal/memory.rs.template";
+            file "src/api/mod.rs"
+                => "templates/rust/backend/axum/hexagonal/api_mod.rs.template";
+            file "src/api/dto.rs"
+                => "templates/rust/backend/axum/hexagonal/dto.rs.template";
+            file "src/api/error.rs"
+                => "templates/rust/backend/axum/hexagonal/api_error.rs.template";
+            file "src/api/handlers.rs"
+                => "templates/rust/backend/axum/hexagonal/handlers.rs.template";
+            file "Cargo.toml"
+                => "templates/rust/backend/axum/Cargo.toml.template";
+            partial ".gitignore" => "gitignore/rust";
+        }
+    }
+}
+
+/// Rust web backend with Axum, in the clean architecture style.
+///
+/// Layers like verb_beta's: domain entities and the repository port,
+/// one application use case per operation, an in-memory repository
+/// adapter, and API handlers calling the use cases.
+pub fn rust_backend_axum_clean() -> Template {
+    use crate::domain::{Framework, RustFramework};
+
+    template! {
+        name: "Rust Web Backend (Axum, Clean)",
+        version: "1.0.0",
+        description: "A Rust web API using Axum with clean architecture use cases",
+        tags: ["rust", "web", "api", "axum", "backend", "clean"],
+
+        matcher {
+            language: Rust,
+            framework: Some(Framework::Rust(RustFramework::Axum)),
+            kind: WebBackend,
+            architecture: Clean,
+        }
+
+        tree {
+            file "src/main.rs"
+                => "templates/rust/backend/axum/clean/main.rs.template";
+            file "src/domain/mod.rs"
+                => "templates/rust/backend/axum/clean/domain_mod.rs.template";
+            file "src/domain/error.rs"
+                => "templates/rust/backend/axum/clean/domain_error.rs.template";
+            file "src/domain/model.rs"
+                => "templates/rust/backend/axum/note.rs.template";
+            file "src/domain/repository.rs"
+                => "templates/rust/backend/axum/clean/repository.rs.template";
+            file "src/application/mod.rs"
+                => "templates/rust/backend/axum/clean/application_mod.rs.template";
+            file "src/application/error.rs"
+                => "templates/rust/backend/axum/clean/application_error.rs.template";
+            file "src/application/use_cases/mod.rs"
+                => "templates/rust/backend/axum/clean/use_cases_mod.rs.template";
+            file "src/application/use_cases/create_note.rs"
+                => "templates/rust/backend/axum/clean/create_note.rs.template";
+            file "src/application/use_cases/get_note.rs"
+                => "templates/rust/backend/axum/clean/get_note.rs.template";
+            file "src/application/use_cases/list_notes.rs"
+                => "templates/rust/backend/axum/clean/list_notes.rs.template";
+            file "src/infrastructure/mod.rs"
+                => "templates/rust/backend/axum/clean/infrastructure_mod.rs.template";
+            file "src/infrastructure/memory_note_repository.rs"
+                => "templates/rust/backend/axum/clean/memory_note_repository.rs.template";
+            file "src/api/mod.rs"
+                => "templates/rust/backend/axum/clean/api_mod.rs.template";
+            file "src/api/dto.rs"
+                => "templates/rust/backend/axum/clean/dto.rs.template";
+            file "src/api/error.rs"
+                => "templates/rust/backend/axum/clean/api_error.rs.template";
+            file "src/api/handlers.rs"
+                => "templates/rust/backend/axum/clean/handlers.rs.template";
+            file "Cargo.toml"
+                => "templates/rust/backend/axum/Cargo.toml.template";
+            partial ".gitignore" => "gitignore/rust";
         }
     }
 }
@@ -292,17 +448,16 @@ pub fn python_backend_fastapi() -> Template {
             //     => "templates/python/backend/fastapi/requirements.txt.template";
             // file ".env.example"
             //     => "templates/python/backend/fastapi/env.example";
-            // file ".gitignore"
-            //     => "templates/common/python.gitignore";
             // file "README.md"
             //     => "templates/python/backend/fastapi/README.md.template";
+            // partial ".gitignore" => "gitignore/python";
         }
     }
 }
 
-/// TypeScript frontend with React.
+/// TypeScript frontend with React, built with Vite.
 ///
-/// Creates a React application with modern tooling (Vite).
+/// Code is grouped by kind: `components/`, `hooks/` and `lib/` under `src/`.
 pub fn typescript_frontend_react() -> Template {
     use crate::domain::{Framework, TypeScriptFramework};
 
@@ -320,30 +475,336 @@ pub fn typescript_frontend_react() -> Template {
         }
 
         tree {
-            // dir "src";
-            // dir "src/components";
-            // dir "src/hooks";
-            // dir "src/utils";
-            // dir "public";
-
-            // file "src/main.tsx"
-            //     => "templates/typescript/frontend/react/main.tsx.template";
-            // file "src/App.tsx"
-            //     => "templates/typescript/frontend/react/App.tsx.template";
-            // file "src/vite-env.d.ts"
-            //     => "templates/typescript/frontend/react/vite-env.d.ts.template";
-            // file "index.html"
-            //     => "templates/typescript/frontend/react/index.html.template";
-            // file "package.json"
-            //     => "templates/typescript/frontend/react/package.json.template";
-            // file "tsconfig.json"
-            //     => "templates/typescript/frontend/react/tsconfig.json.template";
-            // file "vite.config.ts"
-            //     => "templates/typescript/frontend/react/vite.config.ts.template";
-            // file ".gitignore"
-            //     => "templates/common/node.gitignore";
-            // file "README.md"
-            //     => "templates/typescript/frontend/react/README.md.template";
+            dir "src/hooks";
+            dir "src/lib";
+            dir "public";
+
+            file "src/main.tsx"
+                => "templates/typescript/frontend/react/layered/main.tsx.template";
+            file "src/App.tsx"
+                => "templates/typescript/frontend/react/layered/App.tsx.template";
+            file "src/components/Counter.tsx"
+                => "templates/typescript/frontend/react/Counter.tsx.template";
+            file "src/vite-env.d.ts"
+                => "templates/typescript/frontend/react/vite-env.d.ts.template";
+            file "index.html"
+                => "templates/typescript/frontend/react/index.html.template";
+            file "package.json"
+                => "templates/typescript/frontend/react/package.json.template";
+            file "tsconfig.json"
+                => "templates/typescript/frontend/react/tsconfig.json.template";
+            file "vite.config.ts"
+                => "templates/typescript/frontend/react/vite.config.ts.template";
+            partial ".gitignore" => "gitignore/node";
+        }
+    }
+}
+
+/// TypeScript frontend with React, built with Vite, organized by feature.
+///
+/// The app shell lives in `src/app/`, each feature in `src/features/<name>/`
+/// behind an `index.ts`, and code shared between features in `src/shared/`.
+pub fn typescript_frontend_react_modular() -> Template {
+    use crate::domain::{Framework, TypeScriptFramework};
+
+    template! {
+        name: "TypeScript Frontend (React, Modular)",
+        version: "1.0.0",
+        description: "A React application with TypeScript and Vite, organized by feature",
+        tags: ["typescript", "react", "frontend", "vite", "modular"],
+
+        matcher {
+            language: TypeScript,
+            framework: Some(Framework::TypeScript(TypeScriptFramework::React)),
+            kind: WebFrontend,
+            architecture: Modular,
+        }
+
+        tree {
+            dir "src/shared";
+            dir "public";
+
+            file "src/main.tsx"
+                => "templates/typescript/frontend/react/modular/main.tsx.template";
+            file "src/app/App.tsx"
+                => "templates/typescript/frontend/react/modular/App.tsx.template";
+            file "src/features/counter/Counter.tsx"
+                => "templates/typescript/frontend/react/Counter.tsx.template";
+            file "src/features/counter/index.ts"
+                => "templates/typescript/frontend/react/modular/counter-index.ts.template";
+            file "src/vite-env.d.ts"
+                => "templates/typescript/frontend/react/vite-env.d.ts.template";
+            file "index.html"
+                => "templates/typescript/frontend/react/index.html.template";
+            file "package.json"
+                => "templates/typescript/frontend/react/package.json.template";
+            file "tsconfig.json"
+                => "templates/typescript/frontend/react/tsconfig.json.template";
+            file "vite.config.ts"
+                => "templates/typescript/frontend/react/vite.config.ts.template";
+            partial ".gitignore" => "gitignore/node";
+        }
+    }
+}
+
+/// TypeScript frontend with Next.js and the App Router.
+///
+/// Routes live in `src/app/`, shared components in `src/components/`.
+pub fn typescript_frontend_nextjs() -> Template {
+    use crate::domain::{Framework, TypeScriptFramework};
+
+    template! {
+        name: "TypeScript Frontend (Next.js)",
+        version: "1.0.0",
+        description: "A Next.js application with TypeScript and the App Router",
+        tags: ["typescript", "nextjs", "react", "frontend"],
+
+        matcher {
+            language: TypeScript,
+            framework: Some(Framework::TypeScript(TypeScriptFramework::NextJs)),
+            kind: WebFrontend,
+            architecture: AppRouter,
+        }
+
+        tree {
+            dir "src/lib";
+            dir "public";
+
+            file "src/app/layout.tsx"
+                => "templates/typescript/frontend/nextjs/layout.tsx.template";
+            file "src/app/page.tsx"
+                => "templates/typescript/frontend/nextjs/app-router/page.tsx.template";
+            file "src/app/globals.css"
+                => "templates/typescript/frontend/nextjs/globals.css.template";
+            file "src/components/Counter.tsx"
+                => "templates/typescript/frontend/nextjs/Counter.tsx.template";
+            file "next-env.d.ts"
+                => "templates/typescript/frontend/nextjs/next-env.d.ts.template";
+            file "next.config.mjs"
+                => "templates/typescript/frontend/nextjs/next.config.mjs.template";
+            file "package.json"
+                => "templates/typescript/frontend/nextjs/package.json.template";
+            file "tsconfig.json"
+                => "templates/typescript/frontend/nextjs/tsconfig.json.template";
+            partial ".gitignore" => "gitignore/node";
+        }
+    }
+}
+
+/// TypeScript frontend with Next.js, organized by feature.
+///
+/// `src/app/` only holds routes; what they render comes from
+/// `src/features/<name>/`, with cross-feature code in `src/shared/`.
+pub fn typescript_frontend_nextjs_modular() -> Template {
+    use crate::domain::{Framework, TypeScriptFramework};
+
+    template! {
+        name: "TypeScript Frontend (Next.js, Modular)",
+        version: "1.0.0",
+        description: "A Next.js application with TypeScript, organized by feature",
+        tags: ["typescript", "nextjs", "react", "frontend", "modular"],
+
+        matcher {
+            language: TypeScript,
+            framework: Some(Framework::TypeScript(TypeScriptFramework::NextJs)),
+            kind: WebFrontend,
+            architecture: Modular,
+        }
+
+        tree {
+            dir "src/shared";
+            dir "public";
+
+            file "src/app/layout.tsx"
+                => "templates/typescript/frontend/nextjs/layout.tsx.template";
+            file "src/app/page.tsx"
+                => "templates/typescript/frontend/nextjs/modular/page.tsx.template";
+            file "src/app/globals.css"
+                => "templates/typescript/frontend/nextjs/globals.css.template";
+            file "src/features/counter/Counter.tsx"
+                => "templates/typescript/frontend/nextjs/Counter.tsx.template";
+            file "src/features/counter/index.ts"
+                => "templates/typescript/frontend/nextjs/modular/counter-index.ts.template";
+            file "next-env.d.ts"
+                => "templates/typescript/frontend/nextjs/next-env.d.ts.template";
+            file "next.config.mjs"
+                => "templates/typescript/frontend/nextjs/next.config.mjs.template";
+            file "package.json"
+                => "templates/typescript/frontend/nextjs/package.json.template";
+            file "tsconfig.json"
+                => "templates/typescript/frontend/nextjs/tsconfig.json.template";
+            partial ".gitignore" => "gitignore/node";
+        }
+    }
+}
+
+/// TypeScript frontend with SvelteKit.
+///
+/// Routes live in `src/routes/`, reusable code in `src/lib/` (`$lib`).
+pub fn typescript_frontend_sveltekit() -> Template {
+    use crate::domain::{Framework, TypeScriptFramework};
+
+    template! {
+        name: "TypeScript Frontend (SvelteKit)",
+        version: "1.0.0",
+        description: "A SvelteKit application with TypeScript",
+        tags: ["typescript", "svelte", "sveltekit", "frontend", "vite"],
+
+        matcher {
+            language: TypeScript,
+            framework: Some(Framework::TypeScript(TypeScriptFramework::SvelteKit)),
+            kind: WebFrontend,
+            architecture: Layered,
+        }
+
+        tree {
+            dir "static";
+
+            file "src/app.html"
+                => "templates/typescript/frontend/sveltekit/app.html.template";
+            file "src/app.d.ts"
+                => "templates/typescript/frontend/sveltekit/app.d.ts.template";
+            file "src/routes/+page.svelte"
+                => "templates/typescript/frontend/sveltekit/layered/page.svelte.template";
+            file "src/lib/index.ts"
+                => "templates/typescript/frontend/sveltekit/layered/lib-index.ts.template";
+            file "src/lib/components/Counter.svelte"
+                => "templates/typescript/frontend/sveltekit/Counter.svelte.template";
+            file "package.json"
+                => "templates/typescript/frontend/sveltekit/package.json.template";
+            file "svelte.config.js"
+                => "templates/typescript/frontend/sveltekit/svelte.config.js.template";
+            file "tsconfig.json"
+                => "templates/typescript/frontend/sveltekit/tsconfig.json.template";
+            file "vite.config.ts"
+                => "templates/typescript/frontend/sveltekit/vite.config.ts.template";
+            partial ".gitignore" => "gitignore/node";
+        }
+    }
+}
+
+/// TypeScript frontend with SvelteKit, organized by feature.
+///
+/// Features live in `src/lib/features/<name>/`, cross-feature code in
+/// `src/lib/shared/`; routes import features through `$lib/features`.
+pub fn typescript_frontend_sveltekit_modular() -> Template {
+    use crate::domain::{Framework, TypeScriptFramework};
+
+    template! {
+        name: "TypeScript Frontend (SvelteKit, Modular)",
+        version: "1.0.0",
+        description: "A SvelteKit application with TypeScript, organized by feature",
+        tags: ["typescript", "svelte", "sveltekit", "frontend", "vite", "modular"],
+
+        matcher {
+            language: TypeScript,
+            framework: Some(Framework::TypeScript(TypeScriptFramework::SvelteKit)),
+            kind: WebFrontend,
+            architecture: Modular,
+        }
+
+        tree {
+            dir "src/lib/shared";
+            dir "static";
+
+            file "src/app.html"
+                => "templates/typescript/frontend/sveltekit/app.html.template";
+            file "src/app.d.ts"
+                => "templates/typescript/frontend/sveltekit/app.d.ts.template";
+            file "src/routes/+page.svelte"
+                => "templates/typescript/frontend/sveltekit/modular/page.svelte.template";
+            file "src/lib/features/counter/Counter.svelte"
+                => "templates/typescript/frontend/sveltekit/Counter.svelte.template";
+            file "src/lib/features/counter/index.ts"
+                => "templates/typescript/frontend/sveltekit/modular/counter-index.ts.template";
+            file "package.json"
+                => "templates/typescript/frontend/sveltekit/package.json.template";
+            file "svelte.config.js"
+                => "templates/typescript/frontend/sveltekit/svelte.config.js.template";
+            file "tsconfig.json"
+                => "templates/typescript/frontend/sveltekit/tsconfig.json.template";
+            file "vite.config.ts"
+                => "templates/typescript/frontend/sveltekit/vite.config.ts.template";
+            partial ".gitignore" => "gitignore/node";
+        }
+    }
+}
+
+/// Cargo workspace root.
+///
+/// Lists every member under `crates/` in `[workspace] members`; the members
+/// are rendered from their own templates by `Engine::scaffold_workspace`.
+pub fn rust_workspace() -> Template {
+    template! {
+        name: "Rust Workspace",
+        version: "1.0.0",
+        description: "A Cargo workspace with its member crates under crates/",
+        tags: ["rust", "workspace", "monorepo"],
+
+        matcher {
+            language: Rust,
+            framework: None,
+            kind: Workspace,
+            architecture: Layered,
+        }
+
+        tree {
+            dir "crates";
+            file "Cargo.toml"
+                => "templates/rust/workspace/Cargo.toml.template";
+            partial ".gitignore" => "gitignore/rust";
+        }
+    }
+}
+
+/// pnpm workspace root, with its members under `packages/`.
+pub fn typescript_workspace() -> Template {
+    template! {
+        name: "TypeScript Workspace (pnpm)",
+        version: "1.0.0",
+        description: "A pnpm workspace with its member packages under packages/",
+        tags: ["typescript", "workspace", "monorepo", "pnpm"],
+
+        matcher {
+            language: TypeScript,
+            framework: None,
+            kind: Workspace,
+            architecture: Layered,
+        }
+
+        tree {
+            dir "packages";
+            file "package.json"
+                => "templates/typescript/workspace/package.json.template";
+            file "pnpm-workspace.yaml"
+                => "templates/typescript/workspace/pnpm-workspace.yaml.template";
+            partial ".gitignore" => "gitignore/node";
+        }
+    }
+}
+
+/// uv workspace root, with its members under `packages/`.
+///
+/// `.python-version` and `uv.lock` come from the uv tooling files.
+pub fn python_workspace() -> Template {
+    template! {
+        name: "Python Workspace (uv)",
+        version: "1.0.0",
+        description: "A uv workspace with its member packages under packages/",
+        tags: ["python", "workspace", "monorepo", "uv"],
+
+        matcher {
+            language: Python,
+            framework: None,
+            kind: Workspace,
+            architecture: Layered,
+        }
+
+        tree {
+            dir "packages";
+            file "pyproject.toml"
+                => "templates/python/workspace/pyproject.toml.template";
+            partial ".gitignore" => "gitignore/python";
         }
     }
 }
@@ -361,8 +822,18 @@ pub fn all_templates() -> Vec<Template> {
         rust_cli_default(),
         // rust_cli_layered(),
         // rust_backend_axum(),
+        rust_backend_axum_hexagonal(),
+        rust_backend_axum_clean(),
         // python_backend_fastapi(),
-        // typescript_frontend_react(),
+        typescript_frontend_react(),
+        typescript_frontend_react_modular(),
+        typescript_frontend_nextjs(),
+        typescript_frontend_nextjs_modular(),
+        typescript_frontend_sveltekit(),
+        typescript_frontend_sveltekit_modular(),
+        rust_workspace(),
+        typescript_workspace(),
+        python_workspace(),
     ]
 }
 
@@ -434,6 +905,48 @@ mod tests {
         assert_eq!(template.matcher.kind, Some(ProjectKind::WebBackend));
     }
 
+    #[test]
+    fn rust_backend_architectures_have_their_own_template() {
+        use crate::domain::{Framework, RustFramework, Target};
+
+        let templates = all_templates();
+        let find = |architecture| {
+            let target = Target::builder()
+                .language(Language::Rust)
+                .kind(ProjectKind::WebBackend)
+                .unwrap()
+                .framework(Framework::Rust(RustFramework::Axum))
+                .unwrap()
+                .architecture(architecture)
+                .unwrap()
+                .build()
+                .unwrap();
+            let matching: Vec<_> = templates
+                .iter()
+                .filter(|t| t.matcher.matches(&target))
+                .collect();
+            assert_eq!(matching.len(), 1, "one template for {target}");
+            matching[0]
+        };
+
+        let hexagonal = find(Architecture::Hexagonal);
+        assert_eq!(
+            hexagonal.metadata.name,
+            "Rust Web Backend (Axum, Hexagonal)"
+        );
+        assert!(hexagonal.tree.has_file("src/domain/ports.rs"));
+        assert!(hexagonal.tree.has_file("src/infrastructure/memory.rs"));
+
+        let clean = find(Architecture::Clean);
+        assert_eq!(clean.metadata.name, "Rust Web Backend (Axum, Clean)");
+        assert!(
+            clean
+                .tree
+                .has_file("src/application/use_cases/create_note.rs")
+        );
+        assert!(clean.tree.has_file("src/domain/repository.rs"));
+    }
+
     #[test]
     fn python_backend_fastapi_template() {
         use crate::domain::{Framework, PythonFramework};
@@ -461,6 +974,58 @@ mod tests {
         assert_eq!(template.matcher.kind, Some(ProjectKind::WebFrontend));
     }
 
+    #[test]
+    fn typescript_frontend_templates_cover_each_architecture() {
+        use crate::domain::{Framework, Target, TypeScriptFramework};
+
+        let templates = all_templates();
+        let find = |target: Target| {
+            let matching: Vec<_> = templates
+                .iter()
+                .filter(|t| t.matcher.matches(&target))
+                .collect();
+            assert_eq!(matching.len(), 1, "one template for {target}");
+            matching[0].metadata.name.clone()
+        };
+        let variant = |framework, architecture| {
+            Target::builder()
+                .language(Language::TypeScript)
+                .kind(ProjectKind::WebFrontend)
+                .unwrap()
+                .framework(Framework::TypeScript(framework))
+                .unwrap()
+                .architecture(architecture)
+                .unwrap()
+                .build()
+                .unwrap()
+        };
+
+        assert_eq!(
+            find(Target::typescript_frontend_vite_react().unwrap()),
+            "TypeScript Frontend (React)"
+        );
+        assert_eq!(
+            find(Target::typescript_frontend_nextjs().unwrap()),
+            "TypeScript Frontend (Next.js)"
+        );
+        assert_eq!(
+            find(Target::typescript_frontend_sveltekit().unwrap()),
+            "TypeScript Frontend (SvelteKit)"
+        );
+        assert_eq!(
+            find(variant(
+                TypeScriptFramework::SvelteKit,
+                Architecture::Modular
+            )),
+            "TypeScript Frontend (SvelteKit, Modular)"
+        );
+
+        let modular = typescript_frontend_react_modular();
+        assert!(modular.tree.has_file("src/features/counter/index.ts"));
+        assert!(!modular.tree.has_file("src/components/Counter.tsx"));
+        assert!(typescript_frontend_nextjs().tree.has_file("package.json"));
+    }
+
     #[test]
     fn template_count_matches_vec_length() {
         assert_eq!(template_count(), all_templates().len());
@@ -471,7 +1036,7 @@ mod tests {
         use std::collections::HashSet;
 
         let templates = all_templates();
-        let names: HashSet<_> = templates.iter().map(|t| t.metadata.name).collect();
+        let names: HashSet<_> = templates.iter().map(|t| t.metadata.name.as_str()).collect();
 
         assert_eq!(
             names.len(),
@@ -0,0 +1,310 @@
+//! CI pipeline files.
+//!
+//! Templates don't ship a pipeline: one is generated for the target's
+//! [`CiProvider`] at the provider's usual path:
+//! - GitHub Actions: `.github/workflows/ci.yml`
+//! - GitLab CI: `.gitlab-ci.yml`
+//! - CircleCI: `.circleci/config.yml`
+//!
+//! Every pipeline runs in the language's container image and has the same
+//! jobs: build, test (when the project has a test runner), lint, and a
+//! release job for `v*` tags when the project kind has something to ship
+//! (a binary, a Python distribution, a frontend bundle). The provider
+//! templates are shared by all languages; the commands they run come from
+//! the `CI_*` variables set here.
+
+use crate::domain::{
+    CiProvider, DependencyManager, FileSpec, Framework, Language, ProjectKind, RenderContext,
+    Target, Template, TemplateContent, TemplateNode, TemplateSource, TestRunner,
+    TypeScriptFramework,
+};
+use crate::template::backend::DEFAULT_ENGINE;
+
+/// Pipeline template of `provider`, in the built-in syntax.
+fn text(provider: CiProvider) -> &'static str {
+    match provider {
+        CiProvider::GitHubActions => include_str!("templates/ci/github-actions.yml.template"),
+        CiProvider::GitLabCi => include_str!("templates/ci/gitlab-ci.yml.template"),
+        CiProvider::CircleCi => include_str!("templates/ci/circleci.yml.template"),
+    }
+}
+
+/// Commands of each job, in the order they run.
+#[derive(Debug, Default)]
+struct Steps {
+    image: &'static str,
+    install: Vec<&'static str>,
+    build: Vec<&'static str>,
+    test: Vec<&'static str>,
+    lint: Vec<&'static str>,
+    release: Vec<&'static str>,
+    /// File or directory the release job keeps
+    artifact: String,
+}
+
+fn rust(kind: ProjectKind, crate_name: &str) -> Steps {
+    let (release, artifact) = match kind {
+        ProjectKind::Workspace => (
+            vec!["cargo build --workspace --release"],
+            "target/release".to_string(),
+        ),
+        ProjectKind::Cli | ProjectKind::WebBackend | ProjectKind::Worker => (
+            vec!["cargo build --release"],
+            format!("target/release/{crate_name}"),
+        ),
+        _ => (Vec::new(), String::new()),
+    };
+    Steps {
+        image: "rust:1",
+        install: Vec::new(),
+        build: vec!["cargo build --workspace --all-targets"],
+        test: vec!["cargo test --workspace"],
+        lint: vec![
+            "rustup component add rustfmt clippy",
+            "cargo fmt --all -- --check",
+            "cargo clippy --workspace --all-targets -- -D warnings",
+        ],
+        release,
+        artifact,
+    }
+}
+
+fn python(kind: ProjectKind, manager: DependencyManager) -> Steps {
+    let (install, test, lint, package) = match manager {
+        DependencyManager::Uv => (
+            vec!["pip install uv", "uv sync"],
+            "uv run pytest",
+            vec!["uvx ruff check ."],
+            Some("uv build"),
+        ),
+        DependencyManager::Poetry => (
+            vec!["pip install poetry", "poetry install"],
+            "poetry run pytest",
+            vec!["pip install ruff", "ruff check ."],
+            Some("poetry build"),
+        ),
+        DependencyManager::Pip => (
+            vec!["pip install -r requirements-dev.txt"],
+            "pytest",
+            vec!["pip install ruff", "ruff check ."],
+            None,
+        ),
+    };
+    // Command-line tools are released as a wheel and sdist
+    let (release, artifact) = match (kind, package) {
+        (ProjectKind::Cli, Some(package)) => (vec![package], "dist".to_string()),
+        _ => (Vec::new(), String::new()),
+    };
+    Steps {
+        image: "python:3.12",
+        install,
+        build: vec!["python -m compileall -q ."],
+        test: vec![test],
+        lint,
+        release,
+        artifact,
+    }
+}
+
+fn typescript(
+    kind: ProjectKind,
+    framework: Option<Framework>,
+    runner: Option<TestRunner>,
+) -> Steps {
+    // Workspaces are pnpm workspaces
+    let (install, build, test) = if kind == ProjectKind::Workspace {
+        (
+            vec!["corepack enable", "pnpm install"],
+            "pnpm build",
+            "pnpm test",
+        )
+    } else {
+        (vec!["npm install"], "npm run build", "npm test")
+    };
+    let test = match runner {
+        Some(TestRunner::None) => Vec::new(),
+        _ => vec![test],
+    };
+    // Frontends release their production bundle
+    let bundle = match framework {
+        Some(Framework::TypeScript(TypeScriptFramework::NextJs)) => Some(".next"),
+        Some(Framework::TypeScript(TypeScriptFramework::SvelteKit)) => Some("build"),
+        Some(Framework::TypeScript(TypeScriptFramework::React | TypeScriptFramework::Vue)) => {
+            Some("dist")
+        }
+        _ => None,
+    };
+    let (release, artifact) = match (kind, bundle) {
+        (ProjectKind::WebFrontend | ProjectKind::Fullstack, Some(bundle)) => {
+            (vec![build], bundle.to_string())
+        }
+        _ => (Vec::new(), String::new()),
+    };
+    Steps {
+        image: "node:20",
+        install,
+        build: vec![build],
+        test,
+        lint: vec!["npx tsc --noEmit"],
+        release,
+        artifact,
+    }
+}
+
+/// Add the pipeline for the target's CI provider to `template`, and the
+/// `CI_*` variables it renders from to `ctx`.
+///
+/// A pipeline file the template already provides is kept as it is.
+/// Targets without a CI provider are returned unchanged.
+pub(crate) fn with_ci(
+    mut template: Template,
+    ctx: RenderContext,
+    target: &Target,
+) -> (Template, RenderContext) {
+    let Some(provider) = target.ci() else {
+        return (template, ctx);
+    };
+
+    let path = provider.config_path();
+    if !template.tree.has_file(path) {
+        // Written in the built-in syntax, whatever the template's engine
+        template.tree.push(TemplateNode::File(
+            FileSpec::new(
+                path,
+                TemplateContent::Parameterized(TemplateSource::Static(text(provider))),
+            )
+            .engine(DEFAULT_ENGINE),
+        ));
+    }
+
+    let steps = match target.language() {
+        Language::Rust => rust(
+            target.kind(),
+            ctx.get("PROJECT_NAME_KEBAB").unwrap_or_default(),
+        ),
+        Language::Python => python(
+            target.kind(),
+            target
+                .dependency_manager()
+                .unwrap_or(DependencyManager::Pip),
+        ),
+        Language::TypeScript => typescript(target.kind(), target.framework(), target.test_runner()),
+    };
+
+    let ctx = ctx
+        .with_var("CI_IMAGE", steps.image)
+        .with_list("CI_INSTALL", steps.install)
+        .with_list("CI_BUILD", steps.build)
+        .with_list("CI_TEST", steps.test)
+        .with_list("CI_LINT", steps.lint)
+        .with_list("CI_RELEASE", steps.release)
+        .with_var("CI_ARTIFACT", steps.artifact);
+    (template, ctx)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{TargetMatcher, TemplateId, TemplateMetadata, TemplateTree},
+        template::syntax,
+    };
+
+    fn template() -> Template {
+        Template {
+            id: TemplateId::new("rust", "0.1.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("rust"),
+            tree: TemplateTree::new(),
+            hooks: Vec::new(),
+        }
+    }
+
+    fn render(target: &Target) -> String {
+        let provider = target.ci().unwrap();
+        let (template, ctx) = with_ci(
+            template(),
+            RenderContext::new("my-tool").with_target(target),
+            target,
+        );
+        assert!(template.tree.has_file(provider.config_path()));
+        syntax::render(text(provider), &ctx).unwrap()
+    }
+
+    #[test]
+    fn rust_cli_pipeline_releases_its_binary() {
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .ci(CiProvider::GitHubActions)
+            .build()
+            .unwrap();
+
+        let workflow = render(&target);
+
+        assert!(workflow.contains("container: rust:1\n"));
+        assert!(workflow.contains("- run: cargo test --workspace\n"));
+        assert!(
+            workflow.contains("- run: cargo clippy --workspace --all-targets -- -D warnings\n")
+        );
+        assert!(workflow.contains("startsWith(github.ref, 'refs/tags/v')"));
+        assert!(workflow.contains("path: target/release/my-tool\n"));
+    }
+
+    #[test]
+    fn python_pipeline_uses_the_dependency_manager() {
+        let target = Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .dependency_manager(DependencyManager::Uv)
+            .unwrap()
+            .ci(CiProvider::GitLabCi)
+            .build()
+            .unwrap();
+
+        let pipeline = render(&target);
+
+        assert!(pipeline.starts_with("image: python:3.12\n"));
+        assert!(pipeline.contains("    - uv sync\n"));
+        assert!(pipeline.contains("    - uv run pytest\n"));
+        // Web backends have no release artifact
+        assert!(!pipeline.contains("release"));
+    }
+
+    #[test]
+    fn typescript_frontend_releases_its_bundle_and_skips_missing_tests() {
+        let target = Target::builder()
+            .language(Language::TypeScript)
+            .kind(ProjectKind::WebFrontend)
+            .unwrap()
+            .framework(Framework::TypeScript(TypeScriptFramework::React))
+            .unwrap()
+            .test_runner(TestRunner::None)
+            .unwrap()
+            .ci(CiProvider::CircleCi)
+            .build()
+            .unwrap();
+
+        let config = render(&target);
+
+        assert!(config.contains("- image: node:20\n"));
+        assert!(!config.contains("npm test"));
+        assert!(config.contains("path: dist\n"));
+        assert!(config.contains("requires: [build, lint]\n"));
+    }
+
+    #[test]
+    fn no_provider_leaves_the_template_alone() {
+        let target = Target::rust_cli().unwrap();
+        let (template, ctx) = with_ci(template(), RenderContext::new("demo"), &target);
+
+        assert!(template.tree.nodes.is_empty());
+        assert!(!ctx.has("CI_IMAGE"));
+    }
+}
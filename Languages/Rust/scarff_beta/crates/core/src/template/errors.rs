@@ -1,8 +1,13 @@
 //! Template-specific errors.
 
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
-use crate::domain::{Target, TemplateId};
+use crate::{
+    domain::{Target, TemplateId},
+    errors::ErrorPayload,
+};
 
 /// Errors that can occur during template operations.
 #[derive(Debug, Error, Clone)]
@@ -42,6 +47,46 @@ pub enum TemplateError {
     /// Rendering error
     #[error("Rendering failed: {0}")]
     RenderingFailed(String),
+
+    /// Content includes a partial that isn't registered
+    #[error("Unknown partial: {0}")]
+    UnknownPartial(String),
+
+    /// Partials include each other in a loop (the ids, first repeated last)
+    #[error("Include cycle: {}", .0.join(" -> "))]
+    IncludeCycle(Vec<String>),
+
+    /// A template on disk couldn't be read or is invalid
+    #[error("Failed to load template at {path}: {reason}")]
+    LoadFailed { path: String, reason: String },
+
+    /// A `template.toml` is malformed or breaks the schema, `at` a key or line
+    #[error("Invalid template manifest at {at}: {reason}")]
+    InvalidManifest { at: String, reason: String },
+
+    /// Parameterized content has a syntax error or an undefined variable
+    #[error("Template syntax error in {file} at line {line}: {reason}")]
+    Syntax {
+        file: String,
+        line: usize,
+        reason: String,
+    },
+
+    /// Content asks for a template engine that isn't registered
+    #[error("Unknown template engine: {0} (register it with Engine::register_backend)")]
+    UnknownEngine(String),
+
+    /// A template engine failed without pointing at a line
+    #[error("Template engine {engine} failed on {file}: {reason}")]
+    EngineFailed {
+        engine: String,
+        file: String,
+        reason: String,
+    },
+
+    /// The store can't be modified through the `Store` API
+    #[error("Template store is read-only: {0}")]
+    ReadOnly(String),
 }
 
 impl TemplateError {
@@ -59,4 +104,79 @@ impl TemplateError {
             count,
         }
     }
+
+    /// Stable, machine-readable code for this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidTarget(_) => "template.invalid_target",
+            Self::InvalidTemplate(_) => "template.invalid_template",
+            Self::NotFound(_) => "template.not_found",
+            Self::AlreadyExists(_) => "template.already_exists",
+            Self::LockError => "template.lock_error",
+            Self::NoMatch { .. } => "template.no_match",
+            Self::AmbiguousMatch { .. } => "template.ambiguous_match",
+            Self::UuidParseError => "template.uuid_parse_error",
+            Self::RenderingFailed(_) => "template.rendering_failed",
+            Self::UnknownPartial(_) => "template.unknown_partial",
+            Self::IncludeCycle(_) => "template.include_cycle",
+            Self::LoadFailed { .. } => "template.load_failed",
+            Self::InvalidManifest { .. } => "template.invalid_manifest",
+            Self::Syntax { .. } => "template.syntax",
+            Self::UnknownEngine(_) => "template.unknown_engine",
+            Self::EngineFailed { .. } => "template.engine_failed",
+            Self::ReadOnly(_) => "template.read_only",
+        }
+    }
+
+    /// Structured details of the failure.
+    pub fn context(&self) -> BTreeMap<&'static str, String> {
+        match self {
+            Self::InvalidTarget(reason)
+            | Self::InvalidTemplate(reason)
+            | Self::RenderingFailed(reason) => BTreeMap::from([("reason", reason.clone())]),
+            Self::NotFound(id) | Self::AlreadyExists(id) => {
+                BTreeMap::from([("template_id", id.to_string())])
+            }
+            Self::NoMatch { target } => BTreeMap::from([("target", target.clone())]),
+            Self::AmbiguousMatch { target, count } => {
+                BTreeMap::from([("target", target.clone()), ("count", count.to_string())])
+            }
+            Self::UnknownPartial(id) => BTreeMap::from([("partial", id.clone())]),
+            Self::IncludeCycle(chain) => BTreeMap::from([("chain", chain.join(" -> "))]),
+            Self::LoadFailed { path, reason } => {
+                BTreeMap::from([("path", path.clone()), ("reason", reason.clone())])
+            }
+            Self::InvalidManifest { at, reason } => {
+                BTreeMap::from([("at", at.clone()), ("reason", reason.clone())])
+            }
+            Self::Syntax { file, line, reason } => BTreeMap::from([
+                ("file", file.clone()),
+                ("line", line.to_string()),
+                ("reason", reason.clone()),
+            ]),
+            Self::UnknownEngine(engine) => BTreeMap::from([("engine", engine.clone())]),
+            Self::EngineFailed {
+                engine,
+                file,
+                reason,
+            } => BTreeMap::from([
+                ("engine", engine.clone()),
+                ("file", file.clone()),
+                ("reason", reason.clone()),
+            ]),
+            Self::ReadOnly(store) => BTreeMap::from([("store", store.clone())]),
+            Self::LockError | Self::UuidParseError => BTreeMap::new(),
+        }
+    }
+
+    /// Machine-readable form of this error.
+    pub fn to_payload(&self) -> ErrorPayload {
+        ErrorPayload::new(self.code(), self.to_string(), self.context())
+    }
+}
+
+impl Serialize for TemplateError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_payload().serialize(serializer)
+    }
 }
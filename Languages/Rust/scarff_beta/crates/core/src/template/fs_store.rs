@@ -0,0 +1,610 @@
+//! Filesystem-backed template store.
+//!
+//! User templates live in a directory tree (e.g. `~/.scarff/templates`).
+//! Every directory holding a `template.toml` is one template; the files it
+//! generates sit under its `files/` directory, at the paths they will have
+//! in the generated project:
+//!
+//! ```text
+//! ~/.scarff/templates/
+//! └── rust-cli-clap/
+//!     ├── template.toml
+//!     └── files/
+//!         ├── Cargo.toml
+//!         └── src/
+//!             └── main.rs
+//! ```
+//!
+//! The manifest format is described in [`TemplateManifest`]; files it
+//! declares are generated alongside those under `files/`, which may then
+//! be left out. Contents under `files/` are parameterized
+//! (`{{PROJECT_NAME}}` and friends are substituted), executable files
+//! stay executable on Unix, and symlinks are generated as symlinks to the
+//! same (relative) target, never followed.
+//!
+//! Templates are validated as they are loaded; a broken template is
+//! rejected on its own without hiding the others. Nothing is watched in
+//! the background: call [`FsStore::rescan`] to re-read the tree, or
+//! [`FsStore::rescan_if_changed`] to do so only when something on disk
+//! changed since the last scan.
+
+use std::{
+    collections::{HashMap, hash_map::DefaultHasher},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use tracing::{debug, info, instrument, warn};
+
+use crate::{
+    domain::{RelativePath, Target, validator::validate_template},
+    errors::CoreResult,
+    template::{
+        DirectorySpec, FileSpec, Store, SymlinkSpec, Template, TemplateBuilder, TemplateContent,
+        TemplateError, TemplateId, TemplateNode, TemplateSource, TemplateTree,
+        manifest::{MANIFEST_FILE, TemplateManifest},
+    },
+};
+
+/// Directory, next to the manifest, holding the files a template generates.
+pub const FILES_DIR: &str = "files";
+
+// ============================================================================
+// FsStore
+// ============================================================================
+
+/// Template store reading user templates from a directory tree.
+///
+/// Cloning is cheap: clones share the loaded templates, so an [`Engine`]
+/// built over one clone sees rescans made through another.
+///
+/// The store is read-only through [`Store`]: templates are added, changed
+/// and removed on disk, then picked up by a rescan.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// let store = FsStore::open(home.join(".scarff/templates"))?;
+/// let engine = Engine::with_store(Box::new(store.clone()));
+///
+/// // ... later, after editing a template
+/// store.rescan_if_changed()?;
+/// ```
+///
+/// [`Engine`]: crate::Engine
+#[derive(Clone)]
+pub struct FsStore {
+    root: PathBuf,
+    inner: Arc<RwLock<Loaded>>,
+}
+
+/// Outcome of a scan.
+#[derive(Debug, Clone, Default)]
+pub struct ScanReport {
+    /// Templates now in the store
+    pub loaded: Vec<TemplateId>,
+    /// Templates that failed to load, as `TemplateError::LoadFailed`
+    pub rejected: Vec<TemplateError>,
+}
+
+impl FsStore {
+    /// Create an empty store over `root`; nothing is read until a rescan.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            inner: Arc::new(RwLock::new(Loaded::default())),
+        }
+    }
+
+    /// Create a store over `root` and load its templates.
+    ///
+    /// Rejected templates are logged; use [`FsStore::rescan`] to get them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::LoadFailed` if `root` can't be read.
+    pub fn open(root: impl Into<PathBuf>) -> CoreResult<Self> {
+        let store = Self::new(root);
+        store.rescan()?;
+        Ok(store)
+    }
+
+    /// Root directory templates are read from.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Re-read every template under the root, replacing the loaded set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::LoadFailed` if the root can't be read; the
+    /// previously loaded templates are kept.
+    #[instrument(skip(self), fields(root = %self.root.display()))]
+    pub fn rescan(&self) -> CoreResult<ScanReport> {
+        let fingerprint = fingerprint(&self.root)?;
+        let (templates, report) = scan(&self.root)?;
+
+        for error in &report.rejected {
+            warn!(%error, "Skipping template");
+        }
+        info!(
+            loaded = report.loaded.len(),
+            rejected = report.rejected.len(),
+            "Templates scanned"
+        );
+
+        let mut loaded = self.inner.write().map_err(|_| TemplateError::LockError)?;
+        *loaded = Loaded {
+            templates,
+            fingerprint: Some(fingerprint),
+        };
+        Ok(report)
+    }
+
+    /// Rescan only if files under the root changed since the last scan.
+    ///
+    /// Cheap enough to call before every scaffold: it only reads metadata.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FsStore::rescan`].
+    pub fn rescan_if_changed(&self) -> CoreResult<Option<ScanReport>> {
+        let current = fingerprint(&self.root)?;
+        let last = self
+            .inner
+            .read()
+            .map_err(|_| TemplateError::LockError)?
+            .fingerprint;
+
+        if last == Some(current) {
+            debug!("Templates unchanged");
+            return Ok(None);
+        }
+        self.rescan().map(Some)
+    }
+
+    /// Number of loaded templates.
+    pub fn len(&self) -> usize {
+        self.inner.read().map_or(0, |loaded| loaded.templates.len())
+    }
+
+    /// Check if no template is loaded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read_only(&self) -> TemplateError {
+        TemplateError::ReadOnly(self.root.display().to_string())
+    }
+}
+
+impl Store for FsStore {
+    fn find(&self, target: &Target) -> CoreResult<Vec<Template>> {
+        let loaded = self.inner.read().map_err(|_| TemplateError::LockError)?;
+
+        Ok(loaded
+            .templates
+            .values()
+            .filter(|t| t.matcher.matches(target))
+            .cloned()
+            .collect())
+    }
+
+    fn get(&self, id: &TemplateId) -> CoreResult<Template> {
+        let loaded = self.inner.read().map_err(|_| TemplateError::LockError)?;
+
+        loaded
+            .templates
+            .get(id)
+            .cloned()
+            .ok_or_else(|| TemplateError::NotFound(id.clone()).into())
+    }
+
+    fn insert(&self, _template: Template) -> CoreResult<()> {
+        Err(self.read_only().into())
+    }
+
+    fn list(&self) -> CoreResult<Vec<Template>> {
+        let loaded = self.inner.read().map_err(|_| TemplateError::LockError)?;
+
+        Ok(loaded.templates.values().cloned().collect())
+    }
+
+    fn contains(&self, id: &TemplateId) -> bool {
+        self.inner
+            .read()
+            .is_ok_and(|loaded| loaded.templates.contains_key(id))
+    }
+
+    fn remove(&self, _id: &TemplateId) -> CoreResult<()> {
+        Err(self.read_only().into())
+    }
+}
+
+// ============================================================================
+// Internal State
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct Loaded {
+    templates: HashMap<TemplateId, Template>,
+    /// Fingerprint of the tree at the last scan; `None` before the first
+    fingerprint: Option<u64>,
+}
+
+// ============================================================================
+// Scanning
+// ============================================================================
+
+/// Load every template under `root`, keyed like `InMemoryStore` keys them.
+fn scan(root: &Path) -> CoreResult<(HashMap<TemplateId, Template>, ScanReport)> {
+    let mut dirs = Vec::new();
+    find_template_dirs(root, &mut dirs).map_err(|e| load_failed(root, e.to_string()))?;
+
+    let mut templates = HashMap::new();
+    let mut sources: HashMap<TemplateId, PathBuf> = HashMap::new();
+    let mut report = ScanReport::default();
+
+    for dir in dirs {
+        let template = match load_template(&dir) {
+            Ok(template) => template,
+            Err(reason) => {
+                report.rejected.push(load_failed(&dir, reason));
+                continue;
+            }
+        };
+
+        let id = TemplateId::new(
+            template.metadata.name.clone(),
+            template.metadata.version.clone(),
+        );
+        if let Some(first) = sources.get(&id) {
+            let reason = format!("{id} is already defined in {}", first.display());
+            report.rejected.push(load_failed(&dir, reason));
+            continue;
+        }
+
+        debug!(template_id = %id, dir = %dir.display(), "Loaded template");
+        sources.insert(id.clone(), dir);
+        report.loaded.push(id.clone());
+        templates.insert(id, template);
+    }
+
+    Ok((templates, report))
+}
+
+/// Collect directories holding a manifest, in path order.
+///
+/// A template directory isn't searched further, and symlinks are never
+/// followed.
+fn find_template_dirs(dir: &Path, found: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if dir.join(MANIFEST_FILE).is_file() {
+        found.push(dir.to_path_buf());
+        return Ok(());
+    }
+
+    for entry in sorted_entries(dir)? {
+        if entry.file_type()?.is_dir() {
+            find_template_dirs(&entry.path(), found)?;
+        }
+    }
+    Ok(())
+}
+
+fn load_template(dir: &Path) -> Result<Template, String> {
+    let template = read_template(dir)?;
+    validate_template(&template).map_err(|e| e.to_string())?;
+    Ok(template)
+}
+
+/// The template in `dir` as written, without validating it, so it can be
+/// linted.
+///
+/// # Errors
+///
+/// [`TemplateError::LoadFailed`] if the manifest or files can't be read.
+pub(crate) fn read_template_dir(dir: &Path) -> Result<Template, TemplateError> {
+    read_template(dir).map_err(|reason| load_failed(dir, reason))
+}
+
+fn read_template(dir: &Path) -> Result<Template, String> {
+    let raw = fs::read_to_string(dir.join(MANIFEST_FILE)).map_err(|e| e.to_string())?;
+    let manifest = TemplateManifest::from_toml(&raw).map_err(|e| e.to_string())?;
+    let mut builder = TemplateBuilder::from_manifest(manifest, dir).map_err(|e| e.to_string())?;
+
+    let files = dir.join(FILES_DIR);
+    if files.is_dir() {
+        let mut tree = TemplateTree::new();
+        read_tree(&files, Path::new(""), &mut tree)?;
+        for node in tree.nodes {
+            builder = builder.add_node(node);
+        }
+    }
+
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Add the contents of `dir` (at `prefix` in the project) to `tree`:
+/// directories before the files inside them.
+fn read_tree(dir: &Path, prefix: &Path, tree: &mut TemplateTree) -> Result<(), String> {
+    for entry in sorted_entries(dir).map_err(|e| e.to_string())? {
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        let name = entry.file_name();
+        let path = prefix.join(&name);
+        let relative = || {
+            RelativePath::try_new(&path)
+                .map_err(|path| format!("{} is not a valid path", path.display()))
+        };
+
+        if file_type.is_symlink() {
+            let target = fs::read_link(entry.path())
+                .map_err(|e| format!("{FILES_DIR}/{}: {e}", path.display()))?;
+            tree.push(TemplateNode::Symlink(SymlinkSpec::new(
+                relative()?,
+                target.to_string_lossy().into_owned(),
+            )));
+        } else if file_type.is_dir() {
+            tree.push(TemplateNode::Directory(DirectorySpec::new(relative()?)));
+            read_tree(&entry.path(), &path, tree)?;
+        } else {
+            let content = fs::read_to_string(entry.path())
+                .map_err(|e| format!("{FILES_DIR}/{}: {e}", path.display()))?;
+            let mut spec = FileSpec::new(
+                relative()?,
+                TemplateContent::Parameterized(TemplateSource::Owned(content)),
+            );
+            if is_executable(&entry.path()) {
+                spec = spec.executable();
+            }
+            tree.push(TemplateNode::File(spec));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+/// Hash of every path, size and modification time under `root`.
+fn fingerprint(root: &Path) -> CoreResult<u64> {
+    fn walk(dir: &Path, hasher: &mut DefaultHasher) -> std::io::Result<()> {
+        for entry in sorted_entries(dir)? {
+            let metadata = entry.metadata()?;
+            entry.path().hash(hasher);
+            metadata.len().hash(hasher);
+            metadata.modified().ok().hash(hasher);
+            if metadata.is_dir() {
+                walk(&entry.path(), hasher)?;
+            }
+        }
+        Ok(())
+    }
+
+    let mut hasher = DefaultHasher::new();
+    walk(root, &mut hasher).map_err(|e| load_failed(root, e.to_string()))?;
+    Ok(hasher.finish())
+}
+
+fn sorted_entries(dir: &Path) -> std::io::Result<Vec<fs::DirEntry>> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(fs::DirEntry::file_name);
+    Ok(entries)
+}
+
+fn load_failed(path: &Path, reason: impl Into<String>) -> TemplateError {
+    TemplateError::LoadFailed {
+        path: path.display().to_string(),
+        reason: reason.into(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CoreError,
+        domain::{Architecture, Language, ProjectKind},
+    };
+
+    const MANIFEST: &str = r#"
+        name = "rust-cli-clap"
+        version = "1.0.0"
+        tags = ["rust", "cli"]
+
+        [matcher]
+        language = "rust"
+        type = "cli"
+    "#;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn add_template(root: &Path, dir: &str, manifest: &str) {
+        write(&root.join(dir).join(MANIFEST_FILE), manifest);
+        write(
+            &root.join(dir).join("files/src/main.rs"),
+            "fn main() { println!(\"{{PROJECT_NAME}}\"); }\n",
+        );
+    }
+
+    fn rust_cli_target() -> Target {
+        Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .architecture(Architecture::Layered)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn loads_manifest_and_files() {
+        let root = tempfile::tempdir().unwrap();
+        add_template(root.path(), "clap", MANIFEST);
+
+        let store = FsStore::open(root.path()).unwrap();
+        let template = store
+            .get(&TemplateId::new("rust-cli-clap", "1.0.0".to_string()))
+            .unwrap();
+
+        assert_eq!(template.metadata.tags, ["rust", "cli"]);
+        assert!(template.tree.has_file("src/main.rs"));
+        assert!(matches!(
+            &template.tree.nodes[0],
+            TemplateNode::Directory(dir) if dir.path.as_path() == Path::new("src")
+        ));
+        assert_eq!(store.find(&rust_cli_target()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn broken_template_is_rejected_alone() {
+        let root = tempfile::tempdir().unwrap();
+        add_template(root.path(), "good", MANIFEST);
+        add_template(
+            root.path(),
+            "nested/bad",
+            "name = \"bad\"\nversion = \"1.0.0\"\n[matcher]\nlanguage = \"cobol\"\n",
+        );
+
+        let store = FsStore::new(root.path());
+        let report = store.rescan().unwrap();
+
+        assert_eq!(report.loaded.len(), 1);
+        assert_eq!(report.rejected.len(), 1);
+        let TemplateError::LoadFailed { path, reason } = &report.rejected[0] else {
+            panic!("expected LoadFailed, got {:?}", report.rejected[0]);
+        };
+        assert!(path.ends_with("bad"), "{path}");
+        assert!(reason.contains("cobol"), "{reason}");
+    }
+
+    #[test]
+    fn duplicate_ids_keep_the_first_in_path_order() {
+        let root = tempfile::tempdir().unwrap();
+        add_template(root.path(), "a", MANIFEST);
+        add_template(root.path(), "b", MANIFEST);
+
+        let report = FsStore::new(root.path()).rescan().unwrap();
+
+        assert_eq!(report.loaded.len(), 1);
+        assert!(report.rejected[0].to_string().contains("already defined"));
+    }
+
+    #[test]
+    fn rescan_if_changed_picks_up_new_templates_only_when_needed() {
+        let root = tempfile::tempdir().unwrap();
+        add_template(root.path(), "clap", MANIFEST);
+        let store = FsStore::open(root.path()).unwrap();
+
+        assert!(store.rescan_if_changed().unwrap().is_none());
+
+        add_template(
+            root.path(),
+            "other",
+            "name = \"other\"\nversion = \"0.1.0\"\n",
+        );
+        let report = store.rescan_if_changed().unwrap().expect("tree changed");
+
+        assert_eq!(report.loaded.len(), 2);
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn missing_root_keeps_loaded_templates() {
+        let root = tempfile::tempdir().unwrap();
+        add_template(root.path(), "clap", MANIFEST);
+        let store = FsStore::open(root.path()).unwrap();
+
+        fs::remove_dir_all(root.path()).unwrap();
+
+        let err = store.rescan().unwrap_err();
+        assert!(matches!(
+            err,
+            CoreError::Template(TemplateError::LoadFailed { .. })
+        ));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn manifest_only_template_needs_no_files_dir() {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            &root.path().join("inline").join(MANIFEST_FILE),
+            "name = \"inline\"\nversion = \"1.0.0\"\n\n\
+             [[file]]\npath = \"README.md\"\nsource = \"readme.md\"\n",
+        );
+        write(
+            &root.path().join("inline/readme.md"),
+            "# {{PROJECT_NAME}}\n",
+        );
+
+        let store = FsStore::open(root.path()).unwrap();
+        let template = store
+            .get(&TemplateId::new("inline", "1.0.0".to_string()))
+            .unwrap();
+
+        assert!(template.tree.has_file("README.md"));
+    }
+
+    #[test]
+    fn store_is_read_only() {
+        let root = tempfile::tempdir().unwrap();
+        add_template(root.path(), "clap", MANIFEST);
+        let store = FsStore::open(root.path()).unwrap();
+        let id = TemplateId::new("rust-cli-clap", "1.0.0".to_string());
+
+        let err = store.remove(&id).unwrap_err();
+        assert_eq!(err.code(), "template.read_only");
+        assert!(store.contains(&id));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn executable_files_stay_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = tempfile::tempdir().unwrap();
+        add_template(root.path(), "clap", MANIFEST);
+        let script = root.path().join("clap/files/run.sh");
+        write(&script, "#!/bin/sh\n");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let template = FsStore::open(root.path())
+            .unwrap()
+            .list()
+            .unwrap()
+            .remove(0);
+        let run = template
+            .tree
+            .nodes
+            .iter()
+            .find_map(|node| match node {
+                TemplateNode::File(spec) if spec.path.as_path() == Path::new("run.sh") => {
+                    Some(spec)
+                }
+                _ => None,
+            })
+            .unwrap();
+
+        assert!(run.permissions.executable_flag());
+    }
+}
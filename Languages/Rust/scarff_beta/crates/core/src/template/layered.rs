@@ -0,0 +1,204 @@
+//! Several template stores searched as one.
+//!
+//! An engine reads built-in templates, a user's template directory and any
+//! number of shared (remote) template trees. [`LayeredStore`] stacks those
+//! stores in priority order: a template id found in an earlier layer hides
+//! the same id in later ones, so a user can override a built-in template by
+//! publishing one with the same name and version.
+//!
+//! Each layer has a name (`builtin`, a directory, a remote source spec),
+//! reported by [`Store::origin`] so search results can say where a template
+//! comes from.
+
+use std::collections::HashSet;
+
+use crate::{
+    domain::Target,
+    errors::CoreResult,
+    template::{Store, Template, TemplateError, TemplateId},
+};
+
+/// Named stores, searched in order.
+#[derive(Default)]
+pub(crate) struct LayeredStore {
+    layers: Vec<(String, Box<dyn Store>)>,
+}
+
+impl LayeredStore {
+    /// A store with no layers.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `store` below the existing layers (builder style).
+    #[must_use]
+    pub(crate) fn with_layer(mut self, name: impl Into<String>, store: Box<dyn Store>) -> Self {
+        self.layers.push((name.into(), store));
+        self
+    }
+
+    /// Templates from every layer, each id only from the first layer that
+    /// has it.
+    fn merge(
+        &self,
+        mut templates_of: impl FnMut(&dyn Store) -> CoreResult<Vec<Template>>,
+    ) -> CoreResult<Vec<Template>> {
+        let mut seen = HashSet::new();
+        let mut merged = Vec::new();
+        for (_, store) in &self.layers {
+            for template in templates_of(store.as_ref())? {
+                if seen.insert(template_id(&template)) {
+                    merged.push(template);
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    fn layer_of(&self, id: &TemplateId) -> Option<&(String, Box<dyn Store>)> {
+        self.layers.iter().find(|(_, store)| store.contains(id))
+    }
+}
+
+fn template_id(template: &Template) -> TemplateId {
+    TemplateId::new(
+        template.metadata.name.clone(),
+        template.metadata.version.clone(),
+    )
+}
+
+impl Store for LayeredStore {
+    fn find(&self, target: &Target) -> CoreResult<Vec<Template>> {
+        self.merge(|store| store.find(target))
+    }
+
+    fn get(&self, id: &TemplateId) -> CoreResult<Template> {
+        match self.layer_of(id) {
+            Some((_, store)) => store.get(id),
+            None => Err(TemplateError::NotFound(id.clone()).into()),
+        }
+    }
+
+    /// Inserts into the first layer.
+    fn insert(&self, template: Template) -> CoreResult<()> {
+        match self.layers.first() {
+            Some((_, store)) => store.insert(template),
+            None => Err(TemplateError::InvalidTemplate(
+                "cannot insert into a layered store without layers".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    fn list(&self) -> CoreResult<Vec<Template>> {
+        self.merge(|store| store.list())
+    }
+
+    fn contains(&self, id: &TemplateId) -> bool {
+        self.layer_of(id).is_some()
+    }
+
+    /// Removes from the layer the template is served from.
+    fn remove(&self, id: &TemplateId) -> CoreResult<()> {
+        match self.layer_of(id) {
+            Some((_, store)) => store.remove(id),
+            None => Err(TemplateError::NotFound(id.clone()).into()),
+        }
+    }
+
+    fn origin(&self, id: &TemplateId) -> Option<String> {
+        self.layer_of(id).map(|(name, _)| name.clone())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{
+            Architecture, FileSpec, Language, ProjectKind, TargetMatcher, TemplateContent,
+            TemplateMetadata, TemplateNode, TemplateSource, TemplateTree,
+        },
+        template::InMemoryStore,
+    };
+
+    fn template(name: &str, content: &'static str) -> Template {
+        Template {
+            id: TemplateId::new(name, "1.0.0".to_string()),
+            matcher: TargetMatcher::builder().language(Language::Rust).build(),
+            metadata: TemplateMetadata::new(name).version("1.0.0"),
+            tree: TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
+                "src/main.rs",
+                TemplateContent::Literal(TemplateSource::Static(content)),
+            ))),
+            hooks: Vec::new(),
+        }
+    }
+
+    fn store(templates: Vec<Template>) -> Box<dyn Store> {
+        Box::new(InMemoryStore::with_templates(templates).unwrap())
+    }
+
+    fn content(template: &Template) -> &str {
+        match &template.tree.nodes[0] {
+            TemplateNode::File(file) => match &file.content {
+                TemplateContent::Literal(source) => source.as_str(),
+                _ => unreachable!(),
+            },
+            TemplateNode::Directory(_) | TemplateNode::Symlink(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn earlier_layers_hide_the_same_template_in_later_ones() {
+        let layered = LayeredStore::new()
+            .with_layer("user", store(vec![template("rust-cli", "// mine")]))
+            .with_layer(
+                "builtin",
+                store(vec![
+                    template("rust-cli", "// stock"),
+                    template("rust-lib", ""),
+                ]),
+            );
+        let id = TemplateId::new("rust-cli", "1.0.0".to_string());
+
+        assert_eq!(layered.list().unwrap().len(), 2);
+        assert_eq!(content(&layered.get(&id).unwrap()), "// mine");
+        assert_eq!(layered.origin(&id).as_deref(), Some("user"));
+        assert_eq!(
+            layered
+                .origin(&TemplateId::new("rust-lib", "1.0.0".to_string()))
+                .as_deref(),
+            Some("builtin")
+        );
+
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .architecture(Architecture::Layered)
+            .unwrap()
+            .build()
+            .unwrap();
+        let found = layered.find(&target).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().any(|t| content(t) == "// mine"));
+    }
+
+    #[test]
+    fn missing_templates_are_not_found_in_any_layer() {
+        let layered = LayeredStore::new().with_layer("user", store(vec![]));
+        let id = TemplateId::new("nope", "1.0.0".to_string());
+
+        assert!(!layered.contains(&id));
+        assert!(layered.origin(&id).is_none());
+        assert_eq!(
+            layered.remove(&id).unwrap_err().code(),
+            "template.not_found"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! LICENSE files.
+//!
+//! Templates don't ship a license: the `LICENSE` file is generated for the
+//! target's [`License`], with the copyright line filled in from `YEAR` and
+//! `AUTHOR` (`<project> contributors` when no author is given). The
+//! Unlicense dedicates the project to the public domain and names no one.
+//!
+//! Templates declare the license in their manifest with `LICENSE`, the
+//! SPDX id (`none` without a license, false in `{{#if}}`):
+//!
+//! ```text
+//! {{#if LICENSE}}
+//! license = "{{LICENSE}}"
+//! {{/if}}
+//! ```
+
+use crate::domain::{
+    FileSpec, License, RenderContext, Target, Template, TemplateContent, TemplateNode,
+    TemplateSource,
+};
+use crate::template::backend::DEFAULT_ENGINE;
+
+/// Path of the generated license file.
+const LICENSE_PATH: &str = "LICENSE";
+
+/// Full text of `license`, in the built-in syntax.
+fn text(license: License) -> &'static str {
+    match license {
+        License::Mit => include_str!("templates/licenses/MIT.template"),
+        License::Apache2 => include_str!("templates/licenses/Apache-2.0.template"),
+        License::Gpl3 => include_str!("templates/licenses/GPL-3.0.template"),
+        License::Mpl2 => include_str!("templates/licenses/MPL-2.0.template"),
+        License::Unlicense => include_str!("templates/licenses/Unlicense.template"),
+    }
+}
+
+/// Add the LICENSE file for the target's license to `template`, and an
+/// `AUTHOR` for its copyright line to `ctx` when none is set.
+///
+/// A LICENSE the template already provides is kept as it is. Targets
+/// without a license are returned unchanged.
+pub(crate) fn with_license(
+    mut template: Template,
+    mut ctx: RenderContext,
+    target: &Target,
+) -> (Template, RenderContext) {
+    let Some(license) = target.license() else {
+        return (template, ctx);
+    };
+
+    if !template.tree.has_file(LICENSE_PATH) {
+        // Written in the built-in syntax, whatever the template's engine
+        template.tree.push(TemplateNode::File(
+            FileSpec::new(
+                LICENSE_PATH,
+                TemplateContent::Parameterized(TemplateSource::Static(text(license))),
+            )
+            .engine(DEFAULT_ENGINE),
+        ));
+    }
+
+    if ctx.get("AUTHOR").is_none_or(str::is_empty) {
+        let author = format!(
+            "{} contributors",
+            ctx.get("PROJECT_NAME").unwrap_or_default()
+        );
+        ctx.set_var("AUTHOR", author);
+    }
+    (template, ctx)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Language, TargetMatcher, TemplateId, TemplateMetadata, TemplateTree};
+
+    fn template() -> Template {
+        Template {
+            id: TemplateId::new("rust", "0.1.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("rust"),
+            tree: TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
+                "src/main.rs",
+                TemplateContent::Literal(TemplateSource::Static("")),
+            ))),
+            hooks: Vec::new(),
+        }
+    }
+
+    fn rust(license: Option<License>) -> Target {
+        let builder = Target::builder().language(Language::Rust);
+        match license {
+            Some(license) => builder.license(license),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn adds_the_license_text_and_a_default_author() {
+        let (template, ctx) = with_license(
+            template(),
+            RenderContext::new("demo"),
+            &rust(Some(License::Apache2)),
+        );
+
+        assert!(template.tree.has_file("LICENSE"));
+        assert!(text(License::Apache2).contains("Copyright {{YEAR}} {{AUTHOR}}"));
+        assert_eq!(ctx.get("AUTHOR"), Some("demo contributors"));
+    }
+
+    #[test]
+    fn keeps_the_author_and_a_template_license() {
+        let mut own = template();
+        own.tree.push(TemplateNode::File(FileSpec::new(
+            "LICENSE",
+            TemplateContent::Literal(TemplateSource::Static("custom")),
+        )));
+        let files = own.tree.nodes.len();
+
+        let (template, ctx) = with_license(
+            own,
+            RenderContext::new("demo").with_var("AUTHOR", "Ada"),
+            &rust(Some(License::Mit)),
+        );
+
+        assert_eq!(template.tree.nodes.len(), files);
+        assert_eq!(ctx.get("AUTHOR"), Some("Ada"));
+    }
+
+    #[test]
+    fn no_license_leaves_the_template_alone() {
+        let (template, ctx) = with_license(template(), RenderContext::new("demo"), &rust(None));
+
+        assert!(!template.tree.has_file("LICENSE"));
+        assert!(!ctx.has("AUTHOR"));
+    }
+}
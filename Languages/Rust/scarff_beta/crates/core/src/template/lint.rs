@@ -0,0 +1,516 @@
+//! Template linting: mistakes a custom template author can catch before use.
+//!
+//! [`Engine::validate_template`](crate::Engine::validate_template) reports
+//! every problem it finds instead of stopping at the first, as rendering
+//! does:
+//!
+//! - two nodes at the same path
+//! - a matcher no valid target satisfies, or a node gated by a feature no
+//!   target can enable
+//! - syntax errors in content using the built-in engine
+//! - variables the engine never defines. Only a warning: the caller can
+//!   still pass them as scaffold variables
+//! - external content with an empty or unknown partial id, and includes
+//!   that loop
+
+use std::{collections::HashMap, fmt, path::PathBuf};
+
+use crate::{
+    domain::{RenderContext, Target, check_feature},
+    template::{
+        Template, TemplateContent, TemplateError, TemplateNode,
+        backend::DEFAULT_ENGINE,
+        partials::{self, ExpandedCache, Partials},
+        syntax,
+    },
+};
+
+// ============================================================================
+// Diagnostics
+// ============================================================================
+
+/// How serious a [`TemplateDiagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The template may still render, depending on the caller's variables
+    Warning,
+    /// Rendering fails, or part of the template can never be used
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// What a [`TemplateDiagnostic`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// More than one node at the same path
+    DuplicatePath,
+    /// No valid target matches the template
+    UnreachableMatcher,
+    /// A node's feature is one no target can enable
+    UnreachableNode,
+    /// Content reads a variable the engine doesn't define
+    UndefinedVariable,
+    /// Content doesn't parse
+    Syntax,
+    /// External content with an empty partial id
+    EmptyContentId,
+    /// Content names a partial that isn't registered
+    UnknownPartial,
+    /// Partials include each other in a loop
+    IncludeCycle,
+}
+
+impl DiagnosticKind {
+    /// Stable, machine-readable code, like `template.duplicate_path`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::DuplicatePath => "template.duplicate_path",
+            Self::UnreachableMatcher => "template.unreachable_matcher",
+            Self::UnreachableNode => "template.unreachable_node",
+            Self::UndefinedVariable => "template.undefined_variable",
+            Self::Syntax => "template.syntax",
+            Self::EmptyContentId => "template.empty_content_id",
+            Self::UnknownPartial => "template.unknown_partial",
+            Self::IncludeCycle => "template.include_cycle",
+        }
+    }
+
+    /// How serious a diagnostic of this kind is.
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::UndefinedVariable => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// A problem found in a template, with the node and line it's on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateDiagnostic {
+    /// What's wrong
+    pub kind: DiagnosticKind,
+    /// Node the problem is in; `None` for the template as a whole
+    pub path: Option<PathBuf>,
+    /// 1-based line in the node's (expanded) content
+    pub line: Option<usize>,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+impl TemplateDiagnostic {
+    fn new(kind: DiagnosticKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: None,
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    fn at(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    fn on_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// How serious the problem is.
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+
+    /// Whether the template shouldn't be used until this is fixed.
+    pub fn is_error(&self) -> bool {
+        self.severity() == Severity::Error
+    }
+
+    /// Stable, machine-readable code (see [`DiagnosticKind::code`]).
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+}
+
+impl fmt::Display for TemplateDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.severity())?;
+        if let Some(path) = &self.path {
+            write!(f, "{}", path.display())?;
+            if let Some(line) = self.line {
+                write!(f, ":{line}")?;
+            }
+            write!(f, ": ")?;
+        }
+        write!(f, "{} [{}]", self.message, self.code())
+    }
+}
+
+// ============================================================================
+// Linting
+// ============================================================================
+
+/// Check `template` against the `partials` it can include.
+pub(crate) fn lint(template: &Template, partials: &Partials) -> Vec<TemplateDiagnostic> {
+    let mut diagnostics = Vec::new();
+    lint_matcher(template, &mut diagnostics);
+    lint_paths(template, &mut diagnostics);
+
+    // Everything the engine defines for any target: the values differ,
+    // the names don't
+    let probe = RenderContext::new("project")
+        .with_target(&Target::rust_cli().expect("built-in target is valid"))
+        .with_template(&template.metadata);
+    let mut cache = ExpandedCache::new();
+    let engine = template
+        .metadata
+        .engine
+        .as_deref()
+        .unwrap_or(DEFAULT_ENGINE);
+
+    for node in &template.tree.nodes {
+        if let Some(feature) = node.feature()
+            && let Err(e) = check_feature(feature)
+        {
+            diagnostics.push(
+                TemplateDiagnostic::new(
+                    DiagnosticKind::UnreachableNode,
+                    format!(
+                        "generated only with feature `{feature}`, which no target can enable: {e}"
+                    ),
+                )
+                .at(node.path().as_path()),
+            );
+        }
+
+        match node {
+            TemplateNode::File(spec) => {
+                let path = spec.path.as_path();
+                let source = match &spec.content {
+                    TemplateContent::Literal(_) => continue,
+                    TemplateContent::Parameterized(source) => {
+                        partials::expand_includes(source.as_str(), partials, &mut cache)
+                    }
+                    TemplateContent::External(id) if id.0.trim().is_empty() => {
+                        diagnostics.push(
+                            TemplateDiagnostic::new(
+                                DiagnosticKind::EmptyContentId,
+                                "external content has an empty partial id",
+                            )
+                            .at(path),
+                        );
+                        continue;
+                    }
+                    TemplateContent::External(id) => {
+                        partials::resolve(id.0, partials, &mut cache).map(|p| p.to_string())
+                    }
+                };
+
+                match source {
+                    Ok(source) if spec.engine.as_deref().unwrap_or(engine) == DEFAULT_ENGINE => {
+                        lint_source(&source, &probe, path, &mut diagnostics);
+                    }
+                    // Other engines have their own syntax
+                    Ok(_) => {}
+                    Err(e) => diagnostics.push(partial_diagnostic(&e).at(path)),
+                }
+            }
+            TemplateNode::Symlink(spec) if engine == DEFAULT_ENGINE => {
+                lint_source(&spec.target, &probe, spec.path.as_path(), &mut diagnostics);
+            }
+            TemplateNode::Symlink(_) | TemplateNode::Directory(_) => {}
+        }
+    }
+
+    diagnostics
+}
+
+fn lint_matcher(template: &Template, diagnostics: &mut Vec<TemplateDiagnostic>) {
+    let matcher = &template.matcher;
+    for feature in &matcher.features {
+        if let Err(e) = check_feature(feature) {
+            diagnostics.push(TemplateDiagnostic::new(
+                DiagnosticKind::UnreachableMatcher,
+                format!("matcher requires feature `{feature}`, which no target can enable: {e}"),
+            ));
+        }
+    }
+
+    if !matcher.is_satisfiable() {
+        let wanted: Vec<String> = [
+            matcher.language.map(|l| format!("language {l}")),
+            matcher.kind.map(|k| format!("kind {k}")),
+            matcher.framework.map(|f| format!("framework {f}")),
+            matcher.architecture.map(|a| format!("architecture {a}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        diagnostics.push(TemplateDiagnostic::new(
+            DiagnosticKind::UnreachableMatcher,
+            format!("no valid target has {}", wanted.join(", ")),
+        ));
+    }
+}
+
+fn lint_paths(template: &Template, diagnostics: &mut Vec<TemplateDiagnostic>) {
+    let mut seen: HashMap<&std::path::Path, usize> = HashMap::new();
+    for node in &template.tree.nodes {
+        let count = seen.entry(node.path().as_path()).or_default();
+        *count += 1;
+        // Once per path, however often it repeats
+        if *count == 2 {
+            diagnostics.push(
+                TemplateDiagnostic::new(
+                    DiagnosticKind::DuplicatePath,
+                    "more than one node at this path",
+                )
+                .at(node.path().as_path()),
+            );
+        }
+    }
+}
+
+fn lint_source(
+    source: &str,
+    probe: &RenderContext,
+    path: &std::path::Path,
+    diagnostics: &mut Vec<TemplateDiagnostic>,
+) {
+    let uses = match syntax::variables(source) {
+        Ok(uses) => uses,
+        Err(e) => {
+            diagnostics.push(
+                TemplateDiagnostic::new(DiagnosticKind::Syntax, e.reason)
+                    .at(path)
+                    .on_line(e.line),
+            );
+            return;
+        }
+    };
+
+    for read in uses {
+        let upper = read.name.to_ascii_uppercase();
+        let defined = [read.name.as_str(), upper.as_str()]
+            .into_iter()
+            .any(|key| probe.get(key).is_some() || probe.list(key).is_some());
+        if !defined {
+            diagnostics.push(
+                TemplateDiagnostic::new(
+                    DiagnosticKind::UndefinedVariable,
+                    format!(
+                        "`{}` isn't defined by the engine; scaffolds must pass it as a variable",
+                        read.name
+                    ),
+                )
+                .at(path)
+                .on_line(read.line),
+            );
+        }
+    }
+}
+
+fn partial_diagnostic(e: &TemplateError) -> TemplateDiagnostic {
+    match e {
+        TemplateError::UnknownPartial(id) if id.is_empty() => TemplateDiagnostic::new(
+            DiagnosticKind::EmptyContentId,
+            "includes a partial with an empty id",
+        ),
+        TemplateError::UnknownPartial(id) => TemplateDiagnostic::new(
+            DiagnosticKind::UnknownPartial,
+            format!("unknown partial `{id}`"),
+        ),
+        TemplateError::IncludeCycle(_) => {
+            TemplateDiagnostic::new(DiagnosticKind::IncludeCycle, e.to_string())
+        }
+        other => TemplateDiagnostic::new(DiagnosticKind::Syntax, other.to_string()),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        ContentTemplateId, FileSpec, Framework, Language, ProjectKind, PythonFramework,
+        SymlinkSpec, TargetMatcher, TemplateId, TemplateMetadata, TemplateNode, TemplateTree,
+    };
+    use crate::template::built_in_templates::all_templates;
+
+    fn template(matcher: TargetMatcher, nodes: Vec<TemplateNode>) -> Template {
+        let mut tree = TemplateTree::new();
+        for node in nodes {
+            tree.push(node);
+        }
+        Template {
+            id: TemplateId::new("lint", "1.0.0".to_string()),
+            matcher,
+            metadata: TemplateMetadata::new("lint"),
+            tree,
+            hooks: Vec::new(),
+        }
+    }
+
+    fn rust() -> TargetMatcher {
+        TargetMatcher::builder().language(Language::Rust).build()
+    }
+
+    fn file(path: &str, source: &'static str) -> TemplateNode {
+        TemplateNode::File(FileSpec::new(
+            path,
+            TemplateContent::Parameterized(source.into()),
+        ))
+    }
+
+    fn kinds(diagnostics: &[TemplateDiagnostic]) -> Vec<DiagnosticKind> {
+        diagnostics.iter().map(|d| d.kind).collect()
+    }
+
+    #[test]
+    fn a_clean_template_has_no_diagnostics() {
+        let clean = template(
+            rust(),
+            vec![
+                file("README.md", "# {{ project_name }} ({{ LANGUAGE }})"),
+                file(
+                    "FEATURES",
+                    "{{#each features as f}}{{ f }}{{/each}}{{#if AUTHOR}}{{/if}}",
+                ),
+                TemplateNode::Symlink(SymlinkSpec::new("latest", "{{ PROJECT_NAME }}")),
+            ],
+        );
+
+        assert_eq!(lint(&clean, &Partials::builtin()), []);
+    }
+
+    #[test]
+    fn reports_every_duplicate_path_once() {
+        let dup = template(
+            rust(),
+            vec![
+                file("a.txt", ""),
+                file("a.txt", ""),
+                file("a.txt", ""),
+                file("b.txt", ""),
+            ],
+        );
+
+        let diagnostics = lint(&dup, &Partials::builtin());
+        assert_eq!(kinds(&diagnostics), [DiagnosticKind::DuplicatePath]);
+        assert_eq!(diagnostics[0].path, Some(PathBuf::from("a.txt")));
+        assert!(diagnostics[0].is_error());
+    }
+
+    #[test]
+    fn flags_matchers_and_nodes_no_target_reaches() {
+        let mismatched = TargetMatcher::builder()
+            .language(Language::Rust)
+            .framework(Framework::Python(PythonFramework::FastApi))
+            .build();
+        let unreachable = template(mismatched, Vec::new());
+        let diagnostics = lint(&unreachable, &Partials::builtin());
+        assert_eq!(kinds(&diagnostics), [DiagnosticKind::UnreachableMatcher]);
+        assert!(diagnostics[0].message.contains("language rust"));
+
+        let mut bad_feature = rust();
+        bad_feature.features.push("Not A Feature".to_string());
+        let gated =
+            FileSpec::new("gated.txt", TemplateContent::Literal("".into())).feature("-gated");
+        let diagnostics = lint(
+            &template(bad_feature, vec![TemplateNode::File(gated)]),
+            &Partials::builtin(),
+        );
+        assert_eq!(
+            kinds(&diagnostics),
+            [
+                DiagnosticKind::UnreachableMatcher,
+                DiagnosticKind::UnreachableNode
+            ]
+        );
+
+        // Python has no CLI kind
+        let cli = TargetMatcher::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::Cli)
+            .build();
+        assert_eq!(
+            kinds(&lint(&template(cli, Vec::new()), &Partials::builtin())),
+            [DiagnosticKind::UnreachableMatcher]
+        );
+    }
+
+    #[test]
+    fn built_in_templates_have_no_errors() {
+        for template in all_templates() {
+            let errors: Vec<_> = lint(&template, &Partials::builtin())
+                .into_iter()
+                .filter(TemplateDiagnostic::is_error)
+                .collect();
+            assert_eq!(errors, [], "{}", template.metadata.name);
+        }
+    }
+
+    #[test]
+    fn warns_about_undefined_variables_with_their_line() {
+        let typo = template(
+            rust(),
+            vec![file(
+                "main.rs",
+                "// {{ project_name }}\n{{ projet_name }}\n",
+            )],
+        );
+
+        let diagnostics = lint(&typo, &Partials::builtin());
+        assert_eq!(kinds(&diagnostics), [DiagnosticKind::UndefinedVariable]);
+        assert_eq!(diagnostics[0].severity(), Severity::Warning);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "warning: main.rs:2: `projet_name` isn't defined by the engine; \
+             scaffolds must pass it as a variable [template.undefined_variable]"
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors_and_bad_partials() {
+        let broken = template(
+            rust(),
+            vec![
+                file("open.txt", "{{#if x}}\nnever closed"),
+                file("include.txt", "{{> no-such-partial}}"),
+                TemplateNode::File(FileSpec::new(
+                    "external.txt",
+                    TemplateContent::External(ContentTemplateId("")),
+                )),
+                // Not checked: another engine's syntax
+                TemplateNode::File(
+                    FileSpec::new("other.txt", TemplateContent::Parameterized("{{#".into()))
+                        .engine("other"),
+                ),
+            ],
+        );
+
+        let diagnostics = lint(&broken, &Partials::builtin());
+        assert_eq!(
+            kinds(&diagnostics),
+            [
+                DiagnosticKind::Syntax,
+                DiagnosticKind::UnknownPartial,
+                DiagnosticKind::EmptyContentId
+            ]
+        );
+        assert_eq!(diagnostics[0].line, Some(1));
+        assert_eq!(diagnostics[2].code(), "template.empty_content_id");
+    }
+}
@@ -0,0 +1,890 @@
+//! Declarative template manifests (`template.toml`).
+//!
+//! A manifest describes a whole template: its metadata, when it applies,
+//! and the directories and files it generates. File content is either
+//! inline or read from a content file next to the manifest:
+//!
+//! ```toml
+//! name = "rust-cli-clap"
+//! version = "1.0.0"
+//! description = "Rust CLI with clap"    # optional
+//! author = "ACME"                       # optional
+//! tags = ["rust", "cli"]                # optional
+//! engine = "simple"                     # optional, a registered template engine
+//!
+//! [matcher]                             # optional, every key a wildcard when unset
+//! language = "rust"
+//! type = "cli"
+//! features = []                         # optional, features the target must enable
+//!
+//! [[directory]]
+//! path = "src"
+//!
+//! [[file]]
+//! path = "Dockerfile"
+//! source = "content/Dockerfile"
+//! feature = "docker"                    # optional, only for targets with the feature
+//!
+//! [[file]]
+//! path = "src/main.rs"
+//! source = "content/main.rs"            # relative to the manifest
+//!
+//! [[file]]
+//! path = "run.sh"
+//! content = "#!/bin/sh\ncargo run\n"    # inline
+//! executable = true                     # optional
+//! read_only = true                      # optional
+//! literal = true                        # optional, skip {{VARIABLE}} substitution
+//! engine = "simple"                     # optional, instead of the template's engine
+//!
+//! [[symlink]]
+//! path = "bin/run"
+//! target = "../run.sh"                  # relative to the link, inside the project
+//! feature = "scripts"                   # optional
+//!
+//! [[hook]]                              # optional, run in order after writing
+//! type = "command"                      # or "script", "make-executable", "next-steps"
+//! program = "cargo"
+//! args = ["fmt"]
+//!
+//! [[hook]]
+//! type = "next-steps"
+//! steps = ["cd {{PROJECT_NAME}}", "cargo run"]
+//! ```
+//!
+//! [`TemplateManifest::from_toml`] checks the schema and reports problems
+//! as `TemplateError::InvalidManifest`, naming the offending key (e.g.
+//! `file[2].source`) or line. [`TemplateBuilder::from_manifest`] then
+//! reads the content files and builds the template.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::{
+        self, Architecture, BuiltinHook, DomainError, Framework, Hook, Language, Permissions,
+        ProjectKind, RelativePath, SymlinkSpec, SymlinkToCreate,
+    },
+    template::{
+        DirectorySpec, FileSpec, TargetMatcher, Template, TemplateBuilder, TemplateContent,
+        TemplateError, TemplateId, TemplateMetadata, TemplateNode, TemplateSource,
+    },
+};
+
+/// Conventional manifest file name.
+pub const MANIFEST_FILE: &str = "template.toml";
+
+// ============================================================================
+// TemplateManifest
+// ============================================================================
+
+/// Parsed and schema-checked `template.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateManifest {
+    /// Template name, unique together with the version
+    pub name: String,
+    /// Template version
+    pub version: String,
+    /// One-line summary
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Author; "Scarff" when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Tags for discovery
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Template engine the content is written for; the built-in one when
+    /// unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+    /// Targets the template applies to
+    #[serde(default, skip_serializing_if = "MatcherManifest::is_wildcard")]
+    pub matcher: MatcherManifest,
+    /// `[[directory]]` entries
+    #[serde(default, rename = "directory", skip_serializing_if = "Vec::is_empty")]
+    pub directories: Vec<DirectoryManifest>,
+    /// `[[file]]` entries
+    #[serde(default, rename = "file", skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<FileManifest>,
+    /// `[[symlink]]` entries
+    #[serde(default, rename = "symlink", skip_serializing_if = "Vec::is_empty")]
+    pub symlinks: Vec<SymlinkManifest>,
+    /// `[[hook]]` entries
+    #[serde(default, rename = "hook", skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookManifest>,
+}
+
+/// `[matcher]`: unset keys are wildcards.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatcherManifest {
+    /// Required language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    /// Required project type (`type` in the file)
+    #[serde(default, alias = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ProjectKind>,
+    /// Required framework
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework: Option<Framework>,
+    /// Required architecture
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<Architecture>,
+    /// Required features
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+}
+
+/// `[[directory]]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DirectoryManifest {
+    /// Path in the generated project
+    pub path: PathBuf,
+    /// Only generated for targets with this feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+}
+
+/// `[[file]]`: exactly one of `content` and `source`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileManifest {
+    /// Path in the generated project
+    pub path: PathBuf,
+    /// Inline content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Content file, relative to the manifest's directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<PathBuf>,
+    /// Written as-is, without variable substitution
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub literal: bool,
+    /// Generated with the executable bit set
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub executable: bool,
+    /// Generated without write permission
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub read_only: bool,
+    /// Only generated for targets with this feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+    /// Template engine for this file instead of the template's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+}
+
+/// `[[symlink]]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SymlinkManifest {
+    /// Path of the link in the generated project
+    pub path: PathBuf,
+    /// Where it points, relative to the link's directory
+    pub target: PathBuf,
+    /// Only generated for targets with this feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+}
+
+/// `[[hook]]`: a post-scaffold step, chosen by `type`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum HookManifest {
+    /// Run a program in the project root
+    Command {
+        /// Program to run
+        program: String,
+        /// Its arguments
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+    },
+    /// Run a script with the platform shell in the project root
+    Script {
+        /// Script source
+        run: String,
+    },
+    /// `chmod +x` a generated file
+    MakeExecutable {
+        /// Path in the generated project
+        path: PathBuf,
+    },
+    /// Steps shown to the user once the project is ready
+    NextSteps {
+        /// One line per step
+        steps: Vec<String>,
+    },
+}
+
+impl TemplateManifest {
+    /// Parse and check a manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::InvalidManifest` if the TOML is malformed,
+    /// has unknown or mistyped keys, or breaks a rule of the schema.
+    pub fn from_toml(raw: &str) -> Result<Self, TemplateError> {
+        let manifest: Self = toml::from_str(raw).map_err(|e| {
+            let at = e.span().map_or_else(
+                || MANIFEST_FILE.to_string(),
+                |span| line_col(raw, span.start),
+            );
+            invalid(at, e.message())
+        })?;
+        manifest.check()?;
+        Ok(manifest)
+    }
+
+    /// Serialize as `template.toml`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::InvalidManifest` if serialization fails.
+    pub fn to_toml(&self) -> Result<String, TemplateError> {
+        toml::to_string(self).map_err(|e| invalid(MANIFEST_FILE, e.to_string()))
+    }
+
+    /// Rules serde can't express.
+    fn check(&self) -> Result<(), TemplateError> {
+        if self.name.trim().is_empty() {
+            return Err(invalid("name", "must not be empty"));
+        }
+        if self.version.trim().is_empty() {
+            return Err(invalid("version", "must not be empty"));
+        }
+
+        if let Some(engine) = &self.engine {
+            check_engine("engine", engine)?;
+        }
+        for (i, feature) in self.matcher.features.iter().enumerate() {
+            check_feature(&format!("matcher.features[{i}]"), feature)?;
+        }
+
+        // Where each project path was first declared
+        let mut declared: HashMap<&Path, String> = HashMap::new();
+
+        for (i, dir) in self.directories.iter().enumerate() {
+            let at = format!("directory[{i}].path");
+            check_relative(&at, &dir.path)?;
+            if let Some(first) = declared.insert(&dir.path, at.clone()) {
+                return Err(already_declared(&at, &dir.path, &first));
+            }
+            if let Some(feature) = &dir.feature {
+                check_feature(&format!("directory[{i}].feature"), feature)?;
+            }
+        }
+
+        for (i, file) in self.files.iter().enumerate() {
+            let at = format!("file[{i}].path");
+            check_relative(&at, &file.path)?;
+            if let Some(first) = declared.insert(&file.path, at.clone()) {
+                return Err(already_declared(&at, &file.path, &first));
+            }
+            if let Some(feature) = &file.feature {
+                check_feature(&format!("file[{i}].feature"), feature)?;
+            }
+            if let Some(engine) = &file.engine {
+                check_engine(&format!("file[{i}].engine"), engine)?;
+            }
+
+            match (&file.content, &file.source) {
+                (Some(_), Some(_)) => {
+                    return Err(invalid(
+                        format!("file[{i}]"),
+                        "set either `content` or `source`, not both",
+                    ));
+                }
+                (None, None) => {
+                    return Err(invalid(
+                        format!("file[{i}]"),
+                        "missing content: set `content` (inline) or `source` (a file next to the manifest)",
+                    ));
+                }
+                (None, Some(source)) => check_relative(&format!("file[{i}].source"), source)?,
+                (Some(_), None) => {}
+            }
+        }
+
+        for (i, link) in self.symlinks.iter().enumerate() {
+            let at = format!("symlink[{i}].path");
+            check_relative(&at, &link.path)?;
+            if let Some(first) = declared.insert(&link.path, at.clone()) {
+                return Err(already_declared(&at, &link.path, &first));
+            }
+            if SymlinkToCreate::new(&link.path, &link.target)
+                .resolved_target()
+                .is_none()
+            {
+                return Err(invalid(
+                    format!("symlink[{i}].target"),
+                    format!(
+                        "'{}' must be relative to the link and stay inside the project",
+                        link.target.display()
+                    ),
+                ));
+            }
+            if let Some(feature) = &link.feature {
+                check_feature(&format!("symlink[{i}].feature"), feature)?;
+            }
+        }
+
+        for (i, hook) in self.hooks.iter().enumerate() {
+            match hook {
+                HookManifest::Command { program, .. } if program.trim().is_empty() => {
+                    return Err(invalid(format!("hook[{i}].program"), "must not be empty"));
+                }
+                HookManifest::Script { run } if run.trim().is_empty() => {
+                    return Err(invalid(format!("hook[{i}].run"), "must not be empty"));
+                }
+                HookManifest::MakeExecutable { path } => {
+                    check_relative(&format!("hook[{i}].path"), path)?;
+                }
+                HookManifest::NextSteps { steps } if steps.is_empty() => {
+                    return Err(invalid(format!("hook[{i}].steps"), "must not be empty"));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl MatcherManifest {
+    fn is_wildcard(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl From<MatcherManifest> for TargetMatcher {
+    fn from(m: MatcherManifest) -> Self {
+        TargetMatcher {
+            language: m.language,
+            framework: m.framework,
+            kind: m.kind,
+            architecture: m.architecture,
+            features: m.features,
+        }
+    }
+}
+
+impl From<&TargetMatcher> for MatcherManifest {
+    fn from(m: &TargetMatcher) -> Self {
+        Self {
+            language: m.language,
+            kind: m.kind,
+            framework: m.framework,
+            architecture: m.architecture,
+            features: m.features.clone(),
+        }
+    }
+}
+
+impl From<HookManifest> for Hook {
+    fn from(h: HookManifest) -> Self {
+        match h {
+            HookManifest::Command { program, args } => Hook::Command { program, args },
+            HookManifest::Script { run } => Hook::Script(run),
+            HookManifest::MakeExecutable { path } => Hook::make_executable(RelativePath::new(path)),
+            HookManifest::NextSteps { steps } => Hook::next_steps(steps),
+        }
+    }
+}
+
+impl From<&Hook> for HookManifest {
+    fn from(h: &Hook) -> Self {
+        match h {
+            Hook::Command { program, args } => Self::Command {
+                program: program.clone(),
+                args: args.clone(),
+            },
+            Hook::Script(run) => Self::Script { run: run.clone() },
+            Hook::Builtin(BuiltinHook::MakeExecutable(path)) => Self::MakeExecutable {
+                path: path.as_path().to_path_buf(),
+            },
+            Hook::Builtin(BuiltinHook::NextSteps(steps)) => Self::NextSteps {
+                steps: steps.clone(),
+            },
+        }
+    }
+}
+
+// ============================================================================
+// TemplateBuilder::from_manifest
+// ============================================================================
+
+impl TemplateBuilder {
+    /// Start a builder from a manifest, reading its content files from `dir`.
+    ///
+    /// Directories come first, then files, then symlinks, each in manifest
+    /// order; hooks keep theirs. More nodes can be added before building.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::InvalidManifest` if a content file can't be
+    /// read or is a symlink.
+    pub fn from_manifest(manifest: TemplateManifest, dir: &Path) -> Result<Self, TemplateError> {
+        let mut metadata = TemplateMetadata::new(manifest.name.clone())
+            .version(manifest.version.clone())
+            .description(manifest.description)
+            .tags(manifest.tags);
+        if let Some(author) = manifest.author {
+            metadata = metadata.author(author);
+        }
+        if let Some(engine) = manifest.engine {
+            metadata = metadata.engine(engine);
+        }
+
+        let mut builder = Template::builder()
+            .id(TemplateId::new(manifest.name, manifest.version))
+            .matcher(manifest.matcher.into())
+            .metadata(metadata);
+
+        for directory in manifest.directories {
+            let mut spec = DirectorySpec::new(RelativePath::new(directory.path));
+            if let Some(feature) = directory.feature {
+                spec = spec.feature(feature);
+            }
+            builder = builder.add_node(TemplateNode::Directory(spec));
+        }
+
+        for (i, file) in manifest.files.into_iter().enumerate() {
+            let content = match (file.content, file.source) {
+                (Some(content), _) => content,
+                (None, Some(source)) => read_source(dir, &source)
+                    .map_err(|reason| invalid(format!("file[{i}].source"), reason))?,
+                (None, None) => unreachable!("checked by TemplateManifest::from_toml"),
+            };
+            let source = TemplateSource::Owned(content);
+            let content = if file.literal {
+                TemplateContent::Literal(source)
+            } else {
+                TemplateContent::Parameterized(source)
+            };
+
+            let mut spec = FileSpec::new(RelativePath::new(file.path), content);
+            spec = spec.permissions(match (file.executable, file.read_only) {
+                (true, true) => Permissions::executable(),
+                (true, false) => Permissions::full(),
+                (false, true) => Permissions::read_only(),
+                (false, false) => Permissions::read_write(),
+            });
+            if let Some(feature) = file.feature {
+                spec = spec.feature(feature);
+            }
+            if let Some(engine) = file.engine {
+                spec = spec.engine(engine);
+            }
+            builder = builder.add_node(TemplateNode::File(spec));
+        }
+
+        for link in manifest.symlinks {
+            let mut spec = SymlinkSpec::new(
+                RelativePath::new(link.path),
+                link.target.to_string_lossy().into_owned(),
+            );
+            if let Some(feature) = link.feature {
+                spec = spec.feature(feature);
+            }
+            builder = builder.add_node(TemplateNode::Symlink(spec));
+        }
+
+        for hook in manifest.hooks {
+            builder = builder.hook(hook.into());
+        }
+
+        Ok(builder)
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn read_source(dir: &Path, source: &Path) -> Result<String, String> {
+    let path = dir.join(source);
+    let metadata =
+        fs::symlink_metadata(&path).map_err(|e| format!("can't read {}: {e}", source.display()))?;
+    if metadata.file_type().is_symlink() {
+        return Err(format!(
+            "{} is a symlink, which templates can't contain",
+            source.display()
+        ));
+    }
+    fs::read_to_string(&path).map_err(|e| format!("can't read {}: {e}", source.display()))
+}
+
+fn check_relative(at: &str, path: &Path) -> Result<(), TemplateError> {
+    RelativePath::check(path).map_err(|_| {
+        invalid(
+            at,
+            format!(
+                "'{}' must be a relative path that stays inside the project",
+                path.display()
+            ),
+        )
+    })
+}
+
+fn check_feature(at: &str, feature: &str) -> Result<(), TemplateError> {
+    domain::check_feature(feature).map_err(|e| match e {
+        DomainError::InvalidFeature { feature, reason } => {
+            invalid(at, format!("'{feature}' {reason}"))
+        }
+        other => invalid(at, other.to_string()),
+    })
+}
+
+/// Engines are registered at runtime, so only the name's shape is checked.
+fn check_engine(at: &str, engine: &str) -> Result<(), TemplateError> {
+    if engine.trim().is_empty() {
+        return Err(invalid(at, "must not be empty"));
+    }
+    Ok(())
+}
+
+fn already_declared(at: &str, path: &Path, first: &str) -> TemplateError {
+    invalid(
+        at,
+        format!("'{}' is already declared by {first}", path.display()),
+    )
+}
+
+/// `line L, column C` of byte `offset` in `raw`, both 1-based.
+fn line_col(raw: &str, offset: usize) -> String {
+    let before = &raw[..offset.min(raw.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    format!("line {line}, column {column}")
+}
+
+fn invalid(at: impl Into<String>, reason: impl Into<String>) -> TemplateError {
+    TemplateError::InvalidManifest {
+        at: at.into(),
+        reason: reason.into(),
+    }
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_false(b: &bool) -> bool {
+    !b
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::validator::validate_template;
+
+    const MANIFEST: &str = r##"
+name = "rust-cli-clap"
+version = "1.0.0"
+description = "Rust CLI with clap"
+tags = ["rust", "cli"]
+engine = "simple"
+
+[matcher]
+language = "rust"
+type = "cli"
+
+[[directory]]
+path = "src"
+
+[[file]]
+path = "src/main.rs"
+source = "main.rs"
+
+[[file]]
+path = "run.sh"
+content = "#!/bin/sh\ncargo run\n"
+literal = true
+executable = true
+
+[[file]]
+path = "Dockerfile"
+content = "FROM rust\n"
+feature = "docker"
+read_only = true
+
+[[symlink]]
+path = "bin/run"
+target = "../run.sh"
+
+[[hook]]
+type = "command"
+program = "cargo"
+args = ["fmt"]
+
+[[hook]]
+type = "next-steps"
+steps = ["cd {{PROJECT_NAME}}", "./run.sh"]
+"##;
+
+    /// Describe `template` as a manifest, with every file inline.
+    ///
+    /// Shared partials can't be expressed and are an error.
+    fn manifest_of(template: &Template) -> Result<TemplateManifest, TemplateError> {
+        let mut directories = Vec::new();
+        let mut files = Vec::new();
+        let mut symlinks = Vec::new();
+
+        for node in &template.tree.nodes {
+            match node {
+                TemplateNode::Directory(spec) => directories.push(DirectoryManifest {
+                    path: spec.path.as_path().to_path_buf(),
+                    feature: spec.feature.clone(),
+                }),
+                TemplateNode::File(spec) => {
+                    let (content, literal) = match &spec.content {
+                        TemplateContent::Literal(source) => (source.as_str(), true),
+                        TemplateContent::Parameterized(source) => (source.as_str(), false),
+                        TemplateContent::External(id) => {
+                            return Err(invalid(
+                                format!("file[{}]", files.len()),
+                                format!(
+                                    "{} uses shared partial '{}', which manifests can't express",
+                                    spec.path.as_path().display(),
+                                    id.0
+                                ),
+                            ));
+                        }
+                    };
+                    files.push(FileManifest {
+                        path: spec.path.as_path().to_path_buf(),
+                        content: Some(content.to_string()),
+                        source: None,
+                        literal,
+                        executable: spec.permissions.executable_flag(),
+                        read_only: !spec.permissions.writable(),
+                        feature: spec.feature.clone(),
+                        engine: spec.engine.clone(),
+                    });
+                }
+                TemplateNode::Symlink(spec) => symlinks.push(SymlinkManifest {
+                    path: spec.path.as_path().to_path_buf(),
+                    target: spec.target.clone().into(),
+                    feature: spec.feature.clone(),
+                }),
+            }
+        }
+
+        let metadata = &template.metadata;
+        Ok(TemplateManifest {
+            name: template.id.name.clone(),
+            version: template.id.version.clone(),
+            description: metadata.description.clone(),
+            author: Some(metadata.author.clone()),
+            tags: metadata.tags.clone(),
+            engine: metadata.engine.clone(),
+            matcher: (&template.matcher).into(),
+            directories,
+            files,
+            symlinks,
+            hooks: template.hooks.iter().map(Into::into).collect(),
+        })
+    }
+
+    fn reason_at(raw: &str) -> (String, String) {
+        match TemplateManifest::from_toml(raw).unwrap_err() {
+            TemplateError::InvalidManifest { at, reason } => (at, reason),
+            other => panic!("expected InvalidManifest, got {other:?}"),
+        }
+    }
+
+    fn build(manifest: TemplateManifest, dir: &Path) -> Template {
+        TemplateBuilder::from_manifest(manifest, dir)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn builds_template_from_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+
+        let manifest = TemplateManifest::from_toml(MANIFEST).unwrap();
+        let template = build(manifest, dir.path());
+
+        validate_template(&template).unwrap();
+        assert_eq!(template.id.to_string(), "rust-cli-clap@1.0.0");
+        assert_eq!(template.matcher.language, Some(Language::Rust));
+        assert_eq!(template.matcher.kind, Some(ProjectKind::Cli));
+        assert_eq!(template.metadata.engine.as_deref(), Some("simple"));
+        assert!(matches!(
+            &template.tree.nodes[0],
+            TemplateNode::Directory(_)
+        ));
+
+        let TemplateNode::File(main) = &template.tree.nodes[1] else {
+            panic!("expected src/main.rs");
+        };
+        assert!(matches!(
+            &main.content,
+            TemplateContent::Parameterized(source) if source.as_str() == "fn main() {}\n"
+        ));
+
+        let TemplateNode::File(run) = &template.tree.nodes[2] else {
+            panic!("expected run.sh");
+        };
+        assert!(matches!(run.content, TemplateContent::Literal(_)));
+        assert!(run.permissions.executable_flag());
+        assert_eq!(run.feature, None);
+        assert_eq!(template.tree.nodes[3].feature(), Some("docker"));
+
+        let TemplateNode::Symlink(link) = &template.tree.nodes[4] else {
+            panic!("expected bin/run");
+        };
+        assert_eq!(link.path.as_path(), Path::new("bin/run"));
+        assert_eq!(link.target, "../run.sh");
+
+        assert_eq!(
+            template.hooks,
+            [
+                Hook::command("cargo", ["fmt"]),
+                Hook::next_steps(["cd {{PROJECT_NAME}}", "./run.sh"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn toml_round_trips() {
+        let manifest = TemplateManifest::from_toml(MANIFEST).unwrap();
+        let written = manifest.to_toml().unwrap();
+
+        assert_eq!(TemplateManifest::from_toml(&written).unwrap(), manifest);
+    }
+
+    #[test]
+    fn template_round_trips_through_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        let template = build(TemplateManifest::from_toml(MANIFEST).unwrap(), dir.path());
+
+        let manifest = manifest_of(&template).unwrap();
+        let reparsed = TemplateManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        let rebuilt = build(reparsed.clone(), Path::new("unused"));
+
+        assert_eq!(reparsed, manifest);
+        assert_eq!(rebuilt.id, template.id);
+        assert_eq!(rebuilt.matcher, template.matcher);
+        assert_eq!(rebuilt.tree.len(), template.tree.len());
+        assert_eq!(rebuilt.hooks, template.hooks);
+        assert_eq!(manifest_of(&rebuilt).unwrap(), manifest);
+    }
+
+    #[test]
+    fn syntax_and_type_errors_point_at_the_line() {
+        let (at, reason) = reason_at("name = \"x\"\nversion = 1\n");
+        assert_eq!(at, "line 2, column 11");
+        assert!(reason.contains("string"), "{reason}");
+
+        let (at, reason) = reason_at("name = \"x\"\nversion = \"1.0.0\"\nnmae = \"y\"\n");
+        assert!(at.starts_with("line 3"), "{at}");
+        assert!(reason.contains("unknown field `nmae`"), "{reason}");
+    }
+
+    #[test]
+    fn unknown_matcher_values_list_the_options() {
+        let (_, reason) =
+            reason_at("name = \"x\"\nversion = \"1.0.0\"\n[matcher]\nlanguage = \"cobol\"\n");
+
+        assert!(reason.contains("cobol"), "{reason}");
+        assert!(reason.contains("rust"), "{reason}");
+    }
+
+    #[test]
+    fn schema_rules_name_the_offending_key() {
+        let head = "name = \"x\"\nversion = \"1.0.0\"\n";
+        let cases = [
+            ("name = \" \"\nversion = \"1.0.0\"\n", "name", "empty"),
+            (
+                "[[file]]\npath = \"a\"\ncontent = \"\"\nsource = \"b\"\n",
+                "file[0]",
+                "not both",
+            ),
+            ("[[file]]\npath = \"a\"\n", "file[0]", "missing content"),
+            (
+                "[[file]]\npath = \"../a\"\ncontent = \"\"\n",
+                "file[0].path",
+                "inside the project",
+            ),
+            (
+                "[[file]]\npath = \"a\"\nsource = \"/etc/passwd\"\n",
+                "file[0].source",
+                "relative",
+            ),
+            (
+                "[[directory]]\npath = \"src\"\n[[file]]\npath = \"src\"\ncontent = \"\"\n",
+                "file[0].path",
+                "already declared by directory[0].path",
+            ),
+            (
+                "[[symlink]]\npath = \"run\"\ntarget = \"../run.sh\"\n",
+                "symlink[0].target",
+                "stay inside the project",
+            ),
+            (
+                "[[hook]]\ntype = \"make-executable\"\npath = \"/bin/sh\"\n",
+                "hook[0].path",
+                "inside the project",
+            ),
+            (
+                "[[hook]]\ntype = \"command\"\nprogram = \"\"\n",
+                "hook[0].program",
+                "empty",
+            ),
+            (
+                "[[file]]\npath = \"a\"\ncontent = \"\"\nfeature = \"Docker\"\n",
+                "file[0].feature",
+                "'Docker' must start with a lowercase letter",
+            ),
+            ("engine = \" \"\n", "engine", "must not be empty"),
+            (
+                "[[file]]\npath = \"a\"\ncontent = \"\"\nengine = \"\"\n",
+                "file[0].engine",
+                "must not be empty",
+            ),
+            (
+                "[matcher]\nfeatures = [\"ci_github\"]\n",
+                "matcher.features[0]",
+                "only lowercase letters",
+            ),
+        ];
+
+        for (body, expected_at, expected_reason) in cases {
+            let raw = if body.starts_with("name") {
+                body.to_string()
+            } else {
+                format!("{head}{body}")
+            };
+            let (at, reason) = reason_at(&raw);
+            assert_eq!(at, expected_at, "{raw}");
+            assert!(reason.contains(expected_reason), "{raw}: {reason}");
+        }
+    }
+
+    #[test]
+    fn missing_content_file_is_reported_against_its_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = TemplateManifest::from_toml(MANIFEST).unwrap();
+
+        let Err(err) = TemplateBuilder::from_manifest(manifest, dir.path()) else {
+            panic!("main.rs doesn't exist");
+        };
+
+        assert_eq!(err.code(), "template.invalid_manifest");
+        assert!(err.to_string().contains("file[0].source"), "{err}");
+    }
+}
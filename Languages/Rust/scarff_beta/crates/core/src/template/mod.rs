@@ -1,6 +1,14 @@
 //! Template contains the following;
 //! - TemplateStore: In-memory store that stores and loads builtin templates #MVP
 //!
+//! - FsStore: user templates loaded from a directory tree, rescanned on demand
+//!
+//! - LayeredStore: several stores (built-in, filesystem, remote) searched as one
+//!
+//! - search: ranked, filtered template search (`TemplateQuery`)
+//!
+//! - manifest: `template.toml`, the declarative form of a template
+//!
 //! - TemplateResolver: resolves Target to a Template. This is where the matching happens
 //!
 //! - TemplateRenderer: renders Template to a ProjectStructure.
@@ -8,23 +16,75 @@
 //!
 //! - TemplateError: template specific errors
 //!
+//! - syntax: variables, conditionals and loops in parameterized content
+//!
+//! - backend: pluggable template engines (`TemplateBackend`), the built-in syntax by default
+//!
+//! - lint: diagnostics for custom templates (`TemplateDiagnostic`) before they're used
+//!
+//! - partials: shared content (`{{> id}}` includes) reused across templates
+//!
+//! - readme: README generated for templates that don't ship one
+//!
+//! - python_tooling: dependency manager files (uv, poetry, pip) for Python targets
+//!
+//! - test_runner: test runner config and example test (vitest, jest) for TypeScript targets
+//!
+//! - license: LICENSE file for the target's license (MIT, Apache-2.0, GPL-3.0, MPL-2.0, Unlicense)
+//!
+//! - task_runner: justfile/Makefile (test, lint, run) and example tests for Rust targets
+//!
+//! - ci: build/test/lint/release pipeline for the target's CI provider (GitHub Actions, GitLab CI, CircleCI)
+//!
 //! - templates: In memory templates to scaffold if matched #MVP
 //! Template system: storage, resolution, and rendering.
 
+pub use backend::{BackendError, DEFAULT_ENGINE, SimpleBackend, TemplateBackend, Variables};
+pub(crate) use ci::with_ci;
 pub(crate) use errors::TemplateError;
+pub(crate) use fs_store::read_template_dir;
+pub use fs_store::{FsStore, ScanReport};
+pub(crate) use layered::LayeredStore;
+pub(crate) use license::with_license;
+pub use lint::{DiagnosticKind, Severity, TemplateDiagnostic};
+pub use manifest::{
+    DirectoryManifest, FileManifest, HookManifest, MatcherManifest, SymlinkManifest,
+    TemplateManifest,
+};
+pub(crate) use partials::with_gitignore;
+pub(crate) use python_tooling::with_tooling;
 pub(crate) use renderer::TemplateRenderer;
 pub(crate) use resolver::TemplateResolver;
+pub use search::TemplateQuery;
+pub(crate) use search::search;
 pub(crate) use store::{InMemoryStore, Store};
+pub(crate) use task_runner::with_task_runner;
+pub(crate) use test_runner::with_test_runner;
 
 // Re-export from domain
 pub(crate) use crate::domain::{
-    ContentTemplateId, DirectorySpec, FileSpec, TargetMatcher, Template, TemplateContent,
-    TemplateId, TemplateMetadata, TemplateNode, TemplateRecord, TemplateSource, TemplateTree,
+    ContentTemplateId, DirectorySpec, FileSpec, SymlinkSpec, TargetMatcher, Template,
+    TemplateBuilder, TemplateContent, TemplateId, TemplateMetadata, TemplateNode, TemplateRecord,
+    TemplateSource, TemplateTree,
 };
 
 // Public for built-in templates
+mod backend;
 pub(crate) mod built_in_templates;
+mod ci;
 mod errors;
+mod fs_store;
+mod layered;
+mod license;
+mod lint;
+mod manifest;
+mod partials;
+mod python_tooling;
+mod readme;
 mod renderer;
 mod resolver;
+mod search;
 mod store;
+mod syntax;
+mod task_runner;
+mod test_runner;
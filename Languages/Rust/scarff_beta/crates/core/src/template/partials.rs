@@ -0,0 +1,297 @@
+//! Content template partials: shared snippets reused across templates.
+//!
+//! A partial is content registered under a [`ContentTemplateId`] (license
+//! headers, `.gitignore` files, error-module boilerplate). Templates use
+//! one in two ways:
+//!
+//! - `{{> license/rust-header}}` inside parameterized content includes it
+//!   inline (without its trailing newline, so it can stand in for a line)
+//! - `TemplateContent::External(ContentTemplateId("gitignore/rust"))`
+//!   makes it the whole file
+//!
+//! Partials may include other partials. Includes are expanded before
+//! variable substitution, so a partial sees the same `{{VARIABLES}}` as the
+//! file it lands in; the expansion itself doesn't depend on variables and
+//! is cached by the renderer.
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    domain::Language,
+    template::{
+        ContentTemplateId, FileSpec, Template, TemplateContent, TemplateError, TemplateNode,
+        TemplateSource,
+    },
+};
+
+/// Opening of an include tag: `{{> id}}`.
+const INCLUDE_OPEN: &str = "{{>";
+const INCLUDE_CLOSE: &str = "}}";
+
+/// Partials shipped with Scarff.
+const BUILTIN: &[(&str, &str)] = &[
+    (
+        "gitignore/rust",
+        include_str!("templates/partials/gitignore/rust.gitignore"),
+    ),
+    (
+        "gitignore/python",
+        include_str!("templates/partials/gitignore/python.gitignore"),
+    ),
+    (
+        "gitignore/node",
+        include_str!("templates/partials/gitignore/node.gitignore"),
+    ),
+    (
+        "license/rust-header",
+        include_str!("templates/partials/license/rust-header.partial"),
+    ),
+    (
+        "license/python-header",
+        include_str!("templates/partials/license/python-header.partial"),
+    ),
+    (
+        "rust/error-module",
+        include_str!("templates/partials/rust/error-module.partial"),
+    ),
+];
+
+/// Add the `.gitignore` for `language` to `template`, unless it has one.
+pub(crate) fn with_gitignore(mut template: Template, language: Language) -> Template {
+    let id = match language {
+        Language::Rust => "gitignore/rust",
+        Language::Python => "gitignore/python",
+        Language::TypeScript => "gitignore/node",
+    };
+    if !template.tree.has_file(".gitignore") {
+        template.tree.push(TemplateNode::File(FileSpec::new(
+            ".gitignore",
+            TemplateContent::External(ContentTemplateId(id)),
+        )));
+    }
+    template
+}
+
+/// Partials by id.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Partials {
+    sources: HashMap<&'static str, TemplateSource>,
+}
+
+impl Partials {
+    /// The partials shipped with Scarff.
+    pub(crate) fn builtin() -> Self {
+        let mut partials = Self::default();
+        for &(id, source) in BUILTIN {
+            partials.insert(ContentTemplateId(id), source);
+        }
+        partials
+    }
+
+    /// Register (or replace) a partial.
+    pub(crate) fn insert(&mut self, id: ContentTemplateId, source: impl Into<TemplateSource>) {
+        self.sources.insert(id.0, source.into());
+    }
+
+    /// Raw (unexpanded) source of a partial.
+    pub(crate) fn get(&self, id: &str) -> Option<&str> {
+        self.sources.get(id).map(TemplateSource::as_str)
+    }
+}
+
+/// Expanded partials, keyed by id.
+pub(crate) type ExpandedCache = HashMap<String, Arc<str>>;
+
+/// Replace every `{{> id}}` in `content` with the expanded partial.
+///
+/// # Errors
+///
+/// [`TemplateError::UnknownPartial`] for an id that isn't registered and
+/// [`TemplateError::IncludeCycle`] if partials include each other in a loop.
+pub(crate) fn expand_includes(
+    content: &str,
+    partials: &Partials,
+    cache: &mut ExpandedCache,
+) -> Result<String, TemplateError> {
+    let mut out = String::with_capacity(content.len());
+    expand_into(content, partials, cache, &mut Vec::new(), &mut out)?;
+    Ok(out)
+}
+
+/// A partial with all of its includes expanded.
+///
+/// # Errors
+///
+/// Same as [`expand_includes`].
+pub(crate) fn resolve(
+    id: &str,
+    partials: &Partials,
+    cache: &mut ExpandedCache,
+) -> Result<Arc<str>, TemplateError> {
+    resolve_with(id, partials, cache, &mut Vec::new())
+}
+
+fn expand_into(
+    content: &str,
+    partials: &Partials,
+    cache: &mut ExpandedCache,
+    stack: &mut Vec<String>,
+    out: &mut String,
+) -> Result<(), TemplateError> {
+    let mut rest = content;
+    while let Some(start) = rest.find(INCLUDE_OPEN) {
+        let tag = &rest[start + INCLUDE_OPEN.len()..];
+        let Some(end) = tag.find(INCLUDE_CLOSE) else {
+            // Unterminated: not an include, leave it as text
+            break;
+        };
+
+        out.push_str(&rest[..start]);
+        let partial = resolve_with(tag[..end].trim(), partials, cache, stack)?;
+        out.push_str(partial.strip_suffix('\n').unwrap_or(&partial));
+        rest = &tag[end + INCLUDE_CLOSE.len()..];
+    }
+    out.push_str(rest);
+    Ok(())
+}
+
+fn resolve_with(
+    id: &str,
+    partials: &Partials,
+    cache: &mut ExpandedCache,
+    stack: &mut Vec<String>,
+) -> Result<Arc<str>, TemplateError> {
+    if let Some(expanded) = cache.get(id) {
+        return Ok(Arc::clone(expanded));
+    }
+    if let Some(pos) = stack.iter().position(|seen| seen == id) {
+        let mut chain = stack[pos..].to_vec();
+        chain.push(id.to_string());
+        return Err(TemplateError::IncludeCycle(chain));
+    }
+    let source = partials
+        .get(id)
+        .ok_or_else(|| TemplateError::UnknownPartial(id.to_string()))?;
+
+    stack.push(id.to_string());
+    let mut expanded = String::with_capacity(source.len());
+    expand_into(source, partials, cache, stack, &mut expanded)?;
+    stack.pop();
+
+    let expanded: Arc<str> = expanded.into();
+    cache.insert(id.to_string(), Arc::clone(&expanded));
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partials(entries: &[(&'static str, &'static str)]) -> Partials {
+        let mut partials = Partials::default();
+        for &(id, source) in entries {
+            partials.insert(ContentTemplateId(id), source);
+        }
+        partials
+    }
+
+    #[test]
+    fn includes_are_expanded_recursively() {
+        let partials = partials(&[("outer", "[{{> inner}}]\n"), ("inner", "x\n")]);
+        let mut cache = ExpandedCache::new();
+
+        let out = expand_includes("a {{> outer }} b", &partials, &mut cache).unwrap();
+
+        assert_eq!(out, "a [x] b");
+        assert_eq!(&*cache["outer"], "[x]\n");
+        assert!(cache.contains_key("inner"));
+    }
+
+    #[test]
+    fn variables_are_left_for_substitution() {
+        let partials = partials(&[("header", "// {{PROJECT_NAME}}\n")]);
+
+        let out = expand_includes(
+            "{{> header}}\nfn main() {}",
+            &partials,
+            &mut ExpandedCache::new(),
+        )
+        .unwrap();
+
+        assert_eq!(out, "// {{PROJECT_NAME}}\nfn main() {}");
+    }
+
+    #[test]
+    fn cached_expansion_is_reused() {
+        let partials = partials(&[("p", "one")]);
+        let mut cache = ExpandedCache::new();
+        cache.insert("p".to_string(), "cached".into());
+
+        assert_eq!(&*resolve("p", &partials, &mut cache).unwrap(), "cached");
+    }
+
+    #[test]
+    fn unknown_partial_is_an_error() {
+        let err = expand_includes(
+            "{{> nope}}",
+            &Partials::default(),
+            &mut ExpandedCache::new(),
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, TemplateError::UnknownPartial(id) if id == "nope"));
+    }
+
+    #[test]
+    fn include_cycle_is_reported_with_its_chain() {
+        let partials = partials(&[("a", "{{> b}}"), ("b", "{{> c}}"), ("c", "{{> b}}")]);
+
+        let err = resolve("a", &partials, &mut ExpandedCache::new()).unwrap_err();
+
+        assert!(matches!(&err, TemplateError::IncludeCycle(chain) if chain == &["b", "c", "b"]));
+        assert_eq!(err.to_string(), "Include cycle: b -> c -> b");
+    }
+
+    #[test]
+    fn unterminated_tag_is_plain_text() {
+        let out =
+            expand_includes("{{> oops", &Partials::default(), &mut ExpandedCache::new()).unwrap();
+        assert_eq!(out, "{{> oops");
+    }
+
+    #[test]
+    fn builtin_partials_all_resolve() {
+        let partials = Partials::builtin();
+        let mut cache = ExpandedCache::new();
+
+        for &(id, _) in BUILTIN {
+            let expanded = resolve(id, &partials, &mut cache).unwrap();
+            assert!(!expanded.contains("{{>"), "{id} has an unexpanded include");
+        }
+    }
+
+    #[test]
+    fn gitignore_is_added_only_when_missing() {
+        use crate::domain::{TargetMatcher, TemplateId, TemplateMetadata, TemplateTree};
+
+        let template = Template {
+            id: TemplateId::new("python", "0.1.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("python"),
+            tree: TemplateTree::new(),
+            hooks: Vec::new(),
+        };
+
+        let template = with_gitignore(template, Language::Python);
+        let template = with_gitignore(template, Language::Rust);
+
+        assert_eq!(template.tree.nodes.len(), 1);
+        let TemplateNode::File(spec) = &template.tree.nodes[0] else {
+            panic!("expected a file node");
+        };
+        assert!(matches!(
+            spec.content,
+            TemplateContent::External(ContentTemplateId("gitignore/python"))
+        ));
+    }
+}
@@ -0,0 +1,419 @@
+//! Python dependency manager files.
+//!
+//! Python templates don't ship dependency files of their own: they are
+//! generated for the target's [`DependencyManager`], with the dependencies
+//! of the target's framework:
+//! - uv: `pyproject.toml`, `.python-version` and a `uv.lock` placeholder
+//! - poetry: `pyproject.toml` (`[tool.poetry]`) and a `poetry.lock` placeholder
+//! - pip: `requirements.txt` and `requirements-dev.txt`
+//!
+//! Lock files are placeholders; the manager writes the real ones on its
+//! first install.
+//!
+//! The target's [`PythonLayout`] decides where the packages live. Flat
+//! keeps them at the project root, and the project isn't installed
+//! (`package = false` / `package-mode = false`). Src moves every top-level
+//! package of the template under `src/` and declares them for the build
+//! backend, so tests import the installed package rather than the working
+//! tree.
+
+use std::{collections::BTreeSet, fmt::Write, path::Path};
+
+use crate::domain::{
+    DependencyManager, FileSpec, Framework, ProjectKind, PythonFramework, PythonLayout,
+    RelativePath, RenderContext, Target, Template, TemplateContent, TemplateNode, TemplateSource,
+};
+use crate::template::backend::DEFAULT_ENGINE;
+
+/// Runtime dependencies per framework, as `(package, minimum version)`.
+fn dependencies(framework: Option<Framework>) -> &'static [(&'static str, &'static str)] {
+    match framework {
+        Some(Framework::Python(PythonFramework::FastApi)) => {
+            &[("fastapi", "0.115"), ("uvicorn[standard]", "0.30")]
+        }
+        Some(Framework::Python(PythonFramework::Django)) => &[("django", "5.0")],
+        _ => &[],
+    }
+}
+
+/// Development dependencies, shared by every Python project.
+const DEV_DEPENDENCIES: &[(&str, &str)] = &[("pytest", "8.0")];
+
+/// Files generated for each manager, as `(path, content template)`.
+fn files(manager: DependencyManager) -> &'static [(&'static str, &'static str)] {
+    match manager {
+        DependencyManager::Uv => &[
+            (
+                "pyproject.toml",
+                include_str!("templates/python/tooling/uv/pyproject.toml.template"),
+            ),
+            (
+                ".python-version",
+                include_str!("templates/python/tooling/uv/python-version.template"),
+            ),
+            (
+                "uv.lock",
+                include_str!("templates/python/tooling/uv/uv.lock.template"),
+            ),
+        ],
+        DependencyManager::Poetry => &[
+            (
+                "pyproject.toml",
+                include_str!("templates/python/tooling/poetry/pyproject.toml.template"),
+            ),
+            (
+                "poetry.lock",
+                include_str!("templates/python/tooling/poetry/poetry.lock.template"),
+            ),
+        ],
+        DependencyManager::Pip => &[
+            (
+                "requirements.txt",
+                include_str!("templates/python/tooling/pip/requirements.txt.template"),
+            ),
+            (
+                "requirements-dev.txt",
+                include_str!("templates/python/tooling/pip/requirements-dev.txt.template"),
+            ),
+        ],
+    }
+}
+
+/// Add the dependency manager's files to `template`, and the
+/// `PYTHON_DEPENDENCIES` / `PYTHON_DEV_DEPENDENCIES` /
+/// `PYTHON_SRC_PACKAGES` they render from to `ctx`.
+///
+/// With the src layout the template's packages are moved under `src/`
+/// first (see [`into_src_layout`]). Files the template already provides
+/// are kept as they are. Targets without a dependency manager are
+/// returned unchanged.
+pub(crate) fn with_tooling(
+    mut template: Template,
+    ctx: RenderContext,
+    target: &Target,
+) -> (Template, RenderContext) {
+    let Some(manager) = target.dependency_manager() else {
+        return (template, ctx);
+    };
+
+    // A workspace root has no package of its own; members pick their layout
+    let packages = match target.python_layout() {
+        Some(PythonLayout::Src) if target.kind() != ProjectKind::Workspace => into_src_layout(
+            &mut template,
+            ctx.get("PROJECT_NAME_SNAKE").unwrap_or("app"),
+        ),
+        _ => Vec::new(),
+    };
+
+    for (path, content) in files(manager) {
+        if !template.tree.has_file(path) {
+            // Written in the built-in syntax, whatever the template's engine
+            template.tree.push(TemplateNode::File(
+                FileSpec::new(
+                    *path,
+                    TemplateContent::Parameterized(TemplateSource::Static(content)),
+                )
+                .engine(DEFAULT_ENGINE),
+            ));
+        }
+    }
+
+    let ctx = ctx
+        .with_var(
+            "PYTHON_DEPENDENCIES",
+            format_dependencies(manager, dependencies(target.framework())),
+        )
+        .with_var(
+            "PYTHON_DEV_DEPENDENCIES",
+            format_dependencies(manager, DEV_DEPENDENCIES),
+        )
+        .with_list("PYTHON_SRC_PACKAGES", packages);
+
+    (template, ctx)
+}
+
+/// Directories that hold tests rather than importable code.
+const TEST_DIRS: &[&str] = &["tests", "test"];
+
+/// Move the template's top-level packages under `src/` and return their
+/// names.
+///
+/// A package is a top-level directory with an `__init__.py`, other than
+/// the test directories; everything beneath it moves with it. Packages
+/// already under `src/` stay where they are. A template without any gets
+/// an empty `src/<default>/__init__.py`, so there is something to build.
+fn into_src_layout(template: &mut Template, default: &str) -> Vec<String> {
+    let mut packages = BTreeSet::new();
+    let mut moved = BTreeSet::new();
+    for node in &template.tree.nodes {
+        let TemplateNode::File(spec) = node else {
+            continue;
+        };
+        let mut parts = spec.path.as_path().iter().filter_map(|part| part.to_str());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some("src"), Some(package), Some("__init__.py"), None) => {
+                packages.insert(package.to_string());
+            }
+            (Some(package), Some("__init__.py"), None, None) if !TEST_DIRS.contains(&package) => {
+                packages.insert(package.to_string());
+                moved.insert(package.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    for node in &mut template.tree.nodes {
+        let path = match node {
+            TemplateNode::File(spec) => &mut spec.path,
+            TemplateNode::Directory(spec) => &mut spec.path,
+            TemplateNode::Symlink(spec) => &mut spec.path,
+        };
+        let top = path.as_path().iter().next().and_then(|part| part.to_str());
+        if top.is_some_and(|top| moved.contains(top)) {
+            *path = RelativePath::new(Path::new("src").join(path.as_path()));
+        }
+    }
+
+    if packages.is_empty() {
+        template.tree.push(TemplateNode::File(FileSpec::new(
+            format!("src/{default}/__init__.py"),
+            TemplateContent::Literal(TemplateSource::Static("")),
+        )));
+        packages.insert(default.to_string());
+    }
+    packages.into_iter().collect()
+}
+
+/// Dependencies in the syntax of the manager's file.
+fn format_dependencies(manager: DependencyManager, deps: &[(&str, &str)]) -> String {
+    match manager {
+        // Entries of a TOML array: `[\n    "fastapi>=0.115",\n]`
+        DependencyManager::Uv if deps.is_empty() => String::new(),
+        DependencyManager::Uv => {
+            let mut entries = String::new();
+            for (name, version) in deps {
+                let _ = write!(entries, "\n    \"{name}>={version}\",");
+            }
+            entries.push('\n');
+            entries
+        }
+        // Keys of a `[tool.poetry.*dependencies]` table
+        DependencyManager::Poetry => deps
+            .iter()
+            .map(|(name, version)| match name.split_once('[') {
+                Some((name, extras)) => format!(
+                    "{name} = {{ version = \"^{version}\", extras = [\"{}\"] }}",
+                    extras.trim_end_matches(']')
+                ),
+                None => format!("{name} = \"^{version}\""),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        // Requirement specifiers, one per line
+        DependencyManager::Pip => deps
+            .iter()
+            .map(|(name, version)| format!("{name}>={version}"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{Language, TargetMatcher, TemplateId, TemplateMetadata, TemplateTree},
+        template::syntax,
+    };
+
+    fn template() -> Template {
+        Template {
+            id: TemplateId::new("python", "0.1.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("python"),
+            tree: TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
+                "app/main.py",
+                TemplateContent::Literal(TemplateSource::Static("")),
+            ))),
+            hooks: Vec::new(),
+        }
+    }
+
+    fn fastapi(manager: DependencyManager) -> Target {
+        Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .dependency_manager(manager)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn src_layout(manager: DependencyManager) -> Target {
+        Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .dependency_manager(manager)
+            .unwrap()
+            .python_layout(PythonLayout::Src)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn paths(template: &Template) -> Vec<&str> {
+        template
+            .tree
+            .nodes
+            .iter()
+            .filter_map(|node| match node {
+                TemplateNode::File(spec) => spec.path.as_path().to_str(),
+                TemplateNode::Directory(_) | TemplateNode::Symlink(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn adds_files_for_each_manager() {
+        let cases = [
+            (
+                DependencyManager::Uv,
+                vec!["pyproject.toml", ".python-version", "uv.lock"],
+            ),
+            (
+                DependencyManager::Poetry,
+                vec!["pyproject.toml", "poetry.lock"],
+            ),
+            (
+                DependencyManager::Pip,
+                vec!["requirements.txt", "requirements-dev.txt"],
+            ),
+        ];
+
+        for (manager, expected) in cases {
+            let (template, _) =
+                with_tooling(template(), RenderContext::new("api"), &fastapi(manager));
+            assert_eq!(paths(&template)[1..], expected[..], "{manager}");
+        }
+    }
+
+    #[test]
+    fn dependencies_follow_framework_and_manager() {
+        let (_, ctx) = with_tooling(
+            template(),
+            RenderContext::new("api"),
+            &fastapi(DependencyManager::Poetry),
+        );
+        assert_eq!(
+            ctx.get("PYTHON_DEPENDENCIES").unwrap(),
+            "fastapi = \"^0.115\"\nuvicorn = { version = \"^0.30\", extras = [\"standard\"] }"
+        );
+
+        let (_, ctx) = with_tooling(
+            template(),
+            RenderContext::new("api"),
+            &fastapi(DependencyManager::Uv),
+        );
+        assert_eq!(
+            ctx.get("PYTHON_DEPENDENCIES").unwrap(),
+            "\n    \"fastapi>=0.115\",\n    \"uvicorn[standard]>=0.30\",\n"
+        );
+        assert_eq!(
+            ctx.get("PYTHON_DEV_DEPENDENCIES").unwrap(),
+            "\n    \"pytest>=8.0\",\n"
+        );
+    }
+
+    #[test]
+    fn src_layout_moves_packages_and_declares_them() {
+        let mut own = template();
+        for path in ["app/__init__.py", "tests/__init__.py", "tests/test_main.py"] {
+            own.tree.push(TemplateNode::File(FileSpec::new(
+                path,
+                TemplateContent::Literal(TemplateSource::Static("")),
+            )));
+        }
+
+        let (own, ctx) = with_tooling(
+            own,
+            RenderContext::new("api"),
+            &src_layout(DependencyManager::Uv),
+        );
+
+        assert_eq!(
+            paths(&own)[..4],
+            [
+                "src/app/main.py",
+                "src/app/__init__.py",
+                "tests/__init__.py",
+                "tests/test_main.py"
+            ]
+        );
+        assert_eq!(ctx.list("PYTHON_SRC_PACKAGES").unwrap(), ["app"]);
+        let pyproject = syntax::render(files(DependencyManager::Uv)[0].1, &ctx).unwrap();
+        assert!(pyproject.contains("packages = [\n    \"src/app\",\n]\n"));
+        assert!(!pyproject.contains("package = false"));
+    }
+
+    #[test]
+    fn src_layout_adds_a_package_when_the_template_has_none() {
+        let (template, ctx) = with_tooling(
+            template(),
+            RenderContext::new("my-api"),
+            &src_layout(DependencyManager::Poetry),
+        );
+
+        // `app/main.py` has no `__init__.py`, so it isn't a package
+        assert!(template.tree.has_file("app/main.py"));
+        assert!(template.tree.has_file("src/my_api/__init__.py"));
+        let pyproject = syntax::render(files(DependencyManager::Poetry)[0].1, &ctx).unwrap();
+        assert!(pyproject.contains("{ include = \"my_api\", from = \"src\" },"));
+        assert!(!pyproject.contains("package-mode"));
+    }
+
+    #[test]
+    fn flat_layout_is_not_packaged() {
+        let (template, ctx) = with_tooling(
+            template(),
+            RenderContext::new("api"),
+            &fastapi(DependencyManager::Uv),
+        );
+
+        assert_eq!(paths(&template)[0], "app/main.py");
+        let pyproject = syntax::render(files(DependencyManager::Uv)[0].1, &ctx).unwrap();
+        assert!(pyproject.ends_with("[tool.uv]\npackage = false\n"));
+    }
+
+    #[test]
+    fn keeps_template_files_and_ignores_other_languages() {
+        let mut own = template();
+        own.tree.push(TemplateNode::File(FileSpec::new(
+            "requirements.txt",
+            TemplateContent::Literal(TemplateSource::Static("flask\n")),
+        )));
+
+        let (own, _) = with_tooling(
+            own,
+            RenderContext::new("api"),
+            &fastapi(DependencyManager::Pip),
+        );
+        assert_eq!(
+            paths(&own),
+            ["app/main.py", "requirements.txt", "requirements-dev.txt"]
+        );
+
+        let (rust, ctx) = with_tooling(
+            template(),
+            RenderContext::new("cli"),
+            &Target::rust_cli().unwrap(),
+        );
+        assert_eq!(paths(&rust), ["app/main.py"]);
+        assert!(!ctx.has("PYTHON_DEPENDENCIES"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Generated project README.
+//!
+//! Templates that don't ship their own `README.md` get one rendered from
+//! a shared content template. Everything it shows comes from the render
+//! context: template metadata, the chosen target options, and the
+//! next-step commands for the target's language and framework.
+
+use crate::domain::{
+    DependencyManager, FileSpec, Framework, Language, ProjectKind, PythonFramework, RenderContext,
+    RustFramework, Target, Template, TemplateContent, TemplateNode, TemplateSource, TestRunner,
+    TypeScriptFramework,
+};
+use crate::template::backend::DEFAULT_ENGINE;
+
+/// Path of the generated README, relative to the project root.
+pub(crate) const README_PATH: &str = "README.md";
+
+/// Content template for the generated README.
+const README_TEMPLATE: &str = include_str!("templates/common/README.md.template");
+
+/// Whether the template already provides a README.
+pub(crate) fn has_readme(template: &Template) -> bool {
+    template.tree.nodes.iter().any(|node| match node {
+        TemplateNode::File(spec) => spec
+            .path
+            .as_path()
+            .to_str()
+            .is_some_and(|p| p.eq_ignore_ascii_case(README_PATH)),
+        TemplateNode::Directory(_) | TemplateNode::Symlink(_) => false,
+    })
+}
+
+/// The README file node, rendered against the context like any other file
+/// (always with the built-in engine, whatever the template's).
+pub(crate) fn readme_node() -> TemplateNode {
+    TemplateNode::File(
+        FileSpec::new(
+            README_PATH,
+            TemplateContent::Parameterized(TemplateSource::Static(README_TEMPLATE)),
+        )
+        .engine(DEFAULT_ENGINE),
+    )
+}
+
+/// Add the `NEXT_STEPS` list: the shell commands to build and run the
+/// project.
+///
+/// Computed from the context's `PROJECT_NAME` and `PROJECT_NAME_SNAKE`,
+/// so it must be added after those are final.
+pub(crate) fn with_next_steps(ctx: RenderContext, target: &Target) -> RenderContext {
+    let project = ctx.get("PROJECT_NAME").unwrap_or_default().to_string();
+    let module = ctx
+        .get("PROJECT_NAME_SNAKE")
+        .unwrap_or_default()
+        .to_string();
+
+    let mut steps = vec![format!("cd {project}")];
+    steps.extend(next_steps(target, &module));
+
+    ctx.with_list("NEXT_STEPS", steps)
+}
+
+/// Commands to run inside the project, per language and framework.
+fn next_steps(target: &Target, module: &str) -> Vec<String> {
+    let steps: &[&str] = match (target.language(), target.framework()) {
+        (Language::Rust, Some(Framework::Rust(RustFramework::Axum | RustFramework::Actix))) => {
+            &["cargo run", "cargo test"]
+        }
+        (Language::Rust, _) if target.kind() == ProjectKind::Cli => {
+            &["cargo run -- --help", "cargo test"]
+        }
+        (Language::Rust, _) => &["cargo build", "cargo test"],
+
+        (Language::Python, framework) => {
+            // Commands run inside the environment through the manager
+            let (mut steps, run) = match target.dependency_manager() {
+                Some(DependencyManager::Uv) => (vec!["uv sync".to_string()], "uv run "),
+                Some(DependencyManager::Poetry) => {
+                    (vec!["poetry install".to_string()], "poetry run ")
+                }
+                Some(DependencyManager::Pip) | None => (
+                    vec![
+                        "python -m venv .venv".to_string(),
+                        "source .venv/bin/activate".to_string(),
+                        "pip install -r requirements-dev.txt".to_string(),
+                    ],
+                    "",
+                ),
+            };
+            match framework {
+                Some(Framework::Python(PythonFramework::FastApi)) => {
+                    steps.push(format!("{run}uvicorn app.main:app --reload"));
+                }
+                Some(Framework::Python(PythonFramework::Django)) => {
+                    steps.push(format!("{run}python manage.py migrate"));
+                    steps.push(format!("{run}python manage.py runserver"));
+                }
+                _ => steps.push(format!("{run}python -m {module}")),
+            }
+            steps.push(format!("{run}python -m pytest"));
+            return steps;
+        }
+
+        (Language::TypeScript, framework) => {
+            let mut steps: Vec<String> = match framework {
+                Some(Framework::TypeScript(TypeScriptFramework::NestJs)) => {
+                    vec!["npm install".into(), "npm run start:dev".into()]
+                }
+                Some(_) => vec!["npm install".into(), "npm run dev".into()],
+                None => vec![
+                    "npm install".into(),
+                    "npm run build".into(),
+                    "npm start".into(),
+                ],
+            };
+            if target
+                .test_runner()
+                .is_some_and(|runner| runner != TestRunner::None)
+            {
+                steps.push("npm test".into());
+            }
+            return steps;
+        }
+    };
+
+    steps.iter().map(ToString::to_string).collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{TargetMatcher, TemplateId, TemplateMetadata, TemplateTree};
+
+    fn template(tree: TemplateTree) -> Template {
+        Template {
+            id: TemplateId::new("test", "0.1.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("test"),
+            tree,
+            hooks: Vec::new(),
+        }
+    }
+
+    fn steps(target: &Target) -> String {
+        let ctx = with_next_steps(RenderContext::new("my-app"), target);
+        ctx.list("NEXT_STEPS").unwrap().join("\n")
+    }
+
+    #[test]
+    fn detects_existing_readme() {
+        let with = template(
+            TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
+                "readme.md",
+                TemplateContent::Literal(TemplateSource::Static("# Mine")),
+            ))),
+        );
+        let without = template(
+            TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
+                "docs/README.md",
+                TemplateContent::Literal(TemplateSource::Static("")),
+            ))),
+        );
+
+        assert!(has_readme(&with));
+        assert!(!has_readme(&without));
+    }
+
+    #[test]
+    fn next_steps_follow_language_and_framework() {
+        let cli = Target::rust_cli().unwrap();
+        assert_eq!(steps(&cli), "cd my-app\ncargo run -- --help\ncargo test");
+
+        let fastapi = Target::python_backend_fastapi().unwrap();
+        assert!(steps(&fastapi).contains("uvicorn app.main:app --reload"));
+
+        let uv = Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .dependency_manager(DependencyManager::Uv)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            steps(&uv),
+            "cd my-app\nuv sync\nuv run uvicorn app.main:app --reload\nuv run python -m pytest"
+        );
+
+        let react = Target::typescript_frontend_react().unwrap();
+        assert!(steps(&react).contains("npm run dev"));
+    }
+
+    #[test]
+    fn typescript_runs_tests_only_with_a_runner() {
+        let target = |runner| {
+            Target::builder()
+                .language(Language::TypeScript)
+                .kind(ProjectKind::WebFrontend)
+                .unwrap()
+                .test_runner(runner)
+                .unwrap()
+                .build()
+                .unwrap()
+        };
+
+        assert_eq!(
+            steps(&target(TestRunner::Vitest)),
+            "cd my-app\nnpm install\nnpm run dev\nnpm test"
+        );
+        assert!(!steps(&target(TestRunner::None)).contains("npm test"));
+    }
+
+    #[test]
+    fn plain_python_runs_the_project_module() {
+        let target = Target::builder()
+            .language(Language::Python)
+            .kind(ProjectKind::Cli)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(steps(&target).contains("python -m my_app"));
+    }
+}
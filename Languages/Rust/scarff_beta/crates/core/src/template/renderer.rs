@@ -3,15 +3,25 @@
 //! The renderer takes a template and a render context (variables) and
 //! produces a `ProjectStructure` ready to be written to disk.
 
-use std::path::PathBuf;
-use tracing::{debug, info, instrument, warn};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tracing::{debug, info, instrument};
 
 use crate::{
     domain::{
-        ProjectStructure, RenderContext, TemplateContent, TemplateNode, TemplateSource, validator,
+        Hook, ProjectStructure, RelativePath, RenderContext, Target, TemplateContent, TemplateNode,
+        TemplateSource, validator,
     },
     errors::CoreResult,
-    template::{Template, TemplateError},
+    template::{
+        Template, TemplateError,
+        backend::{BackendError, Backends, DEFAULT_ENGINE, TemplateBackend, Variables},
+        lint::{self, TemplateDiagnostic},
+        partials::{self, ExpandedCache, Partials},
+        readme,
+    },
 };
 
 // ============================================================================
@@ -21,9 +31,17 @@ use crate::{
 /// Renders templates into project structures.
 ///
 /// The renderer is responsible for:
-/// 1. Variable substitution ({{PROJECT_NAME}}, etc.)
-/// 2. Converting template nodes into filesystem entries
-/// 3. Validating the resulting structure
+/// 1. Expanding partials (`{{> id}}` includes and external content)
+/// 2. Rendering the content with the template's engine: by default the
+///    built-in syntax of `{{ project_name }}` variables, `{{#if}}`
+///    conditionals and `{{#each}}` loops, or a registered
+///    [`TemplateBackend`] the template names
+/// 3. Converting template nodes into filesystem entries
+/// 4. Rendering the template's hooks (arguments, scripts, steps) the same way
+/// 5. Validating the resulting structure
+///
+/// Expanded partials are cached for the renderer's lifetime, so shared
+/// snippets are only assembled once however many files and renders use them.
 ///
 /// ## Example
 ///
@@ -32,12 +50,45 @@ use crate::{
 /// let context = RenderContext::new("my-project");
 /// let structure = renderer.render(&template, &context, output_path)?;
 /// ```
-pub struct TemplateRenderer;
+pub struct TemplateRenderer {
+    partials: Partials,
+    expanded: Mutex<ExpandedCache>,
+    backends: Backends,
+}
 
 impl TemplateRenderer {
-    /// Create a new template renderer.
+    /// Create a new template renderer with the built-in partials and
+    /// engine.
     pub fn new() -> Self {
-        Self
+        Self {
+            partials: Partials::builtin(),
+            expanded: Mutex::new(ExpandedCache::new()),
+            backends: Backends::builtin(),
+        }
+    }
+
+    /// Add a template engine, replacing one registered under the same name.
+    pub fn register_backend(&self, backend: Arc<dyn TemplateBackend>) {
+        self.backends.register(backend);
+    }
+
+    /// Register (or replace) a partial.
+    ///
+    /// Primarily used for testing includes without touching the built-ins.
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_partial(
+        mut self,
+        id: crate::template::ContentTemplateId,
+        source: impl Into<TemplateSource>,
+    ) -> Self {
+        self.partials.insert(id, source);
+        // Anything cached may have included the old version
+        self.expanded
+            .get_mut()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clear();
+        self
     }
 
     /// Render a template into a project structure.
@@ -56,7 +107,10 @@ impl TemplateRenderer {
     ///
     /// Returns an error if:
     /// - Template has invalid content
-    /// - Variable substitution fails
+    /// - Content has a syntax error or uses an undefined variable
+    ///   ([`TemplateError::Syntax`], with the file and line)
+    /// - Content names an engine that isn't registered
+    ///   ([`TemplateError::UnknownEngine`])
     /// - Resulting structure is invalid (duplicates, absolute paths, etc.)
     ///
     /// # Example
@@ -73,7 +127,7 @@ impl TemplateRenderer {
     #[instrument(
         skip(self, template, ctx),
         fields(
-            template_name = template.metadata.name,
+            template_name = %template.metadata.name,
             output_root = %output_root.display()
         )
     )]
@@ -94,6 +148,11 @@ impl TemplateRenderer {
         // validate that template is not empty and has valid matcher, metadata and tree nodes
 
         let mut structure = ProjectStructure::new(output_root);
+        let engine = template
+            .metadata
+            .engine
+            .as_deref()
+            .unwrap_or(DEFAULT_ENGINE);
 
         // Process each node in the template tree
         for (idx, node) in template.tree.nodes.iter().enumerate() {
@@ -102,10 +161,19 @@ impl TemplateRenderer {
                 "Processing template node"
             );
 
+            // Optional nodes only for targets with their feature enabled
+            if let Some(feature) = node.feature()
+                && !ctx.has_feature(feature)
+            {
+                debug!(feature, "Skipping node of a disabled feature");
+                continue;
+            }
+
             match node {
                 TemplateNode::File(spec) => {
                     // Render file content
-                    let content = self.render_content(&spec.content, ctx)?;
+                    let engine = spec.engine.as_deref().unwrap_or(engine);
+                    let content = self.render_content(&spec.content, ctx, &spec.path, engine)?;
 
                     debug!(
                         path = %spec.path,
@@ -123,9 +191,23 @@ impl TemplateRenderer {
 
                     structure.add_directory(spec.path.as_path(), spec.permissions);
                 }
+                TemplateNode::Symlink(spec) => {
+                    // The target may name the project, like file content
+                    let target = self
+                        .backend(engine)?
+                        .render(&spec.target, &Variables::new(ctx))
+                        .map_err(|e| engine_error(engine, spec.path.to_string(), e))?;
+
+                    debug!(path = %spec.path, target, "Adding symlink");
+
+                    structure.add_symlink(spec.path.as_path(), target);
+                }
             }
         }
 
+        // Hooks see the same variables as file content
+        structure.hooks = self.render_hooks(template, ctx, engine)?;
+
         // Validate the structure before returning
 
         info!(
@@ -143,14 +225,64 @@ impl TemplateRenderer {
         Ok(structure)
     }
 
+    /// Render a template, generating a `README.md` if it has none.
+    ///
+    /// The README is an ordinary parameterized file: it is filled in from
+    /// `ctx` (see [`RenderContext::with_target`] and
+    /// [`RenderContext::with_template`]) plus next-step commands for the
+    /// target's language and framework. Templates that ship their own
+    /// README are rendered unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`TemplateRenderer::render`].
+    pub fn render_with_readme(
+        &self,
+        template: &Template,
+        target: &Target,
+        ctx: &RenderContext,
+        output_root: PathBuf,
+    ) -> CoreResult<ProjectStructure> {
+        if readme::has_readme(template) {
+            debug!("Template provides its own README");
+            return self.render(template, ctx, output_root);
+        }
+
+        let mut template = template.clone();
+        template.tree.push(readme::readme_node());
+        let ctx = readme::with_next_steps(ctx.clone(), target);
+
+        self.render(&template, &ctx, output_root)
+    }
+
+    /// Every problem in `template` found without rendering it, given the
+    /// partials this renderer knows (see [`lint`]).
+    pub fn lint(&self, template: &Template) -> Vec<TemplateDiagnostic> {
+        lint::lint(template, &self.partials)
+    }
+
     /// Render template content with variable substitution.
     ///
     /// # Content Types
     ///
-    /// - **Literal**: Return as-is (no substitution)
-    /// - **Parameterized**: Replace {{VARIABLE}} placeholders
-    /// - **External**: Not yet supported (post-MVP)
-    fn render_content(&self, content: &TemplateContent, ctx: &RenderContext) -> CoreResult<String> {
+    /// - **Literal**: Return as-is (no includes, no substitution)
+    /// - **Parameterized**: Expand `{{> id}}` includes, then render with
+    ///   `engine`. Syntax error lines count in the expanded content
+    /// - **External**: The partial with that id, rendered like parameterized
+    ///   content
+    fn render_content(
+        &self,
+        content: &TemplateContent,
+        ctx: &RenderContext,
+        path: &RelativePath,
+        engine: &str,
+    ) -> Result<String, TemplateError> {
+        let render = |source: &str| {
+            self.backend(engine)?
+                .render(source, &Variables::new(ctx))
+                .map_err(|e| engine_error(engine, path.to_string(), e))
+        };
+
         match content {
             TemplateContent::Literal(source) => {
                 // No variable substitution
@@ -158,9 +290,13 @@ impl TemplateRenderer {
             }
 
             TemplateContent::Parameterized(source) => {
-                // Perform variable substitution
-                let template_str = source.as_str();
-                let rendered = ctx.render(template_str);
+                // Expand includes, then perform variable substitution
+                let template_str = partials::expand_includes(
+                    source.as_str(),
+                    &self.partials,
+                    &mut *self.cache()?,
+                )?;
+                let rendered = render(&template_str)?;
 
                 debug!(
                     original_len = template_str.len(),
@@ -172,21 +308,69 @@ impl TemplateRenderer {
             }
 
             TemplateContent::External(template_id) => {
-                // External template engines (Tera, Handlebars, etc.)
-                // Not implemented in MVP
-                warn!(
-                    template_id = %template_id.0,
-                    "External template rendering not yet implemented"
-                );
+                let partial =
+                    partials::resolve(template_id.0, &self.partials, &mut *self.cache()?)?;
 
-                Err(crate::template::TemplateError::InvalidTemplate(format!(
-                    "External template '{}' not supported in MVP",
-                    template_id.0
-                ))
-                .into())
+                debug!(partial = %template_id.0, "Rendering partial as file content");
+
+                render(&partial)
             }
         }
     }
+
+    /// Render the strings of every hook with the template's `engine`;
+    /// errors name the hook as `hook[N]`.
+    fn render_hooks(
+        &self,
+        template: &Template,
+        ctx: &RenderContext,
+        engine: &str,
+    ) -> Result<Vec<Hook>, TemplateError> {
+        if template.hooks.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let backend = self.backend(engine)?;
+        let vars = Variables::new(ctx);
+        template
+            .hooks
+            .iter()
+            .enumerate()
+            .map(|(i, hook)| {
+                hook.try_map_strings(|s| {
+                    backend
+                        .render(s, &vars)
+                        .map_err(|e| engine_error(engine, format!("hook[{i}]"), e))
+                })
+            })
+            .collect()
+    }
+
+    fn backend(&self, engine: &str) -> Result<Arc<dyn TemplateBackend>, TemplateError> {
+        self.backends
+            .get(engine)
+            .ok_or_else(|| TemplateError::UnknownEngine(engine.to_string()))
+    }
+
+    fn cache(&self) -> Result<MutexGuard<'_, ExpandedCache>, TemplateError> {
+        self.expanded.lock().map_err(|_| TemplateError::LockError)
+    }
+}
+
+/// A backend's error for `file`: a syntax error when it has a line.
+fn engine_error(engine: &str, file: String, e: BackendError) -> TemplateError {
+    match e.line {
+        Some(line) => TemplateError::Syntax {
+            file,
+            line,
+            reason: e.message,
+        },
+        None => TemplateError::EngineFailed {
+            engine: engine.to_string(),
+            file,
+            reason: e.message,
+        },
+    }
 }
 
 impl Default for TemplateRenderer {
@@ -203,13 +387,10 @@ impl Default for TemplateRenderer {
 mod tests {
     use super::*;
     use crate::{
-        domain::{
-            Language, ProjectKind, TargetMatcher, TemplateId,
-            common::{Permissions, RelativePath},
-        },
+        domain::{Language, ProjectKind, SymlinkSpec, TargetMatcher, TemplateId},
         template::{
-            DirectorySpec, FileSpec, TemplateContent, TemplateMetadata, TemplateNode,
-            TemplateSource, TemplateTree,
+            ContentTemplateId, DirectorySpec, FileSpec, TemplateContent, TemplateMetadata,
+            TemplateNode, TemplateSource, TemplateTree,
         },
     };
 
@@ -233,6 +414,7 @@ mod tests {
                     "README.md",
                     TemplateContent::Literal(TemplateSource::Static("# My Project")),
                 ))),
+            hooks: Vec::new(),
         }
     }
 
@@ -311,6 +493,7 @@ mod tests {
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree,
+            hooks: Vec::new(),
         };
 
         let context = RenderContext::new("test");
@@ -330,6 +513,7 @@ mod tests {
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree: TemplateTree::new(), // Empty!
+            hooks: Vec::new(),
         };
 
         let context = RenderContext::new("test");
@@ -359,6 +543,7 @@ mod tests {
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree,
+            hooks: Vec::new(),
         };
 
         let context = RenderContext::new("test");
@@ -398,6 +583,7 @@ year = {{YEAR}}
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree,
+            hooks: Vec::new(),
         };
 
         let context = RenderContext::new("MyAwesomeProject");
@@ -420,7 +606,402 @@ year = {{YEAR}}
     }
 
     #[test]
-    fn render_external_template_not_supported() {
+    fn render_with_readme_fills_in_metadata_and_options() {
+        let renderer = TemplateRenderer::new();
+        let mut template = create_test_template("test");
+        template.tree.nodes.retain(|node| match node {
+            TemplateNode::File(spec) => !spec.path.as_path().ends_with("README.md"),
+            TemplateNode::Directory(_) | TemplateNode::Symlink(_) => true,
+        });
+        template.metadata = template.metadata.description("A tiny CLI");
+        let target = Target::rust_cli().unwrap();
+        let context = RenderContext::new("my-cli")
+            .with_target(&target)
+            .with_template(&template.metadata);
+
+        let structure = renderer
+            .render_with_readme(&template, &target, &context, PathBuf::from("/tmp/test"))
+            .unwrap();
+
+        let readme = structure
+            .files()
+            .find(|f| f.path.ends_with("README.md"))
+            .unwrap();
+        assert!(readme.content.starts_with("# my-cli\n\nA tiny CLI\n"));
+        assert!(readme.content.contains("cd my-cli\ncargo run -- --help"));
+        assert!(readme.content.contains("| Language     | rust "));
+        assert!(readme.content.contains("**test** template (v1.0.0)"));
+        assert!(!readme.content.contains("{{"));
+    }
+
+    #[test]
+    fn render_with_readme_keeps_template_readme() {
+        let renderer = TemplateRenderer::new();
+        let template = create_test_template("test");
+        let target = Target::rust_cli().unwrap();
+
+        let structure = renderer
+            .render_with_readme(
+                &template,
+                &target,
+                &RenderContext::new("my-cli"),
+                PathBuf::from("/tmp/test"),
+            )
+            .unwrap();
+
+        let readme = structure
+            .files()
+            .find(|f| f.path.ends_with("README.md"))
+            .unwrap();
+        assert_eq!(readme.content, "# My Project");
+        assert_eq!(structure.file_count(), 2);
+    }
+
+    fn single_file_template(content: TemplateContent) -> Template {
+        Template {
+            id: TemplateId::new("test", "0.1.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("test"),
+            tree: TemplateTree::new().with_node(TemplateNode::File(FileSpec::new("file", content))),
+            hooks: Vec::new(),
+        }
+    }
+
+    fn render_single_file(renderer: &TemplateRenderer, content: TemplateContent) -> String {
+        let structure = renderer
+            .render(
+                &single_file_template(content),
+                &RenderContext::new("my-cli"),
+                PathBuf::from("/tmp/test"),
+            )
+            .unwrap();
+        structure.files().next().unwrap().content.clone()
+    }
+
+    #[test]
+    fn render_expands_includes_before_variables() {
+        let renderer = TemplateRenderer::new()
+            .with_partial(ContentTemplateId("header"), "// {{PROJECT_NAME}}\n");
+
+        let content = render_single_file(
+            &renderer,
+            TemplateContent::Parameterized(TemplateSource::Static("{{> header}}\nfn main() {}\n")),
+        );
+
+        assert_eq!(content, "// my-cli\nfn main() {}\n");
+    }
+
+    #[test]
+    fn render_external_content_uses_partial() {
+        let renderer = TemplateRenderer::new();
+
+        let content = render_single_file(
+            &renderer,
+            TemplateContent::External(ContentTemplateId("rust/error-module")),
+        );
+
+        assert!(content.starts_with("// Copyright (c) "));
+        assert!(content.contains("my-cli contributors\n"));
+        assert!(content.contains("//! Error types for my-cli."));
+    }
+
+    #[test]
+    fn with_partial_replaces_cached_expansion() {
+        let renderer = TemplateRenderer::new().with_partial(ContentTemplateId("p"), "old");
+        let external = || TemplateContent::External(ContentTemplateId("p"));
+        assert_eq!(render_single_file(&renderer, external()), "old");
+
+        let renderer = renderer.with_partial(ContentTemplateId("p"), "new");
+        assert_eq!(render_single_file(&renderer, external()), "new");
+    }
+
+    #[test]
+    fn render_evaluates_conditionals_and_loops() {
+        let renderer = TemplateRenderer::new();
+        let context = RenderContext::new("my-cli")
+            .with_var("AUTHOR", "Ada")
+            .with_list("BINS", ["server", "worker"]);
+        let template = single_file_template(TemplateContent::Parameterized(
+            TemplateSource::Static(
+                "name = \"{{ project_name }}\"\n{{#if author}}\nauthors = [\"{{ author }}\"]\n{{/if}}\n{{#each bins as bin}}\n[[bin]] {{ bin }}\n{{/each}}\n",
+            ),
+        ));
+
+        let structure = renderer
+            .render(&template, &context, PathBuf::from("/tmp/test"))
+            .unwrap();
+
+        assert_eq!(
+            structure.files().next().unwrap().content,
+            "name = \"my-cli\"\nauthors = [\"Ada\"]\n[[bin]] server\n[[bin]] worker\n"
+        );
+    }
+
+    #[test]
+    fn render_generates_feature_nodes_only_when_enabled() {
+        let renderer = TemplateRenderer::new();
+        let template = Template {
+            id: TemplateId::new("featured", "1.0.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("featured"),
+            tree: TemplateTree::new()
+                .with_node(TemplateNode::File(FileSpec::new(
+                    "README.md",
+                    TemplateContent::Parameterized(TemplateSource::Static(
+                        "{{#if feature_docker}}docker run {{project_name}}{{else}}no docker{{/if}}",
+                    )),
+                )))
+                .with_node(TemplateNode::Directory(
+                    DirectorySpec::new(".github").feature("ci-github"),
+                ))
+                .with_node(TemplateNode::File(
+                    FileSpec::new(
+                        "Dockerfile",
+                        TemplateContent::Literal(TemplateSource::Static("FROM rust\n")),
+                    )
+                    .feature("docker"),
+                )),
+            hooks: Vec::new(),
+        };
+        assert_eq!(template.tree.features(), ["ci-github", "docker"]);
+
+        let render = |target: Target| {
+            let context = RenderContext::new("app").with_target(&target);
+            renderer
+                .render(&template, &context, PathBuf::from("/tmp/test"))
+                .unwrap()
+        };
+
+        let plain = render(Target::rust_cli().unwrap());
+        assert_eq!(plain.file_count(), 1);
+        assert_eq!(plain.directory_count(), 0);
+        assert_eq!(plain.files().next().unwrap().content, "no docker");
+
+        let docker = render(
+            Target::builder()
+                .language(Language::Rust)
+                .feature("docker")
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(docker.file_count(), 2);
+        assert_eq!(docker.directory_count(), 0);
+        assert_eq!(docker.files().next().unwrap().content, "docker run app");
+    }
+
+    /// `${NAME}` substitution, as a stand-in for a real engine.
+    struct Dollar;
+
+    impl TemplateBackend for Dollar {
+        fn name(&self) -> &str {
+            "dollar"
+        }
+
+        fn render(&self, source: &str, vars: &Variables<'_>) -> Result<String, BackendError> {
+            let mut out = source.to_string();
+            while let Some(start) = out.find("${") {
+                let end = out[start..]
+                    .find('}')
+                    .ok_or_else(|| BackendError::new("unclosed ${"))?;
+                let name = &out[start + 2..start + end];
+                let value = vars
+                    .get(name)
+                    .ok_or_else(|| BackendError::new(format!("no variable {name}")))?;
+                out.replace_range(start..=start + end, value);
+            }
+            Ok(out)
+        }
+    }
+
+    #[test]
+    fn render_uses_the_engine_the_template_selects() {
+        let renderer = TemplateRenderer::new();
+        renderer.register_backend(Arc::new(Dollar));
+
+        let mut template = Template {
+            id: TemplateId::new("dollar", "1.0.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("dollar").engine("dollar"),
+            tree: TemplateTree::new()
+                .with_node(TemplateNode::File(FileSpec::new(
+                    "name.txt",
+                    TemplateContent::Parameterized(TemplateSource::Static("${PROJECT_NAME}")),
+                )))
+                .with_node(TemplateNode::File(
+                    FileSpec::new(
+                        "simple.txt",
+                        TemplateContent::Parameterized(TemplateSource::Static(
+                            "{{PROJECT_NAME}} ${kept}",
+                        )),
+                    )
+                    .engine(DEFAULT_ENGINE),
+                )),
+            hooks: vec![Hook::next_steps(["cd ${PROJECT_NAME}"])],
+        };
+        let target = Target::rust_cli().unwrap();
+        let context = RenderContext::new("app")
+            .with_target(&target)
+            .with_template(&template.metadata);
+
+        let structure = renderer
+            .render_with_readme(&template, &target, &context, PathBuf::from("/tmp/test"))
+            .unwrap();
+        let contents: Vec<_> = structure.files().map(|f| f.content.as_str()).collect();
+        assert_eq!(contents[..2], ["app", "app ${kept}"]);
+        // The generated README is in the built-in syntax
+        assert!(contents[2].starts_with("# app"));
+        assert_eq!(structure.hooks, [Hook::next_steps(["cd app"])]);
+
+        // Errors name the engine and file
+        template.tree.push(TemplateNode::File(FileSpec::new(
+            "broken.txt",
+            TemplateContent::Parameterized(TemplateSource::Static("${NOPE}")),
+        )));
+        let err = renderer
+            .render(&template, &context, PathBuf::from("/tmp/test"))
+            .unwrap_err();
+        assert_eq!(err.code(), "template.engine_failed");
+        assert_eq!(err.context()["engine"], "dollar");
+        assert_eq!(err.context()["file"], "broken.txt");
+    }
+
+    #[test]
+    fn render_rejects_an_unregistered_engine() {
+        let renderer = TemplateRenderer::new();
+        let mut template = create_test_template("tera");
+        template.metadata = template.metadata.engine("tera");
+
+        let err = renderer
+            .render(&template, &RenderContext::new("app"), PathBuf::from("/tmp"))
+            .unwrap_err();
+
+        assert_eq!(err.code(), "template.unknown_engine");
+        assert_eq!(err.context()["engine"], "tera");
+    }
+
+    #[test]
+    fn render_reports_syntax_errors_with_file_and_line() {
+        let renderer = TemplateRenderer::new();
+        let template = single_file_template(TemplateContent::Parameterized(
+            TemplateSource::Static("// {{PROJECT_NAME}}\n{{#if AUTHOR}}\n// by {{AUTHOR}}\n"),
+        ));
+
+        let err = renderer
+            .render(
+                &template,
+                &RenderContext::new("test"),
+                PathBuf::from("/tmp/test"),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.code(), "template.syntax");
+        assert_eq!(err.context()["file"], "file");
+        assert_eq!(err.context()["line"], "2");
+    }
+
+    #[test]
+    fn render_substitutes_variables_in_hooks() {
+        let renderer = TemplateRenderer::new();
+        let mut template = single_file_template(TemplateContent::Literal("x".into()));
+        template.hooks = vec![
+            Hook::command("cargo", ["new", "{{PROJECT_NAME}}"]),
+            Hook::next_steps(["cd {{PROJECT_NAME}}"]),
+        ];
+
+        let structure = renderer
+            .render(
+                &template,
+                &RenderContext::new("demo"),
+                PathBuf::from("/tmp/demo"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            structure.hooks,
+            vec![
+                Hook::command("cargo", ["new", "demo"]),
+                Hook::next_steps(["cd demo"]),
+            ]
+        );
+
+        template.hooks = vec![Hook::script("echo {{#if}}")];
+        let err = renderer
+            .render(
+                &template,
+                &RenderContext::new("demo"),
+                PathBuf::from("/tmp/demo"),
+            )
+            .unwrap_err();
+        assert_eq!(err.context()["file"], "hook[0]");
+    }
+
+    #[test]
+    fn render_fills_in_symlink_targets_and_keeps_permissions() {
+        let renderer = TemplateRenderer::new();
+        let mut template = create_test_template("test");
+        template.tree.push(TemplateNode::File(
+            FileSpec::new(
+                "scripts/run.sh",
+                TemplateContent::Literal(TemplateSource::Static("#!/bin/sh\n")),
+            )
+            .executable(),
+        ));
+        template.tree.push(TemplateNode::Symlink(SymlinkSpec::new(
+            "bin/{{PROJECT_NAME}}",
+            "../scripts/{{PROJECT_NAME}}.sh",
+        )));
+
+        let structure = renderer
+            .render(
+                &template,
+                &RenderContext::new("demo"),
+                PathBuf::from("/tmp/demo"),
+            )
+            .unwrap();
+
+        let script = structure.files().last().unwrap();
+        assert!(script.permissions.executable_flag());
+        let link = structure.symlinks().next().unwrap();
+        assert_eq!(
+            link.path,
+            PathBuf::from("bin/{{PROJECT_NAME}}"),
+            "paths aren't rendered"
+        );
+        assert_eq!(link.target, PathBuf::from("../scripts/demo.sh"));
+
+        template
+            .tree
+            .push(TemplateNode::Symlink(SymlinkSpec::new("up", "../..")));
+        let err = renderer
+            .render(
+                &template,
+                &RenderContext::new("demo"),
+                PathBuf::from("/tmp/demo"),
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("outside the project"), "{err}");
+    }
+
+    #[test]
+    fn render_reports_include_cycles() {
+        let renderer = TemplateRenderer::new()
+            .with_partial(ContentTemplateId("a"), "{{> b}}")
+            .with_partial(ContentTemplateId("b"), "{{> a}}");
+
+        let err = renderer
+            .render(
+                &single_file_template(TemplateContent::External(ContentTemplateId("a"))),
+                &RenderContext::new("test"),
+                PathBuf::from("/tmp/test"),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.code(), "template.include_cycle");
+    }
+
+    #[test]
+    fn render_unknown_external_template_fails() {
         let renderer = TemplateRenderer::new();
 
         let tree = TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
@@ -433,13 +1014,14 @@ year = {{YEAR}}
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree,
+            hooks: Vec::new(),
         };
 
         let context = RenderContext::new("test");
 
         let result = renderer.render(&template, &context, PathBuf::from("/tmp/test"));
 
-        // Should error for external templates in MVP
-        assert!(result.is_err());
+        // No partial by that id
+        assert_eq!(result.unwrap_err().code(), "template.unknown_partial");
     }
 }
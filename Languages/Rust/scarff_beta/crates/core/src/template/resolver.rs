@@ -99,7 +99,7 @@ impl TemplateResolver {
         if matches.len() == 1 {
             let template = matches.into_iter().next().unwrap();
             info!(
-                template_name = template.metadata.name,
+                template_name = %template.metadata.name,
                 "Resolved to single matching template"
             );
             return Ok(template);
@@ -124,7 +124,10 @@ impl TemplateResolver {
 
         // Step 5: Check for ambiguous matches
         if most_specific.len() > 1 {
-            let template_names: Vec<_> = most_specific.iter().map(|t| t.metadata.name).collect();
+            let template_names: Vec<_> = most_specific
+                .iter()
+                .map(|t| t.metadata.name.as_str())
+                .collect();
 
             debug!(
                 ?template_names,
@@ -143,7 +146,7 @@ impl TemplateResolver {
 
         validator::validate_template(&template)?;
         info!(
-            template_name = template.metadata.name,
+            template_name = %template.metadata.name,
             specificity = max_specificity,
             "Resolved to most specific template"
         );
@@ -177,6 +180,12 @@ impl TemplateResolver {
         Ok(matches)
     }
 
+    /// Where a template comes from, if the store knows (see
+    /// `Store::origin`).
+    pub fn origin(&self, id: &crate::template::TemplateId) -> Option<String> {
+        self.store.origin(id)
+    }
+
     /// Get a specific template by name and version.
     ///
     /// This bypasses matching and directly retrieves a template by ID.
@@ -214,6 +223,7 @@ mod tests {
                 framework: None,
                 kind,
                 architecture,
+                features: Vec::new(),
             },
             metadata: TemplateMetadata::new(name).version("1.0.0"),
             tree: TemplateTree::new().with_node(crate::template::TemplateNode::File(
@@ -222,6 +232,7 @@ mod tests {
                     TemplateContent::Literal(TemplateSource::Static("fn main() {}")),
                 ),
             )),
+            hooks: Vec::new(),
         }
     }
 
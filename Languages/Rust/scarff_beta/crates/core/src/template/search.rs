@@ -0,0 +1,239 @@
+//! Searching the templates an engine knows.
+//!
+//! A [`TemplateQuery`] is free text plus optional filters. Every
+//! whitespace-separated term must appear (case-insensitively) in the name,
+//! the description, a tag, or the language or framework the template is
+//! for. Results are ranked like the community index search: an exact name
+//! first, then an exact tag or framework, then other hits, ties by name.
+
+use crate::domain::{Architecture, Language, ProjectKind, Template};
+
+/// What to search for: text, narrowed by what the template is for.
+///
+/// A filter keeps templates made for that value and templates that accept
+/// any (e.g. a template without a language matches every language filter).
+///
+/// ```rust,ignore
+/// let hits = engine.search_templates(
+///     TemplateQuery::new("axum").kind(ProjectKind::WebBackend),
+/// )?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateQuery {
+    /// Search terms; empty matches every template
+    pub text: String,
+    /// Only templates for this language
+    pub language: Option<Language>,
+    /// Only templates for this kind of project
+    pub kind: Option<ProjectKind>,
+    /// Only templates for this architecture
+    pub architecture: Option<Architecture>,
+}
+
+impl TemplateQuery {
+    /// Search for `text`, unfiltered.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Only templates for `language` (builder style).
+    #[must_use]
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// Only templates for `kind` (builder style).
+    #[must_use]
+    pub fn kind(mut self, kind: ProjectKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Only templates for `architecture` (builder style).
+    #[must_use]
+    pub fn architecture(mut self, architecture: Architecture) -> Self {
+        self.architecture = Some(architecture);
+        self
+    }
+
+    /// Whether `template` passes the filters and contains every term.
+    pub(crate) fn matches(&self, template: &Template) -> bool {
+        let matcher = &template.matcher;
+        let passes = self
+            .language
+            .is_none_or(|l| matcher.language.is_none_or(|m| m == l))
+            && self
+                .kind
+                .is_none_or(|k| matcher.kind.is_none_or(|m| m == k))
+            && self
+                .architecture
+                .is_none_or(|a| matcher.architecture.is_none_or(|m| m == a));
+        if !passes {
+            return false;
+        }
+
+        let haystack = [
+            template.metadata.name.to_lowercase(),
+            template.metadata.description.to_lowercase(),
+            matcher
+                .language
+                .map(|l| l.to_string())
+                .unwrap_or_default()
+                .to_lowercase(),
+            matcher
+                .framework
+                .map(|f| f.to_string())
+                .unwrap_or_default()
+                .to_lowercase(),
+        ];
+        self.text.split_whitespace().all(|term| {
+            let term = term.to_lowercase();
+            haystack.iter().any(|field| field.contains(&term))
+                || template
+                    .metadata
+                    .tags
+                    .iter()
+                    .any(|tag| tag.eq_ignore_ascii_case(&term))
+        })
+    }
+
+    /// How well `template` matches: exact name, then exact tag or
+    /// framework, then anything else. Higher is better.
+    fn score(&self, template: &Template) -> u8 {
+        let text = self.text.trim();
+        if template.metadata.name.eq_ignore_ascii_case(text) {
+            3
+        } else if template
+            .metadata
+            .tags
+            .iter()
+            .any(|tag| tag.eq_ignore_ascii_case(text))
+            || template
+                .matcher
+                .framework
+                .is_some_and(|f| f.to_string().eq_ignore_ascii_case(text))
+        {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+impl From<&str> for TemplateQuery {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for TemplateQuery {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// The templates matching `query`, best first.
+pub(crate) fn search(templates: Vec<Template>, query: &TemplateQuery) -> Vec<Template> {
+    let mut hits: Vec<_> = templates
+        .into_iter()
+        .filter(|template| query.matches(template))
+        .collect();
+    hits.sort_by(|a, b| {
+        query
+            .score(b)
+            .cmp(&query.score(a))
+            .then_with(|| a.metadata.name.cmp(&b.metadata.name))
+    });
+    hits
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        FileSpec, Framework, RustFramework, TargetMatcher, TemplateContent, TemplateId,
+        TemplateMetadata, TemplateNode, TemplateSource, TemplateTree,
+    };
+
+    fn template(name: &str, matcher: TargetMatcher, tags: &[&str]) -> Template {
+        Template {
+            id: TemplateId::new(name, "1.0.0".to_string()),
+            matcher,
+            metadata: TemplateMetadata::new(name)
+                .version("1.0.0")
+                .tags(tags.iter().copied()),
+            tree: TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
+                "README.md",
+                TemplateContent::Literal(TemplateSource::Static("")),
+            ))),
+            hooks: Vec::new(),
+        }
+    }
+
+    fn templates() -> Vec<Template> {
+        vec![
+            template(
+                "rust-axum-postgres",
+                TargetMatcher::builder()
+                    .language(Language::Rust)
+                    .framework(Framework::Rust(RustFramework::Axum))
+                    .kind(ProjectKind::WebBackend)
+                    .build(),
+                &["backend"],
+            ),
+            template(
+                "axum",
+                TargetMatcher::builder()
+                    .language(Language::Rust)
+                    .kind(ProjectKind::WebBackend)
+                    .build(),
+                &[],
+            ),
+            template(
+                "rust-cli",
+                TargetMatcher::builder()
+                    .language(Language::Rust)
+                    .kind(ProjectKind::Cli)
+                    .build(),
+                &["cli"],
+            ),
+            template("editorconfig", TargetMatcher::builder().build(), &[]),
+        ]
+    }
+
+    fn names(hits: &[Template]) -> Vec<&str> {
+        hits.iter().map(|t| t.metadata.name.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_exact_name_before_framework_and_substring_hits() {
+        let hits = search(templates(), &TemplateQuery::new("axum"));
+
+        assert_eq!(names(&hits), ["axum", "rust-axum-postgres"]);
+    }
+
+    #[test]
+    fn every_term_must_match() {
+        assert_eq!(
+            names(&search(templates(), &"rust backend".into())),
+            ["rust-axum-postgres"]
+        );
+        assert_eq!(search(templates(), &"python".into()).len(), 0);
+        assert_eq!(search(templates(), &"".into()).len(), 4);
+    }
+
+    #[test]
+    fn filters_keep_templates_that_accept_any_value() {
+        let hits = search(templates(), &TemplateQuery::new("").kind(ProjectKind::Cli));
+
+        assert_eq!(names(&hits), ["editorconfig", "rust-cli"]);
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This module provides storage abstractions for templates:
 //! - In-memory store (for built-in templates)
-//! - Extensible trait for future stores (filesystem, remote registry)
+//! - Extensible trait for other stores: filesystem ([`FsStore`](super::FsStore)),
+//!   remote registry
 
 use std::{
     collections::HashMap,
@@ -80,6 +81,12 @@ pub trait Store: Send + Sync {
     ///
     /// Returns `TemplateError::NotFound` if the template doesn't exist.
     fn remove(&self, id: &TemplateId) -> CoreResult<()>;
+
+    /// Where a template comes from (`builtin`, a directory, a remote
+    /// source), for stores that combine several; `None` otherwise.
+    fn origin(&self, _id: &TemplateId) -> Option<String> {
+        None
+    }
 }
 
 // ============================================================================
@@ -219,8 +226,8 @@ impl Store for InMemoryStore {
 
         // Create TemplateId from metadata
         let id = TemplateId::new(
-            template.metadata.name,
-            template.metadata.version.to_string(),
+            template.metadata.name.clone(),
+            template.metadata.version.clone(),
         );
 
         // Insert or update (idempotent operation)
@@ -293,6 +300,7 @@ mod tests {
                     TemplateContent::Literal(TemplateSource::Static("fn main() {}")),
                 ),
             )),
+            hooks: Vec::new(),
         }
     }
 
@@ -354,7 +362,7 @@ mod tests {
     #[test]
     fn validate_invalid_template_empty_name() {
         let mut template = create_test_template("test");
-        template.metadata.name = "";
+        template.metadata.name = String::new();
 
         let result = validate_template(&template);
         assert!(result.is_err());
@@ -367,6 +375,7 @@ mod tests {
             matcher: TargetMatcher::builder().build(),
             metadata: TemplateMetadata::new("test"),
             tree: TemplateTree::new(),
+            hooks: Vec::new(),
         };
 
         let result = validate_template(&template);
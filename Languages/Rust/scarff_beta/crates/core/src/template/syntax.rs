@@ -0,0 +1,665 @@
+//! Template syntax: variables, conditionals and loops.
+//!
+//! Parameterized content is rendered against a [`RenderContext`]:
+//!
+//! - `{{ project_name }}` is replaced by the variable's value. Names are
+//!   looked up as written, then upper-cased, so `{{ author }}` reads
+//!   `AUTHOR`
+//! - `{{#if NAME}}…{{else}}…{{/if}}` keeps one branch. A variable is true
+//!   when it's set to anything but an empty string, `false` or `none`; a
+//!   list when it has items. `{{#unless NAME}}…{{/unless}}` is the negation
+//! - `{{#each LIST as item}}…{{/each}}` repeats its body for every item of
+//!   a list variable (see [`RenderContext::with_list`]) with the item bound
+//!   to `item`, the name used when `as …` is left out
+//! - `\{{` writes a literal `{{`
+//!
+//! A block tag alone on its line takes the line with it, so blocks don't
+//! leave blank lines behind. Mistakes (an undefined variable, a block
+//! that's never closed, a stray `{{/if}}`) are reported with their line.
+
+use crate::domain::RenderContext;
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+const ESCAPE: char = '\\';
+
+/// Loop variable of an `{{#each}}` without `as`.
+const DEFAULT_BINDING: &str = "item";
+
+/// A mistake in a template on a (1-based) `line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SyntaxError {
+    pub(crate) line: usize,
+    pub(crate) reason: String,
+}
+
+impl SyntaxError {
+    fn new(line: usize, reason: impl Into<String>) -> Self {
+        Self {
+            line,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Render `source` against `ctx`.
+///
+/// # Errors
+///
+/// A [`SyntaxError`] for malformed tags, unbalanced blocks and variables
+/// `ctx` doesn't define.
+pub(crate) fn render(source: &str, ctx: &RenderContext) -> Result<String, SyntaxError> {
+    let nodes = parse(tokenize(source)?)?;
+    let mut out = String::with_capacity(source.len());
+    let mut scope = Scope {
+        ctx,
+        locals: Vec::new(),
+    };
+    render_nodes(&nodes, &mut scope, &mut out)?;
+    Ok(out)
+}
+
+// ============================================================================
+// Tokens
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    If,
+    Unless,
+    Each,
+}
+
+impl Block {
+    fn name(self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::Unless => "unless",
+            Self::Each => "each",
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Tag {
+    Var(String),
+    If { name: String, negate: bool },
+    Each { list: String, binding: String },
+    Else,
+    End(Block),
+}
+
+impl Tag {
+    fn is_block(&self) -> bool {
+        !matches!(self, Self::Var(_))
+    }
+}
+
+/// Text and tags, alternating: texts (possibly empty) at even indices.
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Tag { tag: Tag, line: usize },
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, SyntaxError> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut line = 1;
+    let mut rest = source;
+
+    while let Some(start) = rest.find(OPEN) {
+        let (before, after) = rest.split_at(start);
+        line += before.matches('\n').count();
+
+        if let Some(literal) = before.strip_suffix(ESCAPE) {
+            text.push_str(literal);
+            text.push_str(OPEN);
+            rest = &after[OPEN.len()..];
+            continue;
+        }
+        text.push_str(before);
+
+        let Some(end) = after[OPEN.len()..].find(CLOSE) else {
+            return Err(SyntaxError::new(
+                line,
+                "`{{` is never closed with `}}` (write `\\{{` for a literal `{{`)",
+            ));
+        };
+        let inner = &after[OPEN.len()..OPEN.len() + end];
+        tokens.push(Token::Text(std::mem::take(&mut text)));
+        tokens.push(Token::Tag {
+            tag: parse_tag(inner, line)?,
+            line,
+        });
+        line += inner.matches('\n').count();
+        rest = &after[OPEN.len() + end + CLOSE.len()..];
+    }
+
+    text.push_str(rest);
+    tokens.push(Token::Text(text));
+    strip_standalone(&mut tokens);
+    Ok(tokens)
+}
+
+fn parse_tag(inner: &str, line: usize) -> Result<Tag, SyntaxError> {
+    let words: Vec<&str> = inner.split_whitespace().collect();
+    let name = |word: &str| {
+        if is_name(word) {
+            Ok(word.to_owned())
+        } else {
+            Err(SyntaxError::new(
+                line,
+                format!("`{word}` isn't a variable name (letters, digits and `_`)"),
+            ))
+        }
+    };
+
+    Ok(match words.as_slice() {
+        ["#if", var] => Tag::If {
+            name: name(var)?,
+            negate: false,
+        },
+        ["#unless", var] => Tag::If {
+            name: name(var)?,
+            negate: true,
+        },
+        ["#each", list] => Tag::Each {
+            list: name(list)?,
+            binding: DEFAULT_BINDING.to_owned(),
+        },
+        ["#each", list, "as", binding] => Tag::Each {
+            list: name(list)?,
+            binding: name(binding)?,
+        },
+        ["else"] => Tag::Else,
+        ["/if"] => Tag::End(Block::If),
+        ["/unless"] => Tag::End(Block::Unless),
+        ["/each"] => Tag::End(Block::Each),
+        [var] if is_name(var) => Tag::Var((*var).to_owned()),
+        _ => {
+            return Err(SyntaxError::new(
+                line,
+                format!(
+                    "unrecognized tag `{{{{{inner}}}}}`: expected a variable, \
+                     `#if`, `#unless`, `#each`, `else` or a closing tag"
+                ),
+            ));
+        }
+    })
+}
+
+fn is_name(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Drop the line of every block tag that's alone on it.
+///
+/// Decided on the original texts first, so two such tags on consecutive
+/// lines both see the newline between them.
+fn strip_standalone(tokens: &mut [Token]) {
+    let last = tokens.len() - 1;
+    let text = |i: usize| match &tokens[i] {
+        Token::Text(text) => text.as_str(),
+        Token::Tag { .. } => unreachable!("texts and tags alternate"),
+    };
+    let standalone: Vec<bool> = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| match token {
+            Token::Tag { tag, .. } => {
+                tag.is_block()
+                    && ends_in_blank_line(text(i - 1), i - 1 == 0)
+                    && starts_with_blank_line(text(i + 1), i + 1 == last)
+            }
+            Token::Text(_) => false,
+        })
+        .collect();
+
+    for (i, token) in tokens.iter_mut().enumerate() {
+        let Token::Text(text) = token else { continue };
+        let start = if i > 0 && standalone[i - 1] {
+            text.find('\n').map_or(text.len(), |nl| nl + 1)
+        } else {
+            0
+        };
+        let end = if i < last && standalone[i + 1] {
+            text.rfind('\n').map_or(0, |nl| nl + 1)
+        } else {
+            text.len()
+        };
+        *text = text[start..end.max(start)].to_owned();
+    }
+}
+
+/// Only whitespace since the last newline (or the start of the source)
+fn ends_in_blank_line(text: &str, is_first: bool) -> bool {
+    match text.rfind('\n') {
+        Some(nl) => text[nl + 1..].trim().is_empty(),
+        None => is_first && text.trim().is_empty(),
+    }
+}
+
+/// Only whitespace up to the next newline (or the end of the source)
+fn starts_with_blank_line(text: &str, is_last: bool) -> bool {
+    match text.find('\n') {
+        Some(nl) => text[..nl].trim().is_empty(),
+        None => is_last && text.trim().is_empty(),
+    }
+}
+
+// ============================================================================
+// Syntax tree
+// ============================================================================
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var {
+        name: String,
+        line: usize,
+    },
+    If {
+        name: String,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        list: String,
+        binding: String,
+        body: Vec<Node>,
+        line: usize,
+    },
+}
+
+fn parse(tokens: Vec<Token>) -> Result<Vec<Node>, SyntaxError> {
+    let (nodes, end) = parse_until(&mut tokens.into_iter())?;
+    match end {
+        None => Ok(nodes),
+        Some((tag, line)) => Err(SyntaxError::new(
+            line,
+            format!("`{}` without an open block", describe(&tag)),
+        )),
+    }
+}
+
+/// Nodes up to the next `else` or closing tag (returned with its line), or
+/// to the end of the tokens.
+#[allow(clippy::type_complexity)]
+fn parse_until(
+    tokens: &mut impl Iterator<Item = Token>,
+) -> Result<(Vec<Node>, Option<(Tag, usize)>), SyntaxError> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let (tag, line) = match token {
+            Token::Text(text) => {
+                if !text.is_empty() {
+                    nodes.push(Node::Text(text));
+                }
+                continue;
+            }
+            Token::Tag { tag, line } => (tag, line),
+        };
+
+        match tag {
+            Tag::Var(name) => nodes.push(Node::Var { name, line }),
+            Tag::If { name, negate } => {
+                let block = if negate { Block::Unless } else { Block::If };
+                let (then, end) = parse_until(tokens)?;
+                let otherwise = match end {
+                    Some((Tag::Else, _)) => {
+                        let (otherwise, end) = parse_until(tokens)?;
+                        close(block, end, line)?;
+                        otherwise
+                    }
+                    end => {
+                        close(block, end, line)?;
+                        Vec::new()
+                    }
+                };
+                nodes.push(Node::If {
+                    name,
+                    negate,
+                    then,
+                    otherwise,
+                });
+            }
+            Tag::Each { list, binding } => {
+                let (body, end) = parse_until(tokens)?;
+                close(Block::Each, end, line)?;
+                nodes.push(Node::Each {
+                    list,
+                    binding,
+                    body,
+                    line,
+                });
+            }
+            Tag::Else | Tag::End(_) => return Ok((nodes, Some((tag, line)))),
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+/// Check that `end` closes the `block` opened on `line`.
+fn close(block: Block, end: Option<(Tag, usize)>, line: usize) -> Result<(), SyntaxError> {
+    match end {
+        Some((Tag::End(closed), _)) if closed == block => Ok(()),
+        Some((tag, at)) => Err(SyntaxError::new(
+            at,
+            format!(
+                "unexpected `{}`: the `{{{{#{name}}}}}` on line {line} is closed by `{{{{/{name}}}}}`",
+                describe(&tag),
+                name = block.name(),
+            ),
+        )),
+        None => Err(SyntaxError::new(
+            line,
+            format!(
+                "`{{{{#{name}}}}}` is never closed with `{{{{/{name}}}}}`",
+                name = block.name()
+            ),
+        )),
+    }
+}
+
+/// An `else` or closing tag as written.
+fn describe(tag: &Tag) -> String {
+    match tag {
+        Tag::Else => "{{else}}".to_owned(),
+        Tag::End(block) => format!("{{{{/{}}}}}", block.name()),
+        Tag::Var(_) | Tag::If { .. } | Tag::Each { .. } => unreachable!("only ends a block"),
+    }
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+enum Value<'a> {
+    Text(&'a str),
+    List(&'a [String]),
+}
+
+struct Scope<'a> {
+    ctx: &'a RenderContext,
+    /// Loop variables, innermost last
+    locals: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Scope<'a> {
+    fn lookup(&self, name: &str) -> Option<Value<'a>> {
+        if let Some(&(_, value)) = self.locals.iter().rev().find(|(local, _)| *local == name) {
+            return Some(Value::Text(value));
+        }
+        let upper = name.to_ascii_uppercase();
+        [name, upper.as_str()].into_iter().find_map(|key| {
+            self.ctx
+                .get(key)
+                .map(Value::Text)
+                .or_else(|| self.ctx.list(key).map(Value::List))
+        })
+    }
+}
+
+fn render_nodes<'a>(
+    nodes: &'a [Node],
+    scope: &mut Scope<'a>,
+    out: &mut String,
+) -> Result<(), SyntaxError> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var { name, line } => match scope.lookup(name) {
+                Some(Value::Text(value)) => out.push_str(value),
+                Some(Value::List(_)) => {
+                    return Err(SyntaxError::new(
+                        *line,
+                        format!("`{name}` is a list; loop over it with `{{{{#each {name}}}}}`"),
+                    ));
+                }
+                None => {
+                    return Err(SyntaxError::new(
+                        *line,
+                        format!("undefined variable `{name}`"),
+                    ));
+                }
+            },
+            Node::If {
+                name,
+                negate,
+                then,
+                otherwise,
+            } => {
+                let branch = if is_true(scope.lookup(name)) ^ negate {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, scope, out)?;
+            }
+            Node::Each {
+                list,
+                binding,
+                body,
+                line,
+            } => {
+                let items = match scope.lookup(list) {
+                    Some(Value::List(items)) => items,
+                    Some(Value::Text(_)) => {
+                        return Err(SyntaxError::new(*line, format!("`{list}` isn't a list")));
+                    }
+                    None => {
+                        return Err(SyntaxError::new(*line, format!("undefined list `{list}`")));
+                    }
+                };
+                for item in items {
+                    scope.locals.push((binding, item));
+                    render_nodes(body, scope, out)?;
+                    scope.locals.pop();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_true(value: Option<Value<'_>>) -> bool {
+    match value {
+        Some(Value::Text(text)) => {
+            !(text.is_empty()
+                || text.eq_ignore_ascii_case("false")
+                || text.eq_ignore_ascii_case("none"))
+        }
+        Some(Value::List(items)) => !items.is_empty(),
+        None => false,
+    }
+}
+
+// ============================================================================
+// Variables
+// ============================================================================
+
+/// A variable or list `source` reads, on a (1-based) `line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VariableUse {
+    pub(crate) name: String,
+    pub(crate) line: usize,
+}
+
+/// Every variable `source` substitutes and list it loops over, in order.
+///
+/// Loop bindings aren't included, and neither are conditionals: an
+/// undefined name there is just false.
+///
+/// # Errors
+///
+/// A [`SyntaxError`] for malformed tags and unbalanced blocks.
+pub(crate) fn variables(source: &str) -> Result<Vec<VariableUse>, SyntaxError> {
+    let nodes = parse(tokenize(source)?)?;
+    let mut uses = Vec::new();
+    collect_variables(&nodes, &mut Vec::new(), &mut uses);
+    Ok(uses)
+}
+
+fn collect_variables<'a>(
+    nodes: &'a [Node],
+    locals: &mut Vec<&'a str>,
+    uses: &mut Vec<VariableUse>,
+) {
+    fn read(name: &str, line: usize, locals: &[&str], uses: &mut Vec<VariableUse>) {
+        if !locals.contains(&name) {
+            uses.push(VariableUse {
+                name: name.to_string(),
+                line,
+            });
+        }
+    }
+
+    for node in nodes {
+        match node {
+            Node::Text(_) => {}
+            Node::Var { name, line } => read(name, *line, locals, uses),
+            Node::If {
+                then, otherwise, ..
+            } => {
+                collect_variables(then, locals, uses);
+                collect_variables(otherwise, locals, uses);
+            }
+            Node::Each {
+                list,
+                binding,
+                body,
+                line,
+            } => {
+                read(list, *line, locals, uses);
+                locals.push(binding);
+                collect_variables(body, locals, uses);
+                locals.pop();
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> RenderContext {
+        RenderContext::new("my-cli")
+            .with_var("AUTHOR", "Ada")
+            .with_var("FRAMEWORK", "none")
+            .with_list("FEATURES", ["json", "color"])
+    }
+
+    fn render_ok(source: &str) -> String {
+        render(source, &ctx()).unwrap()
+    }
+
+    #[test]
+    fn substitutes_variables_by_any_case() {
+        assert_eq!(
+            render_ok("{{ project_name }} by {{AUTHOR}} ({{ year}})"),
+            "my-cli by Ada (2026)"
+        );
+    }
+
+    #[test]
+    fn conditionals_pick_a_branch() {
+        assert_eq!(render_ok("{{#if author}}by {{author}}{{/if}}"), "by Ada");
+        assert_eq!(render_ok("{{#if license}}MIT{{else}}none{{/if}}"), "none");
+        assert_eq!(
+            render_ok("{{#if framework}}fw{{else}}plain{{/if}}"),
+            "plain"
+        );
+        assert_eq!(
+            render_ok("{{#unless license}}unlicensed{{/unless}}"),
+            "unlicensed"
+        );
+        assert_eq!(
+            render_ok("{{#if features}}has features{{/if}}"),
+            "has features"
+        );
+    }
+
+    #[test]
+    fn loops_bind_each_item() {
+        assert_eq!(
+            render_ok("{{#each features}}[{{item}}]{{/each}}"),
+            "[json][color]"
+        );
+        assert_eq!(
+            render_ok("{{#each FEATURES as f}}{{PROJECT_NAME}}/{{f}} {{/each}}"),
+            "my-cli/json my-cli/color "
+        );
+    }
+
+    #[test]
+    fn standalone_block_tags_take_their_line() {
+        let source = "[features]\n{{#each features}}\n  {{#if item}}\n{{item}} = true\n  {{/if}}\n{{/each}}\nend\n";
+        assert_eq!(
+            render_ok(source),
+            "[features]\njson = true\ncolor = true\nend\n"
+        );
+    }
+
+    #[test]
+    fn escaped_braces_are_literal() {
+        assert_eq!(
+            render_ok(r"$\{{ matrix.os }} for {{project_name}}"),
+            "${{ matrix.os }} for my-cli"
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_line() {
+        let err = |source| render(source, &ctx()).unwrap_err();
+
+        assert_eq!(
+            err("ok\n{{ authr }}\n"),
+            SyntaxError::new(2, "undefined variable `authr`")
+        );
+        assert_eq!(err("a\nb\n{{#if author}}\nc\n").line, 3);
+        assert_eq!(err("{{#each features}}\n{{/if}}\n").line, 2);
+        assert_eq!(err("\n\n{{/each}}").line, 3);
+        assert_eq!(err("x\n{{ oops").line, 2);
+        assert_eq!(err("{{#loop x}}").line, 1);
+        assert!(
+            err("{{ project name }}")
+                .reason
+                .contains("unrecognized tag")
+        );
+        assert!(
+            err("{{#each author}}{{/each}}")
+                .reason
+                .contains("isn't a list")
+        );
+    }
+
+    #[test]
+    fn variables_lists_reads_but_not_loop_bindings() {
+        let uses = variables(
+            "{{ project_name }}\n{{#if license}}{{ LICENSE }}{{/if}}\n\
+             {{#each features as f}}{{ f }} {{ year }}{{/each}}\n",
+        )
+        .unwrap();
+        let names: Vec<_> = uses.iter().map(|u| (u.name.as_str(), u.line)).collect();
+
+        assert_eq!(
+            names,
+            [
+                ("project_name", 1),
+                ("LICENSE", 2),
+                ("features", 3),
+                ("year", 3)
+            ]
+        );
+        assert_eq!(variables("{{#if x}}").unwrap_err().line, 1);
+    }
+}
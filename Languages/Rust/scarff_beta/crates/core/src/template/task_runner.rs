@@ -0,0 +1,161 @@
+//! Rust task runner files.
+//!
+//! Rust templates don't ship task files: they are generated for the
+//! target's [`TaskRunner`], with an example test suite for the `test`
+//! recipe to run:
+//! - just: `justfile`
+//! - make: `Makefile`
+//! - none: nothing
+//!
+//! Both have `test`, `lint` (rustfmt and clippy) and `run` recipes; in a
+//! workspace `run` takes the member package to run. The example tests are
+//! `tests/cli.rs` for command-line tools, which runs the binary, and
+//! `tests/example.rs` for other single-crate projects.
+
+use crate::domain::{
+    FileSpec, ProjectKind, RenderContext, Target, TaskRunner, Template, TemplateContent,
+    TemplateNode, TemplateSource,
+};
+use crate::template::backend::DEFAULT_ENGINE;
+
+/// Task file of each runner, as `(path, content template)`.
+fn task_file(runner: TaskRunner) -> Option<(&'static str, &'static str)> {
+    match runner {
+        TaskRunner::Just => Some((
+            "justfile",
+            include_str!("templates/rust/tasks/justfile.template"),
+        )),
+        TaskRunner::Make => Some((
+            "Makefile",
+            include_str!("templates/rust/tasks/Makefile.template"),
+        )),
+        TaskRunner::None => None,
+    }
+}
+
+/// Example test of the project kind, as `(path, content template)`.
+fn example_test(kind: ProjectKind) -> Option<(&'static str, &'static str)> {
+    match kind {
+        ProjectKind::Cli => Some((
+            "tests/cli.rs",
+            include_str!("templates/rust/tasks/cli_test.rs.template"),
+        )),
+        // A virtual workspace has no crate of its own to test
+        ProjectKind::Workspace => None,
+        _ => Some((
+            "tests/example.rs",
+            include_str!("templates/rust/tasks/example_test.rs.template"),
+        )),
+    }
+}
+
+/// Add the task runner's file and the example tests to `template`, and
+/// the `TASKS_IN_WORKSPACE` flag they render from to `ctx`.
+///
+/// Files the template already provides are kept as they are. Targets
+/// without a task runner (or with [`TaskRunner::None`]) are returned
+/// unchanged.
+pub(crate) fn with_task_runner(
+    mut template: Template,
+    ctx: RenderContext,
+    target: &Target,
+) -> (Template, RenderContext) {
+    let Some(task_file) = target.task_runner().and_then(task_file) else {
+        return (template, ctx);
+    };
+
+    for (path, content) in [Some(task_file), example_test(target.kind())]
+        .into_iter()
+        .flatten()
+    {
+        if !template.tree.has_file(path) {
+            // Written in the built-in syntax, whatever the template's engine
+            template.tree.push(TemplateNode::File(
+                FileSpec::new(
+                    path,
+                    TemplateContent::Parameterized(TemplateSource::Static(content)),
+                )
+                .engine(DEFAULT_ENGINE),
+            ));
+        }
+    }
+
+    let workspace = target.kind() == ProjectKind::Workspace;
+    let ctx = ctx.with_var("TASKS_IN_WORKSPACE", workspace.to_string());
+    (template, ctx)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{Language, TargetMatcher, TemplateId, TemplateMetadata, TemplateTree},
+        template::syntax,
+    };
+
+    fn template() -> Template {
+        Template {
+            id: TemplateId::new("rust", "0.1.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("rust"),
+            tree: TemplateTree::new(),
+            hooks: Vec::new(),
+        }
+    }
+
+    fn rust(kind: ProjectKind, runner: TaskRunner) -> Target {
+        Target::builder()
+            .language(Language::Rust)
+            .kind(kind)
+            .unwrap()
+            .task_runner(runner)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn justfile_and_binary_smoke_test_for_a_cli() {
+        let target = rust(ProjectKind::Cli, TaskRunner::Just);
+        let (template, ctx) = with_task_runner(
+            template(),
+            RenderContext::new("my-tool").with_target(&target),
+            &target,
+        );
+
+        assert!(template.tree.has_file("justfile"));
+        assert!(template.tree.has_file("tests/cli.rs"));
+        let justfile = syntax::render(task_file(TaskRunner::Just).unwrap().1, &ctx).unwrap();
+        assert!(justfile.contains("run *args:\n    cargo run -- {{args}}\n"));
+        let test = syntax::render(example_test(ProjectKind::Cli).unwrap().1, &ctx).unwrap();
+        assert!(test.contains("env!(\"CARGO_BIN_EXE_my-tool\")"));
+    }
+
+    #[test]
+    fn makefile_runs_a_member_in_a_workspace() {
+        let target = rust(ProjectKind::Workspace, TaskRunner::Make);
+        let (template, ctx) = with_task_runner(
+            template(),
+            RenderContext::new("platform").with_target(&target),
+            &target,
+        );
+
+        assert_eq!(template.tree.nodes.len(), 1);
+        let makefile = syntax::render(task_file(TaskRunner::Make).unwrap().1, &ctx).unwrap();
+        assert!(makefile.contains("run:\n\tcargo run -p $(PACKAGE) -- $(ARGS)\n"));
+        assert!(makefile.contains("lint:\n\tcargo fmt --all -- --check\n"));
+    }
+
+    #[test]
+    fn none_leaves_the_template_alone() {
+        let target = rust(ProjectKind::Cli, TaskRunner::None);
+        let (template, ctx) = with_task_runner(template(), RenderContext::new("demo"), &target);
+
+        assert!(template.tree.nodes.is_empty());
+        assert!(!ctx.has("TASKS_IN_WORKSPACE"));
+    }
+}
@@ -0,0 +1,285 @@
+//! TypeScript test runner files.
+//!
+//! TypeScript templates don't ship test configuration: it is generated for
+//! the target's [`TestRunner`], with an example test to start from:
+//! - vitest: `vitest.config.ts` and `src/example.test.ts`
+//! - jest: `jest.config.js` (`ts-jest`, or `next/jest` for Next.js) and
+//!   `src/example.test.ts`
+//! - none: nothing
+//!
+//! Both runners test in `jsdom` for frontend and fullstack projects, and in
+//! `node` otherwise. Templates wire the runner into their `package.json`
+//! with `TEST_SCRIPT` (the `npm test` command, empty without a runner) and
+//! `TEST_DEV_DEPENDENCIES` (`"name": "version"` entries, one per item).
+
+use crate::domain::{
+    FileSpec, Framework, ProjectKind, RenderContext, Target, Template, TemplateContent,
+    TemplateNode, TemplateSource, TestRunner, TypeScriptFramework,
+};
+use crate::template::backend::DEFAULT_ENGINE;
+
+/// Files generated for each runner, as `(path, content template)`.
+fn files(
+    runner: TestRunner,
+    framework: Option<Framework>,
+) -> &'static [(&'static str, &'static str)] {
+    match (runner, framework) {
+        (TestRunner::Vitest, _) => &[
+            (
+                "vitest.config.ts",
+                include_str!("templates/typescript/testing/vitest/vitest.config.ts.template"),
+            ),
+            (
+                "src/example.test.ts",
+                include_str!("templates/typescript/testing/vitest/example.test.ts.template"),
+            ),
+        ],
+        (TestRunner::Jest, Some(Framework::TypeScript(TypeScriptFramework::NextJs))) => &[
+            (
+                "jest.config.js",
+                include_str!("templates/typescript/testing/jest/jest.config.next.js.template"),
+            ),
+            (
+                "src/example.test.ts",
+                include_str!("templates/typescript/testing/jest/example.test.ts.template"),
+            ),
+        ],
+        (TestRunner::Jest, _) => &[
+            (
+                "jest.config.js",
+                include_str!("templates/typescript/testing/jest/jest.config.js.template"),
+            ),
+            (
+                "src/example.test.ts",
+                include_str!("templates/typescript/testing/jest/example.test.ts.template"),
+            ),
+        ],
+        (TestRunner::None, _) => &[],
+    }
+}
+
+/// `package.json` dev dependencies of the runner, as `"name": "version"`.
+fn dev_dependencies(
+    runner: TestRunner,
+    framework: Option<Framework>,
+    jsdom: bool,
+) -> Vec<&'static str> {
+    let mut deps = match (runner, framework) {
+        (TestRunner::Vitest, _) => vec![r#""vitest": "^2.1.8""#],
+        // next/jest compiles with SWC, no ts-jest needed
+        (TestRunner::Jest, Some(Framework::TypeScript(TypeScriptFramework::NextJs))) => {
+            vec![r#""@jest/globals": "^29.7.0""#, r#""jest": "^29.7.0""#]
+        }
+        (TestRunner::Jest, _) => vec![
+            r#""@jest/globals": "^29.7.0""#,
+            r#""jest": "^29.7.0""#,
+            r#""ts-jest": "^29.2.5""#,
+        ],
+        (TestRunner::None, _) => return Vec::new(),
+    };
+    if jsdom {
+        deps.push(match runner {
+            TestRunner::Jest => r#""jest-environment-jsdom": "^29.7.0""#,
+            _ => r#""jsdom": "^25.0.1""#,
+        });
+    }
+    deps
+}
+
+/// Add the test runner's config and example test to `template`, and the
+/// `TEST_ENVIRONMENT`, `TEST_SCRIPT` and `TEST_DEV_DEPENDENCIES` they and
+/// `package.json` render from to `ctx`.
+///
+/// Files the template already provides are kept as they are. Targets
+/// without a test runner are returned unchanged.
+pub(crate) fn with_test_runner(
+    mut template: Template,
+    ctx: RenderContext,
+    target: &Target,
+) -> (Template, RenderContext) {
+    let Some(runner) = target.test_runner() else {
+        return (template, ctx);
+    };
+
+    for (path, content) in files(runner, target.framework()) {
+        if !template.tree.has_file(path) {
+            // Written in the built-in syntax, whatever the template's engine
+            template.tree.push(TemplateNode::File(
+                FileSpec::new(
+                    *path,
+                    TemplateContent::Parameterized(TemplateSource::Static(content)),
+                )
+                .engine(DEFAULT_ENGINE),
+            ));
+        }
+    }
+
+    let jsdom = matches!(
+        target.kind(),
+        ProjectKind::WebFrontend | ProjectKind::Fullstack
+    );
+    let script = match runner {
+        TestRunner::Vitest => "vitest run",
+        TestRunner::Jest => "jest",
+        TestRunner::None => "",
+    };
+
+    let ctx = ctx
+        .with_var("TEST_ENVIRONMENT", if jsdom { "jsdom" } else { "node" })
+        .with_var("TEST_SCRIPT", script)
+        .with_list(
+            "TEST_DEV_DEPENDENCIES",
+            dev_dependencies(runner, target.framework(), jsdom),
+        );
+    (template, ctx)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Language, TargetMatcher, TemplateId, TemplateMetadata, TemplateTree};
+
+    fn template() -> Template {
+        Template {
+            id: TemplateId::new("typescript", "0.1.0".to_string()),
+            matcher: TargetMatcher::builder().build(),
+            metadata: TemplateMetadata::new("typescript"),
+            tree: TemplateTree::new().with_node(TemplateNode::File(FileSpec::new(
+                "src/main.ts",
+                TemplateContent::Literal(TemplateSource::Static("")),
+            ))),
+            hooks: Vec::new(),
+        }
+    }
+
+    fn typescript(framework: TypeScriptFramework, runner: TestRunner) -> Target {
+        let kind = match framework {
+            TypeScriptFramework::Express | TypeScriptFramework::NestJs => ProjectKind::WebBackend,
+            TypeScriptFramework::React
+            | TypeScriptFramework::Vue
+            | TypeScriptFramework::SvelteKit => ProjectKind::WebFrontend,
+            TypeScriptFramework::NextJs => ProjectKind::Fullstack,
+        };
+        Target::builder()
+            .language(Language::TypeScript)
+            .kind(kind)
+            .unwrap()
+            .framework(Framework::TypeScript(framework))
+            .unwrap()
+            .test_runner(runner)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn content(template: &Template, path: &str) -> &'static str {
+        template
+            .tree
+            .nodes
+            .iter()
+            .find_map(|node| match node {
+                TemplateNode::File(spec) if spec.path.as_path().to_str() == Some(path) => {
+                    match spec.content {
+                        TemplateContent::Parameterized(TemplateSource::Static(s)) => Some(s),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn vitest_config_follows_project_kind() {
+        let (react, ctx) = with_test_runner(
+            template(),
+            RenderContext::new("web"),
+            &typescript(TypeScriptFramework::React, TestRunner::Vitest),
+        );
+
+        assert!(content(&react, "vitest.config.ts").contains("vitest/config"));
+        assert!(content(&react, "src/example.test.ts").contains("from \"vitest\""));
+        assert_eq!(ctx.get("TEST_ENVIRONMENT"), Some("jsdom"));
+
+        let (_, ctx) = with_test_runner(
+            template(),
+            RenderContext::new("api"),
+            &typescript(TypeScriptFramework::Express, TestRunner::Vitest),
+        );
+        assert_eq!(ctx.get("TEST_ENVIRONMENT"), Some("node"));
+    }
+
+    #[test]
+    fn package_json_vars_follow_runner_and_environment() {
+        let deps = |ctx: &RenderContext| ctx.list("TEST_DEV_DEPENDENCIES").unwrap().to_vec();
+
+        let (_, svelte) = with_test_runner(
+            template(),
+            RenderContext::new("web"),
+            &typescript(TypeScriptFramework::SvelteKit, TestRunner::Vitest),
+        );
+        assert_eq!(svelte.get("TEST_SCRIPT"), Some("vitest run"));
+        assert_eq!(
+            deps(&svelte),
+            [r#""vitest": "^2.1.8""#, r#""jsdom": "^25.0.1""#]
+        );
+
+        let (_, nest) = with_test_runner(
+            template(),
+            RenderContext::new("api"),
+            &typescript(TypeScriptFramework::NestJs, TestRunner::Jest),
+        );
+        assert_eq!(nest.get("TEST_SCRIPT"), Some("jest"));
+        assert!(deps(&nest).iter().any(|d| d.contains("ts-jest")));
+        assert!(!deps(&nest).iter().any(|d| d.contains("jsdom")));
+
+        let (_, vue) = with_test_runner(
+            template(),
+            RenderContext::new("web"),
+            &typescript(TypeScriptFramework::Vue, TestRunner::None),
+        );
+        assert_eq!(vue.get("TEST_SCRIPT"), Some(""));
+        assert!(deps(&vue).is_empty());
+    }
+
+    #[test]
+    fn jest_config_uses_next_preset_for_nextjs() {
+        let (next, _) = with_test_runner(
+            template(),
+            RenderContext::new("site"),
+            &typescript(TypeScriptFramework::NextJs, TestRunner::Jest),
+        );
+        assert!(content(&next, "jest.config.js").contains("next/jest"));
+
+        let (nest, _) = with_test_runner(
+            template(),
+            RenderContext::new("api"),
+            &typescript(TypeScriptFramework::NestJs, TestRunner::Jest),
+        );
+        assert!(content(&nest, "jest.config.js").contains("ts-jest"));
+        assert!(content(&nest, "src/example.test.ts").contains("@jest/globals"));
+    }
+
+    #[test]
+    fn none_and_other_languages_add_nothing() {
+        let (vue, ctx) = with_test_runner(
+            template(),
+            RenderContext::new("web"),
+            &typescript(TypeScriptFramework::Vue, TestRunner::None),
+        );
+        assert_eq!(vue.tree.len(), 1);
+        assert!(ctx.has("TEST_ENVIRONMENT"));
+
+        let (rust, ctx) = with_test_runner(
+            template(),
+            RenderContext::new("cli"),
+            &Target::rust_cli().unwrap(),
+        );
+        assert_eq!(rust.tree.len(), 1);
+        assert!(!ctx.has("TEST_ENVIRONMENT"));
+    }
+}
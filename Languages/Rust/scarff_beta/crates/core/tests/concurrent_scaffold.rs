@@ -0,0 +1,74 @@
+//! Stress test: one shared engine scaffolding many projects in parallel.
+//!
+//! Mirrors embedding `scarff_core` in a service that generates projects on
+//! demand, with every request landing in its own directory.
+
+use std::thread;
+
+use scarff_core_v1::{CustomTree, Engine, Language, ProjectKind, ScaffoldOptions, Target};
+
+const PROJECTS: usize = 64;
+const THREADS: usize = 8;
+
+/// Alternate between the built-in Rust CLI template and a custom tree.
+fn request(i: usize) -> (Target, ScaffoldOptions) {
+    let builder = Target::builder().language(Language::Rust);
+    if i.is_multiple_of(2) {
+        let target = builder.kind(ProjectKind::Cli).unwrap().build().unwrap();
+        (target, ScaffoldOptions::new())
+    } else {
+        let target = builder.kind(ProjectKind::Custom).unwrap().build().unwrap();
+        let tree = CustomTree::parse("src/\n  lib.rs\nREADME.md\n").unwrap();
+        (target, ScaffoldOptions::new().custom_tree(tree))
+    }
+}
+
+#[test]
+fn scaffolds_many_projects_concurrently() {
+    let out = tempfile::tempdir().unwrap();
+    let engine = Engine::new();
+
+    let reports = thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let engine = engine.clone();
+                let out = out.path();
+                scope.spawn(move || {
+                    (t..PROJECTS)
+                        .step_by(THREADS)
+                        .map(|i| {
+                            let name = format!("project-{i}");
+                            let (target, options) = request(i);
+                            let report = engine
+                                .scaffold_with(target, &name, out, &options)
+                                .unwrap_or_else(|e| panic!("{name} failed: {e}"));
+                            (i, report)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("scaffold thread panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(reports.len(), PROJECTS);
+
+    for (i, report) in reports {
+        let root = out.path().join(format!("project-{i}"));
+        assert_eq!(report.root(), root);
+        assert!(report.files_created() > 0);
+        assert_eq!(report.failed_entries().count(), 0);
+
+        for entry in report.created_entries() {
+            assert!(
+                root.join(&entry.path).exists(),
+                "{} missing from project-{i}",
+                entry.path.display()
+            );
+        }
+    }
+}
@@ -2,7 +2,7 @@
 // //!
 // //! These tests verify the entire flow from Target → Template → ProjectStructure → Filesystem.
 
-use scarff_core::{
+use scarff_core_v1::{
     Architecture, Engine, Language, ProjectKind, Target, scaffold::filesystem::MockFilesystem,
 };
 use std::path::Path;
@@ -55,7 +55,7 @@ use std::path::Path;
 
 // #[test]
 // fn test_scaffold_rust_backend_axum() {
-//     use scarff_core::{Framework, RustFramework};
+//     use scarff_core_v1::{Framework, RustFramework};
 
 //     let mock_fs = Box::new(MockFilesystem::new());
 //     let fs_clone = mock_fs.clone();
@@ -81,7 +81,7 @@ use std::path::Path;
 
 // #[test]
 // fn test_scaffold_python_backend_fastapi() {
-//     use scarff_core::{Framework, PythonFramework};
+//     use scarff_core_v1::{Framework, PythonFramework};
 
 //     let mock_fs = Box::new(MockFilesystem::new());
 //     let fs_clone = mock_fs.clone();
@@ -106,7 +106,7 @@ use std::path::Path;
 
 // #[test]
 // fn test_scaffold_typescript_frontend_react() {
-//     use scarff_core::{Framework, TypeScriptFramework};
+//     use scarff_core_v1::{Framework, TypeScriptFramework};
 
 //     let mock_fs = Box::new(MockFilesystem::new());
 //     let fs_clone = mock_fs.clone();
@@ -241,7 +241,7 @@ use std::path::Path;
 
 // #[test]
 // fn test_scaffold_with_invalid_target_fails() {
-//     use scarff_core::{Framework, PythonFramework};
+//     use scarff_core_v1::{Framework, PythonFramework};
 
 //     // Create a target with mismatched framework
 //     let result = Target::builder()
@@ -259,7 +259,7 @@ use std::path::Path;
 
 // #[test]
 // fn test_target_validation_rejects_incompatible_combinations() {
-//     use scarff_core::{Framework, RustFramework};
+//     use scarff_core_v1::{Framework, RustFramework};
 
 //     // Try to create a CLI with a web framework (should fail during inference)
 //     let result = Target::builder()
@@ -291,8 +291,8 @@ use std::path::Path;
 
 // #[test]
 // fn test_rollback_on_partial_failure() {
-//     use scarff_core::domain::{Permissions, ProjectStructure};
-//     use scarff_core::scaffold::{filesystem::MockFilesystem, writer::FileWriter, writer::Writer};
+//     use scarff_core_v1::domain::{Permissions, ProjectStructure};
+//     use scarff_core_v1::scaffold::{filesystem::MockFilesystem, writer::FileWriter, writer::Writer};
 
 //     let fs = Box::new(MockFilesystem::new());
 //     let fs_clone = fs.clone();
@@ -1,5 +1,6 @@
 use crate::command::Command;
 use crate::error::TCError;
+use crate::registry;
 
 pub struct Cli<'a> {
     pub command: Command<'a>,
@@ -7,13 +8,8 @@ pub struct Cli<'a> {
 
 impl<'a> Cli<'a> {
     pub fn cmd(args: &'a [&'a str]) -> Result<Self, TCError> {
-        let command = args.first().ok_or(TCError::CliError)?;
-
-        match *command {
-            "parse" => Ok(Self {
-                command: Command::Parse(&args[1..]),
-            }),
-            _ => Err(TCError::CliError),
-        }
+        Ok(Self {
+            command: registry::resolve(args)?,
+        })
     }
 }
@@ -1,15 +1,47 @@
+use crate::config::Config;
 use crate::error::TCError;
 use crate::parse::Parse;
+use crate::registry::{self, CommandSpec};
 
 #[derive(Debug)]
 pub enum Command<'a> {
     Parse(&'a [&'a str]),
+    ConfigSet {
+        key: &'a str,
+        value: &'a str,
+    },
+    ConfigGet(&'a str),
+    ConfigList,
+    // Commands available under `path` (`tc config help`)
+    Help {
+        path: Vec<&'static str>,
+        commands: &'static [CommandSpec],
+    },
 }
 
 impl<'a> Command<'a> {
     pub fn run(&self) -> Result<(), TCError> {
         match self {
             Command::Parse(args) => Parse::run(args),
+            Command::ConfigSet { key, value } => Config::load()?.set(key, value),
+            Command::ConfigGet(key) => {
+                let config = Config::load()?;
+                let value = config
+                    .get(key)
+                    .ok_or_else(|| TCError::ConfigError(format!("no setting: {key}")))?;
+                println!("{value}");
+                Ok(())
+            }
+            Command::ConfigList => {
+                for (key, value) in Config::load()?.iter() {
+                    println!("{key}={value}");
+                }
+                Ok(())
+            }
+            Command::Help { path, commands } => {
+                print!("{}", registry::help(path, commands));
+                Ok(())
+            }
         }
     }
 }
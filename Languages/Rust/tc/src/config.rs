@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::error::TCError;
+
+// Settings live in a key=value file, one per line: $TC_CONFIG if set,
+// else ~/.tcconfig
+pub struct Config {
+    path: PathBuf,
+    values: BTreeMap<String, String>,
+}
+
+impl Config {
+    pub fn load() -> Result<Self, TCError> {
+        let path = Self::path();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(TCError::ConfigError(format!(
+                    "cannot read {}: {e}",
+                    path.display()
+                )));
+            }
+        };
+
+        let values = text
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .collect();
+        Ok(Self { path, values })
+    }
+
+    fn path() -> PathBuf {
+        if let Some(path) = std::env::var_os("TC_CONFIG") {
+            return PathBuf::from(path);
+        }
+        std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join(".tcconfig")
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), TCError> {
+        if key.is_empty() || key.contains(['=', '\n']) || value.contains('\n') {
+            return Err(TCError::ConfigError(format!("invalid setting: {key}")));
+        }
+        self.values.insert(key.to_string(), value.to_string());
+        self.save()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    fn save(&self) -> Result<(), TCError> {
+        let text: String = self.iter().map(|(k, v)| format!("{k}={v}\n")).collect();
+        fs::write(&self.path, text)
+            .map_err(|e| TCError::ConfigError(format!("cannot write {}: {e}", self.path.display())))
+    }
+}
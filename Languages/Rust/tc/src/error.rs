@@ -4,6 +4,9 @@ use std::fmt;
 pub enum TCError {
     CliError,
     ParseError(String),
+    UnknownCommand(String),
+    UsageError(String),
+    ConfigError(String),
 }
 
 impl fmt::Display for TCError {
@@ -11,6 +14,11 @@ impl fmt::Display for TCError {
         match self {
             TCError::CliError => write!(f, "CLI error"),
             TCError::ParseError(msg) => write!(f, "Parse error: {msg}"),
+            TCError::UnknownCommand(cmd) => {
+                write!(f, "Unknown command: {cmd} (see `tc help`)")
+            }
+            TCError::UsageError(usage) => write!(f, "Usage: {usage}"),
+            TCError::ConfigError(msg) => write!(f, "Config error: {msg}"),
         }
     }
 }
@@ -1,4 +1,6 @@
 pub mod cli;
 pub mod command;
+pub mod config;
 pub mod error;
 pub mod parse;
+pub mod registry;
@@ -0,0 +1,277 @@
+use crate::command::Command;
+use crate::error::TCError;
+
+// Turns the arguments left after a command's path into the command to run
+pub type Build = for<'a> fn(&'a [&'a str]) -> Result<Command<'a>, TCError>;
+
+#[derive(Debug)]
+pub enum Action {
+    Run(Build),
+    Group(&'static [CommandSpec]),
+}
+
+#[derive(Debug)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub args: &'static str,
+    pub about: &'static str,
+    pub action: Action,
+}
+
+impl CommandSpec {
+    fn matches(&self, word: &str) -> bool {
+        self.name == word || self.aliases.contains(&word)
+    }
+}
+
+pub static COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "parse",
+        aliases: &["p"],
+        args: "--flag[=value]...",
+        about: "parse flags and report conflicts",
+        action: Action::Run(|args| Ok(Command::Parse(args))),
+    },
+    CommandSpec {
+        name: "config",
+        aliases: &["co"],
+        args: "",
+        about: "read and write tc settings",
+        action: Action::Group(CONFIG),
+    },
+];
+
+static CONFIG: &[CommandSpec] = &[
+    CommandSpec {
+        name: "set",
+        aliases: &["s"],
+        args: "<key> <value>",
+        about: "set a setting",
+        action: Action::Run(config_set),
+    },
+    CommandSpec {
+        name: "get",
+        aliases: &["g"],
+        args: "<key>",
+        about: "print a setting",
+        action: Action::Run(config_get),
+    },
+    CommandSpec {
+        name: "list",
+        aliases: &["ls"],
+        args: "",
+        about: "print every setting",
+        action: Action::Run(config_list),
+    },
+];
+
+fn config_set<'a>(args: &'a [&'a str]) -> Result<Command<'a>, TCError> {
+    match args {
+        [key, value] => Ok(Command::ConfigSet { key, value }),
+        _ => Err(TCError::UsageError("tc config set <key> <value>".into())),
+    }
+}
+
+fn config_get<'a>(args: &'a [&'a str]) -> Result<Command<'a>, TCError> {
+    match args {
+        [key] => Ok(Command::ConfigGet(key)),
+        _ => Err(TCError::UsageError("tc config get <key>".into())),
+    }
+}
+
+fn config_list<'a>(args: &'a [&'a str]) -> Result<Command<'a>, TCError> {
+    match args {
+        [] => Ok(Command::ConfigList),
+        _ => Err(TCError::UsageError("tc config list".into())),
+    }
+}
+
+fn is_help(word: &str) -> bool {
+    matches!(word, "help" | "--help" | "-h")
+}
+
+// Walk the registry down the argument list: each word picks a command (by
+// name or alias) among the current group's, until one that runs
+//
+//   tc co s name Alice  ->  config > set  ->  ConfigSet("name", "Alice")
+//   tc config           ->  Help for the config group
+pub fn resolve<'a>(args: &'a [&'a str]) -> Result<Command<'a>, TCError> {
+    let mut commands = COMMANDS;
+    let mut path: Vec<&'static str> = Vec::new();
+    let mut rest = args;
+
+    loop {
+        let Some((word, tail)) = rest.split_first() else {
+            return Ok(Command::Help { path, commands });
+        };
+        if is_help(word) {
+            return Ok(Command::Help { path, commands });
+        }
+
+        let spec = commands
+            .iter()
+            .find(|spec| spec.matches(word))
+            .ok_or_else(|| {
+                let mut full = path.clone();
+                full.push(word);
+                TCError::UnknownCommand(full.join(" "))
+            })?;
+        rest = tail;
+
+        match &spec.action {
+            Action::Group(children) => {
+                path.push(spec.name);
+                commands = children;
+            }
+            // `tc config set --help` shows set's line under its group
+            Action::Run(_) if rest.first().is_some_and(|word| is_help(word)) => {
+                return Ok(Command::Help {
+                    path,
+                    commands: std::slice::from_ref(spec),
+                });
+            }
+            Action::Run(build) => return build(rest),
+        }
+    }
+}
+
+// Usage line and the commands under `path`, nested groups indented
+//
+//   Usage: tc <command>
+//
+//   Commands:
+//     parse (p) --flag[=value]...   parse flags and report conflicts
+//     config (co)                   read and write tc settings
+//       set (s) <key> <value>       set a setting
+pub fn help(path: &[&str], commands: &[CommandSpec]) -> String {
+    let mut rows = Vec::new();
+    collect_rows(commands, 0, &mut rows);
+    let width = rows.iter().map(|(left, _)| left.len()).max().unwrap_or(0);
+
+    let mut out = String::from("Usage: tc");
+    for word in path {
+        out.push(' ');
+        out.push_str(word);
+    }
+    out.push_str(" <command>\n\nCommands:\n");
+    for (left, about) in rows {
+        out.push_str(&format!("  {left:width$}   {about}\n"));
+    }
+    out
+}
+
+fn collect_rows(commands: &[CommandSpec], depth: usize, rows: &mut Vec<(String, &'static str)>) {
+    for spec in commands {
+        let mut left = format!("{}{}", "  ".repeat(depth), spec.name);
+        if !spec.aliases.is_empty() {
+            left.push_str(&format!(" ({})", spec.aliases.join(", ")));
+        }
+        if !spec.args.is_empty() {
+            left.push(' ');
+            left.push_str(spec.args);
+        }
+        rows.push((left, spec.about));
+
+        if let Action::Group(children) = &spec.action {
+            collect_rows(children, depth + 1, rows);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_resolve_like_names() {
+        let by_name = ["config", "set", "name", "Alice"];
+        let by_alias = ["co", "s", "name", "Alice"];
+        for args in [&by_name[..], &by_alias[..]] {
+            match resolve(args).unwrap() {
+                Command::ConfigSet { key, value } => assert_eq!((key, value), ("name", "Alice")),
+                other => panic!("expected ConfigSet, got {other:?}"),
+            }
+        }
+        assert!(matches!(
+            resolve(&["p", "--x"]),
+            Ok(Command::Parse(["--x"]))
+        ));
+    }
+
+    #[test]
+    fn nested_config_subcommands_resolve() {
+        assert!(matches!(
+            resolve(&["config", "get", "name"]),
+            Ok(Command::ConfigGet("name"))
+        ));
+        assert!(matches!(
+            resolve(&["config", "ls"]),
+            Ok(Command::ConfigList)
+        ));
+        assert!(matches!(
+            resolve(&["config", "get"]),
+            Err(TCError::UsageError(_))
+        ));
+        match resolve(&["config", "rm", "name"]) {
+            Err(TCError::UnknownCommand(cmd)) => assert_eq!(cmd, "config rm"),
+            other => panic!("expected UnknownCommand, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn groups_and_help_flags_resolve_to_help() {
+        for args in [&[][..], &["help"][..], &["-h"][..]] {
+            match resolve(args).unwrap() {
+                Command::Help { path, commands } => {
+                    assert!(path.is_empty());
+                    assert_eq!(commands.len(), COMMANDS.len());
+                }
+                other => panic!("expected Help, got {other:?}"),
+            }
+        }
+
+        match resolve(&["co"]).unwrap() {
+            Command::Help { path, commands } => {
+                assert_eq!(path, ["config"]);
+                assert_eq!(commands.len(), CONFIG.len());
+            }
+            other => panic!("expected Help, got {other:?}"),
+        }
+
+        match resolve(&["config", "set", "--help"]).unwrap() {
+            Command::Help { path, commands } => {
+                assert_eq!(path, ["config"]);
+                assert_eq!(commands.len(), 1);
+                assert_eq!(commands[0].name, "set");
+            }
+            other => panic!("expected Help, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn help_indents_nested_groups() {
+        let text = help(&[], COMMANDS);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "Usage: tc <command>");
+        assert_eq!(lines[2], "Commands:");
+        assert!(lines[3].starts_with("  parse (p) --flag[=value]..."));
+        assert!(lines[4].starts_with("  config (co) "));
+        assert!(lines[5].starts_with("    set (s) <key> <value> "));
+        assert!(lines[6].starts_with("    get (g) <key> "));
+        assert!(lines[7].starts_with("    list (ls) "));
+
+        // Descriptions line up in one column
+        let column = lines[3].find("parse flags").unwrap();
+        assert_eq!(lines[5].find("set a setting"), Some(column));
+    }
+
+    #[test]
+    fn help_for_a_group_shows_its_path() {
+        let text = help(&["config"], CONFIG);
+        assert!(text.starts_with("Usage: tc config <command>\n"));
+        assert!(text.contains("  set (s) <key> <value>"));
+        assert!(!text.contains("parse"));
+    }
+}
@@ -0,0 +1,66 @@
+//! Async bridge — submit CPU-bound jobs from async code.
+//!
+//! An async runtime has a few threads that must never be busy for long: a
+//! task that computes for 200ms on one of them stalls every other task
+//! scheduled there. The fix is to hand the work to the pool and `.await`
+//! the answer:
+//!
+//! ```text
+//! async task ──spawn_async(job)──> pool queue ──> worker runs job
+//!     │                                               │
+//!     └── .await on oneshot receiver <── send(result) ┘
+//! ```
+//!
+//! The pool itself stays runtime-agnostic: the only async piece is the
+//! oneshot channel, whose receiver is a plain `Future` that works under
+//! tokio, async-std or `futures::executor::block_on`.
+
+use futures_channel::oneshot;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::thread_pool::{PoolError, WorkStealingPool, panic_message};
+
+impl WorkStealingPool {
+    /// Run `job` on a worker and get its result as a future.
+    ///
+    /// The job is queued right away, not when the future is first polled,
+    /// and keeps running even if the future is dropped. Submission never
+    /// blocks: a full queue resolves to [`PoolError::QueueFull`] so the
+    /// caller can back off instead of stalling the runtime.
+    ///
+    /// A panic in `job` resolves to [`PoolError::Panicked`], and a job that
+    /// was still queued at shutdown to [`PoolError::Shutdown`].
+    pub fn spawn_async<F, R>(
+        &self,
+        job: F,
+    ) -> impl Future<Output = Result<R, PoolError>> + Send + 'static + use<F, R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (result_tx, result_rx) = oneshot::channel();
+
+        let submitted = self.try_execute(move || {
+            // Err from send means the caller dropped the future; nobody to
+            // tell
+            match panic::catch_unwind(AssertUnwindSafe(job)) {
+                Ok(value) => {
+                    let _ = result_tx.send(Ok(value));
+                }
+                Err(payload) => {
+                    let message = panic_message(payload.as_ref());
+                    let _ = result_tx.send(Err(PoolError::Panicked(message)));
+                    // Let the worker see it too, so it's counted as failed
+                    panic::resume_unwind(payload);
+                }
+            }
+        });
+
+        async move {
+            submitted?;
+            // Canceled: the task was dropped without running
+            result_rx.await.unwrap_or(Err(PoolError::Shutdown))
+        }
+    }
+}
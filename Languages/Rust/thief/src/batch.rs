@@ -0,0 +1,108 @@
+//! Chunked processing — map a big `Vec` across the workers.
+//!
+//! One job per element drowns small work in scheduling overhead (a channel
+//! send, a queue push, maybe a steal, for a multiply). One job for the whole
+//! `Vec` leaves every other core idle. Chunks sit in between:
+//!
+//! ```text
+//! data:     [a b c d | e f g h | i j k l | m n]
+//!              │         │         │       │
+//! workers:   job 0     job 1     job 2   job 3     (any order)
+//!              │         │         │       │
+//! results:  [A B C D | E F G H | I J K L | M N]   (reassembled by index)
+//! ```
+//!
+//! With `chunk_size = 0` the size is picked from the worker count: a few
+//! chunks per worker, so a worker that finishes early can steal a remaining
+//! chunk instead of waiting on the slowest one.
+
+use crossbeam::channel;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use crate::thread_pool::{PoolError, WorkStealingPool, panic_message};
+
+/// Chunks per worker when sizing automatically.
+const CHUNKS_PER_WORKER: usize = 4;
+
+impl WorkStealingPool {
+    /// Apply `f` to every element of `data` on the pool, `chunk_size`
+    /// elements per job, and return the results in the original order.
+    ///
+    /// `chunk_size = 0` picks a size from the number of workers.
+    ///
+    /// Blocks until every chunk is done, so don't call it from inside a
+    /// job: the worker it occupies can't help with the chunks it waits for.
+    /// A panic in `f` comes back as [`PoolError::Panicked`]; the other
+    /// chunks still run to completion on their workers.
+    pub fn map_chunks<T, U, F>(
+        &self,
+        data: Vec<T>,
+        chunk_size: usize,
+        f: F,
+    ) -> Result<Vec<U>, PoolError>
+    where
+        T: Send + 'static,
+        U: Send + 'static,
+        F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let len = data.len();
+        let chunk_size = match chunk_size {
+            0 => auto_chunk_size(len, self.num_workers()),
+            n => n,
+        };
+        let num_chunks = len.div_ceil(chunk_size);
+
+        let f = Arc::new(f);
+        let (done_tx, done_rx) = channel::bounded(num_chunks);
+        let mut elements = data.into_iter();
+
+        for index in 0..num_chunks {
+            let chunk: Vec<T> = elements.by_ref().take(chunk_size).collect();
+            let f = Arc::clone(&f);
+            let done_tx = done_tx.clone();
+
+            self.execute(move || {
+                let mapped = panic::catch_unwind(AssertUnwindSafe(|| {
+                    chunk.into_iter().map(&*f).collect::<Vec<U>>()
+                }));
+                match mapped {
+                    Ok(values) => {
+                        let _ = done_tx.send((index, Ok(values)));
+                    }
+                    Err(payload) => {
+                        let message = panic_message(payload.as_ref());
+                        let _ = done_tx.send((index, Err(PoolError::Panicked(message))));
+                        // Let the worker see it too, so it's counted as failed
+                        panic::resume_unwind(payload);
+                    }
+                }
+            })?;
+        }
+        // Only the jobs hold senders now: shutdown drops every job that
+        // hasn't started, even one queued as it shuts down, so once the
+        // running ones finish recv fails instead of waiting forever
+        drop(done_tx);
+
+        let mut chunks: Vec<Option<Vec<U>>> = (0..num_chunks).map(|_| None).collect();
+        for _ in 0..num_chunks {
+            let (index, mapped) = done_rx.recv().map_err(|_| PoolError::Shutdown)?;
+            chunks[index] = Some(mapped?);
+        }
+
+        let mut results = Vec::with_capacity(len);
+        for chunk in chunks {
+            results.extend(chunk.expect("every chunk index is received once"));
+        }
+        Ok(results)
+    }
+}
+
+/// A few chunks per worker, at least one element each.
+fn auto_chunk_size(len: usize, workers: usize) -> usize {
+    len.div_ceil(workers.max(1) * CHUNKS_PER_WORKER).max(1)
+}
@@ -1,2 +1,5 @@
+pub mod async_bridge;
+pub mod batch;
+pub mod stats;
 pub mod thread_pool;
 pub mod with_rayon;
@@ -0,0 +1,187 @@
+//! Pool metrics — watch the executor while it runs.
+//!
+//! Counters are plain atomics bumped by workers and submitters with
+//! `Relaxed` ordering: each one is only ever added to, and nothing else is
+//! published through them, so they need atomicity but no synchronization.
+//! A snapshot is therefore a set of individually exact numbers that may be
+//! a few jobs apart from each other, which is fine for monitoring.
+//!
+//! Queue depth is mostly read from the queues themselves (injector + every
+//! worker's local deque). The submission channel is counted instead: a
+//! handle holding its receiver would keep queued tasks, and whatever they
+//! captured, alive for as long as the handle lives.
+//!
+//! ```text
+//! let stats = pool.stats();          // cheap handle, Clone + Send
+//! ...
+//! println!("{}", stats.snapshot());
+//! ```
+
+use crossbeam::deque::{Injector, Stealer};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use crate::thread_pool::Task;
+
+/// A handle to a pool's counters. Clone it freely; it stays readable after
+/// the pool shuts down.
+#[derive(Clone)]
+pub struct PoolStats {
+    inner: Arc<Shared>,
+}
+
+struct Shared {
+    submitted: AtomicU64,
+    completed: AtomicU64,
+    failed: AtomicU64,
+    workers: Vec<WorkerCounters>,
+    /// Tasks in the submission channel
+    in_channel: AtomicUsize,
+    // Only read for their lengths
+    injector: Arc<Injector<Task>>,
+    stealers: Arc<Vec<Stealer<Task>>>,
+}
+
+#[derive(Default)]
+struct WorkerCounters {
+    jobs: AtomicU64,
+    busy_nanos: AtomicU64,
+    steals: AtomicU64,
+}
+
+/// Counters at one point in time.
+#[derive(Debug, Clone)]
+pub struct StatsSnapshot {
+    /// Jobs accepted by `execute`/`submit`/`spawn_async`/...
+    pub submitted: u64,
+    /// Jobs that returned normally
+    pub completed: u64,
+    /// Jobs that panicked
+    pub failed: u64,
+    /// Jobs waiting to start, in any queue
+    pub queue_depth: usize,
+    /// One entry per worker thread, by index
+    pub workers: Vec<WorkerStats>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WorkerStats {
+    /// Jobs this worker ran, stolen or not
+    pub jobs: u64,
+    /// Time spent inside jobs (the rest is looking for work or parked)
+    pub busy: Duration,
+    /// Jobs taken from another worker's local deque
+    pub steals: u64,
+}
+
+impl PoolStats {
+    pub(crate) fn new(injector: Arc<Injector<Task>>, stealers: Arc<Vec<Stealer<Task>>>) -> Self {
+        let workers = (0..stealers.len())
+            .map(|_| WorkerCounters::default())
+            .collect();
+        PoolStats {
+            inner: Arc::new(Shared {
+                submitted: AtomicU64::new(0),
+                completed: AtomicU64::new(0),
+                failed: AtomicU64::new(0),
+                workers,
+                in_channel: AtomicUsize::new(0),
+                injector,
+                stealers,
+            }),
+        }
+    }
+
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let inner = &*self.inner;
+        StatsSnapshot {
+            submitted: inner.submitted.load(Ordering::Relaxed),
+            completed: inner.completed.load(Ordering::Relaxed),
+            failed: inner.failed.load(Ordering::Relaxed),
+            queue_depth: self.queue_depth(),
+            workers: inner
+                .workers
+                .iter()
+                .map(|w| WorkerStats {
+                    jobs: w.jobs.load(Ordering::Relaxed),
+                    busy: Duration::from_nanos(w.busy_nanos.load(Ordering::Relaxed)),
+                    steals: w.steals.load(Ordering::Relaxed),
+                })
+                .collect(),
+        }
+    }
+
+    /// Jobs waiting to start, in any queue.
+    pub fn queue_depth(&self) -> usize {
+        let inner = &*self.inner;
+        inner.in_channel.load(Ordering::Relaxed)
+            + inner.injector.len()
+            + inner.stealers.iter().map(Stealer::len).sum::<usize>()
+    }
+
+    pub(crate) fn record_submitted(&self) {
+        self.inner.submitted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A task is about to be sent into the submission channel.
+    pub(crate) fn record_queued(&self) {
+        self.inner.in_channel.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A task left the submission channel (or never made it in).
+    pub(crate) fn record_dequeued(&self) {
+        self.inner.in_channel.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_steal(&self, worker: usize) {
+        self.inner.workers[worker]
+            .steals
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_job(&self, worker: usize, busy: Duration, ok: bool) {
+        let counters = &self.inner.workers[worker];
+        counters.jobs.fetch_add(1, Ordering::Relaxed);
+        counters
+            .busy_nanos
+            .fetch_add(busy.as_nanos() as u64, Ordering::Relaxed);
+
+        let outcome = if ok {
+            &self.inner.completed
+        } else {
+            &self.inner.failed
+        };
+        outcome.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl StatsSnapshot {
+    /// Total steals across workers.
+    pub fn steals(&self) -> u64 {
+        self.workers.iter().map(|w| w.steals).sum()
+    }
+}
+
+impl fmt::Display for StatsSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "submitted {} | completed {} | failed {} | queued {} | steals {}",
+            self.submitted,
+            self.completed,
+            self.failed,
+            self.queue_depth,
+            self.steals()
+        )?;
+        for (i, w) in self.workers.iter().enumerate() {
+            writeln!(
+                f,
+                "  worker {i:>2}: {:>6} jobs, {:>6} steals, busy {:?}",
+                w.jobs, w.steals, w.busy
+            )?;
+        }
+        Ok(())
+    }
+}
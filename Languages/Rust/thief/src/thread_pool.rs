@@ -16,23 +16,24 @@
 //! - parking_lot (fast locks if needed later)
 //! - num_cpus (core scaling)
 
-use crossbeam::channel::{self, Receiver, Sender};
+use crossbeam::channel::{self, Receiver, Sender, TrySendError};
 use crossbeam::deque::{Injector, Steal, Stealer, Worker};
-use std::panic;
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::{
     Arc,
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
 };
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use crate::stats::PoolStats;
 
 /// Bounded submission capacity (backpressure)
 static SUBMIT_BOUND: usize = 32;
 
-/// A CPU-bound job.
-///
-/// In a real system this would usually be a boxed closure:
-/// `Box<dyn FnOnce() + Send + 'static>`
+/// A simulated CPU-bound job, see [`WorkStealingPool::submit`].
 #[derive(Debug)]
 pub struct Job {
     pub id: usize,
@@ -44,6 +45,46 @@ pub struct JobResult {
     pub id: usize,
 }
 
+/// What the queues actually carry: any closure, type-erased, numbered in
+/// submission order for logs and tracing.
+pub(crate) struct Task {
+    id: u64,
+    run: Box<dyn FnOnce() + Send + 'static>,
+}
+
+/// Why a job didn't produce a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolError {
+    /// The submission queue is full and the caller chose not to block
+    QueueFull,
+    /// The pool shut down before the job ran
+    Shutdown,
+    /// The job panicked; carries the panic message
+    Panicked(String),
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::QueueFull => write!(f, "submission queue is full"),
+            PoolError::Shutdown => write!(f, "executor shutting down"),
+            PoolError::Panicked(msg) => write!(f, "job panicked: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for PoolError {}
+
+/// The message of a caught panic (`panic!("...")` payloads are `&str` or
+/// `String`; anything else is opaque).
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string())
+}
+
 /// Thread pool implementing work stealing.
 ///
 /// Architecture:
@@ -63,10 +104,16 @@ pub struct JobResult {
 /// Each worker owns a local LIFO deque for cache locality.
 /// When idle, it first checks the global injector, then steals.
 pub struct WorkStealingPool {
-    injector: Arc<Injector<Job>>,
-    stealers: Arc<Vec<Stealer<Job>>>,
-    submit_tx: Sender<Job>,
+    injector: Arc<Injector<Task>>,
+    stealers: Arc<Vec<Stealer<Task>>>,
+    submit_tx: Sender<Task>,
+    // Kept to drop what's still in the channel at shutdown
+    submit_rx: Receiver<Task>,
+    result_tx: Sender<JobResult>,
     shutdown: Arc<AtomicBool>,
+    num_workers: usize,
+    next_id: AtomicU64,
+    stats: PoolStats,
 }
 
 impl WorkStealingPool {
@@ -91,16 +138,27 @@ impl WorkStealingPool {
         }
 
         let stealers = Arc::new(stealers);
+        let stats = PoolStats::new(Arc::clone(&injector), Arc::clone(&stealers));
 
         // Dispatcher: moves jobs from bounded channel into injector
         {
+            let submit_rx = submit_rx.clone();
             let injector = Arc::clone(&injector);
             let shutdown = Arc::clone(&shutdown);
+            let stats = stats.clone();
 
             thread::spawn(move || {
-                while !shutdown.load(Ordering::Acquire) {
+                while !shutdown.load(Ordering::SeqCst) {
                     match submit_rx.recv() {
-                        Ok(job) => injector.push(job),
+                        Ok(task) => {
+                            stats.record_dequeued();
+                            injector.push(task);
+                            // shutdown() may have drained the injector
+                            // between the recv and the push
+                            if shutdown.load(Ordering::SeqCst) {
+                                drop_queued(&submit_rx, &injector, &stats);
+                            }
+                        }
                         Err(_) => break,
                     }
                 }
@@ -108,13 +166,16 @@ impl WorkStealingPool {
         }
 
         // Spawn worker threads
-        for local in workers {
-            let injector = Arc::clone(&injector);
-            let stealers = Arc::clone(&stealers);
-            let shutdown = Arc::clone(&shutdown);
-            let result_tx = result_tx.clone();
+        for (index, local) in workers.into_iter().enumerate() {
+            let ctx = WorkerContext {
+                index,
+                injector: Arc::clone(&injector),
+                stealers: Arc::clone(&stealers),
+                shutdown: Arc::clone(&shutdown),
+                stats: stats.clone(),
+            };
 
-            thread::spawn(move || worker_loop(local, injector, stealers, shutdown, result_tx));
+            thread::spawn(move || worker_loop(local, ctx));
         }
 
         (
@@ -122,56 +183,167 @@ impl WorkStealingPool {
                 injector,
                 stealers,
                 submit_tx,
+                submit_rx,
+                result_tx,
                 shutdown,
+                num_workers,
+                next_id: AtomicU64::new(0),
+                stats,
             },
             result_rx,
         )
     }
 
-    /// Submit a job to the pool.
+    /// Submit a simulated job; its [`JobResult`] arrives on the receiver
+    /// returned by [`WorkStealingPool::new`].
     ///
     /// This method applies **backpressure**:
     /// if the submission queue is full, the caller blocks.
     pub fn submit(&self, job: Job) -> Result<(), String> {
-        self.submit_tx
-            .send(job)
-            .map_err(|_| "executor shutting down".into())
+        let result_tx = self.result_tx.clone();
+        self.execute(move || {
+            // Simulate CPU-heavy work
+            std::thread::sleep(Duration::from_millis(50));
+            let _ = result_tx.send(JobResult { id: job.id });
+        })
+        .map_err(|e| e.to_string())
+    }
+
+    /// Run `f` on a worker, blocking while the submission queue is full.
+    ///
+    /// A panic in `f` is caught and logged; it doesn't take the worker down.
+    pub fn execute<F>(&self, f: F) -> Result<(), PoolError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.is_shutdown() {
+            return Err(PoolError::Shutdown);
+        }
+        // Counted before sending so the dispatcher never takes it out of
+        // the count before it's in
+        self.stats.record_queued();
+        self.submit_tx.send(self.task(f)).map_err(|_| {
+            self.stats.record_dequeued();
+            PoolError::Shutdown
+        })?;
+        self.stats.record_submitted();
+        self.drop_if_shut_down();
+        Ok(())
+    }
+
+    /// Like [`execute`](Self::execute), but fails with
+    /// [`PoolError::QueueFull`] instead of blocking.
+    ///
+    /// For callers that must not block, such as async tasks.
+    pub fn try_execute<F>(&self, f: F) -> Result<(), PoolError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.is_shutdown() {
+            return Err(PoolError::Shutdown);
+        }
+        self.stats.record_queued();
+        self.submit_tx.try_send(self.task(f)).map_err(|e| {
+            self.stats.record_dequeued();
+            match e {
+                TrySendError::Full(_) => PoolError::QueueFull,
+                TrySendError::Disconnected(_) => PoolError::Shutdown,
+            }
+        })?;
+        self.stats.record_submitted();
+        self.drop_if_shut_down();
+        Ok(())
+    }
+
+    /// Drop the queued tasks if shutdown raced with a submission that
+    /// passed the `is_shutdown` check, so the new task doesn't sit in the
+    /// channel after shutdown drained it.
+    fn drop_if_shut_down(&self) {
+        if self.is_shutdown() {
+            drop_queued(&self.submit_rx, &self.injector, &self.stats);
+        }
+    }
+
+    fn task<F>(&self, f: F) -> Task
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        Task {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            run: Box::new(f),
+        }
+    }
+
+    /// A handle to this pool's counters, see [`PoolStats`].
+    pub fn stats(&self) -> PoolStats {
+        self.stats.clone()
+    }
+
+    /// Number of worker threads.
+    pub fn num_workers(&self) -> usize {
+        self.num_workers
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.shutdown.load(Ordering::SeqCst)
     }
 
     /// Initiate a cooperative shutdown.
     ///
-    /// Workers will finish in-flight jobs and exit.
+    /// Workers will finish in-flight jobs and exit. Jobs still queued are
+    /// dropped without running, including ones submitted concurrently with
+    /// the shutdown.
     pub fn shutdown(&self) {
-        self.shutdown.store(true, Ordering::Release);
-        let _ = &self.submit_tx; //dropping it
+        // SeqCst pairs with the loads after each push: either the pusher
+        // sees the flag and drops its task, or the drains below see the task
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        // Dropping a queued task drops whatever it captured, e.g. the
+        // sending half of a spawn_async channel, so its waiter wakes up
+        drop_queued(&self.submit_rx, &self.injector, &self.stats);
+        for stealer in self.stealers.iter() {
+            while !stealer.steal().is_empty() {}
+        }
     }
 }
 
+/// Drop every task in the submission channel, then in the injector.
+fn drop_queued(submit_rx: &Receiver<Task>, injector: &Injector<Task>, stats: &PoolStats) {
+    while submit_rx.try_recv().is_ok() {
+        stats.record_dequeued();
+    }
+    while !injector.steal().is_empty() {}
+}
+
+/// Everything a worker thread needs besides its own deque.
+struct WorkerContext {
+    index: usize,
+    injector: Arc<Injector<Task>>,
+    stealers: Arc<Vec<Stealer<Task>>>,
+    shutdown: Arc<AtomicBool>,
+    stats: PoolStats,
+}
+
 /// Main worker loop.
 ///
 /// Scheduling order:
 /// 1. Local queue (LIFO)
-/// 2. Global injector (FIFO)
+/// 2. Global injector (FIFO), taking a batch into the local queue
 /// 3. Steal from other workers
 /// 4. Park (yield)
-fn worker_loop(
-    local: Worker<Job>,
-    injector: Arc<Injector<Job>>,
-    stealers: Arc<Vec<Stealer<Job>>>,
-    shutdown: Arc<AtomicBool>,
-    result_tx: Sender<JobResult>,
-) {
-    while !shutdown.load(Ordering::Acquire) {
+fn worker_loop(local: Worker<Task>, ctx: WorkerContext) {
+    while !ctx.shutdown.load(Ordering::Acquire) {
         // 1️⃣ Local queue
         if let Some(job) = local.pop() {
-            run_job(job, &result_tx, &shutdown);
+            run_job(job, &ctx);
             continue;
         }
 
-        // 2️⃣ Global injector
-        match injector.steal() {
+        // 2️⃣ Global injector: the extra jobs in the batch are what idle
+        // peers get to steal
+        match ctx.injector.steal_batch_and_pop(&local) {
             Steal::Success(job) => {
-                run_job(job, &result_tx, &shutdown);
+                run_job(job, &ctx);
                 continue;
             }
             Steal::Retry => continue,
@@ -180,9 +352,14 @@ fn worker_loop(
 
         // 3️⃣ Steal from peers
         let mut stolen = None;
-        for stealer in stealers.iter() {
+        for (peer, stealer) in ctx.stealers.iter().enumerate() {
+            if peer == ctx.index {
+                continue;
+            }
             match stealer.steal() {
                 Steal::Success(job) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(worker = ctx.index, from = peer, job = job.id, "stole job");
                     stolen = Some(job);
                     break;
                 }
@@ -192,36 +369,47 @@ fn worker_loop(
         }
 
         if let Some(job) = stolen {
-            run_job(job, &result_tx, &shutdown);
+            ctx.stats.record_steal(ctx.index);
+            run_job(job, &ctx);
         } else {
             // 4️⃣ No work — park cooperatively
             thread::sleep(Duration::from_millis(1));
         }
     }
+
+    // Jobs batched here behind the one running at shutdown
+    while local.pop().is_some() {}
 }
 
 /// Execute a job with panic isolation.
-fn run_job(job: Job, result_tx: &Sender<JobResult>, shutdown: &AtomicBool) {
-    if shutdown.load(Ordering::Relaxed) {
+fn run_job(task: Task, ctx: &WorkerContext) {
+    if ctx.shutdown.load(Ordering::Relaxed) {
         return;
     }
 
-    let res = panic::catch_unwind(|| {
-        // Simulate CPU-heavy work
-        std::thread::sleep(Duration::from_millis(50));
-        JobResult { id: job.id }
-    });
+    // Everything the job logs through tracing is attributed to this span
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("job", id = task.id, worker = ctx.index).entered();
+
+    let started = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(task.run));
+    let busy = started.elapsed();
+    ctx.stats.record_job(ctx.index, busy, result.is_ok());
 
-    match res {
-        Ok(result) => {
-            let _ = result_tx.send(result);
+    match result {
+        Ok(()) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(?busy, "job completed");
         }
-        Err(_) => {
+        Err(payload) => {
             // Supervisor policy could be:
             // - retry
             // - log & drop
             // - initiate shutdown
-            eprintln!("job {} panicked", job.id);
+            let message = panic_message(payload.as_ref());
+            #[cfg(feature = "tracing")]
+            tracing::error!(?busy, panic = %message, "job panicked");
+            eprintln!("job {} panicked: {}", task.id, message);
         }
     }
 }
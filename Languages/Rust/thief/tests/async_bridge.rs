@@ -0,0 +1,174 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use thief::thread_pool::{PoolError, WorkStealingPool};
+
+/// Busy-loop on the calling thread, like real CPU-bound work.
+fn spin_for(duration: Duration) -> u64 {
+    let start = Instant::now();
+    let mut n = 0u64;
+    while start.elapsed() < duration {
+        n = n.wrapping_add(1);
+    }
+    n
+}
+
+#[tokio::test]
+async fn returns_the_job_result() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    let sum = pool.spawn_async(|| (1..=100u64).sum::<u64>()).await;
+
+    assert_eq!(sum, Ok(5050));
+}
+
+#[tokio::test]
+async fn panicking_job_becomes_an_error() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    let result = pool.spawn_async(|| -> u32 { panic!("boom") }).await;
+    assert_eq!(result, Err(PoolError::Panicked("boom".to_string())));
+
+    // The worker survived the panic
+    assert_eq!(pool.spawn_async(|| 7).await, Ok(7));
+}
+
+#[tokio::test]
+async fn many_jobs_complete_concurrently() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    let handles: Vec<_> = (0..16u64)
+        .map(|i| {
+            tokio::spawn(pool.spawn_async(move || {
+                spin_for(Duration::from_millis(5));
+                i * i
+            }))
+        })
+        .collect();
+
+    let mut squares = Vec::new();
+    for handle in handles {
+        squares.push(handle.await.unwrap().unwrap());
+    }
+    assert_eq!(squares, (0..16u64).map(|i| i * i).collect::<Vec<_>>());
+}
+
+/// With a single runtime thread, anything that blocked it would starve the
+/// ticker below. The job runs on the pool, so the ticker keeps ticking.
+#[tokio::test(flavor = "current_thread")]
+async fn cpu_work_does_not_block_the_runtime() {
+    let (pool, _results) = WorkStealingPool::new();
+    let ticks = Arc::new(AtomicUsize::new(0));
+
+    let ticker = {
+        let ticks = Arc::clone(&ticks);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(10));
+            loop {
+                interval.tick().await;
+                ticks.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+    };
+
+    let job = pool.spawn_async(|| spin_for(Duration::from_millis(300)));
+    assert!(job.await.is_ok());
+    ticker.abort();
+
+    // ~30 ticks if the runtime was free the whole time
+    let ticks = ticks.load(Ordering::Relaxed);
+    assert!(ticks >= 10, "runtime was blocked: only {ticks} ticks");
+}
+
+#[tokio::test]
+async fn submitting_after_shutdown_fails() {
+    let (pool, _results) = WorkStealingPool::new();
+    pool.shutdown();
+
+    assert_eq!(pool.spawn_async(|| 1).await, Err(PoolError::Shutdown));
+}
+
+#[tokio::test]
+async fn dropping_the_future_does_not_cancel_the_job() {
+    let (pool, _results) = WorkStealingPool::new();
+    let ran = Arc::new(AtomicUsize::new(0));
+
+    let counter = Arc::clone(&ran);
+    drop(pool.spawn_async(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while ran.load(Ordering::SeqCst) == 0 && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(ran.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn queued_jobs_resolve_to_shutdown_while_the_pool_lives() {
+    let (pool, _results) = WorkStealingPool::new();
+    let started = Arc::new(AtomicUsize::new(0));
+    let release = Arc::new(AtomicUsize::new(0));
+
+    // Keep every worker busy so the next jobs stay queued
+    let running: Vec<_> = (0..pool.num_workers())
+        .map(|_| {
+            let (started, release) = (Arc::clone(&started), Arc::clone(&release));
+            pool.spawn_async(move || {
+                started.fetch_add(1, Ordering::SeqCst);
+                while release.load(Ordering::SeqCst) == 0 {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            })
+        })
+        .collect();
+    while started.load(Ordering::SeqCst) < pool.num_workers() {
+        thread::sleep(Duration::from_millis(1));
+    }
+    let queued: Vec<_> = (0..100).map(|i| pool.spawn_async(move || i)).collect();
+
+    pool.shutdown();
+    release.store(1, Ordering::SeqCst);
+
+    for job in running {
+        assert_eq!(job.await, Ok(()));
+    }
+    for job in queued {
+        let result = tokio::time::timeout(Duration::from_secs(5), job)
+            .await
+            .expect("a queued job's future hung after shutdown");
+        assert!(
+            matches!(result, Err(PoolError::Shutdown | PoolError::QueueFull)),
+            "{result:?}"
+        );
+    }
+    drop(pool);
+}
+
+#[tokio::test]
+async fn shutdown_racing_submissions_leaves_no_future_hanging() {
+    for _ in 0..20 {
+        let (pool, _results) = WorkStealingPool::new();
+        let pool = Arc::new(pool);
+
+        let submitter = {
+            let pool = Arc::clone(&pool);
+            thread::spawn(move || {
+                let jobs: Vec<_> = (0..200)
+                    .map(|_| pool.spawn_async(|| spin_for(Duration::from_micros(50))))
+                    .collect();
+                jobs
+            })
+        };
+        thread::sleep(Duration::from_millis(1));
+        pool.shutdown();
+
+        for job in submitter.join().unwrap() {
+            let result = tokio::time::timeout(Duration::from_secs(5), job).await;
+            assert!(result.is_ok(), "a future hung after shutdown");
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use thief::thread_pool::{PoolError, WorkStealingPool};
+
+#[test]
+fn results_keep_input_order() {
+    let (pool, _results) = WorkStealingPool::new();
+    let data: Vec<u64> = (0..1000).collect();
+
+    let squares = pool.map_chunks(data, 7, |x| x * x).unwrap();
+
+    assert_eq!(squares, (0..1000u64).map(|x| x * x).collect::<Vec<_>>());
+}
+
+#[test]
+fn auto_chunk_size_covers_every_element() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    for len in [
+        1,
+        2,
+        3,
+        pool.num_workers(),
+        4 * pool.num_workers() + 1,
+        10_000,
+    ] {
+        let data: Vec<usize> = (0..len).collect();
+        let doubled = pool.map_chunks(data, 0, |x| x * 2).unwrap();
+        assert_eq!(doubled, (0..len).map(|x| x * 2).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn empty_input_gives_empty_output() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    let out: Vec<String> = pool
+        .map_chunks(Vec::<u8>::new(), 0, |x| x.to_string())
+        .unwrap();
+
+    assert!(out.is_empty());
+}
+
+#[test]
+fn chunk_larger_than_input_is_one_job() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    let out = pool
+        .map_chunks(vec!["a", "b"], 100, str::to_uppercase)
+        .unwrap();
+
+    assert_eq!(out, ["A", "B"]);
+}
+
+#[test]
+fn chunks_are_spread_across_workers() {
+    let (pool, _results) = WorkStealingPool::new();
+    if pool.num_workers() < 2 {
+        return;
+    }
+    let threads = Arc::new(Mutex::new(HashSet::new()));
+
+    let seen = Arc::clone(&threads);
+    pool.map_chunks((0..64).collect(), 1, move |x: u32| {
+        seen.lock().unwrap().insert(thread::current().id());
+        // Long enough that one worker can't take every chunk
+        thread::sleep(Duration::from_millis(5));
+        x
+    })
+    .unwrap();
+
+    assert!(threads.lock().unwrap().len() > 1);
+}
+
+#[test]
+fn panic_in_a_chunk_is_reported() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    let result = pool.map_chunks((0..100).collect(), 10, |x: i32| {
+        if x == 42 {
+            panic!("bad element");
+        }
+        x
+    });
+
+    assert_eq!(result, Err(PoolError::Panicked("bad element".to_string())));
+}
+
+#[test]
+fn shut_down_pool_rejects_work() {
+    let (pool, _results) = WorkStealingPool::new();
+    pool.shutdown();
+
+    assert_eq!(
+        pool.map_chunks(vec![1, 2, 3], 0, |x: i32| x),
+        Err(PoolError::Shutdown)
+    );
+}
+
+#[test]
+fn shutdown_during_map_returns_instead_of_hanging() {
+    let (pool, _results) = WorkStealingPool::new();
+    let pool = Arc::new(pool);
+
+    let (done_tx, done_rx) = std::sync::mpsc::channel();
+    {
+        let pool = Arc::clone(&pool);
+        thread::spawn(move || {
+            let mapped = pool.map_chunks((0..2000).collect(), 1, |x: u32| {
+                thread::sleep(Duration::from_millis(1));
+                x
+            });
+            let _ = done_tx.send(mapped);
+        });
+    }
+    thread::sleep(Duration::from_millis(20));
+    pool.shutdown();
+
+    let mapped = done_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("map_chunks hung after shutdown");
+    assert_eq!(mapped, Err(PoolError::Shutdown));
+}
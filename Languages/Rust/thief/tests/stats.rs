@@ -0,0 +1,167 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use thief::stats::StatsSnapshot;
+use thief::thread_pool::WorkStealingPool;
+
+/// Poll until `done` holds for a snapshot, or give up after 5 seconds.
+fn wait_for(pool: &WorkStealingPool, done: impl Fn(&StatsSnapshot) -> bool) -> StatsSnapshot {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let snapshot = pool.stats().snapshot();
+        if done(&snapshot) || Instant::now() > deadline {
+            return snapshot;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn counts_completed_and_failed_jobs() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    for i in 0..20 {
+        pool.execute(move || {
+            if i % 5 == 0 {
+                panic!("job {i} fails");
+            }
+        })
+        .unwrap();
+    }
+
+    let stats = wait_for(&pool, |s| s.completed + s.failed == 20);
+    assert_eq!(stats.submitted, 20);
+    assert_eq!(stats.completed, 16);
+    assert_eq!(stats.failed, 4);
+    assert_eq!(stats.queue_depth, 0);
+    assert_eq!(stats.workers.iter().map(|w| w.jobs).sum::<u64>(), 20);
+}
+
+#[test]
+fn panics_caught_by_map_chunks_still_count_as_failed() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    let result = pool.map_chunks((0..10).collect(), 5, |x: i32| {
+        assert!(x != 7, "seven");
+        x
+    });
+    assert!(result.is_err());
+
+    let stats = wait_for(&pool, |s| s.completed + s.failed == 2);
+    assert_eq!((stats.completed, stats.failed), (1, 1));
+}
+
+#[test]
+fn queue_depth_shows_waiting_jobs() {
+    let (pool, _results) = WorkStealingPool::new();
+    let workers = pool.num_workers();
+
+    // Occupy every worker until we say so
+    let (release_tx, release_rx) = crossbeam::channel::unbounded::<()>();
+    let (started_tx, started_rx) = mpsc::channel();
+    for _ in 0..workers {
+        let release_rx = release_rx.clone();
+        let started_tx = started_tx.clone();
+        pool.execute(move || {
+            started_tx.send(()).unwrap();
+            let _ = release_rx.recv();
+        })
+        .unwrap();
+    }
+    for _ in 0..workers {
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    for _ in 0..10 {
+        pool.execute(|| {}).unwrap();
+    }
+    assert_eq!(pool.stats().queue_depth(), 10);
+
+    drop(release_tx);
+    let stats = wait_for(&pool, |s| s.completed == workers as u64 + 10);
+    assert_eq!(stats.queue_depth, 0);
+}
+
+#[test]
+fn stats_handle_does_not_keep_queued_jobs_alive() {
+    let (pool, _results) = WorkStealingPool::new();
+    let workers = pool.num_workers();
+    let stats = pool.stats();
+
+    let (release_tx, release_rx) = crossbeam::channel::unbounded::<()>();
+    let (started_tx, started_rx) = mpsc::channel();
+    for _ in 0..workers {
+        let release_rx = release_rx.clone();
+        let started_tx = started_tx.clone();
+        pool.execute(move || {
+            started_tx.send(()).unwrap();
+            let _ = release_rx.recv();
+        })
+        .unwrap();
+    }
+    for _ in 0..workers {
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    // Each queued job holds a sender; it's dropped with the job
+    let (alive_tx, alive_rx) = mpsc::channel::<()>();
+    for _ in 0..10 {
+        let alive_tx = alive_tx.clone();
+        pool.execute(move || drop(alive_tx)).unwrap();
+    }
+    drop(alive_tx);
+
+    pool.shutdown();
+    drop(release_tx);
+    drop(pool);
+
+    assert_eq!(
+        alive_rx.recv_timeout(Duration::from_secs(5)),
+        Err(mpsc::RecvTimeoutError::Disconnected)
+    );
+    assert_eq!(stats.queue_depth(), 0);
+}
+
+#[test]
+fn busy_time_is_recorded() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    pool.execute(|| thread::sleep(Duration::from_millis(30)))
+        .unwrap();
+
+    let stats = wait_for(&pool, |s| s.completed == 1);
+    let busy: Duration = stats.workers.iter().map(|w| w.busy).sum();
+    assert!(busy >= Duration::from_millis(30), "busy only {busy:?}");
+}
+
+#[test]
+fn idle_workers_steal_from_busy_ones() {
+    let (pool, _results) = WorkStealingPool::new();
+    if pool.num_workers() < 2 {
+        return;
+    }
+
+    // Workers pull batches from the injector, so jobs pile up in one
+    // worker's deque and the others have to steal them
+    let out = pool
+        .map_chunks((0..200).collect(), 1, |x: u32| {
+            thread::sleep(Duration::from_millis(1));
+            x
+        })
+        .unwrap();
+    assert_eq!(out.len(), 200);
+
+    let stats = wait_for(&pool, |s| s.completed == 200);
+    assert!(stats.steals() > 0, "no steals:\n{stats}");
+}
+
+#[test]
+fn snapshot_prints_one_line_per_worker() {
+    let (pool, _results) = WorkStealingPool::new();
+
+    let text = pool.stats().snapshot().to_string();
+
+    assert!(text.starts_with("submitted 0 | completed 0 | failed 0"));
+    assert_eq!(text.lines().count(), 1 + pool.num_workers());
+}
@@ -0,0 +1,102 @@
+//! Facade throughput: create / list / transition through `VerbFacade`
+//! (use cases + repositories, no HTTP) for each database backend
+//!
+//! `cargo bench --bench facade`. Criterion keeps the previous run under
+//! `target/criterion` and reports the change against it. The HTTP side is
+//! measured by `verb_beta bench` (see `src/bench`).
+//!
+//! Backends: in-memory, and in-memory behind the read-through cache. There
+//! is no SQLite backend yet; once there is, it gets a line in `main`.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use criterion::{Criterion, Throughput};
+use tokio::runtime::Runtime;
+use verb_beta::bench_support::{
+    CachePolicy, CachedDatabase, Database, InMemoryCache, InMemoryDatabase, VerbFacade, VerbFilter,
+    VerbState,
+};
+
+/// Verbs in the store while listing
+const LIST_STORE_SIZE: usize = 1_000;
+
+fn bench_backend<D: Database>(c: &mut Criterion, name: &str, db: impl Fn() -> D) {
+    let runtime = Runtime::new().expect("tokio runtime");
+    let mut group = c.benchmark_group(format!("facade/{name}"));
+    group.throughput(Throughput::Elements(1));
+
+    let facade = VerbFacade::new(Arc::new(db()));
+    let mut n = 0usize;
+    group.bench_function("create", |b| {
+        b.to_async(&runtime).iter(|| {
+            n += 1;
+            facade.create_verb(format!("Bench verb {n}"), String::new())
+        })
+    });
+
+    let facade = VerbFacade::new(Arc::new(db()));
+    runtime.block_on(async {
+        for i in 0..LIST_STORE_SIZE {
+            facade
+                .create_verb(format!("Bench verb {i}"), String::new())
+                .await
+                .expect("seed verb");
+        }
+    });
+    group.bench_function("list", |b| {
+        b.to_async(&runtime)
+            .iter(|| facade.list_verbs(VerbFilter::new().with_limit(50)))
+    });
+
+    // Each verb can only be activated once: create a fresh batch per
+    // sample and time the transitions alone
+    let facade = VerbFacade::new(Arc::new(db()));
+    group.bench_function("transition", |b| {
+        b.to_async(&runtime).iter_custom(|iters| {
+            let facade = &facade;
+            async move {
+                let mut ids = Vec::with_capacity(iters as usize);
+                for i in 0..iters {
+                    let verb = facade
+                        .create_verb(format!("Bench verb {i}"), String::new())
+                        .await
+                        .expect("create verb");
+                    ids.push(verb.id());
+                }
+
+                let start = Instant::now();
+                for id in ids {
+                    facade
+                        .transition_verb(id, VerbState::Active, None)
+                        .await
+                        .expect("transition verb");
+                }
+                start.elapsed()
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn main() {
+    let mut c = Criterion::default().configure_from_args();
+
+    bench_backend(&mut c, "memory", InMemoryDatabase::new);
+    bench_backend(&mut c, "memory+cache", || {
+        let policy = CachePolicy {
+            verbs: Some(Duration::from_secs(30)),
+            ..CachePolicy::default()
+        };
+        CachedDatabase::new(
+            InMemoryDatabase::new(),
+            Arc::new(InMemoryCache::default()),
+            policy,
+        )
+    });
+
+    c.final_summary();
+}
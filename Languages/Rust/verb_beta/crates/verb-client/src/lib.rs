@@ -0,0 +1,260 @@
+//! # verb-client
+//!
+//! Typed async client for the verb HTTP API (`/api/v1`). Bodies are the
+//! [`verb_types`] the server itself serializes, so a field renamed on
+//! one side fails to compile on the other.
+//!
+//! ```no_run
+//! use verb_client::{ListVerbsQuery, VerbClient, VerbStateDTO};
+//!
+//! # async fn run() -> Result<(), verb_client::ClientError> {
+//! let client = VerbClient::new("http://127.0.0.1:3000");
+//! let verb = client.create_verb("Ship it", None).await?;
+//! client.transition(&verb.id, VerbStateDTO::Active, None).await?;
+//!
+//! let active = client
+//!     .list(&ListVerbsQuery {
+//!         state: Some(VerbStateDTO::Active),
+//!         ..ListVerbsQuery::default()
+//!     })
+//!     .await?;
+//! println!("{} active", active.total);
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use verb_types::{CreateVerbRequest, Envelope, Problem, UpdateStateRequest};
+
+pub use verb_types::{ListVerbsQuery, ListVerbsResponse, VerbResponse, VerbStateDTO};
+
+/// Why a call failed
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// Connection, timeout or body decoding failure
+    #[error("Request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The server rejected the request; `code` is stable to match on.
+    /// Boxed to keep `Result`s small.
+    #[error("{} ({}): {}", .0.title, .0.code, .0.detail)]
+    Api(Box<Problem>),
+
+    /// A failure that isn't problem+json, e.g. from a proxy in between
+    #[error("Unexpected {status} response: {body}")]
+    UnexpectedResponse { status: u16, body: String },
+}
+
+/// Client for one verb server
+#[derive(Debug, Clone)]
+pub struct VerbClient {
+    http: Client,
+    base_url: String,
+}
+
+impl VerbClient {
+    /// Client for the server at `base_url`, e.g. `http://127.0.0.1:3000`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_client(base_url, Client::new())
+    }
+
+    /// Like [`VerbClient::new`], sending through a preconfigured client
+    /// (timeouts, proxies, default headers)
+    pub fn with_client(base_url: impl Into<String>, http: Client) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Self { http, base_url }
+    }
+
+    /// `POST /api/v1/verbs`
+    pub async fn create_verb(
+        &self,
+        title: impl Into<String>,
+        description: Option<String>,
+    ) -> Result<VerbResponse, ClientError> {
+        let body = CreateVerbRequest {
+            title: title.into(),
+            description,
+        };
+        self.send(self.http.post(self.url("/verbs")).json(&body))
+            .await
+    }
+
+    /// `PUT /api/v1/verbs/{id}/state`
+    ///
+    /// `reason` is recorded in the action log (pausing, dropping).
+    pub async fn transition(
+        &self,
+        id: &str,
+        state: VerbStateDTO,
+        reason: Option<String>,
+    ) -> Result<VerbResponse, ClientError> {
+        let body = UpdateStateRequest { state, reason };
+        self.send(
+            self.http
+                .put(self.url(&format!("/verbs/{id}/state")))
+                .json(&body),
+        )
+        .await
+    }
+
+    /// `GET /api/v1/verbs`
+    pub async fn list(&self, query: &ListVerbsQuery) -> Result<ListVerbsResponse, ClientError> {
+        self.send(self.http.get(self.url("/verbs")).query(query))
+            .await
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/api/v1{path}", self.base_url)
+    }
+
+    /// Send and unwrap the success envelope, or decode the problem
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, ClientError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json::<Envelope<T>>().await?.data);
+        }
+
+        let body = response.text().await?;
+        match serde_json::from_str::<Problem>(&body) {
+            Ok(problem) => Err(ClientError::Api(Box::new(problem))),
+            Err(_) => Err(ClientError::UnexpectedResponse {
+                status: status.as_u16(),
+                body,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Json, Router,
+        extract::{Path, Query},
+        http::StatusCode,
+        response::IntoResponse,
+        routing::{get, post, put},
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn verb(id: &str, title: &str, state: VerbStateDTO) -> VerbResponse {
+        VerbResponse {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            context: None,
+            due_at: None,
+            state,
+            created_at: "2026-03-01T09:00:00Z".to_string(),
+            updated_at: "2026-03-01T09:00:00Z".to_string(),
+        }
+    }
+
+    /// Stand-in server answering with the shared types
+    async fn serve() -> VerbClient {
+        let app = Router::new()
+            .route(
+                "/api/v1/verbs",
+                post(|Json(body): Json<CreateVerbRequest>| async move {
+                    Json(Envelope::ok(
+                        verb("v1", &body.title, VerbStateDTO::Captured),
+                        None,
+                    ))
+                }),
+            )
+            .route(
+                "/api/v1/verbs",
+                get(|Query(query): Query<ListVerbsQuery>| async move {
+                    let verbs = vec![verb("v1", "Ship it", query.state.unwrap())];
+                    Json(Envelope::ok(
+                        ListVerbsResponse {
+                            total: 1,
+                            limit: query.limit.unwrap_or(50),
+                            offset: 0,
+                            verbs,
+                        },
+                        None,
+                    ))
+                }),
+            )
+            .route(
+                "/api/v1/verbs/{id}/state",
+                put(
+                    |Path(id): Path<String>, Json(body): Json<UpdateStateRequest>| async move {
+                        if id != "v1" {
+                            let problem = json!({
+                                "type": "urn:verb:problem:verb-not-found",
+                                "title": "Verb not found",
+                                "status": 404,
+                                "detail": "Verb not found",
+                                "code": "VERB_NOT_FOUND"
+                            });
+                            return (StatusCode::NOT_FOUND, Json(problem)).into_response();
+                        }
+                        Json(Envelope::ok(verb("v1", "Ship it", body.state), None)).into_response()
+                    },
+                ),
+            );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        VerbClient::new(format!("http://{addr}/"))
+    }
+
+    #[tokio::test]
+    async fn typed_calls_round_trip() {
+        let client = serve().await;
+
+        let created = client.create_verb("Ship it", None).await.unwrap();
+        assert_eq!(created.title, "Ship it");
+        assert_eq!(created.state, VerbStateDTO::Captured);
+
+        let active = client
+            .transition(&created.id, VerbStateDTO::Active, None)
+            .await
+            .unwrap();
+        assert_eq!(active.state, VerbStateDTO::Active);
+
+        let listed = client
+            .list(&ListVerbsQuery {
+                state: Some(VerbStateDTO::Paused),
+                limit: Some(10),
+                offset: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(listed.limit, 10);
+        assert_eq!(listed.verbs[0].state, VerbStateDTO::Paused);
+    }
+
+    #[tokio::test]
+    async fn problems_become_api_errors() {
+        let client = serve().await;
+
+        let err = client
+            .transition("missing", VerbStateDTO::Done, None)
+            .await
+            .unwrap_err();
+        match err {
+            ClientError::Api(problem) => {
+                assert_eq!(problem.status, 404);
+                assert_eq!(problem.code, "VERB_NOT_FOUND");
+            }
+            other => panic!("expected an API error, got {other:?}"),
+        }
+
+        let err = client
+            .send::<VerbResponse>(client.http.get(client.url("/nowhere")))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ClientError::UnexpectedResponse { status: 404, .. }
+        ));
+    }
+}
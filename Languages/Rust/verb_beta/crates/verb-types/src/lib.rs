@@ -0,0 +1,176 @@
+//! # Verb API wire types
+//!
+//! Request and response bodies of the verb HTTP API, shared by the server
+//! (`verb_beta`) and `verb-client` so the two can't drift apart. Only
+//! serde shapes and request validation live here; conversions from domain
+//! entities stay in the server.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::Validate;
+
+// ==================================================
+// Request DTOs
+// ==================================================
+
+/// Request to create a new verb
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateVerbRequest {
+    #[validate(length(min = 1, max = 200, message = "Title must be 1-200 characters"))]
+    pub title: String,
+
+    #[validate(length(max = 2000, message = "Description cannot exceed 2000 characters"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Request to update verb state
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct UpdateStateRequest {
+    pub state: VerbStateDTO,
+
+    #[validate(length(max = 500, message = "Reason cannot exceed 500 characters"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Query parameters for listing verbs
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ListVerbsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<VerbStateDTO>,
+
+    #[validate(range(min = 1, max = 100))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+}
+
+impl Default for ListVerbsQuery {
+    fn default() -> Self {
+        Self {
+            state: None,
+            limit: Some(50),
+            offset: Some(0),
+        }
+    }
+}
+
+// ==================================================
+// Response DTOs
+// ==================================================
+
+/// Verb state DTO (serializable enum)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerbStateDTO {
+    Captured,
+    Active,
+    Paused,
+    Done,
+    Dropped,
+}
+
+/// Verb response DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerbResponse {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub context: Option<String>,
+    /// RFC 3339
+    pub due_at: Option<String>,
+    pub state: VerbStateDTO,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// List of verbs response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListVerbsResponse {
+    pub verbs: Vec<VerbResponse>,
+    pub total: u32,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+// ==================================================
+// Envelopes
+// ==================================================
+
+/// Success body: `{"status": "ok", "data": ..., "meta": ...}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope<T> {
+    pub status: String,
+    pub data: T,
+    #[serde(default)]
+    pub meta: Option<Value>,
+}
+
+impl<T> Envelope<T> {
+    pub fn ok(data: T, meta: Option<Value>) -> Self {
+        Self {
+            status: "ok".to_string(),
+            data,
+            meta,
+        }
+    }
+}
+
+/// Failure body (`application/problem+json`, RFC 7807)
+///
+/// `C` is the error code: the server's `ErrorCode` enum, or its
+/// SCREAMING_SNAKE_CASE name on the client side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Problem<C = String> {
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: C,
+    /// Per-field messages for validation failures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn list_query_omits_unset_parameters() {
+        let query = ListVerbsQuery {
+            state: Some(VerbStateDTO::Paused),
+            limit: None,
+            offset: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({ "state": "paused" })
+        );
+    }
+
+    #[test]
+    fn envelope_and_problem_round_trip() {
+        let envelope: Envelope<Vec<u32>> =
+            serde_json::from_value(json!({ "status": "ok", "data": [1, 2], "meta": null }))
+                .unwrap();
+        assert_eq!(envelope.data, vec![1, 2]);
+
+        let problem: Problem = serde_json::from_value(json!({
+            "type": "urn:verb:problem:verb-not-found",
+            "title": "Verb not found",
+            "status": 404,
+            "detail": "Verb not found",
+            "code": "VERB_NOT_FOUND"
+        }))
+        .unwrap();
+        assert_eq!(problem.code, "VERB_NOT_FOUND");
+        assert!(problem.errors.is_none());
+    }
+}
@@ -0,0 +1,12 @@
+-- Verbs: a user's intent over time
+CREATE TABLE verbs (
+    id          TEXT PRIMARY KEY NOT NULL,
+    title       TEXT NOT NULL CHECK (length(title) BETWEEN 1 AND 200),
+    description TEXT NOT NULL DEFAULT '' CHECK (length(description) <= 2000),
+    state       TEXT NOT NULL CHECK (state IN ('Captured', 'Active', 'Paused', 'Done', 'Dropped')),
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+CREATE INDEX idx_verbs_state ON verbs (state);
+CREATE INDEX idx_verbs_updated_at ON verbs (updated_at);
@@ -0,0 +1,13 @@
+-- Append-only audit trail of verb changes
+CREATE TABLE action_logs (
+    id             TEXT PRIMARY KEY NOT NULL,
+    verb_id        TEXT NOT NULL REFERENCES verbs (id),
+    action_type    TEXT NOT NULL,
+    from_state     TEXT,
+    to_state       TEXT NOT NULL,
+    reason         TEXT CHECK (reason IS NULL OR length(reason) <= 500),
+    changed_fields TEXT NOT NULL DEFAULT '[]', -- JSON array of field names
+    timestamp      TEXT NOT NULL
+);
+
+CREATE INDEX idx_action_logs_verb_id ON action_logs (verb_id, timestamp);
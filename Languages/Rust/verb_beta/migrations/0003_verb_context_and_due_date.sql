@@ -0,0 +1,3 @@
+-- Editable fields added with PATCH /verbs/{id}
+ALTER TABLE verbs ADD COLUMN context TEXT CHECK (context IS NULL OR length(context) <= 1000);
+ALTER TABLE verbs ADD COLUMN due_at TEXT;
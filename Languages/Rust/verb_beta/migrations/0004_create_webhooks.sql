@@ -0,0 +1,23 @@
+-- Registered callback urls and their delivery-attempt log
+CREATE TABLE webhooks (
+    id         TEXT PRIMARY KEY NOT NULL,
+    url        TEXT NOT NULL,
+    events     TEXT NOT NULL, -- JSON array of event names
+    secret     TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE webhook_deliveries (
+    id              TEXT PRIMARY KEY NOT NULL,
+    webhook_id      TEXT NOT NULL,
+    event_id        TEXT NOT NULL,
+    event           TEXT NOT NULL,
+    verb_id         TEXT NOT NULL,
+    attempt         INTEGER NOT NULL,
+    status          TEXT NOT NULL CHECK (status IN ('Succeeded', 'Failed')),
+    response_status INTEGER,
+    error           TEXT,
+    timestamp       TEXT NOT NULL
+);
+
+CREATE INDEX idx_webhook_deliveries_webhook_id ON webhook_deliveries (webhook_id, timestamp);
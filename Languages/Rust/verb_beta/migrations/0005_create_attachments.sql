@@ -0,0 +1,11 @@
+-- Metadata of files attached to verbs; contents live in the blob store
+CREATE TABLE attachments (
+    id           TEXT PRIMARY KEY NOT NULL,
+    verb_id      TEXT NOT NULL REFERENCES verbs (id),
+    filename     TEXT NOT NULL CHECK (length(filename) BETWEEN 1 AND 255),
+    content_type TEXT NOT NULL,
+    size         INTEGER NOT NULL CHECK (size > 0),
+    created_at   TEXT NOT NULL
+);
+
+CREATE INDEX idx_attachments_verb_id ON attachments (verb_id, created_at);
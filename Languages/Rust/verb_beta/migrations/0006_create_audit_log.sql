@@ -0,0 +1,18 @@
+-- Who did what, from where: one row per mutating request (or per entity
+-- it changed). Separate from action_logs, and never part of snapshots.
+CREATE TABLE audit_log (
+    id          TEXT PRIMARY KEY NOT NULL,
+    actor       TEXT NOT NULL, -- admin, anonymous or user:<uuid>
+    ip          TEXT,
+    method      TEXT NOT NULL,
+    endpoint    TEXT NOT NULL, -- route template, e.g. /api/v1/verbs/{id}
+    status      INTEGER NOT NULL,
+    entity_kind TEXT,
+    entity_id   TEXT,
+    before      TEXT, -- JSON snapshot
+    after       TEXT, -- JSON snapshot
+    timestamp   TEXT NOT NULL
+);
+
+CREATE INDEX idx_audit_log_actor ON audit_log (actor, timestamp);
+CREATE INDEX idx_audit_log_timestamp ON audit_log (timestamp);
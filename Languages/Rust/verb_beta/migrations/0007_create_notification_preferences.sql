@@ -0,0 +1,9 @@
+-- What each user wants to be notified about, and when not to be
+CREATE TABLE notification_preferences (
+    user_id           TEXT PRIMARY KEY NOT NULL,
+    disabled_channels TEXT NOT NULL, -- JSON array of channel names
+    muted_topics      TEXT NOT NULL, -- JSON array of topic names
+    quiet_hours       TEXT, -- HH:MM-HH:MM, read at utc_offset
+    utc_offset        TEXT NOT NULL DEFAULT '+00:00', -- ±HH:MM
+    updated_at        TEXT NOT NULL
+);
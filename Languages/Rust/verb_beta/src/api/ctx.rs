@@ -0,0 +1,55 @@
+//! Per-request context
+//!
+//! [`Ctx`] carries what the client asked for about presentation — the
+//! negotiated [`Locale`] and an optional UTC offset for timestamps. It is
+//! resolved once by the `resolve_ctx` middleware, available to handlers as
+//! an extractor, and scoped over the rest of the request so response
+//! rendering (error titles, DTO timestamps) can reach it without threading
+//! it through every call.
+
+use std::convert::Infallible;
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::api::i18n::Locale;
+
+tokio::task_local! {
+    static CURRENT: Ctx;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Ctx {
+    /// Language for human-readable response text
+    pub locale: Locale,
+    /// Offset to render timestamps in; `None` keeps them in UTC
+    pub utc_offset: Option<UtcOffset>,
+}
+
+impl Ctx {
+    /// The context of the request being handled, or the default outside one
+    pub fn current() -> Self {
+        CURRENT.try_with(|ctx| *ctx).unwrap_or_default()
+    }
+
+    /// Run `f` with `self` as the current context
+    pub async fn scope<F: Future>(self, f: F) -> F::Output {
+        CURRENT.scope(self, f).await
+    }
+
+    /// `at` in the client's requested offset
+    pub fn local_time(&self, at: OffsetDateTime) -> OffsetDateTime {
+        match self.utc_offset {
+            Some(offset) => at.to_offset(offset),
+            None => at,
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Ctx {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<Ctx>().copied().unwrap_or_default())
+    }
+}
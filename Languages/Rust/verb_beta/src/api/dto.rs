@@ -2,55 +2,137 @@
 //!
 //! DTOs define the contract between HTTP clients and the application layer.
 
+use std::collections::BTreeMap;
+
 use axum::{Json, http::StatusCode, response::IntoResponse};
-use serde::{Deserialize, Serialize};
-use serde_json::{Value, json};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use time::{OffsetDateTime, UtcOffset, format_description::well_known::Rfc3339};
 use validator::Validate;
 
-use crate::domain::model::{ActionLog, ActionType, Verb, VerbId, VerbState};
+use crate::{
+    api::{
+        ctx::Ctx,
+        error::{ApiError, ErrorCode},
+    },
+    application::{
+        Feature, VerbEvent,
+        use_cases::{
+            Burndown, DailyReview, ReviewEntry, SyncChanges, SyncMutation, SyncOp, SyncOutcome,
+            SyncStatus, VerbChanges,
+        },
+    },
+    domain::{
+        DomainError,
+        model::{
+            ActionLog, ActionType, Actor, Attachment, AuditEntry, Channel, NotificationPreferences,
+            NotificationTopic, QuietHours, UserId, Verb, VerbId, VerbState, Webhook,
+            WebhookDelivery,
+        },
+        repository::AuditFilter,
+    },
+    infra::{
+        db::DatabaseStats,
+        repository::cached::{CacheCounts, CacheStats},
+    },
+};
+
+/// A response timestamp in the client's requested offset (see [`Ctx`])
+fn timestamp(at: OffsetDateTime) -> String {
+    Ctx::current().local_time(at).to_string()
+}
+
+/// Like [`timestamp`], formatted as RFC 3339
+fn rfc3339(at: OffsetDateTime) -> String {
+    Ctx::current()
+        .local_time(at)
+        .format(&Rfc3339)
+        .unwrap_or_default()
+}
+
+/// `offset` as `±HH:MM`, the form the `Time-Zone` header takes
+fn utc_offset(offset: UtcOffset) -> String {
+    let (hours, minutes, _) = offset.as_hms();
+    let sign = if offset.is_negative() { '-' } else { '+' };
+    format!("{sign}{:02}:{:02}", hours.abs(), minutes.abs())
+}
 
 // ==================================================
 // Request DTOs
 // ==================================================
 
-/// Request to create a new verb
-#[derive(Debug, Deserialize, Validate)]
-pub struct CreateVerbRequest {
-    #[validate(length(min = 1, max = 200, message = "Title must be 1-200 characters"))]
-    pub title: String,
+// Verb requests, responses and the envelopes are shared with `verb-client`
+pub use verb_types::{
+    CreateVerbRequest, Envelope, ListVerbsQuery, ListVerbsResponse, UpdateStateRequest,
+    VerbResponse, VerbStateDTO,
+};
 
-    #[validate(length(max = 2000, message = "Description cannot exceed 2000 characters"))]
-    pub description: Option<String>,
-}
+/// Partial update of a verb (`application/merge-patch+json`, RFC 7386)
+///
+/// Omitted fields are left alone; `null` clears a field. State is not
+/// patchable here; use `PUT /verbs/{id}/state`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatchVerbRequest {
+    #[serde(default, deserialize_with = "present")]
+    pub title: Option<Option<String>>,
 
-/// Request to update verb state
-#[derive(Debug, Deserialize, Validate)]
-pub struct UpdateStateRequest {
-    pub state: VerbStateDTO,
+    #[serde(default, deserialize_with = "present")]
+    pub description: Option<Option<String>>,
 
-    #[validate(length(max = 500, message = "Reason cannot exceed 500 characters"))]
-    pub reason: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    pub context: Option<Option<String>>,
+
+    /// RFC 3339 timestamp, e.g. `2026-03-01T17:00:00Z`
+    #[serde(default, deserialize_with = "present")]
+    pub due_at: Option<Option<String>>,
 }
 
-/// Query parameters for listing verbs
-#[derive(Debug, Deserialize, Validate)]
-pub struct ListVerbsQuery {
-    pub state: Option<VerbStateDTO>,
+impl PatchVerbRequest {
+    /// Convert to the application's change set, rejecting values JSON can
+    /// express but a verb cannot hold; content rules are checked by the
+    /// value objects the application builds from it
+    pub fn into_changes(self) -> Result<VerbChanges, String> {
+        let title = match self.title {
+            Some(None) => return Err("Title cannot be null".to_string()),
+            other => other.flatten(),
+        };
 
-    #[validate(range(min = 1, max = 100))]
-    pub limit: Option<u32>,
+        let due_at = self
+            .due_at
+            .map(|due| {
+                due.map(|raw| {
+                    OffsetDateTime::parse(&raw, &Rfc3339)
+                        .map_err(|_| format!("Invalid due_at '{raw}', expected RFC 3339"))
+                })
+                .transpose()
+            })
+            .transpose()?;
 
-    pub offset: Option<u32>,
+        Ok(VerbChanges {
+            title,
+            description: self.description,
+            context: self.context,
+            due_at,
+        })
+    }
 }
 
-impl Default for ListVerbsQuery {
-    fn default() -> Self {
-        Self {
-            state: None,
-            limit: Some(50),
-            offset: Some(0),
-        }
-    }
+/// Deserialize a field that is present in the body (value or `null`) as
+/// `Some(..)`; combined with `#[serde(default)]`, absent fields stay `None`.
+fn present<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Query parameters for streaming verbs
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StreamVerbsQuery {
+    pub state: Option<VerbStateDTO>,
 }
 
 ///Query Params for getting logs by verb_id
@@ -74,21 +156,37 @@ impl Default for GetLogsQuery {
     }
 }
 
+/// Request to register a webhook
+#[derive(Debug, Deserialize, Validate)]
+pub struct RegisterWebhookRequest {
+    #[validate(length(min = 1, max = 2000, message = "Url must be 1-2000 characters"))]
+    pub url: String,
+
+    /// Event names, e.g. `verb.created`, `verb.done`
+    #[validate(length(min = 1, message = "At least one event is required"))]
+    pub events: Vec<String>,
+
+    /// Signing secret; generated when omitted
+    #[validate(length(min = 1, max = 200, message = "Secret must be 1-200 characters"))]
+    pub secret: Option<String>,
+
+    /// User whose notification preferences deliveries follow
+    pub user_id: Option<String>,
+}
+
+/// Query parameters for listing webhook deliveries
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListDeliveriesQuery {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+
+    pub offset: Option<u32>,
+}
+
 // ==================================================
 // Response DTOs
 // ==================================================
 
-/// Verb state DTO (serializable enum)
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
-pub enum VerbStateDTO {
-    Captured,
-    Active,
-    Paused,
-    Done,
-    Dropped,
-}
-
 impl From<VerbState> for VerbStateDTO {
     fn from(state: VerbState) -> Self {
         match state {
@@ -122,6 +220,8 @@ pub enum ActionTypeDTO {
     Paused,
     Completed,
     Dropped,
+    UpdatedFields,
+    SyncConflict,
 }
 
 impl From<ActionType> for ActionTypeDTO {
@@ -132,6 +232,8 @@ impl From<ActionType> for ActionTypeDTO {
             ActionType::Paused => ActionTypeDTO::Paused,
             ActionType::Completed => ActionTypeDTO::Completed,
             ActionType::Dropped => ActionTypeDTO::Dropped,
+            ActionType::UpdatedFields => ActionTypeDTO::UpdatedFields,
+            ActionType::SyncConflict => ActionTypeDTO::SyncConflict,
         }
     }
 }
@@ -144,41 +246,45 @@ impl From<ActionTypeDTO> for ActionType {
             ActionTypeDTO::Paused => ActionType::Paused,
             ActionTypeDTO::Completed => ActionType::Completed,
             ActionTypeDTO::Dropped => ActionType::Dropped,
+            ActionTypeDTO::UpdatedFields => ActionType::UpdatedFields,
+            ActionTypeDTO::SyncConflict => ActionType::SyncConflict,
         }
     }
 }
 
-/// Verb response DTO
-#[derive(Debug, Serialize)]
-pub struct VerbResponse {
-    pub id: String,
-    pub title: String,
-    pub description: String,
-    pub state: VerbStateDTO,
-    pub created_at: String,
-    pub updated_at: String,
-}
-
 impl From<Verb> for VerbResponse {
     fn from(verb: Verb) -> Self {
         Self {
             id: verb.id().to_string(),
             title: verb.title().to_string(),
             description: verb.description().to_string(),
+            context: verb.context().map(str::to_string),
+            due_at: verb.due_at().map(rfc3339),
             state: verb.state().into(),
-            created_at: verb.created_at().to_string(),
-            updated_at: verb.updated_at().to_string(),
+            created_at: timestamp(verb.created_at()),
+            updated_at: timestamp(verb.updated_at()),
         }
     }
 }
 
-/// List of verbs response
+/// A verb event on the live SSE stream; same shape as the webhook body
 #[derive(Debug, Serialize)]
-pub struct ListVerbsResponse {
-    pub verbs: Vec<VerbResponse>,
-    pub total: u32,
-    pub limit: u32,
-    pub offset: u32,
+pub struct VerbEventResponse {
+    pub id: String,
+    pub event: &'static str,
+    pub occurred_at: String,
+    pub data: VerbResponse,
+}
+
+impl From<VerbEvent> for VerbEventResponse {
+    fn from(event: VerbEvent) -> Self {
+        Self {
+            id: event.id.to_string(),
+            event: event.event_type.as_str(),
+            occurred_at: rfc3339(event.occurred_at),
+            data: event.verb.into(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -189,6 +295,8 @@ pub struct ActionLogResponse {
     pub from_state: Option<String>,
     pub to_state: String,
     pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_fields: Vec<String>,
     pub timestamp: String,
 }
 
@@ -201,7 +309,8 @@ impl From<ActionLog> for ActionLogResponse {
             from_state: log.from_state().map(|s| s.as_str().to_string()),
             to_state: log.to_state().as_str().to_string(),
             reason: log.reason().map(|s| s.to_string()),
-            timestamp: log.timestamp().to_string(),
+            changed_fields: log.changed_fields().to_vec(),
+            timestamp: timestamp(log.timestamp()),
         }
     }
 }
@@ -214,87 +323,947 @@ pub struct GetActionLogsResponse {
     pub offset: u32,
 }
 
+/// Webhook response DTO (never includes the secret)
+#[derive(Debug, Serialize)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    pub created_at: String,
+}
+
+impl From<&Webhook> for WebhookResponse {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            id: webhook.id().to_string(),
+            url: webhook.url().to_string(),
+            events: webhook.events().iter().map(|e| e.as_str()).collect(),
+            user_id: webhook.owner().map(|u| u.to_string()),
+            created_at: timestamp(webhook.created_at()),
+        }
+    }
+}
+
+/// Returned once on registration: the only time the secret is shown
+#[derive(Debug, Serialize)]
+pub struct WebhookCreatedResponse {
+    #[serde(flatten)]
+    pub webhook: WebhookResponse,
+    pub secret: String,
+}
+
+impl From<Webhook> for WebhookCreatedResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            webhook: WebhookResponse::from(&webhook),
+            secret: webhook.secret().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub webhook_id: String,
+    pub event_id: String,
+    pub event: &'static str,
+    pub verb_id: String,
+    pub attempt: u32,
+    pub status: &'static str,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub timestamp: String,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id().to_string(),
+            webhook_id: delivery.webhook_id().to_string(),
+            event_id: delivery.event_id().to_string(),
+            event: delivery.event().as_str(),
+            verb_id: delivery.verb_id().to_string(),
+            attempt: delivery.attempt(),
+            status: delivery.status().as_str(),
+            response_status: delivery.response_status(),
+            error: delivery.error().map(str::to_string),
+            timestamp: timestamp(delivery.timestamp()),
+        }
+    }
+}
+
+///List of webhook deliveries response
+#[derive(Debug, Serialize)]
+pub struct ListDeliveriesResponse {
+    pub deliveries: Vec<WebhookDeliveryResponse>,
+    pub total: u32,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+/// Attachment metadata
+#[derive(Debug, Serialize)]
+pub struct AttachmentResponse {
+    pub id: String,
+    pub verb_id: String,
+    pub filename: String,
+    pub content_type: String,
+    pub size: u64,
+    pub created_at: String,
+}
+
+impl From<&Attachment> for AttachmentResponse {
+    fn from(attachment: &Attachment) -> Self {
+        Self {
+            id: attachment.id().to_string(),
+            verb_id: attachment.verb_id().to_string(),
+            filename: attachment.filename().to_string(),
+            content_type: attachment.content_type().to_string(),
+            size: attachment.size(),
+            created_at: timestamp(attachment.created_at()),
+        }
+    }
+}
+
+/// A verb in the daily review
+#[derive(Debug, Serialize)]
+pub struct ReviewEntryResponse {
+    pub verb: VerbResponse,
+    /// RFC 3339; completed at, paused since, or due at, per section
+    pub at: String,
+}
+
+impl From<ReviewEntry> for ReviewEntryResponse {
+    fn from(entry: ReviewEntry) -> Self {
+        Self {
+            at: rfc3339(entry.at),
+            verb: VerbResponse::from(entry.verb),
+        }
+    }
+}
+
+/// Daily review digest
+#[derive(Debug, Serialize)]
+pub struct DailyReviewResponse {
+    /// `YYYY-MM-DD`, UTC
+    pub date: String,
+    pub completed_yesterday: Vec<ReviewEntryResponse>,
+    pub stale_paused: Vec<ReviewEntryResponse>,
+    pub overdue: Vec<ReviewEntryResponse>,
+}
+
+impl From<DailyReview> for DailyReviewResponse {
+    fn from(review: DailyReview) -> Self {
+        let entries = |list: Vec<ReviewEntry>| list.into_iter().map(Into::into).collect();
+        Self {
+            date: review.date.to_string(),
+            completed_yesterday: entries(review.completed_yesterday),
+            stale_paused: entries(review.stale_paused),
+            overdue: entries(review.overdue),
+        }
+    }
+}
+
 // ==================================================
-// Standard API Response Envelope
+// Stats DTOs
 // ==================================================
 
-/// Standard API response wrapper
+/// Query parameters for the burn-down chart
+#[derive(Debug, Default, Deserialize)]
+pub struct BurndownQuery {
+    /// Window length in days or weeks, e.g. `30d` or `4w`; default `30d`
+    pub window: Option<String>,
+}
+
+impl BurndownQuery {
+    const DEFAULT_DAYS: u16 = 30;
+    const MAX_DAYS: u16 = 366;
+
+    /// Window length in days, between 1 and a year
+    pub fn window_days(&self) -> Result<u16, String> {
+        let Some(raw) = self.window.as_deref() else {
+            return Ok(Self::DEFAULT_DAYS);
+        };
+        let invalid = || format!("Invalid window '{raw}', expected e.g. 30d or 4w");
+
+        let (count, unit) = raw.split_at(raw.len().saturating_sub(1));
+        let count: u16 = count.parse().map_err(|_| invalid())?;
+        let days = match unit {
+            "d" => count,
+            "w" => count.checked_mul(7).ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+
+        if (1..=Self::MAX_DAYS).contains(&days) {
+            Ok(days)
+        } else {
+            Err(format!(
+                "Window must be between 1 and {} days",
+                Self::MAX_DAYS
+            ))
+        }
+    }
+}
+
+/// Daily series for a burn-down chart; `dates[i]` labels index `i` of
+/// every series
 #[derive(Debug, Serialize)]
-#[serde(tag = "status", rename_all = "snake_case")]
-pub enum ApiResponse<T> {
-    Ok { data: T, meta: Option<Value> },
-    Error { error: ApiError },
+pub struct BurndownResponse {
+    pub window_days: usize,
+    /// `YYYY-MM-DD`, UTC
+    pub from: String,
+    pub to: String,
+    pub dates: Vec<String>,
+    pub series: BurndownSeries,
 }
 
-impl<T: Serialize> ApiResponse<T> {
-    pub fn ok(data: T) -> Self {
-        Self::Ok { data, meta: None }
+#[derive(Debug, Serialize)]
+pub struct BurndownSeries {
+    pub created: Vec<u32>,
+    pub completed: Vec<u32>,
+    pub dropped: Vec<u32>,
+}
+
+impl From<Burndown> for BurndownResponse {
+    fn from(burndown: Burndown) -> Self {
+        Self {
+            window_days: burndown.dates.len(),
+            from: burndown.from.to_string(),
+            to: burndown.to.to_string(),
+            dates: burndown.dates.iter().map(ToString::to_string).collect(),
+            series: BurndownSeries {
+                created: burndown.created,
+                completed: burndown.completed,
+                dropped: burndown.dropped,
+            },
+        }
     }
+}
 
-    pub fn ok_with_meta(data: T, meta: Value) -> Self {
-        Self::Ok {
-            data,
-            meta: Some(meta),
+// ==================================================
+// Notification Preferences DTOs
+// ==================================================
+
+fn on() -> bool {
+    true
+}
+
+/// Which channels may be used; omitted channels are on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelToggles {
+    #[serde(default = "on")]
+    pub email: bool,
+    #[serde(default = "on")]
+    pub webhook: bool,
+    #[serde(default = "on")]
+    pub websocket: bool,
+}
+
+impl Default for ChannelToggles {
+    fn default() -> Self {
+        Self {
+            email: true,
+            webhook: true,
+            websocket: true,
         }
     }
+}
+
+/// Replacement preferences for a user (`PUT`)
+///
+/// Omitted channels and topics are on; `quiet_hours` is
+/// `HH:MM-HH:MM`, or absent for none. Quiet hours are read in the
+/// request's `Time-Zone` offset (UTC without one), stored with them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotificationPreferencesRequest {
+    #[serde(default)]
+    pub channels: ChannelToggles,
+    /// Topic name to on/off, e.g. `{"verb.created": false}`
+    #[serde(default)]
+    pub events: BTreeMap<String, bool>,
+    pub quiet_hours: Option<String>,
+}
+
+impl NotificationPreferencesRequest {
+    /// Build the preferences to store for `user_id`, whose local offset
+    /// is `utc_offset`
+    pub fn into_preferences(
+        self,
+        user_id: UserId,
+        utc_offset: UtcOffset,
+    ) -> Result<NotificationPreferences, DomainError> {
+        let mut preferences = NotificationPreferences::defaults(user_id);
+        preferences.set_utc_offset(utc_offset);
+        preferences.set_channel(Channel::Email, self.channels.email);
+        preferences.set_channel(Channel::Webhook, self.channels.webhook);
+        preferences.set_channel(Channel::WebSocket, self.channels.websocket);
+        for (topic, enabled) in &self.events {
+            preferences.set_topic(NotificationTopic::from_str(topic)?, *enabled);
+        }
+        preferences.set_quiet_hours(self.quiet_hours.map(QuietHours::try_from).transpose()?);
+        Ok(preferences)
+    }
+}
+
+/// A user's effective preferences; every topic is listed
+#[derive(Debug, Serialize)]
+pub struct NotificationPreferencesResponse {
+    pub user_id: String,
+    pub channels: ChannelToggles,
+    pub events: BTreeMap<&'static str, bool>,
+    pub quiet_hours: Option<String>,
+    /// Offset quiet hours are read in, `±HH:MM`
+    pub time_zone: String,
+    pub updated_at: String,
+}
 
-    pub fn error(code: ErrorCode, message: impl Into<String>) -> Self {
-        Self::Error {
-            error: ApiError {
-                code,
-                message: message.into(),
-                details: None,
+impl From<NotificationPreferences> for NotificationPreferencesResponse {
+    fn from(preferences: NotificationPreferences) -> Self {
+        Self {
+            user_id: preferences.user_id().to_string(),
+            channels: ChannelToggles {
+                email: preferences.channel_enabled(Channel::Email),
+                webhook: preferences.channel_enabled(Channel::Webhook),
+                websocket: preferences.channel_enabled(Channel::WebSocket),
             },
+            events: NotificationTopic::ALL
+                .into_iter()
+                .map(|t| (t.as_str(), preferences.topic_enabled(t)))
+                .collect(),
+            quiet_hours: preferences.quiet_hours().map(|q| q.to_string()),
+            time_zone: utc_offset(preferences.utc_offset()),
+            updated_at: timestamp(preferences.updated_at()),
         }
     }
 }
 
-impl<T: Serialize> IntoResponse for ApiResponse<T> {
-    fn into_response(self) -> axum::response::Response {
-        match self {
-            ApiResponse::Ok { data, meta } => {
-                let body = json!({
-                    "status": "ok",
-                    "data": data,
-                    "meta": meta
-                });
-                (StatusCode::OK, Json(body)).into_response()
-            }
-            ApiResponse::Error { error } => {
-                let status = match error.code {
-                    ErrorCode::ValidationError => StatusCode::BAD_REQUEST,
-                    ErrorCode::NotFound => StatusCode::NOT_FOUND,
-                    ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
-                    ErrorCode::Conflict => StatusCode::CONFLICT,
-                    ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
-                };
+// ==================================================
+// Admin DTOs
+// ==================================================
 
-                let body = json!({
-                    "status": "error",
-                    "error": error
-                });
+/// Request to replace the log filter
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetLogLevelRequest {
+    /// `RUST_LOG` syntax, e.g. `info,verb=trace`
+    #[validate(length(min = 1, message = "Filter must not be empty"))]
+    pub filter: String,
+}
 
-                (status, Json(body)).into_response()
-            }
+/// Request to switch a feature on or off
+#[derive(Debug, Deserialize)]
+pub struct SetFeatureRequest {
+    pub enabled: bool,
+}
+
+/// Active log filter
+#[derive(Debug, Serialize)]
+pub struct LogLevelResponse {
+    pub filter: Option<String>,
+}
+
+/// One feature flag and its current value
+#[derive(Debug, Serialize)]
+pub struct FeatureResponse {
+    pub name: &'static str,
+    pub enabled: bool,
+}
+
+impl From<(Feature, bool)> for FeatureResponse {
+    fn from((feature, enabled): (Feature, bool)) -> Self {
+        Self {
+            name: feature.as_str(),
+            enabled,
         }
     }
 }
 
-/// API error structure
+/// Database row counts
 #[derive(Debug, Serialize)]
-pub struct ApiError {
-    pub code: ErrorCode,
-    pub message: String,
-    pub details: Option<Value>,
+pub struct DatabaseStatsResponse {
+    pub backend: &'static str,
+    pub verbs: usize,
+    pub action_logs: usize,
+    pub webhooks: usize,
+    pub webhook_deliveries: usize,
+    pub attachments: usize,
+    pub notification_preferences: usize,
+    pub audit_entries: usize,
+    /// Only when the repository cache is on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheStatsResponse>,
+}
+
+/// Repository cache hits and misses per entity type
+#[derive(Debug, Serialize)]
+pub struct CacheStatsResponse {
+    pub verbs: CacheCountsResponse,
+    pub webhooks: CacheCountsResponse,
+    pub notification_preferences: CacheCountsResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheCountsResponse {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl From<DatabaseStats> for DatabaseStatsResponse {
+    fn from(stats: DatabaseStats) -> Self {
+        Self {
+            backend: stats.backend,
+            verbs: stats.verbs,
+            action_logs: stats.action_logs,
+            webhooks: stats.webhooks,
+            webhook_deliveries: stats.webhook_deliveries,
+            attachments: stats.attachments,
+            notification_preferences: stats.notification_preferences,
+            audit_entries: stats.audit_entries,
+            cache: stats.cache.map(CacheStatsResponse::from),
+        }
+    }
+}
+
+impl From<CacheStats> for CacheStatsResponse {
+    fn from(stats: CacheStats) -> Self {
+        let counts = |c: CacheCounts| CacheCountsResponse {
+            hits: c.hits,
+            misses: c.misses,
+        };
+        Self {
+            verbs: counts(stats.verbs),
+            webhooks: counts(stats.webhooks),
+            notification_preferences: counts(stats.notification_preferences),
+        }
+    }
+}
+
+/// Query parameters for listing the audit log
+///
+/// `from` and `to` are RFC 3339 timestamps bounding `[from, to)`.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct ListAuditQuery {
+    /// `admin`, `anonymous` or `user:<uuid>`
+    pub actor: Option<String>,
+
+    pub from: Option<String>,
+
+    pub to: Option<String>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+
+    pub offset: Option<u32>,
+}
+
+impl ListAuditQuery {
+    /// The repository filter, rejecting unknown actors, malformed times
+    /// and empty ranges
+    pub fn into_filter(self) -> Result<AuditFilter, String> {
+        let time = |name: &str, raw: Option<String>| {
+            raw.map(|raw| {
+                OffsetDateTime::parse(&raw, &Rfc3339)
+                    .map_err(|_| format!("Invalid {name} '{raw}', expected RFC 3339"))
+            })
+            .transpose()
+        };
+
+        let actor = self
+            .actor
+            .map(|raw| raw.parse::<Actor>())
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        let from = time("from", self.from)?;
+        let to = time("to", self.to)?;
+        if let (Some(from), Some(to)) = (from, to)
+            && from >= to
+        {
+            return Err("'from' must be before 'to'".to_string());
+        }
+
+        Ok(AuditFilter {
+            actor,
+            from,
+            to,
+            limit: self.limit.unwrap_or(50),
+            offset: self.offset.unwrap_or(0),
+        })
+    }
+}
+
+/// One audit log entry
+#[derive(Debug, Serialize)]
+pub struct AuditEntryResponse {
+    pub id: String,
+    pub actor: String,
+    pub ip: Option<String>,
+    pub method: String,
+    pub endpoint: String,
+    pub status: u16,
+    pub entity: Option<AuditEntityResponse>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditEntityResponse {
+    pub kind: String,
+    pub id: String,
+}
+
+impl From<AuditEntry> for AuditEntryResponse {
+    fn from(entry: AuditEntry) -> Self {
+        Self {
+            id: entry.id().to_string(),
+            actor: entry.actor().to_string(),
+            ip: entry.ip().map(|ip| ip.to_string()),
+            method: entry.method().to_string(),
+            endpoint: entry.endpoint().to_string(),
+            status: entry.status(),
+            entity: entry.entity().map(|e| AuditEntityResponse {
+                kind: e.kind.clone(),
+                id: e.id.clone(),
+            }),
+            before: entry.before().cloned(),
+            after: entry.after().cloned(),
+            timestamp: rfc3339(entry.timestamp()),
+        }
+    }
+}
+
+/// Page of the audit log
+#[derive(Debug, Serialize)]
+pub struct ListAuditResponse {
+    pub entries: Vec<AuditEntryResponse>,
+    pub total: u32,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+// ==================================================
+// Sync DTOs
+// ==================================================
+
+/// A sync cursor as the client sent it; absent means "from the start"
+fn sync_cursor(raw: Option<&str>) -> Result<u64, String> {
+    raw.map_or(Ok(0), |raw| {
+        raw.parse()
+            .map_err(|_| format!("Invalid cursor '{raw}', pass back a cursor the server returned"))
+    })
+}
+
+/// Query parameters for pulling changes
+#[derive(Debug, Default, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct SyncQuery {
+    /// Cursor returned by the previous pull; omit for a full sync
+    pub since: Option<String>,
+
+    #[validate(range(min = 1, max = 500))]
+    pub limit: Option<u32>,
 }
 
-/// Error codes for API responses
+impl SyncQuery {
+    pub fn cursor(&self) -> Result<u64, String> {
+        sync_cursor(self.since.as_deref())
+    }
+}
+
+/// Changes since a cursor; keep pulling from `cursor` while `has_more`
 #[derive(Debug, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum ErrorCode {
-    ValidationError,
-    NotFound,
-    Unauthorized,
-    Conflict,
-    InternalError,
+pub struct SyncChangesResponse {
+    /// Current state of every verb the logs touch
+    pub verbs: Vec<VerbResponse>,
+    /// Oldest first, including `sync_conflict` records
+    pub logs: Vec<ActionLogResponse>,
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+impl From<SyncChanges> for SyncChangesResponse {
+    fn from(changes: SyncChanges) -> Self {
+        Self {
+            verbs: changes.verbs.into_iter().map(VerbResponse::from).collect(),
+            logs: changes
+                .logs
+                .into_iter()
+                .map(ActionLogResponse::from)
+                .collect(),
+            cursor: changes.cursor.to_string(),
+            has_more: changes.has_more,
+        }
+    }
+}
+
+/// A batch of mutations made offline, applied in order
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SyncPushRequest {
+    /// Cursor of the client's last pull: server changes after it conflict
+    pub since: Option<String>,
+
+    pub mutations: Vec<SyncMutationRequest>,
+}
+
+impl SyncPushRequest {
+    const MAX_MUTATIONS: usize = 500;
+
+    pub fn cursor(&self) -> Result<u64, String> {
+        sync_cursor(self.since.as_deref())
+    }
+
+    /// Whether any mutation edits verb fields (gated by `verb_editing`)
+    pub fn edits_fields(&self) -> bool {
+        self.mutations
+            .iter()
+            .any(|m| matches!(m.op, SyncOpRequest::Update { .. }))
+    }
+
+    /// The application's mutations, rejecting empty or oversized batches
+    /// and the first mutation that doesn't parse
+    pub fn into_mutations(self) -> Result<Vec<SyncMutation>, String> {
+        if !(1..=Self::MAX_MUTATIONS).contains(&self.mutations.len()) {
+            return Err(format!(
+                "Send between 1 and {} mutations per batch",
+                Self::MAX_MUTATIONS
+            ));
+        }
+        self.mutations
+            .into_iter()
+            .enumerate()
+            .map(|(i, m)| {
+                m.into_mutation()
+                    .map_err(|e| format!("mutations[{i}]: {e}"))
+            })
+            .collect()
+    }
+}
+
+/// One offline mutation: `{"id", "verb_id", "at", "op": "create" | "update" | "transition", ...}`
+#[derive(Debug, Deserialize)]
+pub struct SyncMutationRequest {
+    /// Client-generated UUID, echoed back in the result
+    pub id: String,
+    /// Client-generated for creates
+    pub verb_id: String,
+    /// When the change was made on the client, RFC 3339
+    pub at: String,
+    #[serde(flatten)]
+    pub op: SyncOpRequest,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SyncOpRequest {
+    Create {
+        title: String,
+        #[serde(default)]
+        description: Option<String>,
+    },
+    Update {
+        changes: PatchVerbRequest,
+    },
+    Transition {
+        state: VerbStateDTO,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+impl SyncMutationRequest {
+    pub fn into_mutation(self) -> Result<SyncMutation, String> {
+        let id = self
+            .id
+            .parse()
+            .map_err(|_| format!("Invalid mutation id '{}'", self.id))?;
+        let verb_id = self
+            .verb_id
+            .parse::<VerbId>()
+            .map_err(|_| format!("Invalid verb_id '{}'", self.verb_id))?;
+        let at = OffsetDateTime::parse(&self.at, &Rfc3339)
+            .map_err(|_| format!("Invalid at '{}', expected RFC 3339", self.at))?;
+
+        let op = match self.op {
+            SyncOpRequest::Create { title, description } => SyncOp::Create {
+                title,
+                description: description.unwrap_or_default(),
+            },
+            SyncOpRequest::Update { changes } => SyncOp::Update(changes.into_changes()?),
+            SyncOpRequest::Transition { state, reason } => SyncOp::Transition {
+                state: state.into(),
+                reason,
+            },
+        };
+
+        Ok(SyncMutation {
+            id,
+            verb_id,
+            at,
+            op,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncPushResponse {
+    /// One per mutation, in request order
+    pub results: Vec<SyncResultResponse>,
+}
+
+/// What became of one mutation
+#[derive(Debug, Serialize)]
+pub struct SyncResultResponse {
+    pub id: String,
+    pub verb_id: String,
+    /// `applied`, `duplicate`, `conflict` or `rejected`
+    pub status: &'static str,
+    /// Conflicts only: `client` or `server`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<&'static str>,
+    /// Rejections only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<SyncErrorResponse>,
+    /// The verb as the server now has it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verb: Option<VerbResponse>,
+}
+
+/// Why a mutation was rejected, with the code the matching endpoint
+/// would have failed with
+#[derive(Debug, Serialize)]
+pub struct SyncErrorResponse {
+    pub code: ErrorCode,
+    pub detail: String,
+}
+
+impl From<SyncOutcome> for SyncResultResponse {
+    fn from(outcome: SyncOutcome) -> Self {
+        let (status, winner, error) = match outcome.status {
+            SyncStatus::Applied => ("applied", None, None),
+            SyncStatus::Duplicate => ("duplicate", None, None),
+            SyncStatus::Conflict { client_won } => (
+                "conflict",
+                Some(if client_won { "client" } else { "server" }),
+                None,
+            ),
+            SyncStatus::Rejected(e) => {
+                let e = ApiError::from(e);
+                let error = SyncErrorResponse {
+                    code: e.code(),
+                    detail: e.detail().to_string(),
+                };
+                ("rejected", None, Some(error))
+            }
+        };
+
+        Self {
+            id: outcome.mutation_id.to_string(),
+            verb_id: outcome.verb_id.to_string(),
+            status,
+            winner,
+            error,
+            verb: outcome.verb.map(VerbResponse::from),
+        }
+    }
+}
+
+// ==================================================
+// Standard API Response Envelope
+// ==================================================
+
+/// Standard success envelope: `{"status": "ok", "data": ..., "meta": ...}`
+///
+/// Failures are reported as problem+json instead, see [`ApiError`](super::error::ApiError).
+#[derive(Debug)]
+pub struct ApiResponse<T> {
+    data: T,
+    meta: Option<Value>,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    pub fn ok(data: T) -> Self {
+        Self { data, meta: None }
+    }
+
+    pub fn ok_with_meta(data: T, meta: Value) -> Self {
+        Self {
+            data,
+            meta: Some(meta),
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for ApiResponse<T> {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(Envelope::ok(self.data, self.meta))).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn patch_request_distinguishes_absent_from_null() {
+        let request: PatchVerbRequest = serde_json::from_value(json!({
+            "description": null,
+            "context": "kitchen",
+            "due_at": "2026-03-01T17:00:00Z"
+        }))
+        .unwrap();
+
+        let patch = request.into_changes().unwrap();
+
+        assert!(patch.title.is_none());
+        assert_eq!(patch.description, Some(None));
+        assert_eq!(patch.context, Some(Some("kitchen".to_string())));
+        assert!(matches!(patch.due_at, Some(Some(_))));
+    }
+
+    #[test]
+    fn burndown_window_accepts_days_and_weeks() {
+        let window = |raw: &str| {
+            BurndownQuery {
+                window: Some(raw.to_string()),
+            }
+            .window_days()
+        };
+
+        assert_eq!(BurndownQuery::default().window_days(), Ok(30));
+        assert_eq!(window("7d"), Ok(7));
+        assert_eq!(window("4w"), Ok(28));
+        for bad in ["", "30", "d", "-1d", "0d", "400d", "30h", "9999w"] {
+            assert!(window(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn patch_request_rejects_null_title_bad_date_and_unknown_fields() {
+        let null_title: PatchVerbRequest =
+            serde_json::from_value(json!({ "title": null })).unwrap();
+        let bad_date: PatchVerbRequest =
+            serde_json::from_value(json!({ "due_at": "tomorrow" })).unwrap();
+
+        assert!(null_title.into_changes().is_err());
+        assert!(bad_date.into_changes().is_err());
+        assert!(serde_json::from_value::<PatchVerbRequest>(json!({ "state": "done" })).is_err());
+    }
+
+    #[test]
+    fn sync_mutations_parse_by_op() {
+        let verb_id = VerbId::new();
+        let request: SyncPushRequest = serde_json::from_value(json!({
+            "since": "12",
+            "mutations": [
+                {
+                    "id": uuid::Uuid::new_v4(),
+                    "verb_id": verb_id.to_string(),
+                    "at": "2026-03-01T17:00:00Z",
+                    "op": "update",
+                    "changes": { "context": null }
+                },
+                {
+                    "id": uuid::Uuid::new_v4(),
+                    "verb_id": verb_id.to_string(),
+                    "at": "2026-03-01T17:05:00Z",
+                    "op": "transition",
+                    "state": "active"
+                }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(request.cursor(), Ok(12));
+        assert!(request.edits_fields());
+        let mutations = request.into_mutations().unwrap();
+        assert!(matches!(
+            &mutations[0].op,
+            SyncOp::Update(VerbChanges {
+                context: Some(None),
+                ..
+            })
+        ));
+        assert!(matches!(
+            mutations[1].op,
+            SyncOp::Transition {
+                state: VerbState::Active,
+                reason: None
+            }
+        ));
+        assert_eq!(mutations[1].verb_id, verb_id);
+    }
+
+    #[test]
+    fn sync_rejects_bad_cursors_and_ids() {
+        let query = SyncQuery {
+            since: Some("yesterday".to_string()),
+            limit: None,
+        };
+        assert!(query.cursor().is_err());
+        assert_eq!(SyncQuery::default().cursor(), Ok(0));
+
+        let request: SyncPushRequest = serde_json::from_value(json!({
+            "mutations": [{
+                "id": "not-a-uuid",
+                "verb_id": VerbId::new().to_string(),
+                "at": "2026-03-01T17:00:00Z",
+                "op": "create",
+                "title": "Offline"
+            }]
+        }))
+        .unwrap();
+        let err = request.into_mutations().unwrap_err();
+        assert!(
+            err.starts_with("mutations[0]: Invalid mutation id"),
+            "{err}"
+        );
+
+        let empty: SyncPushRequest = serde_json::from_value(json!({ "mutations": [] })).unwrap();
+        assert!(empty.into_mutations().is_err());
+    }
+
+    #[test]
+    fn preferences_request_defaults_to_on() {
+        let request: NotificationPreferencesRequest = serde_json::from_value(json!({
+            "channels": { "webhook": false },
+            "events": { "review.daily": false },
+            "quiet_hours": "22:00-07:00"
+        }))
+        .unwrap();
+
+        let user = UserId::from_uuid(uuid::Uuid::new_v4());
+        let offset = UtcOffset::from_hms(-5, -30, 0).unwrap();
+        let preferences = request.into_preferences(user, offset).unwrap();
+        assert!(preferences.channel_enabled(Channel::Email));
+        assert!(!preferences.channel_enabled(Channel::Webhook));
+        assert!(preferences.topic_enabled(NotificationTopic::VerbDone));
+        assert!(!preferences.topic_enabled(NotificationTopic::DailyReview));
+        assert_eq!(
+            preferences.quiet_hours().unwrap().to_string(),
+            "22:00-07:00"
+        );
+        assert_eq!(
+            NotificationPreferencesResponse::from(preferences).time_zone,
+            "-05:30"
+        );
+
+        let unknown: NotificationPreferencesRequest =
+            serde_json::from_value(json!({ "events": { "verb.deleted": true } })).unwrap();
+        assert!(matches!(
+            unknown.into_preferences(user, UtcOffset::UTC),
+            Err(DomainError::PreferencesUnknownTopic(_))
+        ));
+        assert!(
+            serde_json::from_value::<NotificationPreferencesRequest>(
+                json!({ "channels": { "sms": true } })
+            )
+            .is_err()
+        );
+    }
 }
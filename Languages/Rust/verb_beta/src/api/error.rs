@@ -1,34 +1,490 @@
 //! Error conversion for API layer
 //!
-//! Converts application errors to HTTP responses
+//! Every failure leaves the API as an RFC 7807 `application/problem+json`
+//! document. Besides the standard members it carries a stable `code`
+//! clients can match on, and for validation failures an `errors` map of
+//! field → messages:
+//!
+//! ```json
+//! {
+//!   "type": "urn:verb:problem:title-too-long",
+//!   "title": "Title too long",
+//!   "status": 400,
+//!   "detail": "Title cannot exceed 200 characters",
+//!   "code": "TITLE_TOO_LONG"
+//! }
+//! ```
+//!
+//! `title` follows the locale negotiated from `Accept-Language`; `code`,
+//! `type` and `detail` never change with it.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    Json,
+    extract::{
+        multipart::{MultipartError, MultipartRejection},
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+    },
+    http::{HeaderValue, StatusCode, header},
+    response::IntoResponse,
+};
+use serde::Serialize;
+use serde_json::Value;
+use validator::ValidationErrors;
+use verb_types::Problem;
+
+use crate::{
+    api::ctx::Ctx,
+    application::{ApplicationError, Feature},
+    domain::DomainError,
+};
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
 
-use axum::{Json, http::StatusCode, response::IntoResponse};
-use serde_json::json;
+/// Stable, machine-readable error codes
+///
+/// The serialized names are part of the API contract: add new codes
+/// freely, but never rename or reuse one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // Request shape
+    MalformedRequest,
+    UnsupportedMediaType,
+    ValidationError,
+    InvalidId,
+    PayloadTooLarge,
+    RequestTimeout,
 
-use crate::application::ApplicationError;
+    // Verbs
+    VerbNotFound,
+    TitleEmpty,
+    TitleTooLong,
+    TitleInvalidCharacters,
+    DescriptionTooLong,
+    DescriptionInvalidCharacters,
+    ContextTooLong,
+    ReasonTooLong,
+    InvalidState,
+    InvalidTransition,
 
-use super::dto::{ApiError, ApiResponse, ErrorCode};
+    // Webhooks
+    WebhookNotFound,
+    WebhookInvalidUrl,
+    WebhookNoEvents,
+    WebhookUnknownEvent,
+    WebhookEmptySecret,
 
-/// Convert ApplicationError to HTTP response
-impl IntoResponse for ApplicationError {
+    // Attachments
+    AttachmentNotFound,
+    AttachmentMissingFile,
+    AttachmentEmpty,
+    AttachmentInvalidFilename,
+    AttachmentInvalidContentType,
+    AttachmentTooLarge,
+    AttachmentUnsupportedType,
+
+    // Admin & feature flags
+    Unauthorized,
+    AdminDisabled,
+    FeatureDisabled,
+    UnknownFeature,
+    InvalidLogFilter,
+    LogLevelUnavailable,
+    SnapshotUnsupported,
+    SnapshotFormatUnsupported,
+
+    // Notifications
+    NotifierUnavailable,
+    EventsUnavailable,
+    NotificationFailed,
+    NotificationSuppressed,
+    PreferencesUnknownTopic,
+    PreferencesInvalidQuietHours,
+
+    InternalError,
+}
+
+impl ErrorCode {
+    pub fn status(self) -> StatusCode {
+        match self {
+            Self::UnsupportedMediaType | Self::AttachmentUnsupportedType => {
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            }
+            Self::PayloadTooLarge | Self::AttachmentTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::RequestTimeout => StatusCode::REQUEST_TIMEOUT,
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::AdminDisabled | Self::FeatureDisabled => StatusCode::FORBIDDEN,
+            Self::VerbNotFound
+            | Self::WebhookNotFound
+            | Self::AttachmentNotFound
+            | Self::UnknownFeature => StatusCode::NOT_FOUND,
+            Self::InvalidTransition | Self::NotificationSuppressed => StatusCode::CONFLICT,
+            Self::LogLevelUnavailable | Self::NotifierUnavailable | Self::EventsUnavailable => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            Self::NotificationFailed => StatusCode::BAD_GATEWAY,
+            Self::SnapshotUnsupported => StatusCode::NOT_IMPLEMENTED,
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// Short human-readable summary; the same for every occurrence
+    ///
+    /// English; other locales come from the `i18n` catalogs
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::MalformedRequest => "Malformed request",
+            Self::UnsupportedMediaType => "Unsupported media type",
+            Self::ValidationError => "Validation failed",
+            Self::InvalidId => "Invalid identifier",
+            Self::PayloadTooLarge => "Payload too large",
+            Self::RequestTimeout => "Request timeout",
+            Self::VerbNotFound => "Verb not found",
+            Self::TitleEmpty => "Title empty",
+            Self::TitleTooLong => "Title too long",
+            Self::TitleInvalidCharacters => "Invalid characters in title",
+            Self::DescriptionTooLong => "Description too long",
+            Self::DescriptionInvalidCharacters => "Invalid characters in description",
+            Self::ContextTooLong => "Context too long",
+            Self::ReasonTooLong => "Reason too long",
+            Self::InvalidState => "Invalid state",
+            Self::InvalidTransition => "Invalid state transition",
+            Self::WebhookNotFound => "Webhook not found",
+            Self::WebhookInvalidUrl => "Invalid webhook url",
+            Self::WebhookNoEvents => "Webhook has no events",
+            Self::WebhookUnknownEvent => "Unknown webhook event",
+            Self::WebhookEmptySecret => "Webhook secret empty",
+            Self::AttachmentNotFound => "Attachment not found",
+            Self::AttachmentMissingFile => "No file uploaded",
+            Self::AttachmentEmpty => "Attachment empty",
+            Self::AttachmentInvalidFilename => "Invalid attachment file name",
+            Self::AttachmentInvalidContentType => "Invalid attachment content type",
+            Self::AttachmentTooLarge => "Attachment too large",
+            Self::AttachmentUnsupportedType => "Unsupported attachment type",
+            Self::Unauthorized => "Unauthorized",
+            Self::AdminDisabled => "Admin API disabled",
+            Self::FeatureDisabled => "Feature disabled",
+            Self::UnknownFeature => "Unknown feature",
+            Self::InvalidLogFilter => "Invalid log filter",
+            Self::LogLevelUnavailable => "Log level control unavailable",
+            Self::SnapshotUnsupported => "Snapshots not supported",
+            Self::SnapshotFormatUnsupported => "Unsupported snapshot format",
+            Self::NotifierUnavailable => "Notifications not configured",
+            Self::EventsUnavailable => "Live events unavailable",
+            Self::NotificationFailed => "Notification failed",
+            Self::NotificationSuppressed => "Notification suppressed",
+            Self::PreferencesUnknownTopic => "Unknown notification topic",
+            Self::PreferencesInvalidQuietHours => "Invalid quiet hours",
+            Self::InternalError => "Internal server error",
+        }
+    }
+
+    /// Problem `type` URI, derived from the code
+    fn type_uri(self) -> String {
+        let code = serde_json::to_value(self)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_owned))
+            .unwrap_or_default();
+        format!(
+            "urn:verb:problem:{}",
+            code.to_ascii_lowercase().replace('_', "-")
+        )
+    }
+}
+
+/// An API failure, rendered as problem+json
+#[derive(Debug)]
+pub struct ApiError {
+    code: ErrorCode,
+    detail: String,
+    errors: Option<Value>,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, detail: impl Into<String>) -> Self {
+        Self {
+            code,
+            detail: detail.into(),
+            errors: None,
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    /// A path id that doesn't parse, e.g. `ApiError::invalid_id("verb")`
+    pub fn invalid_id(resource: &str) -> Self {
+        Self::new(
+            ErrorCode::InvalidId,
+            format!("Invalid {resource} ID format"),
+        )
+    }
+
+    /// A request to a feature switched off through the admin API
+    pub fn feature_disabled(feature: Feature) -> Self {
+        Self::new(
+            ErrorCode::FeatureDisabled,
+            format!("The '{feature}' feature is currently disabled"),
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        let (code, message) = match self {
-            ApplicationError::Domain(e) => (ErrorCode::ValidationError, e.to_string()),
-            ApplicationError::NotFound => (ErrorCode::NotFound, "Resource not found".to_string()),
-            ApplicationError::Transaction(e) => {
-                tracing::error!(error = %e, "Transaction error");
-                (ErrorCode::InternalError, "Transaction failed".to_string())
-            }
-            // ApplicationError::Infrastructure(e) => {
-            //     tracing::error!(error = %e, "Infrastructure error");
-            //     (
-            //         ErrorCode::InternalError,
-            //         "Internal server error".to_string(),
-            //     )
-            // }
-            ApplicationError::Database(_) => todo!(),
+        let status = self.code.status();
+        let problem = Problem {
+            type_uri: self.code.type_uri(),
+            title: Ctx::current().locale.error_title(self.code).to_string(),
+            status: status.as_u16(),
+            detail: self.detail,
+            code: self.code,
+            errors: self.errors,
+        };
+
+        let mut response = (status, Json(problem)).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        response
+    }
+}
+
+impl From<DomainError> for ApiError {
+    fn from(e: DomainError) -> Self {
+        let code = match &e {
+            DomainError::VerbInvalidIdFromStr(_) => ErrorCode::InvalidId,
+            DomainError::VerbEmptyTitle => ErrorCode::TitleEmpty,
+            DomainError::VerbTitleTooLong => ErrorCode::TitleTooLong,
+            DomainError::VerbTitleInvalidCharacters => ErrorCode::TitleInvalidCharacters,
+            DomainError::VerbDescriptionTooLong => ErrorCode::DescriptionTooLong,
+            DomainError::VerbDescriptionInvalidCharacters => {
+                ErrorCode::DescriptionInvalidCharacters
+            }
+            DomainError::VerbContextTooLong => ErrorCode::ContextTooLong,
+            DomainError::VerbInvalidState(_) => ErrorCode::InvalidState,
+            DomainError::InvalidTransition { .. } => ErrorCode::InvalidTransition,
+            DomainError::ReasonTooLong => ErrorCode::ReasonTooLong,
+            DomainError::WebhookInvalidUrl(_) => ErrorCode::WebhookInvalidUrl,
+            DomainError::WebhookNoEvents => ErrorCode::WebhookNoEvents,
+            DomainError::WebhookUnknownEvent(_) => ErrorCode::WebhookUnknownEvent,
+            DomainError::WebhookEmptySecret => ErrorCode::WebhookEmptySecret,
+            DomainError::AttachmentEmpty => ErrorCode::AttachmentEmpty,
+            DomainError::AttachmentInvalidFilename(_) => ErrorCode::AttachmentInvalidFilename,
+            DomainError::AttachmentInvalidContentType(_) => ErrorCode::AttachmentInvalidContentType,
+            DomainError::PreferencesUnknownTopic(_) => ErrorCode::PreferencesUnknownTopic,
+            DomainError::PreferencesInvalidQuietHours(_) => ErrorCode::PreferencesInvalidQuietHours,
+            DomainError::AuditInvalidActor(_) => ErrorCode::ValidationError,
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+impl From<ApplicationError> for ApiError {
+    fn from(e: ApplicationError) -> Self {
+        match e {
+            ApplicationError::Domain(e) => e.into(),
+            ApplicationError::NotFound => Self::new(ErrorCode::VerbNotFound, "Verb not found"),
+            ApplicationError::WebhookNotFound => {
+                Self::new(ErrorCode::WebhookNotFound, "Webhook not found")
+            }
+            ApplicationError::AttachmentNotFound => {
+                Self::new(ErrorCode::AttachmentNotFound, "Attachment not found")
+            }
+            e @ ApplicationError::AttachmentTooLarge { .. } => {
+                Self::new(ErrorCode::AttachmentTooLarge, e.to_string())
+            }
+            e @ ApplicationError::UnsupportedAttachmentType(_) => {
+                Self::new(ErrorCode::AttachmentUnsupportedType, e.to_string())
+            }
+            e @ ApplicationError::SnapshotUnsupported => {
+                Self::new(ErrorCode::SnapshotUnsupported, e.to_string())
+            }
+            e @ ApplicationError::SnapshotFormat { .. } => {
+                Self::new(ErrorCode::SnapshotFormatUnsupported, e.to_string())
+            }
+            ApplicationError::NotifierUnavailable => Self::new(
+                ErrorCode::NotifierUnavailable,
+                "No notification channel is configured",
+            ),
+            e @ ApplicationError::NotificationSuppressed => {
+                Self::new(ErrorCode::NotificationSuppressed, e.to_string())
+            }
+            ApplicationError::Notification(e) => {
+                tracing::error!(error = %e, "Notification failed");
+                Self::new(
+                    ErrorCode::NotificationFailed,
+                    "The notification could not be delivered",
+                )
+            }
+            // Internal details stay in the logs
+            ApplicationError::Blob(e) => {
+                tracing::error!(error = %e, "Blob store error");
+                Self::new(
+                    ErrorCode::InternalError,
+                    "The request could not be completed",
+                )
+            }
+            ApplicationError::Database(e) | ApplicationError::Transaction(e) => {
+                tracing::error!(error = %e, "Storage error");
+                Self::new(
+                    ErrorCode::InternalError,
+                    "The request could not be completed",
+                )
+            }
+        }
+    }
+}
+
+/// `validator` failures, with per-field messages under `errors`
+impl From<ValidationErrors> for ApiError {
+    fn from(e: ValidationErrors) -> Self {
+        let errors: BTreeMap<_, Vec<String>> = e
+            .field_errors()
+            .into_iter()
+            .map(|(field, errors)| {
+                let messages = errors
+                    .iter()
+                    .map(|err| {
+                        err.message
+                            .as_ref()
+                            .map_or_else(|| err.code.to_string(), ToString::to_string)
+                    })
+                    .collect();
+                (field.to_string(), messages)
+            })
+            .collect();
+
+        Self {
+            code: ErrorCode::ValidationError,
+            detail: "One or more fields are invalid".into(),
+            errors: serde_json::to_value(errors).ok(),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        let code = match rejection.status() {
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => ErrorCode::UnsupportedMediaType,
+            StatusCode::PAYLOAD_TOO_LARGE => ErrorCode::PayloadTooLarge,
+            _ => ErrorCode::MalformedRequest,
         };
+        Self::new(code, rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::new(ErrorCode::MalformedRequest, rejection.body_text())
+    }
+}
+
+impl From<MultipartRejection> for ApiError {
+    fn from(rejection: MultipartRejection) -> Self {
+        let code = match rejection.status() {
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => ErrorCode::UnsupportedMediaType,
+            _ => ErrorCode::MalformedRequest,
+        };
+        Self::new(code, rejection.body_text())
+    }
+}
+
+/// Failures while reading multipart fields, including hitting the body limit
+impl From<MultipartError> for ApiError {
+    fn from(e: MultipartError) -> Self {
+        let code = match e.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => ErrorCode::PayloadTooLarge,
+            _ => ErrorCode::MalformedRequest,
+        };
+        Self::new(code, e.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::new(ErrorCode::MalformedRequest, rejection.body_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use validator::Validate;
+
+    use super::*;
+    use crate::domain::model::VerbState;
+
+    async fn render(error: ApiError) -> (StatusCode, String, Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            content_type,
+            serde_json::from_slice(&bytes).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn renders_problem_json() {
+        let error = ApiError::from(ApplicationError::Domain(DomainError::InvalidTransition {
+            from: VerbState::Captured,
+            to: VerbState::Done,
+        }));
+
+        let (status, content_type, body) = render(error).await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(content_type, PROBLEM_JSON);
+        assert_eq!(body["type"], "urn:verb:problem:invalid-transition");
+        assert_eq!(body["title"], "Invalid state transition");
+        assert_eq!(body["status"], 409);
+        assert_eq!(body["code"], "INVALID_TRANSITION");
+        assert!(body["detail"].as_str().unwrap().contains("Captured"));
+        assert!(body.get("errors").is_none());
+    }
+
+    #[tokio::test]
+    async fn hides_storage_details() {
+        let error = ApiError::from(ApplicationError::Database("disk on fire".into()));
+
+        let (status, _, body) = render(error).await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["code"], "INTERNAL_ERROR");
+        assert!(!body.to_string().contains("disk on fire"));
+    }
+
+    #[tokio::test]
+    async fn lists_validation_errors_per_field() {
+        #[derive(Validate)]
+        struct Input {
+            #[validate(length(min = 1, message = "Title must not be empty"))]
+            title: String,
+        }
+        let errors = Input {
+            title: String::new(),
+        }
+        .validate()
+        .unwrap_err();
+
+        let (status, _, body) = render(errors.into()).await;
 
-        ApiResponse::<()>::error(code, message).into_response()
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "VALIDATION_ERROR");
+        assert_eq!(body["errors"]["title"][0], "Title must not be empty");
     }
 }
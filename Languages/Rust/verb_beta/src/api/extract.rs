@@ -0,0 +1,80 @@
+//! Request extractors whose rejections are reported as [`ApiError`]
+//!
+//! Drop-in replacements for axum's `Json`, `Query` and `Path`: a malformed
+//! body or query string comes back as problem+json instead of plain text.
+
+use axum::extract::{FromRequest, FromRequestParts};
+
+use super::error::ApiError;
+
+/// JSON request body
+#[derive(Debug, FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct ApiJson<T>(pub T);
+
+/// Query string parameters
+#[derive(Debug, FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
+
+/// Path parameters
+#[derive(Debug, FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiError))]
+pub struct ApiPath<T>(pub T);
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode, header},
+        routing::post,
+    };
+    use serde::Deserialize;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::api::error::PROBLEM_JSON;
+
+    #[derive(Deserialize)]
+    struct Payload {
+        #[allow(dead_code)]
+        title: String,
+    }
+
+    async fn post_json(content_type: &str, body: &'static str) -> (StatusCode, Value) {
+        let app = Router::new().route("/", post(|ApiJson(_): ApiJson<Payload>| async { "ok" }));
+        let response = app
+            .oneshot(
+                Request::post("/")
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn rejections_are_problem_json() {
+        let (status, body) = post_json("application/json", "{not json").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "MALFORMED_REQUEST");
+
+        let (status, body) = post_json("application/json", r#"{"title": 1}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "MALFORMED_REQUEST");
+
+        let (status, body) = post_json("text/plain", r#"{"title": "x"}"#).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body["code"], "UNSUPPORTED_MEDIA_TYPE");
+    }
+}
@@ -0,0 +1,212 @@
+//! Contract tests: how verb handlers turn use-case outcomes into HTTP
+//!
+//! The verb use case is a [`MockVerbUseCase`], so each test fixes what the
+//! application layer answers and checks the exact status and problem code
+//! the client sees, with nothing behind the handlers.
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use crate::{
+    api::{self, AppState},
+    application::{
+        AdminFacade, ApplicationError, AttachmentFacade, AuditFacade,
+        NotificationPreferencesFacade, ReviewFacade, StatsFacade, SyncFacade, VerbFacade,
+        WebhookFacade, facade::mock::MockVerbUseCase, use_cases::AttachmentPolicy,
+    },
+    config::{AllowedOrigins, ApiLimits, HttpLogConfig, SharedLimits},
+    domain::{
+        DomainError,
+        model::{Description, Title, Verb, VerbId, VerbState},
+    },
+    infra::{blob::InMemoryBlobStore, repository::in_memory::InMemoryDatabase},
+};
+
+fn verb() -> Verb {
+    Verb::new(
+        Title::new("Write contract tests").unwrap(),
+        Description::new("").unwrap(),
+    )
+}
+
+fn invalid_transition() -> ApplicationError {
+    ApplicationError::Domain(DomainError::InvalidTransition {
+        from: VerbState::Done,
+        to: VerbState::Active,
+    })
+}
+
+/// The API router with `verbs` behind every verb route
+fn app(verbs: &Arc<MockVerbUseCase>) -> Router {
+    let db = Arc::new(InMemoryDatabase::new());
+    let state = AppState::new(
+        VerbFacade::new(Arc::clone(&db)),
+        WebhookFacade::new(Arc::clone(&db)),
+        AdminFacade::new(Arc::clone(&db)),
+        AttachmentFacade::new(
+            Arc::clone(&db),
+            Arc::new(InMemoryBlobStore::new()),
+            AttachmentPolicy::default(),
+        ),
+        ReviewFacade::new(Arc::clone(&db)),
+        StatsFacade::new(Arc::clone(&db)),
+        NotificationPreferencesFacade::new(Arc::clone(&db)),
+        AuditFacade::new(Arc::clone(&db)),
+        SyncFacade::new(Arc::clone(&db), VerbFacade::new(db)),
+    )
+    .with_verb_use_case(verbs.clone());
+
+    api::app(
+        state,
+        &SharedLimits::new(ApiLimits::default()),
+        &AllowedOrigins::default(),
+        &HttpLogConfig::default(),
+        None,
+    )
+}
+
+async fn send(
+    verbs: &Arc<MockVerbUseCase>,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+
+    let response = app(verbs).oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, body)
+}
+
+fn verb_uri(suffix: &str) -> String {
+    format!("/api/v1/verbs/{}{suffix}", VerbId::new())
+}
+
+#[tokio::test]
+async fn success_renders_the_returned_verb() {
+    let verb = verb();
+    let verbs = Arc::new(MockVerbUseCase::returning(verb.clone()));
+
+    let (status, body) = send(&verbs, Method::GET, &verb_uri(""), None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["id"], verb.id().to_string());
+    assert_eq!(verbs.calls(), ["get_verb"]);
+}
+
+#[tokio::test]
+async fn missing_verb_is_404_on_every_verb_route() {
+    let verbs = Arc::new(MockVerbUseCase::failing(|| ApplicationError::NotFound));
+    let requests = [
+        (Method::GET, verb_uri(""), None),
+        (Method::PATCH, verb_uri(""), Some(json!({ "title": "New" }))),
+        (
+            Method::PUT,
+            verb_uri("/state"),
+            Some(json!({ "state": "active" })),
+        ),
+        (Method::DELETE, verb_uri(""), None),
+        (Method::GET, verb_uri("/logs"), None),
+    ];
+
+    for (method, uri, body) in requests {
+        let (status, problem) = send(&verbs, method.clone(), &uri, body).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{method} {uri}");
+        assert_eq!(problem["code"], "VERB_NOT_FOUND", "{method} {uri}");
+    }
+}
+
+#[tokio::test]
+async fn invalid_transition_is_409() {
+    let verbs = Arc::new(MockVerbUseCase::failing(invalid_transition));
+
+    let (status, problem) = send(
+        &verbs,
+        Method::PUT,
+        &verb_uri("/state"),
+        Some(json!({ "state": "active" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(problem["code"], "INVALID_TRANSITION");
+
+    let (status, problem) = send(&verbs, Method::DELETE, &verb_uri(""), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(problem["code"], "INVALID_TRANSITION");
+
+    assert_eq!(verbs.calls(), ["transition_verb", "transition_verb"]);
+}
+
+#[tokio::test]
+async fn domain_validation_from_the_use_case_is_a_client_error() {
+    let verbs = Arc::new(MockVerbUseCase::failing(|| {
+        ApplicationError::Domain(DomainError::VerbEmptyTitle)
+    }));
+
+    let (status, problem) = send(
+        &verbs,
+        Method::POST,
+        "/api/v1/verbs",
+        Some(json!({ "title": "Looks fine", "description": "" })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(problem["code"], "TITLE_EMPTY");
+}
+
+#[tokio::test]
+async fn storage_failures_are_500_without_details() {
+    let verbs = Arc::new(MockVerbUseCase::failing(|| {
+        ApplicationError::Database("connection reset by peer".into())
+    }));
+
+    let (status, problem) = send(&verbs, Method::GET, "/api/v1/verbs", None).await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(problem["code"], "INTERNAL_ERROR");
+    assert!(!problem.to_string().contains("connection reset"));
+}
+
+#[tokio::test]
+async fn malformed_id_never_reaches_the_use_case() {
+    let verbs = Arc::new(MockVerbUseCase::returning(verb()));
+
+    let (status, problem) = send(&verbs, Method::GET, "/api/v1/verbs/not-a-uuid", None).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(problem["code"], "INVALID_ID");
+    assert!(verbs.calls().is_empty());
+}
+
+#[tokio::test]
+async fn events_without_a_publisher_are_unavailable() {
+    let verbs = Arc::new(MockVerbUseCase::returning(verb()));
+
+    let (status, problem) = send(&verbs, Method::GET, "/api/v1/verbs/events", None).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(problem["code"], "EVENTS_UNAVAILABLE");
+}
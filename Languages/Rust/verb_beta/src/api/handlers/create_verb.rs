@@ -1,11 +1,13 @@
-use axum::{Json, extract::State};
+use axum::extract::State;
 use tracing::instrument;
 use validator::Validate;
 
 use crate::{
     api::{
         AppState,
-        dto::{ApiResponse, CreateVerbRequest, ErrorCode, VerbResponse},
+        dto::{ApiResponse, CreateVerbRequest, VerbResponse},
+        error::ApiError,
+        extract::ApiJson,
     },
     infra::db::Database,
 };
@@ -20,31 +22,20 @@ use crate::{
 #[instrument(skip(state), fields(title = %payload.title))]
 pub async fn create_verb<D: Database>(
     State(state): State<AppState<D>>,
-    Json(payload): Json<CreateVerbRequest>,
-) -> ApiResponse<VerbResponse> {
+    ApiJson(payload): ApiJson<CreateVerbRequest>,
+) -> Result<ApiResponse<VerbResponse>, ApiError> {
     // Step 1: Validate input
-    if let Err(validation_errors) = payload.validate() {
-        return ApiResponse::error(
-            ErrorCode::ValidationError,
-            format!("Validation failed: {}", validation_errors),
-        );
-    }
+    payload.validate()?;
 
     // Step 2: Call application layer
     let description = payload.description.unwrap_or_default();
 
-    match state
+    let verb = state
         .verb_facade
         .create_verb(payload.title, description)
         .await
-    {
-        Ok(verb) => {
-            tracing::info!(verb_id = %verb.id(), "Verb created successfully");
-            ApiResponse::ok(VerbResponse::from(verb))
-        }
-        Err(e) => {
-            tracing::error!(error = %e, "Failed to create verb");
-            ApiResponse::error(ErrorCode::InternalError, e.to_string())
-        }
-    }
+        .inspect_err(|e| tracing::error!(error = %e, "Failed to create verb"))?;
+
+    tracing::info!(verb_id = %verb.id(), "Verb created successfully");
+    Ok(ApiResponse::ok(VerbResponse::from(verb)))
 }
@@ -0,0 +1,27 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{AppState, dto::ApiResponse, error::ApiError, extract::ApiPath},
+    application::Feature,
+    domain::model::WebhookId,
+    infra::db::Database,
+};
+
+/// Handler: Remove a webhook
+#[instrument(skip(state), fields(webhook_id = %id))]
+pub async fn delete_webhook<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<()>, ApiError> {
+    state.ensure_enabled(Feature::Webhooks)?;
+
+    let webhook_id = id
+        .parse::<WebhookId>()
+        .map_err(|_| ApiError::invalid_id("webhook"))?;
+
+    state.webhook_facade.delete_webhook(webhook_id).await?;
+
+    tracing::info!(webhook_id = %webhook_id, "Webhook deleted");
+    Ok(ApiResponse::ok(()))
+}
@@ -1,10 +1,12 @@
-use axum::extract::{Path, State};
+use axum::extract::State;
 use tracing::instrument;
 
 use crate::{
     api::{
         AppState,
-        dto::{ApiResponse, ErrorCode, VerbResponse},
+        dto::{ApiResponse, VerbResponse},
+        error::ApiError,
+        extract::ApiPath,
     },
     domain::model::{VerbId, VerbState},
     infra::db::Database,
@@ -13,19 +15,16 @@ use crate::{
 /// Handler: Drop a verb (transition to Dropped state)
 #[instrument(skip(state), fields(verb_id = %id))]
 pub async fn drop_verb<D: Database>(
-    Path(id): Path<String>,
+    ApiPath(id): ApiPath<String>,
     State(state): State<AppState<D>>,
-) -> ApiResponse<VerbResponse> {
+) -> Result<ApiResponse<VerbResponse>, ApiError> {
     // Parse verb ID
-    let verb_id = match id.parse::<VerbId>() {
-        Ok(id) => id,
-        Err(_) => {
-            return ApiResponse::error(ErrorCode::ValidationError, "Invalid verb ID format");
-        }
-    };
+    let verb_id = id
+        .parse::<VerbId>()
+        .map_err(|_| ApiError::invalid_id("verb"))?;
 
     // Transition to Dropped state
-    match state
+    let verb = state
         .verb_facade
         .transition_verb(
             verb_id,
@@ -33,14 +32,8 @@ pub async fn drop_verb<D: Database>(
             Some("Dropped via API".to_string()),
         )
         .await
-    {
-        Ok(verb) => {
-            tracing::info!(verb_id = %verb.id(), "Verb dropped");
-            ApiResponse::ok(VerbResponse::from(verb))
-        }
-        Err(e) => {
-            tracing::error!(error = %e, "Failed to drop verb");
-            ApiResponse::error(ErrorCode::InternalError, e.to_string())
-        }
-    }
+        .inspect_err(|e| tracing::warn!(error = %e, "Failed to drop verb"))?;
+
+    tracing::info!(verb_id = %verb.id(), "Verb dropped");
+    Ok(ApiResponse::ok(VerbResponse::from(verb)))
 }
@@ -0,0 +1,18 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{AppState, dto::ApiResponse, error::ApiError},
+    infra::db::Database,
+};
+
+/// Handler: Drop every cached entity, so lookups reach the database again
+#[instrument(skip(state))]
+pub async fn flush_cache<D: Database>(
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<()>, ApiError> {
+    state.admin_facade.flush_cache().await?;
+
+    tracing::warn!("Cache flushed");
+    Ok(ApiResponse::ok(()))
+}
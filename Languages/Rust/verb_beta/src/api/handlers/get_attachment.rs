@@ -0,0 +1,90 @@
+use axum::{
+    extract::State,
+    http::{HeaderValue, header},
+    response::{IntoResponse, Response},
+};
+use tracing::instrument;
+
+use crate::{
+    api::{AppState, error::ApiError, extract::ApiPath},
+    domain::model::AttachmentId,
+    infra::db::Database,
+};
+
+/// Handler: Download an attachment
+///
+/// Served with its stored content type as a download (never rendered
+/// inline), and with `nosniff` so browsers don't second-guess the type.
+#[instrument(skip(state), fields(attachment_id = %id))]
+pub async fn get_attachment<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    State(state): State<AppState<D>>,
+) -> Result<Response, ApiError> {
+    let attachment_id = id
+        .parse::<AttachmentId>()
+        .map_err(|_| ApiError::invalid_id("attachment"))?;
+
+    let (attachment, data) = state
+        .attachment_facade
+        .get_attachment(attachment_id)
+        .await?;
+
+    let content_type = HeaderValue::from_str(attachment.content_type())
+        .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                content_disposition(attachment.filename()),
+            ),
+            (
+                header::X_CONTENT_TYPE_OPTIONS,
+                HeaderValue::from_static("nosniff"),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+/// `attachment; filename="..."; filename*=UTF-8''...` (RFC 6266), with an
+/// ASCII fallback for clients that ignore `filename*`
+fn content_disposition(filename: &str) -> HeaderValue {
+    let fallback: String = filename
+        .chars()
+        .map(|c| match c {
+            ' '..='~' if c != '"' && c != '\\' => c,
+            _ => '_',
+        })
+        .collect();
+    let encoded: String = filename
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect();
+
+    HeaderValue::from_str(&format!(
+        "attachment; filename=\"{fallback}\"; filename*=UTF-8''{encoded}"
+    ))
+    .unwrap_or(HeaderValue::from_static("attachment"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_disposition_quotes_and_encodes() {
+        assert_eq!(
+            content_disposition("résumé \"final\".pdf"),
+            "attachment; filename=\"r_sum_ _final_.pdf\"; \
+             filename*=UTF-8''r%C3%A9sum%C3%A9%20%22final%22.pdf"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use axum::extract::State;
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, AuditEntryResponse, ListAuditQuery, ListAuditResponse},
+        error::{ApiError, ErrorCode},
+        extract::ApiQuery,
+    },
+    infra::db::Database,
+};
+
+/// Handler: Audit log, newest first, e.g.
+/// `?actor=admin&from=2026-03-01T00:00:00Z&to=2026-03-02T00:00:00Z`
+#[instrument(skip(state))]
+pub async fn get_audit_log<D: Database>(
+    State(state): State<AppState<D>>,
+    ApiQuery(query): ApiQuery<ListAuditQuery>,
+) -> Result<ApiResponse<ListAuditResponse>, ApiError> {
+    query.validate()?;
+
+    let filter = query
+        .into_filter()
+        .map_err(|message| ApiError::new(ErrorCode::ValidationError, message))?;
+
+    let result = state.audit_facade.list(&filter).await?;
+
+    Ok(ApiResponse::ok(ListAuditResponse {
+        entries: result
+            .entries
+            .into_iter()
+            .map(AuditEntryResponse::from)
+            .collect(),
+        total: result.total,
+        limit: filter.limit,
+        offset: filter.offset,
+    }))
+}
@@ -0,0 +1,27 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, BurndownQuery, BurndownResponse},
+        error::{ApiError, ErrorCode},
+        extract::ApiQuery,
+    },
+    infra::db::Database,
+};
+
+/// Handler: Created vs completed vs dropped per day, e.g. `?window=30d`
+#[instrument(skip(state))]
+pub async fn get_burndown<D: Database>(
+    State(state): State<AppState<D>>,
+    ApiQuery(query): ApiQuery<BurndownQuery>,
+) -> Result<ApiResponse<BurndownResponse>, ApiError> {
+    let window_days = query
+        .window_days()
+        .map_err(|message| ApiError::new(ErrorCode::ValidationError, message))?;
+
+    let burndown = state.stats_facade.burndown(window_days).await?;
+
+    Ok(ApiResponse::ok(BurndownResponse::from(burndown)))
+}
@@ -0,0 +1,21 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, DatabaseStatsResponse},
+        error::ApiError,
+    },
+    infra::db::Database,
+};
+
+/// Handler: Row counts of the backing database
+#[instrument(skip(state))]
+pub async fn get_db_stats<D: Database>(
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<DatabaseStatsResponse>, ApiError> {
+    let stats = state.admin_facade.database_stats().await?;
+
+    Ok(ApiResponse::ok(DatabaseStatsResponse::from(stats)))
+}
@@ -0,0 +1,21 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, LogLevelResponse},
+        error::ApiError,
+    },
+    infra::db::Database,
+};
+
+/// Handler: Show the active log filter
+#[instrument(skip(state))]
+pub async fn get_log_level<D: Database>(
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<LogLevelResponse>, ApiError> {
+    let filter = state.log_level.as_ref().and_then(|handle| handle.current());
+
+    Ok(ApiResponse::ok(LogLevelResponse { filter }))
+}
@@ -0,0 +1,32 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, NotificationPreferencesResponse},
+        error::ApiError,
+        extract::ApiPath,
+    },
+    domain::model::UserId,
+    infra::db::Database,
+};
+
+/// Handler: A user's notification preferences
+///
+/// Users who never saved any get the defaults.
+#[instrument(skip(state), fields(user_id = %id))]
+pub async fn get_notification_preferences<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<NotificationPreferencesResponse>, ApiError> {
+    let user_id = id
+        .parse::<UserId>()
+        .map_err(|_| ApiError::invalid_id("user"))?;
+
+    let preferences = state.preferences_facade.get(user_id).await?;
+
+    Ok(ApiResponse::ok(NotificationPreferencesResponse::from(
+        preferences,
+    )))
+}
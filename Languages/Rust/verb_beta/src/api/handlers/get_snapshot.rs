@@ -0,0 +1,20 @@
+use axum::{Json, extract::State};
+use tracing::instrument;
+
+use crate::{
+    api::{AppState, error::ApiError},
+    infra::db::{Database, Snapshot},
+};
+
+/// Handler: Dump the whole database
+///
+/// Returned bare (no envelope) so it can be saved and sent straight back
+/// to `PUT /admin/snapshot` or `verb_beta serve --snapshot`.
+#[instrument(skip(state))]
+pub async fn get_snapshot<D: Database>(
+    State(state): State<AppState<D>>,
+) -> Result<Json<Snapshot>, ApiError> {
+    let snapshot = state.admin_facade.snapshot().await?;
+
+    Ok(Json(snapshot))
+}
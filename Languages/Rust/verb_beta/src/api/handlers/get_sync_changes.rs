@@ -0,0 +1,32 @@
+use axum::extract::State;
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, SyncChangesResponse, SyncQuery},
+        error::{ApiError, ErrorCode},
+        extract::ApiQuery,
+    },
+    infra::db::Database,
+};
+
+/// Handler: Verbs and action logs changed since a cursor, e.g. `?since=42`
+#[instrument(skip(state))]
+pub async fn get_sync_changes<D: Database>(
+    State(state): State<AppState<D>>,
+    ApiQuery(query): ApiQuery<SyncQuery>,
+) -> Result<ApiResponse<SyncChangesResponse>, ApiError> {
+    query.validate()?;
+    let cursor = query
+        .cursor()
+        .map_err(|message| ApiError::new(ErrorCode::ValidationError, message))?;
+
+    let changes = state
+        .sync_facade
+        .pull(cursor, query.limit.unwrap_or(100))
+        .await?;
+
+    Ok(ApiResponse::ok(SyncChangesResponse::from(changes)))
+}
@@ -1,10 +1,12 @@
-use axum::extract::{Path, State};
+use axum::extract::State;
 use tracing::instrument;
 
 use crate::{
     api::{
         AppState,
-        dto::{ApiResponse, ErrorCode, VerbResponse},
+        dto::{ApiResponse, VerbResponse},
+        error::ApiError,
+        extract::ApiPath,
     },
     domain::model::VerbId,
     infra::db::Database,
@@ -13,22 +15,14 @@ use crate::{
 /// Handler: Get a single verb by ID
 #[instrument(skip(state), fields(verb_id = %id))]
 pub async fn get_verb<D: Database>(
-    Path(id): Path<String>,
+    ApiPath(id): ApiPath<String>,
     State(state): State<AppState<D>>,
-) -> ApiResponse<VerbResponse> {
+) -> Result<ApiResponse<VerbResponse>, ApiError> {
     // Parse verb ID
-    let verb_id = match id.parse::<VerbId>() {
-        Ok(id) => id,
-        Err(_) => {
-            return ApiResponse::error(ErrorCode::ValidationError, "Invalid verb ID format");
-        }
-    };
+    let verb_id = id
+        .parse::<VerbId>()
+        .map_err(|_| ApiError::invalid_id("verb"))?;
 
-    match state.verb_facade.get_verb(verb_id).await {
-        Ok(verb) => ApiResponse::ok(VerbResponse::from(verb)),
-        Err(e) => {
-            tracing::warn!(error = %e, "Verb not found");
-            ApiResponse::error(ErrorCode::NotFound, "Verb not found")
-        }
-    }
+    let verb = state.verb_facade.get_verb(verb_id).await?;
+    Ok(ApiResponse::ok(VerbResponse::from(verb)))
 }
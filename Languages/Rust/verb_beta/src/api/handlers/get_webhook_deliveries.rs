@@ -0,0 +1,52 @@
+use axum::extract::State;
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, ListDeliveriesQuery, ListDeliveriesResponse, WebhookDeliveryResponse},
+        error::ApiError,
+        extract::{ApiPath, ApiQuery},
+    },
+    application::Feature,
+    domain::{model::WebhookId, repository::DeliveryFilter},
+    infra::db::Database,
+};
+
+/// Handler: Get the delivery-attempt log for a webhook
+#[instrument(skip(state), fields(webhook_id = %id))]
+pub async fn get_webhook_deliveries<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    ApiQuery(query): ApiQuery<ListDeliveriesQuery>,
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<ListDeliveriesResponse>, ApiError> {
+    state.ensure_enabled(Feature::Webhooks)?;
+
+    query.validate()?;
+
+    let webhook_id = id
+        .parse::<WebhookId>()
+        .map_err(|_| ApiError::invalid_id("webhook"))?;
+
+    let filter = DeliveryFilter {
+        limit: query.limit.unwrap_or(50),
+        offset: query.offset.unwrap_or(0),
+    };
+
+    let result = state
+        .webhook_facade
+        .list_deliveries(webhook_id, &filter)
+        .await?;
+
+    Ok(ApiResponse::ok(ListDeliveriesResponse {
+        deliveries: result
+            .deliveries
+            .into_iter()
+            .map(WebhookDeliveryResponse::from)
+            .collect(),
+        total: result.total,
+        limit: filter.limit,
+        offset: filter.offset,
+    }))
+}
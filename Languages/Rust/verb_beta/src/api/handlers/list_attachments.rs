@@ -0,0 +1,30 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, AttachmentResponse},
+        error::ApiError,
+        extract::ApiPath,
+    },
+    domain::model::VerbId,
+    infra::db::Database,
+};
+
+/// Handler: List a verb's attachments (metadata only)
+#[instrument(skip(state), fields(verb_id = %id))]
+pub async fn list_attachments<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<Vec<AttachmentResponse>>, ApiError> {
+    let verb_id = id
+        .parse::<VerbId>()
+        .map_err(|_| ApiError::invalid_id("verb"))?;
+
+    let attachments = state.attachment_facade.list_attachments(verb_id).await?;
+
+    Ok(ApiResponse::ok(
+        attachments.iter().map(AttachmentResponse::from).collect(),
+    ))
+}
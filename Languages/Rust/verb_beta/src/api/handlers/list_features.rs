@@ -0,0 +1,26 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, FeatureResponse},
+        error::ApiError,
+    },
+    infra::db::Database,
+};
+
+/// Handler: List feature flags with their current values
+#[instrument(skip(state))]
+pub async fn list_features<D: Database>(
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<Vec<FeatureResponse>>, ApiError> {
+    let features = state
+        .feature_flags
+        .snapshot()
+        .into_iter()
+        .map(FeatureResponse::from)
+        .collect();
+
+    Ok(ApiResponse::ok(features))
+}
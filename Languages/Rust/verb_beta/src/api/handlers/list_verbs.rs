@@ -1,13 +1,12 @@
-use axum::{
-    Json,
-    extract::{Query, State},
-};
+use axum::extract::State;
 use tracing::instrument;
 
 use crate::{
     api::{
         AppState,
-        dto::{ApiResponse, ErrorCode, ListVerbsQuery, ListVerbsResponse, VerbResponse},
+        dto::{ApiResponse, ListVerbsQuery, ListVerbsResponse, VerbResponse},
+        error::ApiError,
+        extract::ApiQuery,
     },
     domain::repository::VerbFilter,
     infra::db::Database,
@@ -17,8 +16,8 @@ use crate::{
 #[instrument(skip(state))]
 pub async fn list_verbs<D: Database>(
     State(state): State<AppState<D>>,
-    Query(query): Query<ListVerbsQuery>,
-) -> ApiResponse<ListVerbsResponse> {
+    ApiQuery(query): ApiQuery<ListVerbsQuery>,
+) -> Result<ApiResponse<ListVerbsResponse>, ApiError> {
     // Convert DTO query to domain filter
     let filter = VerbFilter {
         state: query.state.map(Into::into),
@@ -28,22 +27,19 @@ pub async fn list_verbs<D: Database>(
 
     let filter1 = filter.clone();
 
-    match state.verb_facade.list_verbs(filter).await {
-        Ok(verbs) => {
-            let total = verbs.len() as u32;
-            //FIXME: fix filter sharing from domain layer
-            let filter = filter1;
-            let response = ListVerbsResponse {
-                verbs: verbs.into_iter().map(VerbResponse::from).collect(),
-                total,
-                limit: filter.limit,
-                offset: filter.offset,
-            };
-            ApiResponse::ok(response)
-        }
-        Err(e) => {
-            tracing::error!(error = %e, "Failed to list verbs");
-            ApiResponse::error(ErrorCode::InternalError, e.to_string())
-        }
-    }
+    let verbs = state
+        .verb_facade
+        .list_verbs(filter)
+        .await
+        .inspect_err(|e| tracing::error!(error = %e, "Failed to list verbs"))?;
+
+    let total = verbs.len() as u32;
+    //FIXME: fix filter sharing from domain layer
+    let filter = filter1;
+    Ok(ApiResponse::ok(ListVerbsResponse {
+        verbs: verbs.into_iter().map(VerbResponse::from).collect(),
+        total,
+        limit: filter.limit,
+        offset: filter.offset,
+    }))
 }
@@ -0,0 +1,30 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, WebhookResponse},
+        error::ApiError,
+    },
+    application::Feature,
+    infra::db::Database,
+};
+
+/// Handler: List registered webhooks
+#[instrument(skip(state))]
+pub async fn list_webhooks<D: Database>(
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<Vec<WebhookResponse>>, ApiError> {
+    state.ensure_enabled(Feature::Webhooks)?;
+
+    let webhooks = state
+        .webhook_facade
+        .list_webhooks()
+        .await
+        .inspect_err(|e| tracing::error!(error = %e, "Failed to list webhooks"))?;
+
+    Ok(ApiResponse::ok(
+        webhooks.iter().map(WebhookResponse::from).collect(),
+    ))
+}
@@ -1,16 +1,72 @@
+#[cfg(test)]
+mod contract_tests;
 mod create_verb;
+mod delete_webhook;
 mod drop_verb;
+mod flush_cache;
+mod get_attachment;
+mod get_audit_log;
+mod get_burndown;
+mod get_daily_review;
+mod get_db_stats;
+mod get_log_level;
 mod get_logs;
+mod get_notification_preferences;
+mod get_snapshot;
+mod get_sync_changes;
 mod get_verb;
+mod get_webhook_deliveries;
+mod list_attachments;
+mod list_features;
 mod list_verbs;
+mod list_webhooks;
+mod patch_verb;
+mod push_sync_mutations;
+mod register_webhook;
+mod reset_notification_preferences;
+mod restore_snapshot;
+mod send_daily_review;
+mod set_feature;
+mod set_log_level;
+mod set_notification_preferences;
+mod stream_events;
+mod stream_verbs;
 mod update_state;
+mod upload_attachment;
 
 pub use create_verb::create_verb;
+pub use delete_webhook::delete_webhook;
 pub use drop_verb::drop_verb;
+pub use flush_cache::flush_cache;
+pub use get_attachment::get_attachment;
+pub use get_audit_log::get_audit_log;
+pub use get_burndown::get_burndown;
+pub use get_daily_review::get_daily_review;
+pub use get_db_stats::get_db_stats;
+pub use get_log_level::get_log_level;
 pub use get_logs::get_verb_logs;
+pub use get_notification_preferences::get_notification_preferences;
+pub use get_snapshot::get_snapshot;
+pub use get_sync_changes::get_sync_changes;
 pub use get_verb::get_verb;
+pub use get_webhook_deliveries::get_webhook_deliveries;
+pub use list_attachments::list_attachments;
+pub use list_features::list_features;
 pub use list_verbs::list_verbs;
+pub use list_webhooks::list_webhooks;
+pub use patch_verb::patch_verb;
+pub use push_sync_mutations::push_sync_mutations;
+pub use register_webhook::register_webhook;
+pub use reset_notification_preferences::reset_notification_preferences;
+pub use restore_snapshot::restore_snapshot;
+pub use send_daily_review::send_daily_review;
+pub use set_feature::set_feature;
+pub use set_log_level::set_log_level;
+pub use set_notification_preferences::set_notification_preferences;
+pub use stream_events::stream_events;
+pub use stream_verbs::stream_verbs;
 pub use update_state::update_verb_state;
+pub use upload_attachment::upload_attachment;
 
 // Built-in extractors:
 // Extractor            What It Extracts
@@ -0,0 +1,40 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, PatchVerbRequest, VerbResponse},
+        error::{ApiError, ErrorCode},
+        extract::{ApiJson, ApiPath},
+    },
+    application::Feature,
+    domain::model::VerbId,
+    infra::db::Database,
+};
+
+/// Handler: Partially update a verb (JSON Merge Patch)
+///
+/// Accepts `application/merge-patch+json` as well as `application/json`.
+#[instrument(skip(state, payload), fields(verb_id = %id))]
+pub async fn patch_verb<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    State(state): State<AppState<D>>,
+    ApiJson(payload): ApiJson<PatchVerbRequest>,
+) -> Result<ApiResponse<VerbResponse>, ApiError> {
+    state.ensure_enabled(Feature::VerbEditing)?;
+
+    // Parse verb ID
+    let verb_id = id
+        .parse::<VerbId>()
+        .map_err(|_| ApiError::invalid_id("verb"))?;
+
+    let changes = payload
+        .into_changes()
+        .map_err(|message| ApiError::new(ErrorCode::ValidationError, message))?;
+
+    let verb = state.verb_facade.update_verb(verb_id, changes).await?;
+
+    tracing::info!(verb_id = %verb.id(), "Verb updated");
+    Ok(ApiResponse::ok(VerbResponse::from(verb)))
+}
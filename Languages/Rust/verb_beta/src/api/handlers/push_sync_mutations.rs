@@ -0,0 +1,39 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, SyncPushRequest, SyncPushResponse, SyncResultResponse},
+        error::{ApiError, ErrorCode},
+        extract::ApiJson,
+    },
+    application::Feature,
+    infra::db::Database,
+};
+
+/// Handler: Apply a batch of mutations a client made offline
+///
+/// The batch is checked as a whole (ids, timestamps, changes), then each
+/// mutation is applied on its own: one rejected mutation is reported in
+/// its result and doesn't fail the request.
+#[instrument(skip(state, payload), fields(mutations = payload.mutations.len()))]
+pub async fn push_sync_mutations<D: Database>(
+    State(state): State<AppState<D>>,
+    ApiJson(payload): ApiJson<SyncPushRequest>,
+) -> Result<ApiResponse<SyncPushResponse>, ApiError> {
+    if payload.edits_fields() {
+        state.ensure_enabled(Feature::VerbEditing)?;
+    }
+
+    let invalid = |message| ApiError::new(ErrorCode::ValidationError, message);
+    let since = payload.cursor().map_err(invalid)?;
+    let mutations = payload.into_mutations().map_err(invalid)?;
+
+    let outcomes = state.sync_facade.push(since, mutations).await?;
+
+    tracing::info!(mutations = outcomes.len(), "Sync batch applied");
+    Ok(ApiResponse::ok(SyncPushResponse {
+        results: outcomes.into_iter().map(SyncResultResponse::from).collect(),
+    }))
+}
@@ -0,0 +1,49 @@
+use axum::extract::State;
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, RegisterWebhookRequest, WebhookCreatedResponse},
+        error::ApiError,
+        extract::ApiJson,
+    },
+    application::Feature,
+    domain::model::{EventType, UserId},
+    infra::db::Database,
+};
+
+/// Handler: Register a webhook
+///
+/// The response is the only place the signing secret is returned.
+#[instrument(skip(state, payload), fields(url = %payload.url))]
+pub async fn register_webhook<D: Database>(
+    State(state): State<AppState<D>>,
+    ApiJson(payload): ApiJson<RegisterWebhookRequest>,
+) -> Result<ApiResponse<WebhookCreatedResponse>, ApiError> {
+    state.ensure_enabled(Feature::Webhooks)?;
+
+    payload.validate()?;
+
+    let events = payload
+        .events
+        .iter()
+        .map(|e| EventType::from_str(e))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let owner = payload
+        .user_id
+        .as_deref()
+        .map(str::parse::<UserId>)
+        .transpose()
+        .map_err(|_| ApiError::invalid_id("user"))?;
+
+    let webhook = state
+        .webhook_facade
+        .register_webhook(payload.url, events, payload.secret, owner)
+        .await?;
+
+    tracing::info!(webhook_id = %webhook.id(), "Webhook registered");
+    Ok(ApiResponse::ok(WebhookCreatedResponse::from(webhook)))
+}
@@ -0,0 +1,24 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{AppState, dto::ApiResponse, error::ApiError, extract::ApiPath},
+    domain::model::UserId,
+    infra::db::Database,
+};
+
+/// Handler: Drop a user's notification preferences, back to the defaults
+#[instrument(skip(state), fields(user_id = %id))]
+pub async fn reset_notification_preferences<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<()>, ApiError> {
+    let user_id = id
+        .parse::<UserId>()
+        .map_err(|_| ApiError::invalid_id("user"))?;
+
+    state.preferences_facade.reset(user_id).await?;
+
+    tracing::info!(user_id = %user_id, "Notification preferences reset");
+    Ok(ApiResponse::ok(()))
+}
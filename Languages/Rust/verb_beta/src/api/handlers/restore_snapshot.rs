@@ -0,0 +1,28 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, DatabaseStatsResponse},
+        error::ApiError,
+        extract::ApiJson,
+    },
+    infra::db::{Database, Snapshot},
+};
+
+/// Handler: Replace the whole database with a snapshot
+///
+/// Answers with the row counts after the restore.
+#[instrument(skip(state, snapshot))]
+pub async fn restore_snapshot<D: Database>(
+    State(state): State<AppState<D>>,
+    ApiJson(snapshot): ApiJson<Snapshot>,
+) -> Result<ApiResponse<DatabaseStatsResponse>, ApiError> {
+    let taken_at = snapshot.taken_at;
+    state.admin_facade.restore_snapshot(snapshot).await?;
+    let stats = state.admin_facade.database_stats().await?;
+
+    tracing::warn!(%taken_at, verbs = stats.verbs, "Database restored from snapshot");
+    Ok(ApiResponse::ok(DatabaseStatsResponse::from(stats)))
+}
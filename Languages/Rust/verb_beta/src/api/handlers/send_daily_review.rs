@@ -0,0 +1,21 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, DailyReviewResponse},
+        error::ApiError,
+    },
+    infra::db::Database,
+};
+
+/// Handler: Send today's review through the configured notifier
+#[instrument(skip(state))]
+pub async fn send_daily_review<D: Database>(
+    State(state): State<AppState<D>>,
+) -> Result<ApiResponse<DailyReviewResponse>, ApiError> {
+    let review = state.review_facade.send_daily_review().await?;
+
+    Ok(ApiResponse::ok(DailyReviewResponse::from(review)))
+}
@@ -0,0 +1,38 @@
+use axum::extract::State;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, FeatureResponse, SetFeatureRequest},
+        error::{ApiError, ErrorCode},
+        extract::{ApiJson, ApiPath},
+    },
+    application::{Feature, audit},
+    domain::model::EntityRef,
+    infra::db::Database,
+};
+
+/// Handler: Switch a feature on or off
+#[instrument(skip(state, payload))]
+pub async fn set_feature<D: Database>(
+    ApiPath(name): ApiPath<String>,
+    State(state): State<AppState<D>>,
+    ApiJson(payload): ApiJson<SetFeatureRequest>,
+) -> Result<ApiResponse<FeatureResponse>, ApiError> {
+    let feature = Feature::from_str(&name)
+        .map_err(|e| ApiError::new(ErrorCode::UnknownFeature, e.to_string()))?;
+
+    let before = state.feature_flags.is_enabled(feature);
+    state.feature_flags.set(feature, payload.enabled);
+    audit::record_change(
+        EntityRef::new("feature", feature),
+        Some(&before),
+        Some(&payload.enabled),
+    );
+
+    Ok(ApiResponse::ok(FeatureResponse::from((
+        feature,
+        payload.enabled,
+    ))))
+}
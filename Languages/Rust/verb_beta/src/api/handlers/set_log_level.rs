@@ -0,0 +1,44 @@
+use axum::extract::State;
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, LogLevelResponse, SetLogLevelRequest},
+        error::{ApiError, ErrorCode},
+        extract::ApiJson,
+    },
+    application::audit,
+    domain::model::EntityRef,
+    infra::db::Database,
+};
+
+/// Handler: Replace the log filter of the running process
+#[instrument(skip(state), fields(filter = %payload.filter))]
+pub async fn set_log_level<D: Database>(
+    State(state): State<AppState<D>>,
+    ApiJson(payload): ApiJson<SetLogLevelRequest>,
+) -> Result<ApiResponse<LogLevelResponse>, ApiError> {
+    payload.validate()?;
+
+    let handle = state.log_level.as_ref().ok_or_else(|| {
+        ApiError::new(
+            ErrorCode::LogLevelUnavailable,
+            "Log filter cannot be changed in this process",
+        )
+    })?;
+
+    let before = handle.current();
+    handle
+        .set(&payload.filter)
+        .map_err(|e| ApiError::new(ErrorCode::InvalidLogFilter, e))?;
+    let after = handle.current();
+    audit::record_change(
+        EntityRef::new("log_level", "filter"),
+        Some(&before),
+        Some(&after),
+    );
+
+    Ok(ApiResponse::ok(LogLevelResponse { filter: after }))
+}
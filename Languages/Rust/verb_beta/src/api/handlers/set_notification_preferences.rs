@@ -0,0 +1,38 @@
+use axum::extract::State;
+use time::UtcOffset;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        ctx::Ctx,
+        dto::{ApiResponse, NotificationPreferencesRequest, NotificationPreferencesResponse},
+        error::ApiError,
+        extract::{ApiJson, ApiPath},
+    },
+    domain::model::UserId,
+    infra::db::Database,
+};
+
+/// Handler: Replace a user's notification preferences
+///
+/// Quiet hours are stored with the request's `Time-Zone` offset.
+#[instrument(skip(state, payload), fields(user_id = %id))]
+pub async fn set_notification_preferences<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    State(state): State<AppState<D>>,
+    ApiJson(payload): ApiJson<NotificationPreferencesRequest>,
+) -> Result<ApiResponse<NotificationPreferencesResponse>, ApiError> {
+    let user_id = id
+        .parse::<UserId>()
+        .map_err(|_| ApiError::invalid_id("user"))?;
+
+    let utc_offset = Ctx::current().utc_offset.unwrap_or(UtcOffset::UTC);
+    let preferences = payload.into_preferences(user_id, utc_offset)?;
+    state.preferences_facade.save(&preferences).await?;
+
+    tracing::info!(user_id = %user_id, "Notification preferences saved");
+    Ok(ApiResponse::ok(NotificationPreferencesResponse::from(
+        preferences,
+    )))
+}
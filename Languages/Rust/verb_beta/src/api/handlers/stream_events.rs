@@ -0,0 +1,67 @@
+use std::{convert::Infallible, time::Duration};
+
+use axum::{
+    extract::State,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures_util::stream;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        ctx::Ctx,
+        dto::VerbEventResponse,
+        error::{ApiError, ErrorCode},
+    },
+    application::VerbEvent,
+    infra::db::Database,
+};
+
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Handler: Push verb events as Server-Sent Events
+///
+/// Only events published after the client connects are sent. A client
+/// that falls too far behind skips the oldest events and gets a `lagged`
+/// event carrying how many it missed.
+#[instrument(skip(state))]
+pub async fn stream_events<D: Database>(
+    State(state): State<AppState<D>>,
+) -> Result<Response, ApiError> {
+    let Some(events) = state.verb_facade.subscribe() else {
+        return Err(ApiError::new(
+            ErrorCode::EventsUnavailable,
+            "Events are not enabled on this server",
+        ));
+    };
+    let ctx = Ctx::current();
+
+    let sse = stream::unfold(events, move |mut events| async move {
+        let event = match events.recv().await {
+            Ok(event) => ctx.scope(async { to_sse(event) }).await,
+            Err(RecvError::Lagged(missed)) => {
+                Event::default().event("lagged").data(missed.to_string())
+            }
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok::<_, Infallible>(event), events))
+    });
+
+    Ok(Sse::new(sse)
+        .keep_alive(KeepAlive::new().interval(KEEP_ALIVE))
+        .into_response())
+}
+
+fn to_sse(event: VerbEvent) -> Event {
+    let sse = Event::default()
+        .id(event.id.to_string())
+        .event(event.event_type.as_str());
+    let body = VerbEventResponse::from(event);
+    sse.json_data(&body)
+        .expect("verb event responses always serialize")
+}
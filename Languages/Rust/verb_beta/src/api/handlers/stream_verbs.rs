@@ -0,0 +1,54 @@
+use std::io;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use futures_util::StreamExt;
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        ctx::Ctx,
+        dto::{StreamVerbsQuery, VerbResponse},
+        extract::ApiQuery,
+    },
+    infra::db::Database,
+};
+
+const NDJSON: &str = "application/x-ndjson";
+
+/// Handler: Stream every verb as newline-delimited JSON
+///
+/// Each line is one verb. The next page is read from the repository only
+/// once the client has taken the previous one. A failure mid-stream is
+/// logged and aborts the body, so clients see a truncated response rather
+/// than a final error line.
+#[instrument(skip(state))]
+pub async fn stream_verbs<D: Database>(
+    State(state): State<AppState<D>>,
+    ApiQuery(query): ApiQuery<StreamVerbsQuery>,
+) -> Response {
+    // Timestamps are rendered while the body is polled, outside the request
+    let ctx = Ctx::current();
+
+    let lines = state
+        .verb_facade
+        .stream_verbs(query.state.map(Into::into))
+        .then(move |verb| {
+            ctx.scope(async move {
+                let verb = verb.map_err(|e| {
+                    tracing::error!(error = %e, "Failed to stream verbs");
+                    io::Error::other(e.to_string())
+                })?;
+                let mut line = serde_json::to_vec(&VerbResponse::from(verb))?;
+                line.push(b'\n');
+                Ok::<_, io::Error>(line)
+            })
+        });
+
+    ([(header::CONTENT_TYPE, NDJSON)], Body::from_stream(lines)).into_response()
+}
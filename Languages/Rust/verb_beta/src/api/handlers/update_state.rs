@@ -1,11 +1,13 @@
-use axum::extract::{Json, Path, State};
+use axum::extract::State;
 use tracing::instrument;
 use validator::Validate;
 
 use crate::{
     api::{
         AppState,
-        dto::{ApiResponse, ErrorCode, UpdateStateRequest, VerbResponse},
+        dto::{ApiResponse, UpdateStateRequest, VerbResponse},
+        error::ApiError,
+        extract::{ApiJson, ApiPath},
     },
     domain::model::VerbId,
     infra::db::Database,
@@ -14,46 +16,32 @@ use crate::{
 /// Handler: Update verb state
 #[instrument(skip(state), fields(verb_id = %id))]
 pub async fn update_verb_state<D: Database>(
-    Path(id): Path<String>,
+    ApiPath(id): ApiPath<String>,
     State(state): State<AppState<D>>,
-    Json(payload): Json<UpdateStateRequest>,
-) -> ApiResponse<VerbResponse> {
+    ApiJson(payload): ApiJson<UpdateStateRequest>,
+) -> Result<ApiResponse<VerbResponse>, ApiError> {
     // Validate
-    if let Err(validation_errors) = payload.validate() {
-        return ApiResponse::error(
-            ErrorCode::ValidationError,
-            format!("Validation failed: {}", validation_errors),
-        );
-    }
+    payload.validate()?;
 
     // Parse verb ID
-    let verb_id = match id.parse::<VerbId>() {
-        Ok(id) => id,
-        Err(_) => {
-            return ApiResponse::error(ErrorCode::ValidationError, "Invalid verb ID format");
-        }
-    };
+    let verb_id = id
+        .parse::<VerbId>()
+        .map_err(|_| ApiError::invalid_id("verb"))?;
 
     // Convert DTO state to domain state
     let next_state = payload.state.into();
 
-    match state
+    let verb = state
         .verb_facade
         .transition_verb(verb_id, next_state, payload.reason)
         .await
-    {
-        Ok(verb) => {
-            tracing::info!(
-                verb_id = %verb.id(),
-                new_state = ?verb.state(),
-                "Verb state updated"
-            );
-            //FIXME: This is stale when i update to paused i get no new response on postman?
-            ApiResponse::ok(VerbResponse::from(verb))
-        }
-        Err(e) => {
-            tracing::error!(error = %e, "Failed to update verb state");
-            ApiResponse::error(ErrorCode::InternalError, e.to_string())
-        }
-    }
+        .inspect_err(|e| tracing::warn!(error = %e, "Failed to update verb state"))?;
+
+    tracing::info!(
+        verb_id = %verb.id(),
+        new_state = ?verb.state(),
+        "Verb state updated"
+    );
+    //FIXME: This is stale when i update to paused i get no new response on postman?
+    Ok(ApiResponse::ok(VerbResponse::from(verb)))
 }
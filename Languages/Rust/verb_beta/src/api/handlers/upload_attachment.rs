@@ -0,0 +1,63 @@
+use axum::extract::{Multipart, State, multipart::MultipartRejection};
+use tracing::instrument;
+
+use crate::{
+    api::{
+        AppState,
+        dto::{ApiResponse, AttachmentResponse},
+        error::{ApiError, ErrorCode},
+        extract::ApiPath,
+    },
+    domain::model::VerbId,
+    infra::db::Database,
+};
+
+/// Multipart field carrying the file
+const FILE_FIELD: &str = "file";
+
+/// Handler: Attach a file to a verb
+///
+/// Expects `multipart/form-data` with the file in a `file` field; other
+/// fields are ignored. A part without its own content type is stored as
+/// `application/octet-stream`.
+#[instrument(skip(state, multipart), fields(verb_id = %id))]
+pub async fn upload_attachment<D: Database>(
+    ApiPath(id): ApiPath<String>,
+    State(state): State<AppState<D>>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<ApiResponse<AttachmentResponse>, ApiError> {
+    let verb_id = id
+        .parse::<VerbId>()
+        .map_err(|_| ApiError::invalid_id("verb"))?;
+    let mut multipart = multipart?;
+
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() != Some(FILE_FIELD) {
+            continue;
+        }
+
+        let filename = field.file_name().unwrap_or_default().to_string();
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let data = field.bytes().await?;
+
+        let attachment = state
+            .attachment_facade
+            .add_attachment(verb_id, &filename, &content_type, data.to_vec())
+            .await?;
+
+        tracing::info!(
+            attachment_id = %attachment.id(),
+            size = attachment.size(),
+            "Attachment stored"
+        );
+        return Ok(ApiResponse::ok(AttachmentResponse::from(&attachment)));
+    }
+
+    Err(ApiError::new(
+        ErrorCode::AttachmentMissingFile,
+        format!("Expected a multipart field named '{FILE_FIELD}'"),
+    ))
+}
@@ -0,0 +1,51 @@
+//! Spanish catalog
+
+use crate::api::error::ErrorCode;
+
+pub(super) fn error_title(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::MalformedRequest => "Solicitud mal formada",
+        ErrorCode::UnsupportedMediaType => "Tipo de contenido no admitido",
+        ErrorCode::ValidationError => "Error de validación",
+        ErrorCode::InvalidId => "Identificador no válido",
+        ErrorCode::PayloadTooLarge => "Cuerpo de la solicitud demasiado grande",
+        ErrorCode::RequestTimeout => "Tiempo de espera agotado",
+        ErrorCode::VerbNotFound => "Verbo no encontrado",
+        ErrorCode::TitleEmpty => "Título vacío",
+        ErrorCode::TitleTooLong => "Título demasiado largo",
+        ErrorCode::TitleInvalidCharacters => "Caracteres no válidos en el título",
+        ErrorCode::DescriptionTooLong => "Descripción demasiado larga",
+        ErrorCode::DescriptionInvalidCharacters => "Caracteres no válidos en la descripción",
+        ErrorCode::ContextTooLong => "Contexto demasiado largo",
+        ErrorCode::ReasonTooLong => "Motivo demasiado largo",
+        ErrorCode::InvalidState => "Estado no válido",
+        ErrorCode::InvalidTransition => "Transición de estado no válida",
+        ErrorCode::WebhookNotFound => "Webhook no encontrado",
+        ErrorCode::WebhookInvalidUrl => "URL de webhook no válida",
+        ErrorCode::WebhookNoEvents => "El webhook no tiene eventos",
+        ErrorCode::WebhookUnknownEvent => "Evento de webhook desconocido",
+        ErrorCode::WebhookEmptySecret => "Secreto de webhook vacío",
+        ErrorCode::AttachmentNotFound => "Adjunto no encontrado",
+        ErrorCode::AttachmentMissingFile => "No se ha subido ningún archivo",
+        ErrorCode::AttachmentEmpty => "Adjunto vacío",
+        ErrorCode::AttachmentInvalidFilename => "Nombre de archivo no válido",
+        ErrorCode::AttachmentInvalidContentType => "Tipo de contenido del adjunto no válido",
+        ErrorCode::AttachmentTooLarge => "Adjunto demasiado grande",
+        ErrorCode::AttachmentUnsupportedType => "Tipo de adjunto no admitido",
+        ErrorCode::Unauthorized => "No autorizado",
+        ErrorCode::AdminDisabled => "API de administración desactivada",
+        ErrorCode::FeatureDisabled => "Función desactivada",
+        ErrorCode::UnknownFeature => "Función desconocida",
+        ErrorCode::InvalidLogFilter => "Filtro de registro no válido",
+        ErrorCode::LogLevelUnavailable => "Control del nivel de registro no disponible",
+        ErrorCode::SnapshotUnsupported => "Instantáneas no admitidas",
+        ErrorCode::SnapshotFormatUnsupported => "Formato de instantánea no admitido",
+        ErrorCode::NotifierUnavailable => "Notificaciones no configuradas",
+        ErrorCode::EventsUnavailable => "Eventos en vivo no disponibles",
+        ErrorCode::NotificationFailed => "Error al enviar la notificación",
+        ErrorCode::NotificationSuppressed => "Notificación suprimida",
+        ErrorCode::PreferencesUnknownTopic => "Tema de notificación desconocido",
+        ErrorCode::PreferencesInvalidQuietHours => "Horas de silencio no válidas",
+        ErrorCode::InternalError => "Error interno del servidor",
+    }
+}
@@ -0,0 +1,51 @@
+//! French catalog
+
+use crate::api::error::ErrorCode;
+
+pub(super) fn error_title(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::MalformedRequest => "Requête mal formée",
+        ErrorCode::UnsupportedMediaType => "Type de média non pris en charge",
+        ErrorCode::ValidationError => "Échec de la validation",
+        ErrorCode::InvalidId => "Identifiant invalide",
+        ErrorCode::PayloadTooLarge => "Corps de requête trop volumineux",
+        ErrorCode::RequestTimeout => "Délai de requête dépassé",
+        ErrorCode::VerbNotFound => "Verbe introuvable",
+        ErrorCode::TitleEmpty => "Titre vide",
+        ErrorCode::TitleTooLong => "Titre trop long",
+        ErrorCode::TitleInvalidCharacters => "Caractères invalides dans le titre",
+        ErrorCode::DescriptionTooLong => "Description trop longue",
+        ErrorCode::DescriptionInvalidCharacters => "Caractères invalides dans la description",
+        ErrorCode::ContextTooLong => "Contexte trop long",
+        ErrorCode::ReasonTooLong => "Motif trop long",
+        ErrorCode::InvalidState => "État invalide",
+        ErrorCode::InvalidTransition => "Transition d'état invalide",
+        ErrorCode::WebhookNotFound => "Webhook introuvable",
+        ErrorCode::WebhookInvalidUrl => "URL de webhook invalide",
+        ErrorCode::WebhookNoEvents => "Le webhook n'a aucun événement",
+        ErrorCode::WebhookUnknownEvent => "Événement de webhook inconnu",
+        ErrorCode::WebhookEmptySecret => "Secret de webhook vide",
+        ErrorCode::AttachmentNotFound => "Pièce jointe introuvable",
+        ErrorCode::AttachmentMissingFile => "Aucun fichier envoyé",
+        ErrorCode::AttachmentEmpty => "Pièce jointe vide",
+        ErrorCode::AttachmentInvalidFilename => "Nom de fichier invalide",
+        ErrorCode::AttachmentInvalidContentType => "Type de contenu de pièce jointe invalide",
+        ErrorCode::AttachmentTooLarge => "Pièce jointe trop volumineuse",
+        ErrorCode::AttachmentUnsupportedType => "Type de pièce jointe non pris en charge",
+        ErrorCode::Unauthorized => "Non autorisé",
+        ErrorCode::AdminDisabled => "API d'administration désactivée",
+        ErrorCode::FeatureDisabled => "Fonctionnalité désactivée",
+        ErrorCode::UnknownFeature => "Fonctionnalité inconnue",
+        ErrorCode::InvalidLogFilter => "Filtre de journalisation invalide",
+        ErrorCode::LogLevelUnavailable => "Contrôle du niveau de journalisation indisponible",
+        ErrorCode::SnapshotUnsupported => "Instantanés non pris en charge",
+        ErrorCode::SnapshotFormatUnsupported => "Format d'instantané non pris en charge",
+        ErrorCode::NotifierUnavailable => "Notifications non configurées",
+        ErrorCode::EventsUnavailable => "Événements en direct indisponibles",
+        ErrorCode::NotificationFailed => "Échec de la notification",
+        ErrorCode::NotificationSuppressed => "Notification supprimée",
+        ErrorCode::PreferencesUnknownTopic => "Sujet de notification inconnu",
+        ErrorCode::PreferencesInvalidQuietHours => "Heures de silence invalides",
+        ErrorCode::InternalError => "Erreur interne du serveur",
+    }
+}
@@ -0,0 +1,99 @@
+//! Locale negotiation and message catalogs
+//!
+//! Only the short, fixed strings of a response are translated — problem
+//! `title`s today. `detail` stays English: it is diagnostic text built from
+//! domain errors, not something to show end users verbatim.
+//!
+//! English lives next to each code ([`ErrorCode::title`]); every other
+//! locale has a catalog module with an exhaustive match, so adding a code
+//! without translating it doesn't compile.
+
+mod es;
+mod fr;
+
+use crate::api::error::ErrorCode;
+
+/// Supported response languages
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Fr,
+}
+
+impl Locale {
+    const ALL: [Locale; 3] = [Locale::En, Locale::Es, Locale::Fr];
+
+    /// BCP 47 primary language tag, as sent in `Content-Language`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Es => "es",
+            Self::Fr => "fr",
+        }
+    }
+
+    /// Best supported match for an `Accept-Language` header
+    ///
+    /// Ranges are tried by descending `q` (ties keep header order) and
+    /// matched on their primary subtag, so `es-MX` selects Spanish. `*`
+    /// matches the default. `None` when nothing acceptable is supported.
+    pub fn negotiate(accept_language: &str) -> Option<Self> {
+        let mut ranges: Vec<(&str, f32)> = accept_language
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';').map(str::trim);
+                let range = parts.next().filter(|r| !r.is_empty())?;
+                let q = parts
+                    .find_map(|p| p.strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+                Some((range, q))
+            })
+            .filter(|&(_, q)| q > 0.0)
+            .collect();
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ranges.into_iter().find_map(|(range, _)| {
+            if range == "*" {
+                return Some(Self::default());
+            }
+            let primary = range.split('-').next().unwrap_or(range);
+            Self::ALL
+                .into_iter()
+                .find(|l| l.as_str().eq_ignore_ascii_case(primary))
+        })
+    }
+
+    /// Problem `title` for `code` in this locale
+    pub fn error_title(self, code: ErrorCode) -> &'static str {
+        match self {
+            Self::En => code.title(),
+            Self::Es => es::error_title(code),
+            Self::Fr => fr::error_title(code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_by_quality_and_primary_subtag() {
+        assert_eq!(Locale::negotiate("fr-CA, es;q=0.9"), Some(Locale::Fr));
+        assert_eq!(
+            Locale::negotiate("de, es-MX;q=0.8, fr;q=0.5"),
+            Some(Locale::Es)
+        );
+        assert_eq!(Locale::negotiate("en;q=0.1, FR;q=0.7"), Some(Locale::Fr));
+        assert_eq!(Locale::negotiate("de, *;q=0.1"), Some(Locale::En));
+    }
+
+    #[test]
+    fn nothing_acceptable_is_none() {
+        assert_eq!(Locale::negotiate("de, ja"), None);
+        assert_eq!(Locale::negotiate("fr;q=0"), None);
+        assert_eq!(Locale::negotiate(""), None);
+    }
+}
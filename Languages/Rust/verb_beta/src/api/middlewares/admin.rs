@@ -0,0 +1,108 @@
+//! Admin authentication
+//!
+//! The `/admin` routes are for operators only: callers must present the
+//! configured `ADMIN_TOKEN` as a bearer token. Without a configured token
+//! the whole group answers 403, so the admin API is off by default.
+//! Authenticated requests carry [`Actor::Admin`] for the audit log.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    api::error::{ApiError, ErrorCode},
+    domain::model::Actor,
+};
+
+/// Middleware: reject requests without the admin bearer token
+pub async fn require_admin(
+    State(token): State<Option<Arc<str>>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = token else {
+        return ApiError::new(ErrorCode::AdminDisabled, "The admin API is not configured")
+            .into_response();
+    };
+
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match presented {
+        Some(presented) if constant_time_eq(presented.as_bytes(), expected.as_bytes()) => {
+            request.extensions_mut().insert(Actor::Admin);
+            next.run(request).await
+        }
+        _ => ApiError::new(
+            ErrorCode::Unauthorized,
+            "A valid admin bearer token is required",
+        )
+        .into_response(),
+    }
+}
+
+/// Compare without short-circuiting, so timing doesn't leak how much of
+/// the token matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, http::StatusCode, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn status(token: Option<&str>, authorization: Option<&str>) -> StatusCode {
+        let app =
+            Router::new()
+                .route("/", get(|| async { "ok" }))
+                .layer(middleware::from_fn_with_state(
+                    token.map(Arc::from),
+                    require_admin,
+                ));
+        let mut request = Request::get("/");
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn accepts_only_the_configured_token() {
+        assert_eq!(
+            status(Some("s3cret"), Some("Bearer s3cret")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(Some("s3cret"), Some("Bearer s3cre")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(Some("s3cret"), Some("s3cret")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(status(Some("s3cret"), None).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn disabled_without_a_token() {
+        assert_eq!(
+            status(None, Some("Bearer anything")).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+}
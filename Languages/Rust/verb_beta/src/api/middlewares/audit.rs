@@ -0,0 +1,102 @@
+//! Audit logging of mutating requests
+//!
+//! Every `POST`, `PUT`, `PATCH` and `DELETE` that reaches a route leaves an
+//! audit entry, whatever its outcome:
+//!
+//! - actor: `admin` behind `require_admin`, else the user named by the
+//!   `X-User-Id` header, else `anonymous`
+//! - IP: the peer address of the connection (forwarding headers are not
+//!   trusted)
+//! - endpoint: method and route template, e.g. `PATCH /api/v1/verbs/{id}`
+//! - entity and before/after snapshots: whatever the application recorded
+//!   through [`crate::application::audit`]; one entry per changed entity
+//!
+//! Entries are written after the response is built; failing to write them
+//! is logged but doesn't fail the request.
+
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{HeaderMap, HeaderName, Method},
+    middleware::Next,
+    response::Response,
+};
+use tracing::error;
+
+use crate::{
+    api::AppState,
+    application::AuditRecorder,
+    domain::model::{Actor, AuditEntry},
+    infra::db::Database,
+};
+
+pub(super) const USER_ID: HeaderName = HeaderName::from_static("x-user-id");
+
+/// Middleware: record an audit entry for every mutating request
+pub async fn audit_requests<D: Database>(
+    State(state): State<AppState<D>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    if !is_mutating(&method) {
+        return next.run(request).await;
+    }
+
+    let actor = request
+        .extensions()
+        .get::<Actor>()
+        .copied()
+        .unwrap_or_else(|| actor_from_headers(request.headers()));
+    let ip = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let endpoint = match request.extensions().get::<MatchedPath>() {
+        Some(path) => path.as_str().to_owned(),
+        None => request.uri().path().to_owned(),
+    };
+
+    let recorder = AuditRecorder::new();
+    let response = recorder.scope(next.run(request)).await;
+
+    let entry = AuditEntry::new(
+        actor,
+        ip,
+        method.as_str(),
+        endpoint,
+        response.status().as_u16(),
+    );
+    let changes = recorder.take();
+    let entries: Vec<AuditEntry> = if changes.is_empty() {
+        vec![entry]
+    } else {
+        changes
+            .into_iter()
+            .map(|change| {
+                entry
+                    .clone()
+                    .with_change(change.entity, change.before, change.after)
+            })
+            .collect()
+    };
+
+    if let Err(e) = state.audit_facade.record(&entries).await {
+        error!(error = %e, %actor, %method, "Failed to write audit log");
+    }
+
+    response
+}
+
+fn is_mutating(method: &Method) -> bool {
+    [Method::POST, Method::PUT, Method::PATCH, Method::DELETE].contains(method)
+}
+
+fn actor_from_headers(headers: &HeaderMap) -> Actor {
+    headers
+        .get(USER_ID)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .map_or(Actor::Anonymous, Actor::User)
+}
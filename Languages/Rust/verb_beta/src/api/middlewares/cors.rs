@@ -0,0 +1,87 @@
+//! CORS for browser clients
+//!
+//! Only origins in [`AllowedOrigins`] get CORS headers; the list is read
+//! per request, so a config reload applies immediately.
+
+use axum::http::{HeaderName, Method, header};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use super::{
+    audit::USER_ID,
+    ctx::TIME_ZONE,
+    trace::{TRACEPARENT, TRACESTATE},
+};
+use crate::config::AllowedOrigins;
+
+/// Request headers clients set on API calls
+const ALLOWED_HEADERS: [HeaderName; 7] = [
+    header::ACCEPT_LANGUAGE,
+    header::AUTHORIZATION,
+    header::CONTENT_TYPE,
+    TIME_ZONE,
+    USER_ID,
+    TRACEPARENT,
+    TRACESTATE,
+];
+
+/// Answer preflights and add CORS headers for `origins`
+pub fn cors(origins: AllowedOrigins) -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(move |origin, _| {
+            origins.allows(origin)
+        }))
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_headers(ALLOWED_HEADERS)
+        .expose_headers([header::CONTENT_LANGUAGE])
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn preflight(origin: &str) -> Request<Body> {
+        Request::options("/verbs")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn only_allowed_origins_get_cors_headers() {
+        let origins = AllowedOrigins::new(vec!["https://app.example.com".into()]);
+        let app = Router::new()
+            .route("/verbs", get(|| async { "ok" }))
+            .layer(cors(origins.clone()));
+
+        let response = app
+            .clone()
+            .oneshot(preflight("https://app.example.com"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+
+        origins.set(Vec::new());
+        let response = app
+            .oneshot(preflight("https://app.example.com"))
+            .await
+            .unwrap();
+        assert!(
+            !response
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        );
+    }
+}
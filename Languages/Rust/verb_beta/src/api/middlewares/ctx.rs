@@ -0,0 +1,135 @@
+//! Request context resolution
+//!
+//! Builds the [`Ctx`] for each request from its headers:
+//!
+//! - `Accept-Language` picks the response locale (English when nothing
+//!   supported is acceptable); the choice is echoed in `Content-Language`
+//! - `Time-Zone` asks for timestamps in a fixed UTC offset (`+02:00`,
+//!   `-0530`, `UTC`); anything else is ignored and timestamps stay UTC.
+//!   Named zones (`Europe/Paris`) need a tz database and aren't supported.
+
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderName, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use time::UtcOffset;
+
+use crate::api::{ctx::Ctx, i18n::Locale};
+
+pub(super) const TIME_ZONE: HeaderName = HeaderName::from_static("time-zone");
+
+/// Middleware: resolve the request [`Ctx`] and scope it over the handler
+pub async fn resolve_ctx(mut request: Request, next: Next) -> Response {
+    let ctx = from_headers(request.headers());
+    request.extensions_mut().insert(ctx);
+
+    let mut response = ctx.scope(next.run(request)).await;
+
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_LANGUAGE,
+        HeaderValue::from_static(ctx.locale.as_str()),
+    );
+    headers.append(
+        header::VARY,
+        HeaderValue::from_static("accept-language, time-zone"),
+    );
+    response
+}
+
+fn from_headers(headers: &HeaderMap) -> Ctx {
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+
+    Ctx {
+        locale: header(header::ACCEPT_LANGUAGE)
+            .and_then(Locale::negotiate)
+            .unwrap_or_default(),
+        utc_offset: header(TIME_ZONE).and_then(parse_offset),
+    }
+}
+
+/// `UTC`, `Z`, or `±HH[:MM]` / `±HHMM`
+fn parse_offset(raw: &str) -> Option<UtcOffset> {
+    let raw = raw.trim();
+    if raw.eq_ignore_ascii_case("utc") || raw.eq_ignore_ascii_case("z") {
+        return Some(UtcOffset::UTC);
+    }
+
+    let (sign, rest) = match raw.as_bytes().first()? {
+        b'+' => (1, &raw[1..]),
+        b'-' => (-1, &raw[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    if hours.len() > 2 || minutes.len() > 2 {
+        return None;
+    }
+    let hours: i8 = hours.parse().ok()?;
+    let minutes: i8 = minutes.parse().ok()?;
+    if minutes >= 60 {
+        return None;
+    }
+
+    UtcOffset::from_hms(sign * hours, sign * minutes, 0).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router, body::Body, http::StatusCode, middleware, response::IntoResponse, routing::get,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::api::error::{ApiError, ErrorCode};
+
+    #[test]
+    fn parses_fixed_offsets() {
+        let hm = |h, m| UtcOffset::from_hms(h, m, 0).ok();
+        assert_eq!(parse_offset("+02:00"), hm(2, 0));
+        assert_eq!(parse_offset("-0530"), hm(-5, -30));
+        assert_eq!(parse_offset("+9"), hm(9, 0));
+        assert_eq!(parse_offset("utc"), Some(UtcOffset::UTC));
+        assert_eq!(parse_offset("Europe/Paris"), None);
+        assert_eq!(parse_offset("+02:75"), None);
+        assert_eq!(parse_offset("+30:00"), None);
+    }
+
+    #[tokio::test]
+    async fn errors_are_rendered_in_the_negotiated_locale() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|ctx: Ctx| async move {
+                    assert_eq!(ctx.locale, Locale::Fr);
+                    ApiError::new(ErrorCode::VerbNotFound, "Verb not found").into_response()
+                }),
+            )
+            .layer(middleware::from_fn(resolve_ctx));
+
+        let response = app
+            .oneshot(
+                Request::get("/")
+                    .header(header::ACCEPT_LANGUAGE, "fr-FR, en;q=0.5")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "fr");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["title"], "Verbe introuvable");
+        assert_eq!(problem["code"], "VERB_NOT_FOUND");
+    }
+}
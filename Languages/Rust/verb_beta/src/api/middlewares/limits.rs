@@ -0,0 +1,210 @@
+//! Request timeout and body-size limits
+//!
+//! Limits are read from [`SharedLimits`] on every request, so a config
+//! reload applies to the next request. An exceeded limit is answered with
+//! a bare 408/413, which [`envelope_limit_errors`] rewrites into
+//! problem+json.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{DefaultBodyLimit, Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use http_body_util::Limited;
+
+use crate::{
+    api::error::{ApiError, ErrorCode},
+    config::{ApiLimits, RouteLimits, SharedLimits},
+};
+
+/// Picks a route group's limits out of the API limits
+pub type RouteGroup = fn(&ApiLimits) -> RouteLimits;
+
+/// Apply the `group` limits from `limits` to every route in `router`
+pub fn with_limits<S>(router: Router<S>, limits: &SharedLimits, group: RouteGroup) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Layers wrap what was added before them: the last one is outermost
+    router
+        // Replace axum's 2 MiB default with the configured limit
+        .layer(DefaultBodyLimit::disable())
+        .layer(middleware::from_fn_with_state(
+            (limits.clone(), group),
+            enforce_limits,
+        ))
+        // Sees the responses produced by every layer above
+        .layer(middleware::map_response(envelope_limit_errors))
+}
+
+async fn enforce_limits(
+    State((limits, group)): State<(SharedLimits, RouteGroup)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let limits = group(&limits.get());
+
+    // A declared length over the limit is rejected before reading anything
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|len| len.to_str().ok())
+        .and_then(|len| len.parse::<usize>().ok());
+    if declared.is_some_and(|len| len > limits.max_body_bytes) {
+        return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+    }
+
+    // Otherwise extractors fail with 413 once they read past the limit
+    let request = request.map(|body| Body::new(Limited::new(body, limits.max_body_bytes)));
+
+    // Only covers producing the response head; streamed bodies run on
+    tokio::time::timeout(limits.timeout, next.run(request))
+        .await
+        .unwrap_or_else(|_| StatusCode::REQUEST_TIMEOUT.into_response())
+}
+
+/// Turn limit rejections that aren't already JSON into problem+json
+async fn envelope_limit_errors(response: Response) -> Response {
+    let code = match response.status() {
+        StatusCode::REQUEST_TIMEOUT => ErrorCode::RequestTimeout,
+        StatusCode::PAYLOAD_TOO_LARGE => ErrorCode::PayloadTooLarge,
+        _ => return response,
+    };
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|ct| ct.to_str().ok())
+        .is_some_and(|ct| ct.contains("json"));
+    if is_json {
+        return response;
+    }
+
+    let message = match code {
+        ErrorCode::RequestTimeout => "Request took too long to complete",
+        _ => "Request body is too large",
+    };
+    ApiError::new(code, message).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::{
+        Json,
+        body::Body,
+        http::Request,
+        routing::{get, post},
+    };
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn test_limits(max_body_bytes: usize) -> ApiLimits {
+        ApiLimits {
+            verbs: RouteLimits {
+                timeout: Duration::from_millis(50),
+                max_body_bytes,
+            },
+            ..ApiLimits::default()
+        }
+    }
+
+    fn router_with(limits: &SharedLimits) -> Router {
+        let routes = Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "late"
+                }),
+            )
+            .route(
+                "/echo",
+                post(|Json(body): Json<Value>| async { Json(body) }),
+            );
+
+        with_limits(routes, limits, |limits| limits.verbs)
+    }
+
+    fn router() -> Router {
+        router_with(&SharedLimits::new(test_limits(32)))
+    }
+
+    async fn body_json(response: Response) -> Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn echo(body: Body) -> Request<Body> {
+        Request::post("/echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn slow_handler_times_out_with_envelope() {
+        let response = router()
+            .oneshot(Request::get("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+        assert_eq!(body_json(response).await["code"], "REQUEST_TIMEOUT");
+    }
+
+    #[tokio::test]
+    async fn oversized_body_is_rejected_with_envelope() {
+        let big = format!(r#"{{"pad":"{}"}}"#, "x".repeat(64));
+
+        // Declared length over the limit is rejected up front
+        let mut request = echo(Body::from(big.clone()));
+        request
+            .headers_mut()
+            .insert(header::CONTENT_LENGTH, big.len().into());
+        let response = router().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body_json(response).await["code"], "PAYLOAD_TOO_LARGE");
+
+        // Without a length, the body is cut off while the extractor reads it
+        let response = router().oneshot(echo(Body::from(big))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body_json(response).await["code"], "PAYLOAD_TOO_LARGE");
+    }
+
+    #[tokio::test]
+    async fn small_body_passes_through() {
+        let response = router()
+            .oneshot(echo(Body::from(r#"{"ok":true}"#)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn changed_limits_apply_to_the_next_request() {
+        let limits = SharedLimits::new(test_limits(32));
+        let router = router_with(&limits);
+        let big = format!(r#"{{"pad":"{}"}}"#, "x".repeat(64));
+
+        let response = router
+            .clone()
+            .oneshot(echo(Body::from(big.clone())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        limits.set(test_limits(1024));
+        let response = router.oneshot(echo(Body::from(big))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
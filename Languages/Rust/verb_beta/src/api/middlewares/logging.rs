@@ -0,0 +1,195 @@
+//! Request/response logging
+//!
+//! One `tracing` event per request (target `verb::http`) with method, path,
+//! status and latency, so HTTP traffic lands in the same pipeline as the
+//! application logs. When [`HttpLogConfig::log_bodies`] is on, JSON bodies
+//! are included with the configured fields redacted; anything else is only
+//! summarised.
+
+use std::{sync::Arc, time::Instant};
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+
+use crate::config::HttpLogConfig;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Middleware: log every request once it has been answered
+pub async fn log_requests(
+    State(cfg): State<Arc<HttpLogConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_owned();
+    let start = Instant::now();
+
+    let (request, request_body) = if cfg.log_bodies {
+        let (parts, body) = request.into_parts();
+        let len = body.size_hint().exact();
+        let (body, logged) = capture(body, len, &cfg).await;
+        (Request::from_parts(parts, body), logged)
+    } else {
+        (request, None)
+    };
+
+    let response = next.run(request).await;
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let status = response.status();
+
+    let (response, response_body) = if cfg.log_bodies {
+        let (parts, body) = response.into_parts();
+        let len = body.size_hint().exact();
+        let (body, logged) = capture(body, len, &cfg).await;
+        (Response::from_parts(parts, body), logged)
+    } else {
+        (response, None)
+    };
+
+    macro_rules! log {
+        ($level:ident) => {
+            tracing::$level!(
+                target: "verb::http",
+                %method,
+                path,
+                status = status.as_u16(),
+                latency_ms,
+                request_body,
+                response_body,
+                "HTTP request"
+            )
+        };
+    }
+    if status.is_server_error() {
+        log!(error);
+    } else if status.is_client_error() {
+        log!(warn);
+    } else {
+        log!(info);
+    }
+
+    response
+}
+
+/// Buffer a body of known (`Content-Length` or empty), small size so it can be logged, and hand back
+/// an equivalent body. Streams and large bodies pass through untouched;
+/// empty bodies aren't logged at all.
+async fn capture(body: Body, len: Option<u64>, cfg: &HttpLogConfig) -> (Body, Option<String>) {
+    let len = match len {
+        Some(0) => return (body, None),
+        Some(len) if len <= cfg.max_body_bytes as u64 => len as usize,
+        Some(len) => return (body, Some(format!("<{len} bytes, not logged>"))),
+        None => return (body, Some("<streamed, not logged>".to_string())),
+    };
+
+    match axum::body::to_bytes(body, len).await {
+        Ok(bytes) => {
+            let logged = render(&bytes, cfg);
+            (Body::from(bytes), Some(logged))
+        }
+        // The peer went away mid-body; the handler would have failed too
+        Err(e) => (Body::empty(), Some(format!("<unreadable: {e}>"))),
+    }
+}
+
+/// JSON with redacted fields, or a summary for anything else
+fn render(bytes: &Bytes, cfg: &HttpLogConfig) -> String {
+    match serde_json::from_slice::<Value>(bytes) {
+        Ok(mut value) => {
+            redact(&mut value, cfg);
+            value.to_string()
+        }
+        Err(_) => format!("<{} bytes, not JSON>", bytes.len()),
+    }
+}
+
+fn redact(value: &mut Value, cfg: &HttpLogConfig) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if cfg.is_redacted(key) {
+                    *value = Value::String(REDACTED.into());
+                } else {
+                    redact(value, cfg);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| redact(v, cfg)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, http::header, middleware, routing::post};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn cfg() -> HttpLogConfig {
+        HttpLogConfig {
+            log_bodies: true,
+            max_body_bytes: 256,
+            redact_fields: vec!["title".into(), "Secret".into()],
+        }
+    }
+
+    #[test]
+    fn redacts_configured_fields_at_any_depth() {
+        let body = json!({
+            "data": {
+                "TITLE": "See the doctor about ...",
+                "state": "active",
+                "hooks": [{ "secret": "s3cret", "url": "http://x" }]
+            }
+        });
+
+        let logged = render(&Bytes::from(body.to_string()), &cfg());
+
+        assert!(!logged.contains("doctor"));
+        assert!(!logged.contains("s3cret"));
+        assert!(logged.contains("active"));
+        assert!(logged.contains("http://x"));
+    }
+
+    #[test]
+    fn summarises_non_json() {
+        assert_eq!(
+            render(&Bytes::from_static(b"title=secret"), &cfg()),
+            "<12 bytes, not JSON>"
+        );
+    }
+
+    #[tokio::test]
+    async fn captured_bodies_still_reach_handler_and_client() {
+        let app = Router::new()
+            .route("/", post(|body: String| async move { body }))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(cfg()),
+                log_requests,
+            ));
+        let payload = r#"{"title":"private"}"#;
+
+        let response = app
+            .oneshot(
+                Request::post("/")
+                    .header(header::CONTENT_LENGTH, payload.len())
+                    .body(Body::from(payload))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let echoed = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(echoed, payload.as_bytes());
+    }
+}
@@ -0,0 +1,17 @@
+//! HTTP middleware shared by the route groups
+
+mod admin;
+mod audit;
+mod cors;
+mod ctx;
+mod limits;
+mod logging;
+mod trace;
+
+pub use admin::require_admin;
+pub use audit::audit_requests;
+pub use cors::cors;
+pub use ctx::resolve_ctx;
+pub use limits::with_limits;
+pub use logging::log_requests;
+pub use trace::propagate_trace;
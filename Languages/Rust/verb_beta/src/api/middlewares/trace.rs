@@ -0,0 +1,105 @@
+//! Trace context propagation
+//!
+//! Continues the caller's W3C trace from `traceparent` / `tracestate` (or
+//! starts a new one), opens a `request` span carrying the ids so every log
+//! line of the request can be joined with the upstream gateway's trace,
+//! and scopes the [`TraceContext`] over the handler for outbound calls.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+
+use crate::application::TraceContext;
+
+pub const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
+pub const TRACESTATE: HeaderName = HeaderName::from_static("tracestate");
+
+/// Middleware: resolve the request's [`TraceContext`] and run it in a span
+///
+/// The response echoes `traceparent` with this service's span, so callers
+/// without their own tracing can still quote the trace id.
+pub async fn propagate_trace(request: Request, next: Next) -> Response {
+    let trace = {
+        let header = |name| request.headers().get(name).and_then(|v| v.to_str().ok());
+        TraceContext::from_headers(header(TRACEPARENT), header(TRACESTATE))
+    };
+
+    let span = tracing::info_span!(
+        "request",
+        trace_id = %trace.trace_id(),
+        span_id = %trace.span_id(),
+        parent_span_id = trace.parent_id(),
+        sampled = trace.is_sampled(),
+    );
+    let traceparent = trace.traceparent();
+
+    let mut response = trace.scope(next.run(request)).instrument(span).await;
+
+    if let Ok(value) = HeaderValue::from_str(&traceparent) {
+        response.headers_mut().insert(TRACEPARENT, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    let trace = TraceContext::current().expect("scoped over handler");
+                    format!("{} {}", trace.trace_id(), trace.tracestate().unwrap_or("-"))
+                }),
+            )
+            .layer(middleware::from_fn(propagate_trace))
+    }
+
+    async fn call(request: Request) -> (String, String) {
+        let response = app().oneshot(request).await.unwrap();
+        let traceparent = response.headers()[TRACEPARENT].to_str().unwrap().to_owned();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (String::from_utf8(body.to_vec()).unwrap(), traceparent)
+    }
+
+    #[tokio::test]
+    async fn continues_the_callers_trace() {
+        let request = Request::get("/")
+            .header(
+                TRACEPARENT,
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .header(TRACESTATE, "gw=1")
+            .body(Body::empty())
+            .unwrap();
+
+        let (body, traceparent) = call(request).await;
+
+        assert_eq!(body, "4bf92f3577b34da6a3ce929d0e0e4736 gw=1");
+        assert!(traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+        assert!(!traceparent.contains("00f067aa0ba902b7"), "our own span");
+    }
+
+    #[tokio::test]
+    async fn starts_a_trace_when_absent() {
+        let (body, traceparent) = call(Request::get("/").body(Body::empty()).unwrap()).await;
+
+        let trace_id = body.split(' ').next().unwrap();
+        assert_eq!(trace_id.len(), 32);
+        assert_eq!(
+            traceparent,
+            format!("00-{trace_id}-{}-01", &traceparent[36..52])
+        );
+    }
+}
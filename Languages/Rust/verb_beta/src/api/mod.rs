@@ -5,16 +5,29 @@
 //! application-level use case invocations, and translating application
 //! responses back into HTTP responses.
 
+mod ctx;
 mod dto;
 mod error;
+mod extract;
 mod handlers;
+mod i18n;
+mod middlewares;
 mod routes;
 
 use std::sync::Arc;
 
+pub use error::ApiError;
 pub use routes::app;
 
-use crate::{application::VerbFacade, infra::db::Database};
+use crate::{
+    application::{
+        AdminFacade, AttachmentFacade, AuditFacade, Feature, FeatureFlags,
+        NotificationPreferencesFacade, ReviewFacade, StatsFacade, SyncFacade, VerbFacade,
+        VerbUseCase, WebhookFacade,
+    },
+    config::telemetry::LogLevelHandle,
+    infra::db::Database,
+};
 
 /// HTTP boundary dependency container.
 ///
@@ -28,21 +41,93 @@ use crate::{application::VerbFacade, infra::db::Database};
 /// 2. We want compile-time type safety
 /// 3. Allows swapping database implementations
 ///
+/// The server builds exactly one `AppState` in [`crate::bootstrap`], which
+/// fixes `D` through the `SharedState` alias; only tests name another `D`.
+///
 /// ## Cloning Semantics
 ///
 /// `AppState` is **cheap to clone** because it only holds `Arc` pointers.
 /// Axum clones state for each request, but this just increments reference counts.
 #[derive(Clone)]
 pub struct AppState<D: Database> {
-    /// Application facade for verb operations
-    pub verb_facade: Arc<VerbFacade<D>>,
+    /// Verb operations: the `VerbFacade` in production, a mock in handler
+    /// tests
+    pub verb_facade: Arc<dyn VerbUseCase>,
+    /// Application facade for webhook management
+    pub webhook_facade: Arc<WebhookFacade<D>>,
+    /// Application facade for operational queries
+    pub admin_facade: Arc<AdminFacade<D>>,
+    /// Application facade for verb attachments
+    pub attachment_facade: Arc<AttachmentFacade<D>>,
+    /// Application facade for the daily review digest
+    pub review_facade: Arc<ReviewFacade<D>>,
+    /// Application facade for action log statistics
+    pub stats_facade: Arc<StatsFacade<D>>,
+    /// Application facade for per-user notification preferences
+    pub preferences_facade: Arc<NotificationPreferencesFacade<D>>,
+    /// Application facade for the audit log of mutating requests
+    pub audit_facade: Arc<AuditFacade<D>>,
+    /// Application facade for offline-first client sync
+    pub sync_facade: Arc<SyncFacade<D>>,
+    /// Runtime switches consulted by gated handlers
+    pub feature_flags: FeatureFlags,
+    /// Control over the process log filter; absent when tracing wasn't
+    /// initialized through `telemetry::init_tracing` (e.g. in tests)
+    pub log_level: Option<LogLevelHandle>,
 }
 
 impl<D: Database> AppState<D> {
-    /// Create new application state
-    pub fn new(verb_facade: VerbFacade<D>) -> Self {
+    /// Create new application state, with every feature on
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        verb_facade: VerbFacade<D>,
+        webhook_facade: WebhookFacade<D>,
+        admin_facade: AdminFacade<D>,
+        attachment_facade: AttachmentFacade<D>,
+        review_facade: ReviewFacade<D>,
+        stats_facade: StatsFacade<D>,
+        preferences_facade: NotificationPreferencesFacade<D>,
+        audit_facade: AuditFacade<D>,
+        sync_facade: SyncFacade<D>,
+    ) -> Self {
         Self {
             verb_facade: Arc::new(verb_facade),
+            webhook_facade: Arc::new(webhook_facade),
+            admin_facade: Arc::new(admin_facade),
+            attachment_facade: Arc::new(attachment_facade),
+            review_facade: Arc::new(review_facade),
+            stats_facade: Arc::new(stats_facade),
+            preferences_facade: Arc::new(preferences_facade),
+            audit_facade: Arc::new(audit_facade),
+            sync_facade: Arc::new(sync_facade),
+            feature_flags: FeatureFlags::default(),
+            log_level: None,
+        }
+    }
+
+    pub fn with_feature_flags(mut self, feature_flags: FeatureFlags) -> Self {
+        self.feature_flags = feature_flags;
+        self
+    }
+
+    pub fn with_log_level(mut self, log_level: LogLevelHandle) -> Self {
+        self.log_level = Some(log_level);
+        self
+    }
+
+    /// Serve verb routes from `verb_use_case` instead of the facade
+    #[cfg(test)]
+    pub fn with_verb_use_case(mut self, verb_use_case: Arc<dyn VerbUseCase>) -> Self {
+        self.verb_facade = verb_use_case;
+        self
+    }
+
+    /// Fail with `FEATURE_DISABLED` unless `feature` is on
+    pub fn ensure_enabled(&self, feature: Feature) -> Result<(), ApiError> {
+        if self.feature_flags.is_enabled(feature) {
+            Ok(())
+        } else {
+            Err(ApiError::feature_disabled(feature))
         }
     }
 }
@@ -1,10 +1,22 @@
+use std::sync::Arc;
+
 use axum::{
-    Json, Router,
+    Json, Router, middleware,
     response::Html,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
 };
 
-use crate::{api::handlers, infra::db::Database};
+use crate::{
+    api::{
+        handlers,
+        middlewares::{
+            audit_requests, cors, log_requests, propagate_trace, require_admin, resolve_ctx,
+            with_limits,
+        },
+    },
+    config::{AllowedOrigins, HttpLogConfig, SharedLimits},
+    infra::db::Database,
+};
 
 use super::AppState;
 
@@ -13,10 +25,46 @@ use super::AppState;
 /// ## Type Parameter
 /// The router is generic over `D: Database` to maintain type safety
 /// throughout the application stack.
-pub fn app<D: Database>(state: AppState<D>) -> Router {
+///
+/// Each route group gets its own timeout and body-size limit from `limits`,
+/// and browsers may call from `cors_origins`; both can change at runtime.
+/// Every request is logged according to `http_log`, in a span continuing
+/// the caller's W3C trace context. The `/admin` group
+/// requires `admin_token` as a bearer token. Mutating requests to
+/// `/api/v1` and `/admin` are written to the audit log.
+pub fn app<D: Database>(
+    state: AppState<D>,
+    limits: &SharedLimits,
+    cors_origins: &AllowedOrigins,
+    http_log: &HttpLogConfig,
+    admin_token: Option<&str>,
+) -> Router {
+    let audit = middleware::from_fn_with_state(state.clone(), audit_requests::<D>);
+
     Router::new()
         .merge(root_routes())
-        .nest("/api/v1", api_routes_v1())
+        .nest("/api/v1", api_routes_v1(limits).layer(audit.clone()))
+        .nest(
+            "/admin",
+            with_limits(admin_routes(), limits, |l| l.admin)
+                .merge(with_limits(snapshot_routes(), limits, |l| l.snapshot))
+                // Inside `require_admin`, which marks the actor as admin
+                .layer(audit)
+                .layer(middleware::from_fn_with_state(
+                    admin_token.map(Arc::from),
+                    require_admin,
+                )),
+        )
+        .layer(middleware::from_fn(resolve_ctx))
+        // Outside the handlers, so rejected and timed-out requests are logged too
+        .layer(middleware::from_fn_with_state(
+            Arc::new(http_log.clone()),
+            log_requests,
+        ))
+        // Preflights are answered here, before any auth, and still logged
+        .layer(cors(cors_origins.clone()))
+        // Outermost, so the request log line carries the trace ids
+        .layer(middleware::from_fn(propagate_trace))
         .with_state(state)
 }
 
@@ -28,8 +76,28 @@ fn root_routes<D: Database>() -> Router<AppState<D>> {
 }
 
 /// Version 1 API routes
-fn api_routes_v1<D: Database>() -> Router<AppState<D>> {
-    Router::new().nest("/verbs", verb_routes())
+fn api_routes_v1<D: Database>(limits: &SharedLimits) -> Router<AppState<D>> {
+    Router::new()
+        .nest(
+            "/verbs",
+            with_limits(verb_routes(), limits, |l| l.verbs).merge(with_limits(
+                verb_attachment_routes(),
+                limits,
+                |l| l.attachments,
+            )),
+        )
+        .nest(
+            "/attachments",
+            with_limits(attachment_routes(), limits, |l| l.attachments),
+        )
+        .nest(
+            "/webhooks",
+            with_limits(webhook_routes(), limits, |l| l.webhooks),
+        )
+        .nest("/review", with_limits(review_routes(), limits, |l| l.verbs))
+        .nest("/stats", with_limits(stats_routes(), limits, |l| l.verbs))
+        .nest("/users", with_limits(user_routes(), limits, |l| l.verbs))
+        .nest("/sync", with_limits(sync_routes(), limits, |l| l.verbs))
 }
 
 /// Verb resource routes
@@ -37,20 +105,147 @@ fn api_routes_v1<D: Database>() -> Router<AppState<D>> {
 /// REST endpoints:
 /// - POST   /verbs          → Create verb
 /// - GET    /verbs          → List verbs
+/// - GET    /verbs/stream   → Stream all verbs (NDJSON)
+/// - GET    /verbs/events   → Live verb events (SSE)
 /// - GET    /verbs/{id}      → Get single verb
-/// - PUT    /verbs/{id}      → Update verb state
+/// - PATCH  /verbs/{id}      → Edit fields (JSON Merge Patch)
+/// - PUT    /verbs/{id}/state → Update verb state
 /// - DELETE /verbs/{id}      → Drop verb
 /// - GET    /verbs/{id}/logs → Get action logs
 fn verb_routes<D: Database>() -> Router<AppState<D>> {
     Router::new()
         .route("/", post(handlers::create_verb::<D>))
         .route("/", get(handlers::list_verbs::<D>))
+        .route("/stream", get(handlers::stream_verbs::<D>))
+        .route("/events", get(handlers::stream_events::<D>))
         .route("/{id}", get(handlers::get_verb::<D>))
+        .route("/{id}", patch(handlers::patch_verb::<D>))
         .route("/{id}/state", put(handlers::update_verb_state::<D>))
         .route("/{id}", delete(handlers::drop_verb::<D>))
         .route("/{id}/logs", get(handlers::get_verb_logs::<D>))
 }
 
+/// Attachment routes under a verb, with the (larger) attachment limits
+///
+/// REST endpoints:
+/// - POST   /verbs/{id}/attachments → Upload a file (multipart)
+/// - GET    /verbs/{id}/attachments → List attachment metadata
+fn verb_attachment_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new()
+        .route("/{id}/attachments", post(handlers::upload_attachment::<D>))
+        .route("/{id}/attachments", get(handlers::list_attachments::<D>))
+}
+
+/// Attachment resource routes
+///
+/// REST endpoints:
+/// - GET    /attachments/{id} → Download contents
+fn attachment_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new().route("/{id}", get(handlers::get_attachment::<D>))
+}
+
+/// Webhook resource routes
+///
+/// REST endpoints:
+/// - POST   /webhooks                  → Register webhook
+/// - GET    /webhooks                  → List webhooks
+/// - DELETE /webhooks/{id}             → Remove webhook
+/// - GET    /webhooks/{id}/deliveries  → Delivery-attempt log
+fn webhook_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new()
+        .route("/", post(handlers::register_webhook::<D>))
+        .route("/", get(handlers::list_webhooks::<D>))
+        .route("/{id}", delete(handlers::delete_webhook::<D>))
+        .route(
+            "/{id}/deliveries",
+            get(handlers::get_webhook_deliveries::<D>),
+        )
+}
+
+/// Review digest routes
+///
+/// REST endpoints:
+/// - GET    /review/daily       → Today's review
+/// - POST   /review/daily/send  → Email today's review
+fn review_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new()
+        .route("/daily", get(handlers::get_daily_review::<D>))
+        .route("/daily/send", post(handlers::send_daily_review::<D>))
+}
+
+/// Statistics routes
+///
+/// REST endpoints:
+/// - GET    /stats/burndown?window=30d → Created/completed/dropped per day
+fn stats_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new().route("/burndown", get(handlers::get_burndown::<D>))
+}
+
+/// Offline-first sync routes
+///
+/// REST endpoints:
+/// - GET    /sync?since=cursor → Verbs and action logs changed since a cursor
+/// - POST   /sync              → Apply a batch of offline mutations
+fn sync_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new()
+        .route("/", get(handlers::get_sync_changes::<D>))
+        .route("/", post(handlers::push_sync_mutations::<D>))
+}
+
+/// Per-user settings routes
+///
+/// REST endpoints:
+/// - GET    /users/{id}/notification-preferences → Effective preferences
+/// - PUT    /users/{id}/notification-preferences → Replace preferences
+/// - DELETE /users/{id}/notification-preferences → Back to the defaults
+fn user_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new()
+        .route(
+            "/{id}/notification-preferences",
+            get(handlers::get_notification_preferences::<D>),
+        )
+        .route(
+            "/{id}/notification-preferences",
+            put(handlers::set_notification_preferences::<D>),
+        )
+        .route(
+            "/{id}/notification-preferences",
+            delete(handlers::reset_notification_preferences::<D>),
+        )
+}
+
+/// Operator routes, behind `require_admin`
+///
+/// REST endpoints:
+/// - GET    /admin/log-level        → Active log filter
+/// - PUT    /admin/log-level        → Replace log filter
+/// - GET    /admin/features         → List feature flags
+/// - PUT    /admin/features/{name}  → Switch a feature on/off
+/// - GET    /admin/db/stats         → Database row counts
+/// - GET    /admin/audit            → Audit log, filtered by actor and time
+/// - POST   /admin/cache/flush      → Drop every cached entity
+fn admin_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new()
+        .route("/log-level", get(handlers::get_log_level::<D>))
+        .route("/log-level", put(handlers::set_log_level::<D>))
+        .route("/features", get(handlers::list_features::<D>))
+        .route("/features/{name}", put(handlers::set_feature::<D>))
+        .route("/db/stats", get(handlers::get_db_stats::<D>))
+        .route("/audit", get(handlers::get_audit_log::<D>))
+        .route("/cache/flush", post(handlers::flush_cache::<D>))
+}
+
+/// Database dump/restore, with limits sized for a whole database
+///
+/// REST endpoints:
+/// - GET    /admin/snapshot → Dump every store as JSON
+/// - PUT    /admin/snapshot → Replace every store with a dump
+fn snapshot_routes<D: Database>() -> Router<AppState<D>> {
+    Router::new()
+        .route("/snapshot", get(handlers::get_snapshot::<D>))
+        .route("/snapshot", put(handlers::restore_snapshot::<D>))
+}
+
 // ## Key Points
 
 // 1. **Set state once** at the top-most router level with `.with_state()`
@@ -60,3 +255,89 @@ fn verb_routes<D: Database>() -> Router<AppState<D>> {
 // 5. **Don't call `.with_state()` multiple times** on nested routers
 
 // The error you were getting was likely:
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode, header},
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        application::{
+            AdminFacade, AttachmentFacade, AuditFacade, Cache, NotificationPreferencesFacade,
+            ReviewFacade, StatsFacade, SyncFacade, VerbFacade, WebhookFacade,
+            use_cases::AttachmentPolicy,
+        },
+        config::ApiLimits,
+        infra::{
+            blob::InMemoryBlobStore,
+            cache::InMemoryCache,
+            repository::{
+                cached::{CachePolicy, CachedDatabase},
+                in_memory::InMemoryDatabase,
+            },
+        },
+    };
+
+    /// The whole router over a cached in-memory database, with `s3cret` as
+    /// the admin token
+    fn cached_app(cache: Arc<dyn Cache>) -> Router {
+        let db = Arc::new(CachedDatabase::new(
+            InMemoryDatabase::new(),
+            cache,
+            CachePolicy::default(),
+        ));
+        let state = AppState::new(
+            VerbFacade::new(Arc::clone(&db)),
+            WebhookFacade::new(Arc::clone(&db)),
+            AdminFacade::new(Arc::clone(&db)),
+            AttachmentFacade::new(
+                Arc::clone(&db),
+                Arc::new(InMemoryBlobStore::new()),
+                AttachmentPolicy::default(),
+            ),
+            ReviewFacade::new(Arc::clone(&db)),
+            StatsFacade::new(Arc::clone(&db)),
+            NotificationPreferencesFacade::new(Arc::clone(&db)),
+            AuditFacade::new(Arc::clone(&db)),
+            SyncFacade::new(Arc::clone(&db), VerbFacade::new(db)),
+        );
+
+        app(
+            state,
+            &SharedLimits::new(ApiLimits::default()),
+            &AllowedOrigins::default(),
+            &HttpLogConfig::default(),
+            Some("s3cret"),
+        )
+    }
+
+    #[tokio::test]
+    async fn admin_cache_flush_clears_the_cache_for_admins_only() {
+        let cache = Arc::new(InMemoryCache::new());
+        cache
+            .set("verb:1", b"{}".to_vec(), Duration::from_secs(60))
+            .await
+            .unwrap();
+        let flush = |authorization: Option<&str>| {
+            let mut request = Request::post("/admin/cache/flush");
+            if let Some(value) = authorization {
+                request = request.header(header::AUTHORIZATION, value);
+            }
+            cached_app(cache.clone()).oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = flush(None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(cache.get("verb:1").await.unwrap().is_some());
+
+        let response = flush(Some("Bearer s3cret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(cache.get("verb:1").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,152 @@
+//! Request-scoped audit recording
+//!
+//! The `audit_requests` middleware knows who made a request and from
+//! where; only the application knows which entity it changed and what that
+//! entity looked like before and after. [`AuditRecorder`] bridges the two:
+//! the middleware scopes one over the request, facades report changes to
+//! it with [`record_change`], and the middleware turns them into
+//! [`AuditEntry`](crate::domain::model::AuditEntry)s once the request has
+//! been answered.
+//!
+//! Outside a scope (seeding, benchmarks, tests) recording is a no-op, and
+//! facades skip the extra lookups they'd need for it: check
+//! [`is_recording`] first.
+
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::model::EntityRef;
+
+tokio::task_local! {
+    static CURRENT: AuditRecorder;
+}
+
+/// Fields never written into a snapshot
+const REDACTED_FIELDS: &[&str] = &["secret"];
+const REDACTED: &str = "[REDACTED]";
+
+/// One entity changed by a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditChange {
+    pub entity: EntityRef,
+    /// `None` when the entity was created
+    pub before: Option<Value>,
+    /// `None` when the entity was deleted
+    pub after: Option<Value>,
+}
+
+/// Collects the changes made while handling one request
+#[derive(Debug, Clone, Default)]
+pub struct AuditRecorder {
+    changes: Arc<Mutex<Vec<AuditChange>>>,
+}
+
+impl AuditRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` with `self` collecting its changes
+    pub async fn scope<F: Future>(&self, f: F) -> F::Output {
+        CURRENT.scope(self.clone(), f).await
+    }
+
+    /// The changes recorded so far, in order
+    pub fn take(&self) -> Vec<AuditChange> {
+        std::mem::take(&mut *self.changes.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Whether changes made now would be recorded
+pub fn is_recording() -> bool {
+    CURRENT.try_with(|_| ()).is_ok()
+}
+
+/// Report that `entity` changed from `before` to `after`
+///
+/// Snapshots are taken with serde; secrets are redacted.
+pub fn record_change<B: Serialize, A: Serialize>(
+    entity: EntityRef,
+    before: Option<&B>,
+    after: Option<&A>,
+) {
+    let _ = CURRENT.try_with(|recorder| {
+        let change = AuditChange {
+            entity,
+            before: before.map(snapshot),
+            after: after.map(snapshot),
+        };
+        recorder
+            .changes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(change);
+    });
+}
+
+fn snapshot(value: &impl Serialize) -> Value {
+    let mut value = serde_json::to_value(value).unwrap_or(Value::Null);
+    redact(&mut value);
+    value
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if REDACTED_FIELDS.contains(&key.as_str()) {
+                    *value = Value::String(REDACTED.into());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn records_only_inside_a_scope() {
+        let entity = || EntityRef::new("feature", "webhooks");
+        record_change(entity(), Some(&true), Some(&false));
+        assert!(!is_recording());
+
+        let recorder = AuditRecorder::new();
+        recorder
+            .scope(async {
+                assert!(is_recording());
+                record_change(entity(), None::<&()>, Some(&json!({"on": true})));
+            })
+            .await;
+
+        let changes = recorder.take();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].before, None);
+        assert_eq!(changes[0].after, Some(json!({"on": true})));
+        assert!(recorder.take().is_empty());
+    }
+
+    #[tokio::test]
+    async fn secrets_are_redacted() {
+        let recorder = AuditRecorder::new();
+        recorder
+            .scope(async {
+                let hook = json!({"url": "https://example.com", "secret": "s3cret"});
+                record_change(EntityRef::new("webhook", 1), None::<&()>, Some(&hook));
+            })
+            .await;
+
+        let after = recorder.take().remove(0).after.unwrap();
+        assert_eq!(after["secret"], REDACTED);
+        assert_eq!(after["url"], "https://example.com");
+    }
+}
@@ -0,0 +1,30 @@
+//! File contents storage
+//!
+//! [`BlobStore`] is the port attachment use cases keep file bytes in;
+//! metadata stays in the repositories. Adapters (local disk, in-memory)
+//! live in `infra::blob`.
+
+use std::pin::Pin;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("Blob store error: {0}")]
+pub struct BlobError(pub String);
+
+pub type BlobFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, BlobError>> + Send + 'a>>;
+
+/// PORT: Opaque bytes under caller-chosen keys
+///
+/// Keys are generated by the application (attachment ids), never taken
+/// from clients.
+pub trait BlobStore: Send + Sync {
+    /// Store `data` under `key`, replacing any previous value
+    fn put<'a>(&'a self, key: &'a str, data: Vec<u8>) -> BlobFuture<'a, ()>;
+
+    /// The bytes stored under `key`, if any
+    fn get<'a>(&'a self, key: &'a str) -> BlobFuture<'a, Option<Vec<u8>>>;
+
+    /// Remove `key`; missing keys are not an error
+    fn delete<'a>(&'a self, key: &'a str) -> BlobFuture<'a, ()>;
+}
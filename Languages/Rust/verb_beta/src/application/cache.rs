@@ -0,0 +1,33 @@
+//! Short-lived copies of stored data
+//!
+//! [`Cache`] is the port repository decorators keep serialized entities
+//! in; the repositories stay the source of truth. Adapters (in-memory)
+//! live in `infra::cache`.
+
+use std::{pin::Pin, time::Duration};
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("Cache error: {0}")]
+pub struct CacheError(pub String);
+
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, CacheError>> + Send + 'a>>;
+
+/// PORT: Opaque bytes under caller-chosen keys, each kept for a while
+///
+/// Entries may disappear at any time before their TTL runs out; callers
+/// treat a missing entry as a miss, never as an error.
+pub trait Cache: Send + Sync {
+    /// The bytes stored under `key`, unless they expired
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>>;
+
+    /// Store `data` under `key` for `ttl`, replacing any previous value
+    fn set<'a>(&'a self, key: &'a str, data: Vec<u8>, ttl: Duration) -> CacheFuture<'a, ()>;
+
+    /// Remove `key`; missing keys are not an error
+    fn delete<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()>;
+
+    /// Remove every key
+    fn clear(&self) -> CacheFuture<'_, ()>;
+}
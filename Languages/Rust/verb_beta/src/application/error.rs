@@ -1,6 +1,9 @@
 use thiserror::Error;
 
-use crate::domain::DomainError;
+use crate::{
+    application::{blob_store::BlobError, notifier::NotifyError},
+    domain::DomainError,
+};
 
 /// Application-level errors wrap domain and infrastructure errors.
 ///
@@ -13,11 +16,41 @@ pub enum ApplicationError {
     #[error("Verb not found")]
     NotFound,
 
+    #[error("Webhook not found")]
+    WebhookNotFound,
+
     #[error("Database error: {0}")]
     Database(String),
 
     #[error("Transaction error: {0}")]
     Transaction(String),
+
+    #[error("Attachment not found")]
+    AttachmentNotFound,
+
+    #[error("Attachment exceeds the {limit} byte limit")]
+    AttachmentTooLarge { limit: u64 },
+
+    #[error("Attachments of type {0} are not accepted")]
+    UnsupportedAttachmentType(String),
+
+    #[error(transparent)]
+    Blob(#[from] BlobError),
+
+    #[error("This database backend does not support snapshots")]
+    SnapshotUnsupported,
+
+    #[error("Snapshot format {found} is not supported (expected {expected})")]
+    SnapshotFormat { found: u32, expected: u32 },
+
+    #[error("No notifier configured")]
+    NotifierUnavailable,
+
+    #[error("Not sent: the recipient's notification preferences don't allow it right now")]
+    NotificationSuppressed,
+
+    #[error(transparent)]
+    Notification(#[from] NotifyError),
 }
 
 impl ApplicationError {
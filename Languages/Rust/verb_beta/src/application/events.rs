@@ -0,0 +1,126 @@
+//! Application events
+//!
+//! Use cases publish [`VerbEvent`]s after their transaction commits.
+//! Subscribers (the webhook dispatcher) consume them from the other end of
+//! the channel, so a slow receiver never blocks a request. Each event keeps
+//! the trace context it was published in, so deliveries join that trace.
+//!
+//! Live subscribers (the SSE endpoint) get a copy of every event through a
+//! bounded broadcast channel instead: one that falls more than
+//! [`LIVE_CAPACITY`] events behind loses the oldest ones rather than
+//! holding them in memory.
+use time::OffsetDateTime;
+use tokio::sync::{broadcast, mpsc};
+use uuid::Uuid;
+
+use crate::{
+    application::TraceContext,
+    domain::model::{EventType, Verb},
+};
+
+/// Something that happened to a verb, as seen by outside subscribers.
+#[derive(Debug, Clone)]
+pub struct VerbEvent {
+    /// Unique per event; stays the same across delivery retries
+    pub id: Uuid,
+    pub event_type: EventType,
+    pub verb: Verb,
+    pub occurred_at: OffsetDateTime,
+    /// Trace of the request that caused the event, if any
+    pub trace: Option<TraceContext>,
+}
+
+impl VerbEvent {
+    pub fn new(event_type: EventType, verb: Verb) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            event_type,
+            verb,
+            occurred_at: OffsetDateTime::now_utc(),
+            trace: TraceContext::current(),
+        }
+    }
+}
+
+/// Events a live subscriber may fall behind by before losing the oldest
+pub const LIVE_CAPACITY: usize = 256;
+
+/// Receiving half of the event channel
+pub type EventReceiver = mpsc::UnboundedReceiver<VerbEvent>;
+
+/// A live subscription; see [`EventPublisher::subscribe`]
+pub type LiveEvents = broadcast::Receiver<VerbEvent>;
+
+/// Sending half of the event channel; cheap to clone.
+#[derive(Debug, Clone)]
+pub struct EventPublisher {
+    tx: mpsc::UnboundedSender<VerbEvent>,
+    live: broadcast::Sender<VerbEvent>,
+}
+
+impl EventPublisher {
+    /// Create a publisher and the receiver its events go to
+    pub fn channel() -> (Self, EventReceiver) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (live, _) = broadcast::channel(LIVE_CAPACITY);
+        (Self { tx, live }, rx)
+    }
+
+    /// Publish an event.
+    ///
+    /// Never fails the caller: if nobody is listening any more the event is
+    /// dropped with a warning.
+    pub fn publish(&self, event: VerbEvent) {
+        // No live subscribers is the normal case, not worth a warning
+        let _ = self.live.send(event.clone());
+        if let Err(e) = self.tx.send(event) {
+            tracing::warn!(event = %e.0.event_type, "No event subscriber, event dropped");
+        }
+    }
+
+    /// Receive every event published from now on
+    pub fn subscribe(&self) -> LiveEvents {
+        self.live.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use broadcast::error::TryRecvError;
+
+    use super::*;
+    use crate::domain::model::{Description, Title};
+
+    fn event() -> VerbEvent {
+        let verb = Verb::new(Title::new("Stream me").unwrap(), Description::default());
+        VerbEvent::new(EventType::VerbCreated, verb)
+    }
+
+    #[test]
+    fn live_subscribers_see_events_published_after_subscribing() {
+        let (publisher, mut rx) = EventPublisher::channel();
+        publisher.publish(event());
+        let mut live = publisher.subscribe();
+
+        let published = event();
+        publisher.publish(published.clone());
+
+        assert_eq!(live.try_recv().unwrap().id, published.id);
+        assert!(matches!(live.try_recv(), Err(TryRecvError::Empty)));
+        // The dispatcher still gets every event
+        assert!(rx.try_recv().is_ok() && rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn lagging_subscriber_loses_the_oldest_events() {
+        let (publisher, _rx) = EventPublisher::channel();
+        let mut live = publisher.subscribe();
+
+        for _ in 0..LIVE_CAPACITY + 3 {
+            publisher.publish(event());
+        }
+
+        assert!(matches!(live.try_recv(), Err(TryRecvError::Lagged(3))));
+        assert!(live.try_recv().is_ok());
+    }
+}
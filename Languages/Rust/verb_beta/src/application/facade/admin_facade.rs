@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use crate::{
+    application::ApplicationError,
+    infra::db::{Database, DatabaseError, DatabaseStats, Snapshot},
+};
+
+/// Facade: Operational queries for the admin API
+#[derive(Debug)]
+pub struct AdminFacade<D: Database> {
+    db: Arc<D>,
+}
+
+impl<D: Database> AdminFacade<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    /// Row counts of the backing database
+    pub async fn database_stats(&self) -> Result<DatabaseStats, ApplicationError> {
+        self.db.stats().await.map_err(ApplicationError::from_infra)
+    }
+
+    /// Drop every cached entity; lookups reach the database until they
+    /// fill the cache again
+    pub async fn flush_cache(&self) -> Result<(), ApplicationError> {
+        self.db
+            .flush_cache()
+            .await
+            .map_err(ApplicationError::from_infra)
+    }
+
+    /// Dump the whole database
+    pub async fn snapshot(&self) -> Result<Snapshot, ApplicationError> {
+        self.db.snapshot().await.map_err(snapshot_error)
+    }
+
+    /// Replace the whole database with `snapshot`
+    pub async fn restore_snapshot(&self, snapshot: Snapshot) -> Result<(), ApplicationError> {
+        if snapshot.format != Snapshot::FORMAT {
+            return Err(ApplicationError::SnapshotFormat {
+                found: snapshot.format,
+                expected: Snapshot::FORMAT,
+            });
+        }
+        self.db.restore(snapshot).await.map_err(snapshot_error)
+    }
+}
+
+fn snapshot_error(err: DatabaseError) -> ApplicationError {
+    match err {
+        DatabaseError::NotSupported(_) => ApplicationError::SnapshotUnsupported,
+        err => ApplicationError::from_infra(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::VerbFacade,
+        domain::{
+            model::VerbState, repository::VerbFilter, repository::action_log_repo::ActionLogFilter,
+        },
+        infra::repository::in_memory::InMemoryDatabase,
+    };
+
+    #[tokio::test]
+    async fn restore_reproduces_a_snapshot_through_json() {
+        let source = Arc::new(InMemoryDatabase::new());
+        let verbs = VerbFacade::new(Arc::clone(&source));
+        let verb = verbs
+            .create_verb("Reproduce me".into(), "bug #12".into())
+            .await
+            .unwrap();
+        verbs
+            .transition_verb(verb.id(), VerbState::Active, None)
+            .await
+            .unwrap();
+        let json =
+            serde_json::to_string(&AdminFacade::new(source).snapshot().await.unwrap()).unwrap();
+
+        let target = Arc::new(InMemoryDatabase::new());
+        let snapshot: Snapshot = serde_json::from_str(&json).unwrap();
+        AdminFacade::new(Arc::clone(&target))
+            .restore_snapshot(snapshot)
+            .await
+            .unwrap();
+
+        let verbs = VerbFacade::new(target);
+        let restored = verbs.get_verb(verb.id()).await.unwrap();
+        assert_eq!(restored.title(), "Reproduce me");
+        assert_eq!(restored.state(), VerbState::Active);
+        assert_eq!(restored.created_at(), verb.created_at());
+        let logs = verbs
+            .get_verb_action_logs(verb.id(), &ActionLogFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(logs.total, 2);
+        assert_eq!(
+            verbs.list_verbs(VerbFilter::default()).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_other_formats_and_invalid_values() {
+        let admin = AdminFacade::new(Arc::new(InMemoryDatabase::new()));
+        let mut snapshot = admin.snapshot().await.unwrap();
+        snapshot.format = Snapshot::FORMAT + 1;
+
+        let err = admin.restore_snapshot(snapshot).await.unwrap_err();
+        assert!(matches!(err, ApplicationError::SnapshotFormat { .. }));
+
+        // Value objects revalidate on the way in
+        let json = r#"{"format":1,"taken_at":"2026-01-01T00:00:00Z","verbs":[{
+            "id":"6f1c8a6e-9d5c-4a5e-9a57-2f1f0c7b8c11","title":"  ","description":"",
+            "context":null,"due_at":null,"state":"Captured",
+            "created_at":"2026-01-01T00:00:00Z","updated_at":"2026-01-01T00:00:00Z"}],
+            "action_logs":[],"webhooks":[],"webhook_deliveries":[],"attachments":[]}"#;
+        assert!(serde_json::from_str::<Snapshot>(json).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    application::{
+        ApplicationError, BlobStore, audit,
+        use_cases::{
+            AddAttachmentUseCase, AttachmentPolicy, GetAttachmentUseCase, ListAttachmentsUseCase,
+        },
+    },
+    domain::model::{Attachment, AttachmentId, EntityRef, VerbId},
+    infra::db::Database,
+};
+
+/// Facade: Files attached to verbs
+///
+/// Metadata goes through the database, contents through the [`BlobStore`].
+pub struct AttachmentFacade<D: Database> {
+    add_use_case: AddAttachmentUseCase<D>,
+    get_use_case: GetAttachmentUseCase<D>,
+    list_use_case: ListAttachmentsUseCase<D>,
+}
+
+impl<D: Database> AttachmentFacade<D> {
+    pub fn new(db: Arc<D>, blobs: Arc<dyn BlobStore>, policy: AttachmentPolicy) -> Self {
+        Self {
+            add_use_case: AddAttachmentUseCase::new(Arc::clone(&db), Arc::clone(&blobs), policy),
+            get_use_case: GetAttachmentUseCase::new(Arc::clone(&db), blobs),
+            list_use_case: ListAttachmentsUseCase::new(db),
+        }
+    }
+
+    /// Attach `data` to a verb
+    pub async fn add_attachment(
+        &self,
+        verb_id: VerbId,
+        filename: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<Attachment, ApplicationError> {
+        let attachment = self
+            .add_use_case
+            .execute(verb_id, filename, content_type, data)
+            .await?;
+        audit::record_change(
+            EntityRef::new("attachment", attachment.id()),
+            None::<&Attachment>,
+            Some(&attachment),
+        );
+        Ok(attachment)
+    }
+
+    /// An attachment and its contents
+    pub async fn get_attachment(
+        &self,
+        id: AttachmentId,
+    ) -> Result<(Attachment, Vec<u8>), ApplicationError> {
+        self.get_use_case.execute(id).await
+    }
+
+    /// Attachments of a verb, oldest first
+    pub async fn list_attachments(
+        &self,
+        verb_id: VerbId,
+    ) -> Result<Vec<Attachment>, ApplicationError> {
+        self.list_use_case.execute(verb_id).await
+    }
+}
+
+impl<D: Database> fmt::Debug for AttachmentFacade<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AttachmentFacade")
+            .field("policy", &self.add_use_case.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::VerbFacade,
+        domain::model::VerbState,
+        infra::{blob::InMemoryBlobStore, repository::in_memory::InMemoryDatabase},
+    };
+
+    fn facades() -> (
+        VerbFacade<InMemoryDatabase>,
+        AttachmentFacade<InMemoryDatabase>,
+    ) {
+        let db = Arc::new(InMemoryDatabase::new());
+        let attachments = AttachmentFacade::new(
+            Arc::clone(&db),
+            Arc::new(InMemoryBlobStore::new()),
+            AttachmentPolicy {
+                max_bytes: 8,
+                ..AttachmentPolicy::default()
+            },
+        );
+        (VerbFacade::new(db), attachments)
+    }
+
+    #[tokio::test]
+    async fn stores_and_serves_contents() {
+        let (verbs, attachments) = facades();
+        let verb = verbs
+            .create_verb("Scan".into(), String::new())
+            .await
+            .unwrap();
+
+        let added = attachments
+            .add_attachment(verb.id(), "scan.png", "image/png", b"\x89PNG".to_vec())
+            .await
+            .unwrap();
+        let (found, data) = attachments.get_attachment(added.id()).await.unwrap();
+
+        assert_eq!(found.filename(), "scan.png");
+        assert_eq!(data, b"\x89PNG");
+        assert_eq!(
+            attachments.list_attachments(verb.id()).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn enforces_policy() {
+        let (verbs, attachments) = facades();
+        let verb = verbs
+            .create_verb("Scan".into(), String::new())
+            .await
+            .unwrap();
+
+        let too_big = attachments
+            .add_attachment(verb.id(), "a.txt", "text/plain", vec![b'x'; 9])
+            .await;
+        let wrong_type = attachments
+            .add_attachment(verb.id(), "a.exe", "application/x-msdownload", vec![1])
+            .await;
+
+        assert!(matches!(
+            too_big,
+            Err(ApplicationError::AttachmentTooLarge { limit: 8 })
+        ));
+        assert!(matches!(
+            wrong_type,
+            Err(ApplicationError::UnsupportedAttachmentType(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn hides_attachments_of_dropped_verbs() {
+        let (verbs, attachments) = facades();
+        let verb = verbs
+            .create_verb("Scan".into(), String::new())
+            .await
+            .unwrap();
+        let added = attachments
+            .add_attachment(verb.id(), "a.txt", "text/plain", b"hi".to_vec())
+            .await
+            .unwrap();
+
+        verbs
+            .transition_verb(verb.id(), VerbState::Dropped, None)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            attachments.get_attachment(added.id()).await,
+            Err(ApplicationError::AttachmentNotFound)
+        ));
+        assert!(matches!(
+            attachments
+                .add_attachment(verb.id(), "b.txt", "text/plain", b"hi".to_vec())
+                .await,
+            Err(ApplicationError::NotFound)
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use crate::{
+    application::ApplicationError,
+    domain::{
+        model::AuditEntry,
+        repository::{AuditFilter, AuditListResult},
+    },
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Facade: The audit log of mutating requests
+///
+/// Entries are written by the `audit_requests` middleware; see
+/// [`crate::application::audit`] for how changes reach them.
+#[derive(Debug)]
+pub struct AuditFacade<D: Database> {
+    db: Arc<D>,
+}
+
+impl<D: Database> AuditFacade<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    /// Append entries, in one transaction
+    pub async fn record(&self, entries: &[AuditEntry]) -> Result<(), ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let repo = tx.audit_repository();
+        for entry in entries {
+            repo.append(entry).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))
+    }
+
+    /// Entries matching `filter`, newest first
+    pub async fn list(&self, filter: &AuditFilter) -> Result<AuditListResult, ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        tx.audit_repository().list(filter).await
+    }
+}
+
+impl<D: Database> Clone for AuditFacade<D> {
+    fn clone(&self) -> Self {
+        Self::new(Arc::clone(&self.db))
+    }
+}
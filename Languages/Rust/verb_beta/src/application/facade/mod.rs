@@ -1,3 +1,23 @@
+mod admin_facade;
+mod attachment_facade;
+mod audit_facade;
+mod notification_preferences_facade;
+mod review_facade;
+mod stats_facade;
+mod sync_facade;
 mod verb_facade;
+mod verb_use_case;
+mod webhook_facade;
 
+pub use admin_facade::AdminFacade;
+pub use attachment_facade::AttachmentFacade;
+pub use audit_facade::AuditFacade;
+pub use notification_preferences_facade::NotificationPreferencesFacade;
+pub use review_facade::ReviewFacade;
+pub use stats_facade::StatsFacade;
+pub use sync_facade::SyncFacade;
 pub use verb_facade::VerbFacade;
+pub use verb_use_case::VerbUseCase;
+#[cfg(test)]
+pub use verb_use_case::mock;
+pub use webhook_facade::WebhookFacade;
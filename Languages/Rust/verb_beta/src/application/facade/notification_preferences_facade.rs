@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{ApplicationError, audit, use_cases::GetNotificationPreferencesUseCase},
+    domain::model::{EntityRef, NotificationPreferences, UserId},
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Facade: Per-user notification preferences
+///
+/// Enforcement happens where notifications are sent: the webhook
+/// dispatcher and [`ReviewFacade`](super::ReviewFacade).
+#[derive(Debug)]
+pub struct NotificationPreferencesFacade<D: Database> {
+    get_use_case: GetNotificationPreferencesUseCase<D>,
+}
+
+impl<D: Database> NotificationPreferencesFacade<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self {
+            get_use_case: GetNotificationPreferencesUseCase::new(db),
+        }
+    }
+
+    /// A user's preferences, or the defaults if none are saved
+    pub async fn get(&self, user_id: UserId) -> Result<NotificationPreferences, ApplicationError> {
+        self.get_use_case.execute(user_id).await
+    }
+
+    /// Replace a user's preferences
+    pub async fn save(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Result<(), ApplicationError> {
+        let tx = self
+            .get_use_case
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let repo = tx.notification_preferences_repository();
+        let before = if audit::is_recording() {
+            repo.find_by_user(preferences.user_id()).await?
+        } else {
+            None
+        };
+        repo.save(preferences).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+        audit::record_change(
+            entity(preferences.user_id()),
+            before.as_ref(),
+            Some(preferences),
+        );
+        Ok(())
+    }
+
+    /// Forget a user's preferences, going back to the defaults
+    ///
+    /// Idempotent: resetting a user without saved preferences succeeds.
+    pub async fn reset(&self, user_id: UserId) -> Result<(), ApplicationError> {
+        let tx = self
+            .get_use_case
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let repo = tx.notification_preferences_repository();
+        let before = if audit::is_recording() {
+            repo.find_by_user(user_id).await?
+        } else {
+            None
+        };
+        repo.delete(user_id).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+        // Nothing changed when there was nothing saved
+        if let Some(before) = &before {
+            audit::record_change(
+                entity(user_id),
+                Some(before),
+                None::<&NotificationPreferences>,
+            );
+        }
+        Ok(())
+    }
+}
+
+fn entity(user_id: UserId) -> EntityRef {
+    EntityRef::new("notification_preferences", user_id)
+}
+
+impl<D: Database> Clone for NotificationPreferencesFacade<D> {
+    fn clone(&self) -> Self {
+        Self::new(Arc::clone(&self.get_use_case.db))
+    }
+}
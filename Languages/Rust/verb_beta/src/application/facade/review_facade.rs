@@ -0,0 +1,159 @@
+use std::{fmt, sync::Arc};
+
+use time::OffsetDateTime;
+
+use crate::{
+    application::{
+        ApplicationError, Notifier,
+        use_cases::{
+            DailyReview, DailyReviewUseCase, GetNotificationPreferencesUseCase, ReviewPolicy,
+        },
+    },
+    domain::model::{Channel, NotificationTopic, UserId},
+    infra::db::Database,
+};
+
+/// Facade: Daily review digest, on demand or sent through a [`Notifier`]
+///
+/// With a recipient set, sending follows that user's notification
+/// preferences for the email channel.
+pub struct ReviewFacade<D: Database> {
+    daily_review_use_case: DailyReviewUseCase<D>,
+    preferences_use_case: GetNotificationPreferencesUseCase<D>,
+    policy: ReviewPolicy,
+    notifier: Option<Arc<dyn Notifier>>,
+    recipient: Option<UserId>,
+}
+
+impl<D: Database> ReviewFacade<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self {
+            daily_review_use_case: DailyReviewUseCase::new(Arc::clone(&db)),
+            preferences_use_case: GetNotificationPreferencesUseCase::new(db),
+            policy: ReviewPolicy::default(),
+            notifier: None,
+            recipient: None,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: ReviewPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Send digests through `notifier`
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Send digests on behalf of `recipient`, honouring their preferences
+    pub fn with_recipient(mut self, recipient: UserId) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    /// Today's review
+    pub async fn daily_review(&self) -> Result<DailyReview, ApplicationError> {
+        self.daily_review_use_case
+            .execute(OffsetDateTime::now_utc(), self.policy)
+            .await
+    }
+
+    /// Today's review, also sent through the notifier
+    ///
+    /// Fails with `NotificationSuppressed` when the recipient switched
+    /// email or the digest off, or is in their quiet hours.
+    pub async fn send_daily_review(&self) -> Result<DailyReview, ApplicationError> {
+        let notifier = self
+            .notifier
+            .as_ref()
+            .ok_or(ApplicationError::NotifierUnavailable)?;
+
+        if let Some(recipient) = self.recipient {
+            let preferences = self.preferences_use_case.execute(recipient).await?;
+            if !preferences.allows(
+                Channel::Email,
+                NotificationTopic::DailyReview,
+                OffsetDateTime::now_utc(),
+            ) {
+                tracing::info!(user_id = %recipient, "Daily review suppressed by preferences");
+                return Err(ApplicationError::NotificationSuppressed);
+            }
+        }
+
+        let review = self.daily_review().await?;
+        notifier.notify(&review.to_notification()).await?;
+
+        tracing::info!(date = %review.date, "Daily review sent");
+        Ok(review)
+    }
+}
+
+impl<D: Database> fmt::Debug for ReviewFacade<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReviewFacade")
+            .field("policy", &self.policy)
+            .field("notifier", &self.notifier.is_some())
+            .field("recipient", &self.recipient)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use super::*;
+    use crate::{
+        application::{Notification, NotifyError},
+        domain::model::NotificationPreferences,
+        infra::{db::DatabaseTransaction, repository::in_memory::InMemoryDatabase},
+    };
+
+    #[derive(Default)]
+    struct CountingNotifier(AtomicU32);
+
+    impl Notifier for CountingNotifier {
+        fn notify<'a>(
+            &'a self,
+            _notification: &'a Notification,
+        ) -> Pin<Box<dyn Future<Output = Result<(), NotifyError>> + Send + 'a>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn send_follows_recipient_preferences() {
+        let db = Arc::new(InMemoryDatabase::new());
+        let notifier = Arc::new(CountingNotifier::default());
+        let user = UserId::from_uuid(uuid::Uuid::new_v4());
+        let facade = ReviewFacade::new(Arc::clone(&db))
+            .with_notifier(Arc::clone(&notifier) as Arc<dyn Notifier>)
+            .with_recipient(user);
+
+        // No saved preferences: everything is on
+        facade.send_daily_review().await.unwrap();
+        assert_eq!(notifier.0.load(Ordering::SeqCst), 1);
+
+        let mut preferences = NotificationPreferences::defaults(user);
+        preferences.set_topic(NotificationTopic::DailyReview, false);
+        {
+            let tx = db.begin_tx().await.unwrap();
+            tx.notification_preferences_repository()
+                .save(&preferences)
+                .await
+                .unwrap();
+        }
+
+        assert!(matches!(
+            facade.send_daily_review().await,
+            Err(ApplicationError::NotificationSuppressed)
+        ));
+        assert_eq!(notifier.0.load(Ordering::SeqCst), 1, "nothing sent");
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use time::OffsetDateTime;
+
+use crate::{
+    application::{
+        ApplicationError,
+        use_cases::{Burndown, BurndownUseCase},
+    },
+    infra::db::Database,
+};
+
+/// Facade: Statistics computed from the action log
+#[derive(Debug)]
+pub struct StatsFacade<D: Database> {
+    burndown_use_case: BurndownUseCase<D>,
+}
+
+impl<D: Database> StatsFacade<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self {
+            burndown_use_case: BurndownUseCase::new(db),
+        }
+    }
+
+    /// Burn-down over the last `window_days` days, up to today
+    pub async fn burndown(&self, window_days: u16) -> Result<Burndown, ApplicationError> {
+        self.burndown_use_case
+            .execute(OffsetDateTime::now_utc(), window_days)
+            .await
+    }
+}
@@ -0,0 +1,322 @@
+use std::{collections::HashMap, sync::Arc};
+
+use time::OffsetDateTime;
+
+use crate::{
+    application::{
+        ApplicationError, VerbFacade,
+        use_cases::{SyncChanges, SyncMutation, SyncOp, SyncOutcome, SyncStatus, SyncUseCase},
+    },
+    domain::model::{ActionLog, Verb, VerbId},
+    infra::db::Database,
+};
+
+/// Facade: Offline-first sync
+///
+/// Clients pull the action log since their cursor, and push the
+/// mutations they made offline as one batch. Mutations go through
+/// [`VerbFacade`], so they are audited and publish events like any
+/// other change, each in its own transaction: one rejected mutation
+/// doesn't undo the rest.
+///
+/// Conflicts are last-writer-wins on the client's `at` against the
+/// latest server change appended after the batch's cursor; either way a
+/// `SyncConflict` log records what was overwritten or discarded.
+#[derive(Debug)]
+pub struct SyncFacade<D: Database> {
+    sync_use_case: SyncUseCase<D>,
+    verbs: VerbFacade<D>,
+}
+
+impl<D: Database> SyncFacade<D> {
+    pub fn new(db: Arc<D>, verbs: VerbFacade<D>) -> Self {
+        Self {
+            sync_use_case: SyncUseCase::new(db),
+            verbs,
+        }
+    }
+
+    /// Up to `limit` changes appended after `cursor`
+    pub async fn pull(&self, cursor: u64, limit: u32) -> Result<SyncChanges, ApplicationError> {
+        self.sync_use_case.pull(cursor, limit).await
+    }
+
+    /// Apply a client's offline mutations, in order
+    ///
+    /// `since` is the cursor the client last pulled: server changes
+    /// appended after it are the ones the client couldn't see.
+    pub async fn push(
+        &self,
+        since: u64,
+        mutations: Vec<SyncMutation>,
+    ) -> Result<Vec<SyncOutcome>, ApplicationError> {
+        let server_changes = self.sync_use_case.changed_since(since).await?;
+
+        let mut outcomes = Vec::with_capacity(mutations.len());
+        for mutation in mutations {
+            let mutation_id = mutation.id;
+            let verb_id = mutation.verb_id;
+            let (status, verb) = match self.apply(mutation, &server_changes).await {
+                Ok((status, verb)) => (status, Some(verb)),
+                Err(e) => (SyncStatus::Rejected(e), None),
+            };
+            outcomes.push(SyncOutcome {
+                mutation_id,
+                verb_id,
+                status,
+                verb,
+            });
+        }
+        Ok(outcomes)
+    }
+
+    async fn apply(
+        &self,
+        mutation: SyncMutation,
+        server_changes: &HashMap<VerbId, OffsetDateTime>,
+    ) -> Result<(SyncStatus, Verb), ApplicationError> {
+        let current = match self.verbs.get_verb(mutation.verb_id).await {
+            Ok(verb) => Some(verb),
+            Err(ApplicationError::NotFound) => None,
+            Err(e) => return Err(e),
+        };
+
+        let verb = match (&mutation.op, current) {
+            (SyncOp::Create { .. }, Some(verb)) => return Ok((SyncStatus::Duplicate, verb)),
+            (SyncOp::Create { title, description }, None) => {
+                let verb = self
+                    .verbs
+                    .create_verb_with_id(mutation.verb_id, title.clone(), description.clone())
+                    .await?;
+                return Ok((SyncStatus::Applied, verb));
+            }
+            (_, None) => return Err(ApplicationError::NotFound),
+            (SyncOp::Transition { state, .. }, Some(verb)) if verb.state() == *state => {
+                return Ok((SyncStatus::Duplicate, verb));
+            }
+            (_, Some(verb)) => verb,
+        };
+
+        let Some(&server_at) = server_changes.get(&mutation.verb_id) else {
+            let verb = self.execute(mutation.verb_id, mutation.op).await?;
+            return Ok((SyncStatus::Applied, verb));
+        };
+
+        let client_won = mutation.at >= server_at;
+        let fields = mutation.op.field_names();
+        let (verb, outcome) = if client_won {
+            (
+                self.execute(mutation.verb_id, mutation.op).await?,
+                "overwrote",
+            )
+        } else {
+            (verb, "lost to")
+        };
+
+        let reason = format!(
+            "Sync mutation {} made at {} {outcome} a server change made at {server_at}",
+            mutation.id, mutation.at
+        );
+        self.sync_use_case
+            .record_conflict(&ActionLog::sync_conflict(
+                verb.id(),
+                verb.state(),
+                fields,
+                reason,
+            ))
+            .await?;
+
+        Ok((SyncStatus::Conflict { client_won }, verb))
+    }
+
+    async fn execute(&self, verb_id: VerbId, op: SyncOp) -> Result<Verb, ApplicationError> {
+        match op {
+            SyncOp::Create { .. } => unreachable!("creates never reach an existing verb"),
+            SyncOp::Update(changes) => self.verbs.update_verb(verb_id, changes).await,
+            SyncOp::Transition { state, reason } => {
+                self.verbs.transition_verb(verb_id, state, reason).await
+            }
+        }
+    }
+}
+
+impl<D: Database> Clone for SyncFacade<D> {
+    fn clone(&self) -> Self {
+        Self {
+            sync_use_case: SyncUseCase::new(Arc::clone(&self.sync_use_case.db)),
+            verbs: self.verbs.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Duration;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        application::use_cases::VerbChanges,
+        domain::model::{ActionType, VerbState},
+        infra::repository::in_memory::InMemoryDatabase,
+    };
+
+    fn facade() -> SyncFacade<InMemoryDatabase> {
+        let db = Arc::new(InMemoryDatabase::new());
+        SyncFacade::new(Arc::clone(&db), VerbFacade::new(db))
+    }
+
+    fn mutation(verb_id: VerbId, at: OffsetDateTime, op: SyncOp) -> SyncMutation {
+        SyncMutation {
+            id: Uuid::new_v4(),
+            verb_id,
+            at,
+            op,
+        }
+    }
+
+    fn retitle(title: &str) -> SyncOp {
+        SyncOp::Update(VerbChanges {
+            title: Some(title.to_string()),
+            ..VerbChanges::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn pull_returns_changes_once_per_cursor() {
+        let sync = facade();
+        let verb = sync
+            .verbs
+            .create_verb("Write".into(), String::new())
+            .await
+            .unwrap();
+        sync.verbs
+            .transition_verb(verb.id(), VerbState::Active, None)
+            .await
+            .unwrap();
+
+        let changes = sync.pull(0, 100).await.unwrap();
+        assert_eq!(changes.logs.len(), 2);
+        assert_eq!(changes.verbs.len(), 1, "one entry per changed verb");
+        assert_eq!(changes.verbs[0].state(), VerbState::Active);
+        assert!(!changes.has_more);
+
+        let again = sync.pull(changes.cursor, 100).await.unwrap();
+        assert!(again.logs.is_empty() && again.verbs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn creates_use_the_client_id_and_replays_are_duplicates() {
+        let sync = facade();
+        let verb_id = VerbId::new();
+        let create = mutation(
+            verb_id,
+            OffsetDateTime::now_utc(),
+            SyncOp::Create {
+                title: "Offline".into(),
+                description: String::new(),
+            },
+        );
+
+        let first = sync.push(0, vec![create.clone()]).await.unwrap();
+        assert!(matches!(first[0].status, SyncStatus::Applied));
+        assert_eq!(
+            sync.verbs.get_verb(verb_id).await.unwrap().title(),
+            "Offline"
+        );
+
+        let replay = sync.push(0, vec![create]).await.unwrap();
+        assert!(matches!(replay[0].status, SyncStatus::Duplicate));
+    }
+
+    #[tokio::test]
+    async fn unknown_verbs_are_rejected_without_stopping_the_batch() {
+        let sync = facade();
+        let verb = sync
+            .verbs
+            .create_verb("Known".into(), String::new())
+            .await
+            .unwrap();
+        let cursor = sync.pull(0, 100).await.unwrap().cursor;
+        let now = OffsetDateTime::now_utc();
+
+        let outcomes = sync
+            .push(
+                cursor,
+                vec![
+                    mutation(VerbId::new(), now, retitle("Lost")),
+                    mutation(verb.id(), now, retitle("Renamed")),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            outcomes[0].status,
+            SyncStatus::Rejected(ApplicationError::NotFound)
+        ));
+        assert!(outcomes[0].verb.is_none());
+        assert!(matches!(outcomes[1].status, SyncStatus::Applied));
+        assert_eq!(outcomes[1].verb.as_ref().unwrap().title(), "Renamed");
+    }
+
+    #[tokio::test]
+    async fn later_writer_wins_and_the_conflict_is_logged() {
+        let sync = facade();
+        let verb = sync
+            .verbs
+            .create_verb("Original".into(), String::new())
+            .await
+            .unwrap();
+        let cursor = sync.pull(0, 100).await.unwrap().cursor;
+
+        // Made offline before the server change below
+        let stale = mutation(
+            verb.id(),
+            OffsetDateTime::now_utc() - Duration::minutes(5),
+            retitle("Stale"),
+        );
+        sync.verbs
+            .update_verb(
+                verb.id(),
+                VerbChanges {
+                    title: Some("Server".into()),
+                    ..VerbChanges::default()
+                },
+            )
+            .await
+            .unwrap();
+        let fresh = mutation(
+            verb.id(),
+            OffsetDateTime::now_utc() + Duration::minutes(5),
+            retitle("Fresh"),
+        );
+
+        let outcomes = sync.push(cursor, vec![stale]).await.unwrap();
+        assert!(matches!(
+            outcomes[0].status,
+            SyncStatus::Conflict { client_won: false }
+        ));
+        assert_eq!(outcomes[0].verb.as_ref().unwrap().title(), "Server");
+
+        let outcomes = sync.push(cursor, vec![fresh]).await.unwrap();
+        assert!(matches!(
+            outcomes[0].status,
+            SyncStatus::Conflict { client_won: true }
+        ));
+        assert_eq!(outcomes[0].verb.as_ref().unwrap().title(), "Fresh");
+
+        let conflicts: Vec<_> = sync
+            .pull(cursor, 100)
+            .await
+            .unwrap()
+            .logs
+            .into_iter()
+            .filter(|l| l.action_type() == ActionType::SyncConflict)
+            .collect();
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].changed_fields(), ["title"]);
+        assert!(conflicts[0].reason().unwrap().contains("lost to"));
+        assert!(conflicts[1].reason().unwrap().contains("overwrote"));
+    }
+}
@@ -1,14 +1,17 @@
 use std::sync::Arc;
 
+use futures_util::{Stream, StreamExt, stream};
+
 use crate::{
     application::{
-        ApplicationError,
+        ApplicationError, EventPublisher, LiveEvents, VerbEvent, audit,
         use_cases::{
             CreateVerbUseCase, GetVerbActionLogs, ListVerbsUseCase, TransitionVerbUseCase,
+            UpdateVerbUseCase, VerbChanges,
         },
     },
     domain::{
-        model::{Verb, VerbId, VerbState},
+        model::{EntityRef, EventType, Verb, VerbId, VerbState},
         repository::{
             action_log_repo::{ActionLogFilter, ActionLogListResult},
             verb_repo::VerbFilter,
@@ -17,6 +20,9 @@ use crate::{
     infra::db::{Database, DatabaseTransaction},
 };
 
+/// Verbs fetched per repository round-trip by [`VerbFacade::stream_verbs`]
+const STREAM_PAGE_SIZE: u32 = 100;
+
 /// Facade: Single entry point for all verb operations
 ///
 /// The facade pattern provides a simplified interface over use cases.
@@ -30,8 +36,10 @@ use crate::{
 pub struct VerbFacade<D: Database> {
     create_use_case: CreateVerbUseCase<D>,
     transition_use_case: TransitionVerbUseCase<D>,
+    update_use_case: UpdateVerbUseCase<D>,
     list_use_case: ListVerbsUseCase<D>,
     list_verb_logs_use_case: GetVerbActionLogs<D>,
+    events: Option<EventPublisher>,
 }
 
 impl<D: Database> VerbFacade<D> {
@@ -40,18 +48,63 @@ impl<D: Database> VerbFacade<D> {
         Self {
             create_use_case: CreateVerbUseCase::new(Arc::clone(&db)),
             transition_use_case: TransitionVerbUseCase::new(Arc::clone(&db)),
+            update_use_case: UpdateVerbUseCase::new(Arc::clone(&db)),
             list_use_case: ListVerbsUseCase::new(Arc::clone(&db)),
             list_verb_logs_use_case: GetVerbActionLogs::new(Arc::clone(&db)),
+            events: None,
         }
     }
 
+    /// Publish verb events (created, done) to `publisher` after each commit
+    pub fn with_events(mut self, publisher: EventPublisher) -> Self {
+        self.events = Some(publisher);
+        self
+    }
+
+    fn publish(&self, event_type: EventType, verb: &Verb) {
+        if let Some(events) = &self.events {
+            events.publish(VerbEvent::new(event_type, verb.clone()));
+        }
+    }
+
+    /// The verb as it is now, if the change about to be made is audited
+    async fn audit_before(&self, verb_id: VerbId) -> Option<Verb> {
+        if !audit::is_recording() {
+            return None;
+        }
+        self.get_verb(verb_id).await.ok()
+    }
+
+    fn audit(before: Option<&Verb>, after: &Verb) {
+        audit::record_change(EntityRef::new("verb", after.id()), before, Some(after));
+    }
+
     /// Create a new verb
     pub async fn create_verb(
         &self,
         title: String,
         description: String,
     ) -> Result<Verb, ApplicationError> {
-        self.create_use_case.execute(title, description).await
+        let verb = self.create_use_case.execute(title, description).await?;
+        Self::audit(None, &verb);
+        self.publish(EventType::VerbCreated, &verb);
+        Ok(verb)
+    }
+
+    /// Create a new verb under a client-generated id (offline sync)
+    pub async fn create_verb_with_id(
+        &self,
+        verb_id: VerbId,
+        title: String,
+        description: String,
+    ) -> Result<Verb, ApplicationError> {
+        let verb = self
+            .create_use_case
+            .execute_with_id(verb_id, title, description)
+            .await?;
+        Self::audit(None, &verb);
+        self.publish(EventType::VerbCreated, &verb);
+        Ok(verb)
     }
 
     /// Transition verb state
@@ -61,9 +114,28 @@ impl<D: Database> VerbFacade<D> {
         next_state: VerbState,
         reason: Option<String>,
     ) -> Result<Verb, ApplicationError> {
-        self.transition_use_case
+        let before = self.audit_before(verb_id).await;
+        let verb = self
+            .transition_use_case
             .execute(verb_id, next_state, reason)
-            .await
+            .await?;
+        Self::audit(before.as_ref(), &verb);
+        if verb.state() == VerbState::Done {
+            self.publish(EventType::VerbDone, &verb);
+        }
+        Ok(verb)
+    }
+
+    /// Edit verb fields (merge-patch semantics, see `VerbChanges`)
+    pub async fn update_verb(
+        &self,
+        verb_id: VerbId,
+        changes: VerbChanges,
+    ) -> Result<Verb, ApplicationError> {
+        let before = self.audit_before(verb_id).await;
+        let verb = self.update_use_case.execute(verb_id, changes).await?;
+        Self::audit(before.as_ref(), &verb);
+        Ok(verb)
     }
 
     /// List verbs with filtering
@@ -72,6 +144,47 @@ impl<D: Database> VerbFacade<D> {
         Ok(result.verbs)
     }
 
+    /// Every verb in `state` (or all of them), newest updated first
+    ///
+    /// Pages through the repository one page at a time, and only fetches
+    /// the next page once the consumer has taken the previous one, so a
+    /// slow reader holds at most a page in memory. Not a snapshot: verbs
+    /// updated while streaming may be skipped or repeated. Ends after the
+    /// first error.
+    pub fn stream_verbs(
+        &self,
+        state: Option<VerbState>,
+    ) -> impl Stream<Item = Result<Verb, ApplicationError>> + Send + 'static {
+        let use_case = ListVerbsUseCase::new(Arc::clone(&self.list_use_case.db));
+
+        // Some(offset) while there may be more pages
+        stream::unfold(Some(0), move |offset| {
+            let use_case = use_case.clone();
+            async move {
+                let offset = offset?;
+                let filter = VerbFilter {
+                    state,
+                    limit: STREAM_PAGE_SIZE,
+                    offset,
+                };
+                match use_case.execute(filter).await {
+                    Ok(page) => {
+                        let fetched = page.verbs.len() as u32;
+                        let next = (fetched == STREAM_PAGE_SIZE).then_some(offset + fetched);
+                        Some((page.verbs.into_iter().map(Ok).collect::<Vec<_>>(), next))
+                    }
+                    Err(e) => Some((vec![Err(e)], None)),
+                }
+            }
+        })
+        .flat_map(stream::iter)
+    }
+
+    /// Events published from now on, if events are enabled
+    pub fn subscribe(&self) -> Option<LiveEvents> {
+        self.events.as_ref().map(EventPublisher::subscribe)
+    }
+
     /// Get a single verb by ID
     pub async fn get_verb(&self, verb_id: VerbId) -> Result<Verb, ApplicationError> {
         let tx = self
@@ -113,10 +226,40 @@ impl<D: Database> Clone for VerbFacade<D> {
             transition_use_case: TransitionVerbUseCase::new(Arc::clone(
                 &self.transition_use_case.db,
             )),
+            update_use_case: UpdateVerbUseCase::new(Arc::clone(&self.update_use_case.db)),
             list_use_case: ListVerbsUseCase::new(Arc::clone(&self.list_use_case.db)),
             list_verb_logs_use_case: GetVerbActionLogs::new(Arc::clone(
                 &self.list_verb_logs_use_case.db,
             )),
+            events: self.events.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use futures_util::TryStreamExt;
+
+    use super::*;
+    use crate::infra::repository::in_memory::InMemoryDatabase;
+
+    #[tokio::test]
+    async fn stream_verbs_reads_every_page() {
+        let facade = VerbFacade::new(Arc::new(InMemoryDatabase::new()));
+        let count = STREAM_PAGE_SIZE as usize * 2 + 3;
+        for i in 0..count {
+            facade
+                .create_verb(format!("Verb {i}"), String::new())
+                .await
+                .unwrap();
+        }
+
+        let streamed: Vec<Verb> = facade.stream_verbs(None).try_collect().await.unwrap();
+        assert_eq!(streamed.len(), count);
+
+        let ids: HashSet<_> = streamed.iter().map(Verb::id).collect();
+        assert_eq!(ids.len(), count, "no verb repeated across pages");
+    }
+}
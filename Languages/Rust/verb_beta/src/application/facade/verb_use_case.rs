@@ -0,0 +1,233 @@
+use async_trait::async_trait;
+use futures_util::{StreamExt, stream::BoxStream};
+
+use crate::{
+    application::{ApplicationError, LiveEvents, VerbFacade, use_cases::VerbChanges},
+    domain::{
+        model::{Verb, VerbId, VerbState},
+        repository::{
+            action_log_repo::{ActionLogFilter, ActionLogListResult},
+            verb_repo::VerbFilter,
+        },
+    },
+    infra::db::Database,
+};
+
+/// PORT: Verb operations as the HTTP layer sees them
+///
+/// Handlers hold an `Arc<dyn VerbUseCase>` rather than a `VerbFacade<D>`,
+/// so their tests can substitute [`mock::MockVerbUseCase`] and check how
+/// each application error maps to a response without a database.
+#[async_trait]
+pub trait VerbUseCase: Send + Sync {
+    /// Create a new verb
+    async fn create_verb(
+        &self,
+        title: String,
+        description: String,
+    ) -> Result<Verb, ApplicationError>;
+
+    /// Transition verb state
+    async fn transition_verb(
+        &self,
+        verb_id: VerbId,
+        next_state: VerbState,
+        reason: Option<String>,
+    ) -> Result<Verb, ApplicationError>;
+
+    /// Edit verb fields (merge-patch semantics, see `VerbChanges`)
+    async fn update_verb(
+        &self,
+        verb_id: VerbId,
+        changes: VerbChanges,
+    ) -> Result<Verb, ApplicationError>;
+
+    /// List verbs with filtering
+    async fn list_verbs(&self, filter: VerbFilter) -> Result<Vec<Verb>, ApplicationError>;
+
+    /// Every verb in `state` (or all of them), see [`VerbFacade::stream_verbs`]
+    fn stream_verbs(
+        &self,
+        state: Option<VerbState>,
+    ) -> BoxStream<'static, Result<Verb, ApplicationError>>;
+
+    /// Events published from now on, if events are enabled
+    fn subscribe(&self) -> Option<LiveEvents>;
+
+    /// Get a single verb by ID
+    async fn get_verb(&self, verb_id: VerbId) -> Result<Verb, ApplicationError>;
+
+    /// Get action logs for a single verb
+    async fn get_verb_action_logs(
+        &self,
+        verb_id: VerbId,
+        filter: &ActionLogFilter,
+    ) -> Result<ActionLogListResult, ApplicationError>;
+}
+
+#[async_trait]
+impl<D: Database> VerbUseCase for VerbFacade<D> {
+    async fn create_verb(
+        &self,
+        title: String,
+        description: String,
+    ) -> Result<Verb, ApplicationError> {
+        VerbFacade::create_verb(self, title, description).await
+    }
+
+    async fn transition_verb(
+        &self,
+        verb_id: VerbId,
+        next_state: VerbState,
+        reason: Option<String>,
+    ) -> Result<Verb, ApplicationError> {
+        VerbFacade::transition_verb(self, verb_id, next_state, reason).await
+    }
+
+    async fn update_verb(
+        &self,
+        verb_id: VerbId,
+        changes: VerbChanges,
+    ) -> Result<Verb, ApplicationError> {
+        VerbFacade::update_verb(self, verb_id, changes).await
+    }
+
+    async fn list_verbs(&self, filter: VerbFilter) -> Result<Vec<Verb>, ApplicationError> {
+        VerbFacade::list_verbs(self, filter).await
+    }
+
+    fn stream_verbs(
+        &self,
+        state: Option<VerbState>,
+    ) -> BoxStream<'static, Result<Verb, ApplicationError>> {
+        VerbFacade::stream_verbs(self, state).boxed()
+    }
+
+    fn subscribe(&self) -> Option<LiveEvents> {
+        VerbFacade::subscribe(self)
+    }
+
+    async fn get_verb(&self, verb_id: VerbId) -> Result<Verb, ApplicationError> {
+        VerbFacade::get_verb(self, verb_id).await
+    }
+
+    async fn get_verb_action_logs(
+        &self,
+        verb_id: VerbId,
+        filter: &ActionLogFilter,
+    ) -> Result<ActionLogListResult, ApplicationError> {
+        VerbFacade::get_verb_action_logs(self, verb_id, filter).await
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::sync::Mutex;
+
+    use futures_util::stream;
+
+    use super::*;
+
+    enum Outcome {
+        Verb(Verb),
+        Fail(fn() -> ApplicationError),
+    }
+
+    /// A [`VerbUseCase`] that answers every call the same way
+    ///
+    /// Either with one verb (lists and streams hold just that verb) or with
+    /// a fresh error from `fail`. Records the name of each method called.
+    pub struct MockVerbUseCase {
+        outcome: Outcome,
+        calls: Mutex<Vec<&'static str>>,
+    }
+
+    impl MockVerbUseCase {
+        pub fn returning(verb: Verb) -> Self {
+            Self {
+                outcome: Outcome::Verb(verb),
+                calls: Mutex::default(),
+            }
+        }
+
+        pub fn failing(fail: fn() -> ApplicationError) -> Self {
+            Self {
+                outcome: Outcome::Fail(fail),
+                calls: Mutex::default(),
+            }
+        }
+
+        /// Methods called so far, in order
+        pub fn calls(&self) -> Vec<&'static str> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        fn answer(&self, method: &'static str) -> Result<Verb, ApplicationError> {
+            self.calls.lock().unwrap().push(method);
+            match &self.outcome {
+                Outcome::Verb(verb) => Ok(verb.clone()),
+                Outcome::Fail(fail) => Err(fail()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl VerbUseCase for MockVerbUseCase {
+        async fn create_verb(
+            &self,
+            _title: String,
+            _description: String,
+        ) -> Result<Verb, ApplicationError> {
+            self.answer("create_verb")
+        }
+
+        async fn transition_verb(
+            &self,
+            _verb_id: VerbId,
+            _next_state: VerbState,
+            _reason: Option<String>,
+        ) -> Result<Verb, ApplicationError> {
+            self.answer("transition_verb")
+        }
+
+        async fn update_verb(
+            &self,
+            _verb_id: VerbId,
+            _changes: VerbChanges,
+        ) -> Result<Verb, ApplicationError> {
+            self.answer("update_verb")
+        }
+
+        async fn list_verbs(&self, _filter: VerbFilter) -> Result<Vec<Verb>, ApplicationError> {
+            self.answer("list_verbs").map(|verb| vec![verb])
+        }
+
+        fn stream_verbs(
+            &self,
+            _state: Option<VerbState>,
+        ) -> BoxStream<'static, Result<Verb, ApplicationError>> {
+            stream::iter([self.answer("stream_verbs")]).boxed()
+        }
+
+        fn subscribe(&self) -> Option<LiveEvents> {
+            self.calls.lock().unwrap().push("subscribe");
+            None
+        }
+
+        async fn get_verb(&self, _verb_id: VerbId) -> Result<Verb, ApplicationError> {
+            self.answer("get_verb")
+        }
+
+        async fn get_verb_action_logs(
+            &self,
+            _verb_id: VerbId,
+            _filter: &ActionLogFilter,
+        ) -> Result<ActionLogListResult, ApplicationError> {
+            self.answer("get_verb_action_logs")
+                .map(|_| ActionLogListResult {
+                    action_logs: Vec::new(),
+                    total: 0,
+                })
+        }
+    }
+}
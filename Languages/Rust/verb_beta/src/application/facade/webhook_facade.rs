@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{
+        ApplicationError, audit,
+        use_cases::{ListWebhookDeliveriesUseCase, RegisterWebhookUseCase},
+    },
+    domain::{
+        model::{EntityRef, EventType, UserId, Webhook, WebhookId},
+        repository::{DeliveryFilter, DeliveryListResult},
+    },
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Facade: Single entry point for webhook management
+///
+/// Delivery itself is not done here; see `infra::webhook::WebhookDispatcher`.
+#[derive(Debug)]
+pub struct WebhookFacade<D: Database> {
+    register_use_case: RegisterWebhookUseCase<D>,
+    list_deliveries_use_case: ListWebhookDeliveriesUseCase<D>,
+}
+
+impl<D: Database> WebhookFacade<D> {
+    /// Create a new facade with a database
+    pub fn new(db: Arc<D>) -> Self {
+        Self {
+            register_use_case: RegisterWebhookUseCase::new(Arc::clone(&db)),
+            list_deliveries_use_case: ListWebhookDeliveriesUseCase::new(Arc::clone(&db)),
+        }
+    }
+
+    /// Register a callback url for the given events, optionally on
+    /// behalf of a user
+    pub async fn register_webhook(
+        &self,
+        url: String,
+        events: Vec<EventType>,
+        secret: Option<String>,
+        owner: Option<UserId>,
+    ) -> Result<Webhook, ApplicationError> {
+        let webhook = self
+            .register_use_case
+            .execute(url, events, secret, owner)
+            .await?;
+        audit::record_change(
+            EntityRef::new("webhook", webhook.id()),
+            None::<&Webhook>,
+            Some(&webhook),
+        );
+        Ok(webhook)
+    }
+
+    /// List all registered webhooks
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>, ApplicationError> {
+        let tx = self
+            .register_use_case
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        tx.webhook_repository()
+            .list()
+            .await
+            .map_err(ApplicationError::from_infra)
+    }
+
+    /// Remove a webhook; its delivery log is kept
+    pub async fn delete_webhook(&self, webhook_id: WebhookId) -> Result<(), ApplicationError> {
+        let tx = self
+            .register_use_case
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let before = if audit::is_recording() {
+            tx.webhook_repository()
+                .find_by_id(webhook_id)
+                .await
+                .map_err(ApplicationError::from_infra)?
+        } else {
+            None
+        };
+        let deleted = tx
+            .webhook_repository()
+            .delete(webhook_id)
+            .await
+            .map_err(ApplicationError::from_infra)?;
+        if !deleted {
+            return Err(ApplicationError::WebhookNotFound);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+        audit::record_change(
+            EntityRef::new("webhook", webhook_id),
+            before.as_ref(),
+            None::<&Webhook>,
+        );
+        Ok(())
+    }
+
+    /// Delivery attempts for a webhook, newest first
+    pub async fn list_deliveries(
+        &self,
+        webhook_id: WebhookId,
+        filter: &DeliveryFilter,
+    ) -> Result<DeliveryListResult, ApplicationError> {
+        self.list_deliveries_use_case
+            .execute(webhook_id, filter)
+            .await
+    }
+}
+
+impl<D: Database> Clone for WebhookFacade<D> {
+    fn clone(&self) -> Self {
+        Self::new(Arc::clone(&self.register_use_case.db))
+    }
+}
@@ -0,0 +1,117 @@
+//! Runtime feature flags
+//!
+//! Flags start from `Config::features` and can be flipped at runtime from
+//! the admin API. Handlers consult them before doing gated work.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use thiserror::Error;
+
+/// A feature that can be switched off at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Feature {
+    /// Webhook registration and management endpoints
+    Webhooks,
+    /// Editing verb fields with PATCH
+    VerbEditing,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 2] = [Feature::Webhooks, Feature::VerbEditing];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Feature::Webhooks => "webhooks",
+            Feature::VerbEditing => "verb_editing",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, UnknownFeature> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.as_str() == s)
+            .ok_or_else(|| UnknownFeature(s.to_string()))
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown feature: {0}")]
+pub struct UnknownFeature(pub String);
+
+/// Shared, cheaply cloneable flag set; every feature is on unless turned off
+#[derive(Debug, Clone, Default)]
+pub struct FeatureFlags {
+    overrides: Arc<RwLock<BTreeMap<Feature, bool>>>,
+}
+
+impl FeatureFlags {
+    /// Start from `name -> enabled` overrides, rejecting unknown names
+    pub fn from_overrides(overrides: &BTreeMap<String, bool>) -> Result<Self, UnknownFeature> {
+        let flags = Self::default();
+        for (name, enabled) in overrides {
+            flags.set(Feature::from_str(name)?, *enabled);
+        }
+        Ok(flags)
+    }
+
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        let flags = self.overrides.read().unwrap_or_else(|e| e.into_inner());
+        flags.get(&feature).copied().unwrap_or(true)
+    }
+
+    pub fn set(&self, feature: Feature, enabled: bool) {
+        let mut flags = self.overrides.write().unwrap_or_else(|e| e.into_inner());
+        flags.insert(feature, enabled);
+        tracing::info!(%feature, enabled, "Feature flag changed");
+    }
+
+    /// Every feature with its current value
+    pub fn snapshot(&self) -> BTreeMap<Feature, bool> {
+        Feature::ALL
+            .into_iter()
+            .map(|f| (f, self.is_enabled(f)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_on_and_applies_overrides() {
+        let overrides = BTreeMap::from([("webhooks".to_string(), false)]);
+
+        let flags = FeatureFlags::from_overrides(&overrides).unwrap();
+
+        assert!(!flags.is_enabled(Feature::Webhooks));
+        assert!(flags.is_enabled(Feature::VerbEditing));
+    }
+
+    #[test]
+    fn clones_share_state() {
+        let flags = FeatureFlags::default();
+        let handle = flags.clone();
+
+        handle.set(Feature::VerbEditing, false);
+
+        assert!(!flags.is_enabled(Feature::VerbEditing));
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        let overrides = BTreeMap::from([("teleport".to_string(), true)]);
+
+        assert!(FeatureFlags::from_overrides(&overrides).is_err());
+    }
+}
@@ -1,6 +1,24 @@
+pub mod audit;
+pub mod blob_store;
+pub mod cache;
 pub mod error;
+pub mod events;
 pub mod facade;
+pub mod feature_flags;
+pub mod notifier;
+pub mod seed;
+pub mod trace_context;
 pub mod use_cases;
 
+pub use audit::AuditRecorder;
+pub use blob_store::{BlobError, BlobFuture, BlobStore};
+pub use cache::{Cache, CacheError, CacheFuture};
 pub use error::ApplicationError;
-pub use facade::VerbFacade;
+pub use events::{EventPublisher, EventReceiver, LiveEvents, VerbEvent};
+pub use facade::{
+    AdminFacade, AttachmentFacade, AuditFacade, NotificationPreferencesFacade, ReviewFacade,
+    StatsFacade, SyncFacade, VerbFacade, VerbUseCase, WebhookFacade,
+};
+pub use feature_flags::{Feature, FeatureFlags};
+pub use notifier::{Notification, Notifier, NotifyError};
+pub use trace_context::TraceContext;
@@ -0,0 +1,28 @@
+//! Outbound notifications
+//!
+//! [`Notifier`] is the port use cases send human-facing messages through;
+//! adapters (SMTP today) live in `infra::notify`.
+
+use std::pin::Pin;
+
+use thiserror::Error;
+
+/// A message for the configured recipients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub subject: String,
+    /// Plain text
+    pub body: String,
+}
+
+#[derive(Debug, Error)]
+#[error("Notification failed: {0}")]
+pub struct NotifyError(pub String);
+
+/// PORT: Deliver a notification to whoever the adapter is configured for
+pub trait Notifier: Send + Sync {
+    fn notify<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> Pin<Box<dyn Future<Output = Result<(), NotifyError>> + Send + 'a>>;
+}
@@ -0,0 +1,302 @@
+//! Development fixtures
+//!
+//! Loads a JSON or YAML fixture through the facades, exactly as API calls
+//! would: every verb is created with `create_verb`, edited with
+//! `update_verb` and walked through its `history` with `transition_verb`,
+//! so action logs, validation and webhook events all behave as in
+//! production.
+//!
+//! ```yaml
+//! verbs:
+//!   - title: Write the report
+//!     description: Q3 numbers
+//!     context: office
+//!     due_at: 2026-03-01T17:00:00Z
+//!     history:
+//!       - state: active
+//!       - state: paused
+//!         reason: waiting on finance
+//! webhooks:
+//!   - url: http://localhost:9000/hook
+//!     events: [verb.created, verb.done]
+//! ```
+//!
+//! Tasks have no repository yet, so they cannot be seeded.
+use std::path::Path;
+
+use serde::Deserialize;
+use thiserror::Error;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+use crate::{
+    application::{ApplicationError, VerbFacade, WebhookFacade, use_cases::VerbChanges},
+    domain::model::{EventType, VerbState},
+    infra::db::Database,
+};
+
+/// A dataset to load at startup
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    #[serde(default)]
+    pub verbs: Vec<VerbFixture>,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerbFixture {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub context: Option<String>,
+    /// RFC 3339
+    pub due_at: Option<String>,
+    /// State transitions applied in order, starting from Captured
+    #[serde(default)]
+    pub history: Vec<TransitionFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransitionFixture {
+    /// Target state, case-insensitive (`active`, `Done`, ...)
+    pub state: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookFixture {
+    pub url: String,
+    pub events: Vec<String>,
+    pub secret: Option<String>,
+}
+
+/// What a seed run created
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SeedReport {
+    pub verbs: usize,
+    pub transitions: usize,
+    pub webhooks: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum SeedError {
+    #[error("Failed to read fixture '{path}': {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid fixture: {0}")]
+    Parse(String),
+
+    #[error("Fixture entry {entry}: {reason}")]
+    Invalid { entry: String, reason: String },
+
+    #[error("Fixture entry {entry}: {source}")]
+    Application {
+        entry: String,
+        #[source]
+        source: ApplicationError,
+    },
+}
+
+impl Fixture {
+    /// Read a fixture file; `.yaml`/`.yml` are parsed as YAML, anything
+    /// else as JSON
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, SeedError> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path).map_err(|source| SeedError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml" | "yml") => Self::from_yaml(&raw),
+            _ => Self::from_json(&raw),
+        }
+    }
+
+    pub fn from_json(raw: &str) -> Result<Self, SeedError> {
+        serde_json::from_str(raw).map_err(|e| SeedError::Parse(e.to_string()))
+    }
+
+    pub fn from_yaml(raw: &str) -> Result<Self, SeedError> {
+        serde_yaml::from_str(raw).map_err(|e| SeedError::Parse(e.to_string()))
+    }
+}
+
+/// Load `fixture` through the application facades.
+///
+/// Stops at the first invalid entry; entries before it stay loaded.
+pub async fn seed<D: Database>(
+    verbs: &VerbFacade<D>,
+    webhooks: &WebhookFacade<D>,
+    fixture: Fixture,
+) -> Result<SeedReport, SeedError> {
+    let mut report = SeedReport::default();
+
+    for (index, entry) in fixture.verbs.into_iter().enumerate() {
+        let label = format!("verbs[{index}] ('{}')", entry.title);
+        let app_err = |source| SeedError::Application {
+            entry: label.clone(),
+            source,
+        };
+        let invalid = |reason: String| SeedError::Invalid {
+            entry: label.clone(),
+            reason,
+        };
+
+        let due_at = entry
+            .due_at
+            .map(|raw| {
+                OffsetDateTime::parse(&raw, &Rfc3339)
+                    .map_err(|_| invalid(format!("invalid due_at '{raw}', expected RFC 3339")))
+            })
+            .transpose()?;
+        let history = entry
+            .history
+            .into_iter()
+            .map(|t| {
+                parse_state(&t.state)
+                    .map(|state| (state, t.reason))
+                    .ok_or_else(|| invalid(format!("unknown state '{}'", t.state)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let verb = verbs
+            .create_verb(entry.title.clone(), entry.description)
+            .await
+            .map_err(app_err)?;
+
+        if entry.context.is_some() || due_at.is_some() {
+            let changes = VerbChanges {
+                context: entry.context.map(Some),
+                due_at: due_at.map(Some),
+                ..VerbChanges::default()
+            };
+            verbs
+                .update_verb(verb.id(), changes)
+                .await
+                .map_err(app_err)?;
+        }
+
+        for (state, reason) in history {
+            verbs
+                .transition_verb(verb.id(), state, reason)
+                .await
+                .map_err(app_err)?;
+            report.transitions += 1;
+        }
+
+        report.verbs += 1;
+    }
+
+    for (index, entry) in fixture.webhooks.into_iter().enumerate() {
+        let label = format!("webhooks[{index}] ('{}')", entry.url);
+
+        let events = entry
+            .events
+            .iter()
+            .map(|e| EventType::from_str(e))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| SeedError::Invalid {
+                entry: label.clone(),
+                reason: e.to_string(),
+            })?;
+
+        webhooks
+            .register_webhook(entry.url, events, entry.secret, None)
+            .await
+            .map_err(|source| SeedError::Application {
+                entry: label,
+                source,
+            })?;
+
+        report.webhooks += 1;
+    }
+
+    Ok(report)
+}
+
+fn parse_state(raw: &str) -> Option<VerbState> {
+    [
+        VerbState::Captured,
+        VerbState::Active,
+        VerbState::Paused,
+        VerbState::Done,
+        VerbState::Dropped,
+    ]
+    .into_iter()
+    .find(|state| state.as_str().eq_ignore_ascii_case(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        domain::repository::{VerbFilter, action_log_repo::ActionLogFilter},
+        infra::repository::in_memory::InMemoryDatabase,
+    };
+
+    fn facades() -> (
+        VerbFacade<InMemoryDatabase>,
+        WebhookFacade<InMemoryDatabase>,
+    ) {
+        let db = Arc::new(InMemoryDatabase::new());
+        (VerbFacade::new(Arc::clone(&db)), WebhookFacade::new(db))
+    }
+
+    #[tokio::test]
+    async fn seeds_demo_fixture_through_facades() {
+        let (verbs, webhooks) = facades();
+        let fixture =
+            Fixture::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/demo.yaml")).unwrap();
+
+        let report = seed(&verbs, &webhooks, fixture).await.unwrap();
+
+        let all = verbs.list_verbs(VerbFilter::default()).await.unwrap();
+        assert_eq!(all.len(), report.verbs);
+        assert!(all.iter().any(|v| v.state() == VerbState::Done));
+        assert_eq!(
+            webhooks.list_webhooks().await.unwrap().len(),
+            report.webhooks
+        );
+
+        // History produced real action logs
+        let done = all.iter().find(|v| v.state() == VerbState::Done).unwrap();
+        let logs = verbs
+            .get_verb_action_logs(done.id(), &ActionLogFilter::default())
+            .await
+            .unwrap();
+        assert!(logs.total >= 3, "created + activated + completed");
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_transition_with_entry_label() {
+        let (verbs, webhooks) = facades();
+        let fixture = Fixture::from_json(
+            r#"{ "verbs": [{ "title": "Skip ahead", "history": [{ "state": "done" }] }] }"#,
+        )
+        .unwrap();
+
+        let err = seed(&verbs, &webhooks, fixture).await.unwrap_err();
+
+        assert!(
+            matches!(err, SeedError::Application { ref entry, .. } if entry.contains("Skip ahead"))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_sections() {
+        let err = Fixture::from_json(r#"{ "tasks": [] }"#).unwrap_err();
+
+        assert!(matches!(err, SeedError::Parse(msg) if msg.contains("tasks")));
+    }
+}
@@ -0,0 +1,250 @@
+//! W3C trace context
+//!
+//! [`TraceContext`] is this service's position in a distributed trace, as
+//! carried by the `traceparent` and `tracestate` headers
+//! (<https://www.w3.org/TR/trace-context/>):
+//!
+//! ```text
+//! traceparent: 00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01
+//!              version-trace id-------------------parent span id---flags
+//! ```
+//!
+//! The `propagate_trace` middleware continues the caller's trace (or starts
+//! one) and scopes the context over the request. Events published while
+//! handling it capture [`TraceContext::current`], so outbound webhook calls
+//! send a `traceparent` in the same trace.
+
+use std::fmt;
+
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CURRENT: TraceContext;
+}
+
+/// Longest `tracestate` passed on; longer ones are dropped, as the spec
+/// allows
+const MAX_TRACESTATE_LEN: usize = 512;
+
+/// Flag bit asking downstream services to record the trace
+const SAMPLED: u8 = 0x01;
+
+/// A span in a distributed trace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    /// The caller's span, when the trace came in with the request
+    parent_id: Option<[u8; 8]>,
+    flags: u8,
+    /// Vendor-specific state, passed on unchanged
+    tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Start a new, sampled trace
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_id(),
+            span_id: random_id(),
+            parent_id: None,
+            flags: SAMPLED,
+            tracestate: None,
+        }
+    }
+
+    /// Continue the trace described by incoming headers, or start a new one
+    /// when `traceparent` is absent or invalid (its `tracestate` is then
+    /// discarded too)
+    pub fn from_headers(traceparent: Option<&str>, tracestate: Option<&str>) -> Self {
+        match traceparent.and_then(parse_traceparent) {
+            Some((trace_id, parent_id, flags)) => Self {
+                trace_id,
+                span_id: random_id(),
+                parent_id: Some(parent_id),
+                flags,
+                tracestate: tracestate
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty() && s.len() <= MAX_TRACESTATE_LEN)
+                    .map(str::to_owned),
+            },
+            None => Self::new_root(),
+        }
+    }
+
+    /// A new span in the same trace, with `self` as its parent
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: random_id(),
+            parent_id: Some(self.span_id),
+            flags: self.flags,
+            tracestate: self.tracestate.clone(),
+        }
+    }
+
+    /// The context of the request being handled, if any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+
+    /// Run `f` with `self` as the current context
+    pub async fn scope<F: Future>(self, f: F) -> F::Output {
+        CURRENT.scope(self, f).await
+    }
+
+    /// `traceparent` header value naming this span as the parent
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            self.trace_id(),
+            self.span_id(),
+            self.flags
+        )
+    }
+
+    pub fn tracestate(&self) -> Option<&str> {
+        self.tracestate.as_deref()
+    }
+
+    /// 32 lowercase hex digits
+    pub fn trace_id(&self) -> String {
+        hex::encode(self.trace_id)
+    }
+
+    /// 16 lowercase hex digits
+    pub fn span_id(&self) -> String {
+        hex::encode(self.span_id)
+    }
+
+    pub fn parent_id(&self) -> Option<String> {
+        self.parent_id.map(hex::encode)
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.flags & SAMPLED != 0
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.traceparent())
+    }
+}
+
+/// `(trace id, parent id, flags)` from a `traceparent` value
+///
+/// Future versions may append fields after the four defined by version
+/// `00`; those are ignored. Version `ff`, all-zero ids and uppercase hex
+/// are invalid.
+fn parse_traceparent(raw: &str) -> Option<([u8; 16], [u8; 8], u8)> {
+    let raw = raw.trim();
+    let mut parts = raw.splitn(5, '-');
+    let version = parts.next()?;
+    let (trace_id, parent_id, flags) = (parts.next()?, parts.next()?, parts.next()?);
+    let rest = parts.next();
+
+    if version == "ff" || (version == "00" && rest.is_some()) {
+        return None;
+    }
+    decode::<1>(version)?;
+
+    let trace_id = decode::<16>(trace_id).filter(|id| id.iter().any(|&b| b != 0))?;
+    let parent_id = decode::<8>(parent_id).filter(|id| id.iter().any(|&b| b != 0))?;
+    let [flags] = decode::<1>(flags)?;
+
+    Some((trace_id, parent_id, flags))
+}
+
+/// Exactly `2 * N` lowercase hex digits
+fn decode<const N: usize>(hex_digits: &str) -> Option<[u8; N]> {
+    if hex_digits.len() != 2 * N || hex_digits.bytes().any(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    let mut out = [0; N];
+    hex::decode_to_slice(hex_digits, &mut out).ok()?;
+    Some(out)
+}
+
+/// A random id, taken from v4 UUIDs
+fn random_id<const N: usize>() -> [u8; N] {
+    let mut id = [0; N];
+    for chunk in id.chunks_mut(8) {
+        let (high, low) = Uuid::new_v4().as_u64_pair();
+        chunk.copy_from_slice(&(high ^ low).to_be_bytes()[..chunk.len()]);
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn continues_an_incoming_trace() {
+        let ctx = TraceContext::from_headers(Some(TRACEPARENT), Some("vendor=abc"));
+
+        assert_eq!(ctx.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(ctx.parent_id().as_deref(), Some("00f067aa0ba902b7"));
+        assert_ne!(ctx.span_id(), "00f067aa0ba902b7");
+        assert_eq!(ctx.tracestate(), Some("vendor=abc"));
+        assert!(ctx.is_sampled());
+
+        let child = ctx.child();
+        assert_eq!(child.trace_id(), ctx.trace_id());
+        assert_eq!(child.parent_id(), Some(ctx.span_id()));
+        assert_eq!(
+            child.traceparent(),
+            format!("00-{}-{}-01", ctx.trace_id(), child.span_id())
+        );
+    }
+
+    #[test]
+    fn starts_a_new_trace_for_invalid_headers() {
+        let invalid = [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "0-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        ];
+        for raw in invalid {
+            let ctx = TraceContext::from_headers(Some(raw), Some("vendor=abc"));
+            assert_eq!(ctx.parent_id(), None, "{raw}");
+            assert_eq!(ctx.tracestate(), None, "{raw}");
+        }
+
+        let root = TraceContext::from_headers(None, None);
+        assert_eq!(root.trace_id().len(), 32);
+        assert_eq!(root.span_id().len(), 16);
+        assert_ne!(root.trace_id(), TraceContext::new_root().trace_id());
+    }
+
+    #[test]
+    fn accepts_future_versions_with_extra_fields() {
+        let ctx = TraceContext::from_headers(
+            Some("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-extra"),
+            None,
+        );
+
+        assert_eq!(ctx.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert!(!ctx.is_sampled());
+        // We only speak version 00
+        assert!(ctx.traceparent().starts_with("00-"));
+        assert!(ctx.traceparent().ends_with("-00"));
+    }
+
+    #[tokio::test]
+    async fn current_is_scoped() {
+        assert_eq!(TraceContext::current(), None);
+
+        let ctx = TraceContext::new_root();
+        let seen = ctx.clone().scope(async { TraceContext::current() }).await;
+        assert_eq!(seen, Some(ctx));
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{ApplicationError, BlobStore},
+    domain::model::{Attachment, VerbId, VerbState},
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Which uploads are accepted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentPolicy {
+    /// Largest accepted file, in bytes
+    pub max_bytes: u64,
+    /// Accepted content types: exact (`application/pdf`) or a whole
+    /// top-level type (`image/*`)
+    pub allowed_types: Vec<String>,
+}
+
+impl Default for AttachmentPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: 5 * 1024 * 1024,
+            allowed_types: ["image/*", "text/plain", "text/markdown", "application/pdf"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+impl AttachmentPolicy {
+    pub fn allows(&self, content_type: &str) -> bool {
+        self.allowed_types
+            .iter()
+            .any(|allowed| match allowed.strip_suffix("/*") {
+                Some(kind) => content_type
+                    .split_once('/')
+                    .is_some_and(|(k, _)| k.eq_ignore_ascii_case(kind)),
+                None => allowed.eq_ignore_ascii_case(content_type),
+            })
+    }
+}
+
+/// Use case: Attach a file to a verb
+///
+/// ## Business Rules
+/// - The verb must exist and not be dropped
+/// - Size and content type must pass the [`AttachmentPolicy`]
+/// - Bytes are stored before the metadata, and removed again if the
+///   metadata can't be saved, so metadata never points at nothing
+pub struct AddAttachmentUseCase<D: Database> {
+    pub db: Arc<D>,
+    pub blobs: Arc<dyn BlobStore>,
+    pub policy: AttachmentPolicy,
+}
+
+impl<D: Database> AddAttachmentUseCase<D> {
+    pub fn new(db: Arc<D>, blobs: Arc<dyn BlobStore>, policy: AttachmentPolicy) -> Self {
+        Self { db, blobs, policy }
+    }
+
+    pub async fn execute(
+        &self,
+        verb_id: VerbId,
+        filename: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<Attachment, ApplicationError> {
+        // Step 1: Validate the upload itself
+        let size = data.len() as u64;
+        if size > self.policy.max_bytes {
+            return Err(ApplicationError::AttachmentTooLarge {
+                limit: self.policy.max_bytes,
+            });
+        }
+        let attachment = Attachment::new(verb_id, filename, content_type, size)?;
+        if !self.policy.allows(attachment.content_type()) {
+            return Err(ApplicationError::UnsupportedAttachmentType(
+                attachment.content_type().to_string(),
+            ));
+        }
+
+        // Step 2: Check the owning verb
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let verb = tx.verb_repository().find_by_id(verb_id).await?;
+        if verb.is_none_or(|v| v.state() == VerbState::Dropped) {
+            return Err(ApplicationError::NotFound);
+        }
+
+        // Step 3: Store bytes, then metadata
+        let key = attachment.blob_key();
+        self.blobs.put(&key, data).await?;
+
+        let saved = async {
+            tx.attachment_repository().save(&attachment).await?;
+            tx.commit()
+                .await
+                .map_err(|e| ApplicationError::Transaction(e.to_string()))
+        }
+        .await;
+
+        if let Err(e) = saved {
+            if let Err(cleanup) = self.blobs.delete(&key).await {
+                tracing::warn!(key, error = %cleanup, "Failed to remove orphaned blob");
+            }
+            return Err(e);
+        }
+
+        Ok(attachment)
+    }
+}
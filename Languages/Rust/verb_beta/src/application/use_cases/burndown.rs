@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use time::{Date, Duration, OffsetDateTime, Time, UtcOffset};
+
+use crate::{
+    application::ApplicationError,
+    domain::repository::DailyActionCounts,
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Created, completed and dropped counts for each day of a window,
+/// oldest first, as parallel series ready for charting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burndown {
+    /// First day of the window (UTC)
+    pub from: Date,
+    /// Last day of the window, today (UTC)
+    pub to: Date,
+    /// Every day of the window, including idle ones
+    pub dates: Vec<Date>,
+    pub created: Vec<u32>,
+    pub completed: Vec<u32>,
+    pub dropped: Vec<u32>,
+}
+
+/// Use case: Burn-down statistics over the last `window_days` days
+///
+/// The per-day counting happens in the action log repository; this fills
+/// the idle days in between with zeros.
+#[derive(Debug, Clone)]
+pub struct BurndownUseCase<D: Database> {
+    pub db: Arc<D>,
+}
+
+impl<D: Database> BurndownUseCase<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    /// `window_days` days ending with the day of `now`; at least one
+    pub async fn execute(
+        &self,
+        now: OffsetDateTime,
+        window_days: u16,
+    ) -> Result<Burndown, ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let to = now.to_offset(UtcOffset::UTC).date();
+        let from = to - Duration::days(i64::from(window_days.max(1)) - 1);
+        let start = from.with_time(Time::MIDNIGHT).assume_utc();
+        let end = to.with_time(Time::MIDNIGHT).assume_utc() + Duration::days(1);
+
+        let counts = tx.action_log_repository().daily_counts(start, end).await?;
+
+        Ok(fill(from, to, counts))
+    }
+}
+
+/// One entry per day in `from..=to`, taking counts from `counts` (sorted,
+/// sparse) and zero for the days it skips
+fn fill(from: Date, to: Date, counts: Vec<DailyActionCounts>) -> Burndown {
+    let mut counts = counts.into_iter().peekable();
+    let mut burndown = Burndown {
+        from,
+        to,
+        dates: Vec::new(),
+        created: Vec::new(),
+        completed: Vec::new(),
+        dropped: Vec::new(),
+    };
+
+    let mut date = from;
+    while date <= to {
+        let day = counts
+            .next_if(|c| c.date == date)
+            .unwrap_or_else(|| DailyActionCounts::new(date));
+        burndown.dates.push(date);
+        burndown.created.push(day.created);
+        burndown.completed.push(day.completed);
+        burndown.dropped.push(day.dropped);
+
+        match date.next_day() {
+            Some(next) => date = next,
+            None => break,
+        }
+    }
+
+    burndown
+}
+
+#[cfg(test)]
+mod tests {
+    use time::format_description::well_known::Rfc3339;
+
+    use super::*;
+    use crate::{
+        domain::model::{ActionLog, ActionLogId, ActionType, VerbId, VerbState},
+        infra::repository::in_memory::InMemoryDatabase,
+    };
+
+    fn at(rfc3339: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(rfc3339, &Rfc3339).unwrap()
+    }
+
+    async fn seed(db: &InMemoryDatabase, logs: &[(ActionType, &str)]) {
+        let tx = db.begin_tx().await.unwrap();
+        for (action, timestamp) in logs {
+            let log = ActionLog::from_parts(
+                ActionLogId::new(),
+                VerbId::new(),
+                *action,
+                None,
+                VerbState::Active,
+                None,
+                Vec::new(),
+                at(timestamp),
+            );
+            tx.action_log_repository().append(&log).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fills_every_day_of_the_window() {
+        let db = Arc::new(InMemoryDatabase::new());
+        seed(
+            &db,
+            &[
+                // Before the window
+                (ActionType::Created, "2026-03-06T23:59:59Z"),
+                (ActionType::Created, "2026-03-07T08:00:00Z"),
+                (ActionType::Created, "2026-03-07T09:00:00Z"),
+                (ActionType::Paused, "2026-03-08T09:00:00Z"),
+                (ActionType::Completed, "2026-03-09T10:00:00Z"),
+                (ActionType::Dropped, "2026-03-10T07:00:00Z"),
+            ],
+        )
+        .await;
+
+        let burndown = BurndownUseCase::new(db)
+            .execute(at("2026-03-10T09:00:00Z"), 4)
+            .await
+            .unwrap();
+
+        let dates: Vec<_> = burndown.dates.iter().map(Date::to_string).collect();
+        assert_eq!(
+            dates,
+            ["2026-03-07", "2026-03-08", "2026-03-09", "2026-03-10"]
+        );
+        assert_eq!(burndown.from.to_string(), "2026-03-07");
+        assert_eq!(burndown.to.to_string(), "2026-03-10");
+        assert_eq!(burndown.created, [2, 0, 0, 0]);
+        assert_eq!(burndown.completed, [0, 0, 1, 0]);
+        assert_eq!(burndown.dropped, [0, 0, 0, 1]);
+    }
+
+    #[tokio::test]
+    async fn empty_log_gives_zero_series() {
+        let db = Arc::new(InMemoryDatabase::new());
+
+        let burndown = BurndownUseCase::new(db)
+            .execute(at("2026-03-10T09:00:00Z"), 0)
+            .await
+            .unwrap();
+
+        assert_eq!(burndown.dates.len(), 1, "a window is at least a day");
+        assert_eq!(burndown.created, [0]);
+    }
+}
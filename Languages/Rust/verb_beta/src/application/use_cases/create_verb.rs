@@ -2,14 +2,14 @@ use std::sync::Arc;
 
 use crate::{
     application::ApplicationError,
-    domain::model::{ActionLog, Verb},
+    domain::model::{ActionLog, Description, Title, Verb, VerbId},
     infra::db::{Database, DatabaseTransaction},
 };
 
 // Use case: Create a new verb
 ///
 /// Responsibilities:
-/// 1. Create verb entity (domain validates)
+/// 1. Build the title/description value objects and the verb entity
 /// 2. Create initial action log
 /// 3. Open transaction
 /// 4. Save both atomically
@@ -34,8 +34,21 @@ impl<D: Database> CreateVerbUseCase<D> {
         title: String,
         description: String,
     ) -> Result<Verb, ApplicationError> {
-        // Step 1: Create domain entities (synchronous - domain validates)
-        let verb = Verb::new(title, description)?;
+        self.execute_with_id(VerbId::new(), title, description)
+            .await
+    }
+
+    /// Execute the use case for a verb whose id the client generated
+    ///
+    /// Saving is an upsert, so callers check the id is unused first.
+    pub async fn execute_with_id(
+        &self,
+        id: VerbId,
+        title: String,
+        description: String,
+    ) -> Result<Verb, ApplicationError> {
+        // Step 1: Create domain entities (synchronous - value objects validate)
+        let verb = Verb::new_with_id(id, Title::new(title)?, Description::new(description)?);
         let action_log = ActionLog::created(verb.id());
 
         // Step 2: Begin transaction (async)
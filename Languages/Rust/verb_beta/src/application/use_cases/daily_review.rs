@@ -0,0 +1,334 @@
+use std::sync::Arc;
+
+use time::{Date, Duration, OffsetDateTime, Time};
+
+use crate::{
+    application::{ApplicationError, notifier::Notification},
+    domain::{
+        model::{ActionType, Verb, VerbState},
+        repository::{
+            ActionLogRepository, VerbFilter, VerbRepository, action_log_repo::ActionLogFilter,
+        },
+    },
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// What counts as needing attention in the daily review
+#[derive(Debug, Clone, Copy)]
+pub struct ReviewPolicy {
+    /// A verb paused at least this long is listed as stale
+    pub stale_paused_after: Duration,
+}
+
+impl Default for ReviewPolicy {
+    fn default() -> Self {
+        Self {
+            stale_paused_after: Duration::days(7),
+        }
+    }
+}
+
+/// A verb in the review, with the moment that put it there
+#[derive(Debug, Clone)]
+pub struct ReviewEntry {
+    pub verb: Verb,
+    /// Completed at, paused since, or due at, depending on the section
+    pub at: OffsetDateTime,
+}
+
+/// The daily review for `date` (UTC)
+#[derive(Debug, Clone)]
+pub struct DailyReview {
+    pub date: Date,
+    /// Completed during the previous day, earliest first
+    pub completed_yesterday: Vec<ReviewEntry>,
+    /// Paused for longer than the policy allows, longest first
+    pub stale_paused: Vec<ReviewEntry>,
+    /// Past their due date and still open, most overdue first
+    pub overdue: Vec<ReviewEntry>,
+}
+
+impl DailyReview {
+    /// Plain-text digest, one section per list
+    pub fn to_notification(&self) -> Notification {
+        let mut body = String::new();
+        let sections = [
+            (
+                "Completed yesterday",
+                &self.completed_yesterday,
+                "completed",
+            ),
+            ("Paused too long", &self.stale_paused, "paused since"),
+            ("Overdue", &self.overdue, "due"),
+        ];
+        for (heading, entries, label) in sections {
+            body.push_str(&format!("{heading} ({})\n", entries.len()));
+            if entries.is_empty() {
+                body.push_str("  nothing\n");
+            }
+            for entry in entries {
+                body.push_str(&format!(
+                    "  - {} ({label} {})\n",
+                    entry.verb.title(),
+                    entry.at.date()
+                ));
+            }
+            body.push('\n');
+        }
+
+        Notification {
+            subject: format!("Daily review for {}", self.date),
+            body,
+        }
+    }
+}
+
+/// Use case: Assemble the daily review as of a given moment
+///
+/// Completion and pause times come from the action log, so editing a verb
+/// afterwards doesn't move it between days.
+#[derive(Debug, Clone)]
+pub struct DailyReviewUseCase<D: Database> {
+    pub db: Arc<D>,
+}
+
+impl<D: Database> DailyReviewUseCase<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(
+        &self,
+        now: OffsetDateTime,
+        policy: ReviewPolicy,
+    ) -> Result<DailyReview, ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let now = now.to_offset(time::UtcOffset::UTC);
+        let today = now.date();
+        let today_start = today.with_time(Time::MIDNIGHT).assume_utc();
+        let yesterday_start = today_start - Duration::days(1);
+
+        let mut review = DailyReview {
+            date: today,
+            completed_yesterday: Vec::new(),
+            stale_paused: Vec::new(),
+            overdue: Vec::new(),
+        };
+
+        let verb_repo = tx.verb_repository();
+        let log_repo = tx.action_log_repository();
+
+        for verb in all_verbs(verb_repo).await? {
+            let state = verb.state();
+            if state == VerbState::Dropped {
+                continue;
+            }
+
+            if let Some(due_at) = verb
+                .due_at()
+                .filter(|due| state != VerbState::Done && *due < now)
+            {
+                review.overdue.push(ReviewEntry {
+                    verb: verb.clone(),
+                    at: due_at,
+                });
+            }
+
+            match state {
+                VerbState::Done => {
+                    let at = last_entered(log_repo, &verb, ActionType::Completed).await?;
+                    if (yesterday_start..today_start).contains(&at) {
+                        review.completed_yesterday.push(ReviewEntry { verb, at });
+                    }
+                }
+                VerbState::Paused => {
+                    let at = last_entered(log_repo, &verb, ActionType::Paused).await?;
+                    if now - at >= policy.stale_paused_after {
+                        review.stale_paused.push(ReviewEntry { verb, at });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        review.completed_yesterday.sort_by_key(|e| e.at);
+        review.stale_paused.sort_by_key(|e| e.at);
+        review.overdue.sort_by_key(|e| e.at);
+
+        Ok(review)
+    }
+}
+
+/// Every verb, page by page
+async fn all_verbs(repo: &dyn VerbRepository) -> Result<Vec<Verb>, ApplicationError> {
+    const PAGE: u32 = 200;
+    let mut verbs = Vec::new();
+
+    loop {
+        let page = repo
+            .list(
+                VerbFilter::new()
+                    .with_limit(PAGE)
+                    .with_offset(verbs.len() as u32),
+            )
+            .await?;
+        let fetched = page.verbs.len();
+        verbs.extend(page.verbs);
+        if fetched == 0 || verbs.len() as u32 >= page.total {
+            return Ok(verbs);
+        }
+    }
+}
+
+/// When `verb` last entered its current state, falling back to
+/// `updated_at` for verbs without a matching log entry
+async fn last_entered(
+    repo: &dyn ActionLogRepository,
+    verb: &Verb,
+    action: ActionType,
+) -> Result<OffsetDateTime, ApplicationError> {
+    let filter = ActionLogFilter::new().with_state(action).with_limit(1);
+    let logs = repo.find_by_verb(verb.id(), &filter).await?;
+
+    Ok(logs
+        .first()
+        .map_or(verb.updated_at(), |log| log.timestamp()))
+}
+
+#[cfg(test)]
+mod tests {
+    use time::format_description::well_known::Rfc3339;
+
+    use super::*;
+    use crate::{
+        domain::model::{ActionLog, ActionLogId, VerbId},
+        infra::repository::in_memory::InMemoryDatabase,
+    };
+
+    fn at(rfc3339: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(rfc3339, &Rfc3339).unwrap()
+    }
+
+    fn now() -> OffsetDateTime {
+        at("2026-03-10T09:00:00Z")
+    }
+
+    struct Seed {
+        title: &'static str,
+        state: VerbState,
+        /// When the verb entered `state`, per its action log
+        entered: OffsetDateTime,
+        due_at: Option<OffsetDateTime>,
+    }
+
+    async fn review(seeds: &[Seed]) -> DailyReview {
+        let db = Arc::new(InMemoryDatabase::new());
+        let tx = db.begin_tx().await.unwrap();
+        for seed in seeds {
+            let verb = Verb::from_parts(
+                VerbId::new(),
+                seed.title.to_string(),
+                String::new(),
+                None,
+                seed.due_at,
+                seed.state,
+                seed.entered - Duration::days(30),
+                // Edited after the transition; the log time must win
+                now() - Duration::minutes(5),
+            )
+            .unwrap();
+            let log = ActionLog::from_parts(
+                ActionLogId::new(),
+                verb.id(),
+                ActionType::from(seed.state),
+                None,
+                seed.state,
+                None,
+                Vec::new(),
+                seed.entered,
+            );
+            tx.verb_repository().save(&verb).await.unwrap();
+            tx.action_log_repository().append(&log).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        DailyReviewUseCase::new(db)
+            .execute(now(), ReviewPolicy::default())
+            .await
+            .unwrap()
+    }
+
+    fn titles(entries: &[ReviewEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.verb.title()).collect()
+    }
+
+    #[tokio::test]
+    async fn sorts_verbs_into_sections() {
+        let seeds = [
+            Seed {
+                title: "done yesterday",
+                state: VerbState::Done,
+                entered: at("2026-03-09T18:00:00Z"),
+                due_at: Some(at("2026-03-01T00:00:00Z")),
+            },
+            Seed {
+                title: "done today",
+                state: VerbState::Done,
+                entered: at("2026-03-10T08:00:00Z"),
+                due_at: None,
+            },
+            Seed {
+                title: "paused long",
+                state: VerbState::Paused,
+                entered: now() - Duration::days(10),
+                due_at: None,
+            },
+            Seed {
+                title: "paused briefly",
+                state: VerbState::Paused,
+                entered: now() - Duration::days(2),
+                due_at: None,
+            },
+            Seed {
+                title: "late",
+                state: VerbState::Active,
+                entered: now() - Duration::days(3),
+                due_at: Some(now() - Duration::hours(1)),
+            },
+            Seed {
+                title: "dropped late",
+                state: VerbState::Dropped,
+                entered: now() - Duration::days(3),
+                due_at: Some(now() - Duration::days(1)),
+            },
+        ];
+
+        let review = review(&seeds).await;
+
+        assert_eq!(review.date, now().date());
+        assert_eq!(titles(&review.completed_yesterday), ["done yesterday"]);
+        assert_eq!(titles(&review.stale_paused), ["paused long"]);
+        assert_eq!(titles(&review.overdue), ["late"]);
+    }
+
+    #[tokio::test]
+    async fn renders_every_section() {
+        let review = review(&[]).await;
+
+        let notification = review.to_notification();
+
+        assert_eq!(notification.subject, "Daily review for 2026-03-10");
+        for heading in [
+            "Completed yesterday (0)",
+            "Paused too long (0)",
+            "Overdue (0)",
+        ] {
+            assert!(notification.body.contains(heading), "{heading}");
+        }
+    }
+}
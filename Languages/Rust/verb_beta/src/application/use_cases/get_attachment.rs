@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{ApplicationError, BlobStore},
+    domain::model::{Attachment, AttachmentId, VerbState},
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Use case: Fetch an attachment with its contents
+///
+/// Attachments are only served while their verb exists and isn't dropped;
+/// otherwise they are reported as not found.
+pub struct GetAttachmentUseCase<D: Database> {
+    pub db: Arc<D>,
+    pub blobs: Arc<dyn BlobStore>,
+}
+
+impl<D: Database> GetAttachmentUseCase<D> {
+    pub fn new(db: Arc<D>, blobs: Arc<dyn BlobStore>) -> Self {
+        Self { db, blobs }
+    }
+
+    pub async fn execute(
+        &self,
+        id: AttachmentId,
+    ) -> Result<(Attachment, Vec<u8>), ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let attachment = tx
+            .attachment_repository()
+            .find_by_id(id)
+            .await?
+            .ok_or(ApplicationError::AttachmentNotFound)?;
+
+        // Owner check
+        let verb = tx
+            .verb_repository()
+            .find_by_id(attachment.verb_id())
+            .await?;
+        if verb.is_none_or(|v| v.state() == VerbState::Dropped) {
+            return Err(ApplicationError::AttachmentNotFound);
+        }
+
+        let Some(data) = self.blobs.get(&attachment.blob_key()).await? else {
+            tracing::warn!(attachment_id = %id, "Attachment metadata without stored contents");
+            return Err(ApplicationError::AttachmentNotFound);
+        };
+
+        Ok((attachment, data))
+    }
+}
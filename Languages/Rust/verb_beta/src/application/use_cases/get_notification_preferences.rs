@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::{
+    application::ApplicationError,
+    domain::model::{NotificationPreferences, UserId},
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Use case: The preferences that apply to a user
+///
+/// Users who never saved any get the defaults (everything on, no quiet
+/// hours), so delivery code never has to special-case them.
+#[derive(Debug, Clone)]
+pub struct GetNotificationPreferencesUseCase<D: Database> {
+    pub db: Arc<D>,
+}
+
+impl<D: Database> GetNotificationPreferencesUseCase<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(
+        &self,
+        user_id: UserId,
+    ) -> Result<NotificationPreferences, ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let saved = tx
+            .notification_preferences_repository()
+            .find_by_user(user_id)
+            .await?;
+
+        Ok(saved.unwrap_or_else(|| NotificationPreferences::defaults(user_id)))
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::{
+    application::ApplicationError,
+    domain::model::{Attachment, VerbId, VerbState},
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Use case: Metadata of every attachment of a verb, oldest first
+#[derive(Debug, Clone)]
+pub struct ListAttachmentsUseCase<D: Database> {
+    pub db: Arc<D>,
+}
+
+impl<D: Database> ListAttachmentsUseCase<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(&self, verb_id: VerbId) -> Result<Vec<Attachment>, ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let verb = tx.verb_repository().find_by_id(verb_id).await?;
+        if verb.is_none_or(|v| v.state() == VerbState::Dropped) {
+            return Err(ApplicationError::NotFound);
+        }
+
+        tx.attachment_repository().find_by_verb(verb_id).await
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::{
+    application::ApplicationError,
+    domain::{
+        model::WebhookId,
+        repository::{DeliveryFilter, DeliveryListResult},
+    },
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Use case: List delivery attempts for a webhook
+///
+/// Fails with `WebhookNotFound` for unknown webhooks rather than returning
+/// an empty list.
+#[derive(Debug, Clone)]
+pub struct ListWebhookDeliveriesUseCase<D: Database> {
+    pub db: Arc<D>,
+}
+
+impl<D: Database> ListWebhookDeliveriesUseCase<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(
+        &self,
+        webhook_id: WebhookId,
+        filter: &DeliveryFilter,
+    ) -> Result<DeliveryListResult, ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        tx.webhook_repository()
+            .find_by_id(webhook_id)
+            .await
+            .map_err(ApplicationError::from_infra)?
+            .ok_or(ApplicationError::WebhookNotFound)?;
+
+        let result = tx
+            .webhook_delivery_repository()
+            .find_by_webhook(webhook_id, filter)
+            .await?;
+
+        // No explicit commit needed for read-only
+        Ok(result)
+    }
+}
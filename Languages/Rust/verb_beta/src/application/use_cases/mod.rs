@@ -1,9 +1,29 @@
+mod add_attachment;
+mod burndown;
 mod create_verb;
+mod daily_review;
+mod get_attachment;
 mod get_logs_by_verb_id;
+mod get_notification_preferences;
+mod list_attachments;
 mod list_verbs;
+mod list_webhook_deliveries;
+mod register_webhook;
+mod sync;
 mod transition_verb;
+mod update_verb;
 
+pub use add_attachment::{AddAttachmentUseCase, AttachmentPolicy};
+pub use burndown::{Burndown, BurndownUseCase};
 pub use create_verb::CreateVerbUseCase;
+pub use daily_review::{DailyReview, DailyReviewUseCase, ReviewEntry, ReviewPolicy};
+pub use get_attachment::GetAttachmentUseCase;
 pub use get_logs_by_verb_id::GetVerbActionLogs;
+pub use get_notification_preferences::GetNotificationPreferencesUseCase;
+pub use list_attachments::ListAttachmentsUseCase;
 pub use list_verbs::ListVerbsUseCase;
+pub use list_webhook_deliveries::ListWebhookDeliveriesUseCase;
+pub use register_webhook::RegisterWebhookUseCase;
+pub use sync::{SyncChanges, SyncMutation, SyncOp, SyncOutcome, SyncStatus, SyncUseCase};
 pub use transition_verb::TransitionVerbUseCase;
+pub use update_verb::{UpdateVerbUseCase, VerbChanges};
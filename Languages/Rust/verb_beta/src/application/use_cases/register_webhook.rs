@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::{
+    application::ApplicationError,
+    domain::model::{EventType, UserId, Webhook},
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Use case: Register a webhook
+///
+/// Responsibilities:
+/// 1. Create webhook entity (domain validates url, events, secret)
+/// 2. Attach the owner, whose notification preferences deliveries follow
+/// 3. Save it
+#[derive(Debug, Clone)]
+pub struct RegisterWebhookUseCase<D: Database> {
+    pub db: Arc<D>,
+}
+
+impl<D: Database> RegisterWebhookUseCase<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(
+        &self,
+        url: String,
+        events: Vec<EventType>,
+        secret: Option<String>,
+        owner: Option<UserId>,
+    ) -> Result<Webhook, ApplicationError> {
+        let mut webhook = Webhook::new(url, events, secret)?;
+        if let Some(owner) = owner {
+            webhook = webhook.with_owner(owner);
+        }
+
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        tx.webhook_repository()
+            .save(&webhook)
+            .await
+            .map_err(ApplicationError::from_infra)?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        Ok(webhook)
+    }
+}
@@ -0,0 +1,193 @@
+use std::{collections::HashMap, sync::Arc};
+
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    application::{ApplicationError, use_cases::VerbChanges},
+    domain::model::{ActionLog, ActionType, Verb, VerbId, VerbState},
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Logs appended since a client's cursor, with the verbs they touch
+#[derive(Debug, Clone)]
+pub struct SyncChanges {
+    /// Current state of each verb changed in `logs`, in order of first change
+    pub verbs: Vec<Verb>,
+    /// Oldest first; includes `SyncConflict` records
+    pub logs: Vec<ActionLog>,
+    /// Position after the last log; the client's next `since`
+    pub cursor: u64,
+    pub has_more: bool,
+}
+
+/// A change a client made while offline
+#[derive(Debug, Clone)]
+pub struct SyncMutation {
+    /// Client-generated, echoed back in the outcome
+    pub id: Uuid,
+    /// Client-generated for creates
+    pub verb_id: VerbId,
+    /// When the client made the change; decides last-writer-wins
+    pub at: OffsetDateTime,
+    pub op: SyncOp,
+}
+
+#[derive(Debug, Clone)]
+pub enum SyncOp {
+    Create {
+        title: String,
+        description: String,
+    },
+    Update(VerbChanges),
+    Transition {
+        state: VerbState,
+        reason: Option<String>,
+    },
+}
+
+impl SyncOp {
+    /// Fields a conflict on this operation is about
+    pub fn field_names(&self) -> Vec<String> {
+        match self {
+            SyncOp::Create { .. } => vec!["title".to_string(), "description".to_string()],
+            SyncOp::Update(changes) => changes.field_names(),
+            SyncOp::Transition { .. } => vec!["state".to_string()],
+        }
+    }
+}
+
+/// What pushing one mutation did
+#[derive(Debug)]
+pub struct SyncOutcome {
+    pub mutation_id: Uuid,
+    pub verb_id: VerbId,
+    pub status: SyncStatus,
+    /// The verb once the mutation was handled, unless it was rejected
+    pub verb: Option<Verb>,
+}
+
+#[derive(Debug)]
+pub enum SyncStatus {
+    Applied,
+    /// Already applied (a create for an existing id, or a transition to
+    /// the current state): replaying a batch is safe
+    Duplicate,
+    /// The server changed the verb after the client's cursor; the later
+    /// change won and a `SyncConflict` log records it
+    Conflict {
+        client_won: bool,
+    },
+    Rejected(ApplicationError),
+}
+
+/// Use case: Read and record the action log for offline-first sync
+///
+/// The action log is the change feed: a client pulls every log appended
+/// since its cursor, and a pushed mutation conflicts when its verb has
+/// logs the client hadn't pulled.
+#[derive(Debug, Clone)]
+pub struct SyncUseCase<D: Database> {
+    pub db: Arc<D>,
+}
+
+impl<D: Database> SyncUseCase<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    /// Up to `limit` logs appended after `cursor`, and the verbs they touch
+    pub async fn pull(&self, cursor: u64, limit: u32) -> Result<SyncChanges, ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let changes = tx
+            .action_log_repository()
+            .since(cursor, limit)
+            .await
+            .map_err(ApplicationError::from_infra)?;
+
+        let verb_repo = tx.verb_repository();
+        let mut verbs: Vec<Verb> = Vec::new();
+        for log in &changes.logs {
+            if verbs.iter().any(|v| v.id() == log.verb_id()) {
+                continue;
+            }
+            if let Some(verb) = verb_repo
+                .find_by_id(log.verb_id())
+                .await
+                .map_err(ApplicationError::from_infra)?
+            {
+                verbs.push(verb);
+            }
+        }
+
+        Ok(SyncChanges {
+            verbs,
+            logs: changes.logs,
+            cursor: changes.cursor,
+            has_more: changes.has_more,
+        })
+    }
+
+    /// Time of the latest change to each verb appended after `cursor`
+    ///
+    /// Conflict records are not changes: a client that lost a conflict
+    /// shouldn't lose the next one because of it.
+    pub async fn changed_since(
+        &self,
+        cursor: u64,
+    ) -> Result<HashMap<VerbId, OffsetDateTime>, ApplicationError> {
+        const PAGE_SIZE: u32 = 500;
+
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+        let log_repo = tx.action_log_repository();
+
+        let mut changed: HashMap<VerbId, OffsetDateTime> = HashMap::new();
+        let mut cursor = cursor;
+        loop {
+            let page = log_repo
+                .since(cursor, PAGE_SIZE)
+                .await
+                .map_err(ApplicationError::from_infra)?;
+            for log in page
+                .logs
+                .iter()
+                .filter(|l| l.action_type() != ActionType::SyncConflict)
+            {
+                let latest = changed.entry(log.verb_id()).or_insert(log.timestamp());
+                *latest = (*latest).max(log.timestamp());
+            }
+            if !page.has_more {
+                return Ok(changed);
+            }
+            cursor = page.cursor;
+        }
+    }
+
+    /// Append a `SyncConflict` log
+    pub async fn record_conflict(&self, log: &ActionLog) -> Result<(), ApplicationError> {
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        tx.action_log_repository()
+            .append(log)
+            .await
+            .map_err(ApplicationError::from_infra)?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use time::OffsetDateTime;
+
+use crate::{
+    application::error::ApplicationError,
+    domain::{
+        DomainError,
+        model::{Description, Title, Verb, VerbId, VerbPatch},
+    },
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Requested edits, as they arrive from a client
+///
+/// Same merge-patch shape as [`VerbPatch`] but with raw values; the use
+/// case turns it into a validated patch.
+#[derive(Debug, Clone, Default)]
+pub struct VerbChanges {
+    pub title: Option<String>,
+    /// `Some(None)` clears the description (it becomes empty)
+    pub description: Option<Option<String>>,
+    pub context: Option<Option<String>>,
+    pub due_at: Option<Option<OffsetDateTime>>,
+}
+
+impl VerbChanges {
+    /// Names of the fields these changes touch, as action logs list them
+    pub fn field_names(&self) -> Vec<String> {
+        [
+            ("title", self.title.is_some()),
+            ("description", self.description.is_some()),
+            ("context", self.context.is_some()),
+            ("due_at", self.due_at.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, touched)| *touched)
+        .map(|(name, _)| name.to_string())
+        .collect()
+    }
+
+    fn into_patch(self) -> Result<VerbPatch, DomainError> {
+        Ok(VerbPatch {
+            title: self.title.map(Title::new).transpose()?,
+            description: self
+                .description
+                .map(|d| Description::new(d.unwrap_or_default()))
+                .transpose()?,
+            context: self.context,
+            due_at: self.due_at,
+        })
+    }
+}
+
+/// Use case: Edit a verb's fields (title, description, context, due date)
+///
+/// Responsibilities:
+/// 1. Validate the changes into a patch, then load verb
+/// 2. Apply patch (domain validates, produces action log if anything changed)
+/// 3. Save both atomically
+///
+/// A patch that changes nothing is not persisted and logs nothing.
+#[derive(Debug, Clone)]
+pub struct UpdateVerbUseCase<D: Database> {
+    pub db: Arc<D>,
+}
+
+impl<D: Database> UpdateVerbUseCase<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(
+        &self,
+        verb_id: VerbId,
+        changes: VerbChanges,
+    ) -> Result<Verb, ApplicationError> {
+        let patch = changes.into_patch()?;
+
+        let tx = self
+            .db
+            .begin_tx()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        let verb_repo = tx.verb_repository();
+        let log_repo = tx.action_log_repository();
+
+        let mut verb = verb_repo
+            .find_by_id(verb_id)
+            .await
+            .map_err(ApplicationError::from_infra)?
+            .ok_or(ApplicationError::NotFound)?;
+
+        let Some(action_log) = verb.apply_patch(patch)? else {
+            return Ok(verb);
+        };
+
+        verb_repo
+            .save(&verb)
+            .await
+            .map_err(ApplicationError::from_infra)?;
+
+        log_repo
+            .append(&action_log)
+            .await
+            .map_err(ApplicationError::from_infra)?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ApplicationError::Transaction(e.to_string()))?;
+
+        Ok(verb)
+    }
+}
@@ -0,0 +1,65 @@
+//! Application-level scenarios: facade → use case → repository
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    application::{ApplicationError, VerbFacade},
+    bench::Measurement,
+    domain::{
+        model::{VerbId, VerbState},
+        repository::VerbFilter,
+    },
+    infra::repository::in_memory::InMemoryDatabase,
+};
+
+pub(super) async fn run(iterations: usize) -> Result<Vec<(String, Measurement)>, ApplicationError> {
+    let facade = VerbFacade::new(std::sync::Arc::new(InMemoryDatabase::new()));
+    let mut ids: Vec<VerbId> = Vec::with_capacity(iterations);
+
+    let create = measure(iterations, async |i| {
+        let verb = facade
+            .create_verb(format!("Bench verb {i}"), String::new())
+            .await?;
+        ids.push(verb.id());
+        Ok(())
+    })
+    .await?;
+
+    // Each list sorts the whole store, so run fewer of them
+    let list = measure((iterations / 10).max(1), async |_| {
+        facade.list_verbs(VerbFilter::new().with_limit(50)).await?;
+        Ok(())
+    })
+    .await?;
+
+    let transition = measure(ids.len(), async |i| {
+        facade
+            .transition_verb(ids[i], VerbState::Active, None)
+            .await?;
+        Ok(())
+    })
+    .await?;
+
+    Ok(vec![
+        ("facade/create".into(), create),
+        ("facade/list".into(), list),
+        ("facade/transition".into(), transition),
+    ])
+}
+
+/// Run `op` `iterations` times back to back, timing each call
+async fn measure<F>(iterations: usize, mut op: F) -> Result<Measurement, ApplicationError>
+where
+    F: AsyncFnMut(usize) -> Result<(), ApplicationError>,
+{
+    let mut latencies: Vec<Duration> = Vec::with_capacity(iterations);
+    let start = Instant::now();
+
+    for i in 0..iterations {
+        let t = Instant::now();
+        op(i).await?;
+        latencies.push(t.elapsed());
+    }
+
+    Ok(Measurement::new(latencies, start.elapsed()))
+}
@@ -0,0 +1,139 @@
+//! HTTP scenarios: a closed-loop load generator against the real router
+//!
+//! The server runs in process on an ephemeral port, so results include
+//! the whole middleware stack and (de)serialization but no network hop.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde_json::{Value, json};
+use tokio::sync::Mutex;
+
+use crate::{
+    api,
+    bench::Measurement,
+    bootstrap::SharedState,
+    config::{AllowedOrigins, Config, SharedLimits},
+    error::AppResult,
+};
+
+pub(super) async fn run(
+    state: SharedState,
+    cfg: &Config,
+    iterations: usize,
+    concurrency: usize,
+) -> AppResult<Vec<(String, Measurement)>> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let base = format!("http://{}/api/v1/verbs", listener.local_addr()?);
+    let app = api::app(
+        state,
+        &SharedLimits::new(cfg.api_limits),
+        &AllowedOrigins::default(),
+        &cfg.http_log,
+        None,
+    );
+    let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let client = reqwest::Client::new();
+    let ids = Arc::new(Mutex::new(Vec::with_capacity(iterations)));
+
+    let create = {
+        let (client, base, ids) = (client.clone(), base.clone(), Arc::clone(&ids));
+        drive(iterations, concurrency, move |i| {
+            let (client, base, ids) = (client.clone(), base.clone(), Arc::clone(&ids));
+            async move {
+                let body: Value = client
+                    .post(&base)
+                    .json(&json!({ "title": format!("Bench verb {i}"), "description": "" }))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                let id = body["data"]["id"]
+                    .as_str()
+                    .context("create response without an id")?;
+                ids.lock().await.push(id.to_string());
+                Ok(())
+            }
+        })
+        .await?
+    };
+
+    let list = {
+        let (client, url) = (client.clone(), format!("{base}?limit=50"));
+        drive((iterations / 10).max(1), concurrency, move |_| {
+            let (client, url) = (client.clone(), url.clone());
+            async move {
+                client.get(&url).send().await?.error_for_status()?;
+                Ok(())
+            }
+        })
+        .await?
+    };
+
+    let ids = Arc::new(std::mem::take(&mut *ids.lock().await));
+    let transition = drive(ids.len(), concurrency, move |i| {
+        let (client, url) = (client.clone(), format!("{base}/{}/state", ids[i]));
+        async move {
+            client
+                .put(&url)
+                .json(&json!({ "state": "active" }))
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        }
+    })
+    .await?;
+
+    server.abort();
+
+    Ok(vec![
+        ("http/create".into(), create),
+        ("http/list".into(), list),
+        ("http/transition".into(), transition),
+    ])
+}
+
+/// Run `op` for indices `0..iterations` on `concurrency` workers, each
+/// issuing its next request as soon as the previous one is answered
+async fn drive<F, Fut>(iterations: usize, concurrency: usize, op: F) -> AppResult<Measurement>
+where
+    F: Fn(usize) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = AppResult<()>> + Send,
+{
+    let next = Arc::new(AtomicUsize::new(0));
+    let start = Instant::now();
+
+    let workers: Vec<_> = (0..concurrency.min(iterations))
+        .map(|_| {
+            let (op, next) = (op.clone(), Arc::clone(&next));
+            tokio::spawn(async move {
+                let mut latencies: Vec<Duration> = Vec::new();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= iterations {
+                        return AppResult::Ok(latencies);
+                    }
+                    let t = Instant::now();
+                    op(i).await?;
+                    latencies.push(t.elapsed());
+                }
+            })
+        })
+        .collect();
+
+    let mut latencies = Vec::with_capacity(iterations);
+    for worker in workers {
+        latencies.extend(worker.await??);
+    }
+
+    Ok(Measurement::new(latencies, start.elapsed()))
+}
@@ -0,0 +1,241 @@
+//! Throughput benchmarks
+//!
+//! `verb_beta bench` measures create / list / transition at two levels:
+//!
+//! - `facade/*`: straight through [`VerbFacade`](crate::application::VerbFacade)
+//!   on a fresh database, i.e. use cases + repositories with no HTTP
+//! - `http/*`: the real router on an ephemeral local port, driven by a
+//!   small closed-loop load generator with `--concurrency` workers
+//!
+//! Results print as a table. `--save <file>` keeps them as JSON and
+//! `--baseline <file>` compares against a saved run, failing when any
+//! scenario's throughput dropped by more than [`REGRESSION_TOLERANCE`].
+//!
+//! For statistically sound facade numbers per backend (in-memory, and
+//! in-memory behind the cache), use the criterion harness instead:
+//! `cargo bench --bench facade` (`benches/facade.rs`).
+//!
+//! Only the in-memory backend exists today; once a SQLite backend lands it
+//! belongs in both suites and in the criterion harness.
+
+mod facade;
+mod http;
+
+use std::{collections::BTreeMap, fmt, path::PathBuf, time::Duration};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{bootstrap::bootstrap, config::Config, error::AppResult};
+
+/// Throughput may drop this much (as a fraction) before a run fails
+/// against its baseline; wall-clock benchmarks are noisy
+pub const REGRESSION_TOLERANCE: f64 = 0.25;
+
+/// `verb_beta bench [--iterations N] [--concurrency N] [--save FILE] [--baseline FILE]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Operations per scenario (list scenarios run a tenth as many)
+    pub iterations: usize,
+    /// Concurrent HTTP workers
+    pub concurrency: usize,
+    pub save: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 2_000,
+            concurrency: 8,
+            save: None,
+            baseline: None,
+        }
+    }
+}
+
+impl BenchOptions {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{flag} expects a value"))?;
+            let count = || match value.parse::<usize>() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(format!("{flag} expects a positive number, got '{value}'")),
+            };
+            match flag.as_str() {
+                "--iterations" => options.iterations = count()?,
+                "--concurrency" => options.concurrency = count()?,
+                "--save" => options.save = Some(value.into()),
+                "--baseline" => options.baseline = Some(value.into()),
+                _ => return Err(format!("unknown bench option '{flag}'")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// One scenario's result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Measurement {
+    pub ops: usize,
+    pub ops_per_sec: f64,
+    pub p50_us: f64,
+    pub p99_us: f64,
+}
+
+impl Measurement {
+    /// Summarise per-operation `latencies` over a `wall` clock duration
+    fn new(mut latencies: Vec<Duration>, wall: Duration) -> Self {
+        latencies.sort_unstable();
+        let percentile = |p: f64| {
+            let index = ((latencies.len() as f64 * p).ceil() as usize).saturating_sub(1);
+            latencies.get(index).map_or(0.0, |d| d.as_secs_f64() * 1e6)
+        };
+
+        Self {
+            ops: latencies.len(),
+            ops_per_sec: latencies.len() as f64 / wall.as_secs_f64().max(f64::EPSILON),
+            p50_us: percentile(0.50),
+            p99_us: percentile(0.99),
+        }
+    }
+}
+
+/// Results by scenario name, e.g. `facade/create`
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Report(BTreeMap<String, Measurement>);
+
+impl Report {
+    /// Scenarios whose throughput fell below `baseline` by more than the
+    /// tolerance, as `(name, baseline ops/s, current ops/s)`
+    pub fn regressions(&self, baseline: &Report) -> Vec<(String, f64, f64)> {
+        self.0
+            .iter()
+            .filter_map(|(name, current)| {
+                let before = baseline.0.get(name)?;
+                (current.ops_per_sec < before.ops_per_sec * (1.0 - REGRESSION_TOLERANCE))
+                    .then(|| (name.clone(), before.ops_per_sec, current.ops_per_sec))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<20} {:>8} {:>12} {:>10} {:>10}",
+            "scenario", "ops", "ops/s", "p50 µs", "p99 µs"
+        )?;
+        for (name, m) in &self.0 {
+            writeln!(
+                f,
+                "{:<20} {:>8} {:>12.0} {:>10.1} {:>10.1}",
+                name, m.ops, m.ops_per_sec, m.p50_us, m.p99_us
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// `verb_beta bench`: run both suites, report, and check the baseline
+pub async fn run(cfg: &Config, options: BenchOptions) -> AppResult<()> {
+    let mut report = Report::default();
+
+    report.0.extend(facade::run(options.iterations).await?);
+
+    let state = bootstrap(cfg, None).await?;
+    report
+        .0
+        .extend(http::run(state, cfg, options.iterations, options.concurrency).await?);
+
+    print!("{report}");
+
+    if let Some(path) = &options.save {
+        let json = serde_json::to_string_pretty(&report)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write '{}'", path.display()))?;
+    }
+
+    if let Some(path) = &options.baseline {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline '{}'", path.display()))?;
+        let baseline: Report = serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid baseline '{}'", path.display()))?;
+
+        let regressions = report.regressions(&baseline);
+        for (name, before, now) in &regressions {
+            println!("REGRESSION {name}: {before:.0} → {now:.0} ops/s");
+        }
+        if !regressions.is_empty() {
+            anyhow::bail!("{} scenario(s) regressed", regressions.len());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(ops_per_sec: f64) -> Measurement {
+        Measurement {
+            ops: 1,
+            ops_per_sec,
+            p50_us: 0.0,
+            p99_us: 0.0,
+        }
+    }
+
+    #[test]
+    fn summarises_latencies() {
+        let latencies = (1..=100).map(Duration::from_micros).collect();
+
+        let m = Measurement::new(latencies, Duration::from_millis(50));
+
+        assert_eq!(m.ops, 100);
+        assert_eq!(m.ops_per_sec, 2_000.0);
+        assert_eq!(m.p50_us, 50.0);
+        assert_eq!(m.p99_us, 99.0);
+    }
+
+    #[test]
+    fn flags_only_drops_beyond_tolerance() {
+        let baseline = Report(BTreeMap::from([
+            ("a".to_string(), measurement(1000.0)),
+            ("b".to_string(), measurement(1000.0)),
+            ("gone".to_string(), measurement(1000.0)),
+        ]));
+        let current = Report(BTreeMap::from([
+            ("a".to_string(), measurement(800.0)),
+            ("b".to_string(), measurement(700.0)),
+            ("new".to_string(), measurement(1.0)),
+        ]));
+
+        let regressions = current.regressions(&baseline);
+
+        assert_eq!(regressions, [("b".to_string(), 1000.0, 700.0)]);
+    }
+
+    #[test]
+    fn parses_options() {
+        let args: Vec<String> = ["--iterations", "500", "--save", "out.json"]
+            .map(String::from)
+            .to_vec();
+
+        let options = BenchOptions::parse(&args).unwrap();
+
+        assert_eq!(options.iterations, 500);
+        assert_eq!(options.save, Some("out.json".into()));
+        assert!(BenchOptions::parse(&["--iterations".into()]).is_err());
+        assert!(BenchOptions::parse(&["--concurrency".into(), "0".into()]).is_err());
+    }
+}
@@ -1,107 +1,179 @@
-use std::sync::Arc;
+//! # Composition root
+//!
+//! Builds the one [`AppState`] the HTTP layer serves: database (behind the
+//! repository cache), webhook dispatcher, facades, blob store, notifier,
+//! feature flags and (optionally) a database snapshot and development
+//! fixtures. Everything past this
+//! point sees the state through [`SharedState`], so the concrete database
+//! type is chosen here and nowhere else.
 
-use axum::{
-    Json, Router,
-    extract::State,
-    http::{Response, StatusCode},
-    response::IntoResponse,
-    routing::post,
-};
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-use tokio::net::TcpListener;
-use tracing::{info, info_span, instrument};
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context;
+use tracing::{info, instrument};
 
 use crate::{
-    application::VerbFacade,
-    domain::{model::VerbId, repository::VerbRepository},
-    error::{AppError, AppResult},
-    infra::db::{Database, DatabaseBuilder},
+    api::AppState,
+    application::{
+        AdminFacade, AttachmentFacade, AuditFacade, BlobStore, EventPublisher, FeatureFlags,
+        NotificationPreferencesFacade, ReviewFacade, StatsFacade, SyncFacade, VerbFacade,
+        WebhookFacade,
+        seed::{self, Fixture},
+        use_cases::{AttachmentPolicy, ReviewPolicy},
+    },
+    config::{CacheConfig, Config, telemetry::LogLevelHandle},
+    domain::model::UserId,
+    error::AppResult,
+    infra::{
+        blob::{InMemoryBlobStore, LocalDiskBlobStore},
+        cache::InMemoryCache,
+        db::{DatabaseBuilder, Snapshot},
+        notify::SmtpNotifier,
+        repository::{
+            cached::{CachePolicy, CachedDatabase},
+            in_memory::InMemoryDatabase,
+        },
+        webhook::{DeliveryPolicy, WebhookDispatcher},
+    },
 };
 
-#[derive(Debug, Clone)]
-struct AppState<D: Database> {
-    pub app: Arc<VerbFacade<D>>,
-}
-
-#[instrument()]
-pub async fn bootstrap() -> AppResult<()> {
-    //start bootstrap server
-
-    let db = DatabaseBuilder::new().in_memory().build().await?;
-    let app = VerbFacade::new(Arc::from(db));
-    let state = AppState { app: Arc::new(app) };
-
-    let listener = TcpListener::bind("127.0.0.1:8080").await?;
-
-    let router = Router::new()
-        .route("/", post(create_verb))
-        .with_state(state);
+/// Database backend the server runs on; swap the inner type here to
+/// change storage. The cache in front is a pass-through unless
+/// `CACHE_ENABLED` is on.
+pub type AppDatabase = CachedDatabase<InMemoryDatabase>;
+
+/// Application state as served by the router
+pub type SharedState = AppState<AppDatabase>;
+
+/// Wire every dependency into the application state
+///
+/// Starts the webhook delivery worker as a side effect, so call it from
+/// within the runtime that will serve requests.
+#[instrument(skip_all)]
+pub async fn bootstrap(cfg: &Config, log_level: Option<LogLevelHandle>) -> AppResult<SharedState> {
+    // Step 1: Build database
+    info!("Building database...");
+    let db: InMemoryDatabase = DatabaseBuilder::new()
+        .in_memory() // Change to .sqlite("app.db") for persistence
+        .build()
+        .await
+        .context("Failed to build database")?;
+
+    let policy = cache_policy(&cfg.cache);
+    if policy.is_enabled() {
+        info!(?policy, "Caching repository lookups");
+    }
+    let db: AppDatabase = CachedDatabase::new(db, Arc::new(InMemoryCache::new()), policy);
+
+    let db = Arc::new(db);
+
+    // Step 2: Start webhook delivery worker
+    info!("Starting webhook dispatcher...");
+    let (publisher, events) = EventPublisher::channel();
+    let policy = DeliveryPolicy {
+        max_attempts: cfg.webhook_max_attempts,
+        timeout: Duration::from_secs(cfg.webhook_timeout_secs),
+        ..DeliveryPolicy::default()
+    };
+    WebhookDispatcher::new(Arc::clone(&db), policy)
+        .context("Failed to build webhook HTTP client")?
+        .spawn(events);
+
+    // Step 3: Create application facades
+    info!("Creating application facades...");
+    let facade = VerbFacade::new(Arc::clone(&db)).with_events(publisher);
+    let webhook_facade = WebhookFacade::new(Arc::clone(&db));
+    let admin_facade = AdminFacade::new(Arc::clone(&db));
+    let blobs: Arc<dyn BlobStore> = match &cfg.attachments.dir {
+        Some(dir) => Arc::new(
+            LocalDiskBlobStore::open(dir)
+                .await
+                .context("Failed to open attachment directory")?,
+        ),
+        None => Arc::new(InMemoryBlobStore::new()),
+    };
+    let attachment_facade = AttachmentFacade::new(
+        Arc::clone(&db),
+        blobs,
+        AttachmentPolicy {
+            max_bytes: cfg.attachments.max_bytes,
+            allowed_types: cfg.attachments.allowed_types.clone(),
+        },
+    );
+    let stats_facade = StatsFacade::new(Arc::clone(&db));
+    let preferences_facade = NotificationPreferencesFacade::new(Arc::clone(&db));
+    let audit_facade = AuditFacade::new(Arc::clone(&db));
+    let sync_facade = SyncFacade::new(Arc::clone(&db), facade.clone());
+    let mut review_facade = ReviewFacade::new(db).with_policy(ReviewPolicy {
+        stale_paused_after: time::Duration::days(cfg.review_stale_paused_days.into()),
+    });
+    if let Some(smtp) = &cfg.smtp {
+        let notifier = SmtpNotifier::new(smtp).context("Invalid SMTP configuration")?;
+        review_facade = review_facade.with_notifier(Arc::new(notifier));
+    }
+    if let Some(user_id) = cfg.review_user_id {
+        review_facade = review_facade.with_recipient(UserId::from_uuid(user_id));
+    }
 
-    // Start server
-    axum::serve(listener, router).await?;
+    // Step 3b: Restore a database dump, if configured
+    if let Some(path) = &cfg.snapshot_file {
+        info!(path = %path.display(), "Restoring snapshot...");
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open snapshot '{}'", path.display()))?;
+        let snapshot: Snapshot = serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Invalid snapshot '{}'", path.display()))?;
+        admin_facade
+            .restore_snapshot(snapshot)
+            .await
+            .context("Failed to restore snapshot")?;
+    }
 
-    Ok(())
-}
+    // Step 3c: Load development fixture, if configured
+    if let Some(path) = &cfg.seed_file {
+        info!(path = %path.display(), "Seeding database...");
+        let fixture = Fixture::from_path(path)?;
+        let report = seed::seed(&facade, &webhook_facade, fixture)
+            .await
+            .context("Failed to seed database")?;
+        info!(
+            verbs = report.verbs,
+            transitions = report.transitions,
+            webhooks = report.webhooks,
+            "Database seeded"
+        );
+    }
 
-#[derive(Debug, Clone, Deserialize)]
-struct CreateVerbDTO {
-    title: String,
-    description: String,
-}
+    // Step 4: Assemble state
+    let feature_flags =
+        FeatureFlags::from_overrides(&cfg.features).context("Invalid APP_FEATURES")?;
+    let mut state = AppState::new(
+        facade,
+        webhook_facade,
+        admin_facade,
+        attachment_facade,
+        review_facade,
+        stats_facade,
+        preferences_facade,
+        audit_facade,
+        sync_facade,
+    )
+    .with_feature_flags(feature_flags);
+    if let Some(log_level) = log_level {
+        state = state.with_log_level(log_level);
+    }
 
-#[derive(Debug, Clone, Serialize)]
-struct VerbDTOResponse {
-    success: bool,
-    id: VerbId,
+    Ok(state)
 }
 
-impl IntoResponse for VerbDTOResponse {
-    fn into_response(self) -> axum::response::Response {
-        let body = Json(json!({
-            "success": self.success,
-            "id": self.id
-        }));
-
-        (StatusCode::CREATED, body).into_response()
+/// TTL per entity type, or none at all with the cache turned off
+fn cache_policy(cfg: &CacheConfig) -> CachePolicy {
+    if !cfg.enabled {
+        return CachePolicy::default();
     }
-}
-
-// create verb handler
-#[instrument(skip(state))]
-async fn create_verb<D: Database>(
-    State(state): State<AppState<D>>,
-    Json(dto): Json<CreateVerbDTO>,
-) -> impl IntoResponse {
-    // Normally:
-    // 1. Validate input
-    // 2. Call application service / usecase
-    // 3. Return DTO
-
-    // Temporary mock logic
-    // Application call
-    let verb_res = state.app.create_verb(dto.title, dto.description).await;
-
-    match verb_res {
-        Ok(v) => {
-            tracing::info!(
-                verb_id = ?v,
-                "verb created successfully"
-            );
-            return VerbDTOResponse {
-                success: true,
-                id: v.id(),
-            }
-            .into_response();
-        }
-
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        )
-            .into_response(),
+    let ttl = |ttl: Duration| (!ttl.is_zero()).then_some(ttl);
+    CachePolicy {
+        verbs: ttl(cfg.verb_ttl),
+        webhooks: ttl(cfg.webhook_ttl),
+        notification_preferences: ttl(cfg.preferences_ttl),
     }
 }
-
-// HTTP DTO  → Application Command → Domain Model
-// Domain Result → Application DTO → HTTP Response DTO
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use super::{ConfigError, env, parse_env};
+
+/// Attachment storage and upload rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentConfig {
+    /// Directory for file contents (`ATTACHMENTS_DIR`); kept in memory
+    /// when unset
+    pub dir: Option<PathBuf>,
+    /// Largest accepted file (`ATTACHMENT_MAX_BYTES`, default 5 MiB).
+    /// Keep it below `ATTACHMENTS_MAX_BODY_BYTES`, which also has to fit
+    /// the multipart framing.
+    pub max_bytes: u64,
+    /// Accepted content types (`ATTACHMENT_TYPES`, comma separated);
+    /// `image/*` matches a whole top-level type
+    pub allowed_types: Vec<String>,
+}
+
+impl Default for AttachmentConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            max_bytes: 5 * 1024 * 1024,
+            allowed_types: ["image/*", "text/plain", "text/markdown", "application/pdf"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+impl AttachmentConfig {
+    pub(super) fn from_env() -> Result<Self, ConfigError> {
+        let defaults = Self::default();
+
+        let max_bytes = parse_env("ATTACHMENT_MAX_BYTES")?.unwrap_or(defaults.max_bytes);
+        if max_bytes == 0 {
+            return Err(ConfigError::EnvironmentVariable {
+                key: "ATTACHMENT_MAX_BYTES".into(),
+                reason: "must be at least 1".into(),
+            });
+        }
+
+        let allowed_types = env("ATTACHMENT_TYPES")
+            .map(|raw| {
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_ascii_lowercase)
+                    .collect()
+            })
+            .unwrap_or(defaults.allowed_types);
+
+        Ok(Self {
+            dir: env("ATTACHMENTS_DIR")
+                .filter(|d| !d.is_empty())
+                .map(PathBuf::from),
+            max_bytes,
+            allowed_types,
+        })
+    }
+}
@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use super::{ConfigError, parse_env};
+
+/// Read-through repository cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Put the cache in front of the repositories (`CACHE_ENABLED`,
+    /// default off)
+    pub enabled: bool,
+    /// How long a verb stays cached (`CACHE_VERB_TTL_SECS`, default 30)
+    pub verb_ttl: Duration,
+    /// How long a webhook stays cached (`CACHE_WEBHOOK_TTL_SECS`,
+    /// default 300)
+    pub webhook_ttl: Duration,
+    /// How long a user's notification preferences stay cached
+    /// (`CACHE_PREFERENCES_TTL_SECS`, default 300)
+    pub preferences_ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            verb_ttl: Duration::from_secs(30),
+            webhook_ttl: Duration::from_secs(300),
+            preferences_ttl: Duration::from_secs(300),
+        }
+    }
+}
+
+impl CacheConfig {
+    /// A TTL of 0 leaves that entity type uncached
+    pub(super) fn from_env() -> Result<Self, ConfigError> {
+        let defaults = Self::default();
+        let ttl = |key: &str, default: Duration| -> Result<Duration, ConfigError> {
+            Ok(parse_env(key)?.map(Duration::from_secs).unwrap_or(default))
+        };
+
+        Ok(Self {
+            enabled: parse_env("CACHE_ENABLED")?.unwrap_or(defaults.enabled),
+            verb_ttl: ttl("CACHE_VERB_TTL_SECS", defaults.verb_ttl)?,
+            webhook_ttl: ttl("CACHE_WEBHOOK_TTL_SECS", defaults.webhook_ttl)?,
+            preferences_ttl: ttl("CACHE_PREFERENCES_TTL_SECS", defaults.preferences_ttl)?,
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::{Arc, RwLock};
+
+use axum::http::HeaderValue;
+
+use super::ConfigError;
+
+/// Parse `CORS_ALLOWED_ORIGINS`, e.g.
+/// `https://app.example.com,http://localhost:5173`
+pub(super) fn parse_origins(raw: &str) -> Result<Vec<String>, ConfigError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .map(|origin| {
+            let scheme_ok = origin.starts_with("http://") || origin.starts_with("https://");
+            if !scheme_ok || origin.ends_with('/') || HeaderValue::from_str(origin).is_err() {
+                return Err(ConfigError::EnvironmentVariable {
+                    key: "CORS_ALLOWED_ORIGINS".into(),
+                    reason: format!("expected an origin like https://example.com, got '{origin}'"),
+                });
+            }
+            Ok(origin.to_string())
+        })
+        .collect()
+}
+
+/// Origins browsers may call the API from, replaced when the config file
+/// is reloaded; empty allows no cross-origin requests
+#[derive(Debug, Clone, Default)]
+pub struct AllowedOrigins(Arc<RwLock<Vec<String>>>);
+
+impl AllowedOrigins {
+    pub fn new(origins: Vec<String>) -> Self {
+        Self(Arc::new(RwLock::new(origins)))
+    }
+
+    pub fn allows(&self, origin: &HeaderValue) -> bool {
+        let origins = self.0.read().unwrap_or_else(|e| e.into_inner());
+        origin
+            .to_str()
+            .is_ok_and(|origin| origins.iter().any(|allowed| allowed == origin))
+    }
+
+    pub fn set(&self, origins: Vec<String>) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = origins;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_origin_lists() {
+        assert_eq!(
+            parse_origins(" https://app.example.com, http://localhost:5173 ,").unwrap(),
+            ["https://app.example.com", "http://localhost:5173"]
+        );
+        assert!(parse_origins("app.example.com").is_err());
+        assert!(parse_origins("https://app.example.com/").is_err());
+    }
+
+    #[test]
+    fn allows_exact_origins_only() {
+        let origins = AllowedOrigins::new(vec!["https://app.example.com".into()]);
+
+        assert!(origins.allows(&HeaderValue::from_static("https://app.example.com")));
+        assert!(!origins.allows(&HeaderValue::from_static("https://evil.example.com")));
+
+        origins.set(Vec::new());
+        assert!(!origins.allows(&HeaderValue::from_static("https://app.example.com")));
+    }
+}
@@ -25,6 +25,9 @@ pub enum ConfigError {
 
     #[error("Host '{host}:{port}' did not resolve to any socket addresses")]
     NoResolvedAddresses { host: String, port: u16 },
+
+    #[error("Failed to read config file '{path}': {reason}")]
+    ConfigFile { path: String, reason: String },
 }
 
 // impl fmt::Display for ConfigError {
@@ -0,0 +1,65 @@
+use super::{ConfigError, Environment, env, parse_env};
+
+/// Fields whose values are replaced before a body is logged
+const DEFAULT_REDACTED: &[&str] = &["title", "description", "context", "reason", "secret"];
+
+/// What the HTTP logging middleware records
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpLogConfig {
+    /// Log request and response bodies (`HTTP_LOG_BODIES`).
+    /// Defaults to on outside production; cannot be enabled in production.
+    pub log_bodies: bool,
+    /// Bodies larger than this are summarised instead of logged
+    /// (`HTTP_LOG_MAX_BODY_BYTES`)
+    pub max_body_bytes: usize,
+    /// JSON keys whose values are redacted at any depth, compared
+    /// case-insensitively (`HTTP_LOG_REDACT`, comma separated)
+    pub redact_fields: Vec<String>,
+}
+
+impl Default for HttpLogConfig {
+    fn default() -> Self {
+        Self {
+            log_bodies: false,
+            max_body_bytes: 4096,
+            redact_fields: DEFAULT_REDACTED.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+}
+
+impl HttpLogConfig {
+    pub(super) fn from_env(environment: Environment) -> Result<Self, ConfigError> {
+        let defaults = Self::default();
+
+        let log_bodies = parse_env("HTTP_LOG_BODIES")?.unwrap_or(!environment.is_production());
+        if log_bodies && environment.is_production() {
+            return Err(ConfigError::EnvironmentVariable {
+                key: "HTTP_LOG_BODIES".into(),
+                reason: "body logging is not allowed in production".into(),
+            });
+        }
+
+        let redact_fields = env("HTTP_LOG_REDACT")
+            .map(|raw| {
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or(defaults.redact_fields);
+
+        Ok(Self {
+            log_bodies,
+            max_body_bytes: parse_env("HTTP_LOG_MAX_BODY_BYTES")?
+                .unwrap_or(defaults.max_body_bytes),
+            redact_fields,
+        })
+    }
+
+    pub fn is_redacted(&self, field: &str) -> bool {
+        self.redact_fields
+            .iter()
+            .any(|f| f.eq_ignore_ascii_case(field))
+    }
+}
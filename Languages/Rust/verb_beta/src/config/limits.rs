@@ -0,0 +1,138 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use super::{ConfigError, Lookup, parse_var};
+
+/// Request limits applied to one group of routes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteLimits {
+    /// Time allowed to produce a response before answering 408
+    pub timeout: Duration,
+    /// Largest accepted request body before answering 413
+    pub max_body_bytes: usize,
+}
+
+impl RouteLimits {
+    /// Read `<PREFIX>_TIMEOUT_SECS` and `<PREFIX>_MAX_BODY_BYTES`,
+    /// falling back to `defaults`
+    fn from_vars(vars: Lookup, prefix: &str, defaults: Self) -> Result<Self, ConfigError> {
+        let timeout_key = format!("{prefix}_TIMEOUT_SECS");
+        let body_key = format!("{prefix}_MAX_BODY_BYTES");
+
+        let timeout = parse_var(vars, &timeout_key)?
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.timeout);
+        let max_body_bytes = parse_var(vars, &body_key)?.unwrap_or(defaults.max_body_bytes);
+
+        if timeout.is_zero() {
+            return Err(ConfigError::EnvironmentVariable {
+                key: timeout_key,
+                reason: "must be at least 1".into(),
+            });
+        }
+        if max_body_bytes == 0 {
+            return Err(ConfigError::EnvironmentVariable {
+                key: body_key,
+                reason: "must be at least 1".into(),
+            });
+        }
+
+        Ok(Self {
+            timeout,
+            max_body_bytes,
+        })
+    }
+}
+
+/// Per route group limits for the HTTP API
+///
+/// | Group          | Variables                                                  | Default      |
+/// |----------------|------------------------------------------------------------|--------------|
+/// | `/verbs`       | `VERBS_TIMEOUT_SECS`, `VERBS_MAX_BODY_BYTES`               | 10 s, 64 KiB |
+/// | `/webhooks`    | `WEBHOOKS_TIMEOUT_SECS`, `WEBHOOKS_MAX_BODY_BYTES`         | 10 s, 16 KiB |
+/// | `/admin`       | `ADMIN_TIMEOUT_SECS`, `ADMIN_MAX_BODY_BYTES`               | 10 s, 4 KiB  |
+/// | `/attachments` | `ATTACHMENTS_TIMEOUT_SECS`, `ATTACHMENTS_MAX_BODY_BYTES`   | 30 s, 6 MiB  |
+/// | snapshots      | `SNAPSHOT_TIMEOUT_SECS`, `SNAPSHOT_MAX_BODY_BYTES`         | 30 s, 32 MiB |
+///
+/// The attachment limits cover uploads (`/verbs/{id}/attachments`) and
+/// downloads (`/attachments/{id}`); the snapshot limits cover
+/// `/admin/snapshot`, whose body is a whole database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiLimits {
+    pub verbs: RouteLimits,
+    pub webhooks: RouteLimits,
+    pub admin: RouteLimits,
+    pub attachments: RouteLimits,
+    pub snapshot: RouteLimits,
+}
+
+impl Default for ApiLimits {
+    fn default() -> Self {
+        Self {
+            verbs: RouteLimits {
+                timeout: Duration::from_secs(10),
+                max_body_bytes: 64 * 1024,
+            },
+            webhooks: RouteLimits {
+                timeout: Duration::from_secs(10),
+                max_body_bytes: 16 * 1024,
+            },
+            admin: RouteLimits {
+                timeout: Duration::from_secs(10),
+                max_body_bytes: 4 * 1024,
+            },
+            attachments: RouteLimits {
+                timeout: Duration::from_secs(30),
+                max_body_bytes: 6 * 1024 * 1024,
+            },
+            snapshot: RouteLimits {
+                timeout: Duration::from_secs(30),
+                max_body_bytes: 32 * 1024 * 1024,
+            },
+        }
+    }
+}
+
+impl ApiLimits {
+    const PREFIXES: [&str; 5] = ["VERBS", "WEBHOOKS", "ADMIN", "ATTACHMENTS", "SNAPSHOT"];
+
+    pub(super) fn from_vars(vars: Lookup) -> Result<Self, ConfigError> {
+        let defaults = Self::default();
+
+        Ok(Self {
+            verbs: RouteLimits::from_vars(vars, "VERBS", defaults.verbs)?,
+            webhooks: RouteLimits::from_vars(vars, "WEBHOOKS", defaults.webhooks)?,
+            admin: RouteLimits::from_vars(vars, "ADMIN", defaults.admin)?,
+            attachments: RouteLimits::from_vars(vars, "ATTACHMENTS", defaults.attachments)?,
+            snapshot: RouteLimits::from_vars(vars, "SNAPSHOT", defaults.snapshot)?,
+        })
+    }
+
+    /// Whether `key` is one of the variables read by [`ApiLimits::from_vars`]
+    pub(super) fn is_limit_key(key: &str) -> bool {
+        Self::PREFIXES.iter().any(|prefix| {
+            key.strip_prefix(prefix)
+                .is_some_and(|rest| rest == "_TIMEOUT_SECS" || rest == "_MAX_BODY_BYTES")
+        })
+    }
+}
+
+/// The limits in force, replaced when the config file is reloaded
+#[derive(Debug, Clone)]
+pub struct SharedLimits(Arc<RwLock<ApiLimits>>);
+
+impl SharedLimits {
+    pub fn new(limits: ApiLimits) -> Self {
+        Self(Arc::new(RwLock::new(limits)))
+    }
+
+    pub fn get(&self) -> ApiLimits {
+        *self.0.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set(&self, limits: ApiLimits) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = limits;
+    }
+}
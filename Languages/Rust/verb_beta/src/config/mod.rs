@@ -1,11 +1,32 @@
+mod attachments;
+mod cache;
+mod cors;
 mod environment;
 mod error;
+mod http_log;
+mod limits;
+mod reload;
+mod smtp;
 pub mod telemetry;
 
+pub use attachments::AttachmentConfig;
+pub use cache::CacheConfig;
+pub use cors::AllowedOrigins;
 pub use error::ConfigError;
+pub use http_log::HttpLogConfig;
+pub use limits::{ApiLimits, RouteLimits, SharedLimits};
+pub use reload::{ConfigFile, ReloadableSettings};
+pub use smtp::{SmtpConfig, SmtpTls};
 
-use self::environment::Environment;
-use std::net::{SocketAddr, ToSocketAddrs};
+pub use self::environment::Environment;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::{SocketAddr, ToSocketAddrs},
+    path::PathBuf,
+};
+
+/// Reads a variable by name
+type Lookup<'a> = &'a dyn Fn(&str) -> Option<String>;
 
 #[derive(Debug)]
 pub struct Config {
@@ -13,13 +34,61 @@ pub struct Config {
     pub port: u16,
     // pub rust_log: String,
     pub environment: Environment,
-    // db_url
+    /// SQLite database url for migrations (`DATABASE_URL`), e.g. `sqlite://verb.db`
+    pub database_url: Option<String>,
+    /// Apply pending migrations at startup (`APP_AUTO_MIGRATE`).
+    /// Defaults to on, except in production where pending migrations
+    /// stop the server instead.
+    pub auto_migrate: bool,
+    /// Fixture loaded through the application layer at startup
+    /// (`APP_SEED_FILE`); not allowed in production
+    pub seed_file: Option<PathBuf>,
+    /// Attempts per webhook delivery, including the first (`WEBHOOK_MAX_ATTEMPTS`)
+    pub webhook_max_attempts: u32,
+    /// Per-request timeout for webhook delivery (`WEBHOOK_TIMEOUT_SECS`)
+    pub webhook_timeout_secs: u64,
+    /// Timeouts and body-size limits per route group
+    pub api_limits: ApiLimits,
+    /// Request/response logging
+    pub http_log: HttpLogConfig,
+    /// Bearer token for the `/admin` routes (`ADMIN_TOKEN`);
+    /// the admin API is disabled without one
+    pub admin_token: Option<String>,
+    /// Initial feature flag values (`APP_FEATURES`), e.g.
+    /// `webhooks=off,verb_editing=on`; unset flags default to on
+    pub features: BTreeMap<String, bool>,
+    /// Mail transport for the daily review; `None` without `SMTP_HOST`
+    pub smtp: Option<SmtpConfig>,
+    /// Days a verb may stay paused before the daily review flags it
+    /// (`REVIEW_STALE_PAUSED_DAYS`)
+    pub review_stale_paused_days: u32,
+    /// User the daily review is sent for (`REVIEW_USER_ID`); sending
+    /// follows their notification preferences
+    pub review_user_id: Option<uuid::Uuid>,
+    /// Attachment storage and upload rules
+    pub attachments: AttachmentConfig,
+    /// Read-through repository cache
+    pub cache: CacheConfig,
+    /// Database dump restored at startup (`APP_SNAPSHOT_FILE`, or
+    /// `serve --snapshot`); not allowed in production
+    pub snapshot_file: Option<PathBuf>,
+    /// Origins browsers may call the API from (`CORS_ALLOWED_ORIGINS`),
+    /// comma-separated; none by default
+    pub cors_origins: Vec<String>,
+    /// The `.env` file read at startup, if any; watched for hot reload
+    pub config_file: Option<ConfigFile>,
 }
 
 impl Config {
     pub fn load() -> Result<Self, ConfigError> {
+        // Variables already set win over the file, now and on reload
+        let pinned: BTreeSet<String> = std::env::vars_os()
+            .filter_map(|(key, _)| key.into_string().ok())
+            .collect();
         // Load dotenv early (safe no-op in prod)
-        dotenvy::dotenv().ok();
+        let config_file = dotenvy::dotenv()
+            .ok()
+            .map(|path| ConfigFile::new(path, pinned));
 
         let environment = Environment::from_env();
 
@@ -39,11 +108,83 @@ impl Config {
             None => 3000,
         };
 
-        Ok(Self {
+        let database_url = env("DATABASE_URL");
+        let auto_migrate = parse_env("APP_AUTO_MIGRATE")?.unwrap_or(!environment.is_production());
+
+        let seed_file = env("APP_SEED_FILE").map(PathBuf::from);
+        if seed_file.is_some() && environment.is_production() {
+            return Err(ConfigError::EnvironmentVariable {
+                key: "APP_SEED_FILE".into(),
+                reason: "seeding is not allowed in production".into(),
+            });
+        }
+
+        let webhook_max_attempts = parse_env("WEBHOOK_MAX_ATTEMPTS")?.unwrap_or(5);
+        if webhook_max_attempts == 0 {
+            return Err(ConfigError::EnvironmentVariable {
+                key: "WEBHOOK_MAX_ATTEMPTS".into(),
+                reason: "must be at least 1".into(),
+            });
+        }
+        let webhook_timeout_secs = parse_env("WEBHOOK_TIMEOUT_SECS")?.unwrap_or(10);
+        let api_limits = ApiLimits::from_vars(&env)?;
+        let http_log = HttpLogConfig::from_env(environment)?;
+
+        let admin_token = env("ADMIN_TOKEN").filter(|t| !t.is_empty());
+        let features = env("APP_FEATURES")
+            .map(|raw| parse_features(&raw))
+            .transpose()?
+            .unwrap_or_default();
+
+        let smtp = SmtpConfig::from_env()?;
+        let review_stale_paused_days = parse_env("REVIEW_STALE_PAUSED_DAYS")?.unwrap_or(7);
+        let review_user_id = parse_env("REVIEW_USER_ID")?;
+
+        let attachments = AttachmentConfig::from_env()?;
+        let cache = CacheConfig::from_env()?;
+        let cors_origins = env("CORS_ALLOWED_ORIGINS")
+            .map(|raw| cors::parse_origins(&raw))
+            .transpose()?
+            .unwrap_or_default();
+
+        let config = Self {
             host,
             port,
             environment,
-        })
+            database_url,
+            auto_migrate,
+            seed_file,
+            webhook_max_attempts,
+            webhook_timeout_secs,
+            api_limits,
+            http_log,
+            admin_token,
+            features,
+            smtp,
+            review_stale_paused_days,
+            review_user_id,
+            attachments,
+            cache,
+            snapshot_file: None,
+            cors_origins,
+            config_file,
+        };
+        match env("APP_SNAPSHOT_FILE") {
+            Some(path) => config.with_snapshot_file(path.into()),
+            None => Ok(config),
+        }
+    }
+
+    /// Restore `path` at startup; refused in production, like seeding
+    pub fn with_snapshot_file(mut self, path: PathBuf) -> Result<Self, ConfigError> {
+        if self.environment.is_production() {
+            return Err(ConfigError::EnvironmentVariable {
+                key: "APP_SNAPSHOT_FILE".into(),
+                reason: "restoring snapshots is not allowed in production".into(),
+            });
+        }
+        self.snapshot_file = Some(path);
+        Ok(self)
     }
 
     /// Select a single address for binding (IPv4 preferred)
@@ -83,7 +224,52 @@ fn env(key: &str) -> Option<String> {
     std::env::var(key).ok()
 }
 
-// logging level
+/// Parse an optional numeric variable; present-but-invalid is an error
+fn parse_env<T>(key: &str) -> Result<Option<T>, ConfigError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    parse_var(&env, key)
+}
+
+/// [`parse_env`], reading from `vars`
+fn parse_var<T>(vars: Lookup, key: &str) -> Result<Option<T>, ConfigError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    vars(key)
+        .map(|raw| {
+            raw.parse()
+                .map_err(|e: T::Err| ConfigError::EnvironmentVariable {
+                    key: key.into(),
+                    reason: format!("invalid value '{raw}': {e}"),
+                })
+        })
+        .transpose()
+}
+
+/// Parse `name=on|off` pairs; flag names are checked by `FeatureFlags`
+fn parse_features(raw: &str) -> Result<BTreeMap<String, bool>, ConfigError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let invalid = || ConfigError::EnvironmentVariable {
+                key: "APP_FEATURES".into(),
+                reason: format!("expected name=on|off, got '{pair}'"),
+            };
+            let (name, value) = pair.split_once('=').ok_or_else(invalid)?;
+            let enabled = match value.trim() {
+                "on" | "true" | "1" => true,
+                "off" | "false" | "0" => false,
+                _ => return Err(invalid()),
+            };
+            Ok((name.trim().to_string(), enabled))
+        })
+        .collect()
+}
 
 // debug endpoints
 
@@ -91,7 +277,5 @@ fn env(key: &str) -> Option<String> {
 
 // CORS rules
 
-// feature flags
-
 // TODO: use a builder pattern to allow better testing
 // TODO: unit tests for config
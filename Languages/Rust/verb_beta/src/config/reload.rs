@@ -0,0 +1,197 @@
+//! Settings that can change while the server runs
+//!
+//! The `.env` file read at startup is re-read when it changes. As at
+//! startup, variables set in the process environment win over the file.
+//! Only [`ReloadableSettings`] are applied; a change to anything else is
+//! reported and waits for a restart.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use super::{ApiLimits, Config, ConfigError, Lookup, cors, env, parse_features};
+
+/// The config file and which of its variables the environment overrides
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    path: PathBuf,
+    /// Variables set before the file was read
+    pinned: BTreeSet<String>,
+}
+
+impl ConfigFile {
+    pub fn new(path: PathBuf, pinned: BTreeSet<String>) -> Self {
+        Self { path, pinned }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The variables the file sets now
+    pub fn read(&self) -> Result<BTreeMap<String, String>, ConfigError> {
+        dotenvy::from_path_iter(&self.path)
+            .and_then(|vars| vars.collect())
+            .map_err(|e| ConfigError::ConfigFile {
+                path: self.path.display().to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// Value of a variable, given the file's current `vars`
+    pub fn lookup<'a>(
+        &'a self,
+        vars: &'a BTreeMap<String, String>,
+    ) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            if self.pinned.contains(key) {
+                env(key)
+            } else {
+                vars.get(key).cloned()
+            }
+        }
+    }
+
+    /// Variables whose effective value differs between two readings
+    pub fn changed_keys(
+        &self,
+        before: &BTreeMap<String, String>,
+        after: &BTreeMap<String, String>,
+    ) -> BTreeSet<String> {
+        before
+            .keys()
+            .chain(after.keys())
+            .filter(|key| !self.pinned.contains(*key) && before.get(*key) != after.get(*key))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Settings applied without a restart when the config file changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReloadableSettings {
+    /// `RUST_LOG`; `None` for the environment's default filter
+    pub log_filter: Option<String>,
+    pub api_limits: ApiLimits,
+    /// `APP_FEATURES`
+    pub features: BTreeMap<String, bool>,
+    /// `CORS_ALLOWED_ORIGINS`
+    pub cors_origins: Vec<String>,
+}
+
+impl ReloadableSettings {
+    /// The settings the server started with
+    pub fn from_config(cfg: &Config) -> Self {
+        Self {
+            log_filter: env("RUST_LOG"),
+            api_limits: cfg.api_limits,
+            features: cfg.features.clone(),
+            cors_origins: cfg.cors_origins.clone(),
+        }
+    }
+
+    /// Read the settings from `vars`, validated as at startup
+    pub fn from_vars(vars: Lookup) -> Result<Self, ConfigError> {
+        Ok(Self {
+            log_filter: vars("RUST_LOG"),
+            api_limits: ApiLimits::from_vars(vars)?,
+            features: vars("APP_FEATURES")
+                .map(|raw| parse_features(&raw))
+                .transpose()?
+                .unwrap_or_default(),
+            cors_origins: vars("CORS_ALLOWED_ORIGINS")
+                .map(|raw| cors::parse_origins(&raw))
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Whether a change to `key` can be applied without a restart
+    pub fn is_reloadable(key: &str) -> bool {
+        matches!(key, "RUST_LOG" | "APP_FEATURES" | "CORS_ALLOWED_ORIGINS")
+            || ApiLimits::is_limit_key(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn file(pinned: &[&str]) -> ConfigFile {
+        ConfigFile::new(
+            PathBuf::from(".env"),
+            pinned.iter().map(|k| k.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn pinned_variables_are_not_reported_as_changed() {
+        let file = file(&["APP_PORT"]);
+        let before = vars(&[("APP_PORT", "3000"), ("RUST_LOG", "info")]);
+        let after = vars(&[("APP_PORT", "4000"), ("APP_FEATURES", "webhooks=off")]);
+
+        assert_eq!(
+            file.changed_keys(&before, &after),
+            BTreeSet::from(["APP_FEATURES".to_string(), "RUST_LOG".to_string()])
+        );
+    }
+
+    #[test]
+    fn reads_settings_from_the_file() {
+        let file = file(&[]);
+        let current = vars(&[
+            ("APP_FEATURES", "webhooks=off"),
+            ("VERBS_TIMEOUT_SECS", "3"),
+            ("CORS_ALLOWED_ORIGINS", "https://app.example.com"),
+        ]);
+
+        let settings = ReloadableSettings::from_vars(&file.lookup(&current)).unwrap();
+
+        assert_eq!(settings.log_filter, None);
+        assert_eq!(
+            settings.features,
+            BTreeMap::from([("webhooks".into(), false)])
+        );
+        assert_eq!(settings.api_limits.verbs.timeout, Duration::from_secs(3));
+        assert_eq!(settings.cors_origins, ["https://app.example.com"]);
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        let file = file(&[]);
+        let current = vars(&[("ADMIN_MAX_BODY_BYTES", "0")]);
+
+        let err = ReloadableSettings::from_vars(&file.lookup(&current)).unwrap_err();
+        assert!(err.to_string().contains("ADMIN_MAX_BODY_BYTES"), "{err}");
+    }
+
+    #[test]
+    fn only_runtime_settings_are_reloadable() {
+        for key in [
+            "RUST_LOG",
+            "APP_FEATURES",
+            "SNAPSHOT_MAX_BODY_BYTES",
+            "VERBS_TIMEOUT_SECS",
+        ] {
+            assert!(ReloadableSettings::is_reloadable(key), "{key}");
+        }
+        for key in [
+            "APP_PORT",
+            "WEBHOOK_TIMEOUT_SECS",
+            "ADMIN_TOKEN",
+            "VERBS_TIMEOUT",
+        ] {
+            assert!(!ReloadableSettings::is_reloadable(key), "{key}");
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+
+use super::{ConfigError, env, parse_env};
+
+/// How the SMTP connection is secured (`SMTP_TLS`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Upgrade a plain connection with STARTTLS (`starttls`, default, port 587)
+    StartTls,
+    /// TLS from the first byte (`tls`, port 465)
+    Implicit,
+    /// No encryption (`none`); only for local mail catchers
+    None,
+}
+
+/// Outbound mail for the daily review digest
+///
+/// Enabled by setting `SMTP_HOST`; `SMTP_FROM` and `REVIEW_EMAIL_TO`
+/// (comma separated) are then required.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// `SMTP_PORT`, defaulting to the usual port for `tls`
+    pub port: u16,
+    pub tls: SmtpTls,
+    /// `SMTP_USERNAME` / `SMTP_PASSWORD`; both or neither
+    pub credentials: Option<(String, String)>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+impl SmtpConfig {
+    pub(super) fn from_env() -> Result<Option<Self>, ConfigError> {
+        let Some(host) = env("SMTP_HOST").filter(|h| !h.is_empty()) else {
+            return Ok(None);
+        };
+
+        let tls = match env("SMTP_TLS").as_deref() {
+            None | Some("starttls") => SmtpTls::StartTls,
+            Some("tls") => SmtpTls::Implicit,
+            Some("none") => SmtpTls::None,
+            Some(other) => {
+                return Err(ConfigError::EnvironmentVariable {
+                    key: "SMTP_TLS".into(),
+                    reason: format!("expected starttls, tls or none, got '{other}'"),
+                });
+            }
+        };
+        let port = parse_env("SMTP_PORT")?.unwrap_or(match tls {
+            SmtpTls::StartTls => 587,
+            SmtpTls::Implicit => 465,
+            SmtpTls::None => 25,
+        });
+
+        let credentials = match (env("SMTP_USERNAME"), env("SMTP_PASSWORD")) {
+            (Some(user), Some(pass)) => Some((user, pass)),
+            (None, None) => None,
+            _ => {
+                return Err(ConfigError::EnvironmentVariable {
+                    key: "SMTP_USERNAME".into(),
+                    reason: "SMTP_USERNAME and SMTP_PASSWORD must be set together".into(),
+                });
+            }
+        };
+
+        let required = |key: &str| {
+            env(key)
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| ConfigError::EnvironmentVariable {
+                    key: key.into(),
+                    reason: "required when SMTP_HOST is set".into(),
+                })
+        };
+        let from = required("SMTP_FROM")?;
+        let to = required("REVIEW_EMAIL_TO")?
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        Ok(Some(Self {
+            host,
+            port,
+            tls,
+            credentials,
+            from,
+            to,
+        }))
+    }
+}
+
+/// Keeps the password out of logs
+impl fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("username", &self.credentials.as_ref().map(|(user, _)| user))
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .finish()
+    }
+}
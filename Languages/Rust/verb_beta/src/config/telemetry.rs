@@ -1,7 +1,7 @@
 use tracing::info;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::{EnvFilter, fmt};
+use tracing_subscriber::{EnvFilter, Registry, fmt, reload};
 
 use super::{Config, Environment};
 
@@ -14,22 +14,16 @@ use super::{Config, Environment};
 /// # Panics
 ///
 /// Panics if called more than once (global subscriber can only be set once)
-pub fn init_tracing(cfg: &Config) {
+pub fn init_tracing(cfg: &Config) -> LogLevelHandle {
     // --Get Environment from cfg.environment
     let environment = cfg.environment;
 
     // Build filter from RUST_LOG, with environment-aware defaults
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-        let default_level = match environment {
-            Environment::Production => "info",
-            Environment::UAT => "debug",
-            Environment::Development => "debug",
-        };
+    let env_filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_filter(environment)));
 
-        EnvFilter::new(format!(
-            "verb={default_level},tower_http=debug,axum=debug,{default_level}"
-        ))
-    });
+    // Reloadable, so the filter can be changed at runtime (see `LogLevelHandle`)
+    let (env_filter, handle) = reload::Layer::new(env_filter);
 
     match environment {
         Environment::Production => {
@@ -66,4 +60,36 @@ pub fn init_tracing(cfg: &Config) {
         environment = %environment,
         "Tracing initialized"
     );
+
+    LogLevelHandle(handle)
+}
+
+/// Filter used when `RUST_LOG` is unset
+pub fn default_filter(environment: Environment) -> String {
+    let default_level = match environment {
+        Environment::Production => "info",
+        Environment::UAT => "debug",
+        Environment::Development => "debug",
+    };
+
+    format!("verb={default_level},tower_http=debug,axum=debug,{default_level}")
+}
+
+/// Changes the active log filter of the running process
+#[derive(Clone)]
+pub struct LogLevelHandle(reload::Handle<EnvFilter, Registry>);
+
+impl LogLevelHandle {
+    /// The active filter, in `RUST_LOG` syntax
+    pub fn current(&self) -> Option<String> {
+        self.0.with_current(|filter| filter.to_string()).ok()
+    }
+
+    /// Replace the filter, e.g. `"info,verb=trace"`
+    pub fn set(&self, directives: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+        self.0.reload(filter).map_err(|e| e.to_string())?;
+        info!(filter = directives, "Log filter changed");
+        Ok(())
+    }
 }
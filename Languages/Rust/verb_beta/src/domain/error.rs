@@ -26,10 +26,22 @@ pub enum DomainError {
     #[error("Title cannot exceed 200 characters")]
     VerbTitleTooLong,
 
+    ///Verb: title must be a single line of printable text
+    #[error("Title cannot contain control characters or line breaks")]
+    VerbTitleInvalidCharacters,
+
     ///Verb: description should be <=2000
     #[error("Description cannot exceed 2000 characters")]
     VerbDescriptionTooLong,
 
+    ///Verb: description allows line breaks and tabs, no other control characters
+    #[error("Description cannot contain control characters other than line breaks and tabs")]
+    VerbDescriptionInvalidCharacters,
+
+    ///Verb: context should be <=1000
+    #[error("Context cannot exceed 1000 characters")]
+    VerbContextTooLong,
+
     ///Verb: Invalid state
     #[error("Invalid state value: {0}")]
     VerbInvalidState(String),
@@ -44,4 +56,52 @@ pub enum DomainError {
     ///Action_Log: reason given for paused and dropped states should be <=500
     #[error("Reason cannot exceed 500 characters")]
     ReasonTooLong,
+
+    // Webhook Specific Errors
+    //
+    ///Webhook: callback url must be an absolute http(s) url
+    #[error("Invalid webhook url: {0}")]
+    WebhookInvalidUrl(String),
+
+    ///Webhook: must subscribe to at least one event
+    #[error("Webhook must subscribe to at least one event")]
+    WebhookNoEvents,
+
+    ///Webhook: unknown event type name
+    #[error("Unknown webhook event: {0}")]
+    WebhookUnknownEvent(String),
+
+    ///Webhook: an explicit secret cannot be blank
+    #[error("Webhook secret cannot be empty")]
+    WebhookEmptySecret,
+
+    // Attachment Specific Errors
+    //
+    ///Attachment: file has no content
+    #[error("Attachment cannot be empty")]
+    AttachmentEmpty,
+
+    ///Attachment: file name is blank, a path, or too long
+    #[error("Invalid attachment file name: {0:?}")]
+    AttachmentInvalidFilename(String),
+
+    ///Attachment: content type is not `type/subtype`
+    #[error("Invalid attachment content type: {0:?}")]
+    AttachmentInvalidContentType(String),
+
+    // Notification Preferences Specific Errors
+    //
+    ///Preferences: unknown notification topic name
+    #[error("Unknown notification topic: {0}")]
+    PreferencesUnknownTopic(String),
+
+    ///Preferences: quiet hours must be a non-empty `HH:MM-HH:MM` window
+    #[error("Invalid quiet hours: {0:?}")]
+    PreferencesInvalidQuietHours(String),
+
+    // Audit Specific Errors
+    //
+    ///Audit: actor must be `admin`, `anonymous` or `user:<uuid>`
+    #[error("Invalid actor {0:?}, expected admin, anonymous or user:<uuid>")]
+    AuditInvalidActor(String),
 }
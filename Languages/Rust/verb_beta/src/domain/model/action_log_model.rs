@@ -9,7 +9,8 @@ use crate::domain::DomainError;
 use super::{VerbId, VerbState};
 
 /// Strongly-typed ActionLog identifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct ActionLogId(Uuid);
 
 impl ActionLogId {
@@ -35,8 +36,9 @@ impl fmt::Display for ActionLogId {
 /// Immutable record of a state change.
 ///
 /// Action logs form an append-only audit trail.
-/// They are created by the domain when state transitions occur.
-#[derive(Debug, Clone)]
+/// They are created by the domain when state transitions occur
+/// or when verb fields are edited.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionLog {
     id: ActionLogId,
     verb_id: VerbId,
@@ -44,6 +46,8 @@ pub struct ActionLog {
     from_state: Option<VerbState>,
     to_state: VerbState,
     reason: Option<String>,
+    changed_fields: Vec<String>,
+    #[serde(with = "time::serde::rfc3339")]
     timestamp: OffsetDateTime,
 }
 
@@ -55,6 +59,10 @@ pub enum ActionType {
     Paused,
     Completed,
     Dropped,
+    /// Title, description, context or due date edited (state unchanged)
+    UpdatedFields,
+    /// An offline client's change raced a server change (see `sync_conflict`)
+    SyncConflict,
 }
 
 impl ActionType {
@@ -65,6 +73,8 @@ impl ActionType {
             ActionType::Paused => "Paused",
             ActionType::Completed => "Completed",
             ActionType::Dropped => "Dropped",
+            ActionType::UpdatedFields => "UpdatedFields",
+            ActionType::SyncConflict => "SyncConflict",
         }
     }
 
@@ -75,6 +85,8 @@ impl ActionType {
             "Paused" => Ok(ActionType::Paused),
             "Completed" => Ok(ActionType::Completed),
             "Dropped" => Ok(ActionType::Dropped),
+            "UpdatedFields" => Ok(ActionType::UpdatedFields),
+            "SyncConflict" => Ok(ActionType::SyncConflict),
             _ => Err(DomainError::VerbInvalidState(s.to_string())),
         }
     }
@@ -108,6 +120,7 @@ impl ActionLog {
             from_state: None,
             to_state: VerbState::Captured,
             reason: None,
+            changed_fields: Vec::new(),
             timestamp: OffsetDateTime::now_utc(),
         }
     }
@@ -128,6 +141,46 @@ impl ActionLog {
             from_state,
             to_state,
             reason,
+            changed_fields: Vec::new(),
+            timestamp: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Create action log for an edit of verb fields
+    ///
+    /// The verb's state is unchanged, so `from_state == to_state`.
+    pub fn fields_updated(verb_id: VerbId, state: VerbState, changed_fields: Vec<String>) -> Self {
+        Self {
+            id: ActionLogId::new(),
+            verb_id,
+            action_type: ActionType::UpdatedFields,
+            from_state: Some(state),
+            to_state: state,
+            reason: None,
+            changed_fields,
+            timestamp: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Record a sync conflict: a client mutation and a server change made
+    /// the same verb diverge, and last-writer-wins picked one of them.
+    ///
+    /// `state` is the verb's state once the conflict was resolved; `reason`
+    /// says which side won.
+    pub fn sync_conflict(
+        verb_id: VerbId,
+        state: VerbState,
+        changed_fields: Vec<String>,
+        reason: String,
+    ) -> Self {
+        Self {
+            id: ActionLogId::new(),
+            verb_id,
+            action_type: ActionType::SyncConflict,
+            from_state: Some(state),
+            to_state: state,
+            reason: Some(reason),
+            changed_fields,
             timestamp: OffsetDateTime::now_utc(),
         }
     }
@@ -146,6 +199,7 @@ impl ActionLog {
     }
 
     /// Reconstruct from persistence
+    #[allow(clippy::too_many_arguments)]
     pub fn from_parts(
         id: ActionLogId,
         verb_id: VerbId,
@@ -153,6 +207,7 @@ impl ActionLog {
         from_state: Option<VerbState>,
         to_state: VerbState,
         reason: Option<String>,
+        changed_fields: Vec<String>,
         timestamp: OffsetDateTime,
     ) -> Self {
         Self {
@@ -162,6 +217,7 @@ impl ActionLog {
             from_state,
             to_state,
             reason,
+            changed_fields,
             timestamp,
         }
     }
@@ -186,6 +242,10 @@ impl ActionLog {
     pub fn reason(&self) -> Option<&str> {
         self.reason.as_deref()
     }
+    /// Names of edited fields (only for `UpdatedFields` and `SyncConflict`)
+    pub fn changed_fields(&self) -> &[String] {
+        &self.changed_fields
+    }
     pub fn timestamp(&self) -> OffsetDateTime {
         self.timestamp
     }
@@ -324,6 +384,40 @@ mod tests {
         assert_eq!(log.action_type(), ActionType::Activated);
     }
 
+    #[test]
+    fn fields_updated_keeps_state_and_lists_fields() {
+        let verb_id = dummy_verb_id();
+        let log = ActionLog::fields_updated(
+            verb_id,
+            VerbState::Active,
+            vec!["title".into(), "due_at".into()],
+        );
+
+        assert_eq!(log.action_type(), ActionType::UpdatedFields);
+        assert_eq!(log.from_state(), Some(VerbState::Active));
+        assert_eq!(log.to_state(), VerbState::Active);
+        assert_eq!(log.changed_fields(), ["title", "due_at"]);
+    }
+
+    #[test]
+    fn sync_conflict_keeps_state_and_records_reason() {
+        let verb_id = dummy_verb_id();
+        let log = ActionLog::sync_conflict(
+            verb_id,
+            VerbState::Paused,
+            vec!["title".into()],
+            "server change kept".into(),
+        );
+
+        assert_eq!(log.action_type(), ActionType::SyncConflict);
+        assert_eq!(log.to_state(), VerbState::Paused);
+        assert_eq!(log.reason(), Some("server change kept"));
+        assert_eq!(
+            ActionType::from_str(log.action_type().as_str()).unwrap(),
+            ActionType::SyncConflict
+        );
+    }
+
     // --------------------------------------------------
     // from_parts()
     // --------------------------------------------------
@@ -341,6 +435,7 @@ mod tests {
             Some(VerbState::Active),
             VerbState::Done,
             None,
+            Vec::new(),
             timestamp,
         );
 
@@ -0,0 +1,217 @@
+//! Attachment Model
+//!
+//! A file attached to a verb. Only metadata lives here; the bytes are kept
+//! in a blob store under [`Attachment::blob_key`].
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::DomainError;
+
+use super::VerbId;
+
+// ============================================================================
+// Value Objects
+// ============================================================================
+/// Strongly-typed identity for an Attachment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AttachmentId(Uuid);
+
+impl AttachmentId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl fmt::Display for AttachmentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for AttachmentId {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(Uuid::parse_str(s)?))
+    }
+}
+
+/// Client-supplied file name, reduced to its last path component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+struct FileName(String);
+
+impl FileName {
+    fn new(value: &str) -> Result<Self, DomainError> {
+        let name = value.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+        if name.is_empty()
+            || name == "."
+            || name == ".."
+            || name.chars().count() > 255
+            || name.chars().any(char::is_control)
+        {
+            return Err(DomainError::AttachmentInvalidFilename(value.to_string()));
+        }
+        Ok(Self(name.to_string()))
+    }
+}
+
+impl TryFrom<String> for FileName {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
+impl From<FileName> for String {
+    fn from(value: FileName) -> Self {
+        value.0
+    }
+}
+
+// ============================================================================
+// Attachment Entity
+// ============================================================================
+/// Metadata of a file attached to a verb.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    id: AttachmentId,
+    verb_id: VerbId,
+    filename: FileName,
+    content_type: String,
+    size: u64,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+}
+
+impl Attachment {
+    /// Describe a new attachment.
+    ///
+    /// Enforces:
+    /// - Non-empty file
+    /// - File name without path components or control characters, <=255 chars
+    /// - A `type/subtype` content type (parameters are dropped)
+    pub fn new(
+        verb_id: VerbId,
+        filename: &str,
+        content_type: &str,
+        size: u64,
+    ) -> Result<Self, DomainError> {
+        if size == 0 {
+            return Err(DomainError::AttachmentEmpty);
+        }
+
+        Ok(Self {
+            id: AttachmentId::new(),
+            verb_id,
+            filename: FileName::new(filename)?,
+            content_type: essence(content_type)?,
+            size,
+            created_at: OffsetDateTime::now_utc(),
+        })
+    }
+
+    pub fn id(&self) -> AttachmentId {
+        self.id
+    }
+    pub fn verb_id(&self) -> VerbId {
+        self.verb_id
+    }
+    pub fn filename(&self) -> &str {
+        &self.filename.0
+    }
+    /// Lower-cased `type/subtype`
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+    pub fn created_at(&self) -> OffsetDateTime {
+        self.created_at
+    }
+
+    /// Key of the file contents in the blob store
+    pub fn blob_key(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+/// `Text/Plain; charset=utf-8` -> `text/plain`
+fn essence(content_type: &str) -> Result<String, DomainError> {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let valid = essence.split_once('/').is_some_and(|(kind, sub)| {
+        let token = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+        };
+        token(kind) && token(sub)
+    });
+
+    if valid && essence.len() <= 255 {
+        Ok(essence)
+    } else {
+        Err(DomainError::AttachmentInvalidContentType(
+            content_type.to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_attachment_normalises_name_and_type() {
+        let attachment = Attachment::new(
+            VerbId::new(),
+            "C:\\Users\\me\\notes.txt",
+            "Text/Plain; charset=utf-8",
+            12,
+        )
+        .unwrap();
+
+        assert_eq!(attachment.filename(), "notes.txt");
+        assert_eq!(attachment.content_type(), "text/plain");
+        assert_eq!(attachment.blob_key(), attachment.id().to_string());
+    }
+
+    #[test]
+    fn new_attachment_rejects_invalid_input() {
+        let verb = VerbId::new();
+
+        assert!(matches!(
+            Attachment::new(verb, "a.txt", "text/plain", 0),
+            Err(DomainError::AttachmentEmpty)
+        ));
+        for name in ["", "dir/", "..", "bad\u{0}.txt"] {
+            assert!(
+                matches!(
+                    Attachment::new(verb, name, "text/plain", 1),
+                    Err(DomainError::AttachmentInvalidFilename(_))
+                ),
+                "{name:?}"
+            );
+        }
+        for content_type in ["", "text", "text/", "text/plain\r\nX: y"] {
+            assert!(
+                matches!(
+                    Attachment::new(verb, "a.txt", content_type, 1),
+                    Err(DomainError::AttachmentInvalidContentType(_))
+                ),
+                "{content_type:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::{fmt, net::IpAddr, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::DomainError;
+
+use super::UserId;
+
+/// Strongly-typed AuditEntry identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuditEntryId(Uuid);
+
+impl AuditEntryId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl fmt::Display for AuditEntryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Who made a request
+///
+/// Written as `admin`, `anonymous` or `user:<uuid>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Actor {
+    /// An operator, authenticated with the admin token
+    Admin,
+    /// A caller identifying as a user
+    User(UserId),
+    /// Nobody we could identify
+    Anonymous,
+}
+
+impl fmt::Display for Actor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Actor::Admin => write!(f, "admin"),
+            Actor::User(id) => write!(f, "user:{id}"),
+            Actor::Anonymous => write!(f, "anonymous"),
+        }
+    }
+}
+
+impl FromStr for Actor {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(Actor::Admin),
+            "anonymous" => Ok(Actor::Anonymous),
+            _ => s
+                .strip_prefix("user:")
+                .and_then(|id| id.parse().ok())
+                .map(Actor::User)
+                .ok_or_else(|| DomainError::AuditInvalidActor(s.to_string())),
+        }
+    }
+}
+
+/// The record a request touched, e.g. `verb` `6f1c…`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityRef {
+    pub kind: String,
+    pub id: String,
+}
+
+impl EntityRef {
+    pub fn new(kind: impl Into<String>, id: impl fmt::Display) -> Self {
+        Self {
+            kind: kind.into(),
+            id: id.to_string(),
+        }
+    }
+}
+
+/// Who did what, from where.
+///
+/// One entry per mutating request, or per entity it changed. Unlike
+/// [`ActionLog`](super::ActionLog), which records what happened to a verb
+/// as part of the domain, the audit log records requests: it covers every
+/// resource, keeps failed attempts, and is never restored from or written
+/// into snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    id: AuditEntryId,
+    actor: Actor,
+    ip: Option<IpAddr>,
+    method: String,
+    endpoint: String,
+    status: u16,
+    entity: Option<EntityRef>,
+    before: Option<Value>,
+    after: Option<Value>,
+    timestamp: OffsetDateTime,
+}
+
+impl AuditEntry {
+    /// Record a request answered with `status`, with no entity yet
+    pub fn new(
+        actor: Actor,
+        ip: Option<IpAddr>,
+        method: impl Into<String>,
+        endpoint: impl Into<String>,
+        status: u16,
+    ) -> Self {
+        Self {
+            id: AuditEntryId::new(),
+            actor,
+            ip,
+            method: method.into(),
+            endpoint: endpoint.into(),
+            status,
+            entity: None,
+            before: None,
+            after: None,
+            timestamp: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// The entity the request changed, as it was before and after
+    ///
+    /// `before` is `None` for creations, `after` for deletions.
+    pub fn with_change(
+        mut self,
+        entity: EntityRef,
+        before: Option<Value>,
+        after: Option<Value>,
+    ) -> Self {
+        self.entity = Some(entity);
+        self.before = before;
+        self.after = after;
+        self
+    }
+
+    // Getters
+    pub fn id(&self) -> AuditEntryId {
+        self.id
+    }
+    pub fn actor(&self) -> Actor {
+        self.actor
+    }
+    pub fn ip(&self) -> Option<IpAddr> {
+        self.ip
+    }
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+    /// Route template, e.g. `/api/v1/verbs/{id}`
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+    pub fn entity(&self) -> Option<&EntityRef> {
+        self.entity.as_ref()
+    }
+    pub fn before(&self) -> Option<&Value> {
+        self.before.as_ref()
+    }
+    pub fn after(&self) -> Option<&Value> {
+        self.after.as_ref()
+    }
+    pub fn timestamp(&self) -> OffsetDateTime {
+        self.timestamp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn actor_round_trips_through_its_string_form() {
+        let user = Actor::User(UserId::from_uuid(Uuid::new_v4()));
+
+        for actor in [Actor::Admin, Actor::Anonymous, user] {
+            assert_eq!(actor.to_string().parse::<Actor>().unwrap(), actor);
+        }
+        for bad in ["", "root", "user:", "user:42"] {
+            assert!(bad.parse::<Actor>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn with_change_sets_entity_and_snapshots() {
+        let entry = AuditEntry::new(Actor::Admin, None, "PUT", "/admin/features/{name}", 200)
+            .with_change(
+                EntityRef::new("feature", "webhooks"),
+                Some(json!(true)),
+                Some(json!(false)),
+            );
+
+        assert_eq!(entry.entity(), Some(&EntityRef::new("feature", "webhooks")));
+        assert_eq!(entry.before(), Some(&json!(true)));
+        assert_eq!(entry.after(), Some(&json!(false)));
+        assert_eq!(entry.status(), 200);
+    }
+}
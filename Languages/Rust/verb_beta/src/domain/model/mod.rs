@@ -1,8 +1,18 @@
 //!Domain Model
 //!
 pub mod action_log_model;
+pub mod attachment_model;
+pub mod audit_model;
+pub mod notification_preferences_model;
 pub mod task_model;
 pub mod verb_model;
+pub mod webhook_model;
 
 pub use action_log_model::{ActionLog, ActionLogId, ActionType};
-pub use verb_model::{Verb, VerbId, VerbState};
+pub use attachment_model::{Attachment, AttachmentId};
+pub use audit_model::{Actor, AuditEntry, EntityRef};
+pub use notification_preferences_model::{
+    Channel, NotificationPreferences, NotificationTopic, QuietHours, UserId,
+};
+pub use verb_model::{Description, Title, Verb, VerbId, VerbPatch, VerbState};
+pub use webhook_model::{EventType, Webhook, WebhookDelivery, WebhookId};
@@ -0,0 +1,438 @@
+//! Notification Preferences Model
+//!
+//! How and when a user wants to be notified: which channels may be used,
+//! which topics they care about, and a daily quiet window in their own
+//! UTC offset. Delivery code
+//! asks [`NotificationPreferences::allows`] before sending anything on a
+//! user's behalf.
+//!
+//! Users are managed outside this service; a [`UserId`] is an opaque UUID.
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, UtcOffset};
+use uuid::Uuid;
+
+use crate::domain::DomainError;
+
+use super::EventType;
+
+// ============================================================================
+// Value Objects
+// ============================================================================
+/// Strongly-typed identity for the user notifications are sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UserId(Uuid);
+
+impl UserId {
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for UserId {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(Uuid::parse_str(s)?))
+    }
+}
+
+// ============================================================================
+
+/// Ways a notification can reach a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Channel {
+    /// The daily review digest mail
+    Email,
+    /// Event callbacks to webhooks the user registered
+    Webhook,
+    /// Live push to connected clients
+    WebSocket,
+}
+
+impl Channel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Email => "email",
+            Channel::Webhook => "webhook",
+            Channel::WebSocket => "websocket",
+        }
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a notification is about: a verb event or the daily digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "&'static str")]
+pub enum NotificationTopic {
+    /// A verb was captured
+    VerbCreated,
+    /// A verb was completed
+    VerbDone,
+    /// The daily review digest
+    DailyReview,
+}
+
+impl NotificationTopic {
+    pub const ALL: [NotificationTopic; 3] = [
+        NotificationTopic::VerbCreated,
+        NotificationTopic::VerbDone,
+        NotificationTopic::DailyReview,
+    ];
+
+    /// Wire name; verb events use their webhook event name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationTopic::VerbCreated => EventType::VerbCreated.as_str(),
+            NotificationTopic::VerbDone => EventType::VerbDone.as_str(),
+            NotificationTopic::DailyReview => "review.daily",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        Self::ALL
+            .into_iter()
+            .find(|topic| topic.as_str() == s)
+            .ok_or_else(|| DomainError::PreferencesUnknownTopic(s.to_string()))
+    }
+}
+
+impl From<EventType> for NotificationTopic {
+    fn from(event: EventType) -> Self {
+        match event {
+            EventType::VerbCreated => NotificationTopic::VerbCreated,
+            EventType::VerbDone => NotificationTopic::VerbDone,
+        }
+    }
+}
+
+impl TryFrom<String> for NotificationTopic {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<NotificationTopic> for &'static str {
+    fn from(topic: NotificationTopic) -> Self {
+        topic.as_str()
+    }
+}
+
+impl fmt::Display for NotificationTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ============================================================================
+
+/// A daily window, in local time, during which nothing is sent.
+///
+/// Written as `HH:MM-HH:MM`; the window may wrap midnight
+/// (`22:00-07:00`). Start is inclusive, end exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct QuietHours {
+    /// Minutes after midnight
+    start: u16,
+    end: u16,
+}
+
+impl QuietHours {
+    /// Build from `HH:MM` start and end times.
+    ///
+    /// Enforces:
+    /// - Both times are valid 24-hour `HH:MM`
+    /// - The window is not empty (start != end)
+    pub fn new(start: &str, end: &str) -> Result<Self, DomainError> {
+        let invalid = || DomainError::PreferencesInvalidQuietHours(format!("{start}-{end}"));
+        let start_minute = parse_minute(start).ok_or_else(invalid)?;
+        let end_minute = parse_minute(end).ok_or_else(invalid)?;
+        if start_minute == end_minute {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            start: start_minute,
+            end: end_minute,
+        })
+    }
+
+    pub fn start(&self) -> String {
+        format_minute(self.start)
+    }
+    pub fn end(&self) -> String {
+        format_minute(self.end)
+    }
+
+    /// Whether `at`, read on a clock at `offset`, falls inside the window
+    pub fn contains(&self, at: OffsetDateTime, offset: UtcOffset) -> bool {
+        let at = at.to_offset(offset);
+        let minute = u16::from(at.hour()) * 60 + u16::from(at.minute());
+        if self.start < self.end {
+            (self.start..self.end).contains(&minute)
+        } else {
+            minute >= self.start || minute < self.end
+        }
+    }
+}
+
+fn parse_minute(s: &str) -> Option<u16> {
+    let (hour, minute) = s.split_once(':')?;
+    if hour.len() != 2 || minute.len() != 2 {
+        return None;
+    }
+    let hour: u16 = hour.parse().ok()?;
+    let minute: u16 = minute.parse().ok()?;
+    (hour < 24 && minute < 60).then_some(hour * 60 + minute)
+}
+
+fn format_minute(minute: u16) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+impl TryFrom<String> for QuietHours {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let (start, end) = value
+            .split_once('-')
+            .ok_or_else(|| DomainError::PreferencesInvalidQuietHours(value.clone()))?;
+        Self::new(start, end)
+    }
+}
+
+impl From<QuietHours> for String {
+    fn from(value: QuietHours) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start(), self.end())
+    }
+}
+
+// ============================================================================
+// Notification Preferences Entity
+// ============================================================================
+/// A user's notification settings.
+///
+/// Channels and topics are stored as what the user switched *off*, so
+/// everything is on by default and topics added later start enabled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    user_id: UserId,
+    disabled_channels: Vec<Channel>,
+    muted_topics: Vec<NotificationTopic>,
+    quiet_hours: Option<QuietHours>,
+    /// Offset quiet hours are read in; UTC for preferences stored before
+    /// it was recorded
+    #[serde(default = "utc")]
+    utc_offset: UtcOffset,
+    #[serde(with = "time::serde::rfc3339")]
+    updated_at: OffsetDateTime,
+}
+
+fn utc() -> UtcOffset {
+    UtcOffset::UTC
+}
+
+impl NotificationPreferences {
+    /// Preferences with every channel and topic on and no quiet hours;
+    /// what applies to users who never saved any
+    pub fn defaults(user_id: UserId) -> Self {
+        Self {
+            user_id,
+            disabled_channels: Vec::new(),
+            muted_topics: Vec::new(),
+            quiet_hours: None,
+            utc_offset: UtcOffset::UTC,
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Switch `channel` on or off
+    pub fn set_channel(&mut self, channel: Channel, enabled: bool) {
+        self.disabled_channels.retain(|c| *c != channel);
+        if !enabled {
+            self.disabled_channels.push(channel);
+        }
+        self.touch();
+    }
+
+    /// Subscribe to or mute `topic`
+    pub fn set_topic(&mut self, topic: NotificationTopic, enabled: bool) {
+        self.muted_topics.retain(|t| *t != topic);
+        if !enabled {
+            self.muted_topics.push(topic);
+        }
+        self.touch();
+    }
+
+    pub fn set_quiet_hours(&mut self, quiet_hours: Option<QuietHours>) {
+        self.quiet_hours = quiet_hours;
+        self.touch();
+    }
+
+    /// Read quiet hours at `utc_offset`, the user's local offset
+    pub fn set_utc_offset(&mut self, utc_offset: UtcOffset) {
+        self.utc_offset = utc_offset;
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = OffsetDateTime::now_utc();
+    }
+
+    pub fn user_id(&self) -> UserId {
+        self.user_id
+    }
+    pub fn channel_enabled(&self, channel: Channel) -> bool {
+        !self.disabled_channels.contains(&channel)
+    }
+    pub fn topic_enabled(&self, topic: NotificationTopic) -> bool {
+        !self.muted_topics.contains(&topic)
+    }
+    pub fn quiet_hours(&self) -> Option<QuietHours> {
+        self.quiet_hours
+    }
+    pub fn utc_offset(&self) -> UtcOffset {
+        self.utc_offset
+    }
+    pub fn updated_at(&self) -> OffsetDateTime {
+        self.updated_at
+    }
+
+    /// Whether a `topic` notification may go out over `channel` at `at`
+    pub fn allows(&self, channel: Channel, topic: NotificationTopic, at: OffsetDateTime) -> bool {
+        self.channel_enabled(channel)
+            && self.topic_enabled(topic)
+            && !self
+                .quiet_hours
+                .is_some_and(|q| q.contains(at, self.utc_offset))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use time::format_description::well_known::Rfc3339;
+
+    use super::*;
+
+    fn at(s: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(s, &Rfc3339).unwrap()
+    }
+
+    fn hours(h: i8) -> UtcOffset {
+        UtcOffset::from_hms(h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight() {
+        let utc = UtcOffset::UTC;
+        let night = QuietHours::new("22:00", "07:00").unwrap();
+        assert!(night.contains(at("2026-03-01T23:30:00Z"), utc));
+        assert!(night.contains(at("2026-03-02T06:59:00Z"), utc));
+        assert!(!night.contains(at("2026-03-02T07:00:00Z"), utc));
+        assert!(!night.contains(at("2026-03-02T12:00:00Z"), utc));
+        // Compared at the given offset, whatever the offset of `at`
+        assert!(night.contains(at("2026-03-02T01:00:00+02:00"), utc));
+        assert!(!night.contains(at("2026-03-01T23:30:00Z"), hours(-5)));
+
+        let lunch = QuietHours::new("12:00", "13:30").unwrap();
+        assert!(lunch.contains(at("2026-03-01T12:00:00Z"), utc));
+        assert!(!lunch.contains(at("2026-03-01T13:30:00Z"), utc));
+        assert_eq!(lunch.to_string(), "12:00-13:30");
+    }
+
+    #[test]
+    fn quiet_hours_reject_bad_times() {
+        for (start, end) in [("24:00", "07:00"), ("22:60", "07:00"), ("7:00", "08:00")] {
+            assert!(matches!(
+                QuietHours::new(start, end),
+                Err(DomainError::PreferencesInvalidQuietHours(_))
+            ));
+        }
+        assert!(QuietHours::new("08:00", "08:00").is_err(), "empty window");
+        assert!(QuietHours::try_from("22:00".to_string()).is_err());
+    }
+
+    #[test]
+    fn allows_checks_channel_topic_and_quiet_hours() {
+        let mut prefs = NotificationPreferences::defaults(UserId::from_uuid(Uuid::new_v4()));
+        let noon = at("2026-03-01T12:00:00Z");
+        assert!(prefs.allows(Channel::Email, NotificationTopic::DailyReview, noon));
+
+        prefs.set_channel(Channel::Webhook, false);
+        prefs.set_topic(NotificationTopic::VerbCreated, false);
+        prefs.set_quiet_hours(Some(QuietHours::new("11:00", "13:00").unwrap()));
+
+        assert!(!prefs.allows(Channel::Webhook, NotificationTopic::VerbDone, noon));
+        assert!(!prefs.allows(Channel::Email, NotificationTopic::VerbCreated, noon));
+        assert!(!prefs.allows(Channel::Email, NotificationTopic::DailyReview, noon));
+        assert!(prefs.allows(
+            Channel::Email,
+            NotificationTopic::DailyReview,
+            at("2026-03-01T14:00:00Z")
+        ));
+
+        prefs.set_channel(Channel::Webhook, true);
+        assert!(prefs.channel_enabled(Channel::Webhook));
+    }
+
+    #[test]
+    fn quiet_hours_are_read_at_the_users_offset() {
+        let mut prefs = NotificationPreferences::defaults(UserId::from_uuid(Uuid::new_v4()));
+        prefs.set_quiet_hours(Some(QuietHours::new("22:00", "07:00").unwrap()));
+        prefs.set_utc_offset(hours(9));
+        let allows = |at| prefs.allows(Channel::Email, NotificationTopic::DailyReview, at);
+
+        // 23:30 in Tokyo
+        assert!(!allows(at("2026-03-01T14:30:00Z")));
+        // 23:30 UTC is 08:30 in Tokyo
+        assert!(allows(at("2026-03-01T23:30:00Z")));
+
+        // Stored before the offset was recorded: read in UTC
+        let json = serde_json::to_value(&prefs).unwrap();
+        let mut legacy = json.as_object().unwrap().clone();
+        legacy.remove("utc_offset");
+        let legacy: NotificationPreferences = serde_json::from_value(legacy.into()).unwrap();
+        assert_eq!(legacy.utc_offset(), UtcOffset::UTC);
+        let round_trip: NotificationPreferences = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.utc_offset(), hours(9));
+    }
+
+    #[test]
+    fn topics_use_event_names() {
+        assert_eq!(
+            NotificationTopic::from(EventType::VerbDone).as_str(),
+            "verb.done"
+        );
+        assert_eq!(
+            NotificationTopic::from_str("review.daily").unwrap(),
+            NotificationTopic::DailyReview
+        );
+        assert!(NotificationTopic::from_str("verb.deleted").is_err());
+    }
+}
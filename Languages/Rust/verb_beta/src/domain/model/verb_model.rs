@@ -44,37 +44,118 @@ impl std::str::FromStr for VerbId {
 }
 
 // ============================================================================
-#[derive(Debug, Clone)]
-struct Title(String);
+/// A verb's title: trimmed, 1-200 characters, on a single line.
+///
+/// Built by the application layer from request data, so a `Verb` can only
+/// ever hold a valid one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Title(String);
 
 impl Title {
-    fn new(value: impl Into<String>) -> Result<Self, DomainError> {
+    pub const MAX_CHARS: usize = 200;
+
+    pub fn new(value: impl Into<String>) -> Result<Self, DomainError> {
         let v = value.into().trim().to_string();
         if v.is_empty() {
             return Err(DomainError::VerbEmptyTitle);
         }
-        if v.len() > 200 {
+        if v.chars().count() > Self::MAX_CHARS {
             return Err(DomainError::VerbTitleTooLong);
         }
+        if v.chars().any(char::is_control) {
+            return Err(DomainError::VerbTitleInvalidCharacters);
+        }
         Ok(Self(v))
     }
 
-    fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &str {
         &self.0
     }
 }
 
-// ============================================================================
+impl TryFrom<String> for Title {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Title> for String {
+    fn from(value: Title) -> Self {
+        value.0
+    }
+}
 
-#[derive(Debug, Clone)]
-struct Description(String);
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// ============================================================================
+/// A verb's description: up to 2000 characters, possibly empty.
+///
+/// Line breaks and tabs are allowed; other control characters are not.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Description(String);
 
 impl Description {
-    fn new(value: impl Into<String>) -> Result<Self, DomainError> {
+    pub const MAX_CHARS: usize = 2000;
+
+    pub fn new(value: impl Into<String>) -> Result<Self, DomainError> {
         let v = value.into();
-        if v.len() > 2000 {
+        if v.chars().count() > Self::MAX_CHARS {
             return Err(DomainError::VerbDescriptionTooLong);
         }
+        if v.chars()
+            .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+        {
+            return Err(DomainError::VerbDescriptionInvalidCharacters);
+        }
+        Ok(Self(v))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Description {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Description> for String {
+    fn from(value: Description) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// ============================================================================
+
+/// Free-form notes on where/why/with whom the verb happens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+struct Context(String);
+
+impl Context {
+    fn new(value: impl Into<String>) -> Result<Self, DomainError> {
+        let v = value.into();
+        if v.len() > 1000 {
+            return Err(DomainError::VerbContextTooLong);
+        }
         Ok(Self(v))
     }
 
@@ -83,6 +164,36 @@ impl Description {
     }
 }
 
+impl TryFrom<String> for Context {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Context> for String {
+    fn from(value: Context) -> Self {
+        value.0
+    }
+}
+
+// ============================================================================
+
+/// Partial update of a verb's editable fields (not its state).
+///
+/// Mirrors JSON Merge Patch (RFC 7386): `None` leaves a field untouched,
+/// `Some(None)` clears it, `Some(Some(v))` sets it. Title and description
+/// are required, so they can only be replaced (an empty description is
+/// how one is cleared).
+#[derive(Debug, Clone, Default)]
+pub struct VerbPatch {
+    pub title: Option<Title>,
+    pub description: Option<Description>,
+    pub context: Option<Option<String>>,
+    pub due_at: Option<Option<OffsetDateTime>>,
+}
+
 // ============================================================================
 // Verb Entity
 // ============================================================================
@@ -92,46 +203,56 @@ impl Description {
 /// - Creation validation
 /// - State transitions
 /// - Temporal consistency
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Verb {
     id: VerbId,
     title: Title,
     description: Description,
+    context: Option<Context>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    due_at: Option<OffsetDateTime>,
     state: VerbState,
+    #[serde(with = "time::serde::rfc3339")]
     created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
     updated_at: OffsetDateTime,
 }
 
 impl Verb {
     /// Create a new verb in Captured state.
     ///
-    /// This is a **factory method** that enforces invariants:
-    /// - Title must be 1-200 chars
-    /// - Description must be 0-2000 chars
-    /// - Initial state is always Captured
-    pub fn new(
-        title: impl Into<String>,
-        description: impl Into<String>,
-    ) -> Result<Self, DomainError> {
+    /// Title and description arrive already validated as value objects;
+    /// the initial state is always Captured.
+    pub fn new(title: Title, description: Description) -> Self {
+        Self::new_with_id(VerbId::new(), title, description)
+    }
+
+    /// Create a new verb under an id the client generated (offline sync).
+    pub fn new_with_id(id: VerbId, title: Title, description: Description) -> Self {
         let now = OffsetDateTime::now_utc();
-        Ok(Self {
-            id: VerbId::new(),
-            title: Title::new(title)?,
-            description: Description::new(description)?,
+        Self {
+            id,
+            title,
+            description,
+            context: None,
+            due_at: None,
             state: VerbState::Captured,
             created_at: now,
             updated_at: now,
-        })
+        }
     }
 
     /// Reconstruct a verb from persistence (bypasses validation).
     ///
     /// Used by repositories when loading from database.
     /// Assumes data was validated when originally created.
+    #[allow(clippy::too_many_arguments)]
     pub fn from_parts(
         id: VerbId,
         title: String,
         description: String,
+        context: Option<String>,
+        due_at: Option<OffsetDateTime>,
         state: VerbState,
         created_at: OffsetDateTime,
         updated_at: OffsetDateTime,
@@ -140,6 +261,8 @@ impl Verb {
             id,
             title: Title::new(title)?,
             description: Description::new(description)?,
+            context: context.map(Context::new).transpose()?,
+            due_at,
             state,
             created_at,
             updated_at,
@@ -158,6 +281,12 @@ impl Verb {
     pub fn description(&self) -> &str {
         self.description.as_str()
     }
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_ref().map(Context::as_str)
+    }
+    pub fn due_at(&self) -> Option<OffsetDateTime> {
+        self.due_at
+    }
     pub fn state(&self) -> VerbState {
         self.state
     }
@@ -200,8 +329,12 @@ impl VerbState {
             VerbState::Dropped => "Dropped",
         }
     }
+}
 
-    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+impl std::str::FromStr for VerbState {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "Captured" => Ok(VerbState::Captured),
             "Active" => Ok(VerbState::Active),
@@ -309,6 +442,69 @@ impl Verb {
     }
 }
 
+// ============================================================================
+// Field Edits
+// ============================================================================
+
+impl Verb {
+    /// Apply a partial update, producing an `UpdatedFields` ActionLog.
+    ///
+    /// All fields are validated before any is applied, so a rejected patch
+    /// leaves the verb untouched. Fields set to their current value do not
+    /// count as changes; if nothing changes, no log is produced and
+    /// `updated_at` is kept.
+    ///
+    /// Title and description are validated when the patch is built; the
+    /// remaining fields are validated here.
+    pub fn apply_patch(&mut self, patch: VerbPatch) -> Result<Option<ActionLog>, DomainError> {
+        // Validate everything first
+        let VerbPatch {
+            title, description, ..
+        } = patch;
+        let context = patch
+            .context
+            .map(|c| c.map(Context::new).transpose())
+            .transpose()?;
+
+        // Then apply, recording what actually changed
+        let mut changed = Vec::new();
+
+        if let Some(title) = title
+            && title != self.title
+        {
+            self.title = title;
+            changed.push("title".to_string());
+        }
+        if let Some(description) = description
+            && description != self.description
+        {
+            self.description = description;
+            changed.push("description".to_string());
+        }
+        if let Some(context) = context
+            && context != self.context
+        {
+            self.context = context;
+            changed.push("context".to_string());
+        }
+        if let Some(due_at) = patch.due_at
+            && due_at != self.due_at
+        {
+            self.due_at = due_at;
+            changed.push("due_at".to_string());
+        }
+
+        if changed.is_empty() {
+            return Ok(None);
+        }
+
+        self.updated_at = OffsetDateTime::now_utc();
+        Ok(Some(ActionLog::fields_updated(
+            self.id, self.state, changed,
+        )))
+    }
+}
+
 //================================================
 // UNIT TESTS
 //================================================
@@ -316,39 +512,71 @@ impl Verb {
 mod tests {
     use super::*;
 
+    fn new_verb() -> Verb {
+        Verb::new(
+            Title::new("Test").unwrap(),
+            Description::new("Desc").unwrap(),
+        )
+    }
+
     #[test]
     fn new_verb_starts_in_captured_state() {
-        let verb = Verb::new("Test", "Description").unwrap();
-        assert_eq!(verb.state(), VerbState::Captured);
+        assert_eq!(new_verb().state(), VerbState::Captured);
     }
 
     #[test]
     fn rejects_empty_title() {
-        let result = Verb::new("", "Desc");
+        let result = Title::new("   ");
         assert!(matches!(result, Err(DomainError::VerbEmptyTitle)));
     }
 
     #[test]
     fn trims_title() {
-        let verb = Verb::new("  Test  ", "Desc").unwrap();
-        assert_eq!(verb.title(), "Test");
+        assert_eq!(Title::new("  Test  ").unwrap().as_str(), "Test");
+    }
+
+    #[test]
+    fn title_and_description_limits_count_characters() {
+        assert!(Title::new("é".repeat(Title::MAX_CHARS)).is_ok());
+        assert!(matches!(
+            Title::new("é".repeat(Title::MAX_CHARS + 1)),
+            Err(DomainError::VerbTitleTooLong)
+        ));
+        assert!(Description::new("ü".repeat(Description::MAX_CHARS)).is_ok());
+        assert!(matches!(
+            Description::new("ü".repeat(Description::MAX_CHARS + 1)),
+            Err(DomainError::VerbDescriptionTooLong)
+        ));
+    }
+
+    #[test]
+    fn rejects_control_characters() {
+        assert!(matches!(
+            Title::new("two\nlines"),
+            Err(DomainError::VerbTitleInvalidCharacters)
+        ));
+        assert!(Description::new("line one\n\tline two\r\n").is_ok());
+        assert!(matches!(
+            Description::new("bell\u{7}"),
+            Err(DomainError::VerbDescriptionInvalidCharacters)
+        ));
     }
 
     #[test]
     fn can_activate_from_captured() {
-        let verb = Verb::new("Test", "Desc").unwrap();
+        let verb = new_verb();
         assert!(verb.can_transition_to(VerbState::Active));
     }
 
     #[test]
     fn cannot_complete_from_captured() {
-        let verb = Verb::new("Test", "Desc").unwrap();
+        let verb = new_verb();
         assert!(!verb.can_transition_to(VerbState::Done));
     }
 
     #[test]
     fn transition_updates_state_and_timestamp() {
-        let mut verb = Verb::new("Test", "Desc").unwrap();
+        let mut verb = new_verb();
         let old_updated_at = verb.updated_at();
 
         std::thread::sleep(std::time::Duration::from_millis(10));
@@ -358,6 +586,79 @@ mod tests {
         assert_eq!(verb.state(), VerbState::Active);
         assert!(verb.updated_at() > old_updated_at);
     }
+
+    #[test]
+    fn patch_updates_only_given_fields_and_logs_them() {
+        let mut verb = new_verb();
+        let due = OffsetDateTime::now_utc();
+
+        let log = verb
+            .apply_patch(VerbPatch {
+                title: Some(Title::new("Renamed").unwrap()),
+                context: Some(Some("at home".into())),
+                due_at: Some(Some(due)),
+                ..VerbPatch::default()
+            })
+            .unwrap()
+            .expect("fields changed");
+
+        assert_eq!(verb.title(), "Renamed");
+        assert_eq!(verb.description(), "Desc");
+        assert_eq!(verb.context(), Some("at home"));
+        assert_eq!(verb.due_at(), Some(due));
+        assert_eq!(log.changed_fields(), ["title", "context", "due_at"]);
+        assert_eq!(log.to_state(), VerbState::Captured);
+    }
+
+    #[test]
+    fn patch_null_clears_optional_fields() {
+        let mut verb = new_verb();
+        verb.apply_patch(VerbPatch {
+            context: Some(Some("ctx".into())),
+            ..VerbPatch::default()
+        })
+        .unwrap();
+
+        verb.apply_patch(VerbPatch {
+            description: Some(Description::default()),
+            context: Some(None),
+            ..VerbPatch::default()
+        })
+        .unwrap();
+
+        assert_eq!(verb.description(), "");
+        assert_eq!(verb.context(), None);
+    }
+
+    #[test]
+    fn patch_without_changes_produces_no_log() {
+        let mut verb = new_verb();
+        let updated_at = verb.updated_at();
+
+        let log = verb
+            .apply_patch(VerbPatch {
+                title: Some(Title::new("Test").unwrap()),
+                ..VerbPatch::default()
+            })
+            .unwrap();
+
+        assert!(log.is_none());
+        assert_eq!(verb.updated_at(), updated_at);
+    }
+
+    #[test]
+    fn invalid_patch_leaves_verb_untouched() {
+        let mut verb = new_verb();
+
+        let result = verb.apply_patch(VerbPatch {
+            title: Some(Title::new("Renamed").unwrap()),
+            context: Some(Some("x".repeat(1001))),
+            ..VerbPatch::default()
+        });
+
+        assert!(matches!(result, Err(DomainError::VerbContextTooLong)));
+        assert_eq!(verb.title(), "Test");
+    }
 }
 
 //CRUD Use-Case
@@ -0,0 +1,434 @@
+//! Webhook Models
+//!
+//! A webhook is a callback URL registered for one or more event types.
+//! Every delivery attempt to it is recorded as a [`WebhookDelivery`].
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::DomainError;
+
+use super::{UserId, VerbId};
+
+// ============================================================================
+// Value Objects
+// ============================================================================
+/// Strongly-typed identity for a Webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WebhookId(Uuid);
+
+impl WebhookId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl fmt::Display for WebhookId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for WebhookId {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(Uuid::parse_str(s)?))
+    }
+}
+
+/// Strongly-typed identity for a delivery attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DeliveryId(Uuid);
+
+impl DeliveryId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl fmt::Display for DeliveryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// ============================================================================
+
+/// Events a webhook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "&'static str")]
+pub enum EventType {
+    /// A verb was captured
+    VerbCreated,
+    /// A verb was completed
+    VerbDone,
+}
+
+impl EventType {
+    pub const ALL: [EventType; 2] = [EventType::VerbCreated, EventType::VerbDone];
+
+    /// Wire name used in payloads and registration requests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventType::VerbCreated => "verb.created",
+            EventType::VerbDone => "verb.done",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == s)
+            .ok_or_else(|| DomainError::WebhookUnknownEvent(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for EventType {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<EventType> for &'static str {
+    fn from(event: EventType) -> Self {
+        event.as_str()
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+struct CallbackUrl(String);
+
+impl CallbackUrl {
+    fn new(value: impl Into<String>) -> Result<Self, DomainError> {
+        let v = value.into().trim().to_string();
+        let rest = v
+            .strip_prefix("https://")
+            .or_else(|| v.strip_prefix("http://"));
+        match rest {
+            Some(host) if !host.is_empty() && !host.starts_with('/') && v.len() <= 2000 => {
+                Ok(Self(v))
+            }
+            _ => Err(DomainError::WebhookInvalidUrl(v)),
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for CallbackUrl {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<CallbackUrl> for String {
+    fn from(value: CallbackUrl) -> Self {
+        value.0
+    }
+}
+
+// ============================================================================
+// Webhook Entity
+// ============================================================================
+/// A registered callback URL and the events it receives.
+///
+/// Payloads are signed with `secret` (HMAC-SHA256) so receivers can
+/// verify they came from us. A webhook registered on behalf of a user
+/// follows that user's notification preferences.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    id: WebhookId,
+    url: CallbackUrl,
+    events: Vec<EventType>,
+    secret: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    owner: Option<UserId>,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+}
+
+impl Webhook {
+    /// Register a new webhook.
+    ///
+    /// Enforces:
+    /// - URL must be absolute http(s)
+    /// - At least one event (duplicates are dropped)
+    /// - A secret is generated when none is given
+    pub fn new(
+        url: impl Into<String>,
+        events: Vec<EventType>,
+        secret: Option<String>,
+    ) -> Result<Self, DomainError> {
+        let mut unique = Vec::with_capacity(events.len());
+        for event in events {
+            if !unique.contains(&event) {
+                unique.push(event);
+            }
+        }
+        if unique.is_empty() {
+            return Err(DomainError::WebhookNoEvents);
+        }
+
+        let secret = match secret {
+            Some(s) if s.trim().is_empty() => return Err(DomainError::WebhookEmptySecret),
+            Some(s) => s,
+            None => Uuid::new_v4().simple().to_string(),
+        };
+
+        Ok(Self {
+            id: WebhookId::new(),
+            url: CallbackUrl::new(url)?,
+            events: unique,
+            secret,
+            owner: None,
+            created_at: OffsetDateTime::now_utc(),
+        })
+    }
+
+    /// Deliver on behalf of `owner`
+    pub fn with_owner(mut self, owner: UserId) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn id(&self) -> WebhookId {
+        self.id
+    }
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+    pub fn events(&self) -> &[EventType] {
+        &self.events
+    }
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+    pub fn owner(&self) -> Option<UserId> {
+        self.owner
+    }
+    pub fn created_at(&self) -> OffsetDateTime {
+        self.created_at
+    }
+
+    /// Whether this webhook should receive `event`
+    pub fn subscribes_to(&self, event: EventType) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+// ============================================================================
+// Delivery Log
+// ============================================================================
+
+/// Outcome of a single delivery attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeliveryStatus {
+    /// Receiver answered with a 2xx status
+    Succeeded,
+    /// Non-2xx answer or transport error; may be retried
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Succeeded => "Succeeded",
+            DeliveryStatus::Failed => "Failed",
+        }
+    }
+}
+
+/// Immutable record of one attempt to deliver an event to a webhook.
+///
+/// Retries of the same event share `event_id` and increase `attempt`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    id: DeliveryId,
+    webhook_id: WebhookId,
+    event_id: Uuid,
+    event: EventType,
+    verb_id: VerbId,
+    attempt: u32,
+    status: DeliveryStatus,
+    response_status: Option<u16>,
+    error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    timestamp: OffsetDateTime,
+}
+
+impl WebhookDelivery {
+    /// Record an attempt that got an HTTP response
+    pub fn responded(
+        webhook_id: WebhookId,
+        event_id: Uuid,
+        event: EventType,
+        verb_id: VerbId,
+        attempt: u32,
+        response_status: u16,
+    ) -> Self {
+        let status = if (200..300).contains(&response_status) {
+            DeliveryStatus::Succeeded
+        } else {
+            DeliveryStatus::Failed
+        };
+
+        Self {
+            id: DeliveryId::new(),
+            webhook_id,
+            event_id,
+            event,
+            verb_id,
+            attempt,
+            status,
+            response_status: Some(response_status),
+            error: None,
+            timestamp: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Record an attempt that never got a response (timeout, DNS, refused...)
+    pub fn errored(
+        webhook_id: WebhookId,
+        event_id: Uuid,
+        event: EventType,
+        verb_id: VerbId,
+        attempt: u32,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: DeliveryId::new(),
+            webhook_id,
+            event_id,
+            event,
+            verb_id,
+            attempt,
+            status: DeliveryStatus::Failed,
+            response_status: None,
+            error: Some(error.into()),
+            timestamp: OffsetDateTime::now_utc(),
+        }
+    }
+
+    pub fn id(&self) -> DeliveryId {
+        self.id
+    }
+    pub fn webhook_id(&self) -> WebhookId {
+        self.webhook_id
+    }
+    pub fn event_id(&self) -> Uuid {
+        self.event_id
+    }
+    pub fn event(&self) -> EventType {
+        self.event
+    }
+    pub fn verb_id(&self) -> VerbId {
+        self.verb_id
+    }
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+    pub fn status(&self) -> DeliveryStatus {
+        self.status
+    }
+    pub fn response_status(&self) -> Option<u16> {
+        self.response_status
+    }
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+    pub fn timestamp(&self) -> OffsetDateTime {
+        self.timestamp
+    }
+    pub fn succeeded(&self) -> bool {
+        self.status == DeliveryStatus::Succeeded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_type_round_trips_wire_name() {
+        for event in EventType::ALL {
+            assert_eq!(EventType::from_str(event.as_str()).unwrap(), event);
+        }
+        assert!(matches!(
+            EventType::from_str("verb.exploded"),
+            Err(DomainError::WebhookUnknownEvent(_))
+        ));
+    }
+
+    #[test]
+    fn new_webhook_dedups_events_and_generates_secret() {
+        let hook = Webhook::new(
+            "https://example.com/hook",
+            vec![EventType::VerbDone, EventType::VerbDone],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(hook.events(), &[EventType::VerbDone]);
+        assert!(hook.subscribes_to(EventType::VerbDone));
+        assert!(!hook.subscribes_to(EventType::VerbCreated));
+        assert!(!hook.secret().is_empty());
+    }
+
+    #[test]
+    fn new_webhook_rejects_invalid_input() {
+        let events = vec![EventType::VerbCreated];
+
+        assert!(matches!(
+            Webhook::new("ftp://example.com", events.clone(), None),
+            Err(DomainError::WebhookInvalidUrl(_))
+        ));
+        assert!(matches!(
+            Webhook::new("https://", events.clone(), None),
+            Err(DomainError::WebhookInvalidUrl(_))
+        ));
+        assert!(matches!(
+            Webhook::new("https://example.com", vec![], None),
+            Err(DomainError::WebhookNoEvents)
+        ));
+        assert!(matches!(
+            Webhook::new("https://example.com", events, Some("  ".into())),
+            Err(DomainError::WebhookEmptySecret)
+        ));
+    }
+
+    #[test]
+    fn delivery_status_follows_response_code() {
+        let hook = WebhookId::new();
+        let verb = VerbId::new();
+        let event = Uuid::new_v4();
+
+        let ok = WebhookDelivery::responded(hook, event, EventType::VerbDone, verb, 1, 204);
+        let bad = WebhookDelivery::responded(hook, event, EventType::VerbDone, verb, 2, 500);
+        let err = WebhookDelivery::errored(hook, event, EventType::VerbDone, verb, 3, "timeout");
+
+        assert!(ok.succeeded());
+        assert!(!bad.succeeded());
+        assert_eq!(err.status(), DeliveryStatus::Failed);
+        assert_eq!(err.error(), Some("timeout"));
+    }
+}
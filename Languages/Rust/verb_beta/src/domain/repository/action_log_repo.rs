@@ -1,6 +1,7 @@
 use std::pin::Pin;
 
 use serde::Serialize;
+use time::{Date, OffsetDateTime};
 
 use crate::{
     application::ApplicationError,
@@ -24,6 +25,69 @@ pub trait ActionLogRepository: Send + Sync {
         verb_id: VerbId,
         filter: &ActionLogFilter,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<ActionLog>, ApplicationError>> + Send + '_>>;
+
+    /// Count creations, completions and drops per UTC day in `[from, to)`
+    ///
+    /// Days without any of them are omitted; the rest come oldest first.
+    /// SQL backends should aggregate in the query (`GROUP BY` day) rather
+    /// than load the logs.
+    fn daily_counts(
+        &self,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<DailyActionCounts>, ApplicationError>> + Send + '_>>;
+
+    /// Up to `limit` logs appended after position `cursor`, in append order
+    ///
+    /// The cursor counts appended logs, so it never skips a log whose
+    /// timestamp is older than one already returned. SQL backends can use
+    /// an `AUTOINCREMENT` key (`WHERE seq > ? ORDER BY seq`).
+    fn since(
+        &self,
+        cursor: u64,
+        limit: u32,
+    ) -> Pin<Box<dyn Future<Output = Result<ActionLogChanges, ApplicationError>> + Send + '_>>;
+}
+
+/// A page of logs read with [`ActionLogRepository::since`]
+#[derive(Debug, Clone)]
+pub struct ActionLogChanges {
+    /// Oldest first
+    pub logs: Vec<ActionLog>,
+    /// Position after the last returned log; pass it to the next call
+    pub cursor: u64,
+    /// Whether more logs follow `cursor`
+    pub has_more: bool,
+}
+
+/// Verbs created, completed and dropped on one UTC day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyActionCounts {
+    pub date: Date,
+    pub created: u32,
+    pub completed: u32,
+    pub dropped: u32,
+}
+
+impl DailyActionCounts {
+    pub fn new(date: Date) -> Self {
+        Self {
+            date,
+            created: 0,
+            completed: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Count `action`; actions other than the three tracked are ignored
+    pub fn record(&mut self, action: ActionType) {
+        match action {
+            ActionType::Created => self.created += 1,
+            ActionType::Completed => self.completed += 1,
+            ActionType::Dropped => self.dropped += 1,
+            _ => {}
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
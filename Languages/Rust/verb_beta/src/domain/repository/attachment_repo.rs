@@ -0,0 +1,30 @@
+use std::pin::Pin;
+
+use crate::{
+    application::ApplicationError,
+    domain::model::{Attachment, AttachmentId, VerbId},
+};
+
+// ==================================================
+// ATTACHMENT REPOSITORY TRAIT
+// ==================================================
+/// PORT: What the domain needs from attachment metadata persistence
+pub trait AttachmentRepository: Send + Sync {
+    /// Store attachment metadata
+    fn save(
+        &self,
+        attachment: &Attachment,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>>;
+
+    /// Retrieve attachment metadata by ID
+    fn find_by_id(
+        &self,
+        id: AttachmentId,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<Attachment>, ApplicationError>> + Send + '_>>;
+
+    /// Attachments of a verb, oldest first
+    fn find_by_verb(
+        &self,
+        verb_id: VerbId,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Attachment>, ApplicationError>> + Send + '_>>;
+}
@@ -0,0 +1,65 @@
+use std::pin::Pin;
+
+use time::OffsetDateTime;
+
+use crate::{
+    application::ApplicationError,
+    domain::model::{Actor, AuditEntry},
+};
+
+// ==================================================
+// AUDIT REPOSITORY TRAIT
+// ==================================================
+/// PORT: What the application needs from audit log persistence
+///
+/// Append-only: entries are never updated or removed.
+pub trait AuditRepository: Send + Sync {
+    /// Append an audit entry
+    fn append(
+        &self,
+        entry: &AuditEntry,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>>;
+
+    /// Entries matching `filter`, newest first
+    fn list(
+        &self,
+        filter: &AuditFilter,
+    ) -> Pin<Box<dyn Future<Output = Result<AuditListResult, ApplicationError>> + Send + '_>>;
+}
+
+/// Which audit entries to list; time bounds are `[from, to)`
+#[derive(Debug, Clone)]
+pub struct AuditFilter {
+    pub actor: Option<Actor>,
+    pub from: Option<OffsetDateTime>,
+    pub to: Option<OffsetDateTime>,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+impl Default for AuditFilter {
+    fn default() -> Self {
+        Self {
+            actor: None,
+            from: None,
+            to: None,
+            limit: 50,
+            offset: 0,
+        }
+    }
+}
+
+impl AuditFilter {
+    /// Whether `entry` passes the actor and time filters
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.actor.is_none_or(|actor| entry.actor() == actor)
+            && self.from.is_none_or(|from| entry.timestamp() >= from)
+            && self.to.is_none_or(|to| entry.timestamp() < to)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditListResult {
+    pub entries: Vec<AuditEntry>,
+    pub total: u32,
+}
@@ -2,7 +2,17 @@
 //! a kind of abstraction layer to help define application
 //! use cases as well as facilitate persistence
 pub mod action_log_repo;
+pub mod attachment_repo;
+pub mod audit_repo;
+pub mod notification_preferences_repo;
 pub mod verb_repo;
+pub mod webhook_repo;
 
-pub use action_log_repo::ActionLogRepository;
+pub use action_log_repo::{ActionLogChanges, ActionLogRepository, DailyActionCounts};
+pub use attachment_repo::AttachmentRepository;
+pub use audit_repo::{AuditFilter, AuditListResult, AuditRepository};
+pub use notification_preferences_repo::NotificationPreferencesRepository;
 pub use verb_repo::{VerbFilter, VerbListResult, VerbRepository};
+pub use webhook_repo::{
+    DeliveryFilter, DeliveryListResult, WebhookDeliveryRepository, WebhookRepository,
+};
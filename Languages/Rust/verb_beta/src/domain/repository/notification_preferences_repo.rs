@@ -0,0 +1,39 @@
+use std::pin::Pin;
+
+use crate::{
+    application::ApplicationError,
+    domain::model::{NotificationPreferences, UserId},
+};
+
+// ==================================================
+// NOTIFICATION PREFERENCES REPOSITORY TRAIT
+// ==================================================
+/// PORT: What the domain needs from notification preference persistence
+///
+/// One record per user; users without one get
+/// [`NotificationPreferences::defaults`].
+pub trait NotificationPreferencesRepository: Send + Sync {
+    /// Insert or replace a user's preferences
+    fn save(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>>;
+
+    /// Retrieve a user's saved preferences
+    fn find_by_user(
+        &self,
+        user_id: UserId,
+    ) -> Pin<
+        Box<
+            dyn Future<Output = Result<Option<NotificationPreferences>, ApplicationError>>
+                + Send
+                + '_,
+        >,
+    >;
+
+    /// Remove a user's preferences; returns whether they existed
+    fn delete(
+        &self,
+        user_id: UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, ApplicationError>> + Send + '_>>;
+}
@@ -0,0 +1,81 @@
+use std::pin::Pin;
+
+use crate::{
+    application::ApplicationError,
+    domain::model::{EventType, Webhook, WebhookDelivery, WebhookId},
+};
+
+// ==================================================
+// WEBHOOK REPOSITORY TRAIT
+// ==================================================
+/// PORT: What the domain needs from webhook persistence
+pub trait WebhookRepository: Send + Sync {
+    /// Store a webhook
+    fn save(
+        &self,
+        webhook: &Webhook,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>>;
+
+    /// Retrieve webhook by ID
+    fn find_by_id(
+        &self,
+        id: WebhookId,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<Webhook>, ApplicationError>> + Send + '_>>;
+
+    /// All registered webhooks, oldest first
+    fn list(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Webhook>, ApplicationError>> + Send + '_>>;
+
+    /// Webhooks subscribed to `event`
+    fn find_by_event(
+        &self,
+        event: EventType,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Webhook>, ApplicationError>> + Send + '_>>;
+
+    /// Remove a webhook; returns whether it existed
+    fn delete(
+        &self,
+        id: WebhookId,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, ApplicationError>> + Send + '_>>;
+}
+
+// ==================================================
+// WEBHOOK DELIVERY REPOSITORY TRAIT
+// ==================================================
+/// PORT: Append-only log of delivery attempts
+pub trait WebhookDeliveryRepository: Send + Sync {
+    /// Append a delivery attempt
+    fn append(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>>;
+
+    /// Delivery attempts for a webhook, newest first
+    fn find_by_webhook(
+        &self,
+        webhook_id: WebhookId,
+        filter: &DeliveryFilter,
+    ) -> Pin<Box<dyn Future<Output = Result<DeliveryListResult, ApplicationError>> + Send + '_>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct DeliveryFilter {
+    pub limit: u32,
+    pub offset: u32,
+}
+
+impl Default for DeliveryFilter {
+    fn default() -> Self {
+        Self {
+            limit: 50,
+            offset: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeliveryListResult {
+    pub deliveries: Vec<WebhookDelivery>,
+    pub total: u32,
+}
@@ -0,0 +1,37 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::Mutex;
+
+use crate::application::{BlobFuture, BlobStore};
+
+/// Blobs held in process memory; lost on restart
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryBlobStore {
+    blobs: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl InMemoryBlobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BlobStore for InMemoryBlobStore {
+    fn put<'a>(&'a self, key: &'a str, data: Vec<u8>) -> BlobFuture<'a, ()> {
+        Box::pin(async move {
+            self.blobs.lock().await.insert(key.to_string(), data);
+            Ok(())
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BlobFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move { Ok(self.blobs.lock().await.get(key).cloned()) })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BlobFuture<'a, ()> {
+        Box::pin(async move {
+            self.blobs.lock().await.remove(key);
+            Ok(())
+        })
+    }
+}
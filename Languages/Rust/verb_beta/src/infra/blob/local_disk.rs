@@ -0,0 +1,116 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use uuid::Uuid;
+
+use crate::application::{BlobError, BlobFuture, BlobStore};
+
+/// One file per blob under a root directory
+///
+/// Writes go to a temporary file first and are renamed into place, so a
+/// reader never sees a partial blob.
+#[derive(Debug, Clone)]
+pub struct LocalDiskBlobStore {
+    root: PathBuf,
+}
+
+impl LocalDiskBlobStore {
+    /// Use `root`, creating it if needed
+    pub async fn open(root: impl Into<PathBuf>) -> Result<Self, BlobError> {
+        let root = root.into();
+        tokio::fs::create_dir_all(&root)
+            .await
+            .map_err(|e| BlobError(format!("cannot create {}: {e}", root.display())))?;
+        Ok(Self { root })
+    }
+
+    /// Path for `key`; keys that could escape `root` are refused
+    fn path(&self, key: &str) -> Result<PathBuf, BlobError> {
+        let safe = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !safe {
+            return Err(BlobError(format!("invalid blob key {key:?}")));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> BlobError {
+    BlobError(format!("{}: {e}", path.display()))
+}
+
+impl BlobStore for LocalDiskBlobStore {
+    fn put<'a>(&'a self, key: &'a str, data: Vec<u8>) -> BlobFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            let tmp = self
+                .root
+                .join(format!(".{key}.{}.tmp", Uuid::new_v4().simple()));
+
+            tokio::fs::write(&tmp, data)
+                .await
+                .map_err(|e| io_error(&tmp, e))?;
+            if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+                let _ = tokio::fs::remove_file(&tmp).await;
+                return Err(io_error(&path, e));
+            }
+            Ok(())
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BlobFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            match tokio::fs::read(&path).await {
+                Ok(data) => Ok(Some(data)),
+                Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(io_error(&path, e)),
+            }
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BlobFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            match tokio::fs::remove_file(&path).await {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(io_error(&path, e)),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn round_trips_and_deletes() {
+        let root = std::env::temp_dir().join(format!("verb-blobs-{}", Uuid::new_v4()));
+        let store = LocalDiskBlobStore::open(&root).await.unwrap();
+
+        store.put("abc-123", b"hello".to_vec()).await.unwrap();
+        assert_eq!(store.get("abc-123").await.unwrap().unwrap(), b"hello");
+
+        store.delete("abc-123").await.unwrap();
+        store.delete("abc-123").await.unwrap();
+        assert!(store.get("abc-123").await.unwrap().is_none());
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuses_keys_outside_root() {
+        let store = LocalDiskBlobStore {
+            root: std::env::temp_dir(),
+        };
+
+        for key in ["../etc/passwd", "a/b", "", ".."] {
+            assert!(store.get(key).await.is_err(), "{key:?}");
+        }
+    }
+}
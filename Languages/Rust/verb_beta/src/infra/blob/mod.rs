@@ -0,0 +1,9 @@
+//! Blob storage
+//!
+//! Adapters for the application's [`BlobStore`](crate::application::BlobStore)
+//! port.
+mod in_memory;
+mod local_disk;
+
+pub use in_memory::InMemoryBlobStore;
+pub use local_disk::LocalDiskBlobStore;
@@ -0,0 +1,95 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Mutex;
+
+use crate::application::{Cache, CacheFuture};
+
+/// Entries held in process memory; lost on restart
+///
+/// Expired entries are dropped when next read, so the map only grows
+/// with keys that are written and never read again.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryCache {
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    data: Vec<u8>,
+    expires_at: Instant,
+}
+
+impl InMemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Cache for InMemoryCache {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            let mut entries = self.entries.lock().await;
+            match entries.get(key) {
+                Some(entry) if entry.expires_at > Instant::now() => Ok(Some(entry.data.clone())),
+                Some(_) => {
+                    entries.remove(key);
+                    Ok(None)
+                }
+                None => Ok(None),
+            }
+        })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, data: Vec<u8>, ttl: Duration) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            let entry = Entry {
+                data,
+                expires_at: Instant::now() + ttl,
+            };
+            self.entries.lock().await.insert(key.to_string(), entry);
+            Ok(())
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            self.entries.lock().await.remove(key);
+            Ok(())
+        })
+    }
+
+    fn clear(&self) -> CacheFuture<'_, ()> {
+        Box::pin(async move {
+            self.entries.lock().await.clear();
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn entries_expire_after_their_ttl() {
+        let cache = InMemoryCache::new();
+        cache
+            .set("live", b"1".to_vec(), Duration::from_secs(60))
+            .await
+            .unwrap();
+        cache
+            .set("expired", b"2".to_vec(), Duration::ZERO)
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get("live").await.unwrap(), Some(b"1".to_vec()));
+        assert_eq!(cache.get("expired").await.unwrap(), None);
+
+        cache.delete("live").await.unwrap();
+        assert_eq!(cache.get("live").await.unwrap(), None);
+    }
+}
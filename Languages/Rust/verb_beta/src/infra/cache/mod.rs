@@ -0,0 +1,7 @@
+//! Caching
+//!
+//! Adapters for the application's [`Cache`](crate::application::Cache)
+//! port.
+mod in_memory;
+
+pub use in_memory::InMemoryCache;
@@ -3,8 +3,15 @@ use std::pin::Pin;
 use async_trait::async_trait;
 
 use crate::{
-    domain::repository::{ActionLogRepository, VerbRepository},
-    infra::db::DatabaseError,
+    domain::repository::{
+        ActionLogRepository, AttachmentRepository, AuditRepository,
+        NotificationPreferencesRepository, VerbRepository, WebhookDeliveryRepository,
+        WebhookRepository,
+    },
+    infra::{
+        db::{DatabaseError, Snapshot},
+        repository::cached::CacheStats,
+    },
 };
 
 /// Database trait using GAT for proper type-safe transactions
@@ -38,6 +45,53 @@ pub trait Database: Clone + Send + Sync + 'static {
     fn begin_tx(
         &self,
     ) -> Pin<Box<dyn Future<Output = Result<Self::Transaction<'_>, DatabaseError>> + Send + '_>>;
+
+    /// Row counts, for diagnostics
+    fn stats(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<DatabaseStats, DatabaseError>> + Send + '_>>;
+
+    /// Drop whatever is cached in front of the stores
+    ///
+    /// Nothing to drop by default: only a caching decorator holds entries.
+    fn flush_cache(&self) -> Pin<Box<dyn Future<Output = Result<(), DatabaseError>> + Send + '_>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Copy every store into a [`Snapshot`]
+    ///
+    /// Meant for backends that live in process; the default reports
+    /// [`DatabaseError::NotSupported`].
+    fn snapshot(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Snapshot, DatabaseError>> + Send + '_>> {
+        Box::pin(async { Err(DatabaseError::NotSupported("snapshots".into())) })
+    }
+
+    /// Replace every store with the contents of `snapshot`
+    fn restore(
+        &self,
+        snapshot: Snapshot,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DatabaseError>> + Send + '_>> {
+        let _ = snapshot;
+        Box::pin(async { Err(DatabaseError::NotSupported("snapshots".into())) })
+    }
+}
+
+/// Size of each store, as reported by [`Database::stats`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseStats {
+    /// Backend name, e.g. `"in-memory"`
+    pub backend: &'static str,
+    pub verbs: usize,
+    pub action_logs: usize,
+    pub webhooks: usize,
+    pub webhook_deliveries: usize,
+    pub attachments: usize,
+    pub notification_preferences: usize,
+    pub audit_entries: usize,
+    /// Read-through cache hits and misses, when a cache is in front
+    pub cache: Option<CacheStats>,
 }
 
 // ===========================================================
@@ -57,6 +111,21 @@ pub trait DatabaseTransaction: Send {
     /// Get action log repository for this transaction
     fn action_log_repository(&self) -> &dyn ActionLogRepository;
 
+    /// Get webhook repository for this transaction
+    fn webhook_repository(&self) -> &dyn WebhookRepository;
+
+    /// Get webhook delivery log repository for this transaction
+    fn webhook_delivery_repository(&self) -> &dyn WebhookDeliveryRepository;
+
+    /// Get attachment metadata repository for this transaction
+    fn attachment_repository(&self) -> &dyn AttachmentRepository;
+
+    /// Get notification preferences repository for this transaction
+    fn notification_preferences_repository(&self) -> &dyn NotificationPreferencesRepository;
+
+    /// Get audit log repository for this transaction
+    fn audit_repository(&self) -> &dyn AuditRepository;
+
     /// Commit this transaction
     ///
     /// Takes `self` by value to consume the transaction.
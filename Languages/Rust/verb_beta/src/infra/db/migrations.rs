@@ -0,0 +1,317 @@
+//! Schema migrations
+//!
+//! SQL files under `migrations/` are embedded in the binary at compile time
+//! and applied in version order. Applied versions are recorded in the
+//! `schema_migrations` table together with a checksum, so editing a
+//! migration after it ran is detected instead of silently ignored.
+//!
+//! Run them with `verb_beta migrate [--dry-run]`, or let the server apply
+//! them at startup (see `Config::auto_migrate`).
+//!
+//! Note: the verb repositories are still in-memory; this prepares the
+//! SQLite schema they will be backed by.
+use std::{fmt, str::FromStr};
+
+use sha2::{Digest, Sha256};
+use sqlx::{
+    SqlitePool,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+};
+use time::OffsetDateTime;
+
+use super::DatabaseError;
+
+/// A single embedded migration
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+impl Migration {
+    /// Hex SHA-256 of the SQL, stored when the migration is applied
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+impl fmt::Display for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}_{}", self.version, self.name)
+    }
+}
+
+macro_rules! migration {
+    ($version:literal, $name:literal) => {
+        Migration {
+            version: $version,
+            name: $name,
+            sql: include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/migrations/",
+                stringify!($version),
+                "_",
+                $name,
+                ".sql"
+            )),
+        }
+    };
+}
+
+/// All migrations shipped with this binary, in version order
+///
+/// Versions are written zero-padded so they match the file names.
+#[allow(clippy::zero_prefixed_literal)]
+pub const MIGRATIONS: &[Migration] = &[
+    migration!(0001, "create_verbs"),
+    migration!(0002, "create_action_logs"),
+    migration!(0003, "verb_context_and_due_date"),
+    migration!(0004, "create_webhooks"),
+    migration!(0005, "create_attachments"),
+    migration!(0006, "create_audit_log"),
+    migration!(0007, "create_notification_preferences"),
+];
+
+/// Result of a migration run
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    /// Migrations applied (or, for a dry run, that would be applied)
+    pub applied: Vec<Migration>,
+    pub dry_run: bool,
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.applied.is_empty() {
+            return writeln!(f, "Database is up to date");
+        }
+
+        let verb = if self.dry_run {
+            "Would apply"
+        } else {
+            "Applied"
+        };
+        writeln!(f, "{verb} {} migration(s):", self.applied.len())?;
+        for migration in &self.applied {
+            writeln!(f, "  {migration}")?;
+            if self.dry_run {
+                for line in migration.sql.lines() {
+                    writeln!(f, "    | {line}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Applies embedded migrations to a SQLite database
+pub struct Migrator {
+    pool: SqlitePool,
+    migrations: &'static [Migration],
+}
+
+impl Migrator {
+    /// Connect to `database_url` (e.g. `sqlite://verb.db`), creating the
+    /// file if needed
+    pub async fn connect(database_url: &str) -> Result<Self, DatabaseError> {
+        let options = SqliteConnectOptions::from_str(database_url)
+            .map_err(|e| DatabaseError::InvalidConfig(e.to_string()))?
+            .create_if_missing(true);
+
+        // One connection: migrations are sequential, and `sqlite::memory:`
+        // is per-connection
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .map_err(|e| DatabaseError::Connection(e.to_string()))?;
+
+        Ok(Self {
+            pool,
+            migrations: MIGRATIONS,
+        })
+    }
+
+    /// Use a different migration set
+    #[cfg(test)]
+    pub fn with_migrations(mut self, migrations: &'static [Migration]) -> Self {
+        self.migrations = migrations;
+        self
+    }
+
+    /// Migrations not yet applied, in version order.
+    ///
+    /// # Errors
+    /// Fails if an applied migration's checksum no longer matches the
+    /// embedded SQL, or the database has versions this binary doesn't know.
+    pub async fn pending(&self) -> Result<Vec<Migration>, DatabaseError> {
+        self.ensure_version_table().await?;
+
+        let applied: Vec<(i64, String)> =
+            sqlx::query_as("SELECT version, checksum FROM schema_migrations ORDER BY version")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(query_error)?;
+
+        for (version, checksum) in &applied {
+            let Some(known) = self.migrations.iter().find(|m| m.version == *version) else {
+                return Err(DatabaseError::Migration(format!(
+                    "database has migration {version} which this binary does not know about"
+                )));
+            };
+            if known.checksum() != *checksum {
+                return Err(DatabaseError::Migration(format!(
+                    "migration {known} was modified after it was applied"
+                )));
+            }
+        }
+
+        Ok(self
+            .migrations
+            .iter()
+            .filter(|m| !applied.iter().any(|(version, _)| *version == m.version))
+            .copied()
+            .collect())
+    }
+
+    /// Apply all pending migrations, each in its own transaction.
+    ///
+    /// With `dry_run`, nothing is executed; the report lists what would be.
+    pub async fn run(&self, dry_run: bool) -> Result<MigrationReport, DatabaseError> {
+        let pending = self.pending().await?;
+
+        if !dry_run {
+            for migration in &pending {
+                self.apply(migration).await?;
+                tracing::info!(migration = %migration, "Applied migration");
+            }
+        }
+
+        Ok(MigrationReport {
+            applied: pending,
+            dry_run,
+        })
+    }
+
+    async fn apply(&self, migration: &Migration) -> Result<(), DatabaseError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DatabaseError::Transaction(e.to_string()))?;
+
+        sqlx::raw_sql(migration.sql)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DatabaseError::Migration(format!("{migration}: {e}")))?;
+
+        sqlx::query(
+            "INSERT INTO schema_migrations (version, name, checksum, applied_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(migration.version)
+        .bind(migration.name)
+        .bind(migration.checksum())
+        .bind(OffsetDateTime::now_utc().to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(query_error)?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DatabaseError::Transaction(e.to_string()))
+    }
+
+    async fn ensure_version_table(&self) -> Result<(), DatabaseError> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version    INTEGER PRIMARY KEY NOT NULL,
+                name       TEXT NOT NULL,
+                checksum   TEXT NOT NULL,
+                applied_at TEXT NOT NULL
+            )",
+        )
+        .execute(&self.pool)
+        .await
+        .map(|_| ())
+        .map_err(query_error)
+    }
+}
+
+fn query_error(e: sqlx::Error) -> DatabaseError {
+    DatabaseError::Query(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn memory_migrator() -> Migrator {
+        Migrator::connect("sqlite::memory:").await.unwrap()
+    }
+
+    #[test]
+    fn embedded_migrations_are_ordered_and_unique() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(
+                pair[0].version < pair[1].version,
+                "{} / {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert!(MIGRATIONS.iter().all(|m| !m.sql.trim().is_empty()));
+    }
+
+    #[tokio::test]
+    async fn dry_run_reports_without_applying() {
+        let migrator = memory_migrator().await;
+
+        let report = migrator.run(true).await.unwrap();
+
+        assert_eq!(report.applied.len(), MIGRATIONS.len());
+        assert!(report.to_string().starts_with("Would apply"));
+        assert_eq!(migrator.pending().await.unwrap().len(), MIGRATIONS.len());
+    }
+
+    #[tokio::test]
+    async fn run_applies_all_then_is_a_no_op() {
+        let migrator = memory_migrator().await;
+
+        let first = migrator.run(false).await.unwrap();
+        let second = migrator.run(false).await.unwrap();
+
+        assert_eq!(first.applied.len(), MIGRATIONS.len());
+        assert!(second.applied.is_empty());
+        assert!(migrator.pending().await.unwrap().is_empty());
+
+        // Schema is usable
+        sqlx::query("INSERT INTO verbs (id, title, state, created_at, updated_at, context) VALUES ('1', 't', 'Captured', 'now', 'now', 'desk')")
+            .execute(&migrator.pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn detects_modified_migration() {
+        static ORIGINAL: &[Migration] = &[Migration {
+            version: 1,
+            name: "t",
+            sql: "CREATE TABLE t (id INTEGER);",
+        }];
+        static EDITED: &[Migration] = &[Migration {
+            version: 1,
+            name: "t",
+            sql: "CREATE TABLE t (id INTEGER, extra TEXT);",
+        }];
+        let migrator = memory_migrator().await.with_migrations(ORIGINAL);
+        migrator.run(false).await.unwrap();
+
+        let migrator = migrator.with_migrations(EDITED);
+
+        assert!(matches!(
+            migrator.pending().await,
+            Err(DatabaseError::Migration(msg)) if msg.contains("modified")
+        ));
+    }
+}
@@ -12,11 +12,15 @@ mod builder;
 mod database;
 mod dyn_db; // ← NEW (type erasure layer)
 mod factory;
+mod migrations;
 mod singleton;
+mod snapshot;
 
 pub use builder::DatabaseBuilder;
-pub use database::{Database, DatabaseTransaction};
+pub use database::{Database, DatabaseStats, DatabaseTransaction};
 pub use factory::DatabaseFactory;
+pub use migrations::Migrator;
+pub use snapshot::Snapshot;
 
 use thiserror::Error;
 
@@ -36,4 +40,7 @@ pub enum DatabaseError {
 
     #[error("Query error: {0}")]
     Query(String),
+
+    #[error("Migration error: {0}")]
+    Migration(String),
 }
@@ -0,0 +1,40 @@
+//! Whole-database snapshots
+//!
+//! A [`Snapshot`] is every store of a database at one point in time,
+//! serializable with serde, so a state can be dumped from one process and
+//! restored into another — e.g. to replay a bug report against the exact
+//! data it happened on. Restoring revalidates every value object, so a
+//! hand-edited snapshot can't smuggle invalid data into the repositories.
+//!
+//! Attachment *contents* live in the blob store and are not included;
+//! only their metadata is. The audit log isn't either: restoring a dump
+//! must not rewrite the record of who did what.
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::domain::model::{
+    ActionLog, Attachment, NotificationPreferences, Verb, Webhook, WebhookDelivery,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Snapshot {
+    /// Layout version; bumped whenever a stored model changes shape
+    pub format: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub taken_at: OffsetDateTime,
+    pub verbs: Vec<Verb>,
+    pub action_logs: Vec<ActionLog>,
+    pub webhooks: Vec<Webhook>,
+    pub webhook_deliveries: Vec<WebhookDelivery>,
+    pub attachments: Vec<Attachment>,
+    /// Absent from dumps taken before preferences existed
+    #[serde(default)]
+    pub notification_preferences: Vec<NotificationPreferences>,
+}
+
+impl Snapshot {
+    /// The layout this build writes and accepts
+    pub const FORMAT: u32 = 1;
+}
@@ -1,4 +1,8 @@
 //! Infrastructure Layer
+pub mod blob;
+pub mod cache;
 pub mod db;
 pub mod error;
+pub mod notify;
 pub mod repository;
+pub mod webhook;
@@ -0,0 +1,7 @@
+//! Outbound notifications
+//!
+//! Adapters for the application's [`Notifier`](crate::application::Notifier)
+//! port.
+mod smtp;
+
+pub use smtp::SmtpNotifier;
@@ -0,0 +1,136 @@
+use std::pin::Pin;
+
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+
+use crate::{
+    application::{Notification, Notifier, NotifyError},
+    config::{SmtpConfig, SmtpTls},
+};
+
+/// Sends notifications as plain-text email to a fixed recipient list
+#[derive(Clone)]
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl SmtpNotifier {
+    /// Validate addresses and prepare the transport; no connection is made
+    /// until the first send
+    pub fn new(cfg: &SmtpConfig) -> Result<Self, NotifyError> {
+        let mailbox = |raw: &str| {
+            raw.parse::<Mailbox>()
+                .map_err(|e| NotifyError(format!("invalid address '{raw}': {e}")))
+        };
+        let from = mailbox(&cfg.from)?;
+        let to = cfg
+            .to
+            .iter()
+            .map(|raw| mailbox(raw))
+            .collect::<Result<Vec<_>, _>>()?;
+        if to.is_empty() {
+            return Err(NotifyError("no recipients configured".into()));
+        }
+
+        let builder = match cfg.tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&cfg.host),
+            SmtpTls::Implicit => AsyncSmtpTransport::<Tokio1Executor>::relay(&cfg.host),
+            SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &cfg.host,
+            )),
+        }
+        .map_err(|e| NotifyError(e.to_string()))?
+        .port(cfg.port);
+
+        let builder = match &cfg.credentials {
+            Some((user, pass)) => builder.credentials(Credentials::new(user.clone(), pass.clone())),
+            None => builder,
+        };
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            to,
+        })
+    }
+
+    fn message(&self, notification: &Notification) -> Result<Message, NotifyError> {
+        let builder = self
+            .to
+            .iter()
+            .fold(Message::builder().from(self.from.clone()), |b, to| {
+                b.to(to.clone())
+            });
+
+        builder
+            .subject(&notification.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(notification.body.clone())
+            .map_err(|e| NotifyError(e.to_string()))
+    }
+}
+
+impl Notifier for SmtpNotifier {
+    fn notify<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> Pin<Box<dyn Future<Output = Result<(), NotifyError>> + Send + 'a>> {
+        Box::pin(async move {
+            let message = self.message(notification)?;
+            self.transport
+                .send(message)
+                .await
+                .map_err(|e| NotifyError(e.to_string()))?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg() -> SmtpConfig {
+        SmtpConfig {
+            host: "localhost".into(),
+            port: 1025,
+            tls: SmtpTls::None,
+            credentials: None,
+            from: "Verb <verb@example.com>".into(),
+            to: vec!["me@example.com".into(), "you@example.com".into()],
+        }
+    }
+
+    #[test]
+    fn builds_plain_text_message_for_every_recipient() {
+        let notifier = SmtpNotifier::new(&cfg()).unwrap();
+
+        let message = notifier
+            .message(&Notification {
+                subject: "Daily review".into(),
+                body: "Overdue (0)".into(),
+            })
+            .unwrap();
+
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        assert!(raw.contains("To: me@example.com, you@example.com"));
+        assert!(raw.contains("Subject: Daily review"));
+        assert!(raw.contains("Content-Type: text/plain"));
+        assert_eq!(message.envelope().to().len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        let cfg = SmtpConfig {
+            from: "not an address".into(),
+            ..cfg()
+        };
+
+        assert!(SmtpNotifier::new(&cfg).is_err());
+    }
+}
@@ -0,0 +1,647 @@
+//! Read-through caching for repositories
+//!
+//! [`CachedDatabase`] wraps any [`Database`] so point lookups (a verb or
+//! webhook by id, a user's notification preferences) are answered from a
+//! [`Cache`] when it has them, and from the wrapped repository (filling
+//! the cache) when it doesn't. Lists and every other query always reach
+//! the repository. Use cases can't tell the difference.
+//!
+//! Caching is soft:
+//! - writes invalidate the entry rather than update it, and a key a
+//!   transaction wrote skips the cache until it commits, so uncommitted
+//!   data is never cached
+//! - commit invalidates written keys once more, dropping anything another
+//!   transaction cached in between
+//! - every invalidation bumps a generation counter, and a lookup only
+//!   fills the cache if the generation it started at is still current, so
+//!   a slow read can't cache what a concurrent commit just replaced
+//! - cache failures are logged and count as misses; they never fail a
+//!   request
+//!
+//! Each entity type has its own TTL in [`CachePolicy`]; one without a TTL
+//! isn't cached at all. Hits and misses per type are reported through
+//! [`Database::stats`].
+
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+use tracing::warn;
+
+use crate::{
+    application::{ApplicationError, Cache},
+    domain::{
+        model::{EventType, NotificationPreferences, UserId, Verb, VerbId, Webhook, WebhookId},
+        repository::{
+            ActionLogRepository, AttachmentRepository, AuditRepository,
+            NotificationPreferencesRepository, VerbFilter, VerbListResult, VerbRepository,
+            WebhookDeliveryRepository, WebhookRepository,
+        },
+    },
+    infra::db::{Database, DatabaseError, DatabaseStats, DatabaseTransaction, Snapshot},
+};
+
+type RepoFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, ApplicationError>> + Send + 'a>>;
+
+// ============================================================================
+// POLICY & METRICS
+// ============================================================================
+
+/// How long each entity type stays cached; `None` leaves it uncached
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub verbs: Option<Duration>,
+    pub webhooks: Option<Duration>,
+    pub notification_preferences: Option<Duration>,
+}
+
+impl CachePolicy {
+    /// Whether any entity type is cached
+    pub fn is_enabled(&self) -> bool {
+        self.verbs.is_some() || self.webhooks.is_some() || self.notification_preferences.is_some()
+    }
+
+    fn ttl(&self, entity: Entity) -> Option<Duration> {
+        match entity {
+            Entity::Verb => self.verbs,
+            Entity::Webhook => self.webhooks,
+            Entity::NotificationPreferences => self.notification_preferences,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Entity {
+    Verb,
+    Webhook,
+    NotificationPreferences,
+}
+
+impl Entity {
+    fn key(self, id: impl std::fmt::Display) -> String {
+        let prefix = match self {
+            Entity::Verb => "verb",
+            Entity::Webhook => "webhook",
+            Entity::NotificationPreferences => "notification_preferences",
+        };
+        format!("{prefix}:{id}")
+    }
+}
+
+/// Lookups answered from the cache (`hits`) or the repository (`misses`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheCounts {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Cache hits and misses per entity type, since startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub verbs: CacheCounts,
+    pub webhooks: CacheCounts,
+    pub notification_preferences: CacheCounts,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Counters {
+    fn load(&self) -> CacheCounts {
+        CacheCounts {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheMetrics {
+    verbs: Counters,
+    webhooks: Counters,
+    notification_preferences: Counters,
+}
+
+impl CacheMetrics {
+    fn record(&self, entity: Entity, hit: bool) {
+        let counters = match entity {
+            Entity::Verb => &self.verbs,
+            Entity::Webhook => &self.webhooks,
+            Entity::NotificationPreferences => &self.notification_preferences,
+        };
+        let counter = if hit {
+            &counters.hits
+        } else {
+            &counters.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            verbs: self.verbs.load(),
+            webhooks: self.webhooks.load(),
+            notification_preferences: self.notification_preferences.load(),
+        }
+    }
+}
+
+// ============================================================================
+// DATABASE DECORATOR
+// ============================================================================
+
+/// A [`Database`] whose repositories read through a [`Cache`]
+#[derive(Clone)]
+pub struct CachedDatabase<D: Database> {
+    inner: D,
+    cache: Arc<dyn Cache>,
+    policy: CachePolicy,
+    metrics: Arc<CacheMetrics>,
+    generation: Arc<AtomicU64>,
+}
+
+impl<D: Database> CachedDatabase<D> {
+    pub fn new(inner: D, cache: Arc<dyn Cache>, policy: CachePolicy) -> Self {
+        Self {
+            inner,
+            cache,
+            policy,
+            metrics: Arc::new(CacheMetrics::default()),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+impl<D> Database for CachedDatabase<D>
+where
+    D: Database,
+    for<'tx> D::Transaction<'tx>: Sync,
+{
+    type Transaction<'tx>
+        = CachedTransaction<D::Transaction<'tx>>
+    where
+        Self: 'tx;
+
+    fn begin_tx(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Transaction<'_>, DatabaseError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            Ok(CachedTransaction {
+                inner: self.inner.begin_tx().await?,
+                cache: Arc::clone(&self.cache),
+                policy: self.policy,
+                metrics: Arc::clone(&self.metrics),
+                generation: Arc::clone(&self.generation),
+                written: Mutex::new(HashSet::new()),
+            })
+        })
+    }
+
+    fn stats(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<DatabaseStats, DatabaseError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut stats = self.inner.stats().await?;
+            if self.policy.is_enabled() {
+                stats.cache = Some(self.metrics.stats());
+            }
+            Ok(stats)
+        })
+    }
+
+    fn flush_cache(&self) -> Pin<Box<dyn Future<Output = Result<(), DatabaseError>> + Send + '_>> {
+        Box::pin(async move {
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            self.cache
+                .clear()
+                .await
+                .map_err(|e| DatabaseError::Connection(e.to_string()))
+        })
+    }
+
+    fn snapshot(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Snapshot, DatabaseError>> + Send + '_>> {
+        self.inner.snapshot()
+    }
+
+    fn restore(
+        &self,
+        snapshot: Snapshot,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DatabaseError>> + Send + '_>> {
+        Box::pin(async move {
+            self.inner.restore(snapshot).await?;
+            // Every cached entity may be gone or different now
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            if let Err(e) = self.cache.clear().await {
+                warn!(error = %e, "Failed to clear cache after restore");
+            }
+            Ok(())
+        })
+    }
+}
+
+// ============================================================================
+// TRANSACTION DECORATOR
+// ============================================================================
+
+/// A transaction of the wrapped database, with cached point lookups
+pub struct CachedTransaction<T> {
+    inner: T,
+    cache: Arc<dyn Cache>,
+    policy: CachePolicy,
+    metrics: Arc<CacheMetrics>,
+    /// Bumped before every invalidation, shared by all transactions
+    generation: Arc<AtomicU64>,
+    /// Keys this transaction wrote; they bypass the cache until commit
+    written: Mutex<HashSet<String>>,
+}
+
+impl<T: DatabaseTransaction + Sync> CachedTransaction<T> {
+    /// `load` the entity stored under `key`, through the cache
+    async fn read_through<E>(
+        &self,
+        entity: Entity,
+        key: String,
+        load: RepoFuture<'_, Option<E>>,
+    ) -> Result<Option<E>, ApplicationError>
+    where
+        E: Serialize + DeserializeOwned,
+    {
+        let Some(ttl) = self.policy.ttl(entity) else {
+            return load.await;
+        };
+        if self.is_written(&key) {
+            return load.await;
+        }
+
+        match self.cache.get(&key).await {
+            Ok(Some(bytes)) => match serde_json::from_slice(&bytes) {
+                Ok(value) => {
+                    self.metrics.record(entity, true);
+                    return Ok(Some(value));
+                }
+                Err(e) => warn!(key, error = %e, "Discarding unreadable cache entry"),
+            },
+            Ok(None) => {}
+            Err(e) => warn!(key, error = %e, "Cache unavailable, reading repository"),
+        }
+        self.metrics.record(entity, false);
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let value = load.await?;
+        if let Some(value) = &value
+            && self.generation.load(Ordering::SeqCst) == generation
+        {
+            let stored = match serde_json::to_vec(value) {
+                Ok(bytes) => self.cache.set(&key, bytes, ttl).await,
+                Err(e) => Err(crate::application::CacheError(e.to_string())),
+            };
+            if let Err(e) = stored {
+                warn!(key, error = %e, "Failed to cache entity");
+            }
+            // An invalidation between the check and the fill may have
+            // missed what was just stored
+            if self.generation.load(Ordering::SeqCst) != generation {
+                invalidate(self.cache.as_ref(), &self.generation, &key).await;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Run a write, then drop `key` from the cache
+    async fn write_through<R>(
+        &self,
+        entity: Entity,
+        key: String,
+        write: RepoFuture<'_, R>,
+    ) -> Result<R, ApplicationError> {
+        if self.policy.ttl(entity).is_none() {
+            return write.await;
+        }
+        self.written
+            .lock()
+            .expect("written keys lock poisoned")
+            .insert(key.clone());
+
+        let result = write.await;
+        invalidate(self.cache.as_ref(), &self.generation, &key).await;
+        result
+    }
+
+    fn is_written(&self, key: &str) -> bool {
+        self.written
+            .lock()
+            .expect("written keys lock poisoned")
+            .contains(key)
+    }
+}
+
+/// Drop `key` from the cache, bumping `generation` first so lookups
+/// already in flight don't fill it back
+async fn invalidate(cache: &dyn Cache, generation: &AtomicU64, key: &str) {
+    generation.fetch_add(1, Ordering::SeqCst);
+    if let Err(e) = cache.delete(key).await {
+        warn!(key, error = %e, "Failed to invalidate cache entry");
+    }
+}
+
+impl<T: DatabaseTransaction + Sync> DatabaseTransaction for CachedTransaction<T> {
+    fn verb_repository(&self) -> &dyn VerbRepository {
+        self
+    }
+
+    fn action_log_repository(&self) -> &dyn ActionLogRepository {
+        self.inner.action_log_repository()
+    }
+
+    fn webhook_repository(&self) -> &dyn WebhookRepository {
+        self
+    }
+
+    fn webhook_delivery_repository(&self) -> &dyn WebhookDeliveryRepository {
+        self.inner.webhook_delivery_repository()
+    }
+
+    fn attachment_repository(&self) -> &dyn AttachmentRepository {
+        self.inner.attachment_repository()
+    }
+
+    fn notification_preferences_repository(&self) -> &dyn NotificationPreferencesRepository {
+        self
+    }
+
+    fn audit_repository(&self) -> &dyn AuditRepository {
+        self.inner.audit_repository()
+    }
+
+    fn commit(self) -> Pin<Box<dyn Future<Output = Result<(), DatabaseError>> + Send + 'static>> {
+        let written = self
+            .written
+            .into_inner()
+            .expect("written keys lock poisoned");
+        let cache = self.cache;
+        let generation = self.generation;
+        let commit = self.inner.commit();
+
+        Box::pin(async move {
+            commit.await?;
+            for key in written {
+                invalidate(cache.as_ref(), &generation, &key).await;
+            }
+            Ok(())
+        })
+    }
+
+    fn rollback(self) -> Pin<Box<dyn Future<Output = Result<(), DatabaseError>> + Send + 'static>> {
+        self.inner.rollback()
+    }
+}
+
+impl<T: DatabaseTransaction + Sync> VerbRepository for CachedTransaction<T> {
+    fn save(&self, verb: &Verb) -> RepoFuture<'_, ()> {
+        let key = Entity::Verb.key(verb.id());
+        let write = self.inner.verb_repository().save(verb);
+        Box::pin(self.write_through(Entity::Verb, key, write))
+    }
+
+    fn find_by_id(&self, id: VerbId) -> RepoFuture<'_, Option<Verb>> {
+        let load = self.inner.verb_repository().find_by_id(id);
+        Box::pin(self.read_through(Entity::Verb, Entity::Verb.key(id), load))
+    }
+
+    fn list(&self, filter: VerbFilter) -> RepoFuture<'_, VerbListResult> {
+        self.inner.verb_repository().list(filter)
+    }
+}
+
+impl<T: DatabaseTransaction + Sync> WebhookRepository for CachedTransaction<T> {
+    fn save(&self, webhook: &Webhook) -> RepoFuture<'_, ()> {
+        let key = Entity::Webhook.key(webhook.id());
+        let write = self.inner.webhook_repository().save(webhook);
+        Box::pin(self.write_through(Entity::Webhook, key, write))
+    }
+
+    fn find_by_id(&self, id: WebhookId) -> RepoFuture<'_, Option<Webhook>> {
+        let load = self.inner.webhook_repository().find_by_id(id);
+        Box::pin(self.read_through(Entity::Webhook, Entity::Webhook.key(id), load))
+    }
+
+    fn list(&self) -> RepoFuture<'_, Vec<Webhook>> {
+        self.inner.webhook_repository().list()
+    }
+
+    fn find_by_event(&self, event: EventType) -> RepoFuture<'_, Vec<Webhook>> {
+        self.inner.webhook_repository().find_by_event(event)
+    }
+
+    fn delete(&self, id: WebhookId) -> RepoFuture<'_, bool> {
+        let write = self.inner.webhook_repository().delete(id);
+        Box::pin(self.write_through(Entity::Webhook, Entity::Webhook.key(id), write))
+    }
+}
+
+impl<T: DatabaseTransaction + Sync> NotificationPreferencesRepository for CachedTransaction<T> {
+    fn save(&self, preferences: &NotificationPreferences) -> RepoFuture<'_, ()> {
+        let entity = Entity::NotificationPreferences;
+        let key = entity.key(preferences.user_id());
+        let write = self
+            .inner
+            .notification_preferences_repository()
+            .save(preferences);
+        Box::pin(self.write_through(entity, key, write))
+    }
+
+    fn find_by_user(&self, user_id: UserId) -> RepoFuture<'_, Option<NotificationPreferences>> {
+        let entity = Entity::NotificationPreferences;
+        let load = self
+            .inner
+            .notification_preferences_repository()
+            .find_by_user(user_id);
+        Box::pin(self.read_through(entity, entity.key(user_id), load))
+    }
+
+    fn delete(&self, user_id: UserId) -> RepoFuture<'_, bool> {
+        let entity = Entity::NotificationPreferences;
+        let write = self
+            .inner
+            .notification_preferences_repository()
+            .delete(user_id);
+        Box::pin(self.write_through(entity, entity.key(user_id), write))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::model::{Description, Title},
+        infra::{cache::InMemoryCache, repository::in_memory::InMemoryDatabase},
+    };
+
+    const TTL: Option<Duration> = Some(Duration::from_secs(60));
+
+    fn cached(policy: CachePolicy) -> CachedDatabase<InMemoryDatabase> {
+        CachedDatabase::new(
+            InMemoryDatabase::new(),
+            Arc::new(InMemoryCache::new()),
+            policy,
+        )
+    }
+
+    fn all_cached() -> CachePolicy {
+        CachePolicy {
+            verbs: TTL,
+            webhooks: TTL,
+            notification_preferences: TTL,
+        }
+    }
+
+    fn verb(id: VerbId, title: &str) -> Verb {
+        Verb::new_with_id(id, Title::new(title).unwrap(), Description::default())
+    }
+
+    async fn save(db: &impl Database, verb: &Verb) {
+        let tx = db.begin_tx().await.unwrap();
+        tx.verb_repository().save(verb).await.unwrap();
+        tx.commit().await.unwrap();
+    }
+
+    async fn cache_stats(db: &impl Database) -> CacheStats {
+        db.stats().await.unwrap().cache.unwrap()
+    }
+
+    async fn find(db: &impl Database, id: VerbId) -> Option<Verb> {
+        let tx = db.begin_tx().await.unwrap();
+        tx.verb_repository().find_by_id(id).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn repeated_lookups_hit_the_cache() {
+        let db = cached(all_cached());
+        let verb = verb(VerbId::new(), "Write");
+        save(&db, &verb).await;
+
+        assert_eq!(find(&db, verb.id()).await.unwrap().title(), "Write");
+        assert_eq!(find(&db, verb.id()).await.unwrap().title(), "Write");
+        // Unknown ids aren't cached
+        assert!(find(&db, VerbId::new()).await.is_none());
+        assert!(find(&db, VerbId::new()).await.is_none());
+
+        assert_eq!(
+            cache_stats(&db).await.verbs,
+            CacheCounts { hits: 1, misses: 3 }
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_invalidate_and_skip_the_cache_until_commit() {
+        let db = cached(all_cached());
+        let before = verb(VerbId::new(), "Before");
+        save(&db, &before).await;
+        find(&db, before.id()).await;
+
+        let verb = verb(before.id(), "After");
+        let tx = db.begin_tx().await.unwrap();
+        tx.verb_repository().save(&verb).await.unwrap();
+        // The writer sees its own write; nothing uncommitted is cached
+        let own = tx.verb_repository().find_by_id(verb.id()).await.unwrap();
+        assert_eq!(own.unwrap().title(), "After");
+        tx.commit().await.unwrap();
+
+        assert_eq!(find(&db, verb.id()).await.unwrap().title(), "After");
+        assert_eq!(
+            cache_stats(&db).await.verbs,
+            CacheCounts { hits: 0, misses: 2 }
+        );
+    }
+
+    #[tokio::test]
+    async fn lookups_racing_a_commit_dont_cache_what_it_replaced() {
+        let db = cached(all_cached());
+        let before = verb(VerbId::new(), "Before");
+        save(&db, &before).await;
+
+        let tx = db.begin_tx().await.unwrap();
+        let after = verb(before.id(), "After");
+        // The repository answers, then another transaction commits before
+        // the answer reaches the cache
+        let load: RepoFuture<'_, Option<Verb>> = Box::pin(async {
+            let stale = tx.inner.verb_repository().find_by_id(before.id()).await;
+            save(&db, &after).await;
+            stale
+        });
+        let read = tx
+            .read_through(Entity::Verb, Entity::Verb.key(before.id()), load)
+            .await
+            .unwrap();
+        assert_eq!(read.unwrap().title(), "Before");
+
+        assert_eq!(find(&db, before.id()).await.unwrap().title(), "After");
+    }
+
+    #[tokio::test]
+    async fn flushing_drops_every_entry() {
+        let db = cached(all_cached());
+        let verb = verb(VerbId::new(), "Write");
+        save(&db, &verb).await;
+        find(&db, verb.id()).await;
+
+        db.flush_cache().await.unwrap();
+        find(&db, verb.id()).await;
+
+        assert_eq!(
+            cache_stats(&db).await.verbs,
+            CacheCounts { hits: 0, misses: 2 }
+        );
+    }
+
+    #[tokio::test]
+    async fn uncached_entities_and_disabled_policies_pass_through() {
+        let db = cached(CachePolicy {
+            webhooks: TTL,
+            ..CachePolicy::default()
+        });
+        let verb = verb(VerbId::new(), "Write");
+        save(&db, &verb).await;
+        find(&db, verb.id()).await;
+        find(&db, verb.id()).await;
+        assert_eq!(cache_stats(&db).await.verbs, CacheCounts::default());
+
+        let off = cached(CachePolicy::default());
+        assert_eq!(off.stats().await.unwrap().cache, None);
+    }
+}
+
+#[cfg(test)]
+mod conformance {
+    use std::sync::Arc;
+
+    use super::{CachePolicy, CachedDatabase};
+    use crate::infra::{
+        cache::InMemoryCache,
+        repository::{conformance::repository_conformance, in_memory::InMemoryDatabase},
+    };
+
+    // A cache in front must not change what repositories return
+    repository_conformance!(CachedDatabase::new(
+        InMemoryDatabase::new(),
+        Arc::new(InMemoryCache::new()),
+        CachePolicy {
+            verbs: Some(std::time::Duration::from_secs(60)),
+            webhooks: Some(std::time::Duration::from_secs(60)),
+            notification_preferences: Some(std::time::Duration::from_secs(60)),
+        },
+    ));
+}
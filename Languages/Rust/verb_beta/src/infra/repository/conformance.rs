@@ -0,0 +1,506 @@
+//! Repository conformance suite
+//!
+//! Backend-agnostic checks of the `VerbRepository` / `ActionLogRepository`
+//! port contract, written against [`Database`] so every backend is held to
+//! the same rules:
+//!
+//! - `find_by_id` on an unknown id is `Ok(None)`, never an error
+//! - `save` is an upsert keyed by id
+//! - verbs list newest `updated_at` first; logs list newest `timestamp` first
+//! - filters apply before pagination, and `total` counts every match
+//! - daily counts group by UTC day, oldest first, skipping idle days
+//! - logs read `since` a cursor come in append order, whatever their timestamp
+//! - notification preferences are one record per user
+//! - audit entries filter by actor and time before pagination
+//! - committed writes are visible to later transactions
+//!
+//! Run the suite for a backend with [`repository_conformance!`]:
+//!
+//! ```ignore
+//! repository_conformance!(InMemoryDatabase::new());
+//! // Backends with real transactions also check rollback:
+//! repository_conformance!(SqliteDatabase::connect("sqlite::memory:").await.unwrap(), transactional);
+//! ```
+//!
+//! Only the in-memory backend exists today; a SQLite backend should be
+//! added to the suite with `transactional` as soon as it lands.
+
+use time::{Duration, OffsetDateTime, format_description::well_known::Rfc3339};
+
+use crate::{
+    domain::{
+        model::{
+            ActionLog, ActionLogId, ActionType, Actor, AuditEntry, Channel,
+            NotificationPreferences, UserId, Verb, VerbId, VerbPatch, VerbState,
+        },
+        repository::{AuditFilter, VerbFilter, action_log_repo::ActionLogFilter},
+    },
+    infra::db::{Database, DatabaseTransaction},
+};
+
+/// Generate one `#[tokio::test]` per conformance check for a backend
+///
+/// `$make_db` is evaluated inside each (async) test, so it may `.await`.
+macro_rules! repository_conformance {
+    ($make_db:expr) => {
+        $crate::infra::repository::conformance::repository_conformance!(@checks $make_db;
+            verb_round_trips,
+            unknown_verb_is_none,
+            verb_save_is_upsert,
+            verbs_list_newest_updated_first,
+            verb_filter_applies_before_pagination,
+            logs_list_newest_first_for_one_verb,
+            log_filter_applies_before_pagination,
+            logs_of_unknown_verb_are_empty,
+            daily_counts_group_by_utc_day,
+            logs_since_cursor_follow_append_order,
+            preferences_are_one_per_user,
+            audit_filter_applies_before_pagination,
+            committed_writes_are_visible
+        );
+    };
+    ($make_db:expr, transactional) => {
+        $crate::infra::repository::conformance::repository_conformance!($make_db);
+        $crate::infra::repository::conformance::repository_conformance!(@checks $make_db;
+            rolled_back_writes_are_discarded
+        );
+    };
+    (@checks $make_db:expr; $($check:ident),+) => {
+        $(
+            #[tokio::test]
+            async fn $check() {
+                let db = $make_db;
+                $crate::infra::repository::conformance::$check(&db).await;
+            }
+        )+
+    };
+}
+
+pub(crate) use repository_conformance;
+
+// ==================================================
+// Fixtures
+// ==================================================
+
+fn base_time() -> OffsetDateTime {
+    OffsetDateTime::now_utc() - Duration::days(1)
+}
+
+fn verb(title: &str, state: VerbState, updated_at: OffsetDateTime) -> Verb {
+    Verb::from_parts(
+        VerbId::new(),
+        title.to_string(),
+        String::new(),
+        None,
+        None,
+        state,
+        updated_at - Duration::hours(1),
+        updated_at,
+    )
+    .unwrap()
+}
+
+fn log(verb_id: VerbId, action_type: ActionType, timestamp: OffsetDateTime) -> ActionLog {
+    ActionLog::from_parts(
+        ActionLogId::new(),
+        verb_id,
+        action_type,
+        None,
+        VerbState::Active,
+        None,
+        Vec::new(),
+        timestamp,
+    )
+}
+
+async fn save_all<D: Database>(db: &D, verbs: &[Verb]) {
+    let tx = db.begin_tx().await.unwrap();
+    for verb in verbs {
+        tx.verb_repository().save(verb).await.unwrap();
+    }
+    tx.commit().await.unwrap();
+}
+
+async fn append_all<D: Database>(db: &D, logs: &[ActionLog]) {
+    let tx = db.begin_tx().await.unwrap();
+    for log in logs {
+        tx.action_log_repository().append(log).await.unwrap();
+    }
+    tx.commit().await.unwrap();
+}
+
+async fn list_titles<D: Database>(db: &D, filter: VerbFilter) -> (Vec<String>, u32) {
+    let tx = db.begin_tx().await.unwrap();
+    let result = tx.verb_repository().list(filter).await.unwrap();
+    let titles = result.verbs.iter().map(|v| v.title().to_string()).collect();
+    (titles, result.total)
+}
+
+async fn find<D: Database>(db: &D, id: VerbId) -> Option<Verb> {
+    let tx = db.begin_tx().await.unwrap();
+    tx.verb_repository().find_by_id(id).await.unwrap()
+}
+
+// ==================================================
+// Verb repository
+// ==================================================
+
+pub async fn verb_round_trips<D: Database>(db: &D) {
+    let mut original = verb("Round trip", VerbState::Captured, base_time());
+    original
+        .apply_patch(VerbPatch {
+            context: Some(Some("desk".into())),
+            due_at: Some(Some(base_time() + Duration::days(3))),
+            ..Default::default()
+        })
+        .unwrap();
+    save_all(db, std::slice::from_ref(&original)).await;
+
+    let found = find(db, original.id()).await.expect("saved verb is found");
+
+    assert_eq!(found.id(), original.id());
+    assert_eq!(found.title(), original.title());
+    assert_eq!(found.description(), original.description());
+    assert_eq!(found.context(), original.context());
+    assert_eq!(found.due_at(), original.due_at());
+    assert_eq!(found.state(), original.state());
+    assert_eq!(found.created_at(), original.created_at());
+    assert_eq!(found.updated_at(), original.updated_at());
+}
+
+pub async fn unknown_verb_is_none<D: Database>(db: &D) {
+    save_all(db, &[verb("Other", VerbState::Captured, base_time())]).await;
+
+    assert!(find(db, VerbId::new()).await.is_none());
+}
+
+pub async fn verb_save_is_upsert<D: Database>(db: &D) {
+    let mut v = verb("Upsert", VerbState::Captured, base_time());
+    save_all(db, std::slice::from_ref(&v)).await;
+
+    v.transition_to(VerbState::Active, None).unwrap();
+    save_all(db, std::slice::from_ref(&v)).await;
+
+    let (titles, total) = list_titles(db, VerbFilter::default()).await;
+    assert_eq!((titles.len(), total), (1, 1), "no duplicate rows");
+    assert_eq!(find(db, v.id()).await.unwrap().state(), VerbState::Active);
+}
+
+pub async fn verbs_list_newest_updated_first<D: Database>(db: &D) {
+    let t = base_time();
+    save_all(
+        db,
+        &[
+            verb("middle", VerbState::Captured, t + Duration::minutes(1)),
+            verb("oldest", VerbState::Captured, t),
+            verb("newest", VerbState::Captured, t + Duration::minutes(2)),
+        ],
+    )
+    .await;
+
+    let (titles, _) = list_titles(db, VerbFilter::default()).await;
+
+    assert_eq!(titles, ["newest", "middle", "oldest"]);
+}
+
+pub async fn verb_filter_applies_before_pagination<D: Database>(db: &D) {
+    let t = base_time();
+    let verbs: Vec<Verb> = (0..5)
+        .map(|i| {
+            let state = if i % 2 == 0 {
+                VerbState::Active
+            } else {
+                VerbState::Paused
+            };
+            verb(&format!("v{i}"), state, t + Duration::minutes(i))
+        })
+        .collect();
+    save_all(db, &verbs).await;
+
+    let filter = VerbFilter::new()
+        .with_state(VerbState::Active)
+        .with_limit(2)
+        .with_offset(1);
+    let (titles, total) = list_titles(db, filter).await;
+
+    // Active: v4, v2, v0 (newest first); skip one, take two
+    assert_eq!(titles, ["v2", "v0"]);
+    assert_eq!(total, 3, "total counts every match, not the page");
+
+    let (past_end, total) = list_titles(db, VerbFilter::new().with_offset(10)).await;
+    assert!(past_end.is_empty());
+    assert_eq!(total, 5);
+}
+
+// ==================================================
+// Action log repository
+// ==================================================
+
+pub async fn logs_list_newest_first_for_one_verb<D: Database>(db: &D) {
+    let (a, b) = (VerbId::new(), VerbId::new());
+    let t = base_time();
+    append_all(
+        db,
+        &[
+            log(a, ActionType::Created, t),
+            log(b, ActionType::Created, t + Duration::minutes(1)),
+            log(a, ActionType::Completed, t + Duration::minutes(3)),
+            log(a, ActionType::Activated, t + Duration::minutes(2)),
+        ],
+    )
+    .await;
+
+    let tx = db.begin_tx().await.unwrap();
+    let logs = tx
+        .action_log_repository()
+        .find_by_verb(a, &ActionLogFilter::default())
+        .await
+        .unwrap();
+
+    let types: Vec<_> = logs.iter().map(ActionLog::action_type).collect();
+    assert_eq!(
+        types,
+        [
+            ActionType::Completed,
+            ActionType::Activated,
+            ActionType::Created
+        ]
+    );
+    assert!(logs.iter().all(|l| l.verb_id() == a));
+}
+
+pub async fn log_filter_applies_before_pagination<D: Database>(db: &D) {
+    let id = VerbId::new();
+    let t = base_time();
+    let logs: Vec<ActionLog> = (0..6)
+        .map(|i| {
+            let action = if i % 2 == 0 {
+                ActionType::Paused
+            } else {
+                ActionType::Activated
+            };
+            log(id, action, t + Duration::minutes(i))
+        })
+        .collect();
+    append_all(db, &logs).await;
+
+    let filter = ActionLogFilter::new()
+        .with_state(ActionType::Paused)
+        .with_limit(2)
+        .with_offset(1);
+    let tx = db.begin_tx().await.unwrap();
+    let page = tx
+        .action_log_repository()
+        .find_by_verb(id, &filter)
+        .await
+        .unwrap();
+
+    // Paused at minutes 4, 2, 0 (newest first); skip one, take two
+    let minutes: Vec<_> = page
+        .iter()
+        .map(|l| (l.timestamp() - t).whole_minutes())
+        .collect();
+    assert_eq!(minutes, [2, 0]);
+    assert!(page.iter().all(|l| l.action_type() == ActionType::Paused));
+}
+
+pub async fn logs_of_unknown_verb_are_empty<D: Database>(db: &D) {
+    append_all(db, &[log(VerbId::new(), ActionType::Created, base_time())]).await;
+
+    let tx = db.begin_tx().await.unwrap();
+    let logs = tx
+        .action_log_repository()
+        .find_by_verb(VerbId::new(), &ActionLogFilter::default())
+        .await
+        .unwrap();
+
+    assert!(logs.is_empty());
+}
+
+pub async fn daily_counts_group_by_utc_day<D: Database>(db: &D) {
+    let at = |rfc3339: &str| OffsetDateTime::parse(rfc3339, &Rfc3339).unwrap();
+    let (a, b) = (VerbId::new(), VerbId::new());
+    append_all(
+        db,
+        &[
+            log(a, ActionType::Created, at("2026-02-28T12:00:00Z")),
+            log(a, ActionType::Created, at("2026-03-01T08:00:00Z")),
+            // Still the 1st in UTC
+            log(b, ActionType::Created, at("2026-03-01T23:30:00+02:00")),
+            log(a, ActionType::Activated, at("2026-03-01T09:00:00Z")),
+            log(a, ActionType::Completed, at("2026-03-03T10:00:00Z")),
+            log(b, ActionType::Dropped, at("2026-03-03T23:59:00Z")),
+            log(b, ActionType::Dropped, at("2026-03-04T00:00:00Z")),
+        ],
+    )
+    .await;
+
+    let tx = db.begin_tx().await.unwrap();
+    let counts = tx
+        .action_log_repository()
+        .daily_counts(at("2026-03-01T00:00:00Z"), at("2026-03-04T00:00:00Z"))
+        .await
+        .unwrap();
+
+    let summary: Vec<_> = counts
+        .iter()
+        .map(|c| (c.date.to_string(), c.created, c.completed, c.dropped))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("2026-03-01".to_string(), 2, 0, 0),
+            ("2026-03-03".to_string(), 0, 1, 1),
+        ]
+    );
+}
+
+pub async fn logs_since_cursor_follow_append_order<D: Database>(db: &D) {
+    let t = base_time();
+    let verb_id = VerbId::new();
+    append_all(
+        db,
+        &[
+            log(verb_id, ActionType::Created, t),
+            // An offline client's change, appended late with an older timestamp
+            log(verb_id, ActionType::Paused, t - Duration::hours(2)),
+            log(verb_id, ActionType::Activated, t + Duration::minutes(1)),
+        ],
+    )
+    .await;
+
+    let tx = db.begin_tx().await.unwrap();
+    let repo = tx.action_log_repository();
+
+    let first = repo.since(0, 2).await.unwrap();
+    let types: Vec<_> = first.logs.iter().map(ActionLog::action_type).collect();
+    assert_eq!(types, [ActionType::Created, ActionType::Paused]);
+    assert!(first.has_more);
+
+    let rest = repo.since(first.cursor, 10).await.unwrap();
+    let types: Vec<_> = rest.logs.iter().map(ActionLog::action_type).collect();
+    assert_eq!(types, [ActionType::Activated]);
+    assert!(!rest.has_more);
+
+    let caught_up = repo.since(rest.cursor, 10).await.unwrap();
+    assert!(caught_up.logs.is_empty());
+    assert_eq!(caught_up.cursor, rest.cursor);
+}
+
+// ==================================================
+// Notification preferences
+// ==================================================
+
+pub async fn preferences_are_one_per_user<D: Database>(db: &D) {
+    let user = UserId::from_uuid(uuid::Uuid::new_v4());
+    let mut prefs = NotificationPreferences::defaults(user);
+    let tx = db.begin_tx().await.unwrap();
+    let repo = tx.notification_preferences_repository();
+
+    assert!(repo.find_by_user(user).await.unwrap().is_none());
+    repo.save(&prefs).await.unwrap();
+    prefs.set_channel(Channel::Email, false);
+    repo.save(&prefs).await.unwrap();
+
+    let found = repo.find_by_user(user).await.unwrap().unwrap();
+    assert!(!found.channel_enabled(Channel::Email));
+
+    assert!(repo.delete(user).await.unwrap());
+    assert!(!repo.delete(user).await.unwrap());
+    assert!(repo.find_by_user(user).await.unwrap().is_none());
+}
+
+// ==================================================
+// Audit log
+// ==================================================
+
+pub async fn audit_filter_applies_before_pagination<D: Database>(db: &D) {
+    let tx = db.begin_tx().await.unwrap();
+    let repo = tx.audit_repository();
+    let start = OffsetDateTime::now_utc() - Duration::seconds(1);
+    for actor in [Actor::Admin, Actor::Anonymous, Actor::Admin, Actor::Admin] {
+        let entry = AuditEntry::new(actor, None, "POST", "/api/v1/verbs", 201);
+        repo.append(&entry).await.unwrap();
+    }
+
+    let admin = AuditFilter {
+        actor: Some(Actor::Admin),
+        from: Some(start),
+        limit: 2,
+        ..AuditFilter::default()
+    };
+    let page = repo.list(&admin).await.unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(page.entries.len(), 2);
+    assert!(page.entries.iter().all(|e| e.actor() == Actor::Admin));
+    assert!(page.entries[0].timestamp() >= page.entries[1].timestamp());
+
+    let before_start = AuditFilter {
+        to: Some(start),
+        ..AuditFilter::default()
+    };
+    assert_eq!(repo.list(&before_start).await.unwrap().total, 0);
+}
+
+// ==================================================
+// Transactions
+// ==================================================
+
+pub async fn committed_writes_are_visible<D: Database>(db: &D) {
+    let v = verb("Committed", VerbState::Captured, base_time());
+    {
+        let tx = db.begin_tx().await.unwrap();
+        tx.verb_repository().save(&v).await.unwrap();
+        tx.action_log_repository()
+            .append(&ActionLog::created(v.id()))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+    }
+
+    let tx = db.begin_tx().await.unwrap();
+    assert!(
+        tx.verb_repository()
+            .find_by_id(v.id())
+            .await
+            .unwrap()
+            .is_some()
+    );
+    let logs = tx
+        .action_log_repository()
+        .find_by_verb(v.id(), &ActionLogFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+}
+
+/// Only for backends with real transactions; the in-memory backend
+/// applies writes immediately
+#[allow(dead_code)]
+pub async fn rolled_back_writes_are_discarded<D: Database>(db: &D) {
+    let v = verb("Rolled back", VerbState::Captured, base_time());
+    {
+        let tx = db.begin_tx().await.unwrap();
+        tx.verb_repository().save(&v).await.unwrap();
+        tx.action_log_repository()
+            .append(&ActionLog::created(v.id()))
+            .await
+            .unwrap();
+        tx.rollback().await.unwrap();
+    }
+
+    let tx = db.begin_tx().await.unwrap();
+    assert!(
+        tx.verb_repository()
+            .find_by_id(v.id())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let logs = tx
+        .action_log_repository()
+        .find_by_verb(v.id(), &ActionLogFilter::default())
+        .await
+        .unwrap();
+    assert!(logs.is_empty(), "verb and log roll back together");
+}
@@ -1,14 +1,16 @@
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use time::{OffsetDateTime, UtcOffset};
 use tokio::sync::Mutex;
 
 use crate::application::ApplicationError;
 use crate::domain::repository::action_log_repo::ActionLogFilter;
 use crate::domain::{
-    model::{ActionLog, VerbId},
-    repository::ActionLogRepository,
+    model::{ActionLog, ActionType, VerbId},
+    repository::{ActionLogChanges, ActionLogRepository, DailyActionCounts},
 };
 
 /// In-memory action log repository
@@ -78,4 +80,62 @@ impl ActionLogRepository for InMemoryActionLogRepo {
             Ok(logs)
         })
     }
+
+    fn daily_counts(
+        &self,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<DailyActionCounts>, ApplicationError>> + Send + '_>>
+    {
+        let store = Arc::clone(&self.store);
+        Box::pin(async move {
+            let guard = store.lock().await;
+
+            let days = guard
+                .iter()
+                .filter(|log| (from..to).contains(&log.timestamp()))
+                .filter(|log| {
+                    matches!(
+                        log.action_type(),
+                        ActionType::Created | ActionType::Completed | ActionType::Dropped
+                    )
+                })
+                .fold(BTreeMap::new(), |mut days, log| {
+                    let date = log.timestamp().to_offset(UtcOffset::UTC).date();
+                    days.entry(date)
+                        .or_insert_with(|| DailyActionCounts::new(date))
+                        .record(log.action_type());
+                    days
+                });
+
+            Ok(days.into_values().collect())
+        })
+    }
+
+    fn since(
+        &self,
+        cursor: u64,
+        limit: u32,
+    ) -> Pin<Box<dyn Future<Output = Result<ActionLogChanges, ApplicationError>> + Send + '_>> {
+        let store = Arc::clone(&self.store);
+        Box::pin(async move {
+            let guard = store.lock().await;
+
+            let start = usize::try_from(cursor)
+                .unwrap_or(usize::MAX)
+                .min(guard.len());
+            let logs: Vec<ActionLog> = guard[start..]
+                .iter()
+                .take(limit as usize)
+                .cloned()
+                .collect();
+            let end = start + logs.len();
+
+            Ok(ActionLogChanges {
+                cursor: end as u64,
+                has_more: end < guard.len(),
+                logs,
+            })
+        })
+    }
 }
@@ -0,0 +1,74 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::application::ApplicationError;
+use crate::domain::model::{Attachment, AttachmentId, VerbId};
+use crate::domain::repository::AttachmentRepository;
+
+/// In-memory attachment metadata repository
+#[derive(Clone)]
+pub struct InMemoryAttachmentRepo {
+    store: Arc<Mutex<Vec<Attachment>>>,
+}
+
+impl InMemoryAttachmentRepo {
+    pub fn new(store: Arc<Mutex<Vec<Attachment>>>) -> Self {
+        Self { store }
+    }
+}
+
+impl AttachmentRepository for InMemoryAttachmentRepo {
+    fn save(
+        &self,
+        attachment: &Attachment,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>> {
+        let attachment = attachment.clone();
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let mut guard = store.lock().await;
+
+            if let Some(existing) = guard.iter_mut().find(|a| a.id() == attachment.id()) {
+                *existing = attachment;
+            } else {
+                guard.push(attachment);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        id: AttachmentId,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<Attachment>, ApplicationError>> + Send + '_>>
+    {
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let guard = store.lock().await;
+            Ok(guard.iter().find(|a| a.id() == id).cloned())
+        })
+    }
+
+    fn find_by_verb(
+        &self,
+        verb_id: VerbId,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Attachment>, ApplicationError>> + Send + '_>> {
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let guard = store.lock().await;
+            let mut attachments: Vec<Attachment> = guard
+                .iter()
+                .filter(|a| a.verb_id() == verb_id)
+                .cloned()
+                .collect();
+            attachments.sort_by_key(Attachment::created_at);
+            Ok(attachments)
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::application::ApplicationError;
+use crate::domain::model::AuditEntry;
+use crate::domain::repository::{AuditFilter, AuditListResult, AuditRepository};
+
+/// In-memory audit log
+#[derive(Clone)]
+pub struct InMemoryAuditRepo {
+    store: Arc<Mutex<Vec<AuditEntry>>>,
+}
+
+impl InMemoryAuditRepo {
+    pub fn new(store: Arc<Mutex<Vec<AuditEntry>>>) -> Self {
+        Self { store }
+    }
+}
+
+impl AuditRepository for InMemoryAuditRepo {
+    fn append(
+        &self,
+        entry: &AuditEntry,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>> {
+        let entry = entry.clone();
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            store.lock().await.push(entry);
+            Ok(())
+        })
+    }
+
+    fn list(
+        &self,
+        filter: &AuditFilter,
+    ) -> Pin<Box<dyn Future<Output = Result<AuditListResult, ApplicationError>> + Send + '_>> {
+        let store = Arc::clone(&self.store);
+        let filter = filter.clone();
+
+        Box::pin(async move {
+            let guard = store.lock().await;
+
+            let mut entries: Vec<AuditEntry> = guard
+                .iter()
+                .filter(|e| filter.matches(e))
+                .cloned()
+                .collect();
+
+            drop(guard);
+
+            // Sort by timestamp desc
+            entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp()));
+
+            let total = entries.len() as u32;
+            let entries = entries
+                .into_iter()
+                .skip(filter.offset as usize)
+                .take(filter.limit as usize)
+                .collect();
+
+            Ok(AuditListResult { entries, total })
+        })
+    }
+}
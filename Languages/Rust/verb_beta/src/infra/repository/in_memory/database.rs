@@ -1,11 +1,19 @@
 use std::{pin::Pin, sync::Arc};
 
 use async_trait::async_trait;
+use time::OffsetDateTime;
 use tokio::sync::Mutex;
 
 use crate::infra::{
-    db::{Database, DatabaseError, DatabaseTransaction},
-    repository::in_memory::{action_log_repo::InMemoryActionLogRepo, verb_repo::InMemoryVerbRepo},
+    db::{Database, DatabaseError, DatabaseStats, DatabaseTransaction, Snapshot},
+    repository::in_memory::{
+        action_log_repo::InMemoryActionLogRepo,
+        attachment_repo::InMemoryAttachmentRepo,
+        audit_repo::InMemoryAuditRepo,
+        notification_preferences_repo::InMemoryNotificationPreferencesRepo,
+        verb_repo::InMemoryVerbRepo,
+        webhook_repo::{InMemoryWebhookDeliveryRepo, InMemoryWebhookRepo},
+    },
 };
 
 /// In-memory database implementation
@@ -21,6 +29,11 @@ use crate::infra::{
 pub struct InMemoryDatabase {
     verb_store: Arc<Mutex<Vec<crate::domain::model::Verb>>>,
     action_log_store: Arc<Mutex<Vec<crate::domain::model::ActionLog>>>,
+    webhook_store: Arc<Mutex<Vec<crate::domain::model::Webhook>>>,
+    delivery_store: Arc<Mutex<Vec<crate::domain::model::WebhookDelivery>>>,
+    attachment_store: Arc<Mutex<Vec<crate::domain::model::Attachment>>>,
+    preferences_store: Arc<Mutex<Vec<crate::domain::model::NotificationPreferences>>>,
+    audit_store: Arc<Mutex<Vec<crate::domain::model::AuditEntry>>>,
 }
 
 impl InMemoryDatabase {
@@ -28,10 +41,21 @@ impl InMemoryDatabase {
         Self {
             verb_store: Arc::new(Mutex::new(Vec::new())),
             action_log_store: Arc::new(Mutex::new(Vec::new())),
+            webhook_store: Arc::new(Mutex::new(Vec::new())),
+            delivery_store: Arc::new(Mutex::new(Vec::new())),
+            attachment_store: Arc::new(Mutex::new(Vec::new())),
+            preferences_store: Arc::new(Mutex::new(Vec::new())),
+            audit_store: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
 
+impl Default for InMemoryDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Database for InMemoryDatabase {
     /// GAT: Our transaction type is InMemoryTransaction
     ///
@@ -49,9 +73,91 @@ impl Database for InMemoryDatabase {
         // Clone Arcs (cheap - just reference count increment)
         let verb_store = Arc::clone(&self.verb_store);
         let action_log_store = Arc::clone(&self.action_log_store);
+        let webhook_store = Arc::clone(&self.webhook_store);
+        let delivery_store = Arc::clone(&self.delivery_store);
+        let attachment_store = Arc::clone(&self.attachment_store);
+        let preferences_store = Arc::clone(&self.preferences_store);
+        let audit_store = Arc::clone(&self.audit_store);
 
         // Return boxed future for object safety
-        Box::pin(async move { Ok(InMemoryTransaction::new(verb_store, action_log_store)) })
+        Box::pin(async move {
+            Ok(InMemoryTransaction::new(
+                verb_store,
+                action_log_store,
+                webhook_store,
+                delivery_store,
+                attachment_store,
+                preferences_store,
+                audit_store,
+            ))
+        })
+    }
+
+    fn stats(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<DatabaseStats, DatabaseError>> + Send + '_>> {
+        Box::pin(async move {
+            Ok(DatabaseStats {
+                backend: "in-memory",
+                verbs: self.verb_store.lock().await.len(),
+                action_logs: self.action_log_store.lock().await.len(),
+                webhooks: self.webhook_store.lock().await.len(),
+                webhook_deliveries: self.delivery_store.lock().await.len(),
+                attachments: self.attachment_store.lock().await.len(),
+                notification_preferences: self.preferences_store.lock().await.len(),
+                audit_entries: self.audit_store.lock().await.len(),
+                cache: None,
+            })
+        })
+    }
+
+    fn snapshot(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Snapshot, DatabaseError>> + Send + '_>> {
+        // The audit log is left out: it records requests, not data
+        Box::pin(async move {
+            // Hold every lock at once so the copy is consistent
+            let verbs = self.verb_store.lock().await;
+            let action_logs = self.action_log_store.lock().await;
+            let webhooks = self.webhook_store.lock().await;
+            let deliveries = self.delivery_store.lock().await;
+            let attachments = self.attachment_store.lock().await;
+            let preferences = self.preferences_store.lock().await;
+
+            Ok(Snapshot {
+                format: Snapshot::FORMAT,
+                taken_at: OffsetDateTime::now_utc(),
+                verbs: verbs.clone(),
+                action_logs: action_logs.clone(),
+                webhooks: webhooks.clone(),
+                webhook_deliveries: deliveries.clone(),
+                attachments: attachments.clone(),
+                notification_preferences: preferences.clone(),
+            })
+        })
+    }
+
+    fn restore(
+        &self,
+        snapshot: Snapshot,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DatabaseError>> + Send + '_>> {
+        Box::pin(async move {
+            // Same lock order as `snapshot`
+            let mut verbs = self.verb_store.lock().await;
+            let mut action_logs = self.action_log_store.lock().await;
+            let mut webhooks = self.webhook_store.lock().await;
+            let mut deliveries = self.delivery_store.lock().await;
+            let mut attachments = self.attachment_store.lock().await;
+            let mut preferences = self.preferences_store.lock().await;
+
+            *verbs = snapshot.verbs;
+            *action_logs = snapshot.action_logs;
+            *webhooks = snapshot.webhooks;
+            *deliveries = snapshot.webhook_deliveries;
+            *attachments = snapshot.attachments;
+            *preferences = snapshot.notification_preferences;
+            Ok(())
+        })
     }
 }
 
@@ -67,16 +173,31 @@ impl Database for InMemoryDatabase {
 pub struct InMemoryTransaction {
     verb_repo: InMemoryVerbRepo,
     action_log_repo: InMemoryActionLogRepo,
+    webhook_repo: InMemoryWebhookRepo,
+    delivery_repo: InMemoryWebhookDeliveryRepo,
+    attachment_repo: InMemoryAttachmentRepo,
+    preferences_repo: InMemoryNotificationPreferencesRepo,
+    audit_repo: InMemoryAuditRepo,
 }
 
 impl InMemoryTransaction {
     pub fn new(
         verb_store: Arc<Mutex<Vec<crate::domain::model::Verb>>>,
         action_log_store: Arc<Mutex<Vec<crate::domain::model::ActionLog>>>,
+        webhook_store: Arc<Mutex<Vec<crate::domain::model::Webhook>>>,
+        delivery_store: Arc<Mutex<Vec<crate::domain::model::WebhookDelivery>>>,
+        attachment_store: Arc<Mutex<Vec<crate::domain::model::Attachment>>>,
+        preferences_store: Arc<Mutex<Vec<crate::domain::model::NotificationPreferences>>>,
+        audit_store: Arc<Mutex<Vec<crate::domain::model::AuditEntry>>>,
     ) -> Self {
         Self {
             verb_repo: InMemoryVerbRepo::new(verb_store),
             action_log_repo: InMemoryActionLogRepo::new(action_log_store),
+            webhook_repo: InMemoryWebhookRepo::new(webhook_store),
+            delivery_repo: InMemoryWebhookDeliveryRepo::new(delivery_store),
+            attachment_repo: InMemoryAttachmentRepo::new(attachment_store),
+            preferences_repo: InMemoryNotificationPreferencesRepo::new(preferences_store),
+            audit_repo: InMemoryAuditRepo::new(audit_store),
         }
     }
 }
@@ -90,6 +211,30 @@ impl DatabaseTransaction for InMemoryTransaction {
         &self.action_log_repo
     }
 
+    fn webhook_repository(&self) -> &dyn crate::domain::repository::WebhookRepository {
+        &self.webhook_repo
+    }
+
+    fn webhook_delivery_repository(
+        &self,
+    ) -> &dyn crate::domain::repository::WebhookDeliveryRepository {
+        &self.delivery_repo
+    }
+
+    fn attachment_repository(&self) -> &dyn crate::domain::repository::AttachmentRepository {
+        &self.attachment_repo
+    }
+
+    fn notification_preferences_repository(
+        &self,
+    ) -> &dyn crate::domain::repository::NotificationPreferencesRepository {
+        &self.preferences_repo
+    }
+
+    fn audit_repository(&self) -> &dyn crate::domain::repository::AuditRepository {
+        &self.audit_repo
+    }
+
     fn commit(self) -> Pin<Box<dyn Future<Output = Result<(), DatabaseError>> + Send + 'static>> {
         // In-memory "commits" immediately, nothing to do
         Box::pin(async { Ok(()) })
@@ -1,5 +1,18 @@
 mod action_log_repo;
+mod attachment_repo;
+mod audit_repo;
 mod database;
+mod notification_preferences_repo;
 mod verb_repo;
+mod webhook_repo;
 
 pub use database::{InMemoryDatabase, InMemoryTransaction};
+
+#[cfg(test)]
+mod conformance {
+    use super::InMemoryDatabase;
+    use crate::infra::repository::conformance::repository_conformance;
+
+    // Writes apply immediately, so rollback checks don't apply
+    repository_conformance!(InMemoryDatabase::new());
+}
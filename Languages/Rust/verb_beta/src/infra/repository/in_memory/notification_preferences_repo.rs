@@ -0,0 +1,78 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::application::ApplicationError;
+use crate::domain::model::{NotificationPreferences, UserId};
+use crate::domain::repository::NotificationPreferencesRepository;
+
+/// In-memory notification preferences repository
+#[derive(Clone)]
+pub struct InMemoryNotificationPreferencesRepo {
+    store: Arc<Mutex<Vec<NotificationPreferences>>>,
+}
+
+impl InMemoryNotificationPreferencesRepo {
+    pub fn new(store: Arc<Mutex<Vec<NotificationPreferences>>>) -> Self {
+        Self { store }
+    }
+}
+
+impl NotificationPreferencesRepository for InMemoryNotificationPreferencesRepo {
+    fn save(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>> {
+        let preferences = preferences.clone();
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let mut guard = store.lock().await;
+
+            if let Some(existing) = guard
+                .iter_mut()
+                .find(|p| p.user_id() == preferences.user_id())
+            {
+                *existing = preferences;
+            } else {
+                guard.push(preferences);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: UserId,
+    ) -> Pin<
+        Box<
+            dyn Future<Output = Result<Option<NotificationPreferences>, ApplicationError>>
+                + Send
+                + '_,
+        >,
+    > {
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let guard = store.lock().await;
+            Ok(guard.iter().find(|p| p.user_id() == user_id).cloned())
+        })
+    }
+
+    fn delete(
+        &self,
+        user_id: UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, ApplicationError>> + Send + '_>> {
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let mut guard = store.lock().await;
+            let before = guard.len();
+            guard.retain(|p| p.user_id() != user_id);
+            Ok(guard.len() != before)
+        })
+    }
+}
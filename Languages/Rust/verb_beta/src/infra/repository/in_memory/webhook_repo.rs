@@ -0,0 +1,156 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::application::ApplicationError;
+use crate::domain::model::{EventType, Webhook, WebhookDelivery, WebhookId};
+use crate::domain::repository::{
+    DeliveryFilter, DeliveryListResult, WebhookDeliveryRepository, WebhookRepository,
+};
+
+/// In-memory webhook repository
+#[derive(Clone)]
+pub struct InMemoryWebhookRepo {
+    store: Arc<Mutex<Vec<Webhook>>>,
+}
+
+impl InMemoryWebhookRepo {
+    pub fn new(store: Arc<Mutex<Vec<Webhook>>>) -> Self {
+        Self { store }
+    }
+}
+
+impl WebhookRepository for InMemoryWebhookRepo {
+    fn save(
+        &self,
+        webhook: &Webhook,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>> {
+        let webhook = webhook.clone();
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let mut guard = store.lock().await;
+
+            if let Some(existing) = guard.iter_mut().find(|w| w.id() == webhook.id()) {
+                *existing = webhook;
+            } else {
+                guard.push(webhook);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        id: WebhookId,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<Webhook>, ApplicationError>> + Send + '_>> {
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let guard = store.lock().await;
+            Ok(guard.iter().find(|w| w.id() == id).cloned())
+        })
+    }
+
+    fn list(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Webhook>, ApplicationError>> + Send + '_>> {
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move { Ok(store.lock().await.clone()) })
+    }
+
+    fn find_by_event(
+        &self,
+        event: EventType,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Webhook>, ApplicationError>> + Send + '_>> {
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let guard = store.lock().await;
+            Ok(guard
+                .iter()
+                .filter(|w| w.subscribes_to(event))
+                .cloned()
+                .collect())
+        })
+    }
+
+    fn delete(
+        &self,
+        id: WebhookId,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, ApplicationError>> + Send + '_>> {
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            let mut guard = store.lock().await;
+            let before = guard.len();
+            guard.retain(|w| w.id() != id);
+            Ok(guard.len() != before)
+        })
+    }
+}
+
+/// In-memory webhook delivery log
+#[derive(Clone)]
+pub struct InMemoryWebhookDeliveryRepo {
+    store: Arc<Mutex<Vec<WebhookDelivery>>>,
+}
+
+impl InMemoryWebhookDeliveryRepo {
+    pub fn new(store: Arc<Mutex<Vec<WebhookDelivery>>>) -> Self {
+        Self { store }
+    }
+}
+
+impl WebhookDeliveryRepository for InMemoryWebhookDeliveryRepo {
+    fn append(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApplicationError>> + Send + '_>> {
+        let delivery = delivery.clone();
+        let store = Arc::clone(&self.store);
+
+        Box::pin(async move {
+            store.lock().await.push(delivery);
+            Ok(())
+        })
+    }
+
+    fn find_by_webhook(
+        &self,
+        webhook_id: WebhookId,
+        filter: &DeliveryFilter,
+    ) -> Pin<Box<dyn Future<Output = Result<DeliveryListResult, ApplicationError>> + Send + '_>>
+    {
+        let store = Arc::clone(&self.store);
+        let filter = filter.clone();
+
+        Box::pin(async move {
+            let guard = store.lock().await;
+
+            let mut deliveries: Vec<WebhookDelivery> = guard
+                .iter()
+                .filter(|d| d.webhook_id() == webhook_id)
+                .cloned()
+                .collect();
+
+            drop(guard);
+
+            // Sort by timestamp desc
+            deliveries.sort_by_key(|d| std::cmp::Reverse(d.timestamp()));
+
+            let total = deliveries.len() as u32;
+            let deliveries = deliveries
+                .into_iter()
+                .skip(filter.offset as usize)
+                .take(filter.limit as usize)
+                .collect();
+
+            Ok(DeliveryListResult { deliveries, total })
+        })
+    }
+}
@@ -1 +1,4 @@
+pub mod cached;
+#[cfg(test)]
+pub(crate) mod conformance;
 pub mod in_memory;
@@ -0,0 +1,472 @@
+use std::{sync::Arc, time::Duration};
+
+use serde::Serialize;
+use time::OffsetDateTime;
+use tokio::task::JoinHandle;
+use tracing::instrument;
+
+use crate::{
+    application::{EventReceiver, TraceContext, VerbEvent},
+    domain::model::{Channel, NotificationTopic, Webhook, WebhookDelivery},
+    infra::db::{Database, DatabaseTransaction},
+};
+
+use super::signature::{SIGNATURE_HEADER, sign};
+
+/// Retry and timeout settings for webhook delivery
+#[derive(Debug, Clone)]
+pub struct DeliveryPolicy {
+    /// Total attempts per event and webhook, including the first
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles after each failed attempt
+    pub initial_backoff: Duration,
+    /// Upper bound for the wait between attempts
+    pub max_backoff: Duration,
+    /// Per-request timeout
+    pub timeout: Duration,
+}
+
+impl Default for DeliveryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl DeliveryPolicy {
+    /// Wait before attempt `attempt + 1`
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Background worker delivering verb events to subscribed webhooks.
+///
+/// Each (event, webhook) pair is delivered on its own task so one slow or
+/// failing receiver does not hold up the others.
+#[derive(Clone)]
+pub struct WebhookDispatcher<D: Database> {
+    db: Arc<D>,
+    client: reqwest::Client,
+    policy: DeliveryPolicy,
+}
+
+impl<D: Database> WebhookDispatcher<D> {
+    pub fn new(db: Arc<D>, policy: DeliveryPolicy) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(policy.timeout)
+            .user_agent(concat!("verb-webhooks/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+
+        Ok(Self { db, client, policy })
+    }
+
+    /// Consume events until every publisher is dropped
+    pub fn spawn(self, mut events: EventReceiver) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                self.dispatch(event).await;
+            }
+            tracing::debug!("Event channel closed, webhook dispatcher stopping");
+        })
+    }
+
+    /// Fan an event out to every subscribed webhook
+    ///
+    /// Deliveries join the event's trace; events published outside a
+    /// request start a new one.
+    #[instrument(skip(self, event), fields(event = %event.event_type, event_id = %event.id, trace_id))]
+    async fn dispatch(&self, event: VerbEvent) {
+        let trace = event.trace.clone().unwrap_or_else(TraceContext::new_root);
+        tracing::Span::current().record("trace_id", trace.trace_id());
+
+        let webhooks = match self.subscribers(&event).await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load webhook subscribers");
+                return;
+            }
+        };
+
+        if webhooks.is_empty() {
+            return;
+        }
+
+        let body = match serde_json::to_vec(&EventPayload::from(&event)) {
+            Ok(body) => Arc::new(body),
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize webhook payload");
+                return;
+            }
+        };
+
+        for webhook in webhooks {
+            let dispatcher = self.clone();
+            let event = event.clone();
+            let body = Arc::clone(&body);
+            let trace = trace.clone();
+            tokio::spawn(async move { dispatcher.deliver(&webhook, &event, &body, &trace).await });
+        }
+    }
+
+    /// Webhooks subscribed to the event whose owner's notification
+    /// preferences allow it now; webhooks without an owner always qualify
+    async fn subscribers(&self, event: &VerbEvent) -> anyhow::Result<Vec<Webhook>> {
+        let tx = self.db.begin_tx().await?;
+        let subscribed = tx
+            .webhook_repository()
+            .find_by_event(event.event_type)
+            .await?;
+
+        let topic = NotificationTopic::from(event.event_type);
+        let now = OffsetDateTime::now_utc();
+        let mut webhooks = Vec::with_capacity(subscribed.len());
+        for webhook in subscribed {
+            if let Some(owner) = webhook.owner() {
+                let preferences = tx
+                    .notification_preferences_repository()
+                    .find_by_user(owner)
+                    .await?;
+                if preferences.is_some_and(|p| !p.allows(Channel::Webhook, topic, now)) {
+                    tracing::debug!(
+                        webhook_id = %webhook.id(),
+                        user_id = %owner,
+                        "Delivery suppressed by notification preferences"
+                    );
+                    continue;
+                }
+            }
+            webhooks.push(webhook);
+        }
+        Ok(webhooks)
+    }
+
+    /// Deliver one event to one webhook, retrying until success or the
+    /// policy's attempt budget is spent. Every attempt is logged.
+    ///
+    /// Each attempt is a child span of `trace`, sent as `traceparent`.
+    #[instrument(skip_all, fields(webhook_id = %webhook.id(), event_id = %event.id))]
+    async fn deliver(
+        &self,
+        webhook: &Webhook,
+        event: &VerbEvent,
+        body: &[u8],
+        trace: &TraceContext,
+    ) {
+        let signature = format!("sha256={}", sign(webhook.secret(), body));
+
+        for attempt in 1..=self.policy.max_attempts {
+            let span = trace.child();
+            let mut request = self
+                .client
+                .post(webhook.url())
+                .header("traceparent", span.traceparent());
+            if let Some(tracestate) = span.tracestate() {
+                request = request.header("tracestate", tracestate);
+            }
+            let result = request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header("X-Verb-Event", event.event_type.as_str())
+                .header("X-Verb-Delivery", event.id.to_string())
+                .header(SIGNATURE_HEADER, &signature)
+                .body(body.to_vec())
+                .send()
+                .await;
+
+            let delivery = match result {
+                Ok(response) => WebhookDelivery::responded(
+                    webhook.id(),
+                    event.id,
+                    event.event_type,
+                    event.verb.id(),
+                    attempt,
+                    response.status().as_u16(),
+                ),
+                Err(e) => WebhookDelivery::errored(
+                    webhook.id(),
+                    event.id,
+                    event.event_type,
+                    event.verb.id(),
+                    attempt,
+                    e.to_string(),
+                ),
+            };
+
+            self.record(&delivery).await;
+
+            if delivery.succeeded() {
+                tracing::debug!(attempt, "Webhook delivered");
+                return;
+            }
+
+            tracing::warn!(
+                attempt,
+                status = ?delivery.response_status(),
+                error = ?delivery.error(),
+                "Webhook delivery failed"
+            );
+
+            if attempt < self.policy.max_attempts {
+                tokio::time::sleep(self.policy.backoff(attempt)).await;
+            }
+        }
+
+        tracing::error!(
+            attempts = self.policy.max_attempts,
+            "Giving up on webhook delivery"
+        );
+    }
+
+    async fn record(&self, delivery: &WebhookDelivery) {
+        let result = async {
+            let tx = self.db.begin_tx().await?;
+            tx.webhook_delivery_repository().append(delivery).await?;
+            tx.commit().await?;
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::error!(error = %e, "Failed to record webhook delivery");
+        }
+    }
+}
+
+// ==================================================
+// Wire format
+// ==================================================
+
+#[derive(Debug, Serialize)]
+struct EventPayload {
+    id: String,
+    event: &'static str,
+    occurred_at: String,
+    data: VerbPayload,
+}
+
+#[derive(Debug, Serialize)]
+struct VerbPayload {
+    id: String,
+    title: String,
+    description: String,
+    state: &'static str,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<&VerbEvent> for EventPayload {
+    fn from(event: &VerbEvent) -> Self {
+        let verb = &event.verb;
+        Self {
+            id: event.id.to_string(),
+            event: event.event_type.as_str(),
+            occurred_at: event.occurred_at.to_string(),
+            data: VerbPayload {
+                id: verb.id().to_string(),
+                title: verb.title().to_string(),
+                description: verb.description().to_string(),
+                state: verb.state().as_str(),
+                created_at: verb.created_at().to_string(),
+                updated_at: verb.updated_at().to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use axum::{Router, http::HeaderMap, http::StatusCode, routing::post};
+
+    use super::*;
+    use crate::{
+        application::EventPublisher,
+        domain::{
+            model::{Description, EventType, NotificationPreferences, Title, UserId, Verb},
+            repository::DeliveryFilter,
+        },
+        infra::repository::in_memory::InMemoryDatabase,
+    };
+
+    fn fast_policy(max_attempts: u32) -> DeliveryPolicy {
+        DeliveryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(5),
+            max_backoff: Duration::from_millis(20),
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        let policy = DeliveryPolicy {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            ..DeliveryPolicy::default()
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+        assert_eq!(policy.backoff(40), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_payload_and_retries_failures() {
+        // Receiver fails the first request, then accepts correctly signed ones
+        let calls = Arc::new(AtomicU32::new(0));
+        let receiver_calls = Arc::clone(&calls);
+        let traceparents = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let receiver_traceparents = Arc::clone(&traceparents);
+        let receiver = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: axum::body::Bytes| {
+                let calls = Arc::clone(&receiver_calls);
+                let traceparents = Arc::clone(&receiver_traceparents);
+                async move {
+                    traceparents
+                        .lock()
+                        .unwrap()
+                        .push(headers["traceparent"].to_str().unwrap().to_owned());
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return StatusCode::INTERNAL_SERVER_ERROR;
+                    }
+                    let expected = format!("sha256={}", sign("s3cret", &body));
+                    if headers[SIGNATURE_HEADER] == expected.as_str() {
+                        StatusCode::NO_CONTENT
+                    } else {
+                        StatusCode::UNAUTHORIZED
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, receiver).await.unwrap() });
+
+        let db = Arc::new(InMemoryDatabase::new());
+        let webhook = Webhook::new(
+            format!("http://{addr}/hook"),
+            vec![EventType::VerbDone],
+            Some("s3cret".into()),
+        )
+        .unwrap();
+        {
+            let tx = db.begin_tx().await.unwrap();
+            tx.webhook_repository().save(&webhook).await.unwrap();
+        }
+
+        let (publisher, events) = EventPublisher::channel();
+        let worker = WebhookDispatcher::new(Arc::clone(&db), fast_policy(3))
+            .unwrap()
+            .spawn(events);
+
+        let verb = Verb::new(Title::new("Ship it").unwrap(), Description::default());
+        let trace = TraceContext::from_headers(
+            Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            None,
+        );
+        publisher.publish(VerbEvent::new(EventType::VerbCreated, verb.clone()));
+        let done = trace
+            .clone()
+            .scope(async { VerbEvent::new(EventType::VerbDone, verb) })
+            .await;
+        publisher.publish(done);
+        drop(publisher);
+        worker.await.unwrap();
+
+        // Deliveries run on their own tasks; wait for the log to settle
+        let mut deliveries = Vec::new();
+        for _ in 0..100 {
+            let tx = db.begin_tx().await.unwrap();
+            deliveries = tx
+                .webhook_delivery_repository()
+                .find_by_webhook(webhook.id(), &DeliveryFilter::default())
+                .await
+                .unwrap()
+                .deliveries;
+            if deliveries.iter().any(|d| d.succeeded()) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let mut attempts: Vec<_> = deliveries
+            .iter()
+            .map(|d| (d.attempt(), d.response_status()))
+            .collect();
+        attempts.sort();
+        assert_eq!(attempts, vec![(1, Some(500)), (2, Some(204))]);
+        assert!(deliveries.iter().all(|d| d.event() == EventType::VerbDone));
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            2,
+            "unsubscribed event not sent"
+        );
+
+        // Both attempts join the publishing request's trace as new spans
+        let traceparents = traceparents.lock().unwrap();
+        let prefix = format!("00-{}-", trace.trace_id());
+        assert!(traceparents.iter().all(|tp| tp.starts_with(&prefix)));
+        assert_ne!(traceparents[0], traceparents[1]);
+        assert!(!traceparents.contains(&trace.traceparent()));
+    }
+
+    #[tokio::test]
+    async fn skips_webhooks_whose_owner_opted_out() {
+        let db = Arc::new(InMemoryDatabase::new());
+        let (muted, quiet) = (
+            UserId::from_uuid(uuid::Uuid::new_v4()),
+            UserId::from_uuid(uuid::Uuid::new_v4()),
+        );
+        let hook = |owner: Option<UserId>| {
+            let webhook =
+                Webhook::new("https://example.com/hook", vec![EventType::VerbDone], None).unwrap();
+            match owner {
+                Some(owner) => webhook.with_owner(owner),
+                None => webhook,
+            }
+        };
+        let (unowned, owned, muted_hook) = (hook(None), hook(Some(quiet)), hook(Some(muted)));
+        {
+            let tx = db.begin_tx().await.unwrap();
+            for webhook in [&unowned, &owned, &muted_hook] {
+                tx.webhook_repository().save(webhook).await.unwrap();
+            }
+            // `quiet` only muted another topic; `muted` switched webhooks off
+            let mut preferences = NotificationPreferences::defaults(quiet);
+            preferences.set_topic(NotificationTopic::VerbCreated, false);
+            tx.notification_preferences_repository()
+                .save(&preferences)
+                .await
+                .unwrap();
+            let mut preferences = NotificationPreferences::defaults(muted);
+            preferences.set_channel(Channel::Webhook, false);
+            tx.notification_preferences_repository()
+                .save(&preferences)
+                .await
+                .unwrap();
+        }
+
+        let dispatcher = WebhookDispatcher::new(db, fast_policy(1)).unwrap();
+        let verb = Verb::new(Title::new("Ship it").unwrap(), Description::default());
+        let ids: Vec<_> = dispatcher
+            .subscribers(&VerbEvent::new(EventType::VerbDone, verb))
+            .await
+            .unwrap()
+            .iter()
+            .map(Webhook::id)
+            .collect();
+
+        assert_eq!(ids, vec![unowned.id(), owned.id()]);
+    }
+}
@@ -0,0 +1,9 @@
+//! Outbound webhook delivery
+//!
+//! The [`WebhookDispatcher`] consumes application events and POSTs signed
+//! JSON payloads to every subscribed webhook, retrying with backoff and
+//! recording each attempt in the delivery log.
+mod dispatcher;
+mod signature;
+
+pub use dispatcher::{DeliveryPolicy, WebhookDispatcher};
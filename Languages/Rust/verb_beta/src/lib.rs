@@ -0,0 +1,45 @@
+//! The verb service, split from `main.rs` so the criterion benchmarks in
+//! `benches/` can drive it in-process
+//!
+//! Modules stay private. The two hidden modules below are what the binary
+//! and the benchmarks need; they aren't an API.
+
+mod api;
+mod application;
+mod bench;
+mod bootstrap;
+mod config;
+mod domain;
+mod error;
+mod infra;
+mod reload;
+mod server;
+
+/// What `main.rs` runs
+#[doc(hidden)]
+pub mod cli {
+    pub use crate::{
+        bench::{BenchOptions, run as run_bench},
+        config::{Config, telemetry::init_tracing},
+        error::AppResult,
+        infra::db::Migrator,
+        server::start_server,
+    };
+}
+
+/// What `benches/` measure: the facade over each database backend
+#[doc(hidden)]
+pub mod bench_support {
+    pub use crate::{
+        application::VerbFacade,
+        domain::{model::VerbState, repository::VerbFilter},
+        infra::{
+            cache::InMemoryCache,
+            db::Database,
+            repository::{
+                cached::{CachePolicy, CachedDatabase},
+                in_memory::InMemoryDatabase,
+            },
+        },
+    };
+}
@@ -1,23 +1,71 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
 use tracing::{debug, error};
 
-use crate::{bootstrap::bootstrap, config::telemetry, error::AppResult};
+use verb_beta::cli::{
+    AppResult, BenchOptions, Config, Migrator, init_tracing, run_bench, start_server,
+};
+
+/// What to run, from the command line
+///
+/// - `verb_beta` (or `verb_beta serve`): start the HTTP server
+/// - `verb_beta serve --snapshot <file>`: start from a database dump
+///   taken with `GET /admin/snapshot`
+/// - `verb_beta migrate [--dry-run]`: apply pending migrations and exit
+/// - `verb_beta bench [options]`: run the throughput benchmarks, see
+///   `src/bench`
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Serve { snapshot: Option<PathBuf> },
+    Migrate { dry_run: bool },
+    Bench(BenchOptions),
+}
 
-mod api;
-mod application;
-mod bootstrap;
-mod config;
-mod domain;
-mod error;
-mod infra;
-mod server;
+impl Command {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        match args {
+            [] => Ok(Self::Serve { snapshot: None }),
+            [cmd] if cmd == "serve" => Ok(Self::Serve { snapshot: None }),
+            [cmd, flag, path] if cmd == "serve" && flag == "--snapshot" => Ok(Self::Serve {
+                snapshot: Some(PathBuf::from(path)),
+            }),
+            [cmd, rest @ ..] if cmd == "migrate" => match rest {
+                [] => Ok(Self::Migrate { dry_run: false }),
+                [flag] if flag == "--dry-run" => Ok(Self::Migrate { dry_run: true }),
+                _ => Err(format!("unexpected arguments to migrate: {rest:?}")),
+            },
+            [cmd, rest @ ..] if cmd == "bench" => BenchOptions::parse(rest).map(Self::Bench),
+            _ => Err(format!(
+                "unknown command {args:?}; usage: verb_beta [serve [--snapshot <file>] | migrate [--dry-run] | bench]"
+            )),
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() -> AppResult<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = Command::parse(&args).map_err(anyhow::Error::msg)?;
+
     // Load configuration
-    let config = config::Config::load()?;
+    let mut config = Config::load()?;
+
+    let snapshot = match command {
+        // Benchmarks stay quiet: per-request logging would dominate the numbers
+        Command::Bench(options) => return run_bench(&config, options).await,
+        Command::Migrate { dry_run } => {
+            init_tracing(&config);
+            return migrate(&config, dry_run).await;
+        }
+        Command::Serve { snapshot } => snapshot,
+    };
+    if let Some(path) = snapshot {
+        config = config.with_snapshot_file(path)?;
+    }
 
     // Initialize telemetry
-    telemetry::init_tracing(&config);
+    let log_level = init_tracing(&config);
 
     debug!(
         host = %config.host,
@@ -27,17 +75,66 @@ async fn main() -> AppResult<()> {
     );
 
     // Start server
-    if let Err(e) = server::start_server(&config).await {
+    if let Err(e) = start_server(&config, log_level).await {
         error!(error = %e, "Server failed");
         std::process::exit(1);
     }
 
     Ok(())
+}
+
+/// `verb_beta migrate`: apply (or list, with `--dry-run`) pending migrations
+async fn migrate(config: &Config, dry_run: bool) -> AppResult<()> {
+    let url = config
+        .database_url
+        .as_deref()
+        .context("DATABASE_URL must be set to run migrations")?;
+
+    let migrator = Migrator::connect(url).await?;
+    let report = migrator.run(dry_run).await?;
+    print!("{report}");
+
+    Ok(())
+}
 
-    //start bootstrap - for testing and prototyping
-    // if let Err(e) = bootstrap().await {
-    //     error!(error = %e,
-    //     "Bootstrap failed");
-    //     std::process::exit(1)
-    // }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(
+            Command::parse(&args(&[])),
+            Ok(Command::Serve { snapshot: None })
+        );
+        assert_eq!(
+            Command::parse(&args(&["serve"])),
+            Ok(Command::Serve { snapshot: None })
+        );
+        assert_eq!(
+            Command::parse(&args(&["serve", "--snapshot", "bug-12.json"])),
+            Ok(Command::Serve {
+                snapshot: Some("bug-12.json".into())
+            })
+        );
+        assert!(Command::parse(&args(&["serve", "--snapshot"])).is_err());
+        assert_eq!(
+            Command::parse(&args(&["migrate"])),
+            Ok(Command::Migrate { dry_run: false })
+        );
+        assert_eq!(
+            Command::parse(&args(&["migrate", "--dry-run"])),
+            Ok(Command::Migrate { dry_run: true })
+        );
+        assert!(Command::parse(&args(&["migrate", "--force"])).is_err());
+        assert_eq!(
+            Command::parse(&args(&["bench"])),
+            Ok(Command::Bench(BenchOptions::default()))
+        );
+        assert!(Command::parse(&args(&["seed"])).is_err());
+    }
 }
@@ -0,0 +1,256 @@
+//! Config hot reload
+//!
+//! Watches the config file and applies [`ReloadableSettings`] (log
+//! filter, route limits, feature flags, CORS origins) to the running
+//! server. Each change is logged with its old and new value; changes to
+//! other settings are logged as ignored until the next restart. A file
+//! that doesn't validate is rejected as a whole and the current settings
+//! stay in force.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+    time::Duration,
+};
+
+use notify::{Event, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tracing::{info, instrument, warn};
+
+use crate::{
+    application::{Feature, FeatureFlags},
+    config::{
+        AllowedOrigins, ConfigError, ConfigFile, Environment, ReloadableSettings, SharedLimits,
+        telemetry::{self, LogLevelHandle},
+    },
+};
+
+/// Editors save in several writes; wait this long for them to settle
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// The live handles a reload updates
+#[derive(Clone)]
+pub struct LiveSettings {
+    pub environment: Environment,
+    pub log_level: Option<LogLevelHandle>,
+    pub features: FeatureFlags,
+    pub limits: SharedLimits,
+    pub cors_origins: AllowedOrigins,
+}
+
+/// Start watching `file`; `current` are the settings in force now
+///
+/// The watcher runs until the runtime shuts down.
+pub fn watch(
+    file: ConfigFile,
+    current: ReloadableSettings,
+    live: LiveSettings,
+) -> notify::Result<()> {
+    let (tx, rx) = mpsc::channel(1);
+    let name = file.path().file_name().map(ToOwned::to_owned);
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let touches_file = event.is_ok_and(|event| {
+            event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == name.as_deref())
+        });
+        if touches_file {
+            // Full means a reload is already pending
+            let _ = tx.try_send(());
+        }
+    })?;
+    // The directory, not the file: editors often replace the file on save
+    let dir = match file.path().parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    info!(path = %file.path().display(), "Watching config file");
+    tokio::spawn(async move {
+        // Dropping the watcher would stop the events
+        let _watcher = watcher;
+        run(rx, file, current, live).await;
+    });
+    Ok(())
+}
+
+async fn run(
+    mut changes: mpsc::Receiver<()>,
+    file: ConfigFile,
+    mut current: ReloadableSettings,
+    live: LiveSettings,
+) {
+    let mut vars = file.read().unwrap_or_default();
+
+    while changes.recv().await.is_some() {
+        tokio::time::sleep(SETTLE).await;
+        while changes.try_recv().is_ok() {}
+
+        let next_vars = match file.read() {
+            Ok(next_vars) => next_vars,
+            Err(e) => {
+                warn!(error = %e, "Config file unreadable; keeping current settings");
+                continue;
+            }
+        };
+        if let Some(next) = reload(&file, &vars, &next_vars, &current, &live) {
+            current = next;
+        }
+        vars = next_vars;
+    }
+}
+
+/// Apply the changes between two readings of the file; the new settings,
+/// if any were applied
+#[instrument(skip_all, fields(path = %file.path().display()))]
+fn reload(
+    file: &ConfigFile,
+    before: &BTreeMap<String, String>,
+    after: &BTreeMap<String, String>,
+    current: &ReloadableSettings,
+    live: &LiveSettings,
+) -> Option<ReloadableSettings> {
+    let (reloadable, fixed): (Vec<_>, Vec<_>) = file
+        .changed_keys(before, after)
+        .into_iter()
+        .partition(|key| ReloadableSettings::is_reloadable(key));
+
+    for key in &fixed {
+        warn!(key = %key, "Config setting changed but needs a restart; ignored");
+    }
+    if reloadable.is_empty() {
+        return None;
+    }
+
+    let next = match validate(&file.lookup(after)) {
+        Ok(next) => next,
+        Err(e) => {
+            warn!(error = %e, "Config reload rejected; keeping current settings");
+            return None;
+        }
+    };
+
+    apply(current, &next, live);
+    for key in &reloadable {
+        info!(
+            key = %key,
+            from = before.get(key).map_or("(unset)", String::as_str),
+            to = after.get(key).map_or("(unset)", String::as_str),
+            "Config setting reloaded"
+        );
+    }
+    Some(next)
+}
+
+fn validate(vars: &dyn Fn(&str) -> Option<String>) -> Result<ReloadableSettings, ConfigError> {
+    let next = ReloadableSettings::from_vars(vars)?;
+    FeatureFlags::from_overrides(&next.features).map_err(|e| ConfigError::EnvironmentVariable {
+        key: "APP_FEATURES".into(),
+        reason: e.to_string(),
+    })?;
+    Ok(next)
+}
+
+/// Push what differs between `current` and `next` into the live handles
+fn apply(current: &ReloadableSettings, next: &ReloadableSettings, live: &LiveSettings) {
+    if next.log_filter != current.log_filter
+        && let Some(log_level) = &live.log_level
+    {
+        let filter = next
+            .log_filter
+            .clone()
+            .unwrap_or_else(|| telemetry::default_filter(live.environment));
+        if let Err(e) = log_level.set(&filter) {
+            warn!(error = %e, "Invalid RUST_LOG; keeping the current log filter");
+        }
+    }
+
+    let names: BTreeSet<&String> = current
+        .features
+        .keys()
+        .chain(next.features.keys())
+        .collect();
+    for name in names {
+        let (was, now) = (current.features.get(name), next.features.get(name));
+        if was != now
+            && let Ok(feature) = Feature::from_str(name)
+        {
+            // Back to the default (on) when dropped from the list
+            live.features.set(feature, now.copied().unwrap_or(true));
+        }
+    }
+
+    if next.api_limits != current.api_limits {
+        live.limits.set(next.api_limits);
+    }
+    if next.cors_origins != current.cors_origins {
+        live.cors_origins.set(next.cors_origins.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn setup(start: &BTreeMap<String, String>) -> (ConfigFile, ReloadableSettings, LiveSettings) {
+        let file = ConfigFile::new(PathBuf::from(".env"), BTreeSet::new());
+        let current = ReloadableSettings::from_vars(&file.lookup(start)).unwrap();
+        let live = LiveSettings {
+            environment: Environment::Development,
+            log_level: None,
+            features: FeatureFlags::from_overrides(&current.features).unwrap(),
+            limits: SharedLimits::new(current.api_limits),
+            cors_origins: AllowedOrigins::new(current.cors_origins.clone()),
+        };
+        (file, current, live)
+    }
+
+    #[test]
+    fn applies_reloadable_changes() {
+        let before = vars(&[("APP_FEATURES", "webhooks=off"), ("APP_PORT", "3000")]);
+        let after = vars(&[
+            ("APP_FEATURES", "verb_editing=off"),
+            ("VERBS_TIMEOUT_SECS", "2"),
+            ("APP_PORT", "4000"),
+        ]);
+        let (file, current, live) = setup(&before);
+
+        let next = reload(&file, &before, &after, &current, &live).expect("applied");
+
+        assert!(live.features.is_enabled(Feature::Webhooks));
+        assert!(!live.features.is_enabled(Feature::VerbEditing));
+        assert_eq!(live.limits.get().verbs.timeout, Duration::from_secs(2));
+        assert_eq!(next.api_limits, live.limits.get());
+    }
+
+    #[test]
+    fn invalid_file_changes_nothing() {
+        let before = vars(&[]);
+        let after = vars(&[("APP_FEATURES", "teleport=on"), ("VERBS_TIMEOUT_SECS", "2")]);
+        let (file, current, live) = setup(&before);
+
+        assert!(reload(&file, &before, &after, &current, &live).is_none());
+        assert_eq!(live.limits.get(), current.api_limits);
+    }
+
+    #[test]
+    fn restart_only_changes_are_ignored() {
+        let before = vars(&[("APP_PORT", "3000")]);
+        let after = vars(&[("APP_PORT", "4000")]);
+        let (file, current, live) = setup(&before);
+
+        assert!(reload(&file, &before, &after, &current, &live).is_none());
+    }
+}
@@ -1,35 +1,50 @@
-use std::sync::Arc;
+use std::net::SocketAddr;
 
 use anyhow::Context;
 use tracing::{info, instrument, warn};
 
 use crate::{
-    api::{self, AppState},
-    application::VerbFacade,
-    config::Config,
+    api,
+    bootstrap::bootstrap,
+    config::{AllowedOrigins, Config, ReloadableSettings, SharedLimits, telemetry::LogLevelHandle},
     error::AppResult,
-    infra::db::DatabaseBuilder,
+    infra::db::Migrator,
+    reload::{self, LiveSettings},
 };
 
 /// Start the HTTP server
-#[instrument(skip(cfg), fields(environment = %cfg.environment))]
-pub async fn start_server(cfg: &Config) -> AppResult<()> {
-    // Step 1: Build database
-    info!("Building database...");
-    let db = DatabaseBuilder::new()
-        .in_memory() // Change to .sqlite("app.db") for persistence
-        .build()
-        .await
-        .context("Failed to build database")?;
+#[instrument(skip(cfg, log_level), fields(environment = %cfg.environment))]
+pub async fn start_server(cfg: &Config, log_level: LogLevelHandle) -> AppResult<()> {
+    // Step 0: Bring the schema up to date (or refuse to start)
+    check_migrations(cfg).await?;
 
-    // Step 2: Create application facade
-    info!("Creating application facade...");
-    let facade = VerbFacade::new(Arc::new(db));
+    // Step 1: Wire dependencies into the application state
+    let state = bootstrap(cfg, Some(log_level)).await?;
+    if cfg.admin_token.is_none() {
+        info!("ADMIN_TOKEN not set; admin API disabled");
+    }
 
-    // Step 3: Create application state
-    let state = AppState::new(facade);
+    // Step 1b: Apply config file changes while running
+    let limits = SharedLimits::new(cfg.api_limits);
+    let cors_origins = AllowedOrigins::new(cfg.cors_origins.clone());
+    match &cfg.config_file {
+        Some(file) => {
+            let live = LiveSettings {
+                environment: cfg.environment,
+                log_level: state.log_level.clone(),
+                features: state.feature_flags.clone(),
+                limits: limits.clone(),
+                cors_origins: cors_origins.clone(),
+            };
+            let current = ReloadableSettings::from_config(cfg);
+            if let Err(e) = reload::watch(file.clone(), current, live) {
+                warn!(error = %e, "Failed to watch config file; hot reload disabled");
+            }
+        }
+        None => info!("No config file; hot reload disabled"),
+    }
 
-    // Step 4: Bind listener
+    // Step 2: Bind listener
     let addr = cfg.bind_addr().context("Failed to resolve bind address")?;
     tracing::Span::current().record("addr", tracing::field::display(&addr));
 
@@ -39,15 +54,69 @@ pub async fn start_server(cfg: &Config) -> AppResult<()> {
 
     info!("Listening and ready to accept connections");
 
-    // Step 5: Build router with state
-    let app = api::app(state);
+    // Step 3: Build the router
+    let app = api::app(
+        state,
+        &limits,
+        &cors_origins,
+        &cfg.http_log,
+        cfg.admin_token.as_deref(),
+    );
+
+    // Step 4: Start server with graceful shutdown; peer addresses feed the
+    // audit log
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await
+    .context("Axum server exited unexpectedly")?;
+
+    Ok(())
+}
+
+/// Apply or verify migrations before serving.
+///
+/// - No `DATABASE_URL`: nothing to do
+/// - Auto-migrate on (default outside production): apply pending migrations
+/// - Otherwise pending migrations are fatal in production and a warning
+///   elsewhere
+async fn check_migrations(cfg: &Config) -> AppResult<()> {
+    let Some(url) = &cfg.database_url else {
+        return Ok(());
+    };
 
-    // Step 6: Start server with graceful shutdown
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+    let migrator = Migrator::connect(url)
         .await
-        .context("Axum server exited unexpectedly")?;
+        .context("Failed to connect to database for migrations")?;
+
+    if cfg.auto_migrate {
+        let report = migrator.run(false).await.context("Migration failed")?;
+        info!(applied = report.applied.len(), "Migrations up to date");
+        return Ok(());
+    }
+
+    let pending = migrator
+        .pending()
+        .await
+        .context("Failed to check migrations")?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    if cfg.environment.is_production() {
+        anyhow::bail!(
+            "{} pending migration(s) (first: {}); run `verb_beta migrate` before starting",
+            pending.len(),
+            pending[0]
+        );
+    }
 
+    warn!(
+        pending = pending.len(),
+        "Pending migrations; run `verb_beta migrate`"
+    );
     Ok(())
 }
 